@@ -0,0 +1,819 @@
+//! A Teeworlds client connection, from the handshake to the snapshot stream.
+//!
+//! `Client` is an `Application` of the event loop. It connects to servers
+//! using the 0.6 or 0.7 protocol, sends the client info, negotiates the map
+//! and enters the game. Afterwards, it decodes the received snapshots and
+//! passes them on to a `Handler` along with the game messages.
+
+extern crate arrayvec;
+extern crate common;
+extern crate event_loop;
+extern crate gamenet_common;
+extern crate gamenet_teeworlds_0_6 as gamenet6;
+extern crate gamenet_teeworlds_0_7 as gamenet7;
+#[macro_use]
+extern crate log;
+extern crate packer;
+extern crate snapshot;
+extern crate warn;
+
+mod msg;
+
+use common::digest::Sha256;
+use common::num::Cast;
+use common::pretty;
+use event_loop::collections::PeerMap;
+use event_loop::Addr;
+use event_loop::Application;
+use event_loop::Chunk;
+use event_loop::ConnlessChunk;
+use event_loop::Loop;
+use event_loop::PeerId;
+use event_loop::Timeout;
+use gamenet_common::map_download;
+use msg::Msg;
+use msg::Request;
+use msg::SnapMsg;
+use snapshot::Snap;
+
+pub use event_loop::ProtocolVersion;
+pub use msg::objects;
+pub use msg::send_game;
+pub use msg::Game;
+pub use msg::SnapObj;
+
+/// The client info sent to servers.
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub name: String,
+    pub clan: String,
+    pub password: String,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            name: "nameless tee".to_owned(),
+            clan: String::new(),
+            password: String::new(),
+        }
+    }
+}
+
+/// The map announced by a server.
+#[derive(Clone, Copy, Debug)]
+pub struct MapInfo<'a> {
+    pub name: &'a [u8],
+    pub crc: i32,
+    pub size: u32,
+    /// Only sent by 0.7 servers.
+    pub sha256: Option<Sha256>,
+}
+
+/// Progress of the handshake with a server.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum State {
+    /// Waiting for the connection to be established.
+    Connecting,
+    /// Waiting for the map.
+    MapChange,
+    /// Downloading the map.
+    MapData,
+    /// Waiting for the server to acknowledge that the map is loaded.
+    ConReady,
+    /// Waiting for the server to acknowledge the client info.
+    ReadyToEnter,
+    /// In the game, receiving snapshots.
+    Online,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Event<'a> {
+    /// The connection is established, the handshake starts.
+    Connected,
+    /// The server changed the map, the snapshots start anew.
+    MapChange(MapInfo<'a>),
+    /// A map download requested through `Handler::need_map` finished, the
+    /// data has been checked against the announced checksums.
+    Map(MapInfo<'a>, &'a [u8]),
+    /// The handshake is complete.
+    EnterGame,
+    /// A snapshot along with its tick, see `objects` for decoding it.
+    Snap(i32, &'a Snap),
+    Game(Game<'a>),
+    Disconnect {
+        remote: bool,
+        reason: &'a [u8],
+    },
+}
+
+pub trait Handler<L: Loop> {
+    fn on_event(&mut self, loop_: &mut L, pid: PeerId, event: Event);
+    /// Returns whether the map should be downloaded, `false` by default.
+    ///
+    /// The downloaded map is passed on as `Event::Map`.
+    fn need_map(&mut self, pid: PeerId, map: &MapInfo) -> bool {
+        let _ = (pid, map);
+        false
+    }
+    fn needs_tick(&mut self) -> Timeout {
+        Timeout::inactive()
+    }
+    fn on_tick(&mut self, loop_: &mut L) {
+        let _ = loop_;
+    }
+}
+
+struct Download {
+    name: Vec<u8>,
+    receiver: map_download::Receiver,
+}
+
+struct Peer {
+    version: ProtocolVersion,
+    state: State,
+    download: Option<Download>,
+    snaps: snapshot::Manager,
+    sent_input: bool,
+}
+
+/// Drives the handshake with any number of servers, see the crate
+/// documentation.
+pub struct Client<H> {
+    config: Config,
+    peers: PeerMap<Peer>,
+    handler: H,
+}
+
+impl<H> Client<H> {
+    pub fn new(config: Config, handler: H) -> Client<H> {
+        Client {
+            config,
+            peers: PeerMap::new(),
+            handler,
+        }
+    }
+    pub fn connect<L: Loop>(
+        &mut self,
+        loop_: &mut L,
+        addr: Addr,
+        version: ProtocolVersion,
+    ) -> PeerId {
+        let pid = loop_.connect_with_version(addr, version);
+        self.peers.insert(
+            pid,
+            Peer {
+                version,
+                state: State::Connecting,
+                download: None,
+                snaps: snapshot::Manager::new(),
+                sent_input: false,
+            },
+        );
+        pid
+    }
+    pub fn version(&self, pid: PeerId) -> ProtocolVersion {
+        self.peers[pid].version
+    }
+    pub fn state(&self, pid: PeerId) -> State {
+        self.peers[pid].state
+    }
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+    pub fn handler_mut(&mut self) -> &mut H {
+        &mut self.handler
+    }
+}
+
+impl<L: Loop, H: Handler<L>> Application<L> for Client<H> {
+    fn needs_tick(&mut self) -> Timeout {
+        self.handler.needs_tick()
+    }
+    fn on_tick(&mut self, loop_: &mut L) {
+        self.handler.on_tick(loop_);
+    }
+    fn on_packet(&mut self, loop_: &mut L, chunk: Chunk) {
+        if self.peers.get(chunk.pid).is_some() {
+            self.on_msg(loop_, chunk.pid, chunk.data);
+            loop_.flush(chunk.pid);
+        }
+    }
+    fn on_connless_packet(&mut self, _: &mut L, chunk: ConnlessChunk) {
+        warn!(
+            "connless packet {} {:?}",
+            chunk.addr,
+            pretty::Bytes::new(chunk.data)
+        );
+    }
+    fn on_connect(&mut self, _: &mut L, _: PeerId) {
+        unreachable!();
+    }
+    fn on_ready(&mut self, loop_: &mut L, pid: PeerId) {
+        let peer = &mut self.peers[pid];
+        peer.state = State::MapChange;
+        let password = self.config.password.as_bytes();
+        Request::Info { password }.send(loop_, pid, peer.version);
+        loop_.flush(pid);
+        self.handler.on_event(loop_, pid, Event::Connected);
+    }
+    fn on_disconnect(&mut self, loop_: &mut L, pid: PeerId, remote: bool, reason: &[u8]) {
+        if self.peers.get(pid).is_none() {
+            return;
+        }
+        self.peers.remove(pid);
+        self.handler
+            .on_event(loop_, pid, Event::Disconnect { remote, reason });
+    }
+}
+
+impl<H> Client<H> {
+    fn on_msg<L: Loop>(&mut self, loop_: &mut L, pid: PeerId, data: &[u8])
+    where
+        H: Handler<L>,
+    {
+        let peer = &mut self.peers[pid];
+        let version = peer.version;
+        let msg = match msg::decode(version, data) {
+            Some(m) => m,
+            None => return,
+        };
+        match msg {
+            Msg::Invalid(reason) => {
+                error!("{}", reason);
+                loop_.disconnect(pid, reason.as_bytes());
+            }
+            Msg::MapChange(map, chunks_per_request) => {
+                info!("map change: {}", pretty::AlmostString::new(map.name));
+                peer.snaps.reset();
+                peer.sent_input = false;
+                self.handler.on_event(loop_, pid, Event::MapChange(map));
+                if self.handler.need_map(pid, &map) {
+                    let mut receiver = map_download::Receiver::new(
+                        map.crc,
+                        map.size,
+                        map.sha256,
+                        chunks_per_request,
+                    );
+                    let chunk = receiver.next_request().unwrap().assert_i32();
+                    peer.download = Some(Download {
+                        name: map.name.to_owned(),
+                        receiver,
+                    });
+                    peer.state = State::MapData;
+                    Request::MapData { chunk }.send(loop_, pid, version);
+                } else {
+                    peer.download = None;
+                    peer.state = State::ConReady;
+                    Request::Ready.send(loop_, pid, version);
+                }
+            }
+            Msg::MapData {
+                crc,
+                chunk,
+                last,
+                data,
+            } => {
+                let done = {
+                    let download = match peer.download {
+                        Some(ref mut d) if peer.state == State::MapData => d,
+                        _ => {
+                            warn!("unsolicited map data");
+                            return;
+                        }
+                    };
+                    if crc.map(|c| c != download.receiver.crc()).unwrap_or(false) {
+                        warn!("unsolicited map data crc={:?}", crc);
+                        return;
+                    }
+                    let index = chunk.map(|c| c.try_u32().unwrap_or(u32::max_value()));
+                    match download.receiver.receive(index, data) {
+                        Ok(()) => {}
+                        Err(map_download::Error::UnexpectedChunk(c)) => {
+                            warn!("unsolicited map data chunk={}", c);
+                            return;
+                        }
+                        Err(err) => {
+                            error!("{}", err);
+                            loop_.disconnect(pid, b"map download failed");
+                            return;
+                        }
+                    }
+                    let done = last || download.receiver.is_complete();
+                    if !done {
+                        if let Some(chunk) = download.receiver.next_request() {
+                            let chunk = chunk.assert_i32();
+                            Request::MapData { chunk }.send(loop_, pid, version);
+                        }
+                    }
+                    done
+                };
+                if done {
+                    let Download { name, receiver } = peer.download.take().unwrap();
+                    let info = MapInfo {
+                        name: &name,
+                        crc: receiver.crc(),
+                        size: receiver.size(),
+                        sha256: receiver.sha256(),
+                    };
+                    let data = match receiver.finish() {
+                        Ok(d) => d,
+                        Err(err) => {
+                            error!("{}", err);
+                            loop_.disconnect(pid, b"map download failed");
+                            return;
+                        }
+                    };
+                    info!("download finished");
+                    peer.state = State::ConReady;
+                    Request::Ready.send(loop_, pid, version);
+                    self.handler.on_event(loop_, pid, Event::Map(info, &data));
+                }
+            }
+            Msg::ConReady => {
+                if peer.state != State::ConReady {
+                    warn!("con ready from state {:?}", peer.state);
+                    return;
+                }
+                peer.state = State::ReadyToEnter;
+                let name = self.config.name.as_bytes();
+                let clan = self.config.clan.as_bytes();
+                Request::StartInfo { name, clan }.send(loop_, pid, version);
+            }
+            Msg::ReadyToEnter => {
+                if peer.state != State::ReadyToEnter {
+                    warn!("ready to enter from state {:?}", peer.state);
+                    return;
+                }
+                peer.state = State::Online;
+                Request::EnterGame.send(loop_, pid, version);
+                self.handler.on_event(loop_, pid, Event::EnterGame);
+            }
+            Msg::Snap(snap) => self.on_snap(loop_, pid, snap),
+            Msg::Game(game) => self.handler.on_event(loop_, pid, Event::Game(game)),
+            Msg::Other => {}
+        }
+    }
+    fn on_snap<L: Loop>(&mut self, loop_: &mut L, pid: PeerId, snap: SnapMsg)
+    where
+        H: Handler<L>,
+    {
+        let peer = &mut self.peers[pid];
+        let tick = snap.tick();
+        match snap.apply(peer.version, &mut peer.snaps) {
+            Ok(Some(snap)) => self.handler.on_event(loop_, pid, Event::Snap(tick, snap)),
+            Ok(None) => return,
+            Err(err) => {
+                warn!("snapshot error {:?}", err);
+                return;
+            }
+        }
+        // The handler might have disconnected.
+        let peer = match self.peers.get_mut(pid) {
+            Some(p) => p,
+            None => return,
+        };
+        if !peer.sent_input {
+            // DDNet needs the INPUT message as the first chunk of the
+            // packet.
+            loop_.force_flush(pid);
+            peer.sent_input = true;
+        }
+        let ack_tick = peer.snaps.ack_tick().map(i32::from).unwrap_or(-1);
+        Request::Input { ack_tick }.send(loop_, pid, peer.version);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Client;
+    use super::Config;
+    use super::Event;
+    use super::Handler;
+    use super::MapInfo;
+    use super::ProtocolVersion;
+    use super::State;
+    use common::digest;
+    use event_loop::Addr;
+    use event_loop::Application;
+    use event_loop::Chunk;
+    use event_loop::Loop;
+    use event_loop::PeerId;
+    use event_loop::Timestamp;
+    use gamenet6;
+    use gamenet7;
+    use packer::with_packer;
+    use packer::Unpacker;
+    use snapshot::snap::Builder;
+    use snapshot::Delta;
+    use snapshot::Snap;
+    use std::time::Duration;
+    use warn::Panic;
+
+    const MAP: &[u8] = b"not really a map";
+    const SNAP_TICK: i32 = 50;
+
+    #[derive(Default)]
+    struct FakeLoop {
+        sent: Vec<(bool, Vec<u8>)>,
+        disconnected: Vec<Vec<u8>>,
+    }
+
+    impl Loop for FakeLoop {
+        fn accept_connections_on_port(_: u16) -> FakeLoop {
+            unimplemented!();
+        }
+        fn client() -> FakeLoop {
+            FakeLoop::default()
+        }
+        fn run<A: Application<FakeLoop>>(self, _: A) {
+            unimplemented!();
+        }
+        fn time(&mut self) -> Timestamp {
+            Timestamp::from_secs_since_epoch(0)
+        }
+        fn connect(&mut self, addr: Addr) -> PeerId {
+            self.connect_with_version(addr, ProtocolVersion::V6)
+        }
+        fn connect_with_version(&mut self, _: Addr, _: ProtocolVersion) -> PeerId {
+            PeerId(0)
+        }
+        fn disconnect(&mut self, _: PeerId, reason: &[u8]) {
+            self.disconnected.push(reason.to_owned());
+        }
+        fn send_connless(&mut self, _: Addr, _: &[u8]) {
+            unimplemented!();
+        }
+        fn send(&mut self, chunk: Chunk) {
+            self.sent.push((chunk.vital, chunk.data.to_owned()));
+        }
+        fn force_flush(&mut self, _: PeerId) {}
+        fn flush(&mut self, _: PeerId) {}
+        fn ignore(&mut self, _: PeerId) {
+            unimplemented!();
+        }
+        fn accept(&mut self, _: PeerId) {
+            unimplemented!();
+        }
+        fn reject(&mut self, _: PeerId, _: &[u8]) {
+            unimplemented!();
+        }
+        fn shutdown(&mut self, _: &[u8], _: Duration) {
+            unimplemented!();
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Recorded {
+        Connected,
+        MapChange(Vec<u8>),
+        Map(Vec<u8>),
+        EnterGame,
+        Snap(i32, Option<Vec<i32>>),
+        Game,
+        Disconnect(Vec<u8>),
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<Recorded>,
+    }
+
+    impl Handler<FakeLoop> for Recorder {
+        fn on_event(&mut self, _: &mut FakeLoop, _: PeerId, event: Event) {
+            self.events.push(match event {
+                Event::Connected => Recorded::Connected,
+                Event::MapChange(map) => Recorded::MapChange(map.name.to_owned()),
+                Event::Map(_, data) => Recorded::Map(data.to_owned()),
+                Event::EnterGame => Recorded::EnterGame,
+                Event::Snap(tick, snap) => {
+                    Recorded::Snap(tick, snap.item(snap_type(), 0).map(|d| d.to_owned()))
+                }
+                Event::Game(_) => Recorded::Game,
+                Event::Disconnect { reason, .. } => Recorded::Disconnect(reason.to_owned()),
+            });
+        }
+        fn need_map(&mut self, _: PeerId, _: &MapInfo) -> bool {
+            true
+        }
+    }
+
+    /// The client messages relevant to the handshake.
+    #[derive(Debug, PartialEq)]
+    enum Sent {
+        Info,
+        RequestMapData,
+        Ready,
+        StartInfo,
+        EnterGame,
+        Input { ack_tick: i32, vital: bool },
+    }
+
+    fn decode_sent(version: ProtocolVersion, vital: bool, data: &[u8]) -> Sent {
+        let p = &mut Unpacker::new(data);
+        match version {
+            ProtocolVersion::V6 => {
+                use gamenet6::msg::Game as G;
+                use gamenet6::msg::System as S;
+                use gamenet6::msg::SystemOrGame::*;
+                match gamenet6::msg::decode(&mut Panic, p).unwrap() {
+                    System(S::Info(_)) => Sent::Info,
+                    System(S::RequestMapData(_)) => Sent::RequestMapData,
+                    System(S::Ready(_)) => Sent::Ready,
+                    Game(G::ClStartInfo(_)) => Sent::StartInfo,
+                    System(S::EnterGame(_)) => Sent::EnterGame,
+                    System(S::Input(i)) => Sent::Input {
+                        ack_tick: i.ack_snapshot,
+                        vital,
+                    },
+                    msg => panic!("unexpected message {:?}", msg),
+                }
+            }
+            ProtocolVersion::V7 => {
+                use gamenet7::msg::Game as G;
+                use gamenet7::msg::System as S;
+                use gamenet7::msg::SystemOrGame::*;
+                match gamenet7::msg::decode(&mut Panic, p).unwrap() {
+                    System(S::Info(_)) => Sent::Info,
+                    System(S::RequestMapData(_)) => Sent::RequestMapData,
+                    System(S::Ready(_)) => Sent::Ready,
+                    Game(G::ClStartInfo(_)) => Sent::StartInfo,
+                    System(S::EnterGame(_)) => Sent::EnterGame,
+                    System(S::Input(i)) => Sent::Input {
+                        ack_tick: i.ack_snapshot,
+                        vital,
+                    },
+                    msg => panic!("unexpected message {:?}", msg),
+                }
+            }
+        }
+    }
+
+    fn take_sent(loop_: &mut FakeLoop, version: ProtocolVersion) -> Vec<Sent> {
+        loop_
+            .sent
+            .drain(..)
+            .map(|(vital, data)| decode_sent(version, vital, &data))
+            .collect()
+    }
+
+    /// The server messages relevant to the handshake.
+    enum Msg<'a> {
+        MapChange {
+            crc: i32,
+        },
+        /// The CRC is only repeated by 0.6 servers.
+        MapData {
+            crc: i32,
+        },
+        ConReady,
+        ReadyToEnter,
+        SnapSingle(&'a [u8], i32),
+    }
+
+    fn snap_type() -> u16 {
+        gamenet6::snap_obj::PLAYER_INPUT
+    }
+
+    /// Returns a snapshot with a single item along with the delta creating
+    /// it from the empty snapshot.
+    fn snap(version: ProtocolVersion) -> (Snap, Vec<u8>) {
+        let obj_size = match version {
+            ProtocolVersion::V6 => gamenet6::snap_obj::obj_size,
+            ProtocolVersion::V7 => gamenet7::snap_obj::obj_size,
+        };
+        let size = obj_size(snap_type()).unwrap() as i32;
+        let mut builder = Builder::new();
+        builder
+            .add_item(snap_type(), 0, &(1..size + 1).collect::<Vec<_>>())
+            .unwrap();
+        let snap = builder.finish();
+        let mut delta = Delta::new();
+        delta.create(&Snap::empty(), &snap);
+        let mut buf = Vec::with_capacity(1024);
+        let data = with_packer(&mut buf, |p| delta.write(obj_size, p))
+            .unwrap()
+            .to_vec();
+        (snap, data)
+    }
+
+    fn encode(version: ProtocolVersion, msg: Msg) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(1024);
+        let size = MAP.len() as i32;
+        // Snapshots from the empty one, which has tick -1.
+        let delta_tick = SNAP_TICK + 1;
+        match version {
+            ProtocolVersion::V6 => {
+                use gamenet6::msg::game;
+                use gamenet6::msg::system;
+                use gamenet6::msg::Game;
+                use gamenet6::msg::System;
+                match msg {
+                    Msg::MapChange { crc } => with_packer(&mut buf, |p| {
+                        System::from(system::MapChange {
+                            name: b"dm1",
+                            crc,
+                            size,
+                        })
+                        .encode(p)
+                    }),
+                    Msg::MapData { crc } => with_packer(&mut buf, |p| {
+                        System::from(system::MapData {
+                            last: 1,
+                            crc,
+                            chunk: 0,
+                            data: MAP,
+                        })
+                        .encode(p)
+                    }),
+                    Msg::ConReady => {
+                        with_packer(&mut buf, |p| System::from(system::ConReady).encode(p))
+                    }
+                    Msg::ReadyToEnter => {
+                        with_packer(&mut buf, |p| Game::from(game::SvReadyToEnter).encode(p))
+                    }
+                    Msg::SnapSingle(data, crc) => with_packer(&mut buf, |p| {
+                        System::from(system::SnapSingle {
+                            tick: SNAP_TICK,
+                            delta_tick,
+                            crc,
+                            data,
+                        })
+                        .encode(p)
+                    }),
+                }
+            }
+            ProtocolVersion::V7 => {
+                use gamenet7::msg::game;
+                use gamenet7::msg::system;
+                use gamenet7::msg::Game;
+                use gamenet7::msg::System;
+                match msg {
+                    Msg::MapChange { crc } => with_packer(&mut buf, |p| {
+                        System::from(system::MapChange {
+                            name: b"dm1",
+                            crc,
+                            size,
+                            num_response_chunks_per_request: 1,
+                            chunk_size: 1024,
+                            sha256: digest::sha256(MAP),
+                        })
+                        .encode(p)
+                    }),
+                    Msg::MapData { .. } => with_packer(&mut buf, |p| {
+                        System::from(system::MapData { data: MAP }).encode(p)
+                    }),
+                    Msg::ConReady => {
+                        with_packer(&mut buf, |p| System::from(system::ConReady).encode(p))
+                    }
+                    Msg::ReadyToEnter => {
+                        with_packer(&mut buf, |p| Game::from(game::SvReadyToEnter).encode(p))
+                    }
+                    Msg::SnapSingle(data, crc) => with_packer(&mut buf, |p| {
+                        System::from(system::SnapSingle {
+                            tick: SNAP_TICK,
+                            delta_tick,
+                            crc,
+                            data,
+                        })
+                        .encode(p)
+                    }),
+                }
+            }
+        }
+        .unwrap()
+        .to_vec()
+    }
+
+    fn receive(
+        client: &mut Client<Recorder>,
+        loop_: &mut FakeLoop,
+        pid: PeerId,
+        version: ProtocolVersion,
+        msg: Msg,
+    ) {
+        let data = encode(version, msg);
+        client.on_packet(
+            loop_,
+            Chunk {
+                pid,
+                vital: true,
+                data: &data,
+            },
+        );
+    }
+
+    fn connect(version: ProtocolVersion) -> (Client<Recorder>, FakeLoop, PeerId) {
+        let mut loop_ = FakeLoop::client();
+        let mut client = Client::new(Config::default(), Recorder::default());
+        let addr = Addr::from("127.0.0.1:8303".parse::<::std::net::SocketAddr>().unwrap());
+        let pid = client.connect(&mut loop_, addr, version);
+        assert_eq!(client.state(pid), State::Connecting);
+        client.on_ready(&mut loop_, pid);
+        assert_eq!(client.state(pid), State::MapChange);
+        assert_eq!(take_sent(&mut loop_, version), [Sent::Info]);
+        assert_eq!(client.handler().events, [Recorded::Connected]);
+        (client, loop_, pid)
+    }
+
+    fn handshake(version: ProtocolVersion) {
+        let (mut client, mut loop_, pid) = connect(version);
+        let crc = digest::crc32(MAP) as i32;
+
+        // Messages from later states are ignored.
+        receive(&mut client, &mut loop_, pid, version, Msg::ConReady);
+        receive(&mut client, &mut loop_, pid, version, Msg::ReadyToEnter);
+        assert_eq!(client.state(pid), State::MapChange);
+        assert!(loop_.sent.is_empty());
+
+        receive(
+            &mut client,
+            &mut loop_,
+            pid,
+            version,
+            Msg::MapChange { crc },
+        );
+        assert_eq!(client.state(pid), State::MapData);
+        assert_eq!(take_sent(&mut loop_, version), [Sent::RequestMapData]);
+
+        receive(&mut client, &mut loop_, pid, version, Msg::MapData { crc });
+        assert_eq!(client.state(pid), State::ConReady);
+        assert_eq!(take_sent(&mut loop_, version), [Sent::Ready]);
+
+        receive(&mut client, &mut loop_, pid, version, Msg::ReadyToEnter);
+        assert_eq!(client.state(pid), State::ConReady);
+        assert!(loop_.sent.is_empty());
+
+        receive(&mut client, &mut loop_, pid, version, Msg::ConReady);
+        assert_eq!(client.state(pid), State::ReadyToEnter);
+        assert_eq!(take_sent(&mut loop_, version), [Sent::StartInfo]);
+
+        receive(&mut client, &mut loop_, pid, version, Msg::ReadyToEnter);
+        assert_eq!(client.state(pid), State::Online);
+        assert_eq!(take_sent(&mut loop_, version), [Sent::EnterGame]);
+
+        let (snap, delta) = snap(version);
+        let msg = Msg::SnapSingle(&delta, snap.crc());
+        receive(&mut client, &mut loop_, pid, version, msg);
+        assert_eq!(
+            take_sent(&mut loop_, version),
+            [Sent::Input {
+                ack_tick: SNAP_TICK,
+                vital: false,
+            }]
+        );
+
+        assert!(loop_.disconnected.is_empty());
+        assert_eq!(
+            client.handler().events,
+            [
+                Recorded::Connected,
+                Recorded::MapChange(b"dm1".to_vec()),
+                Recorded::Map(MAP.to_vec()),
+                Recorded::EnterGame,
+                Recorded::Snap(SNAP_TICK, snap.item(snap_type(), 0).map(|d| d.to_owned())),
+            ]
+        );
+    }
+
+    fn bad_map_crc(version: ProtocolVersion) {
+        let (mut client, mut loop_, pid) = connect(version);
+        let crc = digest::crc32(MAP) as i32 ^ 1;
+        receive(
+            &mut client,
+            &mut loop_,
+            pid,
+            version,
+            Msg::MapChange { crc },
+        );
+        assert_eq!(take_sent(&mut loop_, version), [Sent::RequestMapData]);
+        receive(&mut client, &mut loop_, pid, version, Msg::MapData { crc });
+        assert_eq!(loop_.disconnected, [b"map download failed".to_vec()]);
+        assert!(loop_.sent.is_empty());
+        assert!(!client
+            .handler()
+            .events
+            .iter()
+            .any(|e| matches!(*e, Recorded::Map(_))));
+    }
+
+    #[test]
+    fn handshake6() {
+        handshake(ProtocolVersion::V6);
+    }
+
+    #[test]
+    fn handshake7() {
+        handshake(ProtocolVersion::V7);
+    }
+
+    #[test]
+    fn bad_map_crc6() {
+        bad_map_crc(ProtocolVersion::V6);
+    }
+
+    #[test]
+    fn bad_map_crc7() {
+        bad_map_crc(ProtocolVersion::V7);
+    }
+}
@@ -0,0 +1,358 @@
+use arrayvec::ArrayVec;
+use common::num::Cast;
+use event_loop::Chunk;
+use event_loop::Loop;
+use event_loop::PeerId;
+use event_loop::ProtocolVersion;
+use gamenet6;
+use gamenet6::msg::system as system6;
+use gamenet7;
+use packer::with_packer;
+use packer::IntUnpacker;
+use packer::Unpacker;
+use snapshot;
+use snapshot::Snap;
+use std::mem;
+use warn::Log;
+use MapInfo;
+
+/// A game message of either protocol version.
+#[derive(Clone, Copy, Debug)]
+pub enum Game<'a> {
+    V6(gamenet6::msg::Game<'a>),
+    V7(gamenet7::msg::Game<'a>),
+}
+
+/// A snapshot object of either protocol version.
+#[derive(Clone, Copy, Debug)]
+pub enum SnapObj {
+    V6(gamenet6::SnapObj),
+    V7(gamenet7::SnapObj),
+}
+
+/// Decodes the objects of a snapshot along with their IDs.
+///
+/// Objects that fail to decode are skipped with a warning.
+pub fn objects(version: ProtocolVersion, snap: &Snap) -> impl Iterator<Item = (u16, SnapObj)> + '_ {
+    snap.items().filter_map(move |item| {
+        let p = &mut IntUnpacker::new(item.data);
+        let res = match version {
+            ProtocolVersion::V6 => {
+                gamenet6::SnapObj::decode_obj(&mut Log, item.type_id.into(), p).map(SnapObj::V6)
+            }
+            ProtocolVersion::V7 => {
+                gamenet7::SnapObj::decode_obj(&mut Log, item.type_id.into(), p).map(SnapObj::V7)
+            }
+        };
+        match res {
+            Ok(obj) => Some((item.id, obj)),
+            Err(e) => {
+                warn!("item decode error {:?}: {:?}", e, item);
+                None
+            }
+        }
+    })
+}
+
+/// Sends a vital game message, the version must match the peer's.
+pub fn send_game<L: Loop>(loop_: &mut L, pid: PeerId, msg: Game) {
+    let mut buf: ArrayVec<[u8; 2048]> = ArrayVec::new();
+    match msg {
+        Game::V6(msg) => with_packer(&mut buf, |p| msg.encode(p).map(|_| ())).unwrap(),
+        Game::V7(msg) => with_packer(&mut buf, |p| msg.encode(p).map(|_| ())).unwrap(),
+    }
+    loop_.send(Chunk {
+        pid,
+        vital: true,
+        data: &buf,
+    });
+}
+
+/// Snapshot messages, converted to the 0.6 ones understood by
+/// `snapshot::Manager`. They're the same for 0.7.
+pub enum SnapMsg<'a> {
+    Snap(system6::Snap<'a>),
+    Empty(system6::SnapEmpty),
+    Single(system6::SnapSingle<'a>),
+}
+
+impl<'a> SnapMsg<'a> {
+    pub fn tick(&self) -> i32 {
+        match *self {
+            SnapMsg::Snap(s) => s.tick,
+            SnapMsg::Empty(s) => s.tick,
+            SnapMsg::Single(s) => s.tick,
+        }
+    }
+    pub fn apply(
+        self,
+        version: ProtocolVersion,
+        snaps: &mut snapshot::Manager,
+    ) -> Result<Option<&Snap>, snapshot::manager::Error> {
+        let obj_size = match version {
+            ProtocolVersion::V6 => gamenet6::snap_obj::obj_size,
+            ProtocolVersion::V7 => gamenet7::snap_obj::obj_size,
+        };
+        match self {
+            SnapMsg::Snap(s) => snaps.snap(&mut Log, obj_size, s),
+            SnapMsg::Empty(s) => snaps.snap_empty(&mut Log, obj_size, s),
+            SnapMsg::Single(s) => snaps.snap_single(&mut Log, obj_size, s),
+        }
+    }
+}
+
+/// The messages relevant to the handshake, independent of the protocol
+/// version.
+pub enum Msg<'a> {
+    MapChange(MapInfo<'a>, u32),
+    MapData {
+        crc: Option<i32>,
+        chunk: Option<i32>,
+        last: bool,
+        data: &'a [u8],
+    },
+    ConReady,
+    ReadyToEnter,
+    Snap(SnapMsg<'a>),
+    Game(Game<'a>),
+    Invalid(&'static str),
+    Other,
+}
+
+fn map_change<'a>(
+    name: &'a [u8],
+    crc: i32,
+    size: i32,
+    sha256: Option<common::digest::Sha256>,
+    chunks_per_request: i32,
+) -> Msg<'a> {
+    let size = match size.try_u32() {
+        Some(s) => s,
+        None => return Msg::Invalid("invalid map size"),
+    };
+    let chunks_per_request = match chunks_per_request.try_u32() {
+        Some(c) if c != 0 => c,
+        _ => return Msg::Invalid("invalid map chunk count"),
+    };
+    if name.iter().any(|&b| b == b'/' || b == b'\\') {
+        return Msg::Invalid("invalid map name");
+    }
+    let map = MapInfo {
+        name,
+        crc,
+        size,
+        sha256,
+    };
+    Msg::MapChange(map, chunks_per_request)
+}
+
+pub fn decode(version: ProtocolVersion, data: &[u8]) -> Option<Msg<'_>> {
+    match version {
+        ProtocolVersion::V6 => decode6(data),
+        ProtocolVersion::V7 => decode7(data),
+    }
+}
+
+fn decode6(data: &[u8]) -> Option<Msg<'_>> {
+    use gamenet6::msg::Game as G;
+    use gamenet6::msg::System as S;
+    use gamenet6::msg::SystemOrGame::*;
+    let msg = match gamenet6::msg::decode(&mut Log, &mut Unpacker::new(data)) {
+        Ok(m) => m,
+        Err(e) => {
+            warn!("decode error {:?}", e);
+            return None;
+        }
+    };
+    debug!("{:?}", msg);
+    Some(match msg {
+        System(S::MapChange(m)) => map_change(m.name, m.crc, m.size, None, 1),
+        System(S::MapData(m)) => Msg::MapData {
+            crc: Some(m.crc),
+            chunk: Some(m.chunk),
+            last: m.last != 0,
+            data: m.data,
+        },
+        System(S::ConReady(_)) => Msg::ConReady,
+        System(S::Snap(s)) => Msg::Snap(SnapMsg::Snap(s)),
+        System(S::SnapEmpty(s)) => Msg::Snap(SnapMsg::Empty(s)),
+        System(S::SnapSingle(s)) => Msg::Snap(SnapMsg::Single(s)),
+        System(_) => Msg::Other,
+        Game(G::SvReadyToEnter(_)) => Msg::ReadyToEnter,
+        Game(g) => Msg::Game(self::Game::V6(g)),
+    })
+}
+
+fn decode7(data: &[u8]) -> Option<Msg<'_>> {
+    use gamenet7::msg::Game as G;
+    use gamenet7::msg::System as S;
+    use gamenet7::msg::SystemOrGame::*;
+    let msg = match gamenet7::msg::decode(&mut Log, &mut Unpacker::new(data)) {
+        Ok(m) => m,
+        Err(e) => {
+            warn!("decode error {:?}", e);
+            return None;
+        }
+    };
+    debug!("{:?}", msg);
+    Some(match msg {
+        System(S::MapChange(m)) => map_change(
+            m.name,
+            m.crc,
+            m.size,
+            Some(m.sha256),
+            m.num_response_chunks_per_request,
+        ),
+        System(S::MapData(m)) => Msg::MapData {
+            crc: None,
+            chunk: None,
+            last: false,
+            data: m.data,
+        },
+        System(S::ConReady(_)) => Msg::ConReady,
+        System(S::Snap(s)) => Msg::Snap(SnapMsg::Snap(system6::Snap {
+            tick: s.tick,
+            delta_tick: s.delta_tick,
+            num_parts: s.num_parts,
+            part: s.part,
+            crc: s.crc,
+            data: s.data,
+        })),
+        System(S::SnapEmpty(s)) => Msg::Snap(SnapMsg::Empty(system6::SnapEmpty {
+            tick: s.tick,
+            delta_tick: s.delta_tick,
+        })),
+        System(S::SnapSingle(s)) => Msg::Snap(SnapMsg::Single(system6::SnapSingle {
+            tick: s.tick,
+            delta_tick: s.delta_tick,
+            crc: s.crc,
+            data: s.data,
+        })),
+        System(_) => Msg::Other,
+        Game(G::SvReadyToEnter(_)) => Msg::ReadyToEnter,
+        Game(g) => Msg::Game(self::Game::V7(g)),
+    })
+}
+
+/// The messages sent by the client during the handshake.
+pub enum Request<'a> {
+    Info { password: &'a [u8] },
+    MapData { chunk: i32 },
+    Ready,
+    StartInfo { name: &'a [u8], clan: &'a [u8] },
+    EnterGame,
+    Input { ack_tick: i32 },
+}
+
+impl<'a> Request<'a> {
+    pub fn send<L: Loop>(self, loop_: &mut L, pid: PeerId, version: ProtocolVersion) {
+        let mut buf: ArrayVec<[u8; 2048]> = ArrayVec::new();
+        // Inputs are sent often and are outdated once lost.
+        let vital = match self {
+            Request::Input { .. } => false,
+            _ => true,
+        };
+        match version {
+            ProtocolVersion::V6 => self.encode6(&mut buf),
+            ProtocolVersion::V7 => self.encode7(&mut buf),
+        }
+        loop_.send(Chunk {
+            pid,
+            vital,
+            data: &buf,
+        });
+    }
+    fn encode6(self, buf: &mut ArrayVec<[u8; 2048]>) {
+        use gamenet6::msg::game;
+        use gamenet6::msg::Game;
+        use gamenet6::msg::System;
+        use gamenet6::msg::SystemOrGame;
+        use gamenet6::snap_obj::PlayerInput;
+        let msg: SystemOrGame<System, Game> = match self {
+            Request::Info { password } => SystemOrGame::System(
+                system6::Info {
+                    version: gamenet6::enums::VERSION.as_bytes(),
+                    password: Some(password),
+                }
+                .into(),
+            ),
+            Request::MapData { chunk } => {
+                SystemOrGame::System(system6::RequestMapData { chunk }.into())
+            }
+            Request::Ready => SystemOrGame::System(system6::Ready.into()),
+            Request::StartInfo { name, clan } => SystemOrGame::Game(
+                game::ClStartInfo {
+                    name,
+                    clan,
+                    country: -1,
+                    skin: b"default",
+                    use_custom_color: false,
+                    color_body: 0,
+                    color_feet: 0,
+                }
+                .into(),
+            ),
+            Request::EnterGame => SystemOrGame::System(system6::EnterGame.into()),
+            Request::Input { ack_tick } => SystemOrGame::System(
+                system6::Input {
+                    ack_snapshot: ack_tick,
+                    intended_tick: ack_tick,
+                    input_size: mem::size_of::<PlayerInput>().assert_i32(),
+                    input: PlayerInput::default(),
+                }
+                .into(),
+            ),
+        };
+        match msg {
+            SystemOrGame::System(msg) => with_packer(buf, |p| msg.encode(p).map(|_| ())).unwrap(),
+            SystemOrGame::Game(msg) => with_packer(buf, |p| msg.encode(p).map(|_| ())).unwrap(),
+        }
+    }
+    fn encode7(self, buf: &mut ArrayVec<[u8; 2048]>) {
+        use gamenet7::msg::game;
+        use gamenet7::msg::system;
+        use gamenet7::msg::Game;
+        use gamenet7::msg::System;
+        use gamenet7::msg::SystemOrGame;
+        use gamenet7::snap_obj::PlayerInput;
+        let msg: SystemOrGame<System, Game> = match self {
+            Request::Info { password } => SystemOrGame::System(
+                system::Info {
+                    version: gamenet7::enums::VERSION.as_bytes(),
+                    password: Some(password),
+                    client_version: Some(gamenet7::enums::CLIENT_VERSION),
+                }
+                .into(),
+            ),
+            // 0.7 servers send the following chunks without being asked
+            // for a specific one.
+            Request::MapData { .. } => SystemOrGame::System(system::RequestMapData.into()),
+            Request::Ready => SystemOrGame::System(system::Ready.into()),
+            Request::StartInfo { name, clan } => SystemOrGame::Game(
+                game::ClStartInfo {
+                    name,
+                    clan,
+                    country: -1,
+                    skin_part_names: [b"standard", b"", b"", b"standard", b"standard", b"standard"],
+                    use_custom_colors: [false; 6],
+                    skin_part_colors: [0; 6],
+                }
+                .into(),
+            ),
+            Request::EnterGame => SystemOrGame::System(system::EnterGame.into()),
+            Request::Input { ack_tick } => SystemOrGame::System(
+                system::Input {
+                    ack_snapshot: ack_tick,
+                    intended_tick: ack_tick,
+                    input_size: mem::size_of::<PlayerInput>().assert_i32(),
+                    input: PlayerInput::default(),
+                }
+                .into(),
+            ),
+        };
+        match msg {
+            SystemOrGame::System(msg) => with_packer(buf, |p| msg.encode(p).map(|_| ())).unwrap(),
+            SystemOrGame::Game(msg) => with_packer(buf, |p| msg.encode(p).map(|_| ())).unwrap(),
+        }
+    }
+}
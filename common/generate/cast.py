@@ -2,6 +2,7 @@ from .types import TYPES, formatt, printt
 
 prologue = """\
 use std::convert::TryInto;
+use std::error;
 use std::fmt;
 
 trait TypeName {
@@ -20,6 +21,41 @@ fn unwrap_overflow<T: fmt::Display, U: TypeName>(original: T, val: Option<U>) ->
         Some(v) => v,
         None => overflow::<T, U>(original),
     }
+}
+
+/// Error returned by `CheckedCast` if a value can't be represented in the
+/// target type.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CastError {
+    value: i128,
+    target: &'static str,
+}
+
+impl CastError {
+    /// The value that couldn't be converted.
+    pub fn value(&self) -> i128 {
+        self.value
+    }
+    /// Name of the type the value should have been converted to.
+    pub fn target(&self) -> &'static str {
+        self.target
+    }
+}
+
+impl error::Error for CastError {}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "overflow casting {} to `{}`", self.value, self.target)
+    }
+}
+
+#[inline]
+fn ok_or_cast_error<U: TypeName>(original: i128, val: Option<U>) -> Result<U, CastError> {
+    match val {
+        Some(v) => Ok(v),
+        None => Err(CastError { value: original, target: U::type_name() }),
+    }
 }\
 """
 
@@ -36,6 +72,12 @@ def print_cast_trait(types):
         printt("    fn assert_{type}(self) -> {type} where Self: Sized + {ntrait};", t)
     print("}")
 
+def print_checked_cast_trait(types):
+    print("pub trait CheckedCast {")
+    for t in types:
+        printt("    fn checked_{type}(self) -> Result<{type}, CastError> where Self: Sized + {ntrait};", t)
+    print("}")
+
 def print_pos_traits(types):
     for t in types:
         printt("pub trait {trait} {{ }}", t)
@@ -47,6 +89,8 @@ def print_neg_traits(types):
 def print_traits(types):
     print_cast_trait(types)
     print()
+    print_checked_cast_trait(types)
+    print()
     print_pos_traits(types)
     print()
     print_neg_traits(types)
@@ -114,6 +158,15 @@ def print_cast_impl(type, types):
             printt("    #[inline] fn assert_{type}(self) -> {type} {{ unreachable!() }}", t)
     print("}")
 
+def print_checked_cast_impl(type, types):
+    printt("impl CheckedCast for {type} {{", type)
+    for t in types:
+        if not can_always_represent(t, type):
+            printt("    #[inline] fn checked_{type}(self) -> Result<{type}, CastError> {{ ok_or_cast_error(self as i128, self.try_into().ok()) }}", t)
+        else:
+            printt("    #[inline] fn checked_{type}(self) -> Result<{type}, CastError> {{ unreachable!() }}", t)
+    print("}")
+
 def print_cast_impls(types):
     first = True
     for t in types:
@@ -122,6 +175,9 @@ def print_cast_impls(types):
         else:
             first = False
         print_cast_impl(t, types)
+    for t in types:
+        print()
+        print_checked_cast_impl(t, types)
 
 def print_type_name_impls(types):
     for t in types:
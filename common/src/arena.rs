@@ -0,0 +1,143 @@
+use std::cell::RefCell;
+use std::cmp;
+use std::slice;
+
+const MIN_CHUNK_LEN: usize = 1024;
+
+/// Growable list of fixed-capacity chunks.
+///
+/// Chunks are never reallocated while slices into them are handed out, only
+/// new chunks are added, so previously returned slices stay valid until the
+/// next `reset`.
+struct Chunks<T> {
+    chunks: RefCell<Vec<Vec<T>>>,
+}
+
+impl<T: Copy + Default> Chunks<T> {
+    fn new() -> Chunks<T> {
+        Chunks {
+            chunks: RefCell::new(Vec::new()),
+        }
+    }
+    #[allow(clippy::mut_from_ref)]
+    fn alloc(&self, len: usize) -> &mut [T] {
+        if len == 0 {
+            return &mut [];
+        }
+        let mut chunks = self.chunks.borrow_mut();
+        let fits = chunks
+            .last()
+            .map(|c| c.capacity() - c.len() >= len)
+            .unwrap_or(false);
+        if !fits {
+            let last_capacity = chunks.last().map(|c| c.capacity()).unwrap_or(0);
+            let capacity = cmp::max(cmp::max(len, last_capacity * 2), MIN_CHUNK_LEN);
+            chunks.push(Vec::with_capacity(capacity));
+        }
+        let chunk = chunks.last_mut().unwrap();
+        let start = chunk.len();
+        // Doesn't reallocate, the capacity was checked above.
+        chunk.resize(start + len, T::default());
+        unsafe {
+            // The returned slice doesn't overlap with any previously returned
+            // slice and stays valid until `reset`, which requires a mutable
+            // reference to `self`.
+            slice::from_raw_parts_mut(chunk.as_mut_ptr().add(start), len)
+        }
+    }
+    fn reset(&mut self) {
+        let chunks = self.chunks.get_mut();
+        if chunks.len() > 1 {
+            // Replace all chunks by a single one that is large enough to hold
+            // everything that was allocated since the last reset.
+            let capacity = chunks.iter().map(|c| c.capacity()).sum();
+            chunks.clear();
+            chunks.push(Vec::with_capacity(capacity));
+        }
+        if let Some(c) = chunks.last_mut() {
+            c.clear();
+        }
+    }
+    fn allocated(&self) -> usize {
+        self.chunks.borrow().iter().map(|c| c.len()).sum()
+    }
+    fn capacity(&self) -> usize {
+        self.chunks.borrow().iter().map(|c| c.capacity()).sum()
+    }
+}
+
+/// Bump allocator for scratch buffers that only live for one tick.
+///
+/// Hands out zeroed `i32` and `u8` slices that stay valid until the next call
+/// to `reset`. After the first few ticks, the arena has grown large enough to
+/// serve all allocations of a tick without touching the heap.
+pub struct TickArena {
+    i32s: Chunks<i32>,
+    u8s: Chunks<u8>,
+}
+
+impl Default for TickArena {
+    fn default() -> TickArena {
+        TickArena::new()
+    }
+}
+
+impl TickArena {
+    pub fn new() -> TickArena {
+        TickArena {
+            i32s: Chunks::new(),
+            u8s: Chunks::new(),
+        }
+    }
+    /// Allocates a zeroed slice of `len` `i32`s.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_i32(&self, len: usize) -> &mut [i32] {
+        self.i32s.alloc(len)
+    }
+    /// Allocates a zeroed slice of `len` bytes.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_u8(&self, len: usize) -> &mut [u8] {
+        self.u8s.alloc(len)
+    }
+    /// Frees all allocations, keeping the memory for the next tick.
+    pub fn reset(&mut self) {
+        self.i32s.reset();
+        self.u8s.reset();
+    }
+    /// Number of bytes handed out since the last `reset`.
+    pub fn allocated_bytes(&self) -> usize {
+        self.i32s.allocated() * 4 + self.u8s.allocated()
+    }
+    /// Number of bytes reserved by the arena.
+    pub fn capacity_bytes(&self) -> usize {
+        self.i32s.capacity() * 4 + self.u8s.capacity()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TickArena;
+
+    #[test]
+    fn alloc() {
+        let mut arena = TickArena::new();
+        {
+            let a = arena.alloc_i32(3);
+            let b = arena.alloc_u8(5000);
+            let c = arena.alloc_u8(5000);
+            a.copy_from_slice(&[1, 2, 3]);
+            b[4999] = 1;
+            c[0] = 2;
+            assert_eq!(a, &[1, 2, 3]);
+            assert_eq!((b[4999], c[0], c[4999]), (1, 2, 0));
+            assert_eq!(arena.alloc_u8(0).len(), 0);
+        }
+        assert_eq!(arena.allocated_bytes(), 10012);
+        let capacity = arena.capacity_bytes();
+        arena.reset();
+        assert_eq!(arena.allocated_bytes(), 0);
+        assert_eq!(arena.capacity_bytes(), capacity);
+        assert!(arena.alloc_u8(10000).iter().all(|&b| b == 0));
+        assert_eq!(arena.capacity_bytes(), capacity);
+    }
+}
@@ -0,0 +1,223 @@
+use buffer_crate::Buffer;
+use buffer_crate::CapacityError;
+use std::cmp;
+use std::io;
+use std::ops;
+
+#[cfg(feature = "bytes")]
+pub use self::bytes_mut::BytesMutBuffer;
+
+const MIN_GROWTH: usize = 64;
+
+/// Byte buffer around a `Vec<u8>` that grows on demand.
+///
+/// The `Buffer` trait only allows writing into already allocated memory, so
+/// writers fail with `CapacityError` once the capacity is exhausted.
+/// `GrowableBuffer::append` handles this by growing the buffer and retrying
+/// the write, up to a configurable maximum length.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GrowableBuffer {
+    vec: Vec<u8>,
+    max_len: Option<usize>,
+}
+
+impl GrowableBuffer {
+    pub fn new() -> GrowableBuffer {
+        Default::default()
+    }
+    pub fn from_vec(vec: Vec<u8>) -> GrowableBuffer {
+        GrowableBuffer {
+            vec: vec,
+            max_len: None,
+        }
+    }
+    /// Limits the buffer to a total of `max_len` bytes.
+    ///
+    /// `append` fails with `CapacityError` if the data doesn't fit.
+    pub fn with_max_len(max_len: usize) -> GrowableBuffer {
+        GrowableBuffer {
+            vec: Vec::new(),
+            max_len: Some(max_len),
+        }
+    }
+    /// Appends the data written by `f`, retrying with a larger buffer as long
+    /// as `f` fails with `CapacityError`.
+    ///
+    /// `f` must only append to the passed `Vec`. Returns the newly written
+    /// bytes.
+    pub fn append<F>(&mut self, f: F) -> Result<&[u8], CapacityError>
+    where
+        F: FnMut(&mut Vec<u8>) -> Result<(), CapacityError>,
+    {
+        let mut f = f;
+        let start = self.vec.len();
+        loop {
+            match f(&mut self.vec) {
+                Ok(()) if !self.max_len.map(|m| self.vec.len() > m).unwrap_or(false) => {
+                    return Ok(&self.vec[start..]);
+                }
+                Ok(()) | Err(CapacityError) => {}
+            }
+            self.vec.truncate(start);
+            let capacity = self.vec.capacity();
+            if self.max_len.map(|m| capacity >= m).unwrap_or(false) {
+                return Err(CapacityError);
+            }
+            let wanted = cmp::max(capacity * 2, MIN_GROWTH);
+            self.vec.reserve(wanted - start);
+        }
+    }
+    pub fn clear(&mut self) {
+        self.vec.clear();
+    }
+    pub fn into_vec(self) -> Vec<u8> {
+        self.vec
+    }
+}
+
+impl ops::Deref for GrowableBuffer {
+    type Target = [u8];
+    fn deref(&self) -> &[u8] {
+        &self.vec
+    }
+}
+
+/// Appends to the buffer, failing with `io::ErrorKind::WriteZero` if the
+/// maximum length would be exceeded.
+impl io::Write for GrowableBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let remaining = self
+            .max_len
+            .map(|m| m.saturating_sub(self.vec.len()))
+            .unwrap_or(buf.len());
+        if remaining == 0 && !buf.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::WriteZero,
+                "maximum buffer length reached",
+            ));
+        }
+        let len = cmp::min(buf.len(), remaining);
+        self.vec.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'data> Buffer<'data> for &'data mut GrowableBuffer {
+    type Intermediate = <&'data mut Vec<u8> as Buffer<'data>>::Intermediate;
+    fn to_to_buffer_ref(self) -> Self::Intermediate {
+        (&mut self.vec).to_to_buffer_ref()
+    }
+}
+
+#[cfg(feature = "bytes")]
+mod bytes_mut {
+    use buffer_crate::Buffer;
+    use buffer_crate::BufferRef;
+    use buffer_crate::ToBufferRef;
+    use bytes::BufMut;
+    use bytes::BytesMut;
+    use std::slice;
+
+    /// Adapter to use a `BytesMut` as `Buffer`.
+    ///
+    /// Like `Vec<u8>`, only the spare capacity is written to.
+    pub struct BytesMutBuffer<'data>(pub &'data mut BytesMut);
+
+    /// The intermediate step from a `BytesMutBuffer` to a `BufferRef`.
+    pub struct BytesMutToBufferRef<'data> {
+        // Will only touch the length of the `BytesMut` through this
+        // reference, except in `to_buffer_ref`.
+        bytes: &'data mut BytesMut,
+        initialized: usize,
+    }
+
+    impl<'data> Buffer<'data> for BytesMutBuffer<'data> {
+        type Intermediate = BytesMutToBufferRef<'data>;
+        fn to_to_buffer_ref(self) -> Self::Intermediate {
+            BytesMutToBufferRef {
+                bytes: self.0,
+                initialized: 0,
+            }
+        }
+    }
+
+    impl<'data> ToBufferRef<'data> for BytesMutToBufferRef<'data> {
+        fn to_buffer_ref<'size>(&'size mut self) -> BufferRef<'data, 'size> {
+            let spare = self.bytes.spare_capacity_mut();
+            unsafe {
+                // Same trick as `buffer`'s own `Vec` implementation: The
+                // contents are only accessed through the `BufferRef`, the
+                // length only through `self`.
+                let spare = slice::from_raw_parts_mut(spare.as_mut_ptr() as *mut u8, spare.len());
+                BufferRef::new(spare, &mut self.initialized)
+            }
+        }
+    }
+
+    impl<'data> Drop for BytesMutToBufferRef<'data> {
+        fn drop(&mut self) {
+            unsafe {
+                self.bytes.advance_mut(self.initialized);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::GrowableBuffer;
+    use buffer_crate::with_buffer;
+    use buffer_crate::CapacityError;
+
+    fn write(vec: &mut Vec<u8>, len: usize) -> Result<(), CapacityError> {
+        with_buffer(vec, |mut b| {
+            for i in 0..len {
+                b.write(&[i as u8])?;
+            }
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn grow() {
+        let mut buf = GrowableBuffer::new();
+        assert_eq!(buf.append(|v| write(v, 3)).unwrap(), &[0, 1, 2]);
+        assert_eq!(buf.append(|v| write(v, 1000)).unwrap().len(), 1000);
+        assert_eq!(buf.len(), 1003);
+        assert_eq!(&buf[..4], &[0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn max_len() {
+        let mut buf = GrowableBuffer::with_max_len(100);
+        assert_eq!(buf.append(|v| write(v, 100)).unwrap().len(), 100);
+        buf.clear();
+        assert_eq!(buf.append(|v| write(v, 101)), Err(CapacityError));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn io_write() {
+        use std::io::Write;
+
+        let mut buf = GrowableBuffer::with_max_len(4);
+        buf.write_all(b"abc").unwrap();
+        assert!(buf.write_all(b"de").is_err());
+        assert_eq!(&buf[..], b"abcd");
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn bytes_mut() {
+        use super::BytesMutBuffer;
+        use bytes::BytesMut;
+
+        let mut bytes = BytesMut::with_capacity(4);
+        bytes.extend_from_slice(b"a");
+        with_buffer(BytesMutBuffer(&mut bytes), |mut b| b.write(b"bc")).unwrap();
+        assert_eq!(&bytes[..], b"abc");
+    }
+}
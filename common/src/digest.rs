@@ -1,5 +1,7 @@
 use std::error::Error;
 use std::fmt;
+#[cfg(any(feature = "crc32fast", feature = "sha2"))]
+use std::io;
 use std::iter;
 use std::str::FromStr;
 
@@ -81,6 +83,80 @@ impl FromStr for Sha256 {
     }
 }
 
+/// Computes the SHA-256 digest of `data`.
+///
+/// DDNet uses this to identify maps.
+#[cfg(feature = "sha2")]
+pub fn sha256(data: &[u8]) -> Sha256 {
+    let mut hasher = Sha256Hasher::new();
+    hasher.update(data);
+    hasher.finish()
+}
+
+/// Incremental SHA-256 computation.
+#[cfg(feature = "sha2")]
+#[derive(Clone, Default)]
+pub struct Sha256Hasher(sha2::Sha256);
+
+#[cfg(feature = "sha2")]
+impl Sha256Hasher {
+    pub fn new() -> Sha256Hasher {
+        Default::default()
+    }
+    pub fn update(&mut self, data: &[u8]) {
+        sha2::Digest::update(&mut self.0, data);
+    }
+    pub fn finish(self) -> Sha256 {
+        Sha256(sha2::Digest::finalize(self.0).into())
+    }
+}
+
+#[cfg(feature = "sha2")]
+impl io::Write for Sha256Hasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Computes the CRC32 checksum of `data`, as used for map CRCs.
+#[cfg(feature = "crc32fast")]
+pub fn crc32(data: &[u8]) -> u32 {
+    crc32fast::hash(data)
+}
+
+/// Incremental CRC32 computation.
+#[cfg(feature = "crc32fast")]
+#[derive(Clone, Default)]
+pub struct Crc32Hasher(crc32fast::Hasher);
+
+#[cfg(feature = "crc32fast")]
+impl Crc32Hasher {
+    pub fn new() -> Crc32Hasher {
+        Default::default()
+    }
+    pub fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+    pub fn finish(self) -> u32 {
+        self.0.finalize()
+    }
+}
+
+#[cfg(feature = "crc32fast")]
+impl io::Write for Crc32Hasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 #[cfg(feature = "serde")]
 mod serialize {
     use std::fmt;
@@ -122,3 +198,34 @@ mod serialize {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "crc32fast")]
+    #[test]
+    fn crc32() {
+        use super::crc32;
+        use super::Crc32Hasher;
+
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+        let mut hasher = Crc32Hasher::new();
+        hasher.update(b"1234");
+        hasher.update(b"56789");
+        assert_eq!(hasher.finish(), 0xcbf43926);
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn sha256() {
+        use super::sha256;
+        use super::Sha256Hasher;
+
+        let abc = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+        assert_eq!(sha256(b"abc").to_string(), abc);
+        let mut hasher = Sha256Hasher::new();
+        hasher.update(b"a");
+        hasher.update(b"bc");
+        assert_eq!(hasher.finish().to_string(), abc);
+    }
+}
@@ -46,6 +46,19 @@ impl<T: Read> ReadExt for T {}
 
 pub trait FileExt {
     fn read_offset_retry(&self, buffer: &mut [u8], offset: u64) -> io::Result<usize>;
+    /// Reads exactly `buffer.len()` bytes at `offset`.
+    ///
+    /// Fails with `UnexpectedEof` if the file ends before that.
+    fn read_exact_offset(&self, buffer: &mut [u8], offset: u64) -> io::Result<()>;
+    fn write_offset_retry(&self, buffer: &[u8], offset: u64) -> io::Result<usize>;
+    /// Writes all of `buffer` at `offset`.
+    ///
+    /// Fails with `WriteZero` if the data couldn't be written completely.
+    fn write_all_offset(&self, buffer: &[u8], offset: u64) -> io::Result<()>;
+    /// Writes all of `buffer` at the current end of the file.
+    ///
+    /// Returns the offset the data was written to.
+    fn append_all(&self, buffer: &[u8]) -> io::Result<u64>;
 }
 
 impl FileExt for File {
@@ -66,4 +79,178 @@ impl FileExt for File {
         }
         Ok(read)
     }
+    fn read_exact_offset(&self, buffer: &mut [u8], offset: u64) -> io::Result<()> {
+        if self.read_offset_retry(buffer, offset)? != buffer.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "failed to fill whole buffer",
+            ));
+        }
+        Ok(())
+    }
+    fn write_offset_retry(&self, buffer: &[u8], offset: u64) -> io::Result<usize> {
+        // Make sure the additions in this function don't overflow
+        let _end_offset = offset
+            .checked_add(buffer.len().u64())
+            .ok_or_else(seek_overflow)?;
+
+        let mut written = 0;
+        while written != buffer.len() {
+            match self.write_offset(&buffer[written..], offset + written.u64()) {
+                Ok(0) => break,
+                Ok(w) => written += w,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(written)
+    }
+    fn write_all_offset(&self, buffer: &[u8], offset: u64) -> io::Result<()> {
+        if self.write_offset_retry(buffer, offset)? != buffer.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::WriteZero,
+                "failed to write whole buffer",
+            ));
+        }
+        Ok(())
+    }
+    fn append_all(&self, buffer: &[u8]) -> io::Result<u64> {
+        let offset = self.metadata()?.len();
+        self.write_all_offset(buffer, offset)?;
+        Ok(offset)
+    }
+}
+
+/// Read-only file that is memory-mapped if possible.
+///
+/// With the `memmap2` feature, the file is mapped into memory and reads are
+/// plain memory copies instead of one syscall per read. Without the feature,
+/// or if mapping fails (e.g. on platforms without `mmap` or for empty files),
+/// reads fall back to `FileExt::read_offset_retry`.
+pub struct MappedFile {
+    inner: MappedFileInner,
+}
+
+enum MappedFileInner {
+    #[cfg(feature = "memmap2")]
+    Mmap(memmap2::Mmap),
+    File(File),
+}
+
+impl MappedFile {
+    /// Maps the file into memory.
+    ///
+    /// The file must not be modified while it is mapped, otherwise the read
+    /// data is unspecified.
+    pub fn new(file: File) -> MappedFile {
+        #[cfg(feature = "memmap2")]
+        {
+            // SAFETY: See the contract described in the doc comment. We never
+            // hand out references into the mapping that outlive `self`.
+            if let Ok(mmap) = unsafe { memmap2::Mmap::map(&file) } {
+                return MappedFile {
+                    inner: MappedFileInner::Mmap(mmap),
+                };
+            }
+        }
+        MappedFile {
+            inner: MappedFileInner::File(file),
+        }
+    }
+    /// Returns the whole contents of the file if it is memory-mapped.
+    pub fn as_slice(&self) -> Option<&[u8]> {
+        match self.inner {
+            #[cfg(feature = "memmap2")]
+            MappedFileInner::Mmap(ref mmap) => Some(mmap),
+            MappedFileInner::File(_) => None,
+        }
+    }
+    /// Length of the file in bytes.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> io::Result<u64> {
+        match self.inner {
+            #[cfg(feature = "memmap2")]
+            MappedFileInner::Mmap(ref mmap) => Ok(mmap.len().u64()),
+            MappedFileInner::File(ref file) => Ok(file.metadata()?.len()),
+        }
+    }
+    /// Like `FileExt::read_offset_retry`.
+    pub fn read_offset_retry(&self, buffer: &mut [u8], offset: u64) -> io::Result<usize> {
+        match self.inner {
+            #[cfg(feature = "memmap2")]
+            MappedFileInner::Mmap(ref mmap) => {
+                let _end_offset = offset
+                    .checked_add(buffer.len().u64())
+                    .ok_or_else(seek_overflow)?;
+                let start = match offset.try_usize() {
+                    Some(o) if o < mmap.len() => o,
+                    _ => return Ok(0),
+                };
+                let read = ::std::cmp::min(buffer.len(), mmap.len() - start);
+                buffer[..read].copy_from_slice(&mmap[start..start + read]);
+                Ok(read)
+            }
+            MappedFileInner::File(ref file) => file.read_offset_retry(buffer, offset),
+        }
+    }
+    /// Like `FileExt::read_exact_offset`.
+    pub fn read_exact_offset(&self, buffer: &mut [u8], offset: u64) -> io::Result<()> {
+        match self.inner {
+            #[cfg(feature = "memmap2")]
+            MappedFileInner::Mmap(_) => {
+                if self.read_offset_retry(buffer, offset)? != buffer.len() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "failed to fill whole buffer",
+                    ));
+                }
+                Ok(())
+            }
+            MappedFileInner::File(ref file) => file.read_exact_offset(buffer, offset),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FileExt;
+    use super::MappedFile;
+    use std::env;
+    use std::fs;
+    use std::fs::OpenOptions;
+    use std::io;
+    use std::process;
+
+    #[test]
+    fn offset_roundtrip() {
+        let path = env::temp_dir().join(format!("libtw2-common-io-{}", process::id()));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        file.write_all_offset(b"world", 6).unwrap();
+        file.write_all_offset(b"hello ", 0).unwrap();
+        assert_eq!(file.append_all(b"!").unwrap(), 11);
+
+        let mut buffer = [0; 12];
+        file.read_exact_offset(&mut buffer, 0).unwrap();
+        assert_eq!(&buffer, b"hello world!");
+        let err = file.read_exact_offset(&mut buffer, 1).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        let file = MappedFile::new(file);
+        let mut buffer = [0; 5];
+        file.read_exact_offset(&mut buffer, 6).unwrap();
+        assert_eq!(&buffer, b"world");
+        assert_eq!(file.read_offset_retry(&mut buffer, 10).unwrap(), 2);
+        assert_eq!(&buffer[..2], b"d!");
+        assert_eq!(file.read_offset_retry(&mut buffer, 100).unwrap(), 0);
+        let err = file.read_exact_offset(&mut buffer, 10).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
 }
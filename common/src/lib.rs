@@ -3,11 +3,30 @@
 extern crate quickcheck;
 
 extern crate arrayvec;
+extern crate buffer as buffer_crate;
+#[cfg(feature = "bytes")]
+extern crate bytes;
+#[cfg(feature = "crc32fast")]
+extern crate crc32fast;
 extern crate file_offset;
+#[cfg(feature = "log")]
+#[macro_use]
+extern crate log;
+#[cfg(feature = "memmap2")]
+extern crate memmap2;
 #[cfg(feature = "serde")]
 extern crate serde;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde_derive;
+#[cfg(feature = "sha2")]
+extern crate sha2;
+extern crate warn as warn_crate;
 
+pub use arena::TickArena;
+pub use buffer::GrowableBuffer;
 pub use map_iter::MapIterator;
+pub use ringbuf::RingBuffer;
 pub use slice::relative_size_of;
 pub use slice::relative_size_of_mult;
 pub use takeable::Takeable;
@@ -15,12 +34,17 @@ pub use takeable::Takeable;
 #[macro_use]
 mod macros;
 
+pub mod arena;
+pub mod buffer;
 pub mod digest;
 pub mod io;
 pub mod map_iter;
 pub mod num;
 pub mod pretty;
+pub mod ringbuf;
 pub mod slice;
 pub mod str;
 pub mod takeable;
+pub mod time;
 pub mod vec;
+pub mod warn;
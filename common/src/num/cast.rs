@@ -1,4 +1,5 @@
 use std::convert::TryInto;
+use std::error;
 use std::fmt;
 
 trait TypeName {
@@ -19,6 +20,41 @@ fn unwrap_overflow<T: fmt::Display, U: TypeName>(original: T, val: Option<U>) ->
     }
 }
 
+/// Error returned by `CheckedCast` if a value can't be represented in the
+/// target type.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CastError {
+    value: i128,
+    target: &'static str,
+}
+
+impl CastError {
+    /// The value that couldn't be converted.
+    pub fn value(&self) -> i128 {
+        self.value
+    }
+    /// Name of the type the value should have been converted to.
+    pub fn target(&self) -> &'static str {
+        self.target
+    }
+}
+
+impl error::Error for CastError {}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "overflow casting {} to `{}`", self.value, self.target)
+    }
+}
+
+#[inline]
+fn ok_or_cast_error<U: TypeName>(original: i128, val: Option<U>) -> Result<U, CastError> {
+    match val {
+        Some(v) => Ok(v),
+        None => Err(CastError { value: original, target: U::type_name() }),
+    }
+}
+
 pub trait Cast {
     fn i8(self) -> i8 where Self: Sized + I8;
     fn u8(self) -> u8 where Self: Sized + U8;
@@ -52,6 +88,19 @@ pub trait Cast {
     fn assert_usize(self) -> usize where Self: Sized + NUsize;
 }
 
+pub trait CheckedCast {
+    fn checked_i8(self) -> Result<i8, CastError> where Self: Sized + NI8;
+    fn checked_u8(self) -> Result<u8, CastError> where Self: Sized + NU8;
+    fn checked_i16(self) -> Result<i16, CastError> where Self: Sized + NI16;
+    fn checked_u16(self) -> Result<u16, CastError> where Self: Sized + NU16;
+    fn checked_i32(self) -> Result<i32, CastError> where Self: Sized + NI32;
+    fn checked_u32(self) -> Result<u32, CastError> where Self: Sized + NU32;
+    fn checked_i64(self) -> Result<i64, CastError> where Self: Sized + NI64;
+    fn checked_u64(self) -> Result<u64, CastError> where Self: Sized + NU64;
+    fn checked_isize(self) -> Result<isize, CastError> where Self: Sized + NIsize;
+    fn checked_usize(self) -> Result<usize, CastError> where Self: Sized + NUsize;
+}
+
 pub trait I8 { }
 pub trait U8 { }
 pub trait I16 { }
@@ -404,6 +453,136 @@ impl Cast for usize {
     #[inline] fn assert_usize(self) -> usize { unreachable!() }
 }
 
+impl CheckedCast for i8 {
+    #[inline] fn checked_i8(self) -> Result<i8, CastError> { unreachable!() }
+    #[inline] fn checked_u8(self) -> Result<u8, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i16(self) -> Result<i16, CastError> { unreachable!() }
+    #[inline] fn checked_u16(self) -> Result<u16, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i32(self) -> Result<i32, CastError> { unreachable!() }
+    #[inline] fn checked_u32(self) -> Result<u32, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i64(self) -> Result<i64, CastError> { unreachable!() }
+    #[inline] fn checked_u64(self) -> Result<u64, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_isize(self) -> Result<isize, CastError> { unreachable!() }
+    #[inline] fn checked_usize(self) -> Result<usize, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+}
+
+impl CheckedCast for u8 {
+    #[inline] fn checked_i8(self) -> Result<i8, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u8(self) -> Result<u8, CastError> { unreachable!() }
+    #[inline] fn checked_i16(self) -> Result<i16, CastError> { unreachable!() }
+    #[inline] fn checked_u16(self) -> Result<u16, CastError> { unreachable!() }
+    #[inline] fn checked_i32(self) -> Result<i32, CastError> { unreachable!() }
+    #[inline] fn checked_u32(self) -> Result<u32, CastError> { unreachable!() }
+    #[inline] fn checked_i64(self) -> Result<i64, CastError> { unreachable!() }
+    #[inline] fn checked_u64(self) -> Result<u64, CastError> { unreachable!() }
+    #[inline] fn checked_isize(self) -> Result<isize, CastError> { unreachable!() }
+    #[inline] fn checked_usize(self) -> Result<usize, CastError> { unreachable!() }
+}
+
+impl CheckedCast for i16 {
+    #[inline] fn checked_i8(self) -> Result<i8, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u8(self) -> Result<u8, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i16(self) -> Result<i16, CastError> { unreachable!() }
+    #[inline] fn checked_u16(self) -> Result<u16, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i32(self) -> Result<i32, CastError> { unreachable!() }
+    #[inline] fn checked_u32(self) -> Result<u32, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i64(self) -> Result<i64, CastError> { unreachable!() }
+    #[inline] fn checked_u64(self) -> Result<u64, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_isize(self) -> Result<isize, CastError> { unreachable!() }
+    #[inline] fn checked_usize(self) -> Result<usize, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+}
+
+impl CheckedCast for u16 {
+    #[inline] fn checked_i8(self) -> Result<i8, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u8(self) -> Result<u8, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i16(self) -> Result<i16, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u16(self) -> Result<u16, CastError> { unreachable!() }
+    #[inline] fn checked_i32(self) -> Result<i32, CastError> { unreachable!() }
+    #[inline] fn checked_u32(self) -> Result<u32, CastError> { unreachable!() }
+    #[inline] fn checked_i64(self) -> Result<i64, CastError> { unreachable!() }
+    #[inline] fn checked_u64(self) -> Result<u64, CastError> { unreachable!() }
+    #[inline] fn checked_isize(self) -> Result<isize, CastError> { unreachable!() }
+    #[inline] fn checked_usize(self) -> Result<usize, CastError> { unreachable!() }
+}
+
+impl CheckedCast for i32 {
+    #[inline] fn checked_i8(self) -> Result<i8, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u8(self) -> Result<u8, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i16(self) -> Result<i16, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u16(self) -> Result<u16, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i32(self) -> Result<i32, CastError> { unreachable!() }
+    #[inline] fn checked_u32(self) -> Result<u32, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i64(self) -> Result<i64, CastError> { unreachable!() }
+    #[inline] fn checked_u64(self) -> Result<u64, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_isize(self) -> Result<isize, CastError> { unreachable!() }
+    #[inline] fn checked_usize(self) -> Result<usize, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+}
+
+impl CheckedCast for u32 {
+    #[inline] fn checked_i8(self) -> Result<i8, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u8(self) -> Result<u8, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i16(self) -> Result<i16, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u16(self) -> Result<u16, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i32(self) -> Result<i32, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u32(self) -> Result<u32, CastError> { unreachable!() }
+    #[inline] fn checked_i64(self) -> Result<i64, CastError> { unreachable!() }
+    #[inline] fn checked_u64(self) -> Result<u64, CastError> { unreachable!() }
+    #[inline] fn checked_isize(self) -> Result<isize, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_usize(self) -> Result<usize, CastError> { unreachable!() }
+}
+
+impl CheckedCast for i64 {
+    #[inline] fn checked_i8(self) -> Result<i8, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u8(self) -> Result<u8, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i16(self) -> Result<i16, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u16(self) -> Result<u16, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i32(self) -> Result<i32, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u32(self) -> Result<u32, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i64(self) -> Result<i64, CastError> { unreachable!() }
+    #[inline] fn checked_u64(self) -> Result<u64, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_isize(self) -> Result<isize, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_usize(self) -> Result<usize, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+}
+
+impl CheckedCast for u64 {
+    #[inline] fn checked_i8(self) -> Result<i8, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u8(self) -> Result<u8, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i16(self) -> Result<i16, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u16(self) -> Result<u16, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i32(self) -> Result<i32, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u32(self) -> Result<u32, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i64(self) -> Result<i64, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u64(self) -> Result<u64, CastError> { unreachable!() }
+    #[inline] fn checked_isize(self) -> Result<isize, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_usize(self) -> Result<usize, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+}
+
+impl CheckedCast for isize {
+    #[inline] fn checked_i8(self) -> Result<i8, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u8(self) -> Result<u8, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i16(self) -> Result<i16, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u16(self) -> Result<u16, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i32(self) -> Result<i32, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u32(self) -> Result<u32, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i64(self) -> Result<i64, CastError> { unreachable!() }
+    #[inline] fn checked_u64(self) -> Result<u64, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_isize(self) -> Result<isize, CastError> { unreachable!() }
+    #[inline] fn checked_usize(self) -> Result<usize, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+}
+
+impl CheckedCast for usize {
+    #[inline] fn checked_i8(self) -> Result<i8, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u8(self) -> Result<u8, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i16(self) -> Result<i16, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u16(self) -> Result<u16, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i32(self) -> Result<i32, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u32(self) -> Result<u32, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_i64(self) -> Result<i64, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_u64(self) -> Result<u64, CastError> { unreachable!() }
+    #[inline] fn checked_isize(self) -> Result<isize, CastError> { ok_or_cast_error(self as i128, self.try_into().ok()) }
+    #[inline] fn checked_usize(self) -> Result<usize, CastError> { unreachable!() }
+}
+
 impl I8 for i8 { }
 impl NI8 for u8 { }
 impl NI8 for i16 { }
@@ -4,6 +4,8 @@ use std::fmt;
 mod cast;
 
 pub use self::cast::Cast;
+pub use self::cast::CastError;
+pub use self::cast::CheckedCast;
 
 pub trait CastFloat {
     fn round_to_i32(self) -> i32;
@@ -60,7 +62,7 @@ pub struct LeU16([u8; 2]);
 ///
 /// Is internally represented as `[u8; 2]`.
 #[repr(C, packed)]
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
 pub struct LeI16([u8; 2]);
 
 // ======================
@@ -209,6 +211,20 @@ mod test {
             LeI16::from_i16(LeI16::from_bytes(bytes).to_i16()).as_bytes() == bytes
         }
     }
+    #[test]
+    fn checked_cast() {
+        use super::CheckedCast;
+        assert_eq!(300i32.checked_u16(), Ok(300));
+        let err = (-1i32).checked_u16().unwrap_err();
+        assert_eq!(err.value(), -1);
+        assert_eq!(err.target(), "u16");
+        assert_eq!(err.to_string(), "overflow casting -1 to `u16`");
+        assert_eq!(
+            u64::max_value().checked_i64().unwrap_err().value(),
+            u64::max_value() as i128
+        );
+    }
+
     #[test]
     fn order_u16() {
         let be = *BeU16::from_u16(0x1234).as_bytes();
@@ -1,5 +1,6 @@
 use arrayvec::ArrayVec;
 use std::ascii;
+use std::cmp;
 use std::fmt;
 use std::mem;
 use std::ops;
@@ -107,3 +108,204 @@ impl<'a> fmt::Debug for AlmostStringSlice<'a> {
             .finish()
     }
 }
+
+const DEFAULT_HEXDUMP_WIDTH: usize = 16;
+
+fn ascii_char(byte: u8) -> char {
+    if b' ' <= byte && byte <= b'~' {
+        byte as char
+    } else {
+        '.'
+    }
+}
+
+fn hexdump_row(
+    f: &mut fmt::Formatter,
+    prefix: &str,
+    offset: usize,
+    row: &[u8],
+    width: usize,
+) -> fmt::Result {
+    write!(f, "{}{:08x} ", prefix, offset)?;
+    for i in 0..width {
+        match row.get(i) {
+            Some(b) => write!(f, " {:02x}", b)?,
+            None => f.write_str("   ")?,
+        }
+    }
+    f.write_str("  |")?;
+    for &b in row {
+        write!(f, "{}", ascii_char(b))?;
+    }
+    f.write_str("|\n")?;
+    Ok(())
+}
+
+/// Hex dump of a byte slice, in the style of `hexdump -C`.
+///
+/// Each line consists of the offset, the hex representation of up to `width`
+/// bytes and their ASCII representation, non-printable bytes are shown as
+/// `.`.
+///
+/// ```
+/// use common::pretty::HexDump;
+/// assert_eq!(
+///     HexDump::new(b"\x00abc").width(4).to_string(),
+///     "00000000  00 61 62 63  |.abc|\n",
+/// );
+/// ```
+#[derive(Clone, Copy)]
+pub struct HexDump<'a> {
+    bytes: &'a [u8],
+    width: usize,
+}
+
+impl<'a> HexDump<'a> {
+    pub fn new(bytes: &'a [u8]) -> HexDump<'a> {
+        HexDump {
+            bytes: bytes,
+            width: DEFAULT_HEXDUMP_WIDTH,
+        }
+    }
+    /// Sets the number of bytes per line, defaults to 16.
+    ///
+    /// Panics if `width` is zero.
+    pub fn width(self, width: usize) -> HexDump<'a> {
+        assert!(width != 0, "hex dump width must be positive");
+        HexDump {
+            width: width,
+            ..self
+        }
+    }
+}
+
+impl<'a> fmt::Display for HexDump<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, row) in self.bytes.chunks(self.width).enumerate() {
+            hexdump_row(f, "", i * self.width, row, self.width)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Debug for HexDump<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Side-by-side hex dump of two byte slices, highlighting the differences.
+///
+/// Lines that are equal in both slices are printed once. Differing lines are
+/// printed twice, prefixed by `-` for the left and `+` for the right slice,
+/// followed by a line that marks the differing bytes with `^^`. Bytes present
+/// in only one of the slices count as differing.
+///
+/// ```
+/// use common::pretty::HexDiff;
+/// assert_eq!(
+///     HexDiff::new(b"abcd", b"abXd").width(4).to_string(),
+///     "- 00000000  61 62 63 64  |abcd|\n\
+///      + 00000000  61 62 58 64  |abXd|\n\
+///      \x20                 ^^\n",
+/// );
+/// ```
+#[derive(Clone, Copy)]
+pub struct HexDiff<'a> {
+    left: &'a [u8],
+    right: &'a [u8],
+    width: usize,
+}
+
+impl<'a> HexDiff<'a> {
+    pub fn new(left: &'a [u8], right: &'a [u8]) -> HexDiff<'a> {
+        HexDiff {
+            left: left,
+            right: right,
+            width: DEFAULT_HEXDUMP_WIDTH,
+        }
+    }
+    /// Sets the number of bytes per line, defaults to 16.
+    ///
+    /// Panics if `width` is zero.
+    pub fn width(self, width: usize) -> HexDiff<'a> {
+        assert!(width != 0, "hex dump width must be positive");
+        HexDiff {
+            width: width,
+            ..self
+        }
+    }
+}
+
+fn row(bytes: &[u8], start: usize, width: usize) -> &[u8] {
+    let start = cmp::min(start, bytes.len());
+    let end = cmp::min(start + width, bytes.len());
+    &bytes[start..end]
+}
+
+impl<'a> fmt::Display for HexDiff<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let len = cmp::max(self.left.len(), self.right.len());
+        let mut offset = 0;
+        while offset < len {
+            let left = row(self.left, offset, self.width);
+            let right = row(self.right, offset, self.width);
+            if left == right {
+                hexdump_row(f, "  ", offset, left, self.width)?;
+            } else {
+                hexdump_row(f, "- ", offset, left, self.width)?;
+                hexdump_row(f, "+ ", offset, right, self.width)?;
+                let differs = |i: usize| left.get(i) != right.get(i);
+                let last = (0..self.width).rev().find(|&i| differs(i)).unwrap();
+                f.write_str("           ")?;
+                for i in 0..last + 1 {
+                    f.write_str(if differs(i) { " ^^" } else { "   " })?;
+                }
+                f.write_str("\n")?;
+            }
+            offset += self.width;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Debug for HexDiff<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HexDiff;
+    use super::HexDump;
+
+    #[test]
+    fn hexdump() {
+        assert_eq!(HexDump::new(b"").to_string(), "");
+        assert_eq!(
+            HexDump::new(b"0123456789\n\xff\x7f").width(8).to_string(),
+            "00000000  30 31 32 33 34 35 36 37  |01234567|\n\
+             00000008  38 39 0a ff 7f           |89...|\n",
+        );
+    }
+
+    #[test]
+    fn hexdiff() {
+        assert_eq!(HexDiff::new(b"abc", b"abc").to_string(), {
+            "  00000000  61 62 63                                         |abc|\n"
+        });
+        assert_eq!(
+            HexDiff::new(b"aaaabbbbcc", b"aaaaBbbb")
+                .width(4)
+                .to_string(),
+            "  00000000  61 61 61 61  |aaaa|\n\
+             - 00000004  62 62 62 62  |bbbb|\n\
+             + 00000004  42 62 62 62  |Bbbb|\n\
+             \x20           ^^\n\
+             - 00000008  63 63        |cc|\n\
+             + 00000008               ||\n\
+             \x20           ^^ ^^\n",
+        );
+    }
+}
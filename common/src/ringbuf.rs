@@ -0,0 +1,192 @@
+use std::collections::vec_deque;
+use std::collections::VecDeque;
+use std::ops;
+
+/// What happens when pushing to a full `RingBuffer`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Overflow {
+    /// Drop the oldest element to make room for the new one.
+    DropOldest,
+    /// Keep the buffer as is and hand the new element back.
+    RejectNewest,
+}
+
+/// Queue with a fixed capacity.
+///
+/// Elements are iterated in insertion order, oldest first.
+#[derive(Clone, Debug)]
+pub struct RingBuffer<T> {
+    inner: VecDeque<T>,
+    capacity: usize,
+    overflow: Overflow,
+}
+
+impl<T> RingBuffer<T> {
+    /// Creates an empty ring buffer holding at most `capacity` elements.
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize, overflow: Overflow) -> RingBuffer<T> {
+        assert!(capacity != 0, "ring buffer capacity must be positive");
+        RingBuffer {
+            inner: VecDeque::with_capacity(capacity),
+            capacity: capacity,
+            overflow: overflow,
+        }
+    }
+    /// Appends an element.
+    ///
+    /// If the buffer is full, `Overflow::DropOldest` returns the dropped
+    /// element as `Ok(Some(_))` and `Overflow::RejectNewest` returns the
+    /// passed element as `Err(_)`.
+    pub fn push(&mut self, value: T) -> Result<Option<T>, T> {
+        let mut dropped = None;
+        if self.is_full() {
+            match self.overflow {
+                Overflow::DropOldest => dropped = self.inner.pop_front(),
+                Overflow::RejectNewest => return Err(value),
+            }
+        }
+        self.inner.push_back(value);
+        Ok(dropped)
+    }
+    /// Removes and returns the oldest element.
+    pub fn pop(&mut self) -> Option<T> {
+        self.inner.pop_front()
+    }
+    /// Returns the oldest element.
+    pub fn front(&self) -> Option<&T> {
+        self.inner.front()
+    }
+    /// Returns the newest element.
+    pub fn back(&self) -> Option<&T> {
+        self.inner.back()
+    }
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.inner.front_mut()
+    }
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.inner.back_mut()
+    }
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.inner.get(index)
+    }
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.inner.get_mut(index)
+    }
+    /// Removes all but the oldest `len` elements.
+    pub fn truncate(&mut self, len: usize) {
+        self.inner.truncate(len);
+    }
+    /// Removes the oldest `count` elements.
+    pub fn drop_oldest(&mut self, count: usize) {
+        let count = ::std::cmp::min(count, self.inner.len());
+        self.inner.drain(..count);
+    }
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.inner.retain(f);
+    }
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+    pub fn is_full(&self) -> bool {
+        self.inner.len() == self.capacity
+    }
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    pub fn overflow(&self) -> Overflow {
+        self.overflow
+    }
+    /// Iterates over the elements, oldest first.
+    pub fn iter(&self) -> vec_deque::Iter<'_, T> {
+        self.inner.iter()
+    }
+    /// Mutably iterates over the elements, oldest first.
+    pub fn iter_mut(&mut self) -> vec_deque::IterMut<'_, T> {
+        self.inner.iter_mut()
+    }
+}
+
+impl<T> ops::Index<usize> for RingBuffer<T> {
+    type Output = T;
+    fn index(&self, index: usize) -> &T {
+        &self.inner[index]
+    }
+}
+
+impl<T> ops::IndexMut<usize> for RingBuffer<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.inner[index]
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RingBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = vec_deque::Iter<'a, T>;
+    fn into_iter(self) -> vec_deque::Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut RingBuffer<T> {
+    type Item = &'a mut T;
+    type IntoIter = vec_deque::IterMut<'a, T>;
+    fn into_iter(self) -> vec_deque::IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> IntoIterator for RingBuffer<T> {
+    type Item = T;
+    type IntoIter = vec_deque::IntoIter<T>;
+    fn into_iter(self) -> vec_deque::IntoIter<T> {
+        self.inner.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Overflow;
+    use super::RingBuffer;
+
+    #[test]
+    fn drop_oldest() {
+        let mut buf = RingBuffer::new(3, Overflow::DropOldest);
+        assert_eq!(buf.push(1), Ok(None));
+        assert_eq!(buf.push(2), Ok(None));
+        assert_eq!(buf.push(3), Ok(None));
+        assert!(buf.is_full());
+        assert_eq!(buf.push(4), Ok(Some(1)));
+        assert_eq!(buf.iter().cloned().collect::<Vec<_>>(), [2, 3, 4]);
+        assert_eq!((buf.front(), buf.back()), (Some(&2), Some(&4)));
+        assert_eq!(buf.pop(), Some(2));
+        assert_eq!(buf.len(), 2);
+    }
+
+    #[test]
+    fn reject_newest() {
+        let mut buf = RingBuffer::new(2, Overflow::RejectNewest);
+        assert_eq!(buf.push(1), Ok(None));
+        assert_eq!(buf.push(2), Ok(None));
+        assert_eq!(buf.push(3), Err(3));
+        assert_eq!(buf.into_iter().collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn drop_oldest_count() {
+        let mut buf = RingBuffer::new(4, Overflow::RejectNewest);
+        for i in 0..4 {
+            buf.push(i).unwrap();
+        }
+        buf.drop_oldest(3);
+        assert_eq!(buf[0], 3);
+        buf.drop_oldest(10);
+        assert!(buf.is_empty());
+    }
+}
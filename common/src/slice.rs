@@ -23,3 +23,149 @@ pub unsafe fn transmute_mut<T, U>(x: &mut [T]) -> &mut [U] {
     transmute::<T, U>(x); // For the error checking.
     slice::from_raw_parts_mut(x.as_ptr() as *mut U, relative_size_of_mult::<T, U>(x.len()))
 }
+
+/// Reinterprets a slice of `i32`s as bytes in native endianness.
+pub fn i32s_as_bytes(x: &[i32]) -> &[u8] {
+    unsafe { transmute(x) }
+}
+
+/// Reinterprets a mutable slice of `i32`s as bytes in native endianness.
+///
+/// This is safe because any bit pattern is a valid `i32`.
+pub fn i32s_as_bytes_mut(x: &mut [i32]) -> &mut [u8] {
+    unsafe { transmute_mut(x) }
+}
+
+/// Reinterprets bytes as `i32`s in native endianness.
+///
+/// Returns `None` if the slice isn't aligned to `i32` or its length isn't a
+/// multiple of 4.
+pub fn bytes_as_i32s(x: &[u8]) -> Option<&[i32]> {
+    if x.as_ptr() as usize % mem::align_of::<i32>() != 0 || x.len() % mem::size_of::<i32>() != 0 {
+        return None;
+    }
+    Some(unsafe { slice::from_raw_parts(x.as_ptr() as *const i32, x.len() / 4) })
+}
+
+/// Mutable version of `bytes_as_i32s`.
+pub fn bytes_as_i32s_mut(x: &mut [u8]) -> Option<&mut [i32]> {
+    if x.as_ptr() as usize % mem::align_of::<i32>() != 0 || x.len() % mem::size_of::<i32>() != 0 {
+        return None;
+    }
+    Some(unsafe { slice::from_raw_parts_mut(x.as_mut_ptr() as *mut i32, x.len() / 4) })
+}
+
+/// Converts `i32`s read in little endian to native endianness, in place.
+pub fn from_le_i32s(x: &mut [i32]) {
+    for i in x {
+        *i = i32::from_le(*i);
+    }
+}
+
+/// Converts `i32`s in native endianness to little endian, in place.
+pub fn to_le_i32s(x: &mut [i32]) {
+    for i in x {
+        *i = i.to_le();
+    }
+}
+
+/// Iterator over the little endian `i32`s of a byte slice.
+///
+/// Returned by `chunks_exact_le_i32`.
+#[derive(Clone, Debug)]
+pub struct ChunksExactLeI32<'a>(slice::ChunksExact<'a, u8>);
+
+/// Iterator over the big endian `i32`s of a byte slice.
+///
+/// Returned by `chunks_exact_be_i32`.
+#[derive(Clone, Debug)]
+pub struct ChunksExactBeI32<'a>(slice::ChunksExact<'a, u8>);
+
+fn chunk_to_array(chunk: &[u8]) -> [u8; 4] {
+    [chunk[0], chunk[1], chunk[2], chunk[3]]
+}
+
+/// Iterates over `bytes` as little endian `i32`s.
+///
+/// Trailing bytes that don't form a complete `i32` are available through
+/// `remainder`.
+pub fn chunks_exact_le_i32(bytes: &[u8]) -> ChunksExactLeI32<'_> {
+    ChunksExactLeI32(bytes.chunks_exact(4))
+}
+
+/// Iterates over `bytes` as big endian `i32`s.
+///
+/// Trailing bytes that don't form a complete `i32` are available through
+/// `remainder`.
+pub fn chunks_exact_be_i32(bytes: &[u8]) -> ChunksExactBeI32<'_> {
+    ChunksExactBeI32(bytes.chunks_exact(4))
+}
+
+impl<'a> ChunksExactLeI32<'a> {
+    pub fn remainder(&self) -> &'a [u8] {
+        self.0.remainder()
+    }
+}
+
+impl<'a> ChunksExactBeI32<'a> {
+    pub fn remainder(&self) -> &'a [u8] {
+        self.0.remainder()
+    }
+}
+
+impl<'a> Iterator for ChunksExactLeI32<'a> {
+    type Item = i32;
+    fn next(&mut self) -> Option<i32> {
+        self.0.next().map(|c| i32::from_le_bytes(chunk_to_array(c)))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> Iterator for ChunksExactBeI32<'a> {
+    type Item = i32;
+    fn next(&mut self) -> Option<i32> {
+        self.0.next().map(|c| i32::from_be_bytes(chunk_to_array(c)))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for ChunksExactLeI32<'a> {}
+impl<'a> ExactSizeIterator for ChunksExactBeI32<'a> {}
+
+#[cfg(test)]
+mod test {
+    use super::bytes_as_i32s;
+    use super::chunks_exact_be_i32;
+    use super::chunks_exact_le_i32;
+    use super::from_le_i32s;
+    use super::i32s_as_bytes;
+    use super::i32s_as_bytes_mut;
+
+    #[test]
+    fn chunks_exact() {
+        let bytes = [1, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 7];
+        let le = chunks_exact_le_i32(&bytes);
+        assert_eq!(le.len(), 2);
+        assert_eq!(le.remainder(), &[7]);
+        assert_eq!(le.collect::<Vec<_>>(), [1, -1]);
+        let be = chunks_exact_be_i32(&bytes);
+        assert_eq!(be.collect::<Vec<_>>(), [0x01000000, -1]);
+    }
+
+    #[test]
+    fn reinterpret() {
+        let mut ints = [0x01020304, 5];
+        assert_eq!(i32s_as_bytes(&ints).len(), 8);
+        i32s_as_bytes_mut(&mut ints)[4..].copy_from_slice(&6i32.to_le_bytes());
+        from_le_i32s(&mut ints[1..]);
+        assert_eq!(ints[1], 6);
+        let bytes = i32s_as_bytes(&ints);
+        assert_eq!(bytes_as_i32s(bytes), Some(&ints[..]));
+        assert_eq!(bytes_as_i32s(&bytes[1..5]), None);
+        assert_eq!(bytes_as_i32s(&bytes[..7]), None);
+    }
+}
@@ -0,0 +1,153 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::time::Duration;
+
+/// Game tick, as sent over the network and stored in demos and
+/// teehistorian files.
+///
+/// Tick numbers can wrap around, so comparisons between ticks are done on
+/// their wrapping difference, see `Tick::wrapping_cmp`.
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Tick(pub i32);
+
+impl Tick {
+    pub fn new(tick: i32) -> Tick {
+        Tick(tick)
+    }
+    pub fn to_i32(self) -> i32 {
+        self.0
+    }
+    /// Returns the tick `ticks` ticks after this one.
+    pub fn wrapping_add(self, ticks: i32) -> Tick {
+        Tick(self.0.wrapping_add(ticks))
+    }
+    /// Returns the tick `ticks` ticks before this one.
+    pub fn wrapping_sub(self, ticks: i32) -> Tick {
+        Tick(self.0.wrapping_sub(ticks))
+    }
+    /// Returns the number of ticks from `earlier` to this tick.
+    ///
+    /// The result is negative if `earlier` is actually after this tick.
+    pub fn wrapping_diff(self, earlier: Tick) -> i32 {
+        self.0.wrapping_sub(earlier.0)
+    }
+    /// Compares two ticks, taking wrap-around into account.
+    ///
+    /// A tick is considered greater than another one if it is less than half
+    /// the tick range after it.
+    pub fn wrapping_cmp(self, other: Tick) -> Ordering {
+        self.wrapping_diff(other).cmp(&0)
+    }
+    /// Returns whether this tick comes after `other`, see `wrapping_cmp`.
+    pub fn is_after(self, other: Tick) -> bool {
+        self.wrapping_cmp(other) == Ordering::Greater
+    }
+    /// Returns the time elapsed from `earlier` to this tick, or `None` if
+    /// `earlier` is after this tick.
+    pub fn duration_since(self, earlier: Tick, rate: TickRate) -> Option<Duration> {
+        let diff = self.wrapping_diff(earlier);
+        if diff < 0 {
+            return None;
+        }
+        Some(rate.to_duration(diff as u64))
+    }
+}
+
+impl From<i32> for Tick {
+    fn from(tick: i32) -> Tick {
+        Tick(tick)
+    }
+}
+
+impl From<Tick> for i32 {
+    fn from(tick: Tick) -> i32 {
+        tick.0
+    }
+}
+
+impl fmt::Debug for Tick {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Tick({})", self.0)
+    }
+}
+
+impl fmt::Display for Tick {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// Number of ticks per second.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct TickRate(u32);
+
+/// Tick rate of the Teeworlds server.
+pub const DEFAULT_TICK_RATE: TickRate = TickRate(50);
+
+impl Default for TickRate {
+    fn default() -> TickRate {
+        DEFAULT_TICK_RATE
+    }
+}
+
+impl TickRate {
+    /// Panics if `ticks_per_second` is zero.
+    pub fn new(ticks_per_second: u32) -> TickRate {
+        assert!(ticks_per_second != 0, "tick rate must be positive");
+        TickRate(ticks_per_second)
+    }
+    pub fn ticks_per_second(self) -> u32 {
+        self.0
+    }
+    /// Duration of a single tick.
+    pub fn tick_duration(self) -> Duration {
+        self.to_duration(1)
+    }
+    /// Duration of `ticks` ticks.
+    pub fn to_duration(self, ticks: u64) -> Duration {
+        let rate = u64::from(self.0);
+        let nanos = (ticks % rate) * 1_000_000_000 / rate;
+        Duration::new(ticks / rate, nanos as u32)
+    }
+    /// Number of complete ticks in `duration`.
+    pub fn to_ticks(self, duration: Duration) -> u64 {
+        let rate = u64::from(self.0);
+        duration.as_secs() * rate + u64::from(duration.subsec_nanos()) * rate / 1_000_000_000
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Tick;
+    use super::TickRate;
+    use super::DEFAULT_TICK_RATE;
+    use std::cmp::Ordering;
+    use std::i32;
+    use std::time::Duration;
+
+    #[test]
+    fn wrapping() {
+        let max = Tick(i32::MAX);
+        assert_eq!(max.wrapping_add(1), Tick(i32::MIN));
+        assert_eq!(Tick(i32::MIN).wrapping_diff(max), 1);
+        assert!(Tick(i32::MIN).is_after(max));
+        assert!(!max.is_after(Tick(i32::MIN)));
+        assert_eq!(Tick(5).wrapping_cmp(Tick(5)), Ordering::Equal);
+        assert_eq!(Tick(4).wrapping_cmp(Tick(5)), Ordering::Less);
+    }
+
+    #[test]
+    fn duration() {
+        let rate = DEFAULT_TICK_RATE;
+        assert_eq!(rate.tick_duration(), Duration::from_millis(20));
+        assert_eq!(rate.to_duration(125), Duration::from_millis(2500));
+        assert_eq!(rate.to_ticks(Duration::from_millis(2519)), 125);
+        assert_eq!(rate.to_ticks(Duration::from_millis(2520)), 126);
+        assert_eq!(
+            Tick(10).duration_since(Tick(5), TickRate::new(100)),
+            Some(Duration::from_millis(50)),
+        );
+        assert_eq!(Tick(5).duration_since(Tick(10), rate), None);
+    }
+}
@@ -0,0 +1,315 @@
+use std::fmt;
+use std::time::Duration;
+use std::time::Instant;
+use warn_crate::Warn;
+
+/// Collects warnings together with the context they occurred in.
+///
+/// The context is set with `set_context` and attached to every warning
+/// received afterwards, e.g. the peer or packet number the warnings belong
+/// to.
+#[derive(Clone, Debug, Default)]
+pub struct CollectWarnings<W, C = ()> {
+    context: C,
+    warnings: Vec<(C, W)>,
+}
+
+impl<W, C: Clone + Default> CollectWarnings<W, C> {
+    pub fn new() -> CollectWarnings<W, C> {
+        CollectWarnings::with_context(Default::default())
+    }
+}
+
+impl<W, C: Clone> CollectWarnings<W, C> {
+    pub fn with_context(context: C) -> CollectWarnings<W, C> {
+        CollectWarnings {
+            context: context,
+            warnings: Vec::new(),
+        }
+    }
+    /// Sets the context for the following warnings.
+    pub fn set_context(&mut self, context: C) {
+        self.context = context;
+    }
+    pub fn context(&self) -> &C {
+        &self.context
+    }
+    /// The collected warnings, in the order they were received.
+    pub fn warnings(&self) -> &[(C, W)] {
+        &self.warnings
+    }
+    pub fn into_warnings(self) -> Vec<(C, W)> {
+        self.warnings
+    }
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+    pub fn clear(&mut self) {
+        self.warnings.clear();
+    }
+}
+
+impl<W, C: Clone> Warn<W> for CollectWarnings<W, C> {
+    fn warn(&mut self, warning: W) {
+        self.warnings.push((self.context.clone(), warning));
+    }
+}
+
+/// Logs each warning via the `log` crate, with a configurable target.
+///
+/// Pass `module_path!()` as target to get the same filtering as for the
+/// other log messages of the module.
+#[cfg(feature = "log")]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct LogWarn {
+    target: &'static str,
+}
+
+#[cfg(feature = "log")]
+impl LogWarn {
+    pub fn new(target: &'static str) -> LogWarn {
+        LogWarn { target: target }
+    }
+    pub fn target(&self) -> &'static str {
+        self.target
+    }
+}
+
+#[cfg(feature = "log")]
+impl<W: fmt::Debug> Warn<W> for LogWarn {
+    fn warn(&mut self, warning: W) {
+        warn!(target: self.target, "{:?}", warning);
+    }
+}
+
+/// Passes at most a fixed number of warnings per time interval on to the
+/// underlying `Warn` object and drops the rest.
+///
+/// Useful for servers, so that a misbehaving client can't flood the log by
+/// triggering the same warning with every packet.
+#[derive(Clone)]
+pub struct RateLimited<W> {
+    inner: W,
+    max_warnings: u32,
+    interval: Duration,
+    interval_start: Option<Instant>,
+    interval_warnings: u32,
+    suppressed: u64,
+}
+
+impl<W> RateLimited<W> {
+    /// Lets `max_warnings` warnings through per `interval`.
+    pub fn new(inner: W, max_warnings: u32, interval: Duration) -> RateLimited<W> {
+        RateLimited {
+            inner: inner,
+            max_warnings: max_warnings,
+            interval: interval,
+            interval_start: None,
+            interval_warnings: 0,
+            suppressed: 0,
+        }
+    }
+    /// Total number of dropped warnings.
+    pub fn suppressed(&self) -> u64 {
+        self.suppressed
+    }
+    pub fn inner(&self) -> &W {
+        &self.inner
+    }
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+    /// Like `Warn::warn`, but with an explicit current time.
+    pub fn warn_at<T>(&mut self, now: Instant, warning: T)
+    where
+        W: Warn<T>,
+    {
+        let expired = self
+            .interval_start
+            .map(|s| now.duration_since(s) >= self.interval)
+            .unwrap_or(true);
+        if expired {
+            self.interval_start = Some(now);
+            self.interval_warnings = 0;
+        }
+        if self.interval_warnings < self.max_warnings {
+            self.interval_warnings += 1;
+            self.inner.warn(warning);
+        } else {
+            self.suppressed += 1;
+        }
+    }
+}
+
+impl<T, W: Warn<T>> Warn<T> for RateLimited<W> {
+    fn warn(&mut self, warning: T) {
+        self.warn_at(Instant::now(), warning)
+    }
+}
+
+impl<W: fmt::Debug> fmt::Debug for RateLimited<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RateLimited")
+            .field("inner", &self.inner)
+            .field("max_warnings", &self.max_warnings)
+            .field("interval", &self.interval)
+            .field("suppressed", &self.suppressed)
+            .finish()
+    }
+}
+
+/// Warning together with the place in the input where it occurred.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Located<W> {
+    /// Byte offset into the decoded data.
+    pub offset: usize,
+    /// Name of the structure that was being decoded, e.g. the message name.
+    pub structure: &'static str,
+    pub warning: W,
+}
+
+impl<W: fmt::Debug> fmt::Display for Located<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:?} at offset {} in {}",
+            self.warning, self.offset, self.structure
+        )
+    }
+}
+
+/// Helper struct for the `locate` function.
+pub struct Locate<'a, W: 'a> {
+    warn: &'a mut W,
+    offset: usize,
+    structure: &'static str,
+}
+
+/// Attaches a location to all warnings passed to this, before passing them to
+/// the underlying warn object.
+///
+/// For decoders, `offset` is usually the number of bytes read before starting
+/// to decode `structure`, e.g. `Unpacker::num_bytes_read`. The location can
+/// be refined for nested structures or single fields using `set_offset` and
+/// `set_structure`.
+pub fn locate<'a, W>(warn: &'a mut W, offset: usize, structure: &'static str) -> Locate<'a, W> {
+    Locate {
+        warn: warn,
+        offset: offset,
+        structure: structure,
+    }
+}
+
+impl<'a, W> Locate<'a, W> {
+    pub fn set_offset(&mut self, offset: usize) {
+        self.offset = offset;
+    }
+    pub fn set_structure(&mut self, structure: &'static str) {
+        self.structure = structure;
+    }
+}
+
+impl<'a, T, W: Warn<Located<T>>> Warn<T> for Locate<'a, W> {
+    fn warn(&mut self, warning: T) {
+        self.warn.warn(Located {
+            offset: self.offset,
+            structure: self.structure,
+            warning: warning,
+        });
+    }
+}
+
+/// Helper struct for the `strip_location` function.
+pub struct StripLocation<'a, W: 'a> {
+    warn: &'a mut W,
+}
+
+/// Removes the location from all warnings passed to this, before passing them
+/// to the underlying warn object.
+///
+/// Useful for calling functions that report `Located` warnings if the caller
+/// is only interested in the warnings themselves.
+pub fn strip_location<'a, W>(warn: &'a mut W) -> StripLocation<'a, W> {
+    StripLocation { warn }
+}
+
+impl<'a, T, W: Warn<T>> Warn<Located<T>> for StripLocation<'a, W> {
+    fn warn(&mut self, warning: Located<T>) {
+        self.warn.warn(warning.warning);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::locate;
+    use super::strip_location;
+    use super::CollectWarnings;
+    use super::Located;
+    use super::RateLimited;
+    use std::time::Duration;
+    use std::time::Instant;
+    use warn_crate::Warn;
+
+    #[test]
+    fn collect() {
+        let mut warn = CollectWarnings::new();
+        warn.warn("a");
+        warn.set_context(5);
+        warn.warn("b");
+        warn.warn("c");
+        assert_eq!(warn.warnings(), &[(0, "a"), (5, "b"), (5, "c")]);
+    }
+
+    #[test]
+    fn rate_limited() {
+        let start = Instant::now();
+        let second = Duration::from_secs(1);
+        let mut warn = RateLimited::new(Vec::new(), 2, second);
+        for i in 0..5 {
+            warn.warn_at(start, i);
+        }
+        warn.warn_at(start + second, 5);
+        assert_eq!(warn.suppressed(), 3);
+        assert_eq!(warn.into_inner(), [0, 1, 5]);
+    }
+
+    #[test]
+    fn located() {
+        let mut warnings = vec![];
+        {
+            let mut warn = locate(&mut warnings, 3, "Header");
+            warn.warn("a");
+            warn.set_offset(7);
+            warn.warn("b");
+        }
+        assert_eq!(
+            warnings,
+            [
+                Located {
+                    offset: 3,
+                    structure: "Header",
+                    warning: "a",
+                },
+                Located {
+                    offset: 7,
+                    structure: "Header",
+                    warning: "b",
+                },
+            ]
+        );
+        assert_eq!(warnings[1].to_string(), "\"b\" at offset 7 in Header");
+    }
+
+    #[test]
+    fn stripped() {
+        let mut warnings = vec![];
+        {
+            let mut stripped = strip_location(&mut warnings);
+            locate(&mut stripped, 3, "Header").warn("a");
+        }
+        assert_eq!(warnings, ["a"]);
+    }
+}
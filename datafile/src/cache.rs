@@ -0,0 +1,214 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use file::Error;
+use file::Reader;
+use read_at::ReadAt;
+
+/// Statistics of the data cache of a `CachedReader`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CacheStats {
+    /// Number of data reads served from the cache.
+    pub hits: u64,
+    /// Number of data reads that had to be decompressed.
+    pub misses: u64,
+    /// Number of data blobs dropped to stay within the byte budget.
+    pub evictions: u64,
+    /// Number of data blobs currently cached.
+    pub entries: usize,
+    /// Sum of the sizes of the currently cached data blobs.
+    pub bytes: usize,
+}
+
+struct Entry {
+    data: Arc<Vec<u8>>,
+    last_use: u64,
+}
+
+/// Datafile reader that decompresses data blobs on first access and keeps
+/// them in a least-recently-used cache bounded by a byte budget.
+///
+/// Data blobs larger than the whole budget are returned but not cached.
+pub struct CachedReader<R: ReadAt = File> {
+    reader: Reader<R>,
+    budget: usize,
+    entries: HashMap<usize, Entry>,
+    // Maps the time of last use to the data index.
+    lru: BTreeMap<u64, usize>,
+    now: u64,
+    stats: CacheStats,
+}
+
+impl CachedReader<File> {
+    pub fn open<P: AsRef<Path>>(path: P, budget: usize) -> Result<CachedReader, Error> {
+        Ok(CachedReader::new(Reader::open(path)?, budget))
+    }
+}
+
+impl<R: ReadAt> CachedReader<R> {
+    pub fn new(reader: Reader<R>, budget: usize) -> CachedReader<R> {
+        CachedReader {
+            reader,
+            budget,
+            entries: HashMap::new(),
+            lru: BTreeMap::new(),
+            now: 0,
+            stats: CacheStats::default(),
+        }
+    }
+    /// The underlying reader, for accessing the items.
+    pub fn reader(&self) -> &Reader<R> {
+        &self.reader
+    }
+    pub fn into_inner(self) -> Reader<R> {
+        self.reader
+    }
+    /// Returns the uncompressed data blob, from the cache if possible.
+    pub fn read_data(&mut self, index: usize) -> Result<Arc<Vec<u8>>, Error> {
+        self.now += 1;
+        if let Some(entry) = self.entries.get_mut(&index) {
+            self.lru.remove(&entry.last_use);
+            self.lru.insert(self.now, index);
+            entry.last_use = self.now;
+            self.stats.hits += 1;
+            return Ok(entry.data.clone());
+        }
+        self.stats.misses += 1;
+        let data = Arc::new(self.reader.read_data(index)?);
+        if data.len() <= self.budget {
+            self.shrink_to(self.budget - data.len());
+            self.lru.insert(self.now, index);
+            self.entries.insert(
+                index,
+                Entry {
+                    data: data.clone(),
+                    last_use: self.now,
+                },
+            );
+            self.stats.entries += 1;
+            self.stats.bytes += data.len();
+        }
+        Ok(data)
+    }
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+    /// Changes the byte budget, evicting data blobs if necessary.
+    pub fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+        self.shrink_to(budget);
+    }
+    /// Drops all cached data blobs, keeping the statistics.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.lru.clear();
+        self.stats.entries = 0;
+        self.stats.bytes = 0;
+    }
+    fn shrink_to(&mut self, bytes: usize) {
+        while self.stats.bytes > bytes {
+            let (&last_use, &index) = self.lru.iter().next().unwrap();
+            self.lru.remove(&last_use);
+            let entry = self.entries.remove(&index).unwrap();
+            self.stats.evictions += 1;
+            self.stats.entries -= 1;
+            self.stats.bytes -= entry.data.len();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CacheStats;
+    use super::CachedReader;
+    use file::Reader;
+    use writer::Writer;
+
+    /// Creates a cached reader for data blobs of 10, 20 and 30 bytes.
+    fn reader(budget: usize) -> CachedReader<Vec<u8>> {
+        let mut writer = Writer::new(Vec::new());
+        for &len in &[10, 20, 30] {
+            writer.add_data(&vec![len as u8; len]).unwrap();
+        }
+        let file = writer.finish().unwrap();
+        CachedReader::new(Reader::from_read_at(file).unwrap(), budget)
+    }
+
+    fn stats(hits: u64, misses: u64, evictions: u64, entries: usize, bytes: usize) -> CacheStats {
+        CacheStats {
+            hits,
+            misses,
+            evictions,
+            entries,
+            bytes,
+        }
+    }
+
+    #[test]
+    fn hits() {
+        let mut cache = reader(100);
+        assert_eq!(&cache.read_data(1).unwrap()[..], &[20; 20][..]);
+        assert_eq!(cache.stats(), stats(0, 1, 0, 1, 20));
+        assert_eq!(&cache.read_data(1).unwrap()[..], &[20; 20][..]);
+        assert_eq!(cache.stats(), stats(1, 1, 0, 1, 20));
+        cache.read_data(0).unwrap();
+        cache.read_data(2).unwrap();
+        assert_eq!(cache.stats(), stats(1, 3, 0, 3, 60));
+    }
+
+    #[test]
+    fn lru_eviction() {
+        let mut cache = reader(50);
+        cache.read_data(0).unwrap();
+        cache.read_data(1).unwrap();
+        cache.read_data(0).unwrap();
+        // Evicts the least recently used blob 1, not the first read blob 0.
+        cache.read_data(2).unwrap();
+        assert_eq!(cache.stats(), stats(1, 3, 1, 2, 40));
+        cache.read_data(0).unwrap();
+        assert_eq!(cache.stats(), stats(2, 3, 1, 2, 40));
+        cache.read_data(1).unwrap();
+        assert_eq!(cache.stats(), stats(2, 4, 2, 2, 30));
+        cache.read_data(0).unwrap();
+        assert_eq!(cache.stats(), stats(3, 4, 2, 2, 30));
+    }
+
+    #[test]
+    fn budget() {
+        let mut cache = reader(25);
+        // Blobs larger than the budget are returned, but not cached.
+        assert_eq!(&cache.read_data(2).unwrap()[..], &[30; 30][..]);
+        assert_eq!(cache.stats(), stats(0, 1, 0, 0, 0));
+        cache.read_data(2).unwrap();
+        assert_eq!(cache.stats(), stats(0, 2, 0, 0, 0));
+
+        cache.read_data(0).unwrap();
+        cache.read_data(1).unwrap();
+        assert_eq!(cache.stats(), stats(0, 4, 1, 1, 20));
+
+        cache.set_budget(60);
+        assert_eq!(cache.budget(), 60);
+        cache.read_data(0).unwrap();
+        cache.read_data(2).unwrap();
+        assert_eq!(cache.stats(), stats(0, 6, 1, 3, 60));
+        cache.set_budget(35);
+        assert_eq!(cache.stats(), stats(0, 6, 3, 1, 30));
+    }
+
+    #[test]
+    fn clear() {
+        let mut cache = reader(100);
+        cache.read_data(0).unwrap();
+        cache.read_data(0).unwrap();
+        cache.clear();
+        assert_eq!(cache.stats(), stats(1, 1, 0, 0, 0));
+        cache.read_data(0).unwrap();
+        assert_eq!(cache.stats(), stats(1, 2, 0, 1, 10));
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use file::Error;
+use file::Reader;
+use raw::Version;
+use read_at::ReadAt;
+use writer::WriteError;
+use writer::Writer;
+
+#[derive(Debug)]
+pub enum EditError {
+    Read(Error),
+    Write(WriteError),
+}
+
+impl From<Error> for EditError {
+    fn from(err: Error) -> EditError {
+        EditError::Read(err)
+    }
+}
+
+impl From<WriteError> for EditError {
+    fn from(err: WriteError) -> EditError {
+        EditError::Write(err)
+    }
+}
+
+/// Changes items and data blobs of an existing datafile.
+///
+/// The changes are kept in memory until `save` writes a new datafile.
+/// Unchanged data blobs are copied as stored, without decompressing and
+/// compressing them again. Data blobs can't be removed as that would change
+/// the indices referenced from the items.
+pub struct Editor<R: ReadAt = File> {
+    reader: Reader<R>,
+    // `None` for removed items.
+    items: BTreeMap<(u16, u16), Option<Vec<i32>>>,
+    // `None` for unchanged data blobs of the original datafile.
+    data: Vec<Option<Vec<u8>>>,
+}
+
+impl Editor<File> {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Editor, Error> {
+        Ok(Editor::new(Reader::open(path)?))
+    }
+}
+
+impl<R: ReadAt> Editor<R> {
+    pub fn new(reader: Reader<R>) -> Editor<R> {
+        let num_data = reader.num_data();
+        Editor {
+            reader,
+            items: BTreeMap::new(),
+            data: vec![None; num_data],
+        }
+    }
+    /// The original datafile.
+    pub fn reader(&self) -> &Reader<R> {
+        &self.reader
+    }
+    /// The current data of the item, including changes.
+    pub fn item(&self, type_id: u16, id: u16) -> Option<&[i32]> {
+        match self.items.get(&(type_id, id)) {
+            Some(item) => item.as_ref().map(|d| &d[..]),
+            None => self.reader.find_item(type_id, id).map(|i| i.data),
+        }
+    }
+    /// Replaces the item or adds it if it doesn't exist yet.
+    pub fn set_item(&mut self, type_id: u16, id: u16, data: &[i32]) {
+        self.items.insert((type_id, id), Some(data.to_vec()));
+    }
+    /// Removes the item, returns whether it existed.
+    pub fn remove_item(&mut self, type_id: u16, id: u16) -> bool {
+        let existed = self.item(type_id, id).is_some();
+        self.items.insert((type_id, id), None);
+        existed
+    }
+    pub fn num_data(&self) -> usize {
+        self.data.len()
+    }
+    /// The current contents of the data blob, including changes.
+    pub fn read_data(&mut self, index: usize) -> Result<Vec<u8>, Error> {
+        match self.data[index] {
+            Some(ref data) => Ok(data.clone()),
+            None => self.reader.read_data(index),
+        }
+    }
+    /// Replaces the contents of the data blob.
+    pub fn set_data(&mut self, index: usize, data: &[u8]) {
+        self.data[index] = Some(data.to_vec());
+    }
+    /// Appends a data blob and returns its index.
+    pub fn add_data(&mut self, data: &[u8]) -> usize {
+        self.data.push(Some(data.to_vec()));
+        self.data.len() - 1
+    }
+    /// Writes the changed datafile and returns the underlying writer.
+    ///
+    /// The version of the original datafile is kept, except that the wrong
+    /// header sizes of `Version::V4Crude` are fixed. `file` must not be the
+    /// original datafile.
+    pub fn save<W: io::Write>(&mut self, file: W) -> Result<W, EditError> {
+        let version = match self.reader.version() {
+            Version::V3 => Version::V3,
+            Version::V4Crude | Version::V4 => Version::V4,
+        };
+        let mut writer = Writer::new_with_version(file, version);
+        for item in self.reader.items() {
+            if !self.items.contains_key(&(item.type_id, item.id)) {
+                writer.add_item(item.type_id, item.id, item.data)?;
+            }
+        }
+        for (&(type_id, id), data) in &self.items {
+            if let Some(ref data) = *data {
+                writer.add_item(type_id, id, data)?;
+            }
+        }
+        for (index, data) in self.data.iter().enumerate() {
+            match *data {
+                Some(ref data) => {
+                    writer.add_data(data)?;
+                }
+                None => {
+                    let raw = self.reader.read_data_raw(index)?;
+                    writer.add_data_raw(raw, self.reader.data_size(index));
+                }
+            }
+        }
+        Ok(writer.finish()?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Editor;
+    use file::Reader;
+    use raw::Version;
+    use writer::Writer;
+
+    #[test]
+    fn edit() {
+        for &version in &[Version::V3, Version::V4] {
+            let mut writer = Writer::new_with_version(Vec::new(), version);
+            writer.add_item(1, 0, &[1, 2]).unwrap();
+            writer.add_item(1, 1, &[3]).unwrap();
+            writer.add_item(2, 0, &[]).unwrap();
+            writer.add_data(b"first").unwrap();
+            writer.add_data(b"second").unwrap();
+            let original = writer.finish().unwrap();
+
+            let mut editor = Editor::new(Reader::from_read_at(&original[..]).unwrap());
+            editor.set_item(1, 1, &[4, 5, 6]);
+            editor.set_item(0, 7, &[7]);
+            assert!(editor.remove_item(2, 0));
+            assert!(!editor.remove_item(3, 0));
+            editor.set_data(1, b"changed");
+            assert_eq!(editor.add_data(b"third"), 2);
+            assert_eq!(editor.item(1, 1), Some(&[4, 5, 6][..]));
+            assert_eq!(editor.read_data(0).unwrap(), b"first");
+            let saved = editor.save(Vec::new()).unwrap();
+
+            let mut reader = Reader::from_read_at(&saved[..]).unwrap();
+            assert_eq!(reader.version(), version);
+            let items: Vec<_> = reader
+                .items()
+                .map(|i| (i.type_id, i.id, i.data.to_vec()))
+                .collect();
+            assert_eq!(
+                items,
+                [(0, 7, vec![7]), (1, 0, vec![1, 2]), (1, 1, vec![4, 5, 6])]
+            );
+            let data: Vec<_> = reader.data_iter().map(|d| d.unwrap()).collect();
+            assert_eq!(data, [&b"first"[..], b"changed", b"third"]);
+            assert!(reader.verify().unwrap().is_ok());
+        }
+    }
+}
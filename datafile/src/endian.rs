@@ -0,0 +1,137 @@
+//! Conversion between the byte order of datafiles and the native one.
+//!
+//! Datafiles store all integers as little endian `i32`s, regardless of the
+//! platform they were written on. Everything read from or written to a
+//! datafile goes through this module, the rest of the crate only deals with
+//! native integers.
+//!
+//! On big-endian platforms, the tests of this module can be run under
+//! emulation, e.g. `cross test --target s390x-unknown-linux-gnu -p datafile`.
+
+use common::slice::i32s_as_bytes;
+use common::slice::transmute;
+use common::slice::transmute_mut;
+use std::mem;
+
+use format::OnlyI32;
+
+/// Reinterprets `i32`-only values as `i32`s.
+pub fn as_i32s<T: OnlyI32>(x: &[T]) -> &[i32] {
+    // Safe because `T: OnlyI32` consists of tightly packed `i32`s.
+    unsafe { transmute(x) }
+}
+
+/// Reinterprets `i32`-only values as `i32`s.
+pub fn as_mut_i32s<T: OnlyI32>(x: &mut [T]) -> &mut [i32] {
+    // Safe because `T: OnlyI32` consists of tightly packed `i32`s.
+    unsafe { transmute_mut(x) }
+}
+
+/// Returns the bytes of the values in native endianness.
+pub fn as_bytes<T: OnlyI32>(x: &[T]) -> &[u8] {
+    i32s_as_bytes(as_i32s(x))
+}
+
+/// Converts values read from a datafile to native endianness, in place.
+pub fn from_le<T: OnlyI32>(x: &mut [T]) {
+    for i in as_mut_i32s(x) {
+        *i = i32::from_le(*i);
+    }
+}
+
+/// Converts values in native endianness to the one of datafiles, in place.
+pub fn to_le<T: OnlyI32>(x: &mut [T]) {
+    for i in as_mut_i32s(x) {
+        *i = i.to_le();
+    }
+}
+
+/// Returns the bytes of the values as they are stored in datafiles.
+pub fn to_le_bytes<T: OnlyI32>(x: &[T]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(mem::size_of_val(x));
+    for &i in as_i32s(x) {
+        result.extend_from_slice(&i.to_le_bytes());
+    }
+    result
+}
+
+/// Parses values from the bytes stored in a datafile.
+///
+/// Returns `None` if the length of `bytes` isn't a multiple of the size of
+/// `T`.
+pub fn from_le_bytes<T: OnlyI32>(bytes: &[u8]) -> Option<Vec<T>> {
+    if bytes.len() % mem::size_of::<T>() != 0 {
+        return None;
+    }
+    let mut result: Vec<T> = Vec::with_capacity(bytes.len() / mem::size_of::<T>());
+    // Safe because `T: OnlyI32` is POD and the bytes are fully initialized
+    // below.
+    unsafe {
+        result.set_len(bytes.len() / mem::size_of::<T>());
+    }
+    for (i, chunk) in as_mut_i32s(&mut result)
+        .iter_mut()
+        .zip(bytes.chunks(mem::size_of::<i32>()))
+    {
+        *i = i32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod test {
+    use common::num::Cast;
+
+    use super::as_bytes;
+    use super::from_le;
+    use super::from_le_bytes;
+    use super::to_le;
+    use super::to_le_bytes;
+    use format::ItemType;
+    use writer::Writer;
+    use Reader;
+
+    #[test]
+    fn bytes() {
+        let bytes = [1, 0, 0, 0, 0xfe, 0xff, 0xff, 0xff, 0x78, 0x56, 0x34, 0x12];
+        let types: Vec<ItemType> = from_le_bytes(&bytes).unwrap();
+        assert_eq!(types.len(), 1);
+        assert_eq!(
+            (types[0].type_id, types[0].start, types[0].num),
+            (1, -2, 0x12345678)
+        );
+        assert_eq!(to_le_bytes(&types), bytes);
+        assert!(from_le_bytes::<ItemType>(&bytes[..8]).is_none());
+        assert_eq!(from_le_bytes::<i32>(&bytes[..8]).unwrap(), [1, -2]);
+    }
+
+    #[test]
+    fn in_place() {
+        let mut values = [0x12345678, -2];
+        to_le(&mut values);
+        assert_eq!(
+            as_bytes(&values),
+            [0x78, 0x56, 0x34, 0x12, 0xfe, 0xff, 0xff, 0xff]
+        );
+        from_le(&mut values);
+        assert_eq!(values, [0x12345678, -2]);
+    }
+
+    #[test]
+    fn datafile() {
+        let mut writer = Writer::new(Vec::new());
+        writer.add_item(1, 2, &[0x01020304, -1]).unwrap();
+        writer.add_data(b"hello").unwrap();
+        let file = writer.finish().unwrap();
+
+        assert_eq!(&file[..8], b"DATA\x04\x00\x00\x00");
+        // The item data is stored in little endian.
+        let item_data = [4, 3, 2, 1, 0xff, 0xff, 0xff, 0xff];
+        assert!(file.windows(8).any(|w| w == item_data));
+
+        let mut reader = Reader::from_read_at(&file).unwrap();
+        assert_eq!(reader.find_item(1, 2).unwrap().data, [0x01020304, -1]);
+        assert_eq!(reader.read_data(0).unwrap(), b"hello");
+        assert_eq!(file.len(), reader.verify().unwrap().size.usize());
+    }
+}
@@ -1,11 +1,9 @@
+use common::digest::Crc32Hasher;
 use common::io::seek_overflow;
-use common::io::FileExt;
-use common::io::ReadExt;
 use common::num::Cast;
 use common::MapIterator;
 use std::fs::File;
 use std::io;
-use std::io::BufReader;
 use std::io::Seek;
 use std::io::SeekFrom;
 use std::ops;
@@ -17,6 +15,7 @@ use raw;
 use raw::CallbackError;
 use raw::CallbackNew;
 use raw::CallbackReadData;
+use read_at::ReadAt;
 
 #[derive(Debug)]
 pub enum Error {
@@ -36,23 +35,52 @@ impl From<io::Error> for Error {
     }
 }
 
-struct CallbackDataNew {
-    file: BufReader<File>,
+/// An inconsistency found by `Reader::verify`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Inconsistency {
+    /// The `size` and `swaplen` header fields don't account for the data
+    /// sizes. Some old versions wrote these, they can still be read.
+    CrudeSize,
+    /// The file continues after the end of the datafile.
+    TrailingData { len: u64 },
+    /// The data blob doesn't decompress to its declared size.
+    Data { index: usize, error: format::Error },
+}
+
+/// Result of `Reader::verify`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct VerifyReport {
+    /// Size of the datafile in bytes.
+    pub size: u32,
+    /// CRC32 of the datafile, as used for map CRCs.
+    pub crc: u32,
+    pub inconsistencies: Vec<Inconsistency>,
+}
+
+impl VerifyReport {
+    pub fn is_ok(&self) -> bool {
+        self.inconsistencies.is_empty()
+    }
+}
+
+struct CallbackDataNew<'a, R: 'a + ReadAt> {
+    file: &'a R,
     datafile_start: u64,
     cur_datafile_offset: u64,
     seek_base: Option<u64>,
     error: Option<io::Error>,
 }
 
-struct CallbackData {
-    file: File,
+struct CallbackData<R: ReadAt> {
+    file: R,
+    datafile_start: u64,
     seek_base: u64,
     buffer: Option<Vec<u8>>,
     error: Option<io::Error>,
 }
 
-pub struct Reader {
-    callback_data: CallbackData,
+pub struct Reader<R: ReadAt = File> {
+    callback_data: CallbackData<R>,
     raw: raw::Reader,
 }
 
@@ -71,26 +99,39 @@ impl<T> ResultExt for Result<T, raw::Error> {
     }
 }
 
-impl Reader {
-    fn new_impl(file: File, check_initial_offset: bool) -> Result<Reader, Error> {
+impl Reader<File> {
+    /// Reads the datafile starting at the current position of `file`.
+    pub fn new(file: File) -> Result<Reader, Error> {
         let mut file = file;
-        let datafile_start = if check_initial_offset {
-            file.seek(SeekFrom::Current(0))?
-        } else {
-            0
-        };
-        let mut callback_data_new = CallbackDataNew {
-            file: BufReader::new(file),
-            datafile_start: datafile_start,
-            cur_datafile_offset: 0,
-            seek_base: None,
-            error: None,
+        let datafile_start = file.seek(SeekFrom::Current(0))?;
+        Reader::new_impl(file, datafile_start)
+    }
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Reader, Error> {
+        fn inner(path: &Path) -> Result<Reader, Error> {
+            Reader::new_impl(File::open(path)?, 0)
+        }
+        inner(path.as_ref())
+    }
+}
+
+impl<R: ReadAt> Reader<R> {
+    fn new_impl(file: R, datafile_start: u64) -> Result<Reader<R>, Error> {
+        let (raw, seek_base) = {
+            let mut callback_data_new = CallbackDataNew {
+                file: &file,
+                datafile_start: datafile_start,
+                cur_datafile_offset: 0,
+                seek_base: None,
+                error: None,
+            };
+            let raw =
+                raw::Reader::new(&mut callback_data_new).retrieve(&mut callback_data_new.error)?;
+            (raw, callback_data_new.seek_base.unwrap())
         };
-        let raw =
-            raw::Reader::new(&mut callback_data_new).retrieve(&mut callback_data_new.error)?;
         let callback_data = CallbackData {
-            file: callback_data_new.file.into_inner(),
-            seek_base: callback_data_new.seek_base.unwrap(),
+            file: file,
+            datafile_start: datafile_start,
+            seek_base: seek_base,
             buffer: None,
             error: None,
         };
@@ -99,14 +140,13 @@ impl Reader {
             raw: raw,
         })
     }
-    pub fn new(file: File) -> Result<Reader, Error> {
-        Reader::new_impl(file, true)
+    /// Reads the datafile from any source that supports reading at offsets,
+    /// e.g. a byte slice or a `Section` of a demo file.
+    pub fn from_read_at(file: R) -> Result<Reader<R>, Error> {
+        Reader::new_impl(file, 0)
     }
-    pub fn open<P: AsRef<Path>>(path: P) -> Result<Reader, Error> {
-        fn inner(path: &Path) -> Result<Reader, Error> {
-            Reader::new_impl(File::open(path)?, false)
-        }
-        inner(path.as_ref())
+    pub fn into_inner(self) -> R {
+        self.callback_data.file
     }
     pub fn debug_dump(&mut self) -> Result<(), Error> {
         Ok(self
@@ -117,12 +157,67 @@ impl Reader {
     pub fn version(&self) -> raw::Version {
         self.raw.version()
     }
+    /// Checks the whole datafile, including the CRC and all data blobs.
+    ///
+    /// Malformed headers and items are already rejected when opening the
+    /// datafile, this finds the remaining problems that would otherwise only
+    /// show up when accessing the data. Only I/O errors are returned as
+    /// errors, everything else is collected in the report.
+    pub fn verify(&mut self) -> Result<VerifyReport, Error> {
+        let size = self.raw.size();
+        let mut inconsistencies = Vec::new();
+        if self.version() == raw::Version::V4Crude {
+            inconsistencies.push(Inconsistency::CrudeSize);
+        }
+        let start = self.callback_data.datafile_start;
+        let file_len = self.callback_data.file.len()?;
+        let trailing = file_len.saturating_sub(start + size.u64());
+        if trailing != 0 {
+            inconsistencies.push(Inconsistency::TrailingData { len: trailing });
+        }
+
+        let mut crc = Crc32Hasher::new();
+        let mut buffer = vec![0; 64 * 1024];
+        let mut offset = 0;
+        while offset < size {
+            let len = buffer.len().min((size - offset).usize());
+            self.callback_data
+                .file
+                .read_exact_at(&mut buffer[..len], start + offset.u64())?;
+            crc.update(&buffer[..len]);
+            offset += len.assert_u32();
+        }
+
+        for index in 0..self.num_data() {
+            match self.read_data(index) {
+                Ok(_) => {}
+                Err(Error::Df(error)) => inconsistencies.push(Inconsistency::Data { index, error }),
+                Err(Error::Io(e)) => return Err(Error::Io(e)),
+            }
+        }
+        Ok(VerifyReport {
+            size,
+            crc: crc.finish(),
+            inconsistencies,
+        })
+    }
     pub fn read_data(&mut self, index: usize) -> Result<Vec<u8>, Error> {
         self.raw
             .read_data(&mut self.callback_data, index)
             .retrieve(&mut self.callback_data.error)?;
         Ok(self.callback_data.buffer.take().unwrap())
     }
+    /// Reads the data blob without decompressing it, see
+    /// `raw::Reader::read_data_raw`.
+    pub fn read_data_raw(&mut self, index: usize) -> Result<Vec<u8>, Error> {
+        Ok(self
+            .raw
+            .read_data_raw(&mut self.callback_data, index)
+            .retrieve(&mut self.callback_data.error)?)
+    }
+    pub fn data_size(&self, index: usize) -> usize {
+        self.raw.data_size(index)
+    }
     pub fn item(&self, index: usize) -> ItemView {
         self.raw.item(index)
     }
@@ -155,8 +250,8 @@ impl Reader {
     pub fn item_type_items(&self, type_id: u16) -> raw::ItemTypeItems {
         self.raw.item_type_items(type_id)
     }
-    pub fn data_iter(&mut self) -> DataIter {
-        fn map_fn(i: usize, self_: &mut &mut Reader) -> Result<Vec<u8>, Error> {
+    pub fn data_iter(&mut self) -> DataIter<R> {
+        fn map_fn<R: ReadAt>(i: usize, self_: &mut &mut Reader<R>) -> Result<Vec<u8>, Error> {
             self_.read_data(i)
         }
         let num_data = self.num_data();
@@ -164,17 +259,22 @@ impl Reader {
     }
 }
 
-pub type DataIter<'a> = MapIterator<Result<Vec<u8>, Error>, &'a mut Reader, ops::Range<usize>>;
+pub type DataIter<'a, R = File> =
+    MapIterator<Result<Vec<u8>, Error>, &'a mut Reader<R>, ops::Range<usize>>;
 
 // "SeekOverflow"
 fn so(o: Option<u64>) -> io::Result<u64> {
     o.ok_or_else(seek_overflow)
 }
 
-impl CallbackNew for CallbackDataNew {
+impl<'a, R: ReadAt> CallbackNew for CallbackDataNew<'a, R> {
     fn read(&mut self, buffer: &mut [u8]) -> Result<usize, CallbackError> {
-        fn inner(self_: &mut CallbackDataNew, buffer: &mut [u8]) -> io::Result<usize> {
-            let r = self_.file.read_retry(buffer)?;
+        fn inner<R: ReadAt>(
+            self_: &mut CallbackDataNew<R>,
+            buffer: &mut [u8],
+        ) -> io::Result<usize> {
+            let offset = so(self_.datafile_start.checked_add(self_.cur_datafile_offset))?;
+            let r = self_.file.read_at(buffer, offset)?;
             self_.cur_datafile_offset = so(self_.cur_datafile_offset.checked_add(r.u64()))?;
             Ok(r)
         }
@@ -188,10 +288,13 @@ impl CallbackNew for CallbackDataNew {
         Ok(())
     }
     fn ensure_filesize(&mut self, filesize: u32) -> Result<Result<(), ()>, CallbackError> {
-        fn inner(self_: &mut CallbackDataNew, filesize: u32) -> io::Result<Result<(), ()>> {
-            let actual = self_.file.get_ref().metadata()?.len();
+        fn inner<R: ReadAt>(
+            self_: &mut CallbackDataNew<R>,
+            filesize: u32,
+        ) -> io::Result<Result<(), ()>> {
+            let actual = self_.file.len()?;
             Ok(
-                if actual.checked_sub(self_.datafile_start).unwrap() >= filesize.u64() {
+                if actual.saturating_sub(self_.datafile_start) >= filesize.u64() {
                     Ok(())
                 } else {
                     Err(())
@@ -204,11 +307,16 @@ impl CallbackNew for CallbackDataNew {
         })
     }
 }
-impl CallbackReadData for CallbackData {
+impl<R: ReadAt> CallbackReadData for CallbackData<R> {
     fn seek_read(&mut self, start: u32, buffer: &mut [u8]) -> Result<usize, CallbackError> {
-        fn inner(self_: &mut CallbackData, start: u32, buffer: &mut [u8]) -> io::Result<usize> {
+        fn inner<R: ReadAt>(
+            self_: &mut CallbackData<R>,
+            start: u32,
+            buffer: &mut [u8],
+        ) -> io::Result<usize> {
             let offset = so(self_.seek_base.checked_add(start.u64()))?;
-            self_.file.read_offset_retry(buffer, offset)
+            let offset = so(self_.datafile_start.checked_add(offset))?;
+            self_.file.read_at(buffer, offset)
         }
         inner(self, start, buffer).map_err(|e| {
             self.error = Some(e);
@@ -227,3 +335,74 @@ impl CallbackReadData for CallbackData {
         self.buffer.as_mut().unwrap()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::Inconsistency;
+    use super::Reader;
+    use common::digest;
+    use raw::Version;
+    use writer::Writer;
+
+    fn datafile(version: Version) -> Vec<u8> {
+        let mut writer = Writer::new_with_version(Vec::new(), version);
+        writer.add_item(1, 0, &[1, 2, 3]).unwrap();
+        writer.add_data(b"first").unwrap();
+        writer.add_data(&[7; 100]).unwrap();
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn verify_ok() {
+        for &version in &[Version::V3, Version::V4] {
+            let file = datafile(version);
+            let report = Reader::from_read_at(&file[..]).unwrap().verify().unwrap();
+            assert!(report.is_ok(), "{:?}", report);
+            assert_eq!(report.size as usize, file.len());
+            assert_eq!(report.crc, digest::crc32(&file));
+        }
+    }
+
+    #[test]
+    fn verify_crude_size() {
+        let file = datafile(Version::V4Crude);
+        let report = Reader::from_read_at(&file[..]).unwrap().verify().unwrap();
+        assert_eq!(report.inconsistencies, [Inconsistency::CrudeSize]);
+    }
+
+    #[test]
+    fn verify_trailing_data() {
+        let mut file = datafile(Version::V4);
+        let size = file.len();
+        file.extend_from_slice(b"trailing");
+        let report = Reader::from_read_at(&file[..]).unwrap().verify().unwrap();
+        assert_eq!(
+            report.inconsistencies,
+            [Inconsistency::TrailingData { len: 8 }]
+        );
+        // Size and CRC only cover the datafile itself.
+        assert_eq!(report.size as usize, size);
+        assert_eq!(report.crc, digest::crc32(&file[..size]));
+    }
+
+    #[test]
+    fn verify_bad_data() {
+        let mut file = datafile(Version::V4);
+        // The data blobs are at the end of the file, overwrite the last one.
+        let raw_len = Reader::from_read_at(&file[..])
+            .unwrap()
+            .read_data_raw(1)
+            .unwrap()
+            .len();
+        let len = file.len();
+        for b in &mut file[len - raw_len..] {
+            *b = 0xff;
+        }
+        let report = Reader::from_read_at(&file[..]).unwrap().verify().unwrap();
+        match report.inconsistencies[..] {
+            [Inconsistency::Data { index: 1, .. }] => {}
+            ref i => panic!("unexpected inconsistencies {:?}", i),
+        }
+        assert_eq!(report.crc, digest::crc32(&file));
+    }
+}
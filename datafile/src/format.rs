@@ -1,9 +1,8 @@
 use std::mem;
 
-use bitmagic::as_mut_i32_slice;
-use bitmagic::to_little_endian;
 use bitmagic::CallbackNewExt;
 use common::num::Cast;
+use endian;
 use raw;
 use raw::CallbackNew;
 use std::slice;
@@ -96,17 +95,11 @@ pub struct HeaderCheckResult {
 impl Header {
     pub fn read(mut cb: &mut dyn CallbackNew) -> Result<Header, raw::Error> {
         let mut result: Header = unsafe { mem::zeroed() };
-        let read = cb.read_le_i32s(slice::from_mut(&mut result))?;
+        let read = cb.read_raw_i32s(slice::from_mut(&mut result))?;
+        result.from_le();
         if read < mem::size_of_val(&result.hv) {
             return Err(raw::Error::Df(Error::TooShortHeaderVersion));
         }
-        {
-            let slice = as_mut_i32_slice(slice::from_mut(&mut result));
-            // Revert endian conversion for magic field.
-            unsafe {
-                to_little_endian(&mut slice[..1]);
-            }
-        }
         result.hv.check()?;
         if read < mem::size_of_val(&result) {
             return Err(raw::Error::Df(Error::TooShortHeader));
@@ -140,6 +133,25 @@ impl Header {
         }
         Err(Error::MalformedHeader)
     }
+    /// Converts the header read from a datafile to native endianness.
+    ///
+    /// The magic is a byte string and stays unchanged.
+    pub fn from_le(&mut self) {
+        endian::from_le(slice::from_mut(&mut self.hv.version));
+        endian::from_le(slice::from_mut(&mut self.hr));
+    }
+    /// Converts the header to the endianness of datafiles, see `from_le`.
+    pub fn to_le(&mut self) {
+        endian::to_le(slice::from_mut(&mut self.hv.version));
+        endian::to_le(slice::from_mut(&mut self.hr));
+    }
+    /// Fills in the `size` and `swaplen` fields from the rest of the header.
+    pub fn set_size_and_swaplen(&mut self, crude_version: bool) -> Result<(), Error> {
+        let total_size = self.calculate_total_size()?;
+        self.hr.size = self.calculate_size_field(total_size, crude_version);
+        self.hr.swaplen = self.calculate_swaplen_field(total_size, crude_version);
+        Ok(())
+    }
     fn calculate_size_field(&self, total_size: i32, crude_version: bool) -> i32 {
         // The first four i32 fields are not accounted for in the size field.
         let result = total_size - mem::size_of::<i32>().assert_i32() * 4;
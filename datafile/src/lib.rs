@@ -6,19 +6,33 @@ extern crate hexdump;
 extern crate itertools;
 extern crate zlib_minimal as zlib;
 
+pub use cache::CacheStats;
+pub use cache::CachedReader;
+pub use editor::EditError;
+pub use editor::Editor;
 pub use file::DataIter;
 pub use file::Error;
+pub use file::Inconsistency;
 pub use file::Reader;
+pub use file::VerifyReport;
 pub use format::ItemView;
 pub use format::OnlyI32;
 pub use raw::ItemTypeItems;
 pub use raw::ItemTypes;
 pub use raw::Items;
 pub use raw::Version;
+pub use read_at::ReadAt;
+pub use read_at::Section;
+pub use writer::WriteError;
+pub use writer::Writer;
 
 mod bitmagic;
 pub mod buffer;
+mod cache;
+mod editor;
+pub mod endian;
 mod file;
 pub mod format;
 pub mod raw;
+mod read_at;
 mod writer;
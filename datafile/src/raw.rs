@@ -93,6 +93,7 @@ pub struct Reader {
     uncomp_data_sizes: Option<Vec<i32>>,
     items_raw: Vec<i32>,
     version: Version,
+    size: u32,
 }
 
 impl Reader {
@@ -152,6 +153,7 @@ impl Reader {
             uncomp_data_sizes: uncomp_data_sizes,
             items_raw: items_raw,
             version: version,
+            size: header_check.expected_size,
         };
         result.check()?;
         Ok(result)
@@ -309,6 +311,27 @@ impl Reader {
     pub fn version(&self) -> Version {
         self.version
     }
+    /// Size of the datafile in bytes, as calculated from the header.
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+    /// Size of the data blob after decompression.
+    pub fn data_size(&self, index: usize) -> usize {
+        match self.uncomp_data_sizes {
+            Some(ref uds) => uds[index].assert_usize(),
+            None => self.data_size_file(index),
+        }
+    }
+    /// Reads the data blob as stored in the file, i.e. compressed for
+    /// version 4 datafiles.
+    pub fn read_data_raw(
+        &self,
+        mut cb: &mut dyn CallbackReadData,
+        index: usize,
+    ) -> Result<Vec<u8>, Error> {
+        cb.seek_read_exact_owned(self.data_offsets[index] as u32, self.data_size_file(index))
+            .map_err(|e| e.on_eof(format::Error::TooShort))
+    }
     pub fn read_data<'a>(
         &self,
         mut cb: &'a mut dyn CallbackReadData,
@@ -0,0 +1,189 @@
+use common::io::seek_overflow;
+use common::io::FileExt;
+use common::io::MappedFile;
+use common::num::Cast;
+use std::cmp;
+use std::fs::File;
+use std::io;
+
+/// Source of bytes that can be read at arbitrary offsets.
+///
+/// Implemented for files, memory-mapped files, byte slices and vectors.
+/// `Section` restricts another source to a byte range, e.g. a map embedded
+/// in a demo.
+pub trait ReadAt {
+    /// Reads into `buffer`, starting at `offset`.
+    ///
+    /// Returns the number of bytes read, which is only smaller than
+    /// `buffer.len()` at the end of the source.
+    fn read_at(&self, buffer: &mut [u8], offset: u64) -> io::Result<usize>;
+    /// Length of the source in bytes.
+    fn len(&self) -> io::Result<u64>;
+
+    /// Reads exactly `buffer.len()` bytes at `offset`.
+    ///
+    /// Fails with `UnexpectedEof` if the source ends before that.
+    fn read_exact_at(&self, buffer: &mut [u8], offset: u64) -> io::Result<()> {
+        if self.read_at(buffer, offset)? != buffer.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "failed to fill whole buffer",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ReadAt for File {
+    fn read_at(&self, buffer: &mut [u8], offset: u64) -> io::Result<usize> {
+        self.read_offset_retry(buffer, offset)
+    }
+    fn len(&self) -> io::Result<u64> {
+        Ok(self.metadata()?.len())
+    }
+}
+
+impl ReadAt for MappedFile {
+    fn read_at(&self, buffer: &mut [u8], offset: u64) -> io::Result<usize> {
+        self.read_offset_retry(buffer, offset)
+    }
+    fn len(&self) -> io::Result<u64> {
+        MappedFile::len(self)
+    }
+}
+
+impl ReadAt for [u8] {
+    fn read_at(&self, buffer: &mut [u8], offset: u64) -> io::Result<usize> {
+        let start = match offset.try_usize() {
+            Some(o) if o < self.len() => o,
+            _ => return Ok(0),
+        };
+        let read = cmp::min(buffer.len(), self.len() - start);
+        buffer[..read].copy_from_slice(&self[start..start + read]);
+        Ok(read)
+    }
+    fn len(&self) -> io::Result<u64> {
+        Ok(<[u8]>::len(self).u64())
+    }
+}
+
+impl ReadAt for Vec<u8> {
+    fn read_at(&self, buffer: &mut [u8], offset: u64) -> io::Result<usize> {
+        self[..].read_at(buffer, offset)
+    }
+    fn len(&self) -> io::Result<u64> {
+        Ok(Vec::len(self).u64())
+    }
+}
+
+impl<'a, T: ReadAt + ?Sized> ReadAt for &'a T {
+    fn read_at(&self, buffer: &mut [u8], offset: u64) -> io::Result<usize> {
+        (**self).read_at(buffer, offset)
+    }
+    fn len(&self) -> io::Result<u64> {
+        (**self).len()
+    }
+}
+
+/// The bytes from `start` to `start + len` of another source.
+#[derive(Clone, Debug)]
+pub struct Section<R> {
+    inner: R,
+    start: u64,
+    len: u64,
+}
+
+impl<R: ReadAt> Section<R> {
+    pub fn new(inner: R, start: u64, len: u64) -> Section<R> {
+        Section { inner, start, len }
+    }
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: ReadAt> ReadAt for Section<R> {
+    fn read_at(&self, buffer: &mut [u8], offset: u64) -> io::Result<usize> {
+        if offset >= self.len {
+            return Ok(0);
+        }
+        let len = match (self.len - offset).try_usize() {
+            Some(l) if l < buffer.len() => l,
+            _ => buffer.len(),
+        };
+        let offset = self.start.checked_add(offset).ok_or_else(seek_overflow)?;
+        self.inner.read_at(&mut buffer[..len], offset)
+    }
+    fn len(&self) -> io::Result<u64> {
+        Ok(self.len)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ReadAt;
+    use super::Section;
+    use file::Error;
+    use file::Reader;
+    use writer::Writer;
+
+    fn read_at<R: ReadAt>(source: R, len: usize, offset: u64) -> Vec<u8> {
+        let mut buffer = vec![0; len];
+        let read = source.read_at(&mut buffer, offset).unwrap();
+        buffer.truncate(read);
+        buffer
+    }
+
+    #[test]
+    fn slice() {
+        let data: &[u8] = b"0123456789";
+        assert_eq!(ReadAt::len(data).unwrap(), 10);
+        assert_eq!(read_at(data, 4, 0), b"0123");
+        assert_eq!(read_at(data, 4, 8), b"89");
+        assert_eq!(read_at(data, 4, 10), b"");
+        assert_eq!(read_at(data, 4, u64::max_value()), b"");
+        assert!(data.read_exact_at(&mut [0; 3], 7).is_ok());
+        assert!(data.read_exact_at(&mut [0; 3], 8).is_err());
+    }
+
+    #[test]
+    fn section() {
+        let data: &[u8] = b"0123456789";
+        let section = Section::new(data, 2, 5);
+        assert_eq!(ReadAt::len(&section).unwrap(), 5);
+        assert_eq!(read_at(&section, 10, 0), b"23456");
+        assert_eq!(read_at(&section, 2, 3), b"56");
+        assert_eq!(read_at(&section, 2, 5), b"");
+        // Sections may extend past the end of the inner source.
+        assert_eq!(read_at(Section::new(data, 8, 5), 10, 0), b"89");
+    }
+
+    #[test]
+    fn datafile_sources() {
+        let mut writer = Writer::new(Vec::new());
+        writer.add_item(1, 0, &[1, 2, 3]).unwrap();
+        writer.add_item(2, 5, &[]).unwrap();
+        writer.add_data(b"data").unwrap();
+        let file = writer.finish().unwrap();
+
+        fn check<R: ReadAt>(reader: Result<Reader<R>, Error>) {
+            let mut reader = reader.unwrap();
+            let items: Vec<_> = reader
+                .items()
+                .map(|i| (i.type_id, i.id, i.data.to_vec()))
+                .collect();
+            assert_eq!(items, [(1, 0, vec![1, 2, 3]), (2, 5, vec![])]);
+            assert_eq!(reader.read_data(0).unwrap(), b"data");
+            assert!(reader.verify().unwrap().is_ok());
+        }
+        check(Reader::from_read_at(&file[..]));
+        check(Reader::from_read_at(file.clone()));
+
+        // A datafile embedded in other data, like a map in a demo.
+        let mut embedded = b"header".to_vec();
+        embedded.extend_from_slice(&file);
+        embedded.extend_from_slice(b"footer");
+        let section = Section::new(&embedded[..], 6, file.len() as u64);
+        check(Reader::from_read_at(section));
+    }
+}
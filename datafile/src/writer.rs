@@ -1,52 +1,196 @@
-/*
-pub fn write_datafile<T:Datafile,W:Writer>(df: &T, writer: &mut W) -> Result<IoResult<()>,()> {
-    let compressed_data: Vec<Vec<u8>> = try!(result::collect(df.data_iter().map(|maybe_x| maybe_x.map(|x| {
-        zlib::compress_vec(x).unwrap()
-        (item_type.start, item_type.num)
-    }
-}
-
-/*
-pub fn write_datafile<T:Datafile,W:Writer>(df: &T, writer: &mut W) -> Result<IoResult<()>,()> {
-    let compressed_data: Vec<Vec<u8>> = result::collect(df.data_iter().map(|maybe_x| maybe_x.map(|x| {
-        zlib::compress_vec(x).unwrap()
-    })))?;
-
-    let size_items = df.items().fold(0, |s, i| {
-        s + i.data.len() * mem::size_of::<i32>() + mem::size_of::<DatafileItemHeader>()
-    });
-
-    let size_data = compressed_data.iter().fold(0, |s, d| s + d.len());
-
-    DatafileHeaderVersion {
-        magic: DATAFILE_MAGIC,
-        version: 4,
-    }.write(writer)?;
-
-    DatafileHeader {
-        _size: unimplemented!(),
-        _swaplen: unimplemented!(),
-        num_item_types: df.item_types().len(),
-        num_items: df.items().len(),
-        num_data: df.data_iter().len(),
-        size_items: size_items,
-        size_data: size_data,
-    }.write(writer)?;
-
-    for &type_id in df.item_types() {
-        let (start, num) = df.item_type_indexes_start_num(type_id);
-        DatafileItemType {
-            type_id: type_id.as_i32().unwrap(),
-            start: start.as_i32().unwrap(),
-            num: num.as_i32().unwrap(),
-        }.write(writer)?;
-    }
-
-    for DatafileItem { type_id, id, data } in df.items() {
-        DatafileItemHeader::new(type_id, id, data.len()).write(writer)?;
-    }
-    unimplemented!();
-    Ok(Ok(()))
-}
-*/
-*/
+use common::num::Cast;
+use std::collections::btree_map;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io;
+use std::mem;
+use std::path::Path;
+use zlib;
+
+use endian;
+use format;
+use format::OnlyI32;
+use raw::Version;
+
+#[derive(Debug)]
+pub enum WriteError {
+    Io(io::Error),
+    Compression(zlib::Error),
+    DuplicateItem { type_id: u16, id: u16 },
+    TooLarge,
+}
+
+impl From<io::Error> for WriteError {
+    fn from(err: io::Error) -> WriteError {
+        WriteError::Io(err)
+    }
+}
+
+impl From<zlib::Error> for WriteError {
+    fn from(err: zlib::Error) -> WriteError {
+        WriteError::Compression(err)
+    }
+}
+
+impl From<format::Error> for WriteError {
+    fn from(_: format::Error) -> WriteError {
+        WriteError::TooLarge
+    }
+}
+
+struct Data {
+    raw: Vec<u8>,
+    size: usize,
+}
+
+/// Writer for datafiles.
+///
+/// Items and data are collected in memory and laid out when calling
+/// `finish`. Items are sorted by type ID and then by ID, as the format
+/// requires the items of a type to be contiguous.
+pub struct Writer<W: io::Write = File> {
+    file: W,
+    version: Version,
+    items: BTreeMap<(u16, u16), Vec<i32>>,
+    data: Vec<Data>,
+}
+
+impl Writer<File> {
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Writer<File>> {
+        fn inner(path: &Path) -> io::Result<Writer<File>> {
+            Ok(Writer::new(File::create(path)?))
+        }
+        inner(path.as_ref())
+    }
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Creates a writer for version 4 datafiles.
+    pub fn new(file: W) -> Writer<W> {
+        Writer::new_with_version(file, Version::V4)
+    }
+    /// Creates a writer for the given datafile version.
+    ///
+    /// Version 3 stores the data uncompressed, version 4 compresses it with
+    /// zlib. `Version::V4Crude` reproduces the wrong `size` and `swaplen`
+    /// fields of some old writers.
+    pub fn new_with_version(file: W, version: Version) -> Writer<W> {
+        Writer {
+            file,
+            version,
+            items: BTreeMap::new(),
+            data: Vec::new(),
+        }
+    }
+    pub fn version(&self) -> Version {
+        self.version
+    }
+    /// Adds an item, there may only be one item per type ID and ID.
+    pub fn add_item(&mut self, type_id: u16, id: u16, data: &[i32]) -> Result<(), WriteError> {
+        match self.items.entry((type_id, id)) {
+            btree_map::Entry::Occupied(_) => Err(WriteError::DuplicateItem { type_id, id }),
+            btree_map::Entry::Vacant(v) => {
+                v.insert(data.to_vec());
+                Ok(())
+            }
+        }
+    }
+    /// Adds a data blob and returns its index, to be referenced from items.
+    pub fn add_data(&mut self, data: &[u8]) -> Result<usize, WriteError> {
+        let raw = match self.version {
+            Version::V3 => data.to_vec(),
+            Version::V4Crude | Version::V4 => zlib::compress_vec(data)?,
+        };
+        self.data.push(Data {
+            raw,
+            size: data.len(),
+        });
+        Ok(self.data.len() - 1)
+    }
+    /// Adds a data blob as stored in a datafile of the same version, i.e.
+    /// compressed for version 4. `size` is its size after decompression.
+    pub(crate) fn add_data_raw(&mut self, raw: Vec<u8>, size: usize) -> usize {
+        self.data.push(Data { raw, size });
+        self.data.len() - 1
+    }
+    /// Writes the datafile and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W, WriteError> {
+        fn i(v: usize) -> Result<i32, WriteError> {
+            v.try_i32().ok_or(WriteError::TooLarge)
+        }
+        fn write_i32s<W: io::Write, T: OnlyI32>(
+            file: &mut W,
+            values: &[T],
+        ) -> Result<(), WriteError> {
+            file.write_all(&endian::to_le_bytes(values))?;
+            Ok(())
+        }
+        let has_data_sizes = match self.version {
+            Version::V3 => false,
+            Version::V4Crude | Version::V4 => true,
+        };
+
+        let mut item_types: Vec<format::ItemType> = Vec::new();
+        let mut item_offsets = Vec::with_capacity(self.items.len());
+        let mut items_raw = Vec::new();
+        for (index, (&(type_id, id), data)) in self.items.iter().enumerate() {
+            if item_types.last().map(|t| t.type_id) != Some(type_id.i32()) {
+                item_types.push(format::ItemType {
+                    type_id: type_id.i32(),
+                    start: i(index)?,
+                    num: 0,
+                });
+            }
+            item_types.last_mut().unwrap().num += 1;
+            item_offsets.push(i(items_raw.len() * mem::size_of::<i32>())?);
+            let size = i(data.len() * mem::size_of::<i32>())?;
+            let header = format::ItemHeader::new(type_id, id, size);
+            items_raw.push(header.type_id_and_id);
+            items_raw.push(header.size);
+            items_raw.extend_from_slice(data);
+        }
+        let mut data_offsets = Vec::with_capacity(self.data.len());
+        let mut data_sizes = Vec::with_capacity(self.data.len());
+        let mut size_data = 0;
+        for d in &self.data {
+            data_offsets.push(i(size_data)?);
+            data_sizes.push(i(d.size)?);
+            size_data += d.raw.len();
+        }
+
+        let mut header = format::Header {
+            hv: format::HeaderVersion {
+                magic: format::MAGIC,
+                version: match self.version {
+                    Version::V3 => format::VERSION3,
+                    Version::V4Crude | Version::V4 => format::VERSION4,
+                },
+            },
+            hr: format::HeaderRest {
+                size: 0,
+                swaplen: 0,
+                num_item_types: i(item_types.len())?,
+                num_items: i(item_offsets.len())?,
+                num_data: i(data_offsets.len())?,
+                size_items: i(items_raw.len() * mem::size_of::<i32>())?,
+                size_data: i(size_data)?,
+            },
+        };
+        header.set_size_and_swaplen(self.version == Version::V4Crude)?;
+
+        header.to_le();
+        self.file.write_all(endian::as_bytes(&[header]))?;
+        write_i32s(&mut self.file, &item_types)?;
+        write_i32s(&mut self.file, &item_offsets)?;
+        write_i32s(&mut self.file, &data_offsets)?;
+        if has_data_sizes {
+            write_i32s(&mut self.file, &data_sizes)?;
+        }
+        write_i32s(&mut self.file, &items_raw)?;
+        for d in &self.data {
+            self.file.write_all(&d.raw)?;
+        }
+        self.file.flush()?;
+        Ok(self.file)
+    }
+}
@@ -0,0 +1,1190 @@
+//! Conversion of demos between header versions and protocols.
+//!
+//! Demos recorded with the 0.6 (or DDNet) protocol can be converted to the
+//! 0.7 protocol and back. Snapshot items and game messages that have a
+//! counterpart in the other protocol are translated, everything else is
+//! dropped and counted in the returned `Report`.
+
+use common::num::Cast;
+use gamenet_common::error::EncodeError;
+use gamenet_common::error::Error as GamenetError;
+use gamenet_common::msg::MessageId;
+use gamenet_common::snap_obj::Tick;
+use gamenet_common::snap_obj::TypeId;
+use gamenet_common::version;
+use gamenet_common::version::Protocol;
+use gamenet_ddnet::enums as enums6;
+use gamenet_ddnet::msg::Game as Game6;
+use gamenet_ddnet::snap_obj as snap6;
+use gamenet_teeworlds_0_7::enums as enums7;
+use gamenet_teeworlds_0_7::msg::Game as Game7;
+use gamenet_teeworlds_0_7::snap_obj as snap7;
+use packer::with_packer;
+use packer::IntUnpacker;
+use snapshot::snap::Builder;
+use snapshot::Delta;
+use snapshot::Snap;
+use snapshot::SnapReader;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::io;
+use std::mem;
+use thiserror::Error;
+use warn::wrap;
+use warn::Warn;
+
+use crate::ddnet::Warning;
+use crate::format::RawChunk;
+use crate::format::Version;
+use crate::format::MAX_SNAPSHOT_SIZE;
+use crate::reader::ReadError;
+use crate::reader::Reader;
+use crate::writer::DemoHeader;
+use crate::writer::WriteError;
+use crate::writer::Writer;
+
+#[derive(Error, Debug)]
+pub enum ConvertError {
+    #[error(transparent)]
+    Read(#[from] ReadError),
+    #[error(transparent)]
+    Write(#[from] WriteError),
+    #[error("{0:?}")]
+    Snap(snapshot::snap::Error),
+    #[error("Unknown network version of the source demo")]
+    UnknownNetVersion,
+    #[error("No conversion from {0:?} to {1:?}")]
+    Unsupported(Protocol, Protocol),
+    #[error("Converted message too large")]
+    MessageTooLarge,
+    #[error("Converted snapshot too large")]
+    SnapshotTooLarge,
+    #[error("Converted item violates the protocol: {0:?}")]
+    Invalid(GamenetError),
+}
+
+impl From<snapshot::snap::Error> for ConvertError {
+    fn from(err: snapshot::snap::Error) -> ConvertError {
+        ConvertError::Snap(err)
+    }
+}
+
+impl From<snapshot::snap::BuilderError> for ConvertError {
+    fn from(err: snapshot::snap::BuilderError) -> ConvertError {
+        ConvertError::Snap(err.into())
+    }
+}
+
+/// Content of the source demo that could not be converted.
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    /// Dropped game messages, by message ID, with the number of occurrences.
+    pub messages: BTreeMap<MessageId, u64>,
+    /// Dropped snapshot items, by type ID, with the number of snapshots they
+    /// appeared in. This includes DDNet extended items.
+    pub items: BTreeMap<u16, u64>,
+    /// Messages that could not be decoded.
+    pub invalid_messages: u64,
+    /// Snapshot items that could not be decoded.
+    pub invalid_items: u64,
+}
+
+impl Report {
+    /// Whether all of the content was converted.
+    pub fn is_lossless(&self) -> bool {
+        self.messages.is_empty()
+            && self.items.is_empty()
+            && self.invalid_messages == 0
+            && self.invalid_items == 0
+    }
+    fn message(&mut self, id: MessageId) {
+        *self.messages.entry(id).or_insert(0) += 1;
+    }
+    fn item(&mut self, type_id: u16) {
+        *self.items.entry(type_id).or_insert(0) += 1;
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Direction {
+    Copy,
+    To07,
+    To06,
+}
+
+/// Writes the contents of `reader` as a demo of the given header `version`
+/// and `protocol` to `file`.
+///
+/// Converting between header versions and between compatible protocols
+/// copies the chunks unchanged. Between the 0.6 and 0.7 protocols, snapshots
+/// are rebuilt item by item and game messages are re-encoded. 0.5 demos can
+/// only be converted to another header version.
+pub fn convert<F, W>(
+    reader: &mut Reader,
+    file: F,
+    version: Version,
+    protocol: Protocol,
+    warn: &mut W,
+) -> Result<Report, ConvertError>
+where
+    F: io::Write + io::Seek + 'static,
+    W: Warn<Warning>,
+{
+    let from = version::Version::from_net_version(reader.net_version())
+        .ok_or(ConvertError::UnknownNetVersion)?
+        .protocol();
+    let direction = match (from, protocol) {
+        (f, t) if f.is_compatible(t) => Direction::Copy,
+        (Protocol::Teeworlds06, Protocol::Teeworlds07)
+        | (Protocol::Ddnet, Protocol::Teeworlds07) => Direction::To07,
+        (Protocol::Teeworlds07, Protocol::Teeworlds06)
+        | (Protocol::Teeworlds07, Protocol::Ddnet) => Direction::To06,
+        (f, t) => return Err(ConvertError::Unsupported(f, t)),
+    };
+    let map_sha256 = if version == Version::V6Ddnet {
+        reader.map_sha256()
+    } else {
+        None
+    };
+    let header = DemoHeader {
+        version,
+        net_version: protocol.net_version().as_bytes(),
+        map_name: reader.map_name(),
+        map_sha256,
+        test_demo: version == Version::V6Ddnet && reader.is_test_demo(),
+        map_crc: reader.map_crc(),
+        kind: reader.kind(),
+        length: reader.length(),
+        timestamp: reader.timestamp(),
+    };
+    let mut writer = Writer::with_header(file, &header, reader.map_data())?;
+    let mut markers = reader.timeline_markers().to_vec().into_iter().peekable();
+
+    let mut report = Report::default();
+    let mut snap_reader = SnapReader::new();
+    let mut delta = Delta::new();
+    let mut snap = Snap::empty();
+    let mut old_snap = Snap::empty();
+    let mut prev_converted: Option<Snap> = None;
+    let mut cur_tick = 0;
+    let mut keyframe = false;
+    let mut ints = Vec::new();
+    let mut buf: Vec<u8> = Vec::with_capacity(MAX_SNAPSHOT_SIZE);
+    while let Some(chunk) = reader.read_chunk(wrap(warn))? {
+        match (direction, chunk) {
+            (_, RawChunk::Unknown(_)) => continue,
+            (Direction::Copy, chunk) => {
+                if let RawChunk::Tick { tick, .. } = chunk {
+                    cur_tick = tick.to_i32();
+                }
+                writer.write_chunk(chunk)?;
+                while markers.peek().map(|&t| t <= cur_tick).unwrap_or(false) {
+                    markers.next();
+                    writer.add_timeline_marker();
+                }
+                continue;
+            }
+            (_, RawChunk::Tick { tick, keyframe: k }) => {
+                cur_tick = tick.to_i32();
+                keyframe = k;
+                writer.write_tick(k, tick)?;
+                while markers.peek().map(|&t| t <= cur_tick).unwrap_or(false) {
+                    markers.next();
+                    writer.add_timeline_marker();
+                }
+                continue;
+            }
+            (_, RawChunk::Message(msg)) => {
+                buf.clear();
+                let mut unpacker = packer::Unpacker::new_from_demo(msg);
+                let converted = match direction {
+                    Direction::To07 => match Game6::decode(wrap(warn), &mut unpacker) {
+                        Ok(msg) => match msg_to_0_7(&msg) {
+                            Some(m) => Some(with_packer(&mut buf, |p| m.encode(p))),
+                            None => {
+                                report.message(msg.msg_id());
+                                None
+                            }
+                        },
+                        Err(err) => {
+                            warn.warn(Warning::Gamenet(err));
+                            report.invalid_messages += 1;
+                            None
+                        }
+                    },
+                    Direction::To06 => match Game7::decode(wrap(warn), &mut unpacker) {
+                        Ok(msg) => match msg_to_0_6(&msg) {
+                            Some(m) => Some(with_packer(&mut buf, |p| m.encode(p))),
+                            None => {
+                                report.message(msg.msg_id());
+                                None
+                            }
+                        },
+                        Err(err) => {
+                            warn.warn(Warning::Gamenet(err));
+                            report.invalid_messages += 1;
+                            None
+                        }
+                    },
+                    Direction::Copy => unreachable!(),
+                };
+                if let Some(encoded) = converted {
+                    let encoded = encoded.map_err(|err| match err {
+                        EncodeError::Capacity(_) => ConvertError::MessageTooLarge,
+                        EncodeError::Invalid(err) => ConvertError::Invalid(err),
+                    })?;
+                    writer.write_message(encoded)?;
+                }
+                continue;
+            }
+            (_, RawChunk::Snapshot(data)) => {
+                let mut unpacker = packer::Unpacker::new(data);
+                let swap = mem::replace(&mut snap, Snap::empty());
+                snap = snap_reader.read(wrap(warn), swap, &mut unpacker)?;
+            }
+            (_, RawChunk::SnapshotDelta(data)) => {
+                let mut unpacker = packer::Unpacker::new(data);
+                match direction {
+                    Direction::To07 => delta.read(wrap(warn), snap6::obj_size, &mut unpacker)?,
+                    _ => delta.read(wrap(warn), snap7::obj_size, &mut unpacker)?,
+                }
+                old_snap.read_with_delta(wrap(warn), &snap, &delta)?;
+                mem::swap(&mut old_snap, &mut snap);
+            }
+        }
+        let converted = match direction {
+            Direction::To07 => snap_to_0_7(warn, cur_tick, &snap, &mut report)?,
+            _ => snap_to_0_6(warn, cur_tick, &snap, &mut report)?,
+        };
+        buf.clear();
+        match prev_converted {
+            Some(ref prev) if !keyframe => {
+                delta.create(prev, &converted);
+                let encoded = match direction {
+                    Direction::To07 => with_packer(&mut buf, |p| delta.write(snap7::obj_size, p)),
+                    _ => with_packer(&mut buf, |p| delta.write(snap6::obj_size, p)),
+                };
+                let encoded = encoded.map_err(|_| ConvertError::SnapshotTooLarge)?;
+                writer.write_snapshot_delta(encoded)?;
+            }
+            _ => {
+                let encoded = with_packer(&mut buf, |p| converted.write(&mut ints, p));
+                let encoded = encoded.map_err(|_| ConvertError::SnapshotTooLarge)?;
+                writer.write_snapshot(encoded)?;
+            }
+        }
+        prev_converted = Some(converted);
+    }
+    writer.finalize()?;
+    Ok(report)
+}
+
+fn msg_to_0_7<'a>(msg: &Game6<'a>) -> Option<Game7<'a>> {
+    use gamenet_teeworlds_0_7::msg::game::*;
+    Some(match *msg {
+        Game6::SvMotd(m) => Game7::SvMotd(SvMotd { message: m.message }),
+        Game6::SvBroadcast(m) => Game7::SvBroadcast(SvBroadcast { message: m.message }),
+        Game6::SvChat(m) => Game7::SvChat(SvChat {
+            mode: match m.team {
+                0 => enums7::Chat::All,
+                1 => enums7::Chat::Team,
+                _ => return None,
+            },
+            client_id: m.client_id,
+            target_id: -1,
+            message: m.message,
+        }),
+        Game6::SvKillMsg(m) => Game7::SvKillMsg(SvKillMsg {
+            killer: m.killer,
+            victim: m.victim,
+            weapon: m.weapon,
+            mode_special: m.mode_special,
+        }),
+        Game6::SvEmoticon(m) => Game7::SvEmoticon(SvEmoticon {
+            client_id: m.client_id,
+            emoticon: enums7::Emoticon::from_i32(m.emoticon.to_i32()),
+        }),
+        Game6::SvVoteStatus(m) => Game7::SvVoteStatus(SvVoteStatus {
+            yes: m.yes,
+            no: m.no,
+            pass: m.pass,
+            total: m.total,
+        }),
+        _ => return None,
+    })
+}
+
+fn msg_to_0_6<'a>(msg: &Game7<'a>) -> Option<Game6<'a>> {
+    use gamenet_ddnet::msg::game::*;
+    Some(match *msg {
+        Game7::SvMotd(m) => Game6::SvMotd(SvMotd { message: m.message }),
+        Game7::SvBroadcast(m) => Game6::SvBroadcast(SvBroadcast { message: m.message }),
+        Game7::SvChat(m) => Game6::SvChat(SvChat {
+            team: match m.mode {
+                enums7::Chat::All => 0,
+                enums7::Chat::Team => 1,
+                _ => return None,
+            },
+            client_id: m.client_id,
+            message: m.message,
+        }),
+        Game7::SvKillMsg(m) => Game6::SvKillMsg(SvKillMsg {
+            killer: m.killer,
+            victim: m.victim,
+            weapon: m.weapon,
+            mode_special: m.mode_special,
+        }),
+        Game7::SvEmoticon(m) => Game6::SvEmoticon(SvEmoticon {
+            client_id: m.client_id,
+            emoticon: enums6::Emoticon::from_i32(m.emoticon.to_i32()),
+        }),
+        Game7::SvVoteStatus(m) => Game6::SvVoteStatus(SvVoteStatus {
+            yes: m.yes,
+            no: m.no,
+            pass: m.pass,
+            total: m.total,
+        }),
+        _ => return None,
+    })
+}
+
+/// Decodes the items of `snap`, reporting the unknown and invalid ones.
+fn decode_items<W, O, D>(warn: &mut W, snap: &Snap, report: &mut Report, decode: D) -> Vec<(u16, O)>
+where
+    W: Warn<Warning>,
+    D: Fn(&mut W, TypeId, &mut IntUnpacker) -> Result<O, GamenetError>,
+{
+    let mut result = Vec::with_capacity(snap.items().len());
+    for item in snap.items() {
+        let mut unpacker = IntUnpacker::new(item.data);
+        match decode(warn, TypeId::Ordinal(item.type_id), &mut unpacker) {
+            Ok(obj) => result.push((item.id, obj)),
+            Err(GamenetError::UnknownId) => report.item(item.type_id),
+            Err(_) => report.invalid_items += 1,
+        }
+    }
+    result
+}
+
+fn add_0_7<O: Into<snap7::SnapObj>>(
+    builder: &mut Builder,
+    id: u16,
+    obj: O,
+) -> Result<(), ConvertError> {
+    let obj = obj.into();
+    match obj.obj_type_id() {
+        TypeId::Ordinal(type_id) => {
+            let data = obj.encode().map_err(ConvertError::Invalid)?;
+            builder.add_item(type_id, id, data)?
+        }
+        TypeId::Uuid(_) => unreachable!(),
+    }
+    Ok(())
+}
+
+fn add_0_6<O: Into<snap6::SnapObj>>(
+    builder: &mut Builder,
+    id: u16,
+    obj: O,
+) -> Result<(), ConvertError> {
+    let obj = obj.into();
+    match obj.obj_type_id() {
+        TypeId::Ordinal(type_id) => {
+            let data = obj.encode().map_err(ConvertError::Invalid)?;
+            builder.add_item(type_id, id, data)?
+        }
+        TypeId::Uuid(_) => unreachable!(),
+    }
+    Ok(())
+}
+
+fn default_skin_parts_0_7() -> [[i32; 6]; 6] {
+    let standard = packer::string_to_ints6(b"standard");
+    let none = packer::string_to_ints6(b"");
+    [standard, none, none, standard, standard, standard]
+}
+
+/// `DeClientInfo::encode` can't be used because the struct contains `bool`s
+/// and is thus not laid out as a sequence of `i32`s.
+fn de_client_info_ints(info: &snap7::DeClientInfo) -> Vec<i32> {
+    let mut result = vec![info.local as i32, info.team];
+    result.extend_from_slice(&info.name);
+    result.extend_from_slice(&info.clan);
+    result.push(info.country);
+    for part in &info.skin_part_names {
+        result.extend_from_slice(part);
+    }
+    result.extend(info.use_custom_colors.iter().map(|&c| c as i32));
+    result.extend_from_slice(&info.skin_part_colors);
+    result
+}
+
+fn snap_to_0_7<W: Warn<Warning>>(
+    warn: &mut W,
+    tick: i32,
+    snap: &Snap,
+    report: &mut Report,
+) -> Result<Snap, ConvertError> {
+    use snap6::SnapObj as S;
+    let objs = decode_items(warn, snap, report, |w, t, p| {
+        snap6::SnapObj::decode_obj(wrap(w), t, p)
+    });
+    let mut player_flags = HashMap::new();
+    let mut client_infos = HashMap::new();
+    for &(id, ref obj) in &objs {
+        match *obj {
+            S::Character(c) => {
+                player_flags.insert(id, c.player_flags);
+            }
+            S::ClientInfo(c) => {
+                client_infos.insert(id, c);
+            }
+            _ => {}
+        }
+    }
+
+    let mut builder = Builder::new();
+    for (id, obj) in objs {
+        match obj {
+            S::Projectile(p) => add_0_7(
+                &mut builder,
+                id,
+                snap7::Projectile {
+                    x: p.x,
+                    y: p.y,
+                    vel_x: p.vel_x,
+                    vel_y: p.vel_y,
+                    type_: p.type_,
+                    start_tick: p.start_tick,
+                },
+            )?,
+            S::Laser(l) => add_0_7(
+                &mut builder,
+                id,
+                snap7::Laser {
+                    x: l.x,
+                    y: l.y,
+                    from_x: l.from_x,
+                    from_y: l.from_y,
+                    start_tick: l.start_tick,
+                },
+            )?,
+            S::Pickup(p) => {
+                let type_ = match (p.type_, p.subtype) {
+                    (enums6::POWERUP_HEALTH, _) => enums7::PICKUP_HEALTH,
+                    (enums6::POWERUP_ARMOR, _) => enums7::PICKUP_ARMOR,
+                    (enums6::POWERUP_NINJA, _) => enums7::PICKUP_NINJA,
+                    (enums6::POWERUP_WEAPON, enums6::WEAPON_HAMMER) => enums7::PICKUP_HAMMER,
+                    (enums6::POWERUP_WEAPON, enums6::WEAPON_PISTOL) => enums7::PICKUP_GUN,
+                    (enums6::POWERUP_WEAPON, enums6::WEAPON_SHOTGUN) => enums7::PICKUP_SHOTGUN,
+                    (enums6::POWERUP_WEAPON, enums6::WEAPON_GRENADE) => enums7::PICKUP_GRENADE,
+                    (enums6::POWERUP_WEAPON, enums6::WEAPON_RIFLE) => enums7::PICKUP_LASER,
+                    _ => {
+                        report.item(snap6::PICKUP);
+                        continue;
+                    }
+                };
+                add_0_7(
+                    &mut builder,
+                    id,
+                    snap7::Pickup {
+                        x: p.x,
+                        y: p.y,
+                        type_,
+                    },
+                )?;
+            }
+            S::Flag(f) => add_0_7(
+                &mut builder,
+                id,
+                snap7::Flag {
+                    x: f.x,
+                    y: f.y,
+                    team: f.team,
+                },
+            )?,
+            S::GameInfo(g) => {
+                let mut state_flags = 0;
+                for &(from, to) in &[
+                    (snap6::GAMESTATEFLAG_GAMEOVER, snap7::GAMESTATEFLAG_GAMEOVER),
+                    (
+                        snap6::GAMESTATEFLAG_SUDDENDEATH,
+                        snap7::GAMESTATEFLAG_SUDDENDEATH,
+                    ),
+                    (snap6::GAMESTATEFLAG_PAUSED, snap7::GAMESTATEFLAG_PAUSED),
+                ] {
+                    if g.game_state_flags & from != 0 {
+                        state_flags |= to;
+                    }
+                }
+                let mut end_tick = 0;
+                if g.warmup_timer > 0 {
+                    state_flags |= snap7::GAMESTATEFLAG_WARMUP;
+                    end_tick = tick + g.warmup_timer;
+                }
+                add_0_7(
+                    &mut builder,
+                    id,
+                    snap7::GameData {
+                        game_start_tick: g.round_start_tick,
+                        game_state_flags: state_flags,
+                        game_state_end_tick: Tick(end_tick),
+                    },
+                )?;
+                add_0_7(
+                    &mut builder,
+                    id,
+                    snap7::DeGameInfo {
+                        game_flags: g.game_flags & (snap7::GAMEFLAG_TEAMS | snap7::GAMEFLAG_FLAGS),
+                        score_limit: g.score_limit,
+                        time_limit: g.time_limit,
+                        match_num: g.round_num,
+                        match_current: g.round_current,
+                    },
+                )?;
+            }
+            S::GameData(g) => {
+                add_0_7(
+                    &mut builder,
+                    id,
+                    snap7::GameDataTeam {
+                        teamscore_red: g.teamscore_red,
+                        teamscore_blue: g.teamscore_blue,
+                    },
+                )?;
+                if g.flag_carrier_red != enums6::FLAG_MISSING
+                    || g.flag_carrier_blue != enums6::FLAG_MISSING
+                {
+                    add_0_7(
+                        &mut builder,
+                        id,
+                        snap7::GameDataFlag {
+                            flag_carrier_red: g.flag_carrier_red,
+                            flag_carrier_blue: g.flag_carrier_blue,
+                            flag_drop_tick_red: Tick(0),
+                            flag_drop_tick_blue: Tick(0),
+                        },
+                    )?;
+                }
+            }
+            S::Character(c) => {
+                let core = c.character_core;
+                add_0_7(
+                    &mut builder,
+                    id,
+                    snap7::Character {
+                        character_core: snap7::CharacterCore {
+                            tick: Tick(core.tick),
+                            x: core.x,
+                            y: core.y,
+                            vel_x: core.vel_x,
+                            vel_y: core.vel_y,
+                            angle: core.angle,
+                            direction: core.direction,
+                            jumped: core.jumped,
+                            hooked_player: core.hooked_player,
+                            hook_state: core.hook_state,
+                            hook_tick: core.hook_tick,
+                            hook_x: core.hook_x,
+                            hook_y: core.hook_y,
+                            hook_dx: core.hook_dx,
+                            hook_dy: core.hook_dy,
+                        },
+                        health: c.health,
+                        armor: c.armor,
+                        ammo_count: c.ammo_count,
+                        weapon: c.weapon,
+                        emote: c.emote,
+                        attack_tick: Tick(c.attack_tick),
+                        triggered_events: 0,
+                    },
+                )?;
+            }
+            S::PlayerInfo(p) => {
+                let flags = player_flags.get(&id).cloned().unwrap_or(0);
+                let mut player_flags = 0;
+                if flags & snap6::PLAYERFLAG_CHATTING != 0 {
+                    player_flags |= snap7::PLAYERFLAG_CHATTING;
+                }
+                if flags & snap6::PLAYERFLAG_SCOREBOARD != 0 {
+                    player_flags |= snap7::PLAYERFLAG_SCOREBOARD;
+                }
+                add_0_7(
+                    &mut builder,
+                    id,
+                    snap7::PlayerInfo {
+                        player_flags,
+                        score: p.score,
+                        latency: p.latency,
+                    },
+                )?;
+                if let Some(c) = client_infos.get(&id) {
+                    let mut use_custom_colors = [false; 6];
+                    let mut skin_part_colors = [0; 6];
+                    if c.use_custom_color != 0 {
+                        let body = enums7::SKINPART_BODY.assert_usize();
+                        let feet = enums7::SKINPART_FEET.assert_usize();
+                        use_custom_colors[body] = true;
+                        use_custom_colors[feet] = true;
+                        skin_part_colors[body] = c.color_body;
+                        skin_part_colors[feet] = c.color_feet;
+                    }
+                    let info = snap7::DeClientInfo {
+                        local: p.local != 0,
+                        team: p.team,
+                        name: c.name,
+                        clan: c.clan,
+                        country: c.country,
+                        skin_part_names: default_skin_parts_0_7(),
+                        use_custom_colors,
+                        skin_part_colors,
+                    };
+                    builder.add_item(snap7::DE_CLIENT_INFO, id, &de_client_info_ints(&info))?;
+                }
+            }
+            S::ClientInfo(_) => {}
+            S::SpectatorInfo(s) => add_0_7(
+                &mut builder,
+                id,
+                snap7::SpectatorInfo {
+                    spec_mode: if s.spectator_id == enums6::SPEC_FREEVIEW {
+                        enums7::SPEC_FREEVIEW
+                    } else {
+                        enums7::SPEC_PLAYER
+                    },
+                    spectator_id: s.spectator_id,
+                    x: s.x,
+                    y: s.y,
+                },
+            )?,
+            S::Explosion(e) => add_0_7(
+                &mut builder,
+                id,
+                snap7::Explosion {
+                    common: snap7::Common {
+                        x: e.common.x,
+                        y: e.common.y,
+                    },
+                },
+            )?,
+            S::Spawn(e) => add_0_7(
+                &mut builder,
+                id,
+                snap7::Spawn {
+                    common: snap7::Common {
+                        x: e.common.x,
+                        y: e.common.y,
+                    },
+                },
+            )?,
+            S::HammerHit(e) => add_0_7(
+                &mut builder,
+                id,
+                snap7::HammerHit {
+                    common: snap7::Common {
+                        x: e.common.x,
+                        y: e.common.y,
+                    },
+                },
+            )?,
+            S::Death(e) => add_0_7(
+                &mut builder,
+                id,
+                snap7::Death {
+                    common: snap7::Common {
+                        x: e.common.x,
+                        y: e.common.y,
+                    },
+                    client_id: e.client_id,
+                },
+            )?,
+            other => match other.obj_type_id() {
+                TypeId::Ordinal(type_id) => report.item(type_id),
+                TypeId::Uuid(_) => unreachable!(),
+            },
+        }
+    }
+    Ok(builder.finish())
+}
+
+fn snap_to_0_6<W: Warn<Warning>>(
+    warn: &mut W,
+    tick: i32,
+    snap: &Snap,
+    report: &mut Report,
+) -> Result<Snap, ConvertError> {
+    use snap7::SnapObj as S;
+    let objs = decode_items(warn, snap, report, |w, t, p| {
+        snap7::SnapObj::decode_obj(wrap(w), t, p)
+    });
+    let mut player_infos = HashMap::new();
+    let mut game_data = None;
+    let mut game_info = None;
+    let mut team_data = None;
+    let mut flag_data = None;
+    for &(id, ref obj) in &objs {
+        match *obj {
+            S::PlayerInfo(p) => {
+                player_infos.insert(id, p);
+            }
+            S::GameData(g) => game_data = Some((id, g)),
+            S::DeGameInfo(g) => game_info = Some((id, g)),
+            S::GameDataTeam(g) => team_data = Some((id, g)),
+            S::GameDataFlag(g) => flag_data = Some((id, g)),
+            _ => {}
+        }
+    }
+
+    let mut builder = Builder::new();
+    match (game_data, game_info) {
+        (Some((id, d)), Some((_, i))) => {
+            let mut state_flags = 0;
+            for &(from, to) in &[
+                (snap7::GAMESTATEFLAG_GAMEOVER, snap6::GAMESTATEFLAG_GAMEOVER),
+                (
+                    snap7::GAMESTATEFLAG_ROUNDOVER,
+                    snap6::GAMESTATEFLAG_GAMEOVER,
+                ),
+                (
+                    snap7::GAMESTATEFLAG_SUDDENDEATH,
+                    snap6::GAMESTATEFLAG_SUDDENDEATH,
+                ),
+                (snap7::GAMESTATEFLAG_PAUSED, snap6::GAMESTATEFLAG_PAUSED),
+            ] {
+                if d.game_state_flags & from != 0 {
+                    state_flags |= to;
+                }
+            }
+            let warmup_timer = if d.game_state_flags & snap7::GAMESTATEFLAG_WARMUP != 0 {
+                (d.game_state_end_tick.0 - tick).max(1)
+            } else {
+                0
+            };
+            add_0_6(
+                &mut builder,
+                id,
+                snap6::GameInfo {
+                    game_flags: i.game_flags & (snap6::GAMEFLAG_TEAMS | snap6::GAMEFLAG_FLAGS),
+                    game_state_flags: state_flags,
+                    round_start_tick: d.game_start_tick,
+                    warmup_timer,
+                    score_limit: i.score_limit,
+                    time_limit: i.time_limit,
+                    round_num: i.match_num,
+                    round_current: i.match_current,
+                },
+            )?;
+        }
+        (Some(_), None) => report.item(snap7::GAME_DATA),
+        (None, Some(_)) => report.item(snap7::DE_GAME_INFO),
+        (None, None) => {}
+    }
+    if team_data.is_some() || flag_data.is_some() {
+        let id = team_data
+            .map(|(id, _)| id)
+            .or(flag_data.map(|(id, _)| id))
+            .unwrap();
+        add_0_6(
+            &mut builder,
+            id,
+            snap6::GameData {
+                teamscore_red: team_data.map(|(_, t)| t.teamscore_red).unwrap_or(0),
+                teamscore_blue: team_data.map(|(_, t)| t.teamscore_blue).unwrap_or(0),
+                flag_carrier_red: flag_data
+                    .map(|(_, f)| f.flag_carrier_red)
+                    .unwrap_or(enums6::FLAG_MISSING),
+                flag_carrier_blue: flag_data
+                    .map(|(_, f)| f.flag_carrier_blue)
+                    .unwrap_or(enums6::FLAG_MISSING),
+            },
+        )?;
+    }
+    for (id, obj) in objs {
+        match obj {
+            S::Projectile(p) => add_0_6(
+                &mut builder,
+                id,
+                snap6::Projectile {
+                    x: p.x,
+                    y: p.y,
+                    vel_x: p.vel_x,
+                    vel_y: p.vel_y,
+                    type_: p.type_,
+                    start_tick: p.start_tick,
+                },
+            )?,
+            S::Laser(l) => add_0_6(
+                &mut builder,
+                id,
+                snap6::Laser {
+                    x: l.x,
+                    y: l.y,
+                    from_x: l.from_x,
+                    from_y: l.from_y,
+                    start_tick: l.start_tick,
+                },
+            )?,
+            S::Pickup(p) => {
+                let (type_, subtype) = match p.type_ {
+                    enums7::PICKUP_HEALTH => (enums6::POWERUP_HEALTH, 0),
+                    enums7::PICKUP_ARMOR => (enums6::POWERUP_ARMOR, 0),
+                    enums7::PICKUP_NINJA => (enums6::POWERUP_NINJA, enums6::WEAPON_NINJA),
+                    enums7::PICKUP_HAMMER => (enums6::POWERUP_WEAPON, enums6::WEAPON_HAMMER),
+                    enums7::PICKUP_GUN => (enums6::POWERUP_WEAPON, enums6::WEAPON_PISTOL),
+                    enums7::PICKUP_SHOTGUN => (enums6::POWERUP_WEAPON, enums6::WEAPON_SHOTGUN),
+                    enums7::PICKUP_GRENADE => (enums6::POWERUP_WEAPON, enums6::WEAPON_GRENADE),
+                    enums7::PICKUP_LASER => (enums6::POWERUP_WEAPON, enums6::WEAPON_RIFLE),
+                    _ => {
+                        report.item(snap7::PICKUP);
+                        continue;
+                    }
+                };
+                add_0_6(
+                    &mut builder,
+                    id,
+                    snap6::Pickup {
+                        x: p.x,
+                        y: p.y,
+                        type_,
+                        subtype,
+                    },
+                )?;
+            }
+            S::Flag(f) => add_0_6(
+                &mut builder,
+                id,
+                snap6::Flag {
+                    x: f.x,
+                    y: f.y,
+                    team: f.team,
+                },
+            )?,
+            S::GameData(_) | S::DeGameInfo(_) | S::GameDataTeam(_) | S::GameDataFlag(_) => {}
+            S::Character(c) => {
+                let core = c.character_core;
+                let flags = player_infos.get(&id).map(|p| p.player_flags).unwrap_or(0);
+                let mut player_flags = snap6::PLAYERFLAG_PLAYING;
+                if flags & snap7::PLAYERFLAG_CHATTING != 0 {
+                    player_flags |= snap6::PLAYERFLAG_CHATTING;
+                }
+                if flags & snap7::PLAYERFLAG_SCOREBOARD != 0 {
+                    player_flags |= snap6::PLAYERFLAG_SCOREBOARD;
+                }
+                add_0_6(
+                    &mut builder,
+                    id,
+                    snap6::Character {
+                        character_core: snap6::CharacterCore {
+                            tick: core.tick.0,
+                            x: core.x,
+                            y: core.y,
+                            vel_x: core.vel_x,
+                            vel_y: core.vel_y,
+                            angle: core.angle,
+                            direction: core.direction,
+                            jumped: core.jumped,
+                            hooked_player: core.hooked_player,
+                            hook_state: core.hook_state,
+                            hook_tick: core.hook_tick,
+                            hook_x: core.hook_x,
+                            hook_y: core.hook_y,
+                            hook_dx: core.hook_dx,
+                            hook_dy: core.hook_dy,
+                        },
+                        player_flags,
+                        health: c.health,
+                        armor: c.armor,
+                        ammo_count: c.ammo_count,
+                        weapon: c.weapon,
+                        emote: c.emote,
+                        attack_tick: c.attack_tick.0,
+                    },
+                )?;
+            }
+            S::PlayerInfo(_) => {}
+            S::DeClientInfo(c) => {
+                let player = match player_infos.get(&id) {
+                    Some(p) => p,
+                    None => {
+                        report.item(snap7::DE_CLIENT_INFO);
+                        continue;
+                    }
+                };
+                add_0_6(
+                    &mut builder,
+                    id,
+                    snap6::PlayerInfo {
+                        local: c.local as i32,
+                        client_id: id.i32(),
+                        team: c.team,
+                        score: player.score,
+                        latency: player.latency,
+                    },
+                )?;
+                let body = enums7::SKINPART_BODY.assert_usize();
+                let feet = enums7::SKINPART_FEET.assert_usize();
+                add_0_6(
+                    &mut builder,
+                    id,
+                    snap6::ClientInfo {
+                        name: c.name,
+                        clan: c.clan,
+                        country: c.country,
+                        skin: packer::string_to_ints6(b"default"),
+                        use_custom_color: c.use_custom_colors[body] as i32,
+                        color_body: c.skin_part_colors[body],
+                        color_feet: c.skin_part_colors[feet],
+                    },
+                )?;
+            }
+            S::SpectatorInfo(s) => add_0_6(
+                &mut builder,
+                id,
+                snap6::SpectatorInfo {
+                    spectator_id: if s.spec_mode == enums7::SPEC_PLAYER {
+                        s.spectator_id
+                    } else {
+                        enums6::SPEC_FREEVIEW
+                    },
+                    x: s.x,
+                    y: s.y,
+                },
+            )?,
+            S::Explosion(e) => add_0_6(
+                &mut builder,
+                id,
+                snap6::Explosion {
+                    common: snap6::Common {
+                        x: e.common.x,
+                        y: e.common.y,
+                    },
+                },
+            )?,
+            S::Spawn(e) => add_0_6(
+                &mut builder,
+                id,
+                snap6::Spawn {
+                    common: snap6::Common {
+                        x: e.common.x,
+                        y: e.common.y,
+                    },
+                },
+            )?,
+            S::HammerHit(e) => add_0_6(
+                &mut builder,
+                id,
+                snap6::HammerHit {
+                    common: snap6::Common {
+                        x: e.common.x,
+                        y: e.common.y,
+                    },
+                },
+            )?,
+            S::Death(e) => add_0_6(
+                &mut builder,
+                id,
+                snap6::Death {
+                    common: snap6::Common {
+                        x: e.common.x,
+                        y: e.common.y,
+                    },
+                    client_id: e.client_id,
+                },
+            )?,
+            other => match other.obj_type_id() {
+                TypeId::Ordinal(type_id) => report.item(type_id),
+                TypeId::Uuid(_) => unreachable!(),
+            },
+        }
+    }
+    Ok(builder.finish())
+}
+
+#[cfg(test)]
+mod test {
+    use gamenet_common::msg::MessageId;
+    use gamenet_common::snap_obj::Tick;
+    use gamenet_common::version::Protocol;
+    use gamenet_ddnet::enums as enums6;
+    use gamenet_ddnet::msg::game as game6;
+    use gamenet_ddnet::msg::Game as Game6;
+    use gamenet_ddnet::snap_obj as snap6;
+    use gamenet_teeworlds_0_7::enums as enums7;
+    use gamenet_teeworlds_0_7::msg::Game as Game7;
+    use gamenet_teeworlds_0_7::snap_obj as snap7;
+    use packer::with_packer;
+    use packer::IntUnpacker;
+    use snapshot::snap::Builder;
+    use snapshot::Delta;
+    use snapshot::Snap;
+    use snapshot::SnapReader;
+    use std::env;
+    use std::fs;
+    use std::fs::File;
+    use std::path::Path;
+    use std::process;
+    use warn::Ignore;
+
+    use super::convert;
+    use crate::format::DemoKind;
+    use crate::format::RawChunk;
+    use crate::format::Version;
+    use crate::reader::Reader;
+    use crate::writer::Writer;
+
+    const UNKNOWN_ITEM: u16 = 200;
+
+    fn snap(x: i32) -> Snap {
+        let projectile = snap6::Projectile {
+            x,
+            y: 64,
+            vel_x: 1,
+            vel_y: -1,
+            type_: enums6::WEAPON_GRENADE,
+            start_tick: Tick(0),
+        };
+        let mut builder = Builder::new();
+        builder
+            .add_item(snap6::PROJECTILE, 3, projectile.encode().unwrap())
+            .unwrap();
+        builder.add_item(UNKNOWN_ITEM, 0, &[x]).unwrap();
+        builder.finish()
+    }
+
+    fn write_0_6(path: &Path) {
+        let mut writer = Writer::new(
+            File::create(path).unwrap(),
+            Protocol::Teeworlds06.net_version().as_bytes(),
+            b"dm1",
+            None,
+            0x1234,
+            DemoKind::Client,
+            0,
+            b"2026-10-15_04-13-54",
+            &[],
+        )
+        .unwrap();
+        let mut buf = Vec::with_capacity(1024);
+        let chat = Game6::SvChat(game6::SvChat {
+            team: 0,
+            client_id: 1,
+            message: b"gg",
+        });
+        let pickup = Game6::SvWeaponPickup(game6::SvWeaponPickup {
+            weapon: enums6::Weapon::Shotgun,
+        });
+        let mut ints = Vec::new();
+        writer.write_tick(true, Tick(10)).unwrap();
+        for msg in &[chat, pickup] {
+            buf.clear();
+            let encoded = with_packer(&mut buf, |p| msg.encode(p)).unwrap();
+            writer.write_message(encoded).unwrap();
+        }
+        buf.clear();
+        let encoded = with_packer(&mut buf, |p| snap(32).write(&mut ints, p)).unwrap();
+        writer.write_snapshot(encoded).unwrap();
+        let mut delta = Delta::new();
+        delta.create(&snap(32), &snap(48));
+        buf.clear();
+        let encoded = with_packer(&mut buf, |p| delta.write(snap6::obj_size, p)).unwrap();
+        writer.write_tick(false, Tick(11)).unwrap();
+        writer.write_snapshot_delta(encoded).unwrap();
+        writer.finalize().unwrap();
+    }
+
+    /// Returns the ticks, the number of chat messages and the x coordinates
+    /// of the projectile in the snapshots of a converted 0.7 demo.
+    fn read_0_7(path: &Path) -> (Vec<(i32, bool)>, usize, Vec<i32>) {
+        let mut reader = Reader::new(File::open(path).unwrap(), &mut Ignore).unwrap();
+        assert_eq!(
+            reader.net_version(),
+            Protocol::Teeworlds07.net_version().as_bytes()
+        );
+        assert_eq!((reader.map_name(), reader.map_crc()), (&b"dm1"[..], 0x1234));
+        let mut snap_reader = SnapReader::new();
+        let mut delta = Delta::new();
+        let mut snaps: Vec<Snap> = Vec::new();
+        let mut ticks = Vec::new();
+        let mut messages = 0;
+        while let Some(chunk) = reader.read_chunk(&mut Ignore).unwrap() {
+            match chunk {
+                RawChunk::Tick { tick, keyframe } => ticks.push((tick.to_i32(), keyframe)),
+                RawChunk::Message(msg) => {
+                    let mut unpacker = packer::Unpacker::new_from_demo(msg);
+                    match Game7::decode(&mut Ignore, &mut unpacker).unwrap() {
+                        Game7::SvChat(m) => {
+                            assert_eq!(m.mode, enums7::Chat::All);
+                            assert_eq!((m.client_id, m.message), (1, &b"gg"[..]));
+                        }
+                        m => panic!("unexpected message {:?}", m),
+                    }
+                    messages += 1;
+                }
+                RawChunk::Snapshot(data) => {
+                    let mut unpacker = packer::Unpacker::new(data);
+                    let snap = snap_reader
+                        .read(&mut Ignore, Snap::empty(), &mut unpacker)
+                        .unwrap();
+                    snaps.push(snap);
+                }
+                RawChunk::SnapshotDelta(data) => {
+                    let mut unpacker = packer::Unpacker::new(data);
+                    delta
+                        .read(&mut Ignore, snap7::obj_size, &mut unpacker)
+                        .unwrap();
+                    let mut snap = Snap::empty();
+                    snap.read_with_delta(&mut Ignore, snaps.last().unwrap(), &delta)
+                        .unwrap();
+                    snaps.push(snap);
+                }
+                RawChunk::Unknown(_) => panic!("unknown chunk"),
+            }
+        }
+        let xs = snaps
+            .iter()
+            .map(|s| {
+                assert_eq!(s.items().count(), 1);
+                let data = s.item(snap7::PROJECTILE, 3).unwrap();
+                let p = snap7::Projectile::decode(&mut Ignore, &mut IntUnpacker::new(data));
+                let p = p.unwrap();
+                assert_eq!(p.type_, enums7::WEAPON_GRENADE);
+                p.x
+            })
+            .collect();
+        (ticks, messages, xs)
+    }
+
+    #[test]
+    fn convert_to_0_7() {
+        let dir = env::temp_dir().join(format!("libtw2-demo-convert-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (source, target) = (dir.join("0.6.demo"), dir.join("0.7.demo"));
+        write_0_6(&source);
+        let mut reader = Reader::new(File::open(&source).unwrap(), &mut Ignore).unwrap();
+        let report = convert(
+            &mut reader,
+            File::create(&target).unwrap(),
+            Version::V5,
+            Protocol::Teeworlds07,
+            &mut Ignore,
+        )
+        .unwrap();
+        drop(reader);
+        assert!(!report.is_lossless());
+        assert_eq!(
+            report.messages.into_iter().collect::<Vec<_>>(),
+            [(MessageId::Ordinal(game6::SV_WEAPON_PICKUP), 1)]
+        );
+        assert_eq!(
+            report.items.into_iter().collect::<Vec<_>>(),
+            [(UNKNOWN_ITEM, 2)]
+        );
+        assert_eq!((report.invalid_messages, report.invalid_items), (0, 0));
+
+        let (ticks, messages, xs) = read_0_7(&target);
+        assert_eq!(ticks, [(10, true), (11, false)]);
+        assert_eq!(messages, 1);
+        assert_eq!(xs, [32, 48]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,127 @@
+use common::time::Tick;
+use gamenet_ddnet::snap_obj;
+use packer::with_packer;
+use snapshot::Delta;
+use snapshot::Snap;
+use snapshot::SnapReader;
+use std::mem;
+use thiserror::Error;
+use warn::wrap;
+use warn::Warn;
+
+use crate::ddnet::Warning;
+use crate::format::RawChunk;
+use crate::format::MAX_SNAPSHOT_SIZE;
+use crate::reader::ReadError;
+use crate::reader::Reader;
+use crate::writer::WriteError;
+use crate::writer::Writer;
+
+#[derive(Error, Debug)]
+pub enum CutError {
+    #[error(transparent)]
+    Read(#[from] ReadError),
+    #[error(transparent)]
+    Write(#[from] WriteError),
+    #[error("{0:?}")]
+    Snap(snapshot::snap::Error),
+    #[error("No keyframe at or before the start tick")]
+    NoKeyframe,
+}
+
+/// Copies the ticks from `start_tick` to `end_tick` (inclusive) of `reader`
+/// into `writer`.
+///
+/// The snapshot of the first copied tick is written as a full snapshot, so
+/// that the result can be played on its own. Timeline markers within the
+/// range are kept, and the writer is finalized afterwards.
+pub fn cut<W>(
+    reader: &mut Reader,
+    writer: &mut Writer,
+    start_tick: Tick,
+    end_tick: Tick,
+    warn: &mut W,
+) -> Result<(), CutError>
+where
+    W: Warn<Warning>,
+{
+    assert!(!start_tick.is_after(end_tick));
+    if reader.seek_to_tick(start_tick, wrap(warn))?.is_none() {
+        return Err(CutError::NoKeyframe);
+    }
+    let markers: Vec<Tick> = reader
+        .timeline_markers()
+        .iter()
+        .map(|&t| Tick(t))
+        .filter(|&t| !start_tick.is_after(t) && !t.is_after(end_tick))
+        .collect();
+    let mut markers = markers.into_iter().peekable();
+
+    let mut snap_reader = SnapReader::new();
+    let mut delta = Delta::new();
+    let mut snap = Snap::empty();
+    let mut old_snap = Snap::empty();
+    let mut ints = Vec::new();
+    let mut encoded = Vec::with_capacity(MAX_SNAPSHOT_SIZE);
+    let mut started = false;
+    let mut rebase = false;
+    while let Some(chunk) = reader.read_chunk(wrap(warn))? {
+        match chunk {
+            RawChunk::Tick { tick, keyframe } => {
+                if tick.is_after(end_tick) {
+                    break;
+                }
+                if start_tick.is_after(tick) {
+                    continue;
+                }
+                if !started {
+                    started = true;
+                    rebase = true;
+                }
+                writer.write_tick(keyframe || rebase, tick)?;
+                while markers.peek().map(|&t| !t.is_after(tick)).unwrap_or(false) {
+                    markers.next();
+                    writer.add_timeline_marker();
+                }
+            }
+            RawChunk::Snapshot(data) => {
+                let mut unpacker = packer::Unpacker::new(data);
+                let swap = mem::replace(&mut snap, Snap::empty());
+                snap = snap_reader
+                    .read(wrap(warn), swap, &mut unpacker)
+                    .map_err(CutError::Snap)?;
+                if started {
+                    writer.write_snapshot(data)?;
+                    rebase = false;
+                }
+            }
+            RawChunk::SnapshotDelta(data) => {
+                let mut unpacker = packer::Unpacker::new(data);
+                delta
+                    .read(wrap(warn), snap_obj::obj_size, &mut unpacker)
+                    .map_err(CutError::Snap)?;
+                old_snap
+                    .read_with_delta(wrap(warn), &snap, &delta)
+                    .map_err(CutError::Snap)?;
+                mem::swap(&mut old_snap, &mut snap);
+                if rebase {
+                    encoded.clear();
+                    let full = with_packer(&mut encoded, |p| snap.write(&mut ints, p))
+                        .expect("snapshot too large");
+                    writer.write_snapshot(full)?;
+                    rebase = false;
+                } else if started {
+                    writer.write_snapshot_delta(data)?;
+                }
+            }
+            RawChunk::Message(msg) => {
+                if started {
+                    writer.write_message(msg)?;
+                }
+            }
+            RawChunk::Unknown(_) => {}
+        }
+    }
+    writer.finalize()?;
+    Ok(())
+}
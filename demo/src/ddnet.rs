@@ -1,8 +1,10 @@
+use common::time;
 use gamenet_common::snap_obj::TypeId;
 use gamenet_ddnet::snap_obj;
 use std::collections::HashMap;
 use std::io;
 use std::mem;
+use std::ops::Range;
 use std::slice;
 use thiserror::Error;
 use uuid::Uuid;
@@ -70,7 +72,7 @@ impl From<packer::ExcessData> for Warning {
 pub enum Chunk<'a> {
     Message(gamenet_ddnet::msg::Game<'a>),
     Snapshot(slice::Iter<'a, (snap_obj::SnapObj, u16)>),
-    Tick(i32),
+    Tick(time::Tick),
     Invalid,
 }
 
@@ -97,7 +99,7 @@ impl DemoReader {
     ) -> Result<Option<Chunk>, ReadError> {
         match self.raw.read_chunk(wrap(warn))? {
             None => return Ok(None),
-            Some(RawChunk::Unknown) => Ok(Some(Chunk::Invalid)),
+            Some(RawChunk::Unknown(_)) => Ok(Some(Chunk::Invalid)),
             Some(RawChunk::Tick { tick, .. }) => Ok(Some(Chunk::Tick(tick))),
             Some(RawChunk::Message(msg)) => {
                 let mut unpacker = packer::Unpacker::new_from_demo(msg);
@@ -141,6 +143,267 @@ impl DemoReader {
     }
 }
 
+/// The contents of a single tick, see `TickReader`.
+pub struct Tick<'a> {
+    pub tick: time::Tick,
+    pub keyframe: bool,
+    /// The snapshot of this tick, or of the last tick that had one.
+    pub snap: &'a snapshot::Snap,
+    pub messages: Vec<gamenet_ddnet::msg::Game<'a>>,
+}
+
+/// Reads a demo tick by tick, applying the snapshot deltas.
+pub struct TickReader {
+    raw: reader::Reader,
+    delta: snapshot::Delta,
+    snap: snapshot::Snap,
+    old_snap: snapshot::Snap,
+    snap_reader: snapshot::SnapReader,
+    next: Option<(time::Tick, bool)>,
+    message_data: Vec<u8>,
+    messages: Vec<Range<usize>>,
+}
+
+impl TickReader {
+    pub fn new<R, W>(data: R, warn: &mut W) -> Result<Self, ReadError>
+    where
+        R: io::Read + io::Seek + 'static,
+        W: Warn<Warning>,
+    {
+        let reader = reader::Reader::new(data, wrap(warn))?;
+        Ok(TickReader {
+            raw: reader,
+            delta: snapshot::Delta::new(),
+            snap: snapshot::Snap::empty(),
+            old_snap: snapshot::Snap::empty(),
+            snap_reader: snapshot::SnapReader::new(),
+            next: None,
+            message_data: Vec::new(),
+            messages: Vec::new(),
+        })
+    }
+
+    /// Reads all chunks up to the next tick marker.
+    ///
+    /// Chunks before the first tick marker are skipped. Messages that fail
+    /// to decode are reported as warnings.
+    pub fn next_tick<W: Warn<Warning>>(
+        &mut self,
+        warn: &mut W,
+    ) -> Result<Option<Tick<'_>>, ReadError> {
+        self.message_data.clear();
+        self.messages.clear();
+        let (tick, keyframe) = match self.next.take() {
+            Some(next) => next,
+            None => loop {
+                match self.raw.read_chunk(wrap(warn))? {
+                    None => return Ok(None),
+                    Some(RawChunk::Tick { tick, keyframe }) => break (tick, keyframe),
+                    Some(_) => {}
+                }
+            },
+        };
+        loop {
+            match self.raw.read_chunk(wrap(warn))? {
+                None => break,
+                Some(RawChunk::Tick { tick, keyframe }) => {
+                    self.next = Some((tick, keyframe));
+                    break;
+                }
+                Some(RawChunk::Unknown(_)) => {}
+                Some(RawChunk::Message(msg)) => {
+                    let start = self.message_data.len();
+                    self.message_data.extend_from_slice(msg);
+                    self.messages.push(start..self.message_data.len());
+                }
+                Some(RawChunk::Snapshot(snap)) => {
+                    let mut unpacker = packer::Unpacker::new(snap);
+                    let swap = mem::replace(&mut self.snap, snapshot::Snap::empty());
+                    self.snap = self
+                        .snap_reader
+                        .read(wrap(warn), swap, &mut unpacker)
+                        .map_err(ReadError::Snap)?;
+                }
+                Some(RawChunk::SnapshotDelta(dt)) => {
+                    let mut unpacker = packer::Unpacker::new(dt);
+                    self.delta
+                        .read(wrap(warn), snap_obj::obj_size, &mut unpacker)
+                        .map_err(ReadError::Snap)?;
+                    self.old_snap
+                        .read_with_delta(wrap(warn), &self.snap, &self.delta)
+                        .map_err(ReadError::Snap)?;
+                    mem::swap(&mut self.old_snap, &mut self.snap);
+                }
+            }
+        }
+        let mut messages = Vec::with_capacity(self.messages.len());
+        for range in &self.messages {
+            let data = &self.message_data[range.clone()];
+            let mut unpacker = packer::Unpacker::new_from_demo(data);
+            match gamenet_ddnet::msg::Game::decode(wrap(warn), &mut unpacker) {
+                Ok(msg) => messages.push(msg),
+                Err(err) => warn.warn(Warning::Gamenet(err)),
+            }
+        }
+        Ok(Some(Tick {
+            tick,
+            keyframe,
+            snap: &self.snap,
+            messages,
+        }))
+    }
+
+    /// Continues reading at the last keyframe at or before `tick`, see
+    /// `Reader::seek_to_tick`.
+    pub fn seek_to_tick<W: Warn<Warning>>(
+        &mut self,
+        tick: time::Tick,
+        warn: &mut W,
+    ) -> Result<Option<time::Tick>, ReadError> {
+        let result = self.raw.seek_to_tick(tick, wrap(warn))?;
+        if result.is_some() {
+            self.next = None;
+        }
+        Ok(result)
+    }
+
+    pub fn inner(&self) -> &reader::Reader {
+        &self.raw
+    }
+}
+
+/// An event of a demo, see `EventReader`.
+pub enum Event<'a> {
+    TickStart {
+        tick: time::Tick,
+        keyframe: bool,
+    },
+    /// The snapshot of the current tick, with deltas already applied.
+    Snapshot(&'a snapshot::Snap),
+    Message(gamenet_ddnet::msg::Game<'a>),
+    TickEnd(time::Tick),
+    /// A message that couldn't be decoded or an unknown chunk.
+    Invalid,
+}
+
+/// Reads a demo as a stream of events in playback order.
+///
+/// Each tick is enclosed in `TickStart` and `TickEnd` and contains at most
+/// one snapshot, which comes before the tick's messages. Other chunk orders
+/// are rejected with `ReadError::ChunkOrder`.
+pub struct EventReader {
+    raw: reader::Reader,
+    delta: snapshot::Delta,
+    snap: snapshot::Snap,
+    old_snap: snapshot::Snap,
+    snap_reader: snapshot::SnapReader,
+    current: Option<time::Tick>,
+    next: Option<(time::Tick, bool)>,
+    seen_snapshot: bool,
+    seen_message: bool,
+}
+
+impl EventReader {
+    pub fn new<R, W>(data: R, warn: &mut W) -> Result<Self, ReadError>
+    where
+        R: io::Read + io::Seek + 'static,
+        W: Warn<Warning>,
+    {
+        let reader = reader::Reader::new(data, wrap(warn))?;
+        Ok(EventReader {
+            raw: reader,
+            delta: snapshot::Delta::new(),
+            snap: snapshot::Snap::empty(),
+            old_snap: snapshot::Snap::empty(),
+            snap_reader: snapshot::SnapReader::new(),
+            current: None,
+            next: None,
+            seen_snapshot: false,
+            seen_message: false,
+        })
+    }
+
+    fn start_tick(&mut self, tick: time::Tick, keyframe: bool) -> Event<'static> {
+        self.current = Some(tick);
+        self.seen_snapshot = false;
+        self.seen_message = false;
+        Event::TickStart { tick, keyframe }
+    }
+
+    pub fn next_event<W: Warn<Warning>>(
+        &mut self,
+        warn: &mut W,
+    ) -> Result<Option<Event<'_>>, ReadError> {
+        if let Some((tick, keyframe)) = self.next.take() {
+            return Ok(Some(self.start_tick(tick, keyframe)));
+        }
+        let chunk = match self.raw.read_chunk(wrap(warn))? {
+            Some(c) => c,
+            None => return Ok(self.current.take().map(Event::TickEnd)),
+        };
+        if !matches!(chunk, RawChunk::Tick { .. }) && self.current.is_none() {
+            return Err(ReadError::ChunkOrder);
+        }
+        match chunk {
+            RawChunk::Tick { tick, keyframe } => {
+                if let Some(current) = self.current.take() {
+                    self.next = Some((tick, keyframe));
+                    return Ok(Some(Event::TickEnd(current)));
+                }
+                // Not using `start_tick`, `self.raw` is still borrowed.
+                self.current = Some(tick);
+                self.seen_snapshot = false;
+                self.seen_message = false;
+                Ok(Some(Event::TickStart { tick, keyframe }))
+            }
+            RawChunk::Unknown(_) => Ok(Some(Event::Invalid)),
+            RawChunk::Snapshot(snap) => {
+                if self.seen_snapshot || self.seen_message {
+                    return Err(ReadError::ChunkOrder);
+                }
+                self.seen_snapshot = true;
+                let mut unpacker = packer::Unpacker::new(snap);
+                let swap = mem::replace(&mut self.snap, snapshot::Snap::empty());
+                self.snap = self
+                    .snap_reader
+                    .read(wrap(warn), swap, &mut unpacker)
+                    .map_err(ReadError::Snap)?;
+                Ok(Some(Event::Snapshot(&self.snap)))
+            }
+            RawChunk::SnapshotDelta(dt) => {
+                if self.seen_snapshot || self.seen_message {
+                    return Err(ReadError::ChunkOrder);
+                }
+                self.seen_snapshot = true;
+                let mut unpacker = packer::Unpacker::new(dt);
+                self.delta
+                    .read(wrap(warn), snap_obj::obj_size, &mut unpacker)
+                    .map_err(ReadError::Snap)?;
+                self.old_snap
+                    .read_with_delta(wrap(warn), &self.snap, &self.delta)
+                    .map_err(ReadError::Snap)?;
+                mem::swap(&mut self.old_snap, &mut self.snap);
+                Ok(Some(Event::Snapshot(&self.snap)))
+            }
+            RawChunk::Message(msg) => {
+                self.seen_message = true;
+                let mut unpacker = packer::Unpacker::new_from_demo(msg);
+                match gamenet_ddnet::msg::Game::decode(wrap(warn), &mut unpacker) {
+                    Ok(msg) => Ok(Some(Event::Message(msg))),
+                    Err(err) => {
+                        warn.warn(Warning::Gamenet(err));
+                        Ok(Some(Event::Invalid))
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn inner(&self) -> &reader::Reader {
+        &self.raw
+    }
+}
+
 #[derive(Default)]
 struct Snapshot {
     uuid_index: HashMap<u16, Uuid>,
@@ -188,3 +451,194 @@ impl Snapshot {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use common::time::Tick;
+    use gamenet_ddnet::msg::game;
+    use gamenet_ddnet::msg::Game;
+    use gamenet_ddnet::snap_obj;
+    use packer::with_packer;
+    use snapshot::snap::Builder;
+    use snapshot::Delta;
+    use snapshot::Snap;
+    use std::env;
+    use std::fs;
+    use std::fs::File;
+    use std::path::Path;
+    use std::path::PathBuf;
+    use std::process;
+
+    use super::TickReader;
+    use super::Warning;
+    use crate::format::DemoKind;
+    use crate::writer::Writer;
+
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("libtw2-demo-{}-{}.demo", name, process::id()))
+    }
+
+    /// A snapshot with a projectile at `x`.
+    fn snap(x: i32) -> Snap {
+        let projectile = snap_obj::Projectile {
+            x,
+            y: 64,
+            vel_x: 1,
+            vel_y: -1,
+            type_: 0,
+            start_tick: Default::default(),
+        };
+        let mut builder = Builder::new();
+        builder
+            .add_item(snap_obj::PROJECTILE, 3, projectile.encode().unwrap())
+            .unwrap();
+        builder.finish()
+    }
+
+    /// Writes the ticks 10 to 12 and 20 to 22, starting with keyframes at 10
+    /// and 20. The projectile is at the tick's x coordinate, and the ticks
+    /// ending in 1 have a chat message plus one that fails to decode.
+    fn write(path: &Path) {
+        let mut writer = Writer::new(
+            File::create(path).unwrap(),
+            b"0.6 626fce9a778df4d4",
+            b"dm1",
+            None,
+            0xf2159e6e,
+            DemoKind::Client,
+            0,
+            b"2026-10-15_04-13-54",
+            &[],
+        )
+        .unwrap();
+        let mut buf = Vec::with_capacity(1024);
+        let mut ints = Vec::new();
+        let mut prev = Snap::empty();
+        for &tick in &[10, 11, 12, 20, 21, 22] {
+            let keyframe = tick % 10 == 0;
+            let current = snap(tick);
+            writer.write_tick(keyframe, Tick(tick)).unwrap();
+            buf.clear();
+            if keyframe {
+                let encoded = with_packer(&mut buf, |p| current.write(&mut ints, p)).unwrap();
+                writer.write_snapshot(encoded).unwrap();
+            } else {
+                let mut delta = Delta::new();
+                delta.create(&prev, &current);
+                let encoded =
+                    with_packer(&mut buf, |p| delta.write(snap_obj::obj_size, p)).unwrap();
+                writer.write_snapshot_delta(encoded).unwrap();
+            }
+            if tick % 10 == 1 {
+                let message = tick.to_string();
+                let chat = Game::SvChat(game::SvChat {
+                    team: 0,
+                    client_id: 1,
+                    message: message.as_bytes(),
+                });
+                buf.clear();
+                let encoded = with_packer(&mut buf, |p| chat.encode(p)).unwrap();
+                writer.write_message(encoded).unwrap();
+                // Game message ID 40, which doesn't exist.
+                writer.write_message(b"\x90\x01").unwrap();
+                writer.write_unknown(b"\x01\x02\x03").unwrap();
+            }
+            prev = current;
+        }
+        writer.finalize().unwrap();
+    }
+
+    /// Returns the tick, whether it's a keyframe, the x coordinate of the
+    /// projectile and the chat messages of the next tick.
+    fn next_tick(
+        reader: &mut TickReader,
+        warnings: &mut Vec<Warning>,
+    ) -> Option<(i32, bool, i32, Vec<Vec<u8>>)> {
+        let tick = reader.next_tick(warnings).unwrap()?;
+        let x = tick.snap.item(snap_obj::PROJECTILE, 3).unwrap()[0];
+        let messages = tick
+            .messages
+            .iter()
+            .map(|m| match *m {
+                Game::SvChat(c) => c.message.to_vec(),
+                ref m => panic!("unexpected message {:?}", m),
+            })
+            .collect();
+        Some((tick.tick.to_i32(), tick.keyframe, x, messages))
+    }
+
+    fn num_gamenet_warnings(warnings: &[Warning]) -> usize {
+        warnings
+            .iter()
+            .filter(|w| matches!(**w, Warning::Gamenet(_)))
+            .count()
+    }
+
+    #[test]
+    fn next_tick_applies_deltas() {
+        let path = temp_path("ddnet-ticks");
+        write(&path);
+        let mut warnings = Vec::new();
+        let mut reader = TickReader::new(File::open(&path).unwrap(), &mut warnings).unwrap();
+        let mut ticks = Vec::new();
+        while let Some(tick) = next_tick(&mut reader, &mut warnings) {
+            ticks.push(tick);
+        }
+        assert_eq!(
+            ticks,
+            [
+                (10, true, 10, vec![]),
+                (11, false, 11, vec![b"11".to_vec()]),
+                (12, false, 12, vec![]),
+                (20, true, 20, vec![]),
+                (21, false, 21, vec![b"21".to_vec()]),
+                (22, false, 22, vec![]),
+            ]
+        );
+        assert_eq!(num_gamenet_warnings(&warnings), 2);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn seek_to_tick() {
+        let path = temp_path("ddnet-seek");
+        write(&path);
+        let mut warnings = Vec::new();
+        let mut reader = TickReader::new(File::open(&path).unwrap(), &mut warnings).unwrap();
+        assert_eq!(
+            next_tick(&mut reader, &mut warnings),
+            Some((10, true, 10, vec![]))
+        );
+        // No keyframe before tick 10, reading continues where it was.
+        assert_eq!(reader.seek_to_tick(Tick(5), &mut warnings).unwrap(), None);
+        assert_eq!(
+            next_tick(&mut reader, &mut warnings),
+            Some((11, false, 11, vec![b"11".to_vec()]))
+        );
+
+        assert_eq!(
+            reader.seek_to_tick(Tick(21), &mut warnings).unwrap(),
+            Some(Tick(20))
+        );
+        assert_eq!(
+            next_tick(&mut reader, &mut warnings),
+            Some((20, true, 20, vec![]))
+        );
+        assert_eq!(
+            next_tick(&mut reader, &mut warnings),
+            Some((21, false, 21, vec![b"21".to_vec()]))
+        );
+
+        // Seeking backwards, the deltas apply to the keyframe's snapshot.
+        assert_eq!(
+            reader.seek_to_tick(Tick(12), &mut warnings).unwrap(),
+            Some(Tick(10))
+        );
+        let ticks: Vec<_> = (0..3)
+            .map(|_| next_tick(&mut reader, &mut warnings).unwrap())
+            .map(|(tick, keyframe, x, _)| (tick, keyframe, x))
+            .collect();
+        assert_eq!(ticks, [(10, true, 10), (11, false, 11), (12, false, 12)]);
+        fs::remove_file(&path).unwrap();
+    }
+}
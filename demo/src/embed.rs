@@ -0,0 +1,174 @@
+use common::digest;
+use common::time::Tick;
+use std::io;
+use thiserror::Error;
+use warn::Warn;
+
+use crate::format::Version;
+use crate::format::Warning;
+use crate::reader::check_map;
+use crate::reader::MapError;
+use crate::reader::ReadError;
+use crate::reader::Reader;
+use crate::writer::DemoHeader;
+use crate::writer::WriteError;
+use crate::writer::Writer;
+
+#[derive(Error, Debug)]
+pub enum EmbedMapError {
+    #[error(transparent)]
+    Read(#[from] ReadError),
+    #[error(transparent)]
+    Write(#[from] WriteError),
+    #[error(transparent)]
+    Map(#[from] MapError),
+}
+
+/// Copies the demo of `reader` to `file`, embedding `map`.
+///
+/// The map must match the CRC of the header and, if present, its SHA256.
+/// DDNet demos recorded without the SHA256 get it added. All chunks,
+/// including the ones of unknown type, and the timeline markers are copied
+/// unchanged.
+pub fn embed_map<F, W>(
+    reader: &mut Reader,
+    file: F,
+    map: &[u8],
+    warn: &mut W,
+) -> Result<(), EmbedMapError>
+where
+    F: io::Write + io::Seek + 'static,
+    W: Warn<Warning>,
+{
+    check_map(map, reader.map_crc(), reader.map_sha256())?;
+    let map_sha256 = if reader.version() == Version::V6Ddnet {
+        Some(digest::sha256(map))
+    } else {
+        None
+    };
+    let header = DemoHeader {
+        version: reader.version(),
+        net_version: reader.net_version(),
+        map_name: reader.map_name(),
+        map_sha256,
+        test_demo: reader.is_test_demo(),
+        map_crc: reader.map_crc(),
+        kind: reader.kind(),
+        length: reader.length(),
+        timestamp: reader.timestamp(),
+    };
+    let mut writer = Writer::with_header(file, &header, map)?;
+    for &marker in reader.timeline_markers() {
+        writer.add_timeline_marker_at(Tick(marker));
+    }
+    while let Some(chunk) = reader.read_chunk(warn)? {
+        writer.write_chunk(chunk)?;
+    }
+    writer.finalize()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use common::digest;
+    use common::time::Tick;
+    use std::env;
+    use std::fs;
+    use std::fs::File;
+    use std::path::Path;
+    use std::path::PathBuf;
+    use std::process;
+
+    use super::embed_map;
+    use crate::format::DemoKind;
+    use crate::format::RawChunk;
+    use crate::format::Version;
+    use crate::format::Warning;
+    use crate::reader::Reader;
+    use crate::writer::DemoHeader;
+    use crate::writer::Writer;
+
+    #[derive(Debug, PartialEq)]
+    enum Chunk {
+        Tick(i32, bool),
+        Snapshot(Vec<u8>),
+        Message(Vec<u8>),
+        Unknown(Vec<u8>),
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("libtw2-demo-{}-{}.demo", name, process::id()))
+    }
+
+    fn read(path: &Path) -> (Vec<u8>, Vec<i32>, Vec<Chunk>) {
+        let mut warnings = Vec::new();
+        let mut reader = Reader::new(File::open(path).unwrap(), &mut warnings).unwrap();
+        let mut chunks = Vec::new();
+        while let Some(chunk) = reader.read_chunk(&mut warnings).unwrap() {
+            chunks.push(match chunk {
+                RawChunk::Tick { tick, keyframe } => Chunk::Tick(tick.to_i32(), keyframe),
+                RawChunk::Snapshot(s) => Chunk::Snapshot(s.to_vec()),
+                RawChunk::SnapshotDelta(_) => panic!("unexpected chunk"),
+                RawChunk::Message(m) => Chunk::Message(m.to_vec()),
+                RawChunk::Unknown(u) => Chunk::Unknown(u.to_vec()),
+            });
+        }
+        assert_eq!(warnings, [Warning::UnknownChunkType]);
+        let markers = reader.timeline_markers().to_vec();
+        (reader.map_data().to_vec(), markers, chunks)
+    }
+
+    #[test]
+    fn roundtrip() {
+        let source = temp_path("embed-source");
+        let embedded = temp_path("embed-result");
+        let header = DemoHeader {
+            version: Version::V5,
+            net_version: b"0.6 626fce9a778df4d4",
+            map_name: b"dm1",
+            map_sha256: None,
+            test_demo: false,
+            map_crc: digest::crc32(b"map"),
+            kind: DemoKind::Client,
+            length: 0,
+            timestamp: b"2026-10-15_04-13-54",
+        };
+        let file = File::create(&source).unwrap();
+        let mut writer = Writer::with_header(file, &header, b"").unwrap();
+        writer.write_tick(true, Tick(100)).unwrap();
+        writer.write_snapshot(&[1; 40]).unwrap();
+        writer.write_unknown(b"\x01\x02\x03").unwrap();
+        // Marker between two ticks.
+        assert!(writer.add_timeline_marker_at(Tick(103)));
+        writer.write_tick(false, Tick(105)).unwrap();
+        writer.write_message(b"12345678").unwrap();
+        assert!(writer.add_timeline_marker());
+        writer.finalize().unwrap();
+        drop(writer);
+
+        let mut warnings = Vec::new();
+        let mut reader = Reader::new(File::open(&source).unwrap(), &mut warnings).unwrap();
+        let file = File::create(&embedded).unwrap();
+        embed_map(&mut reader, file, b"map", &mut warnings).unwrap();
+        drop(reader);
+        assert_eq!(warnings, [Warning::UnknownChunkType]);
+
+        let (map, markers, chunks) = read(&embedded);
+        assert_eq!(map, b"map");
+        assert_eq!(markers, [103, 105]);
+        assert_eq!(
+            chunks,
+            [
+                Chunk::Tick(100, true),
+                Chunk::Snapshot(vec![1; 40]),
+                Chunk::Unknown(b"\x01\x02\x03".to_vec()),
+                Chunk::Tick(105, false),
+                Chunk::Message(b"12345678".to_vec()),
+            ]
+        );
+        let (_, source_markers, source_chunks) = read(&source);
+        assert_eq!((source_markers, source_chunks), (markers, chunks));
+        fs::remove_file(&source).unwrap();
+        fs::remove_file(&embedded).unwrap();
+    }
+}
@@ -0,0 +1,174 @@
+//! Structured records extracted from the ticks of a demo.
+//!
+//! Feed each tick of a `ddnet::TickReader` to an `Extractor`. Client IDs
+//! are resolved to names using the `ClientInfo` snapshot items, the last
+//! known name is kept after a client leaves.
+
+use common::time;
+use gamenet_ddnet::msg::Game;
+use gamenet_ddnet::snap_obj;
+use packer::IntUnpacker;
+use std::collections::HashMap;
+
+use crate::ddnet::Tick;
+
+#[derive(Clone, Debug)]
+pub struct ChatLine {
+    pub tick: time::Tick,
+    pub team: i32,
+    /// `-1` for server messages.
+    pub client_id: i32,
+    pub author: Option<Vec<u8>>,
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Kill {
+    pub tick: time::Tick,
+    pub killer: i32,
+    pub killer_name: Option<Vec<u8>>,
+    pub victim: i32,
+    pub victim_name: Option<Vec<u8>>,
+    pub weapon: i32,
+    pub mode_special: i32,
+}
+
+#[derive(Clone, Debug)]
+pub struct VoteResult {
+    /// The tick the vote ended.
+    pub tick: time::Tick,
+    pub description: Vec<u8>,
+    pub reason: Vec<u8>,
+    pub yes: i32,
+    pub no: i32,
+    pub pass: i32,
+    pub total: i32,
+    /// Taken from the server's "Vote passed"/"Vote failed" chat message, if
+    /// any.
+    pub passed: Option<bool>,
+}
+
+/// A race finish, as sent by DDNet servers.
+#[derive(Clone, Debug)]
+pub struct Finish {
+    pub tick: time::Tick,
+    pub client_id: i32,
+    pub name: Option<Vec<u8>>,
+    /// Time in milliseconds.
+    pub time: i32,
+    pub record_personal: bool,
+    pub record_server: bool,
+}
+
+#[derive(Clone, Debug)]
+pub enum Record {
+    Chat(ChatLine),
+    Kill(Kill),
+    Vote(VoteResult),
+    Finish(Finish),
+}
+
+#[derive(Default)]
+pub struct Extractor {
+    names: HashMap<i32, Vec<u8>>,
+    vote: Option<VoteResult>,
+}
+
+impl Extractor {
+    pub fn new() -> Extractor {
+        Default::default()
+    }
+    /// The last known name of a client.
+    pub fn name(&self, client_id: i32) -> Option<&[u8]> {
+        self.names.get(&client_id).map(|n| &n[..])
+    }
+    /// Returns the records of a tick, in the order of its messages.
+    pub fn process(&mut self, tick: &Tick) -> Vec<Record> {
+        for item in tick.snap.items() {
+            if item.type_id != snap_obj::CLIENT_INFO {
+                continue;
+            }
+            let mut unpacker = IntUnpacker::new(item.data);
+            if let Ok(info) = snap_obj::ClientInfo::decode(&mut warn::Ignore, &mut unpacker) {
+                self.names
+                    .insert(item.id.into(), ints_to_string(&info.name));
+            }
+        }
+        let mut result = Vec::new();
+        for msg in &tick.messages {
+            match *msg {
+                Game::SvChat(ref chat) => {
+                    if chat.client_id == -1 {
+                        if let Some(ref mut vote) = self.vote {
+                            if chat.message.starts_with(b"Vote passed") {
+                                vote.passed = Some(true);
+                            } else if chat.message.starts_with(b"Vote failed") {
+                                vote.passed = Some(false);
+                            }
+                        }
+                    }
+                    result.push(Record::Chat(ChatLine {
+                        tick: tick.tick,
+                        team: chat.team,
+                        client_id: chat.client_id,
+                        author: self.name_owned(chat.client_id),
+                        message: chat.message.to_owned(),
+                    }));
+                }
+                Game::SvKillMsg(ref kill) => result.push(Record::Kill(Kill {
+                    tick: tick.tick,
+                    killer: kill.killer,
+                    killer_name: self.name_owned(kill.killer),
+                    victim: kill.victim,
+                    victim_name: self.name_owned(kill.victim),
+                    weapon: kill.weapon,
+                    mode_special: kill.mode_special,
+                })),
+                Game::SvVoteSet(ref set) => {
+                    if set.timeout != 0 {
+                        self.vote = Some(VoteResult {
+                            tick: tick.tick,
+                            description: set.description.to_owned(),
+                            reason: set.reason.to_owned(),
+                            yes: 0,
+                            no: 0,
+                            pass: 0,
+                            total: 0,
+                            passed: None,
+                        });
+                    } else if let Some(mut vote) = self.vote.take() {
+                        vote.tick = tick.tick;
+                        result.push(Record::Vote(vote));
+                    }
+                }
+                Game::SvVoteStatus(ref status) => {
+                    if let Some(ref mut vote) = self.vote {
+                        vote.yes = status.yes;
+                        vote.no = status.no;
+                        vote.pass = status.pass;
+                        vote.total = status.total;
+                    }
+                }
+                Game::SvRaceFinish(ref finish) => result.push(Record::Finish(Finish {
+                    tick: tick.tick,
+                    client_id: finish.client_id,
+                    name: self.name_owned(finish.client_id),
+                    time: finish.time,
+                    record_personal: finish.record_personal,
+                    record_server: finish.record_server,
+                })),
+                _ => {}
+            }
+        }
+        result
+    }
+    fn name_owned(&self, client_id: i32) -> Option<Vec<u8>> {
+        self.name(client_id).map(|n| n.to_owned())
+    }
+}
+
+fn ints_to_string(ints: &[i32]) -> Vec<u8> {
+    let mut bytes = vec![0; ints.len() * 4];
+    packer::ints_to_bytes(&mut bytes, ints);
+    packer::bytes_to_string(&mut warn::Ignore, &bytes).to_owned()
+}
@@ -3,6 +3,7 @@ use binrw::BinRead;
 use binrw::BinWrite;
 use common::digest::Sha256;
 use common::num::Cast;
+use common::time::Tick;
 use std::convert::TryFrom;
 use std::io;
 use warn;
@@ -21,6 +22,7 @@ pub enum Warning {
     OverlongChunkSizeEncoding,
     StartingDeltaTick,
     TickOverflow,
+    TruncatedChunk,
     UnknownChunkType,
     WeirdMapName,
     WeirdNetVersion,
@@ -57,13 +59,14 @@ impl Version {
 pub enum RawChunk<'a> {
     /// Tick(keyframe, tick)
     Tick {
-        tick: i32,
+        tick: Tick,
         keyframe: bool,
     },
     Snapshot(&'a ArrayVec<[u8; MAX_SNAPSHOT_SIZE]>),
     SnapshotDelta(&'a ArrayVec<[u8; MAX_SNAPSHOT_SIZE]>),
     Message(&'a [u8]),
-    Unknown,
+    /// Chunk of an unknown type, with its data as stored in the demo.
+    Unknown(&'a [u8]),
 }
 
 #[derive(BinRead, BinWrite, Debug)]
@@ -73,8 +76,11 @@ pub(crate) struct HeaderStart {
     pub header: Header,
     #[br(if(version >= Version::V4))]
     pub timeline_markers: TimelineMarkers,
-    #[br(if(version == Version::V6Ddnet))]
+    /// DDNet demos don't necessarily contain the map's SHA256.
+    #[br(if(version == Version::V6Ddnet), try)]
     pub map_sha256: Option<MapSha256>,
+    #[br(if(version == Version::V6Ddnet), try)]
+    pub test_demo: Option<TestDemoMarker>,
     #[br(count = header.map_size)]
     pub map: Vec<u8>,
 }
@@ -167,7 +173,7 @@ impl TimelineMarkers {
             warn.warn(Warning::WeirdTimelineMarkerPadding);
         }
         if self.markers().windows(2).any(|m| m[0] >= m[1]) {
-            warn.warn(Warning::NonAbsoluteTickmarkerTick)
+            warn.warn(Warning::NonIncreasingTimelineMarkers)
         }
     }
 }
@@ -176,9 +182,41 @@ const SHA_256_EXTENSION: [u8; 16] = [
     0x6b, 0xe6, 0xda, 0x4a, 0xce, 0xbd, 0x38, 0x0c, 0x9b, 0x5b, 0x12, 0x89, 0xc8, 0x42, 0xd7, 0x80,
 ];
 
+/// Derived from the name `demo-test@ddnet.tw`.
+const TEST_DEMO_EXTENSION: [u8; 16] = [
+    0x22, 0x2f, 0x0a, 0x17, 0x1d, 0x5c, 0x30, 0x8a, 0xb1, 0x72, 0x65, 0x81, 0x59, 0x4b, 0x5e, 0x13,
+];
+
+/// DDNet extensions of the demo header, identified by a UUID preceding
+/// their data.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Extension {
+    /// SHA256 of the map, follows the timeline markers in version 6.
+    MapSha256,
+    /// Marks a demo recorded for testing, follows the SHA256 of the map if
+    /// present. No data follows the UUID.
+    TestDemo,
+}
+
+impl Extension {
+    pub fn uuid(self) -> [u8; 16] {
+        match self {
+            Extension::MapSha256 => SHA_256_EXTENSION,
+            Extension::TestDemo => TEST_DEMO_EXTENSION,
+        }
+    }
+    pub fn from_uuid(uuid: &[u8; 16]) -> Option<Extension> {
+        match *uuid {
+            SHA_256_EXTENSION => Some(Extension::MapSha256),
+            TEST_DEMO_EXTENSION => Some(Extension::TestDemo),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Default, BinRead, BinWrite)]
 pub(crate) struct MapSha256 {
-    #[br(assert(_uuid == SHA_256_EXTENSION))]
+    #[br(assert(Extension::from_uuid(&_uuid) == Some(Extension::MapSha256)))]
     _uuid: [u8; 16],
     pub sha_256: [u8; 32],
 }
@@ -186,12 +224,26 @@ pub(crate) struct MapSha256 {
 impl MapSha256 {
     pub(crate) fn new(sha: Sha256) -> Self {
         Self {
-            _uuid: SHA_256_EXTENSION,
+            _uuid: Extension::MapSha256.uuid(),
             sha_256: sha.0,
         }
     }
 }
 
+#[derive(Debug, Default, BinRead, BinWrite)]
+pub(crate) struct TestDemoMarker {
+    #[br(assert(Extension::from_uuid(&_uuid) == Some(Extension::TestDemo)))]
+    _uuid: [u8; 16],
+}
+
+impl TestDemoMarker {
+    pub(crate) fn new() -> Self {
+        Self {
+            _uuid: Extension::TestDemo.uuid(),
+        }
+    }
+}
+
 const CHUNKTYPEFLAG_TICKMARKER: u8 = 0b1000_0000;
 
 const CHUNKTICKFLAG_KEYFRAME: u8 = 0b0100_0000;
@@ -314,15 +366,19 @@ impl ChunkHeader {
     where
         W: io::Write + io::Seek,
     {
-        assert!(version >= Version::V5);
         match *self {
             ChunkHeader::Tick {
                 marker: TickMarker::Delta(dt),
                 keyframe,
             } => {
-                assert!(dt <= version.max_tick_delta());
+                assert!(dt != 0 && dt <= version.max_tick_delta());
                 assert!(!keyframe);
-                let flags: u8 = CHUNKTYPEFLAG_TICKMARKER | CHUNKTICKFLAG_INLINETICK | dt;
+                let inline_flag = if version >= Version::V5 {
+                    CHUNKTICKFLAG_INLINETICK
+                } else {
+                    0
+                };
+                let flags: u8 = CHUNKTYPEFLAG_TICKMARKER | inline_flag | dt;
                 flags.write(file)?;
             }
             ChunkHeader::Tick {
@@ -0,0 +1,597 @@
+//! DDNet ghost files, recordings of a single player's race.
+//!
+//! A ghost file starts with a header naming the player and the map,
+//! followed by chunks of up to 50 items of the same type. Items are stored
+//! as the difference to the previous item of the same type, variable-length
+//! integer encoded and Huffman compressed.
+
+use arrayvec::ArrayVec;
+use binrw::BinRead;
+use binrw::BinWrite;
+use common::digest::Sha256;
+use common::num::Cast;
+use huffman::instances::TEEWORLDS as HUFFMAN;
+use packer::with_packer;
+use std::io;
+use std::io::SeekFrom;
+use thiserror::Error;
+use warn::wrap;
+use warn::Warn;
+
+use crate::format::CappedString;
+
+const MAX_ITEMS_PER_CHUNK: usize = 50;
+/// The chunk size is stored in 16 bits.
+const MAX_CHUNK_SIZE: usize = 65536;
+
+pub const TYPE_SKIN: u8 = 0;
+pub const TYPE_CHARACTER_NO_TICK: u8 = 1;
+pub const TYPE_CHARACTER: u8 = 2;
+pub const TYPE_START_TICK: u8 = 3;
+
+#[derive(BinRead, BinWrite, Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+#[brw(repr(u8), magic = b"TWGHOST\0")]
+pub enum Version {
+    V4 = 4,
+    V5 = 5,
+    /// Identifies the map by SHA256 instead of CRC.
+    V6 = 6,
+}
+
+#[derive(BinRead, BinWrite, Debug)]
+#[brw(big)]
+struct Header {
+    version: Version,
+    owner: CappedString<16>,
+    map_name: CappedString<64>,
+    /// Zero since version 6.
+    map_crc: u32,
+    num_ticks: i32,
+    time: i32,
+    #[br(if(version >= Version::V6))]
+    map_sha256: Option<[u8; 32]>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Warning {
+    Int(packer::Warning),
+    ExcessChunkData,
+}
+
+impl From<packer::Warning> for Warning {
+    fn from(w: packer::Warning) -> Warning {
+        Warning::Int(w)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ReadError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Binrw(#[from] binrw::Error),
+    #[error(transparent)]
+    Huffman(#[from] huffman::DecompressionError),
+    #[error("Unknown item type {0}")]
+    UnknownItemType(u8),
+    #[error("Chunk contains fewer items than announced")]
+    ChunkTooShort,
+}
+
+#[derive(Error, Debug)]
+#[error(transparent)]
+pub struct WriteError(#[from] binrw::Error);
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Skin {
+    pub skin: [i32; 6],
+    pub use_custom_color: i32,
+    pub color_body: i32,
+    pub color_feet: i32,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Character {
+    pub x: i32,
+    pub y: i32,
+    pub vel_x: i32,
+    pub vel_y: i32,
+    pub angle: i32,
+    pub direction: i32,
+    pub weapon: i32,
+    pub hook_state: i32,
+    pub hook_x: i32,
+    pub hook_y: i32,
+    pub attack_tick: i32,
+    /// Not recorded by old clients.
+    pub tick: Option<i32>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Item {
+    Skin(Skin),
+    Character(Character),
+    /// The tick the race started, the character ticks are relative to it.
+    StartTick(i32),
+}
+
+fn item_size(type_: u8) -> Option<usize> {
+    Some(match type_ {
+        TYPE_SKIN => 9,
+        TYPE_CHARACTER_NO_TICK => 11,
+        TYPE_CHARACTER => 12,
+        TYPE_START_TICK => 1,
+        _ => return None,
+    })
+}
+
+impl Item {
+    fn type_(&self) -> u8 {
+        match *self {
+            Item::Skin(_) => TYPE_SKIN,
+            Item::Character(Character { tick: None, .. }) => TYPE_CHARACTER_NO_TICK,
+            Item::Character(Character { tick: Some(_), .. }) => TYPE_CHARACTER,
+            Item::StartTick(_) => TYPE_START_TICK,
+        }
+    }
+    fn encode(&self, result: &mut Vec<i32>) {
+        match *self {
+            Item::Skin(ref s) => {
+                result.extend_from_slice(&s.skin);
+                result.extend_from_slice(&[s.use_custom_color, s.color_body, s.color_feet]);
+            }
+            Item::Character(ref c) => {
+                result.extend_from_slice(&[
+                    c.x,
+                    c.y,
+                    c.vel_x,
+                    c.vel_y,
+                    c.angle,
+                    c.direction,
+                    c.weapon,
+                    c.hook_state,
+                    c.hook_x,
+                    c.hook_y,
+                    c.attack_tick,
+                ]);
+                result.extend(c.tick);
+            }
+            Item::StartTick(t) => result.push(t),
+        }
+    }
+    fn decode(type_: u8, d: &[i32]) -> Item {
+        match type_ {
+            TYPE_SKIN => Item::Skin(Skin {
+                skin: [d[0], d[1], d[2], d[3], d[4], d[5]],
+                use_custom_color: d[6],
+                color_body: d[7],
+                color_feet: d[8],
+            }),
+            TYPE_CHARACTER_NO_TICK | TYPE_CHARACTER => Item::Character(Character {
+                x: d[0],
+                y: d[1],
+                vel_x: d[2],
+                vel_y: d[3],
+                angle: d[4],
+                direction: d[5],
+                weapon: d[6],
+                hook_state: d[7],
+                hook_x: d[8],
+                hook_y: d[9],
+                attack_tick: d[10],
+                tick: d.get(11).cloned(),
+            }),
+            TYPE_START_TICK => Item::StartTick(d[0]),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// The previous item, items are stored as the difference to it if they
+/// have the same type.
+#[derive(Default)]
+struct LastItem {
+    type_: Option<u8>,
+    data: Vec<i32>,
+}
+
+trait SeekableRead: io::Read + io::Seek {}
+impl<T: io::Read + io::Seek> SeekableRead for T {}
+
+pub struct Reader {
+    data: Box<dyn SeekableRead>,
+    header: Header,
+    chunk_type: u8,
+    chunk_items: usize,
+    ints: Vec<i32>,
+    pos: usize,
+    last: LastItem,
+    raw: [u8; MAX_CHUNK_SIZE],
+    huffman: ArrayVec<[u8; MAX_CHUNK_SIZE]>,
+}
+
+impl Reader {
+    pub fn new<R>(mut data: R) -> Result<Reader, ReadError>
+    where
+        R: io::Read + io::Seek + 'static,
+    {
+        let header = Header::read(&mut data)?;
+        Ok(Reader {
+            data: Box::new(data),
+            header,
+            chunk_type: 0,
+            chunk_items: 0,
+            ints: Vec::new(),
+            pos: 0,
+            last: LastItem::default(),
+            raw: [0; MAX_CHUNK_SIZE],
+            huffman: ArrayVec::new(),
+        })
+    }
+    pub fn version(&self) -> Version {
+        self.header.version
+    }
+    /// Name of the player.
+    pub fn owner(&self) -> &[u8] {
+        self.header.owner.raw()
+    }
+    pub fn map_name(&self) -> &[u8] {
+        self.header.map_name.raw()
+    }
+    /// Only present before version 6.
+    pub fn map_crc(&self) -> Option<u32> {
+        if self.header.version < Version::V6 {
+            Some(self.header.map_crc)
+        } else {
+            None
+        }
+    }
+    /// Only present since version 6.
+    pub fn map_sha256(&self) -> Option<Sha256> {
+        self.header.map_sha256.map(Sha256)
+    }
+    /// Number of recorded character ticks.
+    pub fn num_ticks(&self) -> i32 {
+        self.header.num_ticks
+    }
+    /// Race time in milliseconds.
+    pub fn time(&self) -> i32 {
+        self.header.time
+    }
+    pub fn read_item<W>(&mut self, warn: &mut W) -> Result<Option<Item>, ReadError>
+    where
+        W: Warn<Warning>,
+    {
+        if self.chunk_items == 0 && !self.read_chunk(warn)? {
+            return Ok(None);
+        }
+        let size = item_size(self.chunk_type).unwrap();
+        let diff = self
+            .ints
+            .get(self.pos..self.pos + size)
+            .ok_or(ReadError::ChunkTooShort)?;
+        if self.last.type_ == Some(self.chunk_type) {
+            for (l, d) in self.last.data.iter_mut().zip(diff) {
+                *l = l.wrapping_add(*d);
+            }
+        } else {
+            self.last.type_ = Some(self.chunk_type);
+            self.last.data.clear();
+            self.last.data.extend_from_slice(diff);
+        }
+        self.pos += size;
+        self.chunk_items -= 1;
+        if self.chunk_items == 0 && self.pos != self.ints.len() {
+            warn.warn(Warning::ExcessChunkData);
+        }
+        Ok(Some(Item::decode(self.chunk_type, &self.last.data)))
+    }
+    fn read_chunk<W>(&mut self, warn: &mut W) -> Result<bool, ReadError>
+    where
+        W: Warn<Warning>,
+    {
+        loop {
+            let mut header = [0; 4];
+            match self.data.read_exact(&mut header) {
+                Ok(()) => {}
+                Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(false),
+                Err(e) => return Err(e.into()),
+            }
+            let type_ = header[0];
+            let num_items = header[1].usize();
+            let size = u16::from_be_bytes([header[2], header[3]]).usize();
+            if item_size(type_).is_none() {
+                return Err(ReadError::UnknownItemType(type_));
+            }
+            let raw = &mut self.raw[..size];
+            self.data.read_exact(raw)?;
+            self.huffman.clear();
+            HUFFMAN.decompress(raw, &mut self.huffman)?;
+            let mut unpacker = packer::Unpacker::new(&self.huffman);
+            self.ints.clear();
+            while !unpacker.is_empty() {
+                let int = unpacker
+                    .read_int(wrap(warn))
+                    .map_err(|_| ReadError::ChunkTooShort)?;
+                self.ints.push(int);
+            }
+            self.chunk_type = type_;
+            self.chunk_items = num_items;
+            self.pos = 0;
+            if num_items != 0 {
+                return Ok(true);
+            }
+        }
+    }
+}
+
+pub(crate) trait SeekableWrite: io::Write + io::Seek {}
+impl<T: io::Write + io::Seek> SeekableWrite for T {}
+
+pub struct Writer {
+    file: Box<dyn SeekableWrite>,
+    header_pos: u64,
+    header: Header,
+    chunk_type: Option<u8>,
+    chunk_items: usize,
+    ints: Vec<i32>,
+    item: Vec<i32>,
+    last: LastItem,
+    buffer: ArrayVec<[u8; MAX_CHUNK_SIZE]>,
+    huffman: ArrayVec<[u8; MAX_CHUNK_SIZE]>,
+}
+
+impl Writer {
+    /// Writes a version 6 ghost file, call `finish` once the race is over.
+    pub fn new<W: io::Write + io::Seek + 'static>(
+        mut file: W,
+        owner: &[u8],
+        map_name: &[u8],
+        map_sha256: Sha256,
+    ) -> Result<Writer, WriteError> {
+        let header_pos = file.stream_position().map_err(binrw::Error::Io)?;
+        let header = Header {
+            version: Version::V6,
+            owner: CappedString::from_raw(owner),
+            map_name: CappedString::from_raw(map_name),
+            map_crc: 0,
+            num_ticks: 0,
+            time: 0,
+            map_sha256: Some(map_sha256.0),
+        };
+        header.write(&mut file)?;
+        Ok(Writer {
+            file: Box::new(file),
+            header_pos,
+            header,
+            chunk_type: None,
+            chunk_items: 0,
+            ints: Vec::new(),
+            item: Vec::new(),
+            last: LastItem::default(),
+            buffer: ArrayVec::new(),
+            huffman: ArrayVec::new(),
+        })
+    }
+    pub fn write_item(&mut self, item: &Item) -> Result<(), WriteError> {
+        let type_ = item.type_();
+        if self.chunk_type != Some(type_) || self.chunk_items == MAX_ITEMS_PER_CHUNK {
+            self.flush_chunk()?;
+            self.chunk_type = Some(type_);
+        }
+        self.item.clear();
+        item.encode(&mut self.item);
+        if self.last.type_ == Some(type_) {
+            let diff = self.item.iter().zip(&self.last.data);
+            self.ints.extend(diff.map(|(i, l)| i.wrapping_sub(*l)));
+        } else {
+            self.ints.extend_from_slice(&self.item);
+        }
+        self.last.type_ = Some(type_);
+        self.last.data.clear();
+        self.last.data.extend_from_slice(&self.item);
+        self.chunk_items += 1;
+        Ok(())
+    }
+    fn flush_chunk(&mut self) -> Result<(), WriteError> {
+        let type_ = match self.chunk_type {
+            Some(t) if self.chunk_items != 0 => t,
+            _ => return Ok(()),
+        };
+        self.buffer.clear();
+        let ints = &self.ints;
+        with_packer(
+            &mut self.buffer,
+            |mut p| -> Result<(), buffer::CapacityError> {
+                for &i in ints {
+                    p.write_int(i)?;
+                }
+                Ok(())
+            },
+        )
+        .expect("overlong chunk");
+        self.huffman.clear();
+        HUFFMAN
+            .compress(&self.buffer, &mut self.huffman)
+            .expect("too long compression");
+        let size = self.huffman.len().assert_u16().to_be_bytes();
+        let header = [type_, self.chunk_items.assert_u8(), size[0], size[1]];
+        self.file.write_all(&header).map_err(binrw::Error::Io)?;
+        self.file
+            .write_all(&self.huffman)
+            .map_err(binrw::Error::Io)?;
+        self.ints.clear();
+        self.chunk_items = 0;
+        Ok(())
+    }
+    /// Writes the remaining items and fills in the number of character ticks
+    /// and the race time in milliseconds.
+    pub fn finish(mut self, num_ticks: i32, time: i32) -> Result<(), WriteError> {
+        self.flush_chunk()?;
+        self.header.num_ticks = num_ticks;
+        self.header.time = time;
+        self.file
+            .seek(SeekFrom::Start(self.header_pos))
+            .map_err(binrw::Error::Io)?;
+        self.header.write(&mut self.file)?;
+        self.file.seek(SeekFrom::End(0)).map_err(binrw::Error::Io)?;
+        self.file.flush().map_err(binrw::Error::Io)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::digest::Sha256;
+    use std::env;
+    use std::fs;
+    use std::fs::File;
+    use std::io::Cursor;
+    use std::path::Path;
+    use std::process;
+    use warn::Panic;
+
+    use super::Character;
+    use super::Item;
+    use super::Reader;
+    use super::Skin;
+    use super::Version;
+    use super::Writer;
+
+    const SHA256: [u8; 32] = [0x11; 32];
+
+    /// The chunks of `items()`, a start tick chunk and a chunk of two
+    /// characters, the second stored as the difference to the first.
+    const CHUNKS: &[u8] = &[
+        3, 1, 0, 5, 218, 220, 41, 110, 0, // start tick
+        1, 2, 0, 16, 252, 212, 38, 153, 129, 136, 152, 159, 218, 36, 179, 23, 120, 255, 138, 27,
+    ];
+
+    fn items() -> Vec<Item> {
+        let character = Character {
+            x: 1056,
+            y: 592,
+            vel_x: 0,
+            vel_y: 128,
+            angle: 0,
+            direction: 1,
+            weapon: 1,
+            hook_state: 0,
+            hook_x: 1056,
+            hook_y: 592,
+            attack_tick: 0,
+            tick: None,
+        };
+        vec![
+            Item::StartTick(1000),
+            Item::Character(character),
+            Item::Character(Character {
+                x: 1060,
+                vel_x: 256,
+                ..character
+            }),
+        ]
+    }
+
+    /// Lays out DDNet's `CGhostHeader`, the SHA256 is only present since
+    /// version 6.
+    fn ddnet_header(version: u8, map_crc: u32, map_sha256: Option<[u8; 32]>) -> Vec<u8> {
+        let mut result = b"TWGHOST\0".to_vec();
+        result.push(version);
+        let mut owner = [0; 16];
+        owner[..12].copy_from_slice(b"nameless tee");
+        result.extend_from_slice(&owner);
+        let mut map = [0; 64];
+        map[..7].copy_from_slice(b"Kobra 4");
+        result.extend_from_slice(&map);
+        result.extend_from_slice(&map_crc.to_be_bytes());
+        result.extend_from_slice(&2i32.to_be_bytes());
+        result.extend_from_slice(&40i32.to_be_bytes());
+        result.extend(map_sha256.iter().flatten());
+        result
+    }
+
+    fn read_all(reader: &mut Reader) -> Vec<Item> {
+        let mut result = Vec::new();
+        while let Some(item) = reader.read_item(&mut Panic).unwrap() {
+            result.push(item);
+        }
+        result
+    }
+
+    fn write(path: &Path, items: &[Item]) {
+        let file = File::create(path).unwrap();
+        let mut writer = Writer::new(file, b"nameless tee", b"Kobra 4", Sha256(SHA256)).unwrap();
+        for item in items {
+            writer.write_item(item).unwrap();
+        }
+        writer.finish(2, 40).unwrap();
+    }
+
+    #[test]
+    fn ddnet_versions() {
+        let cases = [
+            (4, Version::V4, 0x1234abcd, None),
+            (5, Version::V5, 0x1234abcd, None),
+            (6, Version::V6, 0, Some(SHA256)),
+        ];
+        for &(raw, version, crc, sha256) in &cases {
+            let mut data = ddnet_header(raw, crc, sha256);
+            data.extend_from_slice(CHUNKS);
+            let mut reader = Reader::new(Cursor::new(data)).unwrap();
+            assert_eq!(reader.version(), version);
+            assert_eq!(reader.owner(), b"nameless tee");
+            assert_eq!(reader.map_name(), b"Kobra 4");
+            assert_eq!(reader.map_crc(), sha256.map_or(Some(crc), |_| None));
+            assert_eq!(reader.map_sha256().map(|s| s.0), sha256);
+            assert_eq!((reader.num_ticks(), reader.time()), (2, 40));
+            assert_eq!(read_all(&mut reader), items());
+        }
+    }
+
+    #[test]
+    fn write_ddnet_layout() {
+        let path = env::temp_dir().join(format!("libtw2-ghost-layout-{}.gho", process::id()));
+        write(&path, &items());
+        let mut expected = ddnet_header(6, 0, Some(SHA256));
+        expected.extend_from_slice(CHUNKS);
+        assert_eq!(fs::read(&path).unwrap(), expected);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut items = vec![
+            Item::Skin(Skin {
+                skin: [-1, 2, -3, 4, -5, 6],
+                use_custom_color: 1,
+                color_body: 0xff00ff,
+                color_feet: 0x00ff00,
+            }),
+            Item::StartTick(-5),
+        ];
+        // More than fit into one chunk, the differences continue across
+        // chunks.
+        items.extend((0..120).map(|i| {
+            Item::Character(Character {
+                x: i * 7,
+                y: -i * i,
+                vel_x: i32::MAX - i,
+                angle: i32::MIN + i,
+                tick: Some(i),
+                ..Character::default()
+            })
+        }));
+        items.push(Item::Character(Character::default()));
+        items.push(Item::StartTick(i32::MAX));
+
+        let path = env::temp_dir().join(format!("libtw2-ghost-round-trip-{}.gho", process::id()));
+        write(&path, &items);
+        let mut reader = Reader::new(File::open(&path).unwrap()).unwrap();
+        assert_eq!(reader.version(), Version::V6);
+        assert_eq!(reader.map_sha256().map(|s| s.0), Some(SHA256));
+        assert_eq!((reader.num_ticks(), reader.time()), (2, 40));
+        assert_eq!(read_all(&mut reader), items);
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -4,6 +4,7 @@ extern crate buffer;
 extern crate common;
 extern crate gamenet_common;
 extern crate gamenet_ddnet;
+extern crate gamenet_teeworlds_0_7;
 extern crate huffman;
 #[macro_use]
 extern crate matches;
@@ -12,16 +13,32 @@ extern crate snapshot;
 extern crate thiserror;
 extern crate warn;
 
+mod convert;
+mod cut;
 pub mod ddnet;
+mod embed;
+pub mod extract;
 mod format;
+pub mod ghost;
 mod reader;
 mod writer;
 
+pub use convert::convert;
+pub use convert::ConvertError;
+pub use convert::Report;
+pub use cut::cut;
+pub use cut::CutError;
+pub use embed::embed_map;
+pub use embed::EmbedMapError;
 pub use format::DemoKind;
+pub use format::Extension;
 pub use format::RawChunk;
 pub use format::Version;
 pub use format::Warning;
+pub use reader::MapError;
+pub use reader::Poll;
 pub use reader::ReadError;
 pub use reader::Reader;
+pub use writer::DemoHeader;
 pub use writer::WriteError;
 pub use writer::Writer;
@@ -1,11 +1,17 @@
 use arrayvec::ArrayVec;
 use binrw::BinRead;
+use common::digest;
 use common::digest::Sha256;
 use common::num::Cast;
+use common::time::Tick;
 use huffman;
 use huffman::instances::TEEWORLDS as HUFFMAN;
 use packer;
+use std::fs;
 use std::io;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::path::Path;
 use thiserror::Error;
 use warn::wrap;
 use warn::Warn;
@@ -31,6 +37,8 @@ pub enum ReadError {
     StartingDeltaSnapshot,
     #[error("The tick number overflowed")]
     TickOverflow,
+    #[error("The demo header changed while reading")]
+    HeaderChanged,
 }
 
 impl ReadError {
@@ -41,14 +49,82 @@ impl ReadError {
             err => Err(err),
         }
     }
+    fn is_eof(&self) -> bool {
+        match self {
+            ReadError::Io(io) => io.kind() == io::ErrorKind::UnexpectedEof,
+            ReadError::Binrw(err) => err.is_eof(),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum MapError {
+    #[error("The demo does not contain the map")]
+    NotEmbedded,
+    #[error("Map CRC mismatch")]
+    CrcMismatch,
+    #[error("Map SHA256 mismatch")]
+    Sha256Mismatch,
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Checks `map` against the CRC and, if given, the SHA256 of a demo header.
+pub(crate) fn check_map(map: &[u8], crc: u32, sha256: Option<Sha256>) -> Result<(), MapError> {
+    if map.is_empty() {
+        return Err(MapError::NotEmbedded);
+    }
+    if digest::crc32(map) != crc {
+        return Err(MapError::CrcMismatch);
+    }
+    if let Some(sha256) = sha256 {
+        if digest::sha256(map).0 != sha256.0 {
+            return Err(MapError::Sha256Mismatch);
+        }
+    }
+    Ok(())
+}
+
+/// Result of `Reader::poll_next`.
+#[derive(Debug)]
+pub enum Poll<T> {
+    Ready(T),
+    /// The end of the data written so far was reached.
+    Pending,
 }
 
 trait SeekableRead: io::Read + io::Seek {}
 impl<T: io::Read + io::Seek> SeekableRead for T {}
 
+/// Position of a keyframe in the demo file.
+#[derive(Clone, Copy, Debug)]
+struct Keyframe {
+    tick: i32,
+    /// Offset of the keyframe's tick marker.
+    pos: u64,
+    /// The tick before the keyframe, needed for decoding relative tick
+    /// markers.
+    prev_tick: Option<i32>,
+}
+
+/// A chunk that was read into the buffers of the `Reader`.
+#[derive(Clone, Copy)]
+enum ReadChunk {
+    Tick { tick: i32, keyframe: bool },
+    Snapshot,
+    SnapshotDelta,
+    Message(usize),
+    Unknown(usize),
+}
+
 pub struct Reader {
     data: Box<dyn SeekableRead>,
+    header_pos: u64,
     start: format::HeaderStart,
+    chunks_pos: u64,
+    pending: bool,
+    keyframes: Option<Vec<Keyframe>>,
     current_tick: Option<i32>,
     raw: [u8; MAX_SNAPSHOT_SIZE],
     huffman: ArrayVec<[u8; MAX_SNAPSHOT_SIZE]>,
@@ -60,12 +136,18 @@ impl Reader {
         W: Warn<Warning>,
         R: io::Read + io::Seek + 'static,
     {
+        let header_pos = data.stream_position()?;
         let start = format::HeaderStart::read(&mut data)?;
         start.header.check(warn);
         start.timeline_markers.check(warn);
+        let chunks_pos = data.stream_position()?;
         Ok(Self {
             data: Box::new(data),
+            header_pos,
             start: start,
+            chunks_pos,
+            pending: false,
+            keyframes: None,
             current_tick: None,
             raw: [0; MAX_SNAPSHOT_SIZE],
             huffman: ArrayVec::new(),
@@ -101,68 +183,141 @@ impl Reader {
     pub fn timeline_markers(&self) -> &[i32] {
         self.start.timeline_markers.markers()
     }
+    /// Whether the demo carries the `Extension::TestDemo` marker.
+    pub fn is_test_demo(&self) -> bool {
+        self.start.test_demo.is_some()
+    }
     pub fn map_sha256(&self) -> Option<Sha256> {
         self.start
             .map_sha256
             .as_ref()
             .map(|sha| Sha256(sha.sha_256))
     }
+    /// Checks the embedded map against the CRC and, for DDNet demos, the
+    /// SHA256 of the header.
+    pub fn verify_map(&self) -> Result<(), MapError> {
+        check_map(self.map_data(), self.map_crc(), self.map_sha256())
+    }
+    /// Verifies the embedded map and writes it to `path`.
+    pub fn extract_map<P: AsRef<Path>>(&self, path: P) -> Result<(), MapError> {
+        self.verify_map()?;
+        fs::write(path, self.map_data())?;
+        Ok(())
+    }
     pub fn read_chunk<W>(&mut self, warn: &mut W) -> Result<Option<format::RawChunk>, ReadError>
+    where
+        W: Warn<Warning>,
+    {
+        match self.read_chunk_inner(warn) {
+            Ok(c) => Ok(c.map(move |c| self.chunk(c))),
+            Err(err) if err.is_eof() => {
+                warn.warn(Warning::TruncatedChunk);
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+    /// Reads the next chunk of a demo that might still be being recorded.
+    ///
+    /// At the end of the file, including in the middle of an incomplete
+    /// last chunk, the read position is left at the start of that chunk and
+    /// `Poll::Pending` is returned. Call again once more data is available.
+    /// Once the file has grown, the header is re-read using
+    /// `refresh_header`.
+    pub fn poll_next<W>(&mut self, warn: &mut W) -> Result<Poll<format::RawChunk<'_>>, ReadError>
+    where
+        W: Warn<Warning>,
+    {
+        let pos = self.data.stream_position()?;
+        if self.pending {
+            let end = self.data.seek(SeekFrom::End(0))?;
+            self.data.seek(SeekFrom::Start(pos))?;
+            if end <= pos {
+                return Ok(Poll::Pending);
+            }
+            self.pending = false;
+            self.refresh_header(warn)?;
+        }
+        match self.read_chunk_inner(warn) {
+            Ok(Some(c)) => Ok(Poll::Ready(self.chunk(c))),
+            Ok(None) => {
+                self.pending = true;
+                Ok(Poll::Pending)
+            }
+            Err(err) if err.is_eof() => {
+                self.data.seek(SeekFrom::Start(pos))?;
+                self.pending = true;
+                Ok(Poll::Pending)
+            }
+            Err(err) => Err(err),
+        }
+    }
+    /// Re-reads the demo length and the timeline markers from the header,
+    /// they are only filled in when the recording is finished.
+    ///
+    /// Fails with `ReadError::HeaderChanged` if the rest of the header no
+    /// longer matches, e.g. because a new recording was started in the same
+    /// file. The read position is preserved.
+    pub fn refresh_header<W>(&mut self, warn: &mut W) -> Result<(), ReadError>
+    where
+        W: Warn<Warning>,
+    {
+        let pos = self.data.stream_position()?;
+        self.data.seek(SeekFrom::Start(self.header_pos))?;
+        let version = format::Version::read(&mut self.data)?;
+        let header = format::Header::read(&mut self.data)?;
+        let timeline_markers = if version >= format::Version::V4 {
+            format::TimelineMarkers::read(&mut self.data)?
+        } else {
+            format::TimelineMarkers::default()
+        };
+        self.data.seek(SeekFrom::Start(pos))?;
+        let old = &self.start.header;
+        if version != self.start.version
+            || header.net_version.raw() != old.net_version.raw()
+            || header.map_name.raw() != old.map_name.raw()
+            || header.map_size != old.map_size
+            || header.map_crc != old.map_crc
+        {
+            return Err(ReadError::HeaderChanged);
+        }
+        header.check(warn);
+        timeline_markers.check(warn);
+        self.start.header = header;
+        self.start.timeline_markers = timeline_markers;
+        // More chunks might have been written since the index was built.
+        self.keyframes = None;
+        Ok(())
+    }
+    fn read_chunk_inner<W>(&mut self, warn: &mut W) -> Result<Option<ReadChunk>, ReadError>
     where
         W: Warn<Warning>,
     {
         use crate::format::ChunkHeader;
         use crate::format::DataKind;
-        use crate::format::RawChunk;
 
         let chunk_header = match ChunkHeader::read(&mut self.data, self.start.version, warn)? {
             Some(ch) => ch,
             None => return Ok(None),
         };
         match chunk_header {
-            ChunkHeader::Tick {
-                marker: TickMarker::Absolute(t),
-                keyframe,
-            } => {
-                if let Some(previous) = self.current_tick {
-                    if previous >= t {
-                        return Err(ReadError::NotIncreasingTick);
-                    }
-                }
-                self.current_tick = Some(t);
-                Ok(Some(RawChunk::Tick {
-                    tick: t,
-                    keyframe: keyframe,
-                }))
+            ChunkHeader::Tick { marker, keyframe } => {
+                let tick = Self::next_tick(self.current_tick, marker)?;
+                self.current_tick = Some(tick);
+                Ok(Some(ReadChunk::Tick { tick, keyframe }))
             }
-            ChunkHeader::Tick {
-                marker: TickMarker::Delta(d),
-                keyframe,
-            } => match self.current_tick {
-                None => Err(ReadError::StartingDeltaSnapshot),
-                Some(t) => match t.checked_add(d.i32()) {
-                    None => Err(ReadError::TickOverflow),
-                    Some(new_t) => {
-                        self.current_tick = Some(new_t);
-                        Ok(Some(RawChunk::Tick {
-                            tick: new_t,
-                            keyframe: keyframe,
-                        }))
-                    }
-                },
-            },
             ChunkHeader::Data { kind, size } => {
-                if kind == DataKind::Unknown {
-                    return Ok(Some(RawChunk::Unknown));
-                }
                 let raw_data = &mut self.raw[..size.usize()];
                 self.data.read_exact(raw_data)?;
+                if kind == DataKind::Unknown {
+                    return Ok(Some(ReadChunk::Unknown(size.usize())));
+                }
                 self.huffman.clear();
                 HUFFMAN.decompress(raw_data, &mut self.huffman)?;
                 Ok(Some(match kind {
-                    DataKind::Unknown => RawChunk::Unknown,
-                    DataKind::Snapshot => RawChunk::Snapshot(&self.huffman),
-                    DataKind::SnapshotDelta => RawChunk::SnapshotDelta(&self.huffman),
+                    DataKind::Unknown => unreachable!(),
+                    DataKind::Snapshot => ReadChunk::Snapshot,
+                    DataKind::SnapshotDelta => ReadChunk::SnapshotDelta,
                     DataKind::Message => {
                         let mut unpacker = packer::Unpacker::new(&self.huffman);
                         let mut len = 0;
@@ -177,10 +332,267 @@ impl Reader {
                                 .copy_from_slice(&n.to_le_bytes());
                             len += 4;
                         }
-                        RawChunk::Message(&self.raw[..len])
+                        ReadChunk::Message(len)
                     }
                 }))
             }
         }
     }
+    fn chunk(&self, chunk: ReadChunk) -> format::RawChunk<'_> {
+        use crate::format::RawChunk;
+
+        match chunk {
+            ReadChunk::Tick { tick, keyframe } => RawChunk::Tick {
+                tick: Tick(tick),
+                keyframe,
+            },
+            ReadChunk::Snapshot => RawChunk::Snapshot(&self.huffman),
+            ReadChunk::SnapshotDelta => RawChunk::SnapshotDelta(&self.huffman),
+            ReadChunk::Message(len) => RawChunk::Message(&self.raw[..len]),
+            ReadChunk::Unknown(len) => RawChunk::Unknown(&self.raw[..len]),
+        }
+    }
+    fn next_tick(current_tick: Option<i32>, marker: TickMarker) -> Result<i32, ReadError> {
+        match marker {
+            TickMarker::Absolute(t) => {
+                if let Some(previous) = current_tick {
+                    if previous >= t {
+                        return Err(ReadError::NotIncreasingTick);
+                    }
+                }
+                Ok(t)
+            }
+            TickMarker::Delta(d) => match current_tick {
+                None => Err(ReadError::StartingDeltaSnapshot),
+                Some(t) => t.checked_add(d.i32()).ok_or(ReadError::TickOverflow),
+            },
+        }
+    }
+    /// Scans the demo for keyframes, to allow seeking with `seek_to_tick`.
+    ///
+    /// This is done automatically on the first seek. The read position is
+    /// preserved.
+    pub fn build_index<W>(&mut self, warn: &mut W) -> Result<(), ReadError>
+    where
+        W: Warn<Warning>,
+    {
+        use crate::format::ChunkHeader;
+
+        if self.keyframes.is_some() {
+            return Ok(());
+        }
+        let pos = self.data.stream_position()?;
+        self.data.seek(SeekFrom::Start(self.chunks_pos))?;
+        let mut keyframes = Vec::new();
+        let mut current_tick = None;
+        loop {
+            let chunk_pos = self.data.stream_position()?;
+            let chunk_header = match ChunkHeader::read(&mut self.data, self.start.version, warn) {
+                Ok(Some(ch)) => ch,
+                Ok(None) => break,
+                // Truncated last chunk.
+                Err(err) if err.is_eof() => break,
+                Err(err) => return Err(err.into()),
+            };
+            match chunk_header {
+                ChunkHeader::Tick { marker, keyframe } => {
+                    let tick = Self::next_tick(current_tick, marker)?;
+                    if keyframe {
+                        keyframes.push(Keyframe {
+                            tick,
+                            pos: chunk_pos,
+                            prev_tick: current_tick,
+                        });
+                    }
+                    current_tick = Some(tick);
+                }
+                ChunkHeader::Data { size, .. } => {
+                    self.data.seek(SeekFrom::Current(size.into()))?;
+                }
+            }
+        }
+        self.data.seek(SeekFrom::Start(pos))?;
+        self.keyframes = Some(keyframes);
+        Ok(())
+    }
+    /// Moves the read position to the last keyframe at or before `tick`.
+    ///
+    /// Returns the tick of that keyframe, the next chunks read are its tick
+    /// marker followed by the full snapshot to resume decoding from. Returns
+    /// `None` and leaves the read position unchanged if there is no such
+    /// keyframe.
+    pub fn seek_to_tick<W>(&mut self, tick: Tick, warn: &mut W) -> Result<Option<Tick>, ReadError>
+    where
+        W: Warn<Warning>,
+    {
+        self.build_index(warn)?;
+        let keyframes = self.keyframes.as_ref().unwrap();
+        let keyframe = match keyframes.binary_search_by_key(&tick.to_i32(), |k| k.tick) {
+            Ok(i) => keyframes[i],
+            Err(0) => return Ok(None),
+            Err(i) => keyframes[i - 1],
+        };
+        self.data.seek(SeekFrom::Start(keyframe.pos))?;
+        self.current_tick = keyframe.prev_tick;
+        Ok(Some(Tick(keyframe.tick)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::time::Tick;
+    use std::env;
+    use std::fs;
+    use std::fs::File;
+    use std::path::Path;
+    use std::path::PathBuf;
+    use std::process;
+    use warn::Panic;
+
+    use super::Poll;
+    use super::Reader;
+    use crate::format::DemoKind;
+    use crate::format::RawChunk;
+    use crate::writer::Writer;
+
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("libtw2-demo-{}-{}.demo", name, process::id()))
+    }
+
+    fn writer(path: &Path) -> Writer {
+        Writer::new(
+            File::create(path).unwrap(),
+            b"0.6 626fce9a778df4d4",
+            b"dm1",
+            None,
+            0xf2159e6e,
+            DemoKind::Client,
+            0,
+            b"2026-10-15_04-13-54",
+            &[],
+        )
+        .unwrap()
+    }
+
+    /// Writes the ticks with a snapshot each, the ticks ending in 5 are
+    /// keyframes.
+    fn write_ticks(writer: &mut Writer, ticks: impl Iterator<Item = i32>) {
+        for tick in ticks {
+            writer.write_tick(tick % 10 == 5, Tick(tick)).unwrap();
+            writer.write_snapshot(&[tick as u8; 4]).unwrap();
+        }
+    }
+
+    /// Returns the tick and the snapshot following it.
+    fn next_tick(reader: &mut Reader) -> Option<(i32, bool, u8)> {
+        let (tick, keyframe) = match reader.read_chunk(&mut Panic).unwrap()? {
+            RawChunk::Tick { tick, keyframe } => (tick.to_i32(), keyframe),
+            _ => panic!("not a tick"),
+        };
+        match reader.read_chunk(&mut Panic).unwrap() {
+            Some(RawChunk::Snapshot(s)) => Some((tick, keyframe, s[0])),
+            _ => panic!("not a snapshot"),
+        }
+    }
+
+    #[test]
+    fn seek_to_tick() {
+        let path = temp_path("seek");
+        let mut w = writer(&path);
+        write_ticks(&mut w, 5..31);
+        w.finalize().unwrap();
+        drop(w);
+
+        let mut reader = Reader::new(File::open(&path).unwrap(), &mut Panic).unwrap();
+        assert_eq!(next_tick(&mut reader), Some((5, true, 5)));
+        assert_eq!(next_tick(&mut reader), Some((6, false, 6)));
+        // No keyframe before tick 5, the position is kept.
+        assert_eq!(reader.seek_to_tick(Tick(4), &mut Panic).unwrap(), None);
+        assert_eq!(next_tick(&mut reader), Some((7, false, 7)));
+
+        assert_eq!(
+            reader.seek_to_tick(Tick(24), &mut Panic).unwrap(),
+            Some(Tick(15))
+        );
+        assert_eq!(next_tick(&mut reader), Some((15, true, 15)));
+        // The following tick markers are relative to the keyframe.
+        assert_eq!(next_tick(&mut reader), Some((16, false, 16)));
+
+        assert_eq!(
+            reader.seek_to_tick(Tick(1000), &mut Panic).unwrap(),
+            Some(Tick(25))
+        );
+        let rest: Vec<i32> = (0..)
+            .map_while(|_| next_tick(&mut reader))
+            .map(|t| t.0)
+            .collect();
+        assert_eq!(rest, (25..31).collect::<Vec<_>>());
+
+        // Seeking backwards from the end.
+        assert_eq!(
+            reader.seek_to_tick(Tick(5), &mut Panic).unwrap(),
+            Some(Tick(5))
+        );
+        assert_eq!(next_tick(&mut reader), Some((5, true, 5)));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn build_index_keeps_position() {
+        let path = temp_path("build-index");
+        let mut w = writer(&path);
+        write_ticks(&mut w, 5..31);
+        w.finalize().unwrap();
+        drop(w);
+
+        let mut reader = Reader::new(File::open(&path).unwrap(), &mut Panic).unwrap();
+        for tick in 5..12 {
+            assert_eq!(next_tick(&mut reader).unwrap().0, tick);
+        }
+        reader.build_index(&mut Panic).unwrap();
+        assert_eq!(next_tick(&mut reader), Some((12, false, 12)));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn poll_growing_demo() {
+        let path = temp_path("poll");
+        let mut w = writer(&path);
+        write_ticks(&mut w, 5..8);
+
+        let mut reader = Reader::new(File::open(&path).unwrap(), &mut Panic).unwrap();
+        let mut ticks = Vec::new();
+        loop {
+            match reader.poll_next(&mut Panic).unwrap() {
+                Poll::Ready(RawChunk::Tick { tick, .. }) => ticks.push(tick.to_i32()),
+                Poll::Ready(_) => {}
+                Poll::Pending => break,
+            }
+        }
+        assert_eq!(ticks, [5, 6, 7]);
+        assert!(matches!(
+            reader.poll_next(&mut Panic).unwrap(),
+            Poll::Pending
+        ));
+
+        // The chunks and the header written since are picked up.
+        write_ticks(&mut w, 8..17);
+        w.add_timeline_marker();
+        w.finalize().unwrap();
+        loop {
+            match reader.poll_next(&mut Panic).unwrap() {
+                Poll::Ready(RawChunk::Tick { tick, .. }) => ticks.push(tick.to_i32()),
+                Poll::Ready(_) => {}
+                Poll::Pending => break,
+            }
+        }
+        assert_eq!(ticks, (5..17).collect::<Vec<_>>());
+        assert_eq!(reader.timeline_markers(), [16]);
+        assert_eq!(
+            reader.seek_to_tick(Tick(16), &mut Panic).unwrap(),
+            Some(Tick(15))
+        );
+        drop(w);
+        fs::remove_file(&path).unwrap();
+    }
 }
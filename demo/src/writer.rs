@@ -4,9 +4,11 @@ use buffer;
 use common::digest::Sha256;
 use common::num::Cast;
 use common::num::LeI32;
+use common::time::Tick;
 use huffman::instances::TEEWORLDS as HUFFMAN;
 use packer::with_packer;
 use std::io;
+use std::io::SeekFrom;
 use std::mem;
 use thiserror::Error;
 
@@ -17,6 +19,7 @@ use crate::format::DemoKind;
 use crate::format::Header;
 use crate::format::MapSha256;
 use crate::format::RawChunk;
+use crate::format::TestDemoMarker;
 use crate::format::TickMarker;
 use crate::format::TimelineMarkers;
 use crate::format::Version;
@@ -35,9 +38,31 @@ impl WriteError {
     }
 }
 
+/// Header of a demo to write, see `Writer::with_header`.
+#[derive(Clone, Copy)]
+pub struct DemoHeader<'a> {
+    pub version: Version,
+    pub net_version: &'a [u8],
+    pub map_name: &'a [u8],
+    /// Only for `Version::V6Ddnet`.
+    pub map_sha256: Option<Sha256>,
+    /// Writes the `Extension::TestDemo` marker, only for
+    /// `Version::V6Ddnet`.
+    pub test_demo: bool,
+    pub map_crc: u32,
+    pub kind: DemoKind,
+    /// Length in seconds, updated by `Writer::finalize`.
+    pub length: i32,
+    pub timestamp: &'a [u8],
+}
+
 pub struct Writer {
     file: Box<dyn SeekableWrite>,
+    version: Version,
+    header_pos: u64,
     header: Header,
+    timeline_markers: TimelineMarkers,
+    first_tick: Option<i32>,
     prev_tick: Option<i32>,
     huffman: ArrayVec<[u8; MAX_SNAPSHOT_SIZE]>,
     buffer2: ArrayVec<[u8; MAX_SNAPSHOT_SIZE]>,
@@ -45,11 +70,15 @@ pub struct Writer {
 
 const WRITER_VERSION: Version = Version::V5;
 const WRITER_VERSION_DDNET: Version = Version::V6Ddnet;
+const MAX_TIMELINE_MARKERS: usize = 64;
+const TICKS_PER_SECOND: i32 = 50;
 
 pub(crate) trait SeekableWrite: io::Write + io::Seek {}
 impl<T: io::Write + io::Seek> SeekableWrite for T {}
 
 impl Writer {
+    /// Writes a version 5 demo, or a DDNet version 6 demo if `map_sha256` is
+    /// given.
     pub fn new<W: io::Write + io::Seek + 'static>(
         file: W,
         net_version: &[u8],
@@ -61,8 +90,48 @@ impl Writer {
         timestamp: &[u8],
         map: &[u8],
     ) -> Result<Writer, WriteError> {
+        let version = if map_sha256.is_some() {
+            WRITER_VERSION_DDNET
+        } else {
+            WRITER_VERSION
+        };
+        let header = DemoHeader {
+            version,
+            net_version,
+            map_name,
+            map_sha256,
+            test_demo: false,
+            map_crc,
+            kind,
+            length,
+            timestamp,
+        };
+        Writer::with_header(file, &header, map)
+    }
+    /// Writes a demo with the given header, the header extensions can only
+    /// be given for `Version::V6Ddnet`.
+    pub fn with_header<W: io::Write + io::Seek + 'static>(
+        mut file: W,
+        header: &DemoHeader,
+        map: &[u8],
+    ) -> Result<Writer, WriteError> {
+        let &DemoHeader {
+            version,
+            net_version,
+            map_name,
+            map_sha256,
+            test_demo,
+            map_crc,
+            kind,
+            length,
+            timestamp,
+        } = header;
+        assert!((map_sha256.is_none() && !test_demo) || version == Version::V6Ddnet);
+        let header_pos = file.stream_position().map_err(binrw::Error::Io)?;
         let mut writer = Writer {
             file: Box::new(file),
+            version,
+            header_pos,
             header: Header {
                 net_version: CappedString::from_raw(net_version),
                 map_name: CappedString::from_raw(map_name),
@@ -72,51 +141,89 @@ impl Writer {
                 length,
                 timestamp: CappedString::from_raw(timestamp),
             },
+            timeline_markers: TimelineMarkers::default(),
+            first_tick: None,
             prev_tick: None,
             huffman: ArrayVec::new(),
             buffer2: ArrayVec::new(),
         };
-        writer.write_header(map_sha256.is_some())?;
-        TimelineMarkers {
-            amount: 0,
-            markers: [0; 64],
-        }
-        .write(&mut writer.file)?;
+        writer.write_header()?;
         if let Some(sha256) = map_sha256 {
             MapSha256::new(sha256).write_le(&mut writer.file)?;
         }
+        if test_demo {
+            TestDemoMarker::new().write_le(&mut writer.file)?;
+        }
         map.write(&mut writer.file)?;
         Ok(writer)
     }
-    fn write_header(&mut self, ddnet: bool) -> Result<(), WriteError> {
-        let version = if ddnet {
-            WRITER_VERSION_DDNET
-        } else {
-            WRITER_VERSION
-        };
-        version.write(&mut self.file)?;
+    fn write_header(&mut self) -> Result<(), WriteError> {
+        self.version.write(&mut self.file)?;
         self.header.write(&mut self.file)?;
+        if self.version >= Version::V4 {
+            self.timeline_markers.write(&mut self.file)?;
+        }
         Ok(())
     }
+    pub fn version(&self) -> Version {
+        self.version
+    }
     pub fn write_chunk(&mut self, chunk: RawChunk) -> Result<(), WriteError> {
         match chunk {
             RawChunk::Tick { tick, keyframe } => self.write_tick(keyframe, tick),
             RawChunk::Snapshot(snapshot) => self.write_snapshot(snapshot),
             RawChunk::SnapshotDelta(delta) => self.write_snapshot_delta(delta),
             RawChunk::Message(msg) => self.write_message(msg),
-            RawChunk::Unknown => panic!(),
+            RawChunk::Unknown(data) => self.write_unknown(data),
         }
     }
-    pub fn write_tick(&mut self, keyframe: bool, tick: i32) -> Result<(), WriteError> {
-        let tm = TickMarker::new(tick, self.prev_tick, keyframe, WRITER_VERSION);
+    pub fn write_tick(&mut self, keyframe: bool, tick: Tick) -> Result<(), WriteError> {
+        let tick = tick.to_i32();
+        let tm = TickMarker::new(tick, self.prev_tick, keyframe, self.version);
         ChunkHeader::Tick {
             marker: tm,
             keyframe: keyframe,
         }
-        .write(&mut self.file, WRITER_VERSION)?;
+        .write(&mut self.file, self.version)?;
+        self.first_tick.get_or_insert(tick);
         self.prev_tick = Some(tick);
         Ok(())
     }
+    /// Adds a timeline marker at the last written tick.
+    ///
+    /// Returns `false` if the marker couldn't be added, because there are
+    /// already 64 markers, there is one at the same tick or the version
+    /// doesn't support timeline markers.
+    pub fn add_timeline_marker(&mut self) -> bool {
+        match self.prev_tick {
+            Some(t) => self.add_timeline_marker_at(Tick(t)),
+            None => false,
+        }
+    }
+    /// Adds a timeline marker at `tick`, which doesn't need to have been
+    /// written.
+    ///
+    /// Returns `false` if the marker couldn't be added, because there are
+    /// already 64 markers, there is one at the same or a later tick or the
+    /// version doesn't support timeline markers.
+    pub fn add_timeline_marker_at(&mut self, tick: Tick) -> bool {
+        let tick = tick.to_i32();
+        let amount = self.timeline_markers.amount.assert_usize();
+        if self.version < Version::V4
+            || amount >= MAX_TIMELINE_MARKERS
+            || self
+                .timeline_markers
+                .markers()
+                .last()
+                .map(|&t| t >= tick)
+                .unwrap_or(false)
+        {
+            return false;
+        }
+        self.timeline_markers.markers[amount] = tick;
+        self.timeline_markers.amount += 1;
+        true
+    }
     fn write_chunk_impl(&mut self, kind: DataKind, data: Option<&[u8]>) -> Result<(), WriteError> {
         let data = data.unwrap_or(&self.buffer2);
         self.huffman.clear();
@@ -127,7 +234,7 @@ impl Writer {
             kind,
             size: self.huffman.len().assert_u16(),
         }
-        .write(&mut self.file, WRITER_VERSION)?;
+        .write(&mut self.file, self.version)?;
         self.file
             .write_all(&self.huffman)
             .map_err(binrw::Error::Io)?;
@@ -139,6 +246,16 @@ impl Writer {
     pub fn write_snapshot_delta(&mut self, delta: &[u8]) -> Result<(), WriteError> {
         self.write_chunk_impl(DataKind::SnapshotDelta, Some(delta))
     }
+    /// Writes a chunk of unknown type, `data` is written as is.
+    pub fn write_unknown(&mut self, data: &[u8]) -> Result<(), WriteError> {
+        ChunkHeader::Data {
+            kind: DataKind::Unknown,
+            size: data.len().assert_u16(),
+        }
+        .write(&mut self.file, self.version)?;
+        self.file.write_all(data).map_err(binrw::Error::Io)?;
+        Ok(())
+    }
     pub fn write_message(&mut self, msg: &[u8]) -> Result<(), WriteError> {
         self.buffer2.clear();
         with_packer(
@@ -158,6 +275,211 @@ impl Writer {
         .expect("overlong message");
         self.write_chunk_impl(DataKind::Message, None)
     }
-    // TODO: Add a `finalize` function that writes the demo length into the
-    // original header.
+    /// Writes the demo length and the timeline markers into the header.
+    ///
+    /// The demo stays writable afterwards, so this can also be used to keep
+    /// the header of a running recording up to date.
+    pub fn finalize(&mut self) -> Result<(), WriteError> {
+        if let (Some(first), Some(last)) = (self.first_tick, self.prev_tick) {
+            self.header.length = (last - first) / TICKS_PER_SECOND;
+        }
+        self.file
+            .seek(SeekFrom::Start(self.header_pos))
+            .map_err(binrw::Error::Io)?;
+        self.write_header()?;
+        self.file.seek(SeekFrom::End(0)).map_err(binrw::Error::Io)?;
+        self.file.flush().map_err(binrw::Error::Io)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::digest::Sha256;
+    use common::time::Tick;
+    use packer::uuid_registry::uuid_from_name;
+    use std::env;
+    use std::fs;
+    use std::fs::File;
+    use std::path::Path;
+    use std::path::PathBuf;
+    use std::process;
+    use warn::Panic;
+
+    use super::DemoHeader;
+    use super::Writer;
+    use crate::format::DemoKind;
+    use crate::format::Extension;
+    use crate::format::RawChunk;
+    use crate::format::Version;
+    use crate::reader::Reader;
+
+    #[derive(Debug, PartialEq)]
+    enum Chunk {
+        Tick(i32, bool),
+        Snapshot(Vec<u8>),
+        Message(Vec<u8>),
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("libtw2-demo-{}-{}.demo", name, process::id()))
+    }
+
+    fn header(version: Version) -> DemoHeader<'static> {
+        DemoHeader {
+            version,
+            net_version: b"0.6 626fce9a778df4d4",
+            map_name: b"dm1",
+            map_sha256: None,
+            test_demo: false,
+            map_crc: 0xf2159e6e,
+            kind: DemoKind::Client,
+            length: 0,
+            timestamp: b"2026-10-15_04-13-54",
+        }
+    }
+
+    fn read(path: &Path) -> (Version, i32, Vec<i32>, Vec<Chunk>) {
+        let mut reader = Reader::new(File::open(path).unwrap(), &mut Panic).unwrap();
+        assert_eq!(reader.net_version(), b"0.6 626fce9a778df4d4");
+        assert_eq!(
+            (reader.map_name(), reader.map_crc()),
+            (&b"dm1"[..], 0xf2159e6e)
+        );
+        assert_eq!(reader.map_data(), b"map");
+        let mut chunks = Vec::new();
+        while let Some(chunk) = reader.read_chunk(&mut Panic).unwrap() {
+            chunks.push(match chunk {
+                RawChunk::Tick { tick, keyframe } => Chunk::Tick(tick.to_i32(), keyframe),
+                RawChunk::Snapshot(s) => Chunk::Snapshot(s.to_vec()),
+                RawChunk::Message(m) => Chunk::Message(m.to_vec()),
+                _ => panic!("unexpected chunk"),
+            });
+        }
+        let markers = reader.timeline_markers().to_vec();
+        (reader.version(), reader.length(), markers, chunks)
+    }
+
+    #[test]
+    fn versions() {
+        // Tick deltas that fit into the tick marker in the different
+        // versions, and ones that don't.
+        let ticks = [
+            (100, true),
+            (101, false),
+            (131, false),
+            (163, false),
+            (750, true),
+        ];
+        for &version in &[Version::V3, Version::V4, Version::V5] {
+            let path = temp_path(&format!("version-{:?}", version));
+            let mut writer =
+                Writer::with_header(File::create(&path).unwrap(), &header(version), b"map")
+                    .unwrap();
+            let mut expected = Vec::new();
+            for &(tick, keyframe) in &ticks {
+                writer.write_tick(keyframe, Tick(tick)).unwrap();
+                writer.write_snapshot(&[tick as u8; 40]).unwrap();
+                writer.write_message(b"12345678").unwrap();
+                expected.push(Chunk::Tick(tick, keyframe));
+                expected.push(Chunk::Snapshot(vec![tick as u8; 40]));
+                expected.push(Chunk::Message(b"12345678".to_vec()));
+                if tick == 131 {
+                    assert_eq!(writer.add_timeline_marker(), version >= Version::V4);
+                }
+            }
+            writer.finalize().unwrap();
+            drop(writer);
+            let markers: &[i32] = if version >= Version::V4 { &[131] } else { &[] };
+            assert_eq!(read(&path), (version, 13, markers.to_vec(), expected));
+            fs::remove_file(&path).unwrap();
+        }
+    }
+
+    #[test]
+    fn timeline_markers() {
+        let path = temp_path("timeline-markers");
+        let file = File::create(&path).unwrap();
+        let mut writer = Writer::with_header(file, &header(Version::V5), b"map").unwrap();
+        assert!(!writer.add_timeline_marker());
+        for tick in 0..70 {
+            writer.write_tick(tick == 0, Tick(tick)).unwrap();
+            assert_eq!(writer.add_timeline_marker(), tick < 64);
+            assert!(!writer.add_timeline_marker());
+        }
+        writer.finalize().unwrap();
+        drop(writer);
+        let (_, length, markers, _) = read(&path);
+        assert_eq!(length, 1);
+        assert_eq!(markers, (0..64).collect::<Vec<_>>());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn finalize() {
+        let path = temp_path("finalize");
+        let file = File::create(&path).unwrap();
+        let mut writer = Writer::with_header(file, &header(Version::V5), b"map").unwrap();
+        writer.write_tick(true, Tick(1000)).unwrap();
+        writer.write_tick(false, Tick(1100)).unwrap();
+        writer.add_timeline_marker();
+
+        // Before finalizing, the header still contains the initial values.
+        assert_eq!(read(&path).1, 0);
+        writer.finalize().unwrap();
+        let (_, length, markers, chunks) = read(&path);
+        assert_eq!((length, markers), (2, vec![1100]));
+        assert_eq!(chunks.len(), 2);
+
+        // The writer continues after the chunks written so far.
+        writer.write_tick(false, Tick(1250)).unwrap();
+        writer.finalize().unwrap();
+        drop(writer);
+        let (_, length, markers, chunks) = read(&path);
+        assert_eq!((length, markers), (5, vec![1100]));
+        assert_eq!(
+            chunks,
+            [
+                Chunk::Tick(1000, true),
+                Chunk::Tick(1100, false),
+                Chunk::Tick(1250, false),
+            ]
+        );
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn ddnet_extensions() {
+        assert_eq!(
+            uuid_from_name(b"demo-test@ddnet.tw").as_bytes(),
+            &Extension::TestDemo.uuid()
+        );
+        for &(sha256, test_demo) in &[
+            (None, false),
+            (Some([0x5a; 32]), false),
+            (None, true),
+            (Some([0x5a; 32]), true),
+        ] {
+            let path = temp_path(&format!("extensions-{}-{}", sha256.is_some(), test_demo));
+            let header = DemoHeader {
+                map_sha256: sha256.map(Sha256),
+                test_demo,
+                ..header(Version::V6Ddnet)
+            };
+            let file = File::create(&path).unwrap();
+            let mut writer = Writer::with_header(file, &header, b"map").unwrap();
+            writer.write_tick(true, Tick(1)).unwrap();
+            writer.finalize().unwrap();
+            drop(writer);
+
+            let reader = Reader::new(File::open(&path).unwrap(), &mut Panic).unwrap();
+            assert_eq!(reader.map_sha256().map(|s| s.0), sha256);
+            assert_eq!(reader.is_test_demo(), test_demo);
+            drop(reader);
+            let (version, _, _, chunks) = read(&path);
+            assert_eq!(version, Version::V6Ddnet);
+            assert_eq!(chunks, [Chunk::Tick(1, true)]);
+            fs::remove_file(&path).unwrap();
+        }
+    }
 }
@@ -569,7 +569,7 @@ impl<'a, L: Loop> MainLoop<'a, L> {
                                 // chunk of the packet.
                                 self.loop_.force_flush(pid);
                             }
-                            let tick = peer.snaps.ack_tick().unwrap_or(-1);
+                            let tick = peer.snaps.ack_tick().map(i32::from).unwrap_or(-1);
                             self.loop_.sends(
                                 pid,
                                 Input {
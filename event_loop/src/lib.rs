@@ -9,6 +9,8 @@ extern crate net;
 extern crate socket;
 extern crate warn;
 
+mod tick;
+
 use arrayvec::ArrayVec;
 use common::Takeable;
 use hexdump::hexdump_iter;
@@ -21,12 +23,16 @@ use net::Net;
 use socket::Socket;
 use std::cmp;
 use std::fmt;
+use std::time::Duration;
+use std::time::Instant;
 
 pub use net::collections;
 pub use net::net::PeerId;
+pub use net::ProtocolVersion;
 pub use net::Timeout;
 pub use net::Timestamp;
 pub use socket::Addr;
+pub use tick::TickScheduler;
 
 pub type Chunk<'a> = net::net::Chunk<'a>;
 pub type ConnlessChunk<'a> = net::net::ConnlessChunk<'a, Addr>;
@@ -39,7 +45,19 @@ pub trait Loop {
         Self: Sized;
 
     fn time(&mut self) -> Timestamp;
+    /// Converts a time as returned by `time` to an `Instant`, e.g. for
+    /// deadlines outside the loop.
+    fn instant(&mut self, time: Timestamp) -> Instant {
+        let now = self.time();
+        let instant = Instant::now();
+        if time >= now {
+            instant + Timeout::active(time).time_from(now).unwrap()
+        } else {
+            instant - Timeout::active(now).time_from(time).unwrap()
+        }
+    }
     fn connect(&mut self, addr: Addr) -> PeerId;
+    fn connect_with_version(&mut self, addr: Addr, version: ProtocolVersion) -> PeerId;
     fn disconnect(&mut self, pid: PeerId, reason: &[u8]);
     fn send_connless(&mut self, addr: Addr, data: &[u8]);
     fn send(&mut self, chunk: Chunk);
@@ -48,6 +66,13 @@ pub trait Loop {
     fn ignore(&mut self, pid: PeerId);
     fn accept(&mut self, pid: PeerId);
     fn reject(&mut self, pid: PeerId, reason: &[u8]);
+    /// Shuts the loop down in an orderly fashion.
+    ///
+    /// New peers are no longer accepted. Existing ones are disconnected with
+    /// `reason` once all chunks sent to them have been acked, or after
+    /// `timeout`. Afterwards, `Application::on_shutdown` is called and `run`
+    /// returns.
+    fn shutdown(&mut self, reason: &[u8], timeout: Duration);
 }
 
 pub trait Application<L: Loop> {
@@ -58,6 +83,15 @@ pub trait Application<L: Loop> {
     fn on_connect(&mut self, loop_: &mut L, pid: PeerId);
     fn on_ready(&mut self, loop_: &mut L, pid: PeerId);
     fn on_disconnect(&mut self, loop_: &mut L, pid: PeerId, remote: bool, reason: &[u8]);
+    /// Called once all peers are gone after `Loop::shutdown`.
+    fn on_shutdown(&mut self, loop_: &mut L) {
+        let _ = loop_;
+    }
+}
+
+struct Shutdown {
+    reason: ArrayVec<[u8; 1024]>,
+    deadline: Timestamp,
 }
 
 pub struct SocketLoop {
@@ -65,6 +99,7 @@ pub struct SocketLoop {
     net: Net<Addr>,
     want_to_flush: PeerSet,
     disconnected: Takeable<PeerMap<ArrayVec<[u8; 1024]>>>,
+    shutdown: Option<Shutdown>,
     server: bool,
 }
 
@@ -75,6 +110,7 @@ impl Loop for SocketLoop {
             net: Net::server(),
             want_to_flush: PeerSet::new(),
             disconnected: Default::default(),
+            shutdown: None,
             server: true,
         }
     }
@@ -84,6 +120,7 @@ impl Loop for SocketLoop {
             net: Net::client(),
             want_to_flush: PeerSet::new(),
             disconnected: Default::default(),
+            shutdown: None,
             server: false,
         }
     }
@@ -95,19 +132,57 @@ impl Loop for SocketLoop {
             self.net
                 .tick(&mut self.socket)
                 .foreach(|e| panic!("{:?}", e));
+            let timed_out: Vec<_> = self.net.timed_out().collect();
+            for pid in timed_out {
+                if self.want_to_flush.contains(pid) {
+                    self.want_to_flush.remove(pid);
+                }
+                self.net.ignore(pid);
+                application.on_disconnect(&mut self, pid, true, b"Timeout");
+            }
             application.on_tick(&mut self);
 
             for pid in self.want_to_flush.drain() {
                 self.net.flush(&mut self.socket, pid).unwrap();
             }
 
+            if let Some(ref shutdown) = self.shutdown {
+                let now = self.socket.time();
+                let drained: Vec<_> = self
+                    .net
+                    .peers()
+                    .filter(|&pid| now >= shutdown.deadline || self.net.is_drained(pid))
+                    .collect();
+                for pid in drained {
+                    // Peers that are already gone have been reported.
+                    if self.net.is_disconnected(pid) {
+                        self.net.ignore(pid);
+                        continue;
+                    }
+                    self.disconnected.insert(pid, shutdown.reason.clone());
+                    self.net
+                        .reject(&mut self.socket, pid, &shutdown.reason)
+                        .unwrap();
+                }
+            }
+
             let mut disconnected = self.disconnected.take();
             for (pid, reason) in disconnected.drain() {
                 application.on_disconnect(&mut self, pid, false, &reason);
             }
             self.disconnected.restore(disconnected);
 
-            let sleep_timeout = cmp::min(self.net.needs_tick(), application.needs_tick());
+            if self.shutdown.is_some() && self.net.peers().next().is_none() {
+                application.on_shutdown(&mut self);
+                break;
+            }
+
+            let mut sleep_timeout = cmp::min(self.net.needs_tick(), application.needs_tick());
+            if let Some(ref shutdown) = self.shutdown {
+                sleep_timeout = cmp::min(sleep_timeout, Timeout::active(shutdown.deadline));
+            }
+            // Handling packets and ticks takes time, don't oversleep.
+            self.socket.update_time_cached();
             let sleep_duration = sleep_timeout.time_from(self.socket.time());
             if !self.server && sleep_duration.is_none() {
                 break;
@@ -153,11 +228,21 @@ impl Loop for SocketLoop {
     fn time(&mut self) -> Timestamp {
         self.socket.time()
     }
+    fn instant(&mut self, time: Timestamp) -> Instant {
+        self.socket.instant(time)
+    }
     fn connect(&mut self, addr: Addr) -> PeerId {
         let (pid, res) = self.net.connect(&mut self.socket, addr);
         res.unwrap();
         pid
     }
+    fn connect_with_version(&mut self, addr: Addr, version: ProtocolVersion) -> PeerId {
+        let (pid, res) = self
+            .net
+            .connect_with_version(&mut self.socket, addr, version);
+        res.unwrap();
+        pid
+    }
     fn disconnect(&mut self, pid: PeerId, reason: &[u8]) {
         if self.want_to_flush.contains(pid) {
             self.net.flush(&mut self.socket, pid).unwrap();
@@ -193,6 +278,13 @@ impl Loop for SocketLoop {
     fn reject(&mut self, pid: PeerId, reason: &[u8]) {
         self.net.reject(&mut self.socket, pid, reason).unwrap();
     }
+    fn shutdown(&mut self, reason: &[u8], timeout: Duration) {
+        self.net.set_accept_connections(false);
+        self.shutdown = Some(Shutdown {
+            reason: reason.iter().cloned().collect(),
+            deadline: self.socket.time() + timeout,
+        });
+    }
 }
 
 fn hexdump(level: LogLevel, data: &[u8]) {
@@ -0,0 +1,144 @@
+use net::Timeout;
+use net::Timestamp;
+use std::cmp;
+use std::ops::Range;
+use std::time::Duration;
+
+/// Schedules ticks at a fixed rate, e.g. the 50 game ticks per second.
+///
+/// Tick times are derived from the start time rather than from the previous
+/// tick, so late wakeups don't accumulate. Ticks missed under load are
+/// caught up, but at most `max_catch_up` at once; older ones are skipped.
+#[derive(Clone, Debug)]
+pub struct TickScheduler {
+    start: Timestamp,
+    ticks_per_second: u32,
+    tick: u64,
+    max_catch_up: u64,
+    skipped: u64,
+}
+
+impl TickScheduler {
+    /// Tick 0 happens at `start` and counts as already run.
+    pub fn new(start: Timestamp, ticks_per_second: u32) -> TickScheduler {
+        assert!(ticks_per_second != 0);
+        TickScheduler {
+            start,
+            ticks_per_second,
+            tick: 0,
+            max_catch_up: cmp::max(1, ticks_per_second as u64 / 5),
+            skipped: 0,
+        }
+    }
+    /// Limits the number of ticks run at once after falling behind, a fifth
+    /// of a second worth of ticks by default.
+    pub fn set_max_catch_up(&mut self, max: u32) {
+        assert!(max != 0);
+        self.max_catch_up = max as u64;
+    }
+    /// The last tick returned by `due`.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+    /// Number of ticks skipped because of the catch-up limit.
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+    pub fn tick_time(&self, tick: u64) -> Timestamp {
+        let tps = self.ticks_per_second as u64;
+        let subsec = (tick % tps) * 1_000_000_000 / tps;
+        self.start + Duration::from_secs(tick / tps) + Duration::from_nanos(subsec)
+    }
+    pub fn needs_tick(&self) -> Timeout {
+        Timeout::active(self.tick_time(self.tick + 1))
+    }
+    /// Returns the ticks to run at `now`, in order.
+    pub fn due(&mut self, now: Timestamp) -> Range<u64> {
+        let elapsed = Timeout::active(now).time_from(self.start).unwrap();
+        let latest = (elapsed.as_nanos() * self.ticks_per_second as u128 / 1_000_000_000) as u64;
+        if latest <= self.tick {
+            return self.tick + 1..self.tick + 1;
+        }
+        let first = cmp::max(
+            self.tick + 1,
+            (latest + 1).saturating_sub(self.max_catch_up),
+        );
+        self.skipped += first - (self.tick + 1);
+        self.tick = latest;
+        first..latest + 1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TickScheduler;
+    use net::Timeout;
+    use net::Timestamp;
+    use std::time::Duration;
+
+    fn start() -> Timestamp {
+        Timestamp::from_secs_since_epoch(100)
+    }
+
+    fn at(ms: u64) -> Timestamp {
+        start() + Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn on_time() {
+        let mut ticks = TickScheduler::new(start(), 50);
+        assert_eq!(ticks.due(at(0)), 1..1);
+        assert_eq!(ticks.needs_tick(), Timeout::active(at(20)));
+        assert_eq!(ticks.due(at(19)), 1..1);
+        assert_eq!(ticks.due(at(20)), 1..2);
+        assert_eq!(ticks.due(at(20)), 2..2);
+        assert_eq!(ticks.due(at(40)), 2..3);
+        assert_eq!(ticks.tick(), 2);
+        assert_eq!(ticks.skipped(), 0);
+    }
+
+    #[test]
+    fn late_wakeup() {
+        let mut ticks = TickScheduler::new(start(), 50);
+        assert_eq!(ticks.due(at(35)), 1..2);
+        // The next tick is still scheduled relative to the start, not to the
+        // late wakeup.
+        assert_eq!(ticks.needs_tick(), Timeout::active(at(40)));
+        assert_eq!(ticks.due(at(41)), 2..3);
+        assert_eq!(ticks.needs_tick(), Timeout::active(at(60)));
+    }
+
+    #[test]
+    fn catch_up() {
+        let mut ticks = TickScheduler::new(start(), 50);
+        assert_eq!(ticks.due(at(105)), 1..6);
+        assert_eq!(ticks.skipped(), 0);
+        assert_eq!(ticks.needs_tick(), Timeout::active(at(120)));
+    }
+
+    #[test]
+    fn skipped_ticks() {
+        let mut ticks = TickScheduler::new(start(), 50);
+        // Only a fifth of a second worth of ticks is caught up by default.
+        assert_eq!(ticks.due(at(1000)), 41..51);
+        assert_eq!(ticks.skipped(), 40);
+        assert_eq!(ticks.tick(), 50);
+        assert_eq!(ticks.due(at(1020)), 51..52);
+
+        ticks.set_max_catch_up(1);
+        assert_eq!(ticks.due(at(1100)), 55..56);
+        assert_eq!(ticks.skipped(), 43);
+    }
+
+    #[test]
+    fn tick_time_no_drift() {
+        let ticks = TickScheduler::new(start(), 3);
+        assert_eq!(ticks.tick_time(0), start());
+        assert_eq!(
+            ticks.tick_time(1),
+            start() + Duration::from_nanos(333_333_333)
+        );
+        assert_eq!(ticks.tick_time(3), at(1000));
+        assert_eq!(ticks.tick_time(3_000_000), at(1_000_000_000));
+    }
+}
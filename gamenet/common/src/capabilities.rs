@@ -0,0 +1,122 @@
+//! Server capabilities, sent by DDNet servers in the `Capabilities` system
+//! message during the handshake.
+
+pub const SERVERCAP_CURVERSION: i32 = 5;
+
+pub const SERVERCAPFLAG_DDNET: i32 = 1 << 0;
+pub const SERVERCAPFLAG_CHATTIMEOUTCODE: i32 = 1 << 1;
+pub const SERVERCAPFLAG_ANYPLAYERFLAG: i32 = 1 << 2;
+pub const SERVERCAPFLAG_PINGEX: i32 = 1 << 3;
+pub const SERVERCAPFLAG_ALLOWDUMMY: i32 = 1 << 4;
+pub const SERVERCAPFLAG_SYNCWEAPONINPUT: i32 = 1 << 5;
+
+/// Features a server supports.
+///
+/// Servers not sending the `Capabilities` message at all are described by
+/// `Capabilities::default()`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Capabilities {
+    pub ddnet: bool,
+    pub chat_timeout_code: bool,
+    pub any_player_flag: bool,
+    pub ping_ex: bool,
+    pub allow_dummy: bool,
+    pub sync_weapon_input: bool,
+}
+
+impl Default for Capabilities {
+    fn default() -> Capabilities {
+        Capabilities {
+            ddnet: false,
+            chat_timeout_code: false,
+            any_player_flag: false,
+            ping_ex: false,
+            allow_dummy: true,
+            sync_weapon_input: false,
+        }
+    }
+}
+
+impl Capabilities {
+    /// Interprets the `version` and `flags` fields of the message.
+    ///
+    /// Flags that didn't exist yet in the sent version get their default
+    /// value, like in the reference client.
+    pub fn from_msg(version: i32, flags: i32) -> Capabilities {
+        let has = |flag: i32| flags & flag != 0;
+        let ddnet = version >= 1 && has(SERVERCAPFLAG_DDNET);
+        let mut result = Capabilities {
+            ddnet,
+            chat_timeout_code: ddnet,
+            any_player_flag: ddnet,
+            ..Default::default()
+        };
+        if version >= 1 {
+            result.chat_timeout_code = has(SERVERCAPFLAG_CHATTIMEOUTCODE);
+        }
+        if version >= 2 {
+            result.any_player_flag = has(SERVERCAPFLAG_ANYPLAYERFLAG);
+        }
+        if version >= 3 {
+            result.ping_ex = has(SERVERCAPFLAG_PINGEX);
+        }
+        if version >= 4 {
+            result.allow_dummy = has(SERVERCAPFLAG_ALLOWDUMMY);
+        }
+        if version >= 5 {
+            result.sync_weapon_input = has(SERVERCAPFLAG_SYNCWEAPONINPUT);
+        }
+        result
+    }
+    /// Returns the `version` and `flags` fields to send.
+    pub fn to_msg(self) -> (i32, i32) {
+        let flag = |set: bool, flag: i32| if set { flag } else { 0 };
+        let flags = flag(self.ddnet, SERVERCAPFLAG_DDNET)
+            | flag(self.chat_timeout_code, SERVERCAPFLAG_CHATTIMEOUTCODE)
+            | flag(self.any_player_flag, SERVERCAPFLAG_ANYPLAYERFLAG)
+            | flag(self.ping_ex, SERVERCAPFLAG_PINGEX)
+            | flag(self.allow_dummy, SERVERCAPFLAG_ALLOWDUMMY)
+            | flag(self.sync_weapon_input, SERVERCAPFLAG_SYNCWEAPONINPUT);
+        (SERVERCAP_CURVERSION, flags)
+    }
+    /// Capabilities of a current DDNet server.
+    pub fn ddnet() -> Capabilities {
+        Capabilities {
+            ddnet: true,
+            chat_timeout_code: true,
+            any_player_flag: true,
+            ping_ex: true,
+            allow_dummy: true,
+            sync_weapon_input: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Capabilities;
+    use super::SERVERCAPFLAG_DDNET;
+
+    #[test]
+    fn roundtrip() {
+        let caps = Capabilities::ddnet();
+        let (version, flags) = caps.to_msg();
+        assert_eq!(Capabilities::from_msg(version, flags), caps);
+        let caps = Capabilities {
+            allow_dummy: false,
+            ..caps
+        };
+        let (version, flags) = caps.to_msg();
+        assert_eq!(Capabilities::from_msg(version, flags), caps);
+    }
+
+    #[test]
+    fn old_version() {
+        let caps = Capabilities::from_msg(0, SERVERCAPFLAG_DDNET);
+        assert_eq!(caps, Capabilities::default());
+        let caps = Capabilities::from_msg(3, SERVERCAPFLAG_DDNET);
+        assert!(caps.ddnet);
+        assert!(!caps.chat_timeout_code);
+        assert!(caps.allow_dummy);
+    }
+}
@@ -0,0 +1,177 @@
+//! Version-independent handling of chat messages (`ClSay` and `SvChat`).
+
+/// Prefix of chat commands in DDNet and most mods.
+pub const COMMAND_PREFIX: u8 = b'/';
+
+// Values of `SvChat::team` in DDNet.
+pub const DDNET_CHAT_ALL: i32 = 0;
+pub const DDNET_CHAT_TEAM: i32 = 1;
+pub const DDNET_CHAT_WHISPER_SEND: i32 = 2;
+pub const DDNET_CHAT_WHISPER_RECV: i32 = 3;
+
+// Values of `ClSay::mode` and `SvChat::mode` in 0.7.
+pub const CHAT_0_7_ALL: i32 = 1;
+pub const CHAT_0_7_TEAM: i32 = 2;
+pub const CHAT_0_7_WHISPER: i32 = 3;
+
+/// The recipients of a chat message.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ChatTarget {
+    All,
+    Team,
+    Whisper(i32),
+}
+
+impl ChatTarget {
+    /// Converts the `team` flag of 0.5/0.6 `ClSay` and `SvChat`.
+    pub fn from_team_flag(team: bool) -> ChatTarget {
+        if team {
+            ChatTarget::Team
+        } else {
+            ChatTarget::All
+        }
+    }
+    /// Converts the `mode` and `target` fields of 0.7 `ClSay`.
+    pub fn from_0_7(mode: i32, target: i32) -> Option<ChatTarget> {
+        Some(match mode {
+            CHAT_0_7_ALL => ChatTarget::All,
+            CHAT_0_7_TEAM => ChatTarget::Team,
+            CHAT_0_7_WHISPER => ChatTarget::Whisper(target),
+            _ => return None,
+        })
+    }
+    /// Returns the `mode` and `target` fields for 0.7 `ClSay`.
+    pub fn to_0_7(self) -> (i32, i32) {
+        match self {
+            ChatTarget::All => (CHAT_0_7_ALL, -1),
+            ChatTarget::Team => (CHAT_0_7_TEAM, -1),
+            ChatTarget::Whisper(target) => (CHAT_0_7_WHISPER, target),
+        }
+    }
+    /// Returns whether a message sent by `sender` in team `sender_team`
+    /// is delivered to `receiver` in team `receiver_team`.
+    ///
+    /// The sender always sees their own messages.
+    pub fn delivers_to(
+        self,
+        sender: i32,
+        sender_team: i32,
+        receiver: i32,
+        receiver_team: i32,
+    ) -> bool {
+        if sender == receiver {
+            return true;
+        }
+        match self {
+            ChatTarget::All => true,
+            ChatTarget::Team => sender_team == receiver_team,
+            ChatTarget::Whisper(target) => target == receiver,
+        }
+    }
+}
+
+/// Returns the `team` value of a DDNet `SvChat` sent to `receiver`, along
+/// with the client ID to put into the message.
+///
+/// Whispers are shown differently to sender and receiver: the sender gets
+/// the ID of the receiver and vice versa.
+pub fn ddnet_sv_chat_team(target: ChatTarget, sender: i32, receiver: i32) -> (i32, i32) {
+    match target {
+        ChatTarget::All => (DDNET_CHAT_ALL, sender),
+        ChatTarget::Team => (DDNET_CHAT_TEAM, sender),
+        ChatTarget::Whisper(to) if receiver == sender => (DDNET_CHAT_WHISPER_SEND, to),
+        ChatTarget::Whisper(_) => (DDNET_CHAT_WHISPER_RECV, sender),
+    }
+}
+
+/// A chat command like `/kill` or `/w nameless tee hello`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Command<'a> {
+    pub name: &'a [u8],
+    /// The rest of the message, with leading whitespace removed.
+    pub args: &'a [u8],
+}
+
+impl<'a> Command<'a> {
+    /// Parses a chat message starting with `prefix` as a command.
+    ///
+    /// Returns `None` for regular chat messages.
+    pub fn parse(message: &'a [u8], prefix: u8) -> Option<Command<'a>> {
+        if message.first() != Some(&prefix) {
+            return None;
+        }
+        let rest = &message[1..];
+        let name_len = rest.iter().position(|&b| b == b' ').unwrap_or(rest.len());
+        if name_len == 0 {
+            return None;
+        }
+        let (name, args) = rest.split_at(name_len);
+        Some(Command {
+            name,
+            args: trim_start(args),
+        })
+    }
+    /// Iterates over the space-separated arguments.
+    pub fn split_args(&self) -> impl Iterator<Item = &'a [u8]> {
+        self.args.split(|&b| b == b' ').filter(|a| !a.is_empty())
+    }
+    /// Parses the arguments of a whisper command, `name message` or
+    /// `"name with spaces" message`.
+    ///
+    /// Returns the name of the receiver and the message.
+    pub fn whisper_args(&self) -> Option<(&'a [u8], &'a [u8])> {
+        let args = self.args;
+        if args.first() == Some(&b'"') {
+            let len = args[1..].iter().position(|&b| b == b'"')?;
+            let name = &args[1..1 + len];
+            Some((name, trim_start(&args[2 + len..])))
+        } else {
+            let len = args.iter().position(|&b| b == b' ')?;
+            Some((&args[..len], trim_start(&args[len..])))
+        }
+    }
+}
+
+/// Returns whether the command is a DDNet whisper command.
+pub fn is_whisper_command(name: &[u8]) -> bool {
+    name == b"w" || name == b"whisper"
+}
+
+fn trim_start(s: &[u8]) -> &[u8] {
+    let start = s.iter().position(|&b| b != b' ').unwrap_or(s.len());
+    &s[start..]
+}
+
+#[cfg(test)]
+mod test {
+    use super::ChatTarget;
+    use super::Command;
+    use super::COMMAND_PREFIX;
+
+    #[test]
+    fn command() {
+        assert_eq!(Command::parse(b"hello", COMMAND_PREFIX), None);
+        assert_eq!(Command::parse(b"/", COMMAND_PREFIX), None);
+        let cmd = Command::parse(b"/w \"nameless tee\"  hi there", COMMAND_PREFIX).unwrap();
+        assert_eq!(cmd.name, b"w");
+        assert_eq!(
+            cmd.whisper_args(),
+            Some((&b"nameless tee"[..], &b"hi there"[..]))
+        );
+        let cmd = Command::parse(b"/kill", COMMAND_PREFIX).unwrap();
+        assert_eq!(cmd.args, b"");
+        assert_eq!(cmd.whisper_args(), None);
+        let cmd = Command::parse(b"/pause  a b", COMMAND_PREFIX).unwrap();
+        assert_eq!(cmd.split_args().collect::<Vec<_>>(), [b"a", b"b"]);
+    }
+
+    #[test]
+    fn routing() {
+        assert!(ChatTarget::Team.delivers_to(0, 1, 2, 1));
+        assert!(!ChatTarget::Team.delivers_to(0, 1, 2, 0));
+        assert!(ChatTarget::Whisper(2).delivers_to(0, 1, 0, 1));
+        assert!(!ChatTarget::Whisper(2).delivers_to(0, 1, 3, 1));
+        assert_eq!(ChatTarget::from_0_7(3, 5), Some(ChatTarget::Whisper(5)));
+        assert_eq!(ChatTarget::from_0_7(0, 5), None);
+    }
+}
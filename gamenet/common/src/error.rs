@@ -1,16 +1,54 @@
+use buffer::CapacityError;
 use packer;
+use packer::Unpacker;
+use packer::Warning;
+use warn::Warn;
 
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Error {
     ControlCharacters,
     IntOutOfRange,
     InvalidIntString,
+    MissingValue,
+    /// The data violates the `DecodePolicy::Strict` of the unpacker.
+    PolicyViolation,
+    StringTooLong,
     UnexpectedEnd,
     UnknownId,
 }
 
 pub struct InvalidIntString;
 
+/// Reports decoding that failed because of the `DecodePolicy::Strict` of the
+/// unpacker as `Error::PolicyViolation`.
+pub fn check_policy<T>(p: &Unpacker, result: Result<T, Error>) -> Result<T, Error> {
+    match p.policy_violation() {
+        Some(_) => Err(Error::PolicyViolation),
+        None => result,
+    }
+}
+
+/// Finishes decoding a message with `Unpacker::finish` and applies the
+/// `DecodePolicy` of the unpacker, see `check_policy`.
+pub fn finish<W, T>(warn: &mut W, p: &mut Unpacker, result: Result<T, Error>) -> Result<T, Error>
+where
+    W: Warn<Warning>,
+{
+    if result.is_ok() {
+        p.finish(warn);
+    }
+    check_policy(p, result)
+}
+
+/// Error returned when encoding a message or snapshot object.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum EncodeError {
+    /// The output buffer is too small.
+    Capacity(CapacityError),
+    /// A member violates the protocol.
+    Invalid(Error),
+}
+
 impl From<packer::ControlCharacters> for Error {
     fn from(_: packer::ControlCharacters) -> Error {
         Error::ControlCharacters
@@ -34,3 +72,33 @@ impl From<packer::UnexpectedEnd> for Error {
         Error::UnexpectedEnd
     }
 }
+
+impl From<packer::StringTooLong> for Error {
+    fn from(_: packer::StringTooLong) -> Error {
+        Error::StringTooLong
+    }
+}
+
+impl From<CapacityError> for EncodeError {
+    fn from(e: CapacityError) -> EncodeError {
+        EncodeError::Capacity(e)
+    }
+}
+
+impl From<Error> for EncodeError {
+    fn from(e: Error) -> EncodeError {
+        EncodeError::Invalid(e)
+    }
+}
+
+impl From<packer::ControlCharacters> for EncodeError {
+    fn from(e: packer::ControlCharacters) -> EncodeError {
+        EncodeError::Invalid(e.into())
+    }
+}
+
+impl From<packer::IntOutOfRange> for EncodeError {
+    fn from(e: packer::IntOutOfRange) -> EncodeError {
+        EncodeError::Invalid(e.into())
+    }
+}
@@ -0,0 +1,184 @@
+//! Server-side bookkeeping for the `Input` system message.
+
+use common::time::Tick;
+use common::time::TickRate;
+use std::time::Duration;
+
+/// How far ahead of the current tick the intended tick of an input may be.
+///
+/// Clients predict a few hundred milliseconds ahead at most, anything
+/// beyond this is a broken or malicious client.
+pub const MAX_INPUT_AHEAD: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InputError {
+    /// An input for the same or a later tick has already been accepted.
+    Stale,
+    /// The intended tick is more than `MAX_INPUT_AHEAD` in the future.
+    TooFarAhead,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AcceptedInput {
+    /// Tick at which the input takes effect.
+    ///
+    /// Inputs arriving too late for their intended tick are applied in the
+    /// next tick instead.
+    pub tick: Tick,
+    /// Whether the input differs from the previously accepted one.
+    pub changed: bool,
+}
+
+/// Checks the intended tick of an input against the current server tick.
+///
+/// Returns the tick the input should be applied at.
+pub fn check_intended_tick(
+    current: Tick,
+    intended: Tick,
+    rate: TickRate,
+) -> Result<Tick, InputError> {
+    let max_ahead = rate.to_ticks(MAX_INPUT_AHEAD);
+    if intended.wrapping_diff(current) as i64 > max_ahead as i64 {
+        return Err(InputError::TooFarAhead);
+    }
+    let next = current.wrapping_add(1);
+    Ok(if next.is_after(intended) {
+        next
+    } else {
+        intended
+    })
+}
+
+/// Time in milliseconds from the arrival of an input to the start of its
+/// intended tick, negative if it arrived late.
+///
+/// `since_current` is the time elapsed since the start of the current
+/// tick. This is the value the server reports back in `InputTiming`, the
+/// client uses it to adjust its prediction margin.
+pub fn prediction_margin(
+    current: Tick,
+    intended: Tick,
+    since_current: Duration,
+    rate: TickRate,
+) -> i32 {
+    let ticks = intended.wrapping_diff(current) as i64;
+    let ahead_ms = ticks * 1000 / rate.ticks_per_second() as i64;
+    let since_ms = since_current.as_secs() as i64 * 1000 + since_current.subsec_millis() as i64;
+    (ahead_ms - since_ms) as i32
+}
+
+/// Tracks the inputs of a single client, dropping duplicates.
+///
+/// Clients send each input several times to make up for packet loss, only
+/// the first copy reaching the server is accepted.
+#[derive(Clone, Debug)]
+pub struct InputTracker<T> {
+    last_tick: Option<Tick>,
+    last_input: Option<T>,
+}
+
+impl<T> Default for InputTracker<T> {
+    fn default() -> InputTracker<T> {
+        InputTracker {
+            last_tick: None,
+            last_input: None,
+        }
+    }
+}
+
+impl<T: Copy + PartialEq> InputTracker<T> {
+    pub fn new() -> InputTracker<T> {
+        Default::default()
+    }
+    /// Intended tick of the last accepted input.
+    pub fn last_tick(&self) -> Option<Tick> {
+        self.last_tick
+    }
+    pub fn last_input(&self) -> Option<T> {
+        self.last_input
+    }
+    pub fn process(
+        &mut self,
+        current: Tick,
+        intended: Tick,
+        input: T,
+        rate: TickRate,
+    ) -> Result<AcceptedInput, InputError> {
+        if let Some(last) = self.last_tick {
+            if !intended.is_after(last) {
+                return Err(InputError::Stale);
+            }
+        }
+        let tick = check_intended_tick(current, intended, rate)?;
+        let changed = self.last_input != Some(input);
+        self.last_tick = Some(intended);
+        self.last_input = Some(input);
+        Ok(AcceptedInput { tick, changed })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::check_intended_tick;
+    use super::prediction_margin;
+    use super::AcceptedInput;
+    use super::InputError;
+    use super::InputTracker;
+    use common::time::Tick;
+    use common::time::DEFAULT_TICK_RATE;
+    use std::time::Duration;
+
+    #[test]
+    fn intended_tick() {
+        let rate = DEFAULT_TICK_RATE;
+        assert_eq!(
+            check_intended_tick(Tick(100), Tick(105), rate),
+            Ok(Tick(105))
+        );
+        assert_eq!(
+            check_intended_tick(Tick(100), Tick(90), rate),
+            Ok(Tick(101))
+        );
+        assert_eq!(
+            check_intended_tick(Tick(100), Tick(150), rate),
+            Ok(Tick(150))
+        );
+        assert_eq!(
+            check_intended_tick(Tick(100), Tick(151), rate),
+            Err(InputError::TooFarAhead),
+        );
+    }
+
+    #[test]
+    fn margin() {
+        let rate = DEFAULT_TICK_RATE;
+        let since = Duration::from_millis(5);
+        assert_eq!(prediction_margin(Tick(100), Tick(102), since, rate), 35);
+        assert_eq!(prediction_margin(Tick(100), Tick(100), since, rate), -5);
+    }
+
+    #[test]
+    fn dedup() {
+        let rate = DEFAULT_TICK_RATE;
+        let mut tracker = InputTracker::new();
+        assert_eq!(
+            tracker.process(Tick(100), Tick(102), 1, rate),
+            Ok(AcceptedInput {
+                tick: Tick(102),
+                changed: true
+            }),
+        );
+        assert_eq!(
+            tracker.process(Tick(100), Tick(102), 1, rate),
+            Err(InputError::Stale)
+        );
+        assert_eq!(
+            tracker.process(Tick(101), Tick(103), 1, rate),
+            Ok(AcceptedInput {
+                tick: Tick(103),
+                changed: false
+            }),
+        );
+        assert_eq!(tracker.last_tick(), Some(Tick(103)));
+    }
+}
@@ -8,7 +8,15 @@ extern crate serde_derive;
 extern crate uuid;
 extern crate warn;
 
+pub mod capabilities;
+pub mod chat;
 pub mod debug;
 pub mod error;
+pub mod input;
+pub mod map_download;
 pub mod msg;
+pub mod rcon;
+pub mod serialize;
 pub mod snap_obj;
+pub mod version;
+pub mod vote;
@@ -0,0 +1,338 @@
+//! Map transfer over the game connection.
+//!
+//! The server splits the map into chunks, sent as `MapData` messages in
+//! response to `RequestMapData`. 0.6 clients request each chunk by its index,
+//! 0.7 clients ask for the next `chunks_per_request` chunks at once without
+//! specifying an index.
+
+use common::digest;
+use common::digest::Sha256;
+use common::num::Cast;
+use std::cmp;
+use std::error;
+use std::fmt;
+use std::ops::Range;
+
+/// Chunk size used by Teeworlds 0.6 servers.
+pub const CHUNK_SIZE_0_6: u32 = 1024 - 128;
+
+/// A chunk of map data, the payload of a `MapData` message.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Chunk<'a> {
+    pub index: u32,
+    pub last: bool,
+    pub data: &'a [u8],
+}
+
+/// Server side of the map transfer.
+#[derive(Clone, Debug)]
+pub struct Sender {
+    data: Vec<u8>,
+    chunk_size: u32,
+    chunks_per_request: u32,
+}
+
+/// Progress of the transfer to a single client, see `Sender::request`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Transfer {
+    next: u32,
+}
+
+impl Transfer {
+    pub fn new() -> Transfer {
+        Default::default()
+    }
+}
+
+impl Sender {
+    pub fn new(data: Vec<u8>, chunk_size: u32, chunks_per_request: u32) -> Sender {
+        assert!(chunk_size != 0 && chunks_per_request != 0);
+        assert!(data.len().try_i32().is_some(), "map too large");
+        Sender {
+            data,
+            chunk_size,
+            chunks_per_request,
+        }
+    }
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+    pub fn chunk_size(&self) -> u32 {
+        self.chunk_size
+    }
+    pub fn chunks_per_request(&self) -> u32 {
+        self.chunks_per_request
+    }
+    pub fn num_chunks(&self) -> u32 {
+        let size = self.data.len().assert_u32();
+        size.div_ceil(self.chunk_size)
+    }
+    pub fn chunk(&self, index: u32) -> Option<Chunk<'_>> {
+        if index >= self.num_chunks() {
+            return None;
+        }
+        let start = index.usize() * self.chunk_size.usize();
+        let end = cmp::min(start + self.chunk_size.usize(), self.data.len());
+        Some(Chunk {
+            index,
+            last: end == self.data.len(),
+            data: &self.data[start..end],
+        })
+    }
+    /// Answers a `RequestMapData` message.
+    ///
+    /// Requests for a specific chunk, as sent by 0.6 clients, yield just that
+    /// chunk. Otherwise, the next `chunks_per_request` chunks of the transfer
+    /// are returned.
+    pub fn request<'a>(&'a self, transfer: &mut Transfer, index: Option<u32>) -> Chunks<'a> {
+        let range = match index {
+            Some(i) => i..i.saturating_add(1),
+            None => transfer.next..transfer.next.saturating_add(self.chunks_per_request),
+        };
+        let range = range.start..cmp::min(range.end, self.num_chunks());
+        transfer.next = cmp::max(transfer.next, range.end);
+        Chunks {
+            sender: self,
+            range,
+        }
+    }
+}
+
+/// Iterator over the chunks returned by `Sender::request`.
+pub struct Chunks<'a> {
+    sender: &'a Sender,
+    range: Range<u32>,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = Chunk<'a>;
+    fn next(&mut self) -> Option<Chunk<'a>> {
+        let index = self.range.next()?;
+        self.sender.chunk(index)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The chunk isn't the next one expected, it is ignored.
+    UnexpectedChunk(u32),
+    /// More data than announced was received.
+    TooLarge,
+    SizeMismatch,
+    CrcMismatch,
+    Sha256Mismatch,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::UnexpectedChunk(i) => write!(f, "unexpected map chunk {}", i),
+            Error::TooLarge => f.write_str("map data larger than announced"),
+            Error::SizeMismatch => f.write_str("map size mismatch"),
+            Error::CrcMismatch => f.write_str("map CRC mismatch"),
+            Error::Sha256Mismatch => f.write_str("map SHA256 mismatch"),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+/// Client side of the map transfer, reassembling and checking the map.
+#[derive(Clone, Debug)]
+pub struct Receiver {
+    crc: i32,
+    size: u32,
+    sha256: Option<Sha256>,
+    chunks_per_request: u32,
+    data: Vec<u8>,
+    next: u32,
+    requested: u32,
+}
+
+impl Receiver {
+    /// `sha256` is only announced by 0.7 and DDNet servers. 0.6 servers
+    /// send a single chunk per request.
+    pub fn new(crc: i32, size: u32, sha256: Option<Sha256>, chunks_per_request: u32) -> Receiver {
+        assert!(chunks_per_request != 0);
+        Receiver {
+            crc,
+            size,
+            sha256,
+            chunks_per_request,
+            data: Vec::with_capacity(size.usize()),
+            next: 0,
+            requested: 0,
+        }
+    }
+    /// Continues an interrupted download from the data received so far.
+    ///
+    /// Incomplete chunks at the end of `partial` are dropped. Only servers
+    /// that let clients request specific chunks, i.e. 0.6 ones, support
+    /// this.
+    pub fn resume(
+        crc: i32,
+        size: u32,
+        sha256: Option<Sha256>,
+        chunk_size: u32,
+        mut partial: Vec<u8>,
+    ) -> Receiver {
+        assert!(chunk_size != 0);
+        let num_chunks = cmp::min(partial.len(), size.usize()) / chunk_size.usize();
+        partial.truncate(num_chunks * chunk_size.usize());
+        let mut result = Receiver::new(crc, size, sha256, 1);
+        result.data.extend_from_slice(&partial);
+        result.next = num_chunks.assert_u32();
+        result.requested = result.next;
+        result
+    }
+    pub fn crc(&self) -> i32 {
+        self.crc
+    }
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+    pub fn sha256(&self) -> Option<Sha256> {
+        self.sha256
+    }
+    /// The data received so far.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+    pub fn is_complete(&self) -> bool {
+        self.data.len() >= self.size.usize()
+    }
+    /// Returns the index of the chunk to request next, if all previously
+    /// requested chunks have arrived.
+    ///
+    /// Requests for more than one chunk per request carry no index on the
+    /// wire.
+    pub fn next_request(&mut self) -> Option<u32> {
+        if self.is_complete() || self.next < self.requested {
+            return None;
+        }
+        self.requested = self.next + self.chunks_per_request;
+        Some(self.next)
+    }
+    /// Adds a received chunk. `index` is only sent by 0.6 servers.
+    pub fn receive(&mut self, index: Option<u32>, data: &[u8]) -> Result<(), Error> {
+        if let Some(index) = index {
+            if index != self.next {
+                return Err(Error::UnexpectedChunk(index));
+            }
+        }
+        if self.data.len() + data.len() > self.size.usize() {
+            return Err(Error::TooLarge);
+        }
+        self.data.extend_from_slice(data);
+        self.next += 1;
+        Ok(())
+    }
+    /// Checks the map against the announced size and checksums.
+    pub fn finish(self) -> Result<Vec<u8>, Error> {
+        if self.data.len() != self.size.usize() {
+            return Err(Error::SizeMismatch);
+        }
+        if digest::crc32(&self.data) as i32 != self.crc {
+            return Err(Error::CrcMismatch);
+        }
+        if let Some(sha256) = self.sha256 {
+            if digest::sha256(&self.data).0 != sha256.0 {
+                return Err(Error::Sha256Mismatch);
+            }
+        }
+        Ok(self.data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Error;
+    use super::Receiver;
+    use super::Sender;
+    use super::Transfer;
+    use common::digest;
+
+    fn map() -> Vec<u8> {
+        (0..2500u32).map(|i| (i * 7) as u8).collect()
+    }
+
+    fn transfer(sender: &Sender, receiver: &mut Receiver, indexed: bool) {
+        let mut transfer = Transfer::new();
+        while let Some(index) = receiver.next_request() {
+            let index = if indexed { Some(index) } else { None };
+            for chunk in sender.request(&mut transfer, index) {
+                let index = if indexed { Some(chunk.index) } else { None };
+                receiver.receive(index, chunk.data).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn chunks() {
+        let sender = Sender::new(map(), 1000, 1);
+        assert_eq!(sender.num_chunks(), 3);
+        assert_eq!(sender.chunk(1).unwrap().data.len(), 1000);
+        assert!(!sender.chunk(1).unwrap().last);
+        assert_eq!(sender.chunk(2).unwrap().data.len(), 500);
+        assert!(sender.chunk(2).unwrap().last);
+        assert!(sender.chunk(3).is_none());
+    }
+
+    #[test]
+    fn indexed() {
+        let data = map();
+        let crc = digest::crc32(&data) as i32;
+        let sender = Sender::new(data.clone(), 1000, 1);
+        let mut receiver = Receiver::new(crc, 2500, None, 1);
+        transfer(&sender, &mut receiver, true);
+        assert_eq!(receiver.finish(), Ok(data));
+    }
+
+    #[test]
+    fn batched() {
+        let data = map();
+        let crc = digest::crc32(&data) as i32;
+        let sha256 = Some(digest::sha256(&data));
+        let sender = Sender::new(data.clone(), 100, 4);
+        let mut receiver = Receiver::new(crc, 2500, sha256, 4);
+        assert_eq!(receiver.next_request(), Some(0));
+        // Nothing is requested until the batch has arrived.
+        assert_eq!(receiver.next_request(), None);
+        for chunk in sender.request(&mut Transfer::new(), None) {
+            receiver.receive(None, chunk.data).unwrap();
+        }
+        assert_eq!(receiver.next_request(), Some(4));
+        let mut receiver = Receiver::new(crc, 2500, sha256, 4);
+        transfer(&sender, &mut receiver, false);
+        assert_eq!(receiver.finish(), Ok(data));
+    }
+
+    #[test]
+    fn resume() {
+        let data = map();
+        let crc = digest::crc32(&data) as i32;
+        let sender = Sender::new(data.clone(), 1000, 1);
+        let mut receiver = Receiver::resume(crc, 2500, None, 1000, data[..1500].to_owned());
+        assert_eq!(receiver.data().len(), 1000);
+        assert_eq!(receiver.next_request(), Some(1));
+        assert_eq!(
+            receiver.receive(Some(2), &data[2000..]),
+            Err(Error::UnexpectedChunk(2))
+        );
+        receiver.receive(Some(1), &data[1000..2000]).unwrap();
+        transfer(&sender, &mut receiver, true);
+        assert_eq!(receiver.finish(), Ok(data));
+    }
+
+    #[test]
+    fn corrupt() {
+        let data = map();
+        let crc = digest::crc32(&data) as i32;
+        let mut receiver = Receiver::new(crc, 2500, None, 1);
+        assert_eq!(receiver.receive(None, &[0; 2501]), Err(Error::TooLarge));
+        receiver.receive(None, &[0; 2500]).unwrap();
+        assert_eq!(receiver.finish(), Err(Error::CrcMismatch));
+        let receiver = Receiver::new(crc, 2500, None, 1);
+        assert_eq!(receiver.finish(), Err(Error::SizeMismatch));
+    }
+}
@@ -1,13 +1,19 @@
 use arrayvec::ArrayVec;
 use buffer::CapacityError;
 use common::num::BeU16;
+use common::num::LeU16;
 use common::slice;
+use common::warn::strip_location;
+use common::warn::Located;
+use error::check_policy;
 use error::Error;
 use error::InvalidIntString;
 use packer::ExcessData;
 use packer::Packer;
 use packer::Unpacker;
 use packer::Warning;
+use serde::Serialize;
+use serde::Serializer;
 use std::fmt;
 use std::io::Write;
 use std::mem;
@@ -22,11 +28,17 @@ pub struct ClientsData<'a> {
     inner: &'a [u8],
 }
 
+impl<'a> Serialize for ClientsData<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.inner)
+    }
+}
+
 impl<'a> ClientsData<'a> {
     pub fn from_bytes(bytes: &[u8]) -> ClientsData {
         ClientsData { inner: bytes }
     }
-    pub fn as_bytes(&self) -> &[u8] {
+    pub fn as_bytes(&self) -> &'a [u8] {
         self.inner
     }
 }
@@ -38,26 +50,101 @@ pub struct AddrPacked {
     port: BeU16,
 }
 
+impl AddrPacked {
+    pub fn new(ip_address: [u8; 16], port: u16) -> AddrPacked {
+        AddrPacked {
+            ip_address,
+            port: BeU16::from_u16(port),
+        }
+    }
+    pub fn ip_address(&self) -> [u8; 16] {
+        self.ip_address
+    }
+    pub fn port(&self) -> u16 {
+        self.port.to_u16()
+    }
+}
+
+impl Serialize for AddrPacked {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let ip_address = self.ip_address;
+        let mut s = serializer.serialize_struct("AddrPacked", 2)?;
+        s.serialize_field("ip_address", &ip_address)?;
+        s.serialize_field("port", &self.port.to_u16())?;
+        s.end()
+    }
+}
+
+/// Server address in the 0.5 server list, IPv4 only and with the port in
+/// little endian.
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct Addr5Packed {
+    ip_address: [u8; 4],
+    port: LeU16,
+}
+
+impl Addr5Packed {
+    pub fn new(ip_address: [u8; 4], port: u16) -> Addr5Packed {
+        Addr5Packed {
+            ip_address,
+            port: LeU16::from_u16(port),
+        }
+    }
+    pub fn ip_address(&self) -> [u8; 4] {
+        self.ip_address
+    }
+    pub fn port(&self) -> u16 {
+        self.port.to_u16()
+    }
+}
+
+impl Serialize for Addr5Packed {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let ip_address = self.ip_address;
+        let mut s = serializer.serialize_struct("Addr5Packed", 2)?;
+        s.serialize_field("ip_address", &ip_address)?;
+        s.serialize_field("port", &self.port.to_u16())?;
+        s.end()
+    }
+}
+
 pub trait AddrPackedSliceExt {
     fn from_bytes<'a, W: Warn<ExcessData>>(warn: &mut W, bytes: &'a [u8]) -> &'a Self;
     fn as_bytes(&self) -> &[u8];
 }
 
+/// Only for the packed address types, which have an alignment of 1.
+fn addrs_from_bytes<'a, T, W: Warn<ExcessData>>(warn: &mut W, bytes: &'a [u8]) -> &'a [T] {
+    let remainder = bytes.len() % mem::size_of::<T>();
+    if remainder != 0 {
+        warn.warn(ExcessData);
+    }
+    let actual_len = bytes.len() - remainder;
+    unsafe { slice::transmute(&bytes[..actual_len]) }
+}
+
 impl AddrPackedSliceExt for [AddrPacked] {
     fn from_bytes<'a, W: Warn<ExcessData>>(warn: &mut W, bytes: &'a [u8]) -> &'a [AddrPacked] {
-        let remainder = bytes.len() % mem::size_of::<AddrPacked>();
-        if remainder != 0 {
-            warn.warn(ExcessData);
-        }
-        let actual_len = bytes.len() - remainder;
-        unsafe { slice::transmute(&bytes[..actual_len]) }
+        addrs_from_bytes(warn, bytes)
     }
     fn as_bytes(&self) -> &[u8] {
         unsafe { slice::transmute(self) }
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+impl AddrPackedSliceExt for [Addr5Packed] {
+    fn from_bytes<'a, W: Warn<ExcessData>>(warn: &mut W, bytes: &'a [u8]) -> &'a [Addr5Packed] {
+        addrs_from_bytes(warn, bytes)
+    }
+    fn as_bytes(&self) -> &[u8] {
+        unsafe { slice::transmute(self) }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct TuneParam(pub i32);
 
 impl TuneParam {
@@ -123,17 +210,17 @@ pub trait Protocol<'a> {
         p: &mut Unpacker<'a>,
     ) -> Result<Self::System, Error>
     where
-        W: Warn<Warning>;
+        W: Warn<Located<Warning>>;
     fn decode_game<W>(
         warn: &mut W,
         id: MessageId,
         p: &mut Unpacker<'a>,
     ) -> Result<Self::Game, Error>
     where
-        W: Warn<Warning>;
+        W: Warn<Located<Warning>>;
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum SystemOrGame<S, G> {
     System(S),
     Game(G),
@@ -159,7 +246,8 @@ impl SystemOrGame<MessageId, MessageId> {
     where
         W: Warn<Warning>,
     {
-        let id = p.read_int(warn)?;
+        let id = p.read_int(warn).map_err(Error::from);
+        let id = check_policy(p, id)?;
         let sys = id & 1 != 0;
         let msg = id >> 1;
         let msg = if msg != 0 {
@@ -199,14 +287,28 @@ impl SystemOrGame<MessageId, MessageId> {
 
 pub fn decode<'a, W, P>(
     warn: &mut W,
-    _proto: P,
+    proto: P,
     p: &mut Unpacker<'a>,
 ) -> Result<SystemOrGame<P::System, P::Game>, Error>
 where
     W: Warn<Warning>,
     P: Protocol<'a>,
 {
-    let msg_id = SystemOrGame::decode_id(warn, p)?;
+    decode_located(&mut strip_location(warn), proto, p)
+}
+
+/// Like `decode`, but attaches the offset and the name of the message field
+/// to each warning.
+pub fn decode_located<'a, W, P>(
+    warn: &mut W,
+    _proto: P,
+    p: &mut Unpacker<'a>,
+) -> Result<SystemOrGame<P::System, P::Game>, Error>
+where
+    W: Warn<Located<Warning>>,
+    P: Protocol<'a>,
+{
+    let msg_id = SystemOrGame::decode_id(&mut p.locate(warn, "message ID"), p)?;
     Ok(match msg_id {
         SystemOrGame::System(msg_id) => SystemOrGame::System(P::decode_system(warn, msg_id, p)?),
         SystemOrGame::Game(msg_id) => SystemOrGame::Game(P::decode_game(warn, msg_id, p)?),
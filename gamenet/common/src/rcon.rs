@@ -0,0 +1,194 @@
+//! Remote console state for both ends of a connection.
+//!
+//! The messages involved are `RconAuth`, `RconAuthStatus`, `RconCmd`,
+//! `RconLine`, `RconCmdAdd`, `RconCmdRemove` and, in DDNet,
+//! `RconCmdGroupStart` and `RconCmdGroupEnd` framing the command list.
+
+use std::collections::BTreeMap;
+
+/// Number of wrong passwords after which a client is refused, like the
+/// default `sv_rcon_max_tries`.
+pub const DEFAULT_MAX_TRIES: u32 = 3;
+
+/// Authentication levels as used by DDNet, 0.6 only knows `Admin`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum AuthLevel {
+    Helper,
+    Moderator,
+    Admin,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LoginOutcome {
+    /// Send `RconAuthStatus` with `auth_level` 1.
+    Authed(AuthLevel),
+    /// Wrong password, the client may try again.
+    Failed { tries_left: u32 },
+    /// Wrong password and no tries left, the client should be kicked or
+    /// banned.
+    TooManyTries,
+}
+
+/// Server-side rcon state of a single client.
+#[derive(Clone, Copy, Debug)]
+pub struct RconServerState {
+    auth_level: Option<AuthLevel>,
+    failed_tries: u32,
+    max_tries: u32,
+}
+
+impl Default for RconServerState {
+    fn default() -> RconServerState {
+        RconServerState::new(DEFAULT_MAX_TRIES)
+    }
+}
+
+impl RconServerState {
+    pub fn new(max_tries: u32) -> RconServerState {
+        RconServerState {
+            auth_level: None,
+            failed_tries: 0,
+            max_tries,
+        }
+    }
+    pub fn auth_level(&self) -> Option<AuthLevel> {
+        self.auth_level
+    }
+    pub fn is_authed(&self) -> bool {
+        self.auth_level.is_some()
+    }
+    /// Records the result of checking the password of an `RconAuth`
+    /// message, `None` if it was wrong.
+    pub fn login(&mut self, result: Option<AuthLevel>) -> LoginOutcome {
+        match result {
+            Some(level) => {
+                self.auth_level = Some(level);
+                self.failed_tries = 0;
+                LoginOutcome::Authed(level)
+            }
+            None => {
+                self.failed_tries += 1;
+                if self.failed_tries >= self.max_tries {
+                    LoginOutcome::TooManyTries
+                } else {
+                    LoginOutcome::Failed {
+                        tries_left: self.max_tries - self.failed_tries,
+                    }
+                }
+            }
+        }
+    }
+    pub fn logout(&mut self) {
+        self.auth_level = None;
+    }
+    /// Whether an `RconCmd` needing `required` may be executed.
+    pub fn may_execute(&self, required: AuthLevel) -> bool {
+        self.auth_level.map(|l| l >= required).unwrap_or(false)
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RconCommand {
+    pub help: Vec<u8>,
+    pub params: Vec<u8>,
+}
+
+/// Client-side rcon state, mirroring the server's command list.
+#[derive(Clone, Debug, Default)]
+pub struct RconClientState {
+    authed: bool,
+    commands: BTreeMap<Vec<u8>, RconCommand>,
+    expected_commands: Option<i32>,
+}
+
+impl RconClientState {
+    pub fn new() -> RconClientState {
+        Default::default()
+    }
+    pub fn is_authed(&self) -> bool {
+        self.authed
+    }
+    /// Handles `RconAuthStatus` (`RconAuthOn`/`RconAuthOff` in 0.7).
+    ///
+    /// Losing authentication clears the command list.
+    pub fn on_auth_status(&mut self, authed: bool) {
+        self.authed = authed;
+        if !authed {
+            self.commands.clear();
+            self.expected_commands = None;
+        }
+    }
+    pub fn on_cmd_add(&mut self, name: &[u8], help: &[u8], params: &[u8]) {
+        self.commands.insert(
+            name.to_vec(),
+            RconCommand {
+                help: help.to_vec(),
+                params: params.to_vec(),
+            },
+        );
+    }
+    pub fn on_cmd_remove(&mut self, name: &[u8]) {
+        self.commands.remove(name);
+    }
+    pub fn on_cmd_group_start(&mut self, length: i32) {
+        self.expected_commands = Some(length);
+    }
+    pub fn on_cmd_group_end(&mut self) {
+        self.expected_commands = None;
+    }
+    /// Whether the command list is still being received.
+    pub fn is_syncing(&self) -> bool {
+        self.expected_commands.is_some()
+    }
+    /// Announced size of the command list while it is being received.
+    pub fn expected_commands(&self) -> Option<i32> {
+        self.expected_commands
+    }
+    pub fn commands(&self) -> &BTreeMap<Vec<u8>, RconCommand> {
+        &self.commands
+    }
+    pub fn command(&self, name: &[u8]) -> Option<&RconCommand> {
+        self.commands.get(name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::AuthLevel;
+    use super::LoginOutcome;
+    use super::RconClientState;
+    use super::RconServerState;
+
+    #[test]
+    fn server_login() {
+        let mut state = RconServerState::new(2);
+        assert_eq!(state.login(None), LoginOutcome::Failed { tries_left: 1 });
+        assert_eq!(
+            state.login(Some(AuthLevel::Moderator)),
+            LoginOutcome::Authed(AuthLevel::Moderator)
+        );
+        assert!(state.may_execute(AuthLevel::Helper));
+        assert!(!state.may_execute(AuthLevel::Admin));
+        state.logout();
+        assert!(!state.may_execute(AuthLevel::Helper));
+        assert_eq!(state.login(None), LoginOutcome::Failed { tries_left: 1 });
+        assert_eq!(state.login(None), LoginOutcome::TooManyTries);
+    }
+
+    #[test]
+    fn client_commands() {
+        let mut state = RconClientState::new();
+        state.on_auth_status(true);
+        state.on_cmd_group_start(2);
+        state.on_cmd_add(b"kick", b"Kick player", b"v[id] ?r[reason]");
+        state.on_cmd_add(b"ban", b"Ban player", b"s[ip|id] ?i[minutes] r[reason]");
+        assert!(state.is_syncing());
+        state.on_cmd_group_end();
+        state.on_cmd_remove(b"ban");
+        assert!(!state.is_syncing());
+        assert_eq!(state.commands().len(), 1);
+        assert_eq!(state.command(b"kick").unwrap().help, b"Kick player");
+        state.on_auth_status(false);
+        assert!(state.commands().is_empty());
+    }
+}
@@ -0,0 +1,133 @@
+//! Helpers for serializing the fields of generated messages.
+//!
+//! Byte strings are serialized as strings if they are valid UTF-8, and as
+//! raw bytes otherwise. The modules are meant for `#[serde(with = "...")]`
+//! on owned fields and `#[serde(serialize_with = "...::serialize")]` on
+//! borrowed ones.
+
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+
+struct Borrowed<'a>(&'a [u8]);
+
+impl<'a> Serialize for Borrowed<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        bytes::serialize(&self.0, serializer)
+    }
+}
+
+struct Owned(Vec<u8>);
+
+impl<'de> Deserialize<'de> for Owned {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Owned, D::Error> {
+        bytes::deserialize(deserializer).map(Owned)
+    }
+}
+
+/// A single byte string.
+pub mod bytes {
+    use serde::de;
+    use serde::Deserializer;
+    use serde::Serializer;
+    use std::fmt;
+    use std::str;
+
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        let bytes = bytes.as_ref();
+        match str::from_utf8(bytes) {
+            Ok(s) => serializer.serialize_str(s),
+            Err(_) => serializer.serialize_bytes(bytes),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        deserializer.deserialize_any(Visitor)
+    }
+
+    struct Visitor;
+
+    impl<'de> de::Visitor<'de> for Visitor {
+        type Value = Vec<u8>;
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a string or a byte array")
+        }
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Vec<u8>, E> {
+            Ok(v.as_bytes().to_vec())
+        }
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+            Ok(v.to_vec())
+        }
+        fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+            let mut result = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(b) = seq.next_element()? {
+                result.push(b);
+            }
+            Ok(result)
+        }
+    }
+}
+
+/// An optional byte string.
+pub mod optional_bytes {
+    use super::Borrowed;
+    use super::Owned;
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serializer;
+
+    pub fn serialize<T, S>(bytes: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        match *bytes {
+            Some(ref b) => serializer.serialize_some(&Borrowed(b.as_ref())),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Option::<Owned>::deserialize(deserializer)?.map(|b| b.0))
+    }
+}
+
+/// A fixed-size array of byte strings.
+pub mod bytes_array {
+    use super::Borrowed;
+    use super::Owned;
+    use serde::de;
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serializer;
+    use std::convert::TryInto;
+
+    pub fn serialize<T, S, const N: usize>(array: &[T; N], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        serializer.collect_seq(array.iter().map(|b| Borrowed(b.as_ref())))
+    }
+
+    pub fn deserialize<'de, D, const N: usize>(deserializer: D) -> Result<[Vec<u8>; N], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let vec: Vec<Vec<u8>> = Vec::<Owned>::deserialize(deserializer)?
+            .into_iter()
+            .map(|b| b.0)
+            .collect();
+        let len = vec.len();
+        vec.try_into()
+            .map_err(|_| de::Error::invalid_length(len, &&*format!("an array of length {}", N)))
+    }
+}
@@ -35,5 +35,181 @@ impl From<Uuid> for TypeId {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct Tick(pub i32);
+pub use common::time::Tick;
+
+/// Wraps an angle given in 1/256 radians into the range of `atan2`, the
+/// range the reference implementation sends.
+pub fn wrap_angle(angle: i32) -> i32 {
+    use std::f64::consts::PI;
+    let radians = angle as f64 / 256.0;
+    let wrapped = radians - 2.0 * PI * ((radians + PI) / (2.0 * PI)).floor();
+    (wrapped * 256.0).round() as i32
+}
+
+/// Description of a snapshot object type, available at runtime.
+///
+/// Allows tools to display snapshot objects by field name without
+/// compiling against a specific protocol version.
+#[derive(Clone, Copy, Debug)]
+pub struct ObjMeta {
+    pub type_id: TypeId,
+    pub name: &'static str,
+    /// Size of the object in ints.
+    pub size: u32,
+    pub fields: &'static [FieldMeta],
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct FieldMeta {
+    pub name: &'static str,
+    /// Number of consecutive ints this field occupies, more than one for
+    /// arrays.
+    pub count: u32,
+    pub kind: FieldKind,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum FieldKind {
+    Int,
+    Range {
+        min: i32,
+        max: i32,
+    },
+    Enum {
+        name: &'static str,
+        offset: i32,
+        values: &'static [&'static str],
+    },
+    Flags {
+        name: &'static str,
+        values: &'static [&'static str],
+    },
+    Bool,
+    Tick,
+}
+
+impl ObjMeta {
+    /// Looks up a field by name, returning it together with its offset in
+    /// ints from the start of the object.
+    pub fn field(&self, name: &str) -> Option<(usize, &'static FieldMeta)> {
+        let mut offset = 0;
+        for f in self.fields {
+            if f.name == name {
+                return Some((offset, f));
+            }
+            offset += f.count as usize;
+        }
+        None
+    }
+    /// Returns the field containing the int at offset `index` from the start
+    /// of the object.
+    pub fn field_at(&self, index: usize) -> Option<&'static FieldMeta> {
+        let mut offset = 0;
+        for f in self.fields {
+            offset += f.count as usize;
+            if index < offset {
+                return Some(f);
+            }
+        }
+        None
+    }
+    /// Iterates over the fields together with the ints belonging to them.
+    ///
+    /// Stops early if `data` is shorter than the object.
+    pub fn split<'a>(
+        &self,
+        data: &'a [i32],
+    ) -> impl Iterator<Item = (&'static FieldMeta, &'a [i32])> {
+        let mut rest = data;
+        self.fields.iter().map_while(move |f| {
+            let count = f.count as usize;
+            if rest.len() < count {
+                return None;
+            }
+            let (cur, next) = rest.split_at(count);
+            rest = next;
+            Some((f, cur))
+        })
+    }
+}
+
+impl FieldKind {
+    /// Returns the symbolic name of `value` for enums.
+    pub fn value_name(&self, value: i32) -> Option<&'static str> {
+        match *self {
+            FieldKind::Enum { offset, values, .. } => {
+                values.get(value.checked_sub(offset)? as usize).cloned()
+            }
+            _ => None,
+        }
+    }
+    /// Returns whether `value` lies in the range allowed for this field.
+    pub fn is_valid(&self, value: i32) -> bool {
+        match *self {
+            FieldKind::Int | FieldKind::Tick | FieldKind::Flags { .. } => true,
+            FieldKind::Range { min, max } => min <= value && value <= max,
+            FieldKind::Enum { .. } => self.value_name(value).is_some(),
+            FieldKind::Bool => value == 0 || value == 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::wrap_angle;
+    use super::FieldKind;
+    use super::FieldMeta;
+    use super::ObjMeta;
+    use super::TypeId;
+
+    #[test]
+    fn field_at() {
+        let meta = ObjMeta {
+            type_id: TypeId::Ordinal(1),
+            name: "obj",
+            size: 4,
+            fields: &[
+                FieldMeta {
+                    name: "a",
+                    count: 1,
+                    kind: FieldKind::Int,
+                },
+                FieldMeta {
+                    name: "b",
+                    count: 2,
+                    kind: FieldKind::Int,
+                },
+                FieldMeta {
+                    name: "c",
+                    count: 1,
+                    kind: FieldKind::Int,
+                },
+            ],
+        };
+        let name = |i| meta.field_at(i).map(|f| f.name);
+        assert_eq!(name(0), Some("a"));
+        assert_eq!(name(1), Some("b"));
+        assert_eq!(name(2), Some("b"));
+        assert_eq!(name(3), Some("c"));
+        assert_eq!(name(4), None);
+        assert_eq!(meta.field("c").map(|(offset, _)| offset), Some(3));
+    }
+
+    #[test]
+    fn wrap_angle_in_range() {
+        for &a in &[-804, -1, 0, 1, 400, 804] {
+            assert_eq!(wrap_angle(a), a);
+        }
+    }
+
+    #[test]
+    fn wrap_angle_out_of_range() {
+        // 2π in 1/256 radians is about 1608.5.
+        assert_eq!(wrap_angle(1608), 0);
+        assert_eq!(wrap_angle(1000), -608);
+        assert_eq!(wrap_angle(-1000), 608);
+        assert_eq!(wrap_angle(100 + 10 * 1608), 95);
+        assert!(wrap_angle(i32::MAX).abs() <= 804);
+        assert!(wrap_angle(i32::MIN).abs() <= 804);
+    }
+}
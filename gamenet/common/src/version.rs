@@ -0,0 +1,331 @@
+use std::str;
+
+use snap_obj::ObjMeta;
+use snap_obj::TypeId;
+
+pub const NET_VERSION_0_5: &str = "0.5 b67d1f1a1eea234e";
+pub const NET_VERSION_0_6: &str = "0.6 626fce9a778df4d4";
+pub const NET_VERSION_0_7: &str = "0.7 802f1be60a05665f";
+
+// DDNet client versions that introduced protocol changes, as in DDNet's
+// `src/engine/shared/protocol.h`.
+pub const DDNET_VERSION_OLD: i32 = 2;
+pub const DDNET_VERSION_WHISPER: i32 = 217;
+pub const DDNET_VERSION_GOODHOOK: i32 = 221;
+pub const DDNET_VERSION_EXTRATUNES: i32 = 302;
+pub const DDNET_VERSION_RCONPROTECT: i32 = 408;
+pub const DDNET_VERSION_ANTIPING_PROJECTILE: i32 = 604;
+pub const DDNET_VERSION_HOOKDURATION_TUNE: i32 = 607;
+pub const DDNET_VERSION_FIREDELAY_TUNE: i32 = 701;
+pub const DDNET_VERSION_UPDATER_FIXED: i32 = 707;
+pub const DDNET_VERSION_GAMETICK: i32 = 10042;
+pub const DDNET_VERSION_EARLY_VERSION: i32 = 13020;
+pub const DDNET_VERSION_MSG_LEGACY: i32 = 15040;
+pub const DDNET_VERSION_SWITCH: i32 = 15060;
+pub const DDNET_VERSION_INDEPENDENT_SPECTATORS_TEAM: i32 = 16000;
+pub const DDNET_VERSION_WEAPON_SHIELDS: i32 = 16010;
+pub const DDNET_VERSION_NEW_HUD: i32 = 16020;
+pub const DDNET_VERSION_MULTI_LASER: i32 = 16040;
+pub const DDNET_VERSION_ENTITY_NETOBJS: i32 = 16200;
+pub const DDNET_VERSION_REDIRECT: i32 = 17020;
+
+/// The protocol crate whose message tables and snap object sizes apply.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Protocol {
+    Teeworlds05,
+    Teeworlds06,
+    Teeworlds07,
+    Ddnet,
+}
+
+impl Protocol {
+    /// The version string sent in the `Info` system message.
+    pub fn net_version(self) -> &'static str {
+        match self {
+            Protocol::Teeworlds05 => NET_VERSION_0_5,
+            Protocol::Teeworlds06 | Protocol::Ddnet => NET_VERSION_0_6,
+            Protocol::Teeworlds07 => NET_VERSION_0_7,
+        }
+    }
+    /// Whether a peer speaking `self` can talk to an implementation of
+    /// `other`.
+    ///
+    /// DDNet is an extension of the 0.6 protocol, both can talk to each
+    /// other.
+    pub fn is_compatible(self, other: Protocol) -> bool {
+        self.net_version() == other.net_version()
+    }
+}
+
+/// The snapshot object tables of all protocols, used to select the object
+/// sizes of a [`Version`].
+///
+/// Fill it from the `snap_obj::OBJ_META` constants of the protocol crates.
+#[derive(Clone, Copy, Debug)]
+pub struct ObjTables {
+    pub teeworlds05: &'static [ObjMeta],
+    pub teeworlds06: &'static [ObjMeta],
+    pub teeworlds07: &'static [ObjMeta],
+    pub ddnet: &'static [ObjMeta],
+}
+
+impl ObjTables {
+    pub fn get(&self, protocol: Protocol) -> &'static [ObjMeta] {
+        match protocol {
+            Protocol::Teeworlds05 => self.teeworlds05,
+            Protocol::Teeworlds06 => self.teeworlds06,
+            Protocol::Teeworlds07 => self.teeworlds07,
+            Protocol::Ddnet => self.ddnet,
+        }
+    }
+}
+
+/// The protocol version a peer claims to speak.
+///
+/// Start with [`Version::from_net_version`] on the `Info` system message,
+/// then refine it with [`Version::with_client_version`] once the client
+/// version number is known, from `Info` in 0.7 or from `ClientVersion` and
+/// `ClIsDdnetLegacy` in DDNet.
+///
+/// The network version string doesn't distinguish the 0.6 patch releases,
+/// only the human-readable version string of server infos does, see
+/// [`Version::from_version_string`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Version {
+    Teeworlds05,
+    /// `patch` is e.g. 4 for 0.6.4, `None` if unknown.
+    Teeworlds06 {
+        patch: Option<u32>,
+    },
+    Teeworlds07 {
+        client_version: Option<i32>,
+    },
+    Ddnet {
+        ddnet_version: i32,
+    },
+}
+
+impl Version {
+    /// Parses the network version string of the `Info` system message.
+    pub fn from_net_version(net_version: &[u8]) -> Option<Version> {
+        Some(match net_version {
+            v if v == NET_VERSION_0_5.as_bytes() => Version::Teeworlds05,
+            v if v == NET_VERSION_0_6.as_bytes() => Version::Teeworlds06 { patch: None },
+            v if v == NET_VERSION_0_7.as_bytes() => Version::Teeworlds07 {
+                client_version: None,
+            },
+            _ => return None,
+        })
+    }
+    /// Parses a human-readable version string as found in server infos,
+    /// e.g. `0.6.4`, `0.7.5` or `0.6.4, 17.2.1`.
+    pub fn from_version_string(version: &[u8]) -> Option<Version> {
+        let version = str::from_utf8(version).ok()?;
+        let mut parts = version.splitn(2, ", ");
+        let base = parts.next().unwrap();
+        let ddnet = parts.next();
+        let result = if base.starts_with("0.5") {
+            Version::Teeworlds05
+        } else if let Some(rest) = base.strip_prefix("0.6") {
+            let patch = match rest {
+                "" => None,
+                _ => Some(rest.strip_prefix('.')?.parse().ok()?),
+            };
+            Version::Teeworlds06 { patch }
+        } else if base.starts_with("0.7") {
+            Version::Teeworlds07 {
+                client_version: None,
+            }
+        } else {
+            return None;
+        };
+        match (result, ddnet) {
+            (Version::Teeworlds06 { .. }, Some(ddnet)) => Some(Version::Ddnet {
+                ddnet_version: parse_ddnet_version(ddnet)?,
+            }),
+            (_, Some(_)) => None,
+            (result, None) => Some(result),
+        }
+    }
+    /// Incorporates the client version number sent later in the
+    /// handshake.
+    ///
+    /// For 0.6 peers, this is the DDNet version, turning them into DDNet
+    /// peers.
+    pub fn with_client_version(self, client_version: i32) -> Version {
+        match self {
+            Version::Teeworlds05 => Version::Teeworlds05,
+            Version::Teeworlds06 { .. } | Version::Ddnet { .. } => Version::Ddnet {
+                ddnet_version: client_version,
+            },
+            Version::Teeworlds07 { .. } => Version::Teeworlds07 {
+                client_version: Some(client_version),
+            },
+        }
+    }
+    pub fn protocol(self) -> Protocol {
+        match self {
+            Version::Teeworlds05 => Protocol::Teeworlds05,
+            Version::Teeworlds06 { .. } => Protocol::Teeworlds06,
+            Version::Teeworlds07 { .. } => Protocol::Teeworlds07,
+            Version::Ddnet { .. } => Protocol::Ddnet,
+        }
+    }
+    /// The patch release of a vanilla 0.6 peer, e.g. 5 for 0.6.5.
+    pub fn teeworlds06_patch(self) -> Option<u32> {
+        match self {
+            Version::Teeworlds06 { patch } => patch,
+            _ => None,
+        }
+    }
+    /// Whether the peer is a vanilla 0.6 peer of at least the given patch
+    /// release. Peers of unknown patch release are assumed to be older.
+    pub fn teeworlds06_at_least(self, patch: u32) -> bool {
+        self.teeworlds06_patch()
+            .map(|p| p >= patch)
+            .unwrap_or(false)
+    }
+    /// The description of a snapshot object type in the peer's protocol.
+    pub fn obj_meta(self, tables: &ObjTables, type_id: TypeId) -> Option<&'static ObjMeta> {
+        tables
+            .get(self.protocol())
+            .iter()
+            .find(|m| m.type_id == type_id)
+    }
+    /// Size in ints of a snapshot object type in the peer's protocol,
+    /// `None` for unknown types.
+    pub fn obj_size(self, tables: &ObjTables, type_id: TypeId) -> Option<u32> {
+        self.obj_meta(tables, type_id).map(|m| m.size)
+    }
+    pub fn ddnet_version(self) -> Option<i32> {
+        match self {
+            Version::Ddnet { ddnet_version } => Some(ddnet_version),
+            _ => None,
+        }
+    }
+    /// Whether the peer is a DDNet client of at least the given version,
+    /// one of the `DDNET_VERSION_*` constants.
+    pub fn ddnet_at_least(self, ddnet_version: i32) -> bool {
+        self.ddnet_version()
+            .map(|v| v >= ddnet_version)
+            .unwrap_or(false)
+    }
+    /// Number of client IDs the peer can handle.
+    pub fn max_clients(self) -> i32 {
+        match self {
+            Version::Teeworlds05 | Version::Teeworlds06 { .. } => 16,
+            Version::Teeworlds07 { .. } => 64,
+            Version::Ddnet { .. } => 64,
+        }
+    }
+    /// Whether the peer understands whispers via `SvChat`.
+    pub fn supports_whisper(self) -> bool {
+        self.ddnet_at_least(DDNET_VERSION_WHISPER)
+    }
+    /// Whether the peer understands the tune parameters added by DDNet.
+    pub fn supports_extra_tunes(self) -> bool {
+        self.ddnet_at_least(DDNET_VERSION_EXTRATUNES)
+    }
+    /// Whether the peer expects the DDNet-specific messages that replaced
+    /// the legacy ones, e.g. `SvTeamsState` instead of `SvTeamsStateLegacy`.
+    pub fn supports_msg_legacy_replacements(self) -> bool {
+        self.ddnet_at_least(DDNET_VERSION_MSG_LEGACY)
+    }
+    /// Whether the peer understands the DDNet entity snap objects.
+    pub fn supports_entity_netobjs(self) -> bool {
+        self.ddnet_at_least(DDNET_VERSION_ENTITY_NETOBJS)
+    }
+    /// Whether the peer can be redirected to another server.
+    pub fn supports_redirect(self) -> bool {
+        self.ddnet_at_least(DDNET_VERSION_REDIRECT)
+    }
+}
+
+/// Parses a DDNet version like `17.2.1` into its numeric form `17021`.
+fn parse_ddnet_version(version: &str) -> Option<i32> {
+    let mut numbers = version.split('.').map(|n| n.parse::<i32>().ok());
+    let major = numbers.next()??;
+    let minor = numbers.next().unwrap_or(Some(0))?;
+    let patch = numbers.next().unwrap_or(Some(0))?;
+    if numbers.next().is_some() {
+        return None;
+    }
+    Some(major * 1000 + minor * 10 + patch)
+}
+
+#[cfg(test)]
+mod test {
+    use super::ObjTables;
+    use super::Version;
+    use snap_obj::ObjMeta;
+    use snap_obj::TypeId;
+
+    const TW06: &[ObjMeta] = &[ObjMeta {
+        type_id: TypeId::Ordinal(9),
+        name: "character",
+        size: 22,
+        fields: &[],
+    }];
+    const DDNET: &[ObjMeta] = &[
+        ObjMeta {
+            type_id: TypeId::Ordinal(9),
+            name: "character",
+            size: 22,
+            fields: &[],
+        },
+        ObjMeta {
+            type_id: TypeId::Ordinal(24),
+            name: "ddnet_character",
+            size: 5,
+            fields: &[],
+        },
+    ];
+    const TABLES: ObjTables = ObjTables {
+        teeworlds05: &[],
+        teeworlds06: TW06,
+        teeworlds07: &[],
+        ddnet: DDNET,
+    };
+
+    #[test]
+    fn version_string() {
+        assert_eq!(
+            Version::from_version_string(b"0.6.4, 17.2.1"),
+            Some(Version::Ddnet {
+                ddnet_version: 17021
+            }),
+        );
+        assert_eq!(
+            Version::from_version_string(b"0.6.4"),
+            Some(Version::Teeworlds06 { patch: Some(4) }),
+        );
+        let v065 = Version::from_version_string(b"0.6.5").unwrap();
+        assert_eq!(v065.teeworlds06_patch(), Some(5));
+        assert!(v065.teeworlds06_at_least(5));
+        assert!(!Version::from_version_string(b"0.6.4")
+            .unwrap()
+            .teeworlds06_at_least(5));
+        assert_eq!(Version::from_version_string(b"0.6x"), None);
+        assert_eq!(Version::from_version_string(b"0.8"), None);
+    }
+
+    #[test]
+    fn client_version() {
+        let v = Version::from_net_version(b"0.6 626fce9a778df4d4").unwrap();
+        assert!(!v.supports_whisper());
+        let v = v.with_client_version(17021);
+        assert!(v.supports_whisper());
+        assert!(v.supports_redirect());
+    }
+
+    #[test]
+    fn obj_size() {
+        let vanilla = Version::from_net_version(b"0.6 626fce9a778df4d4").unwrap();
+        let ddnet = vanilla.with_client_version(17021);
+        assert_eq!(vanilla.obj_size(&TABLES, TypeId::Ordinal(9)), Some(22));
+        assert_eq!(vanilla.obj_size(&TABLES, TypeId::Ordinal(24)), None);
+        assert_eq!(ddnet.obj_size(&TABLES, TypeId::Ordinal(24)), Some(5));
+        let v05 = Version::from_version_string(b"0.5.2").unwrap();
+        assert_eq!(v05.obj_size(&TABLES, TypeId::Ordinal(9)), None);
+    }
+}
@@ -0,0 +1,242 @@
+//! Lifecycle of votes as driven by `ClCallVote` and `ClVote`, reported via
+//! `SvVoteSet` and `SvVoteStatus`.
+
+use common::time::Tick;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum VoteKind {
+    Option,
+    Kick,
+    Spectate,
+}
+
+impl VoteKind {
+    /// Parses the `type_` field of `ClCallVote`.
+    pub fn from_call_vote_type(type_: &[u8]) -> Option<VoteKind> {
+        Some(match type_ {
+            b"option" => VoteKind::Option,
+            b"kick" => VoteKind::Kick,
+            b"spec" | b"spectate" => VoteKind::Spectate,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Ballot {
+    Yes,
+    No,
+}
+
+impl Ballot {
+    /// Converts the `vote` field of `ClVote`, `None` means abstaining.
+    pub fn from_i32(vote: i32) -> Option<Ballot> {
+        match vote {
+            1 => Some(Ballot::Yes),
+            -1 => Some(Ballot::No),
+            _ => None,
+        }
+    }
+    pub fn to_i32(self) -> i32 {
+        match self {
+            Ballot::Yes => 1,
+            Ballot::No => -1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum VoteResult {
+    Passed,
+    Failed,
+    Aborted,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VoteError {
+    AlreadyRunning,
+    NotRunning,
+}
+
+/// Fields of `SvVoteStatus`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct VoteStatus {
+    pub yes: i32,
+    pub no: i32,
+    /// Voters that haven't voted yet.
+    pub pass: i32,
+    pub total: i32,
+}
+
+#[derive(Clone, Debug)]
+pub struct Vote {
+    pub caller: i32,
+    pub kind: VoteKind,
+    pub deadline: Tick,
+    ballots: BTreeMap<i32, Ballot>,
+}
+
+impl Vote {
+    pub fn ballot(&self, client_id: i32) -> Option<Ballot> {
+        self.ballots.get(&client_id).cloned()
+    }
+    /// Counts the ballots, `total` being the number of eligible voters.
+    pub fn status(&self, total: i32) -> VoteStatus {
+        let yes = self.ballots.values().filter(|&&b| b == Ballot::Yes).count() as i32;
+        let no = self.ballots.values().filter(|&&b| b == Ballot::No).count() as i32;
+        VoteStatus {
+            yes,
+            no,
+            pass: total - yes - no,
+            total,
+        }
+    }
+    /// Decides the vote once a majority is reached, like the reference
+    /// implementation does.
+    pub fn decide(&self, total: i32) -> Option<VoteResult> {
+        let status = self.status(total);
+        if status.yes > total / 2 {
+            Some(VoteResult::Passed)
+        } else if status.no >= (total + 1) / 2 {
+            Some(VoteResult::Failed)
+        } else {
+            None
+        }
+    }
+}
+
+/// What happened in response to a vote update.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VoteEvent {
+    /// The vote is still running, send the new status to the clients.
+    Progress(VoteStatus),
+    Finished(VoteResult),
+}
+
+/// The vote state of a server, at most one vote runs at a time.
+#[derive(Clone, Debug, Default)]
+pub struct Votes {
+    running: Option<Vote>,
+}
+
+impl Votes {
+    pub fn new() -> Votes {
+        Default::default()
+    }
+    pub fn running(&self) -> Option<&Vote> {
+        self.running.as_ref()
+    }
+    /// Starts a vote, the caller automatically votes yes.
+    pub fn call(&mut self, caller: i32, kind: VoteKind, deadline: Tick) -> Result<(), VoteError> {
+        if self.running.is_some() {
+            return Err(VoteError::AlreadyRunning);
+        }
+        let mut ballots = BTreeMap::new();
+        ballots.insert(caller, Ballot::Yes);
+        self.running = Some(Vote {
+            caller,
+            kind,
+            deadline,
+            ballots,
+        });
+        Ok(())
+    }
+    /// Records the ballot of `client_id`, `None` withdraws it.
+    pub fn cast(
+        &mut self,
+        client_id: i32,
+        ballot: Option<Ballot>,
+        total: i32,
+    ) -> Result<VoteEvent, VoteError> {
+        {
+            let vote = self.running.as_mut().ok_or(VoteError::NotRunning)?;
+            match ballot {
+                Some(b) => vote.ballots.insert(client_id, b),
+                None => vote.ballots.remove(&client_id),
+            };
+        }
+        Ok(self.update(total))
+    }
+    /// Forgets the ballot of a client that left.
+    pub fn remove_voter(&mut self, client_id: i32) {
+        if let Some(ref mut vote) = self.running {
+            vote.ballots.remove(&client_id);
+        }
+    }
+    /// Ends the vote if its deadline has passed, failing it.
+    pub fn tick(&mut self, now: Tick) -> Option<VoteResult> {
+        let expired = self
+            .running
+            .as_ref()
+            .map(|v| !v.deadline.is_after(now))
+            .unwrap_or(false);
+        if !expired {
+            return None;
+        }
+        self.running = None;
+        Some(VoteResult::Failed)
+    }
+    pub fn abort(&mut self) -> Option<VoteResult> {
+        self.running.take().map(|_| VoteResult::Aborted)
+    }
+    fn update(&mut self, total: i32) -> VoteEvent {
+        let vote = self.running.as_ref().unwrap();
+        match vote.decide(total) {
+            Some(result) => {
+                self.running = None;
+                VoteEvent::Finished(result)
+            }
+            None => VoteEvent::Progress(vote.status(total)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Ballot;
+    use super::VoteError;
+    use super::VoteEvent;
+    use super::VoteKind;
+    use super::VoteResult;
+    use super::VoteStatus;
+    use super::Votes;
+    use common::time::Tick;
+
+    #[test]
+    fn lifecycle() {
+        let mut votes = Votes::new();
+        assert_eq!(
+            votes.cast(1, Some(Ballot::Yes), 4),
+            Err(VoteError::NotRunning)
+        );
+        votes.call(0, VoteKind::Option, Tick(100)).unwrap();
+        assert_eq!(
+            votes.call(1, VoteKind::Kick, Tick(100)),
+            Err(VoteError::AlreadyRunning)
+        );
+        assert_eq!(
+            votes.cast(1, Some(Ballot::No), 3),
+            Ok(VoteEvent::Progress(VoteStatus {
+                yes: 1,
+                no: 1,
+                pass: 1,
+                total: 3
+            })),
+        );
+        assert_eq!(
+            votes.cast(2, Some(Ballot::Yes), 3),
+            Ok(VoteEvent::Finished(VoteResult::Passed)),
+        );
+        assert!(votes.running().is_none());
+    }
+
+    #[test]
+    fn timeout() {
+        let mut votes = Votes::new();
+        votes.call(0, VoteKind::Spectate, Tick(100)).unwrap();
+        assert_eq!(votes.tick(Tick(99)), None);
+        assert_eq!(votes.tick(Tick(100)), Some(VoteResult::Failed));
+        assert_eq!(votes.abort(), None);
+    }
+}
@@ -19,8 +19,8 @@ pub const EMOTE_SURPRISE: i32 = 3;
 pub const EMOTE_ANGRY: i32 = 4;
 pub const EMOTE_BLINK: i32 = 5;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Emote {
     Normal,
     Pain,
@@ -28,6 +28,7 @@ pub enum Emote {
     Surprise,
     Angry,
     Blink,
+    Unknown(i32),
 }
 
 pub const POWERUP_HEALTH: i32 = 0;
@@ -39,8 +40,8 @@ pub const POWERUP_ARMOR_GRENADE: i32 = 5;
 pub const POWERUP_ARMOR_NINJA: i32 = 6;
 pub const POWERUP_ARMOR_LASER: i32 = 7;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Powerup {
     Health,
     Armor,
@@ -50,6 +51,7 @@ pub enum Powerup {
     ArmorGrenade,
     ArmorNinja,
     ArmorLaser,
+    Unknown(i32),
 }
 
 pub const EMOTICON_OOP: i32 = 0;
@@ -69,8 +71,8 @@ pub const EMOTICON_WTF: i32 = 13;
 pub const EMOTICON_EYES: i32 = 14;
 pub const EMOTICON_QUESTION: i32 = 15;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Emoticon {
     Oop,
     Exclamation,
@@ -88,6 +90,7 @@ pub enum Emoticon {
     Wtf,
     Eyes,
     Question,
+    Unknown(i32),
 }
 
 pub const AUTHED_NO: i32 = 0;
@@ -95,13 +98,14 @@ pub const AUTHED_HELPER: i32 = 1;
 pub const AUTHED_MOD: i32 = 2;
 pub const AUTHED_ADMIN: i32 = 3;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Authed {
     No,
     Helper,
     Mod,
     Admin,
+    Unknown(i32),
 }
 
 pub const ENTITYCLASS_PROJECTILE: i32 = 0;
@@ -116,8 +120,8 @@ pub const ENTITYCLASS_GUN_UNFREEZE: i32 = 8;
 pub const ENTITYCLASS_LIGHT: i32 = 9;
 pub const ENTITYCLASS_PICKUP: i32 = 10;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Entityclass {
     Projectile,
     Door,
@@ -130,6 +134,7 @@ pub enum Entityclass {
     GunUnfreeze,
     Light,
     Pickup,
+    Unknown(i32),
 }
 
 pub const LASERTYPE_RIFLE: i32 = 0;
@@ -140,8 +145,8 @@ pub const LASERTYPE_DRAGGER: i32 = 4;
 pub const LASERTYPE_GUN: i32 = 5;
 pub const LASERTYPE_PLASMA: i32 = 6;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Lasertype {
     Rifle,
     Shotgun,
@@ -150,6 +155,7 @@ pub enum Lasertype {
     Dragger,
     Gun,
     Plasma,
+    Unknown(i32),
 }
 
 pub const LASERDRAGGERTYPE_WEAK: i32 = 0;
@@ -159,8 +165,8 @@ pub const LASERDRAGGERTYPE_NORMAL_NW: i32 = 3;
 pub const LASERDRAGGERTYPE_STRONG: i32 = 4;
 pub const LASERDRAGGERTYPE_STRONG_NW: i32 = 5;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Laserdraggertype {
     Weak,
     WeakNw,
@@ -168,6 +174,7 @@ pub enum Laserdraggertype {
     NormalNw,
     Strong,
     StrongNw,
+    Unknown(i32),
 }
 
 pub const LASERGUNTYPE_UNFREEZE: i32 = 0;
@@ -175,13 +182,14 @@ pub const LASERGUNTYPE_EXPLOSIVE: i32 = 1;
 pub const LASERGUNTYPE_FREEZE: i32 = 2;
 pub const LASERGUNTYPE_EXPFREEZE: i32 = 3;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Laserguntype {
     Unfreeze,
     Explosive,
     Freeze,
     Expfreeze,
+    Unknown(i32),
 }
 
 pub const WEAPON_HAMMER: i32 = 0;
@@ -191,8 +199,8 @@ pub const WEAPON_GRENADE: i32 = 3;
 pub const WEAPON_RIFLE: i32 = 4;
 pub const WEAPON_NINJA: i32 = 5;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Weapon {
     Hammer,
     Pistol,
@@ -200,18 +208,20 @@ pub enum Weapon {
     Grenade,
     Rifle,
     Ninja,
+    Unknown(i32),
 }
 
 pub const TEAM_SPECTATORS: i32 = -1;
 pub const TEAM_RED: i32 = 0;
 pub const TEAM_BLUE: i32 = 1;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Team {
-    Spectators = -1,
+    Spectators,
     Red,
     Blue,
+    Unknown(i32),
 }
 
 pub const SOUND_GUN_FIRE: i32 = 0;
@@ -256,8 +266,8 @@ pub const SOUND_CTF_GRAB_EN: i32 = 38;
 pub const SOUND_CTF_CAPTURE: i32 = 39;
 pub const SOUND_MENU: i32 = 40;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Sound {
     GunFire,
     ShotgunFire,
@@ -300,20 +310,21 @@ pub enum Sound {
     CtfGrabEn,
     CtfCapture,
     Menu,
+    Unknown(i32),
 }
 
 impl Emote {
-    pub fn from_i32(i: i32) -> Result<Emote, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Emote {
         use self::Emote::*;
-        Ok(match i {
+        match i {
             EMOTE_NORMAL => Normal,
             EMOTE_PAIN => Pain,
             EMOTE_HAPPY => Happy,
             EMOTE_SURPRISE => Surprise,
             EMOTE_ANGRY => Angry,
             EMOTE_BLINK => Blink,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Emote::*;
@@ -324,14 +335,21 @@ impl Emote {
             Surprise => EMOTE_SURPRISE,
             Angry => EMOTE_ANGRY,
             Blink => EMOTE_BLINK,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Emote, IntOutOfRange> {
+        match self {
+            Emote::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Powerup {
-    pub fn from_i32(i: i32) -> Result<Powerup, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Powerup {
         use self::Powerup::*;
-        Ok(match i {
+        match i {
             POWERUP_HEALTH => Health,
             POWERUP_ARMOR => Armor,
             POWERUP_WEAPON => Weapon,
@@ -340,8 +358,8 @@ impl Powerup {
             POWERUP_ARMOR_GRENADE => ArmorGrenade,
             POWERUP_ARMOR_NINJA => ArmorNinja,
             POWERUP_ARMOR_LASER => ArmorLaser,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Powerup::*;
@@ -354,14 +372,21 @@ impl Powerup {
             ArmorGrenade => POWERUP_ARMOR_GRENADE,
             ArmorNinja => POWERUP_ARMOR_NINJA,
             ArmorLaser => POWERUP_ARMOR_LASER,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Powerup, IntOutOfRange> {
+        match self {
+            Powerup::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Emoticon {
-    pub fn from_i32(i: i32) -> Result<Emoticon, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Emoticon {
         use self::Emoticon::*;
-        Ok(match i {
+        match i {
             EMOTICON_OOP => Oop,
             EMOTICON_EXCLAMATION => Exclamation,
             EMOTICON_HEARTS => Hearts,
@@ -378,8 +403,8 @@ impl Emoticon {
             EMOTICON_WTF => Wtf,
             EMOTICON_EYES => Eyes,
             EMOTICON_QUESTION => Question,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Emoticon::*;
@@ -400,20 +425,27 @@ impl Emoticon {
             Wtf => EMOTICON_WTF,
             Eyes => EMOTICON_EYES,
             Question => EMOTICON_QUESTION,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Emoticon, IntOutOfRange> {
+        match self {
+            Emoticon::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Authed {
-    pub fn from_i32(i: i32) -> Result<Authed, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Authed {
         use self::Authed::*;
-        Ok(match i {
+        match i {
             AUTHED_NO => No,
             AUTHED_HELPER => Helper,
             AUTHED_MOD => Mod,
             AUTHED_ADMIN => Admin,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Authed::*;
@@ -422,14 +454,21 @@ impl Authed {
             Helper => AUTHED_HELPER,
             Mod => AUTHED_MOD,
             Admin => AUTHED_ADMIN,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Authed, IntOutOfRange> {
+        match self {
+            Authed::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Entityclass {
-    pub fn from_i32(i: i32) -> Result<Entityclass, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Entityclass {
         use self::Entityclass::*;
-        Ok(match i {
+        match i {
             ENTITYCLASS_PROJECTILE => Projectile,
             ENTITYCLASS_DOOR => Door,
             ENTITYCLASS_DRAGGER_WEAK => DraggerWeak,
@@ -441,8 +480,8 @@ impl Entityclass {
             ENTITYCLASS_GUN_UNFREEZE => GunUnfreeze,
             ENTITYCLASS_LIGHT => Light,
             ENTITYCLASS_PICKUP => Pickup,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Entityclass::*;
@@ -458,14 +497,21 @@ impl Entityclass {
             GunUnfreeze => ENTITYCLASS_GUN_UNFREEZE,
             Light => ENTITYCLASS_LIGHT,
             Pickup => ENTITYCLASS_PICKUP,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Entityclass, IntOutOfRange> {
+        match self {
+            Entityclass::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Lasertype {
-    pub fn from_i32(i: i32) -> Result<Lasertype, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Lasertype {
         use self::Lasertype::*;
-        Ok(match i {
+        match i {
             LASERTYPE_RIFLE => Rifle,
             LASERTYPE_SHOTGUN => Shotgun,
             LASERTYPE_DOOR => Door,
@@ -473,8 +519,8 @@ impl Lasertype {
             LASERTYPE_DRAGGER => Dragger,
             LASERTYPE_GUN => Gun,
             LASERTYPE_PLASMA => Plasma,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Lasertype::*;
@@ -486,22 +532,29 @@ impl Lasertype {
             Dragger => LASERTYPE_DRAGGER,
             Gun => LASERTYPE_GUN,
             Plasma => LASERTYPE_PLASMA,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Lasertype, IntOutOfRange> {
+        match self {
+            Lasertype::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Laserdraggertype {
-    pub fn from_i32(i: i32) -> Result<Laserdraggertype, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Laserdraggertype {
         use self::Laserdraggertype::*;
-        Ok(match i {
+        match i {
             LASERDRAGGERTYPE_WEAK => Weak,
             LASERDRAGGERTYPE_WEAK_NW => WeakNw,
             LASERDRAGGERTYPE_NORMAL => Normal,
             LASERDRAGGERTYPE_NORMAL_NW => NormalNw,
             LASERDRAGGERTYPE_STRONG => Strong,
             LASERDRAGGERTYPE_STRONG_NW => StrongNw,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Laserdraggertype::*;
@@ -512,20 +565,27 @@ impl Laserdraggertype {
             NormalNw => LASERDRAGGERTYPE_NORMAL_NW,
             Strong => LASERDRAGGERTYPE_STRONG,
             StrongNw => LASERDRAGGERTYPE_STRONG_NW,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Laserdraggertype, IntOutOfRange> {
+        match self {
+            Laserdraggertype::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Laserguntype {
-    pub fn from_i32(i: i32) -> Result<Laserguntype, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Laserguntype {
         use self::Laserguntype::*;
-        Ok(match i {
+        match i {
             LASERGUNTYPE_UNFREEZE => Unfreeze,
             LASERGUNTYPE_EXPLOSIVE => Explosive,
             LASERGUNTYPE_FREEZE => Freeze,
             LASERGUNTYPE_EXPFREEZE => Expfreeze,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Laserguntype::*;
@@ -534,22 +594,29 @@ impl Laserguntype {
             Explosive => LASERGUNTYPE_EXPLOSIVE,
             Freeze => LASERGUNTYPE_FREEZE,
             Expfreeze => LASERGUNTYPE_EXPFREEZE,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Laserguntype, IntOutOfRange> {
+        match self {
+            Laserguntype::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Weapon {
-    pub fn from_i32(i: i32) -> Result<Weapon, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Weapon {
         use self::Weapon::*;
-        Ok(match i {
+        match i {
             WEAPON_HAMMER => Hammer,
             WEAPON_PISTOL => Pistol,
             WEAPON_SHOTGUN => Shotgun,
             WEAPON_GRENADE => Grenade,
             WEAPON_RIFLE => Rifle,
             WEAPON_NINJA => Ninja,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Weapon::*;
@@ -560,19 +627,26 @@ impl Weapon {
             Grenade => WEAPON_GRENADE,
             Rifle => WEAPON_RIFLE,
             Ninja => WEAPON_NINJA,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Weapon, IntOutOfRange> {
+        match self {
+            Weapon::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Team {
-    pub fn from_i32(i: i32) -> Result<Team, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Team {
         use self::Team::*;
-        Ok(match i {
+        match i {
             TEAM_SPECTATORS => Spectators,
             TEAM_RED => Red,
             TEAM_BLUE => Blue,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Team::*;
@@ -580,14 +654,21 @@ impl Team {
             Spectators => TEAM_SPECTATORS,
             Red => TEAM_RED,
             Blue => TEAM_BLUE,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Team, IntOutOfRange> {
+        match self {
+            Team::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Sound {
-    pub fn from_i32(i: i32) -> Result<Sound, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Sound {
         use self::Sound::*;
-        Ok(match i {
+        match i {
             SOUND_GUN_FIRE => GunFire,
             SOUND_SHOTGUN_FIRE => ShotgunFire,
             SOUND_GRENADE_FIRE => GrenadeFire,
@@ -629,8 +710,8 @@ impl Sound {
             SOUND_CTF_GRAB_EN => CtfGrabEn,
             SOUND_CTF_CAPTURE => CtfCapture,
             SOUND_MENU => Menu,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Sound::*;
@@ -676,6 +757,13 @@ impl Sound {
             CtfGrabEn => SOUND_CTF_GRAB_EN,
             CtfCapture => SOUND_CTF_CAPTURE,
             Menu => SOUND_MENU,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Sound, IntOutOfRange> {
+        match self {
+            Sound::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
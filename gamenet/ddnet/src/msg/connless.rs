@@ -1,6 +1,10 @@
 use buffer::CapacityError;
 use common::num::BeU16;
 use common::pretty;
+use common::warn::Located;
+use common::warn::strip_location;
+use error;
+use error::EncodeError;
 use error::Error;
 use gamenet_common::msg::AddrPackedSliceExt;
 use gamenet_common::msg::int_from_string;
@@ -13,7 +17,7 @@ use packer::with_packer;
 use std::fmt;
 use super::AddrPacked;
 use super::ClientsData;
-use warn::Panic;
+use warn::Ignore;
 use warn::Warn;
 use warn::wrap;
 
@@ -24,7 +28,7 @@ impl<'a> Connless<'a> {
         Connless::decode_connless(warn, connless_id, _p)
     }
     pub fn encode<'d, 's>(&self, mut p: Packer<'d, 's>)
-        -> Result<&'d [u8], CapacityError>
+        -> Result<&'d [u8], EncodeError>
     {
         p.write_raw(&self.connless_id())?;
         with_packer(&mut p, |p| self.encode_connless(p))?;
@@ -75,6 +79,8 @@ pub const REQUEST_INFO: &'static [u8; 8] = b"\xff\xff\xff\xffgie3";
 pub const INFO: &'static [u8; 8] = b"\xff\xff\xff\xffinf3";
 pub const INFO_EXTENDED: &'static [u8; 8] = b"\xff\xff\xff\xffiext";
 pub const INFO_EXTENDED_MORE: &'static [u8; 8] = b"\xff\xff\xff\xffiex+";
+pub const REQUEST_INFO64: &'static [u8; 8] = b"\xff\xff\xff\xfffstd";
+pub const INFO64: &'static [u8; 8] = b"\xff\xff\xff\xffdtsf";
 pub const HEARTBEAT: &'static [u8; 8] = b"\xff\xff\xff\xffbea2";
 pub const FORWARD_CHECK: &'static [u8; 8] = b"\xff\xff\xff\xfffw??";
 pub const FORWARD_RESPONSE: &'static [u8; 8] = b"\xff\xff\xff\xfffw!!";
@@ -82,6 +88,7 @@ pub const FORWARD_OK: &'static [u8; 8] = b"\xff\xff\xff\xfffwok";
 pub const FORWARD_ERROR: &'static [u8; 8] = b"\xff\xff\xff\xfffwer";
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub enum Connless<'a> {
     RequestList(RequestList),
     List(List<'a>),
@@ -91,6 +98,8 @@ pub enum Connless<'a> {
     Info(Info<'a>),
     InfoExtended(InfoExtended<'a>),
     InfoExtendedMore(InfoExtendedMore<'a>),
+    RequestInfo64(RequestInfo64),
+    Info64(Info64<'a>),
     Heartbeat(Heartbeat),
     ForwardCheck(ForwardCheck),
     ForwardResponse(ForwardResponse),
@@ -100,20 +109,25 @@ pub enum Connless<'a> {
 
 impl<'a> Connless<'a> {
     pub fn decode_connless<W: Warn<Warning>>(warn: &mut W, connless_id: [u8; 8], _p: &mut Unpacker<'a>) -> Result<Connless<'a>, Error> {
+        Connless::decode_connless_located(&mut strip_location(warn), connless_id, _p)
+    }
+    pub fn decode_connless_located<W: Warn<Located<Warning>>>(warn: &mut W, connless_id: [u8; 8], _p: &mut Unpacker<'a>) -> Result<Connless<'a>, Error> {
         Ok(match &connless_id {
-            REQUEST_LIST => Connless::RequestList(RequestList::decode(warn, _p)?),
-            LIST => Connless::List(List::decode(warn, _p)?),
-            REQUEST_COUNT => Connless::RequestCount(RequestCount::decode(warn, _p)?),
-            COUNT => Connless::Count(Count::decode(warn, _p)?),
-            REQUEST_INFO => Connless::RequestInfo(RequestInfo::decode(warn, _p)?),
-            INFO => Connless::Info(Info::decode(warn, _p)?),
-            INFO_EXTENDED => Connless::InfoExtended(InfoExtended::decode(warn, _p)?),
-            INFO_EXTENDED_MORE => Connless::InfoExtendedMore(InfoExtendedMore::decode(warn, _p)?),
-            HEARTBEAT => Connless::Heartbeat(Heartbeat::decode(warn, _p)?),
-            FORWARD_CHECK => Connless::ForwardCheck(ForwardCheck::decode(warn, _p)?),
-            FORWARD_RESPONSE => Connless::ForwardResponse(ForwardResponse::decode(warn, _p)?),
-            FORWARD_OK => Connless::ForwardOk(ForwardOk::decode(warn, _p)?),
-            FORWARD_ERROR => Connless::ForwardError(ForwardError::decode(warn, _p)?),
+            REQUEST_LIST => Connless::RequestList(RequestList::decode_located(warn, _p)?),
+            LIST => Connless::List(List::decode_located(warn, _p)?),
+            REQUEST_COUNT => Connless::RequestCount(RequestCount::decode_located(warn, _p)?),
+            COUNT => Connless::Count(Count::decode_located(warn, _p)?),
+            REQUEST_INFO => Connless::RequestInfo(RequestInfo::decode_located(warn, _p)?),
+            INFO => Connless::Info(Info::decode_located(warn, _p)?),
+            INFO_EXTENDED => Connless::InfoExtended(InfoExtended::decode_located(warn, _p)?),
+            INFO_EXTENDED_MORE => Connless::InfoExtendedMore(InfoExtendedMore::decode_located(warn, _p)?),
+            REQUEST_INFO64 => Connless::RequestInfo64(RequestInfo64::decode_located(warn, _p)?),
+            INFO64 => Connless::Info64(Info64::decode_located(warn, _p)?),
+            HEARTBEAT => Connless::Heartbeat(Heartbeat::decode_located(warn, _p)?),
+            FORWARD_CHECK => Connless::ForwardCheck(ForwardCheck::decode_located(warn, _p)?),
+            FORWARD_RESPONSE => Connless::ForwardResponse(ForwardResponse::decode_located(warn, _p)?),
+            FORWARD_OK => Connless::ForwardOk(ForwardOk::decode_located(warn, _p)?),
+            FORWARD_ERROR => Connless::ForwardError(ForwardError::decode_located(warn, _p)?),
             _ => return Err(Error::UnknownId),
         })
     }
@@ -127,6 +141,8 @@ impl<'a> Connless<'a> {
             Connless::Info(_) => *INFO,
             Connless::InfoExtended(_) => *INFO_EXTENDED,
             Connless::InfoExtendedMore(_) => *INFO_EXTENDED_MORE,
+            Connless::RequestInfo64(_) => *REQUEST_INFO64,
+            Connless::Info64(_) => *INFO64,
             Connless::Heartbeat(_) => *HEARTBEAT,
             Connless::ForwardCheck(_) => *FORWARD_CHECK,
             Connless::ForwardResponse(_) => *FORWARD_RESPONSE,
@@ -134,7 +150,7 @@ impl<'a> Connless<'a> {
             Connless::ForwardError(_) => *FORWARD_ERROR,
         }
     }
-    pub fn encode_connless<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode_connless<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         match *self {
             Connless::RequestList(ref i) => i.encode(p),
             Connless::List(ref i) => i.encode(p),
@@ -144,6 +160,8 @@ impl<'a> Connless<'a> {
             Connless::Info(ref i) => i.encode(p),
             Connless::InfoExtended(ref i) => i.encode(p),
             Connless::InfoExtendedMore(ref i) => i.encode(p),
+            Connless::RequestInfo64(ref i) => i.encode(p),
+            Connless::Info64(ref i) => i.encode(p),
             Connless::Heartbeat(ref i) => i.encode(p),
             Connless::ForwardCheck(ref i) => i.encode(p),
             Connless::ForwardResponse(ref i) => i.encode(p),
@@ -164,6 +182,8 @@ impl<'a> fmt::Debug for Connless<'a> {
             Connless::Info(ref i) => i.fmt(f),
             Connless::InfoExtended(ref i) => i.fmt(f),
             Connless::InfoExtendedMore(ref i) => i.fmt(f),
+            Connless::RequestInfo64(ref i) => i.fmt(f),
+            Connless::Info64(ref i) => i.fmt(f),
             Connless::Heartbeat(ref i) => i.fmt(f),
             Connless::ForwardCheck(ref i) => i.fmt(f),
             Connless::ForwardResponse(ref i) => i.fmt(f),
@@ -221,6 +241,18 @@ impl<'a> From<InfoExtendedMore<'a>> for Connless<'a> {
     }
 }
 
+impl<'a> From<RequestInfo64> for Connless<'a> {
+    fn from(i: RequestInfo64) -> Connless<'a> {
+        Connless::RequestInfo64(i)
+    }
+}
+
+impl<'a> From<Info64<'a>> for Connless<'a> {
+    fn from(i: Info64<'a>) -> Connless<'a> {
+        Connless::Info64(i)
+    }
+}
+
 impl<'a> From<Heartbeat> for Connless<'a> {
     fn from(i: Heartbeat) -> Connless<'a> {
         Connless::Heartbeat(i)
@@ -251,32 +283,42 @@ impl<'a> From<ForwardError> for Connless<'a> {
     }
 }
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RequestList;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct List<'a> {
     pub servers: &'a [AddrPacked],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RequestCount;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Count {
     pub count: u16,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RequestInfo {
     pub token: u8,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Info<'a> {
     pub token: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub version: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub map: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub game_type: &'a [u8],
     pub flags: i32,
     pub num_players: i32,
@@ -287,55 +329,99 @@ pub struct Info<'a> {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct InfoExtended<'a> {
     pub token: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub version: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub map: &'a [u8],
     pub map_crc: i32,
     pub map_size: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub game_type: &'a [u8],
     pub flags: i32,
     pub num_players: i32,
     pub max_players: i32,
     pub num_clients: i32,
     pub max_clients: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub reserved: &'a [u8],
     pub clients: ClientsData<'a>,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct InfoExtendedMore<'a> {
     pub token: i32,
     pub packet_no: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub reserved: &'a [u8],
     pub clients: ClientsData<'a>,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RequestInfo64 {
+    pub token: u8,
+}
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Info64<'a> {
+    pub token: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
+    pub version: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
+    pub name: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
+    pub map: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
+    pub game_type: &'a [u8],
+    pub flags: i32,
+    pub num_players: i32,
+    pub max_players: i32,
+    pub num_clients: i32,
+    pub max_clients: i32,
+    pub offset: i32,
+    pub clients: ClientsData<'a>,
+}
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Heartbeat {
     pub alt_port: u16,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ForwardCheck;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ForwardResponse;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ForwardOk;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ForwardError;
 
 impl RequestList {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestList, Error> {
-        let result = Ok(RequestList);
-        _p.finish(warn);
-        result
+        RequestList::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestList, Error> {
+        error::finish(&mut _p.locate(warn, "RequestList"), _p, Ok(RequestList))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -348,13 +434,28 @@ impl fmt::Debug for RequestList {
 
 impl<'a> List<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<List<'a>, Error> {
-        let result = Ok(List {
-            servers: AddrPackedSliceExt::from_bytes(wrap(warn), _p.read_rest()?),
-        });
-        _p.finish(warn);
-        result
+        List::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<List<'a>, Error> {
+        let result = List::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "List"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<List<'a>, Error> {
+        Ok(List {
+            servers: AddrPackedSliceExt::from_bytes(wrap(&mut _p.locate(warn, "List.servers")), _p.read_rest()?),
+        })
+    }
+    pub fn new(servers: &'a [AddrPacked]) -> Result<List<'a>, Error> {
+        let result = List {
+            servers,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_rest(self.servers.as_bytes())?;
         Ok(_p.written())
     }
@@ -369,11 +470,15 @@ impl<'a> fmt::Debug for List<'a> {
 
 impl RequestCount {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestCount, Error> {
-        let result = Ok(RequestCount);
-        _p.finish(warn);
-        result
+        RequestCount::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestCount, Error> {
+        error::finish(&mut _p.locate(warn, "RequestCount"), _p, Ok(RequestCount))
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -386,13 +491,28 @@ impl fmt::Debug for RequestCount {
 
 impl Count {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Count, Error> {
-        let result = Ok(Count {
+        Count::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Count, Error> {
+        let result = Count::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Count"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker) -> Result<Count, Error> {
+        Ok(Count {
             count: { let s = _p.read_raw(2)?; BeU16::from_bytes(&[s[0], s[1]]).to_u16() },
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(count: u16) -> Result<Count, Error> {
+        let result = Count {
+            count,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_raw(BeU16::from_u16(self.count).as_bytes())?;
         Ok(_p.written())
     }
@@ -407,13 +527,28 @@ impl fmt::Debug for Count {
 
 impl RequestInfo {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestInfo, Error> {
-        let result = Ok(RequestInfo {
+        RequestInfo::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestInfo, Error> {
+        let result = RequestInfo::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RequestInfo"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker) -> Result<RequestInfo, Error> {
+        Ok(RequestInfo {
             token: _p.read_raw(1)?[0],
-        });
-        _p.finish(warn);
-        result
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(token: u8) -> Result<RequestInfo, Error> {
+        let result = RequestInfo {
+            token,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_raw(&[self.token])?;
         Ok(_p.written())
     }
@@ -428,27 +563,57 @@ impl fmt::Debug for RequestInfo {
 
 impl<'a> Info<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info<'a>, Error> {
-        let result = Ok(Info {
+        Info::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info<'a>, Error> {
+        let result = Info::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Info"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info<'a>, Error> {
+        Ok(Info {
             token: int_from_string(_p.read_string()?)?,
-            version: sanitize(warn, _p.read_string()?)?,
-            name: sanitize(warn, _p.read_string()?)?,
-            map: sanitize(warn, _p.read_string()?)?,
-            game_type: sanitize(warn, _p.read_string()?)?,
+            version: sanitize(&mut _p.locate(warn, "Info.version"), _p.read_string()?)?,
+            name: sanitize(&mut _p.locate(warn, "Info.name"), _p.read_string()?)?,
+            map: sanitize(&mut _p.locate(warn, "Info.map"), _p.read_string()?)?,
+            game_type: sanitize(&mut _p.locate(warn, "Info.game_type"), _p.read_string()?)?,
             flags: int_from_string(_p.read_string()?)?,
             num_players: int_from_string(_p.read_string()?)?,
             max_players: int_from_string(_p.read_string()?)?,
             num_clients: int_from_string(_p.read_string()?)?,
             max_clients: int_from_string(_p.read_string()?)?,
             clients: ClientsData::from_bytes(_p.read_rest()?),
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.version).unwrap();
-        sanitize(&mut Panic, self.name).unwrap();
-        sanitize(&mut Panic, self.map).unwrap();
-        sanitize(&mut Panic, self.game_type).unwrap();
+        })
+    }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(token: i32, version: &'a [u8], name: &'a [u8], map: &'a [u8], game_type: &'a [u8], flags: i32, num_players: i32, max_players: i32, num_clients: i32, max_clients: i32, clients: ClientsData<'a>) -> Result<Info<'a>, Error> {
+        let result = Info {
+            token,
+            version,
+            name,
+            map,
+            game_type,
+            flags,
+            num_players,
+            max_players,
+            num_clients,
+            max_clients,
+            clients,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.version)?;
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.map)?;
+        sanitize(&mut Ignore, self.game_type)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.version)?;
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.map)?;
+        sanitize(&mut Ignore, self.game_type)?;
         _p.write_string(&string_from_int(self.token))?;
         _p.write_string(self.version)?;
         _p.write_string(self.name)?;
@@ -483,31 +648,65 @@ impl<'a> fmt::Debug for Info<'a> {
 
 impl<'a> InfoExtended<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<InfoExtended<'a>, Error> {
-        let result = Ok(InfoExtended {
+        InfoExtended::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<InfoExtended<'a>, Error> {
+        let result = InfoExtended::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "InfoExtended"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<InfoExtended<'a>, Error> {
+        Ok(InfoExtended {
             token: int_from_string(_p.read_string()?)?,
-            version: sanitize(warn, _p.read_string()?)?,
-            name: sanitize(warn, _p.read_string()?)?,
-            map: sanitize(warn, _p.read_string()?)?,
+            version: sanitize(&mut _p.locate(warn, "InfoExtended.version"), _p.read_string()?)?,
+            name: sanitize(&mut _p.locate(warn, "InfoExtended.name"), _p.read_string()?)?,
+            map: sanitize(&mut _p.locate(warn, "InfoExtended.map"), _p.read_string()?)?,
             map_crc: int_from_string(_p.read_string()?)?,
             map_size: int_from_string(_p.read_string()?)?,
-            game_type: sanitize(warn, _p.read_string()?)?,
+            game_type: sanitize(&mut _p.locate(warn, "InfoExtended.game_type"), _p.read_string()?)?,
             flags: int_from_string(_p.read_string()?)?,
             num_players: int_from_string(_p.read_string()?)?,
             max_players: int_from_string(_p.read_string()?)?,
             num_clients: int_from_string(_p.read_string()?)?,
             max_clients: int_from_string(_p.read_string()?)?,
-            reserved: sanitize(warn, _p.read_string()?)?,
+            reserved: sanitize(&mut _p.locate(warn, "InfoExtended.reserved"), _p.read_string()?)?,
             clients: ClientsData::from_bytes(_p.read_rest()?),
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.version).unwrap();
-        sanitize(&mut Panic, self.name).unwrap();
-        sanitize(&mut Panic, self.map).unwrap();
-        sanitize(&mut Panic, self.game_type).unwrap();
-        sanitize(&mut Panic, self.reserved).unwrap();
+        })
+    }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(token: i32, version: &'a [u8], name: &'a [u8], map: &'a [u8], map_crc: i32, map_size: i32, game_type: &'a [u8], flags: i32, num_players: i32, max_players: i32, num_clients: i32, max_clients: i32, reserved: &'a [u8], clients: ClientsData<'a>) -> Result<InfoExtended<'a>, Error> {
+        let result = InfoExtended {
+            token,
+            version,
+            name,
+            map,
+            map_crc,
+            map_size,
+            game_type,
+            flags,
+            num_players,
+            max_players,
+            num_clients,
+            max_clients,
+            reserved,
+            clients,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.version)?;
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.map)?;
+        sanitize(&mut Ignore, self.game_type)?;
+        sanitize(&mut Ignore, self.reserved)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.version)?;
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.map)?;
+        sanitize(&mut Ignore, self.game_type)?;
+        sanitize(&mut Ignore, self.reserved)?;
         _p.write_string(&string_from_int(self.token))?;
         _p.write_string(self.version)?;
         _p.write_string(self.name)?;
@@ -548,17 +747,36 @@ impl<'a> fmt::Debug for InfoExtended<'a> {
 
 impl<'a> InfoExtendedMore<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<InfoExtendedMore<'a>, Error> {
-        let result = Ok(InfoExtendedMore {
+        InfoExtendedMore::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<InfoExtendedMore<'a>, Error> {
+        let result = InfoExtendedMore::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "InfoExtendedMore"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<InfoExtendedMore<'a>, Error> {
+        Ok(InfoExtendedMore {
             token: int_from_string(_p.read_string()?)?,
             packet_no: int_from_string(_p.read_string()?)?,
-            reserved: sanitize(warn, _p.read_string()?)?,
+            reserved: sanitize(&mut _p.locate(warn, "InfoExtendedMore.reserved"), _p.read_string()?)?,
             clients: ClientsData::from_bytes(_p.read_rest()?),
-        });
-        _p.finish(warn);
-        result
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.reserved).unwrap();
+    pub fn new(token: i32, packet_no: i32, reserved: &'a [u8], clients: ClientsData<'a>) -> Result<InfoExtendedMore<'a>, Error> {
+        let result = InfoExtendedMore {
+            token,
+            packet_no,
+            reserved,
+            clients,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.reserved)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.reserved)?;
         _p.write_string(&string_from_int(self.token))?;
         _p.write_string(&string_from_int(self.packet_no))?;
         _p.write_string(self.reserved)?;
@@ -577,15 +795,155 @@ impl<'a> fmt::Debug for InfoExtendedMore<'a> {
     }
 }
 
+impl RequestInfo64 {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestInfo64, Error> {
+        RequestInfo64::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestInfo64, Error> {
+        let result = RequestInfo64::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RequestInfo64"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker) -> Result<RequestInfo64, Error> {
+        Ok(RequestInfo64 {
+            token: _p.read_raw(1)?[0],
+        })
+    }
+    pub fn new(token: u8) -> Result<RequestInfo64, Error> {
+        let result = RequestInfo64 {
+            token,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        _p.write_raw(&[self.token])?;
+        Ok(_p.written())
+    }
+}
+impl fmt::Debug for RequestInfo64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RequestInfo64")
+            .field("token", &self.token)
+            .finish()
+    }
+}
+
+impl<'a> Info64<'a> {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info64<'a>, Error> {
+        Info64::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info64<'a>, Error> {
+        let result = Info64::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Info64"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info64<'a>, Error> {
+        Ok(Info64 {
+            token: int_from_string(_p.read_string()?)?,
+            version: sanitize(&mut _p.locate(warn, "Info64.version"), _p.read_string()?)?,
+            name: sanitize(&mut _p.locate(warn, "Info64.name"), _p.read_string()?)?,
+            map: sanitize(&mut _p.locate(warn, "Info64.map"), _p.read_string()?)?,
+            game_type: sanitize(&mut _p.locate(warn, "Info64.game_type"), _p.read_string()?)?,
+            flags: int_from_string(_p.read_string()?)?,
+            num_players: int_from_string(_p.read_string()?)?,
+            max_players: int_from_string(_p.read_string()?)?,
+            num_clients: int_from_string(_p.read_string()?)?,
+            max_clients: int_from_string(_p.read_string()?)?,
+            offset: int_from_string(_p.read_string()?)?,
+            clients: ClientsData::from_bytes(_p.read_rest()?),
+        })
+    }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(token: i32, version: &'a [u8], name: &'a [u8], map: &'a [u8], game_type: &'a [u8], flags: i32, num_players: i32, max_players: i32, num_clients: i32, max_clients: i32, offset: i32, clients: ClientsData<'a>) -> Result<Info64<'a>, Error> {
+        let result = Info64 {
+            token,
+            version,
+            name,
+            map,
+            game_type,
+            flags,
+            num_players,
+            max_players,
+            num_clients,
+            max_clients,
+            offset,
+            clients,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.version)?;
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.map)?;
+        sanitize(&mut Ignore, self.game_type)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.version)?;
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.map)?;
+        sanitize(&mut Ignore, self.game_type)?;
+        _p.write_string(&string_from_int(self.token))?;
+        _p.write_string(self.version)?;
+        _p.write_string(self.name)?;
+        _p.write_string(self.map)?;
+        _p.write_string(self.game_type)?;
+        _p.write_string(&string_from_int(self.flags))?;
+        _p.write_string(&string_from_int(self.num_players))?;
+        _p.write_string(&string_from_int(self.max_players))?;
+        _p.write_string(&string_from_int(self.num_clients))?;
+        _p.write_string(&string_from_int(self.max_clients))?;
+        _p.write_string(&string_from_int(self.offset))?;
+        _p.write_rest(self.clients.as_bytes())?;
+        Ok(_p.written())
+    }
+}
+impl<'a> fmt::Debug for Info64<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Info64")
+            .field("token", &self.token)
+            .field("version", &pretty::Bytes::new(&self.version))
+            .field("name", &pretty::Bytes::new(&self.name))
+            .field("map", &pretty::Bytes::new(&self.map))
+            .field("game_type", &pretty::Bytes::new(&self.game_type))
+            .field("flags", &self.flags)
+            .field("num_players", &self.num_players)
+            .field("max_players", &self.max_players)
+            .field("num_clients", &self.num_clients)
+            .field("max_clients", &self.max_clients)
+            .field("offset", &self.offset)
+            .field("clients", &self.clients)
+            .finish()
+    }
+}
+
 impl Heartbeat {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Heartbeat, Error> {
-        let result = Ok(Heartbeat {
+        Heartbeat::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Heartbeat, Error> {
+        let result = Heartbeat::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Heartbeat"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker) -> Result<Heartbeat, Error> {
+        Ok(Heartbeat {
             alt_port: { let s = _p.read_raw(2)?; BeU16::from_bytes(&[s[0], s[1]]).to_u16() },
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(alt_port: u16) -> Result<Heartbeat, Error> {
+        let result = Heartbeat {
+            alt_port,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_raw(BeU16::from_u16(self.alt_port).as_bytes())?;
         Ok(_p.written())
     }
@@ -600,11 +958,15 @@ impl fmt::Debug for Heartbeat {
 
 impl ForwardCheck {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardCheck, Error> {
-        let result = Ok(ForwardCheck);
-        _p.finish(warn);
-        result
+        ForwardCheck::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardCheck, Error> {
+        error::finish(&mut _p.locate(warn, "ForwardCheck"), _p, Ok(ForwardCheck))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -617,11 +979,15 @@ impl fmt::Debug for ForwardCheck {
 
 impl ForwardResponse {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardResponse, Error> {
-        let result = Ok(ForwardResponse);
-        _p.finish(warn);
-        result
+        ForwardResponse::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardResponse, Error> {
+        error::finish(&mut _p.locate(warn, "ForwardResponse"), _p, Ok(ForwardResponse))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -634,11 +1000,15 @@ impl fmt::Debug for ForwardResponse {
 
 impl ForwardOk {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardOk, Error> {
-        let result = Ok(ForwardOk);
-        _p.finish(warn);
-        result
+        ForwardOk::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardOk, Error> {
+        error::finish(&mut _p.locate(warn, "ForwardOk"), _p, Ok(ForwardOk))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -651,11 +1021,15 @@ impl fmt::Debug for ForwardOk {
 
 impl ForwardError {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardError, Error> {
-        let result = Ok(ForwardError);
-        _p.finish(warn);
-        result
+        ForwardError::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardError, Error> {
+        error::finish(&mut _p.locate(warn, "ForwardError"), _p, Ok(ForwardError))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -1,12 +1,16 @@
-use buffer::CapacityError;
 use common::pretty;
+use common::warn::Located;
+use common::warn::strip_location;
 use enums;
+use error;
+use error::EncodeError;
 use error::Error;
 use gamenet_common::debug::DebugSlice;
 use packer::Packer;
 use packer::Unpacker;
 use packer::Warning;
 use packer::in_range;
+use packer::max_length;
 use packer::sanitize;
 use packer::to_bool;
 use packer::with_packer;
@@ -14,7 +18,7 @@ use std::fmt;
 use super::MessageId;
 use super::SystemOrGame;
 use uuid::Uuid;
-use warn::Panic;
+use warn::Ignore;
 use warn::Warn;
 
 pub use gamenet_common::msg::TuneParam;
@@ -30,7 +34,7 @@ impl<'a> Game<'a> {
         }
     }
     pub fn encode<'d, 's>(&self, mut p: Packer<'d, 's>)
-        -> Result<&'d [u8], CapacityError>
+        -> Result<&'d [u8], EncodeError>
     {
         with_packer(&mut p, |p| SystemOrGame::Game(self.msg_id()).encode_id(p))?;
         with_packer(&mut p, |p| self.encode_msg(p))?;
@@ -76,8 +80,40 @@ pub const SV_TEAMS_STATE: Uuid = Uuid::from_u128(0xa091961a_95e8_3744_bb60_5eac9
 pub const SV_DDRACE_TIME: Uuid = Uuid::from_u128(0x5dde8b3c_6f6f_37ac_a72a_bb341fe76de5);
 pub const SV_RECORD: Uuid = Uuid::from_u128(0x804f149f_9b53_3b0a_897f_59663a1c4eb9);
 pub const SV_KILL_MSG_TEAM: Uuid = Uuid::from_u128(0xee610b6f_909f_311e_93f7_11a95f55a086);
+pub const SV_YOUR_VOTE: Uuid = Uuid::from_u128(0xbfd7f0fc_16d5_3e10_8015_a78380f13870);
+pub const SV_RACE_FINISH: Uuid = Uuid::from_u128(0xc915ba68_0a49_3324_915a_7a6220cecf33);
+pub const SV_COMMAND_INFO: Uuid = Uuid::from_u128(0x90778f65_1b8f_322a_9713_cf741aa44a05);
+pub const SV_COMMAND_INFO_REMOVE: Uuid = Uuid::from_u128(0xeb2e77ce_e9a2_35aa_94be_235f523ac1aa);
+pub const SV_VOTE_OPTION_GROUP_START: Uuid = Uuid::from_u128(0x969d127c_b768_390d_8879_6104993769fa);
+pub const SV_VOTE_OPTION_GROUP_END: Uuid = Uuid::from_u128(0x4f096765_39b1_3766_82dc_61b20ccf589a);
+pub const SV_COMMAND_INFO_GROUP_START: Uuid = Uuid::from_u128(0x9e220138_d393_3cb0_90f1_e587c00ab1d0);
+pub const SV_COMMAND_INFO_GROUP_END: Uuid = Uuid::from_u128(0x054125d8_0062_3891_840b_47462285a01f);
+pub const SV_CHANGE_INFO_COOLDOWN: Uuid = Uuid::from_u128(0x746cb54c_6b2b_39a7_8cd8_7c7a1c6c3009);
+
+pub fn uuid_name(uuid: Uuid) -> Option<&'static str> {
+    Some(match uuid {
+        SV_MY_OWN_MESSAGE => "my-own-message@heinrich5991.de",
+        CL_SHOW_DISTANCE => "show-distance@netmsg.ddnet.tw",
+        CL_SHOW_OTHERS => "showothers@netmsg.ddnet.tw",
+        SV_TEAMS_STATE => "teamsstate@netmsg.ddnet.tw",
+        SV_DDRACE_TIME => "ddrace-time@netmsg.ddnet.tw",
+        SV_RECORD => "record@netmsg.ddnet.tw",
+        SV_KILL_MSG_TEAM => "killmsgteam@netmsg.ddnet.tw",
+        SV_YOUR_VOTE => "yourvote@netmsg.ddnet.org",
+        SV_RACE_FINISH => "racefinish@netmsg.ddnet.org",
+        SV_COMMAND_INFO => "commandinfo@netmsg.ddnet.org",
+        SV_COMMAND_INFO_REMOVE => "commandinfo-remove@netmsg.ddnet.org",
+        SV_VOTE_OPTION_GROUP_START => "sv-vote-option-group-start@netmsg.ddnet.org",
+        SV_VOTE_OPTION_GROUP_END => "sv-vote-option-group-end@netmsg.ddnet.org",
+        SV_COMMAND_INFO_GROUP_START => "sv-commandinfo-group-start@netmsg.ddnet.org",
+        SV_COMMAND_INFO_GROUP_END => "sv-commandinfo-group-end@netmsg.ddnet.org",
+        SV_CHANGE_INFO_COOLDOWN => "change-info-cooldown@netmsg.ddnet.org",
+        _ => return None,
+    })
+}
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub enum Game<'a> {
     SvMotd(SvMotd<'a>),
     SvBroadcast(SvBroadcast<'a>),
@@ -117,50 +153,71 @@ pub enum Game<'a> {
     SvDdraceTime(SvDdraceTime),
     SvRecord(SvRecord),
     SvKillMsgTeam(SvKillMsgTeam),
+    SvYourVote(SvYourVote),
+    SvRaceFinish(SvRaceFinish),
+    SvCommandInfo(SvCommandInfo<'a>),
+    SvCommandInfoRemove(SvCommandInfoRemove<'a>),
+    SvVoteOptionGroupStart(SvVoteOptionGroupStart),
+    SvVoteOptionGroupEnd(SvVoteOptionGroupEnd),
+    SvCommandInfoGroupStart(SvCommandInfoGroupStart),
+    SvCommandInfoGroupEnd(SvCommandInfoGroupEnd),
+    SvChangeInfoCooldown(SvChangeInfoCooldown),
 }
 
 impl<'a> Game<'a> {
     pub fn decode_msg<W: Warn<Warning>>(warn: &mut W, msg_id: MessageId, _p: &mut Unpacker<'a>) -> Result<Game<'a>, Error> {
+        Game::decode_msg_located(&mut strip_location(warn), msg_id, _p)
+    }
+    pub fn decode_msg_located<W: Warn<Located<Warning>>>(warn: &mut W, msg_id: MessageId, _p: &mut Unpacker<'a>) -> Result<Game<'a>, Error> {
         use self::MessageId::*;
         Ok(match msg_id {
-            Ordinal(SV_MOTD) => Game::SvMotd(SvMotd::decode(warn, _p)?),
-            Ordinal(SV_BROADCAST) => Game::SvBroadcast(SvBroadcast::decode(warn, _p)?),
-            Ordinal(SV_CHAT) => Game::SvChat(SvChat::decode(warn, _p)?),
-            Ordinal(SV_KILL_MSG) => Game::SvKillMsg(SvKillMsg::decode(warn, _p)?),
-            Ordinal(SV_SOUND_GLOBAL) => Game::SvSoundGlobal(SvSoundGlobal::decode(warn, _p)?),
-            Ordinal(SV_TUNE_PARAMS) => Game::SvTuneParams(SvTuneParams::decode(warn, _p)?),
-            Ordinal(UNUSED) => Game::Unused(Unused::decode(warn, _p)?),
-            Ordinal(SV_READY_TO_ENTER) => Game::SvReadyToEnter(SvReadyToEnter::decode(warn, _p)?),
-            Ordinal(SV_WEAPON_PICKUP) => Game::SvWeaponPickup(SvWeaponPickup::decode(warn, _p)?),
-            Ordinal(SV_EMOTICON) => Game::SvEmoticon(SvEmoticon::decode(warn, _p)?),
-            Ordinal(SV_VOTE_CLEAR_OPTIONS) => Game::SvVoteClearOptions(SvVoteClearOptions::decode(warn, _p)?),
-            Ordinal(SV_VOTE_OPTION_LIST_ADD) => Game::SvVoteOptionListAdd(SvVoteOptionListAdd::decode(warn, _p)?),
-            Ordinal(SV_VOTE_OPTION_ADD) => Game::SvVoteOptionAdd(SvVoteOptionAdd::decode(warn, _p)?),
-            Ordinal(SV_VOTE_OPTION_REMOVE) => Game::SvVoteOptionRemove(SvVoteOptionRemove::decode(warn, _p)?),
-            Ordinal(SV_VOTE_SET) => Game::SvVoteSet(SvVoteSet::decode(warn, _p)?),
-            Ordinal(SV_VOTE_STATUS) => Game::SvVoteStatus(SvVoteStatus::decode(warn, _p)?),
-            Ordinal(CL_SAY) => Game::ClSay(ClSay::decode(warn, _p)?),
-            Ordinal(CL_SET_TEAM) => Game::ClSetTeam(ClSetTeam::decode(warn, _p)?),
-            Ordinal(CL_SET_SPECTATOR_MODE) => Game::ClSetSpectatorMode(ClSetSpectatorMode::decode(warn, _p)?),
-            Ordinal(CL_START_INFO) => Game::ClStartInfo(ClStartInfo::decode(warn, _p)?),
-            Ordinal(CL_CHANGE_INFO) => Game::ClChangeInfo(ClChangeInfo::decode(warn, _p)?),
-            Ordinal(CL_KILL) => Game::ClKill(ClKill::decode(warn, _p)?),
-            Ordinal(CL_EMOTICON) => Game::ClEmoticon(ClEmoticon::decode(warn, _p)?),
-            Ordinal(CL_VOTE) => Game::ClVote(ClVote::decode(warn, _p)?),
-            Ordinal(CL_CALL_VOTE) => Game::ClCallVote(ClCallVote::decode(warn, _p)?),
-            Ordinal(CL_IS_DDNET_LEGACY) => Game::ClIsDdnetLegacy(ClIsDdnetLegacy::decode(warn, _p)?),
-            Ordinal(SV_DDRACE_TIME_LEGACY) => Game::SvDdraceTimeLegacy(SvDdraceTimeLegacy::decode(warn, _p)?),
-            Ordinal(SV_RECORD_LEGACY) => Game::SvRecordLegacy(SvRecordLegacy::decode(warn, _p)?),
-            Ordinal(UNUSED2) => Game::Unused2(Unused2::decode(warn, _p)?),
-            Ordinal(SV_TEAMS_STATE_LEGACY) => Game::SvTeamsStateLegacy(SvTeamsStateLegacy::decode(warn, _p)?),
-            Ordinal(CL_SHOW_OTHERS_LEGACY) => Game::ClShowOthersLegacy(ClShowOthersLegacy::decode(warn, _p)?),
-            Uuid(SV_MY_OWN_MESSAGE) => Game::SvMyOwnMessage(SvMyOwnMessage::decode(warn, _p)?),
-            Uuid(CL_SHOW_DISTANCE) => Game::ClShowDistance(ClShowDistance::decode(warn, _p)?),
-            Uuid(CL_SHOW_OTHERS) => Game::ClShowOthers(ClShowOthers::decode(warn, _p)?),
-            Uuid(SV_TEAMS_STATE) => Game::SvTeamsState(SvTeamsState::decode(warn, _p)?),
-            Uuid(SV_DDRACE_TIME) => Game::SvDdraceTime(SvDdraceTime::decode(warn, _p)?),
-            Uuid(SV_RECORD) => Game::SvRecord(SvRecord::decode(warn, _p)?),
-            Uuid(SV_KILL_MSG_TEAM) => Game::SvKillMsgTeam(SvKillMsgTeam::decode(warn, _p)?),
+            Ordinal(SV_MOTD) => Game::SvMotd(SvMotd::decode_located(warn, _p)?),
+            Ordinal(SV_BROADCAST) => Game::SvBroadcast(SvBroadcast::decode_located(warn, _p)?),
+            Ordinal(SV_CHAT) => Game::SvChat(SvChat::decode_located(warn, _p)?),
+            Ordinal(SV_KILL_MSG) => Game::SvKillMsg(SvKillMsg::decode_located(warn, _p)?),
+            Ordinal(SV_SOUND_GLOBAL) => Game::SvSoundGlobal(SvSoundGlobal::decode_located(warn, _p)?),
+            Ordinal(SV_TUNE_PARAMS) => Game::SvTuneParams(SvTuneParams::decode_located(warn, _p)?),
+            Ordinal(UNUSED) => Game::Unused(Unused::decode_located(warn, _p)?),
+            Ordinal(SV_READY_TO_ENTER) => Game::SvReadyToEnter(SvReadyToEnter::decode_located(warn, _p)?),
+            Ordinal(SV_WEAPON_PICKUP) => Game::SvWeaponPickup(SvWeaponPickup::decode_located(warn, _p)?),
+            Ordinal(SV_EMOTICON) => Game::SvEmoticon(SvEmoticon::decode_located(warn, _p)?),
+            Ordinal(SV_VOTE_CLEAR_OPTIONS) => Game::SvVoteClearOptions(SvVoteClearOptions::decode_located(warn, _p)?),
+            Ordinal(SV_VOTE_OPTION_LIST_ADD) => Game::SvVoteOptionListAdd(SvVoteOptionListAdd::decode_located(warn, _p)?),
+            Ordinal(SV_VOTE_OPTION_ADD) => Game::SvVoteOptionAdd(SvVoteOptionAdd::decode_located(warn, _p)?),
+            Ordinal(SV_VOTE_OPTION_REMOVE) => Game::SvVoteOptionRemove(SvVoteOptionRemove::decode_located(warn, _p)?),
+            Ordinal(SV_VOTE_SET) => Game::SvVoteSet(SvVoteSet::decode_located(warn, _p)?),
+            Ordinal(SV_VOTE_STATUS) => Game::SvVoteStatus(SvVoteStatus::decode_located(warn, _p)?),
+            Ordinal(CL_SAY) => Game::ClSay(ClSay::decode_located(warn, _p)?),
+            Ordinal(CL_SET_TEAM) => Game::ClSetTeam(ClSetTeam::decode_located(warn, _p)?),
+            Ordinal(CL_SET_SPECTATOR_MODE) => Game::ClSetSpectatorMode(ClSetSpectatorMode::decode_located(warn, _p)?),
+            Ordinal(CL_START_INFO) => Game::ClStartInfo(ClStartInfo::decode_located(warn, _p)?),
+            Ordinal(CL_CHANGE_INFO) => Game::ClChangeInfo(ClChangeInfo::decode_located(warn, _p)?),
+            Ordinal(CL_KILL) => Game::ClKill(ClKill::decode_located(warn, _p)?),
+            Ordinal(CL_EMOTICON) => Game::ClEmoticon(ClEmoticon::decode_located(warn, _p)?),
+            Ordinal(CL_VOTE) => Game::ClVote(ClVote::decode_located(warn, _p)?),
+            Ordinal(CL_CALL_VOTE) => Game::ClCallVote(ClCallVote::decode_located(warn, _p)?),
+            Ordinal(CL_IS_DDNET_LEGACY) => Game::ClIsDdnetLegacy(ClIsDdnetLegacy::decode_located(warn, _p)?),
+            Ordinal(SV_DDRACE_TIME_LEGACY) => Game::SvDdraceTimeLegacy(SvDdraceTimeLegacy::decode_located(warn, _p)?),
+            Ordinal(SV_RECORD_LEGACY) => Game::SvRecordLegacy(SvRecordLegacy::decode_located(warn, _p)?),
+            Ordinal(UNUSED2) => Game::Unused2(Unused2::decode_located(warn, _p)?),
+            Ordinal(SV_TEAMS_STATE_LEGACY) => Game::SvTeamsStateLegacy(SvTeamsStateLegacy::decode_located(warn, _p)?),
+            Ordinal(CL_SHOW_OTHERS_LEGACY) => Game::ClShowOthersLegacy(ClShowOthersLegacy::decode_located(warn, _p)?),
+            Uuid(SV_MY_OWN_MESSAGE) => Game::SvMyOwnMessage(SvMyOwnMessage::decode_located(warn, _p)?),
+            Uuid(CL_SHOW_DISTANCE) => Game::ClShowDistance(ClShowDistance::decode_located(warn, _p)?),
+            Uuid(CL_SHOW_OTHERS) => Game::ClShowOthers(ClShowOthers::decode_located(warn, _p)?),
+            Uuid(SV_TEAMS_STATE) => Game::SvTeamsState(SvTeamsState::decode_located(warn, _p)?),
+            Uuid(SV_DDRACE_TIME) => Game::SvDdraceTime(SvDdraceTime::decode_located(warn, _p)?),
+            Uuid(SV_RECORD) => Game::SvRecord(SvRecord::decode_located(warn, _p)?),
+            Uuid(SV_KILL_MSG_TEAM) => Game::SvKillMsgTeam(SvKillMsgTeam::decode_located(warn, _p)?),
+            Uuid(SV_YOUR_VOTE) => Game::SvYourVote(SvYourVote::decode_located(warn, _p)?),
+            Uuid(SV_RACE_FINISH) => Game::SvRaceFinish(SvRaceFinish::decode_located(warn, _p)?),
+            Uuid(SV_COMMAND_INFO) => Game::SvCommandInfo(SvCommandInfo::decode_located(warn, _p)?),
+            Uuid(SV_COMMAND_INFO_REMOVE) => Game::SvCommandInfoRemove(SvCommandInfoRemove::decode_located(warn, _p)?),
+            Uuid(SV_VOTE_OPTION_GROUP_START) => Game::SvVoteOptionGroupStart(SvVoteOptionGroupStart::decode_located(warn, _p)?),
+            Uuid(SV_VOTE_OPTION_GROUP_END) => Game::SvVoteOptionGroupEnd(SvVoteOptionGroupEnd::decode_located(warn, _p)?),
+            Uuid(SV_COMMAND_INFO_GROUP_START) => Game::SvCommandInfoGroupStart(SvCommandInfoGroupStart::decode_located(warn, _p)?),
+            Uuid(SV_COMMAND_INFO_GROUP_END) => Game::SvCommandInfoGroupEnd(SvCommandInfoGroupEnd::decode_located(warn, _p)?),
+            Uuid(SV_CHANGE_INFO_COOLDOWN) => Game::SvChangeInfoCooldown(SvChangeInfoCooldown::decode_located(warn, _p)?),
             _ => return Err(Error::UnknownId),
         })
     }
@@ -204,9 +261,69 @@ impl<'a> Game<'a> {
             Game::SvDdraceTime(_) => MessageId::from(SV_DDRACE_TIME),
             Game::SvRecord(_) => MessageId::from(SV_RECORD),
             Game::SvKillMsgTeam(_) => MessageId::from(SV_KILL_MSG_TEAM),
+            Game::SvYourVote(_) => MessageId::from(SV_YOUR_VOTE),
+            Game::SvRaceFinish(_) => MessageId::from(SV_RACE_FINISH),
+            Game::SvCommandInfo(_) => MessageId::from(SV_COMMAND_INFO),
+            Game::SvCommandInfoRemove(_) => MessageId::from(SV_COMMAND_INFO_REMOVE),
+            Game::SvVoteOptionGroupStart(_) => MessageId::from(SV_VOTE_OPTION_GROUP_START),
+            Game::SvVoteOptionGroupEnd(_) => MessageId::from(SV_VOTE_OPTION_GROUP_END),
+            Game::SvCommandInfoGroupStart(_) => MessageId::from(SV_COMMAND_INFO_GROUP_START),
+            Game::SvCommandInfoGroupEnd(_) => MessageId::from(SV_COMMAND_INFO_GROUP_END),
+            Game::SvChangeInfoCooldown(_) => MessageId::from(SV_CHANGE_INFO_COOLDOWN),
+        }
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        match *self {
+            Game::SvMotd(ref i) => i.validate(),
+            Game::SvBroadcast(ref i) => i.validate(),
+            Game::SvChat(ref i) => i.validate(),
+            Game::SvKillMsg(ref i) => i.validate(),
+            Game::SvSoundGlobal(ref i) => i.validate(),
+            Game::SvTuneParams(ref i) => i.validate(),
+            Game::Unused(ref i) => i.validate(),
+            Game::SvReadyToEnter(ref i) => i.validate(),
+            Game::SvWeaponPickup(ref i) => i.validate(),
+            Game::SvEmoticon(ref i) => i.validate(),
+            Game::SvVoteClearOptions(ref i) => i.validate(),
+            Game::SvVoteOptionListAdd(ref i) => i.validate(),
+            Game::SvVoteOptionAdd(ref i) => i.validate(),
+            Game::SvVoteOptionRemove(ref i) => i.validate(),
+            Game::SvVoteSet(ref i) => i.validate(),
+            Game::SvVoteStatus(ref i) => i.validate(),
+            Game::ClSay(ref i) => i.validate(),
+            Game::ClSetTeam(ref i) => i.validate(),
+            Game::ClSetSpectatorMode(ref i) => i.validate(),
+            Game::ClStartInfo(ref i) => i.validate(),
+            Game::ClChangeInfo(ref i) => i.validate(),
+            Game::ClKill(ref i) => i.validate(),
+            Game::ClEmoticon(ref i) => i.validate(),
+            Game::ClVote(ref i) => i.validate(),
+            Game::ClCallVote(ref i) => i.validate(),
+            Game::ClIsDdnetLegacy(ref i) => i.validate(),
+            Game::SvDdraceTimeLegacy(ref i) => i.validate(),
+            Game::SvRecordLegacy(ref i) => i.validate(),
+            Game::Unused2(ref i) => i.validate(),
+            Game::SvTeamsStateLegacy(ref i) => i.validate(),
+            Game::ClShowOthersLegacy(ref i) => i.validate(),
+            Game::SvMyOwnMessage(ref i) => i.validate(),
+            Game::ClShowDistance(ref i) => i.validate(),
+            Game::ClShowOthers(ref i) => i.validate(),
+            Game::SvTeamsState(ref i) => i.validate(),
+            Game::SvDdraceTime(ref i) => i.validate(),
+            Game::SvRecord(ref i) => i.validate(),
+            Game::SvKillMsgTeam(ref i) => i.validate(),
+            Game::SvYourVote(ref i) => i.validate(),
+            Game::SvRaceFinish(ref i) => i.validate(),
+            Game::SvCommandInfo(ref i) => i.validate(),
+            Game::SvCommandInfoRemove(ref i) => i.validate(),
+            Game::SvVoteOptionGroupStart(ref i) => i.validate(),
+            Game::SvVoteOptionGroupEnd(ref i) => i.validate(),
+            Game::SvCommandInfoGroupStart(ref i) => i.validate(),
+            Game::SvCommandInfoGroupEnd(ref i) => i.validate(),
+            Game::SvChangeInfoCooldown(ref i) => i.validate(),
         }
     }
-    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         match *self {
             Game::SvMotd(ref i) => i.encode(p),
             Game::SvBroadcast(ref i) => i.encode(p),
@@ -246,6 +363,15 @@ impl<'a> Game<'a> {
             Game::SvDdraceTime(ref i) => i.encode(p),
             Game::SvRecord(ref i) => i.encode(p),
             Game::SvKillMsgTeam(ref i) => i.encode(p),
+            Game::SvYourVote(ref i) => i.encode(p),
+            Game::SvRaceFinish(ref i) => i.encode(p),
+            Game::SvCommandInfo(ref i) => i.encode(p),
+            Game::SvCommandInfoRemove(ref i) => i.encode(p),
+            Game::SvVoteOptionGroupStart(ref i) => i.encode(p),
+            Game::SvVoteOptionGroupEnd(ref i) => i.encode(p),
+            Game::SvCommandInfoGroupStart(ref i) => i.encode(p),
+            Game::SvCommandInfoGroupEnd(ref i) => i.encode(p),
+            Game::SvChangeInfoCooldown(ref i) => i.encode(p),
         }
     }
 }
@@ -291,6 +417,15 @@ impl<'a> fmt::Debug for Game<'a> {
             Game::SvDdraceTime(ref i) => i.fmt(f),
             Game::SvRecord(ref i) => i.fmt(f),
             Game::SvKillMsgTeam(ref i) => i.fmt(f),
+            Game::SvYourVote(ref i) => i.fmt(f),
+            Game::SvRaceFinish(ref i) => i.fmt(f),
+            Game::SvCommandInfo(ref i) => i.fmt(f),
+            Game::SvCommandInfoRemove(ref i) => i.fmt(f),
+            Game::SvVoteOptionGroupStart(ref i) => i.fmt(f),
+            Game::SvVoteOptionGroupEnd(ref i) => i.fmt(f),
+            Game::SvCommandInfoGroupStart(ref i) => i.fmt(f),
+            Game::SvCommandInfoGroupEnd(ref i) => i.fmt(f),
+            Game::SvChangeInfoCooldown(ref i) => i.fmt(f),
         }
     }
 }
@@ -522,24 +657,263 @@ impl<'a> From<SvKillMsgTeam> for Game<'a> {
         Game::SvKillMsgTeam(i)
     }
 }
+
+impl<'a> From<SvYourVote> for Game<'a> {
+    fn from(i: SvYourVote) -> Game<'a> {
+        Game::SvYourVote(i)
+    }
+}
+
+impl<'a> From<SvRaceFinish> for Game<'a> {
+    fn from(i: SvRaceFinish) -> Game<'a> {
+        Game::SvRaceFinish(i)
+    }
+}
+
+impl<'a> From<SvCommandInfo<'a>> for Game<'a> {
+    fn from(i: SvCommandInfo<'a>) -> Game<'a> {
+        Game::SvCommandInfo(i)
+    }
+}
+
+impl<'a> From<SvCommandInfoRemove<'a>> for Game<'a> {
+    fn from(i: SvCommandInfoRemove<'a>) -> Game<'a> {
+        Game::SvCommandInfoRemove(i)
+    }
+}
+
+impl<'a> From<SvVoteOptionGroupStart> for Game<'a> {
+    fn from(i: SvVoteOptionGroupStart) -> Game<'a> {
+        Game::SvVoteOptionGroupStart(i)
+    }
+}
+
+impl<'a> From<SvVoteOptionGroupEnd> for Game<'a> {
+    fn from(i: SvVoteOptionGroupEnd) -> Game<'a> {
+        Game::SvVoteOptionGroupEnd(i)
+    }
+}
+
+impl<'a> From<SvCommandInfoGroupStart> for Game<'a> {
+    fn from(i: SvCommandInfoGroupStart) -> Game<'a> {
+        Game::SvCommandInfoGroupStart(i)
+    }
+}
+
+impl<'a> From<SvCommandInfoGroupEnd> for Game<'a> {
+    fn from(i: SvCommandInfoGroupEnd) -> Game<'a> {
+        Game::SvCommandInfoGroupEnd(i)
+    }
+}
+
+impl<'a> From<SvChangeInfoCooldown> for Game<'a> {
+    fn from(i: SvChangeInfoCooldown) -> Game<'a> {
+        Game::SvChangeInfoCooldown(i)
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GameOwned {
+    SvMotd(SvMotdOwned),
+    SvBroadcast(SvBroadcastOwned),
+    SvChat(SvChatOwned),
+    SvKillMsg(SvKillMsg),
+    SvSoundGlobal(SvSoundGlobal),
+    SvTuneParams(SvTuneParams),
+    Unused(Unused),
+    SvReadyToEnter(SvReadyToEnter),
+    SvWeaponPickup(SvWeaponPickup),
+    SvEmoticon(SvEmoticon),
+    SvVoteClearOptions(SvVoteClearOptions),
+    SvVoteOptionListAdd(SvVoteOptionListAddOwned),
+    SvVoteOptionAdd(SvVoteOptionAddOwned),
+    SvVoteOptionRemove(SvVoteOptionRemoveOwned),
+    SvVoteSet(SvVoteSetOwned),
+    SvVoteStatus(SvVoteStatus),
+    ClSay(ClSayOwned),
+    ClSetTeam(ClSetTeam),
+    ClSetSpectatorMode(ClSetSpectatorMode),
+    ClStartInfo(ClStartInfoOwned),
+    ClChangeInfo(ClChangeInfoOwned),
+    ClKill(ClKill),
+    ClEmoticon(ClEmoticon),
+    ClVote(ClVote),
+    ClCallVote(ClCallVoteOwned),
+    ClIsDdnetLegacy(ClIsDdnetLegacy),
+    SvDdraceTimeLegacy(SvDdraceTimeLegacy),
+    SvRecordLegacy(SvRecordLegacy),
+    Unused2(Unused2),
+    SvTeamsStateLegacy(SvTeamsStateLegacy),
+    ClShowOthersLegacy(ClShowOthersLegacy),
+    SvMyOwnMessage(SvMyOwnMessage),
+    ClShowDistance(ClShowDistance),
+    ClShowOthers(ClShowOthers),
+    SvTeamsState(SvTeamsState),
+    SvDdraceTime(SvDdraceTime),
+    SvRecord(SvRecord),
+    SvKillMsgTeam(SvKillMsgTeam),
+    SvYourVote(SvYourVote),
+    SvRaceFinish(SvRaceFinish),
+    SvCommandInfo(SvCommandInfoOwned),
+    SvCommandInfoRemove(SvCommandInfoRemoveOwned),
+    SvVoteOptionGroupStart(SvVoteOptionGroupStart),
+    SvVoteOptionGroupEnd(SvVoteOptionGroupEnd),
+    SvCommandInfoGroupStart(SvCommandInfoGroupStart),
+    SvCommandInfoGroupEnd(SvCommandInfoGroupEnd),
+    SvChangeInfoCooldown(SvChangeInfoCooldown),
+}
+
+impl<'a> Game<'a> {
+    pub fn to_owned(&self) -> GameOwned {
+        match *self {
+            Game::SvMotd(ref i) => GameOwned::SvMotd(i.to_owned()),
+            Game::SvBroadcast(ref i) => GameOwned::SvBroadcast(i.to_owned()),
+            Game::SvChat(ref i) => GameOwned::SvChat(i.to_owned()),
+            Game::SvKillMsg(i) => GameOwned::SvKillMsg(i),
+            Game::SvSoundGlobal(i) => GameOwned::SvSoundGlobal(i),
+            Game::SvTuneParams(i) => GameOwned::SvTuneParams(i),
+            Game::Unused(i) => GameOwned::Unused(i),
+            Game::SvReadyToEnter(i) => GameOwned::SvReadyToEnter(i),
+            Game::SvWeaponPickup(i) => GameOwned::SvWeaponPickup(i),
+            Game::SvEmoticon(i) => GameOwned::SvEmoticon(i),
+            Game::SvVoteClearOptions(i) => GameOwned::SvVoteClearOptions(i),
+            Game::SvVoteOptionListAdd(ref i) => GameOwned::SvVoteOptionListAdd(i.to_owned()),
+            Game::SvVoteOptionAdd(ref i) => GameOwned::SvVoteOptionAdd(i.to_owned()),
+            Game::SvVoteOptionRemove(ref i) => GameOwned::SvVoteOptionRemove(i.to_owned()),
+            Game::SvVoteSet(ref i) => GameOwned::SvVoteSet(i.to_owned()),
+            Game::SvVoteStatus(i) => GameOwned::SvVoteStatus(i),
+            Game::ClSay(ref i) => GameOwned::ClSay(i.to_owned()),
+            Game::ClSetTeam(i) => GameOwned::ClSetTeam(i),
+            Game::ClSetSpectatorMode(i) => GameOwned::ClSetSpectatorMode(i),
+            Game::ClStartInfo(ref i) => GameOwned::ClStartInfo(i.to_owned()),
+            Game::ClChangeInfo(ref i) => GameOwned::ClChangeInfo(i.to_owned()),
+            Game::ClKill(i) => GameOwned::ClKill(i),
+            Game::ClEmoticon(i) => GameOwned::ClEmoticon(i),
+            Game::ClVote(i) => GameOwned::ClVote(i),
+            Game::ClCallVote(ref i) => GameOwned::ClCallVote(i.to_owned()),
+            Game::ClIsDdnetLegacy(i) => GameOwned::ClIsDdnetLegacy(i),
+            Game::SvDdraceTimeLegacy(i) => GameOwned::SvDdraceTimeLegacy(i),
+            Game::SvRecordLegacy(i) => GameOwned::SvRecordLegacy(i),
+            Game::Unused2(i) => GameOwned::Unused2(i),
+            Game::SvTeamsStateLegacy(i) => GameOwned::SvTeamsStateLegacy(i),
+            Game::ClShowOthersLegacy(i) => GameOwned::ClShowOthersLegacy(i),
+            Game::SvMyOwnMessage(i) => GameOwned::SvMyOwnMessage(i),
+            Game::ClShowDistance(i) => GameOwned::ClShowDistance(i),
+            Game::ClShowOthers(i) => GameOwned::ClShowOthers(i),
+            Game::SvTeamsState(i) => GameOwned::SvTeamsState(i),
+            Game::SvDdraceTime(i) => GameOwned::SvDdraceTime(i),
+            Game::SvRecord(i) => GameOwned::SvRecord(i),
+            Game::SvKillMsgTeam(i) => GameOwned::SvKillMsgTeam(i),
+            Game::SvYourVote(i) => GameOwned::SvYourVote(i),
+            Game::SvRaceFinish(i) => GameOwned::SvRaceFinish(i),
+            Game::SvCommandInfo(ref i) => GameOwned::SvCommandInfo(i.to_owned()),
+            Game::SvCommandInfoRemove(ref i) => GameOwned::SvCommandInfoRemove(i.to_owned()),
+            Game::SvVoteOptionGroupStart(i) => GameOwned::SvVoteOptionGroupStart(i),
+            Game::SvVoteOptionGroupEnd(i) => GameOwned::SvVoteOptionGroupEnd(i),
+            Game::SvCommandInfoGroupStart(i) => GameOwned::SvCommandInfoGroupStart(i),
+            Game::SvCommandInfoGroupEnd(i) => GameOwned::SvCommandInfoGroupEnd(i),
+            Game::SvChangeInfoCooldown(i) => GameOwned::SvChangeInfoCooldown(i),
+        }
+    }
+}
+
+impl GameOwned {
+    pub fn as_ref(&self) -> Game<'_> {
+        match *self {
+            GameOwned::SvMotd(ref i) => Game::SvMotd(i.as_ref()),
+            GameOwned::SvBroadcast(ref i) => Game::SvBroadcast(i.as_ref()),
+            GameOwned::SvChat(ref i) => Game::SvChat(i.as_ref()),
+            GameOwned::SvKillMsg(i) => Game::SvKillMsg(i),
+            GameOwned::SvSoundGlobal(i) => Game::SvSoundGlobal(i),
+            GameOwned::SvTuneParams(i) => Game::SvTuneParams(i),
+            GameOwned::Unused(i) => Game::Unused(i),
+            GameOwned::SvReadyToEnter(i) => Game::SvReadyToEnter(i),
+            GameOwned::SvWeaponPickup(i) => Game::SvWeaponPickup(i),
+            GameOwned::SvEmoticon(i) => Game::SvEmoticon(i),
+            GameOwned::SvVoteClearOptions(i) => Game::SvVoteClearOptions(i),
+            GameOwned::SvVoteOptionListAdd(ref i) => Game::SvVoteOptionListAdd(i.as_ref()),
+            GameOwned::SvVoteOptionAdd(ref i) => Game::SvVoteOptionAdd(i.as_ref()),
+            GameOwned::SvVoteOptionRemove(ref i) => Game::SvVoteOptionRemove(i.as_ref()),
+            GameOwned::SvVoteSet(ref i) => Game::SvVoteSet(i.as_ref()),
+            GameOwned::SvVoteStatus(i) => Game::SvVoteStatus(i),
+            GameOwned::ClSay(ref i) => Game::ClSay(i.as_ref()),
+            GameOwned::ClSetTeam(i) => Game::ClSetTeam(i),
+            GameOwned::ClSetSpectatorMode(i) => Game::ClSetSpectatorMode(i),
+            GameOwned::ClStartInfo(ref i) => Game::ClStartInfo(i.as_ref()),
+            GameOwned::ClChangeInfo(ref i) => Game::ClChangeInfo(i.as_ref()),
+            GameOwned::ClKill(i) => Game::ClKill(i),
+            GameOwned::ClEmoticon(i) => Game::ClEmoticon(i),
+            GameOwned::ClVote(i) => Game::ClVote(i),
+            GameOwned::ClCallVote(ref i) => Game::ClCallVote(i.as_ref()),
+            GameOwned::ClIsDdnetLegacy(i) => Game::ClIsDdnetLegacy(i),
+            GameOwned::SvDdraceTimeLegacy(i) => Game::SvDdraceTimeLegacy(i),
+            GameOwned::SvRecordLegacy(i) => Game::SvRecordLegacy(i),
+            GameOwned::Unused2(i) => Game::Unused2(i),
+            GameOwned::SvTeamsStateLegacy(i) => Game::SvTeamsStateLegacy(i),
+            GameOwned::ClShowOthersLegacy(i) => Game::ClShowOthersLegacy(i),
+            GameOwned::SvMyOwnMessage(i) => Game::SvMyOwnMessage(i),
+            GameOwned::ClShowDistance(i) => Game::ClShowDistance(i),
+            GameOwned::ClShowOthers(i) => Game::ClShowOthers(i),
+            GameOwned::SvTeamsState(i) => Game::SvTeamsState(i),
+            GameOwned::SvDdraceTime(i) => Game::SvDdraceTime(i),
+            GameOwned::SvRecord(i) => Game::SvRecord(i),
+            GameOwned::SvKillMsgTeam(i) => Game::SvKillMsgTeam(i),
+            GameOwned::SvYourVote(i) => Game::SvYourVote(i),
+            GameOwned::SvRaceFinish(i) => Game::SvRaceFinish(i),
+            GameOwned::SvCommandInfo(ref i) => Game::SvCommandInfo(i.as_ref()),
+            GameOwned::SvCommandInfoRemove(ref i) => Game::SvCommandInfoRemove(i.as_ref()),
+            GameOwned::SvVoteOptionGroupStart(i) => Game::SvVoteOptionGroupStart(i),
+            GameOwned::SvVoteOptionGroupEnd(i) => Game::SvVoteOptionGroupEnd(i),
+            GameOwned::SvCommandInfoGroupStart(i) => Game::SvCommandInfoGroupStart(i),
+            GameOwned::SvCommandInfoGroupEnd(i) => Game::SvCommandInfoGroupEnd(i),
+            GameOwned::SvChangeInfoCooldown(i) => Game::SvChangeInfoCooldown(i),
+        }
+    }
+    pub fn msg_id(&self) -> MessageId {
+        self.as_ref().msg_id()
+    }
+    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        self.as_ref().encode_msg(p)
+    }
+}
+
+impl fmt::Debug for GameOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> From<Game<'a>> for GameOwned {
+    fn from(i: Game<'a>) -> GameOwned {
+        i.to_owned()
+    }
+}
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvMotd<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub message: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvBroadcast<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub message: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvChat<'a> {
     pub team: i32,
     pub client_id: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub message: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvKillMsg {
     pub killer: i32,
     pub victim: i32,
@@ -548,11 +922,13 @@ pub struct SvKillMsg {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvSoundGlobal {
     pub sound_id: enums::Sound,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvTuneParams {
     pub ground_control_speed: TuneParam,
     pub ground_control_accel: TuneParam,
@@ -604,49 +980,64 @@ pub struct SvTuneParams {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Unused;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvReadyToEnter;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvWeaponPickup {
     pub weapon: enums::Weapon,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvEmoticon {
     pub client_id: i32,
     pub emoticon: enums::Emoticon,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvVoteClearOptions;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvVoteOptionListAdd<'a> {
     pub num_options: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes_array::serialize"))]
     pub description: [&'a [u8]; 15],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvVoteOptionAdd<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub description: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvVoteOptionRemove<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub description: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvVoteSet<'a> {
     pub timeout: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub description: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub reason: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvVoteStatus {
     pub yes: i32,
     pub no: i32,
@@ -655,26 +1046,34 @@ pub struct SvVoteStatus {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ClSay<'a> {
     pub team: bool,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub message: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClSetTeam {
     pub team: enums::Team,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClSetSpectatorMode {
     pub spectator_id: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ClStartInfo<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub clan: &'a [u8],
     pub country: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub skin: &'a [u8],
     pub use_custom_color: bool,
     pub color_body: i32,
@@ -682,10 +1081,14 @@ pub struct ClStartInfo<'a> {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ClChangeInfo<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub clan: &'a [u8],
     pub country: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub skin: &'a [u8],
     pub use_custom_color: bool,
     pub color_body: i32,
@@ -693,31 +1096,40 @@ pub struct ClChangeInfo<'a> {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClKill;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClEmoticon {
     pub emoticon: enums::Emoticon,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClVote {
     pub vote: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ClCallVote<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub type_: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub value: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub reason: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClIsDdnetLegacy {
     pub ddnet_version: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvDdraceTimeLegacy {
     pub time: i32,
     pub check: i32,
@@ -725,42 +1137,51 @@ pub struct SvDdraceTimeLegacy {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvRecordLegacy {
     pub server_time_best: i32,
     pub player_time_best: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Unused2;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvTeamsStateLegacy;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClShowOthersLegacy {
     pub show: bool,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvMyOwnMessage {
     pub test: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClShowDistance {
     pub x: i32,
     pub y: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClShowOthers {
     pub show: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvTeamsState;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvDdraceTime {
     pub time: i32,
     pub check: i32,
@@ -768,26 +1189,221 @@ pub struct SvDdraceTime {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvRecord {
     pub server_time_best: i32,
     pub player_time_best: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvKillMsgTeam {
     pub team: i32,
     pub first: i32,
 }
 
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvYourVote {
+    pub voted: i32,
+}
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvRaceFinish {
+    pub client_id: i32,
+    pub time: i32,
+    pub diff: i32,
+    pub record_personal: bool,
+    pub record_server: bool,
+}
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SvCommandInfo<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
+    pub name: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
+    pub args_format: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
+    pub help_text: &'a [u8],
+}
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SvCommandInfoRemove<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
+    pub name: &'a [u8],
+}
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvVoteOptionGroupStart;
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvVoteOptionGroupEnd;
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvCommandInfoGroupStart;
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvCommandInfoGroupEnd;
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvChangeInfoCooldown {
+    pub wait_until: ::snap_obj::Tick,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvMotdOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvBroadcastOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvChatOwned {
+    pub team: i32,
+    pub client_id: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvVoteOptionListAddOwned {
+    pub num_options: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes_array"))]
+    pub description: [Vec<u8>; 15],
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvVoteOptionAddOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub description: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvVoteOptionRemoveOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub description: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvVoteSetOwned {
+    pub timeout: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub description: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub reason: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClSayOwned {
+    pub team: bool,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClStartInfoOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub clan: Vec<u8>,
+    pub country: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub skin: Vec<u8>,
+    pub use_custom_color: bool,
+    pub color_body: i32,
+    pub color_feet: i32,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClChangeInfoOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub clan: Vec<u8>,
+    pub country: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub skin: Vec<u8>,
+    pub use_custom_color: bool,
+    pub color_body: i32,
+    pub color_feet: i32,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClCallVoteOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub type_: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub value: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub reason: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvCommandInfoOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub args_format: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub help_text: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvCommandInfoRemoveOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+}
+
 impl<'a> SvMotd<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvMotd<'a>, Error> {
-        let result = Ok(SvMotd {
+        SvMotd::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvMotd<'a>, Error> {
+        let result = SvMotd::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvMotd"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvMotd<'a>, Error> {
+        Ok(SvMotd {
             message: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(message: &'a [u8]) -> Result<SvMotd<'a>, Error> {
+        let result = SvMotd {
+            message,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.message)?;
         Ok(_p.written())
     }
@@ -802,13 +1418,28 @@ impl<'a> fmt::Debug for SvMotd<'a> {
 
 impl<'a> SvBroadcast<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvBroadcast<'a>, Error> {
-        let result = Ok(SvBroadcast {
+        SvBroadcast::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvBroadcast<'a>, Error> {
+        let result = SvBroadcast::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvBroadcast"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvBroadcast<'a>, Error> {
+        Ok(SvBroadcast {
             message: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(message: &'a [u8]) -> Result<SvBroadcast<'a>, Error> {
+        let result = SvBroadcast {
+            message,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.message)?;
         Ok(_p.written())
     }
@@ -823,18 +1454,38 @@ impl<'a> fmt::Debug for SvBroadcast<'a> {
 
 impl<'a> SvChat<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvChat<'a>, Error> {
-        let result = Ok(SvChat {
-            team: in_range(_p.read_int(warn)?, -2, 3)?,
-            client_id: in_range(_p.read_int(warn)?, -1, 63)?,
-            message: sanitize(warn, _p.read_string()?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(-2 <= self.team && self.team <= 3);
-        assert!(-1 <= self.client_id && self.client_id <= 63);
-        sanitize(&mut Panic, self.message).unwrap();
+        SvChat::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvChat<'a>, Error> {
+        let result = SvChat::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvChat"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvChat<'a>, Error> {
+        Ok(SvChat {
+            team: in_range(_p.read_int(&mut _p.locate(warn, "SvChat.team"))?, -2, 3)?,
+            client_id: in_range(_p.read_int(&mut _p.locate(warn, "SvChat.client_id"))?, -1, 63)?,
+            message: sanitize(&mut _p.locate(warn, "SvChat.message"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(team: i32, client_id: i32, message: &'a [u8]) -> Result<SvChat<'a>, Error> {
+        let result = SvChat {
+            team,
+            client_id,
+            message,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.team, -2, 3)?;
+        in_range(self.client_id, -1, 63)?;
+        sanitize(&mut Ignore, self.message)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.team, -2, 3)?;
+        in_range(self.client_id, -1, 63)?;
+        sanitize(&mut Ignore, self.message)?;
         _p.write_int(self.team)?;
         _p.write_int(self.client_id)?;
         _p.write_string(self.message)?;
@@ -853,19 +1504,40 @@ impl<'a> fmt::Debug for SvChat<'a> {
 
 impl SvKillMsg {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvKillMsg, Error> {
-        let result = Ok(SvKillMsg {
-            killer: in_range(_p.read_int(warn)?, 0, 63)?,
-            victim: in_range(_p.read_int(warn)?, 0, 63)?,
-            weapon: in_range(_p.read_int(warn)?, -3, 5)?,
-            mode_special: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.killer && self.killer <= 63);
-        assert!(0 <= self.victim && self.victim <= 63);
-        assert!(-3 <= self.weapon && self.weapon <= 5);
+        SvKillMsg::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvKillMsg, Error> {
+        let result = SvKillMsg::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvKillMsg"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvKillMsg, Error> {
+        Ok(SvKillMsg {
+            killer: in_range(_p.read_int(&mut _p.locate(warn, "SvKillMsg.killer"))?, 0, 63)?,
+            victim: in_range(_p.read_int(&mut _p.locate(warn, "SvKillMsg.victim"))?, 0, 63)?,
+            weapon: in_range(_p.read_int(&mut _p.locate(warn, "SvKillMsg.weapon"))?, -3, 5)?,
+            mode_special: _p.read_int(&mut _p.locate(warn, "SvKillMsg.mode_special"))?,
+        })
+    }
+    pub fn new(killer: i32, victim: i32, weapon: i32, mode_special: i32) -> Result<SvKillMsg, Error> {
+        let result = SvKillMsg {
+            killer,
+            victim,
+            weapon,
+            mode_special,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.killer, 0, 63)?;
+        in_range(self.victim, 0, 63)?;
+        in_range(self.weapon, -3, 5)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.killer, 0, 63)?;
+        in_range(self.victim, 0, 63)?;
+        in_range(self.weapon, -3, 5)?;
         _p.write_int(self.killer)?;
         _p.write_int(self.victim)?;
         _p.write_int(self.weapon)?;
@@ -886,13 +1558,29 @@ impl fmt::Debug for SvKillMsg {
 
 impl SvSoundGlobal {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvSoundGlobal, Error> {
-        let result = Ok(SvSoundGlobal {
-            sound_id: enums::Sound::from_i32(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
+        SvSoundGlobal::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvSoundGlobal, Error> {
+        let result = SvSoundGlobal::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvSoundGlobal"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvSoundGlobal, Error> {
+        Ok(SvSoundGlobal {
+            sound_id: enums::Sound::from_i32(_p.read_int(&mut _p.locate(warn, "SvSoundGlobal.sound_id"))?),
+        })
+    }
+    pub fn new(sound_id: enums::Sound) -> Result<SvSoundGlobal, Error> {
+        let result = SvSoundGlobal {
+            sound_id,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        self.sound_id.known()?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.sound_id.to_i32())?;
         Ok(_p.written())
     }
@@ -907,59 +1595,121 @@ impl fmt::Debug for SvSoundGlobal {
 
 impl SvTuneParams {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvTuneParams, Error> {
-        let result = Ok(SvTuneParams {
-            ground_control_speed: TuneParam(_p.read_int(warn)?),
-            ground_control_accel: TuneParam(_p.read_int(warn)?),
-            ground_friction: TuneParam(_p.read_int(warn)?),
-            ground_jump_impulse: TuneParam(_p.read_int(warn)?),
-            air_jump_impulse: TuneParam(_p.read_int(warn)?),
-            air_control_speed: TuneParam(_p.read_int(warn)?),
-            air_control_accel: TuneParam(_p.read_int(warn)?),
-            air_friction: TuneParam(_p.read_int(warn)?),
-            hook_length: TuneParam(_p.read_int(warn)?),
-            hook_fire_speed: TuneParam(_p.read_int(warn)?),
-            hook_drag_accel: TuneParam(_p.read_int(warn)?),
-            hook_drag_speed: TuneParam(_p.read_int(warn)?),
-            gravity: TuneParam(_p.read_int(warn)?),
-            velramp_start: TuneParam(_p.read_int(warn)?),
-            velramp_range: TuneParam(_p.read_int(warn)?),
-            velramp_curvature: TuneParam(_p.read_int(warn)?),
-            gun_curvature: TuneParam(_p.read_int(warn)?),
-            gun_speed: TuneParam(_p.read_int(warn)?),
-            gun_lifetime: TuneParam(_p.read_int(warn)?),
-            shotgun_curvature: TuneParam(_p.read_int(warn)?),
-            shotgun_speed: TuneParam(_p.read_int(warn)?),
-            shotgun_speeddiff: TuneParam(_p.read_int(warn)?),
-            shotgun_lifetime: TuneParam(_p.read_int(warn)?),
-            grenade_curvature: TuneParam(_p.read_int(warn)?),
-            grenade_speed: TuneParam(_p.read_int(warn)?),
-            grenade_lifetime: TuneParam(_p.read_int(warn)?),
-            laser_reach: TuneParam(_p.read_int(warn)?),
-            laser_bounce_delay: TuneParam(_p.read_int(warn)?),
-            laser_bounce_num: TuneParam(_p.read_int(warn)?),
-            laser_bounce_cost: TuneParam(_p.read_int(warn)?),
-            laser_damage: TuneParam(_p.read_int(warn)?),
-            player_collision: TuneParam(_p.read_int(warn)?),
-            player_hooking: TuneParam(_p.read_int(warn)?),
-            jetpack_strength: TuneParam(_p.read_int(warn)?),
-            shotgun_strength: TuneParam(_p.read_int(warn)?),
-            explosion_strength: TuneParam(_p.read_int(warn)?),
-            hammer_strength: TuneParam(_p.read_int(warn)?),
-            hook_duration: TuneParam(_p.read_int(warn)?),
-            hammer_fire_delay: TuneParam(_p.read_int(warn)?),
-            gun_fire_delay: TuneParam(_p.read_int(warn)?),
-            shotgun_fire_delay: TuneParam(_p.read_int(warn)?),
-            grenade_fire_delay: TuneParam(_p.read_int(warn)?),
-            laser_fire_delay: TuneParam(_p.read_int(warn)?),
-            ninja_fire_delay: TuneParam(_p.read_int(warn)?),
-            hammer_hit_fire_delay: TuneParam(_p.read_int(warn)?),
-            ground_elasticity_x: TuneParam(_p.read_int(warn)?),
-            ground_elasticity_y: TuneParam(_p.read_int(warn)?),
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        SvTuneParams::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvTuneParams, Error> {
+        let result = SvTuneParams::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvTuneParams"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvTuneParams, Error> {
+        Ok(SvTuneParams {
+            ground_control_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.ground_control_speed"))?),
+            ground_control_accel: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.ground_control_accel"))?),
+            ground_friction: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.ground_friction"))?),
+            ground_jump_impulse: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.ground_jump_impulse"))?),
+            air_jump_impulse: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.air_jump_impulse"))?),
+            air_control_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.air_control_speed"))?),
+            air_control_accel: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.air_control_accel"))?),
+            air_friction: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.air_friction"))?),
+            hook_length: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.hook_length"))?),
+            hook_fire_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.hook_fire_speed"))?),
+            hook_drag_accel: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.hook_drag_accel"))?),
+            hook_drag_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.hook_drag_speed"))?),
+            gravity: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.gravity"))?),
+            velramp_start: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.velramp_start"))?),
+            velramp_range: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.velramp_range"))?),
+            velramp_curvature: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.velramp_curvature"))?),
+            gun_curvature: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.gun_curvature"))?),
+            gun_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.gun_speed"))?),
+            gun_lifetime: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.gun_lifetime"))?),
+            shotgun_curvature: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.shotgun_curvature"))?),
+            shotgun_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.shotgun_speed"))?),
+            shotgun_speeddiff: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.shotgun_speeddiff"))?),
+            shotgun_lifetime: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.shotgun_lifetime"))?),
+            grenade_curvature: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.grenade_curvature"))?),
+            grenade_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.grenade_speed"))?),
+            grenade_lifetime: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.grenade_lifetime"))?),
+            laser_reach: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.laser_reach"))?),
+            laser_bounce_delay: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.laser_bounce_delay"))?),
+            laser_bounce_num: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.laser_bounce_num"))?),
+            laser_bounce_cost: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.laser_bounce_cost"))?),
+            laser_damage: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.laser_damage"))?),
+            player_collision: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.player_collision"))?),
+            player_hooking: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.player_hooking"))?),
+            jetpack_strength: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.jetpack_strength"))?),
+            shotgun_strength: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.shotgun_strength"))?),
+            explosion_strength: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.explosion_strength"))?),
+            hammer_strength: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.hammer_strength"))?),
+            hook_duration: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.hook_duration"))?),
+            hammer_fire_delay: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.hammer_fire_delay"))?),
+            gun_fire_delay: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.gun_fire_delay"))?),
+            shotgun_fire_delay: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.shotgun_fire_delay"))?),
+            grenade_fire_delay: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.grenade_fire_delay"))?),
+            laser_fire_delay: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.laser_fire_delay"))?),
+            ninja_fire_delay: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.ninja_fire_delay"))?),
+            hammer_hit_fire_delay: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.hammer_hit_fire_delay"))?),
+            ground_elasticity_x: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.ground_elasticity_x"))?),
+            ground_elasticity_y: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.ground_elasticity_y"))?),
+        })
+    }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(ground_control_speed: TuneParam, ground_control_accel: TuneParam, ground_friction: TuneParam, ground_jump_impulse: TuneParam, air_jump_impulse: TuneParam, air_control_speed: TuneParam, air_control_accel: TuneParam, air_friction: TuneParam, hook_length: TuneParam, hook_fire_speed: TuneParam, hook_drag_accel: TuneParam, hook_drag_speed: TuneParam, gravity: TuneParam, velramp_start: TuneParam, velramp_range: TuneParam, velramp_curvature: TuneParam, gun_curvature: TuneParam, gun_speed: TuneParam, gun_lifetime: TuneParam, shotgun_curvature: TuneParam, shotgun_speed: TuneParam, shotgun_speeddiff: TuneParam, shotgun_lifetime: TuneParam, grenade_curvature: TuneParam, grenade_speed: TuneParam, grenade_lifetime: TuneParam, laser_reach: TuneParam, laser_bounce_delay: TuneParam, laser_bounce_num: TuneParam, laser_bounce_cost: TuneParam, laser_damage: TuneParam, player_collision: TuneParam, player_hooking: TuneParam, jetpack_strength: TuneParam, shotgun_strength: TuneParam, explosion_strength: TuneParam, hammer_strength: TuneParam, hook_duration: TuneParam, hammer_fire_delay: TuneParam, gun_fire_delay: TuneParam, shotgun_fire_delay: TuneParam, grenade_fire_delay: TuneParam, laser_fire_delay: TuneParam, ninja_fire_delay: TuneParam, hammer_hit_fire_delay: TuneParam, ground_elasticity_x: TuneParam, ground_elasticity_y: TuneParam) -> Result<SvTuneParams, Error> {
+        let result = SvTuneParams {
+            ground_control_speed,
+            ground_control_accel,
+            ground_friction,
+            ground_jump_impulse,
+            air_jump_impulse,
+            air_control_speed,
+            air_control_accel,
+            air_friction,
+            hook_length,
+            hook_fire_speed,
+            hook_drag_accel,
+            hook_drag_speed,
+            gravity,
+            velramp_start,
+            velramp_range,
+            velramp_curvature,
+            gun_curvature,
+            gun_speed,
+            gun_lifetime,
+            shotgun_curvature,
+            shotgun_speed,
+            shotgun_speeddiff,
+            shotgun_lifetime,
+            grenade_curvature,
+            grenade_speed,
+            grenade_lifetime,
+            laser_reach,
+            laser_bounce_delay,
+            laser_bounce_num,
+            laser_bounce_cost,
+            laser_damage,
+            player_collision,
+            player_hooking,
+            jetpack_strength,
+            shotgun_strength,
+            explosion_strength,
+            hammer_strength,
+            hook_duration,
+            hammer_fire_delay,
+            gun_fire_delay,
+            shotgun_fire_delay,
+            grenade_fire_delay,
+            laser_fire_delay,
+            ninja_fire_delay,
+            hammer_hit_fire_delay,
+            ground_elasticity_x,
+            ground_elasticity_y,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.ground_control_speed.0)?;
         _p.write_int(self.ground_control_accel.0)?;
         _p.write_int(self.ground_friction.0)?;
@@ -1066,11 +1816,15 @@ impl fmt::Debug for SvTuneParams {
 
 impl Unused {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Unused, Error> {
-        let result = Ok(Unused);
-        _p.finish(warn);
-        result
+        Unused::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Unused, Error> {
+        error::finish(&mut _p.locate(warn, "Unused"), _p, Ok(Unused))
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -1083,11 +1837,15 @@ impl fmt::Debug for Unused {
 
 impl SvReadyToEnter {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvReadyToEnter, Error> {
-        let result = Ok(SvReadyToEnter);
-        _p.finish(warn);
-        result
+        SvReadyToEnter::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvReadyToEnter, Error> {
+        error::finish(&mut _p.locate(warn, "SvReadyToEnter"), _p, Ok(SvReadyToEnter))
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -1100,13 +1858,29 @@ impl fmt::Debug for SvReadyToEnter {
 
 impl SvWeaponPickup {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvWeaponPickup, Error> {
-        let result = Ok(SvWeaponPickup {
-            weapon: enums::Weapon::from_i32(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
+        SvWeaponPickup::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvWeaponPickup, Error> {
+        let result = SvWeaponPickup::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvWeaponPickup"), _p, result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvWeaponPickup, Error> {
+        Ok(SvWeaponPickup {
+            weapon: enums::Weapon::from_i32(_p.read_int(&mut _p.locate(warn, "SvWeaponPickup.weapon"))?),
+        })
+    }
+    pub fn new(weapon: enums::Weapon) -> Result<SvWeaponPickup, Error> {
+        let result = SvWeaponPickup {
+            weapon,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.weapon.known()?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.weapon.to_i32())?;
         Ok(_p.written())
     }
@@ -1121,15 +1895,33 @@ impl fmt::Debug for SvWeaponPickup {
 
 impl SvEmoticon {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvEmoticon, Error> {
-        let result = Ok(SvEmoticon {
-            client_id: in_range(_p.read_int(warn)?, 0, 63)?,
-            emoticon: enums::Emoticon::from_i32(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.client_id && self.client_id <= 63);
+        SvEmoticon::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvEmoticon, Error> {
+        let result = SvEmoticon::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvEmoticon"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvEmoticon, Error> {
+        Ok(SvEmoticon {
+            client_id: in_range(_p.read_int(&mut _p.locate(warn, "SvEmoticon.client_id"))?, 0, 63)?,
+            emoticon: enums::Emoticon::from_i32(_p.read_int(&mut _p.locate(warn, "SvEmoticon.emoticon"))?),
+        })
+    }
+    pub fn new(client_id: i32, emoticon: enums::Emoticon) -> Result<SvEmoticon, Error> {
+        let result = SvEmoticon {
+            client_id,
+            emoticon,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.client_id, 0, 63)?;
+        self.emoticon.known()?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.client_id, 0, 63)?;
         _p.write_int(self.client_id)?;
         _p.write_int(self.emoticon.to_i32())?;
         Ok(_p.written())
@@ -1146,11 +1938,15 @@ impl fmt::Debug for SvEmoticon {
 
 impl SvVoteClearOptions {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteClearOptions, Error> {
-        let result = Ok(SvVoteClearOptions);
-        _p.finish(warn);
-        result
+        SvVoteClearOptions::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteClearOptions, Error> {
+        error::finish(&mut _p.locate(warn, "SvVoteClearOptions"), _p, Ok(SvVoteClearOptions))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -1163,33 +1959,53 @@ impl fmt::Debug for SvVoteClearOptions {
 
 impl<'a> SvVoteOptionListAdd<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteOptionListAdd<'a>, Error> {
-        let result = Ok(SvVoteOptionListAdd {
-            num_options: in_range(_p.read_int(warn)?, 1, 15)?,
+        SvVoteOptionListAdd::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteOptionListAdd<'a>, Error> {
+        let result = SvVoteOptionListAdd::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvVoteOptionListAdd"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteOptionListAdd<'a>, Error> {
+        Ok(SvVoteOptionListAdd {
+            num_options: in_range(_p.read_int(&mut _p.locate(warn, "SvVoteOptionListAdd.num_options"))?, 1, 15)?,
             description: [
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvVoteOptionListAdd.description"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvVoteOptionListAdd.description"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvVoteOptionListAdd.description"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvVoteOptionListAdd.description"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvVoteOptionListAdd.description"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvVoteOptionListAdd.description"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvVoteOptionListAdd.description"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvVoteOptionListAdd.description"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvVoteOptionListAdd.description"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvVoteOptionListAdd.description"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvVoteOptionListAdd.description"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvVoteOptionListAdd.description"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvVoteOptionListAdd.description"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvVoteOptionListAdd.description"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvVoteOptionListAdd.description"), _p.read_string()?)?,
             ],
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(num_options: i32, description: [&'a [u8]; 15]) -> Result<SvVoteOptionListAdd<'a>, Error> {
+        let result = SvVoteOptionListAdd {
+            num_options,
+            description,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.num_options, 1, 15)?;
+        for &e in &self.description {
+            sanitize(&mut Ignore, e)?;
+        }
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(1 <= self.num_options && self.num_options <= 15);
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.num_options, 1, 15)?;
         for &e in &self.description {
-            sanitize(&mut Panic, e).unwrap();
+            sanitize(&mut Ignore, e)?;
         }
         _p.write_int(self.num_options)?;
         for &e in &self.description {
@@ -1209,14 +2025,30 @@ impl<'a> fmt::Debug for SvVoteOptionListAdd<'a> {
 
 impl<'a> SvVoteOptionAdd<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteOptionAdd<'a>, Error> {
-        let result = Ok(SvVoteOptionAdd {
-            description: sanitize(warn, _p.read_string()?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.description).unwrap();
+        SvVoteOptionAdd::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteOptionAdd<'a>, Error> {
+        let result = SvVoteOptionAdd::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvVoteOptionAdd"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteOptionAdd<'a>, Error> {
+        Ok(SvVoteOptionAdd {
+            description: sanitize(&mut _p.locate(warn, "SvVoteOptionAdd.description"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(description: &'a [u8]) -> Result<SvVoteOptionAdd<'a>, Error> {
+        let result = SvVoteOptionAdd {
+            description,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.description)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.description)?;
         _p.write_string(self.description)?;
         Ok(_p.written())
     }
@@ -1231,14 +2063,30 @@ impl<'a> fmt::Debug for SvVoteOptionAdd<'a> {
 
 impl<'a> SvVoteOptionRemove<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteOptionRemove<'a>, Error> {
-        let result = Ok(SvVoteOptionRemove {
-            description: sanitize(warn, _p.read_string()?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.description).unwrap();
+        SvVoteOptionRemove::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteOptionRemove<'a>, Error> {
+        let result = SvVoteOptionRemove::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvVoteOptionRemove"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteOptionRemove<'a>, Error> {
+        Ok(SvVoteOptionRemove {
+            description: sanitize(&mut _p.locate(warn, "SvVoteOptionRemove.description"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(description: &'a [u8]) -> Result<SvVoteOptionRemove<'a>, Error> {
+        let result = SvVoteOptionRemove {
+            description,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.description)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.description)?;
         _p.write_string(self.description)?;
         Ok(_p.written())
     }
@@ -1253,18 +2101,38 @@ impl<'a> fmt::Debug for SvVoteOptionRemove<'a> {
 
 impl<'a> SvVoteSet<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteSet<'a>, Error> {
-        let result = Ok(SvVoteSet {
-            timeout: in_range(_p.read_int(warn)?, 0, 60)?,
-            description: sanitize(warn, _p.read_string()?)?,
-            reason: sanitize(warn, _p.read_string()?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.timeout && self.timeout <= 60);
-        sanitize(&mut Panic, self.description).unwrap();
-        sanitize(&mut Panic, self.reason).unwrap();
+        SvVoteSet::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteSet<'a>, Error> {
+        let result = SvVoteSet::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvVoteSet"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteSet<'a>, Error> {
+        Ok(SvVoteSet {
+            timeout: in_range(_p.read_int(&mut _p.locate(warn, "SvVoteSet.timeout"))?, 0, 60)?,
+            description: sanitize(&mut _p.locate(warn, "SvVoteSet.description"), _p.read_string()?)?,
+            reason: sanitize(&mut _p.locate(warn, "SvVoteSet.reason"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(timeout: i32, description: &'a [u8], reason: &'a [u8]) -> Result<SvVoteSet<'a>, Error> {
+        let result = SvVoteSet {
+            timeout,
+            description,
+            reason,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.timeout, 0, 60)?;
+        sanitize(&mut Ignore, self.description)?;
+        sanitize(&mut Ignore, self.reason)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.timeout, 0, 60)?;
+        sanitize(&mut Ignore, self.description)?;
+        sanitize(&mut Ignore, self.reason)?;
         _p.write_int(self.timeout)?;
         _p.write_string(self.description)?;
         _p.write_string(self.reason)?;
@@ -1283,20 +2151,42 @@ impl<'a> fmt::Debug for SvVoteSet<'a> {
 
 impl SvVoteStatus {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteStatus, Error> {
-        let result = Ok(SvVoteStatus {
-            yes: in_range(_p.read_int(warn)?, 0, 64)?,
-            no: in_range(_p.read_int(warn)?, 0, 64)?,
-            pass: in_range(_p.read_int(warn)?, 0, 64)?,
-            total: in_range(_p.read_int(warn)?, 0, 64)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.yes && self.yes <= 64);
-        assert!(0 <= self.no && self.no <= 64);
-        assert!(0 <= self.pass && self.pass <= 64);
-        assert!(0 <= self.total && self.total <= 64);
+        SvVoteStatus::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteStatus, Error> {
+        let result = SvVoteStatus::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvVoteStatus"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteStatus, Error> {
+        Ok(SvVoteStatus {
+            yes: in_range(_p.read_int(&mut _p.locate(warn, "SvVoteStatus.yes"))?, 0, 64)?,
+            no: in_range(_p.read_int(&mut _p.locate(warn, "SvVoteStatus.no"))?, 0, 64)?,
+            pass: in_range(_p.read_int(&mut _p.locate(warn, "SvVoteStatus.pass"))?, 0, 64)?,
+            total: in_range(_p.read_int(&mut _p.locate(warn, "SvVoteStatus.total"))?, 0, 64)?,
+        })
+    }
+    pub fn new(yes: i32, no: i32, pass: i32, total: i32) -> Result<SvVoteStatus, Error> {
+        let result = SvVoteStatus {
+            yes,
+            no,
+            pass,
+            total,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.yes, 0, 64)?;
+        in_range(self.no, 0, 64)?;
+        in_range(self.pass, 0, 64)?;
+        in_range(self.total, 0, 64)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.yes, 0, 64)?;
+        in_range(self.no, 0, 64)?;
+        in_range(self.pass, 0, 64)?;
+        in_range(self.total, 0, 64)?;
         _p.write_int(self.yes)?;
         _p.write_int(self.no)?;
         _p.write_int(self.pass)?;
@@ -1317,15 +2207,32 @@ impl fmt::Debug for SvVoteStatus {
 
 impl<'a> ClSay<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClSay<'a>, Error> {
-        let result = Ok(ClSay {
-            team: to_bool(_p.read_int(warn)?)?,
-            message: sanitize(warn, _p.read_string()?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.message).unwrap();
+        ClSay::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClSay<'a>, Error> {
+        let result = ClSay::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClSay"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClSay<'a>, Error> {
+        Ok(ClSay {
+            team: to_bool(_p.read_int(&mut _p.locate(warn, "ClSay.team"))?)?,
+            message: sanitize(&mut _p.locate(warn, "ClSay.message"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(team: bool, message: &'a [u8]) -> Result<ClSay<'a>, Error> {
+        let result = ClSay {
+            team,
+            message,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.message)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.message)?;
         _p.write_int(self.team as i32)?;
         _p.write_string(self.message)?;
         Ok(_p.written())
@@ -1342,13 +2249,29 @@ impl<'a> fmt::Debug for ClSay<'a> {
 
 impl ClSetTeam {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClSetTeam, Error> {
-        let result = Ok(ClSetTeam {
-            team: enums::Team::from_i32(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
+        ClSetTeam::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClSetTeam, Error> {
+        let result = ClSetTeam::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClSetTeam"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClSetTeam, Error> {
+        Ok(ClSetTeam {
+            team: enums::Team::from_i32(_p.read_int(&mut _p.locate(warn, "ClSetTeam.team"))?),
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(team: enums::Team) -> Result<ClSetTeam, Error> {
+        let result = ClSetTeam {
+            team,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.team.known()?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.team.to_i32())?;
         Ok(_p.written())
     }
@@ -1363,14 +2286,30 @@ impl fmt::Debug for ClSetTeam {
 
 impl ClSetSpectatorMode {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClSetSpectatorMode, Error> {
-        let result = Ok(ClSetSpectatorMode {
-            spectator_id: in_range(_p.read_int(warn)?, -1, 63)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(-1 <= self.spectator_id && self.spectator_id <= 63);
+        ClSetSpectatorMode::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClSetSpectatorMode, Error> {
+        let result = ClSetSpectatorMode::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClSetSpectatorMode"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClSetSpectatorMode, Error> {
+        Ok(ClSetSpectatorMode {
+            spectator_id: in_range(_p.read_int(&mut _p.locate(warn, "ClSetSpectatorMode.spectator_id"))?, -1, 63)?,
+        })
+    }
+    pub fn new(spectator_id: i32) -> Result<ClSetSpectatorMode, Error> {
+        let result = ClSetSpectatorMode {
+            spectator_id,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.spectator_id, -1, 63)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.spectator_id, -1, 63)?;
         _p.write_int(self.spectator_id)?;
         Ok(_p.written())
     }
@@ -1385,22 +2324,46 @@ impl fmt::Debug for ClSetSpectatorMode {
 
 impl<'a> ClStartInfo<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClStartInfo<'a>, Error> {
-        let result = Ok(ClStartInfo {
-            name: sanitize(warn, _p.read_string()?)?,
-            clan: sanitize(warn, _p.read_string()?)?,
-            country: _p.read_int(warn)?,
-            skin: sanitize(warn, _p.read_string()?)?,
-            use_custom_color: to_bool(_p.read_int(warn)?)?,
-            color_body: _p.read_int(warn)?,
-            color_feet: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.name).unwrap();
-        sanitize(&mut Panic, self.clan).unwrap();
-        sanitize(&mut Panic, self.skin).unwrap();
+        ClStartInfo::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClStartInfo<'a>, Error> {
+        let result = ClStartInfo::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClStartInfo"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClStartInfo<'a>, Error> {
+        Ok(ClStartInfo {
+            name: sanitize(&mut _p.locate(warn, "ClStartInfo.name"), _p.read_string()?)?,
+            clan: sanitize(&mut _p.locate(warn, "ClStartInfo.clan"), _p.read_string()?)?,
+            country: _p.read_int(&mut _p.locate(warn, "ClStartInfo.country"))?,
+            skin: sanitize(&mut _p.locate(warn, "ClStartInfo.skin"), _p.read_string()?)?,
+            use_custom_color: to_bool(_p.read_int(&mut _p.locate(warn, "ClStartInfo.use_custom_color"))?)?,
+            color_body: _p.read_int(&mut _p.locate(warn, "ClStartInfo.color_body"))?,
+            color_feet: _p.read_int(&mut _p.locate(warn, "ClStartInfo.color_feet"))?,
+        })
+    }
+    pub fn new(name: &'a [u8], clan: &'a [u8], country: i32, skin: &'a [u8], use_custom_color: bool, color_body: i32, color_feet: i32) -> Result<ClStartInfo<'a>, Error> {
+        let result = ClStartInfo {
+            name,
+            clan,
+            country,
+            skin,
+            use_custom_color,
+            color_body,
+            color_feet,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, max_length(self.name, 15)?)?;
+        sanitize(&mut Ignore, max_length(self.clan, 11)?)?;
+        sanitize(&mut Ignore, max_length(self.skin, 23)?)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.clan)?;
+        sanitize(&mut Ignore, self.skin)?;
         _p.write_string(self.name)?;
         _p.write_string(self.clan)?;
         _p.write_int(self.country)?;
@@ -1427,22 +2390,46 @@ impl<'a> fmt::Debug for ClStartInfo<'a> {
 
 impl<'a> ClChangeInfo<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClChangeInfo<'a>, Error> {
-        let result = Ok(ClChangeInfo {
-            name: sanitize(warn, _p.read_string()?)?,
-            clan: sanitize(warn, _p.read_string()?)?,
-            country: _p.read_int(warn)?,
-            skin: sanitize(warn, _p.read_string()?)?,
-            use_custom_color: to_bool(_p.read_int(warn)?)?,
-            color_body: _p.read_int(warn)?,
-            color_feet: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.name).unwrap();
-        sanitize(&mut Panic, self.clan).unwrap();
-        sanitize(&mut Panic, self.skin).unwrap();
+        ClChangeInfo::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClChangeInfo<'a>, Error> {
+        let result = ClChangeInfo::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClChangeInfo"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClChangeInfo<'a>, Error> {
+        Ok(ClChangeInfo {
+            name: sanitize(&mut _p.locate(warn, "ClChangeInfo.name"), _p.read_string()?)?,
+            clan: sanitize(&mut _p.locate(warn, "ClChangeInfo.clan"), _p.read_string()?)?,
+            country: _p.read_int(&mut _p.locate(warn, "ClChangeInfo.country"))?,
+            skin: sanitize(&mut _p.locate(warn, "ClChangeInfo.skin"), _p.read_string()?)?,
+            use_custom_color: to_bool(_p.read_int(&mut _p.locate(warn, "ClChangeInfo.use_custom_color"))?)?,
+            color_body: _p.read_int(&mut _p.locate(warn, "ClChangeInfo.color_body"))?,
+            color_feet: _p.read_int(&mut _p.locate(warn, "ClChangeInfo.color_feet"))?,
+        })
+    }
+    pub fn new(name: &'a [u8], clan: &'a [u8], country: i32, skin: &'a [u8], use_custom_color: bool, color_body: i32, color_feet: i32) -> Result<ClChangeInfo<'a>, Error> {
+        let result = ClChangeInfo {
+            name,
+            clan,
+            country,
+            skin,
+            use_custom_color,
+            color_body,
+            color_feet,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, max_length(self.name, 15)?)?;
+        sanitize(&mut Ignore, max_length(self.clan, 11)?)?;
+        sanitize(&mut Ignore, max_length(self.skin, 23)?)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.clan)?;
+        sanitize(&mut Ignore, self.skin)?;
         _p.write_string(self.name)?;
         _p.write_string(self.clan)?;
         _p.write_int(self.country)?;
@@ -1469,11 +2456,15 @@ impl<'a> fmt::Debug for ClChangeInfo<'a> {
 
 impl ClKill {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClKill, Error> {
-        let result = Ok(ClKill);
-        _p.finish(warn);
-        result
+        ClKill::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClKill, Error> {
+        error::finish(&mut _p.locate(warn, "ClKill"), _p, Ok(ClKill))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -1486,13 +2477,29 @@ impl fmt::Debug for ClKill {
 
 impl ClEmoticon {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClEmoticon, Error> {
-        let result = Ok(ClEmoticon {
-            emoticon: enums::Emoticon::from_i32(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
+        ClEmoticon::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClEmoticon, Error> {
+        let result = ClEmoticon::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClEmoticon"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClEmoticon, Error> {
+        Ok(ClEmoticon {
+            emoticon: enums::Emoticon::from_i32(_p.read_int(&mut _p.locate(warn, "ClEmoticon.emoticon"))?),
+        })
+    }
+    pub fn new(emoticon: enums::Emoticon) -> Result<ClEmoticon, Error> {
+        let result = ClEmoticon {
+            emoticon,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        self.emoticon.known()?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.emoticon.to_i32())?;
         Ok(_p.written())
     }
@@ -1507,14 +2514,30 @@ impl fmt::Debug for ClEmoticon {
 
 impl ClVote {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClVote, Error> {
-        let result = Ok(ClVote {
-            vote: in_range(_p.read_int(warn)?, -1, 1)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(-1 <= self.vote && self.vote <= 1);
+        ClVote::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClVote, Error> {
+        let result = ClVote::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClVote"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClVote, Error> {
+        Ok(ClVote {
+            vote: in_range(_p.read_int(&mut _p.locate(warn, "ClVote.vote"))?, -1, 1)?,
+        })
+    }
+    pub fn new(vote: i32) -> Result<ClVote, Error> {
+        let result = ClVote {
+            vote,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.vote, -1, 1)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.vote, -1, 1)?;
         _p.write_int(self.vote)?;
         Ok(_p.written())
     }
@@ -1529,18 +2552,38 @@ impl fmt::Debug for ClVote {
 
 impl<'a> ClCallVote<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClCallVote<'a>, Error> {
-        let result = Ok(ClCallVote {
-            type_: sanitize(warn, _p.read_string()?)?,
-            value: sanitize(warn, _p.read_string()?)?,
-            reason: sanitize(warn, _p.read_string()?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.type_).unwrap();
-        sanitize(&mut Panic, self.value).unwrap();
-        sanitize(&mut Panic, self.reason).unwrap();
+        ClCallVote::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClCallVote<'a>, Error> {
+        let result = ClCallVote::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClCallVote"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClCallVote<'a>, Error> {
+        Ok(ClCallVote {
+            type_: sanitize(&mut _p.locate(warn, "ClCallVote.type_"), _p.read_string()?)?,
+            value: sanitize(&mut _p.locate(warn, "ClCallVote.value"), _p.read_string()?)?,
+            reason: sanitize(&mut _p.locate(warn, "ClCallVote.reason"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(type_: &'a [u8], value: &'a [u8], reason: &'a [u8]) -> Result<ClCallVote<'a>, Error> {
+        let result = ClCallVote {
+            type_,
+            value,
+            reason,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.type_)?;
+        sanitize(&mut Ignore, self.value)?;
+        sanitize(&mut Ignore, self.reason)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.type_)?;
+        sanitize(&mut Ignore, self.value)?;
+        sanitize(&mut Ignore, self.reason)?;
         _p.write_string(self.type_)?;
         _p.write_string(self.value)?;
         _p.write_string(self.reason)?;
@@ -1559,13 +2602,28 @@ impl<'a> fmt::Debug for ClCallVote<'a> {
 
 impl ClIsDdnetLegacy {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClIsDdnetLegacy, Error> {
-        let result = Ok(ClIsDdnetLegacy {
-            ddnet_version: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
+        ClIsDdnetLegacy::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClIsDdnetLegacy, Error> {
+        let result = ClIsDdnetLegacy::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClIsDdnetLegacy"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClIsDdnetLegacy, Error> {
+        Ok(ClIsDdnetLegacy {
+            ddnet_version: _p.read_int(&mut _p.locate(warn, "ClIsDdnetLegacy.ddnet_version"))?,
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(ddnet_version: i32) -> Result<ClIsDdnetLegacy, Error> {
+        let result = ClIsDdnetLegacy {
+            ddnet_version,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.ddnet_version)?;
         Ok(_p.written())
     }
@@ -1580,18 +2638,36 @@ impl fmt::Debug for ClIsDdnetLegacy {
 
 impl SvDdraceTimeLegacy {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvDdraceTimeLegacy, Error> {
-        let result = Ok(SvDdraceTimeLegacy {
-            time: _p.read_int(warn)?,
-            check: _p.read_int(warn)?,
-            finish: in_range(_p.read_int(warn)?, 0, 1)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.finish && self.finish <= 1);
-        _p.write_int(self.time)?;
-        _p.write_int(self.check)?;
+        SvDdraceTimeLegacy::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvDdraceTimeLegacy, Error> {
+        let result = SvDdraceTimeLegacy::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvDdraceTimeLegacy"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvDdraceTimeLegacy, Error> {
+        Ok(SvDdraceTimeLegacy {
+            time: _p.read_int(&mut _p.locate(warn, "SvDdraceTimeLegacy.time"))?,
+            check: _p.read_int(&mut _p.locate(warn, "SvDdraceTimeLegacy.check"))?,
+            finish: in_range(_p.read_int(&mut _p.locate(warn, "SvDdraceTimeLegacy.finish"))?, 0, 1)?,
+        })
+    }
+    pub fn new(time: i32, check: i32, finish: i32) -> Result<SvDdraceTimeLegacy, Error> {
+        let result = SvDdraceTimeLegacy {
+            time,
+            check,
+            finish,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.finish, 0, 1)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.finish, 0, 1)?;
+        _p.write_int(self.time)?;
+        _p.write_int(self.check)?;
         _p.write_int(self.finish)?;
         Ok(_p.written())
     }
@@ -1608,14 +2684,30 @@ impl fmt::Debug for SvDdraceTimeLegacy {
 
 impl SvRecordLegacy {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvRecordLegacy, Error> {
-        let result = Ok(SvRecordLegacy {
-            server_time_best: _p.read_int(warn)?,
-            player_time_best: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        SvRecordLegacy::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvRecordLegacy, Error> {
+        let result = SvRecordLegacy::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvRecordLegacy"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvRecordLegacy, Error> {
+        Ok(SvRecordLegacy {
+            server_time_best: _p.read_int(&mut _p.locate(warn, "SvRecordLegacy.server_time_best"))?,
+            player_time_best: _p.read_int(&mut _p.locate(warn, "SvRecordLegacy.player_time_best"))?,
+        })
+    }
+    pub fn new(server_time_best: i32, player_time_best: i32) -> Result<SvRecordLegacy, Error> {
+        let result = SvRecordLegacy {
+            server_time_best,
+            player_time_best,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.server_time_best)?;
         _p.write_int(self.player_time_best)?;
         Ok(_p.written())
@@ -1632,11 +2724,15 @@ impl fmt::Debug for SvRecordLegacy {
 
 impl Unused2 {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Unused2, Error> {
-        let result = Ok(Unused2);
-        _p.finish(warn);
-        result
+        Unused2::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Unused2, Error> {
+        error::finish(&mut _p.locate(warn, "Unused2"), _p, Ok(Unused2))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -1649,11 +2745,15 @@ impl fmt::Debug for Unused2 {
 
 impl SvTeamsStateLegacy {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvTeamsStateLegacy, Error> {
-        let result = Ok(SvTeamsStateLegacy);
-        _p.finish(warn);
-        result
+        SvTeamsStateLegacy::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvTeamsStateLegacy, Error> {
+        error::finish(&mut _p.locate(warn, "SvTeamsStateLegacy"), _p, Ok(SvTeamsStateLegacy))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -1666,13 +2766,28 @@ impl fmt::Debug for SvTeamsStateLegacy {
 
 impl ClShowOthersLegacy {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClShowOthersLegacy, Error> {
-        let result = Ok(ClShowOthersLegacy {
-            show: to_bool(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
+        ClShowOthersLegacy::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClShowOthersLegacy, Error> {
+        let result = ClShowOthersLegacy::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClShowOthersLegacy"), _p, result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClShowOthersLegacy, Error> {
+        Ok(ClShowOthersLegacy {
+            show: to_bool(_p.read_int(&mut _p.locate(warn, "ClShowOthersLegacy.show"))?)?,
+        })
+    }
+    pub fn new(show: bool) -> Result<ClShowOthersLegacy, Error> {
+        let result = ClShowOthersLegacy {
+            show,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.show as i32)?;
         Ok(_p.written())
     }
@@ -1687,13 +2802,28 @@ impl fmt::Debug for ClShowOthersLegacy {
 
 impl SvMyOwnMessage {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvMyOwnMessage, Error> {
-        let result = Ok(SvMyOwnMessage {
-            test: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
+        SvMyOwnMessage::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvMyOwnMessage, Error> {
+        let result = SvMyOwnMessage::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvMyOwnMessage"), _p, result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvMyOwnMessage, Error> {
+        Ok(SvMyOwnMessage {
+            test: _p.read_int(&mut _p.locate(warn, "SvMyOwnMessage.test"))?,
+        })
+    }
+    pub fn new(test: i32) -> Result<SvMyOwnMessage, Error> {
+        let result = SvMyOwnMessage {
+            test,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.test)?;
         Ok(_p.written())
     }
@@ -1708,14 +2838,30 @@ impl fmt::Debug for SvMyOwnMessage {
 
 impl ClShowDistance {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClShowDistance, Error> {
-        let result = Ok(ClShowDistance {
-            x: _p.read_int(warn)?,
-            y: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        ClShowDistance::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClShowDistance, Error> {
+        let result = ClShowDistance::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClShowDistance"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClShowDistance, Error> {
+        Ok(ClShowDistance {
+            x: _p.read_int(&mut _p.locate(warn, "ClShowDistance.x"))?,
+            y: _p.read_int(&mut _p.locate(warn, "ClShowDistance.y"))?,
+        })
+    }
+    pub fn new(x: i32, y: i32) -> Result<ClShowDistance, Error> {
+        let result = ClShowDistance {
+            x,
+            y,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.x)?;
         _p.write_int(self.y)?;
         Ok(_p.written())
@@ -1732,14 +2878,30 @@ impl fmt::Debug for ClShowDistance {
 
 impl ClShowOthers {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClShowOthers, Error> {
-        let result = Ok(ClShowOthers {
-            show: in_range(_p.read_int(warn)?, 0, 2)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.show && self.show <= 2);
+        ClShowOthers::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClShowOthers, Error> {
+        let result = ClShowOthers::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClShowOthers"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClShowOthers, Error> {
+        Ok(ClShowOthers {
+            show: in_range(_p.read_int(&mut _p.locate(warn, "ClShowOthers.show"))?, 0, 2)?,
+        })
+    }
+    pub fn new(show: i32) -> Result<ClShowOthers, Error> {
+        let result = ClShowOthers {
+            show,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.show, 0, 2)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.show, 0, 2)?;
         _p.write_int(self.show)?;
         Ok(_p.written())
     }
@@ -1754,11 +2916,15 @@ impl fmt::Debug for ClShowOthers {
 
 impl SvTeamsState {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvTeamsState, Error> {
-        let result = Ok(SvTeamsState);
-        _p.finish(warn);
-        result
+        SvTeamsState::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvTeamsState, Error> {
+        error::finish(&mut _p.locate(warn, "SvTeamsState"), _p, Ok(SvTeamsState))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -1771,16 +2937,34 @@ impl fmt::Debug for SvTeamsState {
 
 impl SvDdraceTime {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvDdraceTime, Error> {
-        let result = Ok(SvDdraceTime {
-            time: _p.read_int(warn)?,
-            check: _p.read_int(warn)?,
-            finish: in_range(_p.read_int(warn)?, 0, 1)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.finish && self.finish <= 1);
+        SvDdraceTime::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvDdraceTime, Error> {
+        let result = SvDdraceTime::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvDdraceTime"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvDdraceTime, Error> {
+        Ok(SvDdraceTime {
+            time: _p.read_int(&mut _p.locate(warn, "SvDdraceTime.time"))?,
+            check: _p.read_int(&mut _p.locate(warn, "SvDdraceTime.check"))?,
+            finish: in_range(_p.read_int(&mut _p.locate(warn, "SvDdraceTime.finish"))?, 0, 1)?,
+        })
+    }
+    pub fn new(time: i32, check: i32, finish: i32) -> Result<SvDdraceTime, Error> {
+        let result = SvDdraceTime {
+            time,
+            check,
+            finish,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.finish, 0, 1)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.finish, 0, 1)?;
         _p.write_int(self.time)?;
         _p.write_int(self.check)?;
         _p.write_int(self.finish)?;
@@ -1799,14 +2983,30 @@ impl fmt::Debug for SvDdraceTime {
 
 impl SvRecord {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvRecord, Error> {
-        let result = Ok(SvRecord {
-            server_time_best: _p.read_int(warn)?,
-            player_time_best: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        SvRecord::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvRecord, Error> {
+        let result = SvRecord::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvRecord"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvRecord, Error> {
+        Ok(SvRecord {
+            server_time_best: _p.read_int(&mut _p.locate(warn, "SvRecord.server_time_best"))?,
+            player_time_best: _p.read_int(&mut _p.locate(warn, "SvRecord.player_time_best"))?,
+        })
+    }
+    pub fn new(server_time_best: i32, player_time_best: i32) -> Result<SvRecord, Error> {
+        let result = SvRecord {
+            server_time_best,
+            player_time_best,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.server_time_best)?;
         _p.write_int(self.player_time_best)?;
         Ok(_p.written())
@@ -1823,16 +3023,34 @@ impl fmt::Debug for SvRecord {
 
 impl SvKillMsgTeam {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvKillMsgTeam, Error> {
-        let result = Ok(SvKillMsgTeam {
-            team: in_range(_p.read_int(warn)?, 0, 63)?,
-            first: in_range(_p.read_int(warn)?, -1, 63)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.team && self.team <= 63);
-        assert!(-1 <= self.first && self.first <= 63);
+        SvKillMsgTeam::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvKillMsgTeam, Error> {
+        let result = SvKillMsgTeam::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvKillMsgTeam"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvKillMsgTeam, Error> {
+        Ok(SvKillMsgTeam {
+            team: in_range(_p.read_int(&mut _p.locate(warn, "SvKillMsgTeam.team"))?, 0, 63)?,
+            first: in_range(_p.read_int(&mut _p.locate(warn, "SvKillMsgTeam.first"))?, -1, 63)?,
+        })
+    }
+    pub fn new(team: i32, first: i32) -> Result<SvKillMsgTeam, Error> {
+        let result = SvKillMsgTeam {
+            team,
+            first,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.team, 0, 63)?;
+        in_range(self.first, -1, 63)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.team, 0, 63)?;
+        in_range(self.first, -1, 63)?;
         _p.write_int(self.team)?;
         _p.write_int(self.first)?;
         Ok(_p.written())
@@ -1847,3 +3065,665 @@ impl fmt::Debug for SvKillMsgTeam {
     }
 }
 
+impl SvYourVote {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvYourVote, Error> {
+        SvYourVote::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvYourVote, Error> {
+        let result = SvYourVote::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvYourVote"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvYourVote, Error> {
+        Ok(SvYourVote {
+            voted: in_range(_p.read_int(&mut _p.locate(warn, "SvYourVote.voted"))?, -1, 1)?,
+        })
+    }
+    pub fn new(voted: i32) -> Result<SvYourVote, Error> {
+        let result = SvYourVote {
+            voted,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.voted, -1, 1)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.voted, -1, 1)?;
+        _p.write_int(self.voted)?;
+        Ok(_p.written())
+    }
+}
+impl fmt::Debug for SvYourVote {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SvYourVote")
+            .field("voted", &self.voted)
+            .finish()
+    }
+}
+
+impl SvRaceFinish {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvRaceFinish, Error> {
+        SvRaceFinish::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvRaceFinish, Error> {
+        let result = SvRaceFinish::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvRaceFinish"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvRaceFinish, Error> {
+        Ok(SvRaceFinish {
+            client_id: in_range(_p.read_int(&mut _p.locate(warn, "SvRaceFinish.client_id"))?, 0, 63)?,
+            time: _p.read_int(&mut _p.locate(warn, "SvRaceFinish.time"))?,
+            diff: _p.read_int(&mut _p.locate(warn, "SvRaceFinish.diff"))?,
+            record_personal: to_bool(_p.read_int(&mut _p.locate(warn, "SvRaceFinish.record_personal"))?)?,
+            record_server: to_bool(_p.read_int(&mut _p.locate(warn, "SvRaceFinish.record_server"))?)?,
+        })
+    }
+    pub fn new(client_id: i32, time: i32, diff: i32, record_personal: bool, record_server: bool) -> Result<SvRaceFinish, Error> {
+        let result = SvRaceFinish {
+            client_id,
+            time,
+            diff,
+            record_personal,
+            record_server,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.client_id, 0, 63)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.client_id, 0, 63)?;
+        _p.write_int(self.client_id)?;
+        _p.write_int(self.time)?;
+        _p.write_int(self.diff)?;
+        _p.write_int(self.record_personal as i32)?;
+        _p.write_int(self.record_server as i32)?;
+        Ok(_p.written())
+    }
+}
+impl fmt::Debug for SvRaceFinish {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SvRaceFinish")
+            .field("client_id", &self.client_id)
+            .field("time", &self.time)
+            .field("diff", &self.diff)
+            .field("record_personal", &self.record_personal)
+            .field("record_server", &self.record_server)
+            .finish()
+    }
+}
+
+impl<'a> SvCommandInfo<'a> {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvCommandInfo<'a>, Error> {
+        SvCommandInfo::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvCommandInfo<'a>, Error> {
+        let result = SvCommandInfo::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvCommandInfo"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvCommandInfo<'a>, Error> {
+        Ok(SvCommandInfo {
+            name: sanitize(&mut _p.locate(warn, "SvCommandInfo.name"), _p.read_string()?)?,
+            args_format: sanitize(&mut _p.locate(warn, "SvCommandInfo.args_format"), _p.read_string()?)?,
+            help_text: sanitize(&mut _p.locate(warn, "SvCommandInfo.help_text"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(name: &'a [u8], args_format: &'a [u8], help_text: &'a [u8]) -> Result<SvCommandInfo<'a>, Error> {
+        let result = SvCommandInfo {
+            name,
+            args_format,
+            help_text,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.args_format)?;
+        sanitize(&mut Ignore, self.help_text)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.args_format)?;
+        sanitize(&mut Ignore, self.help_text)?;
+        _p.write_string(self.name)?;
+        _p.write_string(self.args_format)?;
+        _p.write_string(self.help_text)?;
+        Ok(_p.written())
+    }
+}
+impl<'a> fmt::Debug for SvCommandInfo<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SvCommandInfo")
+            .field("name", &pretty::Bytes::new(&self.name))
+            .field("args_format", &pretty::Bytes::new(&self.args_format))
+            .field("help_text", &pretty::Bytes::new(&self.help_text))
+            .finish()
+    }
+}
+
+impl<'a> SvCommandInfoRemove<'a> {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvCommandInfoRemove<'a>, Error> {
+        SvCommandInfoRemove::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvCommandInfoRemove<'a>, Error> {
+        let result = SvCommandInfoRemove::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvCommandInfoRemove"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvCommandInfoRemove<'a>, Error> {
+        Ok(SvCommandInfoRemove {
+            name: sanitize(&mut _p.locate(warn, "SvCommandInfoRemove.name"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(name: &'a [u8]) -> Result<SvCommandInfoRemove<'a>, Error> {
+        let result = SvCommandInfoRemove {
+            name,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.name)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.name)?;
+        _p.write_string(self.name)?;
+        Ok(_p.written())
+    }
+}
+impl<'a> fmt::Debug for SvCommandInfoRemove<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SvCommandInfoRemove")
+            .field("name", &pretty::Bytes::new(&self.name))
+            .finish()
+    }
+}
+
+impl SvVoteOptionGroupStart {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteOptionGroupStart, Error> {
+        SvVoteOptionGroupStart::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteOptionGroupStart, Error> {
+        error::finish(&mut _p.locate(warn, "SvVoteOptionGroupStart"), _p, Ok(SvVoteOptionGroupStart))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        Ok(_p.written())
+    }
+}
+impl fmt::Debug for SvVoteOptionGroupStart {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SvVoteOptionGroupStart")
+            .finish()
+    }
+}
+
+impl SvVoteOptionGroupEnd {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteOptionGroupEnd, Error> {
+        SvVoteOptionGroupEnd::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteOptionGroupEnd, Error> {
+        error::finish(&mut _p.locate(warn, "SvVoteOptionGroupEnd"), _p, Ok(SvVoteOptionGroupEnd))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        Ok(_p.written())
+    }
+}
+impl fmt::Debug for SvVoteOptionGroupEnd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SvVoteOptionGroupEnd")
+            .finish()
+    }
+}
+
+impl SvCommandInfoGroupStart {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvCommandInfoGroupStart, Error> {
+        SvCommandInfoGroupStart::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvCommandInfoGroupStart, Error> {
+        error::finish(&mut _p.locate(warn, "SvCommandInfoGroupStart"), _p, Ok(SvCommandInfoGroupStart))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        Ok(_p.written())
+    }
+}
+impl fmt::Debug for SvCommandInfoGroupStart {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SvCommandInfoGroupStart")
+            .finish()
+    }
+}
+
+impl SvCommandInfoGroupEnd {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvCommandInfoGroupEnd, Error> {
+        SvCommandInfoGroupEnd::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvCommandInfoGroupEnd, Error> {
+        error::finish(&mut _p.locate(warn, "SvCommandInfoGroupEnd"), _p, Ok(SvCommandInfoGroupEnd))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        Ok(_p.written())
+    }
+}
+impl fmt::Debug for SvCommandInfoGroupEnd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SvCommandInfoGroupEnd")
+            .finish()
+    }
+}
+
+impl SvChangeInfoCooldown {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvChangeInfoCooldown, Error> {
+        SvChangeInfoCooldown::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvChangeInfoCooldown, Error> {
+        let result = SvChangeInfoCooldown::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvChangeInfoCooldown"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvChangeInfoCooldown, Error> {
+        Ok(SvChangeInfoCooldown {
+            wait_until: ::snap_obj::Tick(_p.read_int(&mut _p.locate(warn, "SvChangeInfoCooldown.wait_until"))?),
+        })
+    }
+    pub fn new(wait_until: ::snap_obj::Tick) -> Result<SvChangeInfoCooldown, Error> {
+        let result = SvChangeInfoCooldown {
+            wait_until,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        _p.write_int(self.wait_until.0)?;
+        Ok(_p.written())
+    }
+}
+impl fmt::Debug for SvChangeInfoCooldown {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SvChangeInfoCooldown")
+            .field("wait_until", &self.wait_until)
+            .finish()
+    }
+}
+
+impl<'a> SvMotd<'a> {
+    pub fn to_owned(&self) -> SvMotdOwned {
+        SvMotdOwned {
+            message: self.message.to_vec(),
+        }
+    }
+}
+
+impl SvMotdOwned {
+    pub fn as_ref(&self) -> SvMotd<'_> {
+        SvMotd {
+            message: &self.message[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvMotdOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvBroadcast<'a> {
+    pub fn to_owned(&self) -> SvBroadcastOwned {
+        SvBroadcastOwned {
+            message: self.message.to_vec(),
+        }
+    }
+}
+
+impl SvBroadcastOwned {
+    pub fn as_ref(&self) -> SvBroadcast<'_> {
+        SvBroadcast {
+            message: &self.message[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvBroadcastOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvChat<'a> {
+    pub fn to_owned(&self) -> SvChatOwned {
+        SvChatOwned {
+            team: self.team,
+            client_id: self.client_id,
+            message: self.message.to_vec(),
+        }
+    }
+}
+
+impl SvChatOwned {
+    pub fn as_ref(&self) -> SvChat<'_> {
+        SvChat {
+            team: self.team,
+            client_id: self.client_id,
+            message: &self.message[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvChatOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvVoteOptionListAdd<'a> {
+    pub fn to_owned(&self) -> SvVoteOptionListAddOwned {
+        SvVoteOptionListAddOwned {
+            num_options: self.num_options,
+            description: [
+                self.description[0].to_vec(),
+                self.description[1].to_vec(),
+                self.description[2].to_vec(),
+                self.description[3].to_vec(),
+                self.description[4].to_vec(),
+                self.description[5].to_vec(),
+                self.description[6].to_vec(),
+                self.description[7].to_vec(),
+                self.description[8].to_vec(),
+                self.description[9].to_vec(),
+                self.description[10].to_vec(),
+                self.description[11].to_vec(),
+                self.description[12].to_vec(),
+                self.description[13].to_vec(),
+                self.description[14].to_vec(),
+            ],
+        }
+    }
+}
+
+impl SvVoteOptionListAddOwned {
+    pub fn as_ref(&self) -> SvVoteOptionListAdd<'_> {
+        SvVoteOptionListAdd {
+            num_options: self.num_options,
+            description: [
+                &self.description[0][..],
+                &self.description[1][..],
+                &self.description[2][..],
+                &self.description[3][..],
+                &self.description[4][..],
+                &self.description[5][..],
+                &self.description[6][..],
+                &self.description[7][..],
+                &self.description[8][..],
+                &self.description[9][..],
+                &self.description[10][..],
+                &self.description[11][..],
+                &self.description[12][..],
+                &self.description[13][..],
+                &self.description[14][..],
+            ],
+        }
+    }
+}
+
+impl fmt::Debug for SvVoteOptionListAddOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvVoteOptionAdd<'a> {
+    pub fn to_owned(&self) -> SvVoteOptionAddOwned {
+        SvVoteOptionAddOwned {
+            description: self.description.to_vec(),
+        }
+    }
+}
+
+impl SvVoteOptionAddOwned {
+    pub fn as_ref(&self) -> SvVoteOptionAdd<'_> {
+        SvVoteOptionAdd {
+            description: &self.description[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvVoteOptionAddOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvVoteOptionRemove<'a> {
+    pub fn to_owned(&self) -> SvVoteOptionRemoveOwned {
+        SvVoteOptionRemoveOwned {
+            description: self.description.to_vec(),
+        }
+    }
+}
+
+impl SvVoteOptionRemoveOwned {
+    pub fn as_ref(&self) -> SvVoteOptionRemove<'_> {
+        SvVoteOptionRemove {
+            description: &self.description[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvVoteOptionRemoveOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvVoteSet<'a> {
+    pub fn to_owned(&self) -> SvVoteSetOwned {
+        SvVoteSetOwned {
+            timeout: self.timeout,
+            description: self.description.to_vec(),
+            reason: self.reason.to_vec(),
+        }
+    }
+}
+
+impl SvVoteSetOwned {
+    pub fn as_ref(&self) -> SvVoteSet<'_> {
+        SvVoteSet {
+            timeout: self.timeout,
+            description: &self.description[..],
+            reason: &self.reason[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvVoteSetOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> ClSay<'a> {
+    pub fn to_owned(&self) -> ClSayOwned {
+        ClSayOwned {
+            team: self.team,
+            message: self.message.to_vec(),
+        }
+    }
+}
+
+impl ClSayOwned {
+    pub fn as_ref(&self) -> ClSay<'_> {
+        ClSay {
+            team: self.team,
+            message: &self.message[..],
+        }
+    }
+}
+
+impl fmt::Debug for ClSayOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> ClStartInfo<'a> {
+    pub fn to_owned(&self) -> ClStartInfoOwned {
+        ClStartInfoOwned {
+            name: self.name.to_vec(),
+            clan: self.clan.to_vec(),
+            country: self.country,
+            skin: self.skin.to_vec(),
+            use_custom_color: self.use_custom_color,
+            color_body: self.color_body,
+            color_feet: self.color_feet,
+        }
+    }
+}
+
+impl ClStartInfoOwned {
+    pub fn as_ref(&self) -> ClStartInfo<'_> {
+        ClStartInfo {
+            name: &self.name[..],
+            clan: &self.clan[..],
+            country: self.country,
+            skin: &self.skin[..],
+            use_custom_color: self.use_custom_color,
+            color_body: self.color_body,
+            color_feet: self.color_feet,
+        }
+    }
+}
+
+impl fmt::Debug for ClStartInfoOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> ClChangeInfo<'a> {
+    pub fn to_owned(&self) -> ClChangeInfoOwned {
+        ClChangeInfoOwned {
+            name: self.name.to_vec(),
+            clan: self.clan.to_vec(),
+            country: self.country,
+            skin: self.skin.to_vec(),
+            use_custom_color: self.use_custom_color,
+            color_body: self.color_body,
+            color_feet: self.color_feet,
+        }
+    }
+}
+
+impl ClChangeInfoOwned {
+    pub fn as_ref(&self) -> ClChangeInfo<'_> {
+        ClChangeInfo {
+            name: &self.name[..],
+            clan: &self.clan[..],
+            country: self.country,
+            skin: &self.skin[..],
+            use_custom_color: self.use_custom_color,
+            color_body: self.color_body,
+            color_feet: self.color_feet,
+        }
+    }
+}
+
+impl fmt::Debug for ClChangeInfoOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> ClCallVote<'a> {
+    pub fn to_owned(&self) -> ClCallVoteOwned {
+        ClCallVoteOwned {
+            type_: self.type_.to_vec(),
+            value: self.value.to_vec(),
+            reason: self.reason.to_vec(),
+        }
+    }
+}
+
+impl ClCallVoteOwned {
+    pub fn as_ref(&self) -> ClCallVote<'_> {
+        ClCallVote {
+            type_: &self.type_[..],
+            value: &self.value[..],
+            reason: &self.reason[..],
+        }
+    }
+}
+
+impl fmt::Debug for ClCallVoteOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvCommandInfo<'a> {
+    pub fn to_owned(&self) -> SvCommandInfoOwned {
+        SvCommandInfoOwned {
+            name: self.name.to_vec(),
+            args_format: self.args_format.to_vec(),
+            help_text: self.help_text.to_vec(),
+        }
+    }
+}
+
+impl SvCommandInfoOwned {
+    pub fn as_ref(&self) -> SvCommandInfo<'_> {
+        SvCommandInfo {
+            name: &self.name[..],
+            args_format: &self.args_format[..],
+            help_text: &self.help_text[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvCommandInfoOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvCommandInfoRemove<'a> {
+    pub fn to_owned(&self) -> SvCommandInfoRemoveOwned {
+        SvCommandInfoRemoveOwned {
+            name: self.name.to_vec(),
+        }
+    }
+}
+
+impl SvCommandInfoRemoveOwned {
+    pub fn as_ref(&self) -> SvCommandInfoRemove<'_> {
+        SvCommandInfoRemove {
+            name: &self.name[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvCommandInfoRemoveOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
@@ -1,6 +1,8 @@
+use common::warn::Located;
 use gamenet_common::error::Error;
 use packer::Unpacker;
 use packer::Warning;
+use uuid::Uuid;
 use warn::Warn;
 
 pub mod connless;
@@ -9,8 +11,11 @@ pub mod system;
 
 pub use self::connless::Connless;
 pub use self::game::Game;
+pub use self::game::GameOwned;
 pub use self::system::System;
+pub use self::system::SystemOwned;
 
+pub use gamenet_common::msg::Addr5Packed;
 pub use gamenet_common::msg::AddrPacked;
 pub use gamenet_common::msg::CLIENTS_DATA_NONE;
 pub use gamenet_common::msg::ClientsData;
@@ -25,15 +30,15 @@ impl<'a> gamenet_common::msg::Protocol<'a> for Protocol {
 
     fn decode_system<W>(warn: &mut W, id: MessageId, p: &mut Unpacker<'a>)
         -> Result<Self::System, Error>
-        where W: Warn<Warning>
+        where W: Warn<Located<Warning>>
     {
-        System::decode_msg(warn, id, p)
+        System::decode_msg_located(warn, id, p)
     }
     fn decode_game<W>(warn: &mut W, id: MessageId, p: &mut Unpacker<'a>)
         -> Result<Self::Game, Error>
-        where W: Warn<Warning>
+        where W: Warn<Located<Warning>>
     {
-        Game::decode_msg(warn, id, p)
+        Game::decode_msg_located(warn, id, p)
     }
 }
 
@@ -44,3 +49,47 @@ pub fn decode<'a, W>(warn: &mut W, p: &mut Unpacker<'a>)
     gamenet_common::msg::decode(warn, Protocol, p)
 }
 
+/// Like `decode`, but attaches the offset and the name of the message field
+/// to each warning.
+pub fn decode_located<'a, W>(warn: &mut W, p: &mut Unpacker<'a>)
+    -> Result<SystemOrGame<System<'a>, Game<'a>>, Error>
+    where W: Warn<Located<Warning>>
+{
+    gamenet_common::msg::decode_located(warn, Protocol, p)
+}
+
+/// Returns the name a known message or snapshot object UUID was derived
+/// from.
+pub fn uuid_name(uuid: Uuid) -> Option<&'static str> {
+    system::uuid_name(uuid)
+        .or_else(|| game::uuid_name(uuid))
+        .or_else(|| ::snap_obj::uuid_name(uuid))
+}
+
+/// Returns the answer to a `what-is@ddnet.tw` query about `uuid`.
+pub fn what_is_reply(uuid: Uuid) -> System<'static> {
+    match uuid_name(uuid) {
+        Some(name) => System::ItIs(system::ItIs {
+            uuid,
+            name: name.as_bytes(),
+        }),
+        None => System::IDontKnow(system::IDontKnow { uuid }),
+    }
+}
+
+/// Returns the answer to a message with ID `id` if it's an unknown UUID.
+///
+/// Instead of dropping these messages silently, the reference implementation
+/// tells the peer that it doesn't know the UUID.
+pub fn unknown_id_reply(id: SystemOrGame<MessageId, MessageId>) -> Option<System<'static>> {
+    let uuid = match id {
+        SystemOrGame::System(MessageId::Uuid(u)) => u,
+        SystemOrGame::Game(MessageId::Uuid(u)) => u,
+        _ => return None,
+    };
+    if uuid_name(uuid).is_some() {
+        return None;
+    }
+    Some(System::IDontKnow(system::IDontKnow { uuid }))
+}
+
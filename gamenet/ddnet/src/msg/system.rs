@@ -1,6 +1,9 @@
-use buffer::CapacityError;
 use common::digest::Sha256;
 use common::pretty;
+use common::warn::Located;
+use common::warn::strip_location;
+use error;
+use error::EncodeError;
 use error::Error;
 use packer::Packer;
 use packer::Unpacker;
@@ -24,7 +27,7 @@ impl<'a> System<'a> {
         }
     }
     pub fn encode<'d, 's>(&self, mut p: Packer<'d, 's>)
-        -> Result<&'d [u8], CapacityError>
+        -> Result<&'d [u8], EncodeError>
     {
         with_packer(&mut p, |p| SystemOrGame::System(self.msg_id()).encode_id(p))?;
         with_packer(&mut p, |p| self.encode_msg(p))?;
@@ -64,8 +67,33 @@ pub const PONG_EX: Uuid = Uuid::from_u128(0xd8295530_14a7_3a0a_b02e_b2cee08d2033
 pub const CHECKSUM_REQUEST: Uuid = Uuid::from_u128(0x60a7cef1_2ecc_3ed4_b138_00fd0c8f5994);
 pub const CHECKSUM_RESPONSE: Uuid = Uuid::from_u128(0x88fc61ec_5a3c_3fc3_8dfa_fd3b715db9e0);
 pub const CHECKSUM_ERROR: Uuid = Uuid::from_u128(0x090960d1_4000_3fd5_9670_4976ae702a6a);
+pub const REDIRECT: Uuid = Uuid::from_u128(0x4efe406a_7774_33f1_bfde_1806ff6d1528);
+pub const RCON_CMD_GROUP_START: Uuid = Uuid::from_u128(0x85f67ffe_f1b1_3af3_98c4_26dbf77111b7);
+pub const RCON_CMD_GROUP_END: Uuid = Uuid::from_u128(0x5e02c980_6ca1_3c99_a9af_4650ae956252);
+
+pub fn uuid_name(uuid: Uuid) -> Option<&'static str> {
+    Some(match uuid {
+        WHAT_IS => "what-is@ddnet.tw",
+        IT_IS => "it-is@ddnet.tw",
+        I_DONT_KNOW => "i-dont-know@ddnet.tw",
+        RCON_TYPE => "rcon-type@ddnet.tw",
+        MAP_DETAILS => "map-details@ddnet.tw",
+        CAPABILITIES => "capabilities@ddnet.tw",
+        CLIENT_VERSION => "clientver@ddnet.tw",
+        PING_EX => "ping@ddnet.tw",
+        PONG_EX => "pong@ddnet.tw",
+        CHECKSUM_REQUEST => "checksum-request@ddnet.tw",
+        CHECKSUM_RESPONSE => "checksum-response@ddnet.tw",
+        CHECKSUM_ERROR => "checksum-error@ddnet.tw",
+        REDIRECT => "redirect@ddnet.org",
+        RCON_CMD_GROUP_START => "rcon-cmd-group-start@ddnet.org",
+        RCON_CMD_GROUP_END => "rcon-cmd-group-end@ddnet.org",
+        _ => return None,
+    })
+}
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub enum System<'a> {
     Info(Info<'a>),
     MapChange(MapChange<'a>),
@@ -99,44 +127,53 @@ pub enum System<'a> {
     ChecksumRequest(ChecksumRequest),
     ChecksumResponse(ChecksumResponse),
     ChecksumError(ChecksumError),
+    Redirect(Redirect),
+    RconCmdGroupStart(RconCmdGroupStart),
+    RconCmdGroupEnd(RconCmdGroupEnd),
 }
 
 impl<'a> System<'a> {
     pub fn decode_msg<W: Warn<Warning>>(warn: &mut W, msg_id: MessageId, _p: &mut Unpacker<'a>) -> Result<System<'a>, Error> {
+        System::decode_msg_located(&mut strip_location(warn), msg_id, _p)
+    }
+    pub fn decode_msg_located<W: Warn<Located<Warning>>>(warn: &mut W, msg_id: MessageId, _p: &mut Unpacker<'a>) -> Result<System<'a>, Error> {
         use self::MessageId::*;
         Ok(match msg_id {
-            Ordinal(INFO) => System::Info(Info::decode(warn, _p)?),
-            Ordinal(MAP_CHANGE) => System::MapChange(MapChange::decode(warn, _p)?),
-            Ordinal(MAP_DATA) => System::MapData(MapData::decode(warn, _p)?),
-            Ordinal(CON_READY) => System::ConReady(ConReady::decode(warn, _p)?),
-            Ordinal(SNAP) => System::Snap(Snap::decode(warn, _p)?),
-            Ordinal(SNAP_EMPTY) => System::SnapEmpty(SnapEmpty::decode(warn, _p)?),
-            Ordinal(SNAP_SINGLE) => System::SnapSingle(SnapSingle::decode(warn, _p)?),
-            Ordinal(INPUT_TIMING) => System::InputTiming(InputTiming::decode(warn, _p)?),
-            Ordinal(RCON_AUTH_STATUS) => System::RconAuthStatus(RconAuthStatus::decode(warn, _p)?),
-            Ordinal(RCON_LINE) => System::RconLine(RconLine::decode(warn, _p)?),
-            Ordinal(READY) => System::Ready(Ready::decode(warn, _p)?),
-            Ordinal(ENTER_GAME) => System::EnterGame(EnterGame::decode(warn, _p)?),
-            Ordinal(INPUT) => System::Input(Input::decode(warn, _p)?),
-            Ordinal(RCON_CMD) => System::RconCmd(RconCmd::decode(warn, _p)?),
-            Ordinal(RCON_AUTH) => System::RconAuth(RconAuth::decode(warn, _p)?),
-            Ordinal(REQUEST_MAP_DATA) => System::RequestMapData(RequestMapData::decode(warn, _p)?),
-            Ordinal(PING) => System::Ping(Ping::decode(warn, _p)?),
-            Ordinal(PING_REPLY) => System::PingReply(PingReply::decode(warn, _p)?),
-            Ordinal(RCON_CMD_ADD) => System::RconCmdAdd(RconCmdAdd::decode(warn, _p)?),
-            Ordinal(RCON_CMD_REMOVE) => System::RconCmdRemove(RconCmdRemove::decode(warn, _p)?),
-            Uuid(WHAT_IS) => System::WhatIs(WhatIs::decode(warn, _p)?),
-            Uuid(IT_IS) => System::ItIs(ItIs::decode(warn, _p)?),
-            Uuid(I_DONT_KNOW) => System::IDontKnow(IDontKnow::decode(warn, _p)?),
-            Uuid(RCON_TYPE) => System::RconType(RconType::decode(warn, _p)?),
-            Uuid(MAP_DETAILS) => System::MapDetails(MapDetails::decode(warn, _p)?),
-            Uuid(CAPABILITIES) => System::Capabilities(Capabilities::decode(warn, _p)?),
-            Uuid(CLIENT_VERSION) => System::ClientVersion(ClientVersion::decode(warn, _p)?),
-            Uuid(PING_EX) => System::PingEx(PingEx::decode(warn, _p)?),
-            Uuid(PONG_EX) => System::PongEx(PongEx::decode(warn, _p)?),
-            Uuid(CHECKSUM_REQUEST) => System::ChecksumRequest(ChecksumRequest::decode(warn, _p)?),
-            Uuid(CHECKSUM_RESPONSE) => System::ChecksumResponse(ChecksumResponse::decode(warn, _p)?),
-            Uuid(CHECKSUM_ERROR) => System::ChecksumError(ChecksumError::decode(warn, _p)?),
+            Ordinal(INFO) => System::Info(Info::decode_located(warn, _p)?),
+            Ordinal(MAP_CHANGE) => System::MapChange(MapChange::decode_located(warn, _p)?),
+            Ordinal(MAP_DATA) => System::MapData(MapData::decode_located(warn, _p)?),
+            Ordinal(CON_READY) => System::ConReady(ConReady::decode_located(warn, _p)?),
+            Ordinal(SNAP) => System::Snap(Snap::decode_located(warn, _p)?),
+            Ordinal(SNAP_EMPTY) => System::SnapEmpty(SnapEmpty::decode_located(warn, _p)?),
+            Ordinal(SNAP_SINGLE) => System::SnapSingle(SnapSingle::decode_located(warn, _p)?),
+            Ordinal(INPUT_TIMING) => System::InputTiming(InputTiming::decode_located(warn, _p)?),
+            Ordinal(RCON_AUTH_STATUS) => System::RconAuthStatus(RconAuthStatus::decode_located(warn, _p)?),
+            Ordinal(RCON_LINE) => System::RconLine(RconLine::decode_located(warn, _p)?),
+            Ordinal(READY) => System::Ready(Ready::decode_located(warn, _p)?),
+            Ordinal(ENTER_GAME) => System::EnterGame(EnterGame::decode_located(warn, _p)?),
+            Ordinal(INPUT) => System::Input(Input::decode_located(warn, _p)?),
+            Ordinal(RCON_CMD) => System::RconCmd(RconCmd::decode_located(warn, _p)?),
+            Ordinal(RCON_AUTH) => System::RconAuth(RconAuth::decode_located(warn, _p)?),
+            Ordinal(REQUEST_MAP_DATA) => System::RequestMapData(RequestMapData::decode_located(warn, _p)?),
+            Ordinal(PING) => System::Ping(Ping::decode_located(warn, _p)?),
+            Ordinal(PING_REPLY) => System::PingReply(PingReply::decode_located(warn, _p)?),
+            Ordinal(RCON_CMD_ADD) => System::RconCmdAdd(RconCmdAdd::decode_located(warn, _p)?),
+            Ordinal(RCON_CMD_REMOVE) => System::RconCmdRemove(RconCmdRemove::decode_located(warn, _p)?),
+            Uuid(WHAT_IS) => System::WhatIs(WhatIs::decode_located(warn, _p)?),
+            Uuid(IT_IS) => System::ItIs(ItIs::decode_located(warn, _p)?),
+            Uuid(I_DONT_KNOW) => System::IDontKnow(IDontKnow::decode_located(warn, _p)?),
+            Uuid(RCON_TYPE) => System::RconType(RconType::decode_located(warn, _p)?),
+            Uuid(MAP_DETAILS) => System::MapDetails(MapDetails::decode_located(warn, _p)?),
+            Uuid(CAPABILITIES) => System::Capabilities(Capabilities::decode_located(warn, _p)?),
+            Uuid(CLIENT_VERSION) => System::ClientVersion(ClientVersion::decode_located(warn, _p)?),
+            Uuid(PING_EX) => System::PingEx(PingEx::decode_located(warn, _p)?),
+            Uuid(PONG_EX) => System::PongEx(PongEx::decode_located(warn, _p)?),
+            Uuid(CHECKSUM_REQUEST) => System::ChecksumRequest(ChecksumRequest::decode_located(warn, _p)?),
+            Uuid(CHECKSUM_RESPONSE) => System::ChecksumResponse(ChecksumResponse::decode_located(warn, _p)?),
+            Uuid(CHECKSUM_ERROR) => System::ChecksumError(ChecksumError::decode_located(warn, _p)?),
+            Uuid(REDIRECT) => System::Redirect(Redirect::decode_located(warn, _p)?),
+            Uuid(RCON_CMD_GROUP_START) => System::RconCmdGroupStart(RconCmdGroupStart::decode_located(warn, _p)?),
+            Uuid(RCON_CMD_GROUP_END) => System::RconCmdGroupEnd(RconCmdGroupEnd::decode_located(warn, _p)?),
             _ => return Err(Error::UnknownId),
         })
     }
@@ -174,9 +211,51 @@ impl<'a> System<'a> {
             System::ChecksumRequest(_) => MessageId::from(CHECKSUM_REQUEST),
             System::ChecksumResponse(_) => MessageId::from(CHECKSUM_RESPONSE),
             System::ChecksumError(_) => MessageId::from(CHECKSUM_ERROR),
+            System::Redirect(_) => MessageId::from(REDIRECT),
+            System::RconCmdGroupStart(_) => MessageId::from(RCON_CMD_GROUP_START),
+            System::RconCmdGroupEnd(_) => MessageId::from(RCON_CMD_GROUP_END),
+        }
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        match *self {
+            System::Info(ref i) => i.validate(),
+            System::MapChange(ref i) => i.validate(),
+            System::MapData(ref i) => i.validate(),
+            System::ConReady(ref i) => i.validate(),
+            System::Snap(ref i) => i.validate(),
+            System::SnapEmpty(ref i) => i.validate(),
+            System::SnapSingle(ref i) => i.validate(),
+            System::InputTiming(ref i) => i.validate(),
+            System::RconAuthStatus(ref i) => i.validate(),
+            System::RconLine(ref i) => i.validate(),
+            System::Ready(ref i) => i.validate(),
+            System::EnterGame(ref i) => i.validate(),
+            System::Input(ref i) => i.validate(),
+            System::RconCmd(ref i) => i.validate(),
+            System::RconAuth(ref i) => i.validate(),
+            System::RequestMapData(ref i) => i.validate(),
+            System::Ping(ref i) => i.validate(),
+            System::PingReply(ref i) => i.validate(),
+            System::RconCmdAdd(ref i) => i.validate(),
+            System::RconCmdRemove(ref i) => i.validate(),
+            System::WhatIs(ref i) => i.validate(),
+            System::ItIs(ref i) => i.validate(),
+            System::IDontKnow(ref i) => i.validate(),
+            System::RconType(ref i) => i.validate(),
+            System::MapDetails(ref i) => i.validate(),
+            System::Capabilities(ref i) => i.validate(),
+            System::ClientVersion(ref i) => i.validate(),
+            System::PingEx(ref i) => i.validate(),
+            System::PongEx(ref i) => i.validate(),
+            System::ChecksumRequest(ref i) => i.validate(),
+            System::ChecksumResponse(ref i) => i.validate(),
+            System::ChecksumError(ref i) => i.validate(),
+            System::Redirect(ref i) => i.validate(),
+            System::RconCmdGroupStart(ref i) => i.validate(),
+            System::RconCmdGroupEnd(ref i) => i.validate(),
         }
     }
-    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         match *self {
             System::Info(ref i) => i.encode(p),
             System::MapChange(ref i) => i.encode(p),
@@ -210,6 +289,9 @@ impl<'a> System<'a> {
             System::ChecksumRequest(ref i) => i.encode(p),
             System::ChecksumResponse(ref i) => i.encode(p),
             System::ChecksumError(ref i) => i.encode(p),
+            System::Redirect(ref i) => i.encode(p),
+            System::RconCmdGroupStart(ref i) => i.encode(p),
+            System::RconCmdGroupEnd(ref i) => i.encode(p),
         }
     }
 }
@@ -249,6 +331,9 @@ impl<'a> fmt::Debug for System<'a> {
             System::ChecksumRequest(ref i) => i.fmt(f),
             System::ChecksumResponse(ref i) => i.fmt(f),
             System::ChecksumError(ref i) => i.fmt(f),
+            System::Redirect(ref i) => i.fmt(f),
+            System::RconCmdGroupStart(ref i) => i.fmt(f),
+            System::RconCmdGroupEnd(ref i) => i.fmt(f),
         }
     }
 }
@@ -444,78 +529,258 @@ impl<'a> From<ChecksumError> for System<'a> {
         System::ChecksumError(i)
     }
 }
+
+impl<'a> From<Redirect> for System<'a> {
+    fn from(i: Redirect) -> System<'a> {
+        System::Redirect(i)
+    }
+}
+
+impl<'a> From<RconCmdGroupStart> for System<'a> {
+    fn from(i: RconCmdGroupStart) -> System<'a> {
+        System::RconCmdGroupStart(i)
+    }
+}
+
+impl<'a> From<RconCmdGroupEnd> for System<'a> {
+    fn from(i: RconCmdGroupEnd) -> System<'a> {
+        System::RconCmdGroupEnd(i)
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SystemOwned {
+    Info(InfoOwned),
+    MapChange(MapChangeOwned),
+    MapData(MapDataOwned),
+    ConReady(ConReady),
+    Snap(SnapOwned),
+    SnapEmpty(SnapEmpty),
+    SnapSingle(SnapSingleOwned),
+    InputTiming(InputTiming),
+    RconAuthStatus(RconAuthStatus),
+    RconLine(RconLineOwned),
+    Ready(Ready),
+    EnterGame(EnterGame),
+    Input(Input),
+    RconCmd(RconCmdOwned),
+    RconAuth(RconAuthOwned),
+    RequestMapData(RequestMapData),
+    Ping(Ping),
+    PingReply(PingReply),
+    RconCmdAdd(RconCmdAddOwned),
+    RconCmdRemove(RconCmdRemoveOwned),
+    WhatIs(WhatIs),
+    ItIs(ItIsOwned),
+    IDontKnow(IDontKnow),
+    RconType(RconType),
+    MapDetails(MapDetailsOwned),
+    Capabilities(Capabilities),
+    ClientVersion(ClientVersionOwned),
+    PingEx(PingEx),
+    PongEx(PongEx),
+    ChecksumRequest(ChecksumRequest),
+    ChecksumResponse(ChecksumResponse),
+    ChecksumError(ChecksumError),
+    Redirect(Redirect),
+    RconCmdGroupStart(RconCmdGroupStart),
+    RconCmdGroupEnd(RconCmdGroupEnd),
+}
+
+impl<'a> System<'a> {
+    pub fn to_owned(&self) -> SystemOwned {
+        match *self {
+            System::Info(ref i) => SystemOwned::Info(i.to_owned()),
+            System::MapChange(ref i) => SystemOwned::MapChange(i.to_owned()),
+            System::MapData(ref i) => SystemOwned::MapData(i.to_owned()),
+            System::ConReady(i) => SystemOwned::ConReady(i),
+            System::Snap(ref i) => SystemOwned::Snap(i.to_owned()),
+            System::SnapEmpty(i) => SystemOwned::SnapEmpty(i),
+            System::SnapSingle(ref i) => SystemOwned::SnapSingle(i.to_owned()),
+            System::InputTiming(i) => SystemOwned::InputTiming(i),
+            System::RconAuthStatus(i) => SystemOwned::RconAuthStatus(i),
+            System::RconLine(ref i) => SystemOwned::RconLine(i.to_owned()),
+            System::Ready(i) => SystemOwned::Ready(i),
+            System::EnterGame(i) => SystemOwned::EnterGame(i),
+            System::Input(i) => SystemOwned::Input(i),
+            System::RconCmd(ref i) => SystemOwned::RconCmd(i.to_owned()),
+            System::RconAuth(ref i) => SystemOwned::RconAuth(i.to_owned()),
+            System::RequestMapData(i) => SystemOwned::RequestMapData(i),
+            System::Ping(i) => SystemOwned::Ping(i),
+            System::PingReply(i) => SystemOwned::PingReply(i),
+            System::RconCmdAdd(ref i) => SystemOwned::RconCmdAdd(i.to_owned()),
+            System::RconCmdRemove(ref i) => SystemOwned::RconCmdRemove(i.to_owned()),
+            System::WhatIs(i) => SystemOwned::WhatIs(i),
+            System::ItIs(ref i) => SystemOwned::ItIs(i.to_owned()),
+            System::IDontKnow(i) => SystemOwned::IDontKnow(i),
+            System::RconType(i) => SystemOwned::RconType(i),
+            System::MapDetails(ref i) => SystemOwned::MapDetails(i.to_owned()),
+            System::Capabilities(i) => SystemOwned::Capabilities(i),
+            System::ClientVersion(ref i) => SystemOwned::ClientVersion(i.to_owned()),
+            System::PingEx(i) => SystemOwned::PingEx(i),
+            System::PongEx(i) => SystemOwned::PongEx(i),
+            System::ChecksumRequest(i) => SystemOwned::ChecksumRequest(i),
+            System::ChecksumResponse(i) => SystemOwned::ChecksumResponse(i),
+            System::ChecksumError(i) => SystemOwned::ChecksumError(i),
+            System::Redirect(i) => SystemOwned::Redirect(i),
+            System::RconCmdGroupStart(i) => SystemOwned::RconCmdGroupStart(i),
+            System::RconCmdGroupEnd(i) => SystemOwned::RconCmdGroupEnd(i),
+        }
+    }
+}
+
+impl SystemOwned {
+    pub fn as_ref(&self) -> System<'_> {
+        match *self {
+            SystemOwned::Info(ref i) => System::Info(i.as_ref()),
+            SystemOwned::MapChange(ref i) => System::MapChange(i.as_ref()),
+            SystemOwned::MapData(ref i) => System::MapData(i.as_ref()),
+            SystemOwned::ConReady(i) => System::ConReady(i),
+            SystemOwned::Snap(ref i) => System::Snap(i.as_ref()),
+            SystemOwned::SnapEmpty(i) => System::SnapEmpty(i),
+            SystemOwned::SnapSingle(ref i) => System::SnapSingle(i.as_ref()),
+            SystemOwned::InputTiming(i) => System::InputTiming(i),
+            SystemOwned::RconAuthStatus(i) => System::RconAuthStatus(i),
+            SystemOwned::RconLine(ref i) => System::RconLine(i.as_ref()),
+            SystemOwned::Ready(i) => System::Ready(i),
+            SystemOwned::EnterGame(i) => System::EnterGame(i),
+            SystemOwned::Input(i) => System::Input(i),
+            SystemOwned::RconCmd(ref i) => System::RconCmd(i.as_ref()),
+            SystemOwned::RconAuth(ref i) => System::RconAuth(i.as_ref()),
+            SystemOwned::RequestMapData(i) => System::RequestMapData(i),
+            SystemOwned::Ping(i) => System::Ping(i),
+            SystemOwned::PingReply(i) => System::PingReply(i),
+            SystemOwned::RconCmdAdd(ref i) => System::RconCmdAdd(i.as_ref()),
+            SystemOwned::RconCmdRemove(ref i) => System::RconCmdRemove(i.as_ref()),
+            SystemOwned::WhatIs(i) => System::WhatIs(i),
+            SystemOwned::ItIs(ref i) => System::ItIs(i.as_ref()),
+            SystemOwned::IDontKnow(i) => System::IDontKnow(i),
+            SystemOwned::RconType(i) => System::RconType(i),
+            SystemOwned::MapDetails(ref i) => System::MapDetails(i.as_ref()),
+            SystemOwned::Capabilities(i) => System::Capabilities(i),
+            SystemOwned::ClientVersion(ref i) => System::ClientVersion(i.as_ref()),
+            SystemOwned::PingEx(i) => System::PingEx(i),
+            SystemOwned::PongEx(i) => System::PongEx(i),
+            SystemOwned::ChecksumRequest(i) => System::ChecksumRequest(i),
+            SystemOwned::ChecksumResponse(i) => System::ChecksumResponse(i),
+            SystemOwned::ChecksumError(i) => System::ChecksumError(i),
+            SystemOwned::Redirect(i) => System::Redirect(i),
+            SystemOwned::RconCmdGroupStart(i) => System::RconCmdGroupStart(i),
+            SystemOwned::RconCmdGroupEnd(i) => System::RconCmdGroupEnd(i),
+        }
+    }
+    pub fn msg_id(&self) -> MessageId {
+        self.as_ref().msg_id()
+    }
+    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        self.as_ref().encode_msg(p)
+    }
+}
+
+impl fmt::Debug for SystemOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> From<System<'a>> for SystemOwned {
+    fn from(i: System<'a>) -> SystemOwned {
+        i.to_owned()
+    }
+}
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Info<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub version: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::optional_bytes::serialize"))]
     pub password: Option<&'a [u8]>,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct MapChange<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
     pub crc: i32,
     pub size: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct MapData<'a> {
     pub last: i32,
     pub crc: i32,
     pub chunk: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub data: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ConReady;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Snap<'a> {
     pub tick: i32,
     pub delta_tick: i32,
     pub num_parts: i32,
     pub part: i32,
     pub crc: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub data: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SnapEmpty {
     pub tick: i32,
     pub delta_tick: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SnapSingle<'a> {
     pub tick: i32,
     pub delta_tick: i32,
     pub crc: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub data: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct InputTiming {
     pub input_pred_tick: i32,
     pub time_left: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RconAuthStatus {
     pub auth_level: Option<i32>,
     pub receive_commands: Option<i32>,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct RconLine<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub line: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Ready;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EnterGame;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Input {
     pub ack_snapshot: i32,
     pub intended_tick: i32,
@@ -524,92 +789,119 @@ pub struct Input {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct RconCmd<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub cmd: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct RconAuth<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub _unused: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub password: &'a [u8],
     pub request_commands: Option<i32>,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RequestMapData {
     pub chunk: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Ping;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PingReply;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct RconCmdAdd<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub help: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub params: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct RconCmdRemove<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WhatIs {
     pub uuid: Uuid,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ItIs<'a> {
     pub uuid: Uuid,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IDontKnow {
     pub uuid: Uuid,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RconType {
     pub username_required: bool,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct MapDetails<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
     pub sha256: Sha256,
     pub crc: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Capabilities {
     pub version: i32,
     pub flags: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ClientVersion<'a> {
     pub connection_id: Uuid,
     pub ddnet_version: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub ddnet_version_string: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PingEx {
     pub id: Uuid,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PongEx {
     pub id: Uuid,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ChecksumRequest {
     pub id: Uuid,
     pub start: i32,
@@ -617,28 +909,181 @@ pub struct ChecksumRequest {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ChecksumResponse {
     pub id: Uuid,
     pub sha256: Sha256,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ChecksumError {
     pub id: Uuid,
     pub error: i32,
 }
 
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Redirect {
+    pub port: i32,
+}
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RconCmdGroupStart {
+    pub length: i32,
+}
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RconCmdGroupEnd;
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InfoOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub version: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::optional_bytes"))]
+    pub password: Option<Vec<u8>>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MapChangeOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+    pub crc: i32,
+    pub size: i32,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MapDataOwned {
+    pub last: i32,
+    pub crc: i32,
+    pub chunk: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SnapOwned {
+    pub tick: i32,
+    pub delta_tick: i32,
+    pub num_parts: i32,
+    pub part: i32,
+    pub crc: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SnapSingleOwned {
+    pub tick: i32,
+    pub delta_tick: i32,
+    pub crc: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RconLineOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub line: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RconCmdOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub cmd: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RconAuthOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub _unused: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub password: Vec<u8>,
+    pub request_commands: Option<i32>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RconCmdAddOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub help: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub params: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RconCmdRemoveOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ItIsOwned {
+    pub uuid: Uuid,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MapDetailsOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+    pub sha256: Sha256,
+    pub crc: i32,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClientVersionOwned {
+    pub connection_id: Uuid,
+    pub ddnet_version: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub ddnet_version_string: Vec<u8>,
+}
+
 impl<'a> Info<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info<'a>, Error> {
-        let result = Ok(Info {
+        Info::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info<'a>, Error> {
+        let result = Info::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Info"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info<'a>, Error> {
+        Ok(Info {
             version: _p.read_string()?,
             password: _p.read_string().ok(),
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(version: &'a [u8], password: Option<&'a [u8]>) -> Result<Info<'a>, Error> {
+        let result = Info {
+            version,
+            password,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(self.password.is_some());
+    pub fn validate(&self) -> Result<(), Error> {
+        self.password.ok_or(Error::MissingValue)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        self.password.ok_or(Error::MissingValue)?;
         _p.write_string(self.version)?;
         _p.write_string(self.password.unwrap())?;
         Ok(_p.written())
@@ -655,15 +1100,32 @@ impl<'a> fmt::Debug for Info<'a> {
 
 impl<'a> MapChange<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapChange<'a>, Error> {
-        let result = Ok(MapChange {
+        MapChange::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapChange<'a>, Error> {
+        let result = MapChange::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "MapChange"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapChange<'a>, Error> {
+        Ok(MapChange {
             name: _p.read_string()?,
-            crc: _p.read_int(warn)?,
-            size: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
+            crc: _p.read_int(&mut _p.locate(warn, "MapChange.crc"))?,
+            size: _p.read_int(&mut _p.locate(warn, "MapChange.size"))?,
+        })
+    }
+    pub fn new(name: &'a [u8], crc: i32, size: i32) -> Result<MapChange<'a>, Error> {
+        let result = MapChange {
+            name,
+            crc,
+            size,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.name)?;
         _p.write_int(self.crc)?;
         _p.write_int(self.size)?;
@@ -682,16 +1144,34 @@ impl<'a> fmt::Debug for MapChange<'a> {
 
 impl<'a> MapData<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapData<'a>, Error> {
-        let result = Ok(MapData {
-            last: _p.read_int(warn)?,
-            crc: _p.read_int(warn)?,
-            chunk: _p.read_int(warn)?,
-            data: _p.read_data(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        MapData::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapData<'a>, Error> {
+        let result = MapData::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "MapData"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapData<'a>, Error> {
+        Ok(MapData {
+            last: _p.read_int(&mut _p.locate(warn, "MapData.last"))?,
+            crc: _p.read_int(&mut _p.locate(warn, "MapData.crc"))?,
+            chunk: _p.read_int(&mut _p.locate(warn, "MapData.chunk"))?,
+            data: _p.read_data(&mut _p.locate(warn, "MapData.data"))?,
+        })
+    }
+    pub fn new(last: i32, crc: i32, chunk: i32, data: &'a [u8]) -> Result<MapData<'a>, Error> {
+        let result = MapData {
+            last,
+            crc,
+            chunk,
+            data,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.last)?;
         _p.write_int(self.crc)?;
         _p.write_int(self.chunk)?;
@@ -712,11 +1192,15 @@ impl<'a> fmt::Debug for MapData<'a> {
 
 impl ConReady {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ConReady, Error> {
-        let result = Ok(ConReady);
-        _p.finish(warn);
-        result
+        ConReady::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ConReady, Error> {
+        error::finish(&mut _p.locate(warn, "ConReady"), _p, Ok(ConReady))
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -729,18 +1213,38 @@ impl fmt::Debug for ConReady {
 
 impl<'a> Snap<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Snap<'a>, Error> {
-        let result = Ok(Snap {
-            tick: _p.read_int(warn)?,
-            delta_tick: _p.read_int(warn)?,
-            num_parts: _p.read_int(warn)?,
-            part: _p.read_int(warn)?,
-            crc: _p.read_int(warn)?,
-            data: _p.read_data(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        Snap::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Snap<'a>, Error> {
+        let result = Snap::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Snap"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Snap<'a>, Error> {
+        Ok(Snap {
+            tick: _p.read_int(&mut _p.locate(warn, "Snap.tick"))?,
+            delta_tick: _p.read_int(&mut _p.locate(warn, "Snap.delta_tick"))?,
+            num_parts: _p.read_int(&mut _p.locate(warn, "Snap.num_parts"))?,
+            part: _p.read_int(&mut _p.locate(warn, "Snap.part"))?,
+            crc: _p.read_int(&mut _p.locate(warn, "Snap.crc"))?,
+            data: _p.read_data(&mut _p.locate(warn, "Snap.data"))?,
+        })
+    }
+    pub fn new(tick: i32, delta_tick: i32, num_parts: i32, part: i32, crc: i32, data: &'a [u8]) -> Result<Snap<'a>, Error> {
+        let result = Snap {
+            tick,
+            delta_tick,
+            num_parts,
+            part,
+            crc,
+            data,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.tick)?;
         _p.write_int(self.delta_tick)?;
         _p.write_int(self.num_parts)?;
@@ -765,14 +1269,30 @@ impl<'a> fmt::Debug for Snap<'a> {
 
 impl SnapEmpty {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SnapEmpty, Error> {
-        let result = Ok(SnapEmpty {
-            tick: _p.read_int(warn)?,
-            delta_tick: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        SnapEmpty::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SnapEmpty, Error> {
+        let result = SnapEmpty::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SnapEmpty"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SnapEmpty, Error> {
+        Ok(SnapEmpty {
+            tick: _p.read_int(&mut _p.locate(warn, "SnapEmpty.tick"))?,
+            delta_tick: _p.read_int(&mut _p.locate(warn, "SnapEmpty.delta_tick"))?,
+        })
+    }
+    pub fn new(tick: i32, delta_tick: i32) -> Result<SnapEmpty, Error> {
+        let result = SnapEmpty {
+            tick,
+            delta_tick,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.tick)?;
         _p.write_int(self.delta_tick)?;
         Ok(_p.written())
@@ -789,16 +1309,34 @@ impl fmt::Debug for SnapEmpty {
 
 impl<'a> SnapSingle<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SnapSingle<'a>, Error> {
-        let result = Ok(SnapSingle {
-            tick: _p.read_int(warn)?,
-            delta_tick: _p.read_int(warn)?,
-            crc: _p.read_int(warn)?,
-            data: _p.read_data(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        SnapSingle::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SnapSingle<'a>, Error> {
+        let result = SnapSingle::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SnapSingle"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SnapSingle<'a>, Error> {
+        Ok(SnapSingle {
+            tick: _p.read_int(&mut _p.locate(warn, "SnapSingle.tick"))?,
+            delta_tick: _p.read_int(&mut _p.locate(warn, "SnapSingle.delta_tick"))?,
+            crc: _p.read_int(&mut _p.locate(warn, "SnapSingle.crc"))?,
+            data: _p.read_data(&mut _p.locate(warn, "SnapSingle.data"))?,
+        })
+    }
+    pub fn new(tick: i32, delta_tick: i32, crc: i32, data: &'a [u8]) -> Result<SnapSingle<'a>, Error> {
+        let result = SnapSingle {
+            tick,
+            delta_tick,
+            crc,
+            data,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.tick)?;
         _p.write_int(self.delta_tick)?;
         _p.write_int(self.crc)?;
@@ -819,14 +1357,30 @@ impl<'a> fmt::Debug for SnapSingle<'a> {
 
 impl InputTiming {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<InputTiming, Error> {
-        let result = Ok(InputTiming {
-            input_pred_tick: _p.read_int(warn)?,
-            time_left: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        InputTiming::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<InputTiming, Error> {
+        let result = InputTiming::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "InputTiming"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<InputTiming, Error> {
+        Ok(InputTiming {
+            input_pred_tick: _p.read_int(&mut _p.locate(warn, "InputTiming.input_pred_tick"))?,
+            time_left: _p.read_int(&mut _p.locate(warn, "InputTiming.time_left"))?,
+        })
+    }
+    pub fn new(input_pred_tick: i32, time_left: i32) -> Result<InputTiming, Error> {
+        let result = InputTiming {
+            input_pred_tick,
+            time_left,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.input_pred_tick)?;
         _p.write_int(self.time_left)?;
         Ok(_p.written())
@@ -843,16 +1397,34 @@ impl fmt::Debug for InputTiming {
 
 impl RconAuthStatus {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RconAuthStatus, Error> {
-        let result = Ok(RconAuthStatus {
-            auth_level: _p.read_int(warn).ok(),
-            receive_commands: _p.read_int(warn).ok(),
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(self.auth_level.is_some());
-        assert!(self.receive_commands.is_some());
+        RconAuthStatus::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RconAuthStatus, Error> {
+        let result = RconAuthStatus::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconAuthStatus"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RconAuthStatus, Error> {
+        Ok(RconAuthStatus {
+            auth_level: _p.read_int(&mut _p.locate(warn, "RconAuthStatus.auth_level")).ok(),
+            receive_commands: _p.read_int(&mut _p.locate(warn, "RconAuthStatus.receive_commands")).ok(),
+        })
+    }
+    pub fn new(auth_level: Option<i32>, receive_commands: Option<i32>) -> Result<RconAuthStatus, Error> {
+        let result = RconAuthStatus {
+            auth_level,
+            receive_commands,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.auth_level.ok_or(Error::MissingValue)?;
+        self.receive_commands.ok_or(Error::MissingValue)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        self.auth_level.ok_or(Error::MissingValue)?;
+        self.receive_commands.ok_or(Error::MissingValue)?;
         _p.write_int(self.auth_level.unwrap())?;
         _p.write_int(self.receive_commands.unwrap())?;
         Ok(_p.written())
@@ -869,13 +1441,28 @@ impl fmt::Debug for RconAuthStatus {
 
 impl<'a> RconLine<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconLine<'a>, Error> {
-        let result = Ok(RconLine {
+        RconLine::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconLine<'a>, Error> {
+        let result = RconLine::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconLine"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconLine<'a>, Error> {
+        Ok(RconLine {
             line: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(line: &'a [u8]) -> Result<RconLine<'a>, Error> {
+        let result = RconLine {
+            line,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.line)?;
         Ok(_p.written())
     }
@@ -890,11 +1477,15 @@ impl<'a> fmt::Debug for RconLine<'a> {
 
 impl Ready {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Ready, Error> {
-        let result = Ok(Ready);
-        _p.finish(warn);
-        result
+        Ready::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Ready, Error> {
+        error::finish(&mut _p.locate(warn, "Ready"), _p, Ok(Ready))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -907,11 +1498,15 @@ impl fmt::Debug for Ready {
 
 impl EnterGame {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<EnterGame, Error> {
-        let result = Ok(EnterGame);
-        _p.finish(warn);
-        result
+        EnterGame::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<EnterGame, Error> {
+        error::finish(&mut _p.locate(warn, "EnterGame"), _p, Ok(EnterGame))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -924,16 +1519,34 @@ impl fmt::Debug for EnterGame {
 
 impl Input {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Input, Error> {
-        let result = Ok(Input {
-            ack_snapshot: _p.read_int(warn)?,
-            intended_tick: _p.read_int(warn)?,
-            input_size: _p.read_int(warn)?,
-            input: ::snap_obj::PlayerInput::decode_msg(warn, _p)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        Input::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Input, Error> {
+        let result = Input::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Input"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Input, Error> {
+        Ok(Input {
+            ack_snapshot: _p.read_int(&mut _p.locate(warn, "Input.ack_snapshot"))?,
+            intended_tick: _p.read_int(&mut _p.locate(warn, "Input.intended_tick"))?,
+            input_size: _p.read_int(&mut _p.locate(warn, "Input.input_size"))?,
+            input: ::snap_obj::PlayerInput::decode_msg_located(warn, _p)?,
+        })
+    }
+    pub fn new(ack_snapshot: i32, intended_tick: i32, input_size: i32, input: ::snap_obj::PlayerInput) -> Result<Input, Error> {
+        let result = Input {
+            ack_snapshot,
+            intended_tick,
+            input_size,
+            input,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.ack_snapshot)?;
         _p.write_int(self.intended_tick)?;
         _p.write_int(self.input_size)?;
@@ -954,13 +1567,28 @@ impl fmt::Debug for Input {
 
 impl<'a> RconCmd<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmd<'a>, Error> {
-        let result = Ok(RconCmd {
+        RconCmd::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmd<'a>, Error> {
+        let result = RconCmd::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconCmd"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmd<'a>, Error> {
+        Ok(RconCmd {
             cmd: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(cmd: &'a [u8]) -> Result<RconCmd<'a>, Error> {
+        let result = RconCmd {
+            cmd,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.cmd)?;
         Ok(_p.written())
     }
@@ -975,16 +1603,34 @@ impl<'a> fmt::Debug for RconCmd<'a> {
 
 impl<'a> RconAuth<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconAuth<'a>, Error> {
-        let result = Ok(RconAuth {
+        RconAuth::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconAuth<'a>, Error> {
+        let result = RconAuth::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconAuth"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconAuth<'a>, Error> {
+        Ok(RconAuth {
             _unused: _p.read_string()?,
             password: _p.read_string()?,
-            request_commands: _p.read_int(warn).ok(),
-        });
-        _p.finish(warn);
-        result
+            request_commands: _p.read_int(&mut _p.locate(warn, "RconAuth.request_commands")).ok(),
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(self.request_commands.is_some());
+    pub fn new(_unused: &'a [u8], password: &'a [u8], request_commands: Option<i32>) -> Result<RconAuth<'a>, Error> {
+        let result = RconAuth {
+            _unused,
+            password,
+            request_commands,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.request_commands.ok_or(Error::MissingValue)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        self.request_commands.ok_or(Error::MissingValue)?;
         _p.write_string(self._unused)?;
         _p.write_string(self.password)?;
         _p.write_int(self.request_commands.unwrap())?;
@@ -1003,13 +1649,28 @@ impl<'a> fmt::Debug for RconAuth<'a> {
 
 impl RequestMapData {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestMapData, Error> {
-        let result = Ok(RequestMapData {
-            chunk: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
+        RequestMapData::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestMapData, Error> {
+        let result = RequestMapData::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RequestMapData"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestMapData, Error> {
+        Ok(RequestMapData {
+            chunk: _p.read_int(&mut _p.locate(warn, "RequestMapData.chunk"))?,
+        })
+    }
+    pub fn new(chunk: i32) -> Result<RequestMapData, Error> {
+        let result = RequestMapData {
+            chunk,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.chunk)?;
         Ok(_p.written())
     }
@@ -1024,11 +1685,15 @@ impl fmt::Debug for RequestMapData {
 
 impl Ping {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Ping, Error> {
-        let result = Ok(Ping);
-        _p.finish(warn);
-        result
+        Ping::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Ping, Error> {
+        error::finish(&mut _p.locate(warn, "Ping"), _p, Ok(Ping))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -1041,11 +1706,15 @@ impl fmt::Debug for Ping {
 
 impl PingReply {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<PingReply, Error> {
-        let result = Ok(PingReply);
-        _p.finish(warn);
-        result
+        PingReply::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<PingReply, Error> {
+        error::finish(&mut _p.locate(warn, "PingReply"), _p, Ok(PingReply))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -1058,15 +1727,32 @@ impl fmt::Debug for PingReply {
 
 impl<'a> RconCmdAdd<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmdAdd<'a>, Error> {
-        let result = Ok(RconCmdAdd {
+        RconCmdAdd::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmdAdd<'a>, Error> {
+        let result = RconCmdAdd::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconCmdAdd"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmdAdd<'a>, Error> {
+        Ok(RconCmdAdd {
             name: _p.read_string()?,
             help: _p.read_string()?,
             params: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(name: &'a [u8], help: &'a [u8], params: &'a [u8]) -> Result<RconCmdAdd<'a>, Error> {
+        let result = RconCmdAdd {
+            name,
+            help,
+            params,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.name)?;
         _p.write_string(self.help)?;
         _p.write_string(self.params)?;
@@ -1085,13 +1771,28 @@ impl<'a> fmt::Debug for RconCmdAdd<'a> {
 
 impl<'a> RconCmdRemove<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmdRemove<'a>, Error> {
-        let result = Ok(RconCmdRemove {
+        RconCmdRemove::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmdRemove<'a>, Error> {
+        let result = RconCmdRemove::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconCmdRemove"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmdRemove<'a>, Error> {
+        Ok(RconCmdRemove {
             name: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(name: &'a [u8]) -> Result<RconCmdRemove<'a>, Error> {
+        let result = RconCmdRemove {
+            name,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.name)?;
         Ok(_p.written())
     }
@@ -1106,13 +1807,28 @@ impl<'a> fmt::Debug for RconCmdRemove<'a> {
 
 impl WhatIs {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<WhatIs, Error> {
-        let result = Ok(WhatIs {
+        WhatIs::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<WhatIs, Error> {
+        let result = WhatIs::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "WhatIs"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker) -> Result<WhatIs, Error> {
+        Ok(WhatIs {
             uuid: Uuid::from_slice(_p.read_raw(16)?).unwrap(),
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(uuid: Uuid) -> Result<WhatIs, Error> {
+        let result = WhatIs {
+            uuid,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_raw(self.uuid.as_bytes())?;
         Ok(_p.written())
     }
@@ -1127,14 +1843,30 @@ impl fmt::Debug for WhatIs {
 
 impl<'a> ItIs<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ItIs<'a>, Error> {
-        let result = Ok(ItIs {
+        ItIs::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ItIs<'a>, Error> {
+        let result = ItIs::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ItIs"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ItIs<'a>, Error> {
+        Ok(ItIs {
             uuid: Uuid::from_slice(_p.read_raw(16)?).unwrap(),
             name: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(uuid: Uuid, name: &'a [u8]) -> Result<ItIs<'a>, Error> {
+        let result = ItIs {
+            uuid,
+            name,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_raw(self.uuid.as_bytes())?;
         _p.write_string(self.name)?;
         Ok(_p.written())
@@ -1151,13 +1883,28 @@ impl<'a> fmt::Debug for ItIs<'a> {
 
 impl IDontKnow {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<IDontKnow, Error> {
-        let result = Ok(IDontKnow {
+        IDontKnow::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<IDontKnow, Error> {
+        let result = IDontKnow::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "IDontKnow"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker) -> Result<IDontKnow, Error> {
+        Ok(IDontKnow {
             uuid: Uuid::from_slice(_p.read_raw(16)?).unwrap(),
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(uuid: Uuid) -> Result<IDontKnow, Error> {
+        let result = IDontKnow {
+            uuid,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_raw(self.uuid.as_bytes())?;
         Ok(_p.written())
     }
@@ -1172,13 +1919,28 @@ impl fmt::Debug for IDontKnow {
 
 impl RconType {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RconType, Error> {
-        let result = Ok(RconType {
-            username_required: to_bool(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
+        RconType::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RconType, Error> {
+        let result = RconType::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconType"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RconType, Error> {
+        Ok(RconType {
+            username_required: to_bool(_p.read_int(&mut _p.locate(warn, "RconType.username_required"))?)?,
+        })
+    }
+    pub fn new(username_required: bool) -> Result<RconType, Error> {
+        let result = RconType {
+            username_required,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.username_required as i32)?;
         Ok(_p.written())
     }
@@ -1193,15 +1955,32 @@ impl fmt::Debug for RconType {
 
 impl<'a> MapDetails<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapDetails<'a>, Error> {
-        let result = Ok(MapDetails {
+        MapDetails::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapDetails<'a>, Error> {
+        let result = MapDetails::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "MapDetails"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapDetails<'a>, Error> {
+        Ok(MapDetails {
             name: _p.read_string()?,
             sha256: Sha256::from_slice(_p.read_raw(32)?).unwrap(),
-            crc: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
+            crc: _p.read_int(&mut _p.locate(warn, "MapDetails.crc"))?,
+        })
+    }
+    pub fn new(name: &'a [u8], sha256: Sha256, crc: i32) -> Result<MapDetails<'a>, Error> {
+        let result = MapDetails {
+            name,
+            sha256,
+            crc,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.name)?;
         _p.write_raw(&self.sha256.0)?;
         _p.write_int(self.crc)?;
@@ -1220,14 +1999,30 @@ impl<'a> fmt::Debug for MapDetails<'a> {
 
 impl Capabilities {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Capabilities, Error> {
-        let result = Ok(Capabilities {
-            version: _p.read_int(warn)?,
-            flags: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        Capabilities::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Capabilities, Error> {
+        let result = Capabilities::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Capabilities"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Capabilities, Error> {
+        Ok(Capabilities {
+            version: _p.read_int(&mut _p.locate(warn, "Capabilities.version"))?,
+            flags: _p.read_int(&mut _p.locate(warn, "Capabilities.flags"))?,
+        })
+    }
+    pub fn new(version: i32, flags: i32) -> Result<Capabilities, Error> {
+        let result = Capabilities {
+            version,
+            flags,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.version)?;
         _p.write_int(self.flags)?;
         Ok(_p.written())
@@ -1244,15 +2039,32 @@ impl fmt::Debug for Capabilities {
 
 impl<'a> ClientVersion<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClientVersion<'a>, Error> {
-        let result = Ok(ClientVersion {
+        ClientVersion::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClientVersion<'a>, Error> {
+        let result = ClientVersion::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClientVersion"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClientVersion<'a>, Error> {
+        Ok(ClientVersion {
             connection_id: Uuid::from_slice(_p.read_raw(16)?).unwrap(),
-            ddnet_version: _p.read_int(warn)?,
+            ddnet_version: _p.read_int(&mut _p.locate(warn, "ClientVersion.ddnet_version"))?,
             ddnet_version_string: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(connection_id: Uuid, ddnet_version: i32, ddnet_version_string: &'a [u8]) -> Result<ClientVersion<'a>, Error> {
+        let result = ClientVersion {
+            connection_id,
+            ddnet_version,
+            ddnet_version_string,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_raw(self.connection_id.as_bytes())?;
         _p.write_int(self.ddnet_version)?;
         _p.write_string(self.ddnet_version_string)?;
@@ -1271,13 +2083,28 @@ impl<'a> fmt::Debug for ClientVersion<'a> {
 
 impl PingEx {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<PingEx, Error> {
-        let result = Ok(PingEx {
+        PingEx::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<PingEx, Error> {
+        let result = PingEx::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "PingEx"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker) -> Result<PingEx, Error> {
+        Ok(PingEx {
             id: Uuid::from_slice(_p.read_raw(16)?).unwrap(),
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(id: Uuid) -> Result<PingEx, Error> {
+        let result = PingEx {
+            id,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_raw(self.id.as_bytes())?;
         Ok(_p.written())
     }
@@ -1292,13 +2119,28 @@ impl fmt::Debug for PingEx {
 
 impl PongEx {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<PongEx, Error> {
-        let result = Ok(PongEx {
+        PongEx::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<PongEx, Error> {
+        let result = PongEx::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "PongEx"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker) -> Result<PongEx, Error> {
+        Ok(PongEx {
             id: Uuid::from_slice(_p.read_raw(16)?).unwrap(),
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(id: Uuid) -> Result<PongEx, Error> {
+        let result = PongEx {
+            id,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_raw(self.id.as_bytes())?;
         Ok(_p.written())
     }
@@ -1313,15 +2155,32 @@ impl fmt::Debug for PongEx {
 
 impl ChecksumRequest {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ChecksumRequest, Error> {
-        let result = Ok(ChecksumRequest {
+        ChecksumRequest::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ChecksumRequest, Error> {
+        let result = ChecksumRequest::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ChecksumRequest"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ChecksumRequest, Error> {
+        Ok(ChecksumRequest {
             id: Uuid::from_slice(_p.read_raw(16)?).unwrap(),
-            start: _p.read_int(warn)?,
-            length: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
+            start: _p.read_int(&mut _p.locate(warn, "ChecksumRequest.start"))?,
+            length: _p.read_int(&mut _p.locate(warn, "ChecksumRequest.length"))?,
+        })
+    }
+    pub fn new(id: Uuid, start: i32, length: i32) -> Result<ChecksumRequest, Error> {
+        let result = ChecksumRequest {
+            id,
+            start,
+            length,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_raw(self.id.as_bytes())?;
         _p.write_int(self.start)?;
         _p.write_int(self.length)?;
@@ -1340,14 +2199,30 @@ impl fmt::Debug for ChecksumRequest {
 
 impl ChecksumResponse {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ChecksumResponse, Error> {
-        let result = Ok(ChecksumResponse {
+        ChecksumResponse::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ChecksumResponse, Error> {
+        let result = ChecksumResponse::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ChecksumResponse"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker) -> Result<ChecksumResponse, Error> {
+        Ok(ChecksumResponse {
             id: Uuid::from_slice(_p.read_raw(16)?).unwrap(),
             sha256: Sha256::from_slice(_p.read_raw(32)?).unwrap(),
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(id: Uuid, sha256: Sha256) -> Result<ChecksumResponse, Error> {
+        let result = ChecksumResponse {
+            id,
+            sha256,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_raw(self.id.as_bytes())?;
         _p.write_raw(&self.sha256.0)?;
         Ok(_p.written())
@@ -1364,14 +2239,30 @@ impl fmt::Debug for ChecksumResponse {
 
 impl ChecksumError {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ChecksumError, Error> {
-        let result = Ok(ChecksumError {
+        ChecksumError::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ChecksumError, Error> {
+        let result = ChecksumError::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ChecksumError"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ChecksumError, Error> {
+        Ok(ChecksumError {
             id: Uuid::from_slice(_p.read_raw(16)?).unwrap(),
-            error: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
+            error: _p.read_int(&mut _p.locate(warn, "ChecksumError.error"))?,
+        })
+    }
+    pub fn new(id: Uuid, error: i32) -> Result<ChecksumError, Error> {
+        let result = ChecksumError {
+            id,
+            error,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_raw(self.id.as_bytes())?;
         _p.write_int(self.error)?;
         Ok(_p.written())
@@ -1386,3 +2277,428 @@ impl fmt::Debug for ChecksumError {
     }
 }
 
+impl Redirect {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Redirect, Error> {
+        Redirect::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Redirect, Error> {
+        let result = Redirect::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Redirect"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Redirect, Error> {
+        Ok(Redirect {
+            port: _p.read_int(&mut _p.locate(warn, "Redirect.port"))?,
+        })
+    }
+    pub fn new(port: i32) -> Result<Redirect, Error> {
+        let result = Redirect {
+            port,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        _p.write_int(self.port)?;
+        Ok(_p.written())
+    }
+}
+impl fmt::Debug for Redirect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Redirect")
+            .field("port", &self.port)
+            .finish()
+    }
+}
+
+impl RconCmdGroupStart {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RconCmdGroupStart, Error> {
+        RconCmdGroupStart::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RconCmdGroupStart, Error> {
+        let result = RconCmdGroupStart::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconCmdGroupStart"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RconCmdGroupStart, Error> {
+        Ok(RconCmdGroupStart {
+            length: _p.read_int(&mut _p.locate(warn, "RconCmdGroupStart.length"))?,
+        })
+    }
+    pub fn new(length: i32) -> Result<RconCmdGroupStart, Error> {
+        let result = RconCmdGroupStart {
+            length,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        _p.write_int(self.length)?;
+        Ok(_p.written())
+    }
+}
+impl fmt::Debug for RconCmdGroupStart {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RconCmdGroupStart")
+            .field("length", &self.length)
+            .finish()
+    }
+}
+
+impl RconCmdGroupEnd {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RconCmdGroupEnd, Error> {
+        RconCmdGroupEnd::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RconCmdGroupEnd, Error> {
+        error::finish(&mut _p.locate(warn, "RconCmdGroupEnd"), _p, Ok(RconCmdGroupEnd))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        Ok(_p.written())
+    }
+}
+impl fmt::Debug for RconCmdGroupEnd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RconCmdGroupEnd")
+            .finish()
+    }
+}
+
+impl<'a> Info<'a> {
+    pub fn to_owned(&self) -> InfoOwned {
+        InfoOwned {
+            version: self.version.to_vec(),
+            password: self.password.map(|v| v.to_vec()),
+        }
+    }
+}
+
+impl InfoOwned {
+    pub fn as_ref(&self) -> Info<'_> {
+        Info {
+            version: &self.version[..],
+            password: self.password.as_ref().map(|v| &v[..]),
+        }
+    }
+}
+
+impl fmt::Debug for InfoOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> MapChange<'a> {
+    pub fn to_owned(&self) -> MapChangeOwned {
+        MapChangeOwned {
+            name: self.name.to_vec(),
+            crc: self.crc,
+            size: self.size,
+        }
+    }
+}
+
+impl MapChangeOwned {
+    pub fn as_ref(&self) -> MapChange<'_> {
+        MapChange {
+            name: &self.name[..],
+            crc: self.crc,
+            size: self.size,
+        }
+    }
+}
+
+impl fmt::Debug for MapChangeOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> MapData<'a> {
+    pub fn to_owned(&self) -> MapDataOwned {
+        MapDataOwned {
+            last: self.last,
+            crc: self.crc,
+            chunk: self.chunk,
+            data: self.data.to_vec(),
+        }
+    }
+}
+
+impl MapDataOwned {
+    pub fn as_ref(&self) -> MapData<'_> {
+        MapData {
+            last: self.last,
+            crc: self.crc,
+            chunk: self.chunk,
+            data: &self.data[..],
+        }
+    }
+}
+
+impl fmt::Debug for MapDataOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> Snap<'a> {
+    pub fn to_owned(&self) -> SnapOwned {
+        SnapOwned {
+            tick: self.tick,
+            delta_tick: self.delta_tick,
+            num_parts: self.num_parts,
+            part: self.part,
+            crc: self.crc,
+            data: self.data.to_vec(),
+        }
+    }
+}
+
+impl SnapOwned {
+    pub fn as_ref(&self) -> Snap<'_> {
+        Snap {
+            tick: self.tick,
+            delta_tick: self.delta_tick,
+            num_parts: self.num_parts,
+            part: self.part,
+            crc: self.crc,
+            data: &self.data[..],
+        }
+    }
+}
+
+impl fmt::Debug for SnapOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SnapSingle<'a> {
+    pub fn to_owned(&self) -> SnapSingleOwned {
+        SnapSingleOwned {
+            tick: self.tick,
+            delta_tick: self.delta_tick,
+            crc: self.crc,
+            data: self.data.to_vec(),
+        }
+    }
+}
+
+impl SnapSingleOwned {
+    pub fn as_ref(&self) -> SnapSingle<'_> {
+        SnapSingle {
+            tick: self.tick,
+            delta_tick: self.delta_tick,
+            crc: self.crc,
+            data: &self.data[..],
+        }
+    }
+}
+
+impl fmt::Debug for SnapSingleOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> RconLine<'a> {
+    pub fn to_owned(&self) -> RconLineOwned {
+        RconLineOwned {
+            line: self.line.to_vec(),
+        }
+    }
+}
+
+impl RconLineOwned {
+    pub fn as_ref(&self) -> RconLine<'_> {
+        RconLine {
+            line: &self.line[..],
+        }
+    }
+}
+
+impl fmt::Debug for RconLineOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> RconCmd<'a> {
+    pub fn to_owned(&self) -> RconCmdOwned {
+        RconCmdOwned {
+            cmd: self.cmd.to_vec(),
+        }
+    }
+}
+
+impl RconCmdOwned {
+    pub fn as_ref(&self) -> RconCmd<'_> {
+        RconCmd {
+            cmd: &self.cmd[..],
+        }
+    }
+}
+
+impl fmt::Debug for RconCmdOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> RconAuth<'a> {
+    pub fn to_owned(&self) -> RconAuthOwned {
+        RconAuthOwned {
+            _unused: self._unused.to_vec(),
+            password: self.password.to_vec(),
+            request_commands: self.request_commands,
+        }
+    }
+}
+
+impl RconAuthOwned {
+    pub fn as_ref(&self) -> RconAuth<'_> {
+        RconAuth {
+            _unused: &self._unused[..],
+            password: &self.password[..],
+            request_commands: self.request_commands,
+        }
+    }
+}
+
+impl fmt::Debug for RconAuthOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> RconCmdAdd<'a> {
+    pub fn to_owned(&self) -> RconCmdAddOwned {
+        RconCmdAddOwned {
+            name: self.name.to_vec(),
+            help: self.help.to_vec(),
+            params: self.params.to_vec(),
+        }
+    }
+}
+
+impl RconCmdAddOwned {
+    pub fn as_ref(&self) -> RconCmdAdd<'_> {
+        RconCmdAdd {
+            name: &self.name[..],
+            help: &self.help[..],
+            params: &self.params[..],
+        }
+    }
+}
+
+impl fmt::Debug for RconCmdAddOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> RconCmdRemove<'a> {
+    pub fn to_owned(&self) -> RconCmdRemoveOwned {
+        RconCmdRemoveOwned {
+            name: self.name.to_vec(),
+        }
+    }
+}
+
+impl RconCmdRemoveOwned {
+    pub fn as_ref(&self) -> RconCmdRemove<'_> {
+        RconCmdRemove {
+            name: &self.name[..],
+        }
+    }
+}
+
+impl fmt::Debug for RconCmdRemoveOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> ItIs<'a> {
+    pub fn to_owned(&self) -> ItIsOwned {
+        ItIsOwned {
+            uuid: self.uuid,
+            name: self.name.to_vec(),
+        }
+    }
+}
+
+impl ItIsOwned {
+    pub fn as_ref(&self) -> ItIs<'_> {
+        ItIs {
+            uuid: self.uuid,
+            name: &self.name[..],
+        }
+    }
+}
+
+impl fmt::Debug for ItIsOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> MapDetails<'a> {
+    pub fn to_owned(&self) -> MapDetailsOwned {
+        MapDetailsOwned {
+            name: self.name.to_vec(),
+            sha256: self.sha256,
+            crc: self.crc,
+        }
+    }
+}
+
+impl MapDetailsOwned {
+    pub fn as_ref(&self) -> MapDetails<'_> {
+        MapDetails {
+            name: &self.name[..],
+            sha256: self.sha256,
+            crc: self.crc,
+        }
+    }
+}
+
+impl fmt::Debug for MapDetailsOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> ClientVersion<'a> {
+    pub fn to_owned(&self) -> ClientVersionOwned {
+        ClientVersionOwned {
+            connection_id: self.connection_id,
+            ddnet_version: self.ddnet_version,
+            ddnet_version_string: self.ddnet_version_string.to_vec(),
+        }
+    }
+}
+
+impl ClientVersionOwned {
+    pub fn as_ref(&self) -> ClientVersion<'_> {
+        ClientVersion {
+            connection_id: self.connection_id,
+            ddnet_version: self.ddnet_version,
+            ddnet_version_string: &self.ddnet_version_string[..],
+        }
+    }
+}
+
+impl fmt::Debug for ClientVersionOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
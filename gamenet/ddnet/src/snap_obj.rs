@@ -1,7 +1,14 @@
-use buffer::CapacityError;
 use common::slice;
+use common::warn::Located;
+use common::warn::strip_location;
 use enums;
+use error;
+use error::EncodeError;
 use error::Error;
+use gamenet_common::snap_obj::FieldKind;
+use gamenet_common::snap_obj::FieldMeta;
+use gamenet_common::snap_obj::ObjMeta;
+use gamenet_common::snap_obj::wrap_angle;
 use packer::ExcessData;
 use packer::IntUnpacker;
 use packer::Packer;
@@ -155,7 +162,26 @@ pub const SPEC_CHAR: Uuid = Uuid::from_u128(0x4b801c74_e24c_3ce0_b92c_b754d02cfc
 pub const SWITCH_STATE: Uuid = Uuid::from_u128(0xec15e669_ce11_3367_ae8e_b90e5b27b9d5);
 pub const ENTITY_EX: Uuid = Uuid::from_u128(0x2de9aec3_32e4_3986_8f7e_e7459da7f535);
 
+pub fn uuid_name(uuid: Uuid) -> Option<&'static str> {
+    Some(match uuid {
+        MY_OWN_OBJECT => "my-own-object@heinrich5991.de",
+        DDNET_CHARACTER => "character@netobj.ddnet.tw",
+        DDNET_PLAYER => "player@netobj.ddnet.tw",
+        GAME_INFO_EX => "gameinfo@netobj.ddnet.tw",
+        DDRACE_PROJECTILE => "projectile@netobj.ddnet.tw",
+        DDNET_LASER => "laser@netobj.ddnet.tw",
+        DDNET_PROJECTILE => "ddnet-projectile@netobj.ddnet.tw",
+        DDNET_PICKUP => "pickup@netobj.ddnet.tw",
+        MY_OWN_EVENT => "my-own-event@heinrich5991.de",
+        SPEC_CHAR => "spec-char@netobj.ddnet.tw",
+        SWITCH_STATE => "switch-state@netobj.ddnet.tw",
+        ENTITY_EX => "entity-ex@netobj.ddnet.tw",
+        _ => return None,
+    })
+}
+
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SnapObj {
     PlayerInput(PlayerInput),
     Projectile(Projectile),
@@ -266,7 +292,43 @@ impl SnapObj {
             SnapObj::EntityEx(_) => TypeId::from(ENTITY_EX),
         }
     }
-    pub fn encode(&self) -> &[i32] {
+    pub fn validate(&self) -> Result<(), Error> {
+        match *self {
+            SnapObj::PlayerInput(ref i) => i.validate(),
+            SnapObj::Projectile(ref i) => i.validate(),
+            SnapObj::Laser(ref i) => i.validate(),
+            SnapObj::Pickup(ref i) => i.validate(),
+            SnapObj::Flag(ref i) => i.validate(),
+            SnapObj::GameInfo(ref i) => i.validate(),
+            SnapObj::GameData(ref i) => i.validate(),
+            SnapObj::CharacterCore(ref i) => i.validate(),
+            SnapObj::Character(ref i) => i.validate(),
+            SnapObj::PlayerInfo(ref i) => i.validate(),
+            SnapObj::ClientInfo(ref i) => i.validate(),
+            SnapObj::SpectatorInfo(ref i) => i.validate(),
+            SnapObj::MyOwnObject(ref i) => i.validate(),
+            SnapObj::DdnetCharacter(ref i) => i.validate(),
+            SnapObj::DdnetPlayer(ref i) => i.validate(),
+            SnapObj::GameInfoEx(ref i) => i.validate(),
+            SnapObj::DdraceProjectile(ref i) => i.validate(),
+            SnapObj::DdnetLaser(ref i) => i.validate(),
+            SnapObj::DdnetProjectile(ref i) => i.validate(),
+            SnapObj::DdnetPickup(ref i) => i.validate(),
+            SnapObj::Common(ref i) => i.validate(),
+            SnapObj::Explosion(ref i) => i.validate(),
+            SnapObj::Spawn(ref i) => i.validate(),
+            SnapObj::HammerHit(ref i) => i.validate(),
+            SnapObj::Death(ref i) => i.validate(),
+            SnapObj::SoundGlobal(ref i) => i.validate(),
+            SnapObj::SoundWorld(ref i) => i.validate(),
+            SnapObj::DamageInd(ref i) => i.validate(),
+            SnapObj::MyOwnEvent(ref i) => i.validate(),
+            SnapObj::SpecChar(ref i) => i.validate(),
+            SnapObj::SwitchState(ref i) => i.validate(),
+            SnapObj::EntityEx(ref i) => i.validate(),
+        }
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
         match *self {
             SnapObj::PlayerInput(ref i) => i.encode(),
             SnapObj::Projectile(ref i) => i.encode(),
@@ -536,7 +598,8 @@ impl From<EntityEx> for SnapObj {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PlayerInput {
     pub direction: i32,
     pub target_x: i32,
@@ -552,17 +615,20 @@ pub struct PlayerInput {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Projectile {
     pub x: i32,
     pub y: i32,
     pub vel_x: i32,
     pub vel_y: i32,
-    pub type_: enums::Weapon,
+    /// Raw `enums::Weapon` value, convert it using `enums::Weapon::from_i32`.
+    pub type_: i32,
     pub start_tick: ::snap_obj::Tick,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Laser {
     pub x: i32,
     pub y: i32,
@@ -573,6 +639,7 @@ pub struct Laser {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Pickup {
     pub x: i32,
     pub y: i32,
@@ -582,6 +649,7 @@ pub struct Pickup {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Flag {
     pub x: i32,
     pub y: i32,
@@ -590,6 +658,7 @@ pub struct Flag {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GameInfo {
     pub game_flags: i32,
     pub game_state_flags: i32,
@@ -603,6 +672,7 @@ pub struct GameInfo {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GameData {
     pub teamscore_red: i32,
     pub teamscore_blue: i32,
@@ -612,6 +682,7 @@ pub struct GameData {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CharacterCore {
     pub tick: i32,
     pub x: i32,
@@ -632,29 +703,35 @@ pub struct CharacterCore {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Character {
     pub character_core: CharacterCore,
     pub player_flags: i32,
     pub health: i32,
     pub armor: i32,
     pub ammo_count: i32,
-    pub weapon: enums::Weapon,
-    pub emote: enums::Emote,
+    /// Raw `enums::Weapon` value, convert it using `enums::Weapon::from_i32`.
+    pub weapon: i32,
+    /// Raw `enums::Emote` value, convert it using `enums::Emote::from_i32`.
+    pub emote: i32,
     pub attack_tick: i32,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PlayerInfo {
     pub local: i32,
     pub client_id: i32,
-    pub team: enums::Team,
+    /// Raw `enums::Team` value, convert it using `enums::Team::from_i32`.
+    pub team: i32,
     pub score: i32,
     pub latency: i32,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClientInfo {
     pub name: [i32; 4],
     pub clan: [i32; 3],
@@ -667,6 +744,7 @@ pub struct ClientInfo {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SpectatorInfo {
     pub spectator_id: i32,
     pub x: i32,
@@ -675,12 +753,14 @@ pub struct SpectatorInfo {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MyOwnObject {
     pub test: i32,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DdnetCharacter {
     pub flags: i32,
     pub freeze_end: ::snap_obj::Tick,
@@ -696,6 +776,7 @@ pub struct DdnetCharacter {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DdnetPlayer {
     pub flags: i32,
     pub auth_level: i32,
@@ -703,6 +784,7 @@ pub struct DdnetPlayer {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GameInfoEx {
     pub flags: i32,
     pub version: i32,
@@ -711,17 +793,20 @@ pub struct GameInfoEx {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DdraceProjectile {
     pub x: i32,
     pub y: i32,
     pub angle: i32,
     pub data: i32,
-    pub type_: enums::Weapon,
+    /// Raw `enums::Weapon` value, convert it using `enums::Weapon::from_i32`.
+    pub type_: i32,
     pub start_tick: ::snap_obj::Tick,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DdnetLaser {
     pub to_x: i32,
     pub to_y: i32,
@@ -736,12 +821,14 @@ pub struct DdnetLaser {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DdnetProjectile {
     pub x: i32,
     pub y: i32,
     pub vel_x: i32,
     pub vel_y: i32,
-    pub type_: enums::Weapon,
+    /// Raw `enums::Weapon` value, convert it using `enums::Weapon::from_i32`.
+    pub type_: i32,
     pub start_tick: ::snap_obj::Tick,
     pub owner: i32,
     pub switch_number: i32,
@@ -751,6 +838,7 @@ pub struct DdnetProjectile {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DdnetPickup {
     pub x: i32,
     pub y: i32,
@@ -761,6 +849,7 @@ pub struct DdnetPickup {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Common {
     pub x: i32,
     pub y: i32,
@@ -768,24 +857,28 @@ pub struct Common {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Explosion {
     pub common: Common,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Spawn {
     pub common: Common,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HammerHit {
     pub common: Common,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Death {
     pub common: Common,
     pub client_id: i32,
@@ -793,20 +886,25 @@ pub struct Death {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SoundGlobal {
     pub common: Common,
-    pub sound_id: enums::Sound,
+    /// Raw `enums::Sound` value, convert it using `enums::Sound::from_i32`.
+    pub sound_id: i32,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SoundWorld {
     pub common: Common,
-    pub sound_id: enums::Sound,
+    /// Raw `enums::Sound` value, convert it using `enums::Sound::from_i32`.
+    pub sound_id: i32,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DamageInd {
     pub common: Common,
     pub angle: i32,
@@ -814,12 +912,14 @@ pub struct DamageInd {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MyOwnEvent {
     pub test: i32,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SpecChar {
     pub x: i32,
     pub y: i32,
@@ -827,6 +927,7 @@ pub struct SpecChar {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SwitchState {
     pub highest_switch_number: i32,
     pub status: [i32; 8],
@@ -836,6 +937,7 @@ pub struct SwitchState {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EntityEx {
     pub switch_number: i32,
     pub layer: i32,
@@ -878,28 +980,53 @@ impl PlayerInput {
             prev_weapon: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(direction: i32, target_x: i32, target_y: i32, jump: i32, fire: i32, hook: i32, player_flags: i32, wanted_weapon: i32, next_weapon: i32, prev_weapon: i32) -> Result<PlayerInput, Error> {
+        let result = PlayerInput {
+            direction,
+            target_x,
+            target_y,
+            jump,
+            fire,
+            hook,
+            player_flags,
+            wanted_weapon,
+            next_weapon,
+            prev_weapon,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 impl PlayerInput {
     pub fn decode_msg<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<PlayerInput, Error> {
-        let result = Ok(PlayerInput {
-            direction: _p.read_int(warn)?,
-            target_x: _p.read_int(warn)?,
-            target_y: _p.read_int(warn)?,
-            jump: _p.read_int(warn)?,
-            fire: _p.read_int(warn)?,
-            hook: _p.read_int(warn)?,
-            player_flags: _p.read_int(warn)?,
-            wanted_weapon: _p.read_int(warn)?,
-            next_weapon: _p.read_int(warn)?,
-            prev_weapon: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode_msg<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        PlayerInput::decode_msg_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_msg_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<PlayerInput, Error> {
+        let result = PlayerInput::decode_msg_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "PlayerInput"), _p, result)
+    }
+    fn decode_msg_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<PlayerInput, Error> {
+        Ok(PlayerInput {
+            direction: _p.read_int(&mut _p.locate(warn, "PlayerInput.direction"))?,
+            target_x: _p.read_int(&mut _p.locate(warn, "PlayerInput.target_x"))?,
+            target_y: _p.read_int(&mut _p.locate(warn, "PlayerInput.target_y"))?,
+            jump: _p.read_int(&mut _p.locate(warn, "PlayerInput.jump"))?,
+            fire: _p.read_int(&mut _p.locate(warn, "PlayerInput.fire"))?,
+            hook: _p.read_int(&mut _p.locate(warn, "PlayerInput.hook"))?,
+            player_flags: _p.read_int(&mut _p.locate(warn, "PlayerInput.player_flags"))?,
+            wanted_weapon: _p.read_int(&mut _p.locate(warn, "PlayerInput.wanted_weapon"))?,
+            next_weapon: _p.read_int(&mut _p.locate(warn, "PlayerInput.next_weapon"))?,
+            prev_weapon: _p.read_int(&mut _p.locate(warn, "PlayerInput.prev_weapon"))?,
+        })
+    }
+    pub fn encode_msg<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.direction)?;
         _p.write_int(self.target_x)?;
         _p.write_int(self.target_y)?;
@@ -921,7 +1048,7 @@ impl fmt::Debug for Projectile {
             .field("y", &self.y)
             .field("vel_x", &self.vel_x)
             .field("vel_y", &self.vel_y)
-            .field("type_", &self.type_)
+            .field("type_", &enums::Weapon::from_i32(self.type_))
             .field("start_tick", &self.start_tick)
             .finish()
     }
@@ -938,12 +1065,28 @@ impl Projectile {
             y: _p.read_int()?,
             vel_x: _p.read_int()?,
             vel_y: _p.read_int()?,
-            type_: enums::Weapon::from_i32(_p.read_int()?)?,
+            type_: _p.read_int()?,
             start_tick: ::snap_obj::Tick(_p.read_int()?),
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32, vel_x: i32, vel_y: i32, type_: i32, start_tick: ::snap_obj::Tick) -> Result<Projectile, Error> {
+        let result = Projectile {
+            x,
+            y,
+            vel_x,
+            vel_y,
+            type_,
+            start_tick,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        enums::Weapon::from_i32(self.type_).known()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -973,8 +1116,22 @@ impl Laser {
             start_tick: ::snap_obj::Tick(_p.read_int()?),
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32, from_x: i32, from_y: i32, start_tick: ::snap_obj::Tick) -> Result<Laser, Error> {
+        let result = Laser {
+            x,
+            y,
+            from_x,
+            from_y,
+            start_tick,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1002,10 +1159,25 @@ impl Pickup {
             subtype: positive(_p.read_int()?)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(self.type_ >= 0);
-        assert!(self.subtype >= 0);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32, type_: i32, subtype: i32) -> Result<Pickup, Error> {
+        let result = Pickup {
+            x,
+            y,
+            type_,
+            subtype,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        positive(self.type_)?;
+        positive(self.subtype)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        positive(self.type_)?;
+        positive(self.subtype)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1031,9 +1203,22 @@ impl Flag {
             team: in_range(_p.read_int()?, 0, 1)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(0 <= self.team && self.team <= 1);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32, team: i32) -> Result<Flag, Error> {
+        let result = Flag {
+            x,
+            y,
+            team,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.team, 0, 1)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.team, 0, 1)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1069,14 +1254,38 @@ impl GameInfo {
             round_current: positive(_p.read_int()?)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(0 <= self.game_flags && self.game_flags <= 256);
-        assert!(0 <= self.game_state_flags && self.game_state_flags <= 256);
-        assert!(self.score_limit >= 0);
-        assert!(self.time_limit >= 0);
-        assert!(self.round_num >= 0);
-        assert!(self.round_current >= 0);
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(game_flags: i32, game_state_flags: i32, round_start_tick: ::snap_obj::Tick, warmup_timer: i32, score_limit: i32, time_limit: i32, round_num: i32, round_current: i32) -> Result<GameInfo, Error> {
+        let result = GameInfo {
+            game_flags,
+            game_state_flags,
+            round_start_tick,
+            warmup_timer,
+            score_limit,
+            time_limit,
+            round_num,
+            round_current,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.game_flags, 0, 256)?;
+        in_range(self.game_state_flags, 0, 256)?;
+        positive(self.score_limit)?;
+        positive(self.time_limit)?;
+        positive(self.round_num)?;
+        positive(self.round_current)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.game_flags, 0, 256)?;
+        in_range(self.game_state_flags, 0, 256)?;
+        positive(self.score_limit)?;
+        positive(self.time_limit)?;
+        positive(self.round_num)?;
+        positive(self.round_current)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1104,10 +1313,25 @@ impl GameData {
             flag_carrier_blue: in_range(_p.read_int()?, -3, 63)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(-3 <= self.flag_carrier_red && self.flag_carrier_red <= 63);
-        assert!(-3 <= self.flag_carrier_blue && self.flag_carrier_blue <= 63);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(teamscore_red: i32, teamscore_blue: i32, flag_carrier_red: i32, flag_carrier_blue: i32) -> Result<GameData, Error> {
+        let result = GameData {
+            teamscore_red,
+            teamscore_blue,
+            flag_carrier_red,
+            flag_carrier_blue,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.flag_carrier_red, -3, 63)?;
+        in_range(self.flag_carrier_blue, -3, 63)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.flag_carrier_red, -3, 63)?;
+        in_range(self.flag_carrier_blue, -3, 63)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1157,12 +1381,41 @@ impl CharacterCore {
             hook_dy: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(-1 <= self.direction && self.direction <= 1);
-        assert!(0 <= self.jumped && self.jumped <= 3);
-        assert!(-1 <= self.hooked_player && self.hooked_player <= 63);
-        assert!(-1 <= self.hook_state && self.hook_state <= 5);
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(tick: i32, x: i32, y: i32, vel_x: i32, vel_y: i32, angle: i32, direction: i32, jumped: i32, hooked_player: i32, hook_state: i32, hook_tick: ::snap_obj::Tick, hook_x: i32, hook_y: i32, hook_dx: i32, hook_dy: i32) -> Result<CharacterCore, Error> {
+        let result = CharacterCore {
+            tick,
+            x,
+            y,
+            vel_x,
+            vel_y,
+            angle: wrap_angle(angle),
+            direction,
+            jumped,
+            hooked_player,
+            hook_state,
+            hook_tick,
+            hook_x,
+            hook_y,
+            hook_dx,
+            hook_dy,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.direction, -1, 1)?;
+        in_range(self.jumped, 0, 3)?;
+        in_range(self.hooked_player, -1, 63)?;
+        in_range(self.hook_state, -1, 5)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.direction, -1, 1)?;
+        in_range(self.jumped, 0, 3)?;
+        in_range(self.hooked_player, -1, 63)?;
+        in_range(self.hook_state, -1, 5)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1174,8 +1427,8 @@ impl fmt::Debug for Character {
             .field("health", &self.health)
             .field("armor", &self.armor)
             .field("ammo_count", &self.ammo_count)
-            .field("weapon", &self.weapon)
-            .field("emote", &self.emote)
+            .field("weapon", &enums::Weapon::from_i32(self.weapon))
+            .field("emote", &enums::Emote::from_i32(self.emote))
             .field("attack_tick", &self.attack_tick)
             .finish()
     }
@@ -1193,19 +1446,45 @@ impl Character {
             health: in_range(_p.read_int()?, 0, 10)?,
             armor: in_range(_p.read_int()?, 0, 10)?,
             ammo_count: in_range(_p.read_int()?, 0, 10)?,
-            weapon: enums::Weapon::from_i32(_p.read_int()?)?,
-            emote: enums::Emote::from_i32(_p.read_int()?)?,
+            weapon: _p.read_int()?,
+            emote: _p.read_int()?,
             attack_tick: positive(_p.read_int()?)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.character_core.encode();
-        assert!(0 <= self.player_flags && self.player_flags <= 256);
-        assert!(0 <= self.health && self.health <= 10);
-        assert!(0 <= self.armor && self.armor <= 10);
-        assert!(0 <= self.ammo_count && self.ammo_count <= 10);
-        assert!(self.attack_tick >= 0);
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(character_core: CharacterCore, player_flags: i32, health: i32, armor: i32, ammo_count: i32, weapon: i32, emote: i32, attack_tick: i32) -> Result<Character, Error> {
+        let result = Character {
+            character_core,
+            player_flags,
+            health,
+            armor,
+            ammo_count,
+            weapon,
+            emote,
+            attack_tick,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.character_core.validate()?;
+        in_range(self.player_flags, 0, 256)?;
+        in_range(self.health, 0, 10)?;
+        in_range(self.armor, 0, 10)?;
+        in_range(self.ammo_count, 0, 10)?;
+        enums::Weapon::from_i32(self.weapon).known()?;
+        enums::Emote::from_i32(self.emote).known()?;
+        positive(self.attack_tick)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.character_core.encode()?;
+        in_range(self.player_flags, 0, 256)?;
+        in_range(self.health, 0, 10)?;
+        in_range(self.armor, 0, 10)?;
+        in_range(self.ammo_count, 0, 10)?;
+        positive(self.attack_tick)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1214,7 +1493,7 @@ impl fmt::Debug for PlayerInfo {
         f.debug_struct("PlayerInfo")
             .field("local", &self.local)
             .field("client_id", &self.client_id)
-            .field("team", &self.team)
+            .field("team", &enums::Team::from_i32(self.team))
             .field("score", &self.score)
             .field("latency", &self.latency)
             .finish()
@@ -1230,15 +1509,32 @@ impl PlayerInfo {
         Ok(PlayerInfo {
             local: in_range(_p.read_int()?, 0, 1)?,
             client_id: in_range(_p.read_int()?, 0, 63)?,
-            team: enums::Team::from_i32(_p.read_int()?)?,
+            team: _p.read_int()?,
             score: _p.read_int()?,
             latency: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(0 <= self.local && self.local <= 1);
-        assert!(0 <= self.client_id && self.client_id <= 63);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(local: i32, client_id: i32, team: i32, score: i32, latency: i32) -> Result<PlayerInfo, Error> {
+        let result = PlayerInfo {
+            local,
+            client_id,
+            team,
+            score,
+            latency,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.local, 0, 1)?;
+        in_range(self.client_id, 0, 63)?;
+        enums::Team::from_i32(self.team).known()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.local, 0, 1)?;
+        in_range(self.client_id, 0, 63)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1288,9 +1584,26 @@ impl ClientInfo {
             color_feet: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(0 <= self.use_custom_color && self.use_custom_color <= 1);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(name: [i32; 4], clan: [i32; 3], country: i32, skin: [i32; 6], use_custom_color: i32, color_body: i32, color_feet: i32) -> Result<ClientInfo, Error> {
+        let result = ClientInfo {
+            name,
+            clan,
+            country,
+            skin,
+            use_custom_color,
+            color_body,
+            color_feet,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.use_custom_color, 0, 1)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.use_custom_color, 0, 1)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1316,9 +1629,22 @@ impl SpectatorInfo {
             y: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(-1 <= self.spectator_id && self.spectator_id <= 63);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(spectator_id: i32, x: i32, y: i32) -> Result<SpectatorInfo, Error> {
+        let result = SpectatorInfo {
+            spectator_id,
+            x,
+            y,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.spectator_id, -1, 63)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.spectator_id, -1, 63)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1340,8 +1666,18 @@ impl MyOwnObject {
             test: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(test: i32) -> Result<MyOwnObject, Error> {
+        let result = MyOwnObject {
+            test,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1381,11 +1717,34 @@ impl DdnetCharacter {
             target_y: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(-1 <= self.jumps && self.jumps <= 255);
-        assert!(0 <= self.strong_weak_id && self.strong_weak_id <= 63);
-        assert!(-1 <= self.jumped_total && self.jumped_total <= 255);
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(flags: i32, freeze_end: ::snap_obj::Tick, jumps: i32, tele_checkpoint: i32, strong_weak_id: i32, jumped_total: i32, ninja_activation_tick: ::snap_obj::Tick, freeze_start: ::snap_obj::Tick, target_x: i32, target_y: i32) -> Result<DdnetCharacter, Error> {
+        let result = DdnetCharacter {
+            flags,
+            freeze_end,
+            jumps,
+            tele_checkpoint,
+            strong_weak_id,
+            jumped_total,
+            ninja_activation_tick,
+            freeze_start,
+            target_x,
+            target_y,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.jumps, -1, 255)?;
+        in_range(self.strong_weak_id, 0, 63)?;
+        in_range(self.jumped_total, -1, 255)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.jumps, -1, 255)?;
+        in_range(self.strong_weak_id, 0, 63)?;
+        in_range(self.jumped_total, -1, 255)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1409,9 +1768,21 @@ impl DdnetPlayer {
             auth_level: in_range(_p.read_int()?, 0, 3)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(0 <= self.auth_level && self.auth_level <= 3);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(flags: i32, auth_level: i32) -> Result<DdnetPlayer, Error> {
+        let result = DdnetPlayer {
+            flags,
+            auth_level,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.auth_level, 0, 3)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.auth_level, 0, 3)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1437,8 +1808,20 @@ impl GameInfoEx {
             flags2: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(flags: i32, version: i32, flags2: i32) -> Result<GameInfoEx, Error> {
+        let result = GameInfoEx {
+            flags,
+            version,
+            flags2,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1449,7 +1832,7 @@ impl fmt::Debug for DdraceProjectile {
             .field("y", &self.y)
             .field("angle", &self.angle)
             .field("data", &self.data)
-            .field("type_", &self.type_)
+            .field("type_", &enums::Weapon::from_i32(self.type_))
             .field("start_tick", &self.start_tick)
             .finish()
     }
@@ -1466,12 +1849,28 @@ impl DdraceProjectile {
             y: _p.read_int()?,
             angle: _p.read_int()?,
             data: _p.read_int()?,
-            type_: enums::Weapon::from_i32(_p.read_int()?)?,
+            type_: _p.read_int()?,
             start_tick: ::snap_obj::Tick(_p.read_int()?),
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32, angle: i32, data: i32, type_: i32, start_tick: ::snap_obj::Tick) -> Result<DdraceProjectile, Error> {
+        let result = DdraceProjectile {
+            x,
+            y,
+            angle,
+            data,
+            type_,
+            start_tick,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        enums::Weapon::from_i32(self.type_).known()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1509,9 +1908,29 @@ impl DdnetLaser {
             subtype: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(-1 <= self.owner && self.owner <= 63);
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(to_x: i32, to_y: i32, from_x: i32, from_y: i32, start_tick: ::snap_obj::Tick, owner: i32, type_: i32, switch_number: i32, subtype: i32) -> Result<DdnetLaser, Error> {
+        let result = DdnetLaser {
+            to_x,
+            to_y,
+            from_x,
+            from_y,
+            start_tick,
+            owner,
+            type_,
+            switch_number,
+            subtype,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.owner, -1, 63)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.owner, -1, 63)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1522,7 +1941,7 @@ impl fmt::Debug for DdnetProjectile {
             .field("y", &self.y)
             .field("vel_x", &self.vel_x)
             .field("vel_y", &self.vel_y)
-            .field("type_", &self.type_)
+            .field("type_", &enums::Weapon::from_i32(self.type_))
             .field("start_tick", &self.start_tick)
             .field("owner", &self.owner)
             .field("switch_number", &self.switch_number)
@@ -1543,7 +1962,7 @@ impl DdnetProjectile {
             y: _p.read_int()?,
             vel_x: _p.read_int()?,
             vel_y: _p.read_int()?,
-            type_: enums::Weapon::from_i32(_p.read_int()?)?,
+            type_: _p.read_int()?,
             start_tick: ::snap_obj::Tick(_p.read_int()?),
             owner: in_range(_p.read_int()?, -1, 63)?,
             switch_number: _p.read_int()?,
@@ -1551,9 +1970,31 @@ impl DdnetProjectile {
             flags: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(-1 <= self.owner && self.owner <= 63);
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(x: i32, y: i32, vel_x: i32, vel_y: i32, type_: i32, start_tick: ::snap_obj::Tick, owner: i32, switch_number: i32, tune_zone: i32, flags: i32) -> Result<DdnetProjectile, Error> {
+        let result = DdnetProjectile {
+            x,
+            y,
+            vel_x,
+            vel_y,
+            type_,
+            start_tick,
+            owner,
+            switch_number,
+            tune_zone,
+            flags,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        enums::Weapon::from_i32(self.type_).known()?;
+        in_range(self.owner, -1, 63)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.owner, -1, 63)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1583,10 +2024,26 @@ impl DdnetPickup {
             switch_number: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(self.type_ >= 0);
-        assert!(self.subtype >= 0);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32, type_: i32, subtype: i32, switch_number: i32) -> Result<DdnetPickup, Error> {
+        let result = DdnetPickup {
+            x,
+            y,
+            type_,
+            subtype,
+            switch_number,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        positive(self.type_)?;
+        positive(self.subtype)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        positive(self.type_)?;
+        positive(self.subtype)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1610,8 +2067,19 @@ impl Common {
             y: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32) -> Result<Common, Error> {
+        let result = Common {
+            x,
+            y,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1633,9 +2101,20 @@ impl Explosion {
             common: Common::decode_inner(_p)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common) -> Result<Explosion, Error> {
+        let result = Explosion {
+            common,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1657,9 +2136,20 @@ impl Spawn {
             common: Common::decode_inner(_p)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common) -> Result<Spawn, Error> {
+        let result = Spawn {
+            common,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1681,9 +2171,20 @@ impl HammerHit {
             common: Common::decode_inner(_p)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common) -> Result<HammerHit, Error> {
+        let result = HammerHit {
+            common,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1707,10 +2208,23 @@ impl Death {
             client_id: in_range(_p.read_int()?, 0, 63)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        assert!(0 <= self.client_id && self.client_id <= 63);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common, client_id: i32) -> Result<Death, Error> {
+        let result = Death {
+            common,
+            client_id,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        in_range(self.client_id, 0, 63)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        in_range(self.client_id, 0, 63)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1718,7 +2232,7 @@ impl fmt::Debug for SoundGlobal {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("SoundGlobal")
             .field("common", &self.common)
-            .field("sound_id", &self.sound_id)
+            .field("sound_id", &enums::Sound::from_i32(self.sound_id))
             .finish()
     }
 }
@@ -1731,12 +2245,25 @@ impl SoundGlobal {
     pub fn decode_inner(_p: &mut IntUnpacker) -> Result<SoundGlobal, Error> {
         Ok(SoundGlobal {
             common: Common::decode_inner(_p)?,
-            sound_id: enums::Sound::from_i32(_p.read_int()?)?,
+            sound_id: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common, sound_id: i32) -> Result<SoundGlobal, Error> {
+        let result = SoundGlobal {
+            common,
+            sound_id,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        enums::Sound::from_i32(self.sound_id).known()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1744,7 +2271,7 @@ impl fmt::Debug for SoundWorld {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("SoundWorld")
             .field("common", &self.common)
-            .field("sound_id", &self.sound_id)
+            .field("sound_id", &enums::Sound::from_i32(self.sound_id))
             .finish()
     }
 }
@@ -1757,12 +2284,25 @@ impl SoundWorld {
     pub fn decode_inner(_p: &mut IntUnpacker) -> Result<SoundWorld, Error> {
         Ok(SoundWorld {
             common: Common::decode_inner(_p)?,
-            sound_id: enums::Sound::from_i32(_p.read_int()?)?,
+            sound_id: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common, sound_id: i32) -> Result<SoundWorld, Error> {
+        let result = SoundWorld {
+            common,
+            sound_id,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        enums::Sound::from_i32(self.sound_id).known()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1786,9 +2326,21 @@ impl DamageInd {
             angle: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common, angle: i32) -> Result<DamageInd, Error> {
+        let result = DamageInd {
+            common,
+            angle: wrap_angle(angle),
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1810,8 +2362,18 @@ impl MyOwnEvent {
             test: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(test: i32) -> Result<MyOwnEvent, Error> {
+        let result = MyOwnEvent {
+            test,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1835,8 +2397,19 @@ impl SpecChar {
             y: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32) -> Result<SpecChar, Error> {
+        let result = SpecChar {
+            x,
+            y,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1883,8 +2456,21 @@ impl SwitchState {
             ],
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(highest_switch_number: i32, status: [i32; 8], switch_numbers: [i32; 4], end_ticks: [i32; 4]) -> Result<SwitchState, Error> {
+        let result = SwitchState {
+            highest_switch_number,
+            status,
+            switch_numbers,
+            end_ticks,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1910,8 +2496,20 @@ impl EntityEx {
             entity_class: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(switch_number: i32, layer: i32, entity_class: i32) -> Result<EntityEx, Error> {
+        let result = EntityEx {
+            switch_number,
+            layer,
+            entity_class,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1940,3 +2538,402 @@ pub fn obj_size(type_: u16) -> Option<u32> {
         _ => return None,
     })
 }
+
+pub const OBJ_META: &'static [ObjMeta] = &[
+    ObjMeta {
+        type_id: TypeId::Ordinal(PLAYER_INPUT),
+        name: "player_input",
+        size: 10,
+        fields: &[
+            FieldMeta { name: "direction", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "target_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "target_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "jump", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "fire", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "player_flags", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "wanted_weapon", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "next_weapon", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "prev_weapon", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(PROJECTILE),
+        name: "projectile",
+        size: 6,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "type", count: 1, kind: FieldKind::Enum { name: "weapon", offset: 0, values: &["hammer", "pistol", "shotgun", "grenade", "rifle", "ninja"] } },
+            FieldMeta { name: "start_tick", count: 1, kind: FieldKind::Tick },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(LASER),
+        name: "laser",
+        size: 5,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "from_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "from_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "start_tick", count: 1, kind: FieldKind::Tick },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(PICKUP),
+        name: "pickup",
+        size: 4,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "type", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "subtype", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(FLAG),
+        name: "flag",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "team", count: 1, kind: FieldKind::Range { min: 0, max: 1 } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(GAME_INFO),
+        name: "game_info",
+        size: 8,
+        fields: &[
+            FieldMeta { name: "game_flags", count: 1, kind: FieldKind::Range { min: 0, max: 256 } },
+            FieldMeta { name: "game_state_flags", count: 1, kind: FieldKind::Range { min: 0, max: 256 } },
+            FieldMeta { name: "round_start_tick", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "warmup_timer", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "score_limit", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "time_limit", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "round_num", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "round_current", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(GAME_DATA),
+        name: "game_data",
+        size: 4,
+        fields: &[
+            FieldMeta { name: "teamscore_red", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "teamscore_blue", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "flag_carrier_red", count: 1, kind: FieldKind::Range { min: -3, max: 63 } },
+            FieldMeta { name: "flag_carrier_blue", count: 1, kind: FieldKind::Range { min: -3, max: 63 } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(CHARACTER_CORE),
+        name: "character_core",
+        size: 15,
+        fields: &[
+            FieldMeta { name: "tick", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "angle", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "direction", count: 1, kind: FieldKind::Range { min: -1, max: 1 } },
+            FieldMeta { name: "jumped", count: 1, kind: FieldKind::Range { min: 0, max: 3 } },
+            FieldMeta { name: "hooked_player", count: 1, kind: FieldKind::Range { min: -1, max: 63 } },
+            FieldMeta { name: "hook_state", count: 1, kind: FieldKind::Range { min: -1, max: 5 } },
+            FieldMeta { name: "hook_tick", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "hook_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_dx", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_dy", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(CHARACTER),
+        name: "character",
+        size: 22,
+        fields: &[
+            FieldMeta { name: "tick", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "angle", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "direction", count: 1, kind: FieldKind::Range { min: -1, max: 1 } },
+            FieldMeta { name: "jumped", count: 1, kind: FieldKind::Range { min: 0, max: 3 } },
+            FieldMeta { name: "hooked_player", count: 1, kind: FieldKind::Range { min: -1, max: 63 } },
+            FieldMeta { name: "hook_state", count: 1, kind: FieldKind::Range { min: -1, max: 5 } },
+            FieldMeta { name: "hook_tick", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "hook_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_dx", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_dy", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "player_flags", count: 1, kind: FieldKind::Range { min: 0, max: 256 } },
+            FieldMeta { name: "health", count: 1, kind: FieldKind::Range { min: 0, max: 10 } },
+            FieldMeta { name: "armor", count: 1, kind: FieldKind::Range { min: 0, max: 10 } },
+            FieldMeta { name: "ammo_count", count: 1, kind: FieldKind::Range { min: 0, max: 10 } },
+            FieldMeta { name: "weapon", count: 1, kind: FieldKind::Enum { name: "weapon", offset: 0, values: &["hammer", "pistol", "shotgun", "grenade", "rifle", "ninja"] } },
+            FieldMeta { name: "emote", count: 1, kind: FieldKind::Enum { name: "emote", offset: 0, values: &["normal", "pain", "happy", "surprise", "angry", "blink"] } },
+            FieldMeta { name: "attack_tick", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(PLAYER_INFO),
+        name: "player_info",
+        size: 5,
+        fields: &[
+            FieldMeta { name: "local", count: 1, kind: FieldKind::Range { min: 0, max: 1 } },
+            FieldMeta { name: "client_id", count: 1, kind: FieldKind::Range { min: 0, max: 63 } },
+            FieldMeta { name: "team", count: 1, kind: FieldKind::Enum { name: "team", offset: -1, values: &["spectators", "red", "blue"] } },
+            FieldMeta { name: "score", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "latency", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(CLIENT_INFO),
+        name: "client_info",
+        size: 17,
+        fields: &[
+            FieldMeta { name: "name", count: 4, kind: FieldKind::Int },
+            FieldMeta { name: "clan", count: 3, kind: FieldKind::Int },
+            FieldMeta { name: "country", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "skin", count: 6, kind: FieldKind::Int },
+            FieldMeta { name: "use_custom_color", count: 1, kind: FieldKind::Range { min: 0, max: 1 } },
+            FieldMeta { name: "color_body", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "color_feet", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(SPECTATOR_INFO),
+        name: "spectator_info",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "spectator_id", count: 1, kind: FieldKind::Range { min: -1, max: 63 } },
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Uuid(MY_OWN_OBJECT),
+        name: "my_own_object",
+        size: 1,
+        fields: &[
+            FieldMeta { name: "test", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Uuid(DDNET_CHARACTER),
+        name: "ddnet_character",
+        size: 10,
+        fields: &[
+            FieldMeta { name: "flags", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "freeze_end", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "jumps", count: 1, kind: FieldKind::Range { min: -1, max: 255 } },
+            FieldMeta { name: "tele_checkpoint", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "strong_weak_id", count: 1, kind: FieldKind::Range { min: 0, max: 63 } },
+            FieldMeta { name: "jumped_total", count: 1, kind: FieldKind::Range { min: -1, max: 255 } },
+            FieldMeta { name: "ninja_activation_tick", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "freeze_start", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "target_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "target_y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Uuid(DDNET_PLAYER),
+        name: "ddnet_player",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "flags", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "auth_level", count: 1, kind: FieldKind::Range { min: 0, max: 3 } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Uuid(GAME_INFO_EX),
+        name: "game_info_ex",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "flags", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "version", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "flags2", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Uuid(DDRACE_PROJECTILE),
+        name: "ddrace_projectile",
+        size: 6,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "angle", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "data", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "type", count: 1, kind: FieldKind::Enum { name: "weapon", offset: 0, values: &["hammer", "pistol", "shotgun", "grenade", "rifle", "ninja"] } },
+            FieldMeta { name: "start_tick", count: 1, kind: FieldKind::Tick },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Uuid(DDNET_LASER),
+        name: "ddnet_laser",
+        size: 9,
+        fields: &[
+            FieldMeta { name: "to_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "to_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "from_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "from_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "start_tick", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "owner", count: 1, kind: FieldKind::Range { min: -1, max: 63 } },
+            FieldMeta { name: "type", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "switch_number", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "subtype", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Uuid(DDNET_PROJECTILE),
+        name: "ddnet_projectile",
+        size: 10,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "type", count: 1, kind: FieldKind::Enum { name: "weapon", offset: 0, values: &["hammer", "pistol", "shotgun", "grenade", "rifle", "ninja"] } },
+            FieldMeta { name: "start_tick", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "owner", count: 1, kind: FieldKind::Range { min: -1, max: 63 } },
+            FieldMeta { name: "switch_number", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "tune_zone", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "flags", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Uuid(DDNET_PICKUP),
+        name: "ddnet_pickup",
+        size: 5,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "type", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "subtype", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "switch_number", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(COMMON),
+        name: "common",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(EXPLOSION),
+        name: "explosion",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(SPAWN),
+        name: "spawn",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(HAMMER_HIT),
+        name: "hammer_hit",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(DEATH),
+        name: "death",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "client_id", count: 1, kind: FieldKind::Range { min: 0, max: 63 } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(SOUND_GLOBAL),
+        name: "sound_global",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "sound_id", count: 1, kind: FieldKind::Enum { name: "sound", offset: 0, values: &["gun_fire", "shotgun_fire", "grenade_fire", "hammer_fire", "hammer_hit", "ninja_fire", "grenade_explode", "ninja_hit", "rifle_fire", "rifle_bounce", "weapon_switch", "player_pain_short", "player_pain_long", "body_land", "player_airjump", "player_jump", "player_die", "player_spawn", "player_skid", "tee_cry", "hook_loop", "hook_attach_ground", "hook_attach_player", "hook_noattach", "pickup_health", "pickup_armor", "pickup_grenade", "pickup_shotgun", "pickup_ninja", "weapon_spawn", "weapon_noammo", "hit", "chat_server", "chat_client", "chat_highlight", "ctf_drop", "ctf_return", "ctf_grab_pl", "ctf_grab_en", "ctf_capture", "menu"] } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(SOUND_WORLD),
+        name: "sound_world",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "sound_id", count: 1, kind: FieldKind::Enum { name: "sound", offset: 0, values: &["gun_fire", "shotgun_fire", "grenade_fire", "hammer_fire", "hammer_hit", "ninja_fire", "grenade_explode", "ninja_hit", "rifle_fire", "rifle_bounce", "weapon_switch", "player_pain_short", "player_pain_long", "body_land", "player_airjump", "player_jump", "player_die", "player_spawn", "player_skid", "tee_cry", "hook_loop", "hook_attach_ground", "hook_attach_player", "hook_noattach", "pickup_health", "pickup_armor", "pickup_grenade", "pickup_shotgun", "pickup_ninja", "weapon_spawn", "weapon_noammo", "hit", "chat_server", "chat_client", "chat_highlight", "ctf_drop", "ctf_return", "ctf_grab_pl", "ctf_grab_en", "ctf_capture", "menu"] } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(DAMAGE_IND),
+        name: "damage_ind",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "angle", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Uuid(MY_OWN_EVENT),
+        name: "my_own_event",
+        size: 1,
+        fields: &[
+            FieldMeta { name: "test", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Uuid(SPEC_CHAR),
+        name: "spec_char",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Uuid(SWITCH_STATE),
+        name: "switch_state",
+        size: 17,
+        fields: &[
+            FieldMeta { name: "highest_switch_number", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "status", count: 8, kind: FieldKind::Int },
+            FieldMeta { name: "switch_numbers", count: 4, kind: FieldKind::Int },
+            FieldMeta { name: "end_ticks", count: 4, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Uuid(ENTITY_EX),
+        name: "entity_ex",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "switch_number", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "layer", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "entity_class", count: 1, kind: FieldKind::Int },
+        ],
+    },
+];
+
+pub fn obj_meta(type_id: TypeId) -> Option<&'static ObjMeta> {
+    OBJ_META.iter().find(|m| m.type_id == type_id)
+}
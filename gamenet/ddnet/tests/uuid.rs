@@ -0,0 +1,120 @@
+extern crate gamenet_ddnet as gamenet;
+extern crate packer;
+extern crate uuid;
+extern crate warn;
+
+use gamenet::msg;
+use gamenet::msg::game;
+use gamenet::msg::system;
+use gamenet::msg::Game;
+use gamenet::msg::MessageId;
+use gamenet::msg::System;
+use gamenet::msg::SystemOrGame;
+use gamenet::snap_obj;
+use packer::with_packer;
+use packer::Unpacker;
+use uuid::Uuid;
+use warn::Panic;
+
+const UNKNOWN: Uuid = Uuid::from_u128(0x01234567_89ab_cdef_0123_456789abcdef);
+
+fn ex_id(system: bool, uuid: Uuid) -> Vec<u8> {
+    // Message ID 0 (`NETMSG_EX`) followed by the UUID.
+    let mut result = vec![if system { 0x01 } else { 0x00 }];
+    result.extend_from_slice(uuid.as_bytes());
+    result
+}
+
+#[test]
+fn ex_system_message() {
+    let id = Uuid::from_u128(0x00112233_4455_6677_8899_aabbccddeeff);
+    let mut buf = Vec::with_capacity(1024);
+    let encoded = with_packer(&mut buf, |p| System::from(system::PingEx { id }).encode(p))
+        .unwrap()
+        .to_vec();
+    let mut expected = ex_id(true, system::PING_EX);
+    expected.extend_from_slice(id.as_bytes());
+    assert_eq!(encoded, expected);
+
+    match msg::decode(&mut Panic, &mut Unpacker::new(&encoded)).unwrap() {
+        SystemOrGame::System(System::PingEx(p)) => assert_eq!(p.id, id),
+        other => panic!("unexpected message {:?}", other),
+    }
+}
+
+#[test]
+fn ex_game_message() {
+    let mut buf = Vec::with_capacity(1024);
+    let encoded = with_packer(&mut buf, |p| {
+        Game::from(game::SvMyOwnMessage { test: 64 }).encode(p)
+    })
+    .unwrap()
+    .to_vec();
+    let mut expected = ex_id(false, game::SV_MY_OWN_MESSAGE);
+    expected.extend_from_slice(b"\x80\x01");
+    assert_eq!(encoded, expected);
+
+    match msg::decode(&mut Panic, &mut Unpacker::new(&encoded)).unwrap() {
+        SystemOrGame::Game(Game::SvMyOwnMessage(m)) => assert_eq!(m.test, 64),
+        other => panic!("unexpected message {:?}", other),
+    }
+}
+
+#[test]
+fn ex_unknown_message() {
+    let encoded = ex_id(true, UNKNOWN);
+    assert!(msg::decode(&mut Panic, &mut Unpacker::new(&encoded)).is_err());
+}
+
+#[test]
+fn uuid_name() {
+    assert_eq!(msg::uuid_name(system::WHAT_IS), Some("what-is@ddnet.tw"));
+    assert_eq!(
+        msg::uuid_name(game::SV_MY_OWN_MESSAGE),
+        Some("my-own-message@heinrich5991.de")
+    );
+    assert_eq!(
+        msg::uuid_name(snap_obj::DDNET_CHARACTER),
+        Some("character@netobj.ddnet.tw")
+    );
+    assert_eq!(
+        msg::uuid_name(snap_obj::GAME_INFO_EX),
+        Some("gameinfo@netobj.ddnet.tw")
+    );
+    assert_eq!(msg::uuid_name(UNKNOWN), None);
+}
+
+#[test]
+fn what_is_reply() {
+    match msg::what_is_reply(system::PING_EX) {
+        System::ItIs(i) => {
+            assert_eq!(i.uuid, system::PING_EX);
+            assert_eq!(i.name, b"ping@ddnet.tw");
+        }
+        other => panic!("unexpected reply {:?}", other),
+    }
+    match msg::what_is_reply(snap_obj::MY_OWN_OBJECT) {
+        System::ItIs(i) => assert_eq!(i.name, b"my-own-object@heinrich5991.de"),
+        other => panic!("unexpected reply {:?}", other),
+    }
+    match msg::what_is_reply(UNKNOWN) {
+        System::IDontKnow(i) => assert_eq!(i.uuid, UNKNOWN),
+        other => panic!("unexpected reply {:?}", other),
+    }
+}
+
+#[test]
+fn unknown_id_reply() {
+    let reply = msg::unknown_id_reply;
+    match reply(SystemOrGame::System(MessageId::Uuid(UNKNOWN))) {
+        Some(System::IDontKnow(i)) => assert_eq!(i.uuid, UNKNOWN),
+        other => panic!("unexpected reply {:?}", other),
+    }
+    match reply(SystemOrGame::Game(MessageId::Uuid(UNKNOWN))) {
+        Some(System::IDontKnow(i)) => assert_eq!(i.uuid, UNKNOWN),
+        other => panic!("unexpected reply {:?}", other),
+    }
+    assert!(reply(SystemOrGame::System(MessageId::Uuid(system::PING_EX))).is_none());
+    assert!(reply(SystemOrGame::Game(MessageId::Uuid(game::SV_MY_OWN_MESSAGE))).is_none());
+    assert!(reply(SystemOrGame::System(MessageId::Ordinal(100))).is_none());
+}
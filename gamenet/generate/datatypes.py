@@ -1,4 +1,5 @@
 from collections import namedtuple
+import re
 import uuid
 import threading
 
@@ -68,6 +69,7 @@ def load_protocol_spec(json_obj):
     structs = {o.name: o for o in snapshot_objects}
     for o in snapshot_objects:
         o.structs = structs
+    apply_limits(game_messages, snapshot_objects)
     return ProtocolSpec(
         constants,
         game_enumerations,
@@ -78,6 +80,45 @@ def load_protocol_spec(json_obj):
         connless_messages,
     )
 
+# Messages whose strings end up in the client info snapshot object.
+CLIENT_INFO_MESSAGES = [
+    ("cl", "start", "info"),
+    ("cl", "change", "info"),
+    ("cl", "skin", "change"),
+    ("sv", "client", "info"),
+    ("sv", "skin", "change"),
+]
+# Snapshot objects with angles in 1/256 radians.
+ANGLE_OBJECTS = [
+    ("character", "core"),
+    ("damage",),
+    ("damage", "ind"),
+]
+
+def set_string_limit(member, ints):
+    if isinstance(member, NetArray) and isinstance(ints, NetArray) and isinstance(ints.inner, NetArray):
+        set_string_limit(member.inner, ints.inner)
+    elif isinstance(member, NetString) and isinstance(ints, NetArray):
+        # Four bytes per int, minus the NUL terminator.
+        member.max_length = 4 * ints.count - 1
+
+def apply_limits(game_messages, snapshot_objects):
+    client_info = {}
+    for o in snapshot_objects:
+        if o.name in (("client", "info"), ("de", "client", "info")):
+            client_info = {m.name: m for m in o.values}
+    for msg in game_messages:
+        if msg.name in CLIENT_INFO_MESSAGES:
+            for m in msg.values:
+                if m.name in client_info:
+                    set_string_limit(m, client_info[m.name])
+    for o in snapshot_objects:
+        if o.name in ANGLE_OBJECTS:
+            o.values = [
+                NetAngle(m.name) if m.name == ("angle",) and type(m) == NetIntAny else m
+                for m in o.values
+            ]
+
 def deserialize_member(json_obj):
     name = ()
     if "name" in json_obj:
@@ -210,7 +251,7 @@ pub use gamenet_common::snap_obj::TypeId;
 
 def emit_header_msg_system():
     import_(
-        "buffer::CapacityError",
+        "error::EncodeError",
         "error::Error",
         "packer::Packer",
         "packer::Unpacker",
@@ -231,7 +272,7 @@ impl<'a> System<'a> {
         }
     }
     pub fn encode<'d, 's>(&self, mut p: Packer<'d, 's>)
-        -> Result<&'d [u8], CapacityError>
+        -> Result<&'d [u8], EncodeError>
     {
         with_packer(&mut p, |p| SystemOrGame::System(self.msg_id()).encode_id(p))?;
         with_packer(&mut p, |p| self.encode_msg(p))?;
@@ -242,7 +283,7 @@ impl<'a> System<'a> {
 
 def emit_header_msg_game():
     import_(
-        "buffer::CapacityError",
+        "error::EncodeError",
         "error::Error",
         "packer::Packer",
         "packer::Unpacker",
@@ -265,7 +306,7 @@ impl<'a> Game<'a> {
         }
     }
     pub fn encode<'d, 's>(&self, mut p: Packer<'d, 's>)
-        -> Result<&'d [u8], CapacityError>
+        -> Result<&'d [u8], EncodeError>
     {
         with_packer(&mut p, |p| SystemOrGame::Game(self.msg_id()).encode_id(p))?;
         with_packer(&mut p, |p| self.encode_msg(p))?;
@@ -278,6 +319,7 @@ def emit_header_msg_connless(structs):
     import_(
         "buffer::CapacityError",
         "common::pretty",
+        "error::EncodeError",
         "error::Error",
         "packer::Packer",
         "packer::Unpacker",
@@ -296,7 +338,7 @@ impl{l} Connless{l} {{
         Connless::decode_connless(warn, connless_id, _p)
     }}
     pub fn encode<'d, 's>(&self, mut p: Packer<'d, 's>)
-        -> Result<&'d [u8], CapacityError>
+        -> Result<&'d [u8], EncodeError>
     {{
         p.write_raw(&self.connless_id())?;
         with_packer(&mut p, |p| self.encode_connless(p))?;
@@ -340,9 +382,18 @@ impl<'a> fmt::Debug for Client<'a> {{
 pub const INFO_FLAG_PASSWORD: i32 = 1;
 """.format(l=lifetime))
 
+def emit_serde_derive(lifetime):
+    # Borrowed types can't be deserialized from arbitrary formats, their
+    # owned counterparts are used for that.
+    if lifetime:
+        print("#[cfg_attr(feature = \"serde\", derive(Serialize))]")
+    else:
+        print("#[cfg_attr(feature = \"serde\", derive(Serialize, Deserialize))]")
+
 def emit_enum_def(name, structs):
     lifetime = "<'a>" if any(s.lifetime() for s in structs) else ""
     print("#[derive(Clone, Copy)]")
+    emit_serde_derive(lifetime)
     print("pub enum {}{} {{".format(title(name), lifetime))
     for s in structs:
         print("    {}({}{}),".format(title(s.name), title(s.name), s.lifetime()))
@@ -360,7 +411,9 @@ def emit_enum_from(name, structs):
 
 def emit_enum_msg(name, structs):
     import_(
-        "buffer::CapacityError",
+        "common::warn::Located",
+        "common::warn::strip_location",
+        "error::EncodeError",
         "error::Error",
         "packer::Packer",
         "packer::Unpacker",
@@ -375,11 +428,14 @@ def emit_enum_msg(name, structs):
     print()
     print("impl{l} {}{l} {{".format(title(name), l=lifetime))
     print("    pub fn decode_msg<W: Warn<Warning>>(warn: &mut W, msg_id: MessageId, _p: &mut Unpacker{l}) -> Result<{}{l}, Error> {{".format(title(name), l=lifetime))
+    print("        {}::decode_msg_located(&mut strip_location(warn), msg_id, _p)".format(title(name)))
+    print("    }")
+    print("    pub fn decode_msg_located<W: Warn<Located<Warning>>>(warn: &mut W, msg_id: MessageId, _p: &mut Unpacker{l}) -> Result<{}{l}, Error> {{".format(title(name), l=lifetime))
     print("        use self::MessageId::*;")
     print("        Ok(match msg_id {")
     for s in structs:
         constructor = "Ordinal" if isinstance(s.index, int) else "Uuid"
-        print("            {}({}) => {}::{s}({s}::decode(warn, _p)?),".format(constructor, caps(s.name), title(name), s=title(s.name)))
+        print("            {}({}) => {}::{s}({s}::decode_located(warn, _p)?),".format(constructor, caps(s.name), title(name), s=title(s.name)))
     print("            _ => return Err(Error::UnknownId),".format(caps(s.name), title(name), s=title(s.name)))
     print("        })")
     print("    }")
@@ -389,7 +445,13 @@ def emit_enum_msg(name, structs):
         print("            {}::{}(_) => MessageId::from({}),".format(title(name), title(s.name), caps(s.name)))
     print("        }")
     print("    }")
-    print("    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {")
+    print("    pub fn validate(&self) -> Result<(), Error> {")
+    print("        match *self {")
+    for s in structs:
+        print("            {}::{}(ref i) => i.validate(),".format(title(name), title(s.name)))
+    print("        }")
+    print("    }")
+    print("    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {")
     print("        match *self {")
     for s in structs:
         print("            {}::{}(ref i) => i.encode(p),".format(title(name), title(s.name)))
@@ -406,20 +468,90 @@ def emit_enum_msg(name, structs):
     print("    }")
     print("}")
     emit_enum_from(name, structs)
+    print()
+    emit_enum_msg_owned(name, structs)
+
+def emit_enum_msg_owned(name, structs):
+    lifetime = "<'a>" if any(s.lifetime() for s in structs) else ""
+    print("#[derive(Clone)]")
+    emit_serde_derive("")
+    print("pub enum {}Owned {{".format(title(name)))
+    for s in structs:
+        print("    {}({}),".format(title(s.name), s.owned_name()))
+    print("}")
+    print()
+    print("impl{l} {}{l} {{".format(title(name), l=lifetime))
+    print("    pub fn to_owned(&self) -> {}Owned {{".format(title(name)))
+    print("        match *self {")
+    for s in structs:
+        if s.lifetime():
+            print("            {n}::{s}(ref i) => {n}Owned::{s}(i.to_owned()),".format(n=title(name), s=title(s.name)))
+        else:
+            print("            {n}::{s}(i) => {n}Owned::{s}(i),".format(n=title(name), s=title(s.name)))
+    print("        }")
+    print("    }")
+    print("}")
+    print()
+    print("impl {}Owned {{".format(title(name)))
+    print("    pub fn as_ref(&self) -> {}{} {{".format(title(name), "<'_>" if lifetime else ""))
+    print("        match *self {")
+    for s in structs:
+        if s.lifetime():
+            print("            {n}Owned::{s}(ref i) => {n}::{s}(i.as_ref()),".format(n=title(name), s=title(s.name)))
+        else:
+            print("            {n}Owned::{s}(i) => {n}::{s}(i),".format(n=title(name), s=title(s.name)))
+    print("        }")
+    print("    }")
+    print("    pub fn msg_id(&self) -> MessageId {")
+    print("        self.as_ref().msg_id()")
+    print("    }")
+    print("    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {")
+    print("        self.as_ref().encode_msg(p)")
+    print("    }")
+    print("}")
+    print()
+    print("impl fmt::Debug for {}Owned {{".format(title(name)))
+    print("    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {")
+    print("        self.as_ref().fmt(f)")
+    print("    }")
+    print("}")
+    print()
+    print("impl{l} From<{}{l}> for {}Owned {{".format(title(name), title(name), l=lifetime))
+    print("    fn from(i: {}{l}) -> {}Owned {{".format(title(name), title(name), l=lifetime))
+    print("        i.to_owned()")
+    print("    }")
+    print("}")
+
+def emit_uuid_name(structs):
+    ex_structs = [s for s in structs if s.ex is not None]
+    if ex_structs:
+        print("pub fn uuid_name(uuid: Uuid) -> Option<&'static str> {")
+        print("    Some(match uuid {")
+        for s in ex_structs:
+            print("        {} => \"{}\",".format(caps(s.name), s.ex))
+        print("        _ => return None,")
+        print("    })")
+        print("}")
+        print()
 
 def emit_enum_msg_module(name, structs):
     for s in structs:
         s.emit_consts()
     print()
+    emit_uuid_name(structs)
     emit_enum_msg(name, structs)
     for s in structs:
         s.emit_definition()
         print()
+    for s in structs:
+        s.emit_owned_definition()
     for s in structs:
         s.emit_impl_encode_decode()
         s.emit_maybe_default()
         s.emit_impl_debug()
         print()
+    for s in structs:
+        s.emit_impl_owned()
 
 def emit_enum_obj(name, structs):
     import_(
@@ -449,7 +581,13 @@ def emit_enum_obj(name, structs):
         print("            {}::{}(_) => TypeId::from({}),".format(title(name), title(s.name), caps(s.name)))
     print("        }")
     print("    }")
-    print("    pub fn encode(&self) -> &[i32] {")
+    print("    pub fn validate(&self) -> Result<(), Error> {")
+    print("        match *self {")
+    for s in structs:
+        print("            {}::{}(ref i) => i.validate(),".format(title(name), title(s.name)))
+    print("        }")
+    print("    }")
+    print("    pub fn encode(&self) -> Result<&[i32], Error> {")
     print("        match *self {")
     for s in structs:
         print("            {}::{}(ref i) => i.encode(),".format(title(name), title(s.name)))
@@ -467,13 +605,14 @@ def emit_enum_obj(name, structs):
     print("}")
     emit_enum_from(name, structs)
 
-def emit_enum_obj_module(name, structs, flags):
+def emit_enum_obj_module(name, structs, enums, flags):
     for f in flags:
         f.emit_definition()
         print()
     for s in structs:
         s.emit_consts()
     print()
+    emit_uuid_name(structs)
     emit_enum_obj(name, structs)
     print()
     for s in structs:
@@ -486,10 +625,14 @@ def emit_enum_obj_module(name, structs, flags):
             s.emit_impl_encode_decode(suffix=True)
         print()
     emit_snap_obj_sizes(structs)
+    print()
+    emit_snap_obj_meta(structs, enums, flags)
 
 def emit_enum_connless(name, structs):
     import_(
-        "buffer::CapacityError",
+        "common::warn::Located",
+        "common::warn::strip_location",
+        "error::EncodeError",
         "error::Error",
         "packer::Warning",
         "std::fmt",
@@ -501,9 +644,12 @@ def emit_enum_connless(name, structs):
     print()
     print("impl{l} {}{l} {{".format(title(name), l=lifetime))
     print("    pub fn decode_connless<W: Warn<Warning>>(warn: &mut W, connless_id: [u8; 8], _p: &mut Unpacker{l}) -> Result<{}{l}, Error> {{".format(title(name), l=lifetime))
+    print("        {}::decode_connless_located(&mut strip_location(warn), connless_id, _p)".format(title(name)))
+    print("    }")
+    print("    pub fn decode_connless_located<W: Warn<Located<Warning>>>(warn: &mut W, connless_id: [u8; 8], _p: &mut Unpacker{l}) -> Result<{}{l}, Error> {{".format(title(name), l=lifetime))
     print("        Ok(match &connless_id {")
     for s in structs:
-        print("            {} => {}::{s}({s}::decode(warn, _p)?),".format(caps(s.name), title(name), s=title(s.name)))
+        print("            {} => {}::{s}({s}::decode_located(warn, _p)?),".format(caps(s.name), title(name), s=title(s.name)))
     print("            _ => return Err(Error::UnknownId),")
     print("        })")
     print("    }")
@@ -513,7 +659,7 @@ def emit_enum_connless(name, structs):
         print("            {}::{}(_) => *{},".format(title(name), title(s.name), caps(s.name)))
     print("        }")
     print("    }")
-    print("    pub fn encode_connless<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {")
+    print("    pub fn encode_connless<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {")
     print("        match *self {")
     for s in structs:
         print("            {}::{}(ref i) => i.encode(p),".format(title(name), title(s.name)))
@@ -554,6 +700,32 @@ def emit_snap_obj_sizes(objects):
     print("    })")
     print("}")
 
+def emit_snap_obj_meta(objects, enums, flags):
+    import_(
+        "gamenet_common::snap_obj::FieldKind",
+        "gamenet_common::snap_obj::FieldMeta",
+        "gamenet_common::snap_obj::ObjMeta",
+    )
+    enums = {e.name: e for e in enums}
+    flags = {f.name: f for f in flags}
+    print("pub const OBJ_META: &'static [ObjMeta] = &[")
+    for o in objects:
+        constructor = "Ordinal" if isinstance(o.index, int) else "Uuid"
+        print("    ObjMeta {")
+        print("        type_id: TypeId::{}({}),".format(constructor, caps(o.name)))
+        print("        name: \"{}\",".format("_".join(o.name)))
+        print("        size: {},".format(o.int_size()))
+        print("        fields: &[")
+        for m in o.all_members():
+            print("            FieldMeta {{ name: \"{}\", count: {}, kind: {} }},".format("_".join(m.name), m.meta_count(), m.meta_kind(enums, flags)))
+        print("        ],")
+        print("    },")
+    print("];")
+    print()
+    print("pub fn obj_meta(type_id: TypeId) -> Option<&'static ObjMeta> {")
+    print("    OBJ_META.iter().find(|m| m.type_id == type_id)")
+    print("}")
+
 def emit_enum_module(consts, enums):
     for c in consts:
         c.emit_definition()
@@ -579,17 +751,38 @@ buffer = "0.1.9"
 common = {{ path = "../../common/" }}
 gamenet_common = {{ path = "../common/" }}
 packer = {{ path = "../../packer/", features = ["uuid"] }}
+serde = {{ version = "1.0.23", optional = true }}
+serde_derive = {{ version = "1.0.7", optional = true }}
 uuid = "0.8.1"
-warn = ">=0.1.1,<0.3.0"\
-""".format(name))
+warn = ">=0.1.1,<0.3.0"
 
-def emit_main_lib():
+[dev-dependencies]
+{} = {{ path = ".", features = ["serde"] }}
+serde_json = "1.0.7"
+
+[features]
+serde = ["dep:serde", "dep:serde_derive", "common/serde"]\
+""".format(name, name))
+
+PROTOCOLS = {
+    "gamenet_teeworlds_0_5": "Teeworlds05",
+    "gamenet_teeworlds_0_6": "Teeworlds06",
+    "gamenet_teeworlds_0_7": "Teeworlds07",
+    "gamenet_ddnet": "Ddnet",
+}
+
+def emit_main_lib(name):
     print("""\
 extern crate arrayvec;
 extern crate buffer;
 extern crate common;
 extern crate gamenet_common;
 extern crate packer;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde_derive;
 extern crate uuid;
 extern crate warn;
 
@@ -602,11 +795,16 @@ pub mod snap_obj;
 
 pub use gamenet_common::error;
 pub use gamenet_common::error::Error;
-pub use snap_obj::SnapObj;\
-""")
+pub use gamenet_common::version::Protocol;
+pub use gamenet_common::version::Version;
+pub use snap_obj::SnapObj;
+
+pub const PROTOCOL: Protocol = Protocol::{};\
+""".format(PROTOCOLS[name]))
 
-def emit_msg_module():
+def emit_msg_module(system_messages):
     import_(
+        "common::warn::Located",
         "gamenet_common::error::Error",
         "packer::Unpacker",
         "packer::Warning",
@@ -619,8 +817,11 @@ pub mod system;
 
 pub use self::connless::Connless;
 pub use self::game::Game;
+pub use self::game::GameOwned;
 pub use self::system::System;
+pub use self::system::SystemOwned;
 
+pub use gamenet_common::msg::Addr5Packed;
 pub use gamenet_common::msg::AddrPacked;
 pub use gamenet_common::msg::CLIENTS_DATA_NONE;
 pub use gamenet_common::msg::ClientsData;
@@ -635,15 +836,15 @@ impl<'a> gamenet_common::msg::Protocol<'a> for Protocol {
 
     fn decode_system<W>(warn: &mut W, id: MessageId, p: &mut Unpacker<'a>)
         -> Result<Self::System, Error>
-        where W: Warn<Warning>
+        where W: Warn<Located<Warning>>
     {
-        System::decode_msg(warn, id, p)
+        System::decode_msg_located(warn, id, p)
     }
     fn decode_game<W>(warn: &mut W, id: MessageId, p: &mut Unpacker<'a>)
         -> Result<Self::Game, Error>
-        where W: Warn<Warning>
+        where W: Warn<Located<Warning>>
     {
-        Game::decode_msg(warn, id, p)
+        Game::decode_msg_located(warn, id, p)
     }
 }
 
@@ -653,12 +854,65 @@ pub fn decode<'a, W>(warn: &mut W, p: &mut Unpacker<'a>)
 {
     gamenet_common::msg::decode(warn, Protocol, p)
 }
+
+/// Like `decode`, but attaches the offset and the name of the message field
+/// to each warning.
+pub fn decode_located<'a, W>(warn: &mut W, p: &mut Unpacker<'a>)
+    -> Result<SystemOrGame<System<'a>, Game<'a>>, Error>
+    where W: Warn<Located<Warning>>
+{
+    gamenet_common::msg::decode_located(warn, Protocol, p)
+}\
 """)
+    if any(m.name == ("what", "is") for m in system_messages):
+        import_("uuid::Uuid")
+        print("""
+/// Returns the name a known message or snapshot object UUID was derived
+/// from.
+pub fn uuid_name(uuid: Uuid) -> Option<&'static str> {
+    system::uuid_name(uuid)
+        .or_else(|| game::uuid_name(uuid))
+        .or_else(|| ::snap_obj::uuid_name(uuid))
+}
+
+/// Returns the answer to a `what-is@ddnet.tw` query about `uuid`.
+pub fn what_is_reply(uuid: Uuid) -> System<'static> {
+    match uuid_name(uuid) {
+        Some(name) => System::ItIs(system::ItIs {
+            uuid,
+            name: name.as_bytes(),
+        }),
+        None => System::IDontKnow(system::IDontKnow { uuid }),
+    }
+}
+
+/// Returns the answer to a message with ID `id` if it's an unknown UUID.
+///
+/// Instead of dropping these messages silently, the reference implementation
+/// tells the peer that it doesn't know the UUID.
+pub fn unknown_id_reply(id: SystemOrGame<MessageId, MessageId>) -> Option<System<'static>> {
+    let uuid = match id {
+        SystemOrGame::System(MessageId::Uuid(u)) => u,
+        SystemOrGame::Game(MessageId::Uuid(u)) => u,
+        _ => return None,
+    };
+    if uuid_name(uuid).is_some() {
+        return None;
+    }
+    Some(System::IDontKnow(system::IDontKnow { uuid }))
+}\
+""")
+    print()
 
 class Enum(NameValues):
     def __init__(self, name, values, offset=0):
         super().__init__(name, [canonicalize(v) for v in values])
         self.offset = offset
+    def variant(self, name):
+        # `Unknown` is taken by the variant for values not in the enum.
+        if name == ("unknown",):
+            return title(self.name + name)
+        return title(name)
     def emit_definition(self):
         import_(
             "packer::IntOutOfRange",
@@ -666,31 +920,36 @@ class Enum(NameValues):
         for i, name in enumerate(self.values):
             print("pub const {}_{}: i32 = {};".format(caps(self.name), caps(name), i + self.offset))
         print()
-        print("#[repr(i32)]")
         print("#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]")
+        emit_serde_derive("")
         print("pub enum {} {{".format(title(self.name)))
-        for i, name in enumerate(self.values):
-            if self.offset == 0 or i != 0:
-                print("    {},".format(title(name)))
-            else:
-                print("    {} = {},".format(title(name), self.offset))
+        for name in self.values:
+            print("    {},".format(self.variant(name)))
+        print("    Unknown(i32),")
         print("}")
 
     def emit_impl(self):
         print("impl {} {{".format(title(self.name)))
-        print("    pub fn from_i32(i: i32) -> Result<{}, IntOutOfRange> {{".format(title(self.name)))
+        print("    pub fn from_i32(i: i32) -> {} {{".format(title(self.name)))
         print("        use self::{}::*;".format(title(self.name)))
-        print("        Ok(match i {")
+        print("        match i {")
         for name in self.values:
-            print("            {}_{} => {},".format(caps(self.name), caps(name), title(name)))
-        print("            _ => return Err(IntOutOfRange),")
-        print("        })")
+            print("            {}_{} => {},".format(caps(self.name), caps(name), self.variant(name)))
+        print("            _ => Unknown(i),")
+        print("        }")
         print("    }")
         print("    pub fn to_i32(self) -> i32 {")
         print("        use self::{}::*;".format(title(self.name)))
         print("        match self {")
         for name in self.values:
-            print("            {} => {}_{},".format(title(name), caps(self.name), caps(name)))
+            print("            {} => {}_{},".format(self.variant(name), caps(self.name), caps(name)))
+        print("            Unknown(i) => i,")
+        print("        }")
+        print("    }")
+        print("    pub fn known(self) -> Result<{}, IntOutOfRange> {{".format(title(self.name)))
+        print("        match self {")
+        print("            {}::Unknown(_) => Err(IntOutOfRange),".format(title(self.name)))
+        print("            known => Ok(known),")
         print("        }")
         print("    }")
         print("}")
@@ -799,19 +1058,101 @@ class Struct(NameValues):
         if self.name != ("player", "input"):
             print("#[derive(Clone, Copy)]")
         else:
-            print("#[derive(Clone, Copy, Default)]")
+            print("#[derive(Clone, Copy, Default, Eq, PartialEq)]")
+        emit_serde_derive(self.lifetime())
         if self.values or super:
             print("pub struct {}{} {{".format(title(self.name), self.lifetime()))
             if super:
                 print("    pub {}: {}{},".format(snake(super.name), title(super.name), super.lifetime()))
             for member in self.values:
+                member.emit_doc()
+                member.emit_serde_attribute()
                 print("    pub {},".format(member.definition()))
             print("}")
         else:
             print("pub struct {};".format(title(self.name)))
+    def owned_name(self):
+        if self.lifetime():
+            return "{}Owned".format(title(self.name))
+        return title(self.name)
+    def emit_owned_definition(self):
+        if not self.lifetime():
+            return
+        if self.super:
+            raise ValueError("can't create an owned version of a struct with a super type")
+        print("#[derive(Clone)]")
+        emit_serde_derive("")
+        print("pub struct {} {{".format(self.owned_name()))
+        for member in self.values:
+            member.emit_serde_attribute(owned=True)
+            print("    pub {}: {},".format(snake(member.name), member.owned_type()))
+        print("}")
+        print()
+    def emit_impl_owned(self):
+        if not self.lifetime():
+            return
+        print("impl<'a> {}<'a> {{".format(title(self.name)))
+        print("    pub fn to_owned(&self) -> {} {{".format(self.owned_name()))
+        print("        {} {{".format(self.owned_name()))
+        with indent(3):
+            for m in self.values:
+                print("{}: {},".format(snake(m.name), m.to_owned_expr("self.{}".format(snake(m.name)))))
+        print("        }")
+        print("    }")
+        print("}")
+        print()
+        print("impl {} {{".format(self.owned_name()))
+        print("    pub fn as_ref(&self) -> {}<'_> {{".format(title(self.name)))
+        print("        {} {{".format(title(self.name)))
+        with indent(3):
+            for m in self.values:
+                print("{}: {},".format(snake(m.name), m.as_ref_expr("self.{}".format(snake(m.name)))))
+        print("        }")
+        print("    }")
+        print("}")
+        print()
+        print("impl fmt::Debug for {} {{".format(self.owned_name()))
+        print("    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {")
+        print("        self.as_ref().fmt(f)")
+        print("    }")
+        print("}")
+        print()
+    def emit_new(self):
+        if self.super:
+            super = self.structs[self.super]
+        else:
+            super = None
+        if not self.values and not super:
+            return
+        args = []
+        if super:
+            args.append("{}: {}{}".format(snake(super.name), title(super.name), super.lifetime()))
+        args += [m.definition() for m in self.values]
+        if len(args) > 7:
+            print("    #[allow(clippy::too_many_arguments)]")
+        print("    pub fn new({}) -> Result<{}{}, Error> {{".format(", ".join(args), title(self.name), self.lifetime()))
+        print("        let result = {} {{".format(title(self.name)))
+        with indent(3):
+            if super:
+                print("{},".format(snake(super.name)))
+            for m in self.values:
+                m.emit_new()
+        print("        };")
+        print("        result.validate()?;")
+        print("        Ok(result)")
+        print("    }")
+    def emit_validate(self):
+        print("    pub fn validate(&self) -> Result<(), Error> {")
+        if self.super:
+            print("        self.{}.validate()?;".format(snake(self.structs[self.super].name)))
+        with indent(2):
+            for m in self.values:
+                m.emit_check()
+        print("        Ok(())")
+        print("    }")
     def emit_impl_encode_decode(self, suffix=False):
         import_(
-            "buffer::CapacityError",
+            "error::EncodeError",
             "error::Error",
             "packer::Packer",
             "packer::Unpacker",
@@ -824,22 +1165,39 @@ class Struct(NameValues):
         else:
             suffix = ""
         print("impl{l} {}{l} {{".format(title(self.name), l=self.lifetime()))
+        import_("common::warn::strip_location")
         print("    pub fn decode{}<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker{l}) -> Result<{}{l}, Error> {{".format(suffix, title(self.name), l=self.lifetime()))
+        print("        {}::decode{}_located(&mut strip_location(warn), _p)".format(title(self.name), suffix))
+        print("    }")
+        import_("common::warn::Located")
+        print("    pub fn decode{}_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker{l}) -> Result<{}{l}, Error> {{".format(suffix, title(self.name), l=self.lifetime()))
+        import_("error")
         if self.values:
-            print("        let result = Ok({} {{".format(title(self.name)))
-            with indent(3):
-                for m in self.values:
-                    m.emit_decode()
-            print("        });")
+            fields = Emit()
+            with fields:
+                with indent(3):
+                    for m in self.values:
+                        m.emit_decode(title(self.name))
+            import_(*fields.imports)
+            print("        let result = {}::decode{}_fields(warn, _p);".format(title(self.name), suffix))
+            print("        error::finish(&mut _p.locate(warn, \"{}\"), _p, result)".format(title(self.name)))
+            print("    }")
+            warn = "warn" if any(re.search(r"\bwarn\b", l) for l in fields.lines) else "_warn"
+            print("    fn decode{}_fields<W: Warn<Located<Warning>>>({}: &mut W, _p: &mut Unpacker{l}) -> Result<{}{l}, Error> {{".format(suffix, warn, title(self.name), l=self.lifetime()))
+            print("        Ok({} {{".format(title(self.name)))
+            for l in fields.lines:
+                print(l)
+            print("        })")
         else:
-            print("        let result = Ok({});".format(title(self.name)))
-        print("        _p.finish(warn);")
-        print("        result")
+            print("        error::finish(&mut _p.locate(warn, \"{0}\"), _p, Ok({0}))".format(title(self.name)))
         print("    }")
-        print("    pub fn encode{}<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {{".format(suffix, title(self.name), l=self.lifetime()))
+        if not suffix:
+            self.emit_new()
+            self.emit_validate()
+        print("    pub fn encode{}<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {{".format(suffix, title(self.name), l=self.lifetime()))
         with indent(2):
             for m in self.values:
-                m.emit_assert()
+                m.emit_encode_check()
             for m in self.values:
                 m.emit_encode()
         print("        Ok(_p.written())")
@@ -906,6 +1264,11 @@ class NetObject(Struct):
         super().__init__(name, values, ex)
         if not validate_size:
             self.attributes.add("dont_validate_size")
+        # Snap objects are reinterpreted as `[i32]`, so their enum members
+        # have to be stored as plain integers.
+        for member in values:
+            if isinstance(member, NetEnum):
+                member.make_raw()
     def emit_definition(self):
         print("#[repr(C)]")
         super().emit_definition()
@@ -940,15 +1303,22 @@ class NetObject(Struct):
         else:
             print("        Ok({})".format(title(self.name)))
         print("    }")
-        print("    pub fn encode(&self) -> &[i32] {")
+        self.emit_new()
+        self.emit_validate()
+        print("    pub fn encode(&self) -> Result<&[i32], Error> {")
         if super:
-            print("        self.{}.encode();".format(snake(super.name)))
+            print("        self.{}.encode()?;".format(snake(super.name)))
         with indent(2):
             for m in self.values:
-                m.emit_assert()
-        print("        unsafe { slice::transmute(from_ref(self)) }")
+                m.emit_encode_check()
+        print("        Ok(unsafe { slice::transmute(from_ref(self)) })")
         print("    }")
         print("}")
+    def all_members(self):
+        result = []
+        if self.super:
+            result += self.structs[self.super].all_members()
+        return result + self.values
     def int_size(self):
         size = sum(m.int_size() for m in self.values)
         if self.super:
@@ -995,26 +1365,57 @@ class Member:
         self.default = default
     def definition(self):
         return "{}: {}".format(snake(self.name), self.type_)
+    def serialize_with(self):
+        pass
+    def emit_doc(self):
+        pass
+    def emit_serde_attribute(self, owned=False):
+        serialize_with = self.serialize_with()
+        if serialize_with is None:
+            return
+        if owned:
+            print("    #[cfg_attr(feature = \"serde\", serde(with = \"{}\"))]".format(serialize_with))
+        else:
+            print("    #[cfg_attr(feature = \"serde\", serde(serialize_with = \"{}::serialize\"))]".format(serialize_with))
     def contains_lifetime(self):
         return "'a" in self.type_
+    def owned_type(self):
+        if self.contains_lifetime():
+            raise ValueError("can't create an owned version of this type")
+        return self.type_
+    def to_owned_expr(self, self_expr):
+        return self_expr
+    def as_ref_expr(self, self_expr):
+        return self_expr
     def update(self, parent, consts, enums, structs):
         return self
-    def emit_decode(self):
-        print("{}: {},".format(snake(self.name), self.decode_expr()))
+    def emit_decode(self, structure):
+        print("{}: {},".format(snake(self.name), self.located_decode_expr(structure)))
+    def located_decode_expr(self, structure):
+        located = "&mut _p.locate(warn, \"{}.{}\")".format(structure, snake(self.name))
+        return re.sub(r"\bwarn\b", lambda _: located, self.decode_expr())
     def emit_decode_int(self):
         print("{}: {},".format(snake(self.name), self.decode_int_expr()))
-    def emit_assert(self):
-        assertion = self.assert_expr("self.{}".format(snake(self.name)))
-        if assertion is not None:
-            print("{};".format(assertion))
+    def emit_new(self):
+        print("{},".format(snake(self.name)))
+    def emit_encode_check(self):
+        check = self.encode_check_expr("self.{}".format(snake(self.name)))
+        if check is not None:
+            print("{}?;".format(check))
+    def emit_check(self):
+        check = self.check_expr("self.{}".format(snake(self.name)))
+        if check is not None:
+            print("{}?;".format(check))
     def emit_encode(self):
         print("{}?;".format(self.encode_expr("self.{}".format(snake(self.name)))))
     def emit_debug(self):
         print(".field(\"{}\", &{})".format(snake(self.name), self.debug_expr("self.{}".format(snake(self.name)))))
     def validate_expr(self, self_expr):
         pass
-    def assert_expr(self, self_expr):
+    def check_expr(self, self_expr):
         pass
+    def encode_check_expr(self, self_expr):
+        return self.check_expr(self_expr)
     def debug_expr(self, self_expr):
         return self_expr
     def serialize(self):
@@ -1037,15 +1438,24 @@ class NetArray(Member):
         self.inner = inner
         self.count = count
         self.type_ = "[{}; {}]".format(inner.type_, count)
+    def serialize_with(self):
+        if self.inner.serialize_with() == "gamenet_common::serialize::bytes":
+            return "gamenet_common::serialize::bytes_array"
     def decode_expr(self):
         return "[\n{}]".format("".join(
             "    {},\n".format(self.inner.decode_expr()) for _ in range(self.count)
         ))
-    def emit_assert(self):
-        assert_expr = self.inner.assert_expr("e")
-        if assert_expr:
+    def emit_encode_check(self):
+        check_expr = self.inner.encode_check_expr("e")
+        if check_expr:
+            print("for &e in &self.{} {{".format(snake(self.name)))
+            print("    {}?;".format(check_expr))
+            print("}")
+    def emit_check(self):
+        check_expr = self.inner.check_expr("e")
+        if check_expr:
             print("for &e in &self.{} {{".format(snake(self.name)))
-            print("    {};".format(assert_expr))
+            print("    {}?;".format(check_expr))
             print("}")
     def emit_encode(self):
         print("for &e in &self.{} {{".format(snake(self.name)))
@@ -1060,8 +1470,26 @@ class NetArray(Member):
             return self_expr
         import_("gamenet_common::debug::DebugSlice")
         return "DebugSlice::new(&{}, |e| {})".format(self_expr, self.inner.debug_expr("e"))
+    def owned_type(self):
+        return "[{}; {}]".format(self.inner.owned_type(), self.count)
+    def to_owned_expr(self, self_expr):
+        if not self.contains_lifetime():
+            return self_expr
+        return "[\n{}]".format("".join(
+            "    {},\n".format(self.inner.to_owned_expr("{}[{}]".format(self_expr, i))) for i in range(self.count)
+        ))
+    def as_ref_expr(self, self_expr):
+        if not self.contains_lifetime():
+            return self_expr
+        return "[\n{}]".format("".join(
+            "    {},\n".format(self.inner.as_ref_expr("{}[{}]".format(self_expr, i))) for i in range(self.count)
+        ))
     def int_size(self):
         return self.inner.int_size() * self.count
+    def meta_count(self):
+        return self.inner.meta_count() * self.count
+    def meta_kind(self, enums, flags):
+        return self.inner.meta_kind(enums, flags)
     def serialize_type(self):
         return {
             "kind": self.kind,
@@ -1082,6 +1510,9 @@ class NetOptional(Member):
         super().__init__(name)
         self.inner = inner
         self.type_ = "Option<{}>".format(inner.type_)
+    def serialize_with(self):
+        if self.inner.serialize_with() == "gamenet_common::serialize::bytes":
+            return "gamenet_common::serialize::optional_bytes"
     def decode_expr(self):
         END="?"
         inner_decode = self.inner.decode_expr()
@@ -1092,8 +1523,20 @@ class NetOptional(Member):
         return self.inner.encode_expr("{}.unwrap()").format(self_expr)
     def debug_expr(self, self_expr):
         return "{}.as_ref().map(|v| {})".format(self_expr, self.inner.debug_expr("v"))
-    def assert_expr(self, self_expr):
-        return "assert!({}.is_some())".format(self_expr)
+    def owned_type(self):
+        return "Option<{}>".format(self.inner.owned_type())
+    def to_owned_expr(self, self_expr):
+        if not self.contains_lifetime():
+            return self_expr
+        return "{}.map(|v| {})".format(self_expr, self.inner.to_owned_expr("v"))
+    def as_ref_expr(self, self_expr):
+        if not self.contains_lifetime():
+            return self_expr
+        return "{}.as_ref().map(|v| {})".format(self_expr, self.inner.as_ref_expr("v"))
+    def check_expr(self, self_expr):
+        if self.inner.check_expr("v") is not None:
+            raise ValueError("can't check an optional of this type")
+        return "{}.ok_or(Error::MissingValue)".format(self_expr)
     def serialize_type(self):
         return {"kind": self.kind, "inner": self.inner.serialize_type()}
     @staticmethod
@@ -1103,10 +1546,26 @@ class NetOptional(Member):
 class NetString(Member):
     kind = "string"
     type_ = "&'a [u8]"
+    max_length = None
+    def serialize_with(self):
+        return "gamenet_common::serialize::bytes"
+    def owned_type(self):
+        return "Vec<u8>"
+    def to_owned_expr(self, self_expr):
+        return "{}.to_vec()".format(self_expr)
+    def as_ref_expr(self, self_expr):
+        return "&{}[..]".format(self_expr)
     def decode_expr(self):
         return "_p.read_string()?"
     def encode_expr(self, self_expr):
         return "_p.write_string({})".format(self_expr)
+    def check_expr(self, self_expr):
+        if self.max_length is None:
+            return None
+        import_("packer::max_length")
+        return "max_length({}, {})".format(self_expr, self.max_length)
+    def encode_check_expr(self, self_expr):
+        pass
     def debug_expr(self, self_expr):
         import_("common::pretty")
         return "pretty::Bytes::new(&{})".format(self_expr)
@@ -1123,12 +1582,17 @@ class NetStringStrict(NetString):
     def decode_expr(self):
         import_("packer::sanitize")
         return "sanitize(warn, {})?".format(super().decode_expr())
-    def assert_expr(self, self_expr):
+    def check_expr(self, self_expr):
+        length_check = super().check_expr(self_expr)
+        if length_check is not None:
+            self_expr = "{}?".format(length_check)
+        return self.encode_check_expr(self_expr)
+    def encode_check_expr(self, self_expr):
         import_(
             "packer::sanitize",
-            "warn::Panic",
+            "warn::Ignore",
         )
-        return "sanitize(&mut Panic, {}).unwrap()".format(self_expr)
+        return "sanitize(&mut Ignore, {})".format(self_expr)
     def serialize_type(self):
         return {"kind": self.kind, "disallow_cc": True}
 NetStringHalfStrict = NetStringStrict
@@ -1136,6 +1600,14 @@ NetStringHalfStrict = NetStringStrict
 class NetData(Member):
     kind = "data"
     type_ = "&'a [u8]"
+    def serialize_with(self):
+        return "gamenet_common::serialize::bytes"
+    def owned_type(self):
+        return "Vec<u8>"
+    def to_owned_expr(self, self_expr):
+        return "{}.to_vec()".format(self_expr)
+    def as_ref_expr(self, self_expr):
+        return "&{}[..]".format(self_expr)
     def decode_expr(self):
         return "_p.read_data(warn)?"
     def encode_expr(self, self_expr):
@@ -1152,6 +1624,14 @@ class NetData(Member):
 class NetDataRest(Member):
     kind = "rest"
     type_ = "&'a [u8]"
+    def serialize_with(self):
+        return "gamenet_common::serialize::bytes"
+    def owned_type(self):
+        return "Vec<u8>"
+    def to_owned_expr(self, self_expr):
+        return "{}.to_vec()".format(self_expr)
+    def as_ref_expr(self, self_expr):
+        return "&{}[..]".format(self_expr)
     def decode_expr(self):
         return "_p.read_rest()?"
     def encode_expr(self, self_expr):
@@ -1204,6 +1684,10 @@ class NetIntAny(Member):
         return "_p.read_int()?"
     def int_size(self):
         return 1
+    def meta_count(self):
+        return 1
+    def meta_kind(self, enums, flags):
+        return "FieldKind::Int"
     def serialize_type(self):
         return {"kind": self.kind}
     @staticmethod
@@ -1287,15 +1771,18 @@ class NetIntRange(NetIntAny):
         import_consts(self.min)
         import_consts(self.max)
         return "in_range({}, {}, {})?".format(super().decode_expr(), self.min, self.max)
-    def assert_expr(self, self_expr):
+    def check_expr(self, self_expr):
+        import_("packer::in_range")
         import_consts(self.min)
         import_consts(self.max)
-        return "assert!({} <= {s} && {s} <= {})".format(self.min, self.max, s=self_expr)
+        return "in_range({}, {}, {})".format(self_expr, self.min, self.max)
     def decode_int_expr(self):
         import_("packer::in_range")
         import_consts(self.min)
         import_consts(self.max)
         return "in_range({}, {}, {})?".format(super().decode_int_expr(), self.min, self.max)
+    def meta_kind(self, enums, flags):
+        return "FieldKind::Range {{ min: {}, max: {} }}".format(self.min, self.max)
     def serialize_type(self):
         return {"kind": self.kind, "min": self.min, "max": self.max}
 
@@ -1305,11 +1792,14 @@ class NetIntPositive(NetIntAny):
     def decode_expr(self):
         import_("packer::positive")
         return "positive({})?".format(super().decode_expr())
-    def assert_expr(self, self_expr):
-        return "assert!({} >= 0)".format(self_expr)
+    def check_expr(self, self_expr):
+        import_("packer::positive")
+        return "positive({})".format(self_expr)
     def decode_int_expr(self):
         import_("packer::positive")
         return "positive({})?".format(super().decode_int_expr())
+    def meta_kind(self, enums, flags):
+        return "FieldKind::Range { min: 0, max: i32::MAX }"
     def serialize_type(self):
         return {"kind": self.kind, "min": 0}
 
@@ -1320,11 +1810,14 @@ class NetIntMin(NetIntAny):
     def decode_expr(self):
         import_("packer::at_least")
         return "at_least({}, {})?".format(super().decode_expr(), self.min)
-    def assert_expr(self, self_expr):
-        return "assert!({} >= {})".format(self_expr, self.min)
+    def check_expr(self, self_expr):
+        import_("packer::at_least")
+        return "at_least({}, {})".format(self_expr, self.min)
     def decode_int_expr(self):
         import_("packer::at_least")
         return "at_least({}, {})?".format(super().decode_int_expr(), self.min)
+    def meta_kind(self, enums, flags):
+        return "FieldKind::Range {{ min: {}, max: i32::MAX }}".format(self.min)
     def serialize_type(self):
         return {"kind": self.kind, "min": self.min}
 
@@ -1336,20 +1829,58 @@ class NetEnum(NetIntAny):
             enum_name = enum_name.name
         self.enum_name = canonicalize(enum_name)
         self.type_ = "enums::{}".format(title(self.enum_name))
+        self.raw = False
+    def make_raw(self):
+        self.type_ = "i32"
+        self.raw = True
+    def emit_doc(self):
+        if self.raw:
+            print("    /// Raw `enums::{0}` value, convert it using `enums::{0}::from_i32`.".format(title(self.enum_name)))
     def decode_expr(self):
+        if self.raw:
+            return super().decode_expr()
         import_("enums")
-        return "enums::{}::from_i32({})?".format(title(self.enum_name), super().decode_expr())
+        return "enums::{}::from_i32({})".format(title(self.enum_name), super().decode_expr())
     def encode_expr(self, self_expr):
+        if self.raw:
+            return super().encode_expr(self_expr)
         return super().encode_expr("{}.to_i32()".format(self_expr))
     def decode_int_expr(self):
+        if self.raw:
+            return super().decode_int_expr()
         import_("enums")
-        return "enums::{}::from_i32({})?".format(title(self.enum_name), super().decode_int_expr())
+        return "enums::{}::from_i32({})".format(title(self.enum_name), super().decode_int_expr())
+    def check_expr(self, self_expr):
+        if self.raw:
+            return "enums::{}::from_i32({}).known()".format(title(self.enum_name), self_expr)
+        return "{}.known()".format(self_expr)
+    def encode_check_expr(self, self_expr):
+        # Unknown values are passed through unchanged, see `validate` for a
+        # stricter check.
+        pass
+    def debug_expr(self, self_expr):
+        if self.raw:
+            import_("enums")
+            return "enums::{}::from_i32({})".format(title(self.enum_name), self_expr)
+        return self_expr
+    def meta_kind(self, enums, flags):
+        enum = enums[self.enum_name]
+        return "FieldKind::Enum {{ name: \"{}\", offset: {}, values: &[{}] }}".format(
+            "_".join(enum.name),
+            enum.offset,
+            ", ".join("\"{}\"".format("_".join(v)) for v in enum.values),
+        )
     def serialize_type(self):
         return {"kind": self.kind, "enum": self.enum_name}
     @staticmethod
     def deserialize(name, json_obj):
         return NetEnum(name, tuple(json_obj["enum"]))
 
+class NetAngle(NetIntAny):
+    def emit_new(self):
+        import_("gamenet_common::snap_obj::wrap_angle")
+        print("{n}: wrap_angle({n}),".format(n=snake(self.name)))
+
 class NetFlag(NetIntAny):
     kind = "flags"
     def __init__(self, name, flags_name):
@@ -1357,6 +1888,12 @@ class NetFlag(NetIntAny):
         if isinstance(flags_name, Flags):
             flags_name = flags_name.name
         self.flags_name = canonicalize(flags_name)
+    def meta_kind(self, enums, flags):
+        f = flags[self.flags_name]
+        return "FieldKind::Flags {{ name: \"{}\", values: &[{}] }}".format(
+            "_".join(f.name),
+            ", ".join("\"{}\"".format("_".join(v)) for v in f.values),
+        )
     def serialize_type(self):
         return {"kind": self.kind, "flags": self.flags_name}
     @staticmethod
@@ -1374,6 +1911,8 @@ class NetBool(NetIntAny):
     def decode_int_expr(self):
         import_("packer::to_bool")
         return "to_bool({})?".format(super().decode_int_expr())
+    def meta_kind(self, enums, flags):
+        return "FieldKind::Bool"
     def serialize_type(self):
         return {"kind": self.kind}
     @staticmethod
@@ -1402,6 +1941,8 @@ class NetTick(NetIntAny):
         return super().encode_expr("{}.0".format(self_expr))
     def decode_int_expr(self):
         return "::snap_obj::Tick({})".format(super().decode_int_expr())
+    def meta_kind(self, enums, flags):
+        return "FieldKind::Tick"
     def serialize_type(self):
         return {"kind": self.kind}
     @staticmethod
@@ -1416,6 +1957,8 @@ class NetObjectMember(Member):
         self.type_name = type_
     def decode_expr(self):
         return "{}::decode_msg(warn, _p)?".format(self.type_)
+    def located_decode_expr(self, structure):
+        return "{}::decode_msg_located(warn, _p)?".format(self.type_)
     def encode_expr(self, self_expr):
         import_("packer::with_packer")
         return "with_packer(&mut _p, |p| {}.encode_msg(p))".format(self_expr)
@@ -1445,6 +1988,16 @@ class NetAddrs(Member):
     def deserialize(name, json_obj):
         return NetAddrs(name)
 
+class NetAddrs5(NetAddrs):
+    kind = "packed_addresses_v5"
+    type_ = "&'a [Addr5Packed]"
+    def definition(self):
+        import_("super::Addr5Packed")
+        return Member.definition(self)
+    @staticmethod
+    def deserialize(name, json_obj):
+        return NetAddrs5(name)
+
 class NetBigEndianU16(Member):
     kind = "be_uint16"
     type_ = "u16"
@@ -1476,6 +2029,14 @@ class NetU8(Member):
 class NetIntString(NetString):
     kind = "int32_string"
     type_ = "i32"
+    def serialize_with(self):
+        pass
+    def owned_type(self):
+        return self.type_
+    def to_owned_expr(self, self_expr):
+        return self_expr
+    def as_ref_expr(self, self_expr):
+        return self_expr
     def decode_expr(self):
         import_("gamenet_common::msg::int_from_string")
         return "int_from_string(_p.read_string()?)?"
@@ -1557,6 +2118,7 @@ MEMBER_TYPES = [
     NetTick,
     NetObjectMember,
     NetAddrs,
+    NetAddrs5,
     NetBigEndianU16,
     NetU8,
     NetIntString,
@@ -40,17 +40,17 @@ def generate(spec, out_dir, name):
     emit = datatypes.Emit()
     with emit:
         datatypes.emit_header_snap_obj()
-        datatypes.emit_enum_obj_module("SnapObj", protocol.snapshot_objects, protocol.game_flags)
+        datatypes.emit_enum_obj_module("SnapObj", protocol.snapshot_objects, protocol.game_enumerations, protocol.game_flags)
     write_file(os.path.join(out_dir, "src/snap_obj.rs"), emit.get())
 
     emit = datatypes.Emit()
     with emit:
-        datatypes.emit_main_lib()
+        datatypes.emit_main_lib(name)
     write_file(os.path.join(out_dir, "src/lib.rs"), emit.get())
 
     emit = datatypes.Emit()
     with emit:
-        datatypes.emit_msg_module()
+        datatypes.emit_msg_module(protocol.system_messages)
     write_file(os.path.join(out_dir, "src/msg/mod.rs"), emit.get())
 
     emit = datatypes.Emit()
@@ -55,6 +55,37 @@ NETVERSION = {
     VERSION_DDNET_17_2_1: "0.6 626fce9a778df4d4",
 }
 
+# Ex-messages added in DDNet versions after the newest `raw/` network
+# description. They're identified by UUID, so peers that don't know them
+# answer with `i-dont-know@ddnet.tw` instead of misinterpreting them.
+DDNET_EX_MESSAGES_NEWER = [
+    NetMessageEx("Sv_YourVote", "yourvote@netmsg.ddnet.org", [
+        NetIntRange("m_Voted", -1, 1),
+    ]),
+    NetMessageEx("Sv_RaceFinish", "racefinish@netmsg.ddnet.org", [
+        NetIntRange("m_ClientID", 0, 'MAX_CLIENTS-1'),
+        NetIntAny("m_Time"),
+        NetIntAny("m_Diff"),
+        NetBool("m_RecordPersonal"),
+        NetBool("m_RecordServer"),
+    ]),
+    NetMessageEx("Sv_CommandInfo", "commandinfo@netmsg.ddnet.org", [
+        NetStringStrict("m_pName"),
+        NetStringStrict("m_pArgsFormat"),
+        NetStringStrict("m_pHelpText"),
+    ]),
+    NetMessageEx("Sv_CommandInfoRemove", "commandinfo-remove@netmsg.ddnet.org", [
+        NetStringStrict("m_pName"),
+    ]),
+    NetMessageEx("Sv_VoteOptionGroupStart", "sv-vote-option-group-start@netmsg.ddnet.org", []),
+    NetMessageEx("Sv_VoteOptionGroupEnd", "sv-vote-option-group-end@netmsg.ddnet.org", []),
+    NetMessageEx("Sv_CommandInfoGroupStart", "sv-commandinfo-group-start@netmsg.ddnet.org", []),
+    NetMessageEx("Sv_CommandInfoGroupEnd", "sv-commandinfo-group-end@netmsg.ddnet.org", []),
+    NetMessageEx("Sv_ChangeInfoCooldown", "change-info-cooldown@netmsg.ddnet.org", [
+        NetTick("m_WaitUntil"),
+    ]),
+]
+
 def fix_network(network, version):
     if version == VERSION_AUTO:
         version = VERSION_0_6
@@ -155,12 +186,40 @@ def fix_network(network, version):
             network.Messages[i].values.append(NetObjectMember("projectile", ("projectile",)))
         elif network.Messages[i].name in (IS_DDNET, IS_DDNET_LEGACY):
             network.Messages[i].values.append(NetIntAny("ddnet_version"))
+    if version == VERSION_DDNET_17_2_1:
+        network.Messages += DDNET_EX_MESSAGES_NEWER
     extra_msg_generation = set(v.type_name for m in network.Messages + network.System for v in m.values if isinstance(v, NetObjectMember))
     for i in range(len(network.Objects)):
         if network.Objects[i].name in extra_msg_generation:
             network.Objects[i].attributes.add("msg_encoding")
 
     network.Connless = []
+    if version == VERSION_0_5:
+        network.Connless += [
+            NetConnless("RequestList", "reqt", []),
+            NetConnless("List", "list", [
+                NetAddrs5("servers"),
+            ]),
+            NetConnless("RequestCount", "cou2", []),
+            NetConnless("Count", "siz2", [
+                NetBigEndianU16("count"),
+            ]),
+            NetConnless("RequestInfo", "gie2", [
+                NetU8("token"),
+            ]),
+            NetConnless("Info", "inf2", [
+                NetIntString("token"),
+                NetStringStrict("version"),
+                NetStringStrict("name"),
+                NetStringStrict("map"),
+                NetStringStrict("game_type"),
+                NetIntString("flags"),
+                NetIntString("progression"),
+                NetIntString("num_players"),
+                NetIntString("max_players"),
+                NetClients("clients"),
+            ]),
+        ]
     if version != VERSION_0_5:
         network.Connless += [
             NetConnless("RequestList", "req2", []),
@@ -233,6 +292,23 @@ def fix_network(network, version):
                     NetStringStrict("reserved"),
                     NetClients("clients"),
                 ]),
+                NetConnless("RequestInfo64", "fstd", [
+                    NetU8("token"),
+                ]),
+                NetConnless("Info64", "dtsf", [
+                    NetIntString("token"),
+                    NetStringStrict("version"),
+                    NetStringStrict("name"),
+                    NetStringStrict("map"),
+                    NetStringStrict("game_type"),
+                    NetIntString("flags"),
+                    NetIntString("num_players"),
+                    NetIntString("max_players"),
+                    NetIntString("num_clients"),
+                    NetIntString("max_clients"),
+                    NetIntString("offset"),
+                    NetClients("clients"),
+                ]),
             ]
         network.Connless += [
             NetConnless("Heartbeat", "bea2", [
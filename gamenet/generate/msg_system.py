@@ -64,6 +64,8 @@ SYSTEM_MSGS_DDNET_16_2 = SYSTEM_MSGS_DDNET_15_2_5 + [
 
 SYSTEM_MSGS_DDNET_17_2_1 = SYSTEM_MSGS_DDNET_16_2 + [
     ("redirect@ddnet.org", "redirect", "vital", "i:port"),
+    ("rcon-cmd-group-start@ddnet.org", "rcon_cmd_group_start", "vital", "i:length"),
+    ("rcon-cmd-group-end@ddnet.org", "rcon_cmd_group_end", "vital", ""),
 ]
 
 SYSTEM_MSGS_0_7 = [
@@ -145,6 +147,6 @@ SYSTEM_MSGS = {
     "ddnet-15.2.5": make_msgs(SYSTEM_MSGS_DDNET_15_2_5),
     "ddnet-16.2": make_msgs(SYSTEM_MSGS_DDNET_16_2),
     "ddnet-16.7.2": make_msgs(SYSTEM_MSGS_DDNET_16_2),
-    "ddnet-17.2.1": make_msgs(SYSTEM_MSGS_DDNET_16_2),
+    "ddnet-17.2.1": make_msgs(SYSTEM_MSGS_DDNET_17_2_1),
     "0.7": make_msgs(SYSTEM_MSGS_0_7),
 }
@@ -11,13 +11,14 @@ pub const PLAYERSTATE_PLAYING: i32 = 1;
 pub const PLAYERSTATE_IN_MENU: i32 = 2;
 pub const PLAYERSTATE_CHATTING: i32 = 3;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Playerstate {
-    Unknown,
+    PlayerstateUnknown,
     Playing,
     InMenu,
     Chatting,
+    Unknown(i32),
 }
 
 pub const EMOTE_NORMAL: i32 = 0;
@@ -27,8 +28,8 @@ pub const EMOTE_SURPRISE: i32 = 3;
 pub const EMOTE_ANGRY: i32 = 4;
 pub const EMOTE_BLINK: i32 = 5;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Emote {
     Normal,
     Pain,
@@ -36,6 +37,7 @@ pub enum Emote {
     Surprise,
     Angry,
     Blink,
+    Unknown(i32),
 }
 
 pub const POWERUP_HEALTH: i32 = 0;
@@ -43,13 +45,14 @@ pub const POWERUP_ARMOR: i32 = 1;
 pub const POWERUP_WEAPON: i32 = 2;
 pub const POWERUP_NINJA: i32 = 3;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Powerup {
     Health,
     Armor,
     Weapon,
     Ninja,
+    Unknown(i32),
 }
 
 pub const EMOTICON_V1: i32 = 0;
@@ -68,8 +71,8 @@ pub const EMOTICON_V13: i32 = 12;
 pub const EMOTICON_V14: i32 = 13;
 pub const EMOTICON_V15: i32 = 14;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Emoticon {
     V1,
     V2,
@@ -86,6 +89,7 @@ pub enum Emoticon {
     V13,
     V14,
     V15,
+    Unknown(i32),
 }
 
 pub const WEAPON_HAMMER: i32 = 0;
@@ -95,8 +99,8 @@ pub const WEAPON_GRENADE: i32 = 3;
 pub const WEAPON_RIFLE: i32 = 4;
 pub const WEAPON_NINJA: i32 = 5;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Weapon {
     Hammer,
     Pistol,
@@ -104,18 +108,20 @@ pub enum Weapon {
     Grenade,
     Rifle,
     Ninja,
+    Unknown(i32),
 }
 
 pub const TEAM_SPECTATORS: i32 = -1;
 pub const TEAM_RED: i32 = 0;
 pub const TEAM_BLUE: i32 = 1;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Team {
-    Spectators = -1,
+    Spectators,
     Red,
     Blue,
+    Unknown(i32),
 }
 
 pub const SOUND_GUN_FIRE: i32 = 0;
@@ -158,8 +164,8 @@ pub const SOUND_CTF_GRAB_PL: i32 = 36;
 pub const SOUND_CTF_GRAB_EN: i32 = 37;
 pub const SOUND_CTF_CAPTURE: i32 = 38;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Sound {
     GunFire,
     ShotgunFire,
@@ -200,42 +206,50 @@ pub enum Sound {
     CtfGrabPl,
     CtfGrabEn,
     CtfCapture,
+    Unknown(i32),
 }
 
 impl Playerstate {
-    pub fn from_i32(i: i32) -> Result<Playerstate, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Playerstate {
         use self::Playerstate::*;
-        Ok(match i {
-            PLAYERSTATE_UNKNOWN => Unknown,
+        match i {
+            PLAYERSTATE_UNKNOWN => PlayerstateUnknown,
             PLAYERSTATE_PLAYING => Playing,
             PLAYERSTATE_IN_MENU => InMenu,
             PLAYERSTATE_CHATTING => Chatting,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Playerstate::*;
         match self {
-            Unknown => PLAYERSTATE_UNKNOWN,
+            PlayerstateUnknown => PLAYERSTATE_UNKNOWN,
             Playing => PLAYERSTATE_PLAYING,
             InMenu => PLAYERSTATE_IN_MENU,
             Chatting => PLAYERSTATE_CHATTING,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Playerstate, IntOutOfRange> {
+        match self {
+            Playerstate::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Emote {
-    pub fn from_i32(i: i32) -> Result<Emote, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Emote {
         use self::Emote::*;
-        Ok(match i {
+        match i {
             EMOTE_NORMAL => Normal,
             EMOTE_PAIN => Pain,
             EMOTE_HAPPY => Happy,
             EMOTE_SURPRISE => Surprise,
             EMOTE_ANGRY => Angry,
             EMOTE_BLINK => Blink,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Emote::*;
@@ -246,20 +260,27 @@ impl Emote {
             Surprise => EMOTE_SURPRISE,
             Angry => EMOTE_ANGRY,
             Blink => EMOTE_BLINK,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Emote, IntOutOfRange> {
+        match self {
+            Emote::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Powerup {
-    pub fn from_i32(i: i32) -> Result<Powerup, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Powerup {
         use self::Powerup::*;
-        Ok(match i {
+        match i {
             POWERUP_HEALTH => Health,
             POWERUP_ARMOR => Armor,
             POWERUP_WEAPON => Weapon,
             POWERUP_NINJA => Ninja,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Powerup::*;
@@ -268,14 +289,21 @@ impl Powerup {
             Armor => POWERUP_ARMOR,
             Weapon => POWERUP_WEAPON,
             Ninja => POWERUP_NINJA,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Powerup, IntOutOfRange> {
+        match self {
+            Powerup::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Emoticon {
-    pub fn from_i32(i: i32) -> Result<Emoticon, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Emoticon {
         use self::Emoticon::*;
-        Ok(match i {
+        match i {
             EMOTICON_V1 => V1,
             EMOTICON_V2 => V2,
             EMOTICON_V3 => V3,
@@ -291,8 +319,8 @@ impl Emoticon {
             EMOTICON_V13 => V13,
             EMOTICON_V14 => V14,
             EMOTICON_V15 => V15,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Emoticon::*;
@@ -312,22 +340,29 @@ impl Emoticon {
             V13 => EMOTICON_V13,
             V14 => EMOTICON_V14,
             V15 => EMOTICON_V15,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Emoticon, IntOutOfRange> {
+        match self {
+            Emoticon::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Weapon {
-    pub fn from_i32(i: i32) -> Result<Weapon, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Weapon {
         use self::Weapon::*;
-        Ok(match i {
+        match i {
             WEAPON_HAMMER => Hammer,
             WEAPON_PISTOL => Pistol,
             WEAPON_SHOTGUN => Shotgun,
             WEAPON_GRENADE => Grenade,
             WEAPON_RIFLE => Rifle,
             WEAPON_NINJA => Ninja,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Weapon::*;
@@ -338,19 +373,26 @@ impl Weapon {
             Grenade => WEAPON_GRENADE,
             Rifle => WEAPON_RIFLE,
             Ninja => WEAPON_NINJA,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Weapon, IntOutOfRange> {
+        match self {
+            Weapon::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Team {
-    pub fn from_i32(i: i32) -> Result<Team, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Team {
         use self::Team::*;
-        Ok(match i {
+        match i {
             TEAM_SPECTATORS => Spectators,
             TEAM_RED => Red,
             TEAM_BLUE => Blue,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Team::*;
@@ -358,14 +400,21 @@ impl Team {
             Spectators => TEAM_SPECTATORS,
             Red => TEAM_RED,
             Blue => TEAM_BLUE,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Team, IntOutOfRange> {
+        match self {
+            Team::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Sound {
-    pub fn from_i32(i: i32) -> Result<Sound, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Sound {
         use self::Sound::*;
-        Ok(match i {
+        match i {
             SOUND_GUN_FIRE => GunFire,
             SOUND_SHOTGUN_FIRE => ShotgunFire,
             SOUND_GRENADE_FIRE => GrenadeFire,
@@ -405,8 +454,8 @@ impl Sound {
             SOUND_CTF_GRAB_PL => CtfGrabPl,
             SOUND_CTF_GRAB_EN => CtfGrabEn,
             SOUND_CTF_CAPTURE => CtfCapture,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Sound::*;
@@ -450,6 +499,13 @@ impl Sound {
             CtfGrabPl => SOUND_CTF_GRAB_PL,
             CtfGrabEn => SOUND_CTF_GRAB_EN,
             CtfCapture => SOUND_CTF_CAPTURE,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Sound, IntOutOfRange> {
+        match self {
+            Sound::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
@@ -3,6 +3,11 @@ extern crate buffer;
 extern crate common;
 extern crate gamenet_common;
 extern crate packer;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde_derive;
 extern crate uuid;
 extern crate warn;
 
@@ -15,4 +20,8 @@ pub mod snap_obj;
 
 pub use gamenet_common::error;
 pub use gamenet_common::error::Error;
+pub use gamenet_common::version::Protocol;
+pub use gamenet_common::version::Version;
 pub use snap_obj::SnapObj;
+
+pub const PROTOCOL: Protocol = Protocol::Teeworlds05;
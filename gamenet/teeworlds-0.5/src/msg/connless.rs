@@ -1,22 +1,34 @@
 use buffer::CapacityError;
+use common::num::BeU16;
 use common::pretty;
+use common::warn::Located;
+use common::warn::strip_location;
+use error;
+use error::EncodeError;
 use error::Error;
+use gamenet_common::msg::AddrPackedSliceExt;
+use gamenet_common::msg::int_from_string;
 use gamenet_common::msg::string_from_int;
 use packer::Packer;
 use packer::Unpacker;
 use packer::Warning;
+use packer::sanitize;
 use packer::with_packer;
 use std::fmt;
+use super::Addr5Packed;
+use super::ClientsData;
+use warn::Ignore;
 use warn::Warn;
+use warn::wrap;
 
-impl Connless {
-    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Connless, Error> {
+impl<'a> Connless<'a> {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Connless<'a>, Error> {
         let id = _p.read_raw(8)?;
         let connless_id = [id[0], id[1], id[2], id[3], id[4], id[5], id[6], id[7]];
         Connless::decode_connless(warn, connless_id, _p)
     }
     pub fn encode<'d, 's>(&self, mut p: Packer<'d, 's>)
-        -> Result<&'d [u8], CapacityError>
+        -> Result<&'d [u8], EncodeError>
     {
         p.write_raw(&self.connless_id())?;
         with_packer(&mut p, |p| self.encode_connless(p))?;
@@ -59,39 +71,73 @@ impl<'a> fmt::Debug for Client<'a> {
 
 pub const INFO_FLAG_PASSWORD: i32 = 1;
 
+pub const REQUEST_LIST: &'static [u8; 8] = b"\xff\xff\xff\xffreqt";
+pub const LIST: &'static [u8; 8] = b"\xff\xff\xff\xfflist";
+pub const REQUEST_COUNT: &'static [u8; 8] = b"\xff\xff\xff\xffcou2";
+pub const COUNT: &'static [u8; 8] = b"\xff\xff\xff\xffsiz2";
+pub const REQUEST_INFO: &'static [u8; 8] = b"\xff\xff\xff\xffgie2";
+pub const INFO: &'static [u8; 8] = b"\xff\xff\xff\xffinf2";
 pub const FORWARD_CHECK: &'static [u8; 8] = b"\xff\xff\xff\xfffw??";
 pub const FORWARD_RESPONSE: &'static [u8; 8] = b"\xff\xff\xff\xfffw!!";
 pub const FORWARD_OK: &'static [u8; 8] = b"\xff\xff\xff\xfffwok";
 pub const FORWARD_ERROR: &'static [u8; 8] = b"\xff\xff\xff\xfffwer";
 
 #[derive(Clone, Copy)]
-pub enum Connless {
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum Connless<'a> {
+    RequestList(RequestList),
+    List(List<'a>),
+    RequestCount(RequestCount),
+    Count(Count),
+    RequestInfo(RequestInfo),
+    Info(Info<'a>),
     ForwardCheck(ForwardCheck),
     ForwardResponse(ForwardResponse),
     ForwardOk(ForwardOk),
     ForwardError(ForwardError),
 }
 
-impl Connless {
-    pub fn decode_connless<W: Warn<Warning>>(warn: &mut W, connless_id: [u8; 8], _p: &mut Unpacker) -> Result<Connless, Error> {
+impl<'a> Connless<'a> {
+    pub fn decode_connless<W: Warn<Warning>>(warn: &mut W, connless_id: [u8; 8], _p: &mut Unpacker<'a>) -> Result<Connless<'a>, Error> {
+        Connless::decode_connless_located(&mut strip_location(warn), connless_id, _p)
+    }
+    pub fn decode_connless_located<W: Warn<Located<Warning>>>(warn: &mut W, connless_id: [u8; 8], _p: &mut Unpacker<'a>) -> Result<Connless<'a>, Error> {
         Ok(match &connless_id {
-            FORWARD_CHECK => Connless::ForwardCheck(ForwardCheck::decode(warn, _p)?),
-            FORWARD_RESPONSE => Connless::ForwardResponse(ForwardResponse::decode(warn, _p)?),
-            FORWARD_OK => Connless::ForwardOk(ForwardOk::decode(warn, _p)?),
-            FORWARD_ERROR => Connless::ForwardError(ForwardError::decode(warn, _p)?),
+            REQUEST_LIST => Connless::RequestList(RequestList::decode_located(warn, _p)?),
+            LIST => Connless::List(List::decode_located(warn, _p)?),
+            REQUEST_COUNT => Connless::RequestCount(RequestCount::decode_located(warn, _p)?),
+            COUNT => Connless::Count(Count::decode_located(warn, _p)?),
+            REQUEST_INFO => Connless::RequestInfo(RequestInfo::decode_located(warn, _p)?),
+            INFO => Connless::Info(Info::decode_located(warn, _p)?),
+            FORWARD_CHECK => Connless::ForwardCheck(ForwardCheck::decode_located(warn, _p)?),
+            FORWARD_RESPONSE => Connless::ForwardResponse(ForwardResponse::decode_located(warn, _p)?),
+            FORWARD_OK => Connless::ForwardOk(ForwardOk::decode_located(warn, _p)?),
+            FORWARD_ERROR => Connless::ForwardError(ForwardError::decode_located(warn, _p)?),
             _ => return Err(Error::UnknownId),
         })
     }
     pub fn connless_id(&self) -> [u8; 8] {
         match *self {
+            Connless::RequestList(_) => *REQUEST_LIST,
+            Connless::List(_) => *LIST,
+            Connless::RequestCount(_) => *REQUEST_COUNT,
+            Connless::Count(_) => *COUNT,
+            Connless::RequestInfo(_) => *REQUEST_INFO,
+            Connless::Info(_) => *INFO,
             Connless::ForwardCheck(_) => *FORWARD_CHECK,
             Connless::ForwardResponse(_) => *FORWARD_RESPONSE,
             Connless::ForwardOk(_) => *FORWARD_OK,
             Connless::ForwardError(_) => *FORWARD_ERROR,
         }
     }
-    pub fn encode_connless<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode_connless<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         match *self {
+            Connless::RequestList(ref i) => i.encode(p),
+            Connless::List(ref i) => i.encode(p),
+            Connless::RequestCount(ref i) => i.encode(p),
+            Connless::Count(ref i) => i.encode(p),
+            Connless::RequestInfo(ref i) => i.encode(p),
+            Connless::Info(ref i) => i.encode(p),
             Connless::ForwardCheck(ref i) => i.encode(p),
             Connless::ForwardResponse(ref i) => i.encode(p),
             Connless::ForwardOk(ref i) => i.encode(p),
@@ -100,9 +146,15 @@ impl Connless {
     }
 }
 
-impl fmt::Debug for Connless {
+impl<'a> fmt::Debug for Connless<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
+            Connless::RequestList(ref i) => i.fmt(f),
+            Connless::List(ref i) => i.fmt(f),
+            Connless::RequestCount(ref i) => i.fmt(f),
+            Connless::Count(ref i) => i.fmt(f),
+            Connless::RequestInfo(ref i) => i.fmt(f),
+            Connless::Info(ref i) => i.fmt(f),
             Connless::ForwardCheck(ref i) => i.fmt(f),
             Connless::ForwardResponse(ref i) => i.fmt(f),
             Connless::ForwardOk(ref i) => i.fmt(f),
@@ -111,48 +163,368 @@ impl fmt::Debug for Connless {
     }
 }
 
-impl From<ForwardCheck> for Connless {
-    fn from(i: ForwardCheck) -> Connless {
+impl<'a> From<RequestList> for Connless<'a> {
+    fn from(i: RequestList) -> Connless<'a> {
+        Connless::RequestList(i)
+    }
+}
+
+impl<'a> From<List<'a>> for Connless<'a> {
+    fn from(i: List<'a>) -> Connless<'a> {
+        Connless::List(i)
+    }
+}
+
+impl<'a> From<RequestCount> for Connless<'a> {
+    fn from(i: RequestCount) -> Connless<'a> {
+        Connless::RequestCount(i)
+    }
+}
+
+impl<'a> From<Count> for Connless<'a> {
+    fn from(i: Count) -> Connless<'a> {
+        Connless::Count(i)
+    }
+}
+
+impl<'a> From<RequestInfo> for Connless<'a> {
+    fn from(i: RequestInfo) -> Connless<'a> {
+        Connless::RequestInfo(i)
+    }
+}
+
+impl<'a> From<Info<'a>> for Connless<'a> {
+    fn from(i: Info<'a>) -> Connless<'a> {
+        Connless::Info(i)
+    }
+}
+
+impl<'a> From<ForwardCheck> for Connless<'a> {
+    fn from(i: ForwardCheck) -> Connless<'a> {
         Connless::ForwardCheck(i)
     }
 }
 
-impl From<ForwardResponse> for Connless {
-    fn from(i: ForwardResponse) -> Connless {
+impl<'a> From<ForwardResponse> for Connless<'a> {
+    fn from(i: ForwardResponse) -> Connless<'a> {
         Connless::ForwardResponse(i)
     }
 }
 
-impl From<ForwardOk> for Connless {
-    fn from(i: ForwardOk) -> Connless {
+impl<'a> From<ForwardOk> for Connless<'a> {
+    fn from(i: ForwardOk) -> Connless<'a> {
         Connless::ForwardOk(i)
     }
 }
 
-impl From<ForwardError> for Connless {
-    fn from(i: ForwardError) -> Connless {
+impl<'a> From<ForwardError> for Connless<'a> {
+    fn from(i: ForwardError) -> Connless<'a> {
         Connless::ForwardError(i)
     }
 }
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RequestList;
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct List<'a> {
+    pub servers: &'a [Addr5Packed],
+}
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RequestCount;
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Count {
+    pub count: u16,
+}
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RequestInfo {
+    pub token: u8,
+}
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Info<'a> {
+    pub token: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
+    pub version: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
+    pub name: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
+    pub map: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
+    pub game_type: &'a [u8],
+    pub flags: i32,
+    pub progression: i32,
+    pub num_players: i32,
+    pub max_players: i32,
+    pub clients: ClientsData<'a>,
+}
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ForwardCheck;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ForwardResponse;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ForwardOk;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ForwardError;
 
+impl RequestList {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestList, Error> {
+        RequestList::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestList, Error> {
+        error::finish(&mut _p.locate(warn, "RequestList"), _p, Ok(RequestList))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        Ok(_p.written())
+    }
+}
+impl fmt::Debug for RequestList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RequestList")
+            .finish()
+    }
+}
+
+impl<'a> List<'a> {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<List<'a>, Error> {
+        List::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<List<'a>, Error> {
+        let result = List::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "List"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<List<'a>, Error> {
+        Ok(List {
+            servers: AddrPackedSliceExt::from_bytes(wrap(&mut _p.locate(warn, "List.servers")), _p.read_rest()?),
+        })
+    }
+    pub fn new(servers: &'a [Addr5Packed]) -> Result<List<'a>, Error> {
+        let result = List {
+            servers,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        _p.write_rest(self.servers.as_bytes())?;
+        Ok(_p.written())
+    }
+}
+impl<'a> fmt::Debug for List<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("List")
+            .field("servers", &self.servers)
+            .finish()
+    }
+}
+
+impl RequestCount {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestCount, Error> {
+        RequestCount::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestCount, Error> {
+        error::finish(&mut _p.locate(warn, "RequestCount"), _p, Ok(RequestCount))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        Ok(_p.written())
+    }
+}
+impl fmt::Debug for RequestCount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RequestCount")
+            .finish()
+    }
+}
+
+impl Count {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Count, Error> {
+        Count::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Count, Error> {
+        let result = Count::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Count"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker) -> Result<Count, Error> {
+        Ok(Count {
+            count: { let s = _p.read_raw(2)?; BeU16::from_bytes(&[s[0], s[1]]).to_u16() },
+        })
+    }
+    pub fn new(count: u16) -> Result<Count, Error> {
+        let result = Count {
+            count,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        _p.write_raw(BeU16::from_u16(self.count).as_bytes())?;
+        Ok(_p.written())
+    }
+}
+impl fmt::Debug for Count {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Count")
+            .field("count", &self.count)
+            .finish()
+    }
+}
+
+impl RequestInfo {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestInfo, Error> {
+        RequestInfo::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestInfo, Error> {
+        let result = RequestInfo::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RequestInfo"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker) -> Result<RequestInfo, Error> {
+        Ok(RequestInfo {
+            token: _p.read_raw(1)?[0],
+        })
+    }
+    pub fn new(token: u8) -> Result<RequestInfo, Error> {
+        let result = RequestInfo {
+            token,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        _p.write_raw(&[self.token])?;
+        Ok(_p.written())
+    }
+}
+impl fmt::Debug for RequestInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RequestInfo")
+            .field("token", &self.token)
+            .finish()
+    }
+}
+
+impl<'a> Info<'a> {
+    pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info<'a>, Error> {
+        Info::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info<'a>, Error> {
+        let result = Info::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Info"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info<'a>, Error> {
+        Ok(Info {
+            token: int_from_string(_p.read_string()?)?,
+            version: sanitize(&mut _p.locate(warn, "Info.version"), _p.read_string()?)?,
+            name: sanitize(&mut _p.locate(warn, "Info.name"), _p.read_string()?)?,
+            map: sanitize(&mut _p.locate(warn, "Info.map"), _p.read_string()?)?,
+            game_type: sanitize(&mut _p.locate(warn, "Info.game_type"), _p.read_string()?)?,
+            flags: int_from_string(_p.read_string()?)?,
+            progression: int_from_string(_p.read_string()?)?,
+            num_players: int_from_string(_p.read_string()?)?,
+            max_players: int_from_string(_p.read_string()?)?,
+            clients: ClientsData::from_bytes(_p.read_rest()?),
+        })
+    }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(token: i32, version: &'a [u8], name: &'a [u8], map: &'a [u8], game_type: &'a [u8], flags: i32, progression: i32, num_players: i32, max_players: i32, clients: ClientsData<'a>) -> Result<Info<'a>, Error> {
+        let result = Info {
+            token,
+            version,
+            name,
+            map,
+            game_type,
+            flags,
+            progression,
+            num_players,
+            max_players,
+            clients,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.version)?;
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.map)?;
+        sanitize(&mut Ignore, self.game_type)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.version)?;
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.map)?;
+        sanitize(&mut Ignore, self.game_type)?;
+        _p.write_string(&string_from_int(self.token))?;
+        _p.write_string(self.version)?;
+        _p.write_string(self.name)?;
+        _p.write_string(self.map)?;
+        _p.write_string(self.game_type)?;
+        _p.write_string(&string_from_int(self.flags))?;
+        _p.write_string(&string_from_int(self.progression))?;
+        _p.write_string(&string_from_int(self.num_players))?;
+        _p.write_string(&string_from_int(self.max_players))?;
+        _p.write_rest(self.clients.as_bytes())?;
+        Ok(_p.written())
+    }
+}
+impl<'a> fmt::Debug for Info<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Info")
+            .field("token", &self.token)
+            .field("version", &pretty::Bytes::new(&self.version))
+            .field("name", &pretty::Bytes::new(&self.name))
+            .field("map", &pretty::Bytes::new(&self.map))
+            .field("game_type", &pretty::Bytes::new(&self.game_type))
+            .field("flags", &self.flags)
+            .field("progression", &self.progression)
+            .field("num_players", &self.num_players)
+            .field("max_players", &self.max_players)
+            .field("clients", &self.clients)
+            .finish()
+    }
+}
+
 impl ForwardCheck {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardCheck, Error> {
-        let result = Ok(ForwardCheck);
-        _p.finish(warn);
-        result
+        ForwardCheck::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardCheck, Error> {
+        error::finish(&mut _p.locate(warn, "ForwardCheck"), _p, Ok(ForwardCheck))
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -165,11 +537,15 @@ impl fmt::Debug for ForwardCheck {
 
 impl ForwardResponse {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardResponse, Error> {
-        let result = Ok(ForwardResponse);
-        _p.finish(warn);
-        result
+        ForwardResponse::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardResponse, Error> {
+        error::finish(&mut _p.locate(warn, "ForwardResponse"), _p, Ok(ForwardResponse))
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -182,11 +558,15 @@ impl fmt::Debug for ForwardResponse {
 
 impl ForwardOk {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardOk, Error> {
-        let result = Ok(ForwardOk);
-        _p.finish(warn);
-        result
+        ForwardOk::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardOk, Error> {
+        error::finish(&mut _p.locate(warn, "ForwardOk"), _p, Ok(ForwardOk))
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -199,11 +579,15 @@ impl fmt::Debug for ForwardOk {
 
 impl ForwardError {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardError, Error> {
-        let result = Ok(ForwardError);
-        _p.finish(warn);
-        result
+        ForwardError::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardError, Error> {
+        error::finish(&mut _p.locate(warn, "ForwardError"), _p, Ok(ForwardError))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
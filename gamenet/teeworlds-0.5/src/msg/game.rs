@@ -1,18 +1,22 @@
-use buffer::CapacityError;
 use common::pretty;
+use common::warn::Located;
+use common::warn::strip_location;
 use enums;
+use error;
+use error::EncodeError;
 use error::Error;
 use packer::Packer;
 use packer::Unpacker;
 use packer::Warning;
 use packer::in_range;
+use packer::max_length;
 use packer::sanitize;
 use packer::to_bool;
 use packer::with_packer;
 use std::fmt;
 use super::MessageId;
 use super::SystemOrGame;
-use warn::Panic;
+use warn::Ignore;
 use warn::Warn;
 
 pub use gamenet_common::msg::TuneParam;
@@ -28,7 +32,7 @@ impl<'a> Game<'a> {
         }
     }
     pub fn encode<'d, 's>(&self, mut p: Packer<'d, 's>)
-        -> Result<&'d [u8], CapacityError>
+        -> Result<&'d [u8], EncodeError>
     {
         with_packer(&mut p, |p| SystemOrGame::Game(self.msg_id()).encode_id(p))?;
         with_packer(&mut p, |p| self.encode_msg(p))?;
@@ -60,6 +64,7 @@ pub const CL_VOTE: i32 = 21;
 pub const CL_CALL_VOTE: i32 = 22;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub enum Game<'a> {
     SvMotd(SvMotd<'a>),
     SvBroadcast(SvBroadcast<'a>),
@@ -87,30 +92,33 @@ pub enum Game<'a> {
 
 impl<'a> Game<'a> {
     pub fn decode_msg<W: Warn<Warning>>(warn: &mut W, msg_id: MessageId, _p: &mut Unpacker<'a>) -> Result<Game<'a>, Error> {
+        Game::decode_msg_located(&mut strip_location(warn), msg_id, _p)
+    }
+    pub fn decode_msg_located<W: Warn<Located<Warning>>>(warn: &mut W, msg_id: MessageId, _p: &mut Unpacker<'a>) -> Result<Game<'a>, Error> {
         use self::MessageId::*;
         Ok(match msg_id {
-            Ordinal(SV_MOTD) => Game::SvMotd(SvMotd::decode(warn, _p)?),
-            Ordinal(SV_BROADCAST) => Game::SvBroadcast(SvBroadcast::decode(warn, _p)?),
-            Ordinal(SV_CHAT) => Game::SvChat(SvChat::decode(warn, _p)?),
-            Ordinal(SV_KILL_MSG) => Game::SvKillMsg(SvKillMsg::decode(warn, _p)?),
-            Ordinal(SV_SOUND_GLOBAL) => Game::SvSoundGlobal(SvSoundGlobal::decode(warn, _p)?),
-            Ordinal(SV_TUNE_PARAMS) => Game::SvTuneParams(SvTuneParams::decode(warn, _p)?),
-            Ordinal(SV_EXTRA_PROJECTILE) => Game::SvExtraProjectile(SvExtraProjectile::decode(warn, _p)?),
-            Ordinal(SV_READY_TO_ENTER) => Game::SvReadyToEnter(SvReadyToEnter::decode(warn, _p)?),
-            Ordinal(SV_WEAPON_PICKUP) => Game::SvWeaponPickup(SvWeaponPickup::decode(warn, _p)?),
-            Ordinal(SV_EMOTICON) => Game::SvEmoticon(SvEmoticon::decode(warn, _p)?),
-            Ordinal(SV_VOTE_CLEAR_OPTIONS) => Game::SvVoteClearOptions(SvVoteClearOptions::decode(warn, _p)?),
-            Ordinal(SV_VOTE_OPTION) => Game::SvVoteOption(SvVoteOption::decode(warn, _p)?),
-            Ordinal(SV_VOTE_SET) => Game::SvVoteSet(SvVoteSet::decode(warn, _p)?),
-            Ordinal(SV_VOTE_STATUS) => Game::SvVoteStatus(SvVoteStatus::decode(warn, _p)?),
-            Ordinal(CL_SAY) => Game::ClSay(ClSay::decode(warn, _p)?),
-            Ordinal(CL_SET_TEAM) => Game::ClSetTeam(ClSetTeam::decode(warn, _p)?),
-            Ordinal(CL_START_INFO) => Game::ClStartInfo(ClStartInfo::decode(warn, _p)?),
-            Ordinal(CL_CHANGE_INFO) => Game::ClChangeInfo(ClChangeInfo::decode(warn, _p)?),
-            Ordinal(CL_KILL) => Game::ClKill(ClKill::decode(warn, _p)?),
-            Ordinal(CL_EMOTICON) => Game::ClEmoticon(ClEmoticon::decode(warn, _p)?),
-            Ordinal(CL_VOTE) => Game::ClVote(ClVote::decode(warn, _p)?),
-            Ordinal(CL_CALL_VOTE) => Game::ClCallVote(ClCallVote::decode(warn, _p)?),
+            Ordinal(SV_MOTD) => Game::SvMotd(SvMotd::decode_located(warn, _p)?),
+            Ordinal(SV_BROADCAST) => Game::SvBroadcast(SvBroadcast::decode_located(warn, _p)?),
+            Ordinal(SV_CHAT) => Game::SvChat(SvChat::decode_located(warn, _p)?),
+            Ordinal(SV_KILL_MSG) => Game::SvKillMsg(SvKillMsg::decode_located(warn, _p)?),
+            Ordinal(SV_SOUND_GLOBAL) => Game::SvSoundGlobal(SvSoundGlobal::decode_located(warn, _p)?),
+            Ordinal(SV_TUNE_PARAMS) => Game::SvTuneParams(SvTuneParams::decode_located(warn, _p)?),
+            Ordinal(SV_EXTRA_PROJECTILE) => Game::SvExtraProjectile(SvExtraProjectile::decode_located(warn, _p)?),
+            Ordinal(SV_READY_TO_ENTER) => Game::SvReadyToEnter(SvReadyToEnter::decode_located(warn, _p)?),
+            Ordinal(SV_WEAPON_PICKUP) => Game::SvWeaponPickup(SvWeaponPickup::decode_located(warn, _p)?),
+            Ordinal(SV_EMOTICON) => Game::SvEmoticon(SvEmoticon::decode_located(warn, _p)?),
+            Ordinal(SV_VOTE_CLEAR_OPTIONS) => Game::SvVoteClearOptions(SvVoteClearOptions::decode_located(warn, _p)?),
+            Ordinal(SV_VOTE_OPTION) => Game::SvVoteOption(SvVoteOption::decode_located(warn, _p)?),
+            Ordinal(SV_VOTE_SET) => Game::SvVoteSet(SvVoteSet::decode_located(warn, _p)?),
+            Ordinal(SV_VOTE_STATUS) => Game::SvVoteStatus(SvVoteStatus::decode_located(warn, _p)?),
+            Ordinal(CL_SAY) => Game::ClSay(ClSay::decode_located(warn, _p)?),
+            Ordinal(CL_SET_TEAM) => Game::ClSetTeam(ClSetTeam::decode_located(warn, _p)?),
+            Ordinal(CL_START_INFO) => Game::ClStartInfo(ClStartInfo::decode_located(warn, _p)?),
+            Ordinal(CL_CHANGE_INFO) => Game::ClChangeInfo(ClChangeInfo::decode_located(warn, _p)?),
+            Ordinal(CL_KILL) => Game::ClKill(ClKill::decode_located(warn, _p)?),
+            Ordinal(CL_EMOTICON) => Game::ClEmoticon(ClEmoticon::decode_located(warn, _p)?),
+            Ordinal(CL_VOTE) => Game::ClVote(ClVote::decode_located(warn, _p)?),
+            Ordinal(CL_CALL_VOTE) => Game::ClCallVote(ClCallVote::decode_located(warn, _p)?),
             _ => return Err(Error::UnknownId),
         })
     }
@@ -140,7 +148,33 @@ impl<'a> Game<'a> {
             Game::ClCallVote(_) => MessageId::from(CL_CALL_VOTE),
         }
     }
-    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        match *self {
+            Game::SvMotd(ref i) => i.validate(),
+            Game::SvBroadcast(ref i) => i.validate(),
+            Game::SvChat(ref i) => i.validate(),
+            Game::SvKillMsg(ref i) => i.validate(),
+            Game::SvSoundGlobal(ref i) => i.validate(),
+            Game::SvTuneParams(ref i) => i.validate(),
+            Game::SvExtraProjectile(ref i) => i.validate(),
+            Game::SvReadyToEnter(ref i) => i.validate(),
+            Game::SvWeaponPickup(ref i) => i.validate(),
+            Game::SvEmoticon(ref i) => i.validate(),
+            Game::SvVoteClearOptions(ref i) => i.validate(),
+            Game::SvVoteOption(ref i) => i.validate(),
+            Game::SvVoteSet(ref i) => i.validate(),
+            Game::SvVoteStatus(ref i) => i.validate(),
+            Game::ClSay(ref i) => i.validate(),
+            Game::ClSetTeam(ref i) => i.validate(),
+            Game::ClStartInfo(ref i) => i.validate(),
+            Game::ClChangeInfo(ref i) => i.validate(),
+            Game::ClKill(ref i) => i.validate(),
+            Game::ClEmoticon(ref i) => i.validate(),
+            Game::ClVote(ref i) => i.validate(),
+            Game::ClCallVote(ref i) => i.validate(),
+        }
+    }
+    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         match *self {
             Game::SvMotd(ref i) => i.encode(p),
             Game::SvBroadcast(ref i) => i.encode(p),
@@ -328,24 +362,134 @@ impl<'a> From<ClCallVote<'a>> for Game<'a> {
         Game::ClCallVote(i)
     }
 }
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GameOwned {
+    SvMotd(SvMotdOwned),
+    SvBroadcast(SvBroadcastOwned),
+    SvChat(SvChatOwned),
+    SvKillMsg(SvKillMsg),
+    SvSoundGlobal(SvSoundGlobal),
+    SvTuneParams(SvTuneParams),
+    SvExtraProjectile(SvExtraProjectile),
+    SvReadyToEnter(SvReadyToEnter),
+    SvWeaponPickup(SvWeaponPickup),
+    SvEmoticon(SvEmoticon),
+    SvVoteClearOptions(SvVoteClearOptions),
+    SvVoteOption(SvVoteOptionOwned),
+    SvVoteSet(SvVoteSetOwned),
+    SvVoteStatus(SvVoteStatus),
+    ClSay(ClSayOwned),
+    ClSetTeam(ClSetTeam),
+    ClStartInfo(ClStartInfoOwned),
+    ClChangeInfo(ClChangeInfoOwned),
+    ClKill(ClKill),
+    ClEmoticon(ClEmoticon),
+    ClVote(ClVote),
+    ClCallVote(ClCallVoteOwned),
+}
+
+impl<'a> Game<'a> {
+    pub fn to_owned(&self) -> GameOwned {
+        match *self {
+            Game::SvMotd(ref i) => GameOwned::SvMotd(i.to_owned()),
+            Game::SvBroadcast(ref i) => GameOwned::SvBroadcast(i.to_owned()),
+            Game::SvChat(ref i) => GameOwned::SvChat(i.to_owned()),
+            Game::SvKillMsg(i) => GameOwned::SvKillMsg(i),
+            Game::SvSoundGlobal(i) => GameOwned::SvSoundGlobal(i),
+            Game::SvTuneParams(i) => GameOwned::SvTuneParams(i),
+            Game::SvExtraProjectile(i) => GameOwned::SvExtraProjectile(i),
+            Game::SvReadyToEnter(i) => GameOwned::SvReadyToEnter(i),
+            Game::SvWeaponPickup(i) => GameOwned::SvWeaponPickup(i),
+            Game::SvEmoticon(i) => GameOwned::SvEmoticon(i),
+            Game::SvVoteClearOptions(i) => GameOwned::SvVoteClearOptions(i),
+            Game::SvVoteOption(ref i) => GameOwned::SvVoteOption(i.to_owned()),
+            Game::SvVoteSet(ref i) => GameOwned::SvVoteSet(i.to_owned()),
+            Game::SvVoteStatus(i) => GameOwned::SvVoteStatus(i),
+            Game::ClSay(ref i) => GameOwned::ClSay(i.to_owned()),
+            Game::ClSetTeam(i) => GameOwned::ClSetTeam(i),
+            Game::ClStartInfo(ref i) => GameOwned::ClStartInfo(i.to_owned()),
+            Game::ClChangeInfo(ref i) => GameOwned::ClChangeInfo(i.to_owned()),
+            Game::ClKill(i) => GameOwned::ClKill(i),
+            Game::ClEmoticon(i) => GameOwned::ClEmoticon(i),
+            Game::ClVote(i) => GameOwned::ClVote(i),
+            Game::ClCallVote(ref i) => GameOwned::ClCallVote(i.to_owned()),
+        }
+    }
+}
+
+impl GameOwned {
+    pub fn as_ref(&self) -> Game<'_> {
+        match *self {
+            GameOwned::SvMotd(ref i) => Game::SvMotd(i.as_ref()),
+            GameOwned::SvBroadcast(ref i) => Game::SvBroadcast(i.as_ref()),
+            GameOwned::SvChat(ref i) => Game::SvChat(i.as_ref()),
+            GameOwned::SvKillMsg(i) => Game::SvKillMsg(i),
+            GameOwned::SvSoundGlobal(i) => Game::SvSoundGlobal(i),
+            GameOwned::SvTuneParams(i) => Game::SvTuneParams(i),
+            GameOwned::SvExtraProjectile(i) => Game::SvExtraProjectile(i),
+            GameOwned::SvReadyToEnter(i) => Game::SvReadyToEnter(i),
+            GameOwned::SvWeaponPickup(i) => Game::SvWeaponPickup(i),
+            GameOwned::SvEmoticon(i) => Game::SvEmoticon(i),
+            GameOwned::SvVoteClearOptions(i) => Game::SvVoteClearOptions(i),
+            GameOwned::SvVoteOption(ref i) => Game::SvVoteOption(i.as_ref()),
+            GameOwned::SvVoteSet(ref i) => Game::SvVoteSet(i.as_ref()),
+            GameOwned::SvVoteStatus(i) => Game::SvVoteStatus(i),
+            GameOwned::ClSay(ref i) => Game::ClSay(i.as_ref()),
+            GameOwned::ClSetTeam(i) => Game::ClSetTeam(i),
+            GameOwned::ClStartInfo(ref i) => Game::ClStartInfo(i.as_ref()),
+            GameOwned::ClChangeInfo(ref i) => Game::ClChangeInfo(i.as_ref()),
+            GameOwned::ClKill(i) => Game::ClKill(i),
+            GameOwned::ClEmoticon(i) => Game::ClEmoticon(i),
+            GameOwned::ClVote(i) => Game::ClVote(i),
+            GameOwned::ClCallVote(ref i) => Game::ClCallVote(i.as_ref()),
+        }
+    }
+    pub fn msg_id(&self) -> MessageId {
+        self.as_ref().msg_id()
+    }
+    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        self.as_ref().encode_msg(p)
+    }
+}
+
+impl fmt::Debug for GameOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> From<Game<'a>> for GameOwned {
+    fn from(i: Game<'a>) -> GameOwned {
+        i.to_owned()
+    }
+}
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvMotd<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub message: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvBroadcast<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub message: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvChat<'a> {
     pub team: bool,
     pub client_id: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub message: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvKillMsg {
     pub killer: i32,
     pub victim: i32,
@@ -354,11 +498,13 @@ pub struct SvKillMsg {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvSoundGlobal {
     pub sound_id: enums::Sound,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvTuneParams {
     pub ground_control_speed: TuneParam,
     pub ground_control_accel: TuneParam,
@@ -396,40 +542,51 @@ pub struct SvTuneParams {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvExtraProjectile {
     pub projectile: ::snap_obj::Projectile,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvReadyToEnter;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvWeaponPickup {
     pub weapon: enums::Weapon,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvEmoticon {
     pub client_id: i32,
     pub emoticon: enums::Emoticon,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvVoteClearOptions;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvVoteOption<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub command: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvVoteSet<'a> {
     pub timeout: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub description: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub command: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvVoteStatus {
     pub yes: i32,
     pub no: i32,
@@ -438,19 +595,25 @@ pub struct SvVoteStatus {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ClSay<'a> {
     pub team: bool,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub message: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClSetTeam {
     pub team: enums::Team,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ClStartInfo<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub skin: &'a [u8],
     pub use_custom_color: bool,
     pub color_body: i32,
@@ -458,8 +621,11 @@ pub struct ClStartInfo<'a> {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ClChangeInfo<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub skin: &'a [u8],
     pub use_custom_color: bool,
     pub color_body: i32,
@@ -467,33 +633,135 @@ pub struct ClChangeInfo<'a> {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClKill;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClEmoticon {
     pub emoticon: enums::Emoticon,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClVote {
     pub vote: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ClCallVote<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub type_: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub value: &'a [u8],
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvMotdOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvBroadcastOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvChatOwned {
+    pub team: bool,
+    pub client_id: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvVoteOptionOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub command: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvVoteSetOwned {
+    pub timeout: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub description: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub command: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClSayOwned {
+    pub team: bool,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClStartInfoOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub skin: Vec<u8>,
+    pub use_custom_color: bool,
+    pub color_body: i32,
+    pub color_feet: i32,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClChangeInfoOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub skin: Vec<u8>,
+    pub use_custom_color: bool,
+    pub color_body: i32,
+    pub color_feet: i32,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClCallVoteOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub type_: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub value: Vec<u8>,
+}
+
 impl<'a> SvMotd<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvMotd<'a>, Error> {
-        let result = Ok(SvMotd {
+        SvMotd::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvMotd<'a>, Error> {
+        let result = SvMotd::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvMotd"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvMotd<'a>, Error> {
+        Ok(SvMotd {
             message: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(message: &'a [u8]) -> Result<SvMotd<'a>, Error> {
+        let result = SvMotd {
+            message,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.message)?;
         Ok(_p.written())
     }
@@ -508,13 +776,28 @@ impl<'a> fmt::Debug for SvMotd<'a> {
 
 impl<'a> SvBroadcast<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvBroadcast<'a>, Error> {
-        let result = Ok(SvBroadcast {
+        SvBroadcast::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvBroadcast<'a>, Error> {
+        let result = SvBroadcast::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvBroadcast"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvBroadcast<'a>, Error> {
+        Ok(SvBroadcast {
             message: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(message: &'a [u8]) -> Result<SvBroadcast<'a>, Error> {
+        let result = SvBroadcast {
+            message,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.message)?;
         Ok(_p.written())
     }
@@ -529,16 +812,34 @@ impl<'a> fmt::Debug for SvBroadcast<'a> {
 
 impl<'a> SvChat<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvChat<'a>, Error> {
-        let result = Ok(SvChat {
-            team: to_bool(_p.read_int(warn)?)?,
-            client_id: in_range(_p.read_int(warn)?, -1, 15)?,
+        SvChat::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvChat<'a>, Error> {
+        let result = SvChat::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvChat"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvChat<'a>, Error> {
+        Ok(SvChat {
+            team: to_bool(_p.read_int(&mut _p.locate(warn, "SvChat.team"))?)?,
+            client_id: in_range(_p.read_int(&mut _p.locate(warn, "SvChat.client_id"))?, -1, 15)?,
             message: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(-1 <= self.client_id && self.client_id <= 15);
+    pub fn new(team: bool, client_id: i32, message: &'a [u8]) -> Result<SvChat<'a>, Error> {
+        let result = SvChat {
+            team,
+            client_id,
+            message,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.client_id, -1, 15)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.client_id, -1, 15)?;
         _p.write_int(self.team as i32)?;
         _p.write_int(self.client_id)?;
         _p.write_string(self.message)?;
@@ -557,19 +858,40 @@ impl<'a> fmt::Debug for SvChat<'a> {
 
 impl SvKillMsg {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvKillMsg, Error> {
-        let result = Ok(SvKillMsg {
-            killer: in_range(_p.read_int(warn)?, 0, 15)?,
-            victim: in_range(_p.read_int(warn)?, 0, 15)?,
-            weapon: in_range(_p.read_int(warn)?, -3, 5)?,
-            mode_special: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.killer && self.killer <= 15);
-        assert!(0 <= self.victim && self.victim <= 15);
-        assert!(-3 <= self.weapon && self.weapon <= 5);
+        SvKillMsg::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvKillMsg, Error> {
+        let result = SvKillMsg::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvKillMsg"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvKillMsg, Error> {
+        Ok(SvKillMsg {
+            killer: in_range(_p.read_int(&mut _p.locate(warn, "SvKillMsg.killer"))?, 0, 15)?,
+            victim: in_range(_p.read_int(&mut _p.locate(warn, "SvKillMsg.victim"))?, 0, 15)?,
+            weapon: in_range(_p.read_int(&mut _p.locate(warn, "SvKillMsg.weapon"))?, -3, 5)?,
+            mode_special: _p.read_int(&mut _p.locate(warn, "SvKillMsg.mode_special"))?,
+        })
+    }
+    pub fn new(killer: i32, victim: i32, weapon: i32, mode_special: i32) -> Result<SvKillMsg, Error> {
+        let result = SvKillMsg {
+            killer,
+            victim,
+            weapon,
+            mode_special,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.killer, 0, 15)?;
+        in_range(self.victim, 0, 15)?;
+        in_range(self.weapon, -3, 5)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.killer, 0, 15)?;
+        in_range(self.victim, 0, 15)?;
+        in_range(self.weapon, -3, 5)?;
         _p.write_int(self.killer)?;
         _p.write_int(self.victim)?;
         _p.write_int(self.weapon)?;
@@ -590,13 +912,29 @@ impl fmt::Debug for SvKillMsg {
 
 impl SvSoundGlobal {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvSoundGlobal, Error> {
-        let result = Ok(SvSoundGlobal {
-            sound_id: enums::Sound::from_i32(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
+        SvSoundGlobal::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvSoundGlobal, Error> {
+        let result = SvSoundGlobal::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvSoundGlobal"), _p, result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvSoundGlobal, Error> {
+        Ok(SvSoundGlobal {
+            sound_id: enums::Sound::from_i32(_p.read_int(&mut _p.locate(warn, "SvSoundGlobal.sound_id"))?),
+        })
+    }
+    pub fn new(sound_id: enums::Sound) -> Result<SvSoundGlobal, Error> {
+        let result = SvSoundGlobal {
+            sound_id,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.sound_id.known()?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.sound_id.to_i32())?;
         Ok(_p.written())
     }
@@ -611,45 +949,93 @@ impl fmt::Debug for SvSoundGlobal {
 
 impl SvTuneParams {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvTuneParams, Error> {
-        let result = Ok(SvTuneParams {
-            ground_control_speed: TuneParam(_p.read_int(warn)?),
-            ground_control_accel: TuneParam(_p.read_int(warn)?),
-            ground_friction: TuneParam(_p.read_int(warn)?),
-            ground_jump_impulse: TuneParam(_p.read_int(warn)?),
-            air_jump_impulse: TuneParam(_p.read_int(warn)?),
-            air_control_speed: TuneParam(_p.read_int(warn)?),
-            air_control_accel: TuneParam(_p.read_int(warn)?),
-            air_friction: TuneParam(_p.read_int(warn)?),
-            hook_length: TuneParam(_p.read_int(warn)?),
-            hook_fire_speed: TuneParam(_p.read_int(warn)?),
-            hook_drag_accel: TuneParam(_p.read_int(warn)?),
-            hook_drag_speed: TuneParam(_p.read_int(warn)?),
-            gravity: TuneParam(_p.read_int(warn)?),
-            velramp_start: TuneParam(_p.read_int(warn)?),
-            velramp_range: TuneParam(_p.read_int(warn)?),
-            velramp_curvature: TuneParam(_p.read_int(warn)?),
-            gun_curvature: TuneParam(_p.read_int(warn)?),
-            gun_speed: TuneParam(_p.read_int(warn)?),
-            gun_lifetime: TuneParam(_p.read_int(warn)?),
-            shotgun_curvature: TuneParam(_p.read_int(warn)?),
-            shotgun_speed: TuneParam(_p.read_int(warn)?),
-            shotgun_speeddiff: TuneParam(_p.read_int(warn)?),
-            shotgun_lifetime: TuneParam(_p.read_int(warn)?),
-            grenade_curvature: TuneParam(_p.read_int(warn)?),
-            grenade_speed: TuneParam(_p.read_int(warn)?),
-            grenade_lifetime: TuneParam(_p.read_int(warn)?),
-            laser_reach: TuneParam(_p.read_int(warn)?),
-            laser_bounce_delay: TuneParam(_p.read_int(warn)?),
-            laser_bounce_num: TuneParam(_p.read_int(warn)?),
-            laser_bounce_cost: TuneParam(_p.read_int(warn)?),
-            laser_damage: TuneParam(_p.read_int(warn)?),
-            player_collision: TuneParam(_p.read_int(warn)?),
-            player_hooking: TuneParam(_p.read_int(warn)?),
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        SvTuneParams::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvTuneParams, Error> {
+        let result = SvTuneParams::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvTuneParams"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvTuneParams, Error> {
+        Ok(SvTuneParams {
+            ground_control_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.ground_control_speed"))?),
+            ground_control_accel: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.ground_control_accel"))?),
+            ground_friction: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.ground_friction"))?),
+            ground_jump_impulse: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.ground_jump_impulse"))?),
+            air_jump_impulse: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.air_jump_impulse"))?),
+            air_control_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.air_control_speed"))?),
+            air_control_accel: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.air_control_accel"))?),
+            air_friction: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.air_friction"))?),
+            hook_length: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.hook_length"))?),
+            hook_fire_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.hook_fire_speed"))?),
+            hook_drag_accel: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.hook_drag_accel"))?),
+            hook_drag_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.hook_drag_speed"))?),
+            gravity: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.gravity"))?),
+            velramp_start: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.velramp_start"))?),
+            velramp_range: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.velramp_range"))?),
+            velramp_curvature: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.velramp_curvature"))?),
+            gun_curvature: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.gun_curvature"))?),
+            gun_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.gun_speed"))?),
+            gun_lifetime: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.gun_lifetime"))?),
+            shotgun_curvature: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.shotgun_curvature"))?),
+            shotgun_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.shotgun_speed"))?),
+            shotgun_speeddiff: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.shotgun_speeddiff"))?),
+            shotgun_lifetime: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.shotgun_lifetime"))?),
+            grenade_curvature: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.grenade_curvature"))?),
+            grenade_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.grenade_speed"))?),
+            grenade_lifetime: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.grenade_lifetime"))?),
+            laser_reach: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.laser_reach"))?),
+            laser_bounce_delay: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.laser_bounce_delay"))?),
+            laser_bounce_num: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.laser_bounce_num"))?),
+            laser_bounce_cost: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.laser_bounce_cost"))?),
+            laser_damage: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.laser_damage"))?),
+            player_collision: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.player_collision"))?),
+            player_hooking: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.player_hooking"))?),
+        })
+    }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(ground_control_speed: TuneParam, ground_control_accel: TuneParam, ground_friction: TuneParam, ground_jump_impulse: TuneParam, air_jump_impulse: TuneParam, air_control_speed: TuneParam, air_control_accel: TuneParam, air_friction: TuneParam, hook_length: TuneParam, hook_fire_speed: TuneParam, hook_drag_accel: TuneParam, hook_drag_speed: TuneParam, gravity: TuneParam, velramp_start: TuneParam, velramp_range: TuneParam, velramp_curvature: TuneParam, gun_curvature: TuneParam, gun_speed: TuneParam, gun_lifetime: TuneParam, shotgun_curvature: TuneParam, shotgun_speed: TuneParam, shotgun_speeddiff: TuneParam, shotgun_lifetime: TuneParam, grenade_curvature: TuneParam, grenade_speed: TuneParam, grenade_lifetime: TuneParam, laser_reach: TuneParam, laser_bounce_delay: TuneParam, laser_bounce_num: TuneParam, laser_bounce_cost: TuneParam, laser_damage: TuneParam, player_collision: TuneParam, player_hooking: TuneParam) -> Result<SvTuneParams, Error> {
+        let result = SvTuneParams {
+            ground_control_speed,
+            ground_control_accel,
+            ground_friction,
+            ground_jump_impulse,
+            air_jump_impulse,
+            air_control_speed,
+            air_control_accel,
+            air_friction,
+            hook_length,
+            hook_fire_speed,
+            hook_drag_accel,
+            hook_drag_speed,
+            gravity,
+            velramp_start,
+            velramp_range,
+            velramp_curvature,
+            gun_curvature,
+            gun_speed,
+            gun_lifetime,
+            shotgun_curvature,
+            shotgun_speed,
+            shotgun_speeddiff,
+            shotgun_lifetime,
+            grenade_curvature,
+            grenade_speed,
+            grenade_lifetime,
+            laser_reach,
+            laser_bounce_delay,
+            laser_bounce_num,
+            laser_bounce_cost,
+            laser_damage,
+            player_collision,
+            player_hooking,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.ground_control_speed.0)?;
         _p.write_int(self.ground_control_accel.0)?;
         _p.write_int(self.ground_friction.0)?;
@@ -764,13 +1150,28 @@ impl fmt::Debug for SvTuneParams {
 
 impl SvExtraProjectile {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvExtraProjectile, Error> {
-        let result = Ok(SvExtraProjectile {
-            projectile: ::snap_obj::Projectile::decode_msg(warn, _p)?,
-        });
-        _p.finish(warn);
-        result
+        SvExtraProjectile::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvExtraProjectile, Error> {
+        let result = SvExtraProjectile::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvExtraProjectile"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvExtraProjectile, Error> {
+        Ok(SvExtraProjectile {
+            projectile: ::snap_obj::Projectile::decode_msg_located(warn, _p)?,
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(projectile: ::snap_obj::Projectile) -> Result<SvExtraProjectile, Error> {
+        let result = SvExtraProjectile {
+            projectile,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         with_packer(&mut _p, |p| self.projectile.encode_msg(p))?;
         Ok(_p.written())
     }
@@ -785,11 +1186,15 @@ impl fmt::Debug for SvExtraProjectile {
 
 impl SvReadyToEnter {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvReadyToEnter, Error> {
-        let result = Ok(SvReadyToEnter);
-        _p.finish(warn);
-        result
+        SvReadyToEnter::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvReadyToEnter, Error> {
+        error::finish(&mut _p.locate(warn, "SvReadyToEnter"), _p, Ok(SvReadyToEnter))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -802,13 +1207,29 @@ impl fmt::Debug for SvReadyToEnter {
 
 impl SvWeaponPickup {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvWeaponPickup, Error> {
-        let result = Ok(SvWeaponPickup {
-            weapon: enums::Weapon::from_i32(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
+        SvWeaponPickup::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvWeaponPickup, Error> {
+        let result = SvWeaponPickup::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvWeaponPickup"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvWeaponPickup, Error> {
+        Ok(SvWeaponPickup {
+            weapon: enums::Weapon::from_i32(_p.read_int(&mut _p.locate(warn, "SvWeaponPickup.weapon"))?),
+        })
+    }
+    pub fn new(weapon: enums::Weapon) -> Result<SvWeaponPickup, Error> {
+        let result = SvWeaponPickup {
+            weapon,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        self.weapon.known()?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.weapon.to_i32())?;
         Ok(_p.written())
     }
@@ -823,15 +1244,33 @@ impl fmt::Debug for SvWeaponPickup {
 
 impl SvEmoticon {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvEmoticon, Error> {
-        let result = Ok(SvEmoticon {
-            client_id: in_range(_p.read_int(warn)?, 0, 15)?,
-            emoticon: enums::Emoticon::from_i32(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.client_id && self.client_id <= 15);
+        SvEmoticon::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvEmoticon, Error> {
+        let result = SvEmoticon::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvEmoticon"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvEmoticon, Error> {
+        Ok(SvEmoticon {
+            client_id: in_range(_p.read_int(&mut _p.locate(warn, "SvEmoticon.client_id"))?, 0, 15)?,
+            emoticon: enums::Emoticon::from_i32(_p.read_int(&mut _p.locate(warn, "SvEmoticon.emoticon"))?),
+        })
+    }
+    pub fn new(client_id: i32, emoticon: enums::Emoticon) -> Result<SvEmoticon, Error> {
+        let result = SvEmoticon {
+            client_id,
+            emoticon,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.client_id, 0, 15)?;
+        self.emoticon.known()?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.client_id, 0, 15)?;
         _p.write_int(self.client_id)?;
         _p.write_int(self.emoticon.to_i32())?;
         Ok(_p.written())
@@ -848,11 +1287,15 @@ impl fmt::Debug for SvEmoticon {
 
 impl SvVoteClearOptions {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteClearOptions, Error> {
-        let result = Ok(SvVoteClearOptions);
-        _p.finish(warn);
-        result
+        SvVoteClearOptions::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteClearOptions, Error> {
+        error::finish(&mut _p.locate(warn, "SvVoteClearOptions"), _p, Ok(SvVoteClearOptions))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -865,14 +1308,30 @@ impl fmt::Debug for SvVoteClearOptions {
 
 impl<'a> SvVoteOption<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteOption<'a>, Error> {
-        let result = Ok(SvVoteOption {
-            command: sanitize(warn, _p.read_string()?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.command).unwrap();
+        SvVoteOption::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteOption<'a>, Error> {
+        let result = SvVoteOption::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvVoteOption"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteOption<'a>, Error> {
+        Ok(SvVoteOption {
+            command: sanitize(&mut _p.locate(warn, "SvVoteOption.command"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(command: &'a [u8]) -> Result<SvVoteOption<'a>, Error> {
+        let result = SvVoteOption {
+            command,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.command)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.command)?;
         _p.write_string(self.command)?;
         Ok(_p.written())
     }
@@ -887,18 +1346,38 @@ impl<'a> fmt::Debug for SvVoteOption<'a> {
 
 impl<'a> SvVoteSet<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteSet<'a>, Error> {
-        let result = Ok(SvVoteSet {
-            timeout: in_range(_p.read_int(warn)?, 0, 60)?,
-            description: sanitize(warn, _p.read_string()?)?,
-            command: sanitize(warn, _p.read_string()?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.timeout && self.timeout <= 60);
-        sanitize(&mut Panic, self.description).unwrap();
-        sanitize(&mut Panic, self.command).unwrap();
+        SvVoteSet::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteSet<'a>, Error> {
+        let result = SvVoteSet::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvVoteSet"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteSet<'a>, Error> {
+        Ok(SvVoteSet {
+            timeout: in_range(_p.read_int(&mut _p.locate(warn, "SvVoteSet.timeout"))?, 0, 60)?,
+            description: sanitize(&mut _p.locate(warn, "SvVoteSet.description"), _p.read_string()?)?,
+            command: sanitize(&mut _p.locate(warn, "SvVoteSet.command"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(timeout: i32, description: &'a [u8], command: &'a [u8]) -> Result<SvVoteSet<'a>, Error> {
+        let result = SvVoteSet {
+            timeout,
+            description,
+            command,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.timeout, 0, 60)?;
+        sanitize(&mut Ignore, self.description)?;
+        sanitize(&mut Ignore, self.command)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.timeout, 0, 60)?;
+        sanitize(&mut Ignore, self.description)?;
+        sanitize(&mut Ignore, self.command)?;
         _p.write_int(self.timeout)?;
         _p.write_string(self.description)?;
         _p.write_string(self.command)?;
@@ -917,20 +1396,42 @@ impl<'a> fmt::Debug for SvVoteSet<'a> {
 
 impl SvVoteStatus {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteStatus, Error> {
-        let result = Ok(SvVoteStatus {
-            yes: in_range(_p.read_int(warn)?, 0, 16)?,
-            no: in_range(_p.read_int(warn)?, 0, 16)?,
-            pass: in_range(_p.read_int(warn)?, 0, 16)?,
-            total: in_range(_p.read_int(warn)?, 0, 16)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.yes && self.yes <= 16);
-        assert!(0 <= self.no && self.no <= 16);
-        assert!(0 <= self.pass && self.pass <= 16);
-        assert!(0 <= self.total && self.total <= 16);
+        SvVoteStatus::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteStatus, Error> {
+        let result = SvVoteStatus::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvVoteStatus"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteStatus, Error> {
+        Ok(SvVoteStatus {
+            yes: in_range(_p.read_int(&mut _p.locate(warn, "SvVoteStatus.yes"))?, 0, 16)?,
+            no: in_range(_p.read_int(&mut _p.locate(warn, "SvVoteStatus.no"))?, 0, 16)?,
+            pass: in_range(_p.read_int(&mut _p.locate(warn, "SvVoteStatus.pass"))?, 0, 16)?,
+            total: in_range(_p.read_int(&mut _p.locate(warn, "SvVoteStatus.total"))?, 0, 16)?,
+        })
+    }
+    pub fn new(yes: i32, no: i32, pass: i32, total: i32) -> Result<SvVoteStatus, Error> {
+        let result = SvVoteStatus {
+            yes,
+            no,
+            pass,
+            total,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.yes, 0, 16)?;
+        in_range(self.no, 0, 16)?;
+        in_range(self.pass, 0, 16)?;
+        in_range(self.total, 0, 16)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.yes, 0, 16)?;
+        in_range(self.no, 0, 16)?;
+        in_range(self.pass, 0, 16)?;
+        in_range(self.total, 0, 16)?;
         _p.write_int(self.yes)?;
         _p.write_int(self.no)?;
         _p.write_int(self.pass)?;
@@ -951,14 +1452,30 @@ impl fmt::Debug for SvVoteStatus {
 
 impl<'a> ClSay<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClSay<'a>, Error> {
-        let result = Ok(ClSay {
-            team: to_bool(_p.read_int(warn)?)?,
+        ClSay::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClSay<'a>, Error> {
+        let result = ClSay::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClSay"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClSay<'a>, Error> {
+        Ok(ClSay {
+            team: to_bool(_p.read_int(&mut _p.locate(warn, "ClSay.team"))?)?,
             message: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(team: bool, message: &'a [u8]) -> Result<ClSay<'a>, Error> {
+        let result = ClSay {
+            team,
+            message,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.team as i32)?;
         _p.write_string(self.message)?;
         Ok(_p.written())
@@ -975,13 +1492,29 @@ impl<'a> fmt::Debug for ClSay<'a> {
 
 impl ClSetTeam {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClSetTeam, Error> {
-        let result = Ok(ClSetTeam {
-            team: enums::Team::from_i32(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
+        ClSetTeam::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClSetTeam, Error> {
+        let result = ClSetTeam::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClSetTeam"), _p, result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClSetTeam, Error> {
+        Ok(ClSetTeam {
+            team: enums::Team::from_i32(_p.read_int(&mut _p.locate(warn, "ClSetTeam.team"))?),
+        })
+    }
+    pub fn new(team: enums::Team) -> Result<ClSetTeam, Error> {
+        let result = ClSetTeam {
+            team,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.team.known()?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.team.to_i32())?;
         Ok(_p.written())
     }
@@ -996,19 +1529,40 @@ impl fmt::Debug for ClSetTeam {
 
 impl<'a> ClStartInfo<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClStartInfo<'a>, Error> {
-        let result = Ok(ClStartInfo {
-            name: sanitize(warn, _p.read_string()?)?,
-            skin: sanitize(warn, _p.read_string()?)?,
-            use_custom_color: to_bool(_p.read_int(warn)?)?,
-            color_body: _p.read_int(warn)?,
-            color_feet: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.name).unwrap();
-        sanitize(&mut Panic, self.skin).unwrap();
+        ClStartInfo::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClStartInfo<'a>, Error> {
+        let result = ClStartInfo::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClStartInfo"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClStartInfo<'a>, Error> {
+        Ok(ClStartInfo {
+            name: sanitize(&mut _p.locate(warn, "ClStartInfo.name"), _p.read_string()?)?,
+            skin: sanitize(&mut _p.locate(warn, "ClStartInfo.skin"), _p.read_string()?)?,
+            use_custom_color: to_bool(_p.read_int(&mut _p.locate(warn, "ClStartInfo.use_custom_color"))?)?,
+            color_body: _p.read_int(&mut _p.locate(warn, "ClStartInfo.color_body"))?,
+            color_feet: _p.read_int(&mut _p.locate(warn, "ClStartInfo.color_feet"))?,
+        })
+    }
+    pub fn new(name: &'a [u8], skin: &'a [u8], use_custom_color: bool, color_body: i32, color_feet: i32) -> Result<ClStartInfo<'a>, Error> {
+        let result = ClStartInfo {
+            name,
+            skin,
+            use_custom_color,
+            color_body,
+            color_feet,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, max_length(self.name, 23)?)?;
+        sanitize(&mut Ignore, max_length(self.skin, 23)?)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.skin)?;
         _p.write_string(self.name)?;
         _p.write_string(self.skin)?;
         _p.write_int(self.use_custom_color as i32)?;
@@ -1031,19 +1585,40 @@ impl<'a> fmt::Debug for ClStartInfo<'a> {
 
 impl<'a> ClChangeInfo<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClChangeInfo<'a>, Error> {
-        let result = Ok(ClChangeInfo {
-            name: sanitize(warn, _p.read_string()?)?,
-            skin: sanitize(warn, _p.read_string()?)?,
-            use_custom_color: to_bool(_p.read_int(warn)?)?,
-            color_body: _p.read_int(warn)?,
-            color_feet: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.name).unwrap();
-        sanitize(&mut Panic, self.skin).unwrap();
+        ClChangeInfo::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClChangeInfo<'a>, Error> {
+        let result = ClChangeInfo::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClChangeInfo"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClChangeInfo<'a>, Error> {
+        Ok(ClChangeInfo {
+            name: sanitize(&mut _p.locate(warn, "ClChangeInfo.name"), _p.read_string()?)?,
+            skin: sanitize(&mut _p.locate(warn, "ClChangeInfo.skin"), _p.read_string()?)?,
+            use_custom_color: to_bool(_p.read_int(&mut _p.locate(warn, "ClChangeInfo.use_custom_color"))?)?,
+            color_body: _p.read_int(&mut _p.locate(warn, "ClChangeInfo.color_body"))?,
+            color_feet: _p.read_int(&mut _p.locate(warn, "ClChangeInfo.color_feet"))?,
+        })
+    }
+    pub fn new(name: &'a [u8], skin: &'a [u8], use_custom_color: bool, color_body: i32, color_feet: i32) -> Result<ClChangeInfo<'a>, Error> {
+        let result = ClChangeInfo {
+            name,
+            skin,
+            use_custom_color,
+            color_body,
+            color_feet,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, max_length(self.name, 23)?)?;
+        sanitize(&mut Ignore, max_length(self.skin, 23)?)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.skin)?;
         _p.write_string(self.name)?;
         _p.write_string(self.skin)?;
         _p.write_int(self.use_custom_color as i32)?;
@@ -1066,11 +1641,15 @@ impl<'a> fmt::Debug for ClChangeInfo<'a> {
 
 impl ClKill {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClKill, Error> {
-        let result = Ok(ClKill);
-        _p.finish(warn);
-        result
+        ClKill::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClKill, Error> {
+        error::finish(&mut _p.locate(warn, "ClKill"), _p, Ok(ClKill))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -1083,13 +1662,29 @@ impl fmt::Debug for ClKill {
 
 impl ClEmoticon {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClEmoticon, Error> {
-        let result = Ok(ClEmoticon {
-            emoticon: enums::Emoticon::from_i32(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
+        ClEmoticon::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClEmoticon, Error> {
+        let result = ClEmoticon::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClEmoticon"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClEmoticon, Error> {
+        Ok(ClEmoticon {
+            emoticon: enums::Emoticon::from_i32(_p.read_int(&mut _p.locate(warn, "ClEmoticon.emoticon"))?),
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(emoticon: enums::Emoticon) -> Result<ClEmoticon, Error> {
+        let result = ClEmoticon {
+            emoticon,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.emoticon.known()?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.emoticon.to_i32())?;
         Ok(_p.written())
     }
@@ -1104,14 +1699,30 @@ impl fmt::Debug for ClEmoticon {
 
 impl ClVote {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClVote, Error> {
-        let result = Ok(ClVote {
-            vote: in_range(_p.read_int(warn)?, -1, 1)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(-1 <= self.vote && self.vote <= 1);
+        ClVote::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClVote, Error> {
+        let result = ClVote::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClVote"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClVote, Error> {
+        Ok(ClVote {
+            vote: in_range(_p.read_int(&mut _p.locate(warn, "ClVote.vote"))?, -1, 1)?,
+        })
+    }
+    pub fn new(vote: i32) -> Result<ClVote, Error> {
+        let result = ClVote {
+            vote,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.vote, -1, 1)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.vote, -1, 1)?;
         _p.write_int(self.vote)?;
         Ok(_p.written())
     }
@@ -1126,16 +1737,34 @@ impl fmt::Debug for ClVote {
 
 impl<'a> ClCallVote<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClCallVote<'a>, Error> {
-        let result = Ok(ClCallVote {
-            type_: sanitize(warn, _p.read_string()?)?,
-            value: sanitize(warn, _p.read_string()?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.type_).unwrap();
-        sanitize(&mut Panic, self.value).unwrap();
+        ClCallVote::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClCallVote<'a>, Error> {
+        let result = ClCallVote::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClCallVote"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClCallVote<'a>, Error> {
+        Ok(ClCallVote {
+            type_: sanitize(&mut _p.locate(warn, "ClCallVote.type_"), _p.read_string()?)?,
+            value: sanitize(&mut _p.locate(warn, "ClCallVote.value"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(type_: &'a [u8], value: &'a [u8]) -> Result<ClCallVote<'a>, Error> {
+        let result = ClCallVote {
+            type_,
+            value,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.type_)?;
+        sanitize(&mut Ignore, self.value)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.type_)?;
+        sanitize(&mut Ignore, self.value)?;
         _p.write_string(self.type_)?;
         _p.write_string(self.value)?;
         Ok(_p.written())
@@ -1150,3 +1779,229 @@ impl<'a> fmt::Debug for ClCallVote<'a> {
     }
 }
 
+impl<'a> SvMotd<'a> {
+    pub fn to_owned(&self) -> SvMotdOwned {
+        SvMotdOwned {
+            message: self.message.to_vec(),
+        }
+    }
+}
+
+impl SvMotdOwned {
+    pub fn as_ref(&self) -> SvMotd<'_> {
+        SvMotd {
+            message: &self.message[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvMotdOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvBroadcast<'a> {
+    pub fn to_owned(&self) -> SvBroadcastOwned {
+        SvBroadcastOwned {
+            message: self.message.to_vec(),
+        }
+    }
+}
+
+impl SvBroadcastOwned {
+    pub fn as_ref(&self) -> SvBroadcast<'_> {
+        SvBroadcast {
+            message: &self.message[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvBroadcastOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvChat<'a> {
+    pub fn to_owned(&self) -> SvChatOwned {
+        SvChatOwned {
+            team: self.team,
+            client_id: self.client_id,
+            message: self.message.to_vec(),
+        }
+    }
+}
+
+impl SvChatOwned {
+    pub fn as_ref(&self) -> SvChat<'_> {
+        SvChat {
+            team: self.team,
+            client_id: self.client_id,
+            message: &self.message[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvChatOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvVoteOption<'a> {
+    pub fn to_owned(&self) -> SvVoteOptionOwned {
+        SvVoteOptionOwned {
+            command: self.command.to_vec(),
+        }
+    }
+}
+
+impl SvVoteOptionOwned {
+    pub fn as_ref(&self) -> SvVoteOption<'_> {
+        SvVoteOption {
+            command: &self.command[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvVoteOptionOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvVoteSet<'a> {
+    pub fn to_owned(&self) -> SvVoteSetOwned {
+        SvVoteSetOwned {
+            timeout: self.timeout,
+            description: self.description.to_vec(),
+            command: self.command.to_vec(),
+        }
+    }
+}
+
+impl SvVoteSetOwned {
+    pub fn as_ref(&self) -> SvVoteSet<'_> {
+        SvVoteSet {
+            timeout: self.timeout,
+            description: &self.description[..],
+            command: &self.command[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvVoteSetOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> ClSay<'a> {
+    pub fn to_owned(&self) -> ClSayOwned {
+        ClSayOwned {
+            team: self.team,
+            message: self.message.to_vec(),
+        }
+    }
+}
+
+impl ClSayOwned {
+    pub fn as_ref(&self) -> ClSay<'_> {
+        ClSay {
+            team: self.team,
+            message: &self.message[..],
+        }
+    }
+}
+
+impl fmt::Debug for ClSayOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> ClStartInfo<'a> {
+    pub fn to_owned(&self) -> ClStartInfoOwned {
+        ClStartInfoOwned {
+            name: self.name.to_vec(),
+            skin: self.skin.to_vec(),
+            use_custom_color: self.use_custom_color,
+            color_body: self.color_body,
+            color_feet: self.color_feet,
+        }
+    }
+}
+
+impl ClStartInfoOwned {
+    pub fn as_ref(&self) -> ClStartInfo<'_> {
+        ClStartInfo {
+            name: &self.name[..],
+            skin: &self.skin[..],
+            use_custom_color: self.use_custom_color,
+            color_body: self.color_body,
+            color_feet: self.color_feet,
+        }
+    }
+}
+
+impl fmt::Debug for ClStartInfoOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> ClChangeInfo<'a> {
+    pub fn to_owned(&self) -> ClChangeInfoOwned {
+        ClChangeInfoOwned {
+            name: self.name.to_vec(),
+            skin: self.skin.to_vec(),
+            use_custom_color: self.use_custom_color,
+            color_body: self.color_body,
+            color_feet: self.color_feet,
+        }
+    }
+}
+
+impl ClChangeInfoOwned {
+    pub fn as_ref(&self) -> ClChangeInfo<'_> {
+        ClChangeInfo {
+            name: &self.name[..],
+            skin: &self.skin[..],
+            use_custom_color: self.use_custom_color,
+            color_body: self.color_body,
+            color_feet: self.color_feet,
+        }
+    }
+}
+
+impl fmt::Debug for ClChangeInfoOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> ClCallVote<'a> {
+    pub fn to_owned(&self) -> ClCallVoteOwned {
+        ClCallVoteOwned {
+            type_: self.type_.to_vec(),
+            value: self.value.to_vec(),
+        }
+    }
+}
+
+impl ClCallVoteOwned {
+    pub fn as_ref(&self) -> ClCallVote<'_> {
+        ClCallVote {
+            type_: &self.type_[..],
+            value: &self.value[..],
+        }
+    }
+}
+
+impl fmt::Debug for ClCallVoteOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
@@ -1,7 +1,14 @@
-use buffer::CapacityError;
 use common::slice;
+use common::warn::Located;
+use common::warn::strip_location;
 use enums;
+use error;
+use error::EncodeError;
 use error::Error;
+use gamenet_common::snap_obj::FieldKind;
+use gamenet_common::snap_obj::FieldMeta;
+use gamenet_common::snap_obj::ObjMeta;
+use gamenet_common::snap_obj::wrap_angle;
 use packer::ExcessData;
 use packer::IntUnpacker;
 use packer::Packer;
@@ -39,6 +46,7 @@ pub const SOUND_WORLD: u16 = 17;
 pub const DAMAGE_IND: u16 = 18;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SnapObj {
     PlayerInput(PlayerInput),
     Projectile(Projectile),
@@ -107,7 +115,29 @@ impl SnapObj {
             SnapObj::DamageInd(_) => TypeId::from(DAMAGE_IND),
         }
     }
-    pub fn encode(&self) -> &[i32] {
+    pub fn validate(&self) -> Result<(), Error> {
+        match *self {
+            SnapObj::PlayerInput(ref i) => i.validate(),
+            SnapObj::Projectile(ref i) => i.validate(),
+            SnapObj::Laser(ref i) => i.validate(),
+            SnapObj::Pickup(ref i) => i.validate(),
+            SnapObj::Flag(ref i) => i.validate(),
+            SnapObj::Game(ref i) => i.validate(),
+            SnapObj::CharacterCore(ref i) => i.validate(),
+            SnapObj::Character(ref i) => i.validate(),
+            SnapObj::PlayerInfo(ref i) => i.validate(),
+            SnapObj::ClientInfo(ref i) => i.validate(),
+            SnapObj::Common(ref i) => i.validate(),
+            SnapObj::Explosion(ref i) => i.validate(),
+            SnapObj::Spawn(ref i) => i.validate(),
+            SnapObj::HammerHit(ref i) => i.validate(),
+            SnapObj::Death(ref i) => i.validate(),
+            SnapObj::SoundGlobal(ref i) => i.validate(),
+            SnapObj::SoundWorld(ref i) => i.validate(),
+            SnapObj::DamageInd(ref i) => i.validate(),
+        }
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
         match *self {
             SnapObj::PlayerInput(ref i) => i.encode(),
             SnapObj::Projectile(ref i) => i.encode(),
@@ -265,7 +295,8 @@ impl From<DamageInd> for SnapObj {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PlayerInput {
     pub direction: i32,
     pub target_x: i32,
@@ -281,17 +312,20 @@ pub struct PlayerInput {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Projectile {
     pub x: i32,
     pub y: i32,
     pub vel_x: i32,
     pub vel_y: i32,
-    pub type_: enums::Weapon,
+    /// Raw `enums::Weapon` value, convert it using `enums::Weapon::from_i32`.
+    pub type_: i32,
     pub start_tick: ::snap_obj::Tick,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Laser {
     pub x: i32,
     pub y: i32,
@@ -302,6 +336,7 @@ pub struct Laser {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Pickup {
     pub x: i32,
     pub y: i32,
@@ -311,6 +346,7 @@ pub struct Pickup {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Flag {
     pub x: i32,
     pub y: i32,
@@ -320,6 +356,7 @@ pub struct Flag {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Game {
     pub flags: i32,
     pub round_start_tick: ::snap_obj::Tick,
@@ -337,6 +374,7 @@ pub struct Game {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CharacterCore {
     pub tick: i32,
     pub x: i32,
@@ -357,23 +395,29 @@ pub struct CharacterCore {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Character {
     pub character_core: CharacterCore,
-    pub player_state: enums::Playerstate,
+    /// Raw `enums::Playerstate` value, convert it using `enums::Playerstate::from_i32`.
+    pub player_state: i32,
     pub health: i32,
     pub armor: i32,
     pub ammo_count: i32,
-    pub weapon: enums::Weapon,
-    pub emote: enums::Emote,
+    /// Raw `enums::Weapon` value, convert it using `enums::Weapon::from_i32`.
+    pub weapon: i32,
+    /// Raw `enums::Emote` value, convert it using `enums::Emote::from_i32`.
+    pub emote: i32,
     pub attack_tick: i32,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PlayerInfo {
     pub local: i32,
     pub client_id: i32,
-    pub team: enums::Team,
+    /// Raw `enums::Team` value, convert it using `enums::Team::from_i32`.
+    pub team: i32,
     pub score: i32,
     pub latency: i32,
     pub latency_flux: i32,
@@ -381,6 +425,7 @@ pub struct PlayerInfo {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClientInfo {
     pub name: [i32; 6],
     pub skin: [i32; 6],
@@ -391,6 +436,7 @@ pub struct ClientInfo {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Common {
     pub x: i32,
     pub y: i32,
@@ -398,24 +444,28 @@ pub struct Common {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Explosion {
     pub common: Common,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Spawn {
     pub common: Common,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HammerHit {
     pub common: Common,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Death {
     pub common: Common,
     pub client_id: i32,
@@ -423,20 +473,25 @@ pub struct Death {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SoundGlobal {
     pub common: Common,
-    pub sound_id: enums::Sound,
+    /// Raw `enums::Sound` value, convert it using `enums::Sound::from_i32`.
+    pub sound_id: i32,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SoundWorld {
     pub common: Common,
-    pub sound_id: enums::Sound,
+    /// Raw `enums::Sound` value, convert it using `enums::Sound::from_i32`.
+    pub sound_id: i32,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DamageInd {
     pub common: Common,
     pub angle: i32,
@@ -478,30 +533,56 @@ impl PlayerInput {
             prev_weapon: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(0 <= self.player_state && self.player_state <= 4);
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(direction: i32, target_x: i32, target_y: i32, jump: i32, fire: i32, hook: i32, player_state: i32, wanted_weapon: i32, next_weapon: i32, prev_weapon: i32) -> Result<PlayerInput, Error> {
+        let result = PlayerInput {
+            direction,
+            target_x,
+            target_y,
+            jump,
+            fire,
+            hook,
+            player_state,
+            wanted_weapon,
+            next_weapon,
+            prev_weapon,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.player_state, 0, 4)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.player_state, 0, 4)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 impl PlayerInput {
     pub fn decode_msg<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<PlayerInput, Error> {
-        let result = Ok(PlayerInput {
-            direction: _p.read_int(warn)?,
-            target_x: _p.read_int(warn)?,
-            target_y: _p.read_int(warn)?,
-            jump: _p.read_int(warn)?,
-            fire: _p.read_int(warn)?,
-            hook: _p.read_int(warn)?,
-            player_state: in_range(_p.read_int(warn)?, 0, 4)?,
-            wanted_weapon: _p.read_int(warn)?,
-            next_weapon: _p.read_int(warn)?,
-            prev_weapon: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode_msg<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.player_state && self.player_state <= 4);
+        PlayerInput::decode_msg_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_msg_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<PlayerInput, Error> {
+        let result = PlayerInput::decode_msg_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "PlayerInput"), _p, result)
+    }
+    fn decode_msg_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<PlayerInput, Error> {
+        Ok(PlayerInput {
+            direction: _p.read_int(&mut _p.locate(warn, "PlayerInput.direction"))?,
+            target_x: _p.read_int(&mut _p.locate(warn, "PlayerInput.target_x"))?,
+            target_y: _p.read_int(&mut _p.locate(warn, "PlayerInput.target_y"))?,
+            jump: _p.read_int(&mut _p.locate(warn, "PlayerInput.jump"))?,
+            fire: _p.read_int(&mut _p.locate(warn, "PlayerInput.fire"))?,
+            hook: _p.read_int(&mut _p.locate(warn, "PlayerInput.hook"))?,
+            player_state: in_range(_p.read_int(&mut _p.locate(warn, "PlayerInput.player_state"))?, 0, 4)?,
+            wanted_weapon: _p.read_int(&mut _p.locate(warn, "PlayerInput.wanted_weapon"))?,
+            next_weapon: _p.read_int(&mut _p.locate(warn, "PlayerInput.next_weapon"))?,
+            prev_weapon: _p.read_int(&mut _p.locate(warn, "PlayerInput.prev_weapon"))?,
+        })
+    }
+    pub fn encode_msg<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.player_state, 0, 4)?;
         _p.write_int(self.direction)?;
         _p.write_int(self.target_x)?;
         _p.write_int(self.target_y)?;
@@ -523,7 +604,7 @@ impl fmt::Debug for Projectile {
             .field("y", &self.y)
             .field("vel_x", &self.vel_x)
             .field("vel_y", &self.vel_y)
-            .field("type_", &self.type_)
+            .field("type_", &enums::Weapon::from_i32(self.type_))
             .field("start_tick", &self.start_tick)
             .finish()
     }
@@ -540,33 +621,54 @@ impl Projectile {
             y: _p.read_int()?,
             vel_x: _p.read_int()?,
             vel_y: _p.read_int()?,
-            type_: enums::Weapon::from_i32(_p.read_int()?)?,
+            type_: _p.read_int()?,
             start_tick: ::snap_obj::Tick(_p.read_int()?),
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32, vel_x: i32, vel_y: i32, type_: i32, start_tick: ::snap_obj::Tick) -> Result<Projectile, Error> {
+        let result = Projectile {
+            x,
+            y,
+            vel_x,
+            vel_y,
+            type_,
+            start_tick,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        enums::Weapon::from_i32(self.type_).known()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 impl Projectile {
     pub fn decode_msg<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Projectile, Error> {
-        let result = Ok(Projectile {
-            x: _p.read_int(warn)?,
-            y: _p.read_int(warn)?,
-            vel_x: _p.read_int(warn)?,
-            vel_y: _p.read_int(warn)?,
-            type_: enums::Weapon::from_i32(_p.read_int(warn)?)?,
-            start_tick: ::snap_obj::Tick(_p.read_int(warn)?),
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode_msg<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        Projectile::decode_msg_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_msg_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Projectile, Error> {
+        let result = Projectile::decode_msg_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Projectile"), _p, result)
+    }
+    fn decode_msg_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Projectile, Error> {
+        Ok(Projectile {
+            x: _p.read_int(&mut _p.locate(warn, "Projectile.x"))?,
+            y: _p.read_int(&mut _p.locate(warn, "Projectile.y"))?,
+            vel_x: _p.read_int(&mut _p.locate(warn, "Projectile.vel_x"))?,
+            vel_y: _p.read_int(&mut _p.locate(warn, "Projectile.vel_y"))?,
+            type_: _p.read_int(&mut _p.locate(warn, "Projectile.type_"))?,
+            start_tick: ::snap_obj::Tick(_p.read_int(&mut _p.locate(warn, "Projectile.start_tick"))?),
+        })
+    }
+    pub fn encode_msg<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.x)?;
         _p.write_int(self.y)?;
         _p.write_int(self.vel_x)?;
         _p.write_int(self.vel_y)?;
-        _p.write_int(self.type_.to_i32())?;
+        _p.write_int(self.type_)?;
         _p.write_int(self.start_tick.0)?;
         Ok(_p.written())
     }
@@ -598,8 +700,22 @@ impl Laser {
             start_tick: ::snap_obj::Tick(_p.read_int()?),
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32, from_x: i32, from_y: i32, start_tick: ::snap_obj::Tick) -> Result<Laser, Error> {
+        let result = Laser {
+            x,
+            y,
+            from_x,
+            from_y,
+            start_tick,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -627,10 +743,25 @@ impl Pickup {
             subtype: positive(_p.read_int()?)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(self.type_ >= 0);
-        assert!(self.subtype >= 0);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32, type_: i32, subtype: i32) -> Result<Pickup, Error> {
+        let result = Pickup {
+            x,
+            y,
+            type_,
+            subtype,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        positive(self.type_)?;
+        positive(self.subtype)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        positive(self.type_)?;
+        positive(self.subtype)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -658,10 +789,25 @@ impl Flag {
             carried_by: in_range(_p.read_int()?, -2, 15)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(0 <= self.team && self.team <= 1);
-        assert!(-2 <= self.carried_by && self.carried_by <= 15);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32, team: i32, carried_by: i32) -> Result<Flag, Error> {
+        let result = Flag {
+            x,
+            y,
+            team,
+            carried_by,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.team, 0, 1)?;
+        in_range(self.carried_by, -2, 15)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.team, 0, 1)?;
+        in_range(self.carried_by, -2, 15)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -705,17 +851,48 @@ impl Game {
             teamscore_blue: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(0 <= self.flags && self.flags <= 256);
-        assert!(0 <= self.game_over && self.game_over <= 1);
-        assert!(0 <= self.sudden_death && self.sudden_death <= 1);
-        assert!(0 <= self.paused && self.paused <= 1);
-        assert!(self.score_limit >= 0);
-        assert!(self.time_limit >= 0);
-        assert!(self.warmup >= 0);
-        assert!(self.round_num >= 0);
-        assert!(self.round_current >= 0);
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(flags: i32, round_start_tick: ::snap_obj::Tick, game_over: i32, sudden_death: i32, paused: i32, score_limit: i32, time_limit: i32, warmup: i32, round_num: i32, round_current: i32, teamscore_red: i32, teamscore_blue: i32) -> Result<Game, Error> {
+        let result = Game {
+            flags,
+            round_start_tick,
+            game_over,
+            sudden_death,
+            paused,
+            score_limit,
+            time_limit,
+            warmup,
+            round_num,
+            round_current,
+            teamscore_red,
+            teamscore_blue,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.flags, 0, 256)?;
+        in_range(self.game_over, 0, 1)?;
+        in_range(self.sudden_death, 0, 1)?;
+        in_range(self.paused, 0, 1)?;
+        positive(self.score_limit)?;
+        positive(self.time_limit)?;
+        positive(self.warmup)?;
+        positive(self.round_num)?;
+        positive(self.round_current)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.flags, 0, 256)?;
+        in_range(self.game_over, 0, 1)?;
+        in_range(self.sudden_death, 0, 1)?;
+        in_range(self.paused, 0, 1)?;
+        positive(self.score_limit)?;
+        positive(self.time_limit)?;
+        positive(self.warmup)?;
+        positive(self.round_num)?;
+        positive(self.round_current)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -765,12 +942,41 @@ impl CharacterCore {
             hook_dy: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(-1 <= self.direction && self.direction <= 1);
-        assert!(0 <= self.jumped && self.jumped <= 3);
-        assert!(-1 <= self.hooked_player && self.hooked_player <= 15);
-        assert!(-1 <= self.hook_state && self.hook_state <= 5);
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(tick: i32, x: i32, y: i32, vel_x: i32, vel_y: i32, angle: i32, direction: i32, jumped: i32, hooked_player: i32, hook_state: i32, hook_tick: ::snap_obj::Tick, hook_x: i32, hook_y: i32, hook_dx: i32, hook_dy: i32) -> Result<CharacterCore, Error> {
+        let result = CharacterCore {
+            tick,
+            x,
+            y,
+            vel_x,
+            vel_y,
+            angle: wrap_angle(angle),
+            direction,
+            jumped,
+            hooked_player,
+            hook_state,
+            hook_tick,
+            hook_x,
+            hook_y,
+            hook_dx,
+            hook_dy,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.direction, -1, 1)?;
+        in_range(self.jumped, 0, 3)?;
+        in_range(self.hooked_player, -1, 15)?;
+        in_range(self.hook_state, -1, 5)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.direction, -1, 1)?;
+        in_range(self.jumped, 0, 3)?;
+        in_range(self.hooked_player, -1, 15)?;
+        in_range(self.hook_state, -1, 5)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -778,12 +984,12 @@ impl fmt::Debug for Character {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Character")
             .field("character_core", &self.character_core)
-            .field("player_state", &self.player_state)
+            .field("player_state", &enums::Playerstate::from_i32(self.player_state))
             .field("health", &self.health)
             .field("armor", &self.armor)
             .field("ammo_count", &self.ammo_count)
-            .field("weapon", &self.weapon)
-            .field("emote", &self.emote)
+            .field("weapon", &enums::Weapon::from_i32(self.weapon))
+            .field("emote", &enums::Emote::from_i32(self.emote))
             .field("attack_tick", &self.attack_tick)
             .finish()
     }
@@ -797,22 +1003,48 @@ impl Character {
     pub fn decode_inner(_p: &mut IntUnpacker) -> Result<Character, Error> {
         Ok(Character {
             character_core: CharacterCore::decode_inner(_p)?,
-            player_state: enums::Playerstate::from_i32(_p.read_int()?)?,
+            player_state: _p.read_int()?,
             health: in_range(_p.read_int()?, 0, 10)?,
             armor: in_range(_p.read_int()?, 0, 10)?,
             ammo_count: in_range(_p.read_int()?, 0, 10)?,
-            weapon: enums::Weapon::from_i32(_p.read_int()?)?,
-            emote: enums::Emote::from_i32(_p.read_int()?)?,
+            weapon: _p.read_int()?,
+            emote: _p.read_int()?,
             attack_tick: positive(_p.read_int()?)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.character_core.encode();
-        assert!(0 <= self.health && self.health <= 10);
-        assert!(0 <= self.armor && self.armor <= 10);
-        assert!(0 <= self.ammo_count && self.ammo_count <= 10);
-        assert!(self.attack_tick >= 0);
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(character_core: CharacterCore, player_state: i32, health: i32, armor: i32, ammo_count: i32, weapon: i32, emote: i32, attack_tick: i32) -> Result<Character, Error> {
+        let result = Character {
+            character_core,
+            player_state,
+            health,
+            armor,
+            ammo_count,
+            weapon,
+            emote,
+            attack_tick,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.character_core.validate()?;
+        enums::Playerstate::from_i32(self.player_state).known()?;
+        in_range(self.health, 0, 10)?;
+        in_range(self.armor, 0, 10)?;
+        in_range(self.ammo_count, 0, 10)?;
+        enums::Weapon::from_i32(self.weapon).known()?;
+        enums::Emote::from_i32(self.emote).known()?;
+        positive(self.attack_tick)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.character_core.encode()?;
+        in_range(self.health, 0, 10)?;
+        in_range(self.armor, 0, 10)?;
+        in_range(self.ammo_count, 0, 10)?;
+        positive(self.attack_tick)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -821,7 +1053,7 @@ impl fmt::Debug for PlayerInfo {
         f.debug_struct("PlayerInfo")
             .field("local", &self.local)
             .field("client_id", &self.client_id)
-            .field("team", &self.team)
+            .field("team", &enums::Team::from_i32(self.team))
             .field("score", &self.score)
             .field("latency", &self.latency)
             .field("latency_flux", &self.latency_flux)
@@ -838,16 +1070,34 @@ impl PlayerInfo {
         Ok(PlayerInfo {
             local: in_range(_p.read_int()?, 0, 1)?,
             client_id: in_range(_p.read_int()?, 0, 15)?,
-            team: enums::Team::from_i32(_p.read_int()?)?,
+            team: _p.read_int()?,
             score: _p.read_int()?,
             latency: _p.read_int()?,
             latency_flux: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(0 <= self.local && self.local <= 1);
-        assert!(0 <= self.client_id && self.client_id <= 15);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(local: i32, client_id: i32, team: i32, score: i32, latency: i32, latency_flux: i32) -> Result<PlayerInfo, Error> {
+        let result = PlayerInfo {
+            local,
+            client_id,
+            team,
+            score,
+            latency,
+            latency_flux,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.local, 0, 1)?;
+        in_range(self.client_id, 0, 15)?;
+        enums::Team::from_i32(self.team).known()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.local, 0, 1)?;
+        in_range(self.client_id, 0, 15)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -891,9 +1141,24 @@ impl ClientInfo {
             color_feet: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(0 <= self.use_custom_color && self.use_custom_color <= 1);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(name: [i32; 6], skin: [i32; 6], use_custom_color: i32, color_body: i32, color_feet: i32) -> Result<ClientInfo, Error> {
+        let result = ClientInfo {
+            name,
+            skin,
+            use_custom_color,
+            color_body,
+            color_feet,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.use_custom_color, 0, 1)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.use_custom_color, 0, 1)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -917,8 +1182,19 @@ impl Common {
             y: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32) -> Result<Common, Error> {
+        let result = Common {
+            x,
+            y,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -940,9 +1216,20 @@ impl Explosion {
             common: Common::decode_inner(_p)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common) -> Result<Explosion, Error> {
+        let result = Explosion {
+            common,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -964,9 +1251,20 @@ impl Spawn {
             common: Common::decode_inner(_p)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common) -> Result<Spawn, Error> {
+        let result = Spawn {
+            common,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -988,9 +1286,20 @@ impl HammerHit {
             common: Common::decode_inner(_p)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common) -> Result<HammerHit, Error> {
+        let result = HammerHit {
+            common,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1014,10 +1323,23 @@ impl Death {
             client_id: in_range(_p.read_int()?, 0, 15)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        assert!(0 <= self.client_id && self.client_id <= 15);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common, client_id: i32) -> Result<Death, Error> {
+        let result = Death {
+            common,
+            client_id,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        in_range(self.client_id, 0, 15)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        in_range(self.client_id, 0, 15)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1025,7 +1347,7 @@ impl fmt::Debug for SoundGlobal {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("SoundGlobal")
             .field("common", &self.common)
-            .field("sound_id", &self.sound_id)
+            .field("sound_id", &enums::Sound::from_i32(self.sound_id))
             .finish()
     }
 }
@@ -1038,12 +1360,25 @@ impl SoundGlobal {
     pub fn decode_inner(_p: &mut IntUnpacker) -> Result<SoundGlobal, Error> {
         Ok(SoundGlobal {
             common: Common::decode_inner(_p)?,
-            sound_id: enums::Sound::from_i32(_p.read_int()?)?,
+            sound_id: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common, sound_id: i32) -> Result<SoundGlobal, Error> {
+        let result = SoundGlobal {
+            common,
+            sound_id,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        enums::Sound::from_i32(self.sound_id).known()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1051,7 +1386,7 @@ impl fmt::Debug for SoundWorld {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("SoundWorld")
             .field("common", &self.common)
-            .field("sound_id", &self.sound_id)
+            .field("sound_id", &enums::Sound::from_i32(self.sound_id))
             .finish()
     }
 }
@@ -1064,12 +1399,25 @@ impl SoundWorld {
     pub fn decode_inner(_p: &mut IntUnpacker) -> Result<SoundWorld, Error> {
         Ok(SoundWorld {
             common: Common::decode_inner(_p)?,
-            sound_id: enums::Sound::from_i32(_p.read_int()?)?,
+            sound_id: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common, sound_id: i32) -> Result<SoundWorld, Error> {
+        let result = SoundWorld {
+            common,
+            sound_id,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        enums::Sound::from_i32(self.sound_id).known()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1093,9 +1441,21 @@ impl DamageInd {
             angle: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common, angle: i32) -> Result<DamageInd, Error> {
+        let result = DamageInd {
+            common,
+            angle: wrap_angle(angle),
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1122,3 +1482,245 @@ pub fn obj_size(type_: u16) -> Option<u32> {
         _ => return None,
     })
 }
+
+pub const OBJ_META: &'static [ObjMeta] = &[
+    ObjMeta {
+        type_id: TypeId::Ordinal(PLAYER_INPUT),
+        name: "player_input",
+        size: 10,
+        fields: &[
+            FieldMeta { name: "direction", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "target_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "target_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "jump", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "fire", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "player_state", count: 1, kind: FieldKind::Range { min: 0, max: 4 } },
+            FieldMeta { name: "wanted_weapon", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "next_weapon", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "prev_weapon", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(PROJECTILE),
+        name: "projectile",
+        size: 6,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "type", count: 1, kind: FieldKind::Enum { name: "weapon", offset: 0, values: &["hammer", "pistol", "shotgun", "grenade", "rifle", "ninja"] } },
+            FieldMeta { name: "start_tick", count: 1, kind: FieldKind::Tick },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(LASER),
+        name: "laser",
+        size: 5,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "from_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "from_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "start_tick", count: 1, kind: FieldKind::Tick },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(PICKUP),
+        name: "pickup",
+        size: 4,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "type", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "subtype", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(FLAG),
+        name: "flag",
+        size: 4,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "team", count: 1, kind: FieldKind::Range { min: 0, max: 1 } },
+            FieldMeta { name: "carried_by", count: 1, kind: FieldKind::Range { min: -2, max: 15 } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(GAME),
+        name: "game",
+        size: 12,
+        fields: &[
+            FieldMeta { name: "flags", count: 1, kind: FieldKind::Range { min: 0, max: 256 } },
+            FieldMeta { name: "round_start_tick", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "game_over", count: 1, kind: FieldKind::Range { min: 0, max: 1 } },
+            FieldMeta { name: "sudden_death", count: 1, kind: FieldKind::Range { min: 0, max: 1 } },
+            FieldMeta { name: "paused", count: 1, kind: FieldKind::Range { min: 0, max: 1 } },
+            FieldMeta { name: "score_limit", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "time_limit", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "warmup", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "round_num", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "round_current", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "teamscore_red", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "teamscore_blue", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(CHARACTER_CORE),
+        name: "character_core",
+        size: 15,
+        fields: &[
+            FieldMeta { name: "tick", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "angle", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "direction", count: 1, kind: FieldKind::Range { min: -1, max: 1 } },
+            FieldMeta { name: "jumped", count: 1, kind: FieldKind::Range { min: 0, max: 3 } },
+            FieldMeta { name: "hooked_player", count: 1, kind: FieldKind::Range { min: -1, max: 15 } },
+            FieldMeta { name: "hook_state", count: 1, kind: FieldKind::Range { min: -1, max: 5 } },
+            FieldMeta { name: "hook_tick", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "hook_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_dx", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_dy", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(CHARACTER),
+        name: "character",
+        size: 22,
+        fields: &[
+            FieldMeta { name: "tick", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "angle", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "direction", count: 1, kind: FieldKind::Range { min: -1, max: 1 } },
+            FieldMeta { name: "jumped", count: 1, kind: FieldKind::Range { min: 0, max: 3 } },
+            FieldMeta { name: "hooked_player", count: 1, kind: FieldKind::Range { min: -1, max: 15 } },
+            FieldMeta { name: "hook_state", count: 1, kind: FieldKind::Range { min: -1, max: 5 } },
+            FieldMeta { name: "hook_tick", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "hook_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_dx", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_dy", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "player_state", count: 1, kind: FieldKind::Enum { name: "playerstate", offset: 0, values: &["unknown", "playing", "in_menu", "chatting"] } },
+            FieldMeta { name: "health", count: 1, kind: FieldKind::Range { min: 0, max: 10 } },
+            FieldMeta { name: "armor", count: 1, kind: FieldKind::Range { min: 0, max: 10 } },
+            FieldMeta { name: "ammo_count", count: 1, kind: FieldKind::Range { min: 0, max: 10 } },
+            FieldMeta { name: "weapon", count: 1, kind: FieldKind::Enum { name: "weapon", offset: 0, values: &["hammer", "pistol", "shotgun", "grenade", "rifle", "ninja"] } },
+            FieldMeta { name: "emote", count: 1, kind: FieldKind::Enum { name: "emote", offset: 0, values: &["normal", "pain", "happy", "surprise", "angry", "blink"] } },
+            FieldMeta { name: "attack_tick", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(PLAYER_INFO),
+        name: "player_info",
+        size: 6,
+        fields: &[
+            FieldMeta { name: "local", count: 1, kind: FieldKind::Range { min: 0, max: 1 } },
+            FieldMeta { name: "client_id", count: 1, kind: FieldKind::Range { min: 0, max: 15 } },
+            FieldMeta { name: "team", count: 1, kind: FieldKind::Enum { name: "team", offset: -1, values: &["spectators", "red", "blue"] } },
+            FieldMeta { name: "score", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "latency", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "latency_flux", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(CLIENT_INFO),
+        name: "client_info",
+        size: 15,
+        fields: &[
+            FieldMeta { name: "name", count: 6, kind: FieldKind::Int },
+            FieldMeta { name: "skin", count: 6, kind: FieldKind::Int },
+            FieldMeta { name: "use_custom_color", count: 1, kind: FieldKind::Range { min: 0, max: 1 } },
+            FieldMeta { name: "color_body", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "color_feet", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(COMMON),
+        name: "common",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(EXPLOSION),
+        name: "explosion",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(SPAWN),
+        name: "spawn",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(HAMMER_HIT),
+        name: "hammer_hit",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(DEATH),
+        name: "death",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "client_id", count: 1, kind: FieldKind::Range { min: 0, max: 15 } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(SOUND_GLOBAL),
+        name: "sound_global",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "sound_id", count: 1, kind: FieldKind::Enum { name: "sound", offset: 0, values: &["gun_fire", "shotgun_fire", "grenade_fire", "hammer_fire", "hammer_hit", "ninja_fire", "grenade_explode", "ninja_hit", "rifle_fire", "rifle_bounce", "weapon_switch", "player_pain_short", "player_pain_long", "body_land", "player_airjump", "player_jump", "player_die", "player_spawn", "player_skid", "tee_cry", "hook_loop", "hook_attach_ground", "hook_attach_player", "hook_noattach", "pickup_health", "pickup_armor", "pickup_grenade", "pickup_shotgun", "pickup_ninja", "weapon_spawn", "weapon_noammo", "hit", "chat_server", "chat_client", "ctf_drop", "ctf_return", "ctf_grab_pl", "ctf_grab_en", "ctf_capture"] } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(SOUND_WORLD),
+        name: "sound_world",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "sound_id", count: 1, kind: FieldKind::Enum { name: "sound", offset: 0, values: &["gun_fire", "shotgun_fire", "grenade_fire", "hammer_fire", "hammer_hit", "ninja_fire", "grenade_explode", "ninja_hit", "rifle_fire", "rifle_bounce", "weapon_switch", "player_pain_short", "player_pain_long", "body_land", "player_airjump", "player_jump", "player_die", "player_spawn", "player_skid", "tee_cry", "hook_loop", "hook_attach_ground", "hook_attach_player", "hook_noattach", "pickup_health", "pickup_armor", "pickup_grenade", "pickup_shotgun", "pickup_ninja", "weapon_spawn", "weapon_noammo", "hit", "chat_server", "chat_client", "ctf_drop", "ctf_return", "ctf_grab_pl", "ctf_grab_en", "ctf_capture"] } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(DAMAGE_IND),
+        name: "damage_ind",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "angle", count: 1, kind: FieldKind::Int },
+        ],
+    },
+];
+
+pub fn obj_meta(type_id: TypeId) -> Option<&'static ObjMeta> {
+    OBJ_META.iter().find(|m| m.type_id == type_id)
+}
@@ -0,0 +1,95 @@
+extern crate gamenet_teeworlds_0_5 as gamenet;
+extern crate packer;
+extern crate warn;
+
+use gamenet::msg::connless;
+use gamenet::msg::Addr5Packed;
+use gamenet::msg::Connless;
+use gamenet::msg::CLIENTS_DATA_NONE;
+use packer::with_packer;
+use packer::Unpacker;
+use warn::Panic;
+
+fn round_trip(packet: &[u8]) -> Connless {
+    let decoded = Connless::decode(&mut Panic, &mut Unpacker::new(packet)).unwrap();
+    let mut buf = Vec::with_capacity(1024);
+    assert_eq!(
+        with_packer(&mut buf, |p| decoded.encode(p)).unwrap(),
+        packet
+    );
+    decoded
+}
+
+#[test]
+fn list() {
+    let packet = b"\xff\xff\xff\xfflist\x7f\x00\x00\x01\x6f\x20\xc0\xa8\x00\x02\x70\x20";
+    let servers = match round_trip(packet) {
+        Connless::List(list) => list.servers,
+        other => panic!("unexpected message {:?}", other),
+    };
+    assert_eq!(servers.len(), 2);
+    assert_eq!(servers[0].ip_address(), [127, 0, 0, 1]);
+    assert_eq!(servers[0].port(), 8303);
+    assert_eq!(servers[1].ip_address(), [192, 168, 0, 2]);
+    assert_eq!(servers[1].port(), 8304);
+
+    let servers = [Addr5Packed::new([127, 0, 0, 1], 8303)];
+    let list = connless::List::new(&servers).unwrap();
+    let mut buf = Vec::with_capacity(1024);
+    assert_eq!(
+        with_packer(&mut buf, |p| Connless::from(list).encode(p)).unwrap(),
+        &packet[..14],
+    );
+}
+
+#[test]
+fn count() {
+    match round_trip(b"\xff\xff\xff\xffsiz2\x01\x2c") {
+        Connless::Count(c) => assert_eq!(c.count, 300),
+        other => panic!("unexpected message {:?}", other),
+    }
+}
+
+#[test]
+fn info() {
+    let packet = b"\xff\xff\xff\xffinf2\
+        7\x000.5.2\x00My server\x00dm1\x00DM\x000\x0050\x001\x008\x00\
+        nameless tee\x0012\x00";
+    match round_trip(packet) {
+        Connless::Info(info) => {
+            assert_eq!(info.token, 7);
+            assert_eq!(info.version, b"0.5.2");
+            assert_eq!(info.name, b"My server");
+            assert_eq!(info.map, b"dm1");
+            assert_eq!(info.game_type, b"DM");
+            assert_eq!(info.flags, 0);
+            assert_eq!(info.progression, 50);
+            assert_eq!(info.num_players, 1);
+            assert_eq!(info.max_players, 8);
+            assert_eq!(info.clients.as_bytes(), b"nameless tee\x0012\x00");
+        }
+        other => panic!("unexpected message {:?}", other),
+    }
+    assert_eq!(
+        connless::Info::new(
+            0,
+            b"0.5.2",
+            b"\x01",
+            b"",
+            b"",
+            0,
+            0,
+            0,
+            0,
+            CLIENTS_DATA_NONE
+        )
+        .err(),
+        Some(gamenet::error::Error::ControlCharacters),
+    );
+}
+
+#[test]
+fn unknown() {
+    let packet = b"\xff\xff\xff\xfflis2";
+    assert!(Connless::decode(&mut Panic, &mut Unpacker::new(packet)).is_err());
+}
@@ -18,8 +18,8 @@ pub const EMOTE_SURPRISE: i32 = 3;
 pub const EMOTE_ANGRY: i32 = 4;
 pub const EMOTE_BLINK: i32 = 5;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Emote {
     Normal,
     Pain,
@@ -27,6 +27,7 @@ pub enum Emote {
     Surprise,
     Angry,
     Blink,
+    Unknown(i32),
 }
 
 pub const POWERUP_HEALTH: i32 = 0;
@@ -34,13 +35,14 @@ pub const POWERUP_ARMOR: i32 = 1;
 pub const POWERUP_WEAPON: i32 = 2;
 pub const POWERUP_NINJA: i32 = 3;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Powerup {
     Health,
     Armor,
     Weapon,
     Ninja,
+    Unknown(i32),
 }
 
 pub const EMOTICON_OOP: i32 = 0;
@@ -60,8 +62,8 @@ pub const EMOTICON_WTF: i32 = 13;
 pub const EMOTICON_EYES: i32 = 14;
 pub const EMOTICON_QUESTION: i32 = 15;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Emoticon {
     Oop,
     Exclamation,
@@ -79,6 +81,7 @@ pub enum Emoticon {
     Wtf,
     Eyes,
     Question,
+    Unknown(i32),
 }
 
 pub const WEAPON_HAMMER: i32 = 0;
@@ -88,8 +91,8 @@ pub const WEAPON_GRENADE: i32 = 3;
 pub const WEAPON_RIFLE: i32 = 4;
 pub const WEAPON_NINJA: i32 = 5;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Weapon {
     Hammer,
     Pistol,
@@ -97,18 +100,20 @@ pub enum Weapon {
     Grenade,
     Rifle,
     Ninja,
+    Unknown(i32),
 }
 
 pub const TEAM_SPECTATORS: i32 = -1;
 pub const TEAM_RED: i32 = 0;
 pub const TEAM_BLUE: i32 = 1;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Team {
-    Spectators = -1,
+    Spectators,
     Red,
     Blue,
+    Unknown(i32),
 }
 
 pub const SOUND_GUN_FIRE: i32 = 0;
@@ -153,8 +158,8 @@ pub const SOUND_CTF_GRAB_EN: i32 = 38;
 pub const SOUND_CTF_CAPTURE: i32 = 39;
 pub const SOUND_MENU: i32 = 40;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Sound {
     GunFire,
     ShotgunFire,
@@ -197,20 +202,21 @@ pub enum Sound {
     CtfGrabEn,
     CtfCapture,
     Menu,
+    Unknown(i32),
 }
 
 impl Emote {
-    pub fn from_i32(i: i32) -> Result<Emote, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Emote {
         use self::Emote::*;
-        Ok(match i {
+        match i {
             EMOTE_NORMAL => Normal,
             EMOTE_PAIN => Pain,
             EMOTE_HAPPY => Happy,
             EMOTE_SURPRISE => Surprise,
             EMOTE_ANGRY => Angry,
             EMOTE_BLINK => Blink,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Emote::*;
@@ -221,20 +227,27 @@ impl Emote {
             Surprise => EMOTE_SURPRISE,
             Angry => EMOTE_ANGRY,
             Blink => EMOTE_BLINK,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Emote, IntOutOfRange> {
+        match self {
+            Emote::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Powerup {
-    pub fn from_i32(i: i32) -> Result<Powerup, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Powerup {
         use self::Powerup::*;
-        Ok(match i {
+        match i {
             POWERUP_HEALTH => Health,
             POWERUP_ARMOR => Armor,
             POWERUP_WEAPON => Weapon,
             POWERUP_NINJA => Ninja,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Powerup::*;
@@ -243,14 +256,21 @@ impl Powerup {
             Armor => POWERUP_ARMOR,
             Weapon => POWERUP_WEAPON,
             Ninja => POWERUP_NINJA,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Powerup, IntOutOfRange> {
+        match self {
+            Powerup::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Emoticon {
-    pub fn from_i32(i: i32) -> Result<Emoticon, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Emoticon {
         use self::Emoticon::*;
-        Ok(match i {
+        match i {
             EMOTICON_OOP => Oop,
             EMOTICON_EXCLAMATION => Exclamation,
             EMOTICON_HEARTS => Hearts,
@@ -267,8 +287,8 @@ impl Emoticon {
             EMOTICON_WTF => Wtf,
             EMOTICON_EYES => Eyes,
             EMOTICON_QUESTION => Question,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Emoticon::*;
@@ -289,22 +309,29 @@ impl Emoticon {
             Wtf => EMOTICON_WTF,
             Eyes => EMOTICON_EYES,
             Question => EMOTICON_QUESTION,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Emoticon, IntOutOfRange> {
+        match self {
+            Emoticon::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Weapon {
-    pub fn from_i32(i: i32) -> Result<Weapon, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Weapon {
         use self::Weapon::*;
-        Ok(match i {
+        match i {
             WEAPON_HAMMER => Hammer,
             WEAPON_PISTOL => Pistol,
             WEAPON_SHOTGUN => Shotgun,
             WEAPON_GRENADE => Grenade,
             WEAPON_RIFLE => Rifle,
             WEAPON_NINJA => Ninja,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Weapon::*;
@@ -315,19 +342,26 @@ impl Weapon {
             Grenade => WEAPON_GRENADE,
             Rifle => WEAPON_RIFLE,
             Ninja => WEAPON_NINJA,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Weapon, IntOutOfRange> {
+        match self {
+            Weapon::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Team {
-    pub fn from_i32(i: i32) -> Result<Team, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Team {
         use self::Team::*;
-        Ok(match i {
+        match i {
             TEAM_SPECTATORS => Spectators,
             TEAM_RED => Red,
             TEAM_BLUE => Blue,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Team::*;
@@ -335,14 +369,21 @@ impl Team {
             Spectators => TEAM_SPECTATORS,
             Red => TEAM_RED,
             Blue => TEAM_BLUE,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Team, IntOutOfRange> {
+        match self {
+            Team::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Sound {
-    pub fn from_i32(i: i32) -> Result<Sound, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Sound {
         use self::Sound::*;
-        Ok(match i {
+        match i {
             SOUND_GUN_FIRE => GunFire,
             SOUND_SHOTGUN_FIRE => ShotgunFire,
             SOUND_GRENADE_FIRE => GrenadeFire,
@@ -384,8 +425,8 @@ impl Sound {
             SOUND_CTF_GRAB_EN => CtfGrabEn,
             SOUND_CTF_CAPTURE => CtfCapture,
             SOUND_MENU => Menu,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Sound::*;
@@ -431,6 +472,13 @@ impl Sound {
             CtfGrabEn => SOUND_CTF_GRAB_EN,
             CtfCapture => SOUND_CTF_CAPTURE,
             Menu => SOUND_MENU,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Sound, IntOutOfRange> {
+        match self {
+            Sound::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
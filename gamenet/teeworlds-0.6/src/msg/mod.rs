@@ -1,3 +1,4 @@
+use common::warn::Located;
 use gamenet_common::error::Error;
 use packer::Unpacker;
 use packer::Warning;
@@ -9,8 +10,11 @@ pub mod system;
 
 pub use self::connless::Connless;
 pub use self::game::Game;
+pub use self::game::GameOwned;
 pub use self::system::System;
+pub use self::system::SystemOwned;
 
+pub use gamenet_common::msg::Addr5Packed;
 pub use gamenet_common::msg::AddrPacked;
 pub use gamenet_common::msg::CLIENTS_DATA_NONE;
 pub use gamenet_common::msg::ClientsData;
@@ -25,15 +29,15 @@ impl<'a> gamenet_common::msg::Protocol<'a> for Protocol {
 
     fn decode_system<W>(warn: &mut W, id: MessageId, p: &mut Unpacker<'a>)
         -> Result<Self::System, Error>
-        where W: Warn<Warning>
+        where W: Warn<Located<Warning>>
     {
-        System::decode_msg(warn, id, p)
+        System::decode_msg_located(warn, id, p)
     }
     fn decode_game<W>(warn: &mut W, id: MessageId, p: &mut Unpacker<'a>)
         -> Result<Self::Game, Error>
-        where W: Warn<Warning>
+        where W: Warn<Located<Warning>>
     {
-        Game::decode_msg(warn, id, p)
+        Game::decode_msg_located(warn, id, p)
     }
 }
 
@@ -44,3 +48,12 @@ pub fn decode<'a, W>(warn: &mut W, p: &mut Unpacker<'a>)
     gamenet_common::msg::decode(warn, Protocol, p)
 }
 
+/// Like `decode`, but attaches the offset and the name of the message field
+/// to each warning.
+pub fn decode_located<'a, W>(warn: &mut W, p: &mut Unpacker<'a>)
+    -> Result<SystemOrGame<System<'a>, Game<'a>>, Error>
+    where W: Warn<Located<Warning>>
+{
+    gamenet_common::msg::decode_located(warn, Protocol, p)
+}
+
@@ -1,5 +1,8 @@
-use buffer::CapacityError;
 use common::pretty;
+use common::warn::Located;
+use common::warn::strip_location;
+use error;
+use error::EncodeError;
 use error::Error;
 use packer::Packer;
 use packer::Unpacker;
@@ -21,7 +24,7 @@ impl<'a> System<'a> {
         }
     }
     pub fn encode<'d, 's>(&self, mut p: Packer<'d, 's>)
-        -> Result<&'d [u8], CapacityError>
+        -> Result<&'d [u8], EncodeError>
     {
         with_packer(&mut p, |p| SystemOrGame::System(self.msg_id()).encode_id(p))?;
         with_packer(&mut p, |p| self.encode_msg(p))?;
@@ -51,6 +54,7 @@ pub const RCON_CMD_ADD: i32 = 25;
 pub const RCON_CMD_REMOVE: i32 = 26;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub enum System<'a> {
     Info(Info<'a>),
     MapChange(MapChange<'a>),
@@ -76,28 +80,31 @@ pub enum System<'a> {
 
 impl<'a> System<'a> {
     pub fn decode_msg<W: Warn<Warning>>(warn: &mut W, msg_id: MessageId, _p: &mut Unpacker<'a>) -> Result<System<'a>, Error> {
+        System::decode_msg_located(&mut strip_location(warn), msg_id, _p)
+    }
+    pub fn decode_msg_located<W: Warn<Located<Warning>>>(warn: &mut W, msg_id: MessageId, _p: &mut Unpacker<'a>) -> Result<System<'a>, Error> {
         use self::MessageId::*;
         Ok(match msg_id {
-            Ordinal(INFO) => System::Info(Info::decode(warn, _p)?),
-            Ordinal(MAP_CHANGE) => System::MapChange(MapChange::decode(warn, _p)?),
-            Ordinal(MAP_DATA) => System::MapData(MapData::decode(warn, _p)?),
-            Ordinal(CON_READY) => System::ConReady(ConReady::decode(warn, _p)?),
-            Ordinal(SNAP) => System::Snap(Snap::decode(warn, _p)?),
-            Ordinal(SNAP_EMPTY) => System::SnapEmpty(SnapEmpty::decode(warn, _p)?),
-            Ordinal(SNAP_SINGLE) => System::SnapSingle(SnapSingle::decode(warn, _p)?),
-            Ordinal(INPUT_TIMING) => System::InputTiming(InputTiming::decode(warn, _p)?),
-            Ordinal(RCON_AUTH_STATUS) => System::RconAuthStatus(RconAuthStatus::decode(warn, _p)?),
-            Ordinal(RCON_LINE) => System::RconLine(RconLine::decode(warn, _p)?),
-            Ordinal(READY) => System::Ready(Ready::decode(warn, _p)?),
-            Ordinal(ENTER_GAME) => System::EnterGame(EnterGame::decode(warn, _p)?),
-            Ordinal(INPUT) => System::Input(Input::decode(warn, _p)?),
-            Ordinal(RCON_CMD) => System::RconCmd(RconCmd::decode(warn, _p)?),
-            Ordinal(RCON_AUTH) => System::RconAuth(RconAuth::decode(warn, _p)?),
-            Ordinal(REQUEST_MAP_DATA) => System::RequestMapData(RequestMapData::decode(warn, _p)?),
-            Ordinal(PING) => System::Ping(Ping::decode(warn, _p)?),
-            Ordinal(PING_REPLY) => System::PingReply(PingReply::decode(warn, _p)?),
-            Ordinal(RCON_CMD_ADD) => System::RconCmdAdd(RconCmdAdd::decode(warn, _p)?),
-            Ordinal(RCON_CMD_REMOVE) => System::RconCmdRemove(RconCmdRemove::decode(warn, _p)?),
+            Ordinal(INFO) => System::Info(Info::decode_located(warn, _p)?),
+            Ordinal(MAP_CHANGE) => System::MapChange(MapChange::decode_located(warn, _p)?),
+            Ordinal(MAP_DATA) => System::MapData(MapData::decode_located(warn, _p)?),
+            Ordinal(CON_READY) => System::ConReady(ConReady::decode_located(warn, _p)?),
+            Ordinal(SNAP) => System::Snap(Snap::decode_located(warn, _p)?),
+            Ordinal(SNAP_EMPTY) => System::SnapEmpty(SnapEmpty::decode_located(warn, _p)?),
+            Ordinal(SNAP_SINGLE) => System::SnapSingle(SnapSingle::decode_located(warn, _p)?),
+            Ordinal(INPUT_TIMING) => System::InputTiming(InputTiming::decode_located(warn, _p)?),
+            Ordinal(RCON_AUTH_STATUS) => System::RconAuthStatus(RconAuthStatus::decode_located(warn, _p)?),
+            Ordinal(RCON_LINE) => System::RconLine(RconLine::decode_located(warn, _p)?),
+            Ordinal(READY) => System::Ready(Ready::decode_located(warn, _p)?),
+            Ordinal(ENTER_GAME) => System::EnterGame(EnterGame::decode_located(warn, _p)?),
+            Ordinal(INPUT) => System::Input(Input::decode_located(warn, _p)?),
+            Ordinal(RCON_CMD) => System::RconCmd(RconCmd::decode_located(warn, _p)?),
+            Ordinal(RCON_AUTH) => System::RconAuth(RconAuth::decode_located(warn, _p)?),
+            Ordinal(REQUEST_MAP_DATA) => System::RequestMapData(RequestMapData::decode_located(warn, _p)?),
+            Ordinal(PING) => System::Ping(Ping::decode_located(warn, _p)?),
+            Ordinal(PING_REPLY) => System::PingReply(PingReply::decode_located(warn, _p)?),
+            Ordinal(RCON_CMD_ADD) => System::RconCmdAdd(RconCmdAdd::decode_located(warn, _p)?),
+            Ordinal(RCON_CMD_REMOVE) => System::RconCmdRemove(RconCmdRemove::decode_located(warn, _p)?),
             _ => return Err(Error::UnknownId),
         })
     }
@@ -125,7 +132,31 @@ impl<'a> System<'a> {
             System::RconCmdRemove(_) => MessageId::from(RCON_CMD_REMOVE),
         }
     }
-    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        match *self {
+            System::Info(ref i) => i.validate(),
+            System::MapChange(ref i) => i.validate(),
+            System::MapData(ref i) => i.validate(),
+            System::ConReady(ref i) => i.validate(),
+            System::Snap(ref i) => i.validate(),
+            System::SnapEmpty(ref i) => i.validate(),
+            System::SnapSingle(ref i) => i.validate(),
+            System::InputTiming(ref i) => i.validate(),
+            System::RconAuthStatus(ref i) => i.validate(),
+            System::RconLine(ref i) => i.validate(),
+            System::Ready(ref i) => i.validate(),
+            System::EnterGame(ref i) => i.validate(),
+            System::Input(ref i) => i.validate(),
+            System::RconCmd(ref i) => i.validate(),
+            System::RconAuth(ref i) => i.validate(),
+            System::RequestMapData(ref i) => i.validate(),
+            System::Ping(ref i) => i.validate(),
+            System::PingReply(ref i) => i.validate(),
+            System::RconCmdAdd(ref i) => i.validate(),
+            System::RconCmdRemove(ref i) => i.validate(),
+        }
+    }
+    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         match *self {
             System::Info(ref i) => i.encode(p),
             System::MapChange(ref i) => i.encode(p),
@@ -297,78 +328,195 @@ impl<'a> From<RconCmdRemove<'a>> for System<'a> {
         System::RconCmdRemove(i)
     }
 }
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SystemOwned {
+    Info(InfoOwned),
+    MapChange(MapChangeOwned),
+    MapData(MapDataOwned),
+    ConReady(ConReady),
+    Snap(SnapOwned),
+    SnapEmpty(SnapEmpty),
+    SnapSingle(SnapSingleOwned),
+    InputTiming(InputTiming),
+    RconAuthStatus(RconAuthStatus),
+    RconLine(RconLineOwned),
+    Ready(Ready),
+    EnterGame(EnterGame),
+    Input(Input),
+    RconCmd(RconCmdOwned),
+    RconAuth(RconAuthOwned),
+    RequestMapData(RequestMapData),
+    Ping(Ping),
+    PingReply(PingReply),
+    RconCmdAdd(RconCmdAddOwned),
+    RconCmdRemove(RconCmdRemoveOwned),
+}
+
+impl<'a> System<'a> {
+    pub fn to_owned(&self) -> SystemOwned {
+        match *self {
+            System::Info(ref i) => SystemOwned::Info(i.to_owned()),
+            System::MapChange(ref i) => SystemOwned::MapChange(i.to_owned()),
+            System::MapData(ref i) => SystemOwned::MapData(i.to_owned()),
+            System::ConReady(i) => SystemOwned::ConReady(i),
+            System::Snap(ref i) => SystemOwned::Snap(i.to_owned()),
+            System::SnapEmpty(i) => SystemOwned::SnapEmpty(i),
+            System::SnapSingle(ref i) => SystemOwned::SnapSingle(i.to_owned()),
+            System::InputTiming(i) => SystemOwned::InputTiming(i),
+            System::RconAuthStatus(i) => SystemOwned::RconAuthStatus(i),
+            System::RconLine(ref i) => SystemOwned::RconLine(i.to_owned()),
+            System::Ready(i) => SystemOwned::Ready(i),
+            System::EnterGame(i) => SystemOwned::EnterGame(i),
+            System::Input(i) => SystemOwned::Input(i),
+            System::RconCmd(ref i) => SystemOwned::RconCmd(i.to_owned()),
+            System::RconAuth(ref i) => SystemOwned::RconAuth(i.to_owned()),
+            System::RequestMapData(i) => SystemOwned::RequestMapData(i),
+            System::Ping(i) => SystemOwned::Ping(i),
+            System::PingReply(i) => SystemOwned::PingReply(i),
+            System::RconCmdAdd(ref i) => SystemOwned::RconCmdAdd(i.to_owned()),
+            System::RconCmdRemove(ref i) => SystemOwned::RconCmdRemove(i.to_owned()),
+        }
+    }
+}
+
+impl SystemOwned {
+    pub fn as_ref(&self) -> System<'_> {
+        match *self {
+            SystemOwned::Info(ref i) => System::Info(i.as_ref()),
+            SystemOwned::MapChange(ref i) => System::MapChange(i.as_ref()),
+            SystemOwned::MapData(ref i) => System::MapData(i.as_ref()),
+            SystemOwned::ConReady(i) => System::ConReady(i),
+            SystemOwned::Snap(ref i) => System::Snap(i.as_ref()),
+            SystemOwned::SnapEmpty(i) => System::SnapEmpty(i),
+            SystemOwned::SnapSingle(ref i) => System::SnapSingle(i.as_ref()),
+            SystemOwned::InputTiming(i) => System::InputTiming(i),
+            SystemOwned::RconAuthStatus(i) => System::RconAuthStatus(i),
+            SystemOwned::RconLine(ref i) => System::RconLine(i.as_ref()),
+            SystemOwned::Ready(i) => System::Ready(i),
+            SystemOwned::EnterGame(i) => System::EnterGame(i),
+            SystemOwned::Input(i) => System::Input(i),
+            SystemOwned::RconCmd(ref i) => System::RconCmd(i.as_ref()),
+            SystemOwned::RconAuth(ref i) => System::RconAuth(i.as_ref()),
+            SystemOwned::RequestMapData(i) => System::RequestMapData(i),
+            SystemOwned::Ping(i) => System::Ping(i),
+            SystemOwned::PingReply(i) => System::PingReply(i),
+            SystemOwned::RconCmdAdd(ref i) => System::RconCmdAdd(i.as_ref()),
+            SystemOwned::RconCmdRemove(ref i) => System::RconCmdRemove(i.as_ref()),
+        }
+    }
+    pub fn msg_id(&self) -> MessageId {
+        self.as_ref().msg_id()
+    }
+    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        self.as_ref().encode_msg(p)
+    }
+}
+
+impl fmt::Debug for SystemOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> From<System<'a>> for SystemOwned {
+    fn from(i: System<'a>) -> SystemOwned {
+        i.to_owned()
+    }
+}
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Info<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub version: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::optional_bytes::serialize"))]
     pub password: Option<&'a [u8]>,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct MapChange<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
     pub crc: i32,
     pub size: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct MapData<'a> {
     pub last: i32,
     pub crc: i32,
     pub chunk: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub data: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ConReady;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Snap<'a> {
     pub tick: i32,
     pub delta_tick: i32,
     pub num_parts: i32,
     pub part: i32,
     pub crc: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub data: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SnapEmpty {
     pub tick: i32,
     pub delta_tick: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SnapSingle<'a> {
     pub tick: i32,
     pub delta_tick: i32,
     pub crc: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub data: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct InputTiming {
     pub input_pred_tick: i32,
     pub time_left: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RconAuthStatus {
     pub auth_level: Option<i32>,
     pub receive_commands: Option<i32>,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct RconLine<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub line: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Ready;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EnterGame;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Input {
     pub ack_snapshot: i32,
     pub intended_tick: i32,
@@ -377,51 +525,174 @@ pub struct Input {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct RconCmd<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub cmd: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct RconAuth<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub _unused: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub password: &'a [u8],
     pub request_commands: Option<i32>,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RequestMapData {
     pub chunk: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Ping;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PingReply;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct RconCmdAdd<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub help: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub params: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct RconCmdRemove<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InfoOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub version: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::optional_bytes"))]
+    pub password: Option<Vec<u8>>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MapChangeOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+    pub crc: i32,
+    pub size: i32,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MapDataOwned {
+    pub last: i32,
+    pub crc: i32,
+    pub chunk: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SnapOwned {
+    pub tick: i32,
+    pub delta_tick: i32,
+    pub num_parts: i32,
+    pub part: i32,
+    pub crc: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SnapSingleOwned {
+    pub tick: i32,
+    pub delta_tick: i32,
+    pub crc: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RconLineOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub line: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RconCmdOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub cmd: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RconAuthOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub _unused: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub password: Vec<u8>,
+    pub request_commands: Option<i32>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RconCmdAddOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub help: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub params: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RconCmdRemoveOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+}
+
 impl<'a> Info<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info<'a>, Error> {
-        let result = Ok(Info {
+        Info::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info<'a>, Error> {
+        let result = Info::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Info"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info<'a>, Error> {
+        Ok(Info {
             version: _p.read_string()?,
             password: _p.read_string().ok(),
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(version: &'a [u8], password: Option<&'a [u8]>) -> Result<Info<'a>, Error> {
+        let result = Info {
+            version,
+            password,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(self.password.is_some());
+    pub fn validate(&self) -> Result<(), Error> {
+        self.password.ok_or(Error::MissingValue)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        self.password.ok_or(Error::MissingValue)?;
         _p.write_string(self.version)?;
         _p.write_string(self.password.unwrap())?;
         Ok(_p.written())
@@ -438,15 +709,32 @@ impl<'a> fmt::Debug for Info<'a> {
 
 impl<'a> MapChange<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapChange<'a>, Error> {
-        let result = Ok(MapChange {
+        MapChange::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapChange<'a>, Error> {
+        let result = MapChange::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "MapChange"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapChange<'a>, Error> {
+        Ok(MapChange {
             name: _p.read_string()?,
-            crc: _p.read_int(warn)?,
-            size: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
+            crc: _p.read_int(&mut _p.locate(warn, "MapChange.crc"))?,
+            size: _p.read_int(&mut _p.locate(warn, "MapChange.size"))?,
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(name: &'a [u8], crc: i32, size: i32) -> Result<MapChange<'a>, Error> {
+        let result = MapChange {
+            name,
+            crc,
+            size,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.name)?;
         _p.write_int(self.crc)?;
         _p.write_int(self.size)?;
@@ -465,16 +753,34 @@ impl<'a> fmt::Debug for MapChange<'a> {
 
 impl<'a> MapData<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapData<'a>, Error> {
-        let result = Ok(MapData {
-            last: _p.read_int(warn)?,
-            crc: _p.read_int(warn)?,
-            chunk: _p.read_int(warn)?,
-            data: _p.read_data(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        MapData::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapData<'a>, Error> {
+        let result = MapData::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "MapData"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapData<'a>, Error> {
+        Ok(MapData {
+            last: _p.read_int(&mut _p.locate(warn, "MapData.last"))?,
+            crc: _p.read_int(&mut _p.locate(warn, "MapData.crc"))?,
+            chunk: _p.read_int(&mut _p.locate(warn, "MapData.chunk"))?,
+            data: _p.read_data(&mut _p.locate(warn, "MapData.data"))?,
+        })
+    }
+    pub fn new(last: i32, crc: i32, chunk: i32, data: &'a [u8]) -> Result<MapData<'a>, Error> {
+        let result = MapData {
+            last,
+            crc,
+            chunk,
+            data,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.last)?;
         _p.write_int(self.crc)?;
         _p.write_int(self.chunk)?;
@@ -495,11 +801,15 @@ impl<'a> fmt::Debug for MapData<'a> {
 
 impl ConReady {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ConReady, Error> {
-        let result = Ok(ConReady);
-        _p.finish(warn);
-        result
+        ConReady::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ConReady, Error> {
+        error::finish(&mut _p.locate(warn, "ConReady"), _p, Ok(ConReady))
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -512,18 +822,38 @@ impl fmt::Debug for ConReady {
 
 impl<'a> Snap<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Snap<'a>, Error> {
-        let result = Ok(Snap {
-            tick: _p.read_int(warn)?,
-            delta_tick: _p.read_int(warn)?,
-            num_parts: _p.read_int(warn)?,
-            part: _p.read_int(warn)?,
-            crc: _p.read_int(warn)?,
-            data: _p.read_data(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        Snap::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Snap<'a>, Error> {
+        let result = Snap::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Snap"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Snap<'a>, Error> {
+        Ok(Snap {
+            tick: _p.read_int(&mut _p.locate(warn, "Snap.tick"))?,
+            delta_tick: _p.read_int(&mut _p.locate(warn, "Snap.delta_tick"))?,
+            num_parts: _p.read_int(&mut _p.locate(warn, "Snap.num_parts"))?,
+            part: _p.read_int(&mut _p.locate(warn, "Snap.part"))?,
+            crc: _p.read_int(&mut _p.locate(warn, "Snap.crc"))?,
+            data: _p.read_data(&mut _p.locate(warn, "Snap.data"))?,
+        })
+    }
+    pub fn new(tick: i32, delta_tick: i32, num_parts: i32, part: i32, crc: i32, data: &'a [u8]) -> Result<Snap<'a>, Error> {
+        let result = Snap {
+            tick,
+            delta_tick,
+            num_parts,
+            part,
+            crc,
+            data,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.tick)?;
         _p.write_int(self.delta_tick)?;
         _p.write_int(self.num_parts)?;
@@ -548,14 +878,30 @@ impl<'a> fmt::Debug for Snap<'a> {
 
 impl SnapEmpty {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SnapEmpty, Error> {
-        let result = Ok(SnapEmpty {
-            tick: _p.read_int(warn)?,
-            delta_tick: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        SnapEmpty::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SnapEmpty, Error> {
+        let result = SnapEmpty::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SnapEmpty"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SnapEmpty, Error> {
+        Ok(SnapEmpty {
+            tick: _p.read_int(&mut _p.locate(warn, "SnapEmpty.tick"))?,
+            delta_tick: _p.read_int(&mut _p.locate(warn, "SnapEmpty.delta_tick"))?,
+        })
+    }
+    pub fn new(tick: i32, delta_tick: i32) -> Result<SnapEmpty, Error> {
+        let result = SnapEmpty {
+            tick,
+            delta_tick,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.tick)?;
         _p.write_int(self.delta_tick)?;
         Ok(_p.written())
@@ -572,16 +918,34 @@ impl fmt::Debug for SnapEmpty {
 
 impl<'a> SnapSingle<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SnapSingle<'a>, Error> {
-        let result = Ok(SnapSingle {
-            tick: _p.read_int(warn)?,
-            delta_tick: _p.read_int(warn)?,
-            crc: _p.read_int(warn)?,
-            data: _p.read_data(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        SnapSingle::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SnapSingle<'a>, Error> {
+        let result = SnapSingle::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SnapSingle"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SnapSingle<'a>, Error> {
+        Ok(SnapSingle {
+            tick: _p.read_int(&mut _p.locate(warn, "SnapSingle.tick"))?,
+            delta_tick: _p.read_int(&mut _p.locate(warn, "SnapSingle.delta_tick"))?,
+            crc: _p.read_int(&mut _p.locate(warn, "SnapSingle.crc"))?,
+            data: _p.read_data(&mut _p.locate(warn, "SnapSingle.data"))?,
+        })
+    }
+    pub fn new(tick: i32, delta_tick: i32, crc: i32, data: &'a [u8]) -> Result<SnapSingle<'a>, Error> {
+        let result = SnapSingle {
+            tick,
+            delta_tick,
+            crc,
+            data,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.tick)?;
         _p.write_int(self.delta_tick)?;
         _p.write_int(self.crc)?;
@@ -602,14 +966,30 @@ impl<'a> fmt::Debug for SnapSingle<'a> {
 
 impl InputTiming {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<InputTiming, Error> {
-        let result = Ok(InputTiming {
-            input_pred_tick: _p.read_int(warn)?,
-            time_left: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        InputTiming::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<InputTiming, Error> {
+        let result = InputTiming::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "InputTiming"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<InputTiming, Error> {
+        Ok(InputTiming {
+            input_pred_tick: _p.read_int(&mut _p.locate(warn, "InputTiming.input_pred_tick"))?,
+            time_left: _p.read_int(&mut _p.locate(warn, "InputTiming.time_left"))?,
+        })
+    }
+    pub fn new(input_pred_tick: i32, time_left: i32) -> Result<InputTiming, Error> {
+        let result = InputTiming {
+            input_pred_tick,
+            time_left,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.input_pred_tick)?;
         _p.write_int(self.time_left)?;
         Ok(_p.written())
@@ -626,16 +1006,34 @@ impl fmt::Debug for InputTiming {
 
 impl RconAuthStatus {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RconAuthStatus, Error> {
-        let result = Ok(RconAuthStatus {
-            auth_level: _p.read_int(warn).ok(),
-            receive_commands: _p.read_int(warn).ok(),
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(self.auth_level.is_some());
-        assert!(self.receive_commands.is_some());
+        RconAuthStatus::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RconAuthStatus, Error> {
+        let result = RconAuthStatus::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconAuthStatus"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RconAuthStatus, Error> {
+        Ok(RconAuthStatus {
+            auth_level: _p.read_int(&mut _p.locate(warn, "RconAuthStatus.auth_level")).ok(),
+            receive_commands: _p.read_int(&mut _p.locate(warn, "RconAuthStatus.receive_commands")).ok(),
+        })
+    }
+    pub fn new(auth_level: Option<i32>, receive_commands: Option<i32>) -> Result<RconAuthStatus, Error> {
+        let result = RconAuthStatus {
+            auth_level,
+            receive_commands,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.auth_level.ok_or(Error::MissingValue)?;
+        self.receive_commands.ok_or(Error::MissingValue)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        self.auth_level.ok_or(Error::MissingValue)?;
+        self.receive_commands.ok_or(Error::MissingValue)?;
         _p.write_int(self.auth_level.unwrap())?;
         _p.write_int(self.receive_commands.unwrap())?;
         Ok(_p.written())
@@ -652,13 +1050,28 @@ impl fmt::Debug for RconAuthStatus {
 
 impl<'a> RconLine<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconLine<'a>, Error> {
-        let result = Ok(RconLine {
+        RconLine::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconLine<'a>, Error> {
+        let result = RconLine::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconLine"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconLine<'a>, Error> {
+        Ok(RconLine {
             line: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(line: &'a [u8]) -> Result<RconLine<'a>, Error> {
+        let result = RconLine {
+            line,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.line)?;
         Ok(_p.written())
     }
@@ -673,11 +1086,15 @@ impl<'a> fmt::Debug for RconLine<'a> {
 
 impl Ready {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Ready, Error> {
-        let result = Ok(Ready);
-        _p.finish(warn);
-        result
+        Ready::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Ready, Error> {
+        error::finish(&mut _p.locate(warn, "Ready"), _p, Ok(Ready))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -690,11 +1107,15 @@ impl fmt::Debug for Ready {
 
 impl EnterGame {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<EnterGame, Error> {
-        let result = Ok(EnterGame);
-        _p.finish(warn);
-        result
+        EnterGame::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<EnterGame, Error> {
+        error::finish(&mut _p.locate(warn, "EnterGame"), _p, Ok(EnterGame))
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -707,16 +1128,34 @@ impl fmt::Debug for EnterGame {
 
 impl Input {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Input, Error> {
-        let result = Ok(Input {
-            ack_snapshot: _p.read_int(warn)?,
-            intended_tick: _p.read_int(warn)?,
-            input_size: _p.read_int(warn)?,
-            input: ::snap_obj::PlayerInput::decode_msg(warn, _p)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        Input::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Input, Error> {
+        let result = Input::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Input"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Input, Error> {
+        Ok(Input {
+            ack_snapshot: _p.read_int(&mut _p.locate(warn, "Input.ack_snapshot"))?,
+            intended_tick: _p.read_int(&mut _p.locate(warn, "Input.intended_tick"))?,
+            input_size: _p.read_int(&mut _p.locate(warn, "Input.input_size"))?,
+            input: ::snap_obj::PlayerInput::decode_msg_located(warn, _p)?,
+        })
+    }
+    pub fn new(ack_snapshot: i32, intended_tick: i32, input_size: i32, input: ::snap_obj::PlayerInput) -> Result<Input, Error> {
+        let result = Input {
+            ack_snapshot,
+            intended_tick,
+            input_size,
+            input,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.ack_snapshot)?;
         _p.write_int(self.intended_tick)?;
         _p.write_int(self.input_size)?;
@@ -737,13 +1176,28 @@ impl fmt::Debug for Input {
 
 impl<'a> RconCmd<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmd<'a>, Error> {
-        let result = Ok(RconCmd {
+        RconCmd::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmd<'a>, Error> {
+        let result = RconCmd::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconCmd"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmd<'a>, Error> {
+        Ok(RconCmd {
             cmd: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(cmd: &'a [u8]) -> Result<RconCmd<'a>, Error> {
+        let result = RconCmd {
+            cmd,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.cmd)?;
         Ok(_p.written())
     }
@@ -758,16 +1212,34 @@ impl<'a> fmt::Debug for RconCmd<'a> {
 
 impl<'a> RconAuth<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconAuth<'a>, Error> {
-        let result = Ok(RconAuth {
+        RconAuth::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconAuth<'a>, Error> {
+        let result = RconAuth::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconAuth"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconAuth<'a>, Error> {
+        Ok(RconAuth {
             _unused: _p.read_string()?,
             password: _p.read_string()?,
-            request_commands: _p.read_int(warn).ok(),
-        });
-        _p.finish(warn);
-        result
+            request_commands: _p.read_int(&mut _p.locate(warn, "RconAuth.request_commands")).ok(),
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(self.request_commands.is_some());
+    pub fn new(_unused: &'a [u8], password: &'a [u8], request_commands: Option<i32>) -> Result<RconAuth<'a>, Error> {
+        let result = RconAuth {
+            _unused,
+            password,
+            request_commands,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.request_commands.ok_or(Error::MissingValue)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        self.request_commands.ok_or(Error::MissingValue)?;
         _p.write_string(self._unused)?;
         _p.write_string(self.password)?;
         _p.write_int(self.request_commands.unwrap())?;
@@ -786,13 +1258,28 @@ impl<'a> fmt::Debug for RconAuth<'a> {
 
 impl RequestMapData {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestMapData, Error> {
-        let result = Ok(RequestMapData {
-            chunk: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
+        RequestMapData::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestMapData, Error> {
+        let result = RequestMapData::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RequestMapData"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestMapData, Error> {
+        Ok(RequestMapData {
+            chunk: _p.read_int(&mut _p.locate(warn, "RequestMapData.chunk"))?,
+        })
+    }
+    pub fn new(chunk: i32) -> Result<RequestMapData, Error> {
+        let result = RequestMapData {
+            chunk,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.chunk)?;
         Ok(_p.written())
     }
@@ -807,11 +1294,15 @@ impl fmt::Debug for RequestMapData {
 
 impl Ping {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Ping, Error> {
-        let result = Ok(Ping);
-        _p.finish(warn);
-        result
+        Ping::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Ping, Error> {
+        error::finish(&mut _p.locate(warn, "Ping"), _p, Ok(Ping))
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -824,11 +1315,15 @@ impl fmt::Debug for Ping {
 
 impl PingReply {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<PingReply, Error> {
-        let result = Ok(PingReply);
-        _p.finish(warn);
-        result
+        PingReply::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<PingReply, Error> {
+        error::finish(&mut _p.locate(warn, "PingReply"), _p, Ok(PingReply))
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -841,15 +1336,32 @@ impl fmt::Debug for PingReply {
 
 impl<'a> RconCmdAdd<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmdAdd<'a>, Error> {
-        let result = Ok(RconCmdAdd {
+        RconCmdAdd::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmdAdd<'a>, Error> {
+        let result = RconCmdAdd::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconCmdAdd"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmdAdd<'a>, Error> {
+        Ok(RconCmdAdd {
             name: _p.read_string()?,
             help: _p.read_string()?,
             params: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(name: &'a [u8], help: &'a [u8], params: &'a [u8]) -> Result<RconCmdAdd<'a>, Error> {
+        let result = RconCmdAdd {
+            name,
+            help,
+            params,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.name)?;
         _p.write_string(self.help)?;
         _p.write_string(self.params)?;
@@ -868,13 +1380,28 @@ impl<'a> fmt::Debug for RconCmdAdd<'a> {
 
 impl<'a> RconCmdRemove<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmdRemove<'a>, Error> {
-        let result = Ok(RconCmdRemove {
+        RconCmdRemove::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmdRemove<'a>, Error> {
+        let result = RconCmdRemove::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconCmdRemove"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmdRemove<'a>, Error> {
+        Ok(RconCmdRemove {
             name: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(name: &'a [u8]) -> Result<RconCmdRemove<'a>, Error> {
+        let result = RconCmdRemove {
+            name,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.name)?;
         Ok(_p.written())
     }
@@ -887,3 +1414,259 @@ impl<'a> fmt::Debug for RconCmdRemove<'a> {
     }
 }
 
+impl<'a> Info<'a> {
+    pub fn to_owned(&self) -> InfoOwned {
+        InfoOwned {
+            version: self.version.to_vec(),
+            password: self.password.map(|v| v.to_vec()),
+        }
+    }
+}
+
+impl InfoOwned {
+    pub fn as_ref(&self) -> Info<'_> {
+        Info {
+            version: &self.version[..],
+            password: self.password.as_ref().map(|v| &v[..]),
+        }
+    }
+}
+
+impl fmt::Debug for InfoOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> MapChange<'a> {
+    pub fn to_owned(&self) -> MapChangeOwned {
+        MapChangeOwned {
+            name: self.name.to_vec(),
+            crc: self.crc,
+            size: self.size,
+        }
+    }
+}
+
+impl MapChangeOwned {
+    pub fn as_ref(&self) -> MapChange<'_> {
+        MapChange {
+            name: &self.name[..],
+            crc: self.crc,
+            size: self.size,
+        }
+    }
+}
+
+impl fmt::Debug for MapChangeOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> MapData<'a> {
+    pub fn to_owned(&self) -> MapDataOwned {
+        MapDataOwned {
+            last: self.last,
+            crc: self.crc,
+            chunk: self.chunk,
+            data: self.data.to_vec(),
+        }
+    }
+}
+
+impl MapDataOwned {
+    pub fn as_ref(&self) -> MapData<'_> {
+        MapData {
+            last: self.last,
+            crc: self.crc,
+            chunk: self.chunk,
+            data: &self.data[..],
+        }
+    }
+}
+
+impl fmt::Debug for MapDataOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> Snap<'a> {
+    pub fn to_owned(&self) -> SnapOwned {
+        SnapOwned {
+            tick: self.tick,
+            delta_tick: self.delta_tick,
+            num_parts: self.num_parts,
+            part: self.part,
+            crc: self.crc,
+            data: self.data.to_vec(),
+        }
+    }
+}
+
+impl SnapOwned {
+    pub fn as_ref(&self) -> Snap<'_> {
+        Snap {
+            tick: self.tick,
+            delta_tick: self.delta_tick,
+            num_parts: self.num_parts,
+            part: self.part,
+            crc: self.crc,
+            data: &self.data[..],
+        }
+    }
+}
+
+impl fmt::Debug for SnapOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SnapSingle<'a> {
+    pub fn to_owned(&self) -> SnapSingleOwned {
+        SnapSingleOwned {
+            tick: self.tick,
+            delta_tick: self.delta_tick,
+            crc: self.crc,
+            data: self.data.to_vec(),
+        }
+    }
+}
+
+impl SnapSingleOwned {
+    pub fn as_ref(&self) -> SnapSingle<'_> {
+        SnapSingle {
+            tick: self.tick,
+            delta_tick: self.delta_tick,
+            crc: self.crc,
+            data: &self.data[..],
+        }
+    }
+}
+
+impl fmt::Debug for SnapSingleOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> RconLine<'a> {
+    pub fn to_owned(&self) -> RconLineOwned {
+        RconLineOwned {
+            line: self.line.to_vec(),
+        }
+    }
+}
+
+impl RconLineOwned {
+    pub fn as_ref(&self) -> RconLine<'_> {
+        RconLine {
+            line: &self.line[..],
+        }
+    }
+}
+
+impl fmt::Debug for RconLineOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> RconCmd<'a> {
+    pub fn to_owned(&self) -> RconCmdOwned {
+        RconCmdOwned {
+            cmd: self.cmd.to_vec(),
+        }
+    }
+}
+
+impl RconCmdOwned {
+    pub fn as_ref(&self) -> RconCmd<'_> {
+        RconCmd {
+            cmd: &self.cmd[..],
+        }
+    }
+}
+
+impl fmt::Debug for RconCmdOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> RconAuth<'a> {
+    pub fn to_owned(&self) -> RconAuthOwned {
+        RconAuthOwned {
+            _unused: self._unused.to_vec(),
+            password: self.password.to_vec(),
+            request_commands: self.request_commands,
+        }
+    }
+}
+
+impl RconAuthOwned {
+    pub fn as_ref(&self) -> RconAuth<'_> {
+        RconAuth {
+            _unused: &self._unused[..],
+            password: &self.password[..],
+            request_commands: self.request_commands,
+        }
+    }
+}
+
+impl fmt::Debug for RconAuthOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> RconCmdAdd<'a> {
+    pub fn to_owned(&self) -> RconCmdAddOwned {
+        RconCmdAddOwned {
+            name: self.name.to_vec(),
+            help: self.help.to_vec(),
+            params: self.params.to_vec(),
+        }
+    }
+}
+
+impl RconCmdAddOwned {
+    pub fn as_ref(&self) -> RconCmdAdd<'_> {
+        RconCmdAdd {
+            name: &self.name[..],
+            help: &self.help[..],
+            params: &self.params[..],
+        }
+    }
+}
+
+impl fmt::Debug for RconCmdAddOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> RconCmdRemove<'a> {
+    pub fn to_owned(&self) -> RconCmdRemoveOwned {
+        RconCmdRemoveOwned {
+            name: self.name.to_vec(),
+        }
+    }
+}
+
+impl RconCmdRemoveOwned {
+    pub fn as_ref(&self) -> RconCmdRemove<'_> {
+        RconCmdRemove {
+            name: &self.name[..],
+        }
+    }
+}
+
+impl fmt::Debug for RconCmdRemoveOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
@@ -1,7 +1,14 @@
-use buffer::CapacityError;
 use common::slice;
+use common::warn::Located;
+use common::warn::strip_location;
 use enums;
+use error;
+use error::EncodeError;
 use error::Error;
+use gamenet_common::snap_obj::FieldKind;
+use gamenet_common::snap_obj::FieldMeta;
+use gamenet_common::snap_obj::ObjMeta;
+use gamenet_common::snap_obj::wrap_angle;
 use packer::ExcessData;
 use packer::IntUnpacker;
 use packer::Packer;
@@ -50,6 +57,7 @@ pub const SOUND_WORLD: u16 = 19;
 pub const DAMAGE_IND: u16 = 20;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SnapObj {
     PlayerInput(PlayerInput),
     Projectile(Projectile),
@@ -124,7 +132,31 @@ impl SnapObj {
             SnapObj::DamageInd(_) => TypeId::from(DAMAGE_IND),
         }
     }
-    pub fn encode(&self) -> &[i32] {
+    pub fn validate(&self) -> Result<(), Error> {
+        match *self {
+            SnapObj::PlayerInput(ref i) => i.validate(),
+            SnapObj::Projectile(ref i) => i.validate(),
+            SnapObj::Laser(ref i) => i.validate(),
+            SnapObj::Pickup(ref i) => i.validate(),
+            SnapObj::Flag(ref i) => i.validate(),
+            SnapObj::GameInfo(ref i) => i.validate(),
+            SnapObj::GameData(ref i) => i.validate(),
+            SnapObj::CharacterCore(ref i) => i.validate(),
+            SnapObj::Character(ref i) => i.validate(),
+            SnapObj::PlayerInfo(ref i) => i.validate(),
+            SnapObj::ClientInfo(ref i) => i.validate(),
+            SnapObj::SpectatorInfo(ref i) => i.validate(),
+            SnapObj::Common(ref i) => i.validate(),
+            SnapObj::Explosion(ref i) => i.validate(),
+            SnapObj::Spawn(ref i) => i.validate(),
+            SnapObj::HammerHit(ref i) => i.validate(),
+            SnapObj::Death(ref i) => i.validate(),
+            SnapObj::SoundGlobal(ref i) => i.validate(),
+            SnapObj::SoundWorld(ref i) => i.validate(),
+            SnapObj::DamageInd(ref i) => i.validate(),
+        }
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
         match *self {
             SnapObj::PlayerInput(ref i) => i.encode(),
             SnapObj::Projectile(ref i) => i.encode(),
@@ -298,7 +330,8 @@ impl From<DamageInd> for SnapObj {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PlayerInput {
     pub direction: i32,
     pub target_x: i32,
@@ -314,17 +347,20 @@ pub struct PlayerInput {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Projectile {
     pub x: i32,
     pub y: i32,
     pub vel_x: i32,
     pub vel_y: i32,
-    pub type_: enums::Weapon,
+    /// Raw `enums::Weapon` value, convert it using `enums::Weapon::from_i32`.
+    pub type_: i32,
     pub start_tick: ::snap_obj::Tick,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Laser {
     pub x: i32,
     pub y: i32,
@@ -335,6 +371,7 @@ pub struct Laser {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Pickup {
     pub x: i32,
     pub y: i32,
@@ -344,6 +381,7 @@ pub struct Pickup {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Flag {
     pub x: i32,
     pub y: i32,
@@ -352,6 +390,7 @@ pub struct Flag {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GameInfo {
     pub game_flags: i32,
     pub game_state_flags: i32,
@@ -365,6 +404,7 @@ pub struct GameInfo {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GameData {
     pub teamscore_red: i32,
     pub teamscore_blue: i32,
@@ -374,6 +414,7 @@ pub struct GameData {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CharacterCore {
     pub tick: i32,
     pub x: i32,
@@ -394,29 +435,35 @@ pub struct CharacterCore {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Character {
     pub character_core: CharacterCore,
     pub player_flags: i32,
     pub health: i32,
     pub armor: i32,
     pub ammo_count: i32,
-    pub weapon: enums::Weapon,
-    pub emote: enums::Emote,
+    /// Raw `enums::Weapon` value, convert it using `enums::Weapon::from_i32`.
+    pub weapon: i32,
+    /// Raw `enums::Emote` value, convert it using `enums::Emote::from_i32`.
+    pub emote: i32,
     pub attack_tick: i32,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PlayerInfo {
     pub local: i32,
     pub client_id: i32,
-    pub team: enums::Team,
+    /// Raw `enums::Team` value, convert it using `enums::Team::from_i32`.
+    pub team: i32,
     pub score: i32,
     pub latency: i32,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClientInfo {
     pub name: [i32; 4],
     pub clan: [i32; 3],
@@ -429,6 +476,7 @@ pub struct ClientInfo {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SpectatorInfo {
     pub spectator_id: i32,
     pub x: i32,
@@ -437,6 +485,7 @@ pub struct SpectatorInfo {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Common {
     pub x: i32,
     pub y: i32,
@@ -444,24 +493,28 @@ pub struct Common {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Explosion {
     pub common: Common,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Spawn {
     pub common: Common,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HammerHit {
     pub common: Common,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Death {
     pub common: Common,
     pub client_id: i32,
@@ -469,20 +522,25 @@ pub struct Death {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SoundGlobal {
     pub common: Common,
-    pub sound_id: enums::Sound,
+    /// Raw `enums::Sound` value, convert it using `enums::Sound::from_i32`.
+    pub sound_id: i32,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SoundWorld {
     pub common: Common,
-    pub sound_id: enums::Sound,
+    /// Raw `enums::Sound` value, convert it using `enums::Sound::from_i32`.
+    pub sound_id: i32,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DamageInd {
     pub common: Common,
     pub angle: i32,
@@ -524,28 +582,53 @@ impl PlayerInput {
             prev_weapon: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(direction: i32, target_x: i32, target_y: i32, jump: i32, fire: i32, hook: i32, player_flags: i32, wanted_weapon: i32, next_weapon: i32, prev_weapon: i32) -> Result<PlayerInput, Error> {
+        let result = PlayerInput {
+            direction,
+            target_x,
+            target_y,
+            jump,
+            fire,
+            hook,
+            player_flags,
+            wanted_weapon,
+            next_weapon,
+            prev_weapon,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 impl PlayerInput {
     pub fn decode_msg<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<PlayerInput, Error> {
-        let result = Ok(PlayerInput {
-            direction: _p.read_int(warn)?,
-            target_x: _p.read_int(warn)?,
-            target_y: _p.read_int(warn)?,
-            jump: _p.read_int(warn)?,
-            fire: _p.read_int(warn)?,
-            hook: _p.read_int(warn)?,
-            player_flags: _p.read_int(warn)?,
-            wanted_weapon: _p.read_int(warn)?,
-            next_weapon: _p.read_int(warn)?,
-            prev_weapon: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode_msg<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        PlayerInput::decode_msg_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_msg_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<PlayerInput, Error> {
+        let result = PlayerInput::decode_msg_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "PlayerInput"), _p, result)
+    }
+    fn decode_msg_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<PlayerInput, Error> {
+        Ok(PlayerInput {
+            direction: _p.read_int(&mut _p.locate(warn, "PlayerInput.direction"))?,
+            target_x: _p.read_int(&mut _p.locate(warn, "PlayerInput.target_x"))?,
+            target_y: _p.read_int(&mut _p.locate(warn, "PlayerInput.target_y"))?,
+            jump: _p.read_int(&mut _p.locate(warn, "PlayerInput.jump"))?,
+            fire: _p.read_int(&mut _p.locate(warn, "PlayerInput.fire"))?,
+            hook: _p.read_int(&mut _p.locate(warn, "PlayerInput.hook"))?,
+            player_flags: _p.read_int(&mut _p.locate(warn, "PlayerInput.player_flags"))?,
+            wanted_weapon: _p.read_int(&mut _p.locate(warn, "PlayerInput.wanted_weapon"))?,
+            next_weapon: _p.read_int(&mut _p.locate(warn, "PlayerInput.next_weapon"))?,
+            prev_weapon: _p.read_int(&mut _p.locate(warn, "PlayerInput.prev_weapon"))?,
+        })
+    }
+    pub fn encode_msg<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.direction)?;
         _p.write_int(self.target_x)?;
         _p.write_int(self.target_y)?;
@@ -567,7 +650,7 @@ impl fmt::Debug for Projectile {
             .field("y", &self.y)
             .field("vel_x", &self.vel_x)
             .field("vel_y", &self.vel_y)
-            .field("type_", &self.type_)
+            .field("type_", &enums::Weapon::from_i32(self.type_))
             .field("start_tick", &self.start_tick)
             .finish()
     }
@@ -584,33 +667,54 @@ impl Projectile {
             y: _p.read_int()?,
             vel_x: _p.read_int()?,
             vel_y: _p.read_int()?,
-            type_: enums::Weapon::from_i32(_p.read_int()?)?,
+            type_: _p.read_int()?,
             start_tick: ::snap_obj::Tick(_p.read_int()?),
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32, vel_x: i32, vel_y: i32, type_: i32, start_tick: ::snap_obj::Tick) -> Result<Projectile, Error> {
+        let result = Projectile {
+            x,
+            y,
+            vel_x,
+            vel_y,
+            type_,
+            start_tick,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        enums::Weapon::from_i32(self.type_).known()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 impl Projectile {
     pub fn decode_msg<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Projectile, Error> {
-        let result = Ok(Projectile {
-            x: _p.read_int(warn)?,
-            y: _p.read_int(warn)?,
-            vel_x: _p.read_int(warn)?,
-            vel_y: _p.read_int(warn)?,
-            type_: enums::Weapon::from_i32(_p.read_int(warn)?)?,
-            start_tick: ::snap_obj::Tick(_p.read_int(warn)?),
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode_msg<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        Projectile::decode_msg_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_msg_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Projectile, Error> {
+        let result = Projectile::decode_msg_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Projectile"), _p, result)
+    }
+    fn decode_msg_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Projectile, Error> {
+        Ok(Projectile {
+            x: _p.read_int(&mut _p.locate(warn, "Projectile.x"))?,
+            y: _p.read_int(&mut _p.locate(warn, "Projectile.y"))?,
+            vel_x: _p.read_int(&mut _p.locate(warn, "Projectile.vel_x"))?,
+            vel_y: _p.read_int(&mut _p.locate(warn, "Projectile.vel_y"))?,
+            type_: _p.read_int(&mut _p.locate(warn, "Projectile.type_"))?,
+            start_tick: ::snap_obj::Tick(_p.read_int(&mut _p.locate(warn, "Projectile.start_tick"))?),
+        })
+    }
+    pub fn encode_msg<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.x)?;
         _p.write_int(self.y)?;
         _p.write_int(self.vel_x)?;
         _p.write_int(self.vel_y)?;
-        _p.write_int(self.type_.to_i32())?;
+        _p.write_int(self.type_)?;
         _p.write_int(self.start_tick.0)?;
         Ok(_p.written())
     }
@@ -642,8 +746,22 @@ impl Laser {
             start_tick: ::snap_obj::Tick(_p.read_int()?),
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32, from_x: i32, from_y: i32, start_tick: ::snap_obj::Tick) -> Result<Laser, Error> {
+        let result = Laser {
+            x,
+            y,
+            from_x,
+            from_y,
+            start_tick,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -671,10 +789,25 @@ impl Pickup {
             subtype: positive(_p.read_int()?)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(self.type_ >= 0);
-        assert!(self.subtype >= 0);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32, type_: i32, subtype: i32) -> Result<Pickup, Error> {
+        let result = Pickup {
+            x,
+            y,
+            type_,
+            subtype,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        positive(self.type_)?;
+        positive(self.subtype)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        positive(self.type_)?;
+        positive(self.subtype)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -700,9 +833,22 @@ impl Flag {
             team: in_range(_p.read_int()?, 0, 1)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(0 <= self.team && self.team <= 1);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32, team: i32) -> Result<Flag, Error> {
+        let result = Flag {
+            x,
+            y,
+            team,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.team, 0, 1)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.team, 0, 1)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -738,15 +884,40 @@ impl GameInfo {
             round_current: positive(_p.read_int()?)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(0 <= self.game_flags && self.game_flags <= 256);
-        assert!(0 <= self.game_state_flags && self.game_state_flags <= 256);
-        assert!(self.warmup_timer >= 0);
-        assert!(self.score_limit >= 0);
-        assert!(self.time_limit >= 0);
-        assert!(self.round_num >= 0);
-        assert!(self.round_current >= 0);
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(game_flags: i32, game_state_flags: i32, round_start_tick: ::snap_obj::Tick, warmup_timer: i32, score_limit: i32, time_limit: i32, round_num: i32, round_current: i32) -> Result<GameInfo, Error> {
+        let result = GameInfo {
+            game_flags,
+            game_state_flags,
+            round_start_tick,
+            warmup_timer,
+            score_limit,
+            time_limit,
+            round_num,
+            round_current,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.game_flags, 0, 256)?;
+        in_range(self.game_state_flags, 0, 256)?;
+        positive(self.warmup_timer)?;
+        positive(self.score_limit)?;
+        positive(self.time_limit)?;
+        positive(self.round_num)?;
+        positive(self.round_current)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.game_flags, 0, 256)?;
+        in_range(self.game_state_flags, 0, 256)?;
+        positive(self.warmup_timer)?;
+        positive(self.score_limit)?;
+        positive(self.time_limit)?;
+        positive(self.round_num)?;
+        positive(self.round_current)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -774,10 +945,25 @@ impl GameData {
             flag_carrier_blue: in_range(_p.read_int()?, -3, 15)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(-3 <= self.flag_carrier_red && self.flag_carrier_red <= 15);
-        assert!(-3 <= self.flag_carrier_blue && self.flag_carrier_blue <= 15);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(teamscore_red: i32, teamscore_blue: i32, flag_carrier_red: i32, flag_carrier_blue: i32) -> Result<GameData, Error> {
+        let result = GameData {
+            teamscore_red,
+            teamscore_blue,
+            flag_carrier_red,
+            flag_carrier_blue,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.flag_carrier_red, -3, 15)?;
+        in_range(self.flag_carrier_blue, -3, 15)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.flag_carrier_red, -3, 15)?;
+        in_range(self.flag_carrier_blue, -3, 15)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -827,12 +1013,41 @@ impl CharacterCore {
             hook_dy: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(-1 <= self.direction && self.direction <= 1);
-        assert!(0 <= self.jumped && self.jumped <= 3);
-        assert!(-1 <= self.hooked_player && self.hooked_player <= 15);
-        assert!(-1 <= self.hook_state && self.hook_state <= 5);
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(tick: i32, x: i32, y: i32, vel_x: i32, vel_y: i32, angle: i32, direction: i32, jumped: i32, hooked_player: i32, hook_state: i32, hook_tick: ::snap_obj::Tick, hook_x: i32, hook_y: i32, hook_dx: i32, hook_dy: i32) -> Result<CharacterCore, Error> {
+        let result = CharacterCore {
+            tick,
+            x,
+            y,
+            vel_x,
+            vel_y,
+            angle: wrap_angle(angle),
+            direction,
+            jumped,
+            hooked_player,
+            hook_state,
+            hook_tick,
+            hook_x,
+            hook_y,
+            hook_dx,
+            hook_dy,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.direction, -1, 1)?;
+        in_range(self.jumped, 0, 3)?;
+        in_range(self.hooked_player, -1, 15)?;
+        in_range(self.hook_state, -1, 5)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.direction, -1, 1)?;
+        in_range(self.jumped, 0, 3)?;
+        in_range(self.hooked_player, -1, 15)?;
+        in_range(self.hook_state, -1, 5)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -844,8 +1059,8 @@ impl fmt::Debug for Character {
             .field("health", &self.health)
             .field("armor", &self.armor)
             .field("ammo_count", &self.ammo_count)
-            .field("weapon", &self.weapon)
-            .field("emote", &self.emote)
+            .field("weapon", &enums::Weapon::from_i32(self.weapon))
+            .field("emote", &enums::Emote::from_i32(self.emote))
             .field("attack_tick", &self.attack_tick)
             .finish()
     }
@@ -863,19 +1078,45 @@ impl Character {
             health: in_range(_p.read_int()?, 0, 10)?,
             armor: in_range(_p.read_int()?, 0, 10)?,
             ammo_count: in_range(_p.read_int()?, 0, 10)?,
-            weapon: enums::Weapon::from_i32(_p.read_int()?)?,
-            emote: enums::Emote::from_i32(_p.read_int()?)?,
+            weapon: _p.read_int()?,
+            emote: _p.read_int()?,
             attack_tick: positive(_p.read_int()?)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.character_core.encode();
-        assert!(0 <= self.player_flags && self.player_flags <= 256);
-        assert!(0 <= self.health && self.health <= 10);
-        assert!(0 <= self.armor && self.armor <= 10);
-        assert!(0 <= self.ammo_count && self.ammo_count <= 10);
-        assert!(self.attack_tick >= 0);
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(character_core: CharacterCore, player_flags: i32, health: i32, armor: i32, ammo_count: i32, weapon: i32, emote: i32, attack_tick: i32) -> Result<Character, Error> {
+        let result = Character {
+            character_core,
+            player_flags,
+            health,
+            armor,
+            ammo_count,
+            weapon,
+            emote,
+            attack_tick,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.character_core.validate()?;
+        in_range(self.player_flags, 0, 256)?;
+        in_range(self.health, 0, 10)?;
+        in_range(self.armor, 0, 10)?;
+        in_range(self.ammo_count, 0, 10)?;
+        enums::Weapon::from_i32(self.weapon).known()?;
+        enums::Emote::from_i32(self.emote).known()?;
+        positive(self.attack_tick)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.character_core.encode()?;
+        in_range(self.player_flags, 0, 256)?;
+        in_range(self.health, 0, 10)?;
+        in_range(self.armor, 0, 10)?;
+        in_range(self.ammo_count, 0, 10)?;
+        positive(self.attack_tick)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -884,7 +1125,7 @@ impl fmt::Debug for PlayerInfo {
         f.debug_struct("PlayerInfo")
             .field("local", &self.local)
             .field("client_id", &self.client_id)
-            .field("team", &self.team)
+            .field("team", &enums::Team::from_i32(self.team))
             .field("score", &self.score)
             .field("latency", &self.latency)
             .finish()
@@ -900,15 +1141,32 @@ impl PlayerInfo {
         Ok(PlayerInfo {
             local: in_range(_p.read_int()?, 0, 1)?,
             client_id: in_range(_p.read_int()?, 0, 15)?,
-            team: enums::Team::from_i32(_p.read_int()?)?,
+            team: _p.read_int()?,
             score: _p.read_int()?,
             latency: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(0 <= self.local && self.local <= 1);
-        assert!(0 <= self.client_id && self.client_id <= 15);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(local: i32, client_id: i32, team: i32, score: i32, latency: i32) -> Result<PlayerInfo, Error> {
+        let result = PlayerInfo {
+            local,
+            client_id,
+            team,
+            score,
+            latency,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.local, 0, 1)?;
+        in_range(self.client_id, 0, 15)?;
+        enums::Team::from_i32(self.team).known()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.local, 0, 1)?;
+        in_range(self.client_id, 0, 15)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -958,9 +1216,26 @@ impl ClientInfo {
             color_feet: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(0 <= self.use_custom_color && self.use_custom_color <= 1);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(name: [i32; 4], clan: [i32; 3], country: i32, skin: [i32; 6], use_custom_color: i32, color_body: i32, color_feet: i32) -> Result<ClientInfo, Error> {
+        let result = ClientInfo {
+            name,
+            clan,
+            country,
+            skin,
+            use_custom_color,
+            color_body,
+            color_feet,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.use_custom_color, 0, 1)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.use_custom_color, 0, 1)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -986,9 +1261,22 @@ impl SpectatorInfo {
             y: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(-1 <= self.spectator_id && self.spectator_id <= 15);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(spectator_id: i32, x: i32, y: i32) -> Result<SpectatorInfo, Error> {
+        let result = SpectatorInfo {
+            spectator_id,
+            x,
+            y,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.spectator_id, -1, 15)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.spectator_id, -1, 15)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1012,8 +1300,19 @@ impl Common {
             y: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32) -> Result<Common, Error> {
+        let result = Common {
+            x,
+            y,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1035,9 +1334,20 @@ impl Explosion {
             common: Common::decode_inner(_p)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common) -> Result<Explosion, Error> {
+        let result = Explosion {
+            common,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1059,9 +1369,20 @@ impl Spawn {
             common: Common::decode_inner(_p)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common) -> Result<Spawn, Error> {
+        let result = Spawn {
+            common,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1083,9 +1404,20 @@ impl HammerHit {
             common: Common::decode_inner(_p)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common) -> Result<HammerHit, Error> {
+        let result = HammerHit {
+            common,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1109,10 +1441,23 @@ impl Death {
             client_id: in_range(_p.read_int()?, 0, 15)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        assert!(0 <= self.client_id && self.client_id <= 15);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common, client_id: i32) -> Result<Death, Error> {
+        let result = Death {
+            common,
+            client_id,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        in_range(self.client_id, 0, 15)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        in_range(self.client_id, 0, 15)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1120,7 +1465,7 @@ impl fmt::Debug for SoundGlobal {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("SoundGlobal")
             .field("common", &self.common)
-            .field("sound_id", &self.sound_id)
+            .field("sound_id", &enums::Sound::from_i32(self.sound_id))
             .finish()
     }
 }
@@ -1133,12 +1478,25 @@ impl SoundGlobal {
     pub fn decode_inner(_p: &mut IntUnpacker) -> Result<SoundGlobal, Error> {
         Ok(SoundGlobal {
             common: Common::decode_inner(_p)?,
-            sound_id: enums::Sound::from_i32(_p.read_int()?)?,
+            sound_id: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common, sound_id: i32) -> Result<SoundGlobal, Error> {
+        let result = SoundGlobal {
+            common,
+            sound_id,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        enums::Sound::from_i32(self.sound_id).known()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1146,7 +1504,7 @@ impl fmt::Debug for SoundWorld {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("SoundWorld")
             .field("common", &self.common)
-            .field("sound_id", &self.sound_id)
+            .field("sound_id", &enums::Sound::from_i32(self.sound_id))
             .finish()
     }
 }
@@ -1159,12 +1517,25 @@ impl SoundWorld {
     pub fn decode_inner(_p: &mut IntUnpacker) -> Result<SoundWorld, Error> {
         Ok(SoundWorld {
             common: Common::decode_inner(_p)?,
-            sound_id: enums::Sound::from_i32(_p.read_int()?)?,
+            sound_id: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common, sound_id: i32) -> Result<SoundWorld, Error> {
+        let result = SoundWorld {
+            common,
+            sound_id,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        enums::Sound::from_i32(self.sound_id).known()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1188,9 +1559,21 @@ impl DamageInd {
             angle: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common, angle: i32) -> Result<DamageInd, Error> {
+        let result = DamageInd {
+            common,
+            angle: wrap_angle(angle),
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1219,3 +1602,262 @@ pub fn obj_size(type_: u16) -> Option<u32> {
         _ => return None,
     })
 }
+
+pub const OBJ_META: &'static [ObjMeta] = &[
+    ObjMeta {
+        type_id: TypeId::Ordinal(PLAYER_INPUT),
+        name: "player_input",
+        size: 10,
+        fields: &[
+            FieldMeta { name: "direction", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "target_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "target_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "jump", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "fire", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "player_flags", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "wanted_weapon", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "next_weapon", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "prev_weapon", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(PROJECTILE),
+        name: "projectile",
+        size: 6,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "type", count: 1, kind: FieldKind::Enum { name: "weapon", offset: 0, values: &["hammer", "pistol", "shotgun", "grenade", "rifle", "ninja"] } },
+            FieldMeta { name: "start_tick", count: 1, kind: FieldKind::Tick },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(LASER),
+        name: "laser",
+        size: 5,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "from_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "from_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "start_tick", count: 1, kind: FieldKind::Tick },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(PICKUP),
+        name: "pickup",
+        size: 4,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "type", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "subtype", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(FLAG),
+        name: "flag",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "team", count: 1, kind: FieldKind::Range { min: 0, max: 1 } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(GAME_INFO),
+        name: "game_info",
+        size: 8,
+        fields: &[
+            FieldMeta { name: "game_flags", count: 1, kind: FieldKind::Range { min: 0, max: 256 } },
+            FieldMeta { name: "game_state_flags", count: 1, kind: FieldKind::Range { min: 0, max: 256 } },
+            FieldMeta { name: "round_start_tick", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "warmup_timer", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "score_limit", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "time_limit", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "round_num", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "round_current", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(GAME_DATA),
+        name: "game_data",
+        size: 4,
+        fields: &[
+            FieldMeta { name: "teamscore_red", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "teamscore_blue", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "flag_carrier_red", count: 1, kind: FieldKind::Range { min: -3, max: 15 } },
+            FieldMeta { name: "flag_carrier_blue", count: 1, kind: FieldKind::Range { min: -3, max: 15 } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(CHARACTER_CORE),
+        name: "character_core",
+        size: 15,
+        fields: &[
+            FieldMeta { name: "tick", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "angle", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "direction", count: 1, kind: FieldKind::Range { min: -1, max: 1 } },
+            FieldMeta { name: "jumped", count: 1, kind: FieldKind::Range { min: 0, max: 3 } },
+            FieldMeta { name: "hooked_player", count: 1, kind: FieldKind::Range { min: -1, max: 15 } },
+            FieldMeta { name: "hook_state", count: 1, kind: FieldKind::Range { min: -1, max: 5 } },
+            FieldMeta { name: "hook_tick", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "hook_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_dx", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_dy", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(CHARACTER),
+        name: "character",
+        size: 22,
+        fields: &[
+            FieldMeta { name: "tick", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "angle", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "direction", count: 1, kind: FieldKind::Range { min: -1, max: 1 } },
+            FieldMeta { name: "jumped", count: 1, kind: FieldKind::Range { min: 0, max: 3 } },
+            FieldMeta { name: "hooked_player", count: 1, kind: FieldKind::Range { min: -1, max: 15 } },
+            FieldMeta { name: "hook_state", count: 1, kind: FieldKind::Range { min: -1, max: 5 } },
+            FieldMeta { name: "hook_tick", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "hook_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_dx", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_dy", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "player_flags", count: 1, kind: FieldKind::Range { min: 0, max: 256 } },
+            FieldMeta { name: "health", count: 1, kind: FieldKind::Range { min: 0, max: 10 } },
+            FieldMeta { name: "armor", count: 1, kind: FieldKind::Range { min: 0, max: 10 } },
+            FieldMeta { name: "ammo_count", count: 1, kind: FieldKind::Range { min: 0, max: 10 } },
+            FieldMeta { name: "weapon", count: 1, kind: FieldKind::Enum { name: "weapon", offset: 0, values: &["hammer", "pistol", "shotgun", "grenade", "rifle", "ninja"] } },
+            FieldMeta { name: "emote", count: 1, kind: FieldKind::Enum { name: "emote", offset: 0, values: &["normal", "pain", "happy", "surprise", "angry", "blink"] } },
+            FieldMeta { name: "attack_tick", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(PLAYER_INFO),
+        name: "player_info",
+        size: 5,
+        fields: &[
+            FieldMeta { name: "local", count: 1, kind: FieldKind::Range { min: 0, max: 1 } },
+            FieldMeta { name: "client_id", count: 1, kind: FieldKind::Range { min: 0, max: 15 } },
+            FieldMeta { name: "team", count: 1, kind: FieldKind::Enum { name: "team", offset: -1, values: &["spectators", "red", "blue"] } },
+            FieldMeta { name: "score", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "latency", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(CLIENT_INFO),
+        name: "client_info",
+        size: 17,
+        fields: &[
+            FieldMeta { name: "name", count: 4, kind: FieldKind::Int },
+            FieldMeta { name: "clan", count: 3, kind: FieldKind::Int },
+            FieldMeta { name: "country", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "skin", count: 6, kind: FieldKind::Int },
+            FieldMeta { name: "use_custom_color", count: 1, kind: FieldKind::Range { min: 0, max: 1 } },
+            FieldMeta { name: "color_body", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "color_feet", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(SPECTATOR_INFO),
+        name: "spectator_info",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "spectator_id", count: 1, kind: FieldKind::Range { min: -1, max: 15 } },
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(COMMON),
+        name: "common",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(EXPLOSION),
+        name: "explosion",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(SPAWN),
+        name: "spawn",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(HAMMER_HIT),
+        name: "hammer_hit",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(DEATH),
+        name: "death",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "client_id", count: 1, kind: FieldKind::Range { min: 0, max: 15 } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(SOUND_GLOBAL),
+        name: "sound_global",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "sound_id", count: 1, kind: FieldKind::Enum { name: "sound", offset: 0, values: &["gun_fire", "shotgun_fire", "grenade_fire", "hammer_fire", "hammer_hit", "ninja_fire", "grenade_explode", "ninja_hit", "rifle_fire", "rifle_bounce", "weapon_switch", "player_pain_short", "player_pain_long", "body_land", "player_airjump", "player_jump", "player_die", "player_spawn", "player_skid", "tee_cry", "hook_loop", "hook_attach_ground", "hook_attach_player", "hook_noattach", "pickup_health", "pickup_armor", "pickup_grenade", "pickup_shotgun", "pickup_ninja", "weapon_spawn", "weapon_noammo", "hit", "chat_server", "chat_client", "chat_highlight", "ctf_drop", "ctf_return", "ctf_grab_pl", "ctf_grab_en", "ctf_capture", "menu"] } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(SOUND_WORLD),
+        name: "sound_world",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "sound_id", count: 1, kind: FieldKind::Enum { name: "sound", offset: 0, values: &["gun_fire", "shotgun_fire", "grenade_fire", "hammer_fire", "hammer_hit", "ninja_fire", "grenade_explode", "ninja_hit", "rifle_fire", "rifle_bounce", "weapon_switch", "player_pain_short", "player_pain_long", "body_land", "player_airjump", "player_jump", "player_die", "player_spawn", "player_skid", "tee_cry", "hook_loop", "hook_attach_ground", "hook_attach_player", "hook_noattach", "pickup_health", "pickup_armor", "pickup_grenade", "pickup_shotgun", "pickup_ninja", "weapon_spawn", "weapon_noammo", "hit", "chat_server", "chat_client", "chat_highlight", "ctf_drop", "ctf_return", "ctf_grab_pl", "ctf_grab_en", "ctf_capture", "menu"] } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(DAMAGE_IND),
+        name: "damage_ind",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "angle", count: 1, kind: FieldKind::Int },
+        ],
+    },
+];
+
+pub fn obj_meta(type_id: TypeId) -> Option<&'static ObjMeta> {
+    OBJ_META.iter().find(|m| m.type_id == type_id)
+}
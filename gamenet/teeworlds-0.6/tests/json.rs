@@ -0,0 +1,74 @@
+extern crate gamenet_teeworlds_0_6 as gamenet;
+extern crate packer;
+extern crate serde_json;
+extern crate warn;
+
+use gamenet::msg;
+use gamenet::msg::GameOwned;
+use gamenet::msg::SystemOrGame;
+use gamenet::msg::SystemOwned;
+use gamenet::snap_obj::obj_size;
+use gamenet::snap_obj::SnapObj;
+use gamenet::snap_obj::TypeId;
+use packer::with_packer;
+use packer::IntUnpacker;
+use packer::Unpacker;
+use serde_json::Value;
+use warn::Panic;
+
+const MESSAGES: &str = include_str!("json/messages.json");
+const SNAP_OBJS: &str = include_str!("json/snap_objs.json");
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    let hex: Vec<u8> = hex.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    hex.chunks(2)
+        .map(|c| u8::from_str_radix(std::str::from_utf8(c).unwrap(), 16).unwrap())
+        .collect()
+}
+
+fn fixtures(json: &str) -> Vec<Value> {
+    match serde_json::from_str(json).unwrap() {
+        Value::Array(fixtures) => fixtures,
+        _ => panic!("fixture file must contain an array"),
+    }
+}
+
+#[test]
+fn messages_golden() {
+    for fixture in fixtures(MESSAGES) {
+        let packet = from_hex(fixture["packet"].as_str().unwrap());
+        let decoded = msg::decode(&mut Panic, &mut Unpacker::new(&packet)).unwrap();
+        assert_eq!(serde_json::to_value(decoded).unwrap(), fixture["message"]);
+    }
+}
+
+#[test]
+fn messages_round_trip() {
+    for fixture in fixtures(MESSAGES) {
+        let packet = from_hex(fixture["packet"].as_str().unwrap());
+        let owned: SystemOrGame<SystemOwned, GameOwned> =
+            serde_json::from_value(fixture["message"].clone()).unwrap();
+        let mut buf = Vec::with_capacity(1024);
+        let encoded = match owned {
+            SystemOrGame::System(ref m) => with_packer(&mut buf, |p| m.as_ref().encode(p)),
+            SystemOrGame::Game(ref m) => with_packer(&mut buf, |p| m.as_ref().encode(p)),
+        };
+        assert_eq!(encoded.unwrap(), &packet[..]);
+    }
+}
+
+#[test]
+fn snap_objs_golden() {
+    for fixture in fixtures(SNAP_OBJS) {
+        let type_id = fixture["type_id"].as_u64().unwrap() as u16;
+        let data: Vec<i32> = serde_json::from_value(fixture["data"].clone()).unwrap();
+        assert_eq!(obj_size(type_id), Some(data.len() as u32));
+        let mut p = IntUnpacker::new(&data);
+        let decoded = SnapObj::decode_obj(&mut Panic, TypeId::from(type_id), &mut p).unwrap();
+        assert_eq!(serde_json::to_value(decoded).unwrap(), fixture["object"]);
+
+        let parsed: SnapObj = serde_json::from_value(fixture["object"].clone()).unwrap();
+        assert_eq!(parsed.obj_type_id(), TypeId::from(type_id));
+        assert_eq!(parsed.encode().unwrap(), &data[..]);
+    }
+}
@@ -0,0 +1,93 @@
+extern crate common;
+extern crate gamenet_teeworlds_0_6 as gamenet;
+extern crate packer;
+extern crate warn;
+
+use common::warn::Located;
+use gamenet::error::Error;
+use gamenet::msg;
+use gamenet::msg::game::ClSay;
+use gamenet::msg::Game;
+use gamenet::msg::SystemOrGame;
+use packer::DecodePolicy;
+use packer::Unpacker;
+use warn::Ignore;
+
+fn decode(data: &[u8], policy: DecodePolicy) -> Result<(), Error> {
+    let mut p = Unpacker::new(data).with_policy(policy);
+    msg::decode(&mut Ignore, &mut p).map(|_| ())
+}
+
+#[test]
+fn lenient() {
+    let mut warnings = vec![];
+    let msg = msg::decode(&mut warnings, &mut Unpacker::new(b"\x22\x80\x00hi\x00\x00")).unwrap();
+    match msg {
+        SystemOrGame::Game(Game::ClSay(ClSay { team, message })) => {
+            assert!(!team);
+            assert_eq!(message, b"hi");
+        }
+        _ => panic!("unexpected message {:?}", msg),
+    }
+    assert_eq!(
+        warnings,
+        [
+            packer::Warning::OverlongIntEncoding,
+            packer::Warning::ExcessData,
+        ]
+    );
+}
+
+#[test]
+fn located() {
+    let mut warnings = vec![];
+    let data = b"\xa2\x00\x80\x00hi\x00\x00";
+    msg::decode_located(&mut warnings, &mut Unpacker::new(data)).unwrap();
+    assert_eq!(
+        warnings,
+        [
+            Located {
+                offset: 0,
+                structure: "message ID",
+                warning: packer::Warning::OverlongIntEncoding,
+            },
+            Located {
+                offset: 2,
+                structure: "ClSay.team",
+                warning: packer::Warning::OverlongIntEncoding,
+            },
+            Located {
+                offset: 7,
+                structure: "ClSay",
+                warning: packer::Warning::ExcessData,
+            },
+        ]
+    );
+}
+
+#[test]
+fn strict() {
+    use packer::DecodePolicy::*;
+
+    assert_eq!(decode(b"\x22\x00hi\x00", Strict), Ok(()));
+    // Overlong message ID.
+    assert_eq!(decode(b"\xa2\x00\x00hi\x00", Lenient), Ok(()));
+    assert_eq!(
+        decode(b"\xa2\x00\x00hi\x00", Strict),
+        Err(Error::PolicyViolation)
+    );
+    // Overlong member.
+    assert_eq!(decode(b"\x22\x80\x00hi\x00", Lenient), Ok(()));
+    assert_eq!(
+        decode(b"\x22\x80\x00hi\x00", Strict),
+        Err(Error::PolicyViolation)
+    );
+    // Excess data.
+    assert_eq!(decode(b"\x22\x00hi\x00\x00", Lenient), Ok(()));
+    assert_eq!(
+        decode(b"\x22\x00hi\x00\x00", Strict),
+        Err(Error::PolicyViolation)
+    );
+    // Actually truncated data.
+    assert_eq!(decode(b"\x22\x00hi", Strict), Err(Error::UnexpectedEnd));
+}
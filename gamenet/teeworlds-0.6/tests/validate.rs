@@ -0,0 +1,149 @@
+extern crate gamenet_teeworlds_0_6 as gamenet;
+extern crate packer;
+extern crate warn;
+
+use gamenet::enums::Emoticon;
+use gamenet::error::EncodeError;
+use gamenet::error::Error;
+use gamenet::msg::game::ClEmoticon;
+use gamenet::msg::game::ClStartInfo;
+use gamenet::msg::game::SvChat;
+use gamenet::msg::game::SvEmoticon;
+use gamenet::msg::Game;
+use gamenet::snap_obj::CharacterCore;
+use gamenet::snap_obj::Common;
+use gamenet::snap_obj::DamageInd;
+use gamenet::snap_obj::PlayerInfo;
+use gamenet::snap_obj::Projectile;
+use gamenet::snap_obj::SnapObj;
+use gamenet::snap_obj::Tick;
+use gamenet::snap_obj::TypeId;
+use gamenet::snap_obj::PROJECTILE;
+use packer::with_packer;
+use packer::IntUnpacker;
+use packer::Unpacker;
+use warn::Panic;
+
+fn core(angle: i32) -> Result<CharacterCore, Error> {
+    CharacterCore::new(0, 0, 0, 0, 0, angle, 0, 0, -1, 0, Tick(0), 0, 0, 0, 0)
+}
+
+#[test]
+fn message_new() {
+    assert!(SvChat::new(false, -1, b"hello").is_ok());
+    assert_eq!(
+        SvChat::new(false, 16, b"hello").err(),
+        Some(Error::IntOutOfRange)
+    );
+    assert_eq!(
+        SvChat::new(false, 0, b"a\nb").err(),
+        Some(Error::ControlCharacters)
+    );
+    assert!(ClEmoticon::new(Emoticon::Hearts).is_ok());
+    assert_eq!(
+        ClEmoticon::new(Emoticon::from_i32(100)).err(),
+        Some(Error::IntOutOfRange)
+    );
+}
+
+#[test]
+fn message_string_length() {
+    let info = |name| ClStartInfo::new(name, b"", -1, b"default", false, 0, 0);
+    assert!(info(b"fifteen chars!!").is_ok());
+    assert_eq!(info(b"sixteen chars!!!").err(), Some(Error::StringTooLong));
+    assert_eq!(info(b"tab\tname").err(), Some(Error::ControlCharacters));
+}
+
+#[test]
+fn snap_obj_new() {
+    assert!(PlayerInfo::new(1, 15, 0, 0, 0).is_ok());
+    assert_eq!(
+        PlayerInfo::new(1, 16, 0, 0, 0).err(),
+        Some(Error::IntOutOfRange)
+    );
+    assert_eq!(
+        PlayerInfo::new(1, 0, 5, 0, 0).err(),
+        Some(Error::IntOutOfRange)
+    );
+    assert_eq!(core(0).unwrap().angle, 0);
+    assert_eq!(core(-804).unwrap().angle, -804);
+    assert_eq!(core(1000).unwrap().angle, -608);
+    let common = Common::new(0, 0).unwrap();
+    assert!(DamageInd::new(common, 3 * 1608).unwrap().angle.abs() <= 804);
+}
+
+#[test]
+fn encode_invalid() {
+    let mut buf = Vec::with_capacity(1024);
+    let chat = SvChat {
+        team: false,
+        client_id: 100,
+        message: b"",
+    };
+    assert_eq!(
+        with_packer(&mut buf, |p| Game::from(chat).encode(p).map(|_| ())),
+        Err(EncodeError::Invalid(Error::IntOutOfRange)),
+    );
+    buf.clear();
+    let chat = SvChat {
+        team: false,
+        client_id: 0,
+        message: b"\x01",
+    };
+    assert_eq!(
+        with_packer(&mut buf, |p| chat.encode(p).map(|_| ())),
+        Err(EncodeError::Invalid(Error::ControlCharacters)),
+    );
+    let info = PlayerInfo {
+        local: 2,
+        client_id: 0,
+        team: 0,
+        score: 0,
+        latency: 0,
+    };
+    assert_eq!(info.encode().err(), Some(Error::IntOutOfRange));
+}
+
+#[test]
+fn encode_capacity() {
+    let mut buf = Vec::with_capacity(4);
+    let chat = SvChat::new(false, 0, b"a long message").unwrap();
+    match with_packer(&mut buf, |p| chat.encode(p).map(|_| ())) {
+        Err(EncodeError::Capacity(_)) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn unknown_enum_round_trip() {
+    let mut buf = Vec::with_capacity(1024);
+    let emoticon = SvEmoticon {
+        client_id: 0,
+        emoticon: Emoticon::from_i32(100),
+    };
+    let encoded = with_packer(&mut buf, |p| Game::from(emoticon).encode(p))
+        .unwrap()
+        .to_vec();
+    let decoded = match Game::decode(&mut Panic, &mut Unpacker::new(&encoded)).unwrap() {
+        Game::SvEmoticon(m) => m,
+        other => panic!("unexpected message {:?}", other),
+    };
+    assert_eq!(decoded.emoticon, Emoticon::Unknown(100));
+    buf.clear();
+    let reencoded = with_packer(&mut buf, |p| Game::from(decoded).encode(p)).unwrap();
+    assert_eq!(reencoded, &encoded[..]);
+
+    let data = [0, 0, 0, 0, 99, 0];
+    let mut p = IntUnpacker::new(&data);
+    let decoded = SnapObj::decode_obj(&mut Panic, TypeId::from(PROJECTILE), &mut p).unwrap();
+    let projectile = match decoded {
+        SnapObj::Projectile(p) => p,
+        other => panic!("unexpected object {:?}", other),
+    };
+    assert_eq!(projectile.validate(), Err(Error::IntOutOfRange));
+    assert_eq!(projectile.encode().unwrap(), &data[..]);
+    assert_eq!(
+        Projectile::new(0, 0, 0, 0, 99, Tick(0)).err(),
+        Some(Error::IntOutOfRange)
+    );
+}
@@ -20,8 +20,8 @@ pub const PICKUP_NINJA: i32 = 5;
 pub const PICKUP_GUN: i32 = 6;
 pub const PICKUP_HAMMER: i32 = 7;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Pickup {
     Health,
     Armor,
@@ -31,6 +31,7 @@ pub enum Pickup {
     Ninja,
     Gun,
     Hammer,
+    Unknown(i32),
 }
 
 pub const EMOTE_NORMAL: i32 = 0;
@@ -40,8 +41,8 @@ pub const EMOTE_SURPRISE: i32 = 3;
 pub const EMOTE_ANGRY: i32 = 4;
 pub const EMOTE_BLINK: i32 = 5;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Emote {
     Normal,
     Pain,
@@ -49,6 +50,7 @@ pub enum Emote {
     Surprise,
     Angry,
     Blink,
+    Unknown(i32),
 }
 
 pub const EMOTICON_OOP: i32 = 0;
@@ -68,8 +70,8 @@ pub const EMOTICON_WTF: i32 = 13;
 pub const EMOTICON_EYES: i32 = 14;
 pub const EMOTICON_QUESTION: i32 = 15;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Emoticon {
     Oop,
     Exclamation,
@@ -87,6 +89,7 @@ pub enum Emoticon {
     Wtf,
     Eyes,
     Question,
+    Unknown(i32),
 }
 
 pub const VOTE_UNKNOWN: i32 = 0;
@@ -97,16 +100,17 @@ pub const VOTE_END_ABORT: i32 = 4;
 pub const VOTE_END_PASS: i32 = 5;
 pub const VOTE_END_FAIL: i32 = 6;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Vote {
-    Unknown,
+    VoteUnknown,
     StartOp,
     StartKick,
     StartSpec,
     EndAbort,
     EndPass,
     EndFail,
+    Unknown(i32),
 }
 
 pub const CHAT_NONE: i32 = 0;
@@ -114,13 +118,14 @@ pub const CHAT_ALL: i32 = 1;
 pub const CHAT_TEAM: i32 = 2;
 pub const CHAT_WHISPER: i32 = 3;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Chat {
     None,
     All,
     Team,
     Whisper,
+    Unknown(i32),
 }
 
 pub const GAMEMSG_TEAM_SWAP: i32 = 0;
@@ -135,8 +140,8 @@ pub const GAMEMSG_CTF_GRAB: i32 = 8;
 pub const GAMEMSG_CTF_CAPTURE: i32 = 9;
 pub const GAMEMSG_GAME_PAUSED: i32 = 10;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Gamemsg {
     TeamSwap,
     SpecInvalidid,
@@ -149,6 +154,7 @@ pub enum Gamemsg {
     CtfGrab,
     CtfCapture,
     GamePaused,
+    Unknown(i32),
 }
 
 pub const WEAPON_HAMMER: i32 = 0;
@@ -158,8 +164,8 @@ pub const WEAPON_GRENADE: i32 = 3;
 pub const WEAPON_RIFLE: i32 = 4;
 pub const WEAPON_NINJA: i32 = 5;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Weapon {
     Hammer,
     Pistol,
@@ -167,18 +173,20 @@ pub enum Weapon {
     Grenade,
     Rifle,
     Ninja,
+    Unknown(i32),
 }
 
 pub const TEAM_SPECTATORS: i32 = -1;
 pub const TEAM_RED: i32 = 0;
 pub const TEAM_BLUE: i32 = 1;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Team {
-    Spectators = -1,
+    Spectators,
     Red,
     Blue,
+    Unknown(i32),
 }
 
 pub const SOUND_GUN_FIRE: i32 = 0;
@@ -223,8 +231,8 @@ pub const SOUND_CTF_GRAB_EN: i32 = 38;
 pub const SOUND_CTF_CAPTURE: i32 = 39;
 pub const SOUND_MENU: i32 = 40;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Sound {
     GunFire,
     ShotgunFire,
@@ -267,6 +275,7 @@ pub enum Sound {
     CtfGrabEn,
     CtfCapture,
     Menu,
+    Unknown(i32),
 }
 
 pub const SPEC_FREEVIEW: i32 = 0;
@@ -274,13 +283,14 @@ pub const SPEC_PLAYER: i32 = 1;
 pub const SPEC_FLAGRED: i32 = 2;
 pub const SPEC_FLAGBLUE: i32 = 3;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Spec {
     Freeview,
     Player,
     Flagred,
     Flagblue,
+    Unknown(i32),
 }
 
 pub const SKINPART_BODY: i32 = 0;
@@ -290,8 +300,8 @@ pub const SKINPART_HANDS: i32 = 3;
 pub const SKINPART_FEET: i32 = 4;
 pub const SKINPART_EYES: i32 = 5;
 
-#[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Skinpart {
     Body,
     Marking,
@@ -299,12 +309,13 @@ pub enum Skinpart {
     Hands,
     Feet,
     Eyes,
+    Unknown(i32),
 }
 
 impl Pickup {
-    pub fn from_i32(i: i32) -> Result<Pickup, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Pickup {
         use self::Pickup::*;
-        Ok(match i {
+        match i {
             PICKUP_HEALTH => Health,
             PICKUP_ARMOR => Armor,
             PICKUP_GRENADE => Grenade,
@@ -313,8 +324,8 @@ impl Pickup {
             PICKUP_NINJA => Ninja,
             PICKUP_GUN => Gun,
             PICKUP_HAMMER => Hammer,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Pickup::*;
@@ -327,22 +338,29 @@ impl Pickup {
             Ninja => PICKUP_NINJA,
             Gun => PICKUP_GUN,
             Hammer => PICKUP_HAMMER,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Pickup, IntOutOfRange> {
+        match self {
+            Pickup::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Emote {
-    pub fn from_i32(i: i32) -> Result<Emote, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Emote {
         use self::Emote::*;
-        Ok(match i {
+        match i {
             EMOTE_NORMAL => Normal,
             EMOTE_PAIN => Pain,
             EMOTE_HAPPY => Happy,
             EMOTE_SURPRISE => Surprise,
             EMOTE_ANGRY => Angry,
             EMOTE_BLINK => Blink,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Emote::*;
@@ -353,14 +371,21 @@ impl Emote {
             Surprise => EMOTE_SURPRISE,
             Angry => EMOTE_ANGRY,
             Blink => EMOTE_BLINK,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Emote, IntOutOfRange> {
+        match self {
+            Emote::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Emoticon {
-    pub fn from_i32(i: i32) -> Result<Emoticon, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Emoticon {
         use self::Emoticon::*;
-        Ok(match i {
+        match i {
             EMOTICON_OOP => Oop,
             EMOTICON_EXCLAMATION => Exclamation,
             EMOTICON_HEARTS => Hearts,
@@ -377,8 +402,8 @@ impl Emoticon {
             EMOTICON_WTF => Wtf,
             EMOTICON_EYES => Eyes,
             EMOTICON_QUESTION => Question,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Emoticon::*;
@@ -399,48 +424,62 @@ impl Emoticon {
             Wtf => EMOTICON_WTF,
             Eyes => EMOTICON_EYES,
             Question => EMOTICON_QUESTION,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Emoticon, IntOutOfRange> {
+        match self {
+            Emoticon::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Vote {
-    pub fn from_i32(i: i32) -> Result<Vote, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Vote {
         use self::Vote::*;
-        Ok(match i {
-            VOTE_UNKNOWN => Unknown,
+        match i {
+            VOTE_UNKNOWN => VoteUnknown,
             VOTE_START_OP => StartOp,
             VOTE_START_KICK => StartKick,
             VOTE_START_SPEC => StartSpec,
             VOTE_END_ABORT => EndAbort,
             VOTE_END_PASS => EndPass,
             VOTE_END_FAIL => EndFail,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Vote::*;
         match self {
-            Unknown => VOTE_UNKNOWN,
+            VoteUnknown => VOTE_UNKNOWN,
             StartOp => VOTE_START_OP,
             StartKick => VOTE_START_KICK,
             StartSpec => VOTE_START_SPEC,
             EndAbort => VOTE_END_ABORT,
             EndPass => VOTE_END_PASS,
             EndFail => VOTE_END_FAIL,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Vote, IntOutOfRange> {
+        match self {
+            Vote::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Chat {
-    pub fn from_i32(i: i32) -> Result<Chat, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Chat {
         use self::Chat::*;
-        Ok(match i {
+        match i {
             CHAT_NONE => None,
             CHAT_ALL => All,
             CHAT_TEAM => Team,
             CHAT_WHISPER => Whisper,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Chat::*;
@@ -449,14 +488,21 @@ impl Chat {
             All => CHAT_ALL,
             Team => CHAT_TEAM,
             Whisper => CHAT_WHISPER,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Chat, IntOutOfRange> {
+        match self {
+            Chat::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Gamemsg {
-    pub fn from_i32(i: i32) -> Result<Gamemsg, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Gamemsg {
         use self::Gamemsg::*;
-        Ok(match i {
+        match i {
             GAMEMSG_TEAM_SWAP => TeamSwap,
             GAMEMSG_SPEC_INVALIDID => SpecInvalidid,
             GAMEMSG_TEAM_SHUFFLE => TeamShuffle,
@@ -468,8 +514,8 @@ impl Gamemsg {
             GAMEMSG_CTF_GRAB => CtfGrab,
             GAMEMSG_CTF_CAPTURE => CtfCapture,
             GAMEMSG_GAME_PAUSED => GamePaused,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Gamemsg::*;
@@ -485,22 +531,29 @@ impl Gamemsg {
             CtfGrab => GAMEMSG_CTF_GRAB,
             CtfCapture => GAMEMSG_CTF_CAPTURE,
             GamePaused => GAMEMSG_GAME_PAUSED,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Gamemsg, IntOutOfRange> {
+        match self {
+            Gamemsg::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Weapon {
-    pub fn from_i32(i: i32) -> Result<Weapon, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Weapon {
         use self::Weapon::*;
-        Ok(match i {
+        match i {
             WEAPON_HAMMER => Hammer,
             WEAPON_PISTOL => Pistol,
             WEAPON_SHOTGUN => Shotgun,
             WEAPON_GRENADE => Grenade,
             WEAPON_RIFLE => Rifle,
             WEAPON_NINJA => Ninja,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Weapon::*;
@@ -511,19 +564,26 @@ impl Weapon {
             Grenade => WEAPON_GRENADE,
             Rifle => WEAPON_RIFLE,
             Ninja => WEAPON_NINJA,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Weapon, IntOutOfRange> {
+        match self {
+            Weapon::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Team {
-    pub fn from_i32(i: i32) -> Result<Team, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Team {
         use self::Team::*;
-        Ok(match i {
+        match i {
             TEAM_SPECTATORS => Spectators,
             TEAM_RED => Red,
             TEAM_BLUE => Blue,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Team::*;
@@ -531,14 +591,21 @@ impl Team {
             Spectators => TEAM_SPECTATORS,
             Red => TEAM_RED,
             Blue => TEAM_BLUE,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Team, IntOutOfRange> {
+        match self {
+            Team::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Sound {
-    pub fn from_i32(i: i32) -> Result<Sound, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Sound {
         use self::Sound::*;
-        Ok(match i {
+        match i {
             SOUND_GUN_FIRE => GunFire,
             SOUND_SHOTGUN_FIRE => ShotgunFire,
             SOUND_GRENADE_FIRE => GrenadeFire,
@@ -580,8 +647,8 @@ impl Sound {
             SOUND_CTF_GRAB_EN => CtfGrabEn,
             SOUND_CTF_CAPTURE => CtfCapture,
             SOUND_MENU => Menu,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Sound::*;
@@ -627,20 +694,27 @@ impl Sound {
             CtfGrabEn => SOUND_CTF_GRAB_EN,
             CtfCapture => SOUND_CTF_CAPTURE,
             Menu => SOUND_MENU,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Sound, IntOutOfRange> {
+        match self {
+            Sound::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Spec {
-    pub fn from_i32(i: i32) -> Result<Spec, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Spec {
         use self::Spec::*;
-        Ok(match i {
+        match i {
             SPEC_FREEVIEW => Freeview,
             SPEC_PLAYER => Player,
             SPEC_FLAGRED => Flagred,
             SPEC_FLAGBLUE => Flagblue,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Spec::*;
@@ -649,22 +723,29 @@ impl Spec {
             Player => SPEC_PLAYER,
             Flagred => SPEC_FLAGRED,
             Flagblue => SPEC_FLAGBLUE,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Spec, IntOutOfRange> {
+        match self {
+            Spec::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
 
 impl Skinpart {
-    pub fn from_i32(i: i32) -> Result<Skinpart, IntOutOfRange> {
+    pub fn from_i32(i: i32) -> Skinpart {
         use self::Skinpart::*;
-        Ok(match i {
+        match i {
             SKINPART_BODY => Body,
             SKINPART_MARKING => Marking,
             SKINPART_DECORATION => Decoration,
             SKINPART_HANDS => Hands,
             SKINPART_FEET => Feet,
             SKINPART_EYES => Eyes,
-            _ => return Err(IntOutOfRange),
-        })
+            _ => Unknown(i),
+        }
     }
     pub fn to_i32(self) -> i32 {
         use self::Skinpart::*;
@@ -675,6 +756,13 @@ impl Skinpart {
             Hands => SKINPART_HANDS,
             Feet => SKINPART_FEET,
             Eyes => SKINPART_EYES,
+            Unknown(i) => i,
+        }
+    }
+    pub fn known(self) -> Result<Skinpart, IntOutOfRange> {
+        match self {
+            Skinpart::Unknown(_) => Err(IntOutOfRange),
+            known => Ok(known),
         }
     }
 }
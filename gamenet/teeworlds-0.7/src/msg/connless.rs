@@ -1,6 +1,10 @@
 use buffer::CapacityError;
 use common::num::BeU16;
 use common::pretty;
+use common::warn::Located;
+use common::warn::strip_location;
+use error;
+use error::EncodeError;
 use error::Error;
 use gamenet_common::msg::AddrPackedSliceExt;
 use gamenet_common::msg::string_from_int;
@@ -13,7 +17,7 @@ use packer::with_packer;
 use std::fmt;
 use super::AddrPacked;
 use super::ClientsData;
-use warn::Panic;
+use warn::Ignore;
 use warn::Warn;
 use warn::wrap;
 
@@ -24,7 +28,7 @@ impl<'a> Connless<'a> {
         Connless::decode_connless(warn, connless_id, _p)
     }
     pub fn encode<'d, 's>(&self, mut p: Packer<'d, 's>)
-        -> Result<&'d [u8], CapacityError>
+        -> Result<&'d [u8], EncodeError>
     {
         p.write_raw(&self.connless_id())?;
         with_packer(&mut p, |p| self.encode_connless(p))?;
@@ -80,6 +84,7 @@ pub const FORWARD_OK: &'static [u8; 8] = b"\xff\xff\xff\xfffwok";
 pub const FORWARD_ERROR: &'static [u8; 8] = b"\xff\xff\xff\xfffwer";
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub enum Connless<'a> {
     RequestList(RequestList),
     List(List<'a>),
@@ -96,18 +101,21 @@ pub enum Connless<'a> {
 
 impl<'a> Connless<'a> {
     pub fn decode_connless<W: Warn<Warning>>(warn: &mut W, connless_id: [u8; 8], _p: &mut Unpacker<'a>) -> Result<Connless<'a>, Error> {
+        Connless::decode_connless_located(&mut strip_location(warn), connless_id, _p)
+    }
+    pub fn decode_connless_located<W: Warn<Located<Warning>>>(warn: &mut W, connless_id: [u8; 8], _p: &mut Unpacker<'a>) -> Result<Connless<'a>, Error> {
         Ok(match &connless_id {
-            REQUEST_LIST => Connless::RequestList(RequestList::decode(warn, _p)?),
-            LIST => Connless::List(List::decode(warn, _p)?),
-            REQUEST_COUNT => Connless::RequestCount(RequestCount::decode(warn, _p)?),
-            COUNT => Connless::Count(Count::decode(warn, _p)?),
-            REQUEST_INFO => Connless::RequestInfo(RequestInfo::decode(warn, _p)?),
-            INFO => Connless::Info(Info::decode(warn, _p)?),
-            HEARTBEAT => Connless::Heartbeat(Heartbeat::decode(warn, _p)?),
-            FORWARD_CHECK => Connless::ForwardCheck(ForwardCheck::decode(warn, _p)?),
-            FORWARD_RESPONSE => Connless::ForwardResponse(ForwardResponse::decode(warn, _p)?),
-            FORWARD_OK => Connless::ForwardOk(ForwardOk::decode(warn, _p)?),
-            FORWARD_ERROR => Connless::ForwardError(ForwardError::decode(warn, _p)?),
+            REQUEST_LIST => Connless::RequestList(RequestList::decode_located(warn, _p)?),
+            LIST => Connless::List(List::decode_located(warn, _p)?),
+            REQUEST_COUNT => Connless::RequestCount(RequestCount::decode_located(warn, _p)?),
+            COUNT => Connless::Count(Count::decode_located(warn, _p)?),
+            REQUEST_INFO => Connless::RequestInfo(RequestInfo::decode_located(warn, _p)?),
+            INFO => Connless::Info(Info::decode_located(warn, _p)?),
+            HEARTBEAT => Connless::Heartbeat(Heartbeat::decode_located(warn, _p)?),
+            FORWARD_CHECK => Connless::ForwardCheck(ForwardCheck::decode_located(warn, _p)?),
+            FORWARD_RESPONSE => Connless::ForwardResponse(ForwardResponse::decode_located(warn, _p)?),
+            FORWARD_OK => Connless::ForwardOk(ForwardOk::decode_located(warn, _p)?),
+            FORWARD_ERROR => Connless::ForwardError(ForwardError::decode_located(warn, _p)?),
             _ => return Err(Error::UnknownId),
         })
     }
@@ -126,7 +134,7 @@ impl<'a> Connless<'a> {
             Connless::ForwardError(_) => *FORWARD_ERROR,
         }
     }
-    pub fn encode_connless<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode_connless<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         match *self {
             Connless::RequestList(ref i) => i.encode(p),
             Connless::List(ref i) => i.encode(p),
@@ -227,33 +235,44 @@ impl<'a> From<ForwardError> for Connless<'a> {
     }
 }
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RequestList;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct List<'a> {
     pub servers: &'a [AddrPacked],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RequestCount;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Count {
     pub count: u16,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RequestInfo {
     pub token: u8,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Info<'a> {
     pub token: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub version: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub hostname: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub map: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub game_type: &'a [u8],
     pub flags: i32,
     pub skill_level: i32,
@@ -265,29 +284,38 @@ pub struct Info<'a> {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Heartbeat {
     pub alt_port: u16,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ForwardCheck;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ForwardResponse;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ForwardOk;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ForwardError;
 
 impl RequestList {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestList, Error> {
-        let result = Ok(RequestList);
-        _p.finish(warn);
-        result
+        RequestList::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestList, Error> {
+        error::finish(&mut _p.locate(warn, "RequestList"), _p, Ok(RequestList))
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -300,13 +328,28 @@ impl fmt::Debug for RequestList {
 
 impl<'a> List<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<List<'a>, Error> {
-        let result = Ok(List {
-            servers: AddrPackedSliceExt::from_bytes(wrap(warn), _p.read_rest()?),
-        });
-        _p.finish(warn);
-        result
+        List::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<List<'a>, Error> {
+        let result = List::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "List"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<List<'a>, Error> {
+        Ok(List {
+            servers: AddrPackedSliceExt::from_bytes(wrap(&mut _p.locate(warn, "List.servers")), _p.read_rest()?),
+        })
+    }
+    pub fn new(servers: &'a [AddrPacked]) -> Result<List<'a>, Error> {
+        let result = List {
+            servers,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_rest(self.servers.as_bytes())?;
         Ok(_p.written())
     }
@@ -321,11 +364,15 @@ impl<'a> fmt::Debug for List<'a> {
 
 impl RequestCount {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestCount, Error> {
-        let result = Ok(RequestCount);
-        _p.finish(warn);
-        result
+        RequestCount::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestCount, Error> {
+        error::finish(&mut _p.locate(warn, "RequestCount"), _p, Ok(RequestCount))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -338,13 +385,28 @@ impl fmt::Debug for RequestCount {
 
 impl Count {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Count, Error> {
-        let result = Ok(Count {
+        Count::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Count, Error> {
+        let result = Count::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Count"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker) -> Result<Count, Error> {
+        Ok(Count {
             count: { let s = _p.read_raw(2)?; BeU16::from_bytes(&[s[0], s[1]]).to_u16() },
-        });
-        _p.finish(warn);
-        result
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(count: u16) -> Result<Count, Error> {
+        let result = Count {
+            count,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_raw(BeU16::from_u16(self.count).as_bytes())?;
         Ok(_p.written())
     }
@@ -359,13 +421,28 @@ impl fmt::Debug for Count {
 
 impl RequestInfo {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestInfo, Error> {
-        let result = Ok(RequestInfo {
+        RequestInfo::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestInfo, Error> {
+        let result = RequestInfo::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RequestInfo"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker) -> Result<RequestInfo, Error> {
+        Ok(RequestInfo {
             token: _p.read_raw(1)?[0],
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(token: u8) -> Result<RequestInfo, Error> {
+        let result = RequestInfo {
+            token,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_raw(&[self.token])?;
         Ok(_p.written())
     }
@@ -380,31 +457,65 @@ impl fmt::Debug for RequestInfo {
 
 impl<'a> Info<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info<'a>, Error> {
-        let result = Ok(Info {
-            token: _p.read_int(warn)?,
-            version: sanitize(warn, _p.read_string()?)?,
-            name: sanitize(warn, _p.read_string()?)?,
-            hostname: sanitize(warn, _p.read_string()?)?,
-            map: sanitize(warn, _p.read_string()?)?,
-            game_type: sanitize(warn, _p.read_string()?)?,
-            flags: _p.read_int(warn)?,
-            skill_level: in_range(_p.read_int(warn)?, 0, 2)?,
-            num_players: _p.read_int(warn)?,
-            max_players: _p.read_int(warn)?,
-            num_clients: _p.read_int(warn)?,
-            max_clients: _p.read_int(warn)?,
+        Info::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info<'a>, Error> {
+        let result = Info::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Info"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info<'a>, Error> {
+        Ok(Info {
+            token: _p.read_int(&mut _p.locate(warn, "Info.token"))?,
+            version: sanitize(&mut _p.locate(warn, "Info.version"), _p.read_string()?)?,
+            name: sanitize(&mut _p.locate(warn, "Info.name"), _p.read_string()?)?,
+            hostname: sanitize(&mut _p.locate(warn, "Info.hostname"), _p.read_string()?)?,
+            map: sanitize(&mut _p.locate(warn, "Info.map"), _p.read_string()?)?,
+            game_type: sanitize(&mut _p.locate(warn, "Info.game_type"), _p.read_string()?)?,
+            flags: _p.read_int(&mut _p.locate(warn, "Info.flags"))?,
+            skill_level: in_range(_p.read_int(&mut _p.locate(warn, "Info.skill_level"))?, 0, 2)?,
+            num_players: _p.read_int(&mut _p.locate(warn, "Info.num_players"))?,
+            max_players: _p.read_int(&mut _p.locate(warn, "Info.max_players"))?,
+            num_clients: _p.read_int(&mut _p.locate(warn, "Info.num_clients"))?,
+            max_clients: _p.read_int(&mut _p.locate(warn, "Info.max_clients"))?,
             clients: ClientsData::from_bytes(_p.read_rest()?),
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.version).unwrap();
-        sanitize(&mut Panic, self.name).unwrap();
-        sanitize(&mut Panic, self.hostname).unwrap();
-        sanitize(&mut Panic, self.map).unwrap();
-        sanitize(&mut Panic, self.game_type).unwrap();
-        assert!(0 <= self.skill_level && self.skill_level <= 2);
+        })
+    }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(token: i32, version: &'a [u8], name: &'a [u8], hostname: &'a [u8], map: &'a [u8], game_type: &'a [u8], flags: i32, skill_level: i32, num_players: i32, max_players: i32, num_clients: i32, max_clients: i32, clients: ClientsData<'a>) -> Result<Info<'a>, Error> {
+        let result = Info {
+            token,
+            version,
+            name,
+            hostname,
+            map,
+            game_type,
+            flags,
+            skill_level,
+            num_players,
+            max_players,
+            num_clients,
+            max_clients,
+            clients,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.version)?;
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.hostname)?;
+        sanitize(&mut Ignore, self.map)?;
+        sanitize(&mut Ignore, self.game_type)?;
+        in_range(self.skill_level, 0, 2)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.version)?;
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.hostname)?;
+        sanitize(&mut Ignore, self.map)?;
+        sanitize(&mut Ignore, self.game_type)?;
+        in_range(self.skill_level, 0, 2)?;
         _p.write_int(self.token)?;
         _p.write_string(self.version)?;
         _p.write_string(self.name)?;
@@ -443,13 +554,28 @@ impl<'a> fmt::Debug for Info<'a> {
 
 impl Heartbeat {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Heartbeat, Error> {
-        let result = Ok(Heartbeat {
+        Heartbeat::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Heartbeat, Error> {
+        let result = Heartbeat::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Heartbeat"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker) -> Result<Heartbeat, Error> {
+        Ok(Heartbeat {
             alt_port: { let s = _p.read_raw(2)?; BeU16::from_bytes(&[s[0], s[1]]).to_u16() },
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(alt_port: u16) -> Result<Heartbeat, Error> {
+        let result = Heartbeat {
+            alt_port,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_raw(BeU16::from_u16(self.alt_port).as_bytes())?;
         Ok(_p.written())
     }
@@ -464,11 +590,15 @@ impl fmt::Debug for Heartbeat {
 
 impl ForwardCheck {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardCheck, Error> {
-        let result = Ok(ForwardCheck);
-        _p.finish(warn);
-        result
+        ForwardCheck::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardCheck, Error> {
+        error::finish(&mut _p.locate(warn, "ForwardCheck"), _p, Ok(ForwardCheck))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -481,11 +611,15 @@ impl fmt::Debug for ForwardCheck {
 
 impl ForwardResponse {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardResponse, Error> {
-        let result = Ok(ForwardResponse);
-        _p.finish(warn);
-        result
+        ForwardResponse::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardResponse, Error> {
+        error::finish(&mut _p.locate(warn, "ForwardResponse"), _p, Ok(ForwardResponse))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -498,11 +632,15 @@ impl fmt::Debug for ForwardResponse {
 
 impl ForwardOk {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardOk, Error> {
-        let result = Ok(ForwardOk);
-        _p.finish(warn);
-        result
+        ForwardOk::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardOk, Error> {
+        error::finish(&mut _p.locate(warn, "ForwardOk"), _p, Ok(ForwardOk))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -515,11 +653,15 @@ impl fmt::Debug for ForwardOk {
 
 impl ForwardError {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardError, Error> {
-        let result = Ok(ForwardError);
-        _p.finish(warn);
-        result
+        ForwardError::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ForwardError, Error> {
+        error::finish(&mut _p.locate(warn, "ForwardError"), _p, Ok(ForwardError))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
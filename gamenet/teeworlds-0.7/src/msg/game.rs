@@ -1,6 +1,9 @@
-use buffer::CapacityError;
 use common::pretty;
+use common::warn::Located;
+use common::warn::strip_location;
 use enums;
+use error;
+use error::EncodeError;
 use error::Error;
 use gamenet_common::debug::DebugSlice;
 use packer::Packer;
@@ -8,6 +11,7 @@ use packer::Unpacker;
 use packer::Warning;
 use packer::at_least;
 use packer::in_range;
+use packer::max_length;
 use packer::positive;
 use packer::sanitize;
 use packer::to_bool;
@@ -15,7 +19,7 @@ use packer::with_packer;
 use std::fmt;
 use super::MessageId;
 use super::SystemOrGame;
-use warn::Panic;
+use warn::Ignore;
 use warn::Warn;
 
 pub use gamenet_common::msg::TuneParam;
@@ -31,7 +35,7 @@ impl<'a> Game<'a> {
         }
     }
     pub fn encode<'d, 's>(&self, mut p: Packer<'d, 's>)
-        -> Result<&'d [u8], CapacityError>
+        -> Result<&'d [u8], EncodeError>
     {
         with_packer(&mut p, |p| SystemOrGame::Game(self.msg_id()).encode_id(p))?;
         with_packer(&mut p, |p| self.encode_msg(p))?;
@@ -80,6 +84,7 @@ pub const SV_COMMAND_INFO_REMOVE: i32 = 38;
 pub const CL_COMMAND: i32 = 39;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub enum Game<'a> {
     SvMotd(SvMotd<'a>),
     SvBroadcast(SvBroadcast<'a>),
@@ -124,47 +129,50 @@ pub enum Game<'a> {
 
 impl<'a> Game<'a> {
     pub fn decode_msg<W: Warn<Warning>>(warn: &mut W, msg_id: MessageId, _p: &mut Unpacker<'a>) -> Result<Game<'a>, Error> {
+        Game::decode_msg_located(&mut strip_location(warn), msg_id, _p)
+    }
+    pub fn decode_msg_located<W: Warn<Located<Warning>>>(warn: &mut W, msg_id: MessageId, _p: &mut Unpacker<'a>) -> Result<Game<'a>, Error> {
         use self::MessageId::*;
         Ok(match msg_id {
-            Ordinal(SV_MOTD) => Game::SvMotd(SvMotd::decode(warn, _p)?),
-            Ordinal(SV_BROADCAST) => Game::SvBroadcast(SvBroadcast::decode(warn, _p)?),
-            Ordinal(SV_CHAT) => Game::SvChat(SvChat::decode(warn, _p)?),
-            Ordinal(SV_TEAM) => Game::SvTeam(SvTeam::decode(warn, _p)?),
-            Ordinal(SV_KILL_MSG) => Game::SvKillMsg(SvKillMsg::decode(warn, _p)?),
-            Ordinal(SV_TUNE_PARAMS) => Game::SvTuneParams(SvTuneParams::decode(warn, _p)?),
-            Ordinal(SV_EXTRA_PROJECTILE) => Game::SvExtraProjectile(SvExtraProjectile::decode(warn, _p)?),
-            Ordinal(SV_READY_TO_ENTER) => Game::SvReadyToEnter(SvReadyToEnter::decode(warn, _p)?),
-            Ordinal(SV_WEAPON_PICKUP) => Game::SvWeaponPickup(SvWeaponPickup::decode(warn, _p)?),
-            Ordinal(SV_EMOTICON) => Game::SvEmoticon(SvEmoticon::decode(warn, _p)?),
-            Ordinal(SV_VOTE_CLEAR_OPTIONS) => Game::SvVoteClearOptions(SvVoteClearOptions::decode(warn, _p)?),
-            Ordinal(SV_VOTE_OPTION_LIST_ADD) => Game::SvVoteOptionListAdd(SvVoteOptionListAdd::decode(warn, _p)?),
-            Ordinal(SV_VOTE_OPTION_ADD) => Game::SvVoteOptionAdd(SvVoteOptionAdd::decode(warn, _p)?),
-            Ordinal(SV_VOTE_OPTION_REMOVE) => Game::SvVoteOptionRemove(SvVoteOptionRemove::decode(warn, _p)?),
-            Ordinal(SV_VOTE_SET) => Game::SvVoteSet(SvVoteSet::decode(warn, _p)?),
-            Ordinal(SV_VOTE_STATUS) => Game::SvVoteStatus(SvVoteStatus::decode(warn, _p)?),
-            Ordinal(SV_SERVER_SETTINGS) => Game::SvServerSettings(SvServerSettings::decode(warn, _p)?),
-            Ordinal(SV_CLIENT_INFO) => Game::SvClientInfo(SvClientInfo::decode(warn, _p)?),
-            Ordinal(SV_GAME_INFO) => Game::SvGameInfo(SvGameInfo::decode(warn, _p)?),
-            Ordinal(SV_CLIENT_DROP) => Game::SvClientDrop(SvClientDrop::decode(warn, _p)?),
-            Ordinal(SV_GAME_MSG) => Game::SvGameMsg(SvGameMsg::decode(warn, _p)?),
-            Ordinal(DE_CLIENT_ENTER) => Game::DeClientEnter(DeClientEnter::decode(warn, _p)?),
-            Ordinal(DE_CLIENT_LEAVE) => Game::DeClientLeave(DeClientLeave::decode(warn, _p)?),
-            Ordinal(CL_SAY) => Game::ClSay(ClSay::decode(warn, _p)?),
-            Ordinal(CL_SET_TEAM) => Game::ClSetTeam(ClSetTeam::decode(warn, _p)?),
-            Ordinal(CL_SET_SPECTATOR_MODE) => Game::ClSetSpectatorMode(ClSetSpectatorMode::decode(warn, _p)?),
-            Ordinal(CL_START_INFO) => Game::ClStartInfo(ClStartInfo::decode(warn, _p)?),
-            Ordinal(CL_KILL) => Game::ClKill(ClKill::decode(warn, _p)?),
-            Ordinal(CL_READY_CHANGE) => Game::ClReadyChange(ClReadyChange::decode(warn, _p)?),
-            Ordinal(CL_EMOTICON) => Game::ClEmoticon(ClEmoticon::decode(warn, _p)?),
-            Ordinal(CL_VOTE) => Game::ClVote(ClVote::decode(warn, _p)?),
-            Ordinal(CL_CALL_VOTE) => Game::ClCallVote(ClCallVote::decode(warn, _p)?),
-            Ordinal(SV_SKIN_CHANGE) => Game::SvSkinChange(SvSkinChange::decode(warn, _p)?),
-            Ordinal(CL_SKIN_CHANGE) => Game::ClSkinChange(ClSkinChange::decode(warn, _p)?),
-            Ordinal(SV_RACE_FINISH) => Game::SvRaceFinish(SvRaceFinish::decode(warn, _p)?),
-            Ordinal(SV_CHECKPOINT) => Game::SvCheckpoint(SvCheckpoint::decode(warn, _p)?),
-            Ordinal(SV_COMMAND_INFO) => Game::SvCommandInfo(SvCommandInfo::decode(warn, _p)?),
-            Ordinal(SV_COMMAND_INFO_REMOVE) => Game::SvCommandInfoRemove(SvCommandInfoRemove::decode(warn, _p)?),
-            Ordinal(CL_COMMAND) => Game::ClCommand(ClCommand::decode(warn, _p)?),
+            Ordinal(SV_MOTD) => Game::SvMotd(SvMotd::decode_located(warn, _p)?),
+            Ordinal(SV_BROADCAST) => Game::SvBroadcast(SvBroadcast::decode_located(warn, _p)?),
+            Ordinal(SV_CHAT) => Game::SvChat(SvChat::decode_located(warn, _p)?),
+            Ordinal(SV_TEAM) => Game::SvTeam(SvTeam::decode_located(warn, _p)?),
+            Ordinal(SV_KILL_MSG) => Game::SvKillMsg(SvKillMsg::decode_located(warn, _p)?),
+            Ordinal(SV_TUNE_PARAMS) => Game::SvTuneParams(SvTuneParams::decode_located(warn, _p)?),
+            Ordinal(SV_EXTRA_PROJECTILE) => Game::SvExtraProjectile(SvExtraProjectile::decode_located(warn, _p)?),
+            Ordinal(SV_READY_TO_ENTER) => Game::SvReadyToEnter(SvReadyToEnter::decode_located(warn, _p)?),
+            Ordinal(SV_WEAPON_PICKUP) => Game::SvWeaponPickup(SvWeaponPickup::decode_located(warn, _p)?),
+            Ordinal(SV_EMOTICON) => Game::SvEmoticon(SvEmoticon::decode_located(warn, _p)?),
+            Ordinal(SV_VOTE_CLEAR_OPTIONS) => Game::SvVoteClearOptions(SvVoteClearOptions::decode_located(warn, _p)?),
+            Ordinal(SV_VOTE_OPTION_LIST_ADD) => Game::SvVoteOptionListAdd(SvVoteOptionListAdd::decode_located(warn, _p)?),
+            Ordinal(SV_VOTE_OPTION_ADD) => Game::SvVoteOptionAdd(SvVoteOptionAdd::decode_located(warn, _p)?),
+            Ordinal(SV_VOTE_OPTION_REMOVE) => Game::SvVoteOptionRemove(SvVoteOptionRemove::decode_located(warn, _p)?),
+            Ordinal(SV_VOTE_SET) => Game::SvVoteSet(SvVoteSet::decode_located(warn, _p)?),
+            Ordinal(SV_VOTE_STATUS) => Game::SvVoteStatus(SvVoteStatus::decode_located(warn, _p)?),
+            Ordinal(SV_SERVER_SETTINGS) => Game::SvServerSettings(SvServerSettings::decode_located(warn, _p)?),
+            Ordinal(SV_CLIENT_INFO) => Game::SvClientInfo(SvClientInfo::decode_located(warn, _p)?),
+            Ordinal(SV_GAME_INFO) => Game::SvGameInfo(SvGameInfo::decode_located(warn, _p)?),
+            Ordinal(SV_CLIENT_DROP) => Game::SvClientDrop(SvClientDrop::decode_located(warn, _p)?),
+            Ordinal(SV_GAME_MSG) => Game::SvGameMsg(SvGameMsg::decode_located(warn, _p)?),
+            Ordinal(DE_CLIENT_ENTER) => Game::DeClientEnter(DeClientEnter::decode_located(warn, _p)?),
+            Ordinal(DE_CLIENT_LEAVE) => Game::DeClientLeave(DeClientLeave::decode_located(warn, _p)?),
+            Ordinal(CL_SAY) => Game::ClSay(ClSay::decode_located(warn, _p)?),
+            Ordinal(CL_SET_TEAM) => Game::ClSetTeam(ClSetTeam::decode_located(warn, _p)?),
+            Ordinal(CL_SET_SPECTATOR_MODE) => Game::ClSetSpectatorMode(ClSetSpectatorMode::decode_located(warn, _p)?),
+            Ordinal(CL_START_INFO) => Game::ClStartInfo(ClStartInfo::decode_located(warn, _p)?),
+            Ordinal(CL_KILL) => Game::ClKill(ClKill::decode_located(warn, _p)?),
+            Ordinal(CL_READY_CHANGE) => Game::ClReadyChange(ClReadyChange::decode_located(warn, _p)?),
+            Ordinal(CL_EMOTICON) => Game::ClEmoticon(ClEmoticon::decode_located(warn, _p)?),
+            Ordinal(CL_VOTE) => Game::ClVote(ClVote::decode_located(warn, _p)?),
+            Ordinal(CL_CALL_VOTE) => Game::ClCallVote(ClCallVote::decode_located(warn, _p)?),
+            Ordinal(SV_SKIN_CHANGE) => Game::SvSkinChange(SvSkinChange::decode_located(warn, _p)?),
+            Ordinal(CL_SKIN_CHANGE) => Game::ClSkinChange(ClSkinChange::decode_located(warn, _p)?),
+            Ordinal(SV_RACE_FINISH) => Game::SvRaceFinish(SvRaceFinish::decode_located(warn, _p)?),
+            Ordinal(SV_CHECKPOINT) => Game::SvCheckpoint(SvCheckpoint::decode_located(warn, _p)?),
+            Ordinal(SV_COMMAND_INFO) => Game::SvCommandInfo(SvCommandInfo::decode_located(warn, _p)?),
+            Ordinal(SV_COMMAND_INFO_REMOVE) => Game::SvCommandInfoRemove(SvCommandInfoRemove::decode_located(warn, _p)?),
+            Ordinal(CL_COMMAND) => Game::ClCommand(ClCommand::decode_located(warn, _p)?),
             _ => return Err(Error::UnknownId),
         })
     }
@@ -211,7 +219,50 @@ impl<'a> Game<'a> {
             Game::ClCommand(_) => MessageId::from(CL_COMMAND),
         }
     }
-    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        match *self {
+            Game::SvMotd(ref i) => i.validate(),
+            Game::SvBroadcast(ref i) => i.validate(),
+            Game::SvChat(ref i) => i.validate(),
+            Game::SvTeam(ref i) => i.validate(),
+            Game::SvKillMsg(ref i) => i.validate(),
+            Game::SvTuneParams(ref i) => i.validate(),
+            Game::SvExtraProjectile(ref i) => i.validate(),
+            Game::SvReadyToEnter(ref i) => i.validate(),
+            Game::SvWeaponPickup(ref i) => i.validate(),
+            Game::SvEmoticon(ref i) => i.validate(),
+            Game::SvVoteClearOptions(ref i) => i.validate(),
+            Game::SvVoteOptionListAdd(ref i) => i.validate(),
+            Game::SvVoteOptionAdd(ref i) => i.validate(),
+            Game::SvVoteOptionRemove(ref i) => i.validate(),
+            Game::SvVoteSet(ref i) => i.validate(),
+            Game::SvVoteStatus(ref i) => i.validate(),
+            Game::SvServerSettings(ref i) => i.validate(),
+            Game::SvClientInfo(ref i) => i.validate(),
+            Game::SvGameInfo(ref i) => i.validate(),
+            Game::SvClientDrop(ref i) => i.validate(),
+            Game::SvGameMsg(ref i) => i.validate(),
+            Game::DeClientEnter(ref i) => i.validate(),
+            Game::DeClientLeave(ref i) => i.validate(),
+            Game::ClSay(ref i) => i.validate(),
+            Game::ClSetTeam(ref i) => i.validate(),
+            Game::ClSetSpectatorMode(ref i) => i.validate(),
+            Game::ClStartInfo(ref i) => i.validate(),
+            Game::ClKill(ref i) => i.validate(),
+            Game::ClReadyChange(ref i) => i.validate(),
+            Game::ClEmoticon(ref i) => i.validate(),
+            Game::ClVote(ref i) => i.validate(),
+            Game::ClCallVote(ref i) => i.validate(),
+            Game::SvSkinChange(ref i) => i.validate(),
+            Game::ClSkinChange(ref i) => i.validate(),
+            Game::SvRaceFinish(ref i) => i.validate(),
+            Game::SvCheckpoint(ref i) => i.validate(),
+            Game::SvCommandInfo(ref i) => i.validate(),
+            Game::SvCommandInfoRemove(ref i) => i.validate(),
+            Game::ClCommand(ref i) => i.validate(),
+        }
+    }
+    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         match *self {
             Game::SvMotd(ref i) => i.encode(p),
             Game::SvBroadcast(ref i) => i.encode(p),
@@ -535,25 +586,186 @@ impl<'a> From<ClCommand<'a>> for Game<'a> {
         Game::ClCommand(i)
     }
 }
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GameOwned {
+    SvMotd(SvMotdOwned),
+    SvBroadcast(SvBroadcastOwned),
+    SvChat(SvChatOwned),
+    SvTeam(SvTeam),
+    SvKillMsg(SvKillMsg),
+    SvTuneParams(SvTuneParams),
+    SvExtraProjectile(SvExtraProjectile),
+    SvReadyToEnter(SvReadyToEnter),
+    SvWeaponPickup(SvWeaponPickup),
+    SvEmoticon(SvEmoticon),
+    SvVoteClearOptions(SvVoteClearOptions),
+    SvVoteOptionListAdd(SvVoteOptionListAdd),
+    SvVoteOptionAdd(SvVoteOptionAddOwned),
+    SvVoteOptionRemove(SvVoteOptionRemoveOwned),
+    SvVoteSet(SvVoteSetOwned),
+    SvVoteStatus(SvVoteStatus),
+    SvServerSettings(SvServerSettings),
+    SvClientInfo(SvClientInfoOwned),
+    SvGameInfo(SvGameInfo),
+    SvClientDrop(SvClientDropOwned),
+    SvGameMsg(SvGameMsg),
+    DeClientEnter(DeClientEnterOwned),
+    DeClientLeave(DeClientLeaveOwned),
+    ClSay(ClSayOwned),
+    ClSetTeam(ClSetTeam),
+    ClSetSpectatorMode(ClSetSpectatorMode),
+    ClStartInfo(ClStartInfoOwned),
+    ClKill(ClKill),
+    ClReadyChange(ClReadyChange),
+    ClEmoticon(ClEmoticon),
+    ClVote(ClVote),
+    ClCallVote(ClCallVoteOwned),
+    SvSkinChange(SvSkinChangeOwned),
+    ClSkinChange(ClSkinChangeOwned),
+    SvRaceFinish(SvRaceFinish),
+    SvCheckpoint(SvCheckpoint),
+    SvCommandInfo(SvCommandInfoOwned),
+    SvCommandInfoRemove(SvCommandInfoRemoveOwned),
+    ClCommand(ClCommandOwned),
+}
+
+impl<'a> Game<'a> {
+    pub fn to_owned(&self) -> GameOwned {
+        match *self {
+            Game::SvMotd(ref i) => GameOwned::SvMotd(i.to_owned()),
+            Game::SvBroadcast(ref i) => GameOwned::SvBroadcast(i.to_owned()),
+            Game::SvChat(ref i) => GameOwned::SvChat(i.to_owned()),
+            Game::SvTeam(i) => GameOwned::SvTeam(i),
+            Game::SvKillMsg(i) => GameOwned::SvKillMsg(i),
+            Game::SvTuneParams(i) => GameOwned::SvTuneParams(i),
+            Game::SvExtraProjectile(i) => GameOwned::SvExtraProjectile(i),
+            Game::SvReadyToEnter(i) => GameOwned::SvReadyToEnter(i),
+            Game::SvWeaponPickup(i) => GameOwned::SvWeaponPickup(i),
+            Game::SvEmoticon(i) => GameOwned::SvEmoticon(i),
+            Game::SvVoteClearOptions(i) => GameOwned::SvVoteClearOptions(i),
+            Game::SvVoteOptionListAdd(i) => GameOwned::SvVoteOptionListAdd(i),
+            Game::SvVoteOptionAdd(ref i) => GameOwned::SvVoteOptionAdd(i.to_owned()),
+            Game::SvVoteOptionRemove(ref i) => GameOwned::SvVoteOptionRemove(i.to_owned()),
+            Game::SvVoteSet(ref i) => GameOwned::SvVoteSet(i.to_owned()),
+            Game::SvVoteStatus(i) => GameOwned::SvVoteStatus(i),
+            Game::SvServerSettings(i) => GameOwned::SvServerSettings(i),
+            Game::SvClientInfo(ref i) => GameOwned::SvClientInfo(i.to_owned()),
+            Game::SvGameInfo(i) => GameOwned::SvGameInfo(i),
+            Game::SvClientDrop(ref i) => GameOwned::SvClientDrop(i.to_owned()),
+            Game::SvGameMsg(i) => GameOwned::SvGameMsg(i),
+            Game::DeClientEnter(ref i) => GameOwned::DeClientEnter(i.to_owned()),
+            Game::DeClientLeave(ref i) => GameOwned::DeClientLeave(i.to_owned()),
+            Game::ClSay(ref i) => GameOwned::ClSay(i.to_owned()),
+            Game::ClSetTeam(i) => GameOwned::ClSetTeam(i),
+            Game::ClSetSpectatorMode(i) => GameOwned::ClSetSpectatorMode(i),
+            Game::ClStartInfo(ref i) => GameOwned::ClStartInfo(i.to_owned()),
+            Game::ClKill(i) => GameOwned::ClKill(i),
+            Game::ClReadyChange(i) => GameOwned::ClReadyChange(i),
+            Game::ClEmoticon(i) => GameOwned::ClEmoticon(i),
+            Game::ClVote(i) => GameOwned::ClVote(i),
+            Game::ClCallVote(ref i) => GameOwned::ClCallVote(i.to_owned()),
+            Game::SvSkinChange(ref i) => GameOwned::SvSkinChange(i.to_owned()),
+            Game::ClSkinChange(ref i) => GameOwned::ClSkinChange(i.to_owned()),
+            Game::SvRaceFinish(i) => GameOwned::SvRaceFinish(i),
+            Game::SvCheckpoint(i) => GameOwned::SvCheckpoint(i),
+            Game::SvCommandInfo(ref i) => GameOwned::SvCommandInfo(i.to_owned()),
+            Game::SvCommandInfoRemove(ref i) => GameOwned::SvCommandInfoRemove(i.to_owned()),
+            Game::ClCommand(ref i) => GameOwned::ClCommand(i.to_owned()),
+        }
+    }
+}
+
+impl GameOwned {
+    pub fn as_ref(&self) -> Game<'_> {
+        match *self {
+            GameOwned::SvMotd(ref i) => Game::SvMotd(i.as_ref()),
+            GameOwned::SvBroadcast(ref i) => Game::SvBroadcast(i.as_ref()),
+            GameOwned::SvChat(ref i) => Game::SvChat(i.as_ref()),
+            GameOwned::SvTeam(i) => Game::SvTeam(i),
+            GameOwned::SvKillMsg(i) => Game::SvKillMsg(i),
+            GameOwned::SvTuneParams(i) => Game::SvTuneParams(i),
+            GameOwned::SvExtraProjectile(i) => Game::SvExtraProjectile(i),
+            GameOwned::SvReadyToEnter(i) => Game::SvReadyToEnter(i),
+            GameOwned::SvWeaponPickup(i) => Game::SvWeaponPickup(i),
+            GameOwned::SvEmoticon(i) => Game::SvEmoticon(i),
+            GameOwned::SvVoteClearOptions(i) => Game::SvVoteClearOptions(i),
+            GameOwned::SvVoteOptionListAdd(i) => Game::SvVoteOptionListAdd(i),
+            GameOwned::SvVoteOptionAdd(ref i) => Game::SvVoteOptionAdd(i.as_ref()),
+            GameOwned::SvVoteOptionRemove(ref i) => Game::SvVoteOptionRemove(i.as_ref()),
+            GameOwned::SvVoteSet(ref i) => Game::SvVoteSet(i.as_ref()),
+            GameOwned::SvVoteStatus(i) => Game::SvVoteStatus(i),
+            GameOwned::SvServerSettings(i) => Game::SvServerSettings(i),
+            GameOwned::SvClientInfo(ref i) => Game::SvClientInfo(i.as_ref()),
+            GameOwned::SvGameInfo(i) => Game::SvGameInfo(i),
+            GameOwned::SvClientDrop(ref i) => Game::SvClientDrop(i.as_ref()),
+            GameOwned::SvGameMsg(i) => Game::SvGameMsg(i),
+            GameOwned::DeClientEnter(ref i) => Game::DeClientEnter(i.as_ref()),
+            GameOwned::DeClientLeave(ref i) => Game::DeClientLeave(i.as_ref()),
+            GameOwned::ClSay(ref i) => Game::ClSay(i.as_ref()),
+            GameOwned::ClSetTeam(i) => Game::ClSetTeam(i),
+            GameOwned::ClSetSpectatorMode(i) => Game::ClSetSpectatorMode(i),
+            GameOwned::ClStartInfo(ref i) => Game::ClStartInfo(i.as_ref()),
+            GameOwned::ClKill(i) => Game::ClKill(i),
+            GameOwned::ClReadyChange(i) => Game::ClReadyChange(i),
+            GameOwned::ClEmoticon(i) => Game::ClEmoticon(i),
+            GameOwned::ClVote(i) => Game::ClVote(i),
+            GameOwned::ClCallVote(ref i) => Game::ClCallVote(i.as_ref()),
+            GameOwned::SvSkinChange(ref i) => Game::SvSkinChange(i.as_ref()),
+            GameOwned::ClSkinChange(ref i) => Game::ClSkinChange(i.as_ref()),
+            GameOwned::SvRaceFinish(i) => Game::SvRaceFinish(i),
+            GameOwned::SvCheckpoint(i) => Game::SvCheckpoint(i),
+            GameOwned::SvCommandInfo(ref i) => Game::SvCommandInfo(i.as_ref()),
+            GameOwned::SvCommandInfoRemove(ref i) => Game::SvCommandInfoRemove(i.as_ref()),
+            GameOwned::ClCommand(ref i) => Game::ClCommand(i.as_ref()),
+        }
+    }
+    pub fn msg_id(&self) -> MessageId {
+        self.as_ref().msg_id()
+    }
+    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        self.as_ref().encode_msg(p)
+    }
+}
+
+impl fmt::Debug for GameOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> From<Game<'a>> for GameOwned {
+    fn from(i: Game<'a>) -> GameOwned {
+        i.to_owned()
+    }
+}
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvMotd<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub message: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvBroadcast<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub message: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvChat<'a> {
     pub mode: enums::Chat,
     pub client_id: i32,
     pub target_id: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub message: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvTeam {
     pub client_id: i32,
     pub team: enums::Team,
@@ -562,6 +774,7 @@ pub struct SvTeam {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvKillMsg {
     pub killer: i32,
     pub victim: i32,
@@ -570,6 +783,7 @@ pub struct SvKillMsg {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvTuneParams {
     pub ground_control_speed: TuneParam,
     pub ground_control_accel: TuneParam,
@@ -606,50 +820,64 @@ pub struct SvTuneParams {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvExtraProjectile {
     pub projectile: ::snap_obj::Projectile,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvReadyToEnter;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvWeaponPickup {
     pub weapon: enums::Weapon,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvEmoticon {
     pub client_id: i32,
     pub emoticon: enums::Emoticon,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvVoteClearOptions;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvVoteOptionListAdd;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvVoteOptionAdd<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub description: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvVoteOptionRemove<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub description: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvVoteSet<'a> {
     pub client_id: i32,
     pub type_: enums::Vote,
     pub timeout: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub description: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub reason: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvVoteStatus {
     pub yes: i32,
     pub no: i32,
@@ -658,6 +886,7 @@ pub struct SvVoteStatus {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvServerSettings {
     pub kick_vote: bool,
     pub kick_min: i32,
@@ -668,13 +897,17 @@ pub struct SvServerSettings {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvClientInfo<'a> {
     pub client_id: i32,
     pub local: bool,
     pub team: enums::Team,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub clan: &'a [u8],
     pub country: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes_array::serialize"))]
     pub skin_part_names: [&'a [u8]; 6],
     pub use_custom_colors: [bool; 6],
     pub skin_part_colors: [i32; 6],
@@ -682,6 +915,7 @@ pub struct SvClientInfo<'a> {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvGameInfo {
     pub game_flags: i32,
     pub score_limit: i32,
@@ -691,97 +925,126 @@ pub struct SvGameInfo {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvClientDrop<'a> {
     pub client_id: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub reason: &'a [u8],
     pub silent: bool,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvGameMsg;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct DeClientEnter<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
     pub client_id: i32,
     pub team: enums::Team,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct DeClientLeave<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
     pub client_id: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub reason: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ClSay<'a> {
     pub mode: enums::Chat,
     pub target: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub message: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClSetTeam {
     pub team: enums::Team,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClSetSpectatorMode {
     pub spec_mode: enums::Spec,
     pub spectator_id: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ClStartInfo<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub clan: &'a [u8],
     pub country: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes_array::serialize"))]
     pub skin_part_names: [&'a [u8]; 6],
     pub use_custom_colors: [bool; 6],
     pub skin_part_colors: [i32; 6],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClKill;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClReadyChange;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClEmoticon {
     pub emoticon: enums::Emoticon,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClVote {
     pub vote: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ClCallVote<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub type_: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub value: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub reason: &'a [u8],
     pub force: bool,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvSkinChange<'a> {
     pub client_id: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes_array::serialize"))]
     pub skin_part_names: [&'a [u8]; 6],
     pub use_custom_colors: [bool; 6],
     pub skin_part_colors: [i32; 6],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ClSkinChange<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes_array::serialize"))]
     pub skin_part_names: [&'a [u8]; 6],
     pub use_custom_colors: [bool; 6],
     pub skin_part_colors: [i32; 6],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvRaceFinish {
     pub client_id: i32,
     pub time: i32,
@@ -791,37 +1054,239 @@ pub struct SvRaceFinish {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvCheckpoint {
     pub diff: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvCommandInfo<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub args_format: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub help_text: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SvCommandInfoRemove<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ClCommand<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub arguments: &'a [u8],
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvMotdOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvBroadcastOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvChatOwned {
+    pub mode: enums::Chat,
+    pub client_id: i32,
+    pub target_id: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvVoteOptionAddOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub description: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvVoteOptionRemoveOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub description: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvVoteSetOwned {
+    pub client_id: i32,
+    pub type_: enums::Vote,
+    pub timeout: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub description: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub reason: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvClientInfoOwned {
+    pub client_id: i32,
+    pub local: bool,
+    pub team: enums::Team,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub clan: Vec<u8>,
+    pub country: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes_array"))]
+    pub skin_part_names: [Vec<u8>; 6],
+    pub use_custom_colors: [bool; 6],
+    pub skin_part_colors: [i32; 6],
+    pub silent: bool,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvClientDropOwned {
+    pub client_id: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub reason: Vec<u8>,
+    pub silent: bool,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeClientEnterOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+    pub client_id: i32,
+    pub team: enums::Team,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeClientLeaveOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+    pub client_id: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub reason: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClSayOwned {
+    pub mode: enums::Chat,
+    pub target: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClStartInfoOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub clan: Vec<u8>,
+    pub country: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes_array"))]
+    pub skin_part_names: [Vec<u8>; 6],
+    pub use_custom_colors: [bool; 6],
+    pub skin_part_colors: [i32; 6],
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClCallVoteOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub type_: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub value: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub reason: Vec<u8>,
+    pub force: bool,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvSkinChangeOwned {
+    pub client_id: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes_array"))]
+    pub skin_part_names: [Vec<u8>; 6],
+    pub use_custom_colors: [bool; 6],
+    pub skin_part_colors: [i32; 6],
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClSkinChangeOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes_array"))]
+    pub skin_part_names: [Vec<u8>; 6],
+    pub use_custom_colors: [bool; 6],
+    pub skin_part_colors: [i32; 6],
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvCommandInfoOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub args_format: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub help_text: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SvCommandInfoRemoveOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClCommandOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub arguments: Vec<u8>,
+}
+
 impl<'a> SvMotd<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvMotd<'a>, Error> {
-        let result = Ok(SvMotd {
+        SvMotd::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvMotd<'a>, Error> {
+        let result = SvMotd::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvMotd"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvMotd<'a>, Error> {
+        Ok(SvMotd {
             message: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(message: &'a [u8]) -> Result<SvMotd<'a>, Error> {
+        let result = SvMotd {
+            message,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.message)?;
         Ok(_p.written())
     }
@@ -836,13 +1301,28 @@ impl<'a> fmt::Debug for SvMotd<'a> {
 
 impl<'a> SvBroadcast<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvBroadcast<'a>, Error> {
-        let result = Ok(SvBroadcast {
+        SvBroadcast::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvBroadcast<'a>, Error> {
+        let result = SvBroadcast::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvBroadcast"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvBroadcast<'a>, Error> {
+        Ok(SvBroadcast {
             message: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(message: &'a [u8]) -> Result<SvBroadcast<'a>, Error> {
+        let result = SvBroadcast {
+            message,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.message)?;
         Ok(_p.written())
     }
@@ -857,19 +1337,41 @@ impl<'a> fmt::Debug for SvBroadcast<'a> {
 
 impl<'a> SvChat<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvChat<'a>, Error> {
-        let result = Ok(SvChat {
-            mode: enums::Chat::from_i32(_p.read_int(warn)?)?,
-            client_id: in_range(_p.read_int(warn)?, -1, 63)?,
-            target_id: in_range(_p.read_int(warn)?, -1, 63)?,
-            message: sanitize(warn, _p.read_string()?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(-1 <= self.client_id && self.client_id <= 63);
-        assert!(-1 <= self.target_id && self.target_id <= 63);
-        sanitize(&mut Panic, self.message).unwrap();
+        SvChat::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvChat<'a>, Error> {
+        let result = SvChat::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvChat"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvChat<'a>, Error> {
+        Ok(SvChat {
+            mode: enums::Chat::from_i32(_p.read_int(&mut _p.locate(warn, "SvChat.mode"))?),
+            client_id: in_range(_p.read_int(&mut _p.locate(warn, "SvChat.client_id"))?, -1, 63)?,
+            target_id: in_range(_p.read_int(&mut _p.locate(warn, "SvChat.target_id"))?, -1, 63)?,
+            message: sanitize(&mut _p.locate(warn, "SvChat.message"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(mode: enums::Chat, client_id: i32, target_id: i32, message: &'a [u8]) -> Result<SvChat<'a>, Error> {
+        let result = SvChat {
+            mode,
+            client_id,
+            target_id,
+            message,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.mode.known()?;
+        in_range(self.client_id, -1, 63)?;
+        in_range(self.target_id, -1, 63)?;
+        sanitize(&mut Ignore, self.message)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.client_id, -1, 63)?;
+        in_range(self.target_id, -1, 63)?;
+        sanitize(&mut Ignore, self.message)?;
         _p.write_int(self.mode.to_i32())?;
         _p.write_int(self.client_id)?;
         _p.write_int(self.target_id)?;
@@ -890,17 +1392,37 @@ impl<'a> fmt::Debug for SvChat<'a> {
 
 impl SvTeam {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvTeam, Error> {
-        let result = Ok(SvTeam {
-            client_id: in_range(_p.read_int(warn)?, -1, 63)?,
-            team: enums::Team::from_i32(_p.read_int(warn)?)?,
-            silent: to_bool(_p.read_int(warn)?)?,
-            cooldown_tick: ::snap_obj::Tick(_p.read_int(warn)?),
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(-1 <= self.client_id && self.client_id <= 63);
+        SvTeam::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvTeam, Error> {
+        let result = SvTeam::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvTeam"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvTeam, Error> {
+        Ok(SvTeam {
+            client_id: in_range(_p.read_int(&mut _p.locate(warn, "SvTeam.client_id"))?, -1, 63)?,
+            team: enums::Team::from_i32(_p.read_int(&mut _p.locate(warn, "SvTeam.team"))?),
+            silent: to_bool(_p.read_int(&mut _p.locate(warn, "SvTeam.silent"))?)?,
+            cooldown_tick: ::snap_obj::Tick(_p.read_int(&mut _p.locate(warn, "SvTeam.cooldown_tick"))?),
+        })
+    }
+    pub fn new(client_id: i32, team: enums::Team, silent: bool, cooldown_tick: ::snap_obj::Tick) -> Result<SvTeam, Error> {
+        let result = SvTeam {
+            client_id,
+            team,
+            silent,
+            cooldown_tick,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.client_id, -1, 63)?;
+        self.team.known()?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.client_id, -1, 63)?;
         _p.write_int(self.client_id)?;
         _p.write_int(self.team.to_i32())?;
         _p.write_int(self.silent as i32)?;
@@ -921,19 +1443,40 @@ impl fmt::Debug for SvTeam {
 
 impl SvKillMsg {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvKillMsg, Error> {
-        let result = Ok(SvKillMsg {
-            killer: in_range(_p.read_int(warn)?, -2, 63)?,
-            victim: in_range(_p.read_int(warn)?, 0, 63)?,
-            weapon: in_range(_p.read_int(warn)?, -3, 5)?,
-            mode_special: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(-2 <= self.killer && self.killer <= 63);
-        assert!(0 <= self.victim && self.victim <= 63);
-        assert!(-3 <= self.weapon && self.weapon <= 5);
+        SvKillMsg::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvKillMsg, Error> {
+        let result = SvKillMsg::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvKillMsg"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvKillMsg, Error> {
+        Ok(SvKillMsg {
+            killer: in_range(_p.read_int(&mut _p.locate(warn, "SvKillMsg.killer"))?, -2, 63)?,
+            victim: in_range(_p.read_int(&mut _p.locate(warn, "SvKillMsg.victim"))?, 0, 63)?,
+            weapon: in_range(_p.read_int(&mut _p.locate(warn, "SvKillMsg.weapon"))?, -3, 5)?,
+            mode_special: _p.read_int(&mut _p.locate(warn, "SvKillMsg.mode_special"))?,
+        })
+    }
+    pub fn new(killer: i32, victim: i32, weapon: i32, mode_special: i32) -> Result<SvKillMsg, Error> {
+        let result = SvKillMsg {
+            killer,
+            victim,
+            weapon,
+            mode_special,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.killer, -2, 63)?;
+        in_range(self.victim, 0, 63)?;
+        in_range(self.weapon, -3, 5)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.killer, -2, 63)?;
+        in_range(self.victim, 0, 63)?;
+        in_range(self.weapon, -3, 5)?;
         _p.write_int(self.killer)?;
         _p.write_int(self.victim)?;
         _p.write_int(self.weapon)?;
@@ -954,44 +1497,91 @@ impl fmt::Debug for SvKillMsg {
 
 impl SvTuneParams {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvTuneParams, Error> {
-        let result = Ok(SvTuneParams {
-            ground_control_speed: TuneParam(_p.read_int(warn)?),
-            ground_control_accel: TuneParam(_p.read_int(warn)?),
-            ground_friction: TuneParam(_p.read_int(warn)?),
-            ground_jump_impulse: TuneParam(_p.read_int(warn)?),
-            air_jump_impulse: TuneParam(_p.read_int(warn)?),
-            air_control_speed: TuneParam(_p.read_int(warn)?),
-            air_control_accel: TuneParam(_p.read_int(warn)?),
-            air_friction: TuneParam(_p.read_int(warn)?),
-            hook_length: TuneParam(_p.read_int(warn)?),
-            hook_fire_speed: TuneParam(_p.read_int(warn)?),
-            hook_drag_accel: TuneParam(_p.read_int(warn)?),
-            hook_drag_speed: TuneParam(_p.read_int(warn)?),
-            gravity: TuneParam(_p.read_int(warn)?),
-            velramp_start: TuneParam(_p.read_int(warn)?),
-            velramp_range: TuneParam(_p.read_int(warn)?),
-            velramp_curvature: TuneParam(_p.read_int(warn)?),
-            gun_curvature: TuneParam(_p.read_int(warn)?),
-            gun_speed: TuneParam(_p.read_int(warn)?),
-            gun_lifetime: TuneParam(_p.read_int(warn)?),
-            shotgun_curvature: TuneParam(_p.read_int(warn)?),
-            shotgun_speed: TuneParam(_p.read_int(warn)?),
-            shotgun_speeddiff: TuneParam(_p.read_int(warn)?),
-            shotgun_lifetime: TuneParam(_p.read_int(warn)?),
-            grenade_curvature: TuneParam(_p.read_int(warn)?),
-            grenade_speed: TuneParam(_p.read_int(warn)?),
-            grenade_lifetime: TuneParam(_p.read_int(warn)?),
-            laser_reach: TuneParam(_p.read_int(warn)?),
-            laser_bounce_delay: TuneParam(_p.read_int(warn)?),
-            laser_bounce_num: TuneParam(_p.read_int(warn)?),
-            laser_bounce_cost: TuneParam(_p.read_int(warn)?),
-            player_collision: TuneParam(_p.read_int(warn)?),
-            player_hooking: TuneParam(_p.read_int(warn)?),
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        SvTuneParams::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvTuneParams, Error> {
+        let result = SvTuneParams::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvTuneParams"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvTuneParams, Error> {
+        Ok(SvTuneParams {
+            ground_control_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.ground_control_speed"))?),
+            ground_control_accel: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.ground_control_accel"))?),
+            ground_friction: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.ground_friction"))?),
+            ground_jump_impulse: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.ground_jump_impulse"))?),
+            air_jump_impulse: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.air_jump_impulse"))?),
+            air_control_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.air_control_speed"))?),
+            air_control_accel: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.air_control_accel"))?),
+            air_friction: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.air_friction"))?),
+            hook_length: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.hook_length"))?),
+            hook_fire_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.hook_fire_speed"))?),
+            hook_drag_accel: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.hook_drag_accel"))?),
+            hook_drag_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.hook_drag_speed"))?),
+            gravity: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.gravity"))?),
+            velramp_start: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.velramp_start"))?),
+            velramp_range: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.velramp_range"))?),
+            velramp_curvature: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.velramp_curvature"))?),
+            gun_curvature: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.gun_curvature"))?),
+            gun_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.gun_speed"))?),
+            gun_lifetime: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.gun_lifetime"))?),
+            shotgun_curvature: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.shotgun_curvature"))?),
+            shotgun_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.shotgun_speed"))?),
+            shotgun_speeddiff: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.shotgun_speeddiff"))?),
+            shotgun_lifetime: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.shotgun_lifetime"))?),
+            grenade_curvature: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.grenade_curvature"))?),
+            grenade_speed: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.grenade_speed"))?),
+            grenade_lifetime: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.grenade_lifetime"))?),
+            laser_reach: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.laser_reach"))?),
+            laser_bounce_delay: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.laser_bounce_delay"))?),
+            laser_bounce_num: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.laser_bounce_num"))?),
+            laser_bounce_cost: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.laser_bounce_cost"))?),
+            player_collision: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.player_collision"))?),
+            player_hooking: TuneParam(_p.read_int(&mut _p.locate(warn, "SvTuneParams.player_hooking"))?),
+        })
+    }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(ground_control_speed: TuneParam, ground_control_accel: TuneParam, ground_friction: TuneParam, ground_jump_impulse: TuneParam, air_jump_impulse: TuneParam, air_control_speed: TuneParam, air_control_accel: TuneParam, air_friction: TuneParam, hook_length: TuneParam, hook_fire_speed: TuneParam, hook_drag_accel: TuneParam, hook_drag_speed: TuneParam, gravity: TuneParam, velramp_start: TuneParam, velramp_range: TuneParam, velramp_curvature: TuneParam, gun_curvature: TuneParam, gun_speed: TuneParam, gun_lifetime: TuneParam, shotgun_curvature: TuneParam, shotgun_speed: TuneParam, shotgun_speeddiff: TuneParam, shotgun_lifetime: TuneParam, grenade_curvature: TuneParam, grenade_speed: TuneParam, grenade_lifetime: TuneParam, laser_reach: TuneParam, laser_bounce_delay: TuneParam, laser_bounce_num: TuneParam, laser_bounce_cost: TuneParam, player_collision: TuneParam, player_hooking: TuneParam) -> Result<SvTuneParams, Error> {
+        let result = SvTuneParams {
+            ground_control_speed,
+            ground_control_accel,
+            ground_friction,
+            ground_jump_impulse,
+            air_jump_impulse,
+            air_control_speed,
+            air_control_accel,
+            air_friction,
+            hook_length,
+            hook_fire_speed,
+            hook_drag_accel,
+            hook_drag_speed,
+            gravity,
+            velramp_start,
+            velramp_range,
+            velramp_curvature,
+            gun_curvature,
+            gun_speed,
+            gun_lifetime,
+            shotgun_curvature,
+            shotgun_speed,
+            shotgun_speeddiff,
+            shotgun_lifetime,
+            grenade_curvature,
+            grenade_speed,
+            grenade_lifetime,
+            laser_reach,
+            laser_bounce_delay,
+            laser_bounce_num,
+            laser_bounce_cost,
+            player_collision,
+            player_hooking,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.ground_control_speed.0)?;
         _p.write_int(self.ground_control_accel.0)?;
         _p.write_int(self.ground_friction.0)?;
@@ -1068,13 +1658,28 @@ impl fmt::Debug for SvTuneParams {
 
 impl SvExtraProjectile {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvExtraProjectile, Error> {
-        let result = Ok(SvExtraProjectile {
-            projectile: ::snap_obj::Projectile::decode_msg(warn, _p)?,
-        });
-        _p.finish(warn);
-        result
+        SvExtraProjectile::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvExtraProjectile, Error> {
+        let result = SvExtraProjectile::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvExtraProjectile"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvExtraProjectile, Error> {
+        Ok(SvExtraProjectile {
+            projectile: ::snap_obj::Projectile::decode_msg_located(warn, _p)?,
+        })
+    }
+    pub fn new(projectile: ::snap_obj::Projectile) -> Result<SvExtraProjectile, Error> {
+        let result = SvExtraProjectile {
+            projectile,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         with_packer(&mut _p, |p| self.projectile.encode_msg(p))?;
         Ok(_p.written())
     }
@@ -1089,11 +1694,15 @@ impl fmt::Debug for SvExtraProjectile {
 
 impl SvReadyToEnter {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvReadyToEnter, Error> {
-        let result = Ok(SvReadyToEnter);
-        _p.finish(warn);
-        result
+        SvReadyToEnter::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvReadyToEnter, Error> {
+        error::finish(&mut _p.locate(warn, "SvReadyToEnter"), _p, Ok(SvReadyToEnter))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -1106,13 +1715,29 @@ impl fmt::Debug for SvReadyToEnter {
 
 impl SvWeaponPickup {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvWeaponPickup, Error> {
-        let result = Ok(SvWeaponPickup {
-            weapon: enums::Weapon::from_i32(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
+        SvWeaponPickup::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvWeaponPickup, Error> {
+        let result = SvWeaponPickup::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvWeaponPickup"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvWeaponPickup, Error> {
+        Ok(SvWeaponPickup {
+            weapon: enums::Weapon::from_i32(_p.read_int(&mut _p.locate(warn, "SvWeaponPickup.weapon"))?),
+        })
+    }
+    pub fn new(weapon: enums::Weapon) -> Result<SvWeaponPickup, Error> {
+        let result = SvWeaponPickup {
+            weapon,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        self.weapon.known()?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.weapon.to_i32())?;
         Ok(_p.written())
     }
@@ -1127,15 +1752,33 @@ impl fmt::Debug for SvWeaponPickup {
 
 impl SvEmoticon {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvEmoticon, Error> {
-        let result = Ok(SvEmoticon {
-            client_id: in_range(_p.read_int(warn)?, 0, 63)?,
-            emoticon: enums::Emoticon::from_i32(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.client_id && self.client_id <= 63);
+        SvEmoticon::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvEmoticon, Error> {
+        let result = SvEmoticon::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvEmoticon"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvEmoticon, Error> {
+        Ok(SvEmoticon {
+            client_id: in_range(_p.read_int(&mut _p.locate(warn, "SvEmoticon.client_id"))?, 0, 63)?,
+            emoticon: enums::Emoticon::from_i32(_p.read_int(&mut _p.locate(warn, "SvEmoticon.emoticon"))?),
+        })
+    }
+    pub fn new(client_id: i32, emoticon: enums::Emoticon) -> Result<SvEmoticon, Error> {
+        let result = SvEmoticon {
+            client_id,
+            emoticon,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.client_id, 0, 63)?;
+        self.emoticon.known()?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.client_id, 0, 63)?;
         _p.write_int(self.client_id)?;
         _p.write_int(self.emoticon.to_i32())?;
         Ok(_p.written())
@@ -1152,11 +1795,15 @@ impl fmt::Debug for SvEmoticon {
 
 impl SvVoteClearOptions {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteClearOptions, Error> {
-        let result = Ok(SvVoteClearOptions);
-        _p.finish(warn);
-        result
+        SvVoteClearOptions::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteClearOptions, Error> {
+        error::finish(&mut _p.locate(warn, "SvVoteClearOptions"), _p, Ok(SvVoteClearOptions))
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -1169,11 +1816,15 @@ impl fmt::Debug for SvVoteClearOptions {
 
 impl SvVoteOptionListAdd {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteOptionListAdd, Error> {
-        let result = Ok(SvVoteOptionListAdd);
-        _p.finish(warn);
-        result
+        SvVoteOptionListAdd::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteOptionListAdd, Error> {
+        error::finish(&mut _p.locate(warn, "SvVoteOptionListAdd"), _p, Ok(SvVoteOptionListAdd))
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -1186,14 +1837,30 @@ impl fmt::Debug for SvVoteOptionListAdd {
 
 impl<'a> SvVoteOptionAdd<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteOptionAdd<'a>, Error> {
-        let result = Ok(SvVoteOptionAdd {
-            description: sanitize(warn, _p.read_string()?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.description).unwrap();
+        SvVoteOptionAdd::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteOptionAdd<'a>, Error> {
+        let result = SvVoteOptionAdd::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvVoteOptionAdd"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteOptionAdd<'a>, Error> {
+        Ok(SvVoteOptionAdd {
+            description: sanitize(&mut _p.locate(warn, "SvVoteOptionAdd.description"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(description: &'a [u8]) -> Result<SvVoteOptionAdd<'a>, Error> {
+        let result = SvVoteOptionAdd {
+            description,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.description)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.description)?;
         _p.write_string(self.description)?;
         Ok(_p.written())
     }
@@ -1208,14 +1875,30 @@ impl<'a> fmt::Debug for SvVoteOptionAdd<'a> {
 
 impl<'a> SvVoteOptionRemove<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteOptionRemove<'a>, Error> {
-        let result = Ok(SvVoteOptionRemove {
-            description: sanitize(warn, _p.read_string()?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.description).unwrap();
+        SvVoteOptionRemove::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteOptionRemove<'a>, Error> {
+        let result = SvVoteOptionRemove::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvVoteOptionRemove"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteOptionRemove<'a>, Error> {
+        Ok(SvVoteOptionRemove {
+            description: sanitize(&mut _p.locate(warn, "SvVoteOptionRemove.description"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(description: &'a [u8]) -> Result<SvVoteOptionRemove<'a>, Error> {
+        let result = SvVoteOptionRemove {
+            description,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.description)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.description)?;
         _p.write_string(self.description)?;
         Ok(_p.written())
     }
@@ -1230,21 +1913,45 @@ impl<'a> fmt::Debug for SvVoteOptionRemove<'a> {
 
 impl<'a> SvVoteSet<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteSet<'a>, Error> {
-        let result = Ok(SvVoteSet {
-            client_id: in_range(_p.read_int(warn)?, -1, 63)?,
-            type_: enums::Vote::from_i32(_p.read_int(warn)?)?,
-            timeout: in_range(_p.read_int(warn)?, 0, 60)?,
-            description: sanitize(warn, _p.read_string()?)?,
-            reason: sanitize(warn, _p.read_string()?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(-1 <= self.client_id && self.client_id <= 63);
-        assert!(0 <= self.timeout && self.timeout <= 60);
-        sanitize(&mut Panic, self.description).unwrap();
-        sanitize(&mut Panic, self.reason).unwrap();
+        SvVoteSet::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteSet<'a>, Error> {
+        let result = SvVoteSet::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvVoteSet"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvVoteSet<'a>, Error> {
+        Ok(SvVoteSet {
+            client_id: in_range(_p.read_int(&mut _p.locate(warn, "SvVoteSet.client_id"))?, -1, 63)?,
+            type_: enums::Vote::from_i32(_p.read_int(&mut _p.locate(warn, "SvVoteSet.type_"))?),
+            timeout: in_range(_p.read_int(&mut _p.locate(warn, "SvVoteSet.timeout"))?, 0, 60)?,
+            description: sanitize(&mut _p.locate(warn, "SvVoteSet.description"), _p.read_string()?)?,
+            reason: sanitize(&mut _p.locate(warn, "SvVoteSet.reason"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(client_id: i32, type_: enums::Vote, timeout: i32, description: &'a [u8], reason: &'a [u8]) -> Result<SvVoteSet<'a>, Error> {
+        let result = SvVoteSet {
+            client_id,
+            type_,
+            timeout,
+            description,
+            reason,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.client_id, -1, 63)?;
+        self.type_.known()?;
+        in_range(self.timeout, 0, 60)?;
+        sanitize(&mut Ignore, self.description)?;
+        sanitize(&mut Ignore, self.reason)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.client_id, -1, 63)?;
+        in_range(self.timeout, 0, 60)?;
+        sanitize(&mut Ignore, self.description)?;
+        sanitize(&mut Ignore, self.reason)?;
         _p.write_int(self.client_id)?;
         _p.write_int(self.type_.to_i32())?;
         _p.write_int(self.timeout)?;
@@ -1267,20 +1974,42 @@ impl<'a> fmt::Debug for SvVoteSet<'a> {
 
 impl SvVoteStatus {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteStatus, Error> {
-        let result = Ok(SvVoteStatus {
-            yes: in_range(_p.read_int(warn)?, 0, 64)?,
-            no: in_range(_p.read_int(warn)?, 0, 64)?,
-            pass: in_range(_p.read_int(warn)?, 0, 64)?,
-            total: in_range(_p.read_int(warn)?, 0, 64)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.yes && self.yes <= 64);
-        assert!(0 <= self.no && self.no <= 64);
-        assert!(0 <= self.pass && self.pass <= 64);
-        assert!(0 <= self.total && self.total <= 64);
+        SvVoteStatus::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteStatus, Error> {
+        let result = SvVoteStatus::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvVoteStatus"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvVoteStatus, Error> {
+        Ok(SvVoteStatus {
+            yes: in_range(_p.read_int(&mut _p.locate(warn, "SvVoteStatus.yes"))?, 0, 64)?,
+            no: in_range(_p.read_int(&mut _p.locate(warn, "SvVoteStatus.no"))?, 0, 64)?,
+            pass: in_range(_p.read_int(&mut _p.locate(warn, "SvVoteStatus.pass"))?, 0, 64)?,
+            total: in_range(_p.read_int(&mut _p.locate(warn, "SvVoteStatus.total"))?, 0, 64)?,
+        })
+    }
+    pub fn new(yes: i32, no: i32, pass: i32, total: i32) -> Result<SvVoteStatus, Error> {
+        let result = SvVoteStatus {
+            yes,
+            no,
+            pass,
+            total,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.yes, 0, 64)?;
+        in_range(self.no, 0, 64)?;
+        in_range(self.pass, 0, 64)?;
+        in_range(self.total, 0, 64)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.yes, 0, 64)?;
+        in_range(self.no, 0, 64)?;
+        in_range(self.pass, 0, 64)?;
+        in_range(self.total, 0, 64)?;
         _p.write_int(self.yes)?;
         _p.write_int(self.no)?;
         _p.write_int(self.pass)?;
@@ -1301,20 +2030,42 @@ impl fmt::Debug for SvVoteStatus {
 
 impl SvServerSettings {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvServerSettings, Error> {
-        let result = Ok(SvServerSettings {
-            kick_vote: to_bool(_p.read_int(warn)?)?,
-            kick_min: in_range(_p.read_int(warn)?, 0, 64)?,
-            spec_vote: to_bool(_p.read_int(warn)?)?,
-            team_lock: to_bool(_p.read_int(warn)?)?,
-            team_balance: to_bool(_p.read_int(warn)?)?,
-            player_slots: in_range(_p.read_int(warn)?, 0, 64)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.kick_min && self.kick_min <= 64);
-        assert!(0 <= self.player_slots && self.player_slots <= 64);
+        SvServerSettings::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvServerSettings, Error> {
+        let result = SvServerSettings::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvServerSettings"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvServerSettings, Error> {
+        Ok(SvServerSettings {
+            kick_vote: to_bool(_p.read_int(&mut _p.locate(warn, "SvServerSettings.kick_vote"))?)?,
+            kick_min: in_range(_p.read_int(&mut _p.locate(warn, "SvServerSettings.kick_min"))?, 0, 64)?,
+            spec_vote: to_bool(_p.read_int(&mut _p.locate(warn, "SvServerSettings.spec_vote"))?)?,
+            team_lock: to_bool(_p.read_int(&mut _p.locate(warn, "SvServerSettings.team_lock"))?)?,
+            team_balance: to_bool(_p.read_int(&mut _p.locate(warn, "SvServerSettings.team_balance"))?)?,
+            player_slots: in_range(_p.read_int(&mut _p.locate(warn, "SvServerSettings.player_slots"))?, 0, 64)?,
+        })
+    }
+    pub fn new(kick_vote: bool, kick_min: i32, spec_vote: bool, team_lock: bool, team_balance: bool, player_slots: i32) -> Result<SvServerSettings, Error> {
+        let result = SvServerSettings {
+            kick_vote,
+            kick_min,
+            spec_vote,
+            team_lock,
+            team_balance,
+            player_slots,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.kick_min, 0, 64)?;
+        in_range(self.player_slots, 0, 64)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.kick_min, 0, 64)?;
+        in_range(self.player_slots, 0, 64)?;
         _p.write_int(self.kick_vote as i32)?;
         _p.write_int(self.kick_min)?;
         _p.write_int(self.spec_vote as i32)?;
@@ -1339,48 +2090,80 @@ impl fmt::Debug for SvServerSettings {
 
 impl<'a> SvClientInfo<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvClientInfo<'a>, Error> {
-        let result = Ok(SvClientInfo {
-            client_id: in_range(_p.read_int(warn)?, 0, 63)?,
-            local: to_bool(_p.read_int(warn)?)?,
-            team: enums::Team::from_i32(_p.read_int(warn)?)?,
-            name: sanitize(warn, _p.read_string()?)?,
-            clan: sanitize(warn, _p.read_string()?)?,
-            country: _p.read_int(warn)?,
+        SvClientInfo::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvClientInfo<'a>, Error> {
+        let result = SvClientInfo::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvClientInfo"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvClientInfo<'a>, Error> {
+        Ok(SvClientInfo {
+            client_id: in_range(_p.read_int(&mut _p.locate(warn, "SvClientInfo.client_id"))?, 0, 63)?,
+            local: to_bool(_p.read_int(&mut _p.locate(warn, "SvClientInfo.local"))?)?,
+            team: enums::Team::from_i32(_p.read_int(&mut _p.locate(warn, "SvClientInfo.team"))?),
+            name: sanitize(&mut _p.locate(warn, "SvClientInfo.name"), _p.read_string()?)?,
+            clan: sanitize(&mut _p.locate(warn, "SvClientInfo.clan"), _p.read_string()?)?,
+            country: _p.read_int(&mut _p.locate(warn, "SvClientInfo.country"))?,
             skin_part_names: [
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvClientInfo.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvClientInfo.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvClientInfo.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvClientInfo.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvClientInfo.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvClientInfo.skin_part_names"), _p.read_string()?)?,
             ],
             use_custom_colors: [
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "SvClientInfo.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "SvClientInfo.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "SvClientInfo.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "SvClientInfo.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "SvClientInfo.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "SvClientInfo.use_custom_colors"))?)?,
             ],
             skin_part_colors: [
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
+                _p.read_int(&mut _p.locate(warn, "SvClientInfo.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "SvClientInfo.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "SvClientInfo.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "SvClientInfo.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "SvClientInfo.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "SvClientInfo.skin_part_colors"))?,
             ],
-            silent: to_bool(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.client_id && self.client_id <= 63);
-        sanitize(&mut Panic, self.name).unwrap();
-        sanitize(&mut Panic, self.clan).unwrap();
+            silent: to_bool(_p.read_int(&mut _p.locate(warn, "SvClientInfo.silent"))?)?,
+        })
+    }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(client_id: i32, local: bool, team: enums::Team, name: &'a [u8], clan: &'a [u8], country: i32, skin_part_names: [&'a [u8]; 6], use_custom_colors: [bool; 6], skin_part_colors: [i32; 6], silent: bool) -> Result<SvClientInfo<'a>, Error> {
+        let result = SvClientInfo {
+            client_id,
+            local,
+            team,
+            name,
+            clan,
+            country,
+            skin_part_names,
+            use_custom_colors,
+            skin_part_colors,
+            silent,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.client_id, 0, 63)?;
+        self.team.known()?;
+        sanitize(&mut Ignore, max_length(self.name, 15)?)?;
+        sanitize(&mut Ignore, max_length(self.clan, 11)?)?;
+        for &e in &self.skin_part_names {
+            sanitize(&mut Ignore, max_length(e, 23)?)?;
+        }
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.client_id, 0, 63)?;
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.clan)?;
         for &e in &self.skin_part_names {
-            sanitize(&mut Panic, e).unwrap();
+            sanitize(&mut Ignore, e)?;
         }
         _p.write_int(self.client_id)?;
         _p.write_int(self.local as i32)?;
@@ -1420,21 +2203,44 @@ impl<'a> fmt::Debug for SvClientInfo<'a> {
 
 impl SvGameInfo {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvGameInfo, Error> {
-        let result = Ok(SvGameInfo {
-            game_flags: _p.read_int(warn)?,
-            score_limit: positive(_p.read_int(warn)?)?,
-            time_limit: positive(_p.read_int(warn)?)?,
-            match_num: positive(_p.read_int(warn)?)?,
-            match_current: positive(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(self.score_limit >= 0);
-        assert!(self.time_limit >= 0);
-        assert!(self.match_num >= 0);
-        assert!(self.match_current >= 0);
+        SvGameInfo::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvGameInfo, Error> {
+        let result = SvGameInfo::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvGameInfo"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvGameInfo, Error> {
+        Ok(SvGameInfo {
+            game_flags: _p.read_int(&mut _p.locate(warn, "SvGameInfo.game_flags"))?,
+            score_limit: positive(_p.read_int(&mut _p.locate(warn, "SvGameInfo.score_limit"))?)?,
+            time_limit: positive(_p.read_int(&mut _p.locate(warn, "SvGameInfo.time_limit"))?)?,
+            match_num: positive(_p.read_int(&mut _p.locate(warn, "SvGameInfo.match_num"))?)?,
+            match_current: positive(_p.read_int(&mut _p.locate(warn, "SvGameInfo.match_current"))?)?,
+        })
+    }
+    pub fn new(game_flags: i32, score_limit: i32, time_limit: i32, match_num: i32, match_current: i32) -> Result<SvGameInfo, Error> {
+        let result = SvGameInfo {
+            game_flags,
+            score_limit,
+            time_limit,
+            match_num,
+            match_current,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        positive(self.score_limit)?;
+        positive(self.time_limit)?;
+        positive(self.match_num)?;
+        positive(self.match_current)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        positive(self.score_limit)?;
+        positive(self.time_limit)?;
+        positive(self.match_num)?;
+        positive(self.match_current)?;
         _p.write_int(self.game_flags)?;
         _p.write_int(self.score_limit)?;
         _p.write_int(self.time_limit)?;
@@ -1457,17 +2263,36 @@ impl fmt::Debug for SvGameInfo {
 
 impl<'a> SvClientDrop<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvClientDrop<'a>, Error> {
-        let result = Ok(SvClientDrop {
-            client_id: in_range(_p.read_int(warn)?, 0, 63)?,
-            reason: sanitize(warn, _p.read_string()?)?,
-            silent: to_bool(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.client_id && self.client_id <= 63);
-        sanitize(&mut Panic, self.reason).unwrap();
+        SvClientDrop::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvClientDrop<'a>, Error> {
+        let result = SvClientDrop::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvClientDrop"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvClientDrop<'a>, Error> {
+        Ok(SvClientDrop {
+            client_id: in_range(_p.read_int(&mut _p.locate(warn, "SvClientDrop.client_id"))?, 0, 63)?,
+            reason: sanitize(&mut _p.locate(warn, "SvClientDrop.reason"), _p.read_string()?)?,
+            silent: to_bool(_p.read_int(&mut _p.locate(warn, "SvClientDrop.silent"))?)?,
+        })
+    }
+    pub fn new(client_id: i32, reason: &'a [u8], silent: bool) -> Result<SvClientDrop<'a>, Error> {
+        let result = SvClientDrop {
+            client_id,
+            reason,
+            silent,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.client_id, 0, 63)?;
+        sanitize(&mut Ignore, self.reason)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.client_id, 0, 63)?;
+        sanitize(&mut Ignore, self.reason)?;
         _p.write_int(self.client_id)?;
         _p.write_string(self.reason)?;
         _p.write_int(self.silent as i32)?;
@@ -1486,11 +2311,15 @@ impl<'a> fmt::Debug for SvClientDrop<'a> {
 
 impl SvGameMsg {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvGameMsg, Error> {
-        let result = Ok(SvGameMsg);
-        _p.finish(warn);
-        result
+        SvGameMsg::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvGameMsg, Error> {
+        error::finish(&mut _p.locate(warn, "SvGameMsg"), _p, Ok(SvGameMsg))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -1503,17 +2332,37 @@ impl fmt::Debug for SvGameMsg {
 
 impl<'a> DeClientEnter<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<DeClientEnter<'a>, Error> {
-        let result = Ok(DeClientEnter {
-            name: sanitize(warn, _p.read_string()?)?,
-            client_id: in_range(_p.read_int(warn)?, -1, 63)?,
-            team: enums::Team::from_i32(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.name).unwrap();
-        assert!(-1 <= self.client_id && self.client_id <= 63);
+        DeClientEnter::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<DeClientEnter<'a>, Error> {
+        let result = DeClientEnter::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "DeClientEnter"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<DeClientEnter<'a>, Error> {
+        Ok(DeClientEnter {
+            name: sanitize(&mut _p.locate(warn, "DeClientEnter.name"), _p.read_string()?)?,
+            client_id: in_range(_p.read_int(&mut _p.locate(warn, "DeClientEnter.client_id"))?, -1, 63)?,
+            team: enums::Team::from_i32(_p.read_int(&mut _p.locate(warn, "DeClientEnter.team"))?),
+        })
+    }
+    pub fn new(name: &'a [u8], client_id: i32, team: enums::Team) -> Result<DeClientEnter<'a>, Error> {
+        let result = DeClientEnter {
+            name,
+            client_id,
+            team,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.name)?;
+        in_range(self.client_id, -1, 63)?;
+        self.team.known()?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.name)?;
+        in_range(self.client_id, -1, 63)?;
         _p.write_string(self.name)?;
         _p.write_int(self.client_id)?;
         _p.write_int(self.team.to_i32())?;
@@ -1532,18 +2381,38 @@ impl<'a> fmt::Debug for DeClientEnter<'a> {
 
 impl<'a> DeClientLeave<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<DeClientLeave<'a>, Error> {
-        let result = Ok(DeClientLeave {
-            name: sanitize(warn, _p.read_string()?)?,
-            client_id: in_range(_p.read_int(warn)?, -1, 63)?,
-            reason: sanitize(warn, _p.read_string()?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.name).unwrap();
-        assert!(-1 <= self.client_id && self.client_id <= 63);
-        sanitize(&mut Panic, self.reason).unwrap();
+        DeClientLeave::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<DeClientLeave<'a>, Error> {
+        let result = DeClientLeave::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "DeClientLeave"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<DeClientLeave<'a>, Error> {
+        Ok(DeClientLeave {
+            name: sanitize(&mut _p.locate(warn, "DeClientLeave.name"), _p.read_string()?)?,
+            client_id: in_range(_p.read_int(&mut _p.locate(warn, "DeClientLeave.client_id"))?, -1, 63)?,
+            reason: sanitize(&mut _p.locate(warn, "DeClientLeave.reason"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(name: &'a [u8], client_id: i32, reason: &'a [u8]) -> Result<DeClientLeave<'a>, Error> {
+        let result = DeClientLeave {
+            name,
+            client_id,
+            reason,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.name)?;
+        in_range(self.client_id, -1, 63)?;
+        sanitize(&mut Ignore, self.reason)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.name)?;
+        in_range(self.client_id, -1, 63)?;
+        sanitize(&mut Ignore, self.reason)?;
         _p.write_string(self.name)?;
         _p.write_int(self.client_id)?;
         _p.write_string(self.reason)?;
@@ -1562,17 +2431,37 @@ impl<'a> fmt::Debug for DeClientLeave<'a> {
 
 impl<'a> ClSay<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClSay<'a>, Error> {
-        let result = Ok(ClSay {
-            mode: enums::Chat::from_i32(_p.read_int(warn)?)?,
-            target: in_range(_p.read_int(warn)?, -1, 63)?,
-            message: sanitize(warn, _p.read_string()?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(-1 <= self.target && self.target <= 63);
-        sanitize(&mut Panic, self.message).unwrap();
+        ClSay::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClSay<'a>, Error> {
+        let result = ClSay::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClSay"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClSay<'a>, Error> {
+        Ok(ClSay {
+            mode: enums::Chat::from_i32(_p.read_int(&mut _p.locate(warn, "ClSay.mode"))?),
+            target: in_range(_p.read_int(&mut _p.locate(warn, "ClSay.target"))?, -1, 63)?,
+            message: sanitize(&mut _p.locate(warn, "ClSay.message"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(mode: enums::Chat, target: i32, message: &'a [u8]) -> Result<ClSay<'a>, Error> {
+        let result = ClSay {
+            mode,
+            target,
+            message,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.mode.known()?;
+        in_range(self.target, -1, 63)?;
+        sanitize(&mut Ignore, self.message)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.target, -1, 63)?;
+        sanitize(&mut Ignore, self.message)?;
         _p.write_int(self.mode.to_i32())?;
         _p.write_int(self.target)?;
         _p.write_string(self.message)?;
@@ -1591,13 +2480,29 @@ impl<'a> fmt::Debug for ClSay<'a> {
 
 impl ClSetTeam {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClSetTeam, Error> {
-        let result = Ok(ClSetTeam {
-            team: enums::Team::from_i32(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
+        ClSetTeam::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClSetTeam, Error> {
+        let result = ClSetTeam::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClSetTeam"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClSetTeam, Error> {
+        Ok(ClSetTeam {
+            team: enums::Team::from_i32(_p.read_int(&mut _p.locate(warn, "ClSetTeam.team"))?),
+        })
+    }
+    pub fn new(team: enums::Team) -> Result<ClSetTeam, Error> {
+        let result = ClSetTeam {
+            team,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        self.team.known()?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.team.to_i32())?;
         Ok(_p.written())
     }
@@ -1612,15 +2517,33 @@ impl fmt::Debug for ClSetTeam {
 
 impl ClSetSpectatorMode {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClSetSpectatorMode, Error> {
-        let result = Ok(ClSetSpectatorMode {
-            spec_mode: enums::Spec::from_i32(_p.read_int(warn)?)?,
-            spectator_id: in_range(_p.read_int(warn)?, -1, 63)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(-1 <= self.spectator_id && self.spectator_id <= 63);
+        ClSetSpectatorMode::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClSetSpectatorMode, Error> {
+        let result = ClSetSpectatorMode::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClSetSpectatorMode"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClSetSpectatorMode, Error> {
+        Ok(ClSetSpectatorMode {
+            spec_mode: enums::Spec::from_i32(_p.read_int(&mut _p.locate(warn, "ClSetSpectatorMode.spec_mode"))?),
+            spectator_id: in_range(_p.read_int(&mut _p.locate(warn, "ClSetSpectatorMode.spectator_id"))?, -1, 63)?,
+        })
+    }
+    pub fn new(spec_mode: enums::Spec, spectator_id: i32) -> Result<ClSetSpectatorMode, Error> {
+        let result = ClSetSpectatorMode {
+            spec_mode,
+            spectator_id,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.spec_mode.known()?;
+        in_range(self.spectator_id, -1, 63)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.spectator_id, -1, 63)?;
         _p.write_int(self.spec_mode.to_i32())?;
         _p.write_int(self.spectator_id)?;
         Ok(_p.written())
@@ -1637,43 +2560,68 @@ impl fmt::Debug for ClSetSpectatorMode {
 
 impl<'a> ClStartInfo<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClStartInfo<'a>, Error> {
-        let result = Ok(ClStartInfo {
-            name: sanitize(warn, _p.read_string()?)?,
-            clan: sanitize(warn, _p.read_string()?)?,
-            country: _p.read_int(warn)?,
+        ClStartInfo::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClStartInfo<'a>, Error> {
+        let result = ClStartInfo::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClStartInfo"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClStartInfo<'a>, Error> {
+        Ok(ClStartInfo {
+            name: sanitize(&mut _p.locate(warn, "ClStartInfo.name"), _p.read_string()?)?,
+            clan: sanitize(&mut _p.locate(warn, "ClStartInfo.clan"), _p.read_string()?)?,
+            country: _p.read_int(&mut _p.locate(warn, "ClStartInfo.country"))?,
             skin_part_names: [
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "ClStartInfo.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "ClStartInfo.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "ClStartInfo.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "ClStartInfo.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "ClStartInfo.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "ClStartInfo.skin_part_names"), _p.read_string()?)?,
             ],
             use_custom_colors: [
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "ClStartInfo.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "ClStartInfo.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "ClStartInfo.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "ClStartInfo.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "ClStartInfo.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "ClStartInfo.use_custom_colors"))?)?,
             ],
             skin_part_colors: [
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
+                _p.read_int(&mut _p.locate(warn, "ClStartInfo.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "ClStartInfo.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "ClStartInfo.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "ClStartInfo.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "ClStartInfo.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "ClStartInfo.skin_part_colors"))?,
             ],
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(name: &'a [u8], clan: &'a [u8], country: i32, skin_part_names: [&'a [u8]; 6], use_custom_colors: [bool; 6], skin_part_colors: [i32; 6]) -> Result<ClStartInfo<'a>, Error> {
+        let result = ClStartInfo {
+            name,
+            clan,
+            country,
+            skin_part_names,
+            use_custom_colors,
+            skin_part_colors,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, max_length(self.name, 15)?)?;
+        sanitize(&mut Ignore, max_length(self.clan, 11)?)?;
+        for &e in &self.skin_part_names {
+            sanitize(&mut Ignore, max_length(e, 23)?)?;
+        }
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.name).unwrap();
-        sanitize(&mut Panic, self.clan).unwrap();
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.clan)?;
         for &e in &self.skin_part_names {
-            sanitize(&mut Panic, e).unwrap();
+            sanitize(&mut Ignore, e)?;
         }
         _p.write_string(self.name)?;
         _p.write_string(self.clan)?;
@@ -1705,11 +2653,15 @@ impl<'a> fmt::Debug for ClStartInfo<'a> {
 
 impl ClKill {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClKill, Error> {
-        let result = Ok(ClKill);
-        _p.finish(warn);
-        result
+        ClKill::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClKill, Error> {
+        error::finish(&mut _p.locate(warn, "ClKill"), _p, Ok(ClKill))
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -1722,11 +2674,15 @@ impl fmt::Debug for ClKill {
 
 impl ClReadyChange {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClReadyChange, Error> {
-        let result = Ok(ClReadyChange);
-        _p.finish(warn);
-        result
+        ClReadyChange::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClReadyChange, Error> {
+        error::finish(&mut _p.locate(warn, "ClReadyChange"), _p, Ok(ClReadyChange))
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -1739,13 +2695,29 @@ impl fmt::Debug for ClReadyChange {
 
 impl ClEmoticon {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClEmoticon, Error> {
-        let result = Ok(ClEmoticon {
-            emoticon: enums::Emoticon::from_i32(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
+        ClEmoticon::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClEmoticon, Error> {
+        let result = ClEmoticon::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClEmoticon"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClEmoticon, Error> {
+        Ok(ClEmoticon {
+            emoticon: enums::Emoticon::from_i32(_p.read_int(&mut _p.locate(warn, "ClEmoticon.emoticon"))?),
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(emoticon: enums::Emoticon) -> Result<ClEmoticon, Error> {
+        let result = ClEmoticon {
+            emoticon,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.emoticon.known()?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.emoticon.to_i32())?;
         Ok(_p.written())
     }
@@ -1760,14 +2732,30 @@ impl fmt::Debug for ClEmoticon {
 
 impl ClVote {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClVote, Error> {
-        let result = Ok(ClVote {
-            vote: in_range(_p.read_int(warn)?, -1, 1)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(-1 <= self.vote && self.vote <= 1);
+        ClVote::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClVote, Error> {
+        let result = ClVote::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClVote"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ClVote, Error> {
+        Ok(ClVote {
+            vote: in_range(_p.read_int(&mut _p.locate(warn, "ClVote.vote"))?, -1, 1)?,
+        })
+    }
+    pub fn new(vote: i32) -> Result<ClVote, Error> {
+        let result = ClVote {
+            vote,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.vote, -1, 1)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.vote, -1, 1)?;
         _p.write_int(self.vote)?;
         Ok(_p.written())
     }
@@ -1782,19 +2770,40 @@ impl fmt::Debug for ClVote {
 
 impl<'a> ClCallVote<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClCallVote<'a>, Error> {
-        let result = Ok(ClCallVote {
-            type_: sanitize(warn, _p.read_string()?)?,
-            value: sanitize(warn, _p.read_string()?)?,
-            reason: sanitize(warn, _p.read_string()?)?,
-            force: to_bool(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.type_).unwrap();
-        sanitize(&mut Panic, self.value).unwrap();
-        sanitize(&mut Panic, self.reason).unwrap();
+        ClCallVote::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClCallVote<'a>, Error> {
+        let result = ClCallVote::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClCallVote"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClCallVote<'a>, Error> {
+        Ok(ClCallVote {
+            type_: sanitize(&mut _p.locate(warn, "ClCallVote.type_"), _p.read_string()?)?,
+            value: sanitize(&mut _p.locate(warn, "ClCallVote.value"), _p.read_string()?)?,
+            reason: sanitize(&mut _p.locate(warn, "ClCallVote.reason"), _p.read_string()?)?,
+            force: to_bool(_p.read_int(&mut _p.locate(warn, "ClCallVote.force"))?)?,
+        })
+    }
+    pub fn new(type_: &'a [u8], value: &'a [u8], reason: &'a [u8], force: bool) -> Result<ClCallVote<'a>, Error> {
+        let result = ClCallVote {
+            type_,
+            value,
+            reason,
+            force,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.type_)?;
+        sanitize(&mut Ignore, self.value)?;
+        sanitize(&mut Ignore, self.reason)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.type_)?;
+        sanitize(&mut Ignore, self.value)?;
+        sanitize(&mut Ignore, self.reason)?;
         _p.write_string(self.type_)?;
         _p.write_string(self.value)?;
         _p.write_string(self.reason)?;
@@ -1815,40 +2824,62 @@ impl<'a> fmt::Debug for ClCallVote<'a> {
 
 impl<'a> SvSkinChange<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvSkinChange<'a>, Error> {
-        let result = Ok(SvSkinChange {
-            client_id: in_range(_p.read_int(warn)?, 0, 63)?,
+        SvSkinChange::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvSkinChange<'a>, Error> {
+        let result = SvSkinChange::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvSkinChange"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvSkinChange<'a>, Error> {
+        Ok(SvSkinChange {
+            client_id: in_range(_p.read_int(&mut _p.locate(warn, "SvSkinChange.client_id"))?, 0, 63)?,
             skin_part_names: [
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvSkinChange.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvSkinChange.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvSkinChange.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvSkinChange.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvSkinChange.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "SvSkinChange.skin_part_names"), _p.read_string()?)?,
             ],
             use_custom_colors: [
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "SvSkinChange.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "SvSkinChange.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "SvSkinChange.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "SvSkinChange.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "SvSkinChange.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "SvSkinChange.use_custom_colors"))?)?,
             ],
             skin_part_colors: [
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
+                _p.read_int(&mut _p.locate(warn, "SvSkinChange.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "SvSkinChange.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "SvSkinChange.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "SvSkinChange.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "SvSkinChange.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "SvSkinChange.skin_part_colors"))?,
             ],
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(client_id: i32, skin_part_names: [&'a [u8]; 6], use_custom_colors: [bool; 6], skin_part_colors: [i32; 6]) -> Result<SvSkinChange<'a>, Error> {
+        let result = SvSkinChange {
+            client_id,
+            skin_part_names,
+            use_custom_colors,
+            skin_part_colors,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.client_id, 0, 63)?;
+        for &e in &self.skin_part_names {
+            sanitize(&mut Ignore, max_length(e, 23)?)?;
+        }
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.client_id && self.client_id <= 63);
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.client_id, 0, 63)?;
         for &e in &self.skin_part_names {
-            sanitize(&mut Panic, e).unwrap();
+            sanitize(&mut Ignore, e)?;
         }
         _p.write_int(self.client_id)?;
         for &e in &self.skin_part_names {
@@ -1876,38 +2907,58 @@ impl<'a> fmt::Debug for SvSkinChange<'a> {
 
 impl<'a> ClSkinChange<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClSkinChange<'a>, Error> {
-        let result = Ok(ClSkinChange {
+        ClSkinChange::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClSkinChange<'a>, Error> {
+        let result = ClSkinChange::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClSkinChange"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClSkinChange<'a>, Error> {
+        Ok(ClSkinChange {
             skin_part_names: [
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
-                sanitize(warn, _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "ClSkinChange.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "ClSkinChange.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "ClSkinChange.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "ClSkinChange.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "ClSkinChange.skin_part_names"), _p.read_string()?)?,
+                sanitize(&mut _p.locate(warn, "ClSkinChange.skin_part_names"), _p.read_string()?)?,
             ],
             use_custom_colors: [
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
-                to_bool(_p.read_int(warn)?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "ClSkinChange.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "ClSkinChange.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "ClSkinChange.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "ClSkinChange.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "ClSkinChange.use_custom_colors"))?)?,
+                to_bool(_p.read_int(&mut _p.locate(warn, "ClSkinChange.use_custom_colors"))?)?,
             ],
             skin_part_colors: [
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
-                _p.read_int(warn)?,
+                _p.read_int(&mut _p.locate(warn, "ClSkinChange.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "ClSkinChange.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "ClSkinChange.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "ClSkinChange.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "ClSkinChange.skin_part_colors"))?,
+                _p.read_int(&mut _p.locate(warn, "ClSkinChange.skin_part_colors"))?,
             ],
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(skin_part_names: [&'a [u8]; 6], use_custom_colors: [bool; 6], skin_part_colors: [i32; 6]) -> Result<ClSkinChange<'a>, Error> {
+        let result = ClSkinChange {
+            skin_part_names,
+            use_custom_colors,
+            skin_part_colors,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
         for &e in &self.skin_part_names {
-            sanitize(&mut Panic, e).unwrap();
+            sanitize(&mut Ignore, max_length(e, 23)?)?;
+        }
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        for &e in &self.skin_part_names {
+            sanitize(&mut Ignore, e)?;
         }
         for &e in &self.skin_part_names {
             _p.write_string(e)?;
@@ -1933,19 +2984,40 @@ impl<'a> fmt::Debug for ClSkinChange<'a> {
 
 impl SvRaceFinish {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvRaceFinish, Error> {
-        let result = Ok(SvRaceFinish {
-            client_id: in_range(_p.read_int(warn)?, 0, 63)?,
-            time: at_least(_p.read_int(warn)?, -1)?,
-            diff: _p.read_int(warn)?,
-            record_personal: to_bool(_p.read_int(warn)?)?,
-            record_server: to_bool(_p.read_int(warn)?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(0 <= self.client_id && self.client_id <= 63);
-        assert!(self.time >= -1);
+        SvRaceFinish::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvRaceFinish, Error> {
+        let result = SvRaceFinish::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvRaceFinish"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvRaceFinish, Error> {
+        Ok(SvRaceFinish {
+            client_id: in_range(_p.read_int(&mut _p.locate(warn, "SvRaceFinish.client_id"))?, 0, 63)?,
+            time: at_least(_p.read_int(&mut _p.locate(warn, "SvRaceFinish.time"))?, -1)?,
+            diff: _p.read_int(&mut _p.locate(warn, "SvRaceFinish.diff"))?,
+            record_personal: to_bool(_p.read_int(&mut _p.locate(warn, "SvRaceFinish.record_personal"))?)?,
+            record_server: to_bool(_p.read_int(&mut _p.locate(warn, "SvRaceFinish.record_server"))?)?,
+        })
+    }
+    pub fn new(client_id: i32, time: i32, diff: i32, record_personal: bool, record_server: bool) -> Result<SvRaceFinish, Error> {
+        let result = SvRaceFinish {
+            client_id,
+            time,
+            diff,
+            record_personal,
+            record_server,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.client_id, 0, 63)?;
+        at_least(self.time, -1)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.client_id, 0, 63)?;
+        at_least(self.time, -1)?;
         _p.write_int(self.client_id)?;
         _p.write_int(self.time)?;
         _p.write_int(self.diff)?;
@@ -1968,13 +3040,28 @@ impl fmt::Debug for SvRaceFinish {
 
 impl SvCheckpoint {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvCheckpoint, Error> {
-        let result = Ok(SvCheckpoint {
-            diff: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
+        SvCheckpoint::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvCheckpoint, Error> {
+        let result = SvCheckpoint::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvCheckpoint"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SvCheckpoint, Error> {
+        Ok(SvCheckpoint {
+            diff: _p.read_int(&mut _p.locate(warn, "SvCheckpoint.diff"))?,
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(diff: i32) -> Result<SvCheckpoint, Error> {
+        let result = SvCheckpoint {
+            diff,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.diff)?;
         Ok(_p.written())
     }
@@ -1989,18 +3076,38 @@ impl fmt::Debug for SvCheckpoint {
 
 impl<'a> SvCommandInfo<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvCommandInfo<'a>, Error> {
-        let result = Ok(SvCommandInfo {
-            name: sanitize(warn, _p.read_string()?)?,
-            args_format: sanitize(warn, _p.read_string()?)?,
-            help_text: sanitize(warn, _p.read_string()?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.name).unwrap();
-        sanitize(&mut Panic, self.args_format).unwrap();
-        sanitize(&mut Panic, self.help_text).unwrap();
+        SvCommandInfo::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvCommandInfo<'a>, Error> {
+        let result = SvCommandInfo::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvCommandInfo"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvCommandInfo<'a>, Error> {
+        Ok(SvCommandInfo {
+            name: sanitize(&mut _p.locate(warn, "SvCommandInfo.name"), _p.read_string()?)?,
+            args_format: sanitize(&mut _p.locate(warn, "SvCommandInfo.args_format"), _p.read_string()?)?,
+            help_text: sanitize(&mut _p.locate(warn, "SvCommandInfo.help_text"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(name: &'a [u8], args_format: &'a [u8], help_text: &'a [u8]) -> Result<SvCommandInfo<'a>, Error> {
+        let result = SvCommandInfo {
+            name,
+            args_format,
+            help_text,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.args_format)?;
+        sanitize(&mut Ignore, self.help_text)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.args_format)?;
+        sanitize(&mut Ignore, self.help_text)?;
         _p.write_string(self.name)?;
         _p.write_string(self.args_format)?;
         _p.write_string(self.help_text)?;
@@ -2019,14 +3126,30 @@ impl<'a> fmt::Debug for SvCommandInfo<'a> {
 
 impl<'a> SvCommandInfoRemove<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvCommandInfoRemove<'a>, Error> {
-        let result = Ok(SvCommandInfoRemove {
-            name: sanitize(warn, _p.read_string()?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.name).unwrap();
+        SvCommandInfoRemove::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvCommandInfoRemove<'a>, Error> {
+        let result = SvCommandInfoRemove::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SvCommandInfoRemove"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SvCommandInfoRemove<'a>, Error> {
+        Ok(SvCommandInfoRemove {
+            name: sanitize(&mut _p.locate(warn, "SvCommandInfoRemove.name"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(name: &'a [u8]) -> Result<SvCommandInfoRemove<'a>, Error> {
+        let result = SvCommandInfoRemove {
+            name,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.name)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.name)?;
         _p.write_string(self.name)?;
         Ok(_p.written())
     }
@@ -2041,16 +3164,34 @@ impl<'a> fmt::Debug for SvCommandInfoRemove<'a> {
 
 impl<'a> ClCommand<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClCommand<'a>, Error> {
-        let result = Ok(ClCommand {
-            name: sanitize(warn, _p.read_string()?)?,
-            arguments: sanitize(warn, _p.read_string()?)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        sanitize(&mut Panic, self.name).unwrap();
-        sanitize(&mut Panic, self.arguments).unwrap();
+        ClCommand::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClCommand<'a>, Error> {
+        let result = ClCommand::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ClCommand"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ClCommand<'a>, Error> {
+        Ok(ClCommand {
+            name: sanitize(&mut _p.locate(warn, "ClCommand.name"), _p.read_string()?)?,
+            arguments: sanitize(&mut _p.locate(warn, "ClCommand.arguments"), _p.read_string()?)?,
+        })
+    }
+    pub fn new(name: &'a [u8], arguments: &'a [u8]) -> Result<ClCommand<'a>, Error> {
+        let result = ClCommand {
+            name,
+            arguments,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.arguments)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        sanitize(&mut Ignore, self.name)?;
+        sanitize(&mut Ignore, self.arguments)?;
         _p.write_string(self.name)?;
         _p.write_string(self.arguments)?;
         Ok(_p.written())
@@ -2065,3 +3206,535 @@ impl<'a> fmt::Debug for ClCommand<'a> {
     }
 }
 
+impl<'a> SvMotd<'a> {
+    pub fn to_owned(&self) -> SvMotdOwned {
+        SvMotdOwned {
+            message: self.message.to_vec(),
+        }
+    }
+}
+
+impl SvMotdOwned {
+    pub fn as_ref(&self) -> SvMotd<'_> {
+        SvMotd {
+            message: &self.message[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvMotdOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvBroadcast<'a> {
+    pub fn to_owned(&self) -> SvBroadcastOwned {
+        SvBroadcastOwned {
+            message: self.message.to_vec(),
+        }
+    }
+}
+
+impl SvBroadcastOwned {
+    pub fn as_ref(&self) -> SvBroadcast<'_> {
+        SvBroadcast {
+            message: &self.message[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvBroadcastOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvChat<'a> {
+    pub fn to_owned(&self) -> SvChatOwned {
+        SvChatOwned {
+            mode: self.mode,
+            client_id: self.client_id,
+            target_id: self.target_id,
+            message: self.message.to_vec(),
+        }
+    }
+}
+
+impl SvChatOwned {
+    pub fn as_ref(&self) -> SvChat<'_> {
+        SvChat {
+            mode: self.mode,
+            client_id: self.client_id,
+            target_id: self.target_id,
+            message: &self.message[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvChatOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvVoteOptionAdd<'a> {
+    pub fn to_owned(&self) -> SvVoteOptionAddOwned {
+        SvVoteOptionAddOwned {
+            description: self.description.to_vec(),
+        }
+    }
+}
+
+impl SvVoteOptionAddOwned {
+    pub fn as_ref(&self) -> SvVoteOptionAdd<'_> {
+        SvVoteOptionAdd {
+            description: &self.description[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvVoteOptionAddOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvVoteOptionRemove<'a> {
+    pub fn to_owned(&self) -> SvVoteOptionRemoveOwned {
+        SvVoteOptionRemoveOwned {
+            description: self.description.to_vec(),
+        }
+    }
+}
+
+impl SvVoteOptionRemoveOwned {
+    pub fn as_ref(&self) -> SvVoteOptionRemove<'_> {
+        SvVoteOptionRemove {
+            description: &self.description[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvVoteOptionRemoveOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvVoteSet<'a> {
+    pub fn to_owned(&self) -> SvVoteSetOwned {
+        SvVoteSetOwned {
+            client_id: self.client_id,
+            type_: self.type_,
+            timeout: self.timeout,
+            description: self.description.to_vec(),
+            reason: self.reason.to_vec(),
+        }
+    }
+}
+
+impl SvVoteSetOwned {
+    pub fn as_ref(&self) -> SvVoteSet<'_> {
+        SvVoteSet {
+            client_id: self.client_id,
+            type_: self.type_,
+            timeout: self.timeout,
+            description: &self.description[..],
+            reason: &self.reason[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvVoteSetOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvClientInfo<'a> {
+    pub fn to_owned(&self) -> SvClientInfoOwned {
+        SvClientInfoOwned {
+            client_id: self.client_id,
+            local: self.local,
+            team: self.team,
+            name: self.name.to_vec(),
+            clan: self.clan.to_vec(),
+            country: self.country,
+            skin_part_names: [
+                self.skin_part_names[0].to_vec(),
+                self.skin_part_names[1].to_vec(),
+                self.skin_part_names[2].to_vec(),
+                self.skin_part_names[3].to_vec(),
+                self.skin_part_names[4].to_vec(),
+                self.skin_part_names[5].to_vec(),
+            ],
+            use_custom_colors: self.use_custom_colors,
+            skin_part_colors: self.skin_part_colors,
+            silent: self.silent,
+        }
+    }
+}
+
+impl SvClientInfoOwned {
+    pub fn as_ref(&self) -> SvClientInfo<'_> {
+        SvClientInfo {
+            client_id: self.client_id,
+            local: self.local,
+            team: self.team,
+            name: &self.name[..],
+            clan: &self.clan[..],
+            country: self.country,
+            skin_part_names: [
+                &self.skin_part_names[0][..],
+                &self.skin_part_names[1][..],
+                &self.skin_part_names[2][..],
+                &self.skin_part_names[3][..],
+                &self.skin_part_names[4][..],
+                &self.skin_part_names[5][..],
+            ],
+            use_custom_colors: self.use_custom_colors,
+            skin_part_colors: self.skin_part_colors,
+            silent: self.silent,
+        }
+    }
+}
+
+impl fmt::Debug for SvClientInfoOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvClientDrop<'a> {
+    pub fn to_owned(&self) -> SvClientDropOwned {
+        SvClientDropOwned {
+            client_id: self.client_id,
+            reason: self.reason.to_vec(),
+            silent: self.silent,
+        }
+    }
+}
+
+impl SvClientDropOwned {
+    pub fn as_ref(&self) -> SvClientDrop<'_> {
+        SvClientDrop {
+            client_id: self.client_id,
+            reason: &self.reason[..],
+            silent: self.silent,
+        }
+    }
+}
+
+impl fmt::Debug for SvClientDropOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> DeClientEnter<'a> {
+    pub fn to_owned(&self) -> DeClientEnterOwned {
+        DeClientEnterOwned {
+            name: self.name.to_vec(),
+            client_id: self.client_id,
+            team: self.team,
+        }
+    }
+}
+
+impl DeClientEnterOwned {
+    pub fn as_ref(&self) -> DeClientEnter<'_> {
+        DeClientEnter {
+            name: &self.name[..],
+            client_id: self.client_id,
+            team: self.team,
+        }
+    }
+}
+
+impl fmt::Debug for DeClientEnterOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> DeClientLeave<'a> {
+    pub fn to_owned(&self) -> DeClientLeaveOwned {
+        DeClientLeaveOwned {
+            name: self.name.to_vec(),
+            client_id: self.client_id,
+            reason: self.reason.to_vec(),
+        }
+    }
+}
+
+impl DeClientLeaveOwned {
+    pub fn as_ref(&self) -> DeClientLeave<'_> {
+        DeClientLeave {
+            name: &self.name[..],
+            client_id: self.client_id,
+            reason: &self.reason[..],
+        }
+    }
+}
+
+impl fmt::Debug for DeClientLeaveOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> ClSay<'a> {
+    pub fn to_owned(&self) -> ClSayOwned {
+        ClSayOwned {
+            mode: self.mode,
+            target: self.target,
+            message: self.message.to_vec(),
+        }
+    }
+}
+
+impl ClSayOwned {
+    pub fn as_ref(&self) -> ClSay<'_> {
+        ClSay {
+            mode: self.mode,
+            target: self.target,
+            message: &self.message[..],
+        }
+    }
+}
+
+impl fmt::Debug for ClSayOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> ClStartInfo<'a> {
+    pub fn to_owned(&self) -> ClStartInfoOwned {
+        ClStartInfoOwned {
+            name: self.name.to_vec(),
+            clan: self.clan.to_vec(),
+            country: self.country,
+            skin_part_names: [
+                self.skin_part_names[0].to_vec(),
+                self.skin_part_names[1].to_vec(),
+                self.skin_part_names[2].to_vec(),
+                self.skin_part_names[3].to_vec(),
+                self.skin_part_names[4].to_vec(),
+                self.skin_part_names[5].to_vec(),
+            ],
+            use_custom_colors: self.use_custom_colors,
+            skin_part_colors: self.skin_part_colors,
+        }
+    }
+}
+
+impl ClStartInfoOwned {
+    pub fn as_ref(&self) -> ClStartInfo<'_> {
+        ClStartInfo {
+            name: &self.name[..],
+            clan: &self.clan[..],
+            country: self.country,
+            skin_part_names: [
+                &self.skin_part_names[0][..],
+                &self.skin_part_names[1][..],
+                &self.skin_part_names[2][..],
+                &self.skin_part_names[3][..],
+                &self.skin_part_names[4][..],
+                &self.skin_part_names[5][..],
+            ],
+            use_custom_colors: self.use_custom_colors,
+            skin_part_colors: self.skin_part_colors,
+        }
+    }
+}
+
+impl fmt::Debug for ClStartInfoOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> ClCallVote<'a> {
+    pub fn to_owned(&self) -> ClCallVoteOwned {
+        ClCallVoteOwned {
+            type_: self.type_.to_vec(),
+            value: self.value.to_vec(),
+            reason: self.reason.to_vec(),
+            force: self.force,
+        }
+    }
+}
+
+impl ClCallVoteOwned {
+    pub fn as_ref(&self) -> ClCallVote<'_> {
+        ClCallVote {
+            type_: &self.type_[..],
+            value: &self.value[..],
+            reason: &self.reason[..],
+            force: self.force,
+        }
+    }
+}
+
+impl fmt::Debug for ClCallVoteOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvSkinChange<'a> {
+    pub fn to_owned(&self) -> SvSkinChangeOwned {
+        SvSkinChangeOwned {
+            client_id: self.client_id,
+            skin_part_names: [
+                self.skin_part_names[0].to_vec(),
+                self.skin_part_names[1].to_vec(),
+                self.skin_part_names[2].to_vec(),
+                self.skin_part_names[3].to_vec(),
+                self.skin_part_names[4].to_vec(),
+                self.skin_part_names[5].to_vec(),
+            ],
+            use_custom_colors: self.use_custom_colors,
+            skin_part_colors: self.skin_part_colors,
+        }
+    }
+}
+
+impl SvSkinChangeOwned {
+    pub fn as_ref(&self) -> SvSkinChange<'_> {
+        SvSkinChange {
+            client_id: self.client_id,
+            skin_part_names: [
+                &self.skin_part_names[0][..],
+                &self.skin_part_names[1][..],
+                &self.skin_part_names[2][..],
+                &self.skin_part_names[3][..],
+                &self.skin_part_names[4][..],
+                &self.skin_part_names[5][..],
+            ],
+            use_custom_colors: self.use_custom_colors,
+            skin_part_colors: self.skin_part_colors,
+        }
+    }
+}
+
+impl fmt::Debug for SvSkinChangeOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> ClSkinChange<'a> {
+    pub fn to_owned(&self) -> ClSkinChangeOwned {
+        ClSkinChangeOwned {
+            skin_part_names: [
+                self.skin_part_names[0].to_vec(),
+                self.skin_part_names[1].to_vec(),
+                self.skin_part_names[2].to_vec(),
+                self.skin_part_names[3].to_vec(),
+                self.skin_part_names[4].to_vec(),
+                self.skin_part_names[5].to_vec(),
+            ],
+            use_custom_colors: self.use_custom_colors,
+            skin_part_colors: self.skin_part_colors,
+        }
+    }
+}
+
+impl ClSkinChangeOwned {
+    pub fn as_ref(&self) -> ClSkinChange<'_> {
+        ClSkinChange {
+            skin_part_names: [
+                &self.skin_part_names[0][..],
+                &self.skin_part_names[1][..],
+                &self.skin_part_names[2][..],
+                &self.skin_part_names[3][..],
+                &self.skin_part_names[4][..],
+                &self.skin_part_names[5][..],
+            ],
+            use_custom_colors: self.use_custom_colors,
+            skin_part_colors: self.skin_part_colors,
+        }
+    }
+}
+
+impl fmt::Debug for ClSkinChangeOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvCommandInfo<'a> {
+    pub fn to_owned(&self) -> SvCommandInfoOwned {
+        SvCommandInfoOwned {
+            name: self.name.to_vec(),
+            args_format: self.args_format.to_vec(),
+            help_text: self.help_text.to_vec(),
+        }
+    }
+}
+
+impl SvCommandInfoOwned {
+    pub fn as_ref(&self) -> SvCommandInfo<'_> {
+        SvCommandInfo {
+            name: &self.name[..],
+            args_format: &self.args_format[..],
+            help_text: &self.help_text[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvCommandInfoOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SvCommandInfoRemove<'a> {
+    pub fn to_owned(&self) -> SvCommandInfoRemoveOwned {
+        SvCommandInfoRemoveOwned {
+            name: self.name.to_vec(),
+        }
+    }
+}
+
+impl SvCommandInfoRemoveOwned {
+    pub fn as_ref(&self) -> SvCommandInfoRemove<'_> {
+        SvCommandInfoRemove {
+            name: &self.name[..],
+        }
+    }
+}
+
+impl fmt::Debug for SvCommandInfoRemoveOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> ClCommand<'a> {
+    pub fn to_owned(&self) -> ClCommandOwned {
+        ClCommandOwned {
+            name: self.name.to_vec(),
+            arguments: self.arguments.to_vec(),
+        }
+    }
+}
+
+impl ClCommandOwned {
+    pub fn as_ref(&self) -> ClCommand<'_> {
+        ClCommand {
+            name: &self.name[..],
+            arguments: &self.arguments[..],
+        }
+    }
+}
+
+impl fmt::Debug for ClCommandOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
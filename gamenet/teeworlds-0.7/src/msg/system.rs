@@ -1,6 +1,9 @@
-use buffer::CapacityError;
 use common::digest::Sha256;
 use common::pretty;
+use common::warn::Located;
+use common::warn::strip_location;
+use error;
+use error::EncodeError;
 use error::Error;
 use packer::Packer;
 use packer::Unpacker;
@@ -22,7 +25,7 @@ impl<'a> System<'a> {
         }
     }
     pub fn encode<'d, 's>(&self, mut p: Packer<'d, 's>)
-        -> Result<&'d [u8], CapacityError>
+        -> Result<&'d [u8], EncodeError>
     {
         with_packer(&mut p, |p| SystemOrGame::System(self.msg_id()).encode_id(p))?;
         with_packer(&mut p, |p| self.encode_msg(p))?;
@@ -56,6 +59,7 @@ pub const MAPLIST_ENTRY_ADD: i32 = 29;
 pub const MAPLIST_ENTRY_REM: i32 = 30;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub enum System<'a> {
     Info(Info<'a>),
     MapChange(MapChange<'a>),
@@ -85,32 +89,35 @@ pub enum System<'a> {
 
 impl<'a> System<'a> {
     pub fn decode_msg<W: Warn<Warning>>(warn: &mut W, msg_id: MessageId, _p: &mut Unpacker<'a>) -> Result<System<'a>, Error> {
+        System::decode_msg_located(&mut strip_location(warn), msg_id, _p)
+    }
+    pub fn decode_msg_located<W: Warn<Located<Warning>>>(warn: &mut W, msg_id: MessageId, _p: &mut Unpacker<'a>) -> Result<System<'a>, Error> {
         use self::MessageId::*;
         Ok(match msg_id {
-            Ordinal(INFO) => System::Info(Info::decode(warn, _p)?),
-            Ordinal(MAP_CHANGE) => System::MapChange(MapChange::decode(warn, _p)?),
-            Ordinal(MAP_DATA) => System::MapData(MapData::decode(warn, _p)?),
-            Ordinal(SERVER_INFO) => System::ServerInfo(ServerInfo::decode(warn, _p)?),
-            Ordinal(CON_READY) => System::ConReady(ConReady::decode(warn, _p)?),
-            Ordinal(SNAP) => System::Snap(Snap::decode(warn, _p)?),
-            Ordinal(SNAP_EMPTY) => System::SnapEmpty(SnapEmpty::decode(warn, _p)?),
-            Ordinal(SNAP_SINGLE) => System::SnapSingle(SnapSingle::decode(warn, _p)?),
-            Ordinal(INPUT_TIMING) => System::InputTiming(InputTiming::decode(warn, _p)?),
-            Ordinal(RCON_AUTH_ON) => System::RconAuthOn(RconAuthOn::decode(warn, _p)?),
-            Ordinal(RCON_AUTH_OFF) => System::RconAuthOff(RconAuthOff::decode(warn, _p)?),
-            Ordinal(RCON_LINE) => System::RconLine(RconLine::decode(warn, _p)?),
-            Ordinal(RCON_CMD_ADD) => System::RconCmdAdd(RconCmdAdd::decode(warn, _p)?),
-            Ordinal(RCON_CMD_REM) => System::RconCmdRem(RconCmdRem::decode(warn, _p)?),
-            Ordinal(READY) => System::Ready(Ready::decode(warn, _p)?),
-            Ordinal(ENTER_GAME) => System::EnterGame(EnterGame::decode(warn, _p)?),
-            Ordinal(INPUT) => System::Input(Input::decode(warn, _p)?),
-            Ordinal(RCON_CMD) => System::RconCmd(RconCmd::decode(warn, _p)?),
-            Ordinal(RCON_AUTH) => System::RconAuth(RconAuth::decode(warn, _p)?),
-            Ordinal(REQUEST_MAP_DATA) => System::RequestMapData(RequestMapData::decode(warn, _p)?),
-            Ordinal(PING) => System::Ping(Ping::decode(warn, _p)?),
-            Ordinal(PING_REPLY) => System::PingReply(PingReply::decode(warn, _p)?),
-            Ordinal(MAPLIST_ENTRY_ADD) => System::MaplistEntryAdd(MaplistEntryAdd::decode(warn, _p)?),
-            Ordinal(MAPLIST_ENTRY_REM) => System::MaplistEntryRem(MaplistEntryRem::decode(warn, _p)?),
+            Ordinal(INFO) => System::Info(Info::decode_located(warn, _p)?),
+            Ordinal(MAP_CHANGE) => System::MapChange(MapChange::decode_located(warn, _p)?),
+            Ordinal(MAP_DATA) => System::MapData(MapData::decode_located(warn, _p)?),
+            Ordinal(SERVER_INFO) => System::ServerInfo(ServerInfo::decode_located(warn, _p)?),
+            Ordinal(CON_READY) => System::ConReady(ConReady::decode_located(warn, _p)?),
+            Ordinal(SNAP) => System::Snap(Snap::decode_located(warn, _p)?),
+            Ordinal(SNAP_EMPTY) => System::SnapEmpty(SnapEmpty::decode_located(warn, _p)?),
+            Ordinal(SNAP_SINGLE) => System::SnapSingle(SnapSingle::decode_located(warn, _p)?),
+            Ordinal(INPUT_TIMING) => System::InputTiming(InputTiming::decode_located(warn, _p)?),
+            Ordinal(RCON_AUTH_ON) => System::RconAuthOn(RconAuthOn::decode_located(warn, _p)?),
+            Ordinal(RCON_AUTH_OFF) => System::RconAuthOff(RconAuthOff::decode_located(warn, _p)?),
+            Ordinal(RCON_LINE) => System::RconLine(RconLine::decode_located(warn, _p)?),
+            Ordinal(RCON_CMD_ADD) => System::RconCmdAdd(RconCmdAdd::decode_located(warn, _p)?),
+            Ordinal(RCON_CMD_REM) => System::RconCmdRem(RconCmdRem::decode_located(warn, _p)?),
+            Ordinal(READY) => System::Ready(Ready::decode_located(warn, _p)?),
+            Ordinal(ENTER_GAME) => System::EnterGame(EnterGame::decode_located(warn, _p)?),
+            Ordinal(INPUT) => System::Input(Input::decode_located(warn, _p)?),
+            Ordinal(RCON_CMD) => System::RconCmd(RconCmd::decode_located(warn, _p)?),
+            Ordinal(RCON_AUTH) => System::RconAuth(RconAuth::decode_located(warn, _p)?),
+            Ordinal(REQUEST_MAP_DATA) => System::RequestMapData(RequestMapData::decode_located(warn, _p)?),
+            Ordinal(PING) => System::Ping(Ping::decode_located(warn, _p)?),
+            Ordinal(PING_REPLY) => System::PingReply(PingReply::decode_located(warn, _p)?),
+            Ordinal(MAPLIST_ENTRY_ADD) => System::MaplistEntryAdd(MaplistEntryAdd::decode_located(warn, _p)?),
+            Ordinal(MAPLIST_ENTRY_REM) => System::MaplistEntryRem(MaplistEntryRem::decode_located(warn, _p)?),
             _ => return Err(Error::UnknownId),
         })
     }
@@ -142,7 +149,35 @@ impl<'a> System<'a> {
             System::MaplistEntryRem(_) => MessageId::from(MAPLIST_ENTRY_REM),
         }
     }
-    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        match *self {
+            System::Info(ref i) => i.validate(),
+            System::MapChange(ref i) => i.validate(),
+            System::MapData(ref i) => i.validate(),
+            System::ServerInfo(ref i) => i.validate(),
+            System::ConReady(ref i) => i.validate(),
+            System::Snap(ref i) => i.validate(),
+            System::SnapEmpty(ref i) => i.validate(),
+            System::SnapSingle(ref i) => i.validate(),
+            System::InputTiming(ref i) => i.validate(),
+            System::RconAuthOn(ref i) => i.validate(),
+            System::RconAuthOff(ref i) => i.validate(),
+            System::RconLine(ref i) => i.validate(),
+            System::RconCmdAdd(ref i) => i.validate(),
+            System::RconCmdRem(ref i) => i.validate(),
+            System::Ready(ref i) => i.validate(),
+            System::EnterGame(ref i) => i.validate(),
+            System::Input(ref i) => i.validate(),
+            System::RconCmd(ref i) => i.validate(),
+            System::RconAuth(ref i) => i.validate(),
+            System::RequestMapData(ref i) => i.validate(),
+            System::Ping(ref i) => i.validate(),
+            System::PingReply(ref i) => i.validate(),
+            System::MaplistEntryAdd(ref i) => i.validate(),
+            System::MaplistEntryRem(ref i) => i.validate(),
+        }
+    }
+    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         match *self {
             System::Info(ref i) => i.encode(p),
             System::MapChange(ref i) => i.encode(p),
@@ -346,15 +381,129 @@ impl<'a> From<MaplistEntryRem<'a>> for System<'a> {
         System::MaplistEntryRem(i)
     }
 }
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SystemOwned {
+    Info(InfoOwned),
+    MapChange(MapChangeOwned),
+    MapData(MapDataOwned),
+    ServerInfo(ServerInfoOwned),
+    ConReady(ConReady),
+    Snap(SnapOwned),
+    SnapEmpty(SnapEmpty),
+    SnapSingle(SnapSingleOwned),
+    InputTiming(InputTiming),
+    RconAuthOn(RconAuthOn),
+    RconAuthOff(RconAuthOff),
+    RconLine(RconLineOwned),
+    RconCmdAdd(RconCmdAddOwned),
+    RconCmdRem(RconCmdRemOwned),
+    Ready(Ready),
+    EnterGame(EnterGame),
+    Input(Input),
+    RconCmd(RconCmdOwned),
+    RconAuth(RconAuthOwned),
+    RequestMapData(RequestMapData),
+    Ping(Ping),
+    PingReply(PingReply),
+    MaplistEntryAdd(MaplistEntryAddOwned),
+    MaplistEntryRem(MaplistEntryRemOwned),
+}
+
+impl<'a> System<'a> {
+    pub fn to_owned(&self) -> SystemOwned {
+        match *self {
+            System::Info(ref i) => SystemOwned::Info(i.to_owned()),
+            System::MapChange(ref i) => SystemOwned::MapChange(i.to_owned()),
+            System::MapData(ref i) => SystemOwned::MapData(i.to_owned()),
+            System::ServerInfo(ref i) => SystemOwned::ServerInfo(i.to_owned()),
+            System::ConReady(i) => SystemOwned::ConReady(i),
+            System::Snap(ref i) => SystemOwned::Snap(i.to_owned()),
+            System::SnapEmpty(i) => SystemOwned::SnapEmpty(i),
+            System::SnapSingle(ref i) => SystemOwned::SnapSingle(i.to_owned()),
+            System::InputTiming(i) => SystemOwned::InputTiming(i),
+            System::RconAuthOn(i) => SystemOwned::RconAuthOn(i),
+            System::RconAuthOff(i) => SystemOwned::RconAuthOff(i),
+            System::RconLine(ref i) => SystemOwned::RconLine(i.to_owned()),
+            System::RconCmdAdd(ref i) => SystemOwned::RconCmdAdd(i.to_owned()),
+            System::RconCmdRem(ref i) => SystemOwned::RconCmdRem(i.to_owned()),
+            System::Ready(i) => SystemOwned::Ready(i),
+            System::EnterGame(i) => SystemOwned::EnterGame(i),
+            System::Input(i) => SystemOwned::Input(i),
+            System::RconCmd(ref i) => SystemOwned::RconCmd(i.to_owned()),
+            System::RconAuth(ref i) => SystemOwned::RconAuth(i.to_owned()),
+            System::RequestMapData(i) => SystemOwned::RequestMapData(i),
+            System::Ping(i) => SystemOwned::Ping(i),
+            System::PingReply(i) => SystemOwned::PingReply(i),
+            System::MaplistEntryAdd(ref i) => SystemOwned::MaplistEntryAdd(i.to_owned()),
+            System::MaplistEntryRem(ref i) => SystemOwned::MaplistEntryRem(i.to_owned()),
+        }
+    }
+}
+
+impl SystemOwned {
+    pub fn as_ref(&self) -> System<'_> {
+        match *self {
+            SystemOwned::Info(ref i) => System::Info(i.as_ref()),
+            SystemOwned::MapChange(ref i) => System::MapChange(i.as_ref()),
+            SystemOwned::MapData(ref i) => System::MapData(i.as_ref()),
+            SystemOwned::ServerInfo(ref i) => System::ServerInfo(i.as_ref()),
+            SystemOwned::ConReady(i) => System::ConReady(i),
+            SystemOwned::Snap(ref i) => System::Snap(i.as_ref()),
+            SystemOwned::SnapEmpty(i) => System::SnapEmpty(i),
+            SystemOwned::SnapSingle(ref i) => System::SnapSingle(i.as_ref()),
+            SystemOwned::InputTiming(i) => System::InputTiming(i),
+            SystemOwned::RconAuthOn(i) => System::RconAuthOn(i),
+            SystemOwned::RconAuthOff(i) => System::RconAuthOff(i),
+            SystemOwned::RconLine(ref i) => System::RconLine(i.as_ref()),
+            SystemOwned::RconCmdAdd(ref i) => System::RconCmdAdd(i.as_ref()),
+            SystemOwned::RconCmdRem(ref i) => System::RconCmdRem(i.as_ref()),
+            SystemOwned::Ready(i) => System::Ready(i),
+            SystemOwned::EnterGame(i) => System::EnterGame(i),
+            SystemOwned::Input(i) => System::Input(i),
+            SystemOwned::RconCmd(ref i) => System::RconCmd(i.as_ref()),
+            SystemOwned::RconAuth(ref i) => System::RconAuth(i.as_ref()),
+            SystemOwned::RequestMapData(i) => System::RequestMapData(i),
+            SystemOwned::Ping(i) => System::Ping(i),
+            SystemOwned::PingReply(i) => System::PingReply(i),
+            SystemOwned::MaplistEntryAdd(ref i) => System::MaplistEntryAdd(i.as_ref()),
+            SystemOwned::MaplistEntryRem(ref i) => System::MaplistEntryRem(i.as_ref()),
+        }
+    }
+    pub fn msg_id(&self) -> MessageId {
+        self.as_ref().msg_id()
+    }
+    pub fn encode_msg<'d, 's>(&self, p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        self.as_ref().encode_msg(p)
+    }
+}
+
+impl fmt::Debug for SystemOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> From<System<'a>> for SystemOwned {
+    fn from(i: System<'a>) -> SystemOwned {
+        i.to_owned()
+    }
+}
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Info<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub version: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::optional_bytes::serialize"))]
     pub password: Option<&'a [u8]>,
     pub client_version: Option<i32>,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct MapChange<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
     pub crc: i32,
     pub size: i32,
@@ -364,78 +513,102 @@ pub struct MapChange<'a> {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct MapData<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub data: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ServerInfo<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub data: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ConReady;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Snap<'a> {
     pub tick: i32,
     pub delta_tick: i32,
     pub num_parts: i32,
     pub part: i32,
     pub crc: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub data: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SnapEmpty {
     pub tick: i32,
     pub delta_tick: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SnapSingle<'a> {
     pub tick: i32,
     pub delta_tick: i32,
     pub crc: i32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub data: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct InputTiming {
     pub input_pred_tick: i32,
     pub time_left: i32,
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RconAuthOn;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RconAuthOff;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct RconLine<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub line: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct RconCmdAdd<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub help: &'a [u8],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub params: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct RconCmdRem<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Ready;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EnterGame;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Input {
     pub ack_snapshot: i32,
     pub intended_tick: i32,
@@ -444,47 +617,188 @@ pub struct Input {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct RconCmd<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub cmd: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct RconAuth<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub password: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RequestMapData;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Ping;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PingReply;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct MaplistEntryAdd<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct MaplistEntryRem<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "gamenet_common::serialize::bytes::serialize"))]
     pub name: &'a [u8],
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InfoOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub version: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::optional_bytes"))]
+    pub password: Option<Vec<u8>>,
+    pub client_version: Option<i32>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MapChangeOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+    pub crc: i32,
+    pub size: i32,
+    pub num_response_chunks_per_request: i32,
+    pub chunk_size: i32,
+    pub sha256: Sha256,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MapDataOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ServerInfoOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SnapOwned {
+    pub tick: i32,
+    pub delta_tick: i32,
+    pub num_parts: i32,
+    pub part: i32,
+    pub crc: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SnapSingleOwned {
+    pub tick: i32,
+    pub delta_tick: i32,
+    pub crc: i32,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RconLineOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub line: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RconCmdAddOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub help: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub params: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RconCmdRemOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RconCmdOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub cmd: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RconAuthOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub password: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MaplistEntryAddOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MaplistEntryRemOwned {
+    #[cfg_attr(feature = "serde", serde(with = "gamenet_common::serialize::bytes"))]
+    pub name: Vec<u8>,
+}
+
 impl<'a> Info<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info<'a>, Error> {
-        let result = Ok(Info {
+        Info::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info<'a>, Error> {
+        let result = Info::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Info"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Info<'a>, Error> {
+        Ok(Info {
             version: _p.read_string()?,
             password: _p.read_string().ok(),
-            client_version: _p.read_int(warn).ok(),
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(self.password.is_some());
-        assert!(self.client_version.is_some());
+            client_version: _p.read_int(&mut _p.locate(warn, "Info.client_version")).ok(),
+        })
+    }
+    pub fn new(version: &'a [u8], password: Option<&'a [u8]>, client_version: Option<i32>) -> Result<Info<'a>, Error> {
+        let result = Info {
+            version,
+            password,
+            client_version,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.password.ok_or(Error::MissingValue)?;
+        self.client_version.ok_or(Error::MissingValue)?;
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        self.password.ok_or(Error::MissingValue)?;
+        self.client_version.ok_or(Error::MissingValue)?;
         _p.write_string(self.version)?;
         _p.write_string(self.password.unwrap())?;
         _p.write_int(self.client_version.unwrap())?;
@@ -503,18 +817,38 @@ impl<'a> fmt::Debug for Info<'a> {
 
 impl<'a> MapChange<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapChange<'a>, Error> {
-        let result = Ok(MapChange {
+        MapChange::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapChange<'a>, Error> {
+        let result = MapChange::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "MapChange"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapChange<'a>, Error> {
+        Ok(MapChange {
             name: _p.read_string()?,
-            crc: _p.read_int(warn)?,
-            size: _p.read_int(warn)?,
-            num_response_chunks_per_request: _p.read_int(warn)?,
-            chunk_size: _p.read_int(warn)?,
+            crc: _p.read_int(&mut _p.locate(warn, "MapChange.crc"))?,
+            size: _p.read_int(&mut _p.locate(warn, "MapChange.size"))?,
+            num_response_chunks_per_request: _p.read_int(&mut _p.locate(warn, "MapChange.num_response_chunks_per_request"))?,
+            chunk_size: _p.read_int(&mut _p.locate(warn, "MapChange.chunk_size"))?,
             sha256: Sha256::from_slice(_p.read_raw(32)?).unwrap(),
-        });
-        _p.finish(warn);
-        result
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(name: &'a [u8], crc: i32, size: i32, num_response_chunks_per_request: i32, chunk_size: i32, sha256: Sha256) -> Result<MapChange<'a>, Error> {
+        let result = MapChange {
+            name,
+            crc,
+            size,
+            num_response_chunks_per_request,
+            chunk_size,
+            sha256,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.name)?;
         _p.write_int(self.crc)?;
         _p.write_int(self.size)?;
@@ -539,13 +873,28 @@ impl<'a> fmt::Debug for MapChange<'a> {
 
 impl<'a> MapData<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapData<'a>, Error> {
-        let result = Ok(MapData {
+        MapData::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapData<'a>, Error> {
+        let result = MapData::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "MapData"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MapData<'a>, Error> {
+        Ok(MapData {
             data: _p.read_rest()?,
-        });
-        _p.finish(warn);
-        result
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(data: &'a [u8]) -> Result<MapData<'a>, Error> {
+        let result = MapData {
+            data,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_rest(self.data)?;
         Ok(_p.written())
     }
@@ -560,13 +909,28 @@ impl<'a> fmt::Debug for MapData<'a> {
 
 impl<'a> ServerInfo<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ServerInfo<'a>, Error> {
-        let result = Ok(ServerInfo {
+        ServerInfo::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ServerInfo<'a>, Error> {
+        let result = ServerInfo::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "ServerInfo"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<ServerInfo<'a>, Error> {
+        Ok(ServerInfo {
             data: _p.read_rest()?,
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(data: &'a [u8]) -> Result<ServerInfo<'a>, Error> {
+        let result = ServerInfo {
+            data,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_rest(self.data)?;
         Ok(_p.written())
     }
@@ -581,11 +945,15 @@ impl<'a> fmt::Debug for ServerInfo<'a> {
 
 impl ConReady {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<ConReady, Error> {
-        let result = Ok(ConReady);
-        _p.finish(warn);
-        result
+        ConReady::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<ConReady, Error> {
+        error::finish(&mut _p.locate(warn, "ConReady"), _p, Ok(ConReady))
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -598,18 +966,38 @@ impl fmt::Debug for ConReady {
 
 impl<'a> Snap<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Snap<'a>, Error> {
-        let result = Ok(Snap {
-            tick: _p.read_int(warn)?,
-            delta_tick: _p.read_int(warn)?,
-            num_parts: _p.read_int(warn)?,
-            part: _p.read_int(warn)?,
-            crc: _p.read_int(warn)?,
-            data: _p.read_data(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        Snap::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Snap<'a>, Error> {
+        let result = Snap::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Snap"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<Snap<'a>, Error> {
+        Ok(Snap {
+            tick: _p.read_int(&mut _p.locate(warn, "Snap.tick"))?,
+            delta_tick: _p.read_int(&mut _p.locate(warn, "Snap.delta_tick"))?,
+            num_parts: _p.read_int(&mut _p.locate(warn, "Snap.num_parts"))?,
+            part: _p.read_int(&mut _p.locate(warn, "Snap.part"))?,
+            crc: _p.read_int(&mut _p.locate(warn, "Snap.crc"))?,
+            data: _p.read_data(&mut _p.locate(warn, "Snap.data"))?,
+        })
+    }
+    pub fn new(tick: i32, delta_tick: i32, num_parts: i32, part: i32, crc: i32, data: &'a [u8]) -> Result<Snap<'a>, Error> {
+        let result = Snap {
+            tick,
+            delta_tick,
+            num_parts,
+            part,
+            crc,
+            data,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.tick)?;
         _p.write_int(self.delta_tick)?;
         _p.write_int(self.num_parts)?;
@@ -634,14 +1022,30 @@ impl<'a> fmt::Debug for Snap<'a> {
 
 impl SnapEmpty {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<SnapEmpty, Error> {
-        let result = Ok(SnapEmpty {
-            tick: _p.read_int(warn)?,
-            delta_tick: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        SnapEmpty::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SnapEmpty, Error> {
+        let result = SnapEmpty::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SnapEmpty"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<SnapEmpty, Error> {
+        Ok(SnapEmpty {
+            tick: _p.read_int(&mut _p.locate(warn, "SnapEmpty.tick"))?,
+            delta_tick: _p.read_int(&mut _p.locate(warn, "SnapEmpty.delta_tick"))?,
+        })
+    }
+    pub fn new(tick: i32, delta_tick: i32) -> Result<SnapEmpty, Error> {
+        let result = SnapEmpty {
+            tick,
+            delta_tick,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.tick)?;
         _p.write_int(self.delta_tick)?;
         Ok(_p.written())
@@ -658,16 +1062,34 @@ impl fmt::Debug for SnapEmpty {
 
 impl<'a> SnapSingle<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SnapSingle<'a>, Error> {
-        let result = Ok(SnapSingle {
-            tick: _p.read_int(warn)?,
-            delta_tick: _p.read_int(warn)?,
-            crc: _p.read_int(warn)?,
-            data: _p.read_data(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        SnapSingle::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SnapSingle<'a>, Error> {
+        let result = SnapSingle::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "SnapSingle"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<SnapSingle<'a>, Error> {
+        Ok(SnapSingle {
+            tick: _p.read_int(&mut _p.locate(warn, "SnapSingle.tick"))?,
+            delta_tick: _p.read_int(&mut _p.locate(warn, "SnapSingle.delta_tick"))?,
+            crc: _p.read_int(&mut _p.locate(warn, "SnapSingle.crc"))?,
+            data: _p.read_data(&mut _p.locate(warn, "SnapSingle.data"))?,
+        })
+    }
+    pub fn new(tick: i32, delta_tick: i32, crc: i32, data: &'a [u8]) -> Result<SnapSingle<'a>, Error> {
+        let result = SnapSingle {
+            tick,
+            delta_tick,
+            crc,
+            data,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.tick)?;
         _p.write_int(self.delta_tick)?;
         _p.write_int(self.crc)?;
@@ -688,14 +1110,30 @@ impl<'a> fmt::Debug for SnapSingle<'a> {
 
 impl InputTiming {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<InputTiming, Error> {
-        let result = Ok(InputTiming {
-            input_pred_tick: _p.read_int(warn)?,
-            time_left: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        InputTiming::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<InputTiming, Error> {
+        let result = InputTiming::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "InputTiming"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<InputTiming, Error> {
+        Ok(InputTiming {
+            input_pred_tick: _p.read_int(&mut _p.locate(warn, "InputTiming.input_pred_tick"))?,
+            time_left: _p.read_int(&mut _p.locate(warn, "InputTiming.time_left"))?,
+        })
+    }
+    pub fn new(input_pred_tick: i32, time_left: i32) -> Result<InputTiming, Error> {
+        let result = InputTiming {
+            input_pred_tick,
+            time_left,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.input_pred_tick)?;
         _p.write_int(self.time_left)?;
         Ok(_p.written())
@@ -712,11 +1150,15 @@ impl fmt::Debug for InputTiming {
 
 impl RconAuthOn {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RconAuthOn, Error> {
-        let result = Ok(RconAuthOn);
-        _p.finish(warn);
-        result
+        RconAuthOn::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RconAuthOn, Error> {
+        error::finish(&mut _p.locate(warn, "RconAuthOn"), _p, Ok(RconAuthOn))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -729,11 +1171,15 @@ impl fmt::Debug for RconAuthOn {
 
 impl RconAuthOff {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RconAuthOff, Error> {
-        let result = Ok(RconAuthOff);
-        _p.finish(warn);
-        result
+        RconAuthOff::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RconAuthOff, Error> {
+        error::finish(&mut _p.locate(warn, "RconAuthOff"), _p, Ok(RconAuthOff))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -746,13 +1192,28 @@ impl fmt::Debug for RconAuthOff {
 
 impl<'a> RconLine<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconLine<'a>, Error> {
-        let result = Ok(RconLine {
+        RconLine::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconLine<'a>, Error> {
+        let result = RconLine::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconLine"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconLine<'a>, Error> {
+        Ok(RconLine {
             line: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(line: &'a [u8]) -> Result<RconLine<'a>, Error> {
+        let result = RconLine {
+            line,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.line)?;
         Ok(_p.written())
     }
@@ -767,15 +1228,32 @@ impl<'a> fmt::Debug for RconLine<'a> {
 
 impl<'a> RconCmdAdd<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmdAdd<'a>, Error> {
-        let result = Ok(RconCmdAdd {
+        RconCmdAdd::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmdAdd<'a>, Error> {
+        let result = RconCmdAdd::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconCmdAdd"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmdAdd<'a>, Error> {
+        Ok(RconCmdAdd {
             name: _p.read_string()?,
             help: _p.read_string()?,
             params: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn new(name: &'a [u8], help: &'a [u8], params: &'a [u8]) -> Result<RconCmdAdd<'a>, Error> {
+        let result = RconCmdAdd {
+            name,
+            help,
+            params,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.name)?;
         _p.write_string(self.help)?;
         _p.write_string(self.params)?;
@@ -794,13 +1272,28 @@ impl<'a> fmt::Debug for RconCmdAdd<'a> {
 
 impl<'a> RconCmdRem<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmdRem<'a>, Error> {
-        let result = Ok(RconCmdRem {
+        RconCmdRem::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmdRem<'a>, Error> {
+        let result = RconCmdRem::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconCmdRem"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmdRem<'a>, Error> {
+        Ok(RconCmdRem {
             name: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(name: &'a [u8]) -> Result<RconCmdRem<'a>, Error> {
+        let result = RconCmdRem {
+            name,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.name)?;
         Ok(_p.written())
     }
@@ -815,11 +1308,15 @@ impl<'a> fmt::Debug for RconCmdRem<'a> {
 
 impl Ready {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Ready, Error> {
-        let result = Ok(Ready);
-        _p.finish(warn);
-        result
+        Ready::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Ready, Error> {
+        error::finish(&mut _p.locate(warn, "Ready"), _p, Ok(Ready))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -832,11 +1329,15 @@ impl fmt::Debug for Ready {
 
 impl EnterGame {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<EnterGame, Error> {
-        let result = Ok(EnterGame);
-        _p.finish(warn);
-        result
+        EnterGame::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<EnterGame, Error> {
+        error::finish(&mut _p.locate(warn, "EnterGame"), _p, Ok(EnterGame))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -849,16 +1350,34 @@ impl fmt::Debug for EnterGame {
 
 impl Input {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Input, Error> {
-        let result = Ok(Input {
-            ack_snapshot: _p.read_int(warn)?,
-            intended_tick: _p.read_int(warn)?,
-            input_size: _p.read_int(warn)?,
-            input: ::snap_obj::PlayerInput::decode_msg(warn, _p)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        Input::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Input, Error> {
+        let result = Input::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Input"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Input, Error> {
+        Ok(Input {
+            ack_snapshot: _p.read_int(&mut _p.locate(warn, "Input.ack_snapshot"))?,
+            intended_tick: _p.read_int(&mut _p.locate(warn, "Input.intended_tick"))?,
+            input_size: _p.read_int(&mut _p.locate(warn, "Input.input_size"))?,
+            input: ::snap_obj::PlayerInput::decode_msg_located(warn, _p)?,
+        })
+    }
+    pub fn new(ack_snapshot: i32, intended_tick: i32, input_size: i32, input: ::snap_obj::PlayerInput) -> Result<Input, Error> {
+        let result = Input {
+            ack_snapshot,
+            intended_tick,
+            input_size,
+            input,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.ack_snapshot)?;
         _p.write_int(self.intended_tick)?;
         _p.write_int(self.input_size)?;
@@ -879,13 +1398,28 @@ impl fmt::Debug for Input {
 
 impl<'a> RconCmd<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmd<'a>, Error> {
-        let result = Ok(RconCmd {
+        RconCmd::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmd<'a>, Error> {
+        let result = RconCmd::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconCmd"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconCmd<'a>, Error> {
+        Ok(RconCmd {
             cmd: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(cmd: &'a [u8]) -> Result<RconCmd<'a>, Error> {
+        let result = RconCmd {
+            cmd,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.cmd)?;
         Ok(_p.written())
     }
@@ -900,13 +1434,28 @@ impl<'a> fmt::Debug for RconCmd<'a> {
 
 impl<'a> RconAuth<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconAuth<'a>, Error> {
-        let result = Ok(RconAuth {
+        RconAuth::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconAuth<'a>, Error> {
+        let result = RconAuth::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "RconAuth"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<RconAuth<'a>, Error> {
+        Ok(RconAuth {
             password: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(password: &'a [u8]) -> Result<RconAuth<'a>, Error> {
+        let result = RconAuth {
+            password,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.password)?;
         Ok(_p.written())
     }
@@ -921,11 +1470,15 @@ impl<'a> fmt::Debug for RconAuth<'a> {
 
 impl RequestMapData {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestMapData, Error> {
-        let result = Ok(RequestMapData);
-        _p.finish(warn);
-        result
+        RequestMapData::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<RequestMapData, Error> {
+        error::finish(&mut _p.locate(warn, "RequestMapData"), _p, Ok(RequestMapData))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -938,11 +1491,15 @@ impl fmt::Debug for RequestMapData {
 
 impl Ping {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Ping, Error> {
-        let result = Ok(Ping);
-        _p.finish(warn);
-        result
+        Ping::decode_located(&mut strip_location(warn), _p)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Ping, Error> {
+        error::finish(&mut _p.locate(warn, "Ping"), _p, Ok(Ping))
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -955,11 +1512,15 @@ impl fmt::Debug for Ping {
 
 impl PingReply {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<PingReply, Error> {
-        let result = Ok(PingReply);
-        _p.finish(warn);
-        result
+        PingReply::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<PingReply, Error> {
+        error::finish(&mut _p.locate(warn, "PingReply"), _p, Ok(PingReply))
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         Ok(_p.written())
     }
 }
@@ -972,13 +1533,28 @@ impl fmt::Debug for PingReply {
 
 impl<'a> MaplistEntryAdd<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MaplistEntryAdd<'a>, Error> {
-        let result = Ok(MaplistEntryAdd {
+        MaplistEntryAdd::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MaplistEntryAdd<'a>, Error> {
+        let result = MaplistEntryAdd::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "MaplistEntryAdd"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MaplistEntryAdd<'a>, Error> {
+        Ok(MaplistEntryAdd {
             name: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(name: &'a [u8]) -> Result<MaplistEntryAdd<'a>, Error> {
+        let result = MaplistEntryAdd {
+            name,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.name)?;
         Ok(_p.written())
     }
@@ -993,13 +1569,28 @@ impl<'a> fmt::Debug for MaplistEntryAdd<'a> {
 
 impl<'a> MaplistEntryRem<'a> {
     pub fn decode<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MaplistEntryRem<'a>, Error> {
-        let result = Ok(MaplistEntryRem {
+        MaplistEntryRem::decode_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MaplistEntryRem<'a>, Error> {
+        let result = MaplistEntryRem::decode_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "MaplistEntryRem"), _p, result)
+    }
+    fn decode_fields<W: Warn<Located<Warning>>>(_warn: &mut W, _p: &mut Unpacker<'a>) -> Result<MaplistEntryRem<'a>, Error> {
+        Ok(MaplistEntryRem {
             name: _p.read_string()?,
-        });
-        _p.finish(warn);
-        result
+        })
+    }
+    pub fn new(name: &'a [u8]) -> Result<MaplistEntryRem<'a>, Error> {
+        let result = MaplistEntryRem {
+            name,
+        };
+        result.validate()?;
+        Ok(result)
     }
-    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_string(self.name)?;
         Ok(_p.written())
     }
@@ -1012,3 +1603,323 @@ impl<'a> fmt::Debug for MaplistEntryRem<'a> {
     }
 }
 
+impl<'a> Info<'a> {
+    pub fn to_owned(&self) -> InfoOwned {
+        InfoOwned {
+            version: self.version.to_vec(),
+            password: self.password.map(|v| v.to_vec()),
+            client_version: self.client_version,
+        }
+    }
+}
+
+impl InfoOwned {
+    pub fn as_ref(&self) -> Info<'_> {
+        Info {
+            version: &self.version[..],
+            password: self.password.as_ref().map(|v| &v[..]),
+            client_version: self.client_version,
+        }
+    }
+}
+
+impl fmt::Debug for InfoOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> MapChange<'a> {
+    pub fn to_owned(&self) -> MapChangeOwned {
+        MapChangeOwned {
+            name: self.name.to_vec(),
+            crc: self.crc,
+            size: self.size,
+            num_response_chunks_per_request: self.num_response_chunks_per_request,
+            chunk_size: self.chunk_size,
+            sha256: self.sha256,
+        }
+    }
+}
+
+impl MapChangeOwned {
+    pub fn as_ref(&self) -> MapChange<'_> {
+        MapChange {
+            name: &self.name[..],
+            crc: self.crc,
+            size: self.size,
+            num_response_chunks_per_request: self.num_response_chunks_per_request,
+            chunk_size: self.chunk_size,
+            sha256: self.sha256,
+        }
+    }
+}
+
+impl fmt::Debug for MapChangeOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> MapData<'a> {
+    pub fn to_owned(&self) -> MapDataOwned {
+        MapDataOwned {
+            data: self.data.to_vec(),
+        }
+    }
+}
+
+impl MapDataOwned {
+    pub fn as_ref(&self) -> MapData<'_> {
+        MapData {
+            data: &self.data[..],
+        }
+    }
+}
+
+impl fmt::Debug for MapDataOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> ServerInfo<'a> {
+    pub fn to_owned(&self) -> ServerInfoOwned {
+        ServerInfoOwned {
+            data: self.data.to_vec(),
+        }
+    }
+}
+
+impl ServerInfoOwned {
+    pub fn as_ref(&self) -> ServerInfo<'_> {
+        ServerInfo {
+            data: &self.data[..],
+        }
+    }
+}
+
+impl fmt::Debug for ServerInfoOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> Snap<'a> {
+    pub fn to_owned(&self) -> SnapOwned {
+        SnapOwned {
+            tick: self.tick,
+            delta_tick: self.delta_tick,
+            num_parts: self.num_parts,
+            part: self.part,
+            crc: self.crc,
+            data: self.data.to_vec(),
+        }
+    }
+}
+
+impl SnapOwned {
+    pub fn as_ref(&self) -> Snap<'_> {
+        Snap {
+            tick: self.tick,
+            delta_tick: self.delta_tick,
+            num_parts: self.num_parts,
+            part: self.part,
+            crc: self.crc,
+            data: &self.data[..],
+        }
+    }
+}
+
+impl fmt::Debug for SnapOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> SnapSingle<'a> {
+    pub fn to_owned(&self) -> SnapSingleOwned {
+        SnapSingleOwned {
+            tick: self.tick,
+            delta_tick: self.delta_tick,
+            crc: self.crc,
+            data: self.data.to_vec(),
+        }
+    }
+}
+
+impl SnapSingleOwned {
+    pub fn as_ref(&self) -> SnapSingle<'_> {
+        SnapSingle {
+            tick: self.tick,
+            delta_tick: self.delta_tick,
+            crc: self.crc,
+            data: &self.data[..],
+        }
+    }
+}
+
+impl fmt::Debug for SnapSingleOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> RconLine<'a> {
+    pub fn to_owned(&self) -> RconLineOwned {
+        RconLineOwned {
+            line: self.line.to_vec(),
+        }
+    }
+}
+
+impl RconLineOwned {
+    pub fn as_ref(&self) -> RconLine<'_> {
+        RconLine {
+            line: &self.line[..],
+        }
+    }
+}
+
+impl fmt::Debug for RconLineOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> RconCmdAdd<'a> {
+    pub fn to_owned(&self) -> RconCmdAddOwned {
+        RconCmdAddOwned {
+            name: self.name.to_vec(),
+            help: self.help.to_vec(),
+            params: self.params.to_vec(),
+        }
+    }
+}
+
+impl RconCmdAddOwned {
+    pub fn as_ref(&self) -> RconCmdAdd<'_> {
+        RconCmdAdd {
+            name: &self.name[..],
+            help: &self.help[..],
+            params: &self.params[..],
+        }
+    }
+}
+
+impl fmt::Debug for RconCmdAddOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> RconCmdRem<'a> {
+    pub fn to_owned(&self) -> RconCmdRemOwned {
+        RconCmdRemOwned {
+            name: self.name.to_vec(),
+        }
+    }
+}
+
+impl RconCmdRemOwned {
+    pub fn as_ref(&self) -> RconCmdRem<'_> {
+        RconCmdRem {
+            name: &self.name[..],
+        }
+    }
+}
+
+impl fmt::Debug for RconCmdRemOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> RconCmd<'a> {
+    pub fn to_owned(&self) -> RconCmdOwned {
+        RconCmdOwned {
+            cmd: self.cmd.to_vec(),
+        }
+    }
+}
+
+impl RconCmdOwned {
+    pub fn as_ref(&self) -> RconCmd<'_> {
+        RconCmd {
+            cmd: &self.cmd[..],
+        }
+    }
+}
+
+impl fmt::Debug for RconCmdOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> RconAuth<'a> {
+    pub fn to_owned(&self) -> RconAuthOwned {
+        RconAuthOwned {
+            password: self.password.to_vec(),
+        }
+    }
+}
+
+impl RconAuthOwned {
+    pub fn as_ref(&self) -> RconAuth<'_> {
+        RconAuth {
+            password: &self.password[..],
+        }
+    }
+}
+
+impl fmt::Debug for RconAuthOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> MaplistEntryAdd<'a> {
+    pub fn to_owned(&self) -> MaplistEntryAddOwned {
+        MaplistEntryAddOwned {
+            name: self.name.to_vec(),
+        }
+    }
+}
+
+impl MaplistEntryAddOwned {
+    pub fn as_ref(&self) -> MaplistEntryAdd<'_> {
+        MaplistEntryAdd {
+            name: &self.name[..],
+        }
+    }
+}
+
+impl fmt::Debug for MaplistEntryAddOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<'a> MaplistEntryRem<'a> {
+    pub fn to_owned(&self) -> MaplistEntryRemOwned {
+        MaplistEntryRemOwned {
+            name: self.name.to_vec(),
+        }
+    }
+}
+
+impl MaplistEntryRemOwned {
+    pub fn as_ref(&self) -> MaplistEntryRem<'_> {
+        MaplistEntryRem {
+            name: &self.name[..],
+        }
+    }
+}
+
+impl fmt::Debug for MaplistEntryRemOwned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
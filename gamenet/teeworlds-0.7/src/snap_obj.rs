@@ -1,7 +1,14 @@
-use buffer::CapacityError;
 use common::slice;
+use common::warn::Located;
+use common::warn::strip_location;
 use enums;
+use error;
+use error::EncodeError;
 use error::Error;
+use gamenet_common::snap_obj::FieldKind;
+use gamenet_common::snap_obj::FieldMeta;
+use gamenet_common::snap_obj::ObjMeta;
+use gamenet_common::snap_obj::wrap_angle;
 use packer::ExcessData;
 use packer::IntUnpacker;
 use packer::Packer;
@@ -74,6 +81,7 @@ pub const PLAYER_INFO_RACE: u16 = 23;
 pub const GAME_DATA_RACE: u16 = 24;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SnapObj {
     PlayerInput(PlayerInput),
     Projectile(Projectile),
@@ -160,7 +168,35 @@ impl SnapObj {
             SnapObj::GameDataRace(_) => TypeId::from(GAME_DATA_RACE),
         }
     }
-    pub fn encode(&self) -> &[i32] {
+    pub fn validate(&self) -> Result<(), Error> {
+        match *self {
+            SnapObj::PlayerInput(ref i) => i.validate(),
+            SnapObj::Projectile(ref i) => i.validate(),
+            SnapObj::Laser(ref i) => i.validate(),
+            SnapObj::Pickup(ref i) => i.validate(),
+            SnapObj::Flag(ref i) => i.validate(),
+            SnapObj::GameData(ref i) => i.validate(),
+            SnapObj::GameDataTeam(ref i) => i.validate(),
+            SnapObj::GameDataFlag(ref i) => i.validate(),
+            SnapObj::CharacterCore(ref i) => i.validate(),
+            SnapObj::Character(ref i) => i.validate(),
+            SnapObj::PlayerInfo(ref i) => i.validate(),
+            SnapObj::SpectatorInfo(ref i) => i.validate(),
+            SnapObj::DeClientInfo(ref i) => i.validate(),
+            SnapObj::DeGameInfo(ref i) => i.validate(),
+            SnapObj::DeTuneParams(ref i) => i.validate(),
+            SnapObj::Common(ref i) => i.validate(),
+            SnapObj::Explosion(ref i) => i.validate(),
+            SnapObj::Spawn(ref i) => i.validate(),
+            SnapObj::HammerHit(ref i) => i.validate(),
+            SnapObj::Death(ref i) => i.validate(),
+            SnapObj::SoundWorld(ref i) => i.validate(),
+            SnapObj::Damage(ref i) => i.validate(),
+            SnapObj::PlayerInfoRace(ref i) => i.validate(),
+            SnapObj::GameDataRace(ref i) => i.validate(),
+        }
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
         match *self {
             SnapObj::PlayerInput(ref i) => i.encode(),
             SnapObj::Projectile(ref i) => i.encode(),
@@ -366,7 +402,8 @@ impl From<GameDataRace> for SnapObj {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PlayerInput {
     pub direction: i32,
     pub target_x: i32,
@@ -382,17 +419,20 @@ pub struct PlayerInput {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Projectile {
     pub x: i32,
     pub y: i32,
     pub vel_x: i32,
     pub vel_y: i32,
-    pub type_: enums::Weapon,
+    /// Raw `enums::Weapon` value, convert it using `enums::Weapon::from_i32`.
+    pub type_: i32,
     pub start_tick: ::snap_obj::Tick,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Laser {
     pub x: i32,
     pub y: i32,
@@ -403,14 +443,17 @@ pub struct Laser {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Pickup {
     pub x: i32,
     pub y: i32,
-    pub type_: enums::Pickup,
+    /// Raw `enums::Pickup` value, convert it using `enums::Pickup::from_i32`.
+    pub type_: i32,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Flag {
     pub x: i32,
     pub y: i32,
@@ -419,6 +462,7 @@ pub struct Flag {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GameData {
     pub game_start_tick: ::snap_obj::Tick,
     pub game_state_flags: i32,
@@ -427,6 +471,7 @@ pub struct GameData {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GameDataTeam {
     pub teamscore_red: i32,
     pub teamscore_blue: i32,
@@ -434,6 +479,7 @@ pub struct GameDataTeam {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GameDataFlag {
     pub flag_carrier_red: i32,
     pub flag_carrier_blue: i32,
@@ -443,6 +489,7 @@ pub struct GameDataFlag {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CharacterCore {
     pub tick: ::snap_obj::Tick,
     pub x: i32,
@@ -463,19 +510,23 @@ pub struct CharacterCore {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Character {
     pub character_core: CharacterCore,
     pub health: i32,
     pub armor: i32,
     pub ammo_count: i32,
-    pub weapon: enums::Weapon,
-    pub emote: enums::Emote,
+    /// Raw `enums::Weapon` value, convert it using `enums::Weapon::from_i32`.
+    pub weapon: i32,
+    /// Raw `enums::Emote` value, convert it using `enums::Emote::from_i32`.
+    pub emote: i32,
     pub attack_tick: ::snap_obj::Tick,
     pub triggered_events: i32,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PlayerInfo {
     pub player_flags: i32,
     pub score: i32,
@@ -484,8 +535,10 @@ pub struct PlayerInfo {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SpectatorInfo {
-    pub spec_mode: enums::Spec,
+    /// Raw `enums::Spec` value, convert it using `enums::Spec::from_i32`.
+    pub spec_mode: i32,
     pub spectator_id: i32,
     pub x: i32,
     pub y: i32,
@@ -493,9 +546,11 @@ pub struct SpectatorInfo {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DeClientInfo {
     pub local: bool,
-    pub team: enums::Team,
+    /// Raw `enums::Team` value, convert it using `enums::Team::from_i32`.
+    pub team: i32,
     pub name: [i32; 4],
     pub clan: [i32; 3],
     pub country: i32,
@@ -506,6 +561,7 @@ pub struct DeClientInfo {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DeGameInfo {
     pub game_flags: i32,
     pub score_limit: i32,
@@ -516,12 +572,14 @@ pub struct DeGameInfo {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DeTuneParams {
     pub tune_params: [i32; 32],
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Common {
     pub x: i32,
     pub y: i32,
@@ -529,24 +587,28 @@ pub struct Common {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Explosion {
     pub common: Common,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Spawn {
     pub common: Common,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HammerHit {
     pub common: Common,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Death {
     pub common: Common,
     pub client_id: i32,
@@ -554,13 +616,16 @@ pub struct Death {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SoundWorld {
     pub common: Common,
-    pub sound_id: enums::Sound,
+    /// Raw `enums::Sound` value, convert it using `enums::Sound::from_i32`.
+    pub sound_id: i32,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Damage {
     pub common: Common,
     pub client_id: i32,
@@ -572,12 +637,14 @@ pub struct Damage {
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PlayerInfoRace {
     pub race_start_tick: ::snap_obj::Tick,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GameDataRace {
     pub best_time: i32,
     pub precision: i32,
@@ -620,32 +687,59 @@ impl PlayerInput {
             prev_weapon: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(-1 <= self.direction && self.direction <= 1);
-        assert!(0 <= self.wanted_weapon && self.wanted_weapon <= 6);
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(direction: i32, target_x: i32, target_y: i32, jump: bool, fire: i32, hook: bool, player_flags: i32, wanted_weapon: i32, next_weapon: i32, prev_weapon: i32) -> Result<PlayerInput, Error> {
+        let result = PlayerInput {
+            direction,
+            target_x,
+            target_y,
+            jump,
+            fire,
+            hook,
+            player_flags,
+            wanted_weapon,
+            next_weapon,
+            prev_weapon,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.direction, -1, 1)?;
+        in_range(self.wanted_weapon, 0, 6)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.direction, -1, 1)?;
+        in_range(self.wanted_weapon, 0, 6)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 impl PlayerInput {
     pub fn decode_msg<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<PlayerInput, Error> {
-        let result = Ok(PlayerInput {
-            direction: in_range(_p.read_int(warn)?, -1, 1)?,
-            target_x: _p.read_int(warn)?,
-            target_y: _p.read_int(warn)?,
-            jump: to_bool(_p.read_int(warn)?)?,
-            fire: _p.read_int(warn)?,
-            hook: to_bool(_p.read_int(warn)?)?,
-            player_flags: _p.read_int(warn)?,
-            wanted_weapon: in_range(_p.read_int(warn)?, 0, 6)?,
-            next_weapon: _p.read_int(warn)?,
-            prev_weapon: _p.read_int(warn)?,
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode_msg<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
-        assert!(-1 <= self.direction && self.direction <= 1);
-        assert!(0 <= self.wanted_weapon && self.wanted_weapon <= 6);
+        PlayerInput::decode_msg_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_msg_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<PlayerInput, Error> {
+        let result = PlayerInput::decode_msg_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "PlayerInput"), _p, result)
+    }
+    fn decode_msg_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<PlayerInput, Error> {
+        Ok(PlayerInput {
+            direction: in_range(_p.read_int(&mut _p.locate(warn, "PlayerInput.direction"))?, -1, 1)?,
+            target_x: _p.read_int(&mut _p.locate(warn, "PlayerInput.target_x"))?,
+            target_y: _p.read_int(&mut _p.locate(warn, "PlayerInput.target_y"))?,
+            jump: to_bool(_p.read_int(&mut _p.locate(warn, "PlayerInput.jump"))?)?,
+            fire: _p.read_int(&mut _p.locate(warn, "PlayerInput.fire"))?,
+            hook: to_bool(_p.read_int(&mut _p.locate(warn, "PlayerInput.hook"))?)?,
+            player_flags: _p.read_int(&mut _p.locate(warn, "PlayerInput.player_flags"))?,
+            wanted_weapon: in_range(_p.read_int(&mut _p.locate(warn, "PlayerInput.wanted_weapon"))?, 0, 6)?,
+            next_weapon: _p.read_int(&mut _p.locate(warn, "PlayerInput.next_weapon"))?,
+            prev_weapon: _p.read_int(&mut _p.locate(warn, "PlayerInput.prev_weapon"))?,
+        })
+    }
+    pub fn encode_msg<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
+        in_range(self.direction, -1, 1)?;
+        in_range(self.wanted_weapon, 0, 6)?;
         _p.write_int(self.direction)?;
         _p.write_int(self.target_x)?;
         _p.write_int(self.target_y)?;
@@ -667,7 +761,7 @@ impl fmt::Debug for Projectile {
             .field("y", &self.y)
             .field("vel_x", &self.vel_x)
             .field("vel_y", &self.vel_y)
-            .field("type_", &self.type_)
+            .field("type_", &enums::Weapon::from_i32(self.type_))
             .field("start_tick", &self.start_tick)
             .finish()
     }
@@ -684,33 +778,54 @@ impl Projectile {
             y: _p.read_int()?,
             vel_x: _p.read_int()?,
             vel_y: _p.read_int()?,
-            type_: enums::Weapon::from_i32(_p.read_int()?)?,
+            type_: _p.read_int()?,
             start_tick: ::snap_obj::Tick(_p.read_int()?),
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32, vel_x: i32, vel_y: i32, type_: i32, start_tick: ::snap_obj::Tick) -> Result<Projectile, Error> {
+        let result = Projectile {
+            x,
+            y,
+            vel_x,
+            vel_y,
+            type_,
+            start_tick,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        enums::Weapon::from_i32(self.type_).known()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 impl Projectile {
     pub fn decode_msg<W: Warn<Warning>>(warn: &mut W, _p: &mut Unpacker) -> Result<Projectile, Error> {
-        let result = Ok(Projectile {
-            x: _p.read_int(warn)?,
-            y: _p.read_int(warn)?,
-            vel_x: _p.read_int(warn)?,
-            vel_y: _p.read_int(warn)?,
-            type_: enums::Weapon::from_i32(_p.read_int(warn)?)?,
-            start_tick: ::snap_obj::Tick(_p.read_int(warn)?),
-        });
-        _p.finish(warn);
-        result
-    }
-    pub fn encode_msg<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], CapacityError> {
+        Projectile::decode_msg_located(&mut strip_location(warn), _p)
+    }
+    pub fn decode_msg_located<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Projectile, Error> {
+        let result = Projectile::decode_msg_fields(warn, _p);
+        error::finish(&mut _p.locate(warn, "Projectile"), _p, result)
+    }
+    fn decode_msg_fields<W: Warn<Located<Warning>>>(warn: &mut W, _p: &mut Unpacker) -> Result<Projectile, Error> {
+        Ok(Projectile {
+            x: _p.read_int(&mut _p.locate(warn, "Projectile.x"))?,
+            y: _p.read_int(&mut _p.locate(warn, "Projectile.y"))?,
+            vel_x: _p.read_int(&mut _p.locate(warn, "Projectile.vel_x"))?,
+            vel_y: _p.read_int(&mut _p.locate(warn, "Projectile.vel_y"))?,
+            type_: _p.read_int(&mut _p.locate(warn, "Projectile.type_"))?,
+            start_tick: ::snap_obj::Tick(_p.read_int(&mut _p.locate(warn, "Projectile.start_tick"))?),
+        })
+    }
+    pub fn encode_msg<'d, 's>(&self, mut _p: Packer<'d, 's>) -> Result<&'d [u8], EncodeError> {
         _p.write_int(self.x)?;
         _p.write_int(self.y)?;
         _p.write_int(self.vel_x)?;
         _p.write_int(self.vel_y)?;
-        _p.write_int(self.type_.to_i32())?;
+        _p.write_int(self.type_)?;
         _p.write_int(self.start_tick.0)?;
         Ok(_p.written())
     }
@@ -742,8 +857,22 @@ impl Laser {
             start_tick: ::snap_obj::Tick(_p.read_int()?),
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32, from_x: i32, from_y: i32, start_tick: ::snap_obj::Tick) -> Result<Laser, Error> {
+        let result = Laser {
+            x,
+            y,
+            from_x,
+            from_y,
+            start_tick,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -752,7 +881,7 @@ impl fmt::Debug for Pickup {
         f.debug_struct("Pickup")
             .field("x", &self.x)
             .field("y", &self.y)
-            .field("type_", &self.type_)
+            .field("type_", &enums::Pickup::from_i32(self.type_))
             .finish()
     }
 }
@@ -766,11 +895,24 @@ impl Pickup {
         Ok(Pickup {
             x: _p.read_int()?,
             y: _p.read_int()?,
-            type_: enums::Pickup::from_i32(_p.read_int()?)?,
+            type_: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32, type_: i32) -> Result<Pickup, Error> {
+        let result = Pickup {
+            x,
+            y,
+            type_,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        enums::Pickup::from_i32(self.type_).known()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -796,9 +938,22 @@ impl Flag {
             team: in_range(_p.read_int()?, 0, 1)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(0 <= self.team && self.team <= 1);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32, team: i32) -> Result<Flag, Error> {
+        let result = Flag {
+            x,
+            y,
+            team,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.team, 0, 1)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.team, 0, 1)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -824,8 +979,20 @@ impl GameData {
             game_state_end_tick: ::snap_obj::Tick(_p.read_int()?),
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(game_start_tick: ::snap_obj::Tick, game_state_flags: i32, game_state_end_tick: ::snap_obj::Tick) -> Result<GameData, Error> {
+        let result = GameData {
+            game_start_tick,
+            game_state_flags,
+            game_state_end_tick,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -849,8 +1016,19 @@ impl GameDataTeam {
             teamscore_blue: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(teamscore_red: i32, teamscore_blue: i32) -> Result<GameDataTeam, Error> {
+        let result = GameDataTeam {
+            teamscore_red,
+            teamscore_blue,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -878,10 +1056,25 @@ impl GameDataFlag {
             flag_drop_tick_blue: ::snap_obj::Tick(_p.read_int()?),
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(-3 <= self.flag_carrier_red && self.flag_carrier_red <= 63);
-        assert!(-3 <= self.flag_carrier_blue && self.flag_carrier_blue <= 63);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(flag_carrier_red: i32, flag_carrier_blue: i32, flag_drop_tick_red: ::snap_obj::Tick, flag_drop_tick_blue: ::snap_obj::Tick) -> Result<GameDataFlag, Error> {
+        let result = GameDataFlag {
+            flag_carrier_red,
+            flag_carrier_blue,
+            flag_drop_tick_red,
+            flag_drop_tick_blue,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.flag_carrier_red, -3, 63)?;
+        in_range(self.flag_carrier_blue, -3, 63)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.flag_carrier_red, -3, 63)?;
+        in_range(self.flag_carrier_blue, -3, 63)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -931,12 +1124,41 @@ impl CharacterCore {
             hook_dy: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(-1 <= self.direction && self.direction <= 1);
-        assert!(0 <= self.jumped && self.jumped <= 3);
-        assert!(-1 <= self.hooked_player && self.hooked_player <= 63);
-        assert!(-1 <= self.hook_state && self.hook_state <= 5);
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(tick: ::snap_obj::Tick, x: i32, y: i32, vel_x: i32, vel_y: i32, angle: i32, direction: i32, jumped: i32, hooked_player: i32, hook_state: i32, hook_tick: ::snap_obj::Tick, hook_x: i32, hook_y: i32, hook_dx: i32, hook_dy: i32) -> Result<CharacterCore, Error> {
+        let result = CharacterCore {
+            tick,
+            x,
+            y,
+            vel_x,
+            vel_y,
+            angle: wrap_angle(angle),
+            direction,
+            jumped,
+            hooked_player,
+            hook_state,
+            hook_tick,
+            hook_x,
+            hook_y,
+            hook_dx,
+            hook_dy,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        in_range(self.direction, -1, 1)?;
+        in_range(self.jumped, 0, 3)?;
+        in_range(self.hooked_player, -1, 63)?;
+        in_range(self.hook_state, -1, 5)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.direction, -1, 1)?;
+        in_range(self.jumped, 0, 3)?;
+        in_range(self.hooked_player, -1, 63)?;
+        in_range(self.hook_state, -1, 5)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -947,8 +1169,8 @@ impl fmt::Debug for Character {
             .field("health", &self.health)
             .field("armor", &self.armor)
             .field("ammo_count", &self.ammo_count)
-            .field("weapon", &self.weapon)
-            .field("emote", &self.emote)
+            .field("weapon", &enums::Weapon::from_i32(self.weapon))
+            .field("emote", &enums::Emote::from_i32(self.emote))
             .field("attack_tick", &self.attack_tick)
             .field("triggered_events", &self.triggered_events)
             .finish()
@@ -966,17 +1188,40 @@ impl Character {
             health: in_range(_p.read_int()?, 0, 10)?,
             armor: in_range(_p.read_int()?, 0, 10)?,
             ammo_count: _p.read_int()?,
-            weapon: enums::Weapon::from_i32(_p.read_int()?)?,
-            emote: enums::Emote::from_i32(_p.read_int()?)?,
+            weapon: _p.read_int()?,
+            emote: _p.read_int()?,
             attack_tick: ::snap_obj::Tick(_p.read_int()?),
             triggered_events: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.character_core.encode();
-        assert!(0 <= self.health && self.health <= 10);
-        assert!(0 <= self.armor && self.armor <= 10);
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(character_core: CharacterCore, health: i32, armor: i32, ammo_count: i32, weapon: i32, emote: i32, attack_tick: ::snap_obj::Tick, triggered_events: i32) -> Result<Character, Error> {
+        let result = Character {
+            character_core,
+            health,
+            armor,
+            ammo_count,
+            weapon,
+            emote,
+            attack_tick,
+            triggered_events,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.character_core.validate()?;
+        in_range(self.health, 0, 10)?;
+        in_range(self.armor, 0, 10)?;
+        enums::Weapon::from_i32(self.weapon).known()?;
+        enums::Emote::from_i32(self.emote).known()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.character_core.encode()?;
+        in_range(self.health, 0, 10)?;
+        in_range(self.armor, 0, 10)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1002,15 +1247,27 @@ impl PlayerInfo {
             latency: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(player_flags: i32, score: i32, latency: i32) -> Result<PlayerInfo, Error> {
+        let result = PlayerInfo {
+            player_flags,
+            score,
+            latency,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
 impl fmt::Debug for SpectatorInfo {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("SpectatorInfo")
-            .field("spec_mode", &self.spec_mode)
+            .field("spec_mode", &enums::Spec::from_i32(self.spec_mode))
             .field("spectator_id", &self.spectator_id)
             .field("x", &self.x)
             .field("y", &self.y)
@@ -1025,15 +1282,30 @@ impl SpectatorInfo {
     }
     pub fn decode_inner(_p: &mut IntUnpacker) -> Result<SpectatorInfo, Error> {
         Ok(SpectatorInfo {
-            spec_mode: enums::Spec::from_i32(_p.read_int()?)?,
+            spec_mode: _p.read_int()?,
             spectator_id: in_range(_p.read_int()?, -1, 63)?,
             x: _p.read_int()?,
             y: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(-1 <= self.spectator_id && self.spectator_id <= 63);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(spec_mode: i32, spectator_id: i32, x: i32, y: i32) -> Result<SpectatorInfo, Error> {
+        let result = SpectatorInfo {
+            spec_mode,
+            spectator_id,
+            x,
+            y,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        enums::Spec::from_i32(self.spec_mode).known()?;
+        in_range(self.spectator_id, -1, 63)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        in_range(self.spectator_id, -1, 63)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1041,7 +1313,7 @@ impl fmt::Debug for DeClientInfo {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("DeClientInfo")
             .field("local", &self.local)
-            .field("team", &self.team)
+            .field("team", &enums::Team::from_i32(self.team))
             .field("name", &self.name)
             .field("clan", &self.clan)
             .field("country", &self.country)
@@ -1060,7 +1332,7 @@ impl DeClientInfo {
     pub fn decode_inner(_p: &mut IntUnpacker) -> Result<DeClientInfo, Error> {
         Ok(DeClientInfo {
             local: to_bool(_p.read_int()?)?,
-            team: enums::Team::from_i32(_p.read_int()?)?,
+            team: _p.read_int()?,
             name: [
                 _p.read_int()?,
                 _p.read_int()?,
@@ -1141,8 +1413,27 @@ impl DeClientInfo {
             ],
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(local: bool, team: i32, name: [i32; 4], clan: [i32; 3], country: i32, skin_part_names: [[i32; 6]; 6], use_custom_colors: [bool; 6], skin_part_colors: [i32; 6]) -> Result<DeClientInfo, Error> {
+        let result = DeClientInfo {
+            local,
+            team,
+            name,
+            clan,
+            country,
+            skin_part_names,
+            use_custom_colors,
+            skin_part_colors,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        enums::Team::from_i32(self.team).known()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1172,12 +1463,30 @@ impl DeGameInfo {
             match_current: positive(_p.read_int()?)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(self.score_limit >= 0);
-        assert!(self.time_limit >= 0);
-        assert!(self.match_num >= 0);
-        assert!(self.match_current >= 0);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(game_flags: i32, score_limit: i32, time_limit: i32, match_num: i32, match_current: i32) -> Result<DeGameInfo, Error> {
+        let result = DeGameInfo {
+            game_flags,
+            score_limit,
+            time_limit,
+            match_num,
+            match_current,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        positive(self.score_limit)?;
+        positive(self.time_limit)?;
+        positive(self.match_num)?;
+        positive(self.match_current)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        positive(self.score_limit)?;
+        positive(self.time_limit)?;
+        positive(self.match_num)?;
+        positive(self.match_current)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1232,8 +1541,18 @@ impl DeTuneParams {
             ],
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(tune_params: [i32; 32]) -> Result<DeTuneParams, Error> {
+        let result = DeTuneParams {
+            tune_params,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1257,8 +1576,19 @@ impl Common {
             y: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(x: i32, y: i32) -> Result<Common, Error> {
+        let result = Common {
+            x,
+            y,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1280,9 +1610,20 @@ impl Explosion {
             common: Common::decode_inner(_p)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common) -> Result<Explosion, Error> {
+        let result = Explosion {
+            common,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1304,9 +1645,20 @@ impl Spawn {
             common: Common::decode_inner(_p)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common) -> Result<Spawn, Error> {
+        let result = Spawn {
+            common,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1328,9 +1680,20 @@ impl HammerHit {
             common: Common::decode_inner(_p)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common) -> Result<HammerHit, Error> {
+        let result = HammerHit {
+            common,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1354,10 +1717,23 @@ impl Death {
             client_id: in_range(_p.read_int()?, 0, 63)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        assert!(0 <= self.client_id && self.client_id <= 63);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common, client_id: i32) -> Result<Death, Error> {
+        let result = Death {
+            common,
+            client_id,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        in_range(self.client_id, 0, 63)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        in_range(self.client_id, 0, 63)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1365,7 +1741,7 @@ impl fmt::Debug for SoundWorld {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("SoundWorld")
             .field("common", &self.common)
-            .field("sound_id", &self.sound_id)
+            .field("sound_id", &enums::Sound::from_i32(self.sound_id))
             .finish()
     }
 }
@@ -1378,12 +1754,25 @@ impl SoundWorld {
     pub fn decode_inner(_p: &mut IntUnpacker) -> Result<SoundWorld, Error> {
         Ok(SoundWorld {
             common: Common::decode_inner(_p)?,
-            sound_id: enums::Sound::from_i32(_p.read_int()?)?,
+            sound_id: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common, sound_id: i32) -> Result<SoundWorld, Error> {
+        let result = SoundWorld {
+            common,
+            sound_id,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        enums::Sound::from_i32(self.sound_id).known()?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1415,12 +1804,31 @@ impl Damage {
             self_: to_bool(_p.read_int()?)?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        self.common.encode();
-        assert!(0 <= self.client_id && self.client_id <= 63);
-        assert!(0 <= self.health_amount && self.health_amount <= 9);
-        assert!(0 <= self.armor_amount && self.armor_amount <= 9);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(common: Common, client_id: i32, angle: i32, health_amount: i32, armor_amount: i32, self_: bool) -> Result<Damage, Error> {
+        let result = Damage {
+            common,
+            client_id,
+            angle: wrap_angle(angle),
+            health_amount,
+            armor_amount,
+            self_,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        self.common.validate()?;
+        in_range(self.client_id, 0, 63)?;
+        in_range(self.health_amount, 0, 9)?;
+        in_range(self.armor_amount, 0, 9)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        self.common.encode()?;
+        in_range(self.client_id, 0, 63)?;
+        in_range(self.health_amount, 0, 9)?;
+        in_range(self.armor_amount, 0, 9)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1442,8 +1850,18 @@ impl PlayerInfoRace {
             race_start_tick: ::snap_obj::Tick(_p.read_int()?),
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(race_start_tick: ::snap_obj::Tick) -> Result<PlayerInfoRace, Error> {
+        let result = PlayerInfoRace {
+            race_start_tick,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1469,10 +1887,24 @@ impl GameDataRace {
             race_flags: _p.read_int()?,
         })
     }
-    pub fn encode(&self) -> &[i32] {
-        assert!(self.best_time >= -1);
-        assert!(0 <= self.precision && self.precision <= 3);
-        unsafe { slice::transmute(from_ref(self)) }
+    pub fn new(best_time: i32, precision: i32, race_flags: i32) -> Result<GameDataRace, Error> {
+        let result = GameDataRace {
+            best_time,
+            precision,
+            race_flags,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+    pub fn validate(&self) -> Result<(), Error> {
+        at_least(self.best_time, -1)?;
+        in_range(self.precision, 0, 3)?;
+        Ok(())
+    }
+    pub fn encode(&self) -> Result<&[i32], Error> {
+        at_least(self.best_time, -1)?;
+        in_range(self.precision, 0, 3)?;
+        Ok(unsafe { slice::transmute(from_ref(self)) })
     }
 }
 
@@ -1505,3 +1937,297 @@ pub fn obj_size(type_: u16) -> Option<u32> {
         _ => return None,
     })
 }
+
+pub const OBJ_META: &'static [ObjMeta] = &[
+    ObjMeta {
+        type_id: TypeId::Ordinal(PLAYER_INPUT),
+        name: "player_input",
+        size: 10,
+        fields: &[
+            FieldMeta { name: "direction", count: 1, kind: FieldKind::Range { min: -1, max: 1 } },
+            FieldMeta { name: "target_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "target_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "jump", count: 1, kind: FieldKind::Bool },
+            FieldMeta { name: "fire", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook", count: 1, kind: FieldKind::Bool },
+            FieldMeta { name: "player_flags", count: 1, kind: FieldKind::Flags { name: "playerflag", values: &["admin", "chatting", "scoreboard", "ready", "dead", "watching", "bot"] } },
+            FieldMeta { name: "wanted_weapon", count: 1, kind: FieldKind::Range { min: 0, max: 6 } },
+            FieldMeta { name: "next_weapon", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "prev_weapon", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(PROJECTILE),
+        name: "projectile",
+        size: 6,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "type", count: 1, kind: FieldKind::Enum { name: "weapon", offset: 0, values: &["hammer", "pistol", "shotgun", "grenade", "rifle", "ninja"] } },
+            FieldMeta { name: "start_tick", count: 1, kind: FieldKind::Tick },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(LASER),
+        name: "laser",
+        size: 5,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "from_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "from_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "start_tick", count: 1, kind: FieldKind::Tick },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(PICKUP),
+        name: "pickup",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "type", count: 1, kind: FieldKind::Enum { name: "pickup", offset: 0, values: &["health", "armor", "grenade", "shotgun", "laser", "ninja", "gun", "hammer"] } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(FLAG),
+        name: "flag",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "team", count: 1, kind: FieldKind::Range { min: 0, max: 1 } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(GAME_DATA),
+        name: "game_data",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "game_start_tick", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "game_state_flags", count: 1, kind: FieldKind::Flags { name: "gamestateflag", values: &["warmup", "suddendeath", "roundover", "gameover", "paused", "startcountdown"] } },
+            FieldMeta { name: "game_state_end_tick", count: 1, kind: FieldKind::Tick },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(GAME_DATA_TEAM),
+        name: "game_data_team",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "teamscore_red", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "teamscore_blue", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(GAME_DATA_FLAG),
+        name: "game_data_flag",
+        size: 4,
+        fields: &[
+            FieldMeta { name: "flag_carrier_red", count: 1, kind: FieldKind::Range { min: -3, max: 63 } },
+            FieldMeta { name: "flag_carrier_blue", count: 1, kind: FieldKind::Range { min: -3, max: 63 } },
+            FieldMeta { name: "flag_drop_tick_red", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "flag_drop_tick_blue", count: 1, kind: FieldKind::Tick },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(CHARACTER_CORE),
+        name: "character_core",
+        size: 15,
+        fields: &[
+            FieldMeta { name: "tick", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "angle", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "direction", count: 1, kind: FieldKind::Range { min: -1, max: 1 } },
+            FieldMeta { name: "jumped", count: 1, kind: FieldKind::Range { min: 0, max: 3 } },
+            FieldMeta { name: "hooked_player", count: 1, kind: FieldKind::Range { min: -1, max: 63 } },
+            FieldMeta { name: "hook_state", count: 1, kind: FieldKind::Range { min: -1, max: 5 } },
+            FieldMeta { name: "hook_tick", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "hook_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_dx", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_dy", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(CHARACTER),
+        name: "character",
+        size: 22,
+        fields: &[
+            FieldMeta { name: "tick", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "vel_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "angle", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "direction", count: 1, kind: FieldKind::Range { min: -1, max: 1 } },
+            FieldMeta { name: "jumped", count: 1, kind: FieldKind::Range { min: 0, max: 3 } },
+            FieldMeta { name: "hooked_player", count: 1, kind: FieldKind::Range { min: -1, max: 63 } },
+            FieldMeta { name: "hook_state", count: 1, kind: FieldKind::Range { min: -1, max: 5 } },
+            FieldMeta { name: "hook_tick", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "hook_x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_dx", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "hook_dy", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "health", count: 1, kind: FieldKind::Range { min: 0, max: 10 } },
+            FieldMeta { name: "armor", count: 1, kind: FieldKind::Range { min: 0, max: 10 } },
+            FieldMeta { name: "ammo_count", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "weapon", count: 1, kind: FieldKind::Enum { name: "weapon", offset: 0, values: &["hammer", "pistol", "shotgun", "grenade", "rifle", "ninja"] } },
+            FieldMeta { name: "emote", count: 1, kind: FieldKind::Enum { name: "emote", offset: 0, values: &["normal", "pain", "happy", "surprise", "angry", "blink"] } },
+            FieldMeta { name: "attack_tick", count: 1, kind: FieldKind::Tick },
+            FieldMeta { name: "triggered_events", count: 1, kind: FieldKind::Flags { name: "coreeventflag", values: &["ground_jump", "air_jump", "hook_attach_player", "hook_attach_ground", "hook_hit_nohook"] } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(PLAYER_INFO),
+        name: "player_info",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "player_flags", count: 1, kind: FieldKind::Flags { name: "playerflag", values: &["admin", "chatting", "scoreboard", "ready", "dead", "watching", "bot"] } },
+            FieldMeta { name: "score", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "latency", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(SPECTATOR_INFO),
+        name: "spectator_info",
+        size: 4,
+        fields: &[
+            FieldMeta { name: "spec_mode", count: 1, kind: FieldKind::Enum { name: "spec", offset: 0, values: &["freeview", "player", "flagred", "flagblue"] } },
+            FieldMeta { name: "spectator_id", count: 1, kind: FieldKind::Range { min: -1, max: 63 } },
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(DE_CLIENT_INFO),
+        name: "de_client_info",
+        size: 58,
+        fields: &[
+            FieldMeta { name: "local", count: 1, kind: FieldKind::Bool },
+            FieldMeta { name: "team", count: 1, kind: FieldKind::Enum { name: "team", offset: -1, values: &["spectators", "red", "blue"] } },
+            FieldMeta { name: "name", count: 4, kind: FieldKind::Int },
+            FieldMeta { name: "clan", count: 3, kind: FieldKind::Int },
+            FieldMeta { name: "country", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "skin_part_names", count: 36, kind: FieldKind::Int },
+            FieldMeta { name: "use_custom_colors", count: 6, kind: FieldKind::Bool },
+            FieldMeta { name: "skin_part_colors", count: 6, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(DE_GAME_INFO),
+        name: "de_game_info",
+        size: 5,
+        fields: &[
+            FieldMeta { name: "game_flags", count: 1, kind: FieldKind::Flags { name: "gameflag", values: &["teams", "flags", "survival", "race"] } },
+            FieldMeta { name: "score_limit", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "time_limit", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "match_num", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+            FieldMeta { name: "match_current", count: 1, kind: FieldKind::Range { min: 0, max: i32::MAX } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(DE_TUNE_PARAMS),
+        name: "de_tune_params",
+        size: 32,
+        fields: &[
+            FieldMeta { name: "tune_params", count: 32, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(COMMON),
+        name: "common",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(EXPLOSION),
+        name: "explosion",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(SPAWN),
+        name: "spawn",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(HAMMER_HIT),
+        name: "hammer_hit",
+        size: 2,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(DEATH),
+        name: "death",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "client_id", count: 1, kind: FieldKind::Range { min: 0, max: 63 } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(SOUND_WORLD),
+        name: "sound_world",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "sound_id", count: 1, kind: FieldKind::Enum { name: "sound", offset: 0, values: &["gun_fire", "shotgun_fire", "grenade_fire", "hammer_fire", "hammer_hit", "ninja_fire", "grenade_explode", "ninja_hit", "rifle_fire", "rifle_bounce", "weapon_switch", "player_pain_short", "player_pain_long", "body_land", "player_airjump", "player_jump", "player_die", "player_spawn", "player_skid", "tee_cry", "hook_loop", "hook_attach_ground", "hook_attach_player", "hook_noattach", "pickup_health", "pickup_armor", "pickup_grenade", "pickup_shotgun", "pickup_ninja", "weapon_spawn", "weapon_noammo", "hit", "chat_server", "chat_client", "chat_highlight", "ctf_drop", "ctf_return", "ctf_grab_pl", "ctf_grab_en", "ctf_capture", "menu"] } },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(DAMAGE),
+        name: "damage",
+        size: 7,
+        fields: &[
+            FieldMeta { name: "x", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "y", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "client_id", count: 1, kind: FieldKind::Range { min: 0, max: 63 } },
+            FieldMeta { name: "angle", count: 1, kind: FieldKind::Int },
+            FieldMeta { name: "health_amount", count: 1, kind: FieldKind::Range { min: 0, max: 9 } },
+            FieldMeta { name: "armor_amount", count: 1, kind: FieldKind::Range { min: 0, max: 9 } },
+            FieldMeta { name: "self", count: 1, kind: FieldKind::Bool },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(PLAYER_INFO_RACE),
+        name: "player_info_race",
+        size: 1,
+        fields: &[
+            FieldMeta { name: "race_start_tick", count: 1, kind: FieldKind::Tick },
+        ],
+    },
+    ObjMeta {
+        type_id: TypeId::Ordinal(GAME_DATA_RACE),
+        name: "game_data_race",
+        size: 3,
+        fields: &[
+            FieldMeta { name: "best_time", count: 1, kind: FieldKind::Range { min: -1, max: i32::MAX } },
+            FieldMeta { name: "precision", count: 1, kind: FieldKind::Range { min: 0, max: 3 } },
+            FieldMeta { name: "race_flags", count: 1, kind: FieldKind::Flags { name: "raceflag", values: &["hide_killmsg", "finishmsg_as_chat", "keep_wanted_weapon"] } },
+        ],
+    },
+];
+
+pub fn obj_meta(type_id: TypeId) -> Option<&'static ObjMeta> {
+    OBJ_META.iter().find(|m| m.type_id == type_id)
+}
@@ -0,0 +1,40 @@
+extern crate gamenet_teeworlds_0_7 as gamenet;
+extern crate packer;
+extern crate serde_json;
+extern crate warn;
+
+use gamenet::msg::game::ClStartInfo;
+use gamenet::msg::Game;
+use gamenet::msg::GameOwned;
+use packer::with_packer;
+use packer::Unpacker;
+use warn::Panic;
+
+#[test]
+fn skin_parts_round_trip() {
+    let info = ClStartInfo::new(
+        b"nameless tee",
+        b"",
+        -1,
+        [b"standard", b"", b"", b"standard", b"standard", b"standard"],
+        [false, false, false, true, false, false],
+        [1798004, 0, 0, 1799582, 1869630, 1799582],
+    )
+    .unwrap();
+    let mut buf = Vec::with_capacity(1024);
+    let encoded = with_packer(&mut buf, |p| Game::from(info).encode(p))
+        .unwrap()
+        .to_vec();
+
+    let decoded = Game::decode(&mut Panic, &mut Unpacker::new(&encoded)).unwrap();
+    let json = serde_json::to_value(decoded).unwrap();
+    assert_eq!(
+        json["ClStartInfo"]["skin_part_names"],
+        serde_json::json!(["standard", "", "", "standard", "standard", "standard"]),
+    );
+
+    let owned: GameOwned = serde_json::from_value(json).unwrap();
+    let mut buf = Vec::with_capacity(1024);
+    let reencoded = with_packer(&mut buf, |p| owned.as_ref().encode(p)).unwrap();
+    assert_eq!(reencoded, &encoded[..]);
+}
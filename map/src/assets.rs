@@ -0,0 +1,332 @@
+//! Extraction and embedding of the images and sounds of a map.
+//!
+//! Images are converted from and to PNG files with the `png` feature, sounds
+//! are stored as Opus files as they are.
+
+use common::num::Cast;
+#[cfg(feature = "png")]
+use png;
+use std::fs;
+#[cfg(feature = "png")]
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use model::Image;
+use model::Map;
+use model::Sound;
+
+#[derive(Debug)]
+pub enum AssetError {
+    Io(io::Error),
+    /// The name of the asset can't be used as a file name.
+    InvalidName(Vec<u8>),
+    /// Pixel data of the wrong size for the dimensions of an image.
+    InvalidDimensions {
+        width: u32,
+        height: u32,
+        len: usize,
+    },
+    /// Sound data that isn't an Ogg file.
+    InvalidSound,
+    #[cfg(feature = "png")]
+    PngDecoding(png::DecodingError),
+    #[cfg(feature = "png")]
+    PngEncoding(png::EncodingError),
+}
+
+impl From<io::Error> for AssetError {
+    fn from(err: io::Error) -> AssetError {
+        AssetError::Io(err)
+    }
+}
+
+#[cfg(feature = "png")]
+impl From<png::DecodingError> for AssetError {
+    fn from(err: png::DecodingError) -> AssetError {
+        AssetError::PngDecoding(err)
+    }
+}
+
+#[cfg(feature = "png")]
+impl From<png::EncodingError> for AssetError {
+    fn from(err: png::EncodingError) -> AssetError {
+        AssetError::PngEncoding(err)
+    }
+}
+
+fn file_name(name: &[u8], extension: &str) -> Result<String, AssetError> {
+    let invalid = || AssetError::InvalidName(name.to_vec());
+    let name = String::from_utf8(name.to_vec()).map_err(|_| invalid())?;
+    if name.is_empty() || name.starts_with('.') || name.contains(&['/', '\\', '\0'][..]) {
+        return Err(invalid());
+    }
+    Ok(format!("{}.{}", name, extension))
+}
+
+impl Image {
+    pub fn is_embedded(&self) -> bool {
+        self.data.is_some()
+    }
+    /// Embeds the RGBA pixel data into the map.
+    pub fn embed(&mut self, width: u32, height: u32, data: Vec<u8>) -> Result<(), AssetError> {
+        let len = data.len();
+        let expected = width.u64() * height.u64() * 4;
+        if width == 0 || height == 0 || len.u64() != expected {
+            return Err(AssetError::InvalidDimensions { width, height, len });
+        }
+        self.width = width;
+        self.height = height;
+        self.data = Some(data);
+        Ok(())
+    }
+    /// Removes the pixel data so that the image is loaded from the client's
+    /// `mapres` directory instead.
+    pub fn make_external(&mut self) -> Option<Vec<u8>> {
+        self.data.take()
+    }
+    /// Embeds the pixel data of a PNG file.
+    #[cfg(feature = "png")]
+    pub fn embed_png<R: io::Read>(&mut self, png: R) -> Result<(), AssetError> {
+        let (info, mut reader) = png::Decoder::new(png).read_info()?;
+        let mut buffer = vec![0; info.buffer_size()];
+        reader.next_frame(&mut buffer)?;
+        // The decoder expands everything to 8 bits per sample.
+        let samples = info.color_type.samples();
+        let mut data = Vec::with_capacity(info.width.usize() * info.height.usize() * 4);
+        for row in buffer.chunks(info.line_size) {
+            for pixel in row[..info.width.usize() * samples].chunks(samples) {
+                data.extend_from_slice(&match *pixel {
+                    [v] => [v, v, v, 255],
+                    [v, a] => [v, v, v, a],
+                    [r, g, b] => [r, g, b, 255],
+                    [r, g, b, a] => [r, g, b, a],
+                    _ => unreachable!(),
+                });
+            }
+        }
+        self.embed(info.width, info.height, data)
+    }
+    /// Writes the embedded pixel data as PNG file.
+    ///
+    /// Returns `Ok(false)` without writing anything for external images.
+    #[cfg(feature = "png")]
+    pub fn write_png<W: io::Write>(&self, file: W) -> Result<bool, AssetError> {
+        use png::HasParameters;
+
+        let data = match self.data {
+            Some(ref d) => d,
+            None => return Ok(false),
+        };
+        let mut encoder = png::Encoder::new(file, self.width, self.height);
+        encoder.set(png::ColorType::RGBA).set(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(data)?;
+        Ok(true)
+    }
+}
+
+impl Sound {
+    pub fn is_embedded(&self) -> bool {
+        self.data.is_some()
+    }
+    /// Embeds the contents of an Opus file into the map.
+    pub fn embed(&mut self, data: Vec<u8>) -> Result<(), AssetError> {
+        if !data.starts_with(b"OggS") {
+            return Err(AssetError::InvalidSound);
+        }
+        self.data = Some(data);
+        Ok(())
+    }
+    /// Removes the sound data, returning it.
+    pub fn make_external(&mut self) -> Option<Vec<u8>> {
+        self.data.take()
+    }
+}
+
+impl Map {
+    /// The embedded images together with their indices.
+    pub fn embedded_images(&self) -> impl Iterator<Item = (usize, &Image)> {
+        self.images
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| i.is_embedded())
+    }
+    /// The embedded sounds together with their indices.
+    pub fn embedded_sounds(&self) -> impl Iterator<Item = (usize, &Sound)> {
+        self.sounds
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.is_embedded())
+    }
+    /// Writes all embedded images to `<dir>/<name>.png`, returning the
+    /// paths of the written files.
+    #[cfg(feature = "png")]
+    pub fn extract_images(&self, dir: &Path) -> Result<Vec<PathBuf>, AssetError> {
+        let mut result = Vec::new();
+        for (_, image) in self.embedded_images() {
+            let path = dir.join(file_name(&image.name, "png")?);
+            image.write_png(io::BufWriter::new(File::create(&path)?))?;
+            result.push(path);
+        }
+        Ok(result)
+    }
+    /// Writes all embedded sounds to `<dir>/<name>.opus`, returning the
+    /// paths of the written files.
+    pub fn extract_sounds(&self, dir: &Path) -> Result<Vec<PathBuf>, AssetError> {
+        let mut result = Vec::new();
+        for (_, sound) in self.embedded_sounds() {
+            let path = dir.join(file_name(&sound.name, "opus")?);
+            fs::write(&path, sound.data.as_ref().unwrap())?;
+            result.push(path);
+        }
+        Ok(result)
+    }
+    /// Embeds all external images from `<dir>/<name>.png`, e.g. the
+    /// `mapres` directory of the client.
+    #[cfg(feature = "png")]
+    pub fn embed_external_images(&mut self, dir: &Path) -> Result<(), AssetError> {
+        for image in self.images.iter_mut().filter(|i| !i.is_embedded()) {
+            let path = dir.join(file_name(&image.name, "png")?);
+            image.embed_png(io::BufReader::new(File::open(path)?))?;
+        }
+        Ok(())
+    }
+    /// Embeds all external sounds from `<dir>/<name>.opus`.
+    pub fn embed_external_sounds(&mut self, dir: &Path) -> Result<(), AssetError> {
+        for sound in self.sounds.iter_mut().filter(|s| !s.is_embedded()) {
+            let path = dir.join(file_name(&sound.name, "opus")?);
+            sound.embed(fs::read(path)?)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    use super::AssetError;
+    use model::Image;
+    use model::Map;
+    use model::Sound;
+
+    fn image(name: &[u8], data: Option<Vec<u8>>) -> Image {
+        Image {
+            name: name.to_vec(),
+            width: 1,
+            height: 2,
+            data,
+        }
+    }
+
+    fn sound(name: &[u8], data: Option<&[u8]>) -> Sound {
+        Sound {
+            name: name.to_vec(),
+            data: data.map(|d| d.to_vec()),
+        }
+    }
+
+    #[test]
+    fn list() {
+        let mut map = Map::default();
+        map.images.push(image(b"grass_main", None));
+        map.images.push(image(b"custom", Some(vec![0; 8])));
+        map.sounds.push(sound(b"wind", Some(b"OggS wind")));
+        map.sounds.push(sound(b"rain", None));
+        let images: Vec<_> = map.embedded_images().map(|(i, _)| i).collect();
+        let sounds: Vec<_> = map.embedded_sounds().map(|(i, _)| i).collect();
+        assert_eq!(images, [1]);
+        assert_eq!(sounds, [0]);
+    }
+
+    #[test]
+    fn image_dimensions() {
+        let mut image = image(b"custom", None);
+        assert!(image.embed(2, 3, vec![0; 24]).is_ok());
+        assert_eq!((image.width, image.height), (2, 3));
+        assert!(image.is_embedded());
+        assert_eq!(image.make_external(), Some(vec![0; 24]));
+        assert!(!image.is_embedded());
+        match image.embed(2, 3, vec![0; 23]) {
+            Err(AssetError::InvalidDimensions {
+                width: 2,
+                height: 3,
+                len: 23,
+            }) => {}
+            other => panic!("unexpected result {:?}", other),
+        }
+        match image.embed(0, 3, Vec::new()) {
+            Err(AssetError::InvalidDimensions { .. }) => {}
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert!(!image.is_embedded());
+    }
+
+    #[test]
+    fn sound_format() {
+        let mut sound = sound(b"wind", None);
+        match sound.embed(b"RIFF".to_vec()) {
+            Err(AssetError::InvalidSound) => {}
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert!(sound.embed(b"OggS".to_vec()).is_ok());
+        assert!(sound.is_embedded());
+    }
+
+    #[test]
+    fn extract_and_embed_sounds() {
+        let dir = env::temp_dir().join(format!("libtw2-assets-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut map = Map::default();
+        map.sounds.push(sound(b"wind", Some(b"OggS wind")));
+        map.sounds.push(sound(b"rain", None));
+        let paths = map.extract_sounds(&dir).unwrap();
+        assert_eq!(paths, [dir.join("wind.opus")]);
+
+        fs::write(dir.join("rain.opus"), b"OggS rain").unwrap();
+        map.sounds[0].make_external();
+        map.embed_external_sounds(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(map.sounds[0].data.as_ref().unwrap(), b"OggS wind");
+        assert_eq!(map.sounds[1].data.as_ref().unwrap(), b"OggS rain");
+    }
+
+    #[test]
+    fn invalid_names() {
+        let dir = env::temp_dir();
+        for &name in &[&b""[..], b".hidden", b"../escape", b"a\\b", b"\xff"] {
+            let mut map = Map::default();
+            map.sounds.push(sound(name, Some(b"OggS")));
+            match map.extract_sounds(&dir) {
+                Err(AssetError::InvalidName(n)) => assert_eq!(n, name),
+                other => panic!("unexpected result {:?}", other),
+            }
+        }
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn png_round_trip() {
+        let dir = env::temp_dir().join(format!("libtw2-assets-png-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let pixels = vec![1, 2, 3, 4, 5, 6, 7, 8];
+        let mut map = Map::default();
+        map.images.push(image(b"custom", Some(pixels.clone())));
+        map.images.push(image(b"external", None));
+        let paths = map.extract_images(&dir).unwrap();
+        assert_eq!(paths, [dir.join("custom.png")]);
+
+        map.images[0].make_external();
+        map.embed_external_images(&dir).unwrap_err();
+        fs::copy(dir.join("custom.png"), dir.join("external.png")).unwrap();
+        map.embed_external_images(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        for image in &map.images {
+            assert_eq!((image.width, image.height), (1, 2));
+            assert_eq!(image.data, Some(pixels.clone()));
+        }
+    }
+}
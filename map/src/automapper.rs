@@ -0,0 +1,446 @@
+//! Automapper of the DDNet editor.
+//!
+//! Rule files (`editor/automap/*.rules`) consist of configurations, each
+//! started by a `[name]` line. A configuration has one or more runs,
+//! separated by `NewRun`. A run is a list of `Index` rules, each followed by
+//! `Pos` conditions on the surrounding tiles and optional `Random`,
+//! `NoDefaultRule` and `NoLayerCopy` lines:
+//!
+//! ```text
+//! [Grass]
+//! Index 1
+//! Index 2 XFLIP
+//! Pos 0 -1 EMPTY
+//! Pos 1 0 INDEX 3 NONE OR 4 ROTATE
+//! Random 25%
+//! ```
+
+use common::num::Cast;
+use ndarray::Array2;
+use std::str::FromStr;
+
+use format::Tile;
+use format::TILEFLAG_HFLIP;
+use format::TILEFLAG_ROTATE;
+use format::TILEFLAG_VFLIP;
+
+const FLAGS_MASK: u8 = TILEFLAG_ROTATE | TILEFLAG_VFLIP | TILEFLAG_HFLIP;
+
+const HASH_MAX: u32 = 65536;
+
+/// A named set of rules, as selected in the editor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    pub name: String,
+    pub runs: Vec<Run>,
+}
+
+/// Rules applied together to the whole layer, before the next run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    /// Whether the conditions are checked against a copy of the layer from
+    /// before the run, instead of the layer as it is being changed.
+    pub copy: bool,
+    pub index_rules: Vec<IndexRule>,
+}
+
+/// Sets the tile to `index` with `flags` where all conditions hold.
+///
+/// Later rules of a run take precedence over earlier ones.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IndexRule {
+    pub index: i32,
+    pub flags: u8,
+    /// Probability in `0.0..=1.0` that the rule is applied to a matching
+    /// tile.
+    pub probability: f32,
+    pub conditions: Vec<PosRule>,
+    default_rule: bool,
+    skip_empty: bool,
+    skip_full: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PosRuleKind {
+    /// The tile must match one of the indices.
+    Index,
+    /// The tile must not match any of the indices.
+    NotIndex,
+}
+
+/// Condition on the tile at the offset `(x, y)`.
+///
+/// Tiles outside of the layer have index -1.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PosRule {
+    pub x: i32,
+    pub y: i32,
+    pub kind: PosRuleKind,
+    pub indices: Vec<IndexInfo>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct IndexInfo {
+    pub index: i32,
+    /// Required rotation and flip flags, `None` to accept any.
+    pub flags: Option<u8>,
+}
+
+/// Like `sscanf`'s `%d`, returns 0 for invalid numbers.
+fn int(word: Option<&str>) -> i32 {
+    word.and_then(|w| w.parse().ok()).unwrap_or(0)
+}
+
+fn flag(word: &str) -> Option<u8> {
+    Some(match word {
+        "XFLIP" => TILEFLAG_VFLIP,
+        "YFLIP" => TILEFLAG_HFLIP,
+        "ROTATE" => TILEFLAG_ROTATE,
+        "NONE" => 0,
+        _ => return None,
+    })
+}
+
+fn parse_indices<'a, I: Iterator<Item = &'a str>>(words: I) -> Vec<IndexInfo> {
+    let mut result = Vec::new();
+    let mut current: Option<IndexInfo> = None;
+    for word in words {
+        match current {
+            None => {
+                current = Some(IndexInfo {
+                    index: int(Some(word)),
+                    flags: None,
+                })
+            }
+            Some(ref mut info) => {
+                if word == "OR" {
+                    result.extend(current.take());
+                } else if let Some(f) = flag(word) {
+                    info.flags = Some(info.flags.unwrap_or(0) | f);
+                }
+            }
+        }
+    }
+    result.extend(current);
+    result
+}
+
+impl IndexRule {
+    fn new(index: i32, flags: u8) -> IndexRule {
+        IndexRule {
+            index,
+            flags,
+            probability: 1.0,
+            conditions: Vec::new(),
+            default_rule: true,
+            skip_empty: false,
+            skip_full: false,
+        }
+    }
+    fn add_condition(&mut self, rule: PosRule) {
+        if rule.x == 0 && rule.y == 0 {
+            for info in &rule.indices {
+                if rule.kind == PosRuleKind::Index && info.index == 0 {
+                    self.skip_full = true;
+                } else {
+                    self.skip_empty = true;
+                }
+            }
+        }
+        self.conditions.push(rule);
+    }
+    /// Adds the implicit `Pos 0 0 FULL` unless there's a condition on the
+    /// tile itself or `NoDefaultRule` was given.
+    fn finish(&mut self) {
+        let has_own = self.conditions.iter().any(|r| r.x == 0 && r.y == 0);
+        if !has_own && self.default_rule {
+            self.conditions.push(PosRule {
+                x: 0,
+                y: 0,
+                kind: PosRuleKind::NotIndex,
+                indices: vec![IndexInfo {
+                    index: 0,
+                    flags: None,
+                }],
+            });
+            self.skip_empty = true;
+            self.skip_full = false;
+        }
+        if self.skip_empty && self.skip_full {
+            self.skip_empty = false;
+            self.skip_full = false;
+        }
+    }
+}
+
+/// Parses a rule file.
+///
+/// Like the editor, this is lenient: unknown lines are ignored and invalid
+/// numbers are read as zero.
+pub fn parse(rules: &str) -> Vec<Config> {
+    let mut configs: Vec<Config> = Vec::new();
+    for line in rules.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') {
+            let name = line[1..].split(']').next().unwrap();
+            configs.push(Config {
+                name: name.to_owned(),
+                runs: vec![Run {
+                    copy: true,
+                    index_rules: Vec::new(),
+                }],
+            });
+            continue;
+        }
+        let run = match configs.last_mut().and_then(|c| c.runs.last_mut()) {
+            Some(r) => r,
+            None => continue,
+        };
+        let mut words = line.split_whitespace();
+        match words.next().unwrap() {
+            "NewRun" => {
+                configs.last_mut().unwrap().runs.push(Run {
+                    copy: true,
+                    index_rules: Vec::new(),
+                });
+            }
+            "NoLayerCopy" => run.copy = false,
+            "Index" => {
+                let index = int(words.next());
+                let flags = words.filter_map(flag).fold(0, |a, f| a | f);
+                run.index_rules.push(IndexRule::new(index, flags));
+            }
+            command => {
+                let rule = match run.index_rules.last_mut() {
+                    Some(r) => r,
+                    None => continue,
+                };
+                match command {
+                    "Pos" => {
+                        let x = int(words.next());
+                        let y = int(words.next());
+                        let (kind, indices) = match words.next() {
+                            Some("EMPTY") => (
+                                PosRuleKind::Index,
+                                vec![IndexInfo {
+                                    index: 0,
+                                    flags: None,
+                                }],
+                            ),
+                            Some("FULL") => (
+                                PosRuleKind::NotIndex,
+                                vec![IndexInfo {
+                                    index: 0,
+                                    flags: None,
+                                }],
+                            ),
+                            Some("INDEX") => (PosRuleKind::Index, parse_indices(words)),
+                            Some("NOTINDEX") => (PosRuleKind::NotIndex, parse_indices(words)),
+                            _ => continue,
+                        };
+                        rule.add_condition(PosRule {
+                            x,
+                            y,
+                            kind,
+                            indices,
+                        });
+                    }
+                    "Random" => {
+                        let value = words.next().unwrap_or("");
+                        rule.probability = if value.ends_with('%') {
+                            f32::from_str(&value[..value.len() - 1]).unwrap_or(0.0) / 100.0
+                        } else {
+                            1.0 / f32::from_str(value).unwrap_or(0.0)
+                        };
+                    }
+                    "NoDefaultRule" => rule.default_rule = false,
+                    _ => {}
+                }
+            }
+        }
+    }
+    for config in &mut configs {
+        for run in &mut config.runs {
+            for rule in &mut run.index_rules {
+                rule.finish();
+            }
+        }
+    }
+    configs
+}
+
+fn hash_u32(mut num: u32) -> u32 {
+    num = (num ^ 61) ^ (num >> 16);
+    num = num.wrapping_add(num << 3);
+    num ^= num >> 4;
+    num = num.wrapping_mul(0x27d4eb2d);
+    num ^= num >> 15;
+    num
+}
+
+fn hash_location(seed: u32, run: u32, rule: u32, x: u32, y: u32) -> u32 {
+    const PRIME: u32 = 31;
+    let mut hash: u32 = 1;
+    for &v in &[seed, run, rule, x, y] {
+        hash = hash.wrapping_mul(PRIME).wrapping_add(hash_u32(v));
+    }
+    hash_u32(hash.wrapping_mul(PRIME)) % HASH_MAX
+}
+
+fn matches(tile: Option<&Tile>, info: &IndexInfo) -> bool {
+    let (index, flags) = match tile {
+        Some(t) => (i32::from(t.index), t.flags & FLAGS_MASK),
+        None => (-1, 0),
+    };
+    index == info.index && info.flags.map(|f| f == flags).unwrap_or(true)
+}
+
+/// Applies the rules of `config` to the tiles of a layer, the same way the
+/// editor does.
+///
+/// `seed` determines which tiles are chosen by rules with a probability
+/// below 1, the editor uses a random seed unless one is given.
+pub fn apply_automapper(layer: &mut Array2<Tile>, config: &Config, seed: u32) {
+    let (height, width) = layer.dim();
+    for (run_index, run) in config.runs.iter().enumerate() {
+        let copy = if run.copy { Some(layer.clone()) } else { None };
+        for y in 0..height {
+            for x in 0..width {
+                for (rule_index, rule) in run.index_rules.iter().enumerate() {
+                    let read = copy.as_ref().unwrap_or(&*layer);
+                    let tile_index = read[[y, x]].index;
+                    if (rule.skip_empty && tile_index == 0) || (rule.skip_full && tile_index != 0) {
+                        continue;
+                    }
+                    let applies = rule.conditions.iter().all(|c| {
+                        let cx = (x.assert_i64() + c.x.i64()).try_usize();
+                        let cy = (y.assert_i64() + c.y.i64()).try_usize();
+                        let tile = cx.and_then(|cx| cy.and_then(|cy| read.get((cy, cx))));
+                        let any = c.indices.iter().any(|i| matches(tile, i));
+                        match c.kind {
+                            PosRuleKind::Index => any,
+                            PosRuleKind::NotIndex => !any,
+                        }
+                    });
+                    let chosen = rule.probability >= 1.0 || {
+                        let hash = hash_location(
+                            seed,
+                            run_index.assert_u32(),
+                            rule_index.assert_u32(),
+                            x.assert_u32(),
+                            y.assert_u32(),
+                        );
+                        (hash as f32) < (HASH_MAX as f32) * rule.probability
+                    };
+                    if applies && chosen {
+                        // Tile indices wrap around like in the editor.
+                        layer[[y, x]].index = rule.index as u8;
+                        layer[[y, x]].flags = rule.flags;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::Array2;
+
+    use super::apply_automapper;
+    use super::parse;
+    use super::IndexInfo;
+    use super::PosRuleKind;
+    use format::Tile;
+    use format::TILEFLAG_ROTATE;
+    use format::TILEFLAG_VFLIP;
+
+    const RULES: &'static str = "
+# Comment
+[Simple]
+Index 1
+Index 2 XFLIP
+Pos 0 -1 EMPTY
+Pos 1 0 NOTINDEX 5 OR 6 ROTATE
+
+[Second]
+Index 7
+NoDefaultRule
+Random 50%
+NewRun
+NoLayerCopy
+Index 8
+Random 4
+";
+
+    fn tile(index: u8) -> Tile {
+        Tile {
+            index,
+            ..Tile::default()
+        }
+    }
+
+    #[test]
+    fn parse_rules() {
+        let configs = parse(RULES);
+        assert_eq!(configs.len(), 2);
+        let simple = &configs[0];
+        assert_eq!(simple.name, "Simple");
+        assert_eq!(simple.runs.len(), 1);
+        let rules = &simple.runs[0].index_rules;
+        assert_eq!(rules.len(), 2);
+        // The default rule requires the tile itself to be non-empty.
+        assert_eq!(rules[0].conditions.len(), 1);
+        assert_eq!(rules[1].flags, TILEFLAG_VFLIP);
+        assert_eq!(rules[1].conditions.len(), 3);
+        assert_eq!(rules[1].conditions[1].kind, PosRuleKind::NotIndex);
+        assert_eq!(
+            rules[1].conditions[1].indices,
+            [
+                IndexInfo {
+                    index: 5,
+                    flags: None
+                },
+                IndexInfo {
+                    index: 6,
+                    flags: Some(TILEFLAG_ROTATE)
+                },
+            ]
+        );
+        let second = &configs[1];
+        assert_eq!(second.runs.len(), 2);
+        assert!(!second.runs[1].copy);
+        assert_eq!(second.runs[0].index_rules[0].probability, 0.5);
+        assert!(second.runs[0].index_rules[0].conditions.is_empty());
+        assert_eq!(second.runs[1].index_rules[0].probability, 0.25);
+    }
+
+    #[test]
+    fn apply() {
+        let config = &parse(RULES)[0];
+        let mut layer = Array2::from_shape_fn((3, 3), |(y, _)| tile(if y == 0 { 0 } else { 3 }));
+        layer[[1, 1]] = tile(5);
+        apply_automapper(&mut layer, config, 0);
+        let indices: Vec<_> = layer.iter().map(|t| t.index).collect();
+        assert_eq!(indices, [0, 0, 0, 1, 2, 2, 1, 1, 1]);
+        assert_eq!(layer[[1, 1]].flags, TILEFLAG_VFLIP);
+
+        // Rules with a probability only apply to some tiles, depending on
+        // the seed.
+        let config = &parse(RULES)[1];
+        let mut layers = Vec::new();
+        for &seed in &[1, 2] {
+            let mut layer = Array2::from_elem((32, 32), tile(0));
+            apply_automapper(&mut layer, config, seed);
+            let sevens = layer.iter().filter(|t| t.index == 7).count();
+            let eights = layer.iter().filter(|t| t.index == 8).count();
+            assert!(400 < sevens + eights && sevens + eights < 624);
+            assert!(50 < eights && eights < sevens);
+            layers.push(layer);
+        }
+        assert!(layers[0] != layers[1]);
+    }
+}
@@ -0,0 +1,204 @@
+//! Collision queries on the game layers, as used by the game physics.
+//!
+//! Positions are in world coordinates, with 32 units per tile. Positions
+//! outside of the map are clamped to the nearest tile at the border, like in
+//! the reference implementation.
+
+use common::num::Cast;
+use common::num::CastFloat;
+use ndarray::Array2;
+
+use ddnet::GameLayerTiles;
+use ddnet::TILE_DEATH;
+use ddnet::TILE_NOHOOK;
+use ddnet::TILE_SOLID;
+use format::SwitchTile;
+use format::TeleTile;
+use format::Tile;
+
+pub const TILE_SIZE: f32 = 32.0;
+
+pub const COLFLAG_SOLID: u8 = 1;
+pub const COLFLAG_DEATH: u8 = 2;
+pub const COLFLAG_NOHOOK: u8 = 4;
+
+fn tile_flags(tiles: &Array2<Tile>) -> Array2<u8> {
+    tiles.mapv(|t| match t.index {
+        TILE_SOLID => COLFLAG_SOLID,
+        TILE_DEATH => COLFLAG_DEATH,
+        TILE_NOHOOK => COLFLAG_SOLID | COLFLAG_NOHOOK,
+        _ => 0,
+    })
+}
+
+/// Point where a line first hits a solid tile.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Intersection {
+    /// First checked point inside a solid tile.
+    pub pos: (f32, f32),
+    /// Last checked point before `pos`, or the start of the line.
+    pub before: (f32, f32),
+    /// The `COLFLAG_*` flags of the hit tile.
+    pub flags: u8,
+}
+
+#[derive(Clone, Debug)]
+pub struct Collision {
+    flags: Array2<u8>,
+    front_flags: Option<Array2<u8>>,
+    teleport: Option<Array2<TeleTile>>,
+    switch: Option<Array2<SwitchTile>>,
+}
+
+impl Collision {
+    pub fn new(tiles: GameLayerTiles) -> Collision {
+        Collision {
+            flags: tile_flags(&tiles.game),
+            front_flags: tiles.front.as_ref().map(tile_flags),
+            teleport: tiles.teleport,
+            switch: tiles.switch,
+        }
+    }
+    /// Width of the map in tiles.
+    pub fn width(&self) -> u32 {
+        self.flags.cols().assert_u32()
+    }
+    /// Height of the map in tiles.
+    pub fn height(&self) -> u32 {
+        self.flags.rows().assert_u32()
+    }
+    /// Returns the `(row, column)` of the tile at the world position.
+    fn tile_index(&self, x: f32, y: f32) -> (usize, usize) {
+        let clamp = |v: f32, len: usize| {
+            // Integer division, rounding towards zero.
+            let t = v.round_to_i32() / 32;
+            t.max(0).min(len.assert_i32() - 1).assert_usize()
+        };
+        (clamp(y, self.flags.rows()), clamp(x, self.flags.cols()))
+    }
+    /// The `COLFLAG_*` flags of the game layer tile at the position.
+    pub fn flags(&self, x: f32, y: f32) -> u8 {
+        self.flags[self.tile_index(x, y)]
+    }
+    pub fn is_solid(&self, x: f32, y: f32) -> bool {
+        self.flags(x, y) & COLFLAG_SOLID != 0
+    }
+    /// Whether the hook attaches to the tile at the position, i.e. it is
+    /// solid but not unhookable.
+    pub fn is_hookable(&self, x: f32, y: f32) -> bool {
+        self.flags(x, y) & (COLFLAG_SOLID | COLFLAG_NOHOOK) == COLFLAG_SOLID
+    }
+    /// Whether the game layer or the front layer kills at the position.
+    pub fn is_death(&self, x: f32, y: f32) -> bool {
+        let index = self.tile_index(x, y);
+        let front = self.front_flags.as_ref().map(|f| f[index]).unwrap_or(0);
+        (self.flags[index] | front) & COLFLAG_DEATH != 0
+    }
+    /// The teleport tile at the position, if there's one.
+    pub fn tele(&self, x: f32, y: f32) -> Option<TeleTile> {
+        let index = self.tile_index(x, y);
+        self.teleport
+            .as_ref()
+            .map(|t| t[index])
+            .filter(|t| t.index != 0)
+    }
+    /// The switch tile at the position, if there's one.
+    pub fn switch(&self, x: f32, y: f32) -> Option<SwitchTile> {
+        let index = self.tile_index(x, y);
+        self.switch
+            .as_ref()
+            .map(|s| s[index])
+            .filter(|s| s.index != 0)
+    }
+    /// Walks along the line from `from` to `to` in steps of one unit and
+    /// returns the first point inside a solid tile.
+    ///
+    /// The end point itself is not always checked, matching the reference
+    /// implementation.
+    pub fn intersect_line(&self, from: (f32, f32), to: (f32, f32)) -> Option<Intersection> {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let distance = (dx * dx + dy * dy).sqrt();
+        let end = (distance + 1.0).trunc_to_i32();
+        let mut before = from;
+        for i in 0..end {
+            let a = i as f32 / distance;
+            let pos = (from.0 + dx * a, from.1 + dy * a);
+            let flags = self.flags(pos.0, pos.1);
+            if flags & COLFLAG_SOLID != 0 {
+                return Some(Intersection { pos, before, flags });
+            }
+            before = pos;
+        }
+        None
+    }
+    /// Whether no solid tile is between the two points.
+    pub fn line_of_sight(&self, from: (f32, f32), to: (f32, f32)) -> bool {
+        self.intersect_line(from, to).is_none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::Array2;
+
+    use super::Collision;
+    use super::COLFLAG_NOHOOK;
+    use super::COLFLAG_SOLID;
+    use ddnet::GameLayerTiles;
+    use ddnet::TILE_DEATH;
+    use ddnet::TILE_NOHOOK;
+    use ddnet::TILE_SOLID;
+    use ddnet::TILE_TELEIN;
+    use format::TeleTile;
+    use format::Tile;
+
+    fn collision() -> Collision {
+        let mut game = Array2::from_elem((4, 4), Tile::default());
+        game[(0, 3)].index = TILE_SOLID;
+        game[(2, 3)].index = TILE_NOHOOK;
+        let mut front = Array2::from_elem((4, 4), Tile::default());
+        front[(3, 0)].index = TILE_DEATH;
+        let mut teleport = Array2::from_elem((4, 4), TeleTile::default());
+        teleport[(1, 1)] = TeleTile {
+            number: 5,
+            index: TILE_TELEIN,
+        };
+        Collision::new(GameLayerTiles {
+            game,
+            teleport: Some(teleport),
+            speedup: None,
+            front: Some(front),
+            switch: None,
+            tune: None,
+        })
+    }
+
+    #[test]
+    fn point() {
+        let c = collision();
+        assert!(c.is_solid(96.0, 0.0));
+        assert!(c.is_solid(127.4, 31.4));
+        assert!(!c.is_solid(127.6, 31.6));
+        // Outside of the map, the border tiles are used.
+        assert!(c.is_solid(1000.0, -1000.0));
+        assert!(c.is_hookable(96.0, 0.0));
+        assert!(c.is_solid(96.0, 64.0));
+        assert!(!c.is_hookable(96.0, 64.0));
+        assert!(c.is_death(0.0, 96.0));
+        assert!(!c.is_solid(0.0, 96.0));
+        assert_eq!(c.tele(40.0, 40.0).map(|t| t.number), Some(5));
+        assert_eq!(c.tele(0.0, 0.0), None);
+        assert_eq!(c.switch(40.0, 40.0), None);
+    }
+
+    #[test]
+    fn line() {
+        let c = collision();
+        let i = c.intersect_line((16.0, 80.0), (120.0, 80.0)).unwrap();
+        assert_eq!(i.pos, (96.0, 80.0));
+        assert_eq!(i.before, (95.0, 80.0));
+        assert_eq!(i.flags, COLFLAG_SOLID | COLFLAG_NOHOOK);
+        assert!(c.line_of_sight((16.0, 16.0), (80.0, 80.0)));
+        assert!(!c.line_of_sight((16.0, 16.0), (112.0, 16.0)));
+    }
+}
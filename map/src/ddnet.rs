@@ -0,0 +1,111 @@
+//! Tile indices and helpers for the DDNet-specific game layers.
+//!
+//! The teleport, speedup, switch and tune layers each store their own tile
+//! struct, see `format::TeleTile` and friends. Their `index` fields use the
+//! same numbering as the game layer, the `number` fields link tiles with the
+//! same teleporter, switch or tune zone.
+
+use common::num::Cast;
+use ndarray::Array2;
+
+use format::SpeedupTile;
+use format::SwitchTile;
+use format::TeleTile;
+use format::Tile;
+use format::TuneTile;
+
+pub const TILE_AIR: u8 = 0;
+pub const TILE_SOLID: u8 = 1;
+pub const TILE_DEATH: u8 = 2;
+pub const TILE_NOHOOK: u8 = 3;
+pub const TILE_FREEZE: u8 = 9;
+pub const TILE_TELEINEVIL: u8 = 10;
+pub const TILE_UNFREEZE: u8 = 11;
+pub const TILE_DFREEZE: u8 = 12;
+pub const TILE_DUNFREEZE: u8 = 13;
+pub const TILE_TELEINWEAPON: u8 = 14;
+pub const TILE_TELEINHOOK: u8 = 15;
+pub const TILE_SWITCHTIMEDOPEN: u8 = 22;
+pub const TILE_SWITCHTIMEDCLOSE: u8 = 23;
+pub const TILE_SWITCHOPEN: u8 = 24;
+pub const TILE_SWITCHCLOSE: u8 = 25;
+pub const TILE_TELEIN: u8 = 26;
+pub const TILE_TELEOUT: u8 = 27;
+pub const TILE_BOOST: u8 = 28;
+pub const TILE_TELECHECK: u8 = 29;
+pub const TILE_TELECHECKOUT: u8 = 30;
+pub const TILE_TELECHECKIN: u8 = 31;
+pub const TILE_START: u8 = 33;
+pub const TILE_FINISH: u8 = 34;
+pub const TILE_TELECHECKINEVIL: u8 = 63;
+pub const TILE_TUNE: u8 = 68;
+
+impl TeleTile {
+    /// Whether the tile teleports tees away, either to the exits with the
+    /// same number or, for checkpoint teleporters, to the last checkpoint.
+    pub fn is_entrance(&self) -> bool {
+        match self.index {
+            TILE_TELEIN | TILE_TELEINEVIL | TILE_TELEINWEAPON | TILE_TELEINHOOK => true,
+            TILE_TELECHECKIN | TILE_TELECHECKINEVIL => true,
+            _ => false,
+        }
+    }
+    /// Whether tees are teleported to this tile.
+    pub fn is_exit(&self) -> bool {
+        match self.index {
+            TILE_TELEOUT | TILE_TELECHECKOUT => true,
+            _ => false,
+        }
+    }
+    /// Whether the tile is a checkpoint for checkpoint teleporters.
+    pub fn is_checkpoint(&self) -> bool {
+        self.index == TILE_TELECHECK
+    }
+}
+
+impl SpeedupTile {
+    /// Direction of the speedup in degrees.
+    pub fn angle(&self) -> i16 {
+        self.angle.to_i16()
+    }
+}
+
+impl SwitchTile {
+    /// Whether the tile opens or closes the doors with the same number.
+    pub fn is_switch(&self) -> bool {
+        match self.index {
+            TILE_SWITCHOPEN | TILE_SWITCHCLOSE => true,
+            TILE_SWITCHTIMEDOPEN | TILE_SWITCHTIMEDCLOSE => true,
+            _ => false,
+        }
+    }
+}
+
+impl TuneTile {
+    /// Whether the tile applies the tune zone with the same number.
+    pub fn is_tune_zone(&self) -> bool {
+        self.index == TILE_TUNE
+    }
+}
+
+/// Tiles of all game layers of a map.
+///
+/// All layers share the dimensions of the game layer.
+#[derive(Clone, Debug)]
+pub struct GameLayerTiles {
+    pub game: Array2<Tile>,
+    pub teleport: Option<Array2<TeleTile>>,
+    pub speedup: Option<Array2<SpeedupTile>>,
+    pub front: Option<Array2<Tile>>,
+    pub switch: Option<Array2<SwitchTile>>,
+    pub tune: Option<Array2<TuneTile>>,
+}
+
+impl GameLayerTiles {
+    pub fn width(&self) -> u32 {
+        self.game.cols().assert_u32()
+    }
+    pub fn height(&self) -> u32 {
+        self.game.rows().assert_u32()
+    }
+}
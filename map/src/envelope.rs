@@ -0,0 +1,281 @@
+//! Envelopes animate the position and rotation of quads and the color of
+//! layers over time.
+//!
+//! Values are stored as fixed-point numbers with 10 fractional bits, times
+//! in milliseconds.
+
+use std::f64::consts::PI;
+
+pub const CURVETYPE_STEP: i32 = 0;
+pub const CURVETYPE_LINEAR: i32 = 1;
+pub const CURVETYPE_SLOW: i32 = 2;
+pub const CURVETYPE_FAST: i32 = 3;
+pub const CURVETYPE_SMOOTH: i32 = 4;
+pub const CURVETYPE_BEZIER: i32 = 5;
+
+/// Tangents of a bezier curve, per channel.
+///
+/// The x deltas are in milliseconds, the y deltas in the fixed-point format
+/// of the values. All zero in maps without bezier curves.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Bezier {
+    pub in_tangent_dx: [i32; 4],
+    pub in_tangent_dy: [i32; 4],
+    pub out_tangent_dx: [i32; 4],
+    pub out_tangent_dy: [i32; 4],
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct EnvPoint {
+    pub time: i32,
+    /// One of the `CURVETYPE_*` constants, describes the curve up to the
+    /// next point.
+    pub curve_type: i32,
+    pub values: [i32; 4],
+    pub bezier: Bezier,
+}
+
+#[derive(Clone, Debug)]
+pub struct Envelope {
+    pub name: Vec<u8>,
+    /// Number of used channels, 1 for sound, 3 for position and 4 for color
+    /// envelopes.
+    pub channels: i32,
+    /// Whether the envelope follows the game time instead of the local time
+    /// of the client.
+    pub synchronized: bool,
+    pub points: Vec<EnvPoint>,
+}
+
+fn fx2f(v: i32) -> f64 {
+    v as f64 / 1024.0
+}
+
+impl EnvPoint {
+    /// Parses a point from the stored form, 6 `i32`s for envelope versions
+    /// below 3 and 22 `i32`s from version 3 on.
+    pub fn from_raw(raw: &[i32]) -> EnvPoint {
+        let mut bezier = Bezier::default();
+        if raw.len() >= 22 {
+            bezier.in_tangent_dx.copy_from_slice(&raw[6..10]);
+            bezier.in_tangent_dy.copy_from_slice(&raw[10..14]);
+            bezier.out_tangent_dx.copy_from_slice(&raw[14..18]);
+            bezier.out_tangent_dy.copy_from_slice(&raw[18..22]);
+        }
+        EnvPoint {
+            time: raw[0],
+            curve_type: raw[1],
+            values: [raw[2], raw[3], raw[4], raw[5]],
+            bezier,
+        }
+    }
+    /// Appends the stored form of the point, including the tangents if
+    /// `bezier` is set.
+    pub fn write_raw(&self, result: &mut Vec<i32>, bezier: bool) {
+        result.push(self.time);
+        result.push(self.curve_type);
+        result.extend_from_slice(&self.values);
+        if bezier {
+            result.extend_from_slice(&self.bezier.in_tangent_dx);
+            result.extend_from_slice(&self.bezier.in_tangent_dy);
+            result.extend_from_slice(&self.bezier.out_tangent_dx);
+            result.extend_from_slice(&self.bezier.out_tangent_dy);
+        }
+    }
+    fn values_f32(&self) -> [f32; 4] {
+        let mut result = [0.0; 4];
+        for (r, &v) in result.iter_mut().zip(&self.values) {
+            *r = fx2f(v) as f32;
+        }
+        result
+    }
+}
+
+/// Solves `x(t) = x` for the cubic bezier curve with the control points
+/// `p0` to `p3`, the same way the reference implementation does.
+fn solve_bezier(x: f64, p0: f64, p1: f64, p2: f64, p3: f64) -> f64 {
+    let in_range = |t: f64| 0.0 <= t && t <= 1.0001;
+    let x3 = -p0 + 3.0 * p1 - 3.0 * p2 + p3;
+    let x2 = 3.0 * p0 - 6.0 * p1 + 3.0 * p2;
+    let x1 = -3.0 * p0 + 3.0 * p1;
+    let x0 = p0 - x;
+
+    if x3 == 0.0 && x2 == 0.0 {
+        // Linear: x1 * t + x0 = 0.
+        if x1 == 0.0 {
+            return 0.0;
+        }
+        -x0 / x1
+    } else if x3 == 0.0 {
+        // Quadratic: t * t + b * t + c = 0.
+        let b = x1 / x2;
+        let c = x0 / x2;
+        if c == 0.0 {
+            return 0.0;
+        }
+        let sqrt_d = (b * b - 4.0 * c).sqrt();
+        let t = (-b + sqrt_d) / 2.0;
+        if in_range(t) {
+            return t;
+        }
+        (-b - sqrt_d) / 2.0
+    } else {
+        // Cubic: t * t * t + a * t * t + b * t + c = 0, solved with
+        // Cardano's method after substituting t = y - a / 3.
+        let a = x2 / x3;
+        let b = x1 / x3;
+        let c = x0 / x3;
+        let sub = a / 3.0;
+        let p = b / 3.0 - a * a / 9.0;
+        let q = (2.0 * a * a * a / 27.0 - a * b / 3.0 + c) / 2.0;
+        let d = q * q + p * p * p;
+        if d > 0.0 {
+            let s = d.sqrt();
+            (s - q).cbrt() - (s + q).cbrt() - sub
+        } else if d == 0.0 {
+            let s = (-q).cbrt();
+            let t = 2.0 * s - sub;
+            if in_range(t) {
+                return t;
+            }
+            -s - sub
+        } else {
+            let phi = (-q / (-(p * p * p)).sqrt()).acos() / 3.0;
+            let s = 2.0 * (-p).sqrt();
+            let t1 = s * phi.cos() - sub;
+            if in_range(t1) {
+                return t1;
+            }
+            let t2 = -s * (phi + PI / 3.0).cos() - sub;
+            if in_range(t2) {
+                return t2;
+            }
+            -s * (phi - PI / 3.0).cos() - sub
+        }
+    }
+}
+
+fn bezier(p0: f64, p1: f64, p2: f64, p3: f64, t: f64) -> f64 {
+    let u = 1.0 - t;
+    u * u * u * p0 + 3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t * p3
+}
+
+fn clamp(v: f64, min: f64, max: f64) -> f64 {
+    v.max(min).min(max)
+}
+
+impl Envelope {
+    /// Returns the values of the channels at `time_ms`, like the clients
+    /// render them.
+    ///
+    /// The envelope repeats after the time of its last point. Unused
+    /// channels are evaluated as well and can be ignored.
+    pub fn evaluate(&self, time_ms: f64) -> [f32; 4] {
+        let last = match self.points.last() {
+            Some(l) => l,
+            None => return [0.0; 4],
+        };
+        if self.points.len() == 1 || last.time == 0 {
+            return last.values_f32();
+        }
+        let time = time_ms.rem_euclid(last.time as f64);
+        for w in self.points.windows(2) {
+            let (from, to) = (&w[0], &w[1]);
+            if !(from.time as f64 <= time && time <= to.time as f64) {
+                continue;
+            }
+            let delta = (to.time - from.time) as f64;
+            let mut a = if delta > 0.0 {
+                (time - from.time as f64) / delta
+            } else {
+                0.0
+            };
+            let mut result = [0.0; 4];
+            if from.curve_type == CURVETYPE_BEZIER {
+                for c in 0..4 {
+                    let (p0x, p0y) = (from.time as f64, fx2f(from.values[c]));
+                    let (p3x, p3y) = (to.time as f64, fx2f(to.values[c]));
+                    let p1x = clamp(p0x + from.bezier.out_tangent_dx[c] as f64, p0x, p3x);
+                    let p1y = p0y + fx2f(from.bezier.out_tangent_dy[c]);
+                    let p2x = clamp(p3x + to.bezier.in_tangent_dx[c] as f64, p0x, p3x);
+                    let p2y = p3y + fx2f(to.bezier.in_tangent_dy[c]);
+                    let t = clamp(solve_bezier(time, p0x, p1x, p2x, p3x), 0.0, 1.0);
+                    result[c] = bezier(p0y, p1y, p2y, p3y, t) as f32;
+                }
+                return result;
+            }
+            a = match from.curve_type {
+                CURVETYPE_STEP => 0.0,
+                CURVETYPE_SLOW => a * a * a,
+                CURVETYPE_FAST => {
+                    let b = 1.0 - a;
+                    1.0 - b * b * b
+                }
+                CURVETYPE_SMOOTH => -2.0 * a * a * a + 3.0 * a * a,
+                _ => a,
+            };
+            for c in 0..4 {
+                let v0 = fx2f(from.values[c]);
+                let v1 = fx2f(to.values[c]);
+                result[c] = (v0 + (v1 - v0) * a) as f32;
+            }
+            return result;
+        }
+        last.values_f32()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Bezier;
+    use super::EnvPoint;
+    use super::Envelope;
+    use super::CURVETYPE_BEZIER;
+    use super::CURVETYPE_LINEAR;
+    use super::CURVETYPE_STEP;
+
+    fn point(time: i32, curve_type: i32, value: i32) -> EnvPoint {
+        EnvPoint {
+            time,
+            curve_type,
+            values: [value * 1024, 0, 0, 0],
+            bezier: Bezier::default(),
+        }
+    }
+
+    fn envelope(points: Vec<EnvPoint>) -> Envelope {
+        Envelope {
+            name: Vec::new(),
+            channels: 1,
+            synchronized: false,
+            points,
+        }
+    }
+
+    #[test]
+    fn evaluate() {
+        assert_eq!(envelope(vec![]).evaluate(5.0)[0], 0.0);
+        assert_eq!(envelope(vec![point(0, 0, 3)]).evaluate(5.0)[0], 3.0);
+
+        let linear = envelope(vec![point(0, CURVETYPE_LINEAR, 0), point(1000, 0, 10)]);
+        assert_eq!(linear.evaluate(0.0)[0], 0.0);
+        assert_eq!(linear.evaluate(250.0)[0], 2.5);
+        assert_eq!(linear.evaluate(1250.0)[0], 2.5);
+        assert_eq!(linear.evaluate(-750.0)[0], 2.5);
+
+        let step = envelope(vec![point(0, CURVETYPE_STEP, 1), point(1000, 0, 10)]);
+        assert_eq!(step.evaluate(999.0)[0], 1.0);
+
+        // Bezier curve with tangents along the straight line is linear.
+        let mut from = point(0, CURVETYPE_BEZIER, 0);
+        let mut to = point(1000, 0, 10);
+        from.bezier.out_tangent_dx[0] = 100;
+        from.bezier.out_tangent_dy[0] = 1024;
+        to.bezier.in_tangent_dx[0] = -100;
+        to.bezier.in_tangent_dy[0] = -1024;
+        let straight = envelope(vec![from, to]);
+        for &t in &[0.0, 100.0, 333.0, 500.0, 900.0] {
+            assert!((straight.evaluate(t)[0] - t as f32 / 100.0).abs() < 1e-3);
+        }
+    }
+}
@@ -76,6 +76,23 @@ pub fn i32s_to_bytes(result: &mut [u8], input: &[i32]) {
     }
 }
 
+/// Inverse of `i32s_to_bytes`, `input` is padded with zeros.
+///
+/// The last byte is always zero so that the string is null-terminated,
+/// longer input is truncated.
+pub fn bytes_to_i32s(result: &mut [i32], input: &[u8]) {
+    for (i, output) in result.iter_mut().enumerate() {
+        let b = |j: usize| input.get(i * mem::size_of::<i32>() + j).map(|&b| b as i32).unwrap_or(0);
+        *output = ((b(0) + 0x80) & 0xff) << 24
+            | ((b(1) + 0x80) & 0xff) << 16
+            | ((b(2) + 0x80) & 0xff) << 8
+            | ((b(3) + 0x80) & 0xff);
+    }
+    if let Some(last) = result.last_mut() {
+        *last &= !0xff;
+    }
+}
+
 pub fn bytes_to_string(bytes: &[u8]) -> &[u8] {
     for (i, &b) in bytes.iter().enumerate() {
         if b == 0 {
@@ -243,7 +260,8 @@ impl MapItemLayerV1TilemapExtraRace {
     pub fn offset(version: i32, flags: u32) -> Option<usize> {
         let offset = match version {
             2 => MapItemLayerV1TilemapV2::sum_len(),
-            3 => MapItemLayerV1TilemapV3::sum_len(),
+            // Version 4 only adds compression of the tiles.
+            3 | 4 => MapItemLayerV1TilemapV3::sum_len(),
             _ => return None,
         };
         Some(offset + match flags {
@@ -268,7 +286,7 @@ impl MapItemLayerV1TilemapExtraRace {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[repr(C)]
 pub struct Tile {
     pub index: u8,
@@ -277,14 +295,14 @@ pub struct Tile {
     pub reserved: u8,
 }
 
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[repr(C)]
 pub struct TeleTile {
     pub number: u8,
     pub index: u8,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 #[repr(C)]
 pub struct SpeedupTile {
     pub force: u8,
@@ -294,7 +312,7 @@ pub struct SpeedupTile {
     pub angle: LeI16,
 }
 
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[repr(C)]
 pub struct SwitchTile {
     pub number: u8,
@@ -303,7 +321,7 @@ pub struct SwitchTile {
     pub delay: u8,
 }
 
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[repr(C)]
 pub struct TuneTile {
     pub number: u8,
@@ -315,6 +333,9 @@ pub const TILEFLAG_HFLIP: u8 = 1 << 1;
 pub const TILEFLAG_OPAQUE: u8 = 1 << 2;
 pub const TILEFLAG_ROTATE: u8 = 1 << 3;
 
+pub const IMAGEFORMAT_RGB: i32 = 0;
+pub const IMAGEFORMAT_RGBA: i32 = 1;
+
 pub const LAYERFLAG_DETAIL: u32 = 1;
 pub const LAYERFLAGS_ALL: u32 = 1;
 
@@ -325,6 +346,9 @@ pub const TILELAYERFLAG_FRONT: u32 = 8;
 pub const TILELAYERFLAG_SWITCH: u32 = 16;
 pub const TILELAYERFLAG_TUNE: u32 = 32;
 
+/// First tilemap version with tiles compressed using `Tile::skip`.
+pub const TILEMAP_VERSION_COMPRESSED: i32 = 4;
+
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum GroupError {
     TooShort(usize),
@@ -426,6 +450,7 @@ pub enum ImageError {
     InvalidWidth(i32),
     InvalidHeight(i32),
     InvalidNameIndex(i32),
+    InvalidFormat(i32),
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -455,6 +480,8 @@ pub enum Error {
     InconsistentGameLayerDimensions,
     InvalidTilesLength(usize),
     InvalidTeleTilesLength(usize),
+    InvalidSpeedupTilesLength(usize),
+    InvalidSwitchTilesLength(usize),
     InvalidTuneTilesLength(usize),
     InvalidVersion(i32),
     MalformedImageName(usize),
@@ -464,6 +491,10 @@ pub enum Error {
     InvalidTeleTilesDimensions(usize, u32, u32),
     // InvalidTuneTilesDimensions(length, width, height)
     InvalidTuneTilesDimensions(usize, u32, u32),
+    // InvalidEnvelope(index)
+    InvalidEnvelope(usize),
+    // InvalidSound(index)
+    InvalidSound(usize),
     EmptyVersion,
     MissingVersion,
     MissingInfo,
@@ -2,10 +2,30 @@
 extern crate common;
 extern crate datafile;
 extern crate ndarray;
+#[cfg(feature = "png")]
+extern crate png;
+#[cfg(feature = "mapdir")]
+extern crate serde;
+#[cfg(feature = "mapdir")]
+#[macro_use]
+extern crate serde_derive;
+#[cfg(feature = "mapdir")]
+extern crate serde_json;
 
+pub use lint::lint;
+pub use model::Map;
 pub use reader::Error;
 pub use reader::Reader;
 
+pub mod assets;
+pub mod automapper;
+pub mod collision;
+pub mod ddnet;
+pub mod envelope;
 #[rustfmt::skip]
 pub mod format;
+pub mod lint;
+#[cfg(feature = "mapdir")]
+pub mod mapdir;
+pub mod model;
 pub mod reader;
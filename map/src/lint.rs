@@ -0,0 +1,349 @@
+//! Statistics and checks for common mistakes in maps.
+
+use common::num::Cast;
+use ndarray::Array2;
+use std::collections::BTreeSet;
+
+use ddnet::TILE_TELEIN;
+use ddnet::TILE_TELEINEVIL;
+use ddnet::TILE_TELEINHOOK;
+use ddnet::TILE_TELEINWEAPON;
+use ddnet::TILE_TELEOUT;
+use ddnet::TILE_TUNE;
+use model::LayerKind;
+use model::Map;
+use model::Tiles;
+
+/// Thresholds for the size checks of `lint_with`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Limits {
+    /// Maximum number of tiles of a single layer.
+    pub max_layer_tiles: u64,
+    /// Maximum width and height of an embedded image in pixels.
+    pub max_image_size: u32,
+    /// Maximum size of an embedded sound in bytes.
+    pub max_sound_bytes: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits {
+            max_layer_tiles: 1_000 * 1_000,
+            max_image_size: 4096,
+            max_sound_bytes: 1024 * 1024,
+        }
+    }
+}
+
+/// Location of a layer, as indices into `Map::groups` and `Group::layers`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct LayerIndex {
+    pub group: usize,
+    pub layer: usize,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Finding {
+    /// There's no game layer, servers refuse to load the map.
+    MissingGameLayer,
+    /// The image isn't used by any layer.
+    UnusedImage(usize),
+    /// The sound isn't used by any layer.
+    UnusedSound(usize),
+    /// The layer references an image, envelope or sound that doesn't exist.
+    InvalidReference(LayerIndex),
+    /// The image is used by a tile layer, but its dimensions aren't
+    /// divisible by 16.
+    InvalidTilesetDimensions(usize),
+    /// A tile of a teleport or tune layer with an index that has no meaning
+    /// in that layer.
+    InvalidTileIndex {
+        layer: LayerIndex,
+        x: u32,
+        y: u32,
+        index: u8,
+    },
+    /// Teleporter entrances with the number, but no exit to teleport to.
+    ///
+    /// Checkpoint teleporters are not considered, they teleport to the exit
+    /// of the last checkpoint.
+    TeleporterWithoutExit(u8),
+    /// The layer has more than `Limits::max_layer_tiles` tiles.
+    LayerTooLarge(LayerIndex),
+    /// The embedded image is wider or higher than `Limits::max_image_size`.
+    ImageTooLarge(usize),
+    /// The embedded sound is larger than `Limits::max_sound_bytes`.
+    SoundTooLarge(usize),
+}
+
+/// Counts of the parts of a map.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Statistics {
+    pub images: usize,
+    pub embedded_images: usize,
+    /// Size of the pixel data of all embedded images.
+    pub embedded_image_bytes: u64,
+    pub sounds: usize,
+    pub embedded_sounds: usize,
+    pub embedded_sound_bytes: u64,
+    pub envelopes: usize,
+    pub envelope_points: usize,
+    pub groups: usize,
+    pub tile_layers: usize,
+    pub quads_layers: usize,
+    pub sounds_layers: usize,
+    /// Number of tiles of all tile layers, including empty ones.
+    pub tiles: u64,
+    pub quads: usize,
+    pub sound_sources: usize,
+}
+
+/// Returns counts of the parts of the map.
+pub fn statistics(map: &Map) -> Statistics {
+    let mut result = Statistics {
+        images: map.images.len(),
+        sounds: map.sounds.len(),
+        envelopes: map.envelopes.len(),
+        groups: map.groups.len(),
+        ..Statistics::default()
+    };
+    for data in map.images.iter().filter_map(|i| i.data.as_ref()) {
+        result.embedded_images += 1;
+        result.embedded_image_bytes += data.len().u64();
+    }
+    for data in map.sounds.iter().filter_map(|s| s.data.as_ref()) {
+        result.embedded_sounds += 1;
+        result.embedded_sound_bytes += data.len().u64();
+    }
+    result.envelope_points = map.envelopes.iter().map(|e| e.points.len()).sum();
+    for layer in map.groups.iter().flat_map(|g| &g.layers) {
+        match layer.kind {
+            LayerKind::Tiles(ref t) => {
+                result.tile_layers += 1;
+                result.tiles += t.tiles.width().u64() * t.tiles.height().u64();
+            }
+            LayerKind::Quads(ref q) => {
+                result.quads_layers += 1;
+                result.quads += q.num_quads;
+            }
+            LayerKind::Sounds(ref s) => {
+                result.sounds_layers += 1;
+                result.sound_sources += s.num_sources;
+            }
+        }
+    }
+    result
+}
+
+/// Checks the map for common mistakes with the default `Limits`.
+pub fn lint(map: &Map) -> Vec<Finding> {
+    lint_with(map, &Limits::default())
+}
+
+fn invalid_tiles<T, F>(tiles: &Array2<T>, layer: LayerIndex, result: &mut Vec<Finding>, index: F)
+where
+    F: Fn(&T) -> Option<u8>,
+{
+    for ((y, x), tile) in tiles.indexed_iter() {
+        if let Some(index) = index(tile) {
+            result.push(Finding::InvalidTileIndex {
+                layer,
+                x: x.assert_u32(),
+                y: y.assert_u32(),
+                index,
+            });
+        }
+    }
+}
+
+/// Returns whether the index is valid.
+fn mark_used(used: &mut [bool], index: Option<usize>) -> bool {
+    match index.map(|i| used.get_mut(i)) {
+        None => true,
+        Some(Some(u)) => {
+            *u = true;
+            true
+        }
+        Some(None) => false,
+    }
+}
+
+/// Checks the map for common mistakes.
+///
+/// The findings about layers come first, in the order of the layers.
+pub fn lint_with(map: &Map, limits: &Limits) -> Vec<Finding> {
+    let mut result = Vec::new();
+    let mut game_layer = false;
+    let mut used_images = vec![false; map.images.len()];
+    let mut used_sounds = vec![false; map.sounds.len()];
+    let mut tileset_images = BTreeSet::new();
+    let mut entrances = BTreeSet::new();
+    let mut exits = BTreeSet::new();
+    for (g, group) in map.groups.iter().enumerate() {
+        for (l, layer) in group.layers.iter().enumerate() {
+            let index = LayerIndex { group: g, layer: l };
+            let (image, sound, envelope) = match layer.kind {
+                LayerKind::Tiles(ref t) => (t.image, None, t.color_env.map(|(e, _)| e)),
+                LayerKind::Quads(ref q) => (q.image, None, None),
+                LayerKind::Sounds(ref s) => (None, s.sound, None),
+            };
+            let valid = mark_used(&mut used_images, image)
+                & mark_used(&mut used_sounds, sound)
+                & envelope.map(|e| e < map.envelopes.len()).unwrap_or(true);
+            if !valid {
+                result.push(Finding::InvalidReference(index));
+            }
+            let t = match layer.kind {
+                LayerKind::Tiles(ref t) => t,
+                _ => continue,
+            };
+            tileset_images.extend(t.image.filter(|&i| i < map.images.len()));
+            if t.tiles.width().u64() * t.tiles.height().u64() > limits.max_layer_tiles {
+                result.push(Finding::LayerTooLarge(index));
+            }
+            match t.tiles {
+                Tiles::Game(_) => game_layer = true,
+                Tiles::Teleport(ref tiles) => {
+                    for tile in tiles {
+                        match tile.index {
+                            TILE_TELEIN | TILE_TELEINEVIL | TILE_TELEINWEAPON | TILE_TELEINHOOK => {
+                                entrances.insert(tile.number);
+                            }
+                            TILE_TELEOUT => {
+                                exits.insert(tile.number);
+                            }
+                            _ => {}
+                        }
+                    }
+                    invalid_tiles(tiles, index, &mut result, |t| {
+                        let valid =
+                            t.index == 0 || t.is_entrance() || t.is_exit() || t.is_checkpoint();
+                        Some(t.index).filter(|_| !valid)
+                    });
+                }
+                Tiles::Tune(ref tiles) => {
+                    invalid_tiles(tiles, index, &mut result, |t| {
+                        Some(t.index).filter(|&i| i != 0 && i != TILE_TUNE)
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+    if !game_layer {
+        result.push(Finding::MissingGameLayer);
+    }
+    for (i, image) in map.images.iter().enumerate() {
+        if !used_images[i] {
+            result.push(Finding::UnusedImage(i));
+        }
+        if tileset_images.contains(&i) && (image.width % 16 != 0 || image.height % 16 != 0) {
+            result.push(Finding::InvalidTilesetDimensions(i));
+        }
+        let max = limits.max_image_size;
+        if image.is_embedded() && (image.width > max || image.height > max) {
+            result.push(Finding::ImageTooLarge(i));
+        }
+    }
+    for (i, sound) in map.sounds.iter().enumerate() {
+        if !used_sounds[i] {
+            result.push(Finding::UnusedSound(i));
+        }
+        if sound.data.as_ref().map(|d| d.len()).unwrap_or(0) > limits.max_sound_bytes {
+            result.push(Finding::SoundTooLarge(i));
+        }
+    }
+    result.extend(
+        entrances
+            .difference(&exits)
+            .map(|&n| Finding::TeleporterWithoutExit(n)),
+    );
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::Array2;
+
+    use super::lint;
+    use super::statistics;
+    use super::Finding;
+    use super::LayerIndex;
+    use ddnet::TILE_TELEIN;
+    use ddnet::TILE_TELEOUT;
+    use format::TeleTile;
+    use model::Group;
+    use model::Image;
+    use model::Layer;
+    use model::LayerKind;
+    use model::Map;
+    use model::TileLayer;
+    use model::Tiles;
+
+    #[test]
+    fn findings() {
+        let mut tiles = Array2::from_elem((2, 2), TeleTile::default());
+        tiles[(0, 0)] = TeleTile {
+            number: 1,
+            index: TILE_TELEIN,
+        };
+        tiles[(0, 1)] = TeleTile {
+            number: 2,
+            index: TILE_TELEIN,
+        };
+        tiles[(1, 0)] = TeleTile {
+            number: 1,
+            index: TILE_TELEOUT,
+        };
+        tiles[(1, 1)] = TeleTile {
+            number: 0,
+            index: 1,
+        };
+        let mut tele = TileLayer::new(2, 2);
+        tele.tiles = Tiles::Teleport(tiles);
+        let mut normal = TileLayer::new(2, 2);
+        normal.image = Some(1);
+        let layer = |t| Layer {
+            name: Vec::new(),
+            detail: false,
+            kind: LayerKind::Tiles(t),
+        };
+        let image = |width| Image {
+            name: b"image".to_vec(),
+            width,
+            height: 256,
+            data: None,
+        };
+        let mut map = Map::default();
+        map.images = vec![image(256), image(200)];
+        map.groups.push(Group {
+            name: Vec::new(),
+            offset_x: 0,
+            offset_y: 0,
+            parallax_x: 100,
+            parallax_y: 100,
+            clipping: None,
+            layers: vec![layer(tele), layer(normal)],
+        });
+
+        let layer = LayerIndex { group: 0, layer: 0 };
+        assert_eq!(
+            lint(&map),
+            [
+                Finding::InvalidTileIndex {
+                    layer,
+                    x: 1,
+                    y: 1,
+                    index: 1,
+                },
+                Finding::MissingGameLayer,
+                Finding::UnusedImage(0),
+                Finding::InvalidTilesetDimensions(1),
+                Finding::TeleporterWithoutExit(2),
+            ]
+        );
+        let stats = statistics(&map);
+        assert_eq!(stats.tile_layers, 2);
+        assert_eq!(stats.tiles, 8);
+    }
+}
@@ -0,0 +1,751 @@
+//! Conversion between maps and a directory of text files that can be
+//! tracked in version control.
+//!
+//! The directory looks like this:
+//!
+//! ```text
+//! info.json           author, version, credits, license, settings
+//! envelopes.json      all envelopes with their points
+//! images/<i>.json     name and dimensions of image i
+//! images/<i>.rgba     raw RGBA pixels, only for embedded images
+//! sounds/<i>.json     name of sound i
+//! sounds/<i>.opus     sound data, only for embedded sounds
+//! groups/<g>/group.json
+//! groups/<g>/<l>.json properties of layer l of group g
+//! groups/<g>/<l>.csv  tiles of tile layers, one line per row
+//! groups/<g>/<l>.bin  stored form of quads and sound sources
+//! ```
+//!
+//! Tiles are written as their fields separated by `:`, e.g. `index:flags`
+//! for normal tiles, and separated by `,` within a row.
+
+use common::num::Cast;
+use common::num::LeI16;
+use ndarray::Array2;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use envelope::Bezier;
+use envelope::EnvPoint;
+use envelope::Envelope;
+use format::SpeedupTile;
+use format::SwitchTile;
+use format::TeleTile;
+use format::Tile;
+use format::TuneTile;
+use model::Group;
+use model::Image;
+use model::Info;
+use model::Layer;
+use model::LayerKind;
+use model::Map;
+use model::QuadsLayer;
+use model::Sound;
+use model::SoundsLayer;
+use model::TileLayer;
+use model::Tiles;
+use reader::Clipping;
+use reader::Color;
+
+#[derive(Debug)]
+pub enum MapdirError {
+    Io(io::Error),
+    Json(PathBuf, serde_json::Error),
+    /// A string of the map that isn't valid UTF-8.
+    NonUtf8String(Vec<u8>),
+    /// Malformed tile in the given file, at the 1-based line.
+    InvalidTile(PathBuf, usize),
+    /// Tile file with the wrong number of rows or columns.
+    InvalidTilesDimensions(PathBuf),
+    /// Pixel data of the wrong size for the dimensions of an image.
+    InvalidImageData(PathBuf),
+}
+
+impl From<io::Error> for MapdirError {
+    fn from(err: io::Error) -> MapdirError {
+        MapdirError::Io(err)
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+struct InfoJson {
+    author: Option<String>,
+    version: Option<String>,
+    credits: Option<String>,
+    license: Option<String>,
+    settings: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct ImageJson {
+    name: String,
+    width: u32,
+    height: u32,
+    embedded: bool,
+}
+
+#[derive(Deserialize, Serialize)]
+struct SoundJson {
+    name: String,
+    embedded: bool,
+}
+
+#[derive(Deserialize, Serialize)]
+struct EnvelopeJson {
+    name: String,
+    channels: i32,
+    synchronized: bool,
+    points: Vec<EnvPointJson>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct EnvPointJson {
+    time: i32,
+    curve_type: i32,
+    values: [i32; 4],
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bezier: Option<BezierJson>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct BezierJson {
+    in_tangent_dx: [i32; 4],
+    in_tangent_dy: [i32; 4],
+    out_tangent_dx: [i32; 4],
+    out_tangent_dy: [i32; 4],
+}
+
+#[derive(Deserialize, Serialize)]
+struct GroupJson {
+    name: String,
+    offset_x: i32,
+    offset_y: i32,
+    parallax_x: i32,
+    parallax_y: i32,
+    clipping: Option<ClippingJson>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct ClippingJson {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+#[derive(Deserialize, Serialize)]
+struct LayerJson {
+    name: String,
+    detail: bool,
+    #[serde(flatten)]
+    kind: LayerKindJson,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum LayerKindJson {
+    Tiles {
+        tiles: TilesKind,
+        width: u32,
+        height: u32,
+        color: [u8; 4],
+        color_env: Option<usize>,
+        color_env_offset: i32,
+        image: Option<usize>,
+    },
+    Quads {
+        image: Option<usize>,
+        num_quads: usize,
+    },
+    Sounds {
+        sound: Option<usize>,
+        num_sources: usize,
+        legacy: bool,
+    },
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum TilesKind {
+    Normal,
+    Game,
+    Teleport,
+    Speedup,
+    Front,
+    Switch,
+    Tune,
+}
+
+/// Tile types that can be written as a line of text.
+trait TextTile: Copy + Default {
+    fn write(&self, result: &mut String);
+    fn parse(fields: &[&str]) -> Option<Self>;
+}
+
+fn u8s(fields: &[&str]) -> Option<Vec<u8>> {
+    fields.iter().map(|f| f.parse().ok()).collect()
+}
+
+impl TextTile for Tile {
+    fn write(&self, result: &mut String) {
+        result.push_str(&format!("{}:{}", self.index, self.flags));
+    }
+    fn parse(fields: &[&str]) -> Option<Tile> {
+        match *u8s(fields)? {
+            [index, flags] => Some(Tile {
+                index,
+                flags,
+                ..Tile::default()
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl TextTile for TeleTile {
+    fn write(&self, result: &mut String) {
+        result.push_str(&format!("{}:{}", self.number, self.index));
+    }
+    fn parse(fields: &[&str]) -> Option<TeleTile> {
+        match *u8s(fields)? {
+            [number, index] => Some(TeleTile { number, index }),
+            _ => None,
+        }
+    }
+}
+
+impl TextTile for SpeedupTile {
+    fn write(&self, result: &mut String) {
+        result.push_str(&format!(
+            "{}:{}:{}:{}",
+            self.force,
+            self.max_speed,
+            self.index,
+            self.angle.to_i16()
+        ));
+    }
+    fn parse(fields: &[&str]) -> Option<SpeedupTile> {
+        if fields.len() != 4 {
+            return None;
+        }
+        match *u8s(&fields[..3])? {
+            [force, max_speed, index] => Some(SpeedupTile {
+                force,
+                max_speed,
+                index,
+                angle: fields[3].parse().ok().map(LeI16::from_i16)?,
+                ..SpeedupTile::default()
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl TextTile for SwitchTile {
+    fn write(&self, result: &mut String) {
+        result.push_str(&format!(
+            "{}:{}:{}:{}",
+            self.number, self.index, self.flags, self.delay
+        ));
+    }
+    fn parse(fields: &[&str]) -> Option<SwitchTile> {
+        match *u8s(fields)? {
+            [number, index, flags, delay] => Some(SwitchTile {
+                number,
+                index,
+                flags,
+                delay,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl TextTile for TuneTile {
+    fn write(&self, result: &mut String) {
+        result.push_str(&format!("{}:{}", self.number, self.index));
+    }
+    fn parse(fields: &[&str]) -> Option<TuneTile> {
+        match *u8s(fields)? {
+            [number, index] => Some(TuneTile { number, index }),
+            _ => None,
+        }
+    }
+}
+
+fn tiles_to_text<T: TextTile>(tiles: &Array2<T>) -> String {
+    let mut result = String::new();
+    for row in tiles.genrows() {
+        for (i, tile) in row.iter().enumerate() {
+            if i != 0 {
+                result.push(',');
+            }
+            tile.write(&mut result);
+        }
+        result.push('\n');
+    }
+    result
+}
+
+fn tiles_from_text<T: TextTile>(
+    path: &Path,
+    text: &str,
+    width: u32,
+    height: u32,
+) -> Result<Array2<T>, MapdirError> {
+    let invalid_dimensions = || MapdirError::InvalidTilesDimensions(path.to_owned());
+    let mut tiles = Vec::with_capacity(width.usize() * height.usize());
+    let mut rows = 0;
+    for (i, line) in text.lines().enumerate() {
+        let start = tiles.len();
+        for tile in line.split(',') {
+            let fields: Vec<&str> = tile.trim().split(':').collect();
+            let tile = T::parse(&fields)
+                .ok_or_else(|| MapdirError::InvalidTile(path.to_owned(), i + 1))?;
+            tiles.push(tile);
+        }
+        if tiles.len() - start != width.usize() {
+            return Err(invalid_dimensions());
+        }
+        rows += 1;
+    }
+    if rows != height.usize() {
+        return Err(invalid_dimensions());
+    }
+    Ok(Array2::from_shape_vec((height.usize(), width.usize()), tiles).unwrap())
+}
+
+fn string(bytes: &[u8]) -> Result<String, MapdirError> {
+    String::from_utf8(bytes.to_vec()).map_err(|e| MapdirError::NonUtf8String(e.into_bytes()))
+}
+
+fn opt_string(bytes: &Option<Vec<u8>>) -> Result<Option<String>, MapdirError> {
+    bytes.as_ref().map(|b| string(b)).transpose()
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), MapdirError> {
+    let mut json = serde_json::to_string_pretty(value).unwrap();
+    json.push('\n');
+    fs::write(path, json)?;
+    Ok(())
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, MapdirError> {
+    let json = fs::read(path)?;
+    serde_json::from_slice(&json).map_err(|e| MapdirError::Json(path.to_owned(), e))
+}
+
+/// Calls `f` with the JSON files `<dir>/<i>.json` for `i = 0, 1, …`, until
+/// the first missing one.
+fn read_numbered<T, F>(dir: &Path, mut f: F) -> Result<Vec<T>, MapdirError>
+where
+    F: FnMut(&Path, usize) -> Result<T, MapdirError>,
+{
+    let mut result = Vec::new();
+    while dir.join(format!("{}.json", result.len())).exists() {
+        let item = f(dir, result.len())?;
+        result.push(item);
+    }
+    Ok(result)
+}
+
+/// Creates an empty directory, removing the old contents.
+fn create_empty_dir(path: &Path) -> io::Result<()> {
+    if path.exists() {
+        fs::remove_dir_all(path)?;
+    }
+    fs::create_dir_all(path)
+}
+
+fn write_tile_layer(path: &Path, layer: &TileLayer) -> Result<LayerKindJson, MapdirError> {
+    let (kind, text) = match layer.tiles {
+        Tiles::Normal(ref t) => (TilesKind::Normal, tiles_to_text(t)),
+        Tiles::Game(ref t) => (TilesKind::Game, tiles_to_text(t)),
+        Tiles::Teleport(ref t) => (TilesKind::Teleport, tiles_to_text(t)),
+        Tiles::Speedup(ref t) => (TilesKind::Speedup, tiles_to_text(t)),
+        Tiles::Front(ref t) => (TilesKind::Front, tiles_to_text(t)),
+        Tiles::Switch(ref t) => (TilesKind::Switch, tiles_to_text(t)),
+        Tiles::Tune(ref t) => (TilesKind::Tune, tiles_to_text(t)),
+    };
+    fs::write(path.with_extension("csv"), text)?;
+    let c = layer.color;
+    Ok(LayerKindJson::Tiles {
+        tiles: kind,
+        width: layer.tiles.width(),
+        height: layer.tiles.height(),
+        color: [c.red, c.green, c.blue, c.alpha],
+        color_env: layer.color_env.map(|(e, _)| e),
+        color_env_offset: layer.color_env.map(|(_, o)| o).unwrap_or(0),
+        image: layer.image,
+    })
+}
+
+fn read_tiles(path: &Path, kind: TilesKind, width: u32, height: u32) -> Result<Tiles, MapdirError> {
+    let path = path.with_extension("csv");
+    let text = fs::read_to_string(&path)?;
+    let p = &path;
+    Ok(match kind {
+        TilesKind::Normal => Tiles::Normal(tiles_from_text(p, &text, width, height)?),
+        TilesKind::Game => Tiles::Game(tiles_from_text(p, &text, width, height)?),
+        TilesKind::Teleport => Tiles::Teleport(tiles_from_text(p, &text, width, height)?),
+        TilesKind::Speedup => Tiles::Speedup(tiles_from_text(p, &text, width, height)?),
+        TilesKind::Front => Tiles::Front(tiles_from_text(p, &text, width, height)?),
+        TilesKind::Switch => Tiles::Switch(tiles_from_text(p, &text, width, height)?),
+        TilesKind::Tune => Tiles::Tune(tiles_from_text(p, &text, width, height)?),
+    })
+}
+
+fn write_layer(path: &Path, layer: &Layer) -> Result<(), MapdirError> {
+    let kind = match layer.kind {
+        LayerKind::Tiles(ref t) => write_tile_layer(path, t)?,
+        LayerKind::Quads(ref q) => {
+            fs::write(path.with_extension("bin"), &q.data)?;
+            LayerKindJson::Quads {
+                image: q.image,
+                num_quads: q.num_quads,
+            }
+        }
+        LayerKind::Sounds(ref s) => {
+            fs::write(path.with_extension("bin"), &s.data)?;
+            LayerKindJson::Sounds {
+                sound: s.sound,
+                num_sources: s.num_sources,
+                legacy: s.legacy,
+            }
+        }
+    };
+    write_json(
+        &path.with_extension("json"),
+        &LayerJson {
+            name: string(&layer.name)?,
+            detail: layer.detail,
+            kind,
+        },
+    )
+}
+
+fn read_layer(path: &Path) -> Result<Layer, MapdirError> {
+    let json: LayerJson = read_json(&path.with_extension("json"))?;
+    let kind = match json.kind {
+        LayerKindJson::Tiles {
+            tiles,
+            width,
+            height,
+            color,
+            color_env,
+            color_env_offset,
+            image,
+        } => LayerKind::Tiles(TileLayer {
+            color: Color {
+                red: color[0],
+                green: color[1],
+                blue: color[2],
+                alpha: color[3],
+            },
+            color_env: color_env.map(|e| (e, color_env_offset)),
+            image,
+            tiles: read_tiles(path, tiles, width, height)?,
+        }),
+        LayerKindJson::Quads { image, num_quads } => LayerKind::Quads(QuadsLayer {
+            image,
+            num_quads,
+            data: fs::read(path.with_extension("bin"))?,
+        }),
+        LayerKindJson::Sounds {
+            sound,
+            num_sources,
+            legacy,
+        } => LayerKind::Sounds(SoundsLayer {
+            sound,
+            num_sources,
+            data: fs::read(path.with_extension("bin"))?,
+            legacy,
+        }),
+    };
+    Ok(Layer {
+        name: json.name.into_bytes(),
+        detail: json.detail,
+        kind,
+    })
+}
+
+fn envelope_to_json(envelope: &Envelope) -> Result<EnvelopeJson, MapdirError> {
+    let points = envelope
+        .points
+        .iter()
+        .map(|p| EnvPointJson {
+            time: p.time,
+            curve_type: p.curve_type,
+            values: p.values,
+            bezier: if p.bezier != Bezier::default() {
+                Some(BezierJson {
+                    in_tangent_dx: p.bezier.in_tangent_dx,
+                    in_tangent_dy: p.bezier.in_tangent_dy,
+                    out_tangent_dx: p.bezier.out_tangent_dx,
+                    out_tangent_dy: p.bezier.out_tangent_dy,
+                })
+            } else {
+                None
+            },
+        })
+        .collect();
+    Ok(EnvelopeJson {
+        name: string(&envelope.name)?,
+        channels: envelope.channels,
+        synchronized: envelope.synchronized,
+        points,
+    })
+}
+
+fn envelope_from_json(json: EnvelopeJson) -> Envelope {
+    let points = json
+        .points
+        .into_iter()
+        .map(|p| EnvPoint {
+            time: p.time,
+            curve_type: p.curve_type,
+            values: p.values,
+            bezier: p
+                .bezier
+                .map(|b| Bezier {
+                    in_tangent_dx: b.in_tangent_dx,
+                    in_tangent_dy: b.in_tangent_dy,
+                    out_tangent_dx: b.out_tangent_dx,
+                    out_tangent_dy: b.out_tangent_dy,
+                })
+                .unwrap_or_default(),
+        })
+        .collect();
+    Envelope {
+        name: json.name.into_bytes(),
+        channels: json.channels,
+        synchronized: json.synchronized,
+        points,
+    }
+}
+
+impl Map {
+    /// Writes the map as a directory of text files, see the module
+    /// documentation for the layout.
+    ///
+    /// The `images`, `sounds` and `groups` subdirectories are replaced if
+    /// they already exist.
+    pub fn save_dir<P: AsRef<Path>>(&self, dir: P) -> Result<(), MapdirError> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let info = &self.info;
+        write_json(
+            &dir.join("info.json"),
+            &InfoJson {
+                author: opt_string(&info.author)?,
+                version: opt_string(&info.version)?,
+                credits: opt_string(&info.credits)?,
+                license: opt_string(&info.license)?,
+                settings: info
+                    .settings
+                    .iter()
+                    .map(|s| string(s))
+                    .collect::<Result<_, _>>()?,
+            },
+        )?;
+        let envelopes: Result<Vec<_>, _> = self.envelopes.iter().map(envelope_to_json).collect();
+        write_json(&dir.join("envelopes.json"), &envelopes?)?;
+
+        let images = dir.join("images");
+        create_empty_dir(&images)?;
+        for (i, image) in self.images.iter().enumerate() {
+            if let Some(ref data) = image.data {
+                fs::write(images.join(format!("{}.rgba", i)), data)?;
+            }
+            write_json(
+                &images.join(format!("{}.json", i)),
+                &ImageJson {
+                    name: string(&image.name)?,
+                    width: image.width,
+                    height: image.height,
+                    embedded: image.data.is_some(),
+                },
+            )?;
+        }
+
+        let sounds = dir.join("sounds");
+        create_empty_dir(&sounds)?;
+        for (i, sound) in self.sounds.iter().enumerate() {
+            if let Some(ref data) = sound.data {
+                fs::write(sounds.join(format!("{}.opus", i)), data)?;
+            }
+            write_json(
+                &sounds.join(format!("{}.json", i)),
+                &SoundJson {
+                    name: string(&sound.name)?,
+                    embedded: sound.data.is_some(),
+                },
+            )?;
+        }
+
+        let groups = dir.join("groups");
+        create_empty_dir(&groups)?;
+        for (i, group) in self.groups.iter().enumerate() {
+            let group_dir = groups.join(i.to_string());
+            fs::create_dir(&group_dir)?;
+            write_json(
+                &group_dir.join("group.json"),
+                &GroupJson {
+                    name: string(&group.name)?,
+                    offset_x: group.offset_x,
+                    offset_y: group.offset_y,
+                    parallax_x: group.parallax_x,
+                    parallax_y: group.parallax_y,
+                    clipping: group.clipping.map(|c| ClippingJson {
+                        x: c.x,
+                        y: c.y,
+                        width: c.width,
+                        height: c.height,
+                    }),
+                },
+            )?;
+            for (j, layer) in group.layers.iter().enumerate() {
+                write_layer(&group_dir.join(j.to_string()), layer)?;
+            }
+        }
+        Ok(())
+    }
+    /// Reads a map written by `save_dir`.
+    pub fn load_dir<P: AsRef<Path>>(dir: P) -> Result<Map, MapdirError> {
+        let dir = dir.as_ref();
+        let info: InfoJson = read_json(&dir.join("info.json"))?;
+        let info = Info {
+            author: info.author.map(String::into_bytes),
+            version: info.version.map(String::into_bytes),
+            credits: info.credits.map(String::into_bytes),
+            license: info.license.map(String::into_bytes),
+            settings: info.settings.into_iter().map(String::into_bytes).collect(),
+        };
+        let envelopes: Vec<EnvelopeJson> = read_json(&dir.join("envelopes.json"))?;
+        let envelopes = envelopes.into_iter().map(envelope_from_json).collect();
+
+        let images = read_numbered(&dir.join("images"), |dir, i| {
+            let json: ImageJson = read_json(&dir.join(format!("{}.json", i)))?;
+            let data = if json.embedded {
+                let path = dir.join(format!("{}.rgba", i));
+                let data = fs::read(&path)?;
+                if data.len().u64() != json.width.u64() * json.height.u64() * 4 {
+                    return Err(MapdirError::InvalidImageData(path));
+                }
+                Some(data)
+            } else {
+                None
+            };
+            Ok(Image {
+                name: json.name.into_bytes(),
+                width: json.width,
+                height: json.height,
+                data,
+            })
+        })?;
+
+        let sounds = read_numbered(&dir.join("sounds"), |dir, i| {
+            let json: SoundJson = read_json(&dir.join(format!("{}.json", i)))?;
+            let data = if json.embedded {
+                Some(fs::read(dir.join(format!("{}.opus", i)))?)
+            } else {
+                None
+            };
+            Ok(Sound {
+                name: json.name.into_bytes(),
+                data,
+            })
+        })?;
+
+        let mut groups = Vec::new();
+        loop {
+            let group_dir = dir.join("groups").join(groups.len().to_string());
+            if !group_dir.exists() {
+                break;
+            }
+            let json: GroupJson = read_json(&group_dir.join("group.json"))?;
+            let layers = read_numbered(&group_dir, |dir, i| read_layer(&dir.join(i.to_string())))?;
+            groups.push(Group {
+                name: json.name.into_bytes(),
+                offset_x: json.offset_x,
+                offset_y: json.offset_y,
+                parallax_x: json.parallax_x,
+                parallax_y: json.parallax_y,
+                clipping: json.clipping.map(|c| Clipping {
+                    x: c.x,
+                    y: c.y,
+                    width: c.width,
+                    height: c.height,
+                }),
+                layers,
+            });
+        }
+        Ok(Map {
+            info,
+            images,
+            envelopes,
+            groups,
+            sounds,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    use format::Tile;
+    use format::TILEFLAG_ROTATE;
+    use model::Group;
+    use model::Layer;
+    use model::LayerKind;
+    use model::Map;
+    use model::TileLayer;
+
+    #[test]
+    fn round_trip() {
+        let mut layer = TileLayer::new(3, 2);
+        layer.set_tile(
+            2,
+            1,
+            Tile {
+                index: 5,
+                flags: TILEFLAG_ROTATE,
+                ..Tile::default()
+            },
+        );
+        let mut map = Map::default();
+        map.info.author = Some(b"author".to_vec());
+        map.info.settings.push(b"sv_gametype dm".to_vec());
+        map.groups.push(Group {
+            name: b"Game".to_vec(),
+            offset_x: 0,
+            offset_y: 0,
+            parallax_x: 100,
+            parallax_y: 100,
+            clipping: None,
+            layers: vec![Layer {
+                name: b"Tiles".to_vec(),
+                detail: false,
+                kind: LayerKind::Tiles(layer),
+            }],
+        });
+
+        let dir = env::temp_dir().join(format!("libtw2-mapdir-{}", process::id()));
+        map.save_dir(&dir).unwrap();
+        let csv = fs::read_to_string(dir.join("groups/0/0.csv")).unwrap();
+        assert_eq!(csv, "0:0,0:0,0:0\n0:0,0:0,5:8\n");
+        let loaded = Map::load_dir(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let written = map.write(Vec::new()).unwrap();
+        assert_eq!(loaded.write(Vec::new()).unwrap(), written);
+    }
+}
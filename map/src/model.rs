@@ -0,0 +1,925 @@
+use common::num::Cast;
+use common::slice;
+use datafile as df;
+pub use envelope::EnvPoint;
+pub use envelope::Envelope;
+use ndarray::Array2;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use format;
+use format::Error as MapError;
+use format::SpeedupTile;
+use format::SwitchTile;
+use format::TeleTile;
+use format::Tile;
+use format::TuneTile;
+use reader;
+use reader::Clipping;
+use reader::Color;
+use reader::Error;
+use reader::LayerTilemapType;
+use reader::LayerType;
+
+/// Editable in-memory representation of a map.
+///
+/// Images, envelopes and sounds are referenced by their index in the
+/// respective vectors. Items the model doesn't know about are dropped when
+/// loading, so saving a loaded map only keeps the parts described here.
+#[derive(Clone, Debug, Default)]
+pub struct Map {
+    pub info: Info,
+    pub images: Vec<Image>,
+    pub envelopes: Vec<Envelope>,
+    pub groups: Vec<Group>,
+    pub sounds: Vec<Sound>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Info {
+    pub author: Option<Vec<u8>>,
+    pub version: Option<Vec<u8>>,
+    pub credits: Option<Vec<u8>>,
+    pub license: Option<Vec<u8>>,
+    /// Server settings, one command per entry.
+    pub settings: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Image {
+    pub name: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+    /// RGBA pixels, `width * height * 4` bytes. `None` for external
+    /// images.
+    pub data: Option<Vec<u8>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Group {
+    pub name: Vec<u8>,
+    pub offset_x: i32,
+    pub offset_y: i32,
+    pub parallax_x: i32,
+    pub parallax_y: i32,
+    pub clipping: Option<Clipping>,
+    pub layers: Vec<Layer>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Layer {
+    pub name: Vec<u8>,
+    pub detail: bool,
+    pub kind: LayerKind,
+}
+
+#[derive(Clone, Debug)]
+pub enum LayerKind {
+    Tiles(TileLayer),
+    Quads(QuadsLayer),
+    Sounds(SoundsLayer),
+}
+
+#[derive(Clone, Debug)]
+pub struct TileLayer {
+    pub color: Color,
+    pub color_env: Option<(usize, i32)>,
+    pub image: Option<usize>,
+    pub tiles: Tiles,
+}
+
+/// Tiles of a tile layer, indexed by `[y, x]`.
+#[derive(Clone, Debug)]
+pub enum Tiles {
+    Normal(Array2<Tile>),
+    Game(Array2<Tile>),
+    Teleport(Array2<TeleTile>),
+    Speedup(Array2<SpeedupTile>),
+    Front(Array2<Tile>),
+    Switch(Array2<SwitchTile>),
+    Tune(Array2<TuneTile>),
+}
+
+/// Quads are kept in their stored form.
+#[derive(Clone, Debug)]
+pub struct QuadsLayer {
+    pub image: Option<usize>,
+    pub num_quads: usize,
+    pub data: Vec<u8>,
+}
+
+/// Sound sources are kept in their stored form.
+#[derive(Clone, Debug)]
+pub struct SoundsLayer {
+    pub sound: Option<usize>,
+    pub num_sources: usize,
+    pub data: Vec<u8>,
+    pub legacy: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct Sound {
+    pub name: Vec<u8>,
+    /// Opus file contents, `None` for external sounds.
+    pub data: Option<Vec<u8>>,
+}
+
+/// Game the map is written for.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Target {
+    /// DDNet, supports everything of the model.
+    Ddnet,
+    /// Teeworlds 0.7, which compresses tiles and lacks the DDNet
+    /// extensions.
+    Teeworlds07,
+}
+
+/// Part of a map that can't be represented for a `Target`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Incompatibility {
+    /// The server settings are dropped.
+    Settings,
+    /// The DDNet-specific tile layer or sounds layer is dropped.
+    Layer { group: usize, layer: usize },
+    /// The sound is dropped.
+    Sound(usize),
+}
+
+impl Target {
+    fn supports(self, layer: &Layer) -> bool {
+        match (self, &layer.kind) {
+            (Target::Ddnet, _) => true,
+            (Target::Teeworlds07, &LayerKind::Tiles(ref t)) => match t.tiles {
+                Tiles::Normal(_) | Tiles::Game(_) => true,
+                _ => false,
+            },
+            (Target::Teeworlds07, &LayerKind::Quads(_)) => true,
+            (Target::Teeworlds07, &LayerKind::Sounds(_)) => false,
+        }
+    }
+}
+
+fn resized<T: Copy + Default>(tiles: &Array2<T>, width: u32, height: u32) -> Array2<T> {
+    let (old_height, old_width) = tiles.dim();
+    Array2::from_shape_fn((height.usize(), width.usize()), |(y, x)| {
+        if y < old_height && x < old_width {
+            tiles[[y, x]]
+        } else {
+            T::default()
+        }
+    })
+}
+
+fn slice_to_bytes<T: Copy>(tiles: &[T]) -> Vec<u8> {
+    // Safe because the tile types are plain bytes.
+    unsafe { slice::transmute::<T, u8>(tiles) }.to_vec()
+}
+
+fn tiles_to_bytes<T: Copy>(tiles: &Array2<T>) -> Vec<u8> {
+    let tiles: Vec<T> = tiles.iter().cloned().collect();
+    slice_to_bytes(&tiles)
+}
+
+/// Merges runs of equal tiles using `Tile::skip`, like the Teeworlds 0.7
+/// editor.
+fn compress_tiles(tiles: &Array2<Tile>) -> Vec<Tile> {
+    let mut result: Vec<Tile> = Vec::new();
+    for &tile in tiles {
+        let tile = Tile { skip: 0, ..tile };
+        match result.last_mut() {
+            Some(last) if last.skip < 255 && Tile { skip: 0, ..*last } == tile => last.skip += 1,
+            _ => result.push(tile),
+        }
+    }
+    result
+}
+
+fn rgb_to_rgba(data: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(data.len() / 3 * 4);
+    for pixel in data.chunks(3) {
+        result.extend_from_slice(pixel);
+        result.push(255);
+    }
+    result
+}
+
+impl Tiles {
+    pub fn width(&self) -> u32 {
+        self.dim().0
+    }
+    pub fn height(&self) -> u32 {
+        self.dim().1
+    }
+    fn dim(&self) -> (u32, u32) {
+        let (height, width) = match *self {
+            Tiles::Normal(ref t) | Tiles::Game(ref t) | Tiles::Front(ref t) => t.dim(),
+            Tiles::Teleport(ref t) => t.dim(),
+            Tiles::Speedup(ref t) => t.dim(),
+            Tiles::Switch(ref t) => t.dim(),
+            Tiles::Tune(ref t) => t.dim(),
+        };
+        (width.assert_u32(), height.assert_u32())
+    }
+    /// Changes the dimensions, keeping the tiles at the top left and filling
+    /// new space with zeroed tiles.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        match *self {
+            Tiles::Normal(ref mut t) | Tiles::Game(ref mut t) | Tiles::Front(ref mut t) => {
+                *t = resized(t, width, height)
+            }
+            Tiles::Teleport(ref mut t) => *t = resized(t, width, height),
+            Tiles::Speedup(ref mut t) => *t = resized(t, width, height),
+            Tiles::Switch(ref mut t) => *t = resized(t, width, height),
+            Tiles::Tune(ref mut t) => *t = resized(t, width, height),
+        }
+    }
+    /// The tiles of normal, game and front layers.
+    pub fn tiles_mut(&mut self) -> Option<&mut Array2<Tile>> {
+        match *self {
+            Tiles::Normal(ref mut t) | Tiles::Game(ref mut t) | Tiles::Front(ref mut t) => Some(t),
+            _ => None,
+        }
+    }
+    fn flags(&self) -> u32 {
+        match *self {
+            Tiles::Normal(_) => 0,
+            Tiles::Game(_) => format::TILELAYERFLAG_GAME,
+            Tiles::Teleport(_) => format::TILELAYERFLAG_TELEPORT,
+            Tiles::Speedup(_) => format::TILELAYERFLAG_SPEEDUP,
+            Tiles::Front(_) => format::TILELAYERFLAG_FRONT,
+            Tiles::Switch(_) => format::TILELAYERFLAG_SWITCH,
+            Tiles::Tune(_) => format::TILELAYERFLAG_TUNE,
+        }
+    }
+    fn to_bytes(&self) -> Vec<u8> {
+        match *self {
+            Tiles::Normal(ref t) | Tiles::Game(ref t) | Tiles::Front(ref t) => tiles_to_bytes(t),
+            Tiles::Teleport(ref t) => tiles_to_bytes(t),
+            Tiles::Speedup(ref t) => tiles_to_bytes(t),
+            Tiles::Switch(ref t) => tiles_to_bytes(t),
+            Tiles::Tune(ref t) => tiles_to_bytes(t),
+        }
+    }
+}
+
+impl TileLayer {
+    /// Creates a layer of zeroed tiles without image or envelope.
+    pub fn new(width: u32, height: u32) -> TileLayer {
+        let size = (height.usize(), width.usize());
+        TileLayer {
+            color: Color {
+                red: 255,
+                green: 255,
+                blue: 255,
+                alpha: 255,
+            },
+            color_env: None,
+            image: None,
+            tiles: Tiles::Normal(Array2::default(size)),
+        }
+    }
+    pub fn set_tile(&mut self, x: u32, y: u32, tile: Tile) {
+        let tiles = self
+            .tiles
+            .tiles_mut()
+            .expect("layer doesn't consist of plain tiles");
+        tiles[[y.usize(), x.usize()]] = tile;
+    }
+}
+
+fn name(bytes: &[u8]) -> Vec<u8> {
+    format::bytes_to_string(bytes).to_vec()
+}
+
+fn relative(index: usize, indices: &::std::ops::Range<usize>) -> usize {
+    index - indices.start
+}
+
+fn opt_string(reader: &mut reader::Reader, index: Option<usize>) -> Result<Option<Vec<u8>>, Error> {
+    index.map(|i| reader.string(i)).transpose()
+}
+
+impl Map {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Map, Error> {
+        Map::from_reader(&mut reader::Reader::open(path)?)
+    }
+    pub fn from_reader(reader: &mut reader::Reader) -> Result<Map, Error> {
+        reader.check_version()?;
+        let info = match reader.info() {
+            Ok(info) => {
+                let settings = match info.settings {
+                    Some(i) => reader.settings(i)?.iter().map(|s| s.to_vec()).collect(),
+                    None => Vec::new(),
+                };
+                Info {
+                    author: opt_string(reader, info.author)?,
+                    version: opt_string(reader, info.version)?,
+                    credits: opt_string(reader, info.credits)?,
+                    license: opt_string(reader, info.license)?,
+                    settings,
+                }
+            }
+            Err(MapError::MissingInfo) => Info::default(),
+            Err(e) => return Err(e.into()),
+        };
+
+        let image_indices = reader.reader.item_type_indices(format::MAP_ITEMTYPE_IMAGE);
+        let mut images = Vec::with_capacity(image_indices.len());
+        for i in image_indices.clone() {
+            let image = reader.image(i)?;
+            let mut data = image.data.map(|d| reader.image_data(d)).transpose()?;
+            if image.rgb {
+                data = data.map(|d| rgb_to_rgba(&d));
+            }
+            images.push(Image {
+                name: reader.image_name(image.name)?,
+                width: image.width,
+                height: image.height,
+                data,
+            });
+        }
+
+        let envelope_indices = reader
+            .reader
+            .item_type_indices(format::MAP_ITEMTYPE_ENVELOPE);
+        let envelopes = reader.envelopes()?;
+
+        let sound_indices = reader
+            .reader
+            .item_type_indices(format::MAP_ITEMTYPE_DDRACE_SOUND);
+        let mut sounds = Vec::with_capacity(sound_indices.len());
+        for i in sound_indices.clone() {
+            let invalid = MapError::InvalidSound(i);
+            let (external, raw_name, raw_data) = {
+                let raw = reader.reader.item(i).data;
+                if raw.len() < 5 {
+                    return Err(invalid.into());
+                }
+                (raw[1] != 0, raw[2], raw[3])
+            };
+            let get_index = |index: i32| {
+                index
+                    .try_usize()
+                    .filter(|&d| d < reader.reader.num_data())
+                    .ok_or(invalid)
+            };
+            let name_index = get_index(raw_name)?;
+            let data_index = if external {
+                None
+            } else {
+                Some(get_index(raw_data)?)
+            };
+            sounds.push(Sound {
+                name: reader.string(name_index)?,
+                data: data_index.map(|d| reader.reader.read_data(d)).transpose()?,
+            });
+        }
+
+        let mut groups = Vec::new();
+        for i in reader.group_indices() {
+            let group = reader.group(i)?;
+            let mut layers = Vec::with_capacity(group.layer_indices.len());
+            for k in group.layer_indices.clone() {
+                let layer = reader.layer(k)?;
+                let (layer_name, kind) = match layer.t {
+                    LayerType::Tilemap(tilemap) => {
+                        let mut color = Color {
+                            red: 255,
+                            green: 255,
+                            blue: 255,
+                            alpha: 255,
+                        };
+                        let mut color_env = None;
+                        let mut image = None;
+                        let tiles = match tilemap.type_ {
+                            LayerTilemapType::Normal(normal) => {
+                                color = normal.color;
+                                color_env = normal
+                                    .color_env_and_offset
+                                    .map(|(e, o)| (relative(e, &envelope_indices), o));
+                                image = normal.image.map(|i| relative(i, &image_indices));
+                                Tiles::Normal(reader.layer_tiles(tilemap.tiles(normal.data))?)
+                            }
+                            LayerTilemapType::Game(d) => {
+                                Tiles::Game(reader.layer_tiles(tilemap.tiles(d))?)
+                            }
+                            LayerTilemapType::RaceTeleport(d, _) => {
+                                Tiles::Teleport(reader.tele_layer_tiles(tilemap.tiles(d))?)
+                            }
+                            LayerTilemapType::RaceSpeedup(d, _) => {
+                                Tiles::Speedup(reader.speedup_layer_tiles(tilemap.tiles(d))?)
+                            }
+                            LayerTilemapType::DdraceFront(d, _) => {
+                                Tiles::Front(reader.layer_tiles(tilemap.tiles(d))?)
+                            }
+                            LayerTilemapType::DdraceSwitch(d, _) => {
+                                Tiles::Switch(reader.switch_layer_tiles(tilemap.tiles(d))?)
+                            }
+                            LayerTilemapType::DdraceTune(d, _) => {
+                                Tiles::Tune(reader.tune_layer_tiles(tilemap.tiles(d))?)
+                            }
+                        };
+                        let tile_layer = TileLayer {
+                            color,
+                            color_env,
+                            image,
+                            tiles,
+                        };
+                        (tilemap.name, LayerKind::Tiles(tile_layer))
+                    }
+                    LayerType::Quads(quads) => {
+                        let quads_layer = QuadsLayer {
+                            image: quads.image.map(|i| relative(i, &image_indices)),
+                            num_quads: quads.num_quads,
+                            data: reader.reader.read_data(quads.data)?,
+                        };
+                        (quads.name, LayerKind::Quads(quads_layer))
+                    }
+                    LayerType::DdraceSounds(sounds) => {
+                        let sounds_layer = SoundsLayer {
+                            sound: sounds.sound.map(|i| relative(i, &sound_indices)),
+                            num_sources: sounds.num_sources,
+                            data: reader.reader.read_data(sounds.data)?,
+                            legacy: sounds.legacy,
+                        };
+                        (sounds.name, LayerKind::Sounds(sounds_layer))
+                    }
+                };
+                layers.push(Layer {
+                    name: name(&layer_name),
+                    detail: layer.detail,
+                    kind,
+                });
+            }
+            groups.push(Group {
+                name: name(&group.name),
+                offset_x: group.offset_x,
+                offset_y: group.offset_y,
+                parallax_x: group.parallax_x,
+                parallax_y: group.parallax_y,
+                clipping: group.clipping,
+                layers,
+            });
+        }
+
+        Ok(Map {
+            info,
+            images,
+            envelopes,
+            groups,
+            sounds,
+        })
+    }
+    /// The tile layer containing the game tiles, if any.
+    pub fn game_layer_mut(&mut self) -> Option<&mut TileLayer> {
+        self.groups
+            .iter_mut()
+            .flat_map(|g| g.layers.iter_mut())
+            .filter_map(|l| match l.kind {
+                LayerKind::Tiles(ref mut t) => Some(t),
+                _ => None,
+            })
+            .find(|t| match t.tiles {
+                Tiles::Game(_) => true,
+                _ => false,
+            })
+    }
+    /// Removes an image, updating the references of layers to images.
+    pub fn remove_image(&mut self, index: usize) -> Image {
+        let image = self.images.remove(index);
+        let update = |image: &mut Option<usize>| {
+            *image = match *image {
+                Some(i) if i == index => None,
+                Some(i) if i > index => Some(i - 1),
+                i => i,
+            };
+        };
+        for layer in self.groups.iter_mut().flat_map(|g| g.layers.iter_mut()) {
+            match layer.kind {
+                LayerKind::Tiles(ref mut t) => update(&mut t.image),
+                LayerKind::Quads(ref mut q) => update(&mut q.image),
+                LayerKind::Sounds(_) => {}
+            }
+        }
+        image
+    }
+    /// Removes a sound, updating the references of layers to sounds.
+    pub fn remove_sound(&mut self, index: usize) -> Sound {
+        let sound = self.sounds.remove(index);
+        for layer in self.groups.iter_mut().flat_map(|g| g.layers.iter_mut()) {
+            if let LayerKind::Sounds(ref mut s) = layer.kind {
+                s.sound = match s.sound {
+                    Some(i) if i == index => None,
+                    Some(i) if i > index => Some(i - 1),
+                    i => i,
+                };
+            }
+        }
+        sound
+    }
+    /// Lists the parts of the map that are dropped or changed when writing
+    /// it for `target`.
+    pub fn incompatibilities(&self, target: Target) -> Vec<Incompatibility> {
+        let mut result = Vec::new();
+        if target == Target::Ddnet {
+            return result;
+        }
+        if !self.info.settings.is_empty() {
+            result.push(Incompatibility::Settings);
+        }
+        for (g, group) in self.groups.iter().enumerate() {
+            for (l, layer) in group.layers.iter().enumerate() {
+                if !target.supports(layer) {
+                    result.push(Incompatibility::Layer { group: g, layer: l });
+                }
+            }
+        }
+        result.extend((0..self.sounds.len()).map(Incompatibility::Sound));
+        result
+    }
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), df::WriteError> {
+        fn inner(map: &Map, path: &Path) -> Result<(), df::WriteError> {
+            map.write(File::create(path)?)?;
+            Ok(())
+        }
+        inner(self, path.as_ref())
+    }
+    /// Writes the map as a version 4 datafile and returns the underlying
+    /// writer.
+    ///
+    /// The indices referenced from layers must be valid.
+    pub fn write<W: io::Write>(&self, file: W) -> Result<W, df::WriteError> {
+        self.write_for(file, Target::Ddnet)
+    }
+    /// Like `write`, but for the given game.
+    ///
+    /// The parts listed by `incompatibilities` are dropped or changed.
+    pub fn write_for<W: io::Write>(&self, file: W, target: Target) -> Result<W, df::WriteError> {
+        let teeworlds07 = target == Target::Teeworlds07;
+        fn i(v: usize) -> Result<i32, df::WriteError> {
+            v.try_i32().ok_or(df::WriteError::TooLarge)
+        }
+        fn id(v: usize) -> Result<u16, df::WriteError> {
+            v.try_u16().ok_or(df::WriteError::TooLarge)
+        }
+        fn opt(v: Option<usize>) -> Result<i32, df::WriteError> {
+            v.map(i).unwrap_or(Ok(-1))
+        }
+        fn string<W: io::Write>(
+            writer: &mut df::Writer<W>,
+            s: &[u8],
+        ) -> Result<usize, df::WriteError> {
+            let mut data = s.to_vec();
+            data.push(0);
+            writer.add_data(&data)
+        }
+        fn name_i32s<'a>(result: &'a mut [i32], s: &[u8]) -> &'a [i32] {
+            format::bytes_to_i32s(result, s);
+            result
+        }
+
+        let mut writer = df::Writer::new(file);
+        writer.add_item(format::MAP_ITEMTYPE_VERSION, 0, &[1])?;
+
+        let info = &self.info;
+        let mut info_item = vec![1];
+        for s in &[&info.author, &info.version, &info.credits, &info.license] {
+            let index = match **s {
+                Some(ref s) => Some(string(&mut writer, s)?),
+                None => None,
+            };
+            info_item.push(opt(index)?);
+        }
+        let settings = if info.settings.is_empty() || teeworlds07 {
+            None
+        } else {
+            let mut data = Vec::new();
+            for s in &info.settings {
+                data.extend_from_slice(s);
+                data.push(0);
+            }
+            Some(writer.add_data(&data)?)
+        };
+        if !teeworlds07 {
+            info_item.push(opt(settings)?);
+        }
+        writer.add_item(format::MAP_ITEMTYPE_INFO, 0, &info_item)?;
+
+        for (index, image) in self.images.iter().enumerate() {
+            let name = string(&mut writer, &image.name)?;
+            let data = match image.data {
+                Some(ref d) => Some(writer.add_data(d)?),
+                None => None,
+            };
+            let mut item = vec![
+                1,
+                i(image.width.usize())?,
+                i(image.height.usize())?,
+                image.data.is_none() as i32,
+                i(name)?,
+                opt(data)?,
+            ];
+            if teeworlds07 {
+                item[0] = 2;
+                item.push(format::IMAGEFORMAT_RGBA);
+            }
+            writer.add_item(format::MAP_ITEMTYPE_IMAGE, id(index)?, &item)?;
+        }
+
+        let bezier = self
+            .envelopes
+            .iter()
+            .flat_map(|e| &e.points)
+            .any(|p| p.bezier != Default::default());
+        let mut points = Vec::new();
+        let mut num_points = 0;
+        for (index, envelope) in self.envelopes.iter().enumerate() {
+            let mut item = vec![
+                if bezier { 3 } else { 2 },
+                envelope.channels,
+                i(num_points)?,
+                i(envelope.points.len())?,
+            ];
+            item.extend_from_slice(name_i32s(&mut [0; 8], &envelope.name));
+            item.push(envelope.synchronized as i32);
+            writer.add_item(format::MAP_ITEMTYPE_ENVELOPE, id(index)?, &item)?;
+            for point in &envelope.points {
+                point.write_raw(&mut points, bezier);
+            }
+            num_points += envelope.points.len();
+        }
+        writer.add_item(format::MAP_ITEMTYPE_ENVPOINTS, 0, &points)?;
+
+        let mut num_layers = 0;
+        for (index, group) in self.groups.iter().enumerate() {
+            let layers: Vec<&Layer> = group.layers.iter().filter(|l| target.supports(l)).collect();
+            let clipping = group.clipping.unwrap_or(Clipping {
+                x: 0,
+                y: 0,
+                width: 0,
+                height: 0,
+            });
+            let mut item = vec![
+                3,
+                group.offset_x,
+                group.offset_y,
+                group.parallax_x,
+                group.parallax_y,
+                i(num_layers)?,
+                i(layers.len())?,
+                group.clipping.is_some() as i32,
+                clipping.x,
+                clipping.y,
+                clipping.width,
+                clipping.height,
+            ];
+            item.extend_from_slice(name_i32s(&mut [0; 3], &group.name));
+            writer.add_item(format::MAP_ITEMTYPE_GROUP, id(index)?, &item)?;
+
+            for layer in layers {
+                let flags = if layer.detail {
+                    format::LAYERFLAG_DETAIL as i32
+                } else {
+                    0
+                };
+                let mut name = [0; 3];
+                name_i32s(&mut name, &layer.name);
+                let item = match layer.kind {
+                    LayerKind::Tiles(ref t) => {
+                        let width = t.tiles.width();
+                        let height = t.tiles.height();
+                        let flags_tiles = t.tiles.flags();
+                        let (data, extra) = if teeworlds07 {
+                            let tiles = match t.tiles {
+                                Tiles::Normal(ref t) | Tiles::Game(ref t) => compress_tiles(t),
+                                _ => unreachable!(),
+                            };
+                            (writer.add_data(&slice_to_bytes(&tiles))?, None)
+                        } else if flags_tiles & !format::TILELAYERFLAG_GAME == 0 {
+                            (writer.add_data(&t.tiles.to_bytes())?, None)
+                        } else {
+                            // The reference implementation expects zeroed
+                            // normal tiles for the special layers.
+                            let size = (height.usize(), width.usize());
+                            let zeroes = tiles_to_bytes(&Array2::<Tile>::default(size));
+                            let extra = writer.add_data(&t.tiles.to_bytes())?;
+                            (writer.add_data(&zeroes)?, Some(extra))
+                        };
+                        let (color_env, color_env_offset) = match t.color_env {
+                            Some((e, o)) => (i(e)?, o),
+                            None => (-1, 0),
+                        };
+                        let mut item = vec![
+                            0,
+                            format::MAP_ITEMTYPE_LAYER_V1_TILEMAP,
+                            flags,
+                            if teeworlds07 {
+                                format::TILEMAP_VERSION_COMPRESSED
+                            } else {
+                                3
+                            },
+                            i(width.usize())?,
+                            i(height.usize())?,
+                            flags_tiles as i32,
+                            t.color.red.i32(),
+                            t.color.green.i32(),
+                            t.color.blue.i32(),
+                            t.color.alpha.i32(),
+                            color_env,
+                            color_env_offset,
+                            opt(t.image)?,
+                            i(data)?,
+                        ];
+                        item.extend_from_slice(&name);
+                        if !teeworlds07 {
+                            // Teleport, speedup, front, switch and tune data.
+                            let mut extras = [-1; 5];
+                            if let Some(extra) = extra {
+                                let slot = (flags_tiles / format::TILELAYERFLAG_TELEPORT)
+                                    .trailing_zeros()
+                                    .usize();
+                                extras[slot] = i(extra)?;
+                            }
+                            item.extend_from_slice(&extras);
+                        }
+                        item
+                    }
+                    LayerKind::Quads(ref q) => {
+                        let data = writer.add_data(&q.data)?;
+                        let mut item = vec![
+                            0,
+                            format::MAP_ITEMTYPE_LAYER_V1_QUADS,
+                            flags,
+                            2,
+                            i(q.num_quads)?,
+                            i(data)?,
+                            opt(q.image)?,
+                        ];
+                        item.extend_from_slice(&name);
+                        item
+                    }
+                    LayerKind::Sounds(ref s) => {
+                        let data = writer.add_data(&s.data)?;
+                        let mut item = vec![
+                            0,
+                            if s.legacy {
+                                format::MAP_ITEMTYPE_LAYER_V1_DDRACE_SOUNDS_LEGACY
+                            } else {
+                                format::MAP_ITEMTYPE_LAYER_V1_DDRACE_SOUNDS
+                            },
+                            flags,
+                            if s.legacy { 1 } else { 2 },
+                            i(s.num_sources)?,
+                            i(data)?,
+                            opt(s.sound)?,
+                        ];
+                        item.extend_from_slice(&name);
+                        item
+                    }
+                };
+                writer.add_item(format::MAP_ITEMTYPE_LAYER, id(num_layers)?, &item)?;
+                num_layers += 1;
+            }
+        }
+
+        let sounds: &[Sound] = if teeworlds07 { &[] } else { &self.sounds };
+        for (index, sound) in sounds.iter().enumerate() {
+            let name = string(&mut writer, &sound.name)?;
+            let data = match sound.data {
+                Some(ref d) => Some(writer.add_data(d)?),
+                None => None,
+            };
+            let size = sound.data.as_ref().map(|d| d.len()).unwrap_or(0);
+            let item = [
+                1,
+                sound.data.is_none() as i32,
+                i(name)?,
+                opt(data)?,
+                i(size)?,
+            ];
+            writer.add_item(format::MAP_ITEMTYPE_DDRACE_SOUND, id(index)?, &item)?;
+        }
+
+        writer.finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::Array2;
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    use super::EnvPoint;
+    use super::Envelope;
+    use super::Group;
+    use super::Incompatibility;
+    use super::Layer;
+    use super::LayerKind;
+    use super::Map;
+    use super::Target;
+    use super::TileLayer;
+    use super::Tiles;
+    use envelope::Bezier;
+    use envelope::CURVETYPE_BEZIER;
+    use format::Tile;
+    use reader::Reader;
+
+    #[test]
+    fn teeworlds07() {
+        let mut game = TileLayer::new(300, 2);
+        game.tiles = Tiles::Game(Array2::default((2, 300)));
+        for x in 0..300 {
+            game.set_tile(
+                x,
+                1,
+                Tile {
+                    index: 1,
+                    ..Tile::default()
+                },
+            );
+        }
+        game.set_tile(
+            7,
+            0,
+            Tile {
+                index: 2,
+                flags: 1,
+                ..Tile::default()
+            },
+        );
+        let mut tele = TileLayer::new(300, 2);
+        tele.tiles = Tiles::Teleport(Array2::default((2, 300)));
+        let layer = |kind| Layer {
+            name: Vec::new(),
+            detail: false,
+            kind: LayerKind::Tiles(kind),
+        };
+        let mut map = Map::default();
+        map.info.settings.push(b"sv_gametype dm".to_vec());
+        let curve = EnvPoint {
+            time: 0,
+            curve_type: CURVETYPE_BEZIER,
+            values: [0, 1024, 0, 0],
+            bezier: Bezier {
+                out_tangent_dx: [100, 200, 0, 0],
+                out_tangent_dy: [512, -512, 0, 0],
+                ..Bezier::default()
+            },
+        };
+        map.envelopes.push(Envelope {
+            name: b"Curve".to_vec(),
+            channels: 3,
+            synchronized: false,
+            points: vec![
+                curve,
+                EnvPoint {
+                    time: 1000,
+                    ..EnvPoint::default()
+                },
+            ],
+        });
+        map.groups.push(Group {
+            name: b"Game".to_vec(),
+            offset_x: 0,
+            offset_y: 0,
+            parallax_x: 100,
+            parallax_y: 100,
+            clipping: None,
+            layers: vec![layer(tele), layer(game.clone())],
+        });
+        assert_eq!(map.incompatibilities(Target::Ddnet), []);
+        assert_eq!(
+            map.incompatibilities(Target::Teeworlds07),
+            [
+                Incompatibility::Settings,
+                Incompatibility::Layer { group: 0, layer: 0 },
+            ]
+        );
+
+        let path = env::temp_dir().join(format!("libtw2-teeworlds07-{}.map", process::id()));
+        fs::write(
+            &path,
+            map.write_for(Vec::new(), Target::Teeworlds07).unwrap(),
+        )
+        .unwrap();
+        let mut reader = Reader::open(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let layers = reader.game_layers().unwrap();
+        assert!(layers.compressed);
+        assert!(layers.teleport().is_none());
+        let loaded = Map::from_reader(&mut reader).unwrap();
+        assert!(loaded.info.settings.is_empty());
+        assert_eq!(loaded.envelopes[0].points, map.envelopes[0].points);
+        assert_eq!(loaded.groups[0].layers.len(), 1);
+        match loaded.groups[0].layers[0].kind {
+            LayerKind::Tiles(ref t) => match (&t.tiles, &game.tiles) {
+                (&Tiles::Game(ref loaded), &Tiles::Game(ref game)) => assert_eq!(loaded, game),
+                _ => panic!("not a game layer"),
+            },
+            _ => panic!("not a tile layer"),
+        }
+    }
+}
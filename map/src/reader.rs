@@ -7,6 +7,9 @@ use std::mem;
 use std::ops;
 use std::path::Path;
 
+use ddnet::GameLayerTiles;
+use envelope::EnvPoint;
+use envelope::Envelope;
 use format;
 use format::Error as MapError;
 use format::MapItem;
@@ -36,7 +39,7 @@ impl From<io::Error> for Error {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub struct Color {
     pub red: u8,
     pub green: u8,
@@ -94,6 +97,23 @@ where
         .map(Some)
 }
 
+/// Repeats each tile `skip` more times, stopping after `max_len` tiles.
+///
+/// The caller checks the resulting length against the layer dimensions.
+fn decompress_tiles(tiles: &[format::Tile], max_len: usize) -> Vec<format::Tile> {
+    let mut result = Vec::with_capacity(max_len);
+    for &tile in tiles {
+        let repeated = format::Tile { skip: 0, ..tile };
+        for _ in 0..tile.skip.usize() + 1 {
+            if result.len() > max_len {
+                return result;
+            }
+            result.push(repeated);
+        }
+    }
+    result
+}
+
 trait MapItemExtInternal: MapItem {
     fn optional<E, TS>(slice: &[i32], too_short: TS) -> Result<Option<&Self>, E>
     where
@@ -159,6 +179,7 @@ pub struct LayerTilesIndex {
     data_index: usize,
     width: u32,
     height: u32,
+    compressed: bool,
 }
 
 impl Group {
@@ -323,6 +344,8 @@ pub struct LayerTilemap {
     pub height: u32,
     pub type_: LayerTilemapType,
     pub name: [u8; 12],
+    /// Whether the tiles are compressed, as in Teeworlds 0.7 maps.
+    pub compressed: bool,
 }
 
 impl LayerTilemap {
@@ -331,6 +354,7 @@ impl LayerTilemap {
             data_index: data_index,
             width: self.width,
             height: self.height,
+            compressed: self.compressed,
         }
     }
 }
@@ -467,6 +491,7 @@ impl LayerTilemap {
             height: height,
             type_: type_,
             name: name,
+            compressed: v0.version >= format::TILEMAP_VERSION_COMPRESSED,
         })
     }
 }
@@ -536,6 +561,9 @@ pub struct Image {
     pub height: u32,
     pub name: usize,
     pub data: Option<usize>,
+    /// Whether the data has 3 bytes per pixel instead of 4, only possible
+    /// in Teeworlds 0.7 maps.
+    pub rgb: bool,
 }
 
 impl Image {
@@ -543,6 +571,12 @@ impl Image {
         use format::ImageError::*;
 
         let v1 = format::MapItemImageV1::mandatory(raw, TooShort, InvalidVersion)?;
+        let v2 = format::MapItemImageV2::optional(raw, TooShort)?;
+        let rgb = match v2.map(|v2| v2.format) {
+            None | Some(format::IMAGEFORMAT_RGBA) => false,
+            Some(format::IMAGEFORMAT_RGB) => true,
+            Some(f) => return Err(InvalidFormat(f)),
+        };
         // WARN if external is something other than 0,1
         let data = if v1.external != 0 {
             None
@@ -554,6 +588,7 @@ impl Image {
             height: v1.height.try_u32().ok_or(InvalidHeight(v1.height))?,
             name: get_index(v1.name, data_indices.clone(), InvalidNameIndex)?,
             data: data,
+            rgb: rgb,
         })
     }
 }
@@ -568,6 +603,7 @@ pub struct GameLayers {
     pub front_raw: Option<usize>,
     pub switch_raw: Option<usize>,
     pub tune_raw: Option<usize>,
+    pub compressed: bool,
 }
 
 impl GameLayers {
@@ -576,6 +612,7 @@ impl GameLayers {
             data_index: data_index,
             width: self.width,
             height: self.height,
+            compressed: self.compressed,
         }
     }
     pub fn game(&self) -> LayerTilesIndex {
@@ -729,6 +766,44 @@ impl Reader {
         )
         .add_index(index)
     }
+    /// Parses all envelopes with their points.
+    pub fn envelopes(&self) -> Result<Vec<Envelope>, MapError> {
+        let points = self
+            .reader
+            .find_item(format::MAP_ITEMTYPE_ENVPOINTS, 0)
+            .map(|i| i.data)
+            .unwrap_or(&[]);
+        let mut result = Vec::new();
+        for i in self.reader.item_type_indices(format::MAP_ITEMTYPE_ENVELOPE) {
+            let raw = self.reader.item(i).data;
+            let invalid = MapError::InvalidEnvelope(i);
+            if raw.len() < 4 {
+                return Err(invalid);
+            }
+            let point_len = if raw[0] >= 3 { 22 } else { 6 };
+            let start = raw[2].try_usize().ok_or(invalid)?;
+            let num = raw[3].try_usize().ok_or(invalid)?;
+            let point_data = points
+                .get(start * point_len..(start + num) * point_len)
+                .ok_or(invalid)?;
+            // Legacy envelopes only store a single `i32` for the name.
+            let mut name = [0; 32];
+            if raw.len() >= 12 {
+                format::i32s_to_bytes(&mut name, &raw[4..12]);
+                name[31] = 0;
+            }
+            result.push(Envelope {
+                name: format::bytes_to_string(&name).to_vec(),
+                channels: raw[1],
+                synchronized: raw[0] >= 2 && raw.get(12).map(|&s| s != 0).unwrap_or(false),
+                points: point_data
+                    .chunks(point_len)
+                    .map(EnvPoint::from_raw)
+                    .collect(),
+            });
+        }
+        Ok(result)
+    }
     pub fn image(&self, index: usize) -> Result<Image, MapError> {
         let raw = self.reader.item(index);
         let data_indices = 0..self.reader.num_data();
@@ -745,6 +820,7 @@ impl Reader {
             }
         }
         let mut group_index_width_height = None;
+        let mut compressed = false;
         let mut game_group = None;
         let mut game = None;
         let mut teleport = None;
@@ -761,7 +837,10 @@ impl Reader {
                 if let LayerType::Tilemap(tilemap) = layer.t {
                     match tilemap.type_ {
                         LayerTilemapType::Normal(_) => continue,
-                        LayerTilemapType::Game(d) => put(&mut game, d)?,
+                        LayerTilemapType::Game(d) => {
+                            put(&mut game, d)?;
+                            compressed = tilemap.compressed;
+                        }
                         LayerTilemapType::RaceTeleport(d, _) => put(&mut teleport, d)?,
                         LayerTilemapType::RaceSpeedup(d, _) => put(&mut speedup, d)?,
                         LayerTilemapType::DdraceFront(d, _) => put(&mut front, d)?,
@@ -800,6 +879,31 @@ impl Reader {
             front_raw: front,
             switch_raw: switch,
             tune_raw: tune,
+            compressed: compressed,
+        })
+    }
+    /// Loads the tiles of the game layer and the DDNet-specific layers.
+    pub fn game_layer_tiles(&mut self) -> Result<GameLayerTiles, Error> {
+        let layers = self.game_layers()?;
+        Ok(GameLayerTiles {
+            game: self.layer_tiles(layers.game())?,
+            teleport: layers
+                .teleport()
+                .map(|t| self.tele_layer_tiles(t))
+                .transpose()?,
+            speedup: layers
+                .speedup()
+                .map(|t| self.speedup_layer_tiles(t))
+                .transpose()?,
+            front: layers.front().map(|t| self.layer_tiles(t)).transpose()?,
+            switch: layers
+                .switch()
+                .map(|t| self.switch_layer_tiles(t))
+                .transpose()?,
+            tune: layers
+                .tune()
+                .map(|t| self.tune_layer_tiles(t))
+                .transpose()?,
         })
     }
     pub fn image_name(&mut self, data_index: usize) -> Result<Vec<u8>, Error> {
@@ -836,6 +940,7 @@ impl Reader {
             data_index,
             width,
             height,
+            compressed: _,
         } = index;
         let tiles = self.tune_layer_tiles_raw(data_index)?;
         let len = tiles.len();
@@ -850,7 +955,7 @@ impl Reader {
     ) -> Result<Vec<format::SpeedupTile>, Error> {
         let raw = self.reader.read_data(data_index)?;
         if raw.len() % mem::size_of::<format::SpeedupTile>() != 0 {
-            return Err(Error::Map(MapError::InvalidTeleTilesLength(raw.len())));
+            return Err(Error::Map(MapError::InvalidSpeedupTilesLength(raw.len())));
         }
         let tiles: Vec<format::SpeedupTile> = unsafe { vec::transmute(raw) };
         Ok(tiles)
@@ -863,6 +968,7 @@ impl Reader {
             data_index,
             width,
             height,
+            compressed: _,
         } = index;
         let tiles = self.speedup_layer_tiles_raw(data_index)?;
         let len = tiles.len();
@@ -877,7 +983,7 @@ impl Reader {
     ) -> Result<Vec<format::SwitchTile>, Error> {
         let raw = self.reader.read_data(data_index)?;
         if raw.len() % mem::size_of::<format::SwitchTile>() != 0 {
-            return Err(Error::Map(MapError::InvalidTeleTilesLength(raw.len())));
+            return Err(Error::Map(MapError::InvalidSwitchTilesLength(raw.len())));
         }
         let tiles: Vec<format::SwitchTile> = unsafe { vec::transmute(raw) };
         Ok(tiles)
@@ -890,6 +996,7 @@ impl Reader {
             data_index,
             width,
             height,
+            compressed: _,
         } = index;
         let tiles = self.switch_layer_tiles_raw(data_index)?;
         let len = tiles.len();
@@ -917,6 +1024,7 @@ impl Reader {
             data_index,
             width,
             height,
+            compressed: _,
         } = index;
         let tiles = self.tele_layer_tiles_raw(data_index)?;
         let len = tiles.len();
@@ -938,8 +1046,12 @@ impl Reader {
             data_index,
             width,
             height,
+            compressed,
         } = index;
-        let tiles = self.layer_tiles_raw(data_index)?;
+        let mut tiles = self.layer_tiles_raw(data_index)?;
+        if compressed {
+            tiles = decompress_tiles(&tiles, width.usize() * height.usize());
+        }
         let len = tiles.len();
         Ok(
             Array2::from_shape_vec((height.usize(), width.usize()), tiles)
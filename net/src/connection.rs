@@ -3,7 +3,6 @@ use buffer::with_buffer;
 use buffer::Buffer;
 use buffer::BufferRef;
 use protocol;
-use protocol::ChunksIter;
 use protocol::ConnectedPacket;
 use protocol::ConnectedPacketType;
 use protocol::ControlPacket;
@@ -11,18 +10,18 @@ use protocol::Packet;
 use protocol::Token;
 use protocol::MAX_PACKETSIZE;
 use protocol::MAX_PAYLOAD;
+use protocol::MIN_PACKETSIZE;
 use protocol::TOKEN_NONE;
+use protocol7;
 use std::cmp;
 use std::collections::VecDeque;
 use std::iter;
 use std::time::Duration;
+use warn::Ignore;
 use warn::Warn;
 use Timeout;
 use Timestamp;
 
-// TODO: Implement receive timeout.
-// TODO: Don't allow for unbounded backlog of vital messages.
-
 pub trait Callback {
     type Error;
     fn secure_random(&mut self, buffer: &mut [u8]);
@@ -32,7 +31,12 @@ pub trait Callback {
 
 #[derive(Debug)]
 pub enum Error<CE> {
+    /// The chunk doesn't fit into a packet of `Connection::max_packet_size`
+    /// bytes, see `Connection::max_chunk_size`.
     TooLongData,
+    /// More than `Config::max_buffered_chunks` vital chunks are waiting to
+    /// be acked by the peer.
+    BufferFull,
     Callback(CE),
 }
 
@@ -46,6 +50,7 @@ impl<CE> Error<CE> {
     pub fn unwrap_callback(self) -> CE {
         match self {
             Error::TooLongData => panic!("too long data"),
+            Error::BufferFull => panic!("buffer full"),
             Error::Callback(e) => e,
         }
     }
@@ -54,11 +59,154 @@ impl<CE> Error<CE> {
 #[derive(Debug)]
 pub enum Warning {
     Packet(protocol::Warning),
+    Packet7(protocol7::Warning),
     Read(protocol::PacketReadError),
+    Read7(protocol7::PacketReadError),
     TokenMismatch,
     Unexpected,
 }
 
+/// How a chunk is sent.
+///
+/// Chunks are queued and coalesced into packets of up to
+/// `Connection::max_packet_size` bytes. A packet is sent once the next chunk doesn't fit anymore, on
+/// `flush` or after `Config::keepalive_interval`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SendFlags {
+    /// Resend the chunk until the peer acknowledges it.
+    pub vital: bool,
+    /// Send the chunk immediately, together with the ones queued before it.
+    pub flush: bool,
+}
+
+/// Tunables of a connection.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Config {
+    /// Time after which a connection that hasn't completed the handshake
+    /// is given up.
+    pub connect_timeout: Duration,
+    /// Time without any packet from the peer after which an established
+    /// connection is given up.
+    pub timeout: Duration,
+    /// Interval in which queued chunks are flushed and control packets
+    /// (connect attempts, keepalives) are sent.
+    pub keepalive_interval: Duration,
+    /// Time after which unacked vital chunks are sent again.
+    pub resend_delay: Duration,
+    /// Maximum number of vital chunks waiting to be acked.
+    ///
+    /// Must not be larger than half the sequence number space.
+    pub max_buffered_chunks: usize,
+    /// Size of the largest packet sent, between `MIN_PACKETSIZE` and
+    /// `MAX_PACKETSIZE`.
+    ///
+    /// The protocol can't split chunks across packets, so sending chunks that
+    /// don't fit into such a packet fails with `Error::TooLongData`. Larger
+    /// messages have to be split before sending them, like map downloads and
+    /// snapshots are.
+    pub max_packet_size: usize,
+    /// Start with packets of `MIN_PACKETSIZE` and probe for larger ones up
+    /// to `max_packet_size` using padded keepalives.
+    ///
+    /// Only 0.6 connections support this, and only peers answering the
+    /// probes get larger packets. Until then, only chunks of up to
+    /// `Connection::max_chunk_size` bytes can be sent.
+    pub mtu_discovery: bool,
+    /// Queue `Event`s for `Connection::take_events`.
+    ///
+    /// Off by default so that the queue doesn't grow for users not taking
+    /// them.
+    pub events: bool,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            connect_timeout: Duration::from_secs(10),
+            timeout: Duration::from_secs(10),
+            keepalive_interval: Duration::from_millis(500),
+            resend_delay: Duration::from_millis(1_000),
+            max_buffered_chunks: protocol::SEQUENCE_MODULUS as usize / 2,
+            max_packet_size: MAX_PACKETSIZE,
+            mtu_discovery: false,
+            events: false,
+        }
+    }
+}
+
+/// Keepalive and timeout notifications, e.g. for showing connection
+/// problems, see `Config::events`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Event {
+    /// A keepalive was sent because nothing else was sent for
+    /// `Config::keepalive_interval`.
+    KeepaliveSent,
+    /// Nothing was received from the peer for half the timeout. Sent once
+    /// until the peer is heard from again.
+    PeerTimeoutWarning(Duration),
+    /// The connection was given up, nothing was received from the peer for
+    /// the given time.
+    TimedOut(Duration),
+}
+
+/// The packet format spoken on a connection.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum ProtocolVersion {
+    /// The 0.5/0.6 format, including DDNet's token extension.
+    V6,
+    /// The 0.7 format with its token handshake.
+    V7,
+}
+
+/// A transition of the reliability layer, identified by the sequence number
+/// of the vital chunk concerned.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SequenceEvent {
+    /// A vital chunk was queued for sending.
+    Queued(u16),
+    /// A vital chunk was sent for the first time.
+    Sent(u16),
+    /// A vital chunk was sent again, either because it wasn't acked in time
+    /// or because the peer requested it.
+    Resent(u16),
+    /// A vital chunk was acked by the peer and won't be resent anymore.
+    Acked(u16),
+    /// A vital chunk was received from the peer, it'll be acked with the
+    /// next packet.
+    Received(u16),
+    /// A vital chunk from the peer was dropped because it arrived out of
+    /// order or twice.
+    Dropped(u16),
+}
+
+/// Observes the vital chunks of a connection, e.g. for visualizing the
+/// reliability window, see `Connection::set_sequence_debug`.
+pub trait SequenceDebug {
+    fn event(&mut self, time: Timestamp, event: SequenceEvent);
+}
+
+impl<F: FnMut(Timestamp, SequenceEvent)> SequenceDebug for F {
+    fn event(&mut self, time: Timestamp, event: SequenceEvent) {
+        self(time, event)
+    }
+}
+
+#[derive(Default)]
+struct DebugHook(Option<Box<dyn SequenceDebug>>);
+
+impl DebugHook {
+    fn event<CB: Callback>(&mut self, cb: &mut CB, event: SequenceEvent) {
+        if let Some(ref mut debug) = self.0 {
+            debug.event(cb.time(), event);
+        }
+    }
+    fn event_at(&mut self, time: Timestamp, event: SequenceEvent) {
+        if let Some(ref mut debug) = self.0 {
+            debug.event(time, event);
+        }
+    }
+}
+
 trait TimeoutExt {
     fn set<CB: Callback>(&mut self, cb: &mut CB, value: Duration);
     fn has_triggered_level<CB: Callback>(&self, cb: &mut CB) -> bool;
@@ -82,9 +230,95 @@ impl TimeoutExt for Timeout {
 }
 
 pub struct Connection {
+    version: ProtocolVersion,
+    config: Config,
     state: State,
     send: Timeout,
+    last_receive: Timestamp,
+    timed_out: bool,
     builder: PacketBuilder,
+    // 0.7 only: `own_token` is included in every message from the peer,
+    // `peer_token` in every message to the peer. Both protect against IP
+    // spoofing.
+    own_token: Option<protocol7::Token>,
+    peer_token: Option<protocol7::Token>,
+    stats: Stats,
+    packet_size: usize,
+    mtu: Option<MtuDiscovery>,
+    debug: DebugHook,
+    timeout_warned: bool,
+    events: VecDeque<Event>,
+}
+
+const MTU_PROBE_ATTEMPTS: u32 = 3;
+const MTU_PROBE_GRANULARITY: usize = 16;
+
+/// Binary search for the largest packet size that reaches the peer.
+#[derive(Clone, Debug)]
+struct MtuDiscovery {
+    // Packets of size `low` are known to arrive, the ones larger than `high`
+    // are known to get lost.
+    low: usize,
+    high: usize,
+    probe: usize,
+    attempts: u32,
+    next_probe: Timeout,
+}
+
+impl MtuDiscovery {
+    fn new(high: usize) -> MtuDiscovery {
+        MtuDiscovery {
+            low: MIN_PACKETSIZE,
+            high,
+            probe: MIN_PACKETSIZE,
+            attempts: 0,
+            // Start probing as soon as the connection is online.
+            next_probe: Timeout::active(Timestamp::from_secs_since_epoch(0)),
+        }
+    }
+    fn is_done(&self) -> bool {
+        self.high < self.low + MTU_PROBE_GRANULARITY
+    }
+}
+
+/// Counters and quality metrics of a connection, see `Connection::stats`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Stats {
+    pub packets_sent: u64,
+    pub bytes_sent: u64,
+    pub packets_received: u64,
+    pub bytes_received: u64,
+    /// Vital chunks sent again, either because they weren't acked in time
+    /// or because the peer requested it.
+    pub resends: u64,
+    /// Received vital chunks that were dropped because they arrived out of
+    /// order or twice.
+    pub chunks_dropped: u64,
+    /// Smoothed round-trip time, measured using the acks of vital chunks
+    /// that weren't resent.
+    pub rtt: Option<Duration>,
+    /// Smoothed mean deviation of the round-trip time.
+    pub jitter: Duration,
+    /// Last ack received from the peer, the sequence number of the last
+    /// vital chunk it received.
+    pub last_ack: Option<u16>,
+}
+
+impl Stats {
+    fn add_rtt_sample(&mut self, sample: Duration) {
+        // Same smoothing as TCP, see RFC 6298.
+        match self.rtt {
+            None => {
+                self.rtt = Some(sample);
+                self.jitter = sample / 2;
+            }
+            Some(rtt) => {
+                let deviation = sample.abs_diff(rtt);
+                self.jitter = (self.jitter * 3 + deviation) / 4;
+                self.rtt = Some((rtt * 7 + sample) / 8);
+            }
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -117,14 +351,25 @@ impl State {
 #[derive(Clone, Debug)]
 struct ResendChunk {
     next_send: Timeout,
+    first_send: Timestamp,
+    sent: bool,
+    resent: bool,
     sequence: Sequence,
     data: ArrayVec<[u8; 2048]>,
 }
 
 impl ResendChunk {
-    fn new<CB: Callback>(cb: &mut CB, sequence: Sequence, data: &[u8]) -> ResendChunk {
+    fn new<CB: Callback>(
+        cb: &mut CB,
+        sequence: Sequence,
+        data: &[u8],
+        resend_delay: Duration,
+    ) -> ResendChunk {
         let mut result = ResendChunk {
             next_send: Timeout::inactive(),
+            first_send: cb.time(),
+            sent: false,
+            resent: false,
             sequence: sequence,
             data: data.iter().cloned().collect(),
         };
@@ -133,11 +378,11 @@ impl ResendChunk {
             "overlong resend packet {}",
             data.len()
         );
-        result.start_timeout(cb);
+        result.start_timeout(cb, resend_delay);
         result
     }
-    fn start_timeout<CB: Callback>(&mut self, cb: &mut CB) {
-        self.next_send.set(cb, Duration::from_millis(1_000));
+    fn start_timeout<CB: Callback>(&mut self, cb: &mut CB, resend_delay: Duration) {
+        self.next_send.set(cb, resend_delay);
     }
 }
 
@@ -172,20 +417,25 @@ impl<'a> ReceivePacket<'a> {
     fn connected<W>(
         warn: &mut W,
         online: &mut OnlineState,
-        num_chunks: u8,
-        data: &'a [u8],
+        stats: &mut Stats,
+        debug: &mut DebugHook,
+        time: Timestamp,
+        chunks_iter: ChunksIter<'a>,
     ) -> ReceivePacket<'a>
     where
         W: Warn<Warning>,
     {
-        let chunks_iter = ChunksIter::new(data, num_chunks);
         let ack = online.ack.clone();
         let mut iter = chunks_iter.clone();
-        while let Some(c) = iter.next_warn(&mut w(warn)) {
+        while let Some(c) = iter.next_warn(warn) {
             if let Some((sequence, resend)) = c.vital {
                 let _ = resend;
                 if online.ack.update(Sequence::from_u16(sequence)) != SequenceOrdering::Current {
                     online.request_resend = true;
+                    stats.chunks_dropped += 1;
+                    debug.event_at(time, SequenceEvent::Dropped(sequence));
+                } else {
+                    debug.event_at(time, SequenceEvent::Received(sequence));
                 }
             }
         }
@@ -231,6 +481,31 @@ impl<'a> Iterator for ReceivePacket<'a> {
 
 impl<'a> ExactSizeIterator for ReceivePacket<'a> {}
 
+#[derive(Clone)]
+enum ChunksIter<'a> {
+    V6(protocol::ChunksIter<'a>),
+    V7(protocol7::ChunksIter<'a>),
+}
+
+impl<'a> ChunksIter<'a> {
+    fn next_warn<W: Warn<Warning>>(&mut self, warn: &mut W) -> Option<protocol::Chunk<'a>> {
+        match *self {
+            ChunksIter::V6(ref mut iter) => iter.next_warn(&mut w(warn)),
+            ChunksIter::V7(ref mut iter) => iter.next_warn(&mut w(warn)).map(|c| protocol::Chunk {
+                data: c.data,
+                vital: c.vital,
+            }),
+        }
+    }
+}
+
+impl<'a> Iterator for ChunksIter<'a> {
+    type Item = protocol::Chunk<'a>;
+    fn next(&mut self) -> Option<protocol::Chunk<'a>> {
+        self.next_warn(&mut Ignore)
+    }
+}
+
 #[derive(Clone)]
 struct ReceiveChunks<'a> {
     ack: Sequence,
@@ -314,23 +589,65 @@ impl OnlineState {
     fn can_send(&self) -> bool {
         self.packet.num_chunks != 0 || self.request_resend
     }
-    fn ack_chunks(&mut self, ack: Sequence) {
+    /// Removes the chunks acked by the peer from the resend queue.
+    ///
+    /// Returns when the acked chunk was sent if it is usable for measuring
+    /// the round-trip time, i.e. if it wasn't resent.
+    fn ack_chunks<CB: Callback>(
+        &mut self,
+        cb: &mut CB,
+        debug: &mut DebugHook,
+        ack: Sequence,
+    ) -> Option<Timestamp> {
         let index = self
             .resend_queue
             .iter()
-            .position(|chunk| chunk.sequence == ack);
-        index.map(|i| self.resend_queue.truncate(i));
+            .position(|chunk| chunk.sequence == ack)?;
+        let chunk = &self.resend_queue[index];
+        let first_send = if !chunk.resent {
+            Some(chunk.first_send)
+        } else {
+            None
+        };
+        for chunk in self.resend_queue.drain(index..).rev() {
+            debug.event(cb, SequenceEvent::Acked(chunk.sequence.to_u16()));
+        }
+        first_send
     }
+    /// Sends the queued chunks, in the 0.7 format if `peer_token` is set.
     fn flush<CB: Callback>(
         &mut self,
         cb: &mut CB,
         builder: &mut PacketBuilder,
+        debug: &mut DebugHook,
+        peer_token: Option<protocol7::Token>,
     ) -> Result<(), CB::Error> {
         if !self.can_send() {
             return Ok(());
         }
-        let result = builder
-            .send(
+        // The chunks queued since the last packet are at the front of the
+        // resend queue.
+        for chunk in self.resend_queue.iter_mut().rev() {
+            if !chunk.sent {
+                chunk.sent = true;
+                debug.event(cb, SequenceEvent::Sent(chunk.sequence.to_u16()));
+            }
+        }
+        let result = if let Some(peer_token) = peer_token {
+            builder.send_7(
+                cb,
+                protocol7::ConnectedPacket {
+                    ack: self.ack.to_u16(),
+                    token: peer_token,
+                    type_: protocol7::ConnectedPacketType::Chunks(
+                        self.request_resend,
+                        self.packet.num_chunks,
+                        &self.packet.data,
+                    ),
+                },
+            )
+        } else {
+            builder.send(
                 cb,
                 Packet::Connected(ConnectedPacket {
                     token: self.token,
@@ -342,7 +659,8 @@ impl OnlineState {
                     ),
                 }),
             )
-            .map_err(|e| e.unwrap_callback());
+        }
+        .map_err(|e| e.unwrap_callback());
         self.request_resend = false;
         self.packet.clear();
         self.packet_nonvital.clear();
@@ -363,13 +681,20 @@ impl PacketContents {
             data: ArrayVec::new(),
         }
     }
-    fn write_chunk(&mut self, data: &[u8], vital: Option<(u16, bool)>) {
-        protocol::write_chunk(data, vital, &mut self.data).unwrap();
+    fn write_chunk(&mut self, version: ProtocolVersion, data: &[u8], vital: Option<(u16, bool)>) {
+        match version {
+            ProtocolVersion::V6 => protocol::write_chunk(data, vital, &mut self.data).unwrap(),
+            ProtocolVersion::V7 => protocol7::write_chunk(data, vital, &mut self.data).unwrap(),
+        };
         self.num_chunks += 1;
     }
-    fn can_fit_chunk(&self, data: &[u8], vital: bool) -> bool {
+    /// Empty packets fit any chunk, so that vital chunks queued before
+    /// lowering the packet size with `Connection::set_max_packet_size` can
+    /// still be resent.
+    fn can_fit_chunk(&self, data: &[u8], vital: bool, max_payload: usize) -> bool {
         // current size + chunk header + chunk length
-        self.data.len() + protocol::chunk_header_size(vital) + data.len() <= MAX_PAYLOAD
+        self.num_chunks == 0
+            || self.data.len() + protocol::chunk_header_size(vital) + data.len() <= max_payload
     }
     fn clear(&mut self) {
         *self = PacketContents::new();
@@ -431,12 +756,16 @@ impl Sequence {
 
 struct PacketBuilder {
     buffer: [u8; MAX_PACKETSIZE],
+    packets_sent: u64,
+    bytes_sent: u64,
 }
 
 impl PacketBuilder {
     fn new() -> PacketBuilder {
         PacketBuilder {
             buffer: [0; MAX_PACKETSIZE],
+            packets_sent: 0,
+            bytes_sent: 0,
         }
     }
     fn send<CB: Callback>(&mut self, cb: &mut CB, packet: Packet) -> Result<(), Error<CB::Error>> {
@@ -446,6 +775,45 @@ impl PacketBuilder {
             Err(protocol::Error::TooLongData) => return Err(Error::TooLongData),
         };
         cb.send(data)?;
+        self.packets_sent += 1;
+        self.bytes_sent += data.len() as u64;
+        Ok(())
+    }
+    fn send_7<CB: Callback>(
+        &mut self,
+        cb: &mut CB,
+        packet: protocol7::ConnectedPacket,
+    ) -> Result<(), Error<CB::Error>> {
+        let data = match packet.write(&mut self.buffer[..]) {
+            Ok(d) => d,
+            Err(protocol::Error::Capacity(_)) => unreachable!("too short buffer provided"),
+            Err(protocol::Error::TooLongData) => return Err(Error::TooLongData),
+        };
+        cb.send(data)?;
+        self.packets_sent += 1;
+        self.bytes_sent += data.len() as u64;
+        Ok(())
+    }
+    fn send_connless_7<CB: Callback>(
+        &mut self,
+        cb: &mut CB,
+        token: protocol7::Token,
+        response_token: protocol7::Token,
+        data: &[u8],
+    ) -> Result<(), Error<CB::Error>> {
+        let data = match protocol7::write_connless_packet(
+            token,
+            response_token,
+            data,
+            &mut self.buffer[..],
+        ) {
+            Ok(d) => d,
+            Err(protocol::Error::Capacity(_)) => unreachable!("too short buffer provided"),
+            Err(protocol::Error::TooLongData) => return Err(Error::TooLongData),
+        };
+        cb.send(data)?;
+        self.packets_sent += 1;
+        self.bytes_sent += data.len() as u64;
         Ok(())
     }
 }
@@ -464,30 +832,146 @@ impl<'a, W: Warn<Warning>> Warn<protocol::Warning> for WarnCallback<'a, W> {
     }
 }
 
+impl<'a, W: Warn<Warning>> Warn<protocol7::Warning> for WarnCallback<'a, W> {
+    fn warn(&mut self, warning: protocol7::Warning) {
+        self.warn.warn(Warning::Packet7(warning))
+    }
+}
+
 impl Connection {
     pub fn new() -> Connection {
+        Connection::with_version(ProtocolVersion::V6)
+    }
+    pub fn with_version(version: ProtocolVersion) -> Connection {
+        Connection::with_config(version, Config::default())
+    }
+    pub fn with_config(version: ProtocolVersion, config: Config) -> Connection {
+        assert!((MIN_PACKETSIZE..=MAX_PACKETSIZE).contains(&config.max_packet_size));
+        let mtu = Some(MtuDiscovery::new(config.max_packet_size))
+            .filter(|m| config.mtu_discovery && version == ProtocolVersion::V6 && !m.is_done());
         Connection {
+            version,
+            config,
             state: State::Unconnected,
             send: Timeout::inactive(),
+            last_receive: Timestamp::sentinel(),
+            timed_out: false,
             builder: PacketBuilder::new(),
+            own_token: None,
+            peer_token: None,
+            stats: Stats::default(),
+            packet_size: mtu
+                .as_ref()
+                .map(|m| m.low)
+                .unwrap_or(config.max_packet_size),
+            mtu,
+            debug: DebugHook::default(),
+            timeout_warned: false,
+            events: VecDeque::new(),
         }
     }
-    pub fn new_accept_token<CB: Callback>(cb: &mut CB, token: Token) -> Connection {
-        let mut result = Connection {
-            state: State::Online(OnlineState::new(Some(token))),
-            send: Timeout::inactive(),
-            builder: PacketBuilder::new(),
-        };
-        result.send.set(cb, Duration::from_millis(500));
+    pub fn new_accept_token<CB: Callback>(cb: &mut CB, token: Token, config: Config) -> Connection {
+        let mut result = Connection::with_config(ProtocolVersion::V6, config);
+        result.state = State::Online(OnlineState::new(Some(token)));
+        result.send.set(cb, config.keepalive_interval);
+        result.last_receive = cb.time();
+        result
+    }
+    /// Creates an unconnected 0.7 connection for a peer whose connect
+    /// packet has already been checked against `own_token`.
+    ///
+    /// Call `accept_7` or `disconnect` on it afterwards.
+    pub fn new_accept_7(
+        own_token: protocol7::Token,
+        peer_token: protocol7::Token,
+        config: Config,
+    ) -> Connection {
+        let mut result = Connection::with_config(ProtocolVersion::V7, config);
+        result.own_token = Some(own_token);
+        result.peer_token = Some(peer_token);
         result
     }
     pub fn reset(&mut self) {
         assert_matches!(self.state, State::Disconnected);
-        *self = Connection::new();
+        let debug = std::mem::take(&mut self.debug);
+        *self = Connection::with_config(self.version, self.config);
+        self.debug = debug;
+    }
+    pub fn version(&self) -> ProtocolVersion {
+        self.version
+    }
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+    /// Returns whether the connection was given up because the peer didn't
+    /// send anything for too long.
+    ///
+    /// The connection is disconnected in that case.
+    pub fn has_timed_out(&self) -> bool {
+        self.timed_out
+    }
+    /// Returns the events that happened since the last call, see
+    /// `Config::events`.
+    pub fn take_events(&mut self) -> impl Iterator<Item = Event> + '_ {
+        self.events.drain(..)
+    }
+    fn event(&mut self, event: Event) {
+        if self.config.events {
+            self.events.push_back(event);
+        }
+    }
+    pub fn stats(&self) -> Stats {
+        Stats {
+            packets_sent: self.builder.packets_sent,
+            bytes_sent: self.builder.bytes_sent,
+            ..self.stats
+        }
+    }
+    /// Size of the largest packet sent, see `Config::max_packet_size`.
+    pub fn max_packet_size(&self) -> usize {
+        self.packet_size
+    }
+    /// Overrides `Config::max_packet_size`, stopping the MTU discovery.
+    pub fn set_max_packet_size(&mut self, size: usize) {
+        assert!((MIN_PACKETSIZE..=MAX_PACKETSIZE).contains(&size));
+        self.packet_size = size;
+        self.mtu = None;
+    }
+    /// Reports the state transitions of vital chunks to `debug`.
+    pub fn set_sequence_debug(&mut self, debug: Option<Box<dyn SequenceDebug>>) {
+        self.debug = DebugHook(debug);
+    }
+    fn max_payload(&self) -> usize {
+        MAX_PAYLOAD - (MAX_PACKETSIZE - self.packet_size)
+    }
+    /// Size of the largest chunk that can currently be sent, see
+    /// `Config::max_packet_size`.
+    pub fn max_chunk_size(&self) -> usize {
+        self.max_payload() - protocol::chunk_header_size(true)
     }
     pub fn is_unconnected(&self) -> bool {
         matches!(self.state, State::Unconnected)
     }
+    pub fn is_disconnected(&self) -> bool {
+        matches!(self.state, State::Disconnected)
+    }
+    /// Returns whether no chunks are waiting to be sent or acked.
+    pub fn is_drained(&self) -> bool {
+        match self.state {
+            State::Online(ref online) => {
+                online.packet.num_chunks == 0 && online.resend_queue.is_empty()
+            }
+            _ => true,
+        }
+    }
+    /// Returns the DDNet token of an online 0.6 connection, `None` for
+    /// vanilla and 0.7 peers.
+    pub fn token(&self) -> Option<Token> {
+        match self.state {
+            State::Online(ref online) => online.token,
+            _ => None,
+        }
+    }
     pub fn needs_tick(&self) -> Timeout {
         match self.state {
             State::Unconnected | State::Disconnected => return Timeout::inactive(),
@@ -501,14 +985,76 @@ impl Connection {
                 .unwrap_or_default(),
             _ => Timeout::inactive(),
         };
-        cmp::min(self.send, resends)
+        let probe = match (&self.state, self.mtu.as_ref()) {
+            (State::Online(_), Some(mtu)) => mtu.next_probe,
+            _ => Timeout::inactive(),
+        };
+        cmp::min(
+            cmp::min(self.send, resends),
+            cmp::min(
+                probe,
+                cmp::min(self.receive_timeout(), self.warning_timeout()),
+            ),
+        )
+    }
+    fn timeout(&self) -> Option<Duration> {
+        match self.state {
+            State::Unconnected | State::Disconnected => None,
+            State::Connecting | State::Pending(_) => Some(self.config.connect_timeout),
+            State::Online(_) => Some(self.config.timeout),
+        }
+    }
+    fn receive_timeout(&self) -> Timeout {
+        self.timeout()
+            .map(|t| Timeout::active(self.last_receive + t))
+            .unwrap_or_default()
+    }
+    fn warning_timeout(&self) -> Timeout {
+        match self.timeout() {
+            Some(t) if self.config.events && !self.timeout_warned => {
+                Timeout::active(self.last_receive + t / 2)
+            }
+            _ => Timeout::inactive(),
+        }
+    }
+    fn since_receive<CB: Callback>(&self, cb: &mut CB) -> Duration {
+        Timeout::active(cb.time())
+            .time_from(self.last_receive)
+            .unwrap()
     }
     pub fn connect<CB: Callback>(&mut self, cb: &mut CB) -> Result<(), CB::Error> {
         assert_matches!(self.state, State::Unconnected);
         self.state = State::Connecting;
+        self.last_receive = cb.time();
         self.tick_action(cb)?;
         Ok(())
     }
+    /// Accepts a 0.7 connection created by `new_accept_7` or a connect
+    /// packet fed to it.
+    pub fn accept_7<CB: Callback>(&mut self, cb: &mut CB) -> Result<(), CB::Error> {
+        assert_matches!(self.state, State::Unconnected);
+        assert!(self.version == ProtocolVersion::V7 && self.peer_token.is_some());
+        self.state = State::Online(OnlineState::new(None));
+        self.send.set(cb, self.config.keepalive_interval);
+        self.last_receive = cb.time();
+        self.send_control(cb, ControlPacket::Accept)
+    }
+    fn receive_ack<CB: Callback>(&mut self, cb: &mut CB, ack: u16) {
+        if let State::Online(ref mut online) = self.state {
+            self.stats.last_ack = Some(ack);
+            let ack = Sequence::from_u16(ack);
+            if let Some(first_send) = online.ack_chunks(cb, &mut self.debug, ack) {
+                let now = Timeout::active(cb.time());
+                self.stats
+                    .add_rtt_sample(now.time_from(first_send).unwrap());
+            }
+        }
+    }
+    fn own_token<CB: Callback>(&mut self, cb: &mut CB) -> protocol7::Token {
+        *self
+            .own_token
+            .get_or_insert_with(|| protocol7::Token::random(|b| cb.secure_random(b)))
+    }
     pub fn disconnect<CB: Callback>(
         &mut self,
         cb: &mut CB,
@@ -529,6 +1075,7 @@ impl Connection {
         result
     }
     fn resend<CB: Callback>(&mut self, cb: &mut CB) -> Result<(), CB::Error> {
+        let max_payload = self.max_payload();
         let online = self.state.assert_online();
         if online.resend_queue.is_empty() {
             return Ok(());
@@ -536,45 +1083,61 @@ impl Connection {
         online.packet = online.packet_nonvital.clone();
         let mut i = 0;
         for chunk in &mut online.resend_queue {
-            chunk.start_timeout(cb);
+            chunk.start_timeout(cb, self.config.resend_delay);
+            chunk.sent = true;
+            chunk.resent = true;
         }
         while i < online.resend_queue.len() {
             let can_fit;
             {
                 let chunk = &online.resend_queue[online.resend_queue.len() - i - 1];
-                can_fit = online.packet.can_fit_chunk(&chunk.data, true);
+                can_fit = online.packet.can_fit_chunk(&chunk.data, true, max_payload);
                 if can_fit {
                     let vital = (chunk.sequence.to_u16(), true);
-                    online.packet.write_chunk(&chunk.data, Some(vital));
+                    online
+                        .packet
+                        .write_chunk(self.version, &chunk.data, Some(vital));
+                    self.stats.resends += 1;
+                    self.debug.event(cb, SequenceEvent::Resent(vital.0));
                     i += 1;
                 }
             }
             if !can_fit {
-                self.send.set(cb, Duration::from_millis(500));
-                online.flush(cb, &mut self.builder)?;
+                self.send.set(cb, self.config.keepalive_interval);
+                online.flush(cb, &mut self.builder, &mut self.debug, self.peer_token)?;
             }
         }
         Ok(())
     }
     pub fn flush<CB: Callback>(&mut self, cb: &mut CB) -> Result<(), CB::Error> {
-        self.send.set(cb, Duration::from_millis(500));
-        self.state.assert_online().flush(cb, &mut self.builder)
+        self.send.set(cb, self.config.keepalive_interval);
+        let peer_token = self.peer_token;
+        self.state
+            .assert_online()
+            .flush(cb, &mut self.builder, &mut self.debug, peer_token)
     }
     fn queue<CB: Callback>(&mut self, cb: &mut CB, buffer: &[u8], vital: bool) {
         let online = self.state.assert_online();
         let vital = if vital {
             let sequence = online.sequence.next();
-            online
-                .resend_queue
-                .push_front(ResendChunk::new(cb, sequence, buffer));
+            online.resend_queue.push_front(ResendChunk::new(
+                cb,
+                sequence,
+                buffer,
+                self.config.resend_delay,
+            ));
+            self.debug
+                .event(cb, SequenceEvent::Queued(sequence.to_u16()));
             Some((sequence.to_u16(), false))
         } else {
             None
         };
         if vital.is_none() {
-            online.packet_nonvital.write_chunk(buffer, vital);
+            online
+                .packet_nonvital
+                .write_chunk(self.version, buffer, vital);
         }
-        online.packet.write_chunk(buffer, vital)
+        online.packet.write_chunk(self.version, buffer, vital)
     }
     pub fn send<CB: Callback>(
         &mut self,
@@ -582,20 +1145,43 @@ impl Connection {
         buffer: &[u8],
         vital: bool,
     ) -> Result<(), Error<CB::Error>> {
+        let flags = SendFlags {
+            vital,
+            flush: false,
+        };
+        self.send_with_flags(cb, buffer, flags)
+    }
+    pub fn send_with_flags<CB: Callback>(
+        &mut self,
+        cb: &mut CB,
+        buffer: &[u8],
+        flags: SendFlags,
+    ) -> Result<(), Error<CB::Error>> {
+        let vital = flags.vital;
+        let max_payload = self.max_payload();
         let result;
         {
             let online = self.state.assert_online();
-            if buffer.len() > MAX_PAYLOAD {
+            if protocol::chunk_header_size(vital) + buffer.len() > max_payload {
                 return Err(Error::TooLongData);
             }
-            if !online.packet.can_fit_chunk(buffer, vital) {
-                result = online.flush(cb, &mut self.builder).map_err(Error::from);
+            if vital && online.resend_queue.len() >= self.config.max_buffered_chunks {
+                return Err(Error::BufferFull);
+            }
+            if !online.packet.can_fit_chunk(buffer, vital, max_payload) {
+                result = online
+                    .flush(cb, &mut self.builder, &mut self.debug, self.peer_token)
+                    .map_err(Error::from);
             } else {
                 result = Ok(());
             }
         }
         self.queue(cb, buffer, vital);
-        result
+        result?;
+        if flags.flush {
+            self.flush(cb)?;
+        }
+        Ok(())
     }
     pub fn send_connless<CB: Callback>(
         &mut self,
@@ -603,20 +1189,50 @@ impl Connection {
         data: &[u8],
     ) -> Result<(), Error<CB::Error>> {
         self.state.assert_online();
-        self.send.set(cb, Duration::from_millis(500));
-        self.builder.send(cb, Packet::Connless(data))
+        self.send.set(cb, self.config.keepalive_interval);
+        match self.version {
+            ProtocolVersion::V6 => self.builder.send(cb, Packet::Connless(data)),
+            ProtocolVersion::V7 => {
+                let token = self.peer_token.unwrap();
+                let response_token = self.own_token.unwrap();
+                self.builder
+                    .send_connless_7(cb, token, response_token, data)
+            }
+        }
     }
     fn send_control<CB: Callback>(
         &mut self,
         cb: &mut CB,
         control: ControlPacket,
     ) -> Result<(), CB::Error> {
+        if self.version == ProtocolVersion::V7 {
+            let control = match control {
+                ControlPacket::KeepAlive => protocol7::ControlPacket::KeepAlive,
+                // 0.7 clients first request a token from the server, and
+                // only connect once they know it.
+                ControlPacket::Connect => {
+                    let own_token = self.own_token(cb);
+                    if self.peer_token.is_some() {
+                        protocol7::ControlPacket::Connect(own_token)
+                    } else {
+                        protocol7::ControlPacket::Token(own_token)
+                    }
+                }
+                ControlPacket::ConnectAccept => unreachable!(),
+                ControlPacket::Accept => protocol7::ControlPacket::Accept,
+                ControlPacket::Close(reason) => protocol7::ControlPacket::Close(reason),
+                ControlPacket::MtuProbe(_) | ControlPacket::MtuReply(_) => unreachable!(),
+            };
+            let token = self.peer_token.unwrap_or(protocol7::TOKEN_NONE);
+            return self.send_control_7(cb, token, control);
+        }
         let ack = match self.state {
             State::Online(ref mut online) => online.ack.to_u16(),
             _ => 0,
         };
         let token = match self.state {
-            State::Unconnected => unreachable!(),
+            // Only used for rejecting vanilla peers.
+            State::Unconnected => None,
             // Signal support for the token protocol.
             State::Connecting => Some(TOKEN_NONE),
             State::Pending(ref pending) => pending.token,
@@ -634,7 +1250,47 @@ impl Connection {
             )
             .map_err(|e| e.unwrap_callback())
     }
+    fn send_control_7<CB: Callback>(
+        &mut self,
+        cb: &mut CB,
+        token: protocol7::Token,
+        control: protocol7::ControlPacket,
+    ) -> Result<(), CB::Error> {
+        let ack = match self.state {
+            State::Online(ref mut online) => online.ack.to_u16(),
+            _ => 0,
+        };
+        self.builder
+            .send_7(
+                cb,
+                protocol7::ConnectedPacket {
+                    ack,
+                    token,
+                    type_: protocol7::ConnectedPacketType::Control(control),
+                },
+            )
+            .map_err(|e| e.unwrap_callback())
+    }
     pub fn tick<CB: Callback>(&mut self, cb: &mut CB) -> Result<(), CB::Error> {
+        if self.receive_timeout().has_triggered_level(cb) {
+            let since_receive = self.since_receive(cb);
+            self.event(Event::TimedOut(since_receive));
+            self.state = State::Disconnected;
+            self.timed_out = true;
+            return Ok(());
+        }
+        if self.warning_timeout().has_triggered_level(cb) {
+            let since_receive = self.since_receive(cb);
+            self.event(Event::PeerTimeoutWarning(since_receive));
+            self.timeout_warned = true;
+        }
+        let probe = match (&self.state, self.mtu.as_ref()) {
+            (State::Online(_), Some(mtu)) => mtu.next_probe.has_triggered_level(cb),
+            _ => false,
+        };
+        if probe {
+            self.send_mtu_probe(cb)?;
+        }
         let do_resend = match self.state {
             State::Online(ref online) => {
                 // WARN?
@@ -654,6 +1310,49 @@ impl Connection {
             Ok(())
         }
     }
+    fn send_mtu_probe<CB: Callback>(&mut self, cb: &mut CB) -> Result<(), CB::Error> {
+        let probe = {
+            let mtu = self.mtu.as_mut().unwrap();
+            if mtu.attempts == MTU_PROBE_ATTEMPTS {
+                // Assume that the probe is too large if it got lost every
+                // time.
+                mtu.high = mtu.probe - 1;
+                mtu.attempts = 0;
+            }
+            if mtu.is_done() {
+                None
+            } else {
+                if mtu.attempts == 0 {
+                    mtu.probe = mtu.low + (mtu.high - mtu.low).div_ceil(2);
+                }
+                mtu.attempts += 1;
+                mtu.next_probe.set(cb, self.config.resend_delay);
+                Some(mtu.probe)
+            }
+        };
+        match probe {
+            Some(size) => self.send_control(cb, ControlPacket::MtuProbe(size)),
+            None => {
+                self.mtu = None;
+                Ok(())
+            }
+        }
+    }
+    fn receive_mtu_reply<CB: Callback>(&mut self, cb: &mut CB, size: usize) {
+        let done = match self.mtu {
+            Some(ref mut mtu) if mtu.attempts != 0 && size == mtu.probe => {
+                mtu.low = size;
+                mtu.attempts = 0;
+                mtu.next_probe = Timeout::active(cb.time());
+                mtu.is_done()
+            }
+            _ => return,
+        };
+        self.packet_size = size;
+        if done {
+            self.mtu = None;
+        }
+    }
     fn tick_action<CB: Callback>(&mut self, cb: &mut CB) -> Result<(), CB::Error> {
         let control = match self.state {
             State::Connecting => ControlPacket::Connect,
@@ -661,14 +1360,17 @@ impl Connection {
             State::Online(ref mut online) => {
                 if online.can_send() {
                     // TODO: Warn if this happens on reliable networks.
-                    self.send.set(cb, Duration::from_millis(500));
-                    return online.flush(cb, &mut self.builder);
+                    self.send.set(cb, self.config.keepalive_interval);
+                    return online.flush(cb, &mut self.builder, &mut self.debug, self.peer_token);
                 }
                 ControlPacket::KeepAlive
             }
             _ => return Ok(()),
         };
-        self.send.set(cb, Duration::from_millis(500));
+        if let ControlPacket::KeepAlive = control {
+            self.event(Event::KeepaliveSent);
+        }
+        self.send.set(cb, self.config.keepalive_interval);
         self.send_control(cb, control)
     }
     /// Notifies the connection of incoming data.
@@ -690,6 +1392,25 @@ impl Connection {
     }
 
     pub fn feed_impl<'d, 's, CB, W>(
+        &mut self,
+        cb: &mut CB,
+        warn: &mut W,
+        data: &'d [u8],
+        buffer: BufferRef<'d, 's>,
+    ) -> (ReceivePacket<'d>, Result<(), CB::Error>)
+    where
+        CB: Callback,
+        W: Warn<Warning>,
+    {
+        self.stats.packets_received += 1;
+        self.stats.bytes_received += data.len() as u64;
+        match self.version {
+            ProtocolVersion::V6 => self.feed_6(cb, warn, data, buffer),
+            ProtocolVersion::V7 => self.feed_7(cb, warn, data, buffer),
+        }
+    }
+
+    fn feed_6<'d, 's, CB, W>(
         &mut self,
         cb: &mut CB,
         warn: &mut W,
@@ -728,11 +1449,11 @@ impl Connection {
                     return none;
                 }
             }
+            self.last_receive = cb.time();
+            self.timeout_warned = false;
 
             // TODO: Check ack for sanity.
-            if let State::Online(ref mut online) = self.state {
-                online.ack_chunks(Sequence::from_u16(ack));
-            }
+            self.receive_ack(cb, ack);
 
             match type_ {
                 Chunks(request_resend, num_chunks, chunks) => {
@@ -752,8 +1473,17 @@ impl Connection {
                     }
                     match self.state {
                         State::Online(ref mut online) => {
+                            let chunks =
+                                ChunksIter::V6(protocol::ChunksIter::new(chunks, num_chunks));
                             return (
-                                ReceivePacket::connected(warn, online, num_chunks, chunks),
+                                ReceivePacket::connected(
+                                    warn,
+                                    online,
+                                    &mut self.stats,
+                                    &mut self.debug,
+                                    self.last_receive,
+                                    chunks,
+                                ),
                                 result,
                             );
                         }
@@ -763,6 +1493,19 @@ impl Connection {
                     }
                 }
                 Control(KeepAlive) => return none,
+                Control(MtuProbe(size)) => {
+                    return match self.state {
+                        State::Pending(_) | State::Online(_) => (
+                            ReceivePacket::none(),
+                            self.send_control(cb, ControlPacket::MtuReply(size as u16)),
+                        ),
+                        _ => none,
+                    };
+                }
+                Control(MtuReply(size)) => {
+                    self.receive_mtu_reply(cb, size as usize);
+                    return none;
+                }
                 Control(Connect) => {
                     if let State::Unconnected = self.state {
                         let new_token = match token {
@@ -798,23 +1541,152 @@ impl Connection {
         // Fall-through from `Control(Connect)`
         (ReceivePacket::none(), self.tick_action(cb))
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::Callback;
-    use super::Connection;
-    use super::ReceiveChunk;
-    use super::Sequence;
-    use super::SequenceOrdering;
-    use hexdump::hexdump;
-    use itertools::Itertools;
-    use protocol;
-    use std::collections::VecDeque;
-    use void::ResultVoidExt;
-    use void::Void;
-    use warn::Panic;
-    use Timestamp;
+    fn feed_7<'d, 's, CB, W>(
+        &mut self,
+        cb: &mut CB,
+        warn: &mut W,
+        data: &'d [u8],
+        mut buffer: BufferRef<'d, 's>,
+    ) -> (ReceivePacket<'d>, Result<(), CB::Error>)
+    where
+        CB: Callback,
+        W: Warn<Warning>,
+    {
+        use protocol7::ConnectedPacketType::*;
+
+        let none = (ReceivePacket::none(), Ok(()));
+        let packet = match protocol7::Packet::read(&mut w(warn), data, &mut buffer) {
+            Ok(p) => p,
+            Err(e) => {
+                warn.warn(Warning::Read7(e));
+                return none;
+            }
+        };
+
+        let connected = match packet {
+            protocol7::Packet::Connless(data) => return (ReceivePacket::connless(data), Ok(())),
+            protocol7::Packet::Connected(c) => c,
+        };
+        let protocol7::ConnectedPacket { ack, token, type_ } = connected;
+
+        // Token requests are the only packets not carrying our token.
+        if token == protocol7::TOKEN_NONE {
+            if let Control(protocol7::ControlPacket::Token(response_token)) = type_ {
+                if let State::Unconnected = self.state {
+                    let own_token = self.own_token(cb);
+                    let control = protocol7::ControlPacket::Token(own_token);
+                    return (
+                        ReceivePacket::none(),
+                        self.send_control_7(cb, response_token, control),
+                    );
+                }
+            }
+            warn.warn(Warning::Unexpected);
+            return none;
+        }
+        // In contrast to 0.6, this also protects keepalive and close
+        // messages.
+        if Some(token) != self.own_token {
+            warn.warn(Warning::TokenMismatch);
+            return none;
+        }
+        self.last_receive = cb.time();
+        self.timeout_warned = false;
+
+        // TODO: Check ack for sanity.
+        self.receive_ack(cb, ack);
+
+        match type_ {
+            Chunks(request_resend, num_chunks, chunks) => {
+                if !matches!(self.state, State::Online(_)) {
+                    // WARN: packet received while not online.
+                    return none;
+                }
+                let result = if request_resend {
+                    self.resend(cb)
+                } else {
+                    Ok(())
+                };
+                let online = self.state.assert_online();
+                let chunks = ChunksIter::V7(protocol7::ChunksIter::new(chunks, num_chunks));
+                (
+                    ReceivePacket::connected(
+                        warn,
+                        online,
+                        &mut self.stats,
+                        &mut self.debug,
+                        self.last_receive,
+                        chunks,
+                    ),
+                    result,
+                )
+            }
+            Control(protocol7::ControlPacket::KeepAlive) => none,
+            Control(protocol7::ControlPacket::Connect(peer_token)) => match self.state {
+                // Connections created by `new_accept_7` wait for `accept_7`.
+                State::Unconnected if self.peer_token.is_none() => {
+                    self.peer_token = Some(peer_token);
+                    (ReceivePacket::none(), self.accept_7(cb))
+                }
+                // Our accept packet was lost, send it again.
+                State::Online(_) if self.peer_token == Some(peer_token) => (
+                    ReceivePacket::none(),
+                    self.send_control(cb, ControlPacket::Accept),
+                ),
+                _ => none,
+            },
+            Control(protocol7::ControlPacket::Accept) => {
+                if let (&State::Connecting, Some(_)) = (&self.state, self.peer_token) {
+                    self.state = State::Online(OnlineState::new(None));
+                    (ReceivePacket::ready(), Ok(()))
+                } else {
+                    none
+                }
+            }
+            Control(protocol7::ControlPacket::Token(peer_token)) => {
+                if let (&State::Connecting, None) = (&self.state, self.peer_token) {
+                    self.peer_token = Some(peer_token);
+                    (ReceivePacket::none(), self.tick_action(cb))
+                } else {
+                    none
+                }
+            }
+            Control(protocol7::ControlPacket::Close(reason)) => {
+                self.state = State::Disconnected;
+                (ReceivePacket::disconnect(reason), Ok(()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Callback;
+    use super::Config;
+    use super::Connection;
+    use super::Error;
+    use super::ProtocolVersion;
+    use super::ReceiveChunk;
+    use super::SendFlags;
+    use super::Sequence;
+    use super::SequenceDebug;
+    use super::SequenceEvent;
+    use super::SequenceOrdering;
+    use hexdump::hexdump;
+    use itertools::Itertools;
+    use protocol;
+    use protocol7;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+    use std::time::Duration;
+    use void::ResultVoidExt;
+    use void::Void;
+    use warn::Ignore;
+    use warn::Panic;
+    use Timeout;
+    use Timestamp;
 
     #[test]
     fn sequence_compare() {
@@ -836,6 +1708,568 @@ mod test {
         assert_eq!(cmp(end, mid), Past);
     }
 
+    #[test]
+    fn config() {
+        struct Cb(VecDeque<Vec<u8>>, Duration);
+        impl Callback for Cb {
+            type Error = Void;
+            fn secure_random(&mut self, buffer: &mut [u8]) {
+                let _ = buffer;
+                unimplemented!();
+            }
+            fn send(&mut self, data: &[u8]) -> Result<(), Void> {
+                self.0.push_back(data.to_owned());
+                Ok(())
+            }
+            fn time(&mut self) -> Timestamp {
+                Timestamp::from_secs_since_epoch(0) + self.1
+            }
+        }
+        let mut buffer = [0; protocol::MAX_PACKETSIZE];
+        let mut cb = Cb(VecDeque::new(), Duration::from_secs(0));
+        let cb = &mut cb;
+        let config = Config {
+            connect_timeout: Duration::from_secs(1),
+            timeout: Duration::from_secs(2),
+            max_buffered_chunks: 1,
+            ..Config::default()
+        };
+
+        // Nobody answers the connection attempt.
+        let mut client = Connection::with_config(ProtocolVersion::V6, config);
+        client.connect(cb).void_unwrap();
+        cb.1 = Duration::from_millis(999);
+        client.tick(cb).void_unwrap();
+        assert!(!client.has_timed_out());
+        cb.1 = Duration::from_millis(1_000);
+        client.tick(cb).void_unwrap();
+        assert!(client.has_timed_out());
+        client.reset();
+        assert!(!client.has_timed_out());
+        cb.0.clear();
+
+        let mut server = Connection::with_config(ProtocolVersion::V6, config);
+        client.connect(cb).void_unwrap();
+        cb.0.pop_front().unwrap();
+        server
+            .feed(cb, &mut Panic, b"\x10\x00\x00\x01", &mut buffer[..])
+            .1
+            .void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        client
+            .feed(cb, &mut Panic, &packet, &mut buffer[..])
+            .1
+            .void_unwrap();
+        cb.0.clear();
+
+        // Only one vital chunk may wait for an ack.
+        client.send(cb, b"\x42", true).unwrap();
+        client.send(cb, b"\x43", false).unwrap();
+        assert_matches!(client.send(cb, b"\x44", true), Err(Error::BufferFull));
+
+        // Established connections use the longer timeout.
+        cb.1 = Duration::from_millis(2_999);
+        client.tick(cb).void_unwrap();
+        assert!(!client.has_timed_out());
+        cb.1 = Duration::from_millis(3_000);
+        client.tick(cb).void_unwrap();
+        assert!(client.has_timed_out());
+    }
+
+    #[test]
+    fn events() {
+        use super::Event::*;
+
+        struct Cb(VecDeque<Vec<u8>>, Duration);
+        impl Callback for Cb {
+            type Error = Void;
+            fn secure_random(&mut self, buffer: &mut [u8]) {
+                let _ = buffer;
+                unimplemented!();
+            }
+            fn send(&mut self, data: &[u8]) -> Result<(), Void> {
+                self.0.push_back(data.to_owned());
+                Ok(())
+            }
+            fn time(&mut self) -> Timestamp {
+                Timestamp::from_secs_since_epoch(0) + self.1
+            }
+        }
+        let mut buffer = [0; protocol::MAX_PACKETSIZE];
+        let mut cb = Cb(VecDeque::new(), Duration::from_secs(0));
+        let cb = &mut cb;
+        let config = Config {
+            timeout: Duration::from_secs(2),
+            events: true,
+            ..Config::default()
+        };
+        let ms = Duration::from_millis;
+
+        let mut client = Connection::with_config(ProtocolVersion::V6, config);
+        let mut server = Connection::with_config(ProtocolVersion::V6, config);
+        client.connect(cb).void_unwrap();
+        cb.0.pop_front().unwrap();
+        server
+            .feed(cb, &mut Panic, b"\x10\x00\x00\x01", &mut buffer[..])
+            .1
+            .void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        client
+            .feed(cb, &mut Panic, &packet, &mut buffer[..])
+            .1
+            .void_unwrap();
+        cb.0.clear();
+        assert!(client.take_events().next().is_none());
+
+        let tick = |client: &mut Connection, cb: &mut Cb, time| {
+            cb.1 = time;
+            client.tick(cb).void_unwrap();
+            client.take_events().collect_vec()
+        };
+        assert_eq!(tick(&mut client, cb, ms(500)), [KeepaliveSent]);
+        assert_eq!(
+            tick(&mut client, cb, ms(1_000)),
+            [PeerTimeoutWarning(ms(1_000)), KeepaliveSent]
+        );
+        assert_eq!(tick(&mut client, cb, ms(1_200)), []);
+
+        // Hearing from the peer rearms the warning.
+        server.tick(cb).void_unwrap();
+        let packet = cb.0.pop_back().unwrap();
+        client
+            .feed(cb, &mut Panic, &packet, &mut buffer[..])
+            .1
+            .void_unwrap();
+        assert_eq!(tick(&mut client, cb, ms(2_199)), [KeepaliveSent]);
+        assert_eq!(
+            tick(&mut client, cb, ms(2_200)),
+            [PeerTimeoutWarning(ms(1_000))]
+        );
+        assert_eq!(tick(&mut client, cb, ms(3_300)), [TimedOut(ms(2_100))]);
+        assert!(client.has_timed_out());
+    }
+
+    #[test]
+    fn send_flags() {
+        struct Cb(VecDeque<Vec<u8>>, Duration);
+        impl Callback for Cb {
+            type Error = Void;
+            fn secure_random(&mut self, buffer: &mut [u8]) {
+                let _ = buffer;
+                unimplemented!();
+            }
+            fn send(&mut self, data: &[u8]) -> Result<(), Void> {
+                self.0.push_back(data.to_owned());
+                Ok(())
+            }
+            fn time(&mut self) -> Timestamp {
+                Timestamp::from_secs_since_epoch(0) + self.1
+            }
+        }
+        let mut buffer = [0; protocol::MAX_PACKETSIZE];
+        let mut cb = Cb(VecDeque::new(), Duration::from_secs(0));
+        let cb = &mut cb;
+
+        let mut client = Connection::new();
+        let mut server = Connection::new();
+        client.connect(cb).void_unwrap();
+        cb.0.pop_front().unwrap();
+        server
+            .feed(cb, &mut Panic, b"\x10\x00\x00\x01", &mut buffer[..])
+            .1
+            .void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        client
+            .feed(cb, &mut Panic, &packet, &mut buffer[..])
+            .1
+            .void_unwrap();
+        cb.0.clear();
+
+        // Chat can wait, snapshots are sent right away along with it.
+        client.send(cb, b"chat", true).unwrap();
+        assert!(cb.0.is_empty());
+        let flags = SendFlags {
+            vital: false,
+            flush: true,
+        };
+        client.send_with_flags(cb, b"snap", flags).unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        assert!(cb.0.is_empty());
+        let chunks: Vec<_> = server
+            .feed(cb, &mut Panic, &packet, &mut buffer[..])
+            .0
+            .collect();
+        assert_eq!(
+            chunks,
+            [
+                ReceiveChunk::Connected(b"chat", true),
+                ReceiveChunk::Connected(b"snap", false),
+            ]
+        );
+
+        // Queued chunks are coalesced up to the maximum packet size.
+        let chunk = [0; 600];
+        client.send(cb, &chunk, false).unwrap();
+        client.send(cb, &chunk, false).unwrap();
+        assert!(cb.0.is_empty());
+        client.send(cb, &chunk, false).unwrap();
+        assert_eq!(cb.0.pop_front().unwrap()[2], 2);
+        assert!(cb.0.is_empty());
+
+        // The rest is sent when the connection would send a keepalive.
+        cb.1 = Config::default().keepalive_interval;
+        client.tick(cb).void_unwrap();
+        assert_eq!(cb.0.pop_front().unwrap()[2], 1);
+    }
+
+    #[test]
+    fn mtu_discovery() {
+        struct Cb(VecDeque<Vec<u8>>, Duration);
+        impl Callback for Cb {
+            type Error = Void;
+            fn secure_random(&mut self, buffer: &mut [u8]) {
+                let _ = buffer;
+                unimplemented!();
+            }
+            fn send(&mut self, data: &[u8]) -> Result<(), Void> {
+                self.0.push_back(data.to_owned());
+                Ok(())
+            }
+            fn time(&mut self) -> Timestamp {
+                Timestamp::from_secs_since_epoch(0) + self.1
+            }
+        }
+        // Packets larger than this get lost.
+        let link_mtu = 1000;
+        let mut buffer = [0; protocol::MAX_PACKETSIZE];
+        let mut cb = Cb(VecDeque::new(), Duration::from_secs(0));
+        let cb = &mut cb;
+        let config = Config {
+            mtu_discovery: true,
+            ..Config::default()
+        };
+
+        let mut client = Connection::with_config(ProtocolVersion::V6, config);
+        let mut server = Connection::with_config(ProtocolVersion::V6, config);
+        assert_eq!(client.max_packet_size(), protocol::MIN_PACKETSIZE);
+        client.connect(cb).void_unwrap();
+        cb.0.pop_front().unwrap();
+        server
+            .feed(cb, &mut Panic, b"\x10\x00\x00\x01", &mut buffer[..])
+            .1
+            .void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        client
+            .feed(cb, &mut Panic, &packet, &mut buffer[..])
+            .1
+            .void_unwrap();
+        cb.0.clear();
+
+        for _ in 0..50 {
+            client.tick(cb).void_unwrap();
+            let packets: Vec<_> = cb.0.drain(..).collect();
+            for packet in packets.iter().filter(|p| p.len() <= link_mtu) {
+                server
+                    .feed(cb, &mut Panic, packet, &mut buffer[..])
+                    .1
+                    .void_unwrap();
+            }
+            server.tick(cb).void_unwrap();
+            let replies: Vec<_> = cb.0.drain(..).collect();
+            for packet in &replies {
+                client
+                    .feed(cb, &mut Panic, packet, &mut buffer[..])
+                    .1
+                    .void_unwrap();
+            }
+            cb.1 += Duration::from_millis(500);
+        }
+        let size = client.max_packet_size();
+        assert!(link_mtu - 16 < size && size <= link_mtu);
+        assert_eq!(server.max_packet_size(), protocol::MIN_PACKETSIZE);
+
+        // Chunks that don't fit into a packet are rejected instead of being
+        // sent in an oversized packet.
+        client.set_max_packet_size(protocol::MIN_PACKETSIZE);
+        let max = client.max_chunk_size();
+        assert_matches!(
+            client.send(cb, &vec![0; max + 1], true),
+            Err(Error::TooLongData)
+        );
+        assert!(client.is_drained());
+        client.send(cb, &vec![0; max], true).unwrap();
+        client.flush(cb).void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        assert_eq!(packet[2], 1);
+        assert!(packet.len() <= protocol::MIN_PACKETSIZE);
+        assert!(cb.0.is_empty());
+    }
+
+    #[test]
+    fn stats() {
+        struct Cb(VecDeque<Vec<u8>>, Duration);
+        impl Callback for Cb {
+            type Error = Void;
+            fn secure_random(&mut self, buffer: &mut [u8]) {
+                let _ = buffer;
+                unimplemented!();
+            }
+            fn send(&mut self, data: &[u8]) -> Result<(), Void> {
+                self.0.push_back(data.to_owned());
+                Ok(())
+            }
+            fn time(&mut self) -> Timestamp {
+                Timestamp::from_secs_since_epoch(0) + self.1
+            }
+        }
+        let mut buffer = [0; protocol::MAX_PACKETSIZE];
+        let mut cb = Cb(VecDeque::new(), Duration::from_secs(0));
+        let cb = &mut cb;
+
+        let mut client = Connection::new();
+        let mut server = Connection::new();
+
+        client.connect(cb).void_unwrap();
+        cb.0.pop_front().unwrap();
+        server
+            .feed(cb, &mut Panic, b"\x10\x00\x00\x01", &mut buffer[..])
+            .1
+            .void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        client
+            .feed(cb, &mut Panic, &packet, &mut buffer[..])
+            .1
+            .void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        server
+            .feed(cb, &mut Panic, &packet, &mut buffer[..])
+            .1
+            .void_unwrap();
+        assert!(cb.0.is_empty());
+
+        client.send(cb, b"\x42", true).unwrap();
+        client.flush(cb).void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        assert!(
+            server
+                .feed(cb, &mut Panic, &packet, &mut buffer[..])
+                .0
+                .count()
+                == 1
+        );
+        // The duplicate is dropped.
+        assert!(
+            server
+                .feed(cb, &mut Panic, &packet, &mut buffer[..])
+                .0
+                .count()
+                == 0
+        );
+
+        // The ack arrives 100 ms later.
+        cb.1 = Duration::from_millis(100);
+        server.send(cb, b"\x43", true).unwrap();
+        server.flush(cb).void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        assert!(cb.0.is_empty());
+        client
+            .feed(cb, &mut Panic, &packet, &mut buffer[..])
+            .1
+            .void_unwrap();
+
+        let stats = client.stats();
+        assert_eq!(stats.packets_sent, 3);
+        assert_eq!(stats.bytes_sent, 12 + 4 + 7);
+        assert_eq!(stats.packets_received, 2);
+        assert_eq!(stats.last_ack, Some(1));
+        assert_eq!(stats.rtt, Some(Duration::from_millis(100)));
+        assert_eq!(stats.jitter, Duration::from_millis(50));
+        assert_eq!(stats.chunks_dropped, 0);
+        assert_eq!(server.stats().chunks_dropped, 1);
+        assert_eq!(server.stats().packets_received, 4);
+        assert_eq!(server.stats().rtt, None);
+    }
+
+    #[test]
+    fn drained() {
+        struct Cb(VecDeque<Vec<u8>>);
+        impl Callback for Cb {
+            type Error = Void;
+            fn secure_random(&mut self, buffer: &mut [u8]) {
+                let _ = buffer;
+                unimplemented!();
+            }
+            fn send(&mut self, data: &[u8]) -> Result<(), Void> {
+                self.0.push_back(data.to_owned());
+                Ok(())
+            }
+            fn time(&mut self) -> Timestamp {
+                Timestamp::from_secs_since_epoch(0)
+            }
+        }
+        let mut buffer = [0; protocol::MAX_PACKETSIZE];
+        let cb = &mut Cb(VecDeque::new());
+
+        let mut client = Connection::new();
+        let mut server = Connection::new();
+        assert!(client.is_drained());
+        client.connect(cb).void_unwrap();
+        cb.0.pop_front().unwrap();
+        server
+            .feed(cb, &mut Panic, b"\x10\x00\x00\x01", &mut buffer[..])
+            .1
+            .void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        client
+            .feed(cb, &mut Panic, &packet, &mut buffer[..])
+            .1
+            .void_unwrap();
+        cb.0.clear();
+
+        client.send(cb, b"\x42", false).unwrap();
+        assert!(!client.is_drained());
+        client.flush(cb).void_unwrap();
+        assert!(client.is_drained());
+
+        client.send(cb, b"\x43", true).unwrap();
+        client.flush(cb).void_unwrap();
+        assert!(!client.is_drained());
+        // Skip the packet containing the non-vital chunk.
+        cb.0.pop_front().unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        server
+            .feed(cb, &mut Panic, &packet, &mut buffer[..])
+            .1
+            .void_unwrap();
+        server.send(cb, b"\x44", false).unwrap();
+        server.flush(cb).void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        client
+            .feed(cb, &mut Panic, &packet, &mut buffer[..])
+            .1
+            .void_unwrap();
+        assert!(client.is_drained());
+
+        client.disconnect(cb, b"").void_unwrap();
+        assert!(client.is_disconnected() && client.is_drained());
+    }
+
+    #[test]
+    fn sequence_debug() {
+        use super::SequenceEvent::*;
+
+        struct Cb(VecDeque<Vec<u8>>, Duration);
+        impl Callback for Cb {
+            type Error = Void;
+            fn secure_random(&mut self, buffer: &mut [u8]) {
+                let _ = buffer;
+                unimplemented!();
+            }
+            fn send(&mut self, data: &[u8]) -> Result<(), Void> {
+                self.0.push_back(data.to_owned());
+                Ok(())
+            }
+            fn time(&mut self) -> Timestamp {
+                Timestamp::from_secs_since_epoch(0) + self.1
+            }
+        }
+        fn debug(events: &Rc<RefCell<Vec<(u64, SequenceEvent)>>>) -> Box<dyn SequenceDebug> {
+            let events = events.clone();
+            Box::new(move |time: Timestamp, event| {
+                let epoch = Timestamp::from_secs_since_epoch(0);
+                let millis = Timeout::active(time).time_from(epoch).unwrap().as_millis();
+                events.borrow_mut().push((millis as u64, event));
+            })
+        }
+        let mut buffer = [0; protocol::MAX_PACKETSIZE];
+        let mut cb = Cb(VecDeque::new(), Duration::from_secs(0));
+        let cb = &mut cb;
+
+        let mut client = Connection::new();
+        let mut server = Connection::new();
+        let client_events = Rc::new(RefCell::new(Vec::new()));
+        let server_events = Rc::new(RefCell::new(Vec::new()));
+        client.set_sequence_debug(Some(debug(&client_events)));
+        server.set_sequence_debug(Some(debug(&server_events)));
+
+        client.connect(cb).void_unwrap();
+        cb.0.pop_front().unwrap();
+        server
+            .feed(cb, &mut Panic, b"\x10\x00\x00\x01", &mut buffer[..])
+            .1
+            .void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        client
+            .feed(cb, &mut Panic, &packet, &mut buffer[..])
+            .1
+            .void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        server
+            .feed(cb, &mut Panic, &packet, &mut buffer[..])
+            .1
+            .void_unwrap();
+        assert!(cb.0.is_empty());
+
+        client.send(cb, b"\x42", true).unwrap();
+        client.send(cb, b"\x43", false).unwrap();
+        client.send(cb, b"\x44", true).unwrap();
+        client.flush(cb).void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        assert!(
+            server
+                .feed(cb, &mut Panic, &packet, &mut buffer[..])
+                .0
+                .count()
+                == 3
+        );
+
+        // The ack doesn't arrive in time.
+        cb.1 = Duration::from_secs(1);
+        client.tick(cb).void_unwrap();
+        client.flush(cb).void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        assert!(
+            server
+                .feed(cb, &mut Panic, &packet, &mut buffer[..])
+                .0
+                .count()
+                == 0
+        );
+
+        cb.1 = Duration::from_millis(1100);
+        server.send(cb, b"\x45", false).unwrap();
+        server.flush(cb).void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        client
+            .feed(cb, &mut Panic, &packet, &mut buffer[..])
+            .1
+            .void_unwrap();
+        assert!(cb.0.is_empty());
+
+        assert_eq!(
+            *client_events.borrow(),
+            [
+                (0, Queued(1)),
+                (0, Queued(2)),
+                (0, Sent(1)),
+                (0, Sent(2)),
+                (1000, Resent(1)),
+                (1000, Resent(2)),
+                (1100, Acked(1)),
+                (1100, Acked(2)),
+            ]
+        );
+        assert_eq!(
+            *server_events.borrow(),
+            [
+                (0, Received(1)),
+                (0, Received(2)),
+                (1000, Dropped(1)),
+                (1000, Dropped(2)),
+            ]
+        );
+    }
+
     #[test]
     fn establish_connection_no_token() {
         struct Cb(VecDeque<Vec<u8>>);
@@ -1131,7 +2565,7 @@ mod test {
         assert!(cb.0.is_empty());
 
         let token = protocol::Token([0x12, 0x34, 0x56, 0x78]);
-        let mut server = Connection::new_accept_token(cb, token);
+        let mut server = Connection::new_accept_token(cb, token, Config::default());
 
         // Send
         client.send(cb, b"\x42", true).unwrap();
@@ -1171,4 +2605,131 @@ mod test {
         client.reset();
         server.reset();
     }
+
+    #[test]
+    fn establish_connection_7() {
+        struct Cb(VecDeque<Vec<u8>>, u8);
+        impl Cb {
+            fn new() -> Cb {
+                Cb(VecDeque::new(), 0)
+            }
+        }
+        impl Callback for Cb {
+            type Error = Void;
+            fn secure_random(&mut self, buffer: &mut [u8]) {
+                for b in buffer {
+                    self.1 += 1;
+                    *b = self.1;
+                }
+            }
+            fn send(&mut self, data: &[u8]) -> Result<(), Void> {
+                self.0.push_back(data.to_owned());
+                Ok(())
+            }
+            fn time(&mut self) -> Timestamp {
+                Timestamp::from_secs_since_epoch(0)
+            }
+        }
+        let mut buffer = [0; protocol::MAX_PACKETSIZE];
+        let mut cb = Cb::new();
+        let cb = &mut cb;
+        println!("");
+
+        let mut client = Connection::with_version(ProtocolVersion::V7);
+        let mut server = Connection::with_version(ProtocolVersion::V7);
+
+        // Token request
+        client.connect(cb).void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        assert!(cb.0.is_empty());
+        hexdump(&packet);
+        assert!(packet.len() == protocol7::TOKEN_REQUEST_PACKET_SIZE);
+        assert!(&packet[..12] == b"\x04\x00\x00\xff\xff\xff\xff\x05\x01\x02\x03\x04");
+
+        // Token
+        assert!(server
+            .feed(cb, &mut Panic, &packet, &mut buffer[..])
+            .0
+            .next()
+            .is_none());
+        let packet = cb.0.pop_front().unwrap();
+        assert!(cb.0.is_empty());
+        hexdump(&packet);
+        assert!(&packet == b"\x04\x00\x00\x01\x02\x03\x04\x05\x05\x06\x07\x08");
+
+        // Connect
+        assert!(client
+            .feed(cb, &mut Panic, &packet, &mut buffer[..])
+            .0
+            .next()
+            .is_none());
+        let packet = cb.0.pop_front().unwrap();
+        assert!(cb.0.is_empty());
+        hexdump(&packet);
+        assert!(&packet == b"\x04\x00\x00\x05\x06\x07\x08\x01\x01\x02\x03\x04");
+
+        // Accept
+        assert!(server
+            .feed(cb, &mut Panic, &packet, &mut buffer[..])
+            .0
+            .next()
+            .is_none());
+        let packet = cb.0.pop_front().unwrap();
+        assert!(cb.0.is_empty());
+        hexdump(&packet);
+        assert!(&packet == b"\x04\x00\x00\x01\x02\x03\x04\x02");
+
+        assert!(
+            client
+                .feed(cb, &mut Panic, &packet, &mut buffer[..])
+                .0
+                .collect_vec()
+                == &[ReceiveChunk::Ready]
+        );
+        assert!(cb.0.is_empty());
+
+        // Send
+        client.send(cb, b"\x42", true).unwrap();
+        client.flush(cb).void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        assert!(cb.0.is_empty());
+        hexdump(&packet);
+        assert!(&packet == b"\x00\x00\x01\x05\x06\x07\x08\x40\x01\x01\x42");
+
+        // Receive
+        assert!(
+            server
+                .feed(cb, &mut Panic, &packet, &mut buffer[..])
+                .0
+                .collect_vec()
+                == &[ReceiveChunk::Connected(b"\x42", true)]
+        );
+        assert!(cb.0.is_empty());
+
+        // Packets without the right token are dropped.
+        let mut spoofed = packet.clone();
+        spoofed[3] = 0x00;
+        assert!(server
+            .feed(cb, &mut Ignore, &spoofed, &mut buffer[..])
+            .0
+            .next()
+            .is_none());
+
+        // Disconnect
+        server.disconnect(cb, b"42").void_unwrap();
+        let packet = cb.0.pop_front().unwrap();
+        hexdump(&packet);
+        assert!(&packet == b"\x04\x01\x00\x01\x02\x03\x04\x0442\0");
+
+        assert!(
+            client
+                .feed(cb, &mut Panic, &packet, &mut buffer[..])
+                .0
+                .collect_vec()
+                == &[ReceiveChunk::Disconnect(b"42")]
+        );
+
+        client.reset();
+        server.reset();
+    }
 }
@@ -0,0 +1,325 @@
+//! Filters for received packets, applied before they're decoded.
+//!
+//! `RateLimit` limits the number of packets per source address using token
+//! buckets, `BanList` drops packets from banned networks.
+
+use net::Address;
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::net::IpAddr;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::time::Duration;
+use Timeout;
+use Timestamp;
+
+/// Decides whether a received packet is processed, see
+/// `Net::set_packet_filter`.
+pub trait PacketFilter<A: Address> {
+    /// Returns `false` if the packet should be dropped.
+    fn allow(&mut self, time: Timestamp, addr: A, data: &[u8]) -> bool;
+}
+
+impl<A: Address, F: FnMut(Timestamp, A, &[u8]) -> bool> PacketFilter<A> for F {
+    fn allow(&mut self, time: Timestamp, addr: A, data: &[u8]) -> bool {
+        self(time, addr, data)
+    }
+}
+
+fn elapsed(time: Timestamp, earlier: Timestamp) -> Duration {
+    Timeout::active(time).time_from(earlier).unwrap()
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Bucket {
+    tokens: u32,
+    last_refill: Timestamp,
+}
+
+/// Token bucket rate limiting per source address.
+///
+/// Each address may send `burst` packets at once, refilled by one every
+/// `interval`.
+pub struct RateLimit<A: Address> {
+    burst: u32,
+    interval: Duration,
+    max_addresses: usize,
+    buckets: HashMap<A, Bucket>,
+}
+
+impl<A: Address> RateLimit<A> {
+    pub fn new(burst: u32, interval: Duration) -> RateLimit<A> {
+        assert!(burst > 0 && interval > Duration::from_secs(0));
+        RateLimit {
+            burst,
+            interval,
+            max_addresses: 16384,
+            buckets: HashMap::new(),
+        }
+    }
+    /// Limits the number of tracked addresses, 16384 by default.
+    ///
+    /// Addresses whose bucket is full are forgotten when the limit is
+    /// reached. If that doesn't help, packets from new addresses are
+    /// dropped.
+    pub fn set_max_addresses(&mut self, max: usize) {
+        self.max_addresses = max;
+    }
+    fn refill(&self, bucket: &mut Bucket, time: Timestamp) {
+        let elapsed = elapsed(time, bucket.last_refill);
+        let refills = elapsed.as_nanos() / self.interval.as_nanos();
+        if refills == 0 {
+            return;
+        }
+        let tokens = (bucket.tokens as u128 + refills).min(self.burst as u128);
+        bucket.tokens = tokens as u32;
+        bucket.last_refill = if bucket.tokens == self.burst {
+            time
+        } else {
+            bucket.last_refill + self.interval * refills as u32
+        };
+    }
+    fn prune(&mut self, time: Timestamp) {
+        let mut buckets = std::mem::take(&mut self.buckets);
+        buckets.retain(|_, b| {
+            self.refill(b, time);
+            b.tokens < self.burst
+        });
+        self.buckets = buckets;
+    }
+}
+
+impl<A: Address> PacketFilter<A> for RateLimit<A> {
+    fn allow(&mut self, time: Timestamp, addr: A, data: &[u8]) -> bool {
+        let _ = data;
+        if !self.buckets.contains_key(&addr) {
+            if self.buckets.len() >= self.max_addresses {
+                self.prune(time);
+                if self.buckets.len() >= self.max_addresses {
+                    return false;
+                }
+            }
+            let bucket = Bucket {
+                tokens: self.burst,
+                last_refill: time,
+            };
+            self.buckets.insert(addr, bucket);
+        }
+        let mut bucket = self.buckets[&addr];
+        self.refill(&mut bucket, time);
+        let allow = bucket.tokens != 0;
+        if allow {
+            bucket.tokens -= 1;
+        }
+        self.buckets.insert(addr, bucket);
+        allow
+    }
+}
+
+/// An IP network in CIDR notation, e.g. `192.0.2.0/24`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Cidr {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct InvalidCidr;
+
+impl fmt::Display for InvalidCidr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid CIDR network")
+    }
+}
+
+impl error::Error for InvalidCidr {}
+
+impl Cidr {
+    /// Returns `None` if `prefix_len` is longer than the address.
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Option<Cidr> {
+        let max_len = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        if prefix_len > max_len {
+            return None;
+        }
+        Some(Cidr { addr, prefix_len })
+    }
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        fn matches(a: &[u8], b: &[u8], prefix_len: u8) -> bool {
+            let bytes = prefix_len as usize / 8;
+            let bits = prefix_len % 8;
+            if a[..bytes] != b[..bytes] {
+                return false;
+            }
+            bits == 0 || (a[bytes] ^ b[bytes]) >> (8 - bits) == 0
+        }
+        // IPv4-mapped IPv6 addresses are treated like IPv4 addresses.
+        let ip = match ip {
+            IpAddr::V6(ip) => ip.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip.into()),
+            ip => ip,
+        };
+        match (self.addr, ip) {
+            (IpAddr::V4(a), IpAddr::V4(b)) => matches(&a.octets(), &b.octets(), self.prefix_len),
+            (IpAddr::V6(a), IpAddr::V6(b)) => matches(&a.octets(), &b.octets(), self.prefix_len),
+            _ => false,
+        }
+    }
+}
+
+impl From<IpAddr> for Cidr {
+    fn from(addr: IpAddr) -> Cidr {
+        let prefix_len = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        Cidr { addr, prefix_len }
+    }
+}
+
+impl FromStr for Cidr {
+    type Err = InvalidCidr;
+    fn from_str(s: &str) -> Result<Cidr, InvalidCidr> {
+        let mut parts = s.splitn(2, '/');
+        let addr: IpAddr = parts.next().unwrap().parse().map_err(|_| InvalidCidr)?;
+        match parts.next() {
+            None => Ok(Cidr::from(addr)),
+            Some(len) => {
+                let len = len.parse().map_err(|_| InvalidCidr)?;
+                Cidr::new(addr, len).ok_or(InvalidCidr)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Ban {
+    network: Cidr,
+    expires: Option<Timestamp>,
+}
+
+/// Drops packets from banned networks.
+#[derive(Clone, Debug, Default)]
+pub struct BanList {
+    bans: Vec<Ban>,
+}
+
+impl BanList {
+    pub fn new() -> BanList {
+        Default::default()
+    }
+    /// Bans `network` until `expires`, or forever. Replaces an existing ban
+    /// of the same network.
+    pub fn ban(&mut self, network: Cidr, expires: Option<Timestamp>) {
+        self.unban(network);
+        self.bans.push(Ban { network, expires });
+    }
+    /// Returns whether the network was banned.
+    pub fn unban(&mut self, network: Cidr) -> bool {
+        let len = self.bans.len();
+        self.bans.retain(|b| b.network != network);
+        self.bans.len() != len
+    }
+    /// Returns the networks currently banned along with their expiry.
+    pub fn bans(&self) -> impl Iterator<Item = (Cidr, Option<Timestamp>)> + '_ {
+        self.bans.iter().map(|b| (b.network, b.expires))
+    }
+    /// Returns whether `ip` is banned at `time`, removing expired bans.
+    pub fn is_banned(&mut self, time: Timestamp, ip: IpAddr) -> bool {
+        self.bans
+            .retain(|b| b.expires.map(|e| time < e).unwrap_or(true));
+        self.bans.iter().any(|b| b.network.contains(ip))
+    }
+}
+
+impl PacketFilter<SocketAddr> for BanList {
+    fn allow(&mut self, time: Timestamp, addr: SocketAddr, data: &[u8]) -> bool {
+        let _ = data;
+        !self.is_banned(time, addr.ip())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BanList;
+    use super::Cidr;
+    use super::PacketFilter;
+    use super::RateLimit;
+    use std::net::SocketAddr;
+    use std::time::Duration;
+    use Timestamp;
+
+    fn t(millis: u64) -> Timestamp {
+        Timestamp::from_secs_since_epoch(0) + Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn rate_limit() {
+        let mut limit = RateLimit::new(2, Duration::from_millis(100));
+        assert!(limit.allow(t(0), 1, b""));
+        assert!(limit.allow(t(0), 1, b""));
+        assert!(!limit.allow(t(0), 1, b""));
+        assert!(limit.allow(t(0), 2, b""));
+        assert!(!limit.allow(t(99), 1, b""));
+        assert!(limit.allow(t(100), 1, b""));
+        assert!(!limit.allow(t(150), 1, b""));
+        assert!(limit.allow(t(1000), 1, b""));
+        assert!(limit.allow(t(1000), 1, b""));
+        assert!(!limit.allow(t(1000), 1, b""));
+    }
+
+    #[test]
+    fn rate_limit_max_addresses() {
+        let mut limit = RateLimit::new(1, Duration::from_millis(100));
+        limit.set_max_addresses(1);
+        assert!(limit.allow(t(0), 1, b""));
+        assert!(!limit.allow(t(0), 2, b""));
+        // The first address's bucket is full again and can be forgotten.
+        assert!(limit.allow(t(100), 2, b""));
+    }
+
+    #[test]
+    fn cidr() {
+        let net: Cidr = "192.0.2.0/23".parse().unwrap();
+        assert!(net.contains("192.0.3.255".parse().unwrap()));
+        assert!(!net.contains("192.0.4.0".parse().unwrap()));
+        assert!(net.contains("::ffff:192.0.2.1".parse().unwrap()));
+        assert_eq!(net.to_string(), "192.0.2.0/23");
+        let net: Cidr = "2001:db8::/32".parse().unwrap();
+        assert!(net.contains("2001:db8:1::1".parse().unwrap()));
+        assert!(!net.contains("2001:db9::".parse().unwrap()));
+        let net: Cidr = "192.0.2.1".parse().unwrap();
+        assert_eq!(net.prefix_len(), 32);
+        assert!("192.0.2.0/33".parse::<Cidr>().is_err());
+        assert!("192.0.2.0/".parse::<Cidr>().is_err());
+    }
+
+    #[test]
+    fn ban_list() {
+        let addr: SocketAddr = "192.0.2.1:8303".parse().unwrap();
+        let mut bans = BanList::new();
+        assert!(bans.allow(t(0), addr, b""));
+        bans.ban("192.0.2.0/24".parse().unwrap(), Some(t(1000)));
+        assert!(!bans.allow(t(999), addr, b""));
+        assert!(bans.allow(t(1000), addr, b""));
+        assert_eq!(bans.bans().count(), 0);
+        bans.ban("192.0.2.1".parse().unwrap(), None);
+        assert!(!bans.allow(t(1_000_000), addr, b""));
+        assert!(bans.unban("192.0.2.1".parse().unwrap()));
+        assert!(bans.allow(t(1_000_000), addr, b""));
+    }
+}
@@ -11,22 +11,41 @@ extern crate assert_matches;
 extern crate buffer;
 #[macro_use]
 extern crate common;
+#[cfg(feature = "futures-core")]
+extern crate futures_core;
 extern crate huffman;
 extern crate linear_map;
 #[macro_use]
 extern crate matches;
 extern crate optional;
+extern crate packer;
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "tokio")]
+extern crate tokio_crate;
+#[cfg(feature = "tungstenite")]
+extern crate tungstenite;
 extern crate void;
 extern crate warn;
 
 pub mod collections;
 pub mod connection;
+pub mod filter;
 pub mod net;
 pub mod protocol;
 pub mod protocol7;
+pub mod testing;
 pub mod time;
+#[cfg(feature = "tokio")]
+pub mod tokio;
+pub mod trace;
+pub mod transport;
+#[cfg(feature = "tungstenite")]
+pub mod ws;
 
+pub use connection::Config;
 pub use connection::Connection;
+pub use connection::ProtocolVersion;
 pub use net::Net;
 pub use time::Timeout;
 pub use time::Timestamp;
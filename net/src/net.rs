@@ -5,16 +5,24 @@ use buffer::BufferRef;
 use collections::peer_map;
 use collections::PeerMap;
 use connection;
+use connection::Config;
+use connection::ProtocolVersion;
 use connection::ReceiveChunk;
+use filter::PacketFilter;
 use protocol;
 use protocol::ConnectedPacket;
 use protocol::ConnectedPacketType;
 use protocol::ControlPacket;
 use protocol::Packet;
+use protocol7;
+use std::collections::hash_map::DefaultHasher;
 use std::fmt;
 use std::hash::Hash;
+use std::hash::Hasher;
 use std::iter;
+use std::net::SocketAddr;
 use std::ops;
+use std::time::Duration;
 use warn::Panic;
 use warn::Warn;
 use Connection;
@@ -22,6 +30,9 @@ use Timeout;
 use Timestamp;
 
 pub use connection::Error;
+pub use connection::SendFlags;
+pub use connection::SequenceDebug;
+pub use connection::SequenceEvent;
 
 pub trait Callback<A: Address> {
     type Error;
@@ -71,26 +82,20 @@ impl fmt::Display for PeerId {
     }
 }
 
-const CONNECT_PACKET: &'static [u8; 12] = b"\x10\x00\x00\x01TKEN\xff\xff\xff\xff";
 const CONNECT_PACKET_NO_TOKEN: &'static [u8; 4] = b"\x10\x00\x00\x01";
+/// Tokens handed out stay valid for at least this long, and at most twice
+/// as long.
+const TOKEN_SECRET_LIFETIME: Duration = Duration::from_secs(60);
+const TOO_MANY_CONNECTIONS: &[u8] = b"Too many connections from your address";
 
 struct Peer<A: Address> {
     conn: Connection,
     addr: A,
-    /// This flag says whether the incoming connection had indicated token
-    /// support.
-    ///
-    /// DDNet token support is unfortunately kind of hacked in.
-    token: bool,
 }
 
 impl<A: Address> Peer<A> {
-    fn new(addr: A, token: bool) -> Peer<A> {
-        Peer {
-            conn: Connection::new(),
-            addr: addr,
-            token: token,
-        }
+    fn new(conn: Connection, addr: A) -> Peer<A> {
+        Peer { conn, addr: addr }
     }
 }
 
@@ -106,14 +111,14 @@ impl<A: Address> Peers<A> {
             next_peer_id: PeerId(0),
         }
     }
-    fn new_peer(&mut self, addr: A, token: bool) -> (PeerId, &mut Peer<A>) {
+    fn new_peer(&mut self, conn: Connection, addr: A) -> (PeerId, &mut Peer<A>) {
         // FIXME(rust-lang/rfcs#811): Work around missing non-lexical borrows.
         let raw_self: *mut Peers<A> = self;
         unsafe {
             loop {
                 let peer_id = self.next_peer_id.get_and_increment();
                 if let peer_map::Entry::Vacant(v) = (*raw_self).peers.entry(peer_id) {
-                    return (peer_id, v.insert(Peer::new(addr, token)));
+                    return (peer_id, v.insert(Peer::new(conn, addr)));
                 }
             }
         }
@@ -205,6 +210,20 @@ impl ConnlessBuilder {
         cb.send(addr, send_data)?;
         Ok(())
     }
+    fn send_7<A: Address, CB: Callback<A>>(
+        &mut self,
+        cb: &mut CB,
+        addr: A,
+        packet: protocol7::ConnectedPacket,
+    ) -> Result<(), Error<CB::Error>> {
+        let send_data = match packet.write(&mut self.buffer[..]) {
+            Ok(d) => d,
+            Err(protocol::Error::Capacity(_)) => unreachable!("too short buffer provided"),
+            Err(protocol::Error::TooLongData) => return Err(Error::TooLongData),
+        };
+        cb.send(addr, send_data)?;
+        Ok(())
+    }
 }
 
 #[derive(Clone)]
@@ -296,10 +315,40 @@ enum ReceivePacketType<'a, A: Address> {
     Connless(A, iter::Once<&'a [u8]>),
 }
 
+/// Decides whether a new peer may connect, given its address and the
+/// addresses of all existing peers.
+pub type AcceptFilter<A> = Box<dyn FnMut(A, &mut dyn Iterator<Item = A>) -> bool>;
+
+/// Returns an `AcceptFilter` allowing at most `max` peers per IP address.
+pub fn max_connections_per_ip(max: usize) -> AcceptFilter<SocketAddr> {
+    Box::new(
+        move |addr: SocketAddr, peers: &mut dyn Iterator<Item = SocketAddr>| {
+            peers.filter(|p| p.ip() == addr.ip()).count() < max
+        },
+    )
+}
+
+/// Decides whether a peer may move to a new address, given its ID, its old
+/// address and the new one.
+pub type MigrationFilter<A> = Box<dyn FnMut(PeerId, A, A) -> bool>;
+
 pub struct Net<A: Address> {
     peers: Peers<A>,
     builder: ConnlessBuilder,
     accept_connections: bool,
+    accept_filter: Option<AcceptFilter<A>>,
+    packet_filter: Option<Box<dyn PacketFilter<A>>>,
+    migration_filter: Option<MigrationFilter<A>>,
+    /// Secrets from which the tokens handed out to peers are derived, so
+    /// that no state needs to be kept for unverified peers.
+    token_secrets: Option<TokenSecrets>,
+    config: Config,
+}
+
+struct TokenSecrets {
+    current: [u8; 16],
+    previous: [u8; 16],
+    rotate: Timestamp,
 }
 
 struct ConnectionCallback<'a, A: Address, CB: Callback<A> + 'a> {
@@ -327,6 +376,15 @@ impl<'a, A: Address, W: Warn<Warning<A>>> Warn<protocol::Warning> for WarnCallba
     }
 }
 
+impl<'a, A: Address, W: Warn<Warning<A>>> Warn<protocol7::Warning> for WarnCallback<'a, A, W> {
+    fn warn(&mut self, warning: protocol7::Warning) {
+        self.warn.warn(Warning::Connless(
+            self.addr,
+            connection::Warning::Packet7(warning),
+        ))
+    }
+}
+
 struct WarnCallback<'a, A: Address, W: Warn<Warning<A>> + 'a> {
     warn: &'a mut W,
     addr: A,
@@ -377,18 +435,56 @@ impl<'a, A: Address, CB: Callback<A>> connection::Callback for ConnectionCallbac
 }
 
 impl<A: Address> Net<A> {
-    fn new(accept_connections: bool) -> Net<A> {
+    fn new(accept_connections: bool, config: Config) -> Net<A> {
         Net {
             peers: Peers::new(),
             builder: ConnlessBuilder::new(),
             accept_connections: accept_connections,
+            accept_filter: None,
+            packet_filter: None,
+            migration_filter: None,
+            token_secrets: None,
+            config,
         }
     }
     pub fn server() -> Net<A> {
-        Net::new(true)
+        Net::server_with_config(Config::default())
     }
     pub fn client() -> Net<A> {
-        Net::new(false)
+        Net::client_with_config(Config::default())
+    }
+    pub fn server_with_config(config: Config) -> Net<A> {
+        Net::new(true, config)
+    }
+    pub fn client_with_config(config: Config) -> Net<A> {
+        Net::new(false, config)
+    }
+    /// Sets a filter that is consulted before creating a peer for a new
+    /// connection. Rejected peers are sent a close message.
+    pub fn set_accept_filter(&mut self, filter: Option<AcceptFilter<A>>) {
+        self.accept_filter = filter;
+    }
+    /// Sets a filter that is consulted for every received packet before
+    /// decoding it. Rejected packets are dropped silently.
+    pub fn set_packet_filter(&mut self, filter: Option<Box<dyn PacketFilter<A>>>) {
+        self.packet_filter = filter;
+    }
+    /// Allows DDNet peers to change their address, e.g. due to NAT
+    /// rebinding, disabled by default.
+    ///
+    /// A packet from an unknown address carrying the token of an online 0.6
+    /// peer moves that peer to the new address if the filter agrees. The
+    /// packet itself is dropped and later resent by the peer.
+    pub fn set_migration_filter(&mut self, filter: Option<MigrationFilter<A>>) {
+        self.migration_filter = filter;
+    }
+    /// Whether new peers may connect, e.g. to stop accepting them when
+    /// shutting down.
+    pub fn set_accept_connections(&mut self, accept: bool) {
+        self.accept_connections = accept;
+    }
+    pub fn peers<'a>(&'a self) -> impl Iterator<Item = PeerId> + 'a {
+        self.peers.iter().map(|(pid, _)| pid)
     }
     pub fn needs_tick(&self) -> Timeout {
         self.peers
@@ -409,9 +505,60 @@ impl<A: Address> Net<A> {
         cb: &mut CB,
         addr: A,
     ) -> (PeerId, Result<(), CB::Error>) {
-        let (pid, peer) = self.peers.new_peer(addr, false);
+        self.connect_with_version(cb, addr, ProtocolVersion::V6)
+    }
+    pub fn connect_with_version<CB: Callback<A>>(
+        &mut self,
+        cb: &mut CB,
+        addr: A,
+        version: ProtocolVersion,
+    ) -> (PeerId, Result<(), CB::Error>) {
+        let conn = Connection::with_config(version, self.config);
+        let (pid, peer) = self.peers.new_peer(conn, addr);
         (pid, peer.conn.connect(&mut cc(cb, peer.addr)))
     }
+    pub fn version(&self, pid: PeerId) -> ProtocolVersion {
+        self.peers[pid].conn.version()
+    }
+    pub fn stats(&self, pid: PeerId) -> connection::Stats {
+        self.peers[pid].conn.stats()
+    }
+    pub fn max_packet_size(&self, pid: PeerId) -> usize {
+        self.peers[pid].conn.max_packet_size()
+    }
+    /// Overrides `Config::max_packet_size` for a single peer.
+    pub fn set_max_packet_size(&mut self, pid: PeerId, size: usize) {
+        self.peers[pid].conn.set_max_packet_size(size)
+    }
+    /// Reports the state transitions of the peer's vital chunks to `debug`.
+    pub fn set_sequence_debug(&mut self, pid: PeerId, debug: Option<Box<dyn SequenceDebug>>) {
+        self.peers[pid].conn.set_sequence_debug(debug)
+    }
+    /// Returns whether all chunks sent to the peer have been acked.
+    pub fn is_drained(&self, pid: PeerId) -> bool {
+        self.peers[pid].conn.is_drained()
+    }
+    /// Returns whether the connection was closed, by either side or due to
+    /// a timeout.
+    pub fn is_disconnected(&self, pid: PeerId) -> bool {
+        self.peers[pid].conn.is_disconnected()
+    }
+    /// Returns the peers whose connections timed out during `tick`.
+    ///
+    /// They should be removed using `ignore`.
+    pub fn timed_out<'a>(&'a self) -> impl Iterator<Item = PeerId> + 'a {
+        self.peers
+            .iter()
+            .filter(|&(_, p)| p.conn.has_timed_out())
+            .map(|(pid, _)| pid)
+    }
+    /// Returns the connection events of all peers that happened since the
+    /// last call, see `Config::events`.
+    pub fn take_events<'a>(&'a mut self) -> impl Iterator<Item = (PeerId, connection::Event)> + 'a {
+        self.peers
+            .iter_mut()
+            .flat_map(|(pid, p)| p.conn.take_events().map(move |e| (pid, e)))
+    }
     pub fn disconnect<CB: Callback<A>>(
         &mut self,
         cb: &mut CB,
@@ -444,6 +591,17 @@ impl<A: Address> Net<A> {
         peer.conn
             .send(&mut cc(cb, peer.addr), chunk.data, chunk.vital)
     }
+    pub fn send_with_flags<CB: Callback<A>>(
+        &mut self,
+        cb: &mut CB,
+        pid: PeerId,
+        data: &[u8],
+        flags: SendFlags,
+    ) -> Result<(), Error<CB::Error>> {
+        let peer = &mut self.peers[pid];
+        peer.conn
+            .send_with_flags(&mut cc(cb, peer.addr), data, flags)
+    }
     pub fn flush<CB: Callback<A>>(&mut self, cb: &mut CB, pid: PeerId) -> Result<(), CB::Error> {
         let peer = &mut self.peers[pid];
         peer.conn.flush(&mut cc(cb, peer.addr))
@@ -453,16 +611,21 @@ impl<A: Address> Net<A> {
     }
     pub fn accept<CB: Callback<A>>(&mut self, cb: &mut CB, pid: PeerId) -> Result<(), CB::Error> {
         let peer = &mut self.peers[pid];
-        assert!(peer.conn.is_unconnected());
+        if !peer.conn.is_unconnected() {
+            // Peers using the DDNet token extension have already completed
+            // the handshake when they're reported.
+            return Ok(());
+        }
+        if peer.conn.version() == ProtocolVersion::V7 {
+            return peer.conn.accept_7(&mut cc(cb, peer.addr));
+        }
         let mut buf: ArrayVec<[u8; 2048]> = ArrayVec::new();
-        let connect_packet: &[u8] = if peer.token {
-            CONNECT_PACKET
-        } else {
-            CONNECT_PACKET_NO_TOKEN
-        };
-        let (mut none, res) =
-            peer.conn
-                .feed(&mut cc(cb, peer.addr), &mut Panic, connect_packet, &mut buf);
+        let (mut none, res) = peer.conn.feed(
+            &mut cc(cb, peer.addr),
+            &mut Panic,
+            CONNECT_PACKET_NO_TOKEN,
+            &mut buf,
+        );
         assert!(none.next().is_none());
         res
     }
@@ -475,7 +638,6 @@ impl<A: Address> Net<A> {
         let result;
         {
             let peer = &mut self.peers[pid];
-            assert!(peer.conn.is_unconnected());
             result = peer.conn.disconnect(&mut cc(cb, peer.addr), reason);
         }
         self.peers.remove_peer(pid);
@@ -514,6 +676,11 @@ impl<A: Address> Net<A> {
         CB: Callback<A>,
         W: Warn<Warning<A>>,
     {
+        if let Some(ref mut filter) = self.packet_filter {
+            if !filter.allow(cb.time(), addr, data) {
+                return (ReceivePacket::none(), Ok(()));
+            }
+        }
         if let Some(pid) = self.peers.pid_from_addr(addr) {
             let (packet, e) = self.peers[pid].conn.feed(
                 &mut cc(cb, addr),
@@ -522,6 +689,8 @@ impl<A: Address> Net<A> {
                 &mut buf,
             );
             (ReceivePacket::connected(addr, pid, packet, self), e)
+        } else if protocol7::Packet::is_initial(data) {
+            self.feed_initial_7(cb, warn, addr, data, buf)
         } else {
             let packet = match Packet::read(&mut w(warn, addr), data, None, &mut buf) {
                 Ok(p) => p,
@@ -530,28 +699,258 @@ impl<A: Address> Net<A> {
                     return (ReceivePacket::none(), Ok(()));
                 }
             };
-            if let Packet::Connless(d) = packet {
-                (ReceivePacket::connless(addr, d), Ok(()))
-            } else if let Packet::Connected(ConnectedPacket {
-                token,
-                type_: ConnectedPacketType::Control(ControlPacket::Connect),
-                ..
-            }) = packet
-            {
-                if self.accept_connections {
+            let connected = match packet {
+                Packet::Connless(d) => return (ReceivePacket::connless(addr, d), Ok(())),
+                Packet::Connected(c) => c,
+            };
+            if !self.accept_connections {
+                w(warn, addr).warn(connection::Warning::Unexpected);
+                return (ReceivePacket::none(), Ok(()));
+            }
+            let (own_token, previous_token) = self.token_6(cb, addr);
+            match connected {
+                ConnectedPacket {
+                    token: None,
+                    type_: ConnectedPacketType::Control(ControlPacket::Connect),
+                    ..
+                } => {
+                    // Vanilla clients don't support tokens.
+                    //
                     // TODO: This is vulnerable to IP spoofing.
-                    let (pid, _) = self.peers.new_peer(addr, token.is_some());
+                    if !self.allow_peer(addr) {
+                        return (ReceivePacket::none(), self.reject_6(cb, addr, None));
+                    }
+                    let conn = Connection::with_config(ProtocolVersion::V6, self.config);
+                    let (pid, _) = self.peers.new_peer(conn, addr);
+                    (ReceivePacket::connect(pid), Ok(()))
+                }
+                ConnectedPacket {
+                    token: Some(protocol::TOKEN_NONE),
+                    type_: ConnectedPacketType::Control(ControlPacket::Connect),
+                    ..
+                } => {
+                    // DDNet clients get a token derived from their address,
+                    // the peer is only created once they send it back.
+                    let packet = Packet::Connected(ConnectedPacket {
+                        ack: 0,
+                        token: Some(own_token),
+                        type_: ConnectedPacketType::Control(ControlPacket::ConnectAccept),
+                    });
+                    let result = self
+                        .builder
+                        .send(cb, addr, packet)
+                        .map_err(|e| e.unwrap_callback());
+                    (ReceivePacket::none(), result)
+                }
+                ConnectedPacket {
+                    token: Some(token), ..
+                } if token == own_token || token == previous_token => {
+                    // This is usually the `Accept` control message. If it was
+                    // lost, this can also be a packet containing chunks,
+                    // these are dropped and later resent by the peer.
+                    if !self.allow_peer(addr) {
+                        return (ReceivePacket::none(), self.reject_6(cb, addr, Some(token)));
+                    }
+                    let conn = Connection::new_accept_token(&mut cc(cb, addr), token, self.config);
+                    let (pid, _) = self.peers.new_peer(conn, addr);
                     (ReceivePacket::connect(pid), Ok(()))
-                } else {
+                }
+                ConnectedPacket {
+                    token: Some(token), ..
+                } if self.migrate(addr, token) => (ReceivePacket::none(), Ok(())),
+                ConnectedPacket { token: Some(_), .. } => {
+                    w(warn, addr).warn(connection::Warning::TokenMismatch);
+                    (ReceivePacket::none(), Ok(()))
+                }
+                _ => {
                     w(warn, addr).warn(connection::Warning::Unexpected);
                     (ReceivePacket::none(), Ok(()))
                 }
-            } else {
+            }
+        }
+    }
+    /// Handles 0.7 token requests and connect packets from unknown peers.
+    ///
+    /// Token requests are answered without creating a peer, the token is
+    /// derived from the address instead. This way, only peers that can
+    /// receive packets at their address get to create a peer.
+    fn feed_initial_7<'d, 's, CB, W>(
+        &mut self,
+        cb: &mut CB,
+        warn: &mut W,
+        addr: A,
+        data: &'d [u8],
+        mut buf: BufferRef<'d, 's>,
+    ) -> (ReceivePacket<'d, A>, Result<(), CB::Error>)
+    where
+        CB: Callback<A>,
+        W: Warn<Warning<A>>,
+    {
+        use protocol7::ConnectedPacketType::Control;
+
+        let packet = match protocol7::Packet::read(&mut w(warn, addr), data, &mut buf) {
+            Ok(p) => p,
+            Err(e) => {
+                w(warn, addr).warn(connection::Warning::Read7(e));
+                return (ReceivePacket::none(), Ok(()));
+            }
+        };
+        if !self.accept_connections {
+            w(warn, addr).warn(connection::Warning::Unexpected);
+            return (ReceivePacket::none(), Ok(()));
+        }
+        let (own_token, previous_token) = self.token_7(cb, addr);
+        match packet {
+            protocol7::Packet::Connected(protocol7::ConnectedPacket {
+                token: protocol7::TOKEN_NONE,
+                type_: Control(protocol7::ControlPacket::Token(response_token)),
+                ..
+            }) => {
+                let packet = protocol7::ConnectedPacket {
+                    ack: 0,
+                    token: response_token,
+                    type_: Control(protocol7::ControlPacket::Token(own_token)),
+                };
+                let result = self
+                    .builder
+                    .send_7(cb, addr, packet)
+                    .map_err(|e| e.unwrap_callback());
+                (ReceivePacket::none(), result)
+            }
+            protocol7::Packet::Connected(protocol7::ConnectedPacket {
+                token,
+                type_: Control(protocol7::ControlPacket::Connect(peer_token)),
+                ..
+            }) => {
+                if token != own_token && token != previous_token {
+                    w(warn, addr).warn(connection::Warning::TokenMismatch);
+                    return (ReceivePacket::none(), Ok(()));
+                }
+                if !self.allow_peer(addr) {
+                    let packet = protocol7::ConnectedPacket {
+                        ack: 0,
+                        token: peer_token,
+                        type_: Control(protocol7::ControlPacket::Close(TOO_MANY_CONNECTIONS)),
+                    };
+                    let result = self
+                        .builder
+                        .send_7(cb, addr, packet)
+                        .map_err(|e| e.unwrap_callback());
+                    return (ReceivePacket::none(), result);
+                }
+                let conn = Connection::new_accept_7(token, peer_token, self.config);
+                let (pid, _) = self.peers.new_peer(conn, addr);
+                (ReceivePacket::connect(pid), Ok(()))
+            }
+            _ => {
                 w(warn, addr).warn(connection::Warning::Unexpected);
                 (ReceivePacket::none(), Ok(()))
             }
         }
     }
+    fn allow_peer(&mut self, addr: A) -> bool {
+        match self.accept_filter {
+            Some(ref mut filter) => filter(addr, &mut self.peers.iter().map(|(_, p)| p.addr)),
+            None => true,
+        }
+    }
+    fn reject_6<CB: Callback<A>>(
+        &mut self,
+        cb: &mut CB,
+        addr: A,
+        token: Option<protocol::Token>,
+    ) -> Result<(), CB::Error> {
+        let packet = Packet::Connected(ConnectedPacket {
+            ack: 0,
+            token,
+            type_: ConnectedPacketType::Control(ControlPacket::Close(TOO_MANY_CONNECTIONS)),
+        });
+        self.builder
+            .send(cb, addr, packet)
+            .map_err(|e| e.unwrap_callback())
+    }
+    /// Returns the tokens derived from the current and the previous secret,
+    /// rotating the secrets if necessary.
+    fn address_tokens<CB: Callback<A>>(&mut self, cb: &mut CB, addr: A) -> [[u8; 4]; 2] {
+        let now = cb.time();
+        let mut random = || {
+            let mut secret = [0; 16];
+            cb.secure_random(&mut secret);
+            secret
+        };
+        let rotate = match self.token_secrets {
+            Some(ref s) => s.rotate <= now,
+            None => true,
+        };
+        if rotate {
+            let previous = match self.token_secrets {
+                // Tokens from more than one lifetime ago are invalid.
+                Some(ref s) if now < s.rotate + TOKEN_SECRET_LIFETIME => s.current,
+                _ => random(),
+            };
+            self.token_secrets = Some(TokenSecrets {
+                current: random(),
+                previous,
+                rotate: now + TOKEN_SECRET_LIFETIME,
+            });
+        }
+        let secrets = self.token_secrets.as_ref().unwrap();
+        let token = |secret: &[u8; 16]| {
+            let mut hasher = DefaultHasher::new();
+            secret.hash(&mut hasher);
+            addr.hash(&mut hasher);
+            (hasher.finish() as u32).to_be_bytes()
+        };
+        [token(&secrets.current), token(&secrets.previous)]
+    }
+    /// Moves the online peer with the given token to `addr` if the
+    /// migration filter agrees.
+    fn migrate(&mut self, addr: A, token: protocol::Token) -> bool {
+        let filter = match self.migration_filter {
+            Some(ref mut f) => f,
+            None => return false,
+        };
+        if token == protocol::TOKEN_NONE {
+            return false;
+        }
+        let found = self
+            .peers
+            .iter_mut()
+            .find(|(_, p)| p.conn.token() == Some(token));
+        let (pid, peer) = match found {
+            Some(x) => x,
+            None => return false,
+        };
+        if !filter(pid, peer.addr, addr) {
+            return false;
+        }
+        peer.addr = addr;
+        true
+    }
+    fn token_6<CB: Callback<A>>(
+        &mut self,
+        cb: &mut CB,
+        addr: A,
+    ) -> (protocol::Token, protocol::Token) {
+        let fix = |token| match protocol::Token(token) {
+            protocol::TOKEN_NONE | protocol::TOKEN_RESERVED => protocol::Token([0, 0, 0, 1]),
+            token => token,
+        };
+        let [current, previous] = self.address_tokens(cb, addr);
+        (fix(current), fix(previous))
+    }
+    fn token_7<CB: Callback<A>>(
+        &mut self,
+        cb: &mut CB,
+        addr: A,
+    ) -> (protocol7::Token, protocol7::Token) {
+        let fix = |token| match protocol7::Token(token) {
+            protocol7::TOKEN_NONE => protocol7::Token([0; 4]),
+            token => token,
+        };
+        let [current, previous] = self.address_tokens(cb, addr);
+        (fix(current), fix(previous))
+    }
 }
 
 pub struct Tick<'a, A: Address + 'a, CB: Callback<A> + 'a> {
@@ -574,14 +973,25 @@ impl<'a, A: Address + 'a, CB: Callback<A> + 'a> Iterator for Tick<'a, A, CB> {
 
 #[cfg(test)]
 mod test {
+    use super::max_connections_per_ip;
     use super::Callback;
+    use super::Chunk;
     use super::ChunkOrEvent;
     use super::Net;
+    use super::SendFlags;
+    use super::TOKEN_SECRET_LIFETIME;
+    use super::TOO_MANY_CONNECTIONS;
+    use connection::ProtocolVersion;
     use itertools::Itertools;
     use protocol;
+    use std::cell::RefCell;
     use std::collections::VecDeque;
+    use std::net::SocketAddr;
+    use std::rc::Rc;
+    use std::time::Duration;
     use void::ResultVoidExt;
     use void::Void;
+    use warn::Ignore;
     use warn::Panic;
     use Timestamp;
 
@@ -607,13 +1017,9 @@ mod test {
         impl Callback<Address> for Cb {
             type Error = Void;
             fn secure_random(&mut self, buffer: &mut [u8]) {
-                if buffer.len() != 4 {
-                    unimplemented!();
+                for (i, b) in buffer.iter_mut().enumerate() {
+                    *b = 0x12 + i as u8;
                 }
-                buffer[0] = 0x12;
-                buffer[1] = 0x34;
-                buffer[2] = 0x56;
-                buffer[3] = 0x78;
             }
             fn send(&mut self, addr: Address, data: &[u8]) -> Result<(), Void> {
                 assert!(self.recipient == addr);
@@ -637,7 +1043,214 @@ mod test {
         let packet = cb.packets.pop_front().unwrap();
         assert!(cb.packets.is_empty());
 
-        // ConnectAccept
+        // ConnectAccept, the token is handed out without creating a peer.
+        cb.recipient = Address::Client;
+        assert!(net
+            .feed(cb, &mut Panic, Address::Client, &packet, &mut buffer[..])
+            .0
+            .next()
+            .is_none());
+        let packet = cb.packets.pop_front().unwrap();
+        assert!(cb.packets.is_empty());
+
+        // Accept
+        cb.recipient = Address::Server;
+        assert!(
+            net.feed(cb, &mut Panic, Address::Server, &packet, &mut buffer[..])
+                .0
+                .collect_vec()
+                == &[ChunkOrEvent::Ready(c_pid)]
+        );
+        let packet = cb.packets.pop_front().unwrap();
+        assert!(cb.packets.is_empty());
+
+        cb.recipient = Address::Client;
+        let s_pid;
+        {
+            let p = net
+                .feed(cb, &mut Panic, Address::Client, &packet, &mut buffer[..])
+                .0
+                .collect_vec();
+            assert!(p.len() == 1);
+            if let ChunkOrEvent::Connect(s) = p[0] {
+                s_pid = s;
+            } else {
+                panic!();
+            }
+        }
+        assert!(cb.packets.is_empty());
+
+        // The peer has proven its address, accepting it is a no-op.
+        net.accept(cb, s_pid).void_unwrap();
+        assert!(cb.packets.is_empty());
+
+        // Disconnect
+        cb.recipient = Address::Server;
+        net.disconnect(cb, c_pid, b"foobar").void_unwrap();
+        let packet = cb.packets.pop_front().unwrap();
+        assert!(cb.packets.is_empty());
+
+        cb.recipient = Address::Client;
+        assert!(
+            net.feed(cb, &mut Panic, Address::Client, &packet, &mut buffer[..])
+                .0
+                .collect_vec()
+                == &[ChunkOrEvent::Disconnect(s_pid, b"foobar")]
+        );
+        assert!(cb.packets.is_empty());
+    }
+
+    #[test]
+    fn drain() {
+        #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        enum Address {
+            Client,
+            Server,
+        }
+        struct Cb {
+            packets: VecDeque<Vec<u8>>,
+        }
+        impl Callback<Address> for Cb {
+            type Error = Void;
+            fn secure_random(&mut self, buffer: &mut [u8]) {
+                for (i, b) in buffer.iter_mut().enumerate() {
+                    *b = 0x12 + i as u8;
+                }
+            }
+            fn send(&mut self, _: Address, data: &[u8]) -> Result<(), Void> {
+                self.packets.push_back(data.to_owned());
+                Ok(())
+            }
+            fn time(&mut self) -> Timestamp {
+                Timestamp::from_secs_since_epoch(0)
+            }
+        }
+        let cb = &mut Cb {
+            packets: VecDeque::new(),
+        };
+        let mut buffer = [0; protocol::MAX_PACKETSIZE];
+        let mut net = Net::server();
+
+        let (c_pid, res) = net.connect(cb, Address::Server);
+        res.void_unwrap();
+        let packet = cb.packets.pop_front().unwrap();
+        assert!(net
+            .feed(cb, &mut Panic, Address::Client, &packet, &mut buffer[..])
+            .0
+            .next()
+            .is_none());
+        let packet = cb.packets.pop_front().unwrap();
+        assert!(
+            net.feed(cb, &mut Panic, Address::Server, &packet, &mut buffer[..])
+                .0
+                .collect_vec()
+                == &[ChunkOrEvent::Ready(c_pid)]
+        );
+        let packet = cb.packets.pop_front().unwrap();
+        let s_pid = match net
+            .feed(cb, &mut Panic, Address::Client, &packet, &mut buffer[..])
+            .0
+            .collect_vec()[..]
+        {
+            [ChunkOrEvent::Connect(s)] => s,
+            _ => panic!(),
+        };
+        assert!(cb.packets.is_empty());
+
+        // Shutting down: stop accepting, wait for the vital chunks to be
+        // acked, then disconnect.
+        net.set_accept_connections(false);
+        assert!(net.is_drained(s_pid));
+        let chunk = Chunk {
+            pid: s_pid,
+            vital: true,
+            data: b"\x42",
+        };
+        net.send(cb, chunk).unwrap();
+        net.flush(cb, s_pid).void_unwrap();
+        assert!(!net.is_drained(s_pid));
+        let packet = cb.packets.pop_front().unwrap();
+        assert_eq!(
+            net.feed(cb, &mut Panic, Address::Server, &packet, &mut buffer[..])
+                .0
+                .count(),
+            1
+        );
+        assert!(!net.is_drained(s_pid));
+
+        // The client's next packet acks the chunk.
+        let chunk = Chunk {
+            pid: c_pid,
+            vital: false,
+            data: b"\x43",
+        };
+        net.send(cb, chunk).unwrap();
+        net.flush(cb, c_pid).void_unwrap();
+        let packet = cb.packets.pop_front().unwrap();
+        net.feed(cb, &mut Panic, Address::Client, &packet, &mut buffer[..])
+            .0
+            .for_each(drop);
+        assert!(net.is_drained(s_pid));
+        assert!(!net.is_disconnected(s_pid));
+
+        net.reject(cb, s_pid, b"shutdown").void_unwrap();
+        assert!(net.peers().all(|pid| pid != s_pid));
+        let packet = cb.packets.pop_front().unwrap();
+        assert!(
+            net.feed(cb, &mut Panic, Address::Server, &packet, &mut buffer[..])
+                .0
+                .collect_vec()
+                == &[ChunkOrEvent::Disconnect(c_pid, b"shutdown")]
+        );
+        // All peers are gone, the event loop can stop.
+        assert!(net.peers().next().is_none());
+        assert!(cb.packets.is_empty());
+    }
+
+    #[test]
+    fn establish_connection_vanilla() {
+        #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        enum Address {
+            Client,
+            Server,
+        }
+        struct Cb {
+            packets: VecDeque<Vec<u8>>,
+            recipient: Address,
+        }
+        impl Callback<Address> for Cb {
+            type Error = Void;
+            fn secure_random(&mut self, buffer: &mut [u8]) {
+                for (i, b) in buffer.iter_mut().enumerate() {
+                    *b = 0x12 + i as u8;
+                }
+            }
+            fn send(&mut self, addr: Address, data: &[u8]) -> Result<(), Void> {
+                assert!(self.recipient == addr);
+                self.packets.push_back(data.to_owned());
+                Ok(())
+            }
+            fn time(&mut self) -> Timestamp {
+                Timestamp::from_secs_since_epoch(0)
+            }
+        }
+        let mut cb = Cb {
+            packets: VecDeque::new(),
+            recipient: Address::Server,
+        };
+        let cb = &mut cb;
+        let mut buffer = [0; protocol::MAX_PACKETSIZE];
+
+        let mut net = Net::server();
+
+        // Connect, without the DDNet token extension.
+        let (c_pid, res) = net.connect(cb, Address::Server);
+        res.void_unwrap();
+        let mut packet = cb.packets.pop_front().unwrap();
+        assert!(cb.packets.is_empty());
+        assert!(&packet[4..8] == b"TKEN");
+        packet.truncate(4);
+
         cb.recipient = Address::Client;
         let s_pid;
         {
@@ -655,6 +1268,7 @@ mod test {
         // No packets sent out until we accept the client.
         assert!(cb.packets.is_empty());
 
+        // ConnectAccept
         net.accept(cb, s_pid).void_unwrap();
         let packet = cb.packets.pop_front().unwrap();
         assert!(cb.packets.is_empty());
@@ -677,9 +1291,102 @@ mod test {
             .next()
             .is_none());
         assert!(cb.packets.is_empty());
+    }
 
-        // Disconnect
+    #[test]
+    fn establish_connection_7() {
+        #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        enum Address {
+            Client,
+            Server,
+        }
+        struct Cb {
+            packets: VecDeque<Vec<u8>>,
+            recipient: Address,
+        }
+        impl Callback<Address> for Cb {
+            type Error = Void;
+            fn secure_random(&mut self, buffer: &mut [u8]) {
+                for (i, b) in buffer.iter_mut().enumerate() {
+                    *b = i as u8;
+                }
+            }
+            fn send(&mut self, addr: Address, data: &[u8]) -> Result<(), Void> {
+                assert!(self.recipient == addr);
+                self.packets.push_back(data.to_owned());
+                Ok(())
+            }
+            fn time(&mut self) -> Timestamp {
+                Timestamp::from_secs_since_epoch(0)
+            }
+        }
+        let mut cb = Cb {
+            packets: VecDeque::new(),
+            recipient: Address::Server,
+        };
+        let cb = &mut cb;
+        let mut buffer = [0; protocol::MAX_PACKETSIZE];
+
+        let mut net = Net::server();
+
+        // Token request
+        let (c_pid, res) = net.connect_with_version(cb, Address::Server, ProtocolVersion::V7);
+        res.void_unwrap();
+        let packet = cb.packets.pop_front().unwrap();
+        assert!(cb.packets.is_empty());
+
+        // Token, no peer is created for the request.
+        cb.recipient = Address::Client;
+        assert!(net
+            .feed(cb, &mut Panic, Address::Client, &packet, &mut buffer[..])
+            .0
+            .next()
+            .is_none());
+        let packet = cb.packets.pop_front().unwrap();
+        assert!(cb.packets.is_empty());
+
+        // Connect
         cb.recipient = Address::Server;
+        assert!(net
+            .feed(cb, &mut Panic, Address::Server, &packet, &mut buffer[..])
+            .0
+            .next()
+            .is_none());
+        let packet = cb.packets.pop_front().unwrap();
+        assert!(cb.packets.is_empty());
+
+        cb.recipient = Address::Client;
+        let s_pid;
+        {
+            let p = net
+                .feed(cb, &mut Panic, Address::Client, &packet, &mut buffer[..])
+                .0
+                .collect_vec();
+            assert!(p.len() == 1);
+            if let ChunkOrEvent::Connect(s) = p[0] {
+                s_pid = s;
+            } else {
+                panic!();
+            }
+        }
+        assert!(cb.packets.is_empty());
+        assert!(net.version(s_pid) == ProtocolVersion::V7);
+
+        // Accept
+        net.accept(cb, s_pid).void_unwrap();
+        let packet = cb.packets.pop_front().unwrap();
+        assert!(cb.packets.is_empty());
+
+        cb.recipient = Address::Server;
+        assert!(
+            net.feed(cb, &mut Panic, Address::Server, &packet, &mut buffer[..])
+                .0
+                .collect_vec()
+                == &[ChunkOrEvent::Ready(c_pid)]
+        );
+        assert!(cb.packets.is_empty());
+
+        // Disconnect
         net.disconnect(cb, c_pid, b"foobar").void_unwrap();
         let packet = cb.packets.pop_front().unwrap();
         assert!(cb.packets.is_empty());
@@ -693,4 +1400,237 @@ mod test {
         );
         assert!(cb.packets.is_empty());
     }
+
+    #[test]
+    fn accept_filter() {
+        #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        enum Address {
+            Client,
+            Server,
+        }
+        struct Cb {
+            packets: VecDeque<Vec<u8>>,
+            recipient: Address,
+        }
+        impl Callback<Address> for Cb {
+            type Error = Void;
+            fn secure_random(&mut self, buffer: &mut [u8]) {
+                for (i, b) in buffer.iter_mut().enumerate() {
+                    *b = i as u8;
+                }
+            }
+            fn send(&mut self, addr: Address, data: &[u8]) -> Result<(), Void> {
+                assert!(self.recipient == addr);
+                self.packets.push_back(data.to_owned());
+                Ok(())
+            }
+            fn time(&mut self) -> Timestamp {
+                Timestamp::from_secs_since_epoch(0)
+            }
+        }
+        let mut cb = Cb {
+            packets: VecDeque::new(),
+            recipient: Address::Server,
+        };
+        let cb = &mut cb;
+        let mut buffer = [0; protocol::MAX_PACKETSIZE];
+
+        let mut net = Net::server();
+        net.set_accept_filter(Some(Box::new(|addr, peers| {
+            // Only the client's peer exists, the test uses the same `Net`
+            // for both sides.
+            assert!(addr == Address::Client);
+            assert!(peers.collect_vec() == &[Address::Server]);
+            false
+        })));
+
+        let (c_pid, res) = net.connect(cb, Address::Server);
+        res.void_unwrap();
+        let packet = cb.packets.pop_front().unwrap();
+
+        cb.recipient = Address::Client;
+        assert!(net
+            .feed(cb, &mut Panic, Address::Client, &packet, &mut buffer[..])
+            .0
+            .next()
+            .is_none());
+        let packet = cb.packets.pop_front().unwrap();
+
+        cb.recipient = Address::Server;
+        assert!(
+            net.feed(cb, &mut Panic, Address::Server, &packet, &mut buffer[..])
+                .0
+                .collect_vec()
+                == &[ChunkOrEvent::Ready(c_pid)]
+        );
+        let packet = cb.packets.pop_front().unwrap();
+
+        // The peer is rejected once it has proven its address.
+        cb.recipient = Address::Client;
+        assert!(net
+            .feed(cb, &mut Panic, Address::Client, &packet, &mut buffer[..])
+            .0
+            .next()
+            .is_none());
+        let packet = cb.packets.pop_front().unwrap();
+        assert!(cb.packets.is_empty());
+
+        assert!(
+            net.feed(cb, &mut Panic, Address::Server, &packet, &mut buffer[..])
+                .0
+                .collect_vec()
+                == &[ChunkOrEvent::Disconnect(c_pid, TOO_MANY_CONNECTIONS)]
+        );
+    }
+
+    #[test]
+    fn connections_per_ip() {
+        let a: SocketAddr = "192.0.2.1:8303".parse().unwrap();
+        let b: SocketAddr = "192.0.2.1:8304".parse().unwrap();
+        let c: SocketAddr = "192.0.2.2:8303".parse().unwrap();
+        let mut filter = max_connections_per_ip(2);
+        assert!(filter(a, &mut vec![c, c].into_iter()));
+        assert!(filter(a, &mut vec![b].into_iter()));
+        assert!(!filter(a, &mut vec![b, c, a].into_iter()));
+    }
+
+    #[test]
+    fn packet_filter() {
+        struct Cb;
+        impl Callback<u8> for Cb {
+            type Error = Void;
+            fn secure_random(&mut self, _: &mut [u8]) {
+                unreachable!();
+            }
+            fn send(&mut self, _: u8, _: &[u8]) -> Result<(), Void> {
+                unreachable!();
+            }
+            fn time(&mut self) -> Timestamp {
+                Timestamp::from_secs_since_epoch(0)
+            }
+        }
+        let mut buffer = [0; protocol::MAX_PACKETSIZE];
+        let mut net = Net::server();
+        net.set_packet_filter(Some(Box::new(|_, addr, _: &[u8]| addr != 2)));
+        let packet = b"\xff\xff\xff\xff\xff\xffhello";
+        for &(addr, allowed) in &[(1, true), (2, false)] {
+            let (mut chunks, res) = net.feed(&mut Cb, &mut Panic, addr, packet, &mut buffer[..]);
+            res.void_unwrap();
+            assert_eq!(chunks.next().is_some(), allowed);
+        }
+    }
+
+    #[test]
+    fn migration() {
+        struct Cb(VecDeque<(u8, Vec<u8>)>);
+        impl Callback<u8> for Cb {
+            type Error = Void;
+            fn secure_random(&mut self, buffer: &mut [u8]) {
+                for b in buffer {
+                    *b = 0x12;
+                }
+            }
+            fn send(&mut self, addr: u8, data: &[u8]) -> Result<(), Void> {
+                self.0.push_back((addr, data.to_owned()));
+                Ok(())
+            }
+            fn time(&mut self) -> Timestamp {
+                Timestamp::from_secs_since_epoch(0)
+            }
+        }
+        const CLIENT: u8 = 1;
+        const SERVER: u8 = 2;
+        const REBOUND: u8 = 3;
+        let cb = &mut Cb(VecDeque::new());
+        let mut buffer = [0; protocol::MAX_PACKETSIZE];
+        let mut client = Net::client();
+        let mut server = Net::server();
+
+        let (c_pid, res) = client.connect(cb, SERVER);
+        res.void_unwrap();
+        let (_, packet) = cb.0.pop_front().unwrap();
+        let (mut p, res) = server.feed(cb, &mut Panic, CLIENT, &packet, &mut buffer[..]);
+        res.void_unwrap();
+        assert!(p.next().is_none());
+        let (_, packet) = cb.0.pop_front().unwrap();
+        let (p, res) = client.feed(cb, &mut Panic, SERVER, &packet, &mut buffer[..]);
+        res.void_unwrap();
+        assert!(p.collect_vec() == [ChunkOrEvent::Ready(c_pid)]);
+        let (_, packet) = cb.0.pop_front().unwrap();
+        let (p, res) = server.feed(cb, &mut Panic, CLIENT, &packet, &mut buffer[..]);
+        res.void_unwrap();
+        let s_pid = match p.collect_vec()[..] {
+            [ChunkOrEvent::Connect(pid)] => pid,
+            _ => panic!(),
+        };
+
+        let flags = SendFlags {
+            vital: true,
+            flush: true,
+        };
+        client.send_with_flags(cb, c_pid, b"hello", flags).unwrap();
+        let (_, packet) = cb.0.pop_front().unwrap();
+
+        // Migration is disabled by default.
+        let (mut p, res) = server.feed(cb, &mut Ignore, REBOUND, &packet, &mut buffer[..]);
+        res.void_unwrap();
+        assert!(p.next().is_none());
+
+        let migrations = Rc::new(RefCell::new(Vec::new()));
+        let m = migrations.clone();
+        server.set_migration_filter(Some(Box::new(move |pid, from, to| {
+            m.borrow_mut().push((pid, from, to));
+            true
+        })));
+        let (mut p, res) = server.feed(cb, &mut Panic, REBOUND, &packet, &mut buffer[..]);
+        res.void_unwrap();
+        assert!(p.next().is_none());
+        assert!(*migrations.borrow() == [(s_pid, CLIENT, REBOUND)]);
+
+        let (p, res) = server.feed(cb, &mut Panic, REBOUND, &packet, &mut buffer[..]);
+        res.void_unwrap();
+        assert!(
+            p.collect_vec()
+                == [ChunkOrEvent::Chunk(Chunk {
+                    pid: s_pid,
+                    vital: true,
+                    data: b"hello",
+                })]
+        );
+        assert!(cb.0.is_empty());
+        server.send_with_flags(cb, s_pid, b"world", flags).unwrap();
+        assert!(cb.0.pop_front().unwrap().0 == REBOUND);
+    }
+
+    #[test]
+    fn token_rotation() {
+        struct Cb(u8, Duration);
+        impl Callback<()> for Cb {
+            type Error = Void;
+            fn secure_random(&mut self, buffer: &mut [u8]) {
+                self.0 += 1;
+                for b in buffer {
+                    *b = self.0;
+                }
+            }
+            fn send(&mut self, _: (), _: &[u8]) -> Result<(), Void> {
+                unreachable!();
+            }
+            fn time(&mut self) -> Timestamp {
+                Timestamp::from_secs_since_epoch(0) + self.1
+            }
+        }
+        let mut cb = Cb(0, Duration::from_secs(0));
+        let mut net = Net::server();
+        let (first, _) = net.token_6(&mut cb, ());
+        assert!(net.token_6(&mut cb, ()).0 == first);
+
+        cb.1 = TOKEN_SECRET_LIFETIME;
+        let (second, previous) = net.token_6(&mut cb, ());
+        assert!(second != first && previous == first);
+
+        cb.1 = TOKEN_SECRET_LIFETIME * 4;
+        let (third, previous) = net.token_6(&mut cb, ());
+        assert!(third != second && previous != second && previous != first);
+    }
 }
@@ -18,6 +18,8 @@ pub const CHUNK_HEADER_SIZE: usize = 2;
 pub const CHUNK_HEADER_SIZE_VITAL: usize = 3;
 pub const HEADER_SIZE: usize = 3;
 pub const MAX_PACKETSIZE: usize = 1400;
+/// The largest UDP payload that doesn't get fragmented on any IPv4 link.
+pub const MIN_PACKETSIZE: usize = 508;
 pub const PADDING_SIZE_CONNLESS: usize = 3;
 pub const TOKEN_SIZE: usize = 4;
 
@@ -46,6 +48,8 @@ pub const TOKEN_RESERVED: Token = Token([0x00, 0x00, 0x00, 0x00]);
 
 pub const CTRLMSG_CLOSE_REASON_LENGTH: usize = 127;
 pub const CTRLMSG_TOKEN_MAGIC: &[u8; 4] = b"TKEN";
+pub const CTRLMSG_MTU_PROBE_MAGIC: &[u8; 4] = b"MTUP";
+pub const CTRLMSG_MTU_REPLY_MAGIC: &[u8; 4] = b"MTUR";
 pub const CHUNK_FLAGS_BITS: u32 = 2;
 pub const CHUNK_SIZE_BITS: u32 = 10;
 pub const PACKET_FLAGS_BITS: u32 = 4;
@@ -106,6 +110,13 @@ pub enum ControlPacket<'a> {
     ConnectAccept,
     Accept,
     Close(&'a [u8]),
+    /// A keepalive padded to the given packet size, answered with a
+    /// `MtuReply` by peers supporting MTU discovery.
+    ///
+    /// Other peers ignore the padding.
+    MtuProbe(usize),
+    /// A keepalive carrying the size of a received `MtuProbe`.
+    MtuReply(u16),
 }
 
 impl<'a> fmt::Debug for ControlPacket<'a> {
@@ -119,6 +130,8 @@ impl<'a> fmt::Debug for ControlPacket<'a> {
                 .debug_tuple("Close")
                 .field(&pretty::AlmostString::new(reason))
                 .finish(),
+            ControlPacket::MtuProbe(size) => f.debug_tuple("MtuProbe").field(&size).finish(),
+            ControlPacket::MtuReply(size) => f.debug_tuple("MtuReply").field(&size).finish(),
         }
     }
 }
@@ -343,6 +356,7 @@ fn has_token_heuristic(control: bool, num_chunks: u8, payload: &[u8]) -> bool {
                 }
                 1 + nul + 1
             }
+            CTRLMSG_KEEPALIVE if payload.starts_with(CTRLMSG_MTU_REPLY_MAGIC) => 1 + 4 + 2,
             _ => 1,
         }
     } else {
@@ -506,6 +520,13 @@ impl<'a> Packet<'a> {
                     }
                 }
                 CTRLMSG_CLOSE => {} // handled later
+                CTRLMSG_KEEPALIVE => {
+                    let mtu = payload.starts_with(CTRLMSG_MTU_PROBE_MAGIC)
+                        || payload.len() == 6 && payload.starts_with(CTRLMSG_MTU_REPLY_MAGIC);
+                    if !mtu && !payload.is_empty() {
+                        warn.warn(Warning::ControlExcessData);
+                    }
+                }
                 _ => {
                     if payload.len() != 0 {
                         warn.warn(Warning::ControlExcessData);
@@ -513,7 +534,15 @@ impl<'a> Packet<'a> {
                 }
             }
             let control = match control {
-                CTRLMSG_KEEPALIVE => ControlPacket::KeepAlive,
+                CTRLMSG_KEEPALIVE => {
+                    if payload.starts_with(CTRLMSG_MTU_PROBE_MAGIC) {
+                        ControlPacket::MtuProbe(bytes.len())
+                    } else if payload.len() == 6 && payload.starts_with(CTRLMSG_MTU_REPLY_MAGIC) {
+                        ControlPacket::MtuReply(u16::from_be_bytes([payload[4], payload[5]]))
+                    } else {
+                        ControlPacket::KeepAlive
+                    }
+                }
                 CTRLMSG_CONNECT => ControlPacket::Connect,
                 CTRLMSG_CONNECTACCEPT => ControlPacket::ConnectAccept,
                 CTRLMSG_ACCEPT => ControlPacket::Accept,
@@ -682,6 +711,7 @@ impl<'a> ControlPacket<'a> {
             ControlPacket::ConnectAccept => CTRLMSG_CONNECTACCEPT,
             ControlPacket::Accept => CTRLMSG_ACCEPT,
             ControlPacket::Close(..) => CTRLMSG_CLOSE,
+            ControlPacket::MtuProbe(..) | ControlPacket::MtuReply(..) => CTRLMSG_KEEPALIVE,
         };
         buffer.write(&[magic])?;
         if matches!(*self, ControlPacket::Connect | ControlPacket::ConnectAccept) && token.is_some()
@@ -694,6 +724,17 @@ impl<'a> ControlPacket<'a> {
                 buffer.write(m)?;
                 buffer.write(&[0])?;
             }
+            ControlPacket::MtuProbe(size) => {
+                assert!(size <= MAX_PACKETSIZE);
+                buffer.write(CTRLMSG_MTU_PROBE_MAGIC)?;
+                let token_size = if token.is_some() { TOKEN_SIZE } else { 0 };
+                let written = HEADER_SIZE + 1 + CTRLMSG_MTU_PROBE_MAGIC.len() + token_size;
+                buffer.write(&[0; MAX_PACKETSIZE][..size.saturating_sub(written)])?;
+            }
+            ControlPacket::MtuReply(size) => {
+                buffer.write(CTRLMSG_MTU_REPLY_MAGIC)?;
+                buffer.write(&size.to_be_bytes())?;
+            }
             _ => {}
         }
         if let Some(token) = token {
@@ -907,10 +948,12 @@ mod test {
     use super::ChunksIter;
     use super::ConnectedPacket;
     use super::ConnectedPacketType;
+    use super::ControlPacket;
     use super::Packet;
     use super::PacketHeader;
     use super::PacketHeaderPacked;
     use super::PacketReadError;
+    use super::Token;
     use super::Warning;
     use super::CHUNK_FLAGS_BITS;
     use super::CHUNK_SIZE_BITS;
@@ -1032,6 +1075,29 @@ mod test {
             true
         }
     }
+
+    #[test]
+    fn mtu_probe() {
+        let mut buffer = [0; MAX_PACKETSIZE];
+        for &token in &[None, Some(Token([0x12, 0x34, 0x56, 0x78]))] {
+            let packet = ConnectedPacket {
+                token,
+                ack: 0,
+                type_: ConnectedPacketType::Control(ControlPacket::MtuProbe(600)),
+            };
+            let data = packet.write(&mut buffer[..]).unwrap().to_vec();
+            assert_eq!(data.len(), 600);
+            let mut buffer = [0; MAX_PACKETSIZE];
+            let packet = Packet::read(&mut Panic, &data, Some(token.is_some()), &mut buffer[..]);
+            assert_matches!(
+                packet,
+                Ok(Packet::Connected(ConnectedPacket {
+                    type_: ConnectedPacketType::Control(ControlPacket::MtuProbe(600)),
+                    ..
+                }))
+            );
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1078,6 +1144,7 @@ mod test_no_token {
     #[test] fn w_cnt1() { assert_warn(b"\x10\x00\x00\x04\x01", ControlNulTermination) }
     #[test] fn w_cnt2() { assert_no_warn(b"\x10\x00\x00\x04") }
     #[test] fn w_cnc() { assert_warn(b"\x10\x00\xff\x00", ControlNumChunks) }
+    #[test] fn w_mtu() { assert_no_warn(b"\x10\x00\x00\x00MTUR\x05\x78") }
     #[test] fn w_php1() { assert_warn(b"\x08\x00\x00", PacketHeaderPadding) }
     #[test] fn w_php2() { assert_warn(b"\x04\x00\x00", PacketHeaderPadding) }
 
@@ -1155,6 +1222,8 @@ mod test_token {
     #[test] fn w_cnt1() { assert_warn(b"\x10\x00\x00\x04\x01\x12\x34\x56\x78", ControlNulTermination) }
     #[test] fn w_cnt2() { assert_no_warn_no_heur(b"\x10\x00\x00\x04\x12\x34\x56\x78") }
     #[test] fn w_cnc() { assert_warn(b"\x10\x00\xff\x00\x12\x34\x56\x78", ControlNumChunks) }
+    #[test] fn w_mtu1() { assert_no_warn_no_heur(b"\x10\x00\x00\x00MTUP\x00\x00\x12\x34\x56\x78") }
+    #[test] fn w_mtu2() { assert_no_warn(b"\x10\x00\x00\x00MTUR\x05\x78\x12\x34\x56\x78") }
     #[test] fn w_php1() { assert_warn(b"\x08\x00\x00\x12\x34\x56\x78", PacketHeaderPadding) }
     #[test] fn w_php2() { assert_warn(b"\x04\x00\x00\x12\x34\x56\x78", PacketHeaderPadding) }
 
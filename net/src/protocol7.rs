@@ -1,3 +1,5 @@
+use arrayvec::ArrayVec;
+use buffer;
 use buffer::with_buffer;
 use buffer::Buffer;
 use buffer::BufferRef;
@@ -5,6 +7,9 @@ use common::num::Cast;
 use common::pretty;
 use huffman;
 use huffman::instances::TEEWORLDS as HUFFMAN;
+use packer::bits::BitReader;
+use packer::bits::BitWriter;
+use protocol::Error;
 use std::cmp;
 use std::fmt;
 use warn::Ignore;
@@ -124,7 +129,37 @@ impl fmt::Display for Token {
     }
 }
 
+impl Token {
+    pub fn random<F: FnMut(&mut [u8])>(mut f: F) -> Token {
+        loop {
+            let mut token = TOKEN_NONE;
+            f(&mut token.0);
+            if token != TOKEN_NONE {
+                return token;
+            }
+        }
+    }
+}
+
 impl<'a> Packet<'a> {
+    /// Checks whether the packet is a token request or a connect packet,
+    /// i.e. a packet that can be sent by a peer without a connection.
+    pub fn is_initial(packet: &[u8]) -> bool {
+        if packet.len() > MAX_PACKETSIZE {
+            return false;
+        }
+        let (header, payload) =
+            unwrap_or_return!(PacketHeaderPacked::from_byte_slice(packet), false);
+        // Unlike in 0.6, the padding comes first. This is what distinguishes
+        // the two packet formats.
+        if packet[0] & 0b1100_0000 != 0 {
+            return false;
+        }
+        let header = header.unpack_warn(&mut Ignore);
+        let ctrl = payload.first().copied();
+        header.flags & !PACKETFLAG_REQUEST_RESEND == PACKETFLAG_CONTROL
+            && (ctrl == Some(CTRLMSG_TOKEN) || ctrl == Some(CTRLMSG_CONNECT))
+    }
     fn needs_decompression(packet: &[u8]) -> bool {
         if packet.len() > MAX_PACKETSIZE {
             return false;
@@ -286,6 +321,7 @@ impl<'a> Packet<'a> {
 
         Ok(Packet::Connected(ConnectedPacket {
             ack: ack,
+            token: header.token,
             type_: type_,
         }))
     }
@@ -339,9 +375,173 @@ impl<'a> Packet<'a> {
     }
 }
 
+impl<'a> ConnectedPacket<'a> {
+    pub fn write<'b, B: Buffer<'b>>(&self, buffer: B) -> Result<&'b [u8], Error> {
+        with_buffer(buffer, |b| self.write_impl(b))
+    }
+
+    fn write_impl<'d, 's>(&self, mut buffer: BufferRef<'d, 's>) -> Result<&'d [u8], Error> {
+        match self.type_ {
+            ConnectedPacketType::Chunks(request_resend, num_chunks, payload) => {
+                let mut compression_buffer: ArrayVec<[u8; 2048]> = ArrayVec::new();
+                let mut compression = 0;
+                let comp_result = HUFFMAN.compress(payload, &mut compression_buffer);
+                if comp_result
+                    .map(|s| s.len() < payload.len())
+                    .unwrap_or(false)
+                {
+                    compression = PACKETFLAG_COMPRESSION;
+                }
+                let request_resend = if request_resend {
+                    PACKETFLAG_REQUEST_RESEND
+                } else {
+                    0
+                };
+                buffer.write(
+                    PacketHeader {
+                        flags: request_resend | compression,
+                        ack: self.ack,
+                        num_chunks,
+                        token: self.token,
+                    }
+                    .pack()
+                    .as_bytes(),
+                )?;
+                buffer.write(if compression != 0 {
+                    &compression_buffer
+                } else {
+                    payload
+                })?;
+                Ok(buffer.initialized())
+            }
+            ConnectedPacketType::Control(c) => c.write(self.token, self.ack, buffer),
+        }
+    }
+}
+
+impl<'a> ControlPacket<'a> {
+    fn write<'d, 's>(
+        &self,
+        token: Token,
+        ack: u16,
+        mut buffer: BufferRef<'d, 's>,
+    ) -> Result<&'d [u8], Error> {
+        buffer.write(
+            PacketHeader {
+                flags: PACKETFLAG_CONTROL,
+                ack,
+                num_chunks: 0,
+                token,
+            }
+            .pack()
+            .as_bytes(),
+        )?;
+        match *self {
+            ControlPacket::KeepAlive => buffer.write(&[CTRLMSG_KEEPALIVE])?,
+            ControlPacket::Connect(rt) => {
+                buffer.write(&[CTRLMSG_CONNECT])?;
+                buffer.write(&rt.0)?;
+            }
+            ControlPacket::Accept => buffer.write(&[CTRLMSG_ACCEPT])?,
+            ControlPacket::Close(m) => {
+                assert!(m.iter().all(|&b| b != 0));
+                buffer.write(&[CTRLMSG_CLOSE])?;
+                buffer.write(m)?;
+                buffer.write(&[0])?;
+            }
+            ControlPacket::Token(rt) => {
+                buffer.write(&[CTRLMSG_TOKEN])?;
+                buffer.write(&rt.0)?;
+                // Token requests are padded so that the response isn't
+                // larger than the request, preventing amplification attacks.
+                if token == TOKEN_NONE {
+                    let padding = TOKEN_REQUEST_PACKET_SIZE - HEADER_SIZE - 1 - rt.0.len();
+                    buffer.write(&[0; TOKEN_REQUEST_PACKET_SIZE][..padding])?;
+                }
+            }
+        }
+        let result = buffer.initialized();
+        assert!(result.len() <= MAX_PACKETSIZE);
+        Ok(result)
+    }
+}
+
+// TODO: Make this a member function of `Chunk`
+// vital: Some((sequence, resend))
+pub fn write_chunk<'a, B: Buffer<'a>>(
+    bytes: &[u8],
+    vital: Option<(u16, bool)>,
+    buffer: B,
+) -> Result<&'a [u8], buffer::CapacityError> {
+    with_buffer(buffer, |b| write_chunk_impl(bytes, vital, b))
+}
+
+pub fn write_chunk_impl<'d, 's>(
+    bytes: &[u8],
+    vital: Option<(u16, bool)>,
+    mut buffer: BufferRef<'d, 's>,
+) -> Result<&'d [u8], buffer::CapacityError> {
+    assert!(bytes.len() >> CHUNK_SIZE_BITS == 0);
+    let size = bytes.len().assert_u16();
+
+    let (sequence, resend) = vital.unwrap_or((0, false));
+    let resend_flag = if resend { CHUNKFLAG_RESEND } else { 0 };
+    let vital_flag = if vital.is_some() { CHUNKFLAG_VITAL } else { 0 };
+    let header = ChunkHeader {
+        flags: vital_flag | resend_flag,
+        size,
+    };
+    if vital.is_some() {
+        let header = ChunkHeaderVital {
+            h: header,
+            sequence,
+        };
+        buffer.write(header.pack().as_bytes())?;
+    } else {
+        buffer.write(header.pack().as_bytes())?;
+    }
+    buffer.write(bytes)?;
+    Ok(buffer.initialized())
+}
+
+/// Writes a connectionless packet.
+///
+/// `token` is the token of the receiver, `response_token` the one the
+/// receiver should use in its reply.
+pub fn write_connless_packet<'a, B: Buffer<'a>>(
+    token: Token,
+    response_token: Token,
+    bytes: &[u8],
+    buffer: B,
+) -> Result<&'a [u8], Error> {
+    fn inner<'d, 's>(
+        token: Token,
+        response_token: Token,
+        bytes: &[u8],
+        mut buffer: BufferRef<'d, 's>,
+    ) -> Result<&'d [u8], Error> {
+        if bytes.len() > MAX_PACKETSIZE - HEADER_SIZE_CONNLESS {
+            return Err(Error::TooLongData);
+        }
+        let header = PacketHeaderConnless {
+            flags: PACKETFLAG_CONNLESS,
+            version: CONNLESS_VERSION,
+            token,
+            response_token,
+        };
+        buffer.write(header.pack().as_bytes())?;
+        buffer.write(bytes)?;
+        Ok(buffer.initialized())
+    }
+
+    with_buffer(buffer, |b| inner(token, response_token, bytes, b))
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct ConnectedPacket<'a> {
     pub ack: u16, // u10
+    /// Token of the receiver of the packet.
+    pub token: Token,
     pub type_: ConnectedPacketType<'a>,
 }
 
@@ -474,12 +674,14 @@ impl PacketHeaderPacked {
             num_chunks,
             token,
         } = self;
-        if padding_flags_ack & 0b1100_0000 != 0 {
+        let bytes = [padding_flags_ack, ack];
+        let mut bits = BitReader::new(&bytes);
+        if bits.read_bits(2).unwrap() != 0 {
             warn.warn(Warning::PacketHeaderPadding);
         }
         PacketHeader {
-            flags: (padding_flags_ack & 0b0011_1100) >> 2,
-            ack: (((padding_flags_ack & 0b0000_0011) as u16) << 8) | (ack as u16),
+            flags: bits.read_bits(PACKET_FLAGS_BITS).unwrap().assert_u8(),
+            ack: bits.read_bits(SEQUENCE_BITS).unwrap().assert_u16(),
             num_chunks,
             token: Token(token),
         }
@@ -500,9 +702,16 @@ impl PacketHeader {
         // Check that the fields do not exceed their maximal size.
         assert!(flags >> PACKET_FLAGS_BITS == 0);
         assert!(ack >> SEQUENCE_BITS == 0);
+        let mut bytes = [0; 2];
+        {
+            let mut bits = BitWriter::new(&mut bytes);
+            bits.write_bits(0, 2).unwrap();
+            bits.write_bits(flags.u32(), PACKET_FLAGS_BITS).unwrap();
+            bits.write_bits(ack.u32(), SEQUENCE_BITS).unwrap();
+        }
         PacketHeaderPacked {
-            padding_flags_ack: flags << 2 | (ack >> 8) as u8,
-            ack: ack as u8,
+            padding_flags_ack: bytes[0],
+            ack: bytes[1],
             num_chunks,
             token: token.0,
         }
@@ -516,12 +725,14 @@ impl PacketHeaderConnlessPacked {
             token,
             response_token,
         } = self;
-        if padding_flags_version & 0b1100_0000 != 0 {
+        let bytes = [padding_flags_version];
+        let mut bits = BitReader::new(&bytes);
+        if bits.read_bits(2).unwrap() != 0 {
             warn.warn(Warning::PacketHeaderPadding);
         }
         PacketHeaderConnless {
-            flags: (padding_flags_version & 0b0011_1100) >> 2,
-            version: padding_flags_version & 0b0000_0011,
+            flags: bits.read_bits(PACKET_FLAGS_BITS).unwrap().assert_u8(),
+            version: bits.read_bits(VERSION_BITS).unwrap().assert_u8(),
             token: Token(token),
             response_token: Token(response_token),
         }
@@ -542,8 +753,15 @@ impl PacketHeaderConnless {
         // Check that the fields do not exceed their maximal size.
         assert!(flags >> PACKET_FLAGS_BITS == 0);
         assert!(version >> VERSION_BITS == 0);
+        let mut bytes = [0; 1];
+        {
+            let mut bits = BitWriter::new(&mut bytes);
+            bits.write_bits(0, 2).unwrap();
+            bits.write_bits(flags.u32(), PACKET_FLAGS_BITS).unwrap();
+            bits.write_bits(version.u32(), VERSION_BITS).unwrap();
+        }
         PacketHeaderConnlessPacked {
-            padding_flags_version: flags << 2 | version as u8,
+            padding_flags_version: bytes[0],
             token: token.0,
             response_token: response_token.0,
         }
@@ -606,13 +824,17 @@ impl ChunkHeaderPacked {
             flags_size,
             padding_size,
         } = self;
-        if padding_size & 0b1111_0000 != 0 {
+        let bytes = [flags_size, padding_size];
+        let mut bits = BitReader::new(&bytes);
+        let flags = bits.read_bits(CHUNK_FLAGS_BITS).unwrap().assert_u8();
+        let size_high = bits.read_bits(6).unwrap();
+        if bits.read_bits(2).unwrap() != 0 {
             warn.warn(Warning::ChunkHeaderPadding);
         }
+        let size_low = bits.read_bits(6).unwrap();
         ChunkHeader {
-            flags: (flags_size & 0b1100_0000) >> 6,
-            size: ((((flags_size & 0b0011_1111) as u16) << 6)
-                | (padding_size & 0b0011_1111) as u16),
+            flags: flags,
+            size: (size_high << 6 | size_low).assert_u16(),
         }
     }
     pub fn unpack(self) -> ChunkHeader {
@@ -626,9 +848,17 @@ impl ChunkHeader {
         // Check that the fields do not exceed their maximal size.
         assert!(flags >> CHUNK_FLAGS_BITS == 0);
         assert!(size >> CHUNK_SIZE_BITS == 0);
+        let mut bytes = [0; 2];
+        {
+            let mut bits = BitWriter::new(&mut bytes);
+            bits.write_bits(flags.u32(), CHUNK_FLAGS_BITS).unwrap();
+            bits.write_bits(size.u32() >> 6, 6).unwrap();
+            bits.write_bits(0, 2).unwrap();
+            bits.write_bits(size.u32() & 0b11_1111, 6).unwrap();
+        }
         ChunkHeaderPacked {
-            flags_size: (flags & 0b11) << 6 | ((size & 0b1111_1100_0000) >> 6) as u8,
-            padding_size: (size & 0b0000_0011_1111) as u8,
+            flags_size: bytes[0],
+            padding_size: bytes[1],
         }
     }
 }
@@ -640,14 +870,18 @@ impl ChunkHeaderVitalPacked {
             sequence_size,
             sequence,
         } = self;
+        let bytes = [sequence_size, sequence];
+        let mut bits = BitReader::new(&bytes);
+        let sequence_high = bits.read_bits(2).unwrap();
+        let size_low = bits.read_bits(6).unwrap().assert_u8();
+        let sequence_low = bits.read_bits(8).unwrap();
         ChunkHeaderVital {
             h: ChunkHeaderPacked {
                 flags_size: flags_size,
-                padding_size: sequence_size & 0b0011_1111,
+                padding_size: size_low,
             }
             .unpack_warn(warn),
-            sequence: ((sequence_size & 0b1100_0000) as u16) << 2
-                | ((sequence & 0b1111_1111) as u16),
+            sequence: (sequence_high << 8 | sequence_low).assert_u16(),
         }
     }
     pub fn unpack(self) -> ChunkHeaderVital {
@@ -663,10 +897,19 @@ impl ChunkHeaderVital {
             flags_size,
             padding_size,
         } = h.pack();
+        let mut bytes = [0; 2];
+        {
+            // The padding bits of the non-vital header hold the high bits of
+            // the sequence number.
+            let mut bits = BitWriter::new(&mut bytes);
+            bits.write_bits(sequence.u32() >> 8, 2).unwrap();
+            bits.write_bits(padding_size.u32(), 6).unwrap();
+            bits.write_bits(sequence.u32() & 0xff, 8).unwrap();
+        }
         ChunkHeaderVitalPacked {
             flags_size: flags_size,
-            sequence_size: (padding_size & 0b0011_1111) | ((sequence & 0b11_0000_0000) >> 2) as u8,
-            sequence: (sequence & 0b00_1111_1111) as u8,
+            sequence_size: bytes[0],
+            sequence: bytes[1],
         }
     }
 }
@@ -0,0 +1,342 @@
+//! Simulated network conditions for testing the connection state machines.
+//!
+//! `LossyPipe` connects two `Connection`s in-process. Packets between them
+//! can be lost, duplicated, reordered and delayed, driven by a seeded random
+//! number generator so that failures are reproducible.
+
+use connection::Callback;
+use connection::ReceiveChunk;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::time::Duration;
+use void::ResultVoidExt;
+use void::Void;
+use warn::Ignore;
+use Connection;
+use Timestamp;
+
+/// One end of a `LossyPipe`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Side {
+    A,
+    B,
+}
+
+impl Side {
+    pub fn other(self) -> Side {
+        match self {
+            Side::A => Side::B,
+            Side::B => Side::A,
+        }
+    }
+    fn index(self) -> usize {
+        match self {
+            Side::A => 0,
+            Side::B => 1,
+        }
+    }
+}
+
+/// How packets are mistreated on their way, applied to both directions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Conditions {
+    /// Probability of a packet getting lost.
+    pub loss: f64,
+    /// Probability of a packet arriving twice, each copy with its own delay.
+    pub duplication: f64,
+    /// Probability of a packet being held back for another `reorder_delay`,
+    /// so that packets sent after it overtake it.
+    pub reorder: f64,
+    pub reorder_delay: Duration,
+    /// Packets take between `latency` and `latency + jitter` to arrive,
+    /// uniformly distributed.
+    pub latency: Duration,
+    pub jitter: Duration,
+}
+
+impl Default for Conditions {
+    fn default() -> Conditions {
+        Conditions {
+            loss: 0.0,
+            duplication: 0.0,
+            reorder: 0.0,
+            reorder_delay: Duration::from_millis(0),
+            latency: Duration::from_millis(0),
+            jitter: Duration::from_millis(0),
+        }
+    }
+}
+
+/// SplitMix64, good enough for simulating networks and independent of any
+/// `rand` version.
+#[derive(Clone, Debug)]
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+    /// Uniformly distributed in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+    fn chance(&mut self, probability: f64) -> bool {
+        self.next_f64() < probability
+    }
+    /// Uniformly distributed in `[0, max]`.
+    fn duration(&mut self, max: Duration) -> Duration {
+        max.mul_f64(self.next_f64())
+    }
+}
+
+#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
+struct InFlight {
+    arrival: Timestamp,
+    // Keeps the order of packets arriving at the same time.
+    id: u64,
+    to: Side,
+    data: Vec<u8>,
+}
+
+/// The network between the two connections.
+struct Link {
+    conditions: Conditions,
+    rng: Rng,
+    time: Timestamp,
+    next_id: u64,
+    in_flight: BinaryHeap<Reverse<InFlight>>,
+    packets_sent: u64,
+    packets_lost: u64,
+}
+
+impl Link {
+    fn transmit(&mut self, to: Side, data: &[u8]) {
+        self.packets_sent += 1;
+        if self.rng.chance(self.conditions.loss) {
+            self.packets_lost += 1;
+            return;
+        }
+        let copies = if self.rng.chance(self.conditions.duplication) {
+            2
+        } else {
+            1
+        };
+        for _ in 0..copies {
+            let mut delay = self.conditions.latency + self.rng.duration(self.conditions.jitter);
+            if self.rng.chance(self.conditions.reorder) {
+                delay += self.conditions.reorder_delay;
+            }
+            self.in_flight.push(Reverse(InFlight {
+                arrival: self.time + delay,
+                id: self.next_id,
+                to,
+                data: data.to_owned(),
+            }));
+            self.next_id += 1;
+        }
+    }
+    fn pop_until(&mut self, end: Timestamp) -> Option<InFlight> {
+        if self.in_flight.peek()?.0.arrival > end {
+            return None;
+        }
+        self.in_flight.pop().map(|Reverse(p)| p)
+    }
+}
+
+/// The `Callback` of a connection attached to a `LossyPipe`.
+pub struct PipeCallback<'a> {
+    link: &'a mut Link,
+    side: Side,
+}
+
+impl<'a> Callback for PipeCallback<'a> {
+    type Error = Void;
+    fn secure_random(&mut self, buffer: &mut [u8]) {
+        for b in buffer {
+            *b = self.link.rng.next_u64() as u8;
+        }
+    }
+    fn send(&mut self, buffer: &[u8]) -> Result<(), Void> {
+        self.link.transmit(self.side.other(), buffer);
+        Ok(())
+    }
+    fn time(&mut self) -> Timestamp {
+        self.link.time
+    }
+}
+
+/// Two connections talking to each other over a simulated network.
+pub struct LossyPipe {
+    link: Link,
+    connections: [Connection; 2],
+}
+
+impl LossyPipe {
+    /// Runs with the same packet fates for the same `seed`, given the same
+    /// calls.
+    pub fn new(a: Connection, b: Connection, conditions: Conditions, seed: u64) -> LossyPipe {
+        LossyPipe {
+            link: Link {
+                conditions,
+                rng: Rng(seed),
+                time: Timestamp::from_secs_since_epoch(0),
+                next_id: 0,
+                in_flight: BinaryHeap::new(),
+                packets_sent: 0,
+                packets_lost: 0,
+            },
+            connections: [a, b],
+        }
+    }
+    pub fn time(&self) -> Timestamp {
+        self.link.time
+    }
+    pub fn conditions(&self) -> &Conditions {
+        &self.link.conditions
+    }
+    /// Takes effect for packets sent afterwards.
+    pub fn set_conditions(&mut self, conditions: Conditions) {
+        self.link.conditions = conditions;
+    }
+    pub fn connection(&self, side: Side) -> &Connection {
+        &self.connections[side.index()]
+    }
+    /// Calls `f` with the connection of `side` and a callback sending its
+    /// packets through the pipe, e.g. for `connect`, `send` or `flush`.
+    pub fn with<R, F>(&mut self, side: Side, f: F) -> R
+    where
+        F: FnOnce(&mut Connection, &mut PipeCallback) -> R,
+    {
+        let mut cb = PipeCallback {
+            link: &mut self.link,
+            side,
+        };
+        f(&mut self.connections[side.index()], &mut cb)
+    }
+    /// Number of packets sent by both connections and how many of them got
+    /// lost.
+    pub fn packets(&self) -> (u64, u64) {
+        (self.link.packets_sent, self.link.packets_lost)
+    }
+    /// Number of packets still on their way.
+    pub fn in_flight(&self) -> usize {
+        self.link.in_flight.len()
+    }
+    /// Advances the time by `duration`, feeding the packets arriving in the
+    /// meantime to their connection, and ticks both connections afterwards.
+    ///
+    /// `f` is called with the chunks and events received, along with the
+    /// side that received them.
+    pub fn run<F>(&mut self, duration: Duration, mut f: F)
+    where
+        F: FnMut(Side, ReceiveChunk),
+    {
+        let mut buffer = [0; ::protocol::MAX_PACKETSIZE];
+        let end = self.link.time + duration;
+        while let Some(packet) = self.link.pop_until(end) {
+            self.link.time = packet.arrival;
+            let mut cb = PipeCallback {
+                link: &mut self.link,
+                side: packet.to,
+            };
+            let conn = &mut self.connections[packet.to.index()];
+            let (chunks, result) = conn.feed(&mut cb, &mut Ignore, &packet.data, &mut buffer[..]);
+            for chunk in chunks {
+                f(packet.to, chunk);
+            }
+            result.void_unwrap();
+        }
+        self.link.time = end;
+        for &side in &[Side::A, Side::B] {
+            self.with(side, |conn, cb| conn.tick(cb)).void_unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Conditions;
+    use super::LossyPipe;
+    use super::Side;
+    use connection::ReceiveChunk;
+    use std::time::Duration;
+    use void::ResultVoidExt;
+    use Connection;
+
+    fn adverse() -> Conditions {
+        Conditions {
+            loss: 0.2,
+            duplication: 0.1,
+            reorder: 0.1,
+            reorder_delay: Duration::from_millis(100),
+            latency: Duration::from_millis(30),
+            jitter: Duration::from_millis(40),
+        }
+    }
+
+    /// Sends `count` vital chunks from A to B, returning what B received.
+    fn transfer(pipe: &mut LossyPipe, count: u32) -> Vec<u32> {
+        let step = Duration::from_millis(10);
+        pipe.with(Side::A, |conn, cb| conn.connect(cb))
+            .void_unwrap();
+        let mut ready = false;
+        let mut sent = 0;
+        let mut received = Vec::new();
+        for _ in 0..6000 {
+            pipe.run(step, |side, chunk| match (side, chunk) {
+                (Side::A, ReceiveChunk::Ready) => ready = true,
+                (Side::B, ReceiveChunk::Connected(data, true)) => {
+                    let mut n = [0; 4];
+                    n.copy_from_slice(data);
+                    received.push(u32::from_be_bytes(n));
+                }
+                _ => {}
+            });
+            if ready && sent < count {
+                pipe.with(Side::A, |conn, cb| {
+                    conn.send(cb, &sent.to_be_bytes(), true).unwrap();
+                    conn.flush(cb).void_unwrap();
+                });
+                sent += 1;
+            }
+            if received.len() == count as usize {
+                break;
+            }
+        }
+        received
+    }
+
+    #[test]
+    fn perfect() {
+        let mut pipe = LossyPipe::new(
+            Connection::new(),
+            Connection::new(),
+            Conditions::default(),
+            0,
+        );
+        assert_eq!(transfer(&mut pipe, 10), (0..10).collect::<Vec<_>>());
+        assert_eq!(pipe.packets().1, 0);
+    }
+
+    #[test]
+    fn deterministic() {
+        let run = |seed| {
+            let mut pipe = LossyPipe::new(Connection::new(), Connection::new(), adverse(), seed);
+            transfer(&mut pipe, 20);
+            (pipe.packets(), pipe.time())
+        };
+        assert_eq!(run(1), run(1));
+        assert_ne!(run(1), run(2));
+    }
+
+    quickcheck! {
+        fn exactly_once_in_order(seed: u64) -> bool {
+            let mut pipe = LossyPipe::new(Connection::new(), Connection::new(), adverse(), seed);
+            transfer(&mut pipe, 50) == (0..50).collect::<Vec<_>>()
+        }
+    }
+}
@@ -0,0 +1,319 @@
+//! Async driver for `Net` on top of tokio, enabled by the `tokio` feature.
+//!
+//! `TwSocket` owns a UDP socket and a `Net`, feeds it the received packets,
+//! ticks it when its timers expire and yields the resulting events as a
+//! `Stream`.
+
+use arrayvec::ArrayVec;
+use connection::Error;
+use futures_core::Stream;
+use net::Callback;
+use net::ChunkOrEvent;
+use net::PeerId;
+use protocol::MAX_PACKETSIZE;
+use rand::thread_rng;
+use rand::RngCore;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+use tokio_crate::io::ReadBuf;
+use tokio_crate::net::UdpSocket;
+use tokio_crate::time;
+use tokio_crate::time::Sleep;
+use warn::Ignore;
+use Net;
+use Timestamp;
+
+/// An owned `ChunkOrEvent`, plus timeouts.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Event {
+    Chunk {
+        pid: PeerId,
+        vital: bool,
+        data: Vec<u8>,
+    },
+    Connless {
+        addr: SocketAddr,
+        pid: Option<PeerId>,
+        data: Vec<u8>,
+    },
+    Connect(PeerId),
+    Ready(PeerId),
+    Disconnect(PeerId, Vec<u8>),
+    /// The peer didn't send anything for too long and has been removed.
+    Timeout(PeerId),
+}
+
+impl<'a> From<ChunkOrEvent<'a, SocketAddr>> for Event {
+    fn from(chunk: ChunkOrEvent<'a, SocketAddr>) -> Event {
+        match chunk {
+            ChunkOrEvent::Chunk(c) => Event::Chunk {
+                pid: c.pid,
+                vital: c.vital,
+                data: c.data.to_vec(),
+            },
+            ChunkOrEvent::Connless(c) => Event::Connless {
+                addr: c.addr,
+                pid: c.pid,
+                data: c.data.to_vec(),
+            },
+            ChunkOrEvent::Connect(pid) => Event::Connect(pid),
+            ChunkOrEvent::Ready(pid) => Event::Ready(pid),
+            ChunkOrEvent::Disconnect(pid, reason) => Event::Disconnect(pid, reason.to_vec()),
+        }
+    }
+}
+
+struct Cb<'a> {
+    socket: &'a UdpSocket,
+    start: Instant,
+}
+
+impl<'a> Callback<SocketAddr> for Cb<'a> {
+    type Error = io::Error;
+    fn secure_random(&mut self, buffer: &mut [u8]) {
+        thread_rng().fill_bytes(buffer)
+    }
+    fn send(&mut self, addr: SocketAddr, data: &[u8]) -> Result<(), io::Error> {
+        match self.socket.try_send_to(data, addr) {
+            Ok(_) => Ok(()),
+            // Treat it like any other lost packet.
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+    fn time(&mut self) -> Timestamp {
+        Timestamp::from_secs_since_epoch(0) + self.start.elapsed()
+    }
+}
+
+/// A `Net` driven by a tokio UDP socket.
+///
+/// Must be created inside a tokio runtime with the timer enabled.
+pub struct TwSocket {
+    socket: UdpSocket,
+    net: Net<SocketAddr>,
+    start: Instant,
+    timer: Pin<Box<Sleep>>,
+    timer_active: bool,
+    events: VecDeque<Event>,
+    receive_buffer: [u8; MAX_PACKETSIZE],
+}
+
+impl TwSocket {
+    pub fn new(socket: UdpSocket, net: Net<SocketAddr>) -> TwSocket {
+        TwSocket {
+            socket,
+            net,
+            start: Instant::now(),
+            timer: Box::pin(time::sleep(Duration::from_secs(0))),
+            timer_active: false,
+            events: VecDeque::new(),
+            receive_buffer: [0; MAX_PACKETSIZE],
+        }
+    }
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+    pub fn net(&self) -> &Net<SocketAddr> {
+        &self.net
+    }
+    pub fn connect(&mut self, addr: SocketAddr) -> io::Result<PeerId> {
+        let (pid, res) = self.net.connect(&mut cb(&self.socket, self.start), addr);
+        self.update_timer();
+        res.map(|()| pid)
+    }
+    pub fn accept(&mut self, pid: PeerId) -> io::Result<()> {
+        let res = self.net.accept(&mut cb(&self.socket, self.start), pid);
+        self.update_timer();
+        res
+    }
+    pub fn reject(&mut self, pid: PeerId, reason: &[u8]) -> io::Result<()> {
+        let res = self
+            .net
+            .reject(&mut cb(&self.socket, self.start), pid, reason);
+        self.update_timer();
+        res
+    }
+    pub fn disconnect(&mut self, pid: PeerId, reason: &[u8]) -> io::Result<()> {
+        let res = self
+            .net
+            .disconnect(&mut cb(&self.socket, self.start), pid, reason);
+        self.update_timer();
+        res
+    }
+    /// Queues a chunk, call `flush` to send it immediately.
+    pub fn send(&mut self, pid: PeerId, data: &[u8], vital: bool) -> Result<(), Error<io::Error>> {
+        let chunk = ::net::Chunk { pid, vital, data };
+        let res = self.net.send(&mut cb(&self.socket, self.start), chunk);
+        self.update_timer();
+        res
+    }
+    pub fn flush(&mut self, pid: PeerId) -> io::Result<()> {
+        let res = self.net.flush(&mut cb(&self.socket, self.start), pid);
+        self.update_timer();
+        res
+    }
+    pub fn send_connless(&mut self, addr: SocketAddr, data: &[u8]) -> Result<(), Error<io::Error>> {
+        self.net
+            .send_connless(&mut cb(&self.socket, self.start), addr, data)
+    }
+    fn update_timer(&mut self) {
+        let now = Timestamp::from_secs_since_epoch(0) + self.start.elapsed();
+        match self.net.needs_tick().time_from(now) {
+            Some(duration) => {
+                self.timer.as_mut().reset(time::Instant::now() + duration);
+                self.timer_active = true;
+            }
+            None => self.timer_active = false,
+        }
+    }
+    fn tick(&mut self) -> io::Result<()> {
+        let mut result = Ok(());
+        for e in self.net.tick(&mut cb(&self.socket, self.start)) {
+            if result.is_ok() {
+                result = Err(e);
+            }
+        }
+        let timed_out: Vec<_> = self.net.timed_out().collect();
+        for pid in timed_out {
+            self.net.ignore(pid);
+            self.events.push_back(Event::Timeout(pid));
+        }
+        self.update_timer();
+        result
+    }
+    fn feed(&mut self, addr: SocketAddr, len: usize) -> io::Result<()> {
+        let mut buffer: ArrayVec<[u8; 4096]> = ArrayVec::new();
+        let result;
+        {
+            let (chunks, res) = self.net.feed(
+                &mut cb(&self.socket, self.start),
+                &mut Ignore,
+                addr,
+                &self.receive_buffer[..len],
+                &mut buffer,
+            );
+            self.events.extend(chunks.map(Event::from));
+            result = res;
+        }
+        self.update_timer();
+        result
+    }
+}
+
+fn cb(socket: &UdpSocket, start: Instant) -> Cb<'_> {
+    Cb { socket, start }
+}
+
+impl Stream for TwSocket {
+    type Item = io::Result<Event>;
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<io::Result<Event>>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(event) = this.events.pop_front() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+            if this.timer_active && this.timer.as_mut().poll(cx).is_ready() {
+                if let Err(e) = this.tick() {
+                    return Poll::Ready(Some(Err(e)));
+                }
+                continue;
+            }
+            let received = {
+                let mut buf = ReadBuf::new(&mut this.receive_buffer);
+                match this.socket.poll_recv_from(cx, &mut buf) {
+                    Poll::Ready(Ok(addr)) => Ok((addr, buf.filled().len())),
+                    Poll::Ready(Err(e)) => Err(e),
+                    Poll::Pending => return Poll::Pending,
+                }
+            };
+            let result = received.and_then(|(addr, len)| this.feed(addr, len));
+            if let Err(e) = result {
+                return Poll::Ready(Some(Err(e)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Event;
+    use super::TwSocket;
+    use futures_core::Stream;
+    use std::future;
+    use std::net;
+    use std::pin::Pin;
+    use std::task::Poll;
+    use tokio_crate::net::UdpSocket;
+    use tokio_crate::runtime::Runtime;
+    use Net;
+
+    fn socket() -> UdpSocket {
+        let socket = net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket.set_nonblocking(true).unwrap();
+        UdpSocket::from_std(socket).unwrap()
+    }
+
+    /// Returns the next event of either socket, `true` meaning the client.
+    fn next(rt: &Runtime, client: &mut TwSocket, server: &mut TwSocket) -> (bool, Event) {
+        rt.block_on(future::poll_fn(|cx| {
+            if let Poll::Ready(e) = Pin::new(&mut *client).poll_next(cx) {
+                return Poll::Ready((true, e.unwrap().unwrap()));
+            }
+            if let Poll::Ready(e) = Pin::new(&mut *server).poll_next(cx) {
+                return Poll::Ready((false, e.unwrap().unwrap()));
+            }
+            Poll::Pending
+        }))
+    }
+
+    #[test]
+    fn establish_connection() {
+        let rt = tokio_crate::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let _guard = rt.enter();
+        let mut client = TwSocket::new(socket(), Net::client());
+        let mut server = TwSocket::new(socket(), Net::server());
+        let server_addr = server.socket().local_addr().unwrap();
+
+        let c_pid = client.connect(server_addr).unwrap();
+        assert_eq!(
+            next(&rt, &mut client, &mut server),
+            (true, Event::Ready(c_pid))
+        );
+        let s_pid = match next(&rt, &mut client, &mut server) {
+            (false, Event::Connect(pid)) => pid,
+            e => panic!("{:?}", e),
+        };
+        server.accept(s_pid).unwrap();
+
+        client.send(c_pid, b"hello", true).unwrap();
+        client.flush(c_pid).unwrap();
+        assert_eq!(
+            next(&rt, &mut client, &mut server),
+            (
+                false,
+                Event::Chunk {
+                    pid: s_pid,
+                    vital: true,
+                    data: b"hello".to_vec(),
+                }
+            )
+        );
+
+        server.disconnect(s_pid, b"bye").unwrap();
+        assert_eq!(
+            next(&rt, &mut client, &mut server),
+            (true, Event::Disconnect(c_pid, b"bye".to_vec()))
+        );
+    }
+}
@@ -0,0 +1,585 @@
+//! Packet capture and deterministic replay.
+//!
+//! A `Recorder` writes every packet received and sent by a `Net`, along with
+//! the random bytes it requested and the times it was ticked, into a compact
+//! trace. A `Replay` feeds such a trace back into a fresh `Net`, checking
+//! that it sends exactly the recorded packets. This allows reproducing
+//! protocol bugs observed in production in tests.
+//!
+//! The trace starts with `MAGIC`, followed by records of the form
+//!
+//! ```text
+//! kind: u8, time: u64, addr_len: u8, addr: [u8; addr_len], len: u16, data: [u8; len]
+//! ```
+//!
+//! with all integers in big endian. Addresses are kept since the tokens
+//! handed out by `Net` are derived from them.
+
+use net::Address;
+use net::Callback;
+use net::ChunkOrEvent;
+use net::Warning;
+use protocol::MAX_PACKETSIZE;
+use std::fmt;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::marker::PhantomData;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::net::Ipv6Addr;
+use std::net::SocketAddr;
+use void;
+use void::ResultVoidExt;
+use void::Void;
+use warn::Warn;
+use Net;
+use Timestamp;
+
+pub const MAGIC: &[u8; 8] = b"twtrace1";
+
+const RECEIVE: u8 = 0;
+const SEND: u8 = 1;
+const RANDOM: u8 = 2;
+const TICK: u8 = 3;
+
+/// An address that can be stored in a trace.
+pub trait TraceAddress: Address {
+    /// Appends the encoded address, at most 255 bytes.
+    fn encode(&self, buffer: &mut Vec<u8>);
+    fn decode(data: &[u8]) -> Option<Self>;
+}
+
+/// Flow information and scope IDs of IPv6 addresses are not stored.
+impl TraceAddress for SocketAddr {
+    fn encode(&self, buffer: &mut Vec<u8>) {
+        match self.ip() {
+            IpAddr::V4(ip) => buffer.extend_from_slice(&ip.octets()),
+            IpAddr::V6(ip) => buffer.extend_from_slice(&ip.octets()),
+        }
+        buffer.extend_from_slice(&self.port().to_be_bytes());
+    }
+    fn decode(data: &[u8]) -> Option<SocketAddr> {
+        let (ip, port) = match data.len() {
+            6 => {
+                let mut ip = [0; 4];
+                ip.copy_from_slice(&data[..4]);
+                (IpAddr::from(Ipv4Addr::from(ip)), &data[4..])
+            }
+            18 => {
+                let mut ip = [0; 16];
+                ip.copy_from_slice(&data[..16]);
+                (IpAddr::from(Ipv6Addr::from(ip)), &data[16..])
+            }
+            _ => return None,
+        };
+        Some(SocketAddr::new(ip, u16::from_be_bytes([port[0], port[1]])))
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// The trace is malformed.
+    Format,
+    /// The replayed `Net` behaved differently than the recorded one at the
+    /// given record.
+    Diverged(u64),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref e) => e.fmt(f),
+            Error::Format => f.write_str("malformed trace"),
+            Error::Diverged(record) => write!(f, "replay diverged at record {}", record),
+        }
+    }
+}
+
+/// Records the traffic of a `Net<A>`.
+///
+/// I/O errors while recording don't disturb the `Net`, the first one is
+/// returned by `finish`.
+pub struct Recorder<A: TraceAddress, W: Write> {
+    writer: W,
+    buffer: Vec<u8>,
+    error: Option<io::Error>,
+    addr: PhantomData<A>,
+}
+
+impl<A: TraceAddress, W: Write> Recorder<A, W> {
+    pub fn new(mut writer: W) -> io::Result<Recorder<A, W>> {
+        writer.write_all(MAGIC)?;
+        Ok(Recorder {
+            writer,
+            buffer: Vec::new(),
+            error: None,
+            addr: PhantomData,
+        })
+    }
+    fn record(&mut self, kind: u8, time: Timestamp, addr: Option<A>, data: &[u8]) {
+        if self.error.is_some() {
+            return;
+        }
+        let buffer = &mut self.buffer;
+        buffer.clear();
+        buffer.push(kind);
+        buffer.extend_from_slice(&time.as_usecs_since_epoch().to_be_bytes());
+        buffer.push(0);
+        if let Some(addr) = addr {
+            addr.encode(buffer);
+            let addr_len = buffer.len() - 10;
+            assert!(addr_len <= u8::MAX as usize);
+            buffer[9] = addr_len as u8;
+        }
+        assert!(data.len() <= u16::MAX as usize);
+        buffer.extend_from_slice(&(data.len() as u16).to_be_bytes());
+        buffer.extend_from_slice(data);
+        if let Err(e) = self.writer.write_all(buffer) {
+            self.error = Some(e);
+        }
+    }
+    /// Records a packet that is about to be passed to `Net::feed`.
+    pub fn receive(&mut self, time: Timestamp, addr: A, data: &[u8]) {
+        self.record(RECEIVE, time, Some(addr), data);
+    }
+    /// Records that `Net::tick` is about to be called.
+    pub fn tick(&mut self, time: Timestamp) {
+        self.record(TICK, time, None, &[]);
+    }
+    /// Wraps the callback passed to `Net`, recording the packets sent and
+    /// the random bytes requested through it.
+    pub fn callback<'a, CB: Callback<A>>(&'a mut self, cb: &'a mut CB) -> Recording<'a, A, W, CB> {
+        Recording { recorder: self, cb }
+    }
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+pub struct Recording<'a, A: TraceAddress + 'a, W: Write + 'a, CB: Callback<A> + 'a> {
+    recorder: &'a mut Recorder<A, W>,
+    cb: &'a mut CB,
+}
+
+impl<'a, A: TraceAddress, W: Write, CB: Callback<A>> Callback<A> for Recording<'a, A, W, CB> {
+    type Error = CB::Error;
+    fn secure_random(&mut self, buffer: &mut [u8]) {
+        self.cb.secure_random(buffer);
+        let time = self.cb.time();
+        self.recorder.record(RANDOM, time, None, buffer);
+    }
+    fn send(&mut self, addr: A, data: &[u8]) -> Result<(), CB::Error> {
+        let time = self.cb.time();
+        self.recorder.record(SEND, time, Some(addr), data);
+        self.cb.send(addr, data)
+    }
+    fn time(&mut self) -> Timestamp {
+        self.cb.time()
+    }
+}
+
+struct Record<A> {
+    kind: u8,
+    time: Timestamp,
+    addr: Option<A>,
+    data: Vec<u8>,
+}
+
+/// A step of a replayed trace, to be performed on the `Net`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Step<A: Address> {
+    /// Pass the packet to `Net::feed`.
+    Receive(A, Vec<u8>),
+    /// Call `Net::tick`.
+    Tick,
+}
+
+/// Replays a trace into a `Net`.
+///
+/// `Replay` is the callback to pass to the `Net`: its time is the time of
+/// the last record read, the random bytes are the recorded ones and the
+/// sent packets are compared to the recorded ones.
+pub struct Replay<A: TraceAddress, R: Read> {
+    reader: R,
+    time: Timestamp,
+    record: u64,
+    error: Option<Error>,
+    addr: PhantomData<A>,
+}
+
+fn read_exact<R: Read>(reader: &mut R, buffer: &mut [u8]) -> Result<(), Error> {
+    reader.read_exact(buffer).map_err(|e| match e.kind() {
+        io::ErrorKind::UnexpectedEof => Error::Format,
+        _ => Error::Io(e),
+    })
+}
+
+impl<A: TraceAddress, R: Read> Replay<A, R> {
+    pub fn new(mut reader: R) -> Result<Replay<A, R>, Error> {
+        let mut magic = [0; 8];
+        read_exact(&mut reader, &mut magic)?;
+        if &magic != MAGIC {
+            return Err(Error::Format);
+        }
+        Ok(Replay {
+            reader,
+            time: Timestamp::from_secs_since_epoch(0),
+            record: 0,
+            error: None,
+            addr: PhantomData,
+        })
+    }
+    fn read_record(&mut self) -> Result<Option<Record<A>>, Error> {
+        let mut header = [0; 10];
+        let mut read = 0;
+        while read < header.len() {
+            match self.reader.read(&mut header[read..]) {
+                Ok(0) if read == 0 => return Ok(None),
+                Ok(0) => return Err(Error::Format),
+                Ok(n) => read += n,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(Error::Io(e)),
+            }
+        }
+        let mut time = [0; 8];
+        time.copy_from_slice(&header[1..9]);
+        let mut addr = vec![0; header[9] as usize];
+        read_exact(&mut self.reader, &mut addr)?;
+        let mut len = [0; 2];
+        read_exact(&mut self.reader, &mut len)?;
+        let mut data = vec![0; u16::from_be_bytes(len) as usize];
+        read_exact(&mut self.reader, &mut data)?;
+        if header[0] > TICK {
+            return Err(Error::Format);
+        }
+        let addr = if addr.is_empty() {
+            None
+        } else {
+            Some(A::decode(&addr).ok_or(Error::Format)?)
+        };
+        let record = Record {
+            kind: header[0],
+            time: Timestamp::from_usecs_since_epoch(u64::from_be_bytes(time)),
+            addr,
+            data,
+        };
+        self.time = record.time;
+        self.record += 1;
+        Ok(Some(record))
+    }
+    /// Reads the record the `Net` is expected to produce next.
+    fn expect(&mut self, kind: u8) -> Option<Record<A>> {
+        if self.error.is_some() {
+            return None;
+        }
+        match self.read_record() {
+            Ok(Some(ref r)) if r.kind != kind => {}
+            Ok(Some(r)) => return Some(r),
+            Ok(None) => {}
+            Err(e) => {
+                self.error = Some(e);
+                return None;
+            }
+        }
+        self.error = Some(Error::Diverged(self.record));
+        None
+    }
+    /// Returns the next step to perform, or `None` at the end of the trace.
+    pub fn step(&mut self) -> Result<Option<Step<A>>, Error> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        Ok(match self.read_record()? {
+            Some(Record {
+                kind: RECEIVE,
+                addr: Some(addr),
+                data,
+                ..
+            }) => Some(Step::Receive(addr, data)),
+            Some(Record { kind: RECEIVE, .. }) => return Err(Error::Format),
+            Some(Record { kind: TICK, .. }) => Some(Step::Tick),
+            Some(_) => return Err(Error::Diverged(self.record)),
+            None => None,
+        })
+    }
+    /// Performs all steps of the trace on `net`, passing the received chunks
+    /// to `f`.
+    ///
+    /// Only suitable for traces without sends initiated by the application,
+    /// use `step` for the others.
+    pub fn run<W, F>(&mut self, net: &mut Net<A>, warn: &mut W, mut f: F) -> Result<(), Error>
+    where
+        W: Warn<Warning<A>>,
+        F: FnMut(ChunkOrEvent<A>),
+    {
+        let mut buffer = [0; MAX_PACKETSIZE];
+        while let Some(step) = self.step()? {
+            match step {
+                Step::Receive(addr, data) => {
+                    let (chunks, res) = net.feed(self, warn, addr, &data, &mut buffer[..]);
+                    chunks.for_each(&mut f);
+                    res.void_unwrap();
+                }
+                Step::Tick => {
+                    // Without errors, the first `next` ticks all peers.
+                    if let Some(e) = net.tick(self).next() {
+                        void::unreachable(e);
+                    }
+                }
+            }
+        }
+        self.finish()
+    }
+    /// Checks that the `Net` sent all recorded packets.
+    pub fn finish(&mut self) -> Result<(), Error> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        match self.read_record()? {
+            Some(_) => Err(Error::Diverged(self.record)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<A: TraceAddress, R: Read> Callback<A> for Replay<A, R> {
+    type Error = Void;
+    fn secure_random(&mut self, buffer: &mut [u8]) {
+        match self.expect(RANDOM) {
+            Some(ref r) if r.data.len() == buffer.len() => buffer.copy_from_slice(&r.data),
+            Some(_) => {
+                self.error = Some(Error::Diverged(self.record));
+                buffer.iter_mut().for_each(|b| *b = 0);
+            }
+            None => buffer.iter_mut().for_each(|b| *b = 0),
+        }
+    }
+    fn send(&mut self, addr: A, data: &[u8]) -> Result<(), Void> {
+        match self.expect(SEND) {
+            Some(ref r) if r.addr == Some(addr) && r.data == data => {}
+            Some(_) => self.error = Some(Error::Diverged(self.record)),
+            None => {}
+        }
+        Ok(())
+    }
+    fn time(&mut self) -> Timestamp {
+        self.time
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Error;
+    use super::Recorder;
+    use super::Replay;
+    use super::Step;
+    use super::TraceAddress;
+    use net::Callback;
+    use net::ChunkOrEvent;
+    use protocol;
+    use std::collections::VecDeque;
+    use std::net::SocketAddr;
+    use void::ResultVoidExt;
+    use void::Void;
+    use warn::Ignore;
+    use warn::Panic;
+    use Net;
+    use Timestamp;
+
+    #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+    enum Address {
+        Client,
+        Server,
+    }
+
+    impl TraceAddress for Address {
+        fn encode(&self, buffer: &mut Vec<u8>) {
+            buffer.push(*self as u8);
+        }
+        fn decode(data: &[u8]) -> Option<Address> {
+            match *data {
+                [0] => Some(Address::Client),
+                [1] => Some(Address::Server),
+                _ => None,
+            }
+        }
+    }
+
+    struct Cb {
+        packets: VecDeque<(Address, Vec<u8>)>,
+        random: u8,
+    }
+
+    impl Callback<Address> for Cb {
+        type Error = Void;
+        fn secure_random(&mut self, buffer: &mut [u8]) {
+            for b in buffer {
+                *b = self.random;
+                self.random = self.random.wrapping_add(1);
+            }
+        }
+        fn send(&mut self, addr: Address, data: &[u8]) -> Result<(), Void> {
+            self.packets.push_back((addr, data.to_owned()));
+            Ok(())
+        }
+        fn time(&mut self) -> Timestamp {
+            Timestamp::from_secs_since_epoch(1)
+        }
+    }
+
+    fn event<A: ::net::Address>(chunk: ChunkOrEvent<A>) -> String {
+        match chunk {
+            ChunkOrEvent::Chunk(c) => format!("chunk {:?} {:?}", c.pid, c.data),
+            ChunkOrEvent::Connless(c) => format!("connless {:?}", c.data),
+            ChunkOrEvent::Connect(pid) => format!("connect {:?}", pid),
+            ChunkOrEvent::Ready(pid) => format!("ready {:?}", pid),
+            ChunkOrEvent::Disconnect(pid, reason) => format!("disconnect {:?} {:?}", pid, reason),
+        }
+    }
+
+    /// Records the server side of a connection sending a single chunk.
+    fn record() -> (Vec<u8>, Vec<String>) {
+        let mut recorder = Recorder::new(Vec::new()).unwrap();
+        let mut cb = Cb {
+            packets: VecDeque::new(),
+            random: 0,
+        };
+        let mut buffer = [0; protocol::MAX_PACKETSIZE];
+        let mut client = Net::client();
+        let mut server = Net::server();
+        let mut events = Vec::new();
+
+        let (c_pid, res) = client.connect(&mut cb, Address::Server);
+        res.void_unwrap();
+        while let Some((addr, packet)) = cb.packets.pop_front() {
+            let time = cb.time();
+            match addr {
+                Address::Server => {
+                    recorder.receive(time, Address::Client, &packet);
+                    let (chunks, res) = server.feed(
+                        &mut recorder.callback(&mut cb),
+                        &mut Panic,
+                        Address::Client,
+                        &packet,
+                        &mut buffer[..],
+                    );
+                    events.extend(chunks.map(event));
+                    res.void_unwrap();
+                }
+                Address::Client => {
+                    let (chunks, res) = client.feed(
+                        &mut cb,
+                        &mut Panic,
+                        Address::Server,
+                        &packet,
+                        &mut buffer[..],
+                    );
+                    let ready = chunks.count() != 0;
+                    res.void_unwrap();
+                    if ready {
+                        let chunk = ::net::Chunk {
+                            pid: c_pid,
+                            vital: true,
+                            data: b"hello",
+                        };
+                        client.send(&mut cb, chunk).unwrap();
+                        client.flush(&mut cb, c_pid).void_unwrap();
+                    }
+                }
+            }
+        }
+        recorder.tick(cb.time());
+        assert!(server
+            .tick(&mut recorder.callback(&mut cb))
+            .next()
+            .is_none());
+        (recorder.finish().unwrap(), events)
+    }
+
+    #[test]
+    fn replay() {
+        let (trace, events) = record();
+        assert_eq!(events, ["connect p0", "chunk p0 [104, 101, 108, 108, 111]"]);
+        let mut replayed = Vec::new();
+        Replay::<Address, _>::new(&trace[..])
+            .unwrap()
+            .run(&mut Net::server(), &mut Panic, |c| replayed.push(event(c)))
+            .unwrap();
+        assert_eq!(replayed, events);
+    }
+
+    #[test]
+    fn step() {
+        let (trace, _) = record();
+        let mut replay = Replay::<Address, _>::new(&trace[..]).unwrap();
+        let mut net = Net::server();
+        let mut buffer = [0; protocol::MAX_PACKETSIZE];
+        let mut steps = 0;
+        while let Some(step) = replay.step().unwrap() {
+            match step {
+                Step::Receive(addr, data) => {
+                    assert_eq!(addr, Address::Client);
+                    let (chunks, res) =
+                        net.feed(&mut replay, &mut Panic, addr, &data, &mut buffer[..]);
+                    chunks.count();
+                    res.void_unwrap();
+                }
+                Step::Tick => assert!(net.tick(&mut replay).next().is_none()),
+            }
+            steps += 1;
+        }
+        replay.finish().unwrap();
+        assert!(steps > 2);
+
+        // Without feeding, the recorded answers are missing.
+        let mut replay = Replay::<Address, _>::new(&trace[..]).unwrap();
+        replay.step().unwrap();
+        assert!(matches!(replay.step(), Err(Error::Diverged(2))));
+    }
+
+    #[test]
+    fn diverged() {
+        let (trace, _) = record();
+        let result = Replay::<Address, _>::new(&trace[..]).unwrap().run(
+            &mut Net::client(),
+            &mut Ignore,
+            |_| {},
+        );
+        assert!(matches!(result, Err(Error::Diverged(_))));
+    }
+
+    #[test]
+    fn malformed() {
+        let replay = Replay::<Address, _>::new(&b"twtrace"[..]);
+        assert!(matches!(replay, Err(Error::Format)));
+        let replay = Replay::<Address, _>::new(&b"twtrace0"[..]);
+        assert!(matches!(replay, Err(Error::Format)));
+        let (trace, _) = record();
+        let mut replay = Replay::<Address, _>::new(&trace[..trace.len() - 1]).unwrap();
+        let result = replay.run(&mut Net::server(), &mut Panic, |_| {});
+        assert!(matches!(result, Err(Error::Format)));
+    }
+
+    #[test]
+    fn socket_addr() {
+        for &addr in &["127.0.0.1:8303", "[::1]:8303"] {
+            let addr: SocketAddr = addr.parse().unwrap();
+            let mut buffer = Vec::new();
+            addr.encode(&mut buffer);
+            assert_eq!(SocketAddr::decode(&buffer), Some(addr));
+        }
+        assert_eq!(SocketAddr::decode(&[0; 5]), None);
+    }
+}
@@ -0,0 +1,115 @@
+//! The medium packets are carried over.
+//!
+//! `Net` and `Connection` only see opaque packets and addresses, so any
+//! datagram-like transport can carry them, e.g. UDP or WebSocket messages.
+
+use net::Address;
+use net::Callback;
+use std::io;
+use std::net::SocketAddr;
+use std::net::UdpSocket;
+use Timestamp;
+
+pub trait Transport {
+    type Addr: Address;
+    type Error;
+    /// Sends a single packet. Packets may be lost.
+    fn send(&mut self, addr: Self::Addr, data: &[u8]) -> Result<(), Self::Error>;
+    /// Receives a single packet into `buffer` without blocking.
+    ///
+    /// Returns the sender and the length of the packet, or `None` if no
+    /// packet is pending.
+    fn receive(&mut self, buffer: &mut [u8]) -> Result<Option<(Self::Addr, usize)>, Self::Error>;
+}
+
+/// Expects the socket to be in non-blocking mode.
+impl Transport for UdpSocket {
+    type Addr = SocketAddr;
+    type Error = io::Error;
+    fn send(&mut self, addr: SocketAddr, data: &[u8]) -> Result<(), io::Error> {
+        match self.send_to(data, addr) {
+            Ok(_) => Ok(()),
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+    fn receive(&mut self, buffer: &mut [u8]) -> Result<Option<(SocketAddr, usize)>, io::Error> {
+        match self.recv_from(buffer) {
+            Ok((len, addr)) => Ok(Some((addr, len))),
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Makes a `Transport` usable as the `Callback` of `Net`.
+pub struct TransportCallback<'a, T: Transport + 'a> {
+    pub transport: &'a mut T,
+    pub time: Timestamp,
+    pub secure_random: &'a mut dyn FnMut(&mut [u8]),
+}
+
+impl<'a, T: Transport + 'a> Callback<T::Addr> for TransportCallback<'a, T> {
+    type Error = T::Error;
+    fn secure_random(&mut self, buffer: &mut [u8]) {
+        (self.secure_random)(buffer)
+    }
+    fn send(&mut self, addr: T::Addr, data: &[u8]) -> Result<(), T::Error> {
+        self.transport.send(addr, data)
+    }
+    fn time(&mut self) -> Timestamp {
+        self.time
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Transport;
+    use super::TransportCallback;
+    use net::ChunkOrEvent;
+    use std::net::UdpSocket;
+    use warn::Panic;
+    use Net;
+    use Timestamp;
+
+    #[test]
+    fn udp() {
+        let mut client = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let mut server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let server_addr = server.local_addr().unwrap();
+        client.set_nonblocking(true).unwrap();
+        server.set_nonblocking(true).unwrap();
+        let mut buffer = [0; 2048];
+        assert!(server.receive(&mut buffer).unwrap().is_none());
+
+        let mut random = |b: &mut [u8]| b.iter_mut().for_each(|b| *b = 0x42);
+        let mut net = Net::client();
+        net.send_connless(
+            &mut TransportCallback {
+                transport: &mut client,
+                time: Timestamp::from_secs_since_epoch(0),
+                secure_random: &mut random,
+            },
+            server_addr,
+            b"hello",
+        )
+        .unwrap();
+
+        server.set_nonblocking(false).unwrap();
+        let (addr, len) = server.receive(&mut buffer).unwrap().unwrap();
+        assert_eq!(addr, client.local_addr().unwrap());
+        let mut net = Net::server();
+        let mut cb = TransportCallback {
+            transport: &mut server,
+            time: Timestamp::from_secs_since_epoch(0),
+            secure_random: &mut random,
+        };
+        let mut buf = [0; 2048];
+        let (mut chunks, res) = net.feed(&mut cb, &mut Panic, addr, &buffer[..len], &mut buf[..]);
+        res.unwrap();
+        match chunks.next() {
+            Some(ChunkOrEvent::Connless(c)) => assert_eq!(c.data, b"hello"),
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! WebSocket transport as supported by DDNet, enabled by the `tungstenite`
+//! feature.
+//!
+//! Each binary WebSocket message carries exactly one packet, so browser
+//! clients can speak the regular protocol.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::io;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::net::ToSocketAddrs;
+use transport::Transport;
+use tungstenite;
+use tungstenite::handshake::server::NoCallback;
+use tungstenite::handshake::server::ServerHandshake;
+use tungstenite::handshake::HandshakeError;
+use tungstenite::handshake::MidHandshake;
+use tungstenite::Message;
+use tungstenite::WebSocket;
+
+/// Accepts WebSocket clients, addressing them by their TCP address.
+pub struct WsServer {
+    listener: TcpListener,
+    handshakes: Vec<MidHandshake<ServerHandshake<TcpStream, NoCallback>>>,
+    clients: HashMap<SocketAddr, WebSocket<TcpStream>>,
+    received: VecDeque<(SocketAddr, Vec<u8>)>,
+}
+
+impl WsServer {
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<WsServer> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        Ok(WsServer {
+            listener,
+            handshakes: Vec::new(),
+            clients: HashMap::new(),
+            received: VecDeque::new(),
+        })
+    }
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+    fn accept_clients(&mut self) -> io::Result<()> {
+        loop {
+            let stream = match self.listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e),
+            };
+            stream.set_nonblocking(true)?;
+            stream.set_nodelay(true)?;
+            self.handshake(tungstenite::accept(stream));
+        }
+        for mid in std::mem::take(&mut self.handshakes) {
+            self.handshake(mid.handshake());
+        }
+        Ok(())
+    }
+    fn handshake(
+        &mut self,
+        result: Result<
+            WebSocket<TcpStream>,
+            HandshakeError<ServerHandshake<TcpStream, NoCallback>>,
+        >,
+    ) {
+        match result {
+            Ok(ws) => {
+                if let Ok(addr) = ws.get_ref().peer_addr() {
+                    self.clients.insert(addr, ws);
+                }
+            }
+            Err(HandshakeError::Interrupted(mid)) => self.handshakes.push(mid),
+            // Failed handshakes just drop the TCP connection.
+            Err(HandshakeError::Failure(_)) => {}
+        }
+    }
+}
+
+fn is_would_block(e: &tungstenite::Error) -> bool {
+    match *e {
+        tungstenite::Error::Io(ref e) => e.kind() == io::ErrorKind::WouldBlock,
+        _ => false,
+    }
+}
+
+impl Transport for WsServer {
+    type Addr = SocketAddr;
+    type Error = io::Error;
+    fn send(&mut self, addr: SocketAddr, data: &[u8]) -> Result<(), io::Error> {
+        let result = match self.clients.get_mut(&addr) {
+            Some(ws) => ws.send(Message::Binary(data.to_vec())),
+            // The client is gone, like a lost UDP packet.
+            None => return Ok(()),
+        };
+        match result {
+            // The message is queued and sent with the next write.
+            Err(ref e) if is_would_block(e) => {}
+            Err(_) => {
+                self.clients.remove(&addr);
+            }
+            Ok(()) => {}
+        }
+        Ok(())
+    }
+    fn receive(&mut self, buffer: &mut [u8]) -> Result<Option<(SocketAddr, usize)>, io::Error> {
+        if self.received.is_empty() {
+            // Read at most one message per client so that no client can
+            // starve the others.
+            self.accept_clients()?;
+            let mut closed = Vec::new();
+            for (&addr, ws) in &mut self.clients {
+                match ws.read() {
+                    Ok(Message::Binary(data)) => self.received.push_back((addr, data)),
+                    // Pings are answered by tungstenite itself.
+                    Ok(_) => {}
+                    Err(ref e) if is_would_block(e) => {}
+                    Err(_) => closed.push(addr),
+                }
+            }
+            for addr in closed {
+                self.clients.remove(&addr);
+            }
+        }
+        Ok(self.received.pop_front().map(|(addr, data)| {
+            // Overlong packets are invalid anyway, truncate them.
+            let len = data.len().min(buffer.len());
+            buffer[..len].copy_from_slice(&data[..len]);
+            (addr, len)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::WsServer;
+    use std::net::TcpStream;
+    use std::thread;
+    use transport::Transport;
+    use tungstenite;
+    use tungstenite::Message;
+
+    #[test]
+    fn echo() {
+        let mut server = WsServer::bind("127.0.0.1:0").unwrap();
+        let addr = server.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let stream = TcpStream::connect(addr).unwrap();
+            let url = format!("ws://{}/", addr);
+            let (mut ws, _) = tungstenite::client(url, stream).unwrap();
+            ws.send(Message::Binary(b"hello".to_vec())).unwrap();
+            loop {
+                if let Message::Binary(data) = ws.read().unwrap() {
+                    return data;
+                }
+            }
+        });
+        let mut buffer = [0; 2048];
+        let (client_addr, len) = loop {
+            if let Some(r) = server.receive(&mut buffer).unwrap() {
+                break r;
+            }
+            thread::yield_now();
+        };
+        assert_eq!(&buffer[..len], b"hello");
+        server.send(client_addr, b"world").unwrap();
+        assert_eq!(client.join().unwrap(), b"world");
+    }
+}
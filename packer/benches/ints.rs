@@ -0,0 +1,81 @@
+#[macro_use]
+extern crate bencher;
+extern crate packer;
+extern crate warn;
+
+use bencher::black_box;
+use bencher::Bencher;
+use packer::with_packer;
+use packer::Unpacker;
+use warn::Ignore;
+
+const NUM_INTS: usize = 1024;
+
+/// Integers resembling snapshot item data: mostly small, some positions and
+/// some negative values.
+fn sample_ints() -> Vec<i32> {
+    (0..NUM_INTS as i32)
+        .map(|i| match i % 4 {
+            0 => i % 10,
+            1 => i * 32,
+            2 => -i,
+            _ => 0,
+        })
+        .collect()
+}
+
+fn packed() -> Vec<u8> {
+    let mut buf = Vec::with_capacity(NUM_INTS * 5);
+    with_packer(&mut buf, |mut p| p.write_ints(&sample_ints()).unwrap());
+    buf
+}
+
+fn write_int(bench: &mut Bencher) {
+    let ints = sample_ints();
+    let mut buf = Vec::with_capacity(NUM_INTS * 5);
+    bench.iter(|| {
+        buf.clear();
+        with_packer(&mut buf, |mut p| {
+            for &i in &ints {
+                p.write_int(i).unwrap();
+            }
+        });
+        black_box(&buf);
+    });
+}
+
+fn write_ints(bench: &mut Bencher) {
+    let ints = sample_ints();
+    let mut buf = Vec::with_capacity(NUM_INTS * 5);
+    bench.iter(|| {
+        buf.clear();
+        with_packer(&mut buf, |mut p| p.write_ints(&ints).unwrap());
+        black_box(&buf);
+    });
+}
+
+fn read_int(bench: &mut Bencher) {
+    let packed = packed();
+    let mut ints = vec![0; NUM_INTS];
+    bench.iter(|| {
+        let mut p = Unpacker::new(&packed);
+        for i in &mut ints {
+            *i = p.read_int(&mut Ignore).unwrap();
+        }
+        black_box(&ints);
+    });
+}
+
+fn read_ints(bench: &mut Bencher) {
+    let packed = packed();
+    let mut ints = vec![0; NUM_INTS];
+    bench.iter(|| {
+        Unpacker::new(&packed)
+            .read_ints(&mut Ignore, &mut ints)
+            .unwrap();
+        black_box(&ints);
+    });
+}
+
+benchmark_group!(ints, write_int, write_ints, read_int, read_ints);
+benchmark_main!(ints);
@@ -0,0 +1,208 @@
+//! Bit-level reading and writing, as used by the 0.7 packet and chunk
+//! headers.
+//!
+//! Bits are numbered from the most significant bit of the first byte, i.e.
+//! the first field of a header occupies the high bits of its first byte.
+
+use buffer::CapacityError;
+use std::cmp;
+use UnexpectedEnd;
+
+/// Reads fields of up to 32 bits from a byte slice, most significant bit
+/// first.
+#[derive(Clone, Debug)]
+pub struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(data: &'a [u8]) -> BitReader<'a> {
+        BitReader { data: data, pos: 0 }
+    }
+    /// Reads a `num_bits` bit unsigned integer.
+    ///
+    /// Panics if `num_bits` is larger than 32. Doesn't consume anything if
+    /// there are not enough bits left.
+    pub fn read_bits(&mut self, num_bits: u32) -> Result<u32, UnexpectedEnd> {
+        assert!(num_bits <= 32, "can read at most 32 bits at once");
+        let num_bits = num_bits as usize;
+        if num_bits > self.remaining_bits() {
+            return Err(UnexpectedEnd);
+        }
+        let mut result: u32 = 0;
+        let mut left = num_bits;
+        while left != 0 {
+            let byte = self.data[self.pos / 8];
+            let offset = self.pos % 8;
+            let take = cmp::min(8 - offset, left);
+            let bits = (byte >> (8 - offset - take)) & (0xff >> (8 - take));
+            result = (result << take) | bits as u32;
+            self.pos += take;
+            left -= take;
+        }
+        Ok(result)
+    }
+    pub fn read_bool(&mut self) -> Result<bool, UnexpectedEnd> {
+        self.read_bits(1).map(|b| b != 0)
+    }
+    /// Skips to the start of the next byte, unless already there.
+    pub fn align(&mut self) {
+        self.pos = (self.pos + 7) / 8 * 8;
+    }
+    pub fn is_aligned(&self) -> bool {
+        self.pos % 8 == 0
+    }
+    pub fn num_bits_read(&self) -> usize {
+        self.pos
+    }
+    pub fn remaining_bits(&self) -> usize {
+        self.data.len() * 8 - self.pos
+    }
+    /// Returns the bytes following the current position.
+    ///
+    /// Panics if the reader isn't at a byte boundary.
+    pub fn rest(&self) -> &'a [u8] {
+        assert!(self.is_aligned(), "bit reader must be aligned");
+        &self.data[self.pos / 8..]
+    }
+}
+
+/// Writes fields of up to 32 bits into a byte slice, most significant bit
+/// first.
+///
+/// Partially written bytes have their remaining bits zeroed.
+#[derive(Debug)]
+pub struct BitWriter<'a> {
+    data: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> BitWriter<'a> {
+    pub fn new(data: &'a mut [u8]) -> BitWriter<'a> {
+        BitWriter { data: data, pos: 0 }
+    }
+    /// Writes the lowest `num_bits` bits of `value`.
+    ///
+    /// Panics if `num_bits` is larger than 32 or if `value` doesn't fit into
+    /// `num_bits` bits. Doesn't write anything if there's not enough space
+    /// left.
+    pub fn write_bits(&mut self, value: u32, num_bits: u32) -> Result<(), CapacityError> {
+        assert!(num_bits <= 32, "can write at most 32 bits at once");
+        assert!(
+            (value as u64) >> num_bits == 0,
+            "value doesn't fit into {} bits",
+            num_bits
+        );
+        let num_bits = num_bits as usize;
+        if num_bits > self.remaining_bits() {
+            return Err(CapacityError);
+        }
+        let mut left = num_bits;
+        while left != 0 {
+            let offset = self.pos % 8;
+            let take = cmp::min(8 - offset, left);
+            let bits = ((value >> (left - take)) as u8) & (0xff >> (8 - take));
+            let byte = &mut self.data[self.pos / 8];
+            if offset == 0 {
+                *byte = 0;
+            }
+            *byte |= bits << (8 - offset - take);
+            self.pos += take;
+            left -= take;
+        }
+        Ok(())
+    }
+    pub fn write_bool(&mut self, value: bool) -> Result<(), CapacityError> {
+        self.write_bits(value as u32, 1)
+    }
+    /// Pads with zero bits up to the start of the next byte.
+    pub fn align(&mut self) {
+        let padding = (8 - self.pos % 8) % 8;
+        if padding != 0 {
+            // Can't fail, the current byte has enough space left.
+            self.write_bits(0, padding as u32).unwrap();
+        }
+    }
+    pub fn num_bits_written(&self) -> usize {
+        self.pos
+    }
+    pub fn remaining_bits(&self) -> usize {
+        self.data.len() * 8 - self.pos
+    }
+    /// Returns the bytes written so far, including a partially written last
+    /// byte.
+    pub fn written(self) -> &'a [u8] {
+        let len = (self.pos + 7) / 8;
+        &self.data[..len]
+    }
+}
+
+#[cfg(test)]
+#[rustfmt::skip]
+mod test {
+    use super::BitReader;
+    use super::BitWriter;
+    use buffer::CapacityError;
+    use UnexpectedEnd;
+
+    #[test]
+    fn read() {
+        let mut r = BitReader::new(&[0b1011_0011, 0b0101_1111, 0x12]);
+        assert_eq!(r.read_bits(2), Ok(0b10));
+        assert_eq!(r.read_bits(4), Ok(0b1100));
+        assert_eq!(r.read_bits(10), Ok(0b11_0101_1111));
+        assert!(r.is_aligned());
+        assert_eq!(r.rest(), &[0x12]);
+        assert_eq!(r.read_bits(9), Err(UnexpectedEnd));
+        assert_eq!(r.num_bits_read(), 16);
+        assert_eq!(r.read_bool(), Ok(false));
+        r.align();
+        assert_eq!(r.remaining_bits(), 0);
+        assert_eq!(r.read_bits(0), Ok(0));
+    }
+
+    #[test]
+    fn read_32() {
+        let mut r = BitReader::new(&[0xf1, 0x23, 0x45, 0x67, 0x89]);
+        assert_eq!(r.read_bits(4), Ok(0xf));
+        assert_eq!(r.read_bits(32), Ok(0x1234_5678));
+    }
+
+    #[test]
+    fn write() {
+        let mut buf = [0xff; 3];
+        {
+            let mut w = BitWriter::new(&mut buf);
+            w.write_bits(0b10, 2).unwrap();
+            w.write_bits(0b1100, 4).unwrap();
+            w.write_bits(0b11_0101_1111, 10).unwrap();
+            w.write_bool(true).unwrap();
+            assert_eq!(w.write_bits(0, 8), Err(CapacityError));
+            w.align();
+            assert_eq!(w.num_bits_written(), 24);
+            assert_eq!(w.written(), &[0b1011_0011, 0b0101_1111, 0b1000_0000]);
+        }
+    }
+
+    #[test] #[should_panic] fn write_too_large() { BitWriter::new(&mut [0; 1]).write_bits(4, 2).unwrap(); }
+
+    quickcheck! {
+        fn roundtrip(fields: Vec<(u32, u8)>) -> bool {
+            let fields: Vec<_> = fields.into_iter().map(|(v, n)| {
+                let n = u32::from(n % 33);
+                ((v as u64 & ((1 << n) - 1)) as u32, n)
+            }).collect();
+            let mut buf = vec![0; fields.len() * 4];
+            let len = {
+                let mut w = BitWriter::new(&mut buf);
+                for &(v, n) in &fields {
+                    w.write_bits(v, n).unwrap();
+                }
+                w.written().len()
+            };
+            let mut r = BitReader::new(&buf[..len]);
+            fields.iter().all(|&(v, n)| r.read_bits(n) == Ok(v)) && r.remaining_bits() < 8
+        }
+    }
+}
@@ -10,14 +10,26 @@ extern crate common;
 extern crate uuid;
 extern crate warn;
 
+pub mod bits;
+#[cfg(feature = "uuid")]
+pub mod uuid_registry;
+
 use arrayvec::ArrayVec;
 use buffer::with_buffer;
 use buffer::Buffer;
 use buffer::BufferRef;
 use buffer::CapacityError;
 use common::num::Cast;
+use common::warn::locate;
+use common::warn::Locate;
+use common::TickArena;
+use std::borrow::Cow;
+use std::convert::TryInto;
+use std::io;
 use std::iter;
 use std::mem;
+use std::ops;
+use std::ptr;
 use std::slice;
 #[cfg(feature = "uuid")]
 use uuid::Uuid;
@@ -48,9 +60,27 @@ pub struct ControlCharacters;
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct IntOutOfRange;
 
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct StringTooLong;
+
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct UnexpectedEnd;
 
+/// Error returned by `Packer::write_string_sanitized`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum WriteSanitizedError {
+    Capacity(CapacityError),
+    /// The string would be changed by `Unpacker::read_string_sanitized`, see
+    /// `is_sanitized`.
+    NotSanitized,
+}
+
+impl From<CapacityError> for WriteSanitizedError {
+    fn from(e: CapacityError) -> WriteSanitizedError {
+        WriteSanitizedError::Capacity(e)
+    }
+}
+
 // Format: ESDD_DDDD EDDD_DDDD EDDD_DDDD EDDD_DDDD PPPP_DDDD
 // E - Extend
 // S - Sign
@@ -92,6 +122,47 @@ where
     Ok(result)
 }
 
+// Same format as above, extended to 64 bits: ESDD_DDDD, then up to eight
+// bytes EDDD_DDDD and a last byte PPPP_PPPD.
+//
+// Values that fit into an `i32` have the same encoding as with `read_int`.
+fn read_int64<W>(warn: &mut W, iter: &mut slice::Iter<u8>) -> Result<i64, UnexpectedEnd>
+where
+    W: Warn<Warning>,
+{
+    let mut result = 0;
+    let mut len = 1;
+
+    let mut src = *unwrap_or_return!(iter.next(), Err(UnexpectedEnd));
+    let sign = ((src >> 6) & 1) as i64;
+
+    result |= (src & 0b0011_1111) as i64;
+
+    for i in 0..9 {
+        if src & 0b1000_0000 == 0 {
+            break;
+        }
+        src = *unwrap_or_return!(iter.next(), Err(UnexpectedEnd));
+        len += 1;
+        let mut digits = src & 0b0111_1111;
+        if i == 8 {
+            if src & 0b1111_1110 != 0 {
+                warn.warn(Warning::NonZeroIntPadding);
+            }
+            digits &= 0b0000_0001;
+        }
+        result |= (digits as i64) << (6 + 7 * i);
+    }
+
+    if len > 1 && src == 0b0000_0000 {
+        warn.warn(Warning::OverlongIntEncoding);
+    }
+
+    result ^= -sign;
+
+    Ok(result)
+}
+
 /// Sets the n-th bit of a byte conditionally.
 fn to_bit(b: bool, bit: u32) -> u8 {
     assert!(bit < 8);
@@ -118,6 +189,42 @@ fn write_int<E, F: FnMut(&[u8]) -> Result<(), E>>(int: i32, f: F) -> Result<(),
     f(&buf)
 }
 
+/// Encodes an integer like `write_int`, without a data-dependent loop.
+///
+/// All five bytes are computed unconditionally, only the returned number of
+/// bytes at the start of `out` is part of the encoding.
+fn encode_int(int: i32, out: &mut [u8; 5]) -> usize {
+    let sign = (int >> 31) as u32;
+    let int = (int as u32) ^ sign;
+    let significant_bits = 32 - int.leading_zeros();
+    // The first byte holds six bits, every further byte seven bits. The
+    // sign is folded in, so at most 31 bits are significant.
+    let len = (1 + significant_bits / 7) as usize;
+    let extend = |i: usize| ((len > i + 1) as u8) << 7;
+    out[0] = extend(0) | ((sign & 1) as u8) << 6 | (int & 0b0011_1111) as u8;
+    out[1] = extend(1) | (int >> 6 & 0b0111_1111) as u8;
+    out[2] = extend(2) | (int >> 13 & 0b0111_1111) as u8;
+    out[3] = extend(3) | (int >> 20 & 0b0111_1111) as u8;
+    out[4] = (int >> 27) as u8;
+    len
+}
+
+fn write_int64<E, F: FnMut(&[u8]) -> Result<(), E>>(int: i64, f: F) -> Result<(), E> {
+    let mut f = f;
+    let mut buf: ArrayVec<[u8; 10]> = ArrayVec::new();
+    let sign = if int < 0 { 1 } else { 0 };
+    let mut int = (int ^ -sign) as u64;
+    let next = (int & 0b0011_1111) as u8;
+    int >>= 6;
+    buf.push(to_bit(int != 0, 7) | to_bit(sign != 0, 6) | next);
+    while int != 0 {
+        let next = (int & 0b0111_1111) as u8;
+        int >>= 7;
+        buf.push(to_bit(int != 0, 7) | next);
+    }
+    f(&buf)
+}
+
 fn read_string<'a>(iter: &mut slice::Iter<'a, u8>) -> Result<&'a [u8], UnexpectedEnd> {
     let slice = iter.as_slice();
     // `by_ref` is needed as the iterator is silently copied otherwise.
@@ -139,32 +246,155 @@ fn write_string<E, F: FnMut(&[u8]) -> Result<(), E>>(string: &[u8], f: F) -> Res
 
 pub struct Packer<'d, 's> {
     buf: BufferRef<'d, 's>,
+    // Number of bytes written to the uninitialized part of `buf` that haven't
+    // been committed yet. This makes it possible to roll back writes.
+    pending: usize,
+}
+
+/// Position in a `Packer` that can be rolled back to.
+///
+/// Returned by `Packer::checkpoint`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Checkpoint {
+    remaining: usize,
 }
 
 impl<'r, 'd, 's> Buffer<'d> for &'r mut Packer<'d, 's> {
     type Intermediate = buffer::BufferRefBuffer<'r, 'd, 's>;
     fn to_to_buffer_ref(self) -> Self::Intermediate {
+        self.commit();
         (&mut self.buf).to_to_buffer_ref()
     }
 }
 
 impl<'d, 's> Packer<'d, 's> {
     fn new(buf: BufferRef<'d, 's>) -> Packer<'d, 's> {
-        Packer { buf: buf }
+        Packer {
+            buf: buf,
+            pending: 0,
+        }
+    }
+    fn write_bytes(&mut self, data: &[u8]) -> Result<(), CapacityError> {
+        let start = self.pending;
+        let end = start.checked_add(data.len()).ok_or(CapacityError)?;
+        // The uninitialized part is only ever written to.
+        let uninitialized = unsafe { self.buf.uninitialized_mut() };
+        let out = uninitialized.get_mut(start..end).ok_or(CapacityError)?;
+        out.copy_from_slice(data);
+        self.pending = end;
+        Ok(())
+    }
+    fn commit(&mut self) {
+        // The pending bytes have been initialized by `write_bytes`.
+        unsafe {
+            self.buf.advance(self.pending);
+        }
+        self.pending = 0;
+    }
+    /// Number of bytes that can still be written.
+    pub fn remaining(&self) -> usize {
+        self.buf.remaining() - self.pending
+    }
+    /// Returns the current position, to be passed to `rollback`.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            remaining: self.remaining(),
+        }
+    }
+    /// Undoes all writes since `checkpoint` was taken.
+    ///
+    /// Panics if data was written through the `Buffer` implementation of
+    /// the packer (e.g. using `with_packer(&mut packer, ...)`) after the
+    /// checkpoint was taken, as these writes can't be undone.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        let since = checkpoint
+            .remaining
+            .checked_sub(self.remaining())
+            .expect("checkpoint from a different packer");
+        assert!(
+            since <= self.pending,
+            "can't roll back writes through the Buffer interface"
+        );
+        self.pending -= since;
+    }
+    /// Runs `f`, undoing its writes if it fails.
+    ///
+    /// This can be used to append optional data and fall back cleanly if it
+    /// doesn't fit anymore. See `rollback` for restrictions.
+    pub fn try_write<F, R, E>(&mut self, f: F) -> Result<R, E>
+    where
+        F: FnOnce(&mut Packer<'d, 's>) -> Result<R, E>,
+    {
+        let checkpoint = self.checkpoint();
+        let result = f(self);
+        if result.is_err() {
+            self.rollback(checkpoint);
+        }
+        result
     }
     pub fn write_string(&mut self, string: &[u8]) -> Result<(), CapacityError> {
-        write_string(string, |b| self.buf.write(b))
+        write_string(string, |b| self.write_bytes(b))
+    }
+    /// Writes a string that is left unchanged when read with
+    /// `Unpacker::read_string_sanitized` and the same `mode`.
+    ///
+    /// Nothing is written if the string isn't sanitized, see `is_sanitized`.
+    pub fn write_string_sanitized(
+        &mut self,
+        mode: SanitizeMode,
+        string: &[u8],
+    ) -> Result<(), WriteSanitizedError> {
+        if !is_sanitized(mode, string) {
+            return Err(WriteSanitizedError::NotSanitized);
+        }
+        Ok(self.write_string(string)?)
     }
     pub fn write_int(&mut self, int: i32) -> Result<(), CapacityError> {
-        write_int(int, |b| self.buf.write(b))
+        write_int(int, |b| self.write_bytes(b))
+    }
+    /// Writes the integers like consecutive calls to `write_int`.
+    ///
+    /// Either all of the integers are written or, on error, none of them.
+    pub fn write_ints(&mut self, ints: &[i32]) -> Result<(), CapacityError> {
+        let start = self.pending;
+        // The uninitialized part is only ever written to.
+        let out = unsafe { &mut self.buf.uninitialized_mut()[start..] };
+        let mut pos = 0;
+        let mut ints = ints.iter();
+        // As long as there's space for the longest encoding, encode directly
+        // into the buffer. Bytes after the encoded integer are overwritten
+        // by the next one.
+        while out.len() - pos >= 5 {
+            let int = match ints.next() {
+                Some(&int) => int,
+                None => break,
+            };
+            let chunk = (&mut out[pos..pos + 5]).try_into().unwrap();
+            pos += encode_int(int, chunk);
+        }
+        for &int in ints {
+            let mut encoded = [0; 5];
+            let len = encode_int(int, &mut encoded);
+            let chunk = out.get_mut(pos..pos + len).ok_or(CapacityError)?;
+            chunk.copy_from_slice(&encoded[..len]);
+            pos += len;
+        }
+        self.pending = start + pos;
+        Ok(())
+    }
+    /// Writes a 64-bit integer using the extended variable-length encoding.
+    ///
+    /// Integers in the `i32` range are encoded the same way as by
+    /// `write_int`.
+    pub fn write_int64(&mut self, int: i64) -> Result<(), CapacityError> {
+        write_int64(int, |b| self.write_bytes(b))
     }
     pub fn write_data(&mut self, data: &[u8]) -> Result<(), CapacityError> {
         self.write_int(data.len().try_i32().ok_or(CapacityError)?)?;
-        self.buf.write(data)?;
-        Ok(())
+        self.write_bytes(data)
     }
     pub fn write_raw(&mut self, data: &[u8]) -> Result<(), CapacityError> {
-        self.buf.write(data)
+        self.write_bytes(data)
     }
     #[cfg(feature = "uuid")]
     pub fn write_uuid(&mut self, uuid: Uuid) -> Result<(), CapacityError> {
@@ -172,10 +402,117 @@ impl<'d, 's> Packer<'d, 's> {
     }
     pub fn write_rest(&mut self, data: &[u8]) -> Result<(), CapacityError> {
         // TODO: Fail if other stuff happens afterwards.
-        self.buf.write(data)
+        self.write_bytes(data)
+    }
+    pub fn written(mut self) -> &'d [u8] {
+        self.commit();
+        // `Packer` implements `Drop`, so `buf` can't be moved out directly.
+        // It's safe to skip the destructor because everything is committed.
+        let buf = unsafe { ptr::read(&self.buf) };
+        mem::forget(self);
+        buf.initialized()
+    }
+}
+
+impl<'d, 's> Drop for Packer<'d, 's> {
+    fn drop(&mut self) {
+        self.commit();
+    }
+}
+
+const IO_PACKER_CHUNK_SIZE: usize = 1024;
+
+/// Packer that writes into an `io::Write` instead of a fixed buffer.
+///
+/// The data is collected in an internal chunk and written to the underlying
+/// writer whenever the chunk is full, and on `flush` and `finish`. Writing to
+/// e.g. a `Vec<u8>`, a `GrowableBuffer` or a file thus doesn't need an
+/// intermediate buffer that is large enough for all of the data.
+pub struct IoPacker<W: io::Write> {
+    // Only `None` after `finish`.
+    inner: Option<W>,
+    chunk: ArrayVec<[u8; IO_PACKER_CHUNK_SIZE]>,
+    num_bytes_written: u64,
+}
+
+impl<W: io::Write> IoPacker<W> {
+    pub fn new(inner: W) -> IoPacker<W> {
+        IoPacker {
+            inner: Some(inner),
+            chunk: ArrayVec::new(),
+            num_bytes_written: 0,
+        }
+    }
+    fn inner(&mut self) -> &mut W {
+        self.inner.as_mut().unwrap()
+    }
+    fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        self.num_bytes_written += data.len().u64();
+        if self.chunk.remaining_capacity() < data.len() {
+            self.flush_chunk()?;
+        }
+        if data.len() >= self.chunk.capacity() {
+            return self.inner().write_all(data);
+        }
+        self.chunk.try_extend_from_slice(data).unwrap();
+        Ok(())
+    }
+    fn flush_chunk(&mut self) -> io::Result<()> {
+        let inner = self.inner.as_mut().unwrap();
+        inner.write_all(&self.chunk)?;
+        self.chunk.clear();
+        Ok(())
+    }
+    pub fn write_string(&mut self, string: &[u8]) -> io::Result<()> {
+        write_string(string, |b| self.write(b))
+    }
+    pub fn write_int(&mut self, int: i32) -> io::Result<()> {
+        write_int(int, |b| self.write(b))
+    }
+    pub fn write_int64(&mut self, int: i64) -> io::Result<()> {
+        write_int64(int, |b| self.write(b))
+    }
+    pub fn write_data(&mut self, data: &[u8]) -> io::Result<()> {
+        let len = data
+            .len()
+            .try_i32()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "data too long"))?;
+        self.write_int(len)?;
+        self.write(data)
     }
-    pub fn written(self) -> &'d [u8] {
-        self.buf.initialized()
+    pub fn write_raw(&mut self, data: &[u8]) -> io::Result<()> {
+        self.write(data)
+    }
+    #[cfg(feature = "uuid")]
+    pub fn write_uuid(&mut self, uuid: Uuid) -> io::Result<()> {
+        self.write_raw(uuid.as_bytes())
+    }
+    pub fn write_rest(&mut self, data: &[u8]) -> io::Result<()> {
+        self.write(data)
+    }
+    /// Total number of bytes packed, including the ones not yet flushed.
+    pub fn num_bytes_written(&self) -> u64 {
+        self.num_bytes_written
+    }
+    /// Writes the buffered data to the underlying writer and flushes it.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.flush_chunk()?;
+        self.inner().flush()
+    }
+    /// Flushes the packer and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush()?;
+        Ok(self.inner.take().unwrap())
+    }
+}
+
+impl<W: io::Write> Drop for IoPacker<W> {
+    fn drop(&mut self) {
+        // Errors can't be reported here, call `flush` or `finish` to handle
+        // them.
+        if self.inner.is_some() {
+            let _ = self.flush_chunk();
+        }
     }
 }
 
@@ -186,10 +523,55 @@ where
     with_buffer(buf, |b| f(Packer::new(b)))
 }
 
+/// Like `with_packer`, but packs into a buffer of `len` bytes allocated from
+/// `arena`.
+pub fn with_arena_packer<'a, F, R>(arena: &'a TickArena, len: usize, f: F) -> R
+where
+    F: for<'b> FnOnce(Packer<'a, 'b>) -> R,
+{
+    with_packer(arena.alloc_u8(len), f)
+}
+
+/// How the `Unpacker` treats malformed but decodable data.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum DecodePolicy {
+    /// Report overlong integer encodings and non-zero padding bits as
+    /// warnings and continue decoding.
+    Lenient,
+    /// Report them as warnings and then fail the read with
+    /// `UnexpectedEnd`, as if the data ended there. The offending warning is
+    /// remembered, see `Unpacker::policy_violation`.
+    ///
+    /// Excess data at the end is remembered in the same way, but `finish`
+    /// can't fail.
+    Strict,
+}
+
+impl Default for DecodePolicy {
+    fn default() -> DecodePolicy {
+        DecodePolicy::Lenient
+    }
+}
+
+/// Remembers the first warning that passed through it.
+struct SeenWarning<'a, W: Warn<Warning> + 'a> {
+    warn: &'a mut W,
+    seen: Option<Warning>,
+}
+
+impl<'a, W: Warn<Warning>> Warn<Warning> for SeenWarning<'a, W> {
+    fn warn(&mut self, warning: Warning) {
+        self.seen = self.seen.or(Some(warning));
+        self.warn.warn(warning);
+    }
+}
+
 pub struct Unpacker<'a> {
     original: &'a [u8],
     iter: slice::Iter<'a, u8>,
     demo: bool,
+    policy: DecodePolicy,
+    violation: Option<Warning>,
 }
 
 impl<'a> Unpacker<'a> {
@@ -198,8 +580,47 @@ impl<'a> Unpacker<'a> {
             original: data,
             iter: data.iter(),
             demo: demo,
+            policy: DecodePolicy::default(),
+            violation: None,
         }
     }
+    /// Sets the decode policy, the default is `DecodePolicy::Lenient`.
+    pub fn with_policy(self, policy: DecodePolicy) -> Unpacker<'a> {
+        Unpacker {
+            policy: policy,
+            ..self
+        }
+    }
+    pub fn set_policy(&mut self, policy: DecodePolicy) {
+        self.policy = policy;
+    }
+    pub fn policy(&self) -> DecodePolicy {
+        self.policy
+    }
+    /// Returns the warning that was turned into an error by
+    /// `DecodePolicy::Strict`, if any.
+    ///
+    /// This distinguishes reads that failed due to the policy from reads that
+    /// failed because the data actually ended.
+    pub fn policy_violation(&self) -> Option<Warning> {
+        self.violation
+    }
+    fn apply_policy<W, T, F>(&mut self, warn: &mut W, f: F) -> Result<T, UnexpectedEnd>
+    where
+        W: Warn<Warning>,
+        F: FnOnce(&mut SeenWarning<W>, &mut slice::Iter<'a, u8>) -> Result<T, UnexpectedEnd>,
+    {
+        let mut warn = SeenWarning {
+            warn: warn,
+            seen: None,
+        };
+        let result = f(&mut warn, &mut self.iter);
+        if let (Some(warning), DecodePolicy::Strict) = (warn.seen, self.policy) {
+            self.violation = self.violation.or(Some(warning));
+            return self.error();
+        }
+        result
+    }
     pub fn new(data: &[u8]) -> Unpacker {
         Unpacker::new_impl(data, false)
     }
@@ -224,8 +645,41 @@ impl<'a> Unpacker<'a> {
     pub fn read_string(&mut self) -> Result<&'a [u8], UnexpectedEnd> {
         read_string(&mut self.iter)
     }
+    /// Reads a string and sanitizes it like the reference implementation,
+    /// see `sanitize_string`.
+    pub fn read_string_sanitized(
+        &mut self,
+        mode: SanitizeMode,
+    ) -> Result<Cow<'a, [u8]>, UnexpectedEnd> {
+        Ok(sanitize_string(mode, self.read_string()?))
+    }
     pub fn read_int<W: Warn<Warning>>(&mut self, warn: &mut W) -> Result<i32, UnexpectedEnd> {
-        read_int(warn, &mut self.iter)
+        self.apply_policy(warn, |w, iter| read_int(w, iter))
+    }
+    /// Fills `ints` like consecutive calls to `read_int`.
+    pub fn read_ints<W: Warn<Warning>>(
+        &mut self,
+        warn: &mut W,
+        ints: &mut [i32],
+    ) -> Result<(), UnexpectedEnd> {
+        for int in ints {
+            match self.iter.as_slice().first() {
+                // Single-byte integers are the most common ones and can't
+                // cause warnings.
+                Some(&b) if b & 0b1000_0000 == 0 => {
+                    self.iter.next();
+                    *int = (b & 0b0011_1111) as i32 ^ -((b >> 6) as i32);
+                }
+                _ => *int = self.read_int(warn)?,
+            }
+        }
+        Ok(())
+    }
+    /// Reads a 64-bit integer written by `Packer::write_int64`.
+    ///
+    /// This also reads integers written by `Packer::write_int`.
+    pub fn read_int64<W: Warn<Warning>>(&mut self, warn: &mut W) -> Result<i64, UnexpectedEnd> {
+        self.apply_policy(warn, |w, iter| read_int64(w, iter))
     }
     pub fn read_data<W: Warn<Warning>>(&mut self, warn: &mut W) -> Result<&'a [u8], UnexpectedEnd> {
         let len = match self.read_int(warn).map(|l| l.try_usize()) {
@@ -261,14 +715,16 @@ impl<'a> Unpacker<'a> {
         Ok(Uuid::from_slice(self.read_raw(mem::size_of::<Uuid>())?).unwrap())
     }
     pub fn finish<W: Warn<Warning>>(&mut self, warn: &mut W) {
-        if !self.demo {
-            if !self.is_empty() {
-                warn.warn(Warning::ExcessData);
-            }
+        let excess = if !self.demo {
+            !self.is_empty()
         } else {
             let rest = self.as_slice();
-            if rest.len() >= 4 || rest.iter().any(|&b| b != 0) {
-                warn.warn(Warning::ExcessData);
+            rest.len() >= 4 || rest.iter().any(|&b| b != 0)
+        };
+        if excess {
+            warn.warn(Warning::ExcessData);
+            if self.policy == DecodePolicy::Strict {
+                self.violation = self.violation.or(Some(Warning::ExcessData));
             }
         }
         self.use_up();
@@ -276,9 +732,22 @@ impl<'a> Unpacker<'a> {
     pub fn as_slice(&self) -> &'a [u8] {
         self.iter.as_slice()
     }
+    /// Number of bytes consumed so far.
     pub fn num_bytes_read(&self) -> usize {
         self.original.len() - self.iter.len()
     }
+    /// Attaches the current offset and `structure` to all warnings passed to
+    /// the returned warn object, see `common::warn::locate`.
+    ///
+    /// Use this for the warn object of a read to make the resulting warnings
+    /// point at the field being read.
+    pub fn locate<'w, W>(&self, warn: &'w mut W, structure: &'static str) -> Locate<'w, W> {
+        locate(warn, self.num_bytes_read(), structure)
+    }
+    /// Number of bytes left to read.
+    pub fn remaining(&self) -> usize {
+        self.iter.len()
+    }
 }
 
 pub struct IntUnpacker<'a> {
@@ -334,6 +803,90 @@ pub fn sanitize<'a, W: Warn<Warning>>(
     Ok(v)
 }
 
+/// String sanitization flags of the reference implementation's
+/// `CUnpacker::GetString`.
+///
+/// Flags can be combined using `|`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct SanitizeMode(u32);
+
+impl SanitizeMode {
+    /// Leave the string as is.
+    pub const NONE: SanitizeMode = SanitizeMode(0);
+    /// Replace control characters except `\t`, `\n` and `\r` by spaces.
+    pub const SANITIZE: SanitizeMode = SanitizeMode(1);
+    /// Replace all control characters by spaces.
+    ///
+    /// Ignored if `SANITIZE` is also set.
+    pub const SANITIZE_CC: SanitizeMode = SanitizeMode(2);
+    /// Skip whitespace at the start of the string, after replacing control
+    /// characters.
+    pub const SKIP_START_WHITESPACES: SanitizeMode = SanitizeMode(4);
+
+    pub fn contains(self, other: SanitizeMode) -> bool {
+        self.0 & other.0 == other.0
+    }
+    fn replaces(self, byte: u8) -> bool {
+        if self.contains(SanitizeMode::SANITIZE) {
+            byte < b' ' && byte != b'\r' && byte != b'\n' && byte != b'\t'
+        } else if self.contains(SanitizeMode::SANITIZE_CC) {
+            byte < b' '
+        } else {
+            false
+        }
+    }
+    fn skip(self, string: &[u8]) -> usize {
+        if !self.contains(SanitizeMode::SKIP_START_WHITESPACES) {
+            return 0;
+        }
+        string
+            .iter()
+            .position(|&b| match b {
+                b' ' | b'\t' | b'\n' | b'\r' => false,
+                b => !self.replaces(b),
+            })
+            .unwrap_or(string.len())
+    }
+}
+
+impl ops::BitOr for SanitizeMode {
+    type Output = SanitizeMode;
+    fn bitor(self, other: SanitizeMode) -> SanitizeMode {
+        SanitizeMode(self.0 | other.0)
+    }
+}
+
+/// Sanitizes a string the same way the reference implementation does.
+///
+/// Only allocates if characters need to be replaced.
+pub fn sanitize_string(mode: SanitizeMode, string: &[u8]) -> Cow<'_, [u8]> {
+    let string = &string[mode.skip(string)..];
+    if !string.iter().any(|&b| mode.replaces(b)) {
+        return Cow::Borrowed(string);
+    }
+    Cow::Owned(
+        string
+            .iter()
+            .map(|&b| if mode.replaces(b) { b' ' } else { b })
+            .collect(),
+    )
+}
+
+/// Checks whether the string is unchanged by `sanitize_string`.
+pub fn is_sanitized(mode: SanitizeMode, string: &[u8]) -> bool {
+    mode.skip(string) == 0 && !string.iter().any(|&b| mode.replaces(b))
+}
+
+/// Checks that the string fits into a buffer of `max + 1` bytes in the
+/// reference implementation, including the NUL terminator.
+pub fn max_length(v: &[u8], max: usize) -> Result<&[u8], StringTooLong> {
+    if v.len() <= max {
+        Ok(v)
+    } else {
+        Err(StringTooLong)
+    }
+}
+
 pub fn positive(v: i32) -> Result<i32, IntOutOfRange> {
     if v >= 0 {
         Ok(v)
@@ -428,10 +981,13 @@ fn string_to_bytes_buffer_ref<'d, 's>(
 mod test {
     use arrayvec::ArrayVec;
     use std::i32;
+    use std::i64;
+    use super::UnexpectedEnd;
     use super::Unpacker;
     use super::Warning::*;
     use super::Warning;
     use super::with_packer;
+    use buffer::CapacityError;
     use warn::Ignore;
     use warn::Panic;
 
@@ -510,6 +1066,252 @@ mod test {
     #[test] fn str_rest3() { assert_str(b"abc\0\0", b"abc", b"\0") }
     #[test] fn str_rest4() { assert_str(b"\0\0", b"", b"\0") }
 
+    fn assert_int64_warnings(bytes: &[u8], int: i64, warnings: &[Warning]) {
+        let mut vec = vec![];
+        let mut unpacker = Unpacker::new(bytes);
+        assert_eq!(unpacker.read_int64(&mut vec).unwrap(), int);
+        assert!(unpacker.as_slice().is_empty());
+        assert_eq!(vec, warnings);
+
+        if warnings.is_empty() {
+            let mut buf: ArrayVec<[u8; 10]> = ArrayVec::new();
+            let written = with_packer(&mut buf, |mut p| {
+                p.write_int64(int).unwrap();
+                p.written()
+            });
+            assert_eq!(written, bytes);
+        }
+    }
+
+    fn assert_int64(bytes: &[u8], int: i64) {
+        assert_int64_warnings(bytes, int, &[])
+    }
+
+    fn encoded_int64_len(int: i64) -> usize {
+        let mut buf: ArrayVec<[u8; 10]> = ArrayVec::new();
+        with_packer(&mut buf, |mut p| {
+            p.write_int64(int).unwrap();
+            p.written().len()
+        })
+    }
+
+    #[test] fn int64_0() { assert_int64(b"\x00", 0) }
+    #[test] fn int64_m1() { assert_int64(b"\x40", -1) }
+    #[test] fn int64_64() { assert_int64(b"\x80\x01", 64) }
+    #[test] fn int64_i32_max() { assert_int64(b"\xbf\xff\xff\xff\x0f", i32::MAX as i64) }
+    #[test] fn int64_i32_max_p1() { assert_int64(b"\x80\x80\x80\x80\x10", i32::MAX as i64 + 1) }
+    #[test] fn int64_i32_min() { assert_int64(b"\xff\xff\xff\xff\x0f", i32::MIN as i64) }
+    #[test] fn int64_max() { assert_int64(b"\xbf\xff\xff\xff\xff\xff\xff\xff\xff\x01", i64::MAX) }
+    #[test] fn int64_min() { assert_int64(b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01", i64::MIN) }
+    #[test] fn int64_overlong() { assert_int64_warnings(b"\x80\x00", 0, &[OverlongIntEncoding]) }
+    #[test] fn int64_padding() { assert_int64_warnings(b"\xbf\xff\xff\xff\xff\xff\xff\xff\xff\x03", i64::MAX, &[NonZeroIntPadding]) }
+    #[test] fn int64_unexpected_end() {
+        Unpacker::new(b"\x80\x80\x80\x80\x80\x80\x80\x80\x80").read_int64(&mut Panic).unwrap_err();
+    }
+
+    #[test]
+    fn int64_length_boundaries() {
+        // The n-th byte adds 7 bits on top of the 6 bits of the first one.
+        for n in 0..9 {
+            let max = (1i64 << (6 + 7 * n)) - 1;
+            assert_eq!(encoded_int64_len(max), n + 1);
+            assert_eq!(encoded_int64_len(-max - 1), n + 1);
+            assert_eq!(encoded_int64_len(max + 1), n + 2);
+            assert_eq!(encoded_int64_len(-max - 2), n + 2);
+        }
+    }
+
+    // Output of `CVariableInt::Pack` of the reference implementation. It only
+    // handles 32-bit integers, so integers outside of that range can't be
+    // checked against it.
+    const REFERENCE_INTS: &[(i32, &[u8])] = &[
+        (0, b"\x00"),
+        (1, b"\x01"),
+        (-1, b"\x40"),
+        (63, b"\x3f"),
+        (-64, b"\x7f"),
+        (64, b"\x80\x01"),
+        (-65, b"\xc0\x01"),
+        (127, b"\xbf\x01"),
+        (128, b"\x80\x02"),
+        (8191, b"\xbf\x7f"),
+        (-8192, b"\xff\x7f"),
+        (8192, b"\x80\x80\x01"),
+        (-8193, b"\xc0\x80\x01"),
+        (1048575, b"\xbf\xff\x7f"),
+        (-1048576, b"\xff\xff\x7f"),
+        (1048576, b"\x80\x80\x80\x01"),
+        (-1048577, b"\xc0\x80\x80\x01"),
+        (134217727, b"\xbf\xff\xff\x7f"),
+        (-134217728, b"\xff\xff\xff\x7f"),
+        (134217728, b"\x80\x80\x80\x80\x01"),
+        (-134217729, b"\xc0\x80\x80\x80\x01"),
+        (1000000000, b"\x80\xa8\xd6\xb9\x07"),
+        (-1000000000, b"\xff\xa7\xd6\xb9\x07"),
+        (2147483647, b"\xbf\xff\xff\xff\x0f"),
+        (-2147483648, b"\xff\xff\xff\xff\x0f"),
+    ];
+
+    #[test]
+    fn int_reference() {
+        for &(int, bytes) in REFERENCE_INTS {
+            assert_int(bytes, int);
+            assert_int64(bytes, int as i64);
+        }
+    }
+
+    #[test]
+    fn raw_and_rest() {
+        let mut unpacker = Unpacker::new(b"\x01abcdef");
+        assert_eq!(unpacker.read_int(&mut Panic), Ok(1));
+        assert_eq!(unpacker.read_raw(2), Ok(&b"ab"[..]));
+        assert_eq!((unpacker.num_bytes_read(), unpacker.remaining()), (3, 4));
+        assert_eq!(unpacker.read_raw(5), Err(UnexpectedEnd));
+        assert_eq!((unpacker.num_bytes_read(), unpacker.remaining()), (7, 0));
+
+        let mut unpacker = Unpacker::new(b"\x01abc");
+        assert_eq!(unpacker.read_int(&mut Panic), Ok(1));
+        assert_eq!(unpacker.read_rest(), Ok(&b"abc"[..]));
+        assert!(unpacker.is_empty());
+        assert_eq!(unpacker.read_rest(), Ok(&b""[..]));
+    }
+
+    #[test]
+    fn sanitize() {
+        use super::sanitize_string;
+        use super::is_sanitized;
+        use super::SanitizeMode;
+
+        let s = b" \x01a\tb\x1f\n\xff";
+        assert_eq!(&*sanitize_string(SanitizeMode::NONE, s), s);
+        assert_eq!(&*sanitize_string(SanitizeMode::SANITIZE, s), b"  a\tb \n\xff");
+        assert_eq!(&*sanitize_string(SanitizeMode::SANITIZE_CC, s), b"  a b  \xff");
+        let mode = SanitizeMode::SANITIZE_CC | SanitizeMode::SKIP_START_WHITESPACES;
+        assert_eq!(&*sanitize_string(mode, s), b"a b  \xff");
+        assert_eq!(&*sanitize_string(SanitizeMode::SKIP_START_WHITESPACES, b"\t \x01a"), b"\x01a");
+
+        assert!(is_sanitized(SanitizeMode::SANITIZE, b"a\tb"));
+        assert!(!is_sanitized(SanitizeMode::SANITIZE_CC, b"a\tb"));
+        assert!(!is_sanitized(SanitizeMode::SKIP_START_WHITESPACES, b" a"));
+
+        let mut unpacker = Unpacker::new(b" \x01a\x00");
+        assert_eq!(&*unpacker.read_string_sanitized(mode).unwrap(), b"a");
+    }
+
+    #[test]
+    fn write_string_sanitized() {
+        use super::SanitizeMode;
+        use super::WriteSanitizedError;
+
+        let mut buf = Vec::with_capacity(16);
+        let written = with_packer(&mut buf, |mut p| {
+            assert_eq!(
+                p.write_string_sanitized(SanitizeMode::SANITIZE_CC, b"a\tb"),
+                Err(WriteSanitizedError::NotSanitized)
+            );
+            p.write_string_sanitized(SanitizeMode::SANITIZE_CC, b"a b")
+                .unwrap();
+            p.written()
+        });
+        assert_eq!(written, b"a b\x00");
+
+        let mut buf = Vec::with_capacity(2);
+        with_packer(&mut buf, |mut p| {
+            assert_eq!(
+                p.write_string_sanitized(SanitizeMode::NONE, b"abc"),
+                Err(WriteSanitizedError::Capacity(CapacityError))
+            );
+        });
+    }
+
+    #[test]
+    fn io_packer() {
+        use super::IoPacker;
+
+        let data: Vec<u8> = (0..3000).map(|i| i as u8).collect();
+        let mut expected = vec![0; 4096];
+        let len = with_packer(&mut expected[..], |mut p| {
+            p.write_int(-1).unwrap();
+            p.write_string(b"abc").unwrap();
+            p.write_data(&data).unwrap();
+            p.write_int64(1 << 40).unwrap();
+            p.written().len()
+        });
+        expected.truncate(len);
+
+        let mut p = IoPacker::new(Vec::new());
+        p.write_int(-1).unwrap();
+        p.write_string(b"abc").unwrap();
+        p.write_data(&data).unwrap();
+        p.write_int64(1 << 40).unwrap();
+        assert_eq!(p.num_bytes_written(), len as u64);
+        assert_eq!(p.finish().unwrap(), expected);
+    }
+
+    #[test]
+    fn rollback() {
+        let mut buf: ArrayVec<[u8; 8]> = ArrayVec::new();
+        let written = with_packer(&mut buf, |mut p| {
+            p.write_int(1).unwrap();
+            let checkpoint = p.checkpoint();
+            p.write_string(b"abc").unwrap();
+            p.rollback(checkpoint);
+            assert_eq!(p.remaining(), 7);
+            // Doesn't fit, nothing is written.
+            assert_eq!(p.try_write(|p| {
+                p.write_int(2)?;
+                p.write_string(b"too long")
+            }), Err(CapacityError));
+            p.try_write(|p| p.write_string(b"de")).unwrap();
+            p.written()
+        });
+        assert_eq!(written, b"\x01de\x00");
+    }
+
+    #[test]
+    fn sub_packer() {
+        let mut buf: ArrayVec<[u8; 8]> = ArrayVec::new();
+        let written = with_packer(&mut buf, |mut p| {
+            p.write_int(1).unwrap();
+            with_packer(&mut p, |mut p| p.write_int(2)).unwrap();
+            p.write_int(3).unwrap();
+            p.written()
+        });
+        assert_eq!(written, b"\x01\x02\x03");
+    }
+
+    #[test]
+    fn strict_policy() {
+        use super::DecodePolicy;
+
+        let mut warnings = vec![];
+        let mut unpacker = Unpacker::new(b"\x80\x00\x01");
+        assert_eq!(unpacker.read_int(&mut warnings), Ok(0));
+        assert_eq!(unpacker.read_int(&mut warnings), Ok(1));
+        assert_eq!(warnings, [OverlongIntEncoding]);
+
+        warnings.clear();
+        let mut unpacker = Unpacker::new(b"\x80\x00\x01").with_policy(DecodePolicy::Strict);
+        assert_eq!(unpacker.read_int(&mut warnings), Err(UnexpectedEnd));
+        assert!(unpacker.is_empty());
+        assert_eq!(warnings, [OverlongIntEncoding]);
+        assert_eq!(unpacker.policy_violation(), Some(OverlongIntEncoding));
+
+        let mut unpacker = Unpacker::new(b"\xff\xff\xff\xff\xff").with_policy(DecodePolicy::Strict);
+        assert_eq!(unpacker.read_int(&mut Ignore), Err(UnexpectedEnd));
+        assert_eq!(unpacker.policy_violation(), Some(NonZeroIntPadding));
+
+        let mut unpacker = Unpacker::new(b"\x01").with_policy(DecodePolicy::Strict);
+        assert_eq!(unpacker.read_int(&mut Ignore), Ok(1));
+        assert_eq!(unpacker.read_int(&mut Ignore), Err(UnexpectedEnd));
+        assert_eq!(unpacker.policy_violation(), None);
+
+        let mut unpacker = Unpacker::new(b"\x01\x00").with_policy(DecodePolicy::Strict);
+        assert_eq!(unpacker.read_int(&mut Ignore), Ok(1));
+        unpacker.finish(&mut Ignore);
+        assert_eq!(unpacker.policy_violation(), Some(ExcessData));
+    }
+
     #[test]
     fn excess_data() {
         let mut warnings = vec![];
@@ -518,7 +1320,83 @@ mod test {
         assert_eq!(warnings, [ExcessData]);
     }
 
+    #[test]
+    fn located_warnings() {
+        use common::warn::Located;
+
+        let mut warnings = vec![];
+        let mut unpacker = Unpacker::new(b"\x01\x80\x00");
+        for _ in 0..2 {
+            unpacker.read_int(&mut unpacker.locate(&mut warnings, "int")).unwrap();
+        }
+        assert_eq!(warnings, [Located {
+            offset: 1,
+            structure: "int",
+            warning: OverlongIntEncoding,
+        }]);
+    }
+
+    #[test]
+    fn ints_batch() {
+        let ints = [0, -1, 63, 64, -65, i32::MAX, i32::MIN, 5];
+        let mut buf: ArrayVec<[u8; 64]> = ArrayVec::new();
+        let written = with_packer(&mut buf, |mut p| {
+            p.write_ints(&ints).unwrap();
+            p.written()
+        });
+        let mut buf2: ArrayVec<[u8; 64]> = ArrayVec::new();
+        let expected = with_packer(&mut buf2, |mut p| {
+            for &i in &ints {
+                p.write_int(i).unwrap();
+            }
+            p.written()
+        });
+        assert_eq!(written, expected);
+        let mut read = [0; 8];
+        let mut unpacker = Unpacker::new(written);
+        unpacker.read_ints(&mut Panic, &mut read).unwrap();
+        assert_eq!(read, ints);
+        assert!(unpacker.is_empty());
+        assert_eq!(unpacker.read_ints(&mut Panic, &mut [0]), Err(UnexpectedEnd));
+    }
+
+    #[test]
+    fn ints_batch_capacity() {
+        // Exactly fits, the fast path mustn't write beyond the buffer.
+        let mut buf: ArrayVec<[u8; 8]> = ArrayVec::new();
+        with_packer(&mut buf, |mut p| {
+            p.write_ints(&[1, i32::MAX, 64]).unwrap();
+            assert_eq!(p.remaining(), 0);
+        });
+        let mut buf: ArrayVec<[u8; 7]> = ArrayVec::new();
+        let written = with_packer(&mut buf, |mut p| {
+            p.write_int(1).unwrap();
+            assert_eq!(p.write_ints(&[i32::MAX, 64]), Err(CapacityError));
+            p.written()
+        });
+        assert_eq!(written, b"\x01");
+    }
+
+    #[test]
+    fn ints_batch_warnings() {
+        let mut warnings = vec![];
+        let mut read = [0; 2];
+        Unpacker::new(b"\x01\x80\x00").read_ints(&mut warnings, &mut read).unwrap();
+        assert_eq!(read, [1, 0]);
+        assert_eq!(warnings, [OverlongIntEncoding]);
+    }
+
     quickcheck! {
+        fn ints_batch_compatible(ints: Vec<i32>) -> bool {
+            let mut buf = Vec::with_capacity(ints.len() * 5);
+            let written = with_packer(&mut buf, |mut p| {
+                p.write_ints(&ints).unwrap();
+                p.written()
+            }).to_vec();
+            let mut unpacker = Unpacker::new(&written);
+            ints.iter().all(|&i| unpacker.read_int(&mut Panic) == Ok(i)) && unpacker.is_empty()
+        }
+
         fn int_roundtrip(int: i32) -> bool {
             let mut buf: ArrayVec<[u8; 5]> = ArrayVec::new();
             let mut unpacker = Unpacker::new(with_packer(&mut buf, |mut p| {
@@ -529,6 +1407,32 @@ mod test {
             int == read_int && unpacker.as_slice().is_empty()
         }
 
+        fn int64_roundtrip(int: i64) -> bool {
+            let mut buf: ArrayVec<[u8; 10]> = ArrayVec::new();
+            let mut unpacker = Unpacker::new(with_packer(&mut buf, |mut p| {
+                p.write_int64(int).unwrap();
+                p.written()
+            }));
+            let read_int = unpacker.read_int64(&mut Panic).unwrap();
+            int == read_int && unpacker.as_slice().is_empty()
+        }
+
+        fn int64_compatible(int: i32) -> bool {
+            let mut buf: ArrayVec<[u8; 5]> = ArrayVec::new();
+            let mut unpacker = Unpacker::new(with_packer(&mut buf, |mut p| {
+                p.write_int(int).unwrap();
+                p.written()
+            }));
+            let read_int = unpacker.read_int64(&mut Panic).unwrap();
+            int as i64 == read_int && unpacker.as_slice().is_empty()
+        }
+
+        fn int64_no_panic(data: Vec<u8>) -> bool {
+            let mut unpacker = Unpacker::new(&data);
+            let _ = unpacker.read_int64(&mut Ignore);
+            true
+        }
+
         fn int_no_panic(data: Vec<u8>) -> bool {
             let mut unpacker = Unpacker::new(&data);
             let _ = unpacker.read_int(&mut Ignore);
@@ -0,0 +1,137 @@
+//! Name-based UUIDs and their mapping to small indices, as used by DDNet for
+//! extended messages, map items and teehistorian chunks.
+
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Namespace of the UUIDs derived from names.
+pub const TEEWORLDS_NAMESPACE: Uuid = Uuid::from_u128(0xe05ddaaa_c4e6_4cfb_b642_5d48e80c0029);
+
+/// First index handed out by `UuidRegistry`, so that the indices don't
+/// collide with the ordinary message IDs.
+pub const OFFSET_UUID: i32 = 1 << 16;
+
+/// Derives the UUID for a name like `what-is@ddnet.tw`.
+///
+/// This is an MD5-based (version 3) UUID in the `TEEWORLDS_NAMESPACE`.
+pub fn uuid_from_name(name: &[u8]) -> Uuid {
+    Uuid::new_v3(&TEEWORLDS_NAMESPACE, name)
+}
+
+/// Mapping between UUIDs and indices starting at `OFFSET_UUID`.
+///
+/// Indices are assigned in registration order, so both sides of a connection
+/// agree on them if they register the same names in the same order.
+#[derive(Clone, Debug, Default)]
+pub struct UuidRegistry {
+    entries: Vec<(Uuid, &'static str)>,
+    indices: HashMap<Uuid, i32>,
+}
+
+impl UuidRegistry {
+    pub fn new() -> UuidRegistry {
+        Default::default()
+    }
+    /// Registers `name` and returns its index.
+    ///
+    /// Panics if the name or its UUID is already registered.
+    pub fn register(&mut self, name: &'static str) -> i32 {
+        self.register_uuid(uuid_from_name(name.as_bytes()), name)
+    }
+    /// Registers a UUID that isn't derived from its name.
+    ///
+    /// Panics if the UUID is already registered.
+    pub fn register_uuid(&mut self, uuid: Uuid, name: &'static str) -> i32 {
+        let index = OFFSET_UUID + self.entries.len() as i32;
+        assert!(
+            self.indices.insert(uuid, index).is_none(),
+            "UUID {} ({}) registered twice",
+            uuid,
+            name
+        );
+        self.entries.push((uuid, name));
+        index
+    }
+    /// Returns the index of a registered UUID.
+    pub fn lookup(&self, uuid: Uuid) -> Option<i32> {
+        self.indices.get(&uuid).cloned()
+    }
+    fn entry(&self, index: i32) -> Option<&(Uuid, &'static str)> {
+        if index < OFFSET_UUID {
+            return None;
+        }
+        self.entries.get((index - OFFSET_UUID) as usize)
+    }
+    /// Returns the UUID registered at `index`.
+    pub fn uuid(&self, index: i32) -> Option<Uuid> {
+        self.entry(index).map(|&(uuid, _)| uuid)
+    }
+    /// Returns the name of the UUID registered at `index`.
+    pub fn name(&self, index: i32) -> Option<&'static str> {
+        self.entry(index).map(|&(_, name)| name)
+    }
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    /// Iterates over `(index, uuid, name)`, in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = (i32, Uuid, &'static str)> + '_ {
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(i, &(uuid, name))| (OFFSET_UUID + i as i32, uuid, name))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::uuid_from_name;
+    use super::UuidRegistry;
+    use super::OFFSET_UUID;
+    use uuid::Uuid;
+
+    const WHAT_IS: Uuid = Uuid::from_u128(0x245e5097_9fe0_39d6_bf7d_9a29e1691e4c);
+    const SHOW_DISTANCE: Uuid = Uuid::from_u128(0x53bb28af_4252_3ac9_8fd3_6ccbc2a603e3);
+
+    #[test]
+    fn from_name() {
+        assert_eq!(uuid_from_name(b"what-is@ddnet.tw"), WHAT_IS);
+        assert_eq!(
+            uuid_from_name(b"show-distance@netmsg.ddnet.tw"),
+            SHOW_DISTANCE
+        );
+    }
+
+    #[test]
+    fn registry() {
+        let mut registry = UuidRegistry::new();
+        assert_eq!(registry.register("what-is@ddnet.tw"), OFFSET_UUID);
+        assert_eq!(
+            registry.register("show-distance@netmsg.ddnet.tw"),
+            OFFSET_UUID + 1
+        );
+        assert_eq!(registry.lookup(SHOW_DISTANCE), Some(OFFSET_UUID + 1));
+        assert_eq!(registry.lookup(Uuid::nil()), None);
+        assert_eq!(registry.uuid(OFFSET_UUID), Some(WHAT_IS));
+        assert_eq!(
+            registry.name(OFFSET_UUID + 1),
+            Some("show-distance@netmsg.ddnet.tw")
+        );
+        assert_eq!(registry.uuid(OFFSET_UUID + 2), None);
+        assert_eq!(registry.uuid(0), None);
+        assert_eq!(
+            registry.iter().map(|(i, _, _)| i).collect::<Vec<_>>(),
+            [OFFSET_UUID, OFFSET_UUID + 1]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn register_twice() {
+        let mut registry = UuidRegistry::new();
+        registry.register("what-is@ddnet.tw");
+        registry.register_uuid(WHAT_IS, "what-is");
+    }
+}
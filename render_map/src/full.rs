@@ -0,0 +1,622 @@
+//! Rendering of all visible layers, including quads and parallax groups, or
+//! of just the game layer.
+
+use common::num::Cast;
+use map::ddnet::TILE_DEATH;
+use map::ddnet::TILE_NOHOOK;
+use map::ddnet::TILE_SOLID;
+use map::envelope::Envelope;
+use map::format;
+use map::model;
+use map::model::LayerKind;
+use map::model::Tiles;
+use ndarray::Array2;
+use std::collections::hash_map;
+use std::collections::HashMap;
+
+use normalize_tileset;
+use transform_coordinates;
+use Color;
+use Config;
+use Error;
+use OwnError;
+use Rect;
+use TILE_NUM;
+
+/// World units per tile.
+const TILE_UNITS: f32 = 32.0;
+
+/// Size of a stored quad in `i32`s: 5 points, 4 colors, 4 texture
+/// coordinates, position and color envelope with their offsets.
+const QUAD_LEN: usize = 5 * 2 + 4 * 4 + 4 * 2 + 4;
+
+struct Quad {
+    /// Corners and center, in world units.
+    points: [(f32, f32); 5],
+    colors: [[f32; 4]; 4],
+    texcoords: [(f32, f32); 4],
+    pos_env: Option<(usize, i32)>,
+    color_env: Option<(usize, i32)>,
+}
+
+fn fx2f(v: i32) -> f32 {
+    v as f32 / 1024.0
+}
+
+fn quads(layer: &model::QuadsLayer) -> Vec<Quad> {
+    let ints: Vec<i32> = layer
+        .data
+        .chunks(4)
+        .filter(|c| c.len() == 4)
+        .map(|c| i32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect();
+    let env = |i: i32, offset: i32| i.try_usize().map(|i| (i, offset));
+    ints.chunks(QUAD_LEN)
+        .take(layer.num_quads)
+        .filter(|q| q.len() == QUAD_LEN)
+        .map(|q| {
+            let mut quad = Quad {
+                points: [(0.0, 0.0); 5],
+                colors: [[0.0; 4]; 4],
+                texcoords: [(0.0, 0.0); 4],
+                pos_env: env(q[34], q[35]),
+                color_env: env(q[36], q[37]),
+            };
+            for (i, p) in quad.points.iter_mut().enumerate() {
+                *p = (fx2f(q[2 * i]), fx2f(q[2 * i + 1]));
+            }
+            for (i, c) in quad.colors.iter_mut().enumerate() {
+                for (k, v) in c.iter_mut().enumerate() {
+                    *v = q[10 + 4 * i + k] as f32 / 255.0;
+                }
+            }
+            for (i, t) in quad.texcoords.iter_mut().enumerate() {
+                *t = (fx2f(q[26 + 2 * i]), fx2f(q[26 + 2 * i + 1]));
+            }
+            quad
+        })
+        .collect()
+}
+
+/// Values of the envelope at the start of the map, shifted by its time
+/// offset, `default` if there's no envelope.
+fn envelope_at_start(
+    envelopes: &[Envelope],
+    env: Option<(usize, i32)>,
+    default: [f32; 4],
+) -> [f32; 4] {
+    env.and_then(|(i, offset)| envelopes.get(i).map(|e| e.evaluate(offset.into())))
+        .unwrap_or(default)
+}
+
+fn to_u8(v: f32) -> u8 {
+    (v.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn tint(color: Color, tint: [f32; 4]) -> Color {
+    Color {
+        red: to_u8(color.red as f32 / 255.0 * tint[0]),
+        green: to_u8(color.green as f32 / 255.0 * tint[1]),
+        blue: to_u8(color.blue as f32 / 255.0 * tint[2]),
+        alpha: to_u8(color.alpha as f32 / 255.0 * tint[3]),
+    }
+}
+
+/// Canvas covering the rendered rectangle, with `tile_len` pixels per tile.
+struct Canvas {
+    pixels: Array2<Color>,
+    /// World position of the top left pixel.
+    origin: (f32, f32),
+    /// Pixels per world unit.
+    scale: f32,
+    /// Pixel rectangle that may be drawn on, for group clipping.
+    clip: Rect,
+}
+
+impl Canvas {
+    fn blend(&mut self, x: i64, y: i64, color: Color) {
+        let c = &self.clip;
+        let (min_x, min_y) = (c.min_x.i64(), c.min_y.i64());
+        let (max_x, max_y) = (c.max_x.i64(), c.max_y.i64());
+        if min_x <= x && x < max_x && min_y <= y && y < max_y {
+            let p = &mut self.pixels[(y.assert_usize(), x.assert_usize())];
+            *p = p.overlay_with(color);
+        }
+    }
+    fn to_pixels(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        (
+            (x - self.origin.0) * self.scale,
+            (y - self.origin.1) * self.scale,
+        )
+    }
+    /// Restricts drawing to the clipping rectangle of the group. It is in
+    /// world coordinates and doesn't move with the parallax.
+    fn clip_to(&mut self, group: &model::Group) {
+        let (height, width) = self.pixels.dim();
+        self.clip = Rect {
+            min_x: 0,
+            min_y: 0,
+            max_x: width.assert_u32(),
+            max_y: height.assert_u32(),
+        };
+        if let Some(c) = group.clipping {
+            let (min_x, min_y) = self.to_pixels((c.x as f32, c.y as f32));
+            let max = (c.x as f32 + c.width as f32, c.y as f32 + c.height as f32);
+            let (max_x, max_y) = self.to_pixels(max);
+            let clamp = |v: f32, max: u32| v.max(0.0).min(max as f32) as u32;
+            self.clip = Rect {
+                min_x: clamp(min_x, self.clip.max_x),
+                min_y: clamp(min_y, self.clip.max_y),
+                max_x: clamp(max_x, self.clip.max_x),
+                max_y: clamp(max_y, self.clip.max_y),
+            };
+        }
+    }
+}
+
+fn draw_tiles(
+    canvas: &mut Canvas,
+    tiles: &Array2<format::Tile>,
+    tileset: &Array2<Color>,
+    color: Color,
+    shift: (f32, f32),
+    tile_len: u32,
+) {
+    for ((y, x), tile) in tiles.indexed_iter() {
+        let tile_x = tile.index.u32() % TILE_NUM;
+        let tile_y = tile.index.u32() / TILE_NUM;
+        // First tile is guaranteed to be empty (air)
+        if tile_x == 0 && tile_y == 0 {
+            continue;
+        }
+        let world = (
+            x as f32 * TILE_UNITS + shift.0,
+            y as f32 * TILE_UNITS + shift.1,
+        );
+        let (px, py) = canvas.to_pixels(world);
+        let (px, py) = (px.round() as i64, py.round() as i64);
+        let rotate = tile.flags & format::TILEFLAG_ROTATE != 0;
+        let vflip = tile.flags & format::TILEFLAG_VFLIP != 0;
+        let hflip = tile.flags & format::TILEFLAG_HFLIP != 0;
+        for iy in 0..tile_len {
+            for ix in 0..tile_len {
+                let (sy, sx) = transform_coordinates((iy, ix), rotate, vflip, hflip, tile_len);
+                let p = tileset[(
+                    (tile_y * tile_len + sy).usize(),
+                    (tile_x * tile_len + sx).usize(),
+                )];
+                canvas.blend(px + ix.i64(), py + iy.i64(), p.mask(color));
+            }
+        }
+    }
+}
+
+fn rotate((x, y): (f32, f32), (cx, cy): (f32, f32), degrees: f32) -> (f32, f32) {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let (dx, dy) = (x - cx, y - cy);
+    (cx + dx * cos - dy * sin, cy + dx * sin + dy * cos)
+}
+
+fn draw_quad(
+    canvas: &mut Canvas,
+    quad: &Quad,
+    texture: &Array2<Color>,
+    envelopes: &[Envelope],
+    shift: (f32, f32),
+) {
+    let pos = envelope_at_start(envelopes, quad.pos_env, [0.0; 4]);
+    let color = envelope_at_start(envelopes, quad.color_env, [1.0; 4]);
+    let mut points = [(0.0, 0.0); 4];
+    for (p, &q) in points.iter_mut().zip(&quad.points[..4]) {
+        let q = rotate(q, quad.points[4], pos[2]);
+        *p = canvas.to_pixels((q.0 + pos[0] + shift.0, q.1 + pos[1] + shift.1));
+    }
+    // The corners are in "Z" order, the quad is drawn as two triangles.
+    for &[a, b, c] in &[[0, 1, 3], [0, 3, 2]] {
+        let (pa, pb, pc) = (points[a], points[b], points[c]);
+        let area = (pb.0 - pa.0) * (pc.1 - pa.1) - (pc.0 - pa.0) * (pb.1 - pa.1);
+        if area == 0.0 {
+            continue;
+        }
+        let (height, width) = canvas.pixels.dim();
+        let min = |a: f32, b: f32, c: f32| a.min(b).min(c).max(0.0).floor() as usize;
+        let max =
+            |a: f32, b: f32, c: f32, m: usize| (a.max(b).max(c).ceil().max(0.0) as usize).min(m);
+        for y in min(pa.1, pb.1, pc.1)..max(pa.1, pb.1, pc.1, height) {
+            for x in min(pa.0, pb.0, pc.0)..max(pa.0, pb.0, pc.0, width) {
+                let (fx, fy) = (x as f32 + 0.5, y as f32 + 0.5);
+                let wa = ((pb.0 - fx) * (pc.1 - fy) - (pc.0 - fx) * (pb.1 - fy)) / area;
+                let wb = ((pc.0 - fx) * (pa.1 - fy) - (pa.0 - fx) * (pc.1 - fy)) / area;
+                let wc = 1.0 - wa - wb;
+                if wa < 0.0 || wb < 0.0 || wc < 0.0 {
+                    continue;
+                }
+                let mix = |f: &dyn Fn(usize) -> f32| wa * f(a) + wb * f(b) + wc * f(c);
+                let u = mix(&|i| quad.texcoords[i].0);
+                let v = mix(&|i| quad.texcoords[i].1);
+                let (th, tw) = texture.dim();
+                let tx = ((u * tw as f32).floor() as i64).rem_euclid(tw as i64) as usize;
+                let ty = ((v * th as f32).floor() as i64).rem_euclid(th as i64) as usize;
+                let mut tint_color = [0.0; 4];
+                for (k, t) in tint_color.iter_mut().enumerate() {
+                    *t = mix(&|i| quad.colors[i][k]) * color[k];
+                }
+                canvas.blend(
+                    x.assert_i64(),
+                    y.assert_i64(),
+                    tint(texture[(ty, tx)], tint_color),
+                );
+            }
+        }
+    }
+}
+
+fn image_data<E>(
+    map: &model::Map,
+    index: Option<usize>,
+    external_image_loader: &mut E,
+) -> Result<Array2<Color>, Error>
+where
+    E: FnMut(&str) -> Result<Option<Array2<Color>>, Error>,
+{
+    let white = || Array2::from_elem((1, 1), Color::white());
+    let image = match index.and_then(|i| map.images.get(i)) {
+        Some(i) => i,
+        None => return Ok(white()),
+    };
+    match image.data {
+        Some(ref d) => {
+            let shape = (image.height.usize(), image.width.usize());
+            let data = d
+                .chunks(4)
+                .map(|c| Color {
+                    red: c[0],
+                    green: c[1],
+                    blue: c[2],
+                    alpha: c[3],
+                })
+                .collect();
+            Ok(Array2::from_shape_vec(shape, data).map_err(|_| OwnError::ImageShape)?)
+        }
+        None => Ok(::std::str::from_utf8(&image.name)
+            .ok()
+            .and_then(::sanitize)
+            .map(external_image_loader)
+            .transpose()?
+            .unwrap_or(None)
+            .unwrap_or_else(white)),
+    }
+}
+
+fn game_tiles(map: &model::Map) -> Option<&Array2<format::Tile>> {
+    map.groups
+        .iter()
+        .flat_map(|g| &g.layers)
+        .filter_map(|l| match l.kind {
+            LayerKind::Tiles(ref t) => match t.tiles {
+                Tiles::Game(ref tiles) => Some(tiles),
+                _ => None,
+            },
+            _ => None,
+        })
+        .next()
+}
+
+/// Renders all visible layers of the map, as seen from the center of the
+/// crop rectangle, or of the game layer if there's none.
+///
+/// The result has `config.zoom` pixels per tile, 16 if it's not set.
+pub fn render<E>(
+    map: &model::Map,
+    config: &Config,
+    external_image_loader: &mut E,
+) -> Result<Array2<Color>, Error>
+where
+    E: FnMut(&str) -> Result<Option<Array2<Color>>, Error>,
+{
+    let crop = match config.crop {
+        Some(c) => c,
+        None => {
+            let game = game_tiles(map).ok_or(OwnError::EmptyMap)?;
+            Rect {
+                min_x: 0,
+                min_y: 0,
+                max_x: game.dim().1.assert_u32(),
+                max_y: game.dim().0.assert_u32(),
+            }
+        }
+    };
+    let scale = config.zoom.unwrap_or(16);
+    if config.game_layer {
+        render_game_layer(map, &crop, scale)
+    } else {
+        render_region(
+            map,
+            &crop,
+            scale,
+            config.render_detail,
+            external_image_loader,
+        )
+    }
+}
+
+/// Renders the tiles in `rect` of all visible layers, as seen from the
+/// center of `rect`, with `scale` pixels per tile.
+///
+/// Envelopes are evaluated at the start of the map.
+pub fn render_region<E>(
+    map: &model::Map,
+    rect: &Rect,
+    scale: u32,
+    render_detail: bool,
+    external_image_loader: &mut E,
+) -> Result<Array2<Color>, Error>
+where
+    E: FnMut(&str) -> Result<Option<Array2<Color>>, Error>,
+{
+    if rect.is_empty() {
+        return Err(OwnError::EmptyMap.into());
+    }
+    let tile_len = scale;
+    let width = rect.width().checked_mul(tile_len).unwrap();
+    let height = rect.height().checked_mul(tile_len).unwrap();
+    let mut canvas = Canvas {
+        pixels: Array2::default((height.usize(), width.usize())),
+        origin: (
+            rect.min_x as f32 * TILE_UNITS,
+            rect.min_y as f32 * TILE_UNITS,
+        ),
+        scale: tile_len as f32 / TILE_UNITS,
+        clip: Rect {
+            min_x: 0,
+            min_y: 0,
+            max_x: width,
+            max_y: height,
+        },
+    };
+    let center = (
+        (rect.min_x + rect.max_x) as f32 / 2.0 * TILE_UNITS,
+        (rect.min_y + rect.max_y) as f32 / 2.0 * TILE_UNITS,
+    );
+
+    let mut tilesets: HashMap<Option<usize>, Array2<Color>> = HashMap::new();
+    let mut textures: HashMap<Option<usize>, Array2<Color>> = HashMap::new();
+    for group in &map.groups {
+        // Position of the group relative to the game group, for a camera
+        // in the center.
+        let shift = (
+            group.offset_x as f32 + center.0 * (100 - group.parallax_x) as f32 / 100.0,
+            group.offset_y as f32 + center.1 * (100 - group.parallax_y) as f32 / 100.0,
+        );
+        canvas.clip_to(group);
+        for layer in &group.layers {
+            if layer.detail && !render_detail {
+                continue;
+            }
+            match layer.kind {
+                LayerKind::Tiles(ref t) => {
+                    let tiles = match t.tiles {
+                        Tiles::Normal(ref tiles) => tiles,
+                        _ => continue,
+                    };
+                    if let hash_map::Entry::Vacant(v) = tilesets.entry(t.image) {
+                        let data = image_data(map, t.image, external_image_loader)?;
+                        v.insert(normalize_tileset(data, tile_len));
+                    }
+                    let env = envelope_at_start(&map.envelopes, t.color_env, [1.0; 4]);
+                    let color = tint(t.color.into(), env);
+                    draw_tiles(
+                        &mut canvas,
+                        tiles,
+                        &tilesets[&t.image],
+                        color,
+                        shift,
+                        tile_len,
+                    );
+                }
+                LayerKind::Quads(ref q) => {
+                    if let hash_map::Entry::Vacant(v) = textures.entry(q.image) {
+                        v.insert(image_data(map, q.image, external_image_loader)?);
+                    }
+                    for quad in quads(q) {
+                        draw_quad(
+                            &mut canvas,
+                            &quad,
+                            &textures[&q.image],
+                            &map.envelopes,
+                            shift,
+                        );
+                    }
+                }
+                LayerKind::Sounds(_) => {}
+            }
+        }
+    }
+    Ok(canvas.pixels)
+}
+
+const GAME_AIR: Color = Color {
+    red: 0xe0,
+    green: 0xe8,
+    blue: 0xf0,
+    alpha: 0xff,
+};
+const GAME_SOLID: Color = Color {
+    red: 0x40,
+    green: 0x40,
+    blue: 0x40,
+    alpha: 0xff,
+};
+const GAME_NOHOOK: Color = Color {
+    red: 0x80,
+    green: 0x80,
+    blue: 0x80,
+    alpha: 0xff,
+};
+const GAME_DEATH: Color = Color {
+    red: 0xd0,
+    green: 0x20,
+    blue: 0x20,
+    alpha: 0xff,
+};
+
+/// Renders the tiles in `rect` of the game layer only, with one flat color
+/// for air, solid, unhookable and death tiles each, and `scale` pixels per
+/// tile.
+///
+/// Much faster than `render_region`, and doesn't need any images. Tiles
+/// outside of the map are transparent.
+pub fn render_game_layer(
+    map: &model::Map,
+    rect: &Rect,
+    scale: u32,
+) -> Result<Array2<Color>, Error> {
+    let game = game_tiles(map).ok_or(OwnError::EmptyMap)?;
+    if rect.is_empty() {
+        return Err(OwnError::EmptyMap.into());
+    }
+    let width = rect.width().checked_mul(scale).unwrap();
+    let height = rect.height().checked_mul(scale).unwrap();
+    Ok(Array2::from_shape_fn(
+        (height.usize(), width.usize()),
+        |(y, x)| {
+            let tile_y = (rect.min_y + y.assert_u32() / scale).usize();
+            let tile_x = (rect.min_x + x.assert_u32() / scale).usize();
+            match game.get((tile_y, tile_x)).map(|t| t.index) {
+                None => Color::transparent(),
+                Some(TILE_SOLID) => GAME_SOLID,
+                Some(TILE_NOHOOK) => GAME_NOHOOK,
+                Some(TILE_DEATH) => GAME_DEATH,
+                Some(_) => GAME_AIR,
+            }
+        },
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use map::format::Tile;
+    use map::model;
+    use map::model::Layer;
+    use map::model::LayerKind;
+    use map::model::TileLayer;
+    use map::model::Tiles;
+    use map::reader;
+    use ndarray::Array2;
+
+    use super::render_game_layer;
+    use super::render_region;
+    use super::GAME_AIR;
+    use super::GAME_DEATH;
+    use super::GAME_NOHOOK;
+    use super::GAME_SOLID;
+    use map::ddnet::TILE_DEATH;
+    use map::ddnet::TILE_NOHOOK;
+    use map::ddnet::TILE_SOLID;
+    use Color;
+    use Error;
+    use Rect;
+
+    fn tiles(indices: &[[u8; 2]; 2]) -> Array2<Tile> {
+        Array2::from_shape_fn((2, 2), |(y, x)| Tile {
+            index: indices[y][x],
+            ..Tile::default()
+        })
+    }
+
+    fn tile_layer(tiles: Tiles) -> Layer {
+        let white = reader::Color {
+            red: 255,
+            green: 255,
+            blue: 255,
+            alpha: 255,
+        };
+        Layer {
+            name: vec![],
+            detail: false,
+            kind: LayerKind::Tiles(TileLayer {
+                color: white,
+                color_env: None,
+                image: None,
+                tiles,
+            }),
+        }
+    }
+
+    fn map() -> model::Map {
+        let game = [[0, TILE_SOLID], [TILE_NOHOOK, TILE_DEATH]];
+        let mut map = model::Map::default();
+        map.groups.push(model::Group {
+            name: b"Game".to_vec(),
+            offset_x: 0,
+            offset_y: 0,
+            parallax_x: 100,
+            parallax_y: 100,
+            clipping: None,
+            layers: vec![
+                tile_layer(Tiles::Game(tiles(&game))),
+                tile_layer(Tiles::Normal(tiles(&[[0, 1], [0, 0]]))),
+            ],
+        });
+        map
+    }
+
+    fn rect(min_x: u32, min_y: u32, max_x: u32, max_y: u32) -> Rect {
+        Rect {
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+        }
+    }
+
+    fn no_external(_: &str) -> Result<Option<Array2<Color>>, Error> {
+        panic!("no external images expected");
+    }
+
+    #[test]
+    fn game_layer() {
+        let pixels = render_game_layer(&map(), &rect(0, 0, 3, 2), 2).unwrap();
+        assert_eq!(pixels.dim(), (4, 6));
+        assert_eq!(pixels[(0, 0)], GAME_AIR);
+        assert_eq!(pixels[(1, 3)], GAME_SOLID);
+        assert_eq!(pixels[(2, 1)], GAME_NOHOOK);
+        assert_eq!(pixels[(3, 2)], GAME_DEATH);
+        // Outside of the map.
+        assert_eq!(pixels[(0, 4)], Color::transparent());
+    }
+
+    #[test]
+    fn game_layer_region() {
+        let pixels = render_game_layer(&map(), &rect(1, 1, 2, 2), 3).unwrap();
+        assert_eq!(pixels.dim(), (3, 3));
+        assert!(pixels.iter().all(|&p| p == GAME_DEATH));
+    }
+
+    #[test]
+    fn region() {
+        let map = map();
+        let pixels = render_region(&map, &rect(0, 0, 2, 1), 4, true, &mut no_external).unwrap();
+        assert_eq!(pixels.dim(), (4, 8));
+        for ((_, x), &p) in pixels.indexed_iter() {
+            // The first tile of a tileset is always air.
+            let expected = if x < 4 {
+                Color::transparent()
+            } else {
+                Color::white()
+            };
+            assert_eq!(p, expected);
+        }
+
+        let pixels = render_region(&map, &rect(1, 0, 2, 1), 4, true, &mut no_external).unwrap();
+        assert_eq!(pixels.dim(), (4, 4));
+        assert!(pixels.iter().all(|&p| p == Color::white()));
+    }
+
+    #[test]
+    fn empty() {
+        assert!(render_game_layer(&map(), &rect(1, 1, 1, 2), 1).is_err());
+        assert!(render_region(&map(), &rect(0, 0, 0, 0), 1, true, &mut no_external).is_err());
+        assert!(render_game_layer(&model::Map::default(), &rect(0, 0, 1, 1), 1).is_err());
+    }
+}
@@ -0,0 +1,1039 @@
+//! A skeleton for Teeworlds 0.6 servers.
+//!
+//! `Server` is an `Application` of the event loop. It manages the client
+//! slots, takes clients through the handshake including the map download,
+//! collects their inputs and distributes snapshots. The game itself is
+//! supplied as a `GameLogic`.
+
+extern crate arrayvec;
+extern crate common;
+extern crate event_loop;
+extern crate gamenet_common;
+extern crate gamenet_ddnet;
+extern crate gamenet_teeworlds_0_6 as gamenet;
+#[macro_use]
+extern crate log;
+extern crate packer;
+extern crate snapshot;
+extern crate warn;
+
+use arrayvec::ArrayString;
+use arrayvec::ArrayVec;
+use common::digest;
+use common::digest::Sha256;
+use common::num::Cast;
+use common::pretty::AlmostString;
+use common::time::Tick;
+use common::time::TickRate;
+use event_loop::collections::PeerMap;
+use event_loop::Application;
+use event_loop::Chunk;
+use event_loop::ConnlessChunk;
+use event_loop::Loop;
+use event_loop::PeerId;
+use event_loop::TickScheduler;
+use event_loop::Timeout;
+use gamenet::enums::MAX_CLIENTS;
+use gamenet::enums::VERSION;
+use gamenet::msg;
+use gamenet::msg::connless;
+use gamenet::msg::game;
+use gamenet::msg::game::SV_TUNE_PARAMS_DEFAULT;
+use gamenet::msg::system;
+use gamenet::msg::Connless;
+use gamenet::msg::Game;
+use gamenet::msg::System;
+use gamenet::msg::SystemOrGame;
+use gamenet::snap_obj::obj_size;
+use gamenet::snap_obj::PlayerInput;
+use gamenet::snap_obj::TypeId;
+use gamenet::SnapObj;
+use gamenet::Version;
+use gamenet_common::input::InputError;
+use gamenet_common::input::InputTracker;
+use gamenet_common::map_download;
+use packer::with_packer;
+use packer::Unpacker;
+use snapshot::snap;
+use std::fmt::Write;
+use warn::Log;
+
+const PLAYER_NAME_LENGTH: usize = 16 - 1; // -1 for null termination
+const PLAYER_CLAN_LENGTH: usize = 12 - 1;
+
+/// Index of a client slot, also used as the client ID in messages and as
+/// the snapshot item ID of per-client objects.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ClientId(pub u16);
+
+impl ClientId {
+    pub fn i32(self) -> i32 {
+        self.0.i32()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub name: String,
+    pub game_type: String,
+    pub password: Option<String>,
+    pub motd: String,
+    /// Number of client slots, at most `MAX_CLIENTS`.
+    pub max_clients: u32,
+    pub tick_rate: TickRate,
+    /// Snapshots are sent every `snapshot_interval` ticks.
+    pub snapshot_interval: u32,
+    /// Base URL of an HTTP server hosting the maps as `<name>_<sha256>.map`.
+    ///
+    /// DDNet clients download the map from there instead of through the
+    /// game connection.
+    pub maps_base_url: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            name: "unnamed server".to_owned(),
+            game_type: "DM".to_owned(),
+            password: None,
+            motd: String::new(),
+            max_clients: MAX_CLIENTS.assert_u32(),
+            tick_rate: TickRate::default(),
+            snapshot_interval: 2,
+            maps_base_url: None,
+        }
+    }
+}
+
+/// The map served to clients.
+pub struct Map {
+    name: Vec<u8>,
+    sender: map_download::Sender,
+    crc: i32,
+    sha256: Sha256,
+}
+
+impl Map {
+    pub fn new(name: &[u8], data: Vec<u8>) -> Map {
+        Map {
+            name: name.to_owned(),
+            crc: digest::crc32(&data) as i32,
+            sha256: digest::sha256(&data),
+            sender: map_download::Sender::new(data, map_download::CHUNK_SIZE_0_6, 1),
+        }
+    }
+    pub fn name(&self) -> &[u8] {
+        &self.name
+    }
+    pub fn data(&self) -> &[u8] {
+        self.sender.data()
+    }
+    pub fn crc(&self) -> i32 {
+        self.crc
+    }
+    pub fn sha256(&self) -> Sha256 {
+        self.sha256
+    }
+    fn map_change(&self) -> system::MapChange<'_> {
+        system::MapChange {
+            name: &self.name,
+            crc: self.crc,
+            size: self.data().len().assert_i32(),
+        }
+    }
+    fn serve_request(&self, rmd: system::RequestMapData) -> Option<system::MapData<'_>> {
+        let index = rmd.chunk.try_u32()?;
+        let mut transfer = map_download::Transfer::new();
+        let chunk = self.sender.request(&mut transfer, Some(index)).next()?;
+        Some(system::MapData {
+            last: chunk.last as i32,
+            crc: self.crc,
+            chunk: rmd.chunk,
+            data: chunk.data,
+        })
+    }
+}
+
+/// A client that entered the game.
+pub struct Client {
+    pub name: ArrayVec<[u8; PLAYER_NAME_LENGTH]>,
+    pub clan: ArrayVec<[u8; PLAYER_CLAN_LENGTH]>,
+    pub country: i32,
+    /// The latest input, updated before each tick.
+    pub input: PlayerInput,
+}
+
+/// The game run by a `Server`.
+pub trait GameLogic {
+    /// Called once the client has entered the game.
+    fn on_enter<L: Loop>(&mut self, ctx: &mut Context<L>, cid: ClientId);
+    /// Called before the client's slot is freed, only for clients that
+    /// entered the game.
+    fn on_leave<L: Loop>(&mut self, ctx: &mut Context<L>, cid: ClientId, reason: &[u8]);
+    /// Called for the game messages of clients in the game.
+    fn on_message<L: Loop>(&mut self, ctx: &mut Context<L>, cid: ClientId, msg: Game);
+    fn on_tick<L: Loop>(&mut self, ctx: &mut Context<L>);
+    /// Adds the objects seen by `cid` to its snapshot.
+    fn snap(&mut self, clients: &Clients, cid: ClientId, builder: &mut snap::Builder);
+}
+
+/// Helper for adding objects to snapshots.
+pub trait SnapBuilderExt {
+    fn add<O: Into<SnapObj>>(&mut self, id: u16, obj: O);
+}
+
+impl SnapBuilderExt for snap::Builder {
+    fn add<O: Into<SnapObj>>(&mut self, id: u16, obj: O) {
+        fn inner(builder: &mut snap::Builder, id: u16, obj: SnapObj) {
+            use self::TypeId::*;
+            let obj_type_id = match obj.obj_type_id() {
+                Ordinal(i) => i,
+                Uuid(_) => panic!("server doesn't support extended IDs yet"),
+            };
+            builder
+                .add_item(obj_type_id, id, obj.encode().unwrap())
+                .unwrap();
+        }
+        inner(self, id, obj.into())
+    }
+}
+
+enum PeerState {
+    Info,
+    Ready,
+    StartInfo,
+    EnterGame,
+    Ingame,
+}
+
+struct Peer {
+    cid: ClientId,
+    state: PeerState,
+    client: Client,
+    snaps: snapshot::Storage,
+    input_tracker: InputTracker<PlayerInput>,
+}
+
+/// The client slots.
+pub struct Clients {
+    slots: Vec<Option<PeerId>>,
+    peers: PeerMap<Peer>,
+}
+
+impl Clients {
+    fn new(max_clients: u32) -> Clients {
+        assert!(max_clients != 0 && max_clients <= MAX_CLIENTS.assert_u32());
+        Clients {
+            slots: vec![None; max_clients.usize()],
+            peers: PeerMap::with_capacity(max_clients.usize()),
+        }
+    }
+    fn pid(&self, cid: ClientId) -> Option<PeerId> {
+        self.slots.get(cid.0.usize()).cloned().unwrap_or(None)
+    }
+    /// Returns the client in slot `cid` if it entered the game.
+    pub fn get(&self, cid: ClientId) -> Option<&Client> {
+        let peer = &self.peers[self.pid(cid)?];
+        match peer.state {
+            PeerState::Ingame => Some(&peer.client),
+            _ => None,
+        }
+    }
+    /// Iterates over the clients that entered the game.
+    pub fn iter(&self) -> impl Iterator<Item = (ClientId, &Client)> {
+        self.peers
+            .values()
+            .filter(|p| matches!(p.state, PeerState::Ingame))
+            .map(|p| (p.cid, &p.client))
+    }
+    /// Number of occupied slots, including clients still connecting.
+    pub fn num_connected(&self) -> usize {
+        self.peers.len()
+    }
+    pub fn max_clients(&self) -> usize {
+        self.slots.len()
+    }
+}
+
+/// Access to the server from within the `GameLogic`.
+pub struct Context<'a, L: Loop + 'a> {
+    loop_: &'a mut L,
+    clients: &'a Clients,
+    tick: u32,
+}
+
+impl<'a, L: Loop> Context<'a, L> {
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+    pub fn clients(&self) -> &Clients {
+        self.clients
+    }
+    /// Sends a vital game message to a client in the game.
+    pub fn send<'m, G: Into<Game<'m>>>(&mut self, cid: ClientId, msg: G) {
+        if let Some(pid) = self.ingame_pid(cid) {
+            sendg_impl(msg.into(), pid, self.loop_);
+            self.loop_.flush(pid);
+        }
+    }
+    /// Sends a vital game message to all clients in the game.
+    pub fn broadcast<'m, G: Into<Game<'m>>>(&mut self, msg: G) {
+        let msg = msg.into();
+        for (cid, _) in self.clients.iter() {
+            let pid = self.clients.pid(cid).unwrap();
+            sendg_impl(msg, pid, self.loop_);
+            self.loop_.flush(pid);
+        }
+    }
+    /// Disconnects the client in slot `cid`. `GameLogic::on_leave` is called
+    /// later.
+    pub fn kick(&mut self, cid: ClientId, reason: &[u8]) {
+        if let Some(pid) = self.clients.pid(cid) {
+            self.loop_.disconnect(pid, reason);
+        }
+    }
+    fn ingame_pid(&self, cid: ClientId) -> Option<PeerId> {
+        self.clients.get(cid)?;
+        self.clients.pid(cid)
+    }
+}
+
+fn sends_impl<L: Loop + ?Sized>(msg: System, pid: PeerId, vital: bool, loop_: &mut L) {
+    let mut buf: ArrayVec<[u8; 2048]> = ArrayVec::new();
+    with_packer(&mut buf, |p| msg.encode(p).unwrap());
+    loop_.send(Chunk {
+        pid,
+        vital,
+        data: &buf,
+    })
+}
+
+fn sendg_impl<L: Loop + ?Sized>(msg: Game, pid: PeerId, loop_: &mut L) {
+    let mut buf: ArrayVec<[u8; 2048]> = ArrayVec::new();
+    with_packer(&mut buf, |p| msg.encode(p).unwrap());
+    loop_.send(Chunk {
+        pid,
+        vital: true,
+        data: &buf,
+    })
+}
+
+/// Sends DDNet's map details including the HTTP download URL, which isn't
+/// part of the generated message yet.
+fn send_map_details<L: Loop + ?Sized>(map: &Map, url: &str, pid: PeerId, loop_: &mut L) {
+    let details = gamenet_ddnet::msg::System::from(gamenet_ddnet::msg::system::MapDetails {
+        name: &map.name,
+        sha256: map.sha256,
+        crc: map.crc,
+    });
+    let mut buf: ArrayVec<[u8; 2048]> = ArrayVec::new();
+    with_packer(&mut buf, |p| details.encode(p).unwrap());
+    with_packer(&mut buf, |mut p| {
+        p.write_int(map.data().len().assert_i32())?;
+        p.write_string(url.as_bytes())
+    })
+    .unwrap();
+    loop_.send(Chunk {
+        pid,
+        vital: true,
+        data: &buf,
+    })
+}
+
+/// Runs a `GameLogic`, see the crate documentation.
+pub struct Server<G> {
+    config: Config,
+    map: Map,
+    clients: Clients,
+    game: G,
+    ticks: Option<TickScheduler>,
+    tick: u32,
+    delta_buffer: Vec<u8>,
+}
+
+impl<G: GameLogic> Server<G> {
+    pub fn new(config: Config, map: Map, game: G) -> Server<G> {
+        Server {
+            clients: Clients::new(config.max_clients),
+            config,
+            map,
+            game,
+            ticks: None,
+            tick: 0,
+            delta_buffer: Vec::new(),
+        }
+    }
+    pub fn game(&self) -> &G {
+        &self.game
+    }
+    pub fn game_mut(&mut self) -> &mut G {
+        &mut self.game
+    }
+    fn context<'a, L: Loop>(&'a mut self, loop_: &'a mut L) -> (&'a mut G, Context<'a, L>) {
+        let ctx = Context {
+            loop_,
+            clients: &self.clients,
+            tick: self.tick,
+        };
+        (&mut self.game, ctx)
+    }
+}
+
+impl<L: Loop, G: GameLogic> Application<L> for Server<G> {
+    fn needs_tick(&mut self) -> Timeout {
+        match self.ticks {
+            Some(ref ticks) if !self.clients.peers.is_empty() => ticks.needs_tick(),
+            _ => Timeout::inactive(),
+        }
+    }
+    fn on_tick(&mut self, loop_: &mut L) {
+        if self.clients.peers.is_empty() {
+            return;
+        }
+        let now = loop_.time();
+        let due = self.ticks.as_mut().unwrap().due(now);
+        for tick in due {
+            self.tick = tick.assert_u32();
+            let (game, mut ctx) = self.context(loop_);
+            game.on_tick(&mut ctx);
+            if self.tick % self.config.snapshot_interval == 0 {
+                self.send_snapshots(loop_);
+            }
+        }
+    }
+    fn on_packet(&mut self, loop_: &mut L, chunk: Chunk) {
+        self.on_msg(loop_, chunk.pid, chunk.vital, chunk.data);
+    }
+    fn on_connless_packet(&mut self, loop_: &mut L, chunk: ConnlessChunk) {
+        let data = chunk.data;
+        let msg = match Connless::decode(&mut Log, &mut Unpacker::new(data)) {
+            Ok(m) => m,
+            Err(err) => {
+                warn!("decode error {:?}", err);
+                return;
+            }
+        };
+        let request = match msg {
+            Connless::RequestInfo(request) => request,
+            _ => {
+                warn!("unprocessed message {:?}", msg);
+                return;
+            }
+        };
+        let mut clients_buf: ArrayVec<[u8; 1024]> = ArrayVec::new();
+        for (_, client) in self.clients.iter() {
+            with_packer(&mut clients_buf, |p| {
+                connless::Client {
+                    name: &client.name,
+                    clan: &client.clan,
+                    country: client.country,
+                    score: 0,
+                    is_player: 1,
+                }
+                .encode(p)
+                .unwrap()
+            });
+        }
+        let flags = if self.config.password.is_some() {
+            connless::INFO_FLAG_PASSWORD
+        } else {
+            0
+        };
+        let num_clients = self.clients.iter().count().assert_i32();
+        let info = Connless::from(connless::Info {
+            token: request.token.i32(),
+            version: VERSION.as_bytes(),
+            name: self.config.name.as_bytes(),
+            game_type: self.config.game_type.as_bytes(),
+            map: &self.map.name,
+            flags,
+            num_players: num_clients,
+            max_players: self.clients.max_clients().assert_i32(),
+            num_clients,
+            max_clients: self.clients.max_clients().assert_i32(),
+            clients: msg::ClientsData::from_bytes(&clients_buf),
+        });
+        let mut buf: ArrayVec<[u8; 2048]> = ArrayVec::new();
+        with_packer(&mut buf, |p| info.encode(p).unwrap());
+        loop_.send_connless(chunk.addr, &buf);
+    }
+    fn on_connect(&mut self, loop_: &mut L, pid: PeerId) {
+        if self.clients.peers.is_empty() {
+            let tps = self.config.tick_rate.ticks_per_second();
+            self.ticks = Some(TickScheduler::new(loop_.time(), tps));
+            self.tick = 0;
+        }
+        let slot = match self.clients.slots.iter().position(|s| s.is_none()) {
+            Some(s) => s,
+            None => {
+                loop_.reject(pid, b"This server is full");
+                return;
+            }
+        };
+        loop_.accept(pid);
+        self.clients.slots[slot] = Some(pid);
+        let peer = Peer {
+            cid: ClientId(slot.assert_u16()),
+            state: PeerState::Info,
+            client: Client {
+                name: ArrayVec::new(),
+                clan: ArrayVec::new(),
+                country: -1,
+                input: PlayerInput::default(),
+            },
+            snaps: snapshot::Storage::new(),
+            input_tracker: InputTracker::new(),
+        };
+        self.clients.peers.insert(pid, peer);
+        info!("{} starting to connect", pid);
+    }
+    fn on_ready(&mut self, _: &mut L, _: PeerId) {
+        unreachable!();
+    }
+    fn on_disconnect(&mut self, loop_: &mut L, pid: PeerId, remote: bool, reason: &[u8]) {
+        let _ = remote;
+        let (cid, ingame) = match self.clients.peers.get(pid) {
+            Some(p) => (p.cid, matches!(p.state, PeerState::Ingame)),
+            None => return,
+        };
+        if !reason.is_empty() {
+            info!("{} leaves the game ({})", pid, AlmostString::new(reason));
+        } else {
+            info!("{} leaves the game", pid);
+        }
+        if ingame {
+            let (game, mut ctx) = self.context(loop_);
+            game.on_leave(&mut ctx, cid, reason);
+        }
+        self.clients.peers.remove(pid);
+        self.clients.slots[cid.0.usize()] = None;
+    }
+}
+
+impl<G: GameLogic> Server<G> {
+    fn on_msg<L: Loop>(&mut self, loop_: &mut L, pid: PeerId, vital: bool, data: &[u8]) {
+        use self::PeerState::*;
+
+        let msg = match msg::decode(&mut Log, &mut Unpacker::new(data)) {
+            Ok(m) => m,
+            Err(err) => {
+                warn!("decode error {:?}", err);
+                return;
+            }
+        };
+        if !vital && !matches!(msg, SystemOrGame::System(System::Input(..))) {
+            warn!("non-vital message {:?}", msg);
+            return;
+        }
+        let peer = &mut self.clients.peers[pid];
+        match (&peer.state, msg) {
+            (&Info, SystemOrGame::System(System::Info(info))) => {
+                let compatible = Version::from_net_version(info.version)
+                    .map(|v| v.protocol().is_compatible(gamenet::PROTOCOL))
+                    .unwrap_or(false);
+                if !compatible {
+                    let mut buf: ArrayString<[u8; 128]> = ArrayString::new();
+                    write!(
+                        &mut buf,
+                        "Wrong version. Server is running '{}'",
+                        AlmostString::new(VERSION.as_bytes()),
+                    )
+                    .unwrap();
+                    loop_.disconnect(pid, buf.as_bytes());
+                    return;
+                }
+                if let Some(ref password) = self.config.password {
+                    if info.password != Some(password.as_bytes()) {
+                        loop_.disconnect(pid, b"Wrong password");
+                        return;
+                    }
+                }
+                if let Some(ref base_url) = self.config.maps_base_url {
+                    let url = format!(
+                        "{}/{}_{}.map",
+                        base_url,
+                        String::from_utf8_lossy(&self.map.name),
+                        self.map.sha256,
+                    );
+                    send_map_details(&self.map, &url, pid, loop_);
+                }
+                sends_impl(self.map.map_change().into(), pid, true, loop_);
+                loop_.flush(pid);
+                peer.state = Ready;
+            }
+            (&Ready, SystemOrGame::System(System::RequestMapData(rmd))) => {
+                if let Some(md) = self.map.serve_request(rmd) {
+                    sends_impl(md.into(), pid, true, loop_);
+                    loop_.flush(pid);
+                }
+            }
+            (&Ready, SystemOrGame::System(System::Ready(system::Ready))) => {
+                if !self.config.motd.is_empty() {
+                    let motd = game::SvMotd {
+                        message: self.config.motd.as_bytes(),
+                    };
+                    sendg_impl(motd.into(), pid, loop_);
+                }
+                sends_impl(system::ConReady.into(), pid, true, loop_);
+                loop_.flush(pid);
+                peer.state = StartInfo;
+            }
+            (&StartInfo, SystemOrGame::Game(Game::ClStartInfo(info))) => {
+                let client = &mut peer.client;
+                client.name = info.name.iter().cloned().take(PLAYER_NAME_LENGTH).collect();
+                client.clan = info.clan.iter().cloned().take(PLAYER_CLAN_LENGTH).collect();
+                client.country = info.country;
+                info!(
+                    "{}:{} enters the game",
+                    pid,
+                    AlmostString::new(&client.name)
+                );
+                sendg_impl(game::SvVoteClearOptions.into(), pid, loop_);
+                sendg_impl(SV_TUNE_PARAMS_DEFAULT.into(), pid, loop_);
+                sendg_impl(game::SvReadyToEnter.into(), pid, loop_);
+                loop_.flush(pid);
+                peer.state = EnterGame;
+            }
+            (&EnterGame, SystemOrGame::System(System::EnterGame(system::EnterGame))) => {
+                peer.state = Ingame;
+                let cid = peer.cid;
+                let (game, mut ctx) = self.context(loop_);
+                game.on_enter(&mut ctx, cid);
+            }
+            (&Ingame, SystemOrGame::System(System::Input(input))) => {
+                if let Err(e) = peer
+                    .snaps
+                    .set_delta_tick(&mut Log, Tick(input.ack_snapshot))
+                {
+                    warn!("invalid input tick: {:?} ({})", e, input.ack_snapshot);
+                }
+                let current_tick = self.tick.assert_i32();
+                match peer.input_tracker.process(
+                    current_tick.into(),
+                    input.intended_tick.into(),
+                    input.input,
+                    self.config.tick_rate,
+                ) {
+                    Ok(_) => peer.client.input = input.input,
+                    Err(InputError::Stale) => {}
+                    Err(InputError::TooFarAhead) => {
+                        warn!(
+                            "input too far ahead: {} ({})",
+                            input.intended_tick, current_tick
+                        );
+                    }
+                }
+            }
+            (_, SystemOrGame::System(System::RconAuth(..))) => {
+                let line = system::RconLine {
+                    line: b"Wrong password",
+                };
+                sends_impl(line.into(), pid, true, loop_);
+                loop_.flush(pid);
+            }
+            (&Ingame, SystemOrGame::Game(msg)) => {
+                let cid = peer.cid;
+                let (game, mut ctx) = self.context(loop_);
+                game.on_message(&mut ctx, cid, msg);
+            }
+            (_, msg) => warn!("unprocessed message {:?}", msg),
+        }
+    }
+    fn send_snapshots<L: Loop>(&mut self, loop_: &mut L) {
+        let tick = Tick(self.tick.assert_i32());
+        let pids: Vec<_> = self
+            .clients
+            .peers
+            .iter()
+            .filter(|&(_, p)| matches!(p.state, PeerState::Ingame))
+            .map(|(pid, _)| pid)
+            .collect();
+        for pid in pids {
+            let cid = self.clients.peers[pid].cid;
+            let mut builder = self.clients.peers[pid].snaps.new_builder();
+            self.game.snap(&self.clients, cid, &mut builder);
+            let snap = builder.finish();
+            let crc = snap.crc();
+            let peer = &mut self.clients.peers[pid];
+            let delta_tick = peer.snaps.delta_tick().unwrap_or(Tick(-1));
+            let delta = peer.snaps.add_snap(tick, snap);
+
+            self.delta_buffer.clear();
+            self.delta_buffer.reserve(64 * 1024);
+            with_packer(&mut self.delta_buffer, |p| delta.write(obj_size, p)).unwrap();
+            for m in snap::delta_chunks(tick, delta_tick, &self.delta_buffer, crc) {
+                sends_impl(m.into(), pid, false, loop_);
+                loop_.flush(pid);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ClientId;
+    use super::Clients;
+    use super::Config;
+    use super::Context;
+    use super::GameLogic;
+    use super::Map;
+    use super::Server;
+    use super::SnapBuilderExt;
+    use event_loop::Addr;
+    use event_loop::Application;
+    use event_loop::Chunk;
+    use event_loop::Loop;
+    use event_loop::PeerId;
+    use event_loop::ProtocolVersion;
+    use event_loop::Timestamp;
+    use gamenet::enums::VERSION;
+    use gamenet::msg;
+    use gamenet::msg::game;
+    use gamenet::msg::system;
+    use gamenet::msg::Game;
+    use gamenet::msg::System;
+    use gamenet::msg::SystemOrGame;
+    use gamenet::snap_obj::obj_size;
+    use gamenet::snap_obj::PlayerInfo;
+    use gamenet::snap_obj::PlayerInput;
+    use gamenet::snap_obj::PLAYER_INFO;
+    use packer::with_packer;
+    use packer::Unpacker;
+    use snapshot;
+    use snapshot::snap;
+    use std::mem;
+    use std::time::Duration;
+    use warn::Panic;
+
+    #[derive(Default)]
+    struct FakeLoop {
+        now: u64,
+        sent: Vec<(PeerId, bool, Vec<u8>)>,
+        accepted: Vec<PeerId>,
+        rejected: Vec<(PeerId, Vec<u8>)>,
+    }
+
+    impl FakeLoop {
+        /// Advances the time by one tick at the default tick rate.
+        fn advance(&mut self) {
+            self.now += 20_000_000;
+        }
+    }
+
+    impl Loop for FakeLoop {
+        fn accept_connections_on_port(_: u16) -> FakeLoop {
+            FakeLoop::default()
+        }
+        fn client() -> FakeLoop {
+            unimplemented!();
+        }
+        fn run<A: Application<FakeLoop>>(self, _: A) {
+            unimplemented!();
+        }
+        fn time(&mut self) -> Timestamp {
+            Timestamp::from_usecs_since_epoch(self.now)
+        }
+        fn connect(&mut self, _: Addr) -> PeerId {
+            unimplemented!();
+        }
+        fn connect_with_version(&mut self, _: Addr, _: ProtocolVersion) -> PeerId {
+            unimplemented!();
+        }
+        fn disconnect(&mut self, _: PeerId, _: &[u8]) {
+            unimplemented!();
+        }
+        fn send_connless(&mut self, _: Addr, _: &[u8]) {
+            unimplemented!();
+        }
+        fn send(&mut self, chunk: Chunk) {
+            self.sent
+                .push((chunk.pid, chunk.vital, chunk.data.to_owned()));
+        }
+        fn force_flush(&mut self, _: PeerId) {}
+        fn flush(&mut self, _: PeerId) {}
+        fn ignore(&mut self, _: PeerId) {
+            unimplemented!();
+        }
+        fn accept(&mut self, pid: PeerId) {
+            self.accepted.push(pid);
+        }
+        fn reject(&mut self, pid: PeerId, reason: &[u8]) {
+            self.rejected.push((pid, reason.to_owned()));
+        }
+        fn shutdown(&mut self, _: &[u8], _: Duration) {
+            unimplemented!();
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Recorded {
+        Enter(ClientId),
+        Leave(ClientId, Vec<u8>),
+    }
+
+    /// Records the clients entering and leaving, and shows the player infos
+    /// of all clients in the snapshots.
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<Recorded>,
+    }
+
+    impl GameLogic for Recorder {
+        fn on_enter<L: Loop>(&mut self, _: &mut Context<L>, cid: ClientId) {
+            self.events.push(Recorded::Enter(cid));
+        }
+        fn on_leave<L: Loop>(&mut self, _: &mut Context<L>, cid: ClientId, reason: &[u8]) {
+            self.events.push(Recorded::Leave(cid, reason.to_owned()));
+        }
+        fn on_message<L: Loop>(&mut self, _: &mut Context<L>, _: ClientId, _: Game) {}
+        fn on_tick<L: Loop>(&mut self, _: &mut Context<L>) {}
+        fn snap(&mut self, clients: &Clients, cid: ClientId, builder: &mut snap::Builder) {
+            for (other, _) in clients.iter() {
+                builder.add(other.0, player_info(cid, other));
+            }
+        }
+    }
+
+    fn player_info(cid: ClientId, other: ClientId) -> PlayerInfo {
+        PlayerInfo {
+            local: (cid == other) as i32,
+            client_id: other.i32(),
+            team: 0,
+            score: 0,
+            latency: 0,
+        }
+    }
+
+    fn server(config: Config) -> (Server<Recorder>, FakeLoop) {
+        let map = Map::new(b"dm1", b"not really a map".to_vec());
+        let loop_ = FakeLoop::accept_connections_on_port(8303);
+        (Server::new(config, map, Recorder::default()), loop_)
+    }
+
+    fn receive(
+        server: &mut Server<Recorder>,
+        loop_: &mut FakeLoop,
+        pid: PeerId,
+        msg: SystemOrGame<System, Game>,
+    ) {
+        let vital = !matches!(msg, SystemOrGame::System(System::Input(..)));
+        let mut buf = Vec::with_capacity(1024);
+        let data = match msg {
+            SystemOrGame::System(msg) => with_packer(&mut buf, |p| msg.encode(p)),
+            SystemOrGame::Game(msg) => with_packer(&mut buf, |p| msg.encode(p)),
+        }
+        .unwrap()
+        .to_vec();
+        server.on_packet(
+            loop_,
+            Chunk {
+                pid,
+                vital,
+                data: &data,
+            },
+        );
+    }
+
+    fn input(ack_snapshot: i32) -> SystemOrGame<System<'static>, Game<'static>> {
+        SystemOrGame::System(System::from(system::Input {
+            ack_snapshot,
+            intended_tick: ack_snapshot + 1,
+            input_size: mem::size_of::<PlayerInput>() as i32,
+            input: PlayerInput::default(),
+        }))
+    }
+
+    /// Takes the connected client `pid` through the handshake.
+    fn enter(server: &mut Server<Recorder>, loop_: &mut FakeLoop, pid: PeerId) {
+        let info = system::Info {
+            version: VERSION.as_bytes(),
+            password: Some(b""),
+        };
+        receive(server, loop_, pid, SystemOrGame::System(info.into()));
+        receive(
+            server,
+            loop_,
+            pid,
+            SystemOrGame::System(system::Ready.into()),
+        );
+        let start_info = game::ClStartInfo {
+            name: b"nameless tee",
+            clan: b"",
+            country: -1,
+            skin: b"default",
+            use_custom_color: false,
+            color_body: 0,
+            color_feet: 0,
+        };
+        receive(server, loop_, pid, SystemOrGame::Game(start_info.into()));
+        receive(
+            server,
+            loop_,
+            pid,
+            SystemOrGame::System(system::EnterGame.into()),
+        );
+        let ready_to_enter = loop_.sent.drain(..).any(|(p, _, data)| {
+            p == pid
+                && matches!(
+                    msg::decode(&mut Panic, &mut Unpacker::new(&data)).unwrap(),
+                    SystemOrGame::Game(Game::SvReadyToEnter(..))
+                )
+        });
+        assert!(ready_to_enter);
+    }
+
+    fn cid(server: &Server<Recorder>, pid: PeerId) -> ClientId {
+        server.clients.peers[pid].cid
+    }
+
+    #[test]
+    fn slots() {
+        let config = Config {
+            max_clients: 2,
+            ..Config::default()
+        };
+        let (mut server, mut loop_) = server(config);
+        for i in 0..3 {
+            server.on_connect(&mut loop_, PeerId(i));
+        }
+        assert_eq!(loop_.accepted, [PeerId(0), PeerId(1)]);
+        assert_eq!(
+            loop_.rejected,
+            [(PeerId(2), b"This server is full".to_vec())]
+        );
+        assert_eq!(cid(&server, PeerId(0)), ClientId(0));
+        assert_eq!(cid(&server, PeerId(1)), ClientId(1));
+
+        // Clients that haven't entered the game leave silently.
+        server.on_disconnect(&mut loop_, PeerId(0), true, b"Timeout");
+        assert!(server.game().events.is_empty());
+        assert_eq!(server.clients.num_connected(), 1);
+        server.on_connect(&mut loop_, PeerId(3));
+        assert_eq!(cid(&server, PeerId(3)), ClientId(0));
+
+        enter(&mut server, &mut loop_, PeerId(1));
+        assert!(server.clients.get(ClientId(0)).is_none());
+        assert_eq!(
+            &server.clients.get(ClientId(1)).unwrap().name[..],
+            b"nameless tee"
+        );
+        assert_eq!(server.game().events, [Recorded::Enter(ClientId(1))]);
+
+        server.on_disconnect(&mut loop_, PeerId(1), true, b"bye");
+        assert!(server.clients.get(ClientId(1)).is_none());
+        assert_eq!(
+            server.game().events,
+            [
+                Recorded::Enter(ClientId(1)),
+                Recorded::Leave(ClientId(1), b"bye".to_vec()),
+            ]
+        );
+        // Unknown peers are ignored.
+        server.on_disconnect(&mut loop_, PeerId(1), true, b"bye");
+        assert_eq!(server.game().events.len(), 2);
+
+        server.on_connect(&mut loop_, PeerId(4));
+        assert_eq!(cid(&server, PeerId(4)), ClientId(1));
+        assert_eq!(server.clients.num_connected(), 2);
+    }
+
+    /// A snapshot as received by a client.
+    #[derive(Debug, PartialEq)]
+    struct Received {
+        tick: i32,
+        delta_tick: i32,
+        /// `local` and `client_id` of the player infos.
+        player_infos: Vec<(i32, i32)>,
+    }
+
+    /// Applies the snapshot messages sent to `pid` like a client would.
+    fn receive_snaps(
+        loop_: &mut FakeLoop,
+        pid: PeerId,
+        snaps: &mut snapshot::Manager,
+    ) -> Vec<Received> {
+        let sent: Vec<_> = loop_.sent.iter().filter(|s| s.0 == pid).cloned().collect();
+        loop_.sent.retain(|s| s.0 != pid);
+        let mut result = Vec::new();
+        for (_, vital, data) in sent {
+            assert!(!vital);
+            let msg = msg::decode(&mut Panic, &mut Unpacker::new(&data)).unwrap();
+            let (tick, delta_tick, snap) = match msg {
+                SystemOrGame::System(System::SnapSingle(s)) => (
+                    s.tick,
+                    s.delta_tick,
+                    snaps.snap_single(&mut Panic, obj_size, s),
+                ),
+                SystemOrGame::System(System::SnapEmpty(s)) => (
+                    s.tick,
+                    s.delta_tick,
+                    snaps.snap_empty(&mut Panic, obj_size, s),
+                ),
+                msg => panic!("unexpected message {:?}", msg),
+            };
+            let snap = snap.unwrap().unwrap();
+            let mut player_infos: Vec<_> = (0..2)
+                .filter_map(|id| snap.item(PLAYER_INFO, id))
+                .map(|data| (data[0], data[1]))
+                .collect();
+            player_infos.sort_by_key(|&(_, client_id)| client_id);
+            result.push(Received {
+                tick,
+                delta_tick: tick - delta_tick,
+                player_infos,
+            });
+        }
+        result
+    }
+
+    #[test]
+    fn snapshot_interval() {
+        let config = Config {
+            snapshot_interval: 3,
+            ..Config::default()
+        };
+        let (mut server, mut loop_) = server(config);
+        let pids = [PeerId(0), PeerId(1)];
+        for &pid in &pids {
+            server.on_connect(&mut loop_, pid);
+            enter(&mut server, &mut loop_, pid);
+        }
+        let mut snaps = [snapshot::Manager::new(), snapshot::Manager::new()];
+        let infos = |cid: i32| vec![((cid == 0) as i32, 0), ((cid == 1) as i32, 1)];
+        let full = |tick: i32, cid: i32| Received {
+            tick,
+            delta_tick: -1,
+            player_infos: infos(cid),
+        };
+
+        for _ in 0..6 {
+            loop_.advance();
+            server.on_tick(&mut loop_);
+        }
+        // Without acks, the snapshots are deltas to the empty snapshot.
+        for (i, &pid) in pids.iter().enumerate() {
+            let received = receive_snaps(&mut loop_, pid, &mut snaps[i]);
+            assert_eq!(received, [full(3, i as i32), full(6, i as i32)]);
+        }
+
+        receive(&mut server, &mut loop_, pids[0], input(3));
+        for _ in 0..3 {
+            loop_.advance();
+            server.on_tick(&mut loop_);
+        }
+        // Only the acking client gets deltas to the acked snapshot.
+        assert_eq!(
+            receive_snaps(&mut loop_, pids[0], &mut snaps[0]),
+            [Received {
+                tick: 9,
+                delta_tick: 3,
+                player_infos: infos(0),
+            }]
+        );
+        assert_eq!(
+            receive_snaps(&mut loop_, pids[1], &mut snaps[1]),
+            [full(9, 1)]
+        );
+        assert!(loop_.sent.is_empty());
+    }
+}
@@ -3,6 +3,7 @@ extern crate arrayvec;
 extern crate common;
 extern crate datafile;
 extern crate event_loop;
+extern crate gamenet_common;
 extern crate gamenet_teeworlds_0_6 as gamenet;
 extern crate hexdump;
 extern crate itertools;
@@ -22,8 +23,8 @@ extern crate world;
 use arrayvec::ArrayString;
 use arrayvec::ArrayVec;
 use common::num::Cast;
-use common::num::CastFloat;
 use common::pretty::AlmostString;
+use common::time::DEFAULT_TICK_RATE;
 use common::Takeable;
 use event_loop::collections::PeerMap;
 use event_loop::collections::PeerSet;
@@ -34,17 +35,19 @@ use event_loop::ConnlessChunk;
 use event_loop::Loop;
 use event_loop::PeerId;
 use event_loop::SocketLoop;
+use event_loop::TickScheduler;
 use event_loop::Timeout;
-use event_loop::Timestamp;
-use gamenet::enums::Emote;
 use gamenet::enums::Team;
-use gamenet::enums::Weapon;
+use gamenet::enums::EMOTE_NORMAL;
 use gamenet::enums::MAX_CLIENTS;
+use gamenet::enums::TEAM_RED;
+use gamenet::enums::TEAM_SPECTATORS;
 use gamenet::enums::VERSION;
+use gamenet::enums::WEAPON_PISTOL;
 use gamenet::msg;
 use gamenet::msg::connless;
 use gamenet::msg::game;
-use gamenet::msg::game::SV_TUNE_PARAMS_DEFAULT;
+use gamenet::msg::game::SvTuneParams;
 use gamenet::msg::system;
 use gamenet::msg::Connless;
 use gamenet::msg::Game;
@@ -59,10 +62,13 @@ use gamenet::snap_obj::PlayerInfo;
 use gamenet::snap_obj::Tick;
 use gamenet::snap_obj::TypeId;
 use gamenet::SnapObj;
+use gamenet::Version;
+use gamenet_common::input::InputError;
+use gamenet_common::input::InputTracker;
 use hexdump::hexdump_iter;
 use itertools::Itertools;
 use log::LogLevel;
-use ndarray::Array2;
+use map::collision::Collision;
 use packer::string_to_ints3;
 use packer::string_to_ints4;
 use packer::string_to_ints6;
@@ -74,8 +80,8 @@ use std::fmt;
 use std::fmt::Write;
 use std::fs::File;
 use std::io::Read;
-use std::time::Duration;
 use world::vec2;
+use world::Tuning;
 
 const TICKS_PER_SECOND: u32 = 50;
 const PLAYER_NAME_LENGTH: usize = 16 - 1; // -1 for null termination
@@ -147,7 +153,9 @@ impl SnapBuilderExt for snap::Builder {
                 Ordinal(i) => i,
                 Uuid(_) => panic!("server doesn't support extended IDs yet"),
             };
-            builder.add_item(obj_type_id, id, obj.encode()).unwrap();
+            builder
+                .add_item(obj_type_id, id, obj.encode().unwrap())
+                .unwrap();
         }
         inner(self, id, obj.into())
     }
@@ -193,7 +201,7 @@ impl MapContents {
 
 struct Map {
     spawn: vec2,
-    collision: Array2<Option<world::CollisionType>>,
+    collision: Collision,
     data: MapContents,
 }
 
@@ -203,20 +211,15 @@ impl Default for Map {
         let reader = datafile::Reader::open("dm1.map").unwrap();
         let mut map = map::Reader::from_datafile(reader);
         map.check_version().unwrap();
-        let gamelayers = map.game_layers().unwrap();
-        let tiles = map.layer_tiles(gamelayers.game()).unwrap();
         let result = Map {
             spawn: vec2::new(160.0, 160.0),
-            collision: tiles.mapv(|t| match t.index {
-                1 => Some(world::CollisionType::Normal),
-                3 => Some(world::CollisionType::Unhookable),
-                _ => None,
-            }),
+            collision: Collision::new(map.game_layer_tiles().unwrap()),
             data: map_contents,
         };
-        for y in 0..result.collision.dim().0 {
-            for x in 0..result.collision.dim().1 {
-                let c = match result.collision[(y, x)] {
+        for y in 0..result.collision.height() {
+            for x in 0..result.collision.width() {
+                let pos = vec2::new(x as f32 * 32.0, y as f32 * 32.0);
+                let c = match result.check_point(pos) {
                     Some(world::CollisionType::Normal) => '#',
                     Some(world::CollisionType::Unhookable) => '!',
                     None => ' ',
@@ -229,26 +232,24 @@ impl Default for Map {
     }
 }
 
+impl Map {
+    fn check_point(&self, pos: vec2) -> Option<world::CollisionType> {
+        if !self.collision.is_solid(pos.x, pos.y) {
+            None
+        } else if self.collision.is_hookable(pos.x, pos.y) {
+            Some(world::CollisionType::Normal)
+        } else {
+            Some(world::CollisionType::Unhookable)
+        }
+    }
+}
+
 impl world::Collision for Map {
     fn check_point(&mut self, pos: vec2) -> Option<world::CollisionType> {
-        let (x, y) = (pos.x.round_to_i32(), pos.y.round_to_i32());
-        let (mut tx, mut ty) = (
-            (x as f32 / 32.0).trunc_to_i32(),
-            (y as f32 / 32.0).trunc_to_i32(),
-        );
-        if tx < 0 {
-            tx = 0;
-        }
-        if tx > self.collision.dim().1.assert_i32() {
-            tx = self.collision.dim().1.assert_i32() - 1;
-        }
-        if ty < 0 {
-            ty = 0;
-        }
-        if ty > self.collision.dim().0.assert_i32() {
-            ty = self.collision.dim().0.assert_i32() - 1;
-        }
-        self.collision[(ty.assert_usize(), tx.assert_usize())]
+        Map::check_point(self, pos)
+    }
+    fn dimensions(&self) -> (u32, u32) {
+        (self.collision.width(), self.collision.height())
     }
 }
 
@@ -256,21 +257,15 @@ impl world::Collision for Map {
 struct Server {
     peers: PeerMap<Peer>,
     players: Vec<Player>,
-    game_start: Timestamp,
+    ticks: Option<TickScheduler>,
     game_tick: u32,
     delta_buffer: Vec<u8>,
     map: Map,
+    tuning: Tuning,
 
     send_snapshots_peer_set: Takeable<PeerSet>,
 }
 
-impl Server {
-    fn game_tick_time(&self, tick: u32) -> Timestamp {
-        let millis = tick.u64() * 1000 / TICKS_PER_SECOND.u64();
-        self.game_start + Duration::from_millis(millis)
-    }
-}
-
 #[derive(Default)]
 struct Peer {
     state: PeerState,
@@ -344,6 +339,7 @@ struct IngameState {
     snaps: snapshot::Storage,
     spectator: bool,
     input: snap_obj::PlayerInput,
+    input_tracker: InputTracker<snap_obj::PlayerInput>,
 }
 
 impl From<SystemEnterGameState> for IngameState {
@@ -353,6 +349,7 @@ impl From<SystemEnterGameState> for IngameState {
             snaps: Default::default(),
             spectator: true,
             input: Default::default(),
+            input_tracker: InputTracker::new(),
         }
     }
 }
@@ -378,10 +375,9 @@ struct ServerLoop<'a, L: Loop + 'a> {
 
 impl<L: Loop> Application<L> for Server {
     fn needs_tick(&mut self) -> Timeout {
-        if !self.peers.is_empty() {
-            Timeout::active(self.game_tick_time(self.game_tick + 1))
-        } else {
-            Timeout::inactive()
+        match self.ticks {
+            Some(ref ticks) if !self.peers.is_empty() => ticks.needs_tick(),
+            _ => Timeout::inactive(),
         }
     }
     fn on_tick(&mut self, loop_: &mut L) {
@@ -438,7 +434,10 @@ impl<'a, L: Loop> ServerLoop<'a, L> {
         let peer = &mut self.server.peers[pid];
         match (&peer.state, msg) {
             (&SystemInfo, SystemOrGame::System(System::Info(info))) => {
-                if info.version == VERSION.as_bytes() {
+                let compatible = Version::from_net_version(info.version)
+                    .map(|v| v.protocol().is_compatible(gamenet::PROTOCOL))
+                    .unwrap_or(false);
+                if compatible {
                     if info.password == Some(b"foobar") {
                         self.loop_.sends(
                             pid,
@@ -496,7 +495,8 @@ impl<'a, L: Loop> ServerLoop<'a, L> {
             (&GameInfo, SystemOrGame::Game(Game::ClStartInfo(info))) => {
                 info!("{}:{} enters the game", pid, AlmostString::new(info.name));
                 self.loop_.sendg(pid, game::SvVoteClearOptions);
-                self.loop_.sendg(pid, SV_TUNE_PARAMS_DEFAULT);
+                self.loop_
+                    .sendg(pid, SvTuneParams::from(&self.server.tuning));
                 self.loop_.sendg(pid, game::SvReadyToEnter);
                 self.loop_.flush(pid);
                 peer.state = SystemEnterGame(SystemEnterGameState::new(info.name));
@@ -520,12 +520,26 @@ impl<'a, L: Loop> ServerLoop<'a, L> {
                 let ingame = peer.state.assert_ingame();
                 if let Err(e) = ingame
                     .snaps
-                    .set_delta_tick(&mut Warn(pid, data), input.ack_snapshot)
+                    .set_delta_tick(&mut Warn(pid, data), Tick(input.ack_snapshot))
                 {
                     warn!("invalid input tick: {:?} ({})", e, input.ack_snapshot);
                 }
-                // TODO: Teeworlds never ignores old inputs?
-                ingame.input = input.input;
+                let current_tick = self.server.game_tick.assert_i32();
+                match ingame.input_tracker.process(
+                    current_tick.into(),
+                    input.intended_tick.into(),
+                    input.input,
+                    DEFAULT_TICK_RATE,
+                ) {
+                    Ok(_) => ingame.input = input.input,
+                    Err(InputError::Stale) => {}
+                    Err(InputError::TooFarAhead) => {
+                        warn!(
+                            "input too far ahead: {} ({})",
+                            input.intended_tick, current_tick
+                        );
+                    }
+                }
                 processed = true;
             }
             (&Ingame(..), SystemOrGame::Game(Game::ClCallVote(call_vote))) => {
@@ -652,7 +666,8 @@ impl<'a, L: Loop> ServerLoop<'a, L> {
     }
     fn on_connect(&mut self, pid: PeerId) {
         if self.server.peers.is_empty() {
-            self.server.game_start = self.loop_.time();
+            let ticks = TickScheduler::new(self.loop_.time(), TICKS_PER_SECOND);
+            self.server.ticks = Some(ticks);
             self.server.game_tick = 0;
         }
         if self.server.peers.len() == MAX_CLIENTS.assert_usize() {
@@ -715,6 +730,8 @@ impl<'a, L: Loop> ServerLoop<'a, L> {
             }
         }
 
+        // Like the original code, all characters are ticked before any of
+        // them is moved.
         for (cid, p) in self.server.players.iter().enumerate() {
             let input = self.server.peers[p.pid].state.assert_ingame().input;
             let other = &mut OtherCharacters {
@@ -722,8 +739,16 @@ impl<'a, L: Loop> ServerLoop<'a, L> {
                 players: &self.server.players,
             };
             let mut character = p.character.get();
-            character.tick(&mut self.server.map, other, input, &SV_TUNE_PARAMS_DEFAULT);
-            character.move_(&mut self.server.map, other, &SV_TUNE_PARAMS_DEFAULT);
+            character.tick(&mut self.server.map, other, input, &self.server.tuning);
+            p.character.set(character);
+        }
+        for (cid, p) in self.server.players.iter().enumerate() {
+            let other = &mut OtherCharacters {
+                own_cid: CharacterId(cid.assert_u32()),
+                players: &self.server.players,
+            };
+            let mut character = p.character.get();
+            character.move_(&mut self.server.map, other, &self.server.tuning);
             character.quantize();
             p.character.set(character);
         }
@@ -737,7 +762,7 @@ impl<'a, L: Loop> ServerLoop<'a, L> {
             let delta_tick;
             if let PeerState::Ingame(ref mut ingame) = self.server.peers[snap_pid].state {
                 builder = ingame.snaps.new_builder();
-                delta_tick = ingame.snaps.delta_tick().unwrap_or(-1);
+                delta_tick = ingame.snaps.delta_tick().unwrap_or(Tick(-1));
             } else {
                 continue;
             }
@@ -775,9 +800,9 @@ impl<'a, L: Loop> ServerLoop<'a, L> {
                             local: (snap_pid == pid) as i32,
                             client_id: pid.0.assert_i32(),
                             team: if ingame.spectator {
-                                Team::Spectators
+                                TEAM_SPECTATORS
                             } else {
-                                Team::Red
+                                TEAM_RED
                             },
                             score: 0,
                             latency: 20,
@@ -794,15 +819,15 @@ impl<'a, L: Loop> ServerLoop<'a, L> {
                         health: 10,
                         armor: 0,
                         ammo_count: 10,
-                        weapon: Weapon::Pistol,
-                        emote: Emote::Normal,
+                        weapon: WEAPON_PISTOL,
+                        emote: EMOTE_NORMAL,
                         attack_tick: 0,
                     },
                 );
             }
             let snap = builder.finish();
             let crc = snap.crc();
-            let game_tick = self.server.game_tick.assert_i32();
+            let game_tick = Tick(self.server.game_tick.assert_i32());
             let delta = self.server.peers[snap_pid]
                 .state
                 .assert_ingame()
@@ -821,8 +846,9 @@ impl<'a, L: Loop> ServerLoop<'a, L> {
         self.server.send_snapshots_peer_set.restore(peer_set);
     }
     fn tick(&mut self) {
-        while self.server.game_tick_time(self.server.game_tick + 1) <= self.loop_.time() {
-            self.server.game_tick += 1;
+        let now = self.loop_.time();
+        for tick in self.server.ticks.as_mut().unwrap().due(now) {
+            self.server.game_tick = tick.assert_u32();
             self.game_tick();
             if self.server.game_tick % 2 == 0 {
                 self.send_snapshots();
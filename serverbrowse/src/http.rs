@@ -0,0 +1,293 @@
+//! Server list of the DDNet HTTPS master servers.
+//!
+//! The master servers serve the list as JSON, containing the addresses and
+//! the info of all registered servers, see `MASTERSERVER_URL`.
+
+use common::num::Cast;
+use common::str::truncated_arraystring;
+use serde_json;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use ureq;
+
+use protocol::Addr;
+use protocol::ClientInfo;
+use protocol::IpAddr;
+use protocol::ServerInfo;
+use protocol::ServerInfoVersion;
+use protocol::SERVERINFO_FLAG_PASSWORDED;
+
+pub const MASTERSERVER_URL: &'static str = "https://master1.ddnet.org/ddnet/15/servers.json";
+
+#[derive(Debug)]
+pub enum Error {
+    Http(Box<ureq::Error>),
+    Io(io::Error),
+    Json(serde_json::Error),
+}
+
+impl From<ureq::Error> for Error {
+    fn from(err: ureq::Error) -> Error {
+        Error::Http(Box::new(err))
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Error {
+        Error::Json(err)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ServerList {
+    pub servers: Vec<Server>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Server {
+    /// Addresses of the server, e.g. `tw-0.6+udp://203.0.113.1:8303`.
+    pub addresses: Vec<String>,
+    /// Continent and country of the server, e.g. `eu:de`.
+    #[serde(default)]
+    pub location: Option<String>,
+    #[serde(default)]
+    pub community: Option<String>,
+    pub info: Info,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Info {
+    pub max_clients: i32,
+    pub max_players: i32,
+    #[serde(default)]
+    pub passworded: bool,
+    pub game_type: String,
+    pub name: String,
+    pub map: MapInfo,
+    pub version: String,
+    #[serde(default)]
+    pub clients: Vec<Client>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MapInfo {
+    pub name: String,
+    #[serde(default)]
+    pub sha256: Option<String>,
+    #[serde(default)]
+    pub size: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Client {
+    pub name: String,
+    pub clan: String,
+    pub country: i32,
+    pub score: i32,
+    pub is_player: bool,
+    #[serde(default)]
+    pub afk: Option<bool>,
+    #[serde(default)]
+    pub team: Option<i32>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Protocol {
+    V6,
+    V7,
+    /// The DDNet-specific protocol, `ddnet+udp`.
+    Ddnet,
+}
+
+/// Parses an address of the server list, e.g.
+/// `tw-0.6+udp://203.0.113.1:8303`.
+pub fn parse_address(address: &str) -> Option<(Protocol, Addr)> {
+    let (scheme, rest) = address.split_at(address.find("://")?);
+    let protocol = match scheme {
+        "tw-0.6+udp" => Protocol::V6,
+        "tw-0.7+udp" => Protocol::V7,
+        "ddnet+udp" => Protocol::Ddnet,
+        _ => return None,
+    };
+    let addr = match rest["://".len()..].parse().ok()? {
+        SocketAddr::V4(a) => Addr {
+            ip_address: IpAddr::V4(*a.ip()),
+            port: a.port(),
+        },
+        SocketAddr::V6(a) => Addr {
+            ip_address: IpAddr::V6(*a.ip()),
+            port: a.port(),
+        },
+    };
+    Some((protocol, addr))
+}
+
+impl Server {
+    /// The addresses that could be parsed, with their protocol.
+    pub fn addrs<'a>(&'a self) -> impl Iterator<Item = (Protocol, Addr)> + 'a {
+        self.addresses.iter().filter_map(|a| parse_address(a))
+    }
+    pub fn to_server_info(&self) -> ServerInfo {
+        let info = &self.info;
+        let mut clients: Vec<ClientInfo> = info
+            .clients
+            .iter()
+            .map(|c| ClientInfo {
+                name: truncated_arraystring(&c.name),
+                clan: truncated_arraystring(&c.clan),
+                country: c.country,
+                score: c.score,
+                is_player: c.is_player as i32,
+            })
+            .collect();
+        clients.sort();
+        ServerInfo {
+            // The JSON info contains the same as the extended 0.6 info.
+            info_version: ServerInfoVersion::V6Ex,
+            token: 0,
+            version: truncated_arraystring(&info.version),
+            name: truncated_arraystring(&info.name),
+            hostname: None,
+            map: truncated_arraystring(&info.map.name),
+            map_crc: None,
+            map_size: info.map.size,
+            game_type: truncated_arraystring(&info.game_type),
+            flags: if info.passworded {
+                SERVERINFO_FLAG_PASSWORDED
+            } else {
+                0
+            },
+            progression: None,
+            skill_level: None,
+            num_players: info
+                .clients
+                .iter()
+                .filter(|c| c.is_player)
+                .count()
+                .assert_i32(),
+            max_players: info.max_players,
+            num_clients: info.clients.len().assert_i32(),
+            max_clients: info.max_clients,
+            clients,
+            location: self.location.as_ref().map(|l| truncated_arraystring(l)),
+            community: self.community.as_ref().map(|c| truncated_arraystring(c)),
+        }
+    }
+}
+
+pub fn parse<R: io::Read>(reader: R) -> Result<ServerList, serde_json::Error> {
+    serde_json::from_reader(reader)
+}
+
+/// Downloads and parses the server list from a master server, e.g. from
+/// `MASTERSERVER_URL`.
+pub fn fetch(url: &str) -> Result<ServerList, Error> {
+    let response = ureq::get(url).call()?;
+    Ok(parse(response.into_reader())?)
+}
+
+/// Adds the servers of the list to `servers`, under each of their
+/// addresses.
+///
+/// Infos that are already known, e.g. because they were received via UDP,
+/// are kept, only their location and community are filled in.
+pub fn merge(servers: &mut HashMap<Addr, ServerInfo>, list: &ServerList) {
+    for server in &list.servers {
+        let info = server.to_server_info();
+        for (_, addr) in server.addrs() {
+            match servers.entry(addr) {
+                Entry::Occupied(mut e) => {
+                    let existing = e.get_mut();
+                    existing.location = existing.location.or(info.location);
+                    existing.community = existing.community.or(info.community);
+                }
+                Entry::Vacant(e) => {
+                    e.insert(info.clone());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::net::Ipv4Addr;
+
+    use super::merge;
+    use super::parse;
+    use super::parse_address;
+    use super::Protocol;
+    use common::str::truncated_arraystring as b;
+    use protocol::Addr;
+    use protocol::IpAddr;
+    use protocol::ServerInfo;
+
+    const LIST: &'static str = r#"{"servers": [{
+        "addresses": ["tw-0.6+udp://203.0.113.1:8303", "tw-0.7+udp://203.0.113.1:8304"],
+        "location": "eu:de",
+        "community": "ddnet",
+        "info": {
+            "max_clients": 64,
+            "max_players": 64,
+            "passworded": false,
+            "game_type": "DDraceNetwork",
+            "name": "DDNet GER - Novice",
+            "map": {"name": "Kobra 4", "sha256": "00", "size": 1234},
+            "version": "0.6.4, 17.0",
+            "clients": [
+                {"name": "nameless tee", "clan": "", "country": -1, "score": -9999, "is_player": true, "afk": false, "team": 0},
+                {"name": "spectator", "clan": "clan", "country": 276, "score": 0, "is_player": false}
+            ]
+        }
+    }]}"#;
+
+    fn addr(port: u16) -> Addr {
+        Addr {
+            ip_address: IpAddr::V4(Ipv4Addr::new(203, 0, 113, 1)),
+            port,
+        }
+    }
+
+    #[test]
+    fn address() {
+        assert_eq!(
+            parse_address("tw-0.7+udp://203.0.113.1:8304"),
+            Some((Protocol::V7, addr(8304)))
+        );
+        assert!(parse_address("tw-0.6+udp://[2001:db8::1]:8303").is_some());
+        assert_eq!(parse_address("tw-0.6+tcp://203.0.113.1:8303"), None);
+        assert_eq!(parse_address("203.0.113.1:8303"), None);
+    }
+
+    #[test]
+    fn parse_and_merge() {
+        let list = parse(LIST.as_bytes()).unwrap();
+        let info = list.servers[0].to_server_info();
+        assert_eq!(info.map, b("Kobra 4"));
+        assert_eq!(info.map_size, Some(1234));
+        assert_eq!((info.num_players, info.num_clients), (1, 2));
+        assert_eq!(info.location, Some(b("eu:de")));
+        assert_eq!(info.clients[1].name, b("spectator"));
+
+        let mut servers = HashMap::new();
+        let udp = ServerInfo {
+            name: b("from UDP"),
+            ..ServerInfo::default()
+        };
+        servers.insert(addr(8303), udp);
+        merge(&mut servers, &list);
+        assert_eq!(servers.len(), 2);
+        assert_eq!(servers[&addr(8303)].name, b("from UDP"));
+        assert_eq!(servers[&addr(8303)].community, Some(b("ddnet")));
+        assert_eq!(servers[&addr(8304)], info);
+    }
+}
@@ -1,9 +1,23 @@
 extern crate arrayvec;
 #[macro_use]
 extern crate common;
+extern crate gamenet_ddnet;
+extern crate gamenet_teeworlds_0_5;
+extern crate gamenet_teeworlds_0_7;
 #[macro_use]
 extern crate log;
 extern crate packer;
+#[cfg(feature = "http")]
+extern crate serde;
+#[cfg(feature = "http")]
+#[macro_use]
+extern crate serde_derive;
+#[cfg(feature = "http")]
+extern crate serde_json;
+#[cfg(feature = "http")]
+extern crate ureq;
 extern crate warn;
 
+#[cfg(feature = "http")]
+pub mod http;
 pub mod protocol;
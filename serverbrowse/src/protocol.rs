@@ -1,10 +1,11 @@
 use arrayvec::ArrayString;
-use common;
 use common::num::BeU16;
 use common::num::BeU32;
 use common::num::Cast;
-use common::num::LeU16;
 use common::str::truncated_arraystring;
+use gamenet_ddnet::msg::connless as connless6;
+use gamenet_teeworlds_0_5::msg::connless as connless5;
+use gamenet_teeworlds_0_7::msg::connless as connless7;
 use packer::Unpacker;
 use std::default::Default;
 use std::fmt;
@@ -14,6 +15,9 @@ use std::net::Ipv6Addr;
 use std::str;
 use warn::Ignore;
 
+pub use gamenet_ddnet::msg::AddrPacked as Addr6Packed;
+pub use gamenet_teeworlds_0_5::msg::Addr5Packed;
+
 const PLAYER_MAX_NAME_LENGTH: usize = 16 - 1;
 const PLAYER_MAX_CLAN_LENGTH: usize = 12 - 1;
 const MAX_CLIENTS_5: u32 = 16;
@@ -23,7 +27,8 @@ const MAX_CLIENTS_7: u32 = 64;
 pub const MASTERSERVER_PORT: u16 = 8300;
 pub const MASTERSERVER_7_PORT: u16 = 8283;
 
-const HEADER_LEN: usize = 14;
+const PACKET_HEADER_LEN: usize = 6;
+const HEADER_LEN: usize = PACKET_HEADER_LEN + 8;
 pub type Header = &'static [u8; HEADER_LEN];
 pub const REQUEST_LIST_5: Header = b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\xffreqt";
 pub const REQUEST_LIST_6: Header = b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\xffreq2";
@@ -212,16 +217,6 @@ impl From<ReceivedServerInfoVersion> for ServerInfoVersion {
     }
 }
 
-impl ReceivedServerInfoVersion {
-    fn is_normal(self) -> bool {
-        use self::ReceivedServerInfoVersion::*;
-        match self {
-            Normal(_) => true,
-            _ => false,
-        }
-    }
-}
-
 impl ServerInfoVersion {
     pub fn max_clients(self) -> Option<u32> {
         Some(match self {
@@ -289,6 +284,12 @@ pub struct ServerInfo {
     pub num_clients: i32,
     pub max_clients: i32,
     pub clients: Vec<ClientInfo>,
+    /// Continent and country of the server, e.g. `eu:de`, only known from
+    /// the HTTPS master servers.
+    pub location: Option<ArrayString<[u8; 16]>>,
+    /// Community the server belongs to, only known from the HTTPS master
+    /// servers.
+    pub community: Option<ArrayString<[u8; 32]>>,
 }
 
 impl ServerInfo {
@@ -301,7 +302,7 @@ impl fmt::Debug for ServerInfo {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "{:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?}/{:?} {:?}/{:?}: {:?}",
+            "{:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?}/{:?} {:?}/{:?} {:?} {:?}: {:?}",
             self.info_version,
             self.token,
             self.version,
@@ -316,6 +317,8 @@ impl fmt::Debug for ServerInfo {
             self.max_players,
             self.num_clients,
             self.max_clients,
+            self.location,
+            self.community,
             self.clients,
         )
     }
@@ -399,21 +402,172 @@ fn debug_parse_fail(help: &str) -> Option<PartialServerInfo> {
     None
 }
 
-fn parse_server_info<RI, RS>(
-    unpacker: &mut Unpacker,
+/// Server info fields of all server info versions.
+///
+/// The fields a version doesn't send are left at their defaults, see
+/// `ServerInfoVersion`.
+#[derive(Default)]
+struct InfoHeader<'a> {
+    token: i32,
+    packet_no: Option<i32>,
+    version: &'a [u8],
+    name: &'a [u8],
+    hostname: Option<&'a [u8]>,
+    map: &'a [u8],
+    map_crc: Option<i32>,
+    map_size: Option<i32>,
+    game_type: &'a [u8],
+    flags: i32,
+    progression: Option<i32>,
+    skill_level: Option<i32>,
+    num_players: i32,
+    max_players: i32,
+    num_clients: i32,
+    max_clients: i32,
+    offset: i32,
+    clients: &'a [u8],
+}
+
+impl<'a> From<connless5::Info<'a>> for InfoHeader<'a> {
+    fn from(i: connless5::Info<'a>) -> InfoHeader<'a> {
+        InfoHeader {
+            token: i.token,
+            version: i.version,
+            name: i.name,
+            map: i.map,
+            game_type: i.game_type,
+            flags: i.flags,
+            progression: Some(i.progression),
+            num_players: i.num_players,
+            max_players: i.max_players,
+            num_clients: i.num_players,
+            max_clients: i.max_players,
+            clients: i.clients.as_bytes(),
+            ..Default::default()
+        }
+    }
+}
+
+impl<'a> From<connless6::Info<'a>> for InfoHeader<'a> {
+    fn from(i: connless6::Info<'a>) -> InfoHeader<'a> {
+        InfoHeader {
+            token: i.token,
+            version: i.version,
+            name: i.name,
+            map: i.map,
+            game_type: i.game_type,
+            flags: i.flags,
+            num_players: i.num_players,
+            max_players: i.max_players,
+            num_clients: i.num_clients,
+            max_clients: i.max_clients,
+            clients: i.clients.as_bytes(),
+            ..Default::default()
+        }
+    }
+}
+
+impl<'a> From<connless6::Info64<'a>> for InfoHeader<'a> {
+    fn from(i: connless6::Info64<'a>) -> InfoHeader<'a> {
+        InfoHeader {
+            token: i.token,
+            version: i.version,
+            name: i.name,
+            map: i.map,
+            game_type: i.game_type,
+            flags: i.flags,
+            num_players: i.num_players,
+            max_players: i.max_players,
+            num_clients: i.num_clients,
+            max_clients: i.max_clients,
+            offset: i.offset,
+            clients: i.clients.as_bytes(),
+            ..Default::default()
+        }
+    }
+}
+
+impl<'a> From<connless6::InfoExtended<'a>> for InfoHeader<'a> {
+    fn from(i: connless6::InfoExtended<'a>) -> InfoHeader<'a> {
+        InfoHeader {
+            token: i.token,
+            version: i.version,
+            name: i.name,
+            map: i.map,
+            map_crc: Some(i.map_crc),
+            map_size: Some(i.map_size),
+            game_type: i.game_type,
+            flags: i.flags,
+            num_players: i.num_players,
+            max_players: i.max_players,
+            num_clients: i.num_clients,
+            max_clients: i.max_clients,
+            clients: i.clients.as_bytes(),
+            ..Default::default()
+        }
+    }
+}
+
+impl<'a> From<connless6::InfoExtendedMore<'a>> for InfoHeader<'a> {
+    fn from(i: connless6::InfoExtendedMore<'a>) -> InfoHeader<'a> {
+        InfoHeader {
+            token: i.token,
+            packet_no: Some(i.packet_no),
+            clients: i.clients.as_bytes(),
+            ..Default::default()
+        }
+    }
+}
+
+impl<'a> From<connless7::Info<'a>> for InfoHeader<'a> {
+    fn from(i: connless7::Info<'a>) -> InfoHeader<'a> {
+        InfoHeader {
+            token: i.token,
+            version: i.version,
+            name: i.name,
+            hostname: Some(i.hostname),
+            map: i.map,
+            game_type: i.game_type,
+            flags: i.flags,
+            skill_level: Some(i.skill_level),
+            num_players: i.num_players,
+            max_players: i.max_players,
+            num_clients: i.num_clients,
+            max_clients: i.max_clients,
+            clients: i.clients.as_bytes(),
+            ..Default::default()
+        }
+    }
+}
+
+fn decode_info<'a, D, T, E>(data: &'a [u8], decode: D) -> Option<InfoHeader<'a>>
+where
+    D: FnOnce(&mut Ignore, &mut Unpacker<'a>) -> Result<T, E>,
+    T: Into<InfoHeader<'a>>,
+    E: fmt::Debug,
+{
+    match decode(&mut Ignore, &mut Unpacker::new(data)) {
+        Ok(info) => Some(info.into()),
+        Err(e) => {
+            debug!("server info decoding failed: {:?}", e);
+            None
+        }
+    }
+}
+
+fn parse_server_info<RI>(
+    header: InfoHeader,
     read_int: RI,
-    read_str: RS,
     received_version: ReceivedServerInfoVersion,
 ) -> Option<PartialServerInfo>
 where
     RI: FnMut(&mut Unpacker) -> Option<i32>,
-    RS: for<'a> FnMut(&mut Unpacker<'a>) -> Option<&'a str>,
 {
     use self::debug_parse_fail as fail;
 
     let mut read_int = read_int;
-    let mut read_str = read_str;
     let mut result = PartialServerInfo::new();
+    let unpacker = &mut Unpacker::new(header.clients);
 
     macro_rules! int {
         ($cause:expr) => {
@@ -423,7 +577,13 @@ where
 
     macro_rules! str {
         ($cause:expr) => {
-            truncated_arraystring(unwrap_or_return!(read_str(unpacker), fail($cause)))
+            truncated_arraystring(unwrap_or_return!(info_read_str(unpacker), fail($cause)))
+        };
+    }
+
+    macro_rules! header_str {
+        ($field:expr, $cause:expr) => {
+            truncated_arraystring(unwrap_or_return!(str::from_utf8($field).ok(), fail($cause)))
         };
     }
 
@@ -432,63 +592,38 @@ where
     {
         let i = &mut result.info;
         i.info_version = version;
-        i.token = int!("token");
+        i.token = header.token;
         let packet_no;
         let offset;
-        if !received_version.is_normal() {
-            packet_no = int!("packet_no");
+        if let Some(no) = header.packet_no {
+            packet_no = no;
             if packet_no < 1 || packet_no > 64 {
                 return fail("packet_no sanity check");
             }
             offset = 0;
         } else {
             packet_no = 0;
-            i.version = str!("version");
-            i.name = str!("name");
-            if version.has_hostname() {
-                i.hostname = Some(str!("hostname"));
-            } else {
-                i.hostname = None;
-            }
-            i.map = str!("map");
-            if version.has_extended_map_info() {
-                i.map_crc = Some(int!("map_crc") as u32);
-                let map_size = int!("map_size");
-                if map_size < 0 {
-                    return fail("map_size sanity check");
-                }
-                i.map_size = Some(map_size.assert_u32());
-            } else {
-                i.map_crc = None;
-                i.map_size = None;
-            }
-            i.game_type = str!("game_type");
-            i.flags = int!("flags");
-            if version.has_progression() {
-                i.progression = Some(int!("progression"));
-            } else {
-                i.progression = None;
-            }
-            if version.has_skill_level() {
-                i.skill_level = Some(int!("skill_level"));
-            } else {
-                i.skill_level = None;
-            }
-            i.num_players = int!("num_players");
-            i.max_players = int!("max_players");
-            if version.has_extended_player_info() {
-                i.num_clients = int!("num_clients");
-                i.max_clients = int!("max_clients");
-            } else {
-                i.num_clients = i.num_players;
-                i.max_clients = i.max_players;
-            }
-            let raw_offset;
-            if version.has_offset() {
-                raw_offset = int!("offset");
-            } else {
-                raw_offset = 0;
-            }
+            i.version = header_str!(header.version, "version");
+            i.name = header_str!(header.name, "name");
+            i.hostname = match header.hostname {
+                Some(hostname) => Some(header_str!(hostname, "hostname")),
+                None => None,
+            };
+            i.map = header_str!(header.map, "map");
+            i.map_crc = header.map_crc.map(|crc| crc as u32);
+            i.map_size = match header.map_size {
+                Some(size) if size < 0 => return fail("map_size sanity check"),
+                Some(size) => Some(size.assert_u32()),
+                None => None,
+            };
+            i.game_type = header_str!(header.game_type, "game_type");
+            i.flags = header.flags;
+            i.progression = header.progression;
+            i.skill_level = header.skill_level;
+            i.num_players = header.num_players;
+            i.max_players = header.max_players;
+            i.num_clients = header.num_clients;
+            i.max_clients = header.max_clients;
             if i.num_clients < 0
                 || i.num_clients > i.max_clients
                 || i.max_clients < 0
@@ -503,10 +638,7 @@ where
             {
                 return fail("count sanity check");
             }
-            offset = unwrap_or_return!(raw_offset.try_u32(), fail("offset sanity check"));
-        }
-        if version.has_extra_info() {
-            let _: ArrayString<[u8; 0]> = str!("extra_info");
+            offset = unwrap_or_return!(header.offset.try_u32(), fail("offset sanity check"));
         }
 
         if version == ServerInfoVersion::V6Ex {
@@ -514,7 +646,7 @@ where
         }
 
         for j in offset.. {
-            let name = match read_str(unpacker) {
+            let name = match info_read_str(unpacker) {
                 Some(n) => truncated_arraystring(n),
                 None => break,
             };
@@ -578,11 +710,9 @@ fn info_read_str<'a>(unpacker: &mut Unpacker<'a>) -> Option<&'a str> {
 impl<'a> Info5Response<'a> {
     pub fn parse(self) -> Option<ServerInfo> {
         let Info5Response(slice) = self;
-        let mut unpacker = Unpacker::new(slice);
         parse_server_info(
-            &mut unpacker,
+            decode_info(slice, connless5::Info::decode)?,
             info_read_int_v5,
-            info_read_str,
             ReceivedServerInfoVersion::Normal(ServerInfoVersion::V5),
         )
         .map(|mut raw| {
@@ -595,11 +725,9 @@ impl<'a> Info5Response<'a> {
 impl<'a> Info6Response<'a> {
     pub fn parse(self) -> Option<ServerInfo> {
         let Info6Response(slice) = self;
-        let mut unpacker = Unpacker::new(slice);
         parse_server_info(
-            &mut unpacker,
+            decode_info(slice, connless6::Info::decode)?,
             info_read_int_v5,
-            info_read_str,
             ReceivedServerInfoVersion::Normal(ServerInfoVersion::V6),
         )
         .map(|mut raw| {
@@ -612,11 +740,9 @@ impl<'a> Info6Response<'a> {
 impl<'a> Info664Response<'a> {
     pub fn parse(self) -> Option<PartialServerInfo> {
         let Info664Response(slice) = self;
-        let mut unpacker = Unpacker::new(slice);
         parse_server_info(
-            &mut unpacker,
+            decode_info(slice, connless6::Info64::decode)?,
             info_read_int_v5,
-            info_read_str,
             ReceivedServerInfoVersion::Normal(ServerInfoVersion::V664),
         )
     }
@@ -625,11 +751,9 @@ impl<'a> Info664Response<'a> {
 impl<'a> Info6ExResponse<'a> {
     pub fn parse(self) -> Option<PartialServerInfo> {
         let Info6ExResponse(slice) = self;
-        let mut unpacker = Unpacker::new(slice);
         parse_server_info(
-            &mut unpacker,
+            decode_info(slice, connless6::InfoExtended::decode)?,
             info_read_int_v5,
-            info_read_str,
             ReceivedServerInfoVersion::Normal(ServerInfoVersion::V6Ex),
         )
     }
@@ -638,11 +762,9 @@ impl<'a> Info6ExResponse<'a> {
 impl<'a> Info6ExMoreResponse<'a> {
     pub fn parse(self) -> Option<PartialServerInfo> {
         let Info6ExMoreResponse(slice) = self;
-        let mut unpacker = Unpacker::new(slice);
         parse_server_info(
-            &mut unpacker,
+            decode_info(slice, connless6::InfoExtendedMore::decode)?,
             info_read_int_v5,
-            info_read_str,
             ReceivedServerInfoVersion::V6ExMore,
         )
     }
@@ -651,11 +773,9 @@ impl<'a> Info6ExMoreResponse<'a> {
 impl<'a> Info7Response<'a> {
     pub fn parse(self) -> Option<ServerInfo> {
         let Info7Response(_, _, slice) = self;
-        let mut unpacker = Unpacker::new(slice);
         parse_server_info(
-            &mut unpacker,
+            decode_info(slice, connless7::Info::decode)?,
             info_read_int_v7,
-            info_read_str,
             ReceivedServerInfoVersion::Normal(ServerInfoVersion::V7),
         )
         .map(|mut raw| {
@@ -706,37 +826,26 @@ pub enum Response<'a> {
     Token7(Token7Response),
 }
 
-fn parse_list5(data: &[u8]) -> &[Addr5Packed] {
-    let remainder = data.len() % mem::size_of::<Addr5Packed>();
-    if remainder != 0 {
-        warn!("parsing overlong list5");
-    }
-    let data = &data[..data.len() - remainder];
-    unsafe { common::slice::transmute(data) }
-}
-
-fn parse_list6(data: &[u8]) -> &[Addr6Packed] {
-    let remainder = data.len() % mem::size_of::<Addr6Packed>();
-    if remainder != 0 {
-        warn!("parsing overlong list5");
-    }
-    let data = &data[..data.len() - remainder];
-    unsafe { common::slice::transmute(data) }
-}
-
 fn parse_token(data: &[u8]) -> Option<u32> {
     let (token, _) = BeU32::from_byte_slice(data)?;
     Some(token.to_u32())
 }
 
-fn parse_count(data: &[u8]) -> Option<u16> {
-    if data.len() < 2 {
+fn split_connless_id(data: &[u8]) -> Option<([u8; 8], &[u8])> {
+    if data.len() < 8 {
         return None;
     }
-    if data.len() > 2 {
-        warn!("parsing overlong count");
-    }
-    Some(((data[0] as u16) << 8) | (data[1] as u16))
+    let (id, payload) = data.split_at(8);
+    let mut connless_id = [0; 8];
+    connless_id.copy_from_slice(id);
+    Some((connless_id, payload))
+}
+
+fn decode<'a, D, T, E>(data: &'a [u8], decode: D) -> Option<T>
+where
+    D: FnOnce(&mut Ignore, &mut Unpacker<'a>) -> Result<T, E>,
+{
+    decode(&mut Ignore, &mut Unpacker::new(data)).ok()
 }
 
 pub fn parse_response(data: &[u8]) -> Option<Response> {
@@ -763,59 +872,59 @@ pub fn parse_response(data: &[u8]) -> Option<Response> {
             };
         }
         Some(0x21) => {
-            if data.len() < 17 {
+            if data.len() < 9 {
                 return None;
             }
-            let mut header = [0; 17];
             let mut own_token = [0; 4];
             let mut their_token = [0; 4];
-            let payload = &data[17..];
-            header.copy_from_slice(&data[..17]);
-            own_token.copy_from_slice(&header[1..5]);
-            their_token.copy_from_slice(&header[5..9]);
+            own_token.copy_from_slice(&data[1..5]);
+            their_token.copy_from_slice(&data[5..9]);
             let own_token = BeU32::from_bytes(&own_token).to_u32();
             let their_token = BeU32::from_bytes(&their_token).to_u32();
-            for b in &mut header[1..9] {
-                *b = 0xff;
-            }
-            return match &header {
-                LIST_7 => Some(Response::List7(List7Response(
+            let (id, payload) = split_connless_id(&data[9..])?;
+            return match &id {
+                connless7::LIST => Some(Response::List7(List7Response(
                     own_token,
                     their_token,
-                    parse_list6(payload),
+                    decode(payload, connless7::List::decode)?.servers,
                 ))),
-                INFO_7 => Some(Response::Info7(Info7Response(
+                connless7::INFO => Some(Response::Info7(Info7Response(
                     own_token,
                     their_token,
                     payload,
                 ))),
-                COUNT_7 => parse_count(payload)
-                    .map(|x| Response::Count7(Count7Response(own_token, their_token, x))),
+                connless7::COUNT => Some(Response::Count7(Count7Response(
+                    own_token,
+                    their_token,
+                    decode(payload, connless7::Count::decode)?.count,
+                ))),
                 _ => None,
             };
         }
         _ => {}
     }
-    if data.len() < HEADER_LEN {
+    if data.len() < PACKET_HEADER_LEN {
         return None;
     }
     if data[0] & PACKETFLAG_CONNLESS == 0 {
         return None;
     }
-    let (header, data) = data.split_at(HEADER_LEN);
-    let mut header: [u8; HEADER_LEN] = *unsafe { &*(header.as_ptr() as *const [u8; HEADER_LEN]) };
-    for b in &mut header[..6] {
-        *b = 0xff;
-    }
-    match &header {
-        LIST_5 => Some(Response::List5(List5Response(parse_list5(data)))),
-        LIST_6 => Some(Response::List6(List6Response(parse_list6(data)))),
-        INFO_5 => Some(Response::Info5(Info5Response(data))),
-        INFO_6 => Some(Response::Info6(Info6Response(data))),
-        INFO_6_64 => Some(Response::Info664(Info664Response(data))),
-        INFO_6_EX => Some(Response::Info6Ex(Info6ExResponse(data))),
-        INFO_6_EX_MORE => Some(Response::Info6ExMore(Info6ExMoreResponse(data))),
-        COUNT => parse_count(data).map(|x| Response::Count(CountResponse(x))),
+    let (id, payload) = split_connless_id(&data[PACKET_HEADER_LEN..])?;
+    match &id {
+        connless5::LIST => Some(Response::List5(List5Response(
+            decode(payload, connless5::List::decode)?.servers,
+        ))),
+        connless6::LIST => Some(Response::List6(List6Response(
+            decode(payload, connless6::List::decode)?.servers,
+        ))),
+        connless5::INFO => Some(Response::Info5(Info5Response(payload))),
+        connless6::INFO => Some(Response::Info6(Info6Response(payload))),
+        connless6::INFO64 => Some(Response::Info664(Info664Response(payload))),
+        connless6::INFO_EXTENDED => Some(Response::Info6Ex(Info6ExResponse(payload))),
+        connless6::INFO_EXTENDED_MORE => Some(Response::Info6ExMore(Info6ExMoreResponse(payload))),
+        connless6::COUNT => Some(Response::Count(CountResponse(
+            decode(payload, connless6::Count::decode)?.count,
+        ))),
         _ => None,
     }
 }
@@ -856,18 +965,8 @@ impl fmt::Debug for IpAddr {
     }
 }
 
-#[derive(Clone, Copy)]
-#[repr(C, packed)]
-pub struct Addr5Packed {
-    ip_address: [u8; 4],
-    port: LeU16,
-}
-
-#[derive(Clone, Copy)]
-#[repr(C, packed)]
-pub struct Addr6Packed {
-    ip_address: [u8; 16],
-    port: BeU16,
+pub trait AddrPackedExt {
+    fn unpack(self) -> Addr;
 }
 
 // ---------------------------------------
@@ -891,12 +990,12 @@ fn check_alignment_addr5_packed() {
     assert_eq!(mem::align_of::<Addr5Packed>(), 1);
 }
 
-impl Addr5Packed {
-    pub fn unpack(self) -> Addr {
-        let Addr5Packed { ip_address, port } = self;
+impl AddrPackedExt for Addr5Packed {
+    fn unpack(self) -> Addr {
+        let ip_address = self.ip_address();
         Addr {
             ip_address: IpAddr::new_v4(ip_address[0], ip_address[1], ip_address[2], ip_address[3]),
-            port: port.to_u16(),
+            port: self.port(),
         }
     }
 }
@@ -906,9 +1005,9 @@ fn check_alignment_addr6_packed() {
     assert_eq!(mem::align_of::<Addr6Packed>(), 1);
 }
 
-impl Addr6Packed {
-    pub fn unpack(self) -> Addr {
-        let Addr6Packed { ip_address, port } = self;
+impl AddrPackedExt for Addr6Packed {
+    fn unpack(self) -> Addr {
+        let ip_address = self.ip_address();
         let (maybe_ipv4_mapping, ipv4_address) = ip_address.split_at(IPV4_MAPPING.len());
         let new_address = if maybe_ipv4_mapping != IPV4_MAPPING {
             let ip_address: [BeU16; 8] = unsafe { mem::transmute(ip_address) };
@@ -932,21 +1031,157 @@ impl Addr6Packed {
         };
         Addr {
             ip_address: new_address,
-            port: port.to_u16(),
+            port: self.port(),
         }
     }
 }
 
 #[cfg(test)]
 mod test {
+    use super::parse_response;
+    use super::Addr;
+    use super::AddrPackedExt;
     use super::ClientInfo;
+    use super::Count7Response;
+    use super::CountResponse;
     use super::Info6ExMoreResponse;
     use super::Info6ExResponse;
     use super::Info6Response;
+    use super::IpAddr;
+    use super::List5Response;
+    use super::List6Response;
+    use super::List7Response;
+    use super::Response;
     use super::ServerInfo;
     use super::ServerInfoVersion;
     use common::str::truncated_arraystring as b;
 
+    fn addr(a: u8, b: u8, c: u8, d: u8, port: u16) -> Addr {
+        Addr {
+            ip_address: IpAddr::new_v4(a, b, c, d),
+            port,
+        }
+    }
+
+    #[test]
+    fn parse_list5() {
+        let packet = b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\xfflist\x7f\0\0\x01\x6f\x20\xc0\xa8\0\x02\x70\x20";
+        let servers: Vec<_> = match parse_response(packet) {
+            Some(Response::List5(List5Response(s))) => s.iter().map(|s| s.unpack()).collect(),
+            _ => panic!("not a 0.5 list"),
+        };
+        assert_eq!(
+            servers,
+            [addr(127, 0, 0, 1, 8303), addr(192, 168, 0, 2, 8304)]
+        );
+    }
+
+    #[test]
+    fn parse_list6() {
+        let packet = b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\xfflis2\
+            \0\0\0\0\0\0\0\0\0\0\xff\xff\x7f\0\0\x01\x20\x6f\
+            \x20\x01\x0d\xb8\0\0\0\0\0\0\0\0\0\0\0\x01\x20\x70";
+        let servers: Vec<_> = match parse_response(packet) {
+            Some(Response::List6(List6Response(s))) => s.iter().map(|s| s.unpack()).collect(),
+            _ => panic!("not a 0.6 list"),
+        };
+        let ipv6 = Addr {
+            ip_address: IpAddr::new_v6(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+            port: 8304,
+        };
+        assert_eq!(servers, [addr(127, 0, 0, 1, 8303), ipv6]);
+    }
+
+    #[test]
+    fn parse_list7() {
+        let packet = b"\x21\x01\x02\x03\x04\x05\x06\x07\x08\xff\xff\xff\xfflis2\
+            \0\0\0\0\0\0\0\0\0\0\xff\xff\x7f\0\0\x01\x20\x6f";
+        match parse_response(packet) {
+            Some(Response::List7(List7Response(own, their, s))) => {
+                assert_eq!((own, their), (0x01020304, 0x05060708));
+                assert_eq!(s.len(), 1);
+                assert_eq!(s[0].unpack(), addr(127, 0, 0, 1, 8303));
+            }
+            _ => panic!("not a 0.7 list"),
+        }
+    }
+
+    #[test]
+    fn parse_count() {
+        let packet = b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\xffsiz2\x01\x2c";
+        match parse_response(packet) {
+            Some(Response::Count(CountResponse(300))) => {}
+            _ => panic!("not a count of 300"),
+        }
+        let packet = b"\x21\0\0\0\x01\0\0\0\x02\xff\xff\xff\xffsiz2\0\x05";
+        match parse_response(packet) {
+            Some(Response::Count7(Count7Response(1, 2, 5))) => {}
+            _ => panic!("not a 0.7 count of 5"),
+        }
+        let truncated = b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\xffsiz2\x01";
+        assert!(parse_response(truncated).is_none());
+    }
+
+    #[test]
+    fn parse_unknown() {
+        assert!(parse_response(b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\xffxxxx").is_none());
+        assert!(parse_response(b"\xff\xff\xff\xff\xff\xff\xff\xff\xff").is_none());
+        // Missing connless flag.
+        assert!(parse_response(b"\x00\0\0\0\0\0\xff\xff\xff\xfflis2").is_none());
+    }
+
+    #[test]
+    fn parse_info_v5() {
+        let packet = b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\xffinf2\
+            7\x000.5.2\x00My server\x00dm1\x00DM\x000\x0050\x001\x008\x00\
+            nameless tee\x0012\x00";
+        let info = match parse_response(packet) {
+            Some(Response::Info5(info)) => info.parse().unwrap(),
+            _ => panic!("not a 0.5 info"),
+        };
+        let wanted = ServerInfo {
+            info_version: ServerInfoVersion::V5,
+            token: 7,
+            version: b("0.5.2"),
+            name: b("My server"),
+            hostname: None,
+            map: b("dm1"),
+            map_crc: None,
+            map_size: None,
+            game_type: b("DM"),
+            flags: 0,
+            progression: Some(50),
+            skill_level: None,
+            num_players: 1,
+            max_players: 8,
+            num_clients: 1,
+            max_clients: 8,
+            clients: vec![ClientInfo {
+                name: b("nameless tee"),
+                clan: b(""),
+                country: -1,
+                score: 12,
+                is_player: 1,
+            }],
+            location: None,
+            community: None,
+        };
+        assert_eq!(info, wanted);
+    }
+
+    #[test]
+    fn parse_info_invalid() {
+        // Control characters in the server name.
+        let info_raw = b"0\x000.6.4\x00bad\x01name\x00map\x00DM\x000\x000\x0016\x000\x0016\x00";
+        assert_eq!(Info6Response(info_raw).parse(), None);
+        // More players than clients.
+        let info_raw = b"0\x000.6.4\x00name\x00map\x00DM\x000\x002\x0016\x001\x0016\x00";
+        assert_eq!(Info6Response(info_raw).parse(), None);
+        // Truncated.
+        let info_raw = b"0\x000.6.4\x00name\x00map\x00DM\x000\x00";
+        assert_eq!(Info6Response(info_raw).parse(), None);
+    }
+
     #[test]
     fn parse_info_v6_real_world() {
         let info_raw = b"0\x000.6.4, 11.2.1\x00DDNet RUS - Moderate [DDraceNetwork] [0/64]\x00Sunreal\x00DDraceNetwork\x000\x000\x0016\x000\x0016\x00";
@@ -968,6 +1203,8 @@ mod test {
             num_clients: 0,
             max_clients: 16,
             clients: vec![],
+            location: None,
+            community: None,
         };
         assert_eq!(Info6Response(info_raw).parse(), Some(info));
     }
@@ -1008,6 +1245,8 @@ mod test {
                     is_player: 10,
                 },
             ],
+            location: None,
+            community: None,
         };
         assert_eq!(Info6Response(info_raw).parse(), Some(info));
     }
@@ -1099,6 +1338,8 @@ mod test {
                     is_player: 0,
                 },
             ],
+            location: None,
+            community: None,
         };
         let info_p0 = Info6ExResponse(info_raw_p0).parse().unwrap();
         let info_p1 = Info6ExMoreResponse(info_raw_p1).parse().unwrap();
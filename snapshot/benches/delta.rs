@@ -0,0 +1,58 @@
+#[macro_use]
+extern crate bencher;
+extern crate snapshot;
+
+use bencher::black_box;
+use bencher::Bencher;
+use snapshot::snap::Builder;
+use snapshot::Delta;
+use snapshot::Snap;
+
+const NUM_CLIENTS: usize = 64;
+
+/// Builds a snapshot resembling a full server, with a character and a player
+/// info per client and the positions shifted by `tick`.
+fn server_snap(tick: i32) -> Snap {
+    let mut builder = Builder::new();
+    for id in 0..NUM_CLIENTS as u16 {
+        let pos = id as i32 * 32 + tick;
+        let mut character = [0; 22];
+        character[0] = tick;
+        character[1] = pos;
+        character[2] = pos;
+        builder.add_item(9, id, &character).unwrap();
+        builder.add_item(10, id, &[1, id as i32, 0, 0, 0]).unwrap();
+    }
+    builder.add_item(6, 0, &[0, 0, tick, 0, 0, 0]).unwrap();
+    builder.finish()
+}
+
+fn froms() -> Vec<Snap> {
+    (0..NUM_CLIENTS as i32).map(server_snap).collect()
+}
+
+fn create_each(bench: &mut Bencher) {
+    let froms = froms();
+    let to = server_snap(NUM_CLIENTS as i32);
+    let mut deltas = vec![Delta::new(); NUM_CLIENTS];
+    bench.iter(|| {
+        for (delta, from) in deltas.iter_mut().zip(&froms) {
+            delta.create(from, &to);
+        }
+        black_box(&deltas);
+    });
+}
+
+fn create_many(bench: &mut Bencher) {
+    let froms = froms();
+    let froms: Vec<&Snap> = froms.iter().collect();
+    let to = server_snap(NUM_CLIENTS as i32);
+    let mut deltas = vec![Delta::new(); NUM_CLIENTS];
+    bench.iter(|| {
+        Delta::create_many(&mut deltas, &froms, &to);
+        black_box(&deltas);
+    });
+}
+
+benchmark_group!(delta, create_each, create_many);
+benchmark_main!(delta);
@@ -0,0 +1,27 @@
+#![no_main]
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate gamenet_teeworlds_0_6 as gamenet;
+extern crate packer;
+extern crate snapshot;
+extern crate warn;
+
+use gamenet::snap_obj::obj_size;
+use packer::Unpacker;
+use snapshot::Delta;
+use snapshot::Snap;
+use warn::Ignore;
+
+// Never panic, whatever the input. Also apply the resulting delta to an
+// empty snapshot.
+fuzz_target!(|data: &[u8]| {
+    let mut delta = Delta::new();
+    if delta
+        .read(&mut Ignore, obj_size, &mut Unpacker::new(data))
+        .is_err()
+    {
+        return;
+    }
+    let mut snap = Snap::empty();
+    let _ = snap.read_with_delta(&mut Ignore, &Snap::empty(), &delta);
+});
@@ -0,0 +1,64 @@
+#![no_main]
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate gamenet_teeworlds_0_6 as gamenet;
+extern crate packer;
+extern crate snapshot;
+extern crate warn;
+
+use gamenet::snap_obj::obj_size;
+use packer::with_packer;
+use packer::Unpacker;
+use snapshot::Delta;
+use snapshot::Snap;
+use snapshot::SnapReader;
+use warn::Ignore;
+
+fn assert_same(left: &Snap, right: &Snap) {
+    assert_eq!(left.crc(), right.crc());
+    assert_eq!(left.items().len(), right.items().len());
+    for item in left.items() {
+        assert_eq!(right.item(item.type_id, item.id), Some(item.data));
+    }
+}
+
+// Every snapshot that can be read from a delta must survive being written
+// and read again, both as a full snapshot and as a delta.
+fuzz_target!(|data: &[u8]| {
+    let mut delta = Delta::new();
+    if delta
+        .read(&mut Ignore, obj_size, &mut Unpacker::new(data))
+        .is_err()
+    {
+        return;
+    }
+    let mut snap = Snap::empty();
+    if snap
+        .read_with_delta(&mut Ignore, &Snap::empty(), &delta)
+        .is_err()
+    {
+        return;
+    }
+
+    let mut keys = Vec::new();
+    let mut buf: Vec<u8> = Vec::with_capacity(1024 * 1024);
+    let written = with_packer(&mut buf, |p| snap.write(&mut keys, p)).unwrap();
+    let mut reader = SnapReader::new();
+    let read = reader
+        .read(&mut Ignore, Snap::empty(), &mut Unpacker::new(written))
+        .unwrap();
+    assert_same(&snap, &read);
+
+    let mut created = Delta::new();
+    created.create(&Snap::empty(), &snap);
+    let mut buf: Vec<u8> = Vec::with_capacity(1024 * 1024);
+    let written = with_packer(&mut buf, |p| created.write(obj_size, p)).unwrap();
+    let mut read_delta = Delta::new();
+    read_delta
+        .read(&mut Ignore, obj_size, &mut Unpacker::new(written))
+        .unwrap();
+    let mut read = Snap::empty();
+    read.read_with_delta(&mut Ignore, &Snap::empty(), &read_delta)
+        .unwrap();
+    assert_same(&snap, &read);
+});
@@ -0,0 +1,17 @@
+#![no_main]
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate packer;
+extern crate snapshot;
+extern crate warn;
+
+use packer::Unpacker;
+use snapshot::Snap;
+use snapshot::SnapReader;
+use warn::Ignore;
+
+// Never panic, whatever the input.
+fuzz_target!(|data: &[u8]| {
+    let mut reader = SnapReader::new();
+    let _ = reader.read(&mut Ignore, Snap::empty(), &mut Unpacker::new(data));
+});
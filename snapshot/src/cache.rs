@@ -0,0 +1,224 @@
+use common::num::Cast;
+use common::time::Tick;
+use snap::Snap;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::ops;
+use to_usize;
+
+/// Cache for encoded snapshot deltas of a single tick.
+///
+/// Clients that see the same snapshot (e.g. spectators of the same player)
+/// and have acknowledged the same delta tick get the same encoded delta. This
+/// cache makes it possible to only encode it once per tick.
+///
+/// Entries are keyed by delta tick and the contents of both the base snapshot
+/// and the target snapshot of the delta, so a cached delta is only ever
+/// returned for the exact same pair of snapshots.
+///
+/// All entries are dropped when a different tick is requested.
+#[derive(Clone, Default)]
+pub struct Cache {
+    tick: Option<Tick>,
+    // Entries by delta tick and hash of both snapshots.
+    entries: HashMap<(Tick, u64), Vec<Entry>>,
+    buf: Vec<u8>,
+    hits: u64,
+    misses: u64,
+}
+
+#[derive(Clone)]
+struct Entry {
+    from: Snap,
+    to: Snap,
+    // Points into `Cache::buf`.
+    delta: ops::Range<u32>,
+}
+
+/// Hash of the items of a snapshot, independent of their order.
+fn hash(snap: &Snap) -> u64 {
+    snap.items().fold(0, |h, item| {
+        let mut hasher = DefaultHasher::new();
+        (item.type_id, item.id, item.data).hash(&mut hasher);
+        h.wrapping_add(hasher.finish())
+    })
+}
+
+fn key(delta_tick: Tick, from: &Snap, to: &Snap) -> (Tick, u64) {
+    (delta_tick, hash(from).rotate_left(1) ^ hash(to))
+}
+
+impl Cache {
+    pub fn new() -> Cache {
+        Default::default()
+    }
+    /// Drops all cached entries.
+    pub fn clear(&mut self) {
+        self.tick = None;
+        self.entries.clear();
+        self.buf.clear();
+    }
+    /// Returns the cached encoded delta from `from` to `to`, if any.
+    pub fn get(&self, tick: Tick, delta_tick: Tick, from: &Snap, to: &Snap) -> Option<&[u8]> {
+        if self.tick != Some(tick) {
+            return None;
+        }
+        self.entries
+            .get(&key(delta_tick, from, to))?
+            .iter()
+            .find(|e| e.from == *from && e.to == *to)
+            .map(|e| &self.buf[to_usize(e.delta.clone())])
+    }
+    /// Returns the cached encoded delta from `from` to `to` or encodes it
+    /// using `encode`.
+    ///
+    /// `encode` gets passed a buffer it should append the encoded delta to,
+    /// without touching the existing contents. If it fails, nothing is
+    /// cached.
+    pub fn get_or_insert_with<F, E>(
+        &mut self,
+        tick: Tick,
+        delta_tick: Tick,
+        from: &Snap,
+        to: &Snap,
+        encode: F,
+    ) -> Result<&[u8], E>
+    where
+        F: FnOnce(&mut Vec<u8>) -> Result<(), E>,
+    {
+        if self.tick != Some(tick) {
+            self.clear();
+            self.tick = Some(tick);
+        }
+        let entries = self.entries.entry(key(delta_tick, from, to)).or_default();
+        let delta = match entries.iter().find(|e| e.from == *from && e.to == *to) {
+            Some(e) => {
+                self.hits += 1;
+                e.delta.clone()
+            }
+            None => {
+                self.misses += 1;
+                let start = self.buf.len();
+                if let Err(e) = encode(&mut self.buf) {
+                    self.buf.truncate(start);
+                    return Err(e);
+                }
+                let delta = start.assert_u32()..self.buf.len().assert_u32();
+                entries.push(Entry {
+                    from: from.clone(),
+                    to: to.clone(),
+                    delta: delta.clone(),
+                });
+                delta
+            }
+        };
+        Ok(&self.buf[to_usize(delta)])
+    }
+    /// Number of lookups that were served from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+    /// Number of lookups that required encoding a delta.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Cache;
+    use common::time::Tick;
+    use snap::Builder;
+    use snap::Snap;
+
+    fn encode(data: &[u8]) -> impl FnOnce(&mut Vec<u8>) -> Result<(), ()> + '_ {
+        move |buf| {
+            buf.extend_from_slice(data);
+            Ok(())
+        }
+    }
+
+    fn snap(items: &[(u16, &[i32])]) -> Snap {
+        let mut builder = Builder::new();
+        for &(id, data) in items {
+            builder.add_item(1, id, data).unwrap();
+        }
+        builder.finish()
+    }
+
+    #[test]
+    fn hit_and_miss() {
+        let empty = Snap::empty();
+        let a = snap(&[(0, &[1, 2])]);
+        let b = snap(&[(0, &[1, 3])]);
+        let mut cache = Cache::new();
+        assert_eq!(
+            cache.get_or_insert_with(Tick(1), Tick(-1), &empty, &a, encode(b"abc")),
+            Ok(&b"abc"[..])
+        );
+        assert_eq!(
+            cache.get_or_insert_with(Tick(1), Tick(-1), &empty, &a.clone(), encode(b"xyz")),
+            Ok(&b"abc"[..])
+        );
+        assert_eq!(
+            cache.get_or_insert_with(Tick(1), Tick(0), &empty, &a, encode(b"def")),
+            Ok(&b"def"[..])
+        );
+        assert_eq!(cache.get(Tick(1), Tick(-1), &empty, &a), Some(&b"abc"[..]));
+        assert_eq!(cache.get(Tick(1), Tick(-1), &empty, &b), None);
+        assert_eq!(cache.get(Tick(1), Tick(-1), &b, &a), None);
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+
+        // A new tick invalidates everything.
+        assert_eq!(
+            cache.get_or_insert_with(Tick(2), Tick(-1), &empty, &a, encode(b"ghi")),
+            Ok(&b"ghi"[..])
+        );
+        assert_eq!(cache.get(Tick(1), Tick(-1), &empty, &a), None);
+    }
+
+    #[test]
+    fn same_crc() {
+        // Two players swapping their x coordinates.
+        let a = snap(&[(0, &[10, 0]), (1, &[20, 0])]);
+        let b = snap(&[(0, &[20, 0]), (1, &[10, 0])]);
+        assert_eq!(a.crc(), b.crc());
+        assert!(a != b);
+
+        let empty = Snap::empty();
+        let mut cache = Cache::new();
+        assert_eq!(
+            cache.get_or_insert_with(Tick(1), Tick(-1), &empty, &a, encode(b"a")),
+            Ok(&b"a"[..])
+        );
+        assert_eq!(
+            cache.get_or_insert_with(Tick(1), Tick(-1), &empty, &b, encode(b"b")),
+            Ok(&b"b"[..])
+        );
+        // Same target, but different bases at the same delta tick.
+        assert_eq!(
+            cache.get_or_insert_with(Tick(1), Tick(0), &a, &b, encode(b"ab")),
+            Ok(&b"ab"[..])
+        );
+        assert_eq!(
+            cache.get_or_insert_with(Tick(1), Tick(0), &b, &b, encode(b"bb")),
+            Ok(&b"bb"[..])
+        );
+        assert_eq!(cache.get(Tick(1), Tick(-1), &empty, &a), Some(&b"a"[..]));
+        assert_eq!(cache.get(Tick(1), Tick(-1), &empty, &b), Some(&b"b"[..]));
+        assert_eq!((cache.hits(), cache.misses()), (0, 4));
+    }
+
+    #[test]
+    fn failed_encode() {
+        let empty = Snap::empty();
+        let mut cache = Cache::new();
+        assert_eq!(
+            cache.get_or_insert_with(Tick(1), Tick(-1), &empty, &empty, |_| Err(())),
+            Err(())
+        );
+        assert_eq!(cache.get(Tick(1), Tick(-1), &empty, &empty), None);
+    }
+}
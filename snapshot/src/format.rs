@@ -3,6 +3,8 @@ use packer;
 use packer::Packer;
 use packer::Unpacker;
 use snap::Error;
+use std::convert::TryFrom;
+use std::fmt;
 use warn::wrap;
 use warn::Warn;
 
@@ -23,14 +25,82 @@ impl From<packer::Warning> for Warning {
     }
 }
 
+/// Maximum type ID of a snapshot item.
+///
+/// Matches `CSnapshot::MAX_TYPE` of the reference implementation. Since the
+/// type ID makes up the upper 16 bits of the key, this also means that item
+/// keys are never negative.
+pub const MAX_TYPE_ID: u16 = 0x7fff;
+
+/// Maximum ID of a snapshot item.
+pub const MAX_ID: u16 = 0xffff;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct InvalidKey;
+
+/// Key of a snapshot item, combining its type ID and its ID.
+///
+/// The type ID is stored in the upper 16 bits, the ID in the lower 16 bits.
+/// A `SnapKey` can only be constructed with a type ID of at most
+/// `MAX_TYPE_ID`.
+#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct SnapKey(pub(crate) i32);
+
+impl SnapKey {
+    pub fn new(type_id: u16, id: u16) -> Result<SnapKey, InvalidKey> {
+        if type_id > MAX_TYPE_ID {
+            return Err(InvalidKey);
+        }
+        Ok(SnapKey(key(type_id, id)))
+    }
+    pub fn type_id(self) -> u16 {
+        key_to_type_id(self.0)
+    }
+    pub fn id(self) -> u16 {
+        key_to_id(self.0)
+    }
+    pub fn to_i32(self) -> i32 {
+        self.0
+    }
+}
+
+impl TryFrom<i32> for SnapKey {
+    type Error = InvalidKey;
+    fn try_from(key: i32) -> Result<SnapKey, InvalidKey> {
+        SnapKey::new(key_to_type_id(key), key_to_id(key))
+    }
+}
+
+impl From<SnapKey> for i32 {
+    fn from(key: SnapKey) -> i32 {
+        key.to_i32()
+    }
+}
+
+impl fmt::Debug for SnapKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SnapKey")
+            .field("type_id", &self.type_id())
+            .field("id", &self.id())
+            .finish()
+    }
+}
+
+/// Extracts the type ID from a raw item key.
+///
+/// Prefer `SnapKey::type_id` which is only available for validated keys.
 pub fn key_to_type_id(key: i32) -> u16 {
     ((key as u32 >> 16) & 0xffff) as u16
 }
 
+/// Extracts the ID from a raw item key.
 pub fn key_to_id(key: i32) -> u16 {
     ((key as u32) & 0xffff) as u16
 }
 
+/// Builds a raw item key without any validation.
+///
+/// Prefer `SnapKey::new` which checks the type ID range.
 pub fn key(type_id: u16, id: u16) -> i32 {
     (((type_id as u32) << 16) | (id as u32)) as i32
 }
@@ -43,15 +113,19 @@ pub struct Item<'a> {
 }
 
 impl<'a> Item<'a> {
-    pub fn from_key(key: i32, data: &[i32]) -> Item {
+    pub fn from_key(key: SnapKey, data: &[i32]) -> Item {
         Item {
-            type_id: key_to_type_id(key),
-            id: key_to_id(key),
+            type_id: key.type_id(),
+            id: key.id(),
             data: data,
         }
     }
-    pub fn key(&self) -> i32 {
-        key(self.type_id, self.id)
+    /// Returns the key of the item.
+    ///
+    /// Fails if the type ID is larger than `MAX_TYPE_ID`, which can only
+    /// happen for manually constructed items.
+    pub fn key(&self) -> Result<SnapKey, InvalidKey> {
+        SnapKey::new(self.type_id, self.id)
     }
 }
 
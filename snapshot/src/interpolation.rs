@@ -0,0 +1,168 @@
+use common::num::CastFloat;
+use gamenet::snap_obj;
+use gamenet::snap_obj::TypeId;
+use snap::Builder;
+use snap::Snap;
+use std::f32::consts::PI;
+
+/// How a single integer field of a snapshot item is interpolated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Field {
+    /// Take the value from the newer snapshot.
+    Keep,
+    /// Linearly interpolate between the values, e.g. for positions.
+    Linear,
+    /// Interpolate an angle given in 1/256 radians along the shorter
+    /// direction.
+    Angle,
+}
+
+fn lerp(from: i32, to: i32, t: f32) -> i32 {
+    (from as f32 + (to as f32 - from as f32) * t).round_to_i32()
+}
+
+fn lerp_angle(from: i32, to: i32, t: f32) -> i32 {
+    let mut from = from as f32;
+    let to = to as f32;
+    // Same wrap-around correction as the reference client.
+    if to - from > 256.0 * PI {
+        from += 256.0 * 2.0 * PI;
+    } else if from - to > 256.0 * PI {
+        from -= 256.0 * 2.0 * PI;
+    }
+    (from + (to - from) * t).round_to_i32()
+}
+
+/// Interpolated fields of the Teeworlds 0.6 snapshot objects.
+///
+/// Mirrors what the reference client interpolates for rendering: character
+/// and hook positions, character angles and flag positions. The fields are
+/// looked up by name in the object metadata generated from the protocol spec.
+pub fn teeworlds_0_6(type_id: u16, index: usize) -> Field {
+    let meta = match snap_obj::obj_meta(TypeId::from(type_id)) {
+        Some(meta) => meta,
+        None => return Field::Keep,
+    };
+    let field = match meta.field_at(index) {
+        Some(field) => field,
+        None => return Field::Keep,
+    };
+    match (meta.name, field.name) {
+        ("character_core", "x")
+        | ("character_core", "y")
+        | ("character_core", "hook_x")
+        | ("character_core", "hook_y")
+        | ("character", "x")
+        | ("character", "y")
+        | ("character", "hook_x")
+        | ("character", "hook_y")
+        | ("flag", "x")
+        | ("flag", "y") => Field::Linear,
+        ("character_core", "angle") | ("character", "angle") => Field::Angle,
+        _ => Field::Keep,
+    }
+}
+
+/// Interpolates between two snapshots.
+///
+/// `t` is the position between `from` (`0.0`) and `to` (`1.0`). The result
+/// contains the items of `to`. Fields of items that are also present with the
+/// same size in `from` are interpolated as specified by `field`, which gets
+/// passed the type ID of the item and the index of the field, see
+/// `teeworlds_0_6` for an example.
+pub fn interpolate<F>(from: &Snap, to: &Snap, t: f32, field: F) -> Snap
+where
+    F: FnMut(u16, usize) -> Field,
+{
+    let mut field = field;
+    let mut builder = Builder::new();
+    let mut data = Vec::new();
+    for item in to.items() {
+        data.clear();
+        data.extend_from_slice(item.data);
+        if let Some(from_data) = from.item(item.type_id, item.id) {
+            if from_data.len() == data.len() {
+                for (i, (d, &f)) in data.iter_mut().zip(from_data).enumerate() {
+                    *d = match field(item.type_id, i) {
+                        Field::Keep => *d,
+                        Field::Linear => lerp(f, *d, t),
+                        Field::Angle => lerp_angle(f, *d, t),
+                    };
+                }
+            }
+        }
+        builder
+            .add_item(item.type_id, item.id, &data)
+            .expect("items of a valid snapshot");
+    }
+    builder.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use super::interpolate;
+    use super::teeworlds_0_6;
+    use super::Field;
+    use gamenet::snap_obj;
+    use snap::Builder;
+
+    #[test]
+    fn lerp_fields() {
+        let mut from = Builder::new();
+        from.add_item(snap_obj::FLAG, 0, &[0, 100, 1]).unwrap();
+        from.add_item(snap_obj::FLAG, 1, &[0, 0, 0]).unwrap();
+        let from = from.finish();
+        let mut to = Builder::new();
+        to.add_item(snap_obj::FLAG, 0, &[10, 200, 0]).unwrap();
+        to.add_item(snap_obj::FLAG, 2, &[5, 5, 0]).unwrap();
+        let to = to.finish();
+
+        let result = interpolate(&from, &to, 0.5, teeworlds_0_6);
+        assert_eq!(result.item(snap_obj::FLAG, 0), Some(&[5, 150, 0][..]));
+        assert_eq!(result.item(snap_obj::FLAG, 1), None);
+        assert_eq!(result.item(snap_obj::FLAG, 2), Some(&[5, 5, 0][..]));
+    }
+
+    #[test]
+    fn teeworlds_0_6_fields() {
+        use super::Field::*;
+
+        let fields: Vec<_> = (0..15)
+            .map(|i| teeworlds_0_6(snap_obj::CHARACTER_CORE, i))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                Keep, Linear, Linear, Keep, Keep, Angle, Keep, Keep, Keep, Keep, Keep, Linear,
+                Linear, Keep, Keep,
+            ]
+        );
+        assert_eq!(teeworlds_0_6(snap_obj::CHARACTER, 12), Linear);
+        assert_eq!(teeworlds_0_6(snap_obj::CHARACTER, 21), Keep);
+        assert_eq!(teeworlds_0_6(snap_obj::CHARACTER, 22), Keep);
+        assert_eq!(teeworlds_0_6(snap_obj::FLAG, 2), Keep);
+        assert_eq!(teeworlds_0_6(0x7fff, 0), Keep);
+    }
+
+    #[test]
+    fn lerp_angle() {
+        let mut from = Builder::new();
+        from.add_item(1, 0, &[-800, 0]).unwrap();
+        let from = from.finish();
+        let mut to = Builder::new();
+        to.add_item(1, 0, &[800, 800]).unwrap();
+        let to = to.finish();
+
+        let result = interpolate(&from, &to, 0.5, |_, i| {
+            if i == 0 {
+                Field::Angle
+            } else {
+                Field::Linear
+            }
+        });
+        // -800 and 800 are close to each other when wrapping around at π.
+        let angle = result.item(1, 0).unwrap()[0];
+        assert!(angle < -790 || angle > 790, "{}", angle);
+        assert_eq!(result.item(1, 0).unwrap()[1], 400);
+    }
+}
@@ -2,20 +2,27 @@ extern crate buffer;
 extern crate common;
 extern crate gamenet_teeworlds_0_6 as gamenet;
 extern crate packer;
+#[cfg(feature = "rayon")]
+extern crate rayon;
 extern crate vec_map;
 extern crate warn;
 
+pub mod cache;
 pub mod format;
+pub mod interpolation;
 pub mod manager;
 pub mod receiver;
 pub mod snap;
 pub mod storage;
 
+pub use format::SnapKey;
+pub use interpolation::interpolate;
 pub use manager::Manager;
 pub use receiver::DeltaReceiver;
 pub use receiver::ReceivedDelta;
 pub use snap::Delta;
 pub use snap::Snap;
+pub use snap::SnapIndex;
 pub use snap::SnapReader;
 pub use storage::Storage;
 
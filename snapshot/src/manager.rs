@@ -1,5 +1,7 @@
+use common::time::Tick;
 use format;
 use gamenet::msg::system;
+use packer::DecodePolicy;
 use packer::Unpacker;
 use receiver;
 use snap;
@@ -66,6 +68,7 @@ impl From<storage::Warning> for Warning {
 struct ManagerInner {
     temp_delta: Delta,
     storage: Storage,
+    policy: DecodePolicy,
 }
 
 #[derive(Clone, Default)]
@@ -78,11 +81,16 @@ impl Manager {
     pub fn new() -> Manager {
         Default::default()
     }
+    /// Sets the policy for decoding received deltas, the default is
+    /// `DecodePolicy::Lenient`.
+    pub fn set_decode_policy(&mut self, policy: DecodePolicy) {
+        self.inner.policy = policy;
+    }
     pub fn reset(&mut self) {
         self.inner.storage.reset();
         self.receiver.reset();
     }
-    pub fn ack_tick(&self) -> Option<i32> {
+    pub fn ack_tick(&self) -> Option<Tick> {
         self.inner.storage.ack_tick()
     }
     pub fn snap_empty<W, O>(
@@ -154,8 +162,11 @@ impl ManagerInner {
     {
         let crc = delta.data_and_crc.map(|d| d.1);
         if let Some((data, _)) = delta.data_and_crc {
-            self.temp_delta
-                .read(wrap(warn), object_size, &mut Unpacker::new(data))?;
+            self.temp_delta.read(
+                wrap(warn),
+                object_size,
+                &mut Unpacker::new(data).with_policy(self.policy),
+            )?;
         } else {
             self.temp_delta.clear();
         }
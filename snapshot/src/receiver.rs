@@ -1,4 +1,6 @@
 use common::num::Cast;
+use common::num::CheckedCast;
+use common::time::Tick;
 use gamenet::msg::system;
 use std::ops;
 use to_usize;
@@ -19,25 +21,24 @@ pub enum Error {
     DuplicatePart,
 }
 
-// TODO: How to handle `tick` overflowing?
 #[derive(Clone, Debug)]
 struct CurrentDelta {
-    tick: i32,
-    delta_tick: i32,
+    tick: Tick,
+    delta_tick: Tick,
     num_parts: i32,
     crc: i32,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub struct ReceivedDelta<'a> {
-    pub delta_tick: i32,
-    pub tick: i32,
+    pub delta_tick: Tick,
+    pub tick: Tick,
     pub data_and_crc: Option<(&'a [u8], i32)>,
 }
 
 #[derive(Clone, Default)]
 pub struct DeltaReceiver {
-    previous_tick: Option<i32>,
+    previous_tick: Option<Tick>,
     current: Option<CurrentDelta>,
     // `parts` points into `receive_buf`.
     parts: VecMap<ops::Range<u32>>,
@@ -53,11 +54,11 @@ impl DeltaReceiver {
         self.previous_tick = None;
         self.current = None;
     }
-    fn can_receive(&self, tick: i32) -> bool {
+    fn can_receive(&self, tick: Tick) -> bool {
         self.current
             .as_ref()
-            .map(|c| c.tick <= tick)
-            .or(self.previous_tick.map(|t| t < tick))
+            .map(|c| !c.tick.is_after(tick))
+            .or(self.previous_tick.map(|t| tick.is_after(t)))
             .unwrap_or(true)
     }
     fn init_delta(&mut self) {
@@ -65,7 +66,7 @@ impl DeltaReceiver {
         self.receive_buf.clear();
         self.result.clear();
     }
-    fn finish_delta(&mut self, tick: i32) {
+    fn finish_delta(&mut self, tick: Tick) {
         self.current = None;
         self.previous_tick = Some(tick);
     }
@@ -77,22 +78,23 @@ impl DeltaReceiver {
     where
         W: Warn<Warning>,
     {
-        if !self.can_receive(snap.tick) {
+        let snap_tick = Tick(snap.tick);
+        if !self.can_receive(snap_tick) {
             return Err(Error::OldDelta);
         }
         if self
             .current
             .as_ref()
-            .map(|c| c.tick == snap.tick)
+            .map(|c| c.tick == snap_tick)
             .unwrap_or(false)
         {
             warn.warn(Warning::DuplicateSnap);
         }
         self.init_delta();
-        self.finish_delta(snap.tick);
+        self.finish_delta(snap_tick);
         Ok(Some(ReceivedDelta {
-            delta_tick: snap.tick.wrapping_sub(snap.delta_tick),
-            tick: snap.tick,
+            delta_tick: snap_tick.wrapping_sub(snap.delta_tick),
+            tick: snap_tick,
             data_and_crc: None,
         }))
     }
@@ -104,23 +106,24 @@ impl DeltaReceiver {
     where
         W: Warn<Warning>,
     {
-        if !self.can_receive(snap.tick) {
+        let snap_tick = Tick(snap.tick);
+        if !self.can_receive(snap_tick) {
             return Err(Error::OldDelta);
         }
         if self
             .current
             .as_ref()
-            .map(|c| c.tick == snap.tick)
+            .map(|c| c.tick == snap_tick)
             .unwrap_or(false)
         {
             warn.warn(Warning::DuplicateSnap);
         }
         self.init_delta();
-        self.finish_delta(snap.tick);
+        self.finish_delta(snap_tick);
         self.result.extend(snap.data);
         Ok(Some(ReceivedDelta {
-            delta_tick: snap.tick.wrapping_sub(snap.delta_tick),
-            tick: snap.tick,
+            delta_tick: snap_tick.wrapping_sub(snap.delta_tick),
+            tick: snap_tick,
             data_and_crc: Some((&self.result, snap.crc)),
         }))
     }
@@ -132,7 +135,8 @@ impl DeltaReceiver {
     where
         W: Warn<Warning>,
     {
-        if !self.can_receive(snap.tick) {
+        let snap_tick = Tick(snap.tick);
+        if !self.can_receive(snap_tick) {
             return Err(Error::OldDelta);
         }
         if !(0 <= snap.num_parts && snap.num_parts <= 32) {
@@ -144,7 +148,7 @@ impl DeltaReceiver {
         if self
             .current
             .as_ref()
-            .map(|c| c.tick != snap.tick)
+            .map(|c| c.tick != snap_tick)
             .unwrap_or(false)
         {
             self.current = None;
@@ -152,14 +156,16 @@ impl DeltaReceiver {
         if let None = self.current {
             self.init_delta();
             self.current = Some(CurrentDelta {
-                tick: snap.tick,
-                delta_tick: snap.tick.wrapping_sub(snap.delta_tick),
+                tick: snap_tick,
+                delta_tick: snap_tick.wrapping_sub(snap.delta_tick),
                 num_parts: snap.num_parts,
                 crc: snap.crc,
             });
 
-            // Checked above.
-            let num_parts = snap.num_parts.assert_usize();
+            let num_parts = snap
+                .num_parts
+                .checked_usize()
+                .map_err(|_| Error::InvalidNumParts)?;
             self.parts.reserve_len(num_parts);
         }
         let delta_tick;
@@ -168,7 +174,7 @@ impl DeltaReceiver {
         let num_parts;
         {
             let current: &mut CurrentDelta = self.current.as_mut().unwrap();
-            if snap.delta_tick != current.delta_tick
+            if snap_tick.wrapping_sub(snap.delta_tick) != current.delta_tick
                 || snap.num_parts != current.num_parts
                 || snap.crc != current.crc
             {
@@ -179,7 +185,7 @@ impl DeltaReceiver {
             crc = current.crc;
             num_parts = current.num_parts;
         }
-        let part = snap.part.assert_usize();
+        let part = snap.part.checked_usize().map_err(|_| Error::InvalidPart)?;
         if self.parts.contains_key(part) {
             return Err(Error::DuplicatePart);
         }
@@ -213,6 +219,7 @@ mod test {
     use super::Error;
     use super::ReceivedDelta;
     use common::num::Cast;
+    use common::time::Tick;
     use gamenet::msg::system::Snap;
     use gamenet::msg::system::SnapEmpty;
     use gamenet::msg::system::SnapSingle;
@@ -235,8 +242,8 @@ mod test {
             assert_eq!(
                 result,
                 Some(ReceivedDelta {
-                    delta_tick: -1,
-                    tick: 1,
+                    delta_tick: Tick(-1),
+                    tick: Tick(1),
                     data_and_crc: None,
                 })
             );
@@ -258,6 +265,28 @@ mod test {
         );
     }
 
+    #[test]
+    fn wrapping_tick() {
+        let mut receiver = DeltaReceiver::new();
+        let snap = |tick| SnapEmpty {
+            tick,
+            delta_tick: 1,
+        };
+        receiver.snap_empty(&mut Panic, snap(i32::MAX)).unwrap();
+        assert_eq!(
+            receiver.snap_empty(&mut Panic, snap(i32::MIN)).unwrap(),
+            Some(ReceivedDelta {
+                delta_tick: Tick(i32::MAX),
+                tick: Tick(i32::MIN),
+                data_and_crc: None,
+            })
+        );
+        assert_eq!(
+            receiver.snap_empty(&mut Panic, snap(i32::MAX)).unwrap_err(),
+            Error::OldDelta
+        );
+    }
+
     #[test]
     fn reorder() {
         let mut receiver = DeltaReceiver::new();
@@ -282,8 +311,8 @@ mod test {
                 assert_eq!(
                     result,
                     Some(ReceivedDelta {
-                        delta_tick: 1,
-                        tick: 2,
+                        delta_tick: Tick(1),
+                        tick: Tick(2),
                         data_and_crc: Some((b"01__234_", 3)),
                     })
                 );
@@ -1,11 +1,16 @@
 use buffer::CapacityError;
 use common::num::Cast;
-use format::key;
-use format::key_to_id;
-use format::key_to_type_id;
+use common::num::CastError;
+use common::num::CheckedCast;
+use common::time::Tick;
+use common::warn::locate;
+use common::warn::strip_location;
+use common::warn::Located;
+use common::TickArena;
 use format::DeltaHeader;
 use format::Item;
 use format::SnapHeader;
+use format::SnapKey;
 use format::Warning;
 use gamenet::enums::MAX_SNAPSHOT_PACKSIZE;
 use gamenet::msg::system;
@@ -13,14 +18,19 @@ use packer;
 use packer::with_packer;
 use packer::Packer;
 use packer::Unpacker;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 use std::cmp;
 use std::collections::hash_map;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::convert::TryFrom;
 use std::fmt;
 use std::iter;
 use std::mem;
 use std::ops;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use to_usize;
 use warn::wrap;
 use warn::Warn;
@@ -31,6 +41,8 @@ pub const MAX_SNAPSHOT_SIZE: usize = 64 * 1024; // 64 KB
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Error {
     UnexpectedEnd,
+    /// The data violates the `DecodePolicy::Strict` of the unpacker.
+    PolicyViolation,
     IntOutOfRange,
     DeletedItemsUnpacking,
     ItemDiffsUnpacking,
@@ -50,6 +62,7 @@ pub enum Error {
 pub enum BuilderError {
     DuplicateKey,
     TooLongSnap,
+    TypeIdRange,
 }
 
 impl From<BuilderError> for Error {
@@ -57,6 +70,7 @@ impl From<BuilderError> for Error {
         match err {
             BuilderError::DuplicateKey => Error::DuplicateKey,
             BuilderError::TooLongSnap => Error::TooLongSnap,
+            BuilderError::TypeIdRange => Error::TypeIdRange,
         }
     }
 }
@@ -82,6 +96,12 @@ impl From<packer::IntOutOfRange> for Error {
     }
 }
 
+impl From<CastError> for Error {
+    fn from(_: CastError) -> Error {
+        Error::IntOutOfRange
+    }
+}
+
 impl From<packer::UnexpectedEnd> for Error {
     fn from(_: packer::UnexpectedEnd) -> Error {
         Error::UnexpectedEnd
@@ -89,7 +109,9 @@ impl From<packer::UnexpectedEnd> for Error {
 }
 
 fn apply_delta(in_: Option<&[i32]>, delta: &[i32], out: &mut [i32]) -> Result<(), Error> {
-    assert!(delta.len() == out.len());
+    if delta.len() != out.len() {
+        return Err(Error::DeltaDifferingSizes);
+    }
     match in_ {
         Some(in_) => {
             if in_.len() != out.len() {
@@ -117,13 +139,37 @@ fn create_delta(from: Option<&[i32]>, to: &[i32], out: &mut [i32]) {
     }
 }
 
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+/// Returns a number that hasn't been returned before, to tell apart the
+/// contents of snapshots over time.
+fn next_generation() -> u64 {
+    NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
 // TODO: Select a faster hasher?
 #[derive(Clone, Default)]
 pub struct Snap {
-    offsets: HashMap<i32, ops::Range<u32>>,
+    offsets: HashMap<SnapKey, ops::Range<u32>>,
     buf: Vec<i32>,
+    // Changes whenever the contents change, used to check that a `SnapIndex`
+    // belongs to this snapshot. Only `0` for empty snapshots.
+    generation: u64,
+}
+
+/// Snapshots are equal if they contain the same items.
+impl PartialEq for Snap {
+    fn eq(&self, other: &Snap) -> bool {
+        self.offsets.len() == other.offsets.len()
+            && self
+                .offsets
+                .iter()
+                .all(|(&k, o)| other.item_by_key(k) == Some(self.item_from_offset(o.clone())))
+    }
 }
 
+impl Eq for Snap {}
+
 impl Snap {
     pub fn empty() -> Snap {
         Default::default()
@@ -131,13 +177,17 @@ impl Snap {
     fn clear(&mut self) {
         self.offsets.clear();
         self.buf.clear();
+        self.generation = next_generation();
     }
     fn item_from_offset(&self, offset: ops::Range<u32>) -> &[i32] {
         &self.buf[to_usize(offset)]
     }
     pub fn item(&self, type_id: u16, id: u16) -> Option<&[i32]> {
+        self.item_by_key(SnapKey::new(type_id, id).ok()?)
+    }
+    pub fn item_by_key(&self, key: SnapKey) -> Option<&[i32]> {
         self.offsets
-            .get(&key(type_id, id))
+            .get(&key)
             .map(|o| &self.buf[to_usize(o.clone())])
     }
     pub fn items(&self) -> Items {
@@ -147,7 +197,7 @@ impl Snap {
         }
     }
     fn prepare_item_vacant<'a>(
-        entry: hash_map::VacantEntry<'a, i32, ops::Range<u32>>,
+        entry: hash_map::VacantEntry<'a, SnapKey, ops::Range<u32>>,
         buf: &mut Vec<i32>,
         size: usize,
     ) -> Result<&'a mut ops::Range<u32>, TooLongSnap> {
@@ -160,8 +210,8 @@ impl Snap {
         buf.extend(iter::repeat(0).take(size));
         Ok(entry.insert(start..end))
     }
-    fn prepare_item(&mut self, type_id: u16, id: u16, size: usize) -> Result<&mut [i32], Error> {
-        let offset = match self.offsets.entry(key(type_id, id)) {
+    fn prepare_item(&mut self, key: SnapKey, size: usize) -> Result<&mut [i32], Error> {
+        let offset = match self.offsets.entry(key) {
             hash_map::Entry::Occupied(o) => o.into_mut(),
             hash_map::Entry::Vacant(v) => Snap::prepare_item_vacant(v, &mut self.buf, size)?,
         }
@@ -180,10 +230,11 @@ impl Snap {
         self.clear();
 
         let mut num_deletions = 0;
-        for item in from.items() {
-            if !delta.deleted_items.contains(&item.key()) {
-                let out = self.prepare_item(item.type_id, item.id, item.data.len())?;
-                out.copy_from_slice(item.data);
+        for (&key, offset) in &from.offsets {
+            if !delta.deleted_items.contains(&key) {
+                let data = from.item_from_offset(offset.clone());
+                let out = self.prepare_item(key, data.len())?;
+                out.copy_from_slice(data);
             } else {
                 num_deletions += 1;
             }
@@ -193,11 +244,9 @@ impl Snap {
         }
 
         for (&key, offset) in &delta.updated_items {
-            let type_id = key_to_type_id(key);
-            let id = key_to_id(key);
             let diff = &delta.buf[to_usize(offset.clone())];
-            let out = self.prepare_item(type_id, id, diff.len())?;
-            let in_ = from.item(type_id, id);
+            let out = self.prepare_item(key, diff.len())?;
+            let in_ = from.item_by_key(key);
 
             apply_delta(in_, diff, out)?;
         }
@@ -206,12 +255,33 @@ impl Snap {
     pub fn write<'d, 's>(
         &self,
         buf: &mut Vec<i32>,
+        p: Packer<'d, 's>,
+    ) -> Result<&'d [u8], CapacityError> {
+        buf.clear();
+        buf.extend(self.offsets.keys().map(|k| k.to_i32()));
+        self.write_keys(buf, p)
+    }
+    /// Like `write`, but takes the scratch buffer from `arena` instead of the
+    /// heap.
+    pub fn write_with_arena<'d, 's>(
+        &self,
+        arena: &TickArena,
+        p: Packer<'d, 's>,
+    ) -> Result<&'d [u8], CapacityError> {
+        let keys = arena.alloc_i32(self.offsets.len());
+        for (k, key) in keys.iter_mut().zip(self.offsets.keys()) {
+            *k = key.to_i32();
+        }
+        self.write_keys(keys, p)
+    }
+    fn write_keys<'d, 's>(
+        &self,
+        keys: &mut [i32],
         mut p: Packer<'d, 's>,
     ) -> Result<&'d [u8], CapacityError> {
-        let keys = buf;
-        keys.clear();
-        keys.extend(self.offsets.keys().cloned());
-        keys.sort_unstable_by_key(|&k| k as u32);
+        // Keys are never negative, so this is the same as sorting them as
+        // unsigned integers.
+        keys.sort_unstable();
         let data_size = self
             .buf
             .len()
@@ -227,7 +297,7 @@ impl Snap {
         let mut offset = 0;
         for &key in &*keys {
             p.write_int(offset)?;
-            let key_offset = self.offsets[&key].clone();
+            let key_offset = self.offsets[&SnapKey(key)].clone();
             offset = offset
                 .checked_add(
                     (key_offset.end - key_offset.start + 1)
@@ -240,9 +310,7 @@ impl Snap {
         }
         for &key in &*keys {
             p.write_int(key)?;
-            for &i in &self.buf[to_usize(self.offsets[&key].clone())] {
-                p.write_int(i)?;
-            }
+            p.write_ints(&self.buf[to_usize(self.offsets[&SnapKey(key)].clone())])?;
         }
         Ok(p.written())
     }
@@ -259,8 +327,22 @@ pub struct SnapReader {
     sizes: Vec<i32>,
 }
 
-fn read_int_err<W: Warn<Warning>>(p: &mut Unpacker, w: &mut W, e: Error) -> Result<i32, Error> {
-    p.read_int(wrap(w)).map_err(|_| e)
+fn read_int_err<W: Warn<Located<Warning>>>(
+    p: &mut Unpacker,
+    w: &mut W,
+    structure: &'static str,
+    e: Error,
+) -> Result<i32, Error> {
+    p.read_int(wrap(&mut p.locate(w, structure))).map_err(|_| e)
+}
+
+/// Reports reads that failed because of `DecodePolicy::Strict` as
+/// `Error::PolicyViolation`.
+fn check_policy<T>(p: &Unpacker, result: Result<T, Error>) -> Result<T, Error> {
+    match p.policy_violation() {
+        Some(_) => Err(Error::PolicyViolation),
+        None => result,
+    }
 }
 
 impl SnapReader {
@@ -270,12 +352,32 @@ impl SnapReader {
     pub fn read<W>(&mut self, warn: &mut W, snap: Snap, p: &mut Unpacker) -> Result<Snap, Error>
     where
         W: Warn<Warning>,
+    {
+        self.read_located(&mut strip_location(warn), snap, p)
+    }
+    /// Like `read`, but attaches the offset and the part of the snapshot
+    /// that was being read to each warning.
+    pub fn read_located<W>(
+        &mut self,
+        warn: &mut W,
+        snap: Snap,
+        p: &mut Unpacker,
+    ) -> Result<Snap, Error>
+    where
+        W: Warn<Located<Warning>>,
+    {
+        let result = self.read_impl(warn, snap, p);
+        check_policy(p, result)
+    }
+    fn read_impl<W>(&mut self, warn: &mut W, snap: Snap, p: &mut Unpacker) -> Result<Snap, Error>
+    where
+        W: Warn<Located<Warning>>,
     {
         self.sizes.clear();
-        let header = SnapHeader::decode(warn, p)?;
+        let header = SnapHeader::decode(&mut p.locate(warn, "SnapHeader"), p)?;
         let mut prev_offset = None;
         for _ in 0..header.num_items {
-            let offset = p.read_int(wrap(warn)).unwrap();
+            let offset = read_int_err(p, warn, "item offset", Error::OffsetsUnpacking)?;
             if let Some(prev) = prev_offset {
                 if prev > offset {
                     return Err(Error::InvalidOffset);
@@ -303,19 +405,25 @@ impl SnapReader {
                 return Err(Error::InvalidOffset);
             }
             let size = size / 4;
-            let key = read_int_err(p, warn, Error::ItemsUnpacking)?;
-            let type_id = key_to_type_id(key);
-            let id = key_to_id(key);
-            builder.add_packed(warn, type_id, id, size.assert_usize() - 1, p)?;
+            let key = read_int_err(p, warn, "item key", Error::ItemsUnpacking)?;
+            let key = SnapKey::try_from(key).map_err(|_| Error::TypeIdRange)?;
+            let size = size.checked_usize()? - 1;
+            builder.add_packed(
+                &mut p.locate(warn, "item data"),
+                key.type_id(),
+                key.id(),
+                size,
+                p,
+            )?;
         }
-        assert!(p.is_empty());
+        p.finish(wrap(&mut p.locate(warn, "Snap")));
         Ok(builder.finish())
     }
 }
 
 pub struct Items<'a> {
     snap: &'a Snap,
-    iter: hash_map::Iter<'a, i32, ops::Range<u32>>,
+    iter: hash_map::Iter<'a, SnapKey, ops::Range<u32>>,
 }
 
 impl<'a> Iterator for Items<'a> {
@@ -349,8 +457,8 @@ impl fmt::Debug for Snap {
 
 #[derive(Clone, Default)]
 pub struct Delta {
-    deleted_items: HashSet<i32>,
-    updated_items: HashMap<i32, ops::Range<u32>>,
+    deleted_items: HashSet<SnapKey>,
+    updated_items: HashMap<SnapKey, ops::Range<u32>>,
     buf: Vec<i32>,
 }
 
@@ -363,9 +471,7 @@ impl Delta {
         self.updated_items.clear();
         self.buf.clear();
     }
-    fn prepare_update_item(&mut self, type_id: u16, id: u16, size: usize) -> &mut [i32] {
-        let key = key(type_id, id);
-
+    fn prepare_update_item(&mut self, key: SnapKey, size: usize) -> &mut [i32] {
         let offset = self.buf.len();
         let start = offset.assert_u32();
         let end = (offset + size).assert_u32();
@@ -373,19 +479,76 @@ impl Delta {
         assert!(self.updated_items.insert(key, start..end).is_none());
         &mut self.buf[to_usize(start..end)]
     }
+    /// Creates the delta from the `from` snapshot to the `to` snapshot.
     pub fn create(&mut self, from: &Snap, to: &Snap) {
         self.clear();
-        for Item { type_id, id, .. } in from.items() {
-            if to.item(type_id, id).is_none() {
-                assert!(self.deleted_items.insert(key(type_id, id)));
+        for &key in from.offsets.keys() {
+            if to.item_by_key(key).is_none() {
+                assert!(self.deleted_items.insert(key));
             }
         }
-        for Item { type_id, id, data } in to.items() {
-            let from_data = from.item(type_id, id);
-            let out_delta = self.prepare_update_item(type_id, id, data.len());
+        for (&key, offset) in &to.offsets {
+            let from_data = from.item_by_key(key);
+            let data = to.item_from_offset(offset.clone());
+            let out_delta = self.prepare_update_item(key, data.len());
             create_delta(from_data, data, out_delta);
         }
     }
+    /// Creates the delta from the `from` snapshot to the `to` snapshot, like
+    /// `create`, using a precomputed index of `to`.
+    ///
+    /// The layout of the delta only depends on `to`, so it's copied from the
+    /// index instead of being built item by item.
+    ///
+    /// Panics if `to_index` wasn't created from the current contents of `to`.
+    pub fn create_with_index(&mut self, from: &Snap, to: &Snap, to_index: &SnapIndex) {
+        assert!(
+            to_index.generation == Some(to.generation),
+            "index doesn't belong to snapshot"
+        );
+        self.clear();
+        for &key in from.offsets.keys() {
+            if to.item_by_key(key).is_none() {
+                assert!(self.deleted_items.insert(key));
+            }
+        }
+        self.updated_items.clone_from(&to_index.updated_items);
+        self.buf.resize(to_index.size, 0);
+        for &(key, ref to_offset, ref delta_offset) in &to_index.items {
+            let from_data = from.item_by_key(key);
+            let data = to.item_from_offset(to_offset.clone());
+            let out_delta = &mut self.buf[to_usize(delta_offset.clone())];
+            create_delta(from_data, data, out_delta);
+        }
+    }
+    /// Creates one delta for each of the `froms` snapshots to the same `to`
+    /// snapshot, storing them in `deltas`.
+    ///
+    /// This is what a server does each tick, once per client. The index of
+    /// `to` is only computed once. With the `rayon` feature enabled, the
+    /// deltas are created in parallel.
+    ///
+    /// Panics if `deltas` and `froms` have different lengths.
+    pub fn create_many(deltas: &mut [Delta], froms: &[&Snap], to: &Snap) {
+        assert!(
+            deltas.len() == froms.len(),
+            "deltas and froms must have the same length"
+        );
+        let to_index = SnapIndex::new(to);
+        #[cfg(feature = "rayon")]
+        {
+            deltas
+                .par_iter_mut()
+                .zip(froms.par_iter())
+                .for_each(|(delta, from)| delta.create_with_index(from, to, &to_index));
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            for (delta, from) in deltas.iter_mut().zip(froms) {
+                delta.create_with_index(from, to, &to_index);
+            }
+        }
+    }
     pub fn write<'d, 's, O>(
         &self,
         object_size: O,
@@ -403,21 +566,18 @@ impl Delta {
             .encode(p)
         })?;
         for &key in &self.deleted_items {
-            p.write_int(key)?;
+            p.write_int(key.to_i32())?;
         }
         for (&key, range) in &self.updated_items {
             let data = &self.buf[to_usize(range.clone())];
-            let type_id = key_to_type_id(key);
-            let id = key_to_id(key);
+            let type_id = key.type_id();
             p.write_int(type_id.i32())?;
-            p.write_int(id.i32())?;
+            p.write_int(key.id().i32())?;
             match object_size(type_id) {
                 Some(size) => assert!(size.usize() == data.len()),
                 None => p.write_int(data.len().assert_i32())?,
             }
-            for &d in data {
-                p.write_int(d)?;
-            }
+            p.write_ints(data)?;
         }
         Ok(p.written())
     }
@@ -431,67 +591,142 @@ impl Delta {
     where
         W: Warn<Warning>,
         O: FnMut(u16) -> Option<u32>,
+    {
+        self.read_located(&mut strip_location(warn), object_size, p)
+    }
+    /// Like `read`, but attaches the offset and the part of the delta that
+    /// was being read to each warning.
+    pub fn read_located<W, O>(
+        &mut self,
+        warn: &mut W,
+        object_size: O,
+        p: &mut Unpacker,
+    ) -> Result<(), Error>
+    where
+        W: Warn<Located<Warning>>,
+        O: FnMut(u16) -> Option<u32>,
+    {
+        let result = self.read_impl(warn, object_size, p);
+        check_policy(p, result)
+    }
+    fn read_impl<W, O>(
+        &mut self,
+        warn: &mut W,
+        object_size: O,
+        p: &mut Unpacker,
+    ) -> Result<(), Error>
+    where
+        W: Warn<Located<Warning>>,
+        O: FnMut(u16) -> Option<u32>,
     {
         self.clear();
 
         let mut object_size = object_size;
 
-        let header = DeltaHeader::decode(warn, p)?;
+        let header_offset = p.num_bytes_read();
+        let header = DeltaHeader::decode(&mut p.locate(warn, "DeltaHeader"), p)?;
 
+        let deleted_items_offset = p.num_bytes_read();
         for _ in 0..header.num_deleted_items {
-            self.deleted_items
-                .insert(read_int_err(p, warn, Error::DeletedItemsUnpacking)?);
+            let key = read_int_err(p, warn, "deleted item", Error::DeletedItemsUnpacking)?;
+            let key = SnapKey::try_from(key).map_err(|_| Error::TypeIdRange)?;
+            self.deleted_items.insert(key);
         }
-        if header.num_deleted_items.assert_usize() != self.deleted_items.len() {
-            warn.warn(Warning::DuplicateDelete);
+        if header.num_deleted_items.checked_usize()? != self.deleted_items.len() {
+            locate(warn, deleted_items_offset, "deleted items").warn(Warning::DuplicateDelete);
         }
 
         let mut num_updates = 0;
 
         while !p.is_empty() {
-            let type_id = read_int_err(p, warn, Error::ItemDiffsUnpacking)?;
-            let id = read_int_err(p, warn, Error::ItemDiffsUnpacking)?;
+            let item_offset = p.num_bytes_read();
+            let type_id = read_int_err(p, warn, "item type ID", Error::ItemDiffsUnpacking)?;
+            let id = read_int_err(p, warn, "item ID", Error::ItemDiffsUnpacking)?;
 
             let type_id = type_id.try_u16().ok_or(Error::TypeIdRange)?;
             let id = id.try_u16().ok_or(Error::IdRange)?;
+            let key = SnapKey::new(type_id, id).map_err(|_| Error::TypeIdRange)?;
 
             let size = match object_size(type_id) {
                 Some(s) => s,
                 None => {
-                    let s = read_int_err(p, warn, Error::ItemDiffsUnpacking)?;
+                    let s = read_int_err(p, warn, "item size", Error::ItemDiffsUnpacking)?;
                     s.try_u32().ok_or(Error::NegativeSize)?
                 }
             };
             let start = self.buf.len().try_u32().ok_or(Error::TooLongDiff)?;
             let end = start.checked_add(size).ok_or(Error::TooLongDiff)?;
             for _ in 0..size {
-                self.buf
-                    .push(read_int_err(p, warn, Error::ItemDiffsUnpacking)?);
+                self.buf.push(read_int_err(
+                    p,
+                    warn,
+                    "item data",
+                    Error::ItemDiffsUnpacking,
+                )?);
             }
 
             // In case of conflict, take later update (as the original code does).
-            if self
-                .updated_items
-                .insert(key(type_id, id), start..end)
-                .is_some()
-            {
-                warn.warn(Warning::DuplicateUpdate);
+            if self.updated_items.insert(key, start..end).is_some() {
+                locate(warn, item_offset, "item diff").warn(Warning::DuplicateUpdate);
             }
 
-            if self.deleted_items.contains(&key(type_id, id)) {
-                warn.warn(Warning::DeleteUpdate);
+            if self.deleted_items.contains(&key) {
+                locate(warn, item_offset, "item diff").warn(Warning::DeleteUpdate);
             }
             num_updates += 1;
         }
 
         if num_updates != header.num_updated_items {
-            warn.warn(Warning::NumUpdatedItems);
+            locate(warn, header_offset, "DeltaHeader").warn(Warning::NumUpdatedItems);
         }
 
         Ok(())
     }
 }
 
+/// Precomputed layout of the deltas to a snapshot.
+///
+/// Used to create many deltas to the same snapshot, see
+/// `Delta::create_many`.
+#[derive(Clone, Debug, Default)]
+pub struct SnapIndex {
+    /// Generation of the snapshot the index was created from.
+    generation: Option<u64>,
+    /// Key, offset in the snapshot and offset in the delta of each item.
+    items: Vec<(SnapKey, ops::Range<u32>, ops::Range<u32>)>,
+    updated_items: HashMap<SnapKey, ops::Range<u32>>,
+    size: usize,
+}
+
+impl SnapIndex {
+    pub fn new(snap: &Snap) -> SnapIndex {
+        let mut result = SnapIndex::default();
+        result.update(snap);
+        result
+    }
+    /// Recomputes the index for another snapshot, reusing the allocation.
+    pub fn update(&mut self, snap: &Snap) {
+        self.generation = Some(snap.generation);
+        self.items.clear();
+        self.updated_items.clear();
+        let mut size = 0;
+        for (&key, offset) in &snap.offsets {
+            let start = size.assert_u32();
+            size += (offset.end - offset.start).usize();
+            let delta_offset = start..size.assert_u32();
+            self.items.push((key, offset.clone(), delta_offset.clone()));
+            self.updated_items.insert(key, delta_offset);
+        }
+        self.size = size;
+    }
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
 #[derive(Default)]
 pub struct Builder {
     snap: Snap,
@@ -507,7 +742,8 @@ impl Builder {
         id: u16,
         size: usize,
     ) -> Result<&mut [i32], BuilderError> {
-        let offset = match self.snap.offsets.entry(key(type_id, id)) {
+        let key = SnapKey::new(type_id, id).map_err(|_| BuilderError::TypeIdRange)?;
+        let offset = match self.snap.offsets.entry(key) {
             hash_map::Entry::Occupied(..) => return Err(BuilderError::DuplicateKey),
             hash_map::Entry::Vacant(v) => Snap::prepare_item_vacant(v, &mut self.snap.buf, size)?,
         }
@@ -530,20 +766,20 @@ impl Builder {
     where
         W: Warn<Warning>,
     {
-        for x in self.add_item_raw(type_id, id, size)? {
-            *x = read_int_err(p, w, Error::ItemsUnpacking)?;
-        }
-        Ok(())
+        let data = self.add_item_raw(type_id, id, size)?;
+        p.read_ints(wrap(w), data)
+            .map_err(|_| Error::ItemsUnpacking)
     }
-    pub fn finish(self) -> Snap {
+    pub fn finish(mut self) -> Snap {
+        self.snap.generation = next_generation();
         self.snap
     }
 }
 
-pub fn delta_chunks(tick: i32, delta_tick: i32, data: &[u8], crc: i32) -> DeltaChunks {
+pub fn delta_chunks(tick: Tick, delta_tick: Tick, data: &[u8], crc: i32) -> DeltaChunks<'_> {
     DeltaChunks {
-        tick: tick,
-        delta_tick: tick - delta_tick,
+        tick: tick.to_i32(),
+        delta_tick: tick.wrapping_diff(delta_tick),
         crc: crc,
         cur_part: if !data.is_empty() { 0 } else { -1 },
         num_parts: ((data.len() + MAX_SNAPSHOT_PACKSIZE as usize - 1)
@@ -1,3 +1,4 @@
+use common::time::Tick;
 use format;
 use snap;
 use snap::Builder;
@@ -13,7 +14,7 @@ use warn::Warn;
 #[derive(Clone)]
 struct StoredSnap {
     snap: Snap,
-    tick: i32,
+    tick: Tick,
 }
 
 const MAX_STORED_SNAPSHOT: usize = 100;
@@ -57,9 +58,9 @@ pub struct Storage {
     /// The newest elements are in the front.
     snaps: VecDeque<StoredSnap>,
     free: Vec<Snap>,
-    ack_tick: Option<i32>,
+    ack_tick: Option<Tick>,
     delta: Delta,
-    delta_tick: Option<i32>,
+    delta_tick: Option<Tick>,
 }
 
 impl Storage {
@@ -72,28 +73,33 @@ impl Storage {
         self.snaps.drain(..).map(|s| self_free.push(s.snap)).count();
         self.ack_tick = None;
     }
-    pub fn ack_tick(&self) -> Option<i32> {
+    pub fn ack_tick(&self) -> Option<Tick> {
         self.ack_tick
     }
     pub fn add_delta<W>(
         &mut self,
         warn: &mut W,
         crc: Option<i32>,
-        delta_tick: i32,
-        tick: i32,
+        delta_tick: Tick,
+        tick: Tick,
         delta: &Delta,
     ) -> Result<&Snap, Error>
     where
         W: Warn<Warning>,
     {
-        if self.snaps.front().map(|s| s.tick).unwrap_or(-1) >= tick {
+        if self
+            .snaps
+            .front()
+            .map(|s| !tick.is_after(s.tick))
+            .unwrap_or(false)
+        {
             return Err(Error::OldDelta);
         }
         {
             let empty = Snap::empty();
             let delta_snap;
-            if delta_tick >= 0 {
-                if let Some(i) = self.snaps.iter().position(|s| s.tick < delta_tick) {
+            if delta_tick.to_i32() >= 0 {
+                if let Some(i) = self.snaps.iter().position(|s| delta_tick.is_after(s.tick)) {
                     let self_free = &mut self.free;
                     // FIXME: Replace with something like `exhaust`.
                     self.snaps
@@ -114,7 +120,7 @@ impl Storage {
                 }
             } else {
                 delta_snap = &empty;
-                if delta_tick != -1 {
+                if delta_tick.to_i32() != -1 {
                     warn.warn(Warning::WeirdNegativeDeltaTick);
                 }
             }
@@ -142,18 +148,18 @@ impl Storage {
     pub fn new_builder(&mut self) -> Builder {
         self.free.pop().unwrap_or_default().recycle()
     }
-    pub fn set_delta_tick<W>(&mut self, warn: &mut W, tick: i32) -> Result<(), UnknownSnap>
+    pub fn set_delta_tick<W>(&mut self, warn: &mut W, tick: Tick) -> Result<(), UnknownSnap>
     where
         W: Warn<WeirdNegativeDeltaTick>,
     {
-        if tick < 0 {
-            if tick != -1 {
+        if tick.to_i32() < 0 {
+            if tick.to_i32() != -1 {
                 warn.warn(WeirdNegativeDeltaTick);
             }
             self.delta_tick = None;
             return Ok(());
         }
-        if let Some(i) = self.snaps.iter().position(|s| s.tick < tick) {
+        if let Some(i) = self.snaps.iter().position(|s| tick.is_after(s.tick)) {
             let self_free = &mut self.free;
             // FIXME: Replace with something like `exhaust`.
             self.snaps
@@ -168,10 +174,10 @@ impl Storage {
         self.delta_tick = Some(tick);
         Ok(())
     }
-    pub fn delta_tick(&self) -> Option<i32> {
+    pub fn delta_tick(&self) -> Option<Tick> {
         self.delta_tick
     }
-    pub fn add_snap(&mut self, tick: i32, snap: Snap) -> &Delta {
+    pub fn add_snap(&mut self, tick: Tick, snap: Snap) -> &Delta {
         self.snaps.push_front(StoredSnap {
             snap: snap,
             tick: tick,
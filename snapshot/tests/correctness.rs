@@ -1,4 +1,5 @@
 extern crate buffer;
+extern crate common;
 extern crate gamenet_teeworlds_0_6 as gamenet;
 extern crate packer;
 extern crate snapshot;
@@ -61,3 +62,334 @@ fn simple() {
     println!("{:?}", snap);
     assert_eq!(snap.crc(), SECOND_CRC);
 }
+
+#[test]
+fn key_range() {
+    use snapshot::format::InvalidKey;
+    use snapshot::format::Item;
+    use snapshot::format::MAX_TYPE_ID;
+    use snapshot::snap::Builder;
+    use snapshot::snap::BuilderError;
+    use snapshot::SnapKey;
+    use std::convert::TryFrom;
+
+    let key = SnapKey::new(MAX_TYPE_ID, 0xffff).unwrap();
+    assert_eq!(key.type_id(), MAX_TYPE_ID);
+    assert_eq!(key.id(), 0xffff);
+    assert_eq!(SnapKey::try_from(key.to_i32()), Ok(key));
+    assert!(SnapKey::new(MAX_TYPE_ID + 1, 0).is_err());
+    assert!(SnapKey::try_from(-1).is_err());
+
+    let item = |type_id| Item {
+        type_id,
+        id: 0,
+        data: &[],
+    };
+    assert_eq!(item(MAX_TYPE_ID).key(), SnapKey::new(MAX_TYPE_ID, 0));
+    assert_eq!(item(MAX_TYPE_ID + 1).key(), Err(InvalidKey));
+
+    let mut builder = Builder::new();
+    assert_eq!(
+        builder.add_item(MAX_TYPE_ID + 1, 0, &[]),
+        Err(BuilderError::TypeIdRange)
+    );
+    builder.add_item(MAX_TYPE_ID, 0, &[1]).unwrap();
+    let snap = builder.finish();
+    assert_eq!(snap.item(MAX_TYPE_ID, 0), Some(&[1][..]));
+    assert_eq!(snap.item(MAX_TYPE_ID + 1, 0), None);
+}
+
+#[test]
+fn create_many() {
+    use snapshot::snap::Builder;
+
+    fn snap(items: &[(u16, u16, &[i32])]) -> Snap {
+        let mut builder = Builder::new();
+        for &(type_id, id, data) in items {
+            builder.add_item(type_id, id, data).unwrap();
+        }
+        builder.finish()
+    }
+
+    let to = snap(&[(1, 0, &[1, 2]), (1, 1, &[3, 4]), (2, 0, &[5])]);
+    let froms = [
+        Snap::empty(),
+        snap(&[(1, 0, &[1, 1]), (3, 0, &[7])]),
+        snap(&[(1, 1, &[3, 4]), (2, 0, &[-5])]),
+    ];
+    let froms: Vec<&Snap> = froms.iter().collect();
+    let mut deltas = vec![Delta::new(); froms.len()];
+    Delta::create_many(&mut deltas, &froms, &to);
+    for (from, delta) in froms.iter().zip(&deltas) {
+        let mut result = Snap::empty();
+        result.read_with_delta(&mut Panic, from, delta).unwrap();
+        assert_eq!(result.crc(), to.crc());
+        assert_eq!(result.items().len(), to.items().len());
+        for item in to.items() {
+            assert_eq!(result.item(item.type_id, item.id), Some(item.data));
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "index doesn't belong to snapshot")]
+fn create_with_stale_index() {
+    use snapshot::snap::Builder;
+    use snapshot::SnapIndex;
+
+    let mut builder = Builder::new();
+    builder.add_item(1, 0, &[1, 2]).unwrap();
+    let to = builder.finish();
+    let index = SnapIndex::new(&to);
+    Delta::new().create_with_index(&Snap::empty(), &to.clone(), &index);
+
+    // Same number of items, but different offsets.
+    let mut builder = to.recycle();
+    builder.add_item(1, 0, &[1]).unwrap();
+    let to = builder.finish();
+    Delta::new().create_with_index(&Snap::empty(), &to, &index);
+}
+
+fn write_snap(snap: &Snap) -> Vec<u8> {
+    let mut keys = Vec::new();
+    let mut buf = Vec::with_capacity(4096);
+    let len = with_packer(&mut buf, |p| snap.write(&mut keys, p))
+        .unwrap()
+        .len();
+    buf.truncate(len);
+    buf
+}
+
+#[test]
+fn write_with_arena() {
+    use common::TickArena;
+    use packer::with_arena_packer;
+    use snapshot::snap::Builder;
+
+    let mut builder = Builder::new();
+    builder.add_item(2, 0, &[4, 5]).unwrap();
+    builder.add_item(1, 7, &[1, 2, 3]).unwrap();
+    let snap = builder.finish();
+
+    let mut arena = TickArena::new();
+    for _ in 0..2 {
+        {
+            let written =
+                with_arena_packer(&arena, 4096, |p| snap.write_with_arena(&arena, p)).unwrap();
+            assert_eq!(written, &write_snap(&snap)[..]);
+        }
+        arena.reset();
+    }
+}
+
+#[test]
+fn snap_read_truncated() {
+    use snapshot::snap::Builder;
+    use snapshot::SnapReader;
+    use warn::Ignore;
+
+    let mut builder = Builder::new();
+    builder.add_item(1, 0, &[1, 2, 3]).unwrap();
+    builder.add_item(2, 5, &[-1000000]).unwrap();
+    builder.add_item(3, 7, &[]).unwrap();
+    let snap = builder.finish();
+    let data = write_snap(&snap);
+
+    let mut reader = SnapReader::new();
+    let read = reader
+        .read(&mut Panic, Snap::empty(), &mut Unpacker::new(&data))
+        .unwrap();
+    assert_eq!(read.crc(), snap.crc());
+    assert_eq!(read.item(2, 5), Some(&[-1000000][..]));
+
+    for len in 0..data.len() {
+        let result = reader.read(&mut Ignore, Snap::empty(), &mut Unpacker::new(&data[..len]));
+        assert!(result.is_err(), "truncated to {} bytes", len);
+    }
+}
+
+#[test]
+fn snap_read_missing_offsets() {
+    use snapshot::snap::Error;
+    use snapshot::SnapReader;
+
+    // data_size = 8, num_items = 2, but only one offset.
+    let mut reader = SnapReader::new();
+    let result = reader.read(
+        &mut Panic,
+        Snap::empty(),
+        &mut Unpacker::new(b"\x08\x02\x00"),
+    );
+    assert_eq!(result.unwrap_err(), Error::OffsetsUnpacking);
+}
+
+#[test]
+fn snap_read_excess_data() {
+    use snapshot::format::Warning;
+    use snapshot::SnapReader;
+
+    let mut data = write_snap(&Snap::empty());
+    data.push(0);
+    let mut warnings = Vec::new();
+    let mut reader = SnapReader::new();
+    reader
+        .read(&mut warnings, Snap::empty(), &mut Unpacker::new(&data))
+        .unwrap();
+    assert_eq!(warnings, [Warning::Packer(packer::Warning::ExcessData)]);
+}
+
+#[test]
+fn snap_read_strict() {
+    use packer::DecodePolicy;
+    use snapshot::snap::Error;
+    use snapshot::SnapReader;
+    use warn::Ignore;
+
+    let mut reader = SnapReader::new();
+    let mut data = write_snap(&Snap::empty());
+    data.push(0);
+    let mut p = Unpacker::new(&data).with_policy(DecodePolicy::Strict);
+    let result = reader.read(&mut Ignore, Snap::empty(), &mut p);
+    assert_eq!(result.unwrap_err(), Error::PolicyViolation);
+
+    // data_size = 0 with an overlong encoding.
+    let data = b"\x80\x00\x00";
+    let result = reader.read(&mut Ignore, Snap::empty(), &mut Unpacker::new(data));
+    assert!(result.is_ok());
+    let mut p = Unpacker::new(data).with_policy(DecodePolicy::Strict);
+    let result = reader.read(&mut Ignore, Snap::empty(), &mut p);
+    assert_eq!(result.unwrap_err(), Error::PolicyViolation);
+
+    // Truncated data is still reported as such.
+    let mut p = Unpacker::new(b"\x00").with_policy(DecodePolicy::Strict);
+    let result = reader.read(&mut Ignore, Snap::empty(), &mut p);
+    assert_eq!(result.unwrap_err(), Error::UnexpectedEnd);
+}
+
+#[test]
+fn delta_read_strict() {
+    use packer::DecodePolicy;
+    use snapshot::snap::Error;
+    use warn::Ignore;
+
+    // Empty delta, with an overlong encoding of the number of deleted items.
+    let data = b"\x80\x00\x00\x00";
+    let mut delta = Delta::new();
+    delta
+        .read(&mut Ignore, obj_size, &mut Unpacker::new(data))
+        .unwrap();
+    let mut p = Unpacker::new(data).with_policy(DecodePolicy::Strict);
+    assert_eq!(
+        delta.read(&mut Ignore, obj_size, &mut p),
+        Err(Error::PolicyViolation)
+    );
+}
+
+#[test]
+fn delta_read_located() {
+    use common::warn::Located;
+    use snapshot::format::Warning;
+
+    // Two updates of the same item, the first one with an overlong encoding
+    // of its data.
+    let data = b"\x00\x02\x00\x80\x04\x00\x01\x80\x00\x80\x04\x00\x01\x00";
+    let mut warnings = Vec::new();
+    let mut delta = Delta::new();
+    delta
+        .read_located(&mut warnings, obj_size, &mut Unpacker::new(data))
+        .unwrap();
+    assert_eq!(
+        warnings,
+        [
+            Located {
+                offset: 7,
+                structure: "item data",
+                warning: Warning::Packer(packer::Warning::OverlongIntEncoding),
+            },
+            Located {
+                offset: 9,
+                structure: "item diff",
+                warning: Warning::DuplicateUpdate,
+            },
+        ]
+    );
+}
+
+#[test]
+fn delta_differing_sizes() {
+    use snapshot::snap::Builder;
+    use snapshot::snap::Error;
+    use warn::Ignore;
+
+    let mut builder = Builder::new();
+    builder.add_item(0x100, 0, &[1, 2]).unwrap();
+    let from = builder.finish();
+
+    // No deletions, one update of the same item with a different size.
+    let mut buf = Vec::with_capacity(4096);
+    let len = with_packer(&mut buf, |mut p| -> Result<_, CapacityError> {
+        for &i in &[0, 1, 0, 0x100, 0, 3, 1, 1, 1] {
+            p.write_int(i)?;
+        }
+        Ok(p.written().len())
+    })
+    .unwrap();
+    let mut delta = Delta::new();
+    delta
+        .read(&mut Panic, obj_size, &mut Unpacker::new(&buf[..len]))
+        .unwrap();
+    let mut snap = Snap::empty();
+    assert_eq!(
+        snap.read_with_delta(&mut Ignore, &from, &delta),
+        Err(Error::DeltaDifferingSizes)
+    );
+}
+
+#[test]
+fn obj_meta() {
+    use gamenet::snap_obj::CHARACTER;
+    use gamenet::snap_obj::OBJ_META;
+
+    let mut buf = Vec::with_capacity(4096);
+    let mut delta = Delta::new();
+    let mut snap = Snap::default();
+    with_packer(&mut buf, |mut p| -> Result<_, CapacityError> {
+        for &d in FIRST_DATA {
+            p.write_int(d)?;
+        }
+        Ok(p.written())
+    })
+    .unwrap();
+    delta
+        .read(&mut Panic, obj_size, &mut Unpacker::new(&buf))
+        .unwrap();
+    snap.read_with_delta(&mut Panic, &Snap::empty(), &delta)
+        .unwrap();
+
+    // Every item of the snapshot is described by the metadata.
+    for item in snap.items() {
+        let meta = OBJ_META
+            .iter()
+            .find(|m| m.type_id == item.type_id.into())
+            .unwrap();
+        assert_eq!(meta.size as usize, item.data.len());
+        assert_eq!(meta.split(item.data).count(), meta.fields.len());
+    }
+
+    let meta = OBJ_META
+        .iter()
+        .find(|m| m.type_id == CHARACTER.into())
+        .unwrap();
+    let character = snap.item(CHARACTER, 0).unwrap();
+    let fields: Vec<_> = meta.split(character).map(|(f, v)| (f.name, v[0])).collect();
+    assert_eq!(fields[1], ("x", 1584));
+    assert_eq!(fields[2], ("y", 305));
+    assert_eq!(fields[8], ("hooked_player", -1));
+    assert_eq!(fields[16], ("health", 10));
+    for (field, values) in meta.split(character) {
+        assert!(field.kind.is_valid(values[0]), "{}", field.name);
+    }
+    let (offset, weapon) = meta.field("weapon").unwrap();
+    assert_eq!(offset, 19);
+    assert_eq!(weapon.kind.value_name(character[offset]), Some("pistol"));
+}
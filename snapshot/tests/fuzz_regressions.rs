@@ -0,0 +1,119 @@
+extern crate gamenet_teeworlds_0_6 as gamenet;
+extern crate packer;
+extern crate snapshot;
+extern crate warn;
+
+use gamenet::snap_obj::obj_size;
+use packer::with_packer;
+use packer::Unpacker;
+use snapshot::Delta;
+use snapshot::Snap;
+use snapshot::SnapReader;
+use std::fs;
+use std::path::Path;
+use warn::Ignore;
+
+// Bodies of the targets in `fuzz/fuzz_targets`, keep them in sync.
+
+fn snap_read(data: &[u8]) {
+    let mut reader = SnapReader::new();
+    let _ = reader.read(&mut Ignore, Snap::empty(), &mut Unpacker::new(data));
+}
+
+fn delta_read(data: &[u8]) {
+    let mut delta = Delta::new();
+    if delta
+        .read(&mut Ignore, obj_size, &mut Unpacker::new(data))
+        .is_err()
+    {
+        return;
+    }
+    let mut snap = Snap::empty();
+    let _ = snap.read_with_delta(&mut Ignore, &Snap::empty(), &delta);
+}
+
+fn assert_same(left: &Snap, right: &Snap) {
+    assert_eq!(left.crc(), right.crc());
+    assert_eq!(left.items().len(), right.items().len());
+    for item in left.items() {
+        assert_eq!(right.item(item.type_id, item.id), Some(item.data));
+    }
+}
+
+fn roundtrip(data: &[u8]) {
+    let mut delta = Delta::new();
+    if delta
+        .read(&mut Ignore, obj_size, &mut Unpacker::new(data))
+        .is_err()
+    {
+        return;
+    }
+    let mut snap = Snap::empty();
+    if snap
+        .read_with_delta(&mut Ignore, &Snap::empty(), &delta)
+        .is_err()
+    {
+        return;
+    }
+
+    let mut keys = Vec::new();
+    let mut buf: Vec<u8> = Vec::with_capacity(1024 * 1024);
+    let written = with_packer(&mut buf, |p| snap.write(&mut keys, p)).unwrap();
+    let mut reader = SnapReader::new();
+    let read = reader
+        .read(&mut Ignore, Snap::empty(), &mut Unpacker::new(written))
+        .unwrap();
+    assert_same(&snap, &read);
+
+    let mut created = Delta::new();
+    created.create(&Snap::empty(), &snap);
+    let mut buf: Vec<u8> = Vec::with_capacity(1024 * 1024);
+    let written = with_packer(&mut buf, |p| created.write(obj_size, p)).unwrap();
+    let mut read_delta = Delta::new();
+    read_delta
+        .read(&mut Ignore, obj_size, &mut Unpacker::new(written))
+        .unwrap();
+    let mut read = Snap::empty();
+    read.read_with_delta(&mut Ignore, &Snap::empty(), &read_delta)
+        .unwrap();
+    assert_same(&snap, &read);
+}
+
+fn corpus(target: &str, f: fn(&[u8])) {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("fuzz/corpus")
+        .join(target);
+    let mut count = 0;
+    for entry in fs::read_dir(dir).unwrap() {
+        f(&fs::read(entry.unwrap().path()).unwrap());
+        count += 1;
+    }
+    assert!(count != 0);
+}
+
+#[test]
+fn snap_read_corpus() {
+    corpus("snap_read", snap_read);
+}
+
+#[test]
+fn delta_read_corpus() {
+    corpus("delta_read", delta_read);
+}
+
+#[test]
+fn roundtrip_corpus() {
+    corpus("roundtrip", roundtrip);
+}
+
+#[test]
+fn truncated_offsets() {
+    // Announces 10 items, but the data ends before the offsets.
+    let data = include_bytes!("../fuzz/corpus/snap_read/truncated_offsets");
+    let mut reader = SnapReader::new();
+    assert!(reader
+        .read(&mut Ignore, Snap::empty(), &mut Unpacker::new(data))
+        .is_err());
+    delta_read(data);
+    roundtrip(data);
+}
@@ -21,6 +21,7 @@ use mio::net::UdpSocket;
 use mio::Ready;
 use mio::Token;
 use net::net::Callback;
+use net::Timeout;
 use net::Timestamp;
 use net2::UdpBuilder;
 use rand::thread_rng;
@@ -68,6 +69,12 @@ fn dump(dir: Direction, addr: Addr, data: &[u8]) {
 pub struct Addr {
     pub ip: IpAddr,
     pub port: u16,
+    /// Local port the datagram was received on, and the one replies are
+    /// sent from.
+    ///
+    /// `0` stands for the first port the socket is bound to, so only
+    /// servers bound to multiple ports see other values.
+    pub local_port: u16,
 }
 
 impl fmt::Display for Addr {
@@ -87,6 +94,7 @@ impl From<SocketAddr> for Addr {
         Addr {
             ip: sock_addr.ip(),
             port: sock_addr.port(),
+            local_port: 0,
         }
     }
 }
@@ -121,15 +129,22 @@ impl fmt::Display for AddressFamilyNotSupported {
     }
 }
 
+struct Bound {
+    socket: UdpSocket,
+    v6: bool,
+    // `0` for the first port, see `Addr::local_port`.
+    local_port: u16,
+    check: bool,
+}
+
+/// IPv4 and IPv6 UDP sockets on one or more ports, received from and sent
+/// to as one.
 pub struct Socket {
     start: Instant,
     time_cached: Timestamp,
     poll: mio::Poll,
     events: mio::Events,
-    v4: Option<UdpSocket>,
-    v6: Option<UdpSocket>,
-    check_v4: bool,
-    check_v6: bool,
+    sockets: Vec<Bound>,
     loss_rate: f32,
 }
 
@@ -170,48 +185,63 @@ impl Socket {
     pub fn bound_with_loss_rate(port: u16, loss_rate: f32) -> io::Result<Socket> {
         Socket::construct(Some(port), loss_rate)
     }
+    /// Binds to all of `ports` on both IPv4 and IPv6, the first one being
+    /// the default for sending, see `Addr::local_port`.
+    pub fn bound_ports(ports: &[u16]) -> io::Result<Socket> {
+        Socket::construct_ports(ports, 0.0)
+    }
     pub fn construct(port: Option<u16>, loss_rate: f32) -> io::Result<Socket> {
         assert!(port != Some(0));
-        let port = port.unwrap_or(0);
+        Socket::construct_ports(&[port.unwrap_or(0)], loss_rate)
+    }
+    /// A port of `0` binds to an arbitrary port and is only allowed alone.
+    pub fn construct_ports(ports: &[u16], loss_rate: f32) -> io::Result<Socket> {
+        assert!(!ports.is_empty());
+        assert!(ports.len() == 1 || ports.iter().all(|&p| p != 0));
         assert!(0.0 <= loss_rate && loss_rate <= 1.0);
 
-        fn register(poll: &mut mio::Poll, token: usize, socket: &UdpSocket) -> io::Result<()> {
-            use mio::PollOpt;
-            poll.register(socket, Token(token), Ready::readable(), PollOpt::level())
-        }
-
         let addr_v4 = IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0));
         let addr_v6 = IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0));
 
-        let v4 = udp_socket(&SocketAddr::new(addr_v4, port))?;
-        let v6 = udp_socket(&SocketAddr::new(addr_v6, port))?;
+        let mut sockets = Vec::new();
+        for (i, &port) in ports.iter().enumerate() {
+            for &ip in &[addr_v4, addr_v6] {
+                if let Some(socket) = udp_socket(&SocketAddr::new(ip, port))? {
+                    sockets.push(Bound {
+                        socket: socket,
+                        v6: ip.is_ipv6(),
+                        local_port: if i == 0 { 0 } else { port },
+                        check: false,
+                    });
+                }
+            }
+        }
 
-        if v4.is_none() && v6.is_none() {
+        if sockets.is_empty() {
             return Err(io::Error::new(
                 io::ErrorKind::Other,
                 NoAddressFamiliesSupported(()),
             ));
         }
 
-        let mut poll = mio::Poll::new()?;
-        v4.as_ref()
-            .map(|v4| register(&mut poll, 4, &v4))
-            .unwrap_or(Ok(()))?;
-        v6.as_ref()
-            .map(|v6| register(&mut poll, 6, &v6))
-            .unwrap_or(Ok(()))?;
+        let poll = mio::Poll::new()?;
+        for (i, bound) in sockets.iter().enumerate() {
+            use mio::PollOpt;
+            poll.register(&bound.socket, Token(i), Ready::readable(), PollOpt::level())?;
+        }
         Ok(Socket {
             start: Instant::now(),
             time_cached: Timestamp::from_secs_since_epoch(0),
             poll: poll,
-            events: mio::Events::with_capacity(2),
-            v4: v4,
-            v6: v6,
-            check_v4: false,
-            check_v6: false,
+            events: mio::Events::with_capacity(sockets.len()),
+            sockets: sockets,
             loss_rate: loss_rate,
         })
     }
+    /// Addresses of all bound sockets.
+    pub fn local_addrs(&self) -> io::Result<Vec<SocketAddr>> {
+        self.sockets.iter().map(|b| b.socket.local_addr()).collect()
+    }
     fn loss(&self) -> bool {
         self.loss_rate != 0.0 && rand::random::<f32>() < self.loss_rate
     }
@@ -228,25 +258,23 @@ impl Socket {
         let mut result = None;
         {
             let buf_slice = unsafe { buf.uninitialized_mut() };
-            if result.is_none() && self.check_v6 {
-                if let Some(r) = non_block(self.v6.as_ref().unwrap().recv_from(buf_slice)) {
-                    result = Some(r);
-                    self.check_v6 = false;
-                }
-            }
-            if result.is_none() && self.check_v4 {
-                if let Some(r) = non_block(self.v4.as_ref().unwrap().recv_from(buf_slice)) {
-                    result = Some(r);
-                    self.check_v4 = false;
+            for bound in self.sockets.iter_mut().filter(|b| b.check) {
+                if let Some(r) = non_block(bound.socket.recv_from(buf_slice)) {
+                    result = Some((r, bound.local_port));
+                    bound.check = false;
+                    break;
                 }
             }
         }
-        let result = unwrap_or_return!(result);
+        let (result, local_port) = unwrap_or_return!(result);
         if self.loss() {
             return self.receive_impl(buf);
         }
         Some(result.map(|(len, addr)| unsafe {
-            let addr = Addr::from(addr);
+            let addr = Addr {
+                local_port: local_port,
+                ..Addr::from(addr)
+            };
             buf.advance(len);
             let initialized = buf.initialized();
             dump(Direction::Receive, addr, initialized);
@@ -262,11 +290,7 @@ impl Socket {
         // on loss-free networks.
         for ev in &self.events {
             assert!(ev.readiness() == Ready::readable());
-            match ev.token() {
-                Token(4) => self.check_v4 = true,
-                Token(6) => self.check_v6 = true,
-                _ => unreachable!(),
-            }
+            self.sockets[ev.token().0].check = true;
         }
         self.update_time_cached();
         Ok(())
@@ -274,6 +298,11 @@ impl Socket {
     pub fn update_time_cached(&mut self) {
         self.time_cached = Timestamp::from_secs_since_epoch(0) + self.start.elapsed();
     }
+    /// Converts a time as returned by `Callback::time` to an `Instant`.
+    pub fn instant(&self, time: Timestamp) -> Instant {
+        let epoch = Timestamp::from_secs_since_epoch(0);
+        self.start + Timeout::active(time).time_from(epoch).unwrap()
+    }
 }
 
 impl Callback<Addr> for Socket {
@@ -287,14 +316,16 @@ impl Callback<Addr> for Socket {
         }
         dump(Direction::Send, addr, data);
         let sock_addr = SocketAddr::new(addr.ip, addr.port);
-        let maybe_socket = if let IpAddr::V4(..) = addr.ip {
-            &self.v4
-        } else {
-            &self.v6
-        };
+        let v6 = addr.ip.is_ipv6();
+        // Fall back to the first port if the local one isn't bound.
+        let maybe_socket = self
+            .sockets
+            .iter()
+            .find(|b| b.v6 == v6 && b.local_port == addr.local_port)
+            .or_else(|| self.sockets.iter().find(|b| b.v6 == v6));
         let socket;
-        if let Some(ref s) = *maybe_socket {
-            socket = s;
+        if let Some(b) = maybe_socket {
+            socket = &b.socket;
         } else {
             return Err(io::Error::new(
                 io::ErrorKind::Other,
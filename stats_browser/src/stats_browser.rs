@@ -1,6 +1,7 @@
 use common::num::Cast;
 use common::pretty::Bytes;
 use serverbrowse::protocol;
+use serverbrowse::protocol::AddrPackedExt;
 use serverbrowse::protocol::Count7Response;
 use serverbrowse::protocol::CountResponse;
 use serverbrowse::protocol::Info5Response;
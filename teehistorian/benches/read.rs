@@ -0,0 +1,83 @@
+#[macro_use]
+extern crate bencher;
+extern crate chrono;
+extern crate common;
+extern crate teehistorian;
+
+use bencher::black_box;
+use bencher::Bencher;
+use chrono::DateTime;
+use common::time::Tick;
+use std::collections::HashMap;
+use teehistorian::Buffer;
+use teehistorian::Header;
+use teehistorian::Input;
+use teehistorian::Item;
+use teehistorian::Player;
+use teehistorian::PlayerChange;
+use teehistorian::Pos;
+use teehistorian::Reader;
+use teehistorian::Writer;
+
+const NUM_CLIENTS: i32 = 64;
+const NUM_TICKS: i32 = 1000;
+
+/// Writes a file resembling a full server, every client moves and changes
+/// its input every few ticks.
+fn synthetic_file() -> Vec<u8> {
+    let header = Header {
+        version: 2,
+        game_uuid: "97b2e4ec-a72e-4f2f-8ea3-0fae2dcb8a16".parse().unwrap(),
+        timestamp: DateTime::parse_from_rfc3339("2020-05-04T13:37:00+02:00").unwrap(),
+        server_port: 8303,
+        map_name: "Tutorial".into(),
+        map_size: 12345,
+        map_sha256: None,
+        map_crc: 0x0123abcd,
+        config: HashMap::new(),
+    };
+    let mut writer = Writer::new(Vec::new(), &header).unwrap();
+    for tick in 0..NUM_TICKS {
+        writer.write(&Item::TickStart(Tick(tick))).unwrap();
+        for cid in 0..NUM_CLIENTS {
+            let pos = Pos {
+                x: cid * 32 + tick,
+                y: cid * 32 - tick,
+            };
+            if tick == 0 {
+                writer.write(&Item::PlayerNew(Player { cid, pos })).unwrap();
+            } else {
+                let change = PlayerChange {
+                    cid,
+                    pos,
+                    old_pos: pos,
+                };
+                writer.write(&Item::PlayerChange(change)).unwrap();
+            }
+        }
+        for cid in 0..NUM_CLIENTS {
+            if (tick + cid) % 4 == 0 {
+                let input = [tick % 3 - 1, tick, -tick, 0, 0, 0, 0, 0, 0, 0];
+                writer.write(&Item::Input(Input { cid, input })).unwrap();
+            }
+        }
+        writer.write(&Item::TickEnd(Tick(tick))).unwrap();
+    }
+    writer.finish().unwrap()
+}
+
+fn read(bench: &mut Bencher) {
+    let data = synthetic_file();
+    bench.bytes = data.len() as u64;
+    let mut buffer = Buffer::new();
+    bench.iter(|| {
+        buffer.clear();
+        let (_, mut reader) = Reader::new(&data[..], &mut buffer).unwrap();
+        while let Some(item) = reader.read(&mut buffer).unwrap() {
+            black_box(item);
+        }
+    });
+}
+
+benchmark_group!(teehistorian, read);
+benchmark_main!(teehistorian);
@@ -1,6 +1,7 @@
 use std::fs::File;
 use std::io;
-use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
 use std::ops;
 use std::path::Path;
 
@@ -11,6 +12,7 @@ use raw;
 use raw::Callback;
 
 pub use raw::Buffer;
+pub use raw::Cursor;
 pub use raw::Item;
 pub use raw::Pos;
 
@@ -41,17 +43,46 @@ impl From<raw::Error<io::Error>> for Error {
     }
 }
 
-struct CallbackData {
-    file: File,
+struct CallbackData<R> {
+    file: R,
 }
 
-pub struct Reader {
-    callback_data: CallbackData,
+/// Reads a teehistorian file incrementally from an `io::Read`.
+///
+/// Only the data of the current item is kept in the buffer, so files of any
+/// size can be read with bounded memory.
+pub struct Reader<R = File> {
+    callback_data: CallbackData<R>,
     raw: raw::Reader,
 }
 
-impl Reader {
-    fn new_impl<'a>(file: File, buffer: &'a mut Buffer) -> Result<(Header<'a>, Reader), Error> {
+impl Reader<File> {
+    pub fn open<'a, P: AsRef<Path>>(
+        path: P,
+        buffer: &'a mut Buffer,
+    ) -> Result<(Header, Reader), Error> {
+        fn inner<'a>(path: &Path, buffer: &'a mut Buffer) -> Result<(Header<'a>, Reader), Error> {
+            Reader::new_impl(File::open(path)?, buffer)
+        }
+        inner(path.as_ref(), buffer)
+    }
+    /// Opens the file and continues reading at `cursor`.
+    pub fn open_at<P: AsRef<Path>>(
+        path: P,
+        cursor: &Cursor,
+        buffer: &mut Buffer,
+    ) -> Result<Reader, Error> {
+        fn inner(path: &Path, cursor: &Cursor, buffer: &mut Buffer) -> Result<Reader, Error> {
+            let mut file = File::open(path)?;
+            file.seek(SeekFrom::Start(cursor.offset()))?;
+            Ok(Reader::resume(file, cursor, buffer))
+        }
+        inner(path.as_ref(), cursor, buffer)
+    }
+}
+
+impl<R: io::Read> Reader<R> {
+    fn new_impl<'a>(file: R, buffer: &'a mut Buffer) -> Result<(Header<'a>, Reader<R>), Error> {
         let mut callback_data = CallbackData { file: file };
         let (header, raw) = raw::Reader::new(&mut callback_data, buffer)?;
         Ok((
@@ -62,17 +93,20 @@ impl Reader {
             },
         ))
     }
-    pub fn new<'a>(file: File, buffer: &'a mut Buffer) -> Result<(Header<'a>, Reader), Error> {
+    pub fn new<'a>(file: R, buffer: &'a mut Buffer) -> Result<(Header<'a>, Reader<R>), Error> {
         Reader::new_impl(file, buffer)
     }
-    pub fn open<'a, P: AsRef<Path>>(
-        path: P,
-        buffer: &'a mut Buffer,
-    ) -> Result<(Header, Reader), Error> {
-        fn inner<'a>(path: &Path, buffer: &'a mut Buffer) -> Result<(Header<'a>, Reader), Error> {
-            Reader::new_impl(File::open(path)?, buffer)
+    /// Continues reading at `cursor`, `file` must be positioned at
+    /// `cursor.offset()`.
+    pub fn resume(file: R, cursor: &Cursor, buffer: &mut Buffer) -> Reader<R> {
+        Reader {
+            callback_data: CallbackData { file },
+            raw: raw::Reader::resume(cursor, buffer),
         }
-        inner(path.as_ref(), buffer)
+    }
+    /// Returns a cursor to resume reading at the current position later.
+    pub fn cursor(&self, buffer: &Buffer) -> Cursor {
+        self.raw.cursor(buffer)
     }
     pub fn read<'a>(&mut self, buffer: &'a mut Buffer) -> Result<Option<Item<'a>>, Error> {
         Ok(self.raw.read(&mut self.callback_data, buffer)?)
@@ -86,9 +120,12 @@ impl Reader {
     pub fn cids(&self) -> ops::Range<i32> {
         self.raw.cids()
     }
+    pub fn into_inner(self) -> R {
+        self.callback_data.file
+    }
 }
 
-impl Callback for CallbackData {
+impl<R: io::Read> Callback for CallbackData<R> {
     type Error = io::Error;
     fn read_at_most(&mut self, buffer: &mut [u8]) -> io::Result<Option<usize>> {
         match self.file.read(buffer) {
@@ -99,3 +136,75 @@ impl Callback for CallbackData {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use chrono::DateTime;
+    use common::num::Cast;
+    use common::time::Tick;
+    use std::collections::HashMap;
+
+    use super::Buffer;
+    use super::Item;
+    use super::Reader;
+    use format::Header;
+    use raw::Player;
+    use raw::PlayerChange;
+    use raw::Pos;
+    use writer::Writer;
+
+    fn rest(reader: &mut Reader<&[u8]>, buffer: &mut Buffer) -> Vec<String> {
+        let mut result = Vec::new();
+        while let Some(item) = reader.read(buffer).unwrap() {
+            result.push(format!("{:?}", item));
+        }
+        result
+    }
+
+    #[test]
+    fn resume() {
+        let header = Header {
+            version: 2,
+            game_uuid: "97b2e4ec-a72e-4f2f-8ea3-0fae2dcb8a16".parse().unwrap(),
+            timestamp: DateTime::parse_from_rfc3339("2020-05-04T13:37:00+02:00").unwrap(),
+            server_port: 8303,
+            map_name: "Tutorial".into(),
+            map_size: 12345,
+            map_sha256: None,
+            map_crc: 0x0123abcd,
+            config: HashMap::new(),
+        };
+        let mut writer = Writer::new(Vec::new(), &header).unwrap();
+        for tick in 0..100 {
+            writer.write(&Item::TickStart(Tick(tick))).unwrap();
+            for cid in 0..4 {
+                let pos = Pos {
+                    x: tick * cid,
+                    y: 0,
+                };
+                if tick == 0 {
+                    writer.write(&Item::PlayerNew(Player { cid, pos })).unwrap();
+                } else if tick % (cid + 1) == 0 {
+                    let old_pos = Pos { x: 0, y: 0 };
+                    let change = PlayerChange { cid, pos, old_pos };
+                    writer.write(&Item::PlayerChange(change)).unwrap();
+                }
+            }
+            writer.write(&Item::TickEnd(Tick(tick))).unwrap();
+        }
+        let data = writer.finish().unwrap();
+
+        let mut buffer = Buffer::new();
+        let (_, mut reader) = Reader::new(&data[..], &mut buffer).unwrap();
+        for _ in 0..123 {
+            reader.read(&mut buffer).unwrap().unwrap();
+        }
+        let cursor = reader.cursor(&buffer);
+        let expected = rest(&mut reader, &mut buffer);
+
+        let mut buffer = Buffer::new();
+        let offset = cursor.offset().assert_usize();
+        let mut reader = Reader::resume(&data[offset..], &cursor, &mut buffer);
+        assert_eq!(rest(&mut reader, &mut buffer), expected);
+    }
+}
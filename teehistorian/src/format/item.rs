@@ -2,11 +2,13 @@ use arrayvec::ArrayVec;
 use common::num::Cast;
 use common::pretty;
 use packer::positive;
+use packer::IoPacker;
 use packer::Unpacker;
 use serde;
 use serde::ser::SerializeSeq;
 use serde::Serialize;
 use std::fmt;
+use std::io;
 use uuid::Uuid;
 use warn::Ignore;
 
@@ -28,6 +30,10 @@ pub const EX: i32 = -11;
 pub const INPUT_LEN: usize = 10;
 pub const CONSOLE_COMMAND_MAX_ARGS: usize = 16;
 
+pub const UUID_ANTIBOT: [u8; 16] = [
+    // "866bfdac-fb49-3c0b-a887-5fe1f3ea00b8"
+    0x86, 0x6b, 0xfd, 0xac, 0xfb, 0x49, 0x3c, 0x0b, 0xa8, 0x87, 0x5f, 0xe1, 0xf3, 0xea, 0x00, 0xb8,
+];
 pub const UUID_AUTH_INIT: [u8; 16] = [
     // "60daba5c-52c4-3aeb-b8ba-b2953fb55a17"
     0x60, 0xda, 0xba, 0x5c, 0x52, 0xc4, 0x3a, 0xeb, 0xb8, 0xba, 0xb2, 0x95, 0x3f, 0xb5, 0x5a, 0x17,
@@ -56,10 +62,34 @@ pub const UUID_JOINVER7: [u8; 16] = [
     // "59239b05-0540-318d-bea4-9aa1e80e7d2b"
     0x59, 0x23, 0x9b, 0x05, 0x05, 0x40, 0x31, 0x8d, 0xbe, 0xa4, 0x9a, 0xa1, 0xe8, 0x0e, 0x7d, 0x2b,
 ];
+pub const UUID_PLAYER_FINISH: [u8; 16] = [
+    // "68943c01-2348-3e01-9490-3f27f8269d94"
+    0x68, 0x94, 0x3c, 0x01, 0x23, 0x48, 0x3e, 0x01, 0x94, 0x90, 0x3f, 0x27, 0xf8, 0x26, 0x9d, 0x94,
+];
+pub const UUID_PLAYER_NAME: [u8; 16] = [
+    // "d016f9b9-4151-3b87-87e5-3a6087eb5f26"
+    0xd0, 0x16, 0xf9, 0xb9, 0x41, 0x51, 0x3b, 0x87, 0x87, 0xe5, 0x3a, 0x60, 0x87, 0xeb, 0x5f, 0x26,
+];
+pub const UUID_PLAYER_READY: [u8; 16] = [
+    // "7bf9c810-a67e-3d20-a2ac-3b1122aa7c8d"
+    0x7b, 0xf9, 0xc8, 0x10, 0xa6, 0x7e, 0x3d, 0x20, 0xa2, 0xac, 0x3b, 0x11, 0x22, 0xaa, 0x7c, 0x8d,
+];
+pub const UUID_PLAYER_SWAP: [u8; 16] = [
+    // "90c2e681-7e60-382d-b8de-2635a1d55408"
+    0x90, 0xc2, 0xe6, 0x81, 0x7e, 0x60, 0x38, 0x2d, 0xb8, 0xde, 0x26, 0x35, 0xa1, 0xd5, 0x54, 0x08,
+];
 pub const UUID_PLAYER_TEAM: [u8; 16] = [
     // "a111c04e-1ea8-38e0-90b1-d7f993ca0da9"
     0xa1, 0x11, 0xc0, 0x4e, 0x1e, 0xa8, 0x38, 0xe0, 0x90, 0xb1, 0xd7, 0xf9, 0x93, 0xca, 0x0d, 0xa9,
 ];
+pub const UUID_REJOINVER6: [u8; 16] = [
+    // "c1e921d5-96f5-37bb-8a45-7a06f163d27e"
+    0xc1, 0xe9, 0x21, 0xd5, 0x96, 0xf5, 0x37, 0xbb, 0x8a, 0x45, 0x7a, 0x06, 0xf1, 0x63, 0xd2, 0x7e,
+];
+pub const UUID_TEAM_FINISH: [u8; 16] = [
+    // "9588b9af-3fdc-3760-8043-82deeee317a5"
+    0x95, 0x88, 0xb9, 0xaf, 0x3f, 0xdc, 0x37, 0x60, 0x80, 0x43, 0x82, 0xde, 0xee, 0xe3, 0x17, 0xa5,
+];
 pub const UUID_TEAM_LOAD_FAILURE: [u8; 16] = [
     // "ef8905a2-c695-3591-a1cd-53d2015992dd"
     0xef, 0x89, 0x05, 0xa2, 0xc6, 0x95, 0x35, 0x91, 0xa1, 0xcd, 0x53, 0xd2, 0x01, 0x59, 0x92, 0xdd,
@@ -81,6 +111,31 @@ pub const UUID_TEAM_SAVE_SUCCESS: [u8; 16] = [
     0x45, 0x60, 0xc7, 0x56, 0xda, 0x29, 0x30, 0x36, 0x81, 0xd4, 0x90, 0xa5, 0x0f, 0x01, 0x82, 0xcd,
 ];
 
+/// Names of the extended items understood by this crate, as written to the
+/// `uuids` field of the header.
+pub const EX_UUIDS: &[(&str, [u8; 16])] = &[
+    ("teehistorian-antibot@ddnet.org", UUID_ANTIBOT),
+    ("teehistorian-auth-init@ddnet.tw", UUID_AUTH_INIT),
+    ("teehistorian-auth-login@ddnet.tw", UUID_AUTH_LOGIN),
+    ("teehistorian-auth-logout@ddnet.tw", UUID_AUTH_LOGOUT),
+    ("teehistorian-ddnetver@ddnet.tw", UUID_DDNETVER),
+    ("teehistorian-ddnetver-old@ddnet.tw", UUID_DDNETVER_OLD),
+    ("teehistorian-joinver6@ddnet.tw", UUID_JOINVER6),
+    ("teehistorian-joinver7@ddnet.tw", UUID_JOINVER7),
+    ("teehistorian-player-finish@ddnet.org", UUID_PLAYER_FINISH),
+    ("teehistorian-player-name@ddnet.org", UUID_PLAYER_NAME),
+    ("teehistorian-player-ready@ddnet.org", UUID_PLAYER_READY),
+    ("teehistorian-playerswap@ddnet.org", UUID_PLAYER_SWAP),
+    ("teehistorian-player-team@ddnet.tw", UUID_PLAYER_TEAM),
+    ("teehistorian-rejoinver6@ddnet.org", UUID_REJOINVER6),
+    ("teehistorian-team-finish@ddnet.org", UUID_TEAM_FINISH),
+    ("teehistorian-load-failure@ddnet.tw", UUID_TEAM_LOAD_FAILURE),
+    ("teehistorian-load-success@ddnet.tw", UUID_TEAM_LOAD_SUCCESS),
+    ("teehistorian-team-practice@ddnet.tw", UUID_TEAM_PRACTICE),
+    ("teehistorian-save-failure@ddnet.tw", UUID_TEAM_SAVE_FAILURE),
+    ("teehistorian-save-success@ddnet.tw", UUID_TEAM_SAVE_SUCCESS),
+];
+
 #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
 pub enum Kind {
     /// PlayerDiff(cid)
@@ -201,6 +256,7 @@ pub enum Item<'a> {
     Drop(Drop<'a>),
     ConsoleCommand(ConsoleCommand<'a>),
 
+    Antibot(Antibot<'a>),
     AuthInit(AuthInit<'a>),
     AuthLogin(AuthLogin<'a>),
     AuthLogout(AuthLogout),
@@ -208,7 +264,13 @@ pub enum Item<'a> {
     DdnetverOld(DdnetverOld),
     Joinver6(Joinver6),
     Joinver7(Joinver7),
+    PlayerFinish(PlayerFinish),
+    PlayerName(PlayerName<'a>),
+    PlayerReady(PlayerReady),
+    PlayerSwap(PlayerSwap),
     PlayerTeam(PlayerTeam),
+    Rejoinver6(Rejoinver6),
+    TeamFinish(TeamFinish),
     TeamLoadFailure(TeamLoadFailure),
     TeamLoadSuccess(TeamLoadSuccess<'a>),
     TeamPractice(TeamPractice),
@@ -285,6 +347,11 @@ pub struct ConsoleCommand<'a> {
     pub args: ArrayVec<[&'a [u8]; CONSOLE_COMMAND_MAX_ARGS]>,
 }
 
+#[derive(Clone, Serialize)]
+pub struct Antibot<'a> {
+    pub data: &'a [u8],
+}
+
 #[derive(Clone, Serialize)]
 pub struct AuthInit<'a> {
     pub cid: i32,
@@ -331,12 +398,49 @@ pub struct Joinver7 {
     pub cid: i32,
 }
 
+#[derive(Clone, Debug, Serialize)]
+pub struct PlayerFinish {
+    pub cid: i32,
+    /// Finish time in ticks.
+    pub time: i32,
+}
+
+#[derive(Clone, Serialize)]
+pub struct PlayerName<'a> {
+    pub cid: i32,
+    #[serde(serialize_with = "serialize_str_lossy")]
+    pub name: &'a [u8],
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PlayerReady {
+    pub cid: i32,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PlayerSwap {
+    pub cid1: i32,
+    pub cid2: i32,
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct PlayerTeam {
     pub cid: i32,
     pub team: i32,
 }
 
+#[derive(Clone, Debug, Serialize)]
+pub struct Rejoinver6 {
+    pub cid: i32,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TeamFinish {
+    pub team: i32,
+    /// Finish time in ticks.
+    pub time: i32,
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct TeamLoadFailure {
     pub team: i32,
@@ -369,6 +473,7 @@ pub struct TeamSaveSuccess<'a> {
     pub save: &'a [u8],
 }
 
+/// Extended item with a UUID not known to this crate.
 #[derive(Clone, Serialize)]
 pub struct UnknownEx<'a> {
     pub uuid: Uuid,
@@ -397,6 +502,7 @@ impl<'a> Item<'a> {
         let uuid = p.read_uuid()?;
         let data = p.read_data(&mut Ignore)?;
         Ok(match *uuid.as_bytes() {
+            UUID_ANTIBOT => Antibot::decode(&mut Unpacker::new(data))?.into(),
             UUID_AUTH_INIT => AuthInit::decode(&mut Unpacker::new(data))?.into(),
             UUID_AUTH_LOGIN => AuthLogin::decode(&mut Unpacker::new(data))?.into(),
             UUID_AUTH_LOGOUT => AuthLogout::decode(&mut Unpacker::new(data))?.into(),
@@ -404,7 +510,13 @@ impl<'a> Item<'a> {
             UUID_DDNETVER_OLD => DdnetverOld::decode(&mut Unpacker::new(data))?.into(),
             UUID_JOINVER6 => Joinver6::decode(&mut Unpacker::new(data))?.into(),
             UUID_JOINVER7 => Joinver7::decode(&mut Unpacker::new(data))?.into(),
+            UUID_PLAYER_FINISH => PlayerFinish::decode(&mut Unpacker::new(data))?.into(),
+            UUID_PLAYER_NAME => PlayerName::decode(&mut Unpacker::new(data))?.into(),
+            UUID_PLAYER_READY => PlayerReady::decode(&mut Unpacker::new(data))?.into(),
+            UUID_PLAYER_SWAP => PlayerSwap::decode(&mut Unpacker::new(data))?.into(),
             UUID_PLAYER_TEAM => PlayerTeam::decode(&mut Unpacker::new(data))?.into(),
+            UUID_REJOINVER6 => Rejoinver6::decode(&mut Unpacker::new(data))?.into(),
+            UUID_TEAM_FINISH => TeamFinish::decode(&mut Unpacker::new(data))?.into(),
             UUID_TEAM_LOAD_FAILURE => TeamLoadFailure::decode(&mut Unpacker::new(data))?.into(),
             UUID_TEAM_LOAD_SUCCESS => TeamLoadSuccess::decode(&mut Unpacker::new(data))?.into(),
             UUID_TEAM_PRACTICE => TeamPractice::decode(&mut Unpacker::new(data))?.into(),
@@ -417,6 +529,42 @@ impl<'a> Item<'a> {
             .into(),
         })
     }
+    pub fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        match *self {
+            Item::PlayerDiff(ref i) => i.encode(p),
+            Item::Finish(ref i) => i.encode(p),
+            Item::TickSkip(ref i) => i.encode(p),
+            Item::PlayerNew(ref i) => i.encode(p),
+            Item::PlayerOld(ref i) => i.encode(p),
+            Item::InputDiff(ref i) => i.encode(p),
+            Item::InputNew(ref i) => i.encode(p),
+            Item::Message(ref i) => i.encode(p),
+            Item::Join(ref i) => i.encode(p),
+            Item::Drop(ref i) => i.encode(p),
+            Item::ConsoleCommand(ref i) => i.encode(p),
+            Item::Antibot(ref i) => encode_ex(p, UUID_ANTIBOT, |p| i.encode(p)),
+            Item::AuthInit(ref i) => encode_ex(p, UUID_AUTH_INIT, |p| i.encode(p)),
+            Item::AuthLogin(ref i) => encode_ex(p, UUID_AUTH_LOGIN, |p| i.encode(p)),
+            Item::AuthLogout(ref i) => encode_ex(p, UUID_AUTH_LOGOUT, |p| i.encode(p)),
+            Item::Ddnetver(ref i) => encode_ex(p, UUID_DDNETVER, |p| i.encode(p)),
+            Item::DdnetverOld(ref i) => encode_ex(p, UUID_DDNETVER_OLD, |p| i.encode(p)),
+            Item::Joinver6(ref i) => encode_ex(p, UUID_JOINVER6, |p| i.encode(p)),
+            Item::Joinver7(ref i) => encode_ex(p, UUID_JOINVER7, |p| i.encode(p)),
+            Item::PlayerFinish(ref i) => encode_ex(p, UUID_PLAYER_FINISH, |p| i.encode(p)),
+            Item::PlayerName(ref i) => encode_ex(p, UUID_PLAYER_NAME, |p| i.encode(p)),
+            Item::PlayerReady(ref i) => encode_ex(p, UUID_PLAYER_READY, |p| i.encode(p)),
+            Item::PlayerSwap(ref i) => encode_ex(p, UUID_PLAYER_SWAP, |p| i.encode(p)),
+            Item::PlayerTeam(ref i) => encode_ex(p, UUID_PLAYER_TEAM, |p| i.encode(p)),
+            Item::Rejoinver6(ref i) => encode_ex(p, UUID_REJOINVER6, |p| i.encode(p)),
+            Item::TeamFinish(ref i) => encode_ex(p, UUID_TEAM_FINISH, |p| i.encode(p)),
+            Item::TeamLoadFailure(ref i) => encode_ex(p, UUID_TEAM_LOAD_FAILURE, |p| i.encode(p)),
+            Item::TeamLoadSuccess(ref i) => encode_ex(p, UUID_TEAM_LOAD_SUCCESS, |p| i.encode(p)),
+            Item::TeamPractice(ref i) => encode_ex(p, UUID_TEAM_PRACTICE, |p| i.encode(p)),
+            Item::TeamSaveFailure(ref i) => encode_ex(p, UUID_TEAM_SAVE_FAILURE, |p| i.encode(p)),
+            Item::TeamSaveSuccess(ref i) => encode_ex(p, UUID_TEAM_SAVE_SUCCESS, |p| i.encode(p)),
+            Item::UnknownEx(ref i) => encode_ex(p, *i.uuid.as_bytes(), |p| p.write_rest(i.data)),
+        }
+    }
     pub fn cid(&self) -> Option<i32> {
         Some(match *self {
             Item::PlayerDiff(ref i) => i.cid,
@@ -430,6 +578,7 @@ impl<'a> Item<'a> {
             Item::Join(ref i) => i.cid,
             Item::Drop(ref i) => i.cid,
             Item::ConsoleCommand(ref i) => i.cid,
+            Item::Antibot(_) => return None,
             Item::AuthInit(ref i) => i.cid,
             Item::AuthLogin(ref i) => i.cid,
             Item::AuthLogout(ref i) => i.cid,
@@ -437,7 +586,13 @@ impl<'a> Item<'a> {
             Item::DdnetverOld(ref i) => i.cid,
             Item::Joinver6(ref i) => i.cid,
             Item::Joinver7(ref i) => i.cid,
+            Item::PlayerFinish(ref i) => i.cid,
+            Item::PlayerName(ref i) => i.cid,
+            Item::PlayerReady(ref i) => i.cid,
+            Item::PlayerSwap(ref i) => i.cid1,
             Item::PlayerTeam(ref i) => i.cid,
+            Item::Rejoinver6(ref i) => i.cid,
+            Item::TeamFinish(_) => return None,
             Item::TeamLoadFailure(_) => return None,
             Item::TeamLoadSuccess(_) => return None,
             Item::TeamPractice(_) => return None,
@@ -448,6 +603,22 @@ impl<'a> Item<'a> {
     }
 }
 
+fn encode_ex<W, F>(p: &mut IoPacker<W>, uuid: [u8; 16], f: F) -> io::Result<()>
+where
+    W: io::Write,
+    F: FnOnce(&mut IoPacker<&mut Vec<u8>>) -> io::Result<()>,
+{
+    let mut data = Vec::new();
+    {
+        let mut inner = IoPacker::new(&mut data);
+        f(&mut inner)?;
+        inner.finish()?;
+    }
+    p.write_int(EX)?;
+    p.write_uuid(Uuid::from_bytes(uuid))?;
+    p.write_data(&data)
+}
+
 impl PlayerDiff {
     fn decode(cid: i32, _p: &mut Unpacker) -> Result<PlayerDiff, MaybeEnd<Error>> {
         Ok(PlayerDiff {
@@ -456,12 +627,20 @@ impl PlayerDiff {
             dy: _p.read_int(&mut Ignore)?,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.cid)?;
+        p.write_int(self.dx)?;
+        p.write_int(self.dy)
+    }
 }
 
 impl Finish {
     fn decode(_p: &mut Unpacker) -> Result<Finish, MaybeEnd<Error>> {
         Ok(Finish)
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(FINISH)
+    }
 }
 
 impl TickSkip {
@@ -472,6 +651,10 @@ impl TickSkip {
                 .assert_u32(),
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(TICK_SKIP)?;
+        p.write_int(self.dt.assert_i32())
+    }
 }
 
 impl PlayerNew {
@@ -482,12 +665,22 @@ impl PlayerNew {
             y: _p.read_int(&mut Ignore)?,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(PLAYER_NEW)?;
+        p.write_int(self.cid)?;
+        p.write_int(self.x)?;
+        p.write_int(self.y)
+    }
 }
 
 impl PlayerOld {
     fn decode(cid: i32, _p: &mut Unpacker) -> Result<PlayerOld, MaybeEnd<Error>> {
         Ok(PlayerOld { cid: cid })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(PLAYER_OLD)?;
+        p.write_int(self.cid)
+    }
 }
 
 impl InputDiff {
@@ -508,6 +701,14 @@ impl InputDiff {
             ],
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(INPUT_DIFF)?;
+        p.write_int(self.cid)?;
+        for &i in &self.diff {
+            p.write_int(i)?;
+        }
+        Ok(())
+    }
 }
 
 impl InputNew {
@@ -528,6 +729,14 @@ impl InputNew {
             ],
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(INPUT_NEW)?;
+        p.write_int(self.cid)?;
+        for &i in &self.new {
+            p.write_int(i)?;
+        }
+        Ok(())
+    }
 }
 
 impl<'a> Message<'a> {
@@ -537,6 +746,11 @@ impl<'a> Message<'a> {
             msg: _p.read_data(&mut Ignore)?,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(MESSAGE)?;
+        p.write_int(self.cid)?;
+        p.write_data(self.msg)
+    }
 }
 
 impl Join {
@@ -545,6 +759,10 @@ impl Join {
             cid: _p.read_int(&mut Ignore)?,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(JOIN)?;
+        p.write_int(self.cid)
+    }
 }
 
 impl<'a> Drop<'a> {
@@ -554,6 +772,11 @@ impl<'a> Drop<'a> {
             reason: _p.read_string()?,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(DROP)?;
+        p.write_int(self.cid)?;
+        p.write_string(self.reason)
+    }
 }
 
 impl<'a> ConsoleCommand<'a> {
@@ -574,6 +797,28 @@ impl<'a> ConsoleCommand<'a> {
             args: args,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(CONSOLE_COMMAND)?;
+        p.write_int(self.cid)?;
+        p.write_int(self.flag_mask as i32)?;
+        p.write_string(self.cmd)?;
+        p.write_int(self.args.len().assert_i32())?;
+        for arg in &self.args {
+            p.write_string(arg)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Antibot<'a> {
+    fn decode(_p: &mut Unpacker<'a>) -> Result<Antibot<'a>, MaybeEnd<Error>> {
+        Ok(Antibot {
+            data: _p.read_rest()?,
+        })
+    }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_rest(self.data)
+    }
 }
 
 impl<'a> AuthInit<'a> {
@@ -584,6 +829,11 @@ impl<'a> AuthInit<'a> {
             identity: _p.read_string()?,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.cid)?;
+        p.write_int(self.level)?;
+        p.write_string(self.identity)
+    }
 }
 
 impl<'a> AuthLogin<'a> {
@@ -594,6 +844,11 @@ impl<'a> AuthLogin<'a> {
             identity: _p.read_string()?,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.cid)?;
+        p.write_int(self.level)?;
+        p.write_string(self.identity)
+    }
 }
 
 impl AuthLogout {
@@ -602,6 +857,9 @@ impl AuthLogout {
             cid: _p.read_int(&mut Ignore)?,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.cid)
+    }
 }
 
 impl<'a> Ddnetver<'a> {
@@ -613,6 +871,12 @@ impl<'a> Ddnetver<'a> {
             ddnet_version_str: _p.read_string()?,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.cid)?;
+        p.write_uuid(self.connection_id)?;
+        p.write_int(self.ddnet_version)?;
+        p.write_string(self.ddnet_version_str)
+    }
 }
 
 impl DdnetverOld {
@@ -622,6 +886,10 @@ impl DdnetverOld {
             ddnet_version: _p.read_int(&mut Ignore)?,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.cid)?;
+        p.write_int(self.ddnet_version)
+    }
 }
 
 impl Joinver6 {
@@ -630,6 +898,9 @@ impl Joinver6 {
             cid: _p.read_int(&mut Ignore)?,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.cid)
+    }
 }
 
 impl Joinver7 {
@@ -638,6 +909,59 @@ impl Joinver7 {
             cid: _p.read_int(&mut Ignore)?,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.cid)
+    }
+}
+
+impl PlayerFinish {
+    fn decode(_p: &mut Unpacker) -> Result<PlayerFinish, MaybeEnd<Error>> {
+        Ok(PlayerFinish {
+            cid: _p.read_int(&mut Ignore)?,
+            time: _p.read_int(&mut Ignore)?,
+        })
+    }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.cid)?;
+        p.write_int(self.time)
+    }
+}
+
+impl<'a> PlayerName<'a> {
+    fn decode(_p: &mut Unpacker<'a>) -> Result<PlayerName<'a>, MaybeEnd<Error>> {
+        Ok(PlayerName {
+            cid: _p.read_int(&mut Ignore)?,
+            name: _p.read_string()?,
+        })
+    }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.cid)?;
+        p.write_string(self.name)
+    }
+}
+
+impl PlayerReady {
+    fn decode(_p: &mut Unpacker) -> Result<PlayerReady, MaybeEnd<Error>> {
+        Ok(PlayerReady {
+            cid: _p.read_int(&mut Ignore)?,
+        })
+    }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.cid)
+    }
+}
+
+impl PlayerSwap {
+    fn decode(_p: &mut Unpacker) -> Result<PlayerSwap, MaybeEnd<Error>> {
+        Ok(PlayerSwap {
+            cid1: _p.read_int(&mut Ignore)?,
+            cid2: _p.read_int(&mut Ignore)?,
+        })
+    }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.cid1)?;
+        p.write_int(self.cid2)
+    }
 }
 
 impl PlayerTeam {
@@ -647,6 +971,34 @@ impl PlayerTeam {
             team: _p.read_int(&mut Ignore)?,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.cid)?;
+        p.write_int(self.team)
+    }
+}
+
+impl Rejoinver6 {
+    fn decode(_p: &mut Unpacker) -> Result<Rejoinver6, MaybeEnd<Error>> {
+        Ok(Rejoinver6 {
+            cid: _p.read_int(&mut Ignore)?,
+        })
+    }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.cid)
+    }
+}
+
+impl TeamFinish {
+    fn decode(_p: &mut Unpacker) -> Result<TeamFinish, MaybeEnd<Error>> {
+        Ok(TeamFinish {
+            team: _p.read_int(&mut Ignore)?,
+            time: _p.read_int(&mut Ignore)?,
+        })
+    }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.team)?;
+        p.write_int(self.time)
+    }
 }
 
 impl TeamLoadFailure {
@@ -655,6 +1007,9 @@ impl TeamLoadFailure {
             team: _p.read_int(&mut Ignore)?,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.team)
+    }
 }
 
 impl<'a> TeamLoadSuccess<'a> {
@@ -665,6 +1020,11 @@ impl<'a> TeamLoadSuccess<'a> {
             save: _p.read_string()?,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.team)?;
+        p.write_uuid(self.save_uuid)?;
+        p.write_string(self.save)
+    }
 }
 
 impl TeamPractice {
@@ -674,6 +1034,10 @@ impl TeamPractice {
             practice: _p.read_int(&mut Ignore)?,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.team)?;
+        p.write_int(self.practice)
+    }
 }
 
 impl TeamSaveFailure {
@@ -682,6 +1046,9 @@ impl TeamSaveFailure {
             team: _p.read_int(&mut Ignore)?,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.team)
+    }
 }
 
 impl<'a> TeamSaveSuccess<'a> {
@@ -692,6 +1059,11 @@ impl<'a> TeamSaveSuccess<'a> {
             save: _p.read_string()?,
         })
     }
+    fn encode<W: io::Write>(&self, p: &mut IoPacker<W>) -> io::Result<()> {
+        p.write_int(self.team)?;
+        p.write_uuid(self.save_uuid)?;
+        p.write_string(self.save)
+    }
 }
 
 impl<'a> fmt::Debug for Item<'a> {
@@ -708,6 +1080,7 @@ impl<'a> fmt::Debug for Item<'a> {
             Item::Join(ref i) => i.fmt(f),
             Item::Drop(ref i) => i.fmt(f),
             Item::ConsoleCommand(ref i) => i.fmt(f),
+            Item::Antibot(ref i) => i.fmt(f),
             Item::AuthInit(ref i) => i.fmt(f),
             Item::AuthLogin(ref i) => i.fmt(f),
             Item::AuthLogout(ref i) => i.fmt(f),
@@ -715,7 +1088,13 @@ impl<'a> fmt::Debug for Item<'a> {
             Item::DdnetverOld(ref i) => i.fmt(f),
             Item::Joinver6(ref i) => i.fmt(f),
             Item::Joinver7(ref i) => i.fmt(f),
+            Item::PlayerFinish(ref i) => i.fmt(f),
+            Item::PlayerName(ref i) => i.fmt(f),
+            Item::PlayerReady(ref i) => i.fmt(f),
+            Item::PlayerSwap(ref i) => i.fmt(f),
             Item::PlayerTeam(ref i) => i.fmt(f),
+            Item::Rejoinver6(ref i) => i.fmt(f),
+            Item::TeamFinish(ref i) => i.fmt(f),
             Item::TeamLoadFailure(ref i) => i.fmt(f),
             Item::TeamLoadSuccess(ref i) => i.fmt(f),
             Item::TeamPractice(ref i) => i.fmt(f),
@@ -755,6 +1134,14 @@ impl<'a> fmt::Debug for ConsoleCommand<'a> {
     }
 }
 
+impl<'a> fmt::Debug for Antibot<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Antibot")
+            .field("data", &pretty::Bytes::new(self.data))
+            .finish()
+    }
+}
+
 impl<'a> fmt::Debug for AuthInit<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("AuthInit")
@@ -789,6 +1176,15 @@ impl<'a> fmt::Debug for Ddnetver<'a> {
     }
 }
 
+impl<'a> fmt::Debug for PlayerName<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PlayerName")
+            .field("cid", &self.cid)
+            .field("name", &pretty::AlmostString::new(self.name))
+            .finish()
+    }
+}
+
 impl<'a> fmt::Debug for TeamLoadSuccess<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("TeamLoadSuccess")
@@ -884,6 +1280,12 @@ impl<'a> From<ConsoleCommand<'a>> for Item<'a> {
     }
 }
 
+impl<'a> From<Antibot<'a>> for Item<'a> {
+    fn from(i: Antibot<'a>) -> Item<'a> {
+        Item::Antibot(i)
+    }
+}
+
 impl<'a> From<AuthInit<'a>> for Item<'a> {
     fn from(i: AuthInit<'a>) -> Item<'a> {
         Item::AuthInit(i)
@@ -926,12 +1328,48 @@ impl<'a> From<Joinver7> for Item<'a> {
     }
 }
 
+impl<'a> From<PlayerFinish> for Item<'a> {
+    fn from(i: PlayerFinish) -> Item<'a> {
+        Item::PlayerFinish(i)
+    }
+}
+
+impl<'a> From<PlayerName<'a>> for Item<'a> {
+    fn from(i: PlayerName<'a>) -> Item<'a> {
+        Item::PlayerName(i)
+    }
+}
+
+impl<'a> From<PlayerReady> for Item<'a> {
+    fn from(i: PlayerReady) -> Item<'a> {
+        Item::PlayerReady(i)
+    }
+}
+
+impl<'a> From<PlayerSwap> for Item<'a> {
+    fn from(i: PlayerSwap) -> Item<'a> {
+        Item::PlayerSwap(i)
+    }
+}
+
 impl<'a> From<PlayerTeam> for Item<'a> {
     fn from(i: PlayerTeam) -> Item<'a> {
         Item::PlayerTeam(i)
     }
 }
 
+impl<'a> From<Rejoinver6> for Item<'a> {
+    fn from(i: Rejoinver6) -> Item<'a> {
+        Item::Rejoinver6(i)
+    }
+}
+
+impl<'a> From<TeamFinish> for Item<'a> {
+    fn from(i: TeamFinish) -> Item<'a> {
+        Item::TeamFinish(i)
+    }
+}
+
 impl<'a> From<TeamLoadFailure> for Item<'a> {
     fn from(i: TeamLoadFailure) -> Item<'a> {
         Item::TeamLoadFailure(i)
@@ -1,11 +1,16 @@
 use chrono::DateTime;
 use chrono::FixedOffset;
 use common::digest::Sha256;
+use packer::IoPacker;
 use packer::UnexpectedEnd;
 use packer::Unpacker;
+use serde;
+use serde::Serialize;
 use serde_json;
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::io;
 use uuid::Uuid;
 
 pub use self::item::Item;
@@ -25,7 +30,7 @@ pub enum Version {
 }
 
 impl Version {
-    fn has_ex(self) -> bool {
+    pub(crate) fn has_ex(self) -> bool {
         self != Version::V1
     }
 }
@@ -97,7 +102,11 @@ pub fn read_magic(p: &mut Unpacker) -> Result<(), MaybeEnd<WrongMagic>> {
     Ok(())
 }
 
-#[derive(Debug, Deserialize)]
+pub fn write_magic<W: io::Write>(p: &mut IoPacker<W>) -> io::Result<()> {
+    p.write_raw(&UUID)
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 struct JsonHeader<'a> {
     version: Cow<'a, str>,
     game_uuid: Cow<'a, str>,
@@ -105,9 +114,20 @@ struct JsonHeader<'a> {
     server_port: Cow<'a, str>,
     map_name: Cow<'a, str>,
     map_size: Cow<'a, str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     map_sha256: Option<Sha256>,
     map_crc: Cow<'a, str>,
+    #[serde(serialize_with = "serialize_sorted")]
     config: HashMap<Cow<'a, str>, Cow<'a, str>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    uuids: Vec<Cow<'a, str>>,
+}
+
+fn serialize_sorted<S>(map: &HashMap<Cow<str>, Cow<str>>, s: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    map.iter().collect::<BTreeMap<_, _>>().serialize(s)
 }
 
 pub fn read_header<'a>(p: &mut Unpacker<'a>) -> Result<Header<'a>, MaybeEnd<HeaderError>> {
@@ -146,6 +166,36 @@ pub fn read_header<'a>(p: &mut Unpacker<'a>) -> Result<Header<'a>, MaybeEnd<Head
     Ok(header)
 }
 
+/// Writes the header as JSON, listing the extended items known to this
+/// crate in its `uuids` field.
+pub fn write_header<W: io::Write>(p: &mut IoPacker<W>, header: &Header) -> io::Result<()> {
+    let json_header = JsonHeader {
+        version: header.version.to_string().into(),
+        game_uuid: header.game_uuid.to_string().into(),
+        start_time: if header.version == 1 {
+            header.timestamp.format("%Y-%m-%d %H:%M:%S %z").to_string()
+        } else {
+            header.timestamp.to_rfc3339()
+        }
+        .into(),
+        server_port: header.server_port.to_string().into(),
+        map_name: Cow::Borrowed(&header.map_name),
+        map_size: header.map_size.to_string().into(),
+        map_sha256: header.map_sha256,
+        map_crc: format!("{:08x}", header.map_crc).into(),
+        config: header.config.clone(),
+        uuids: if header.version == 1 {
+            Vec::new()
+        } else {
+            item::EX_UUIDS
+                .iter()
+                .map(|&(name, _)| Cow::Borrowed(name))
+                .collect()
+        },
+    };
+    p.write_string(serde_json::to_string(&json_header)?.as_bytes())
+}
+
 impl From<HeaderError> for Error {
     fn from(e: HeaderError) -> Error {
         Error::Header(e)
@@ -198,5 +248,8 @@ mod test {
         assert_uuid(item::UUID_AUTH_INIT, "teehistorian-auth-init@ddnet.tw");
         assert_uuid(item::UUID_AUTH_LOGIN, "teehistorian-auth-login@ddnet.tw");
         assert_uuid(item::UUID_AUTH_LOGOUT, "teehistorian-auth-logout@ddnet.tw");
+        for &(name, uuid) in item::EX_UUIDS {
+            assert_uuid(uuid, name);
+        }
     }
 }
@@ -0,0 +1,247 @@
+use common::num::Cast;
+use common::time::Tick;
+use std::io;
+use std::io::SeekFrom;
+use vec_map::VecMap;
+
+use file::Buffer;
+use file::Cursor;
+use file::Error;
+use file::Item;
+use file::Reader;
+use format::item::INPUT_LEN;
+
+/// Index over a teehistorian file for querying tick ranges and players
+/// without reading the whole file again.
+///
+/// It contains a cursor about every `interval` ticks and, for every client,
+/// the ranges of ticks that contain its items. Gaps of up to `interval`
+/// ticks between two items of a client don't split its range.
+pub struct Index {
+    checkpoints: Vec<(Tick, Cursor)>,
+    players: VecMap<Vec<(Tick, Tick)>>,
+    ticks: Option<(Tick, Tick)>,
+}
+
+impl Index {
+    /// Default number of ticks between two checkpoints, 10 seconds.
+    pub const DEFAULT_INTERVAL: i32 = 500;
+
+    /// Builds the index from the remaining items of `reader`, which must be
+    /// positioned right after the header.
+    pub fn build<R: io::Read>(
+        reader: &mut Reader<R>,
+        buffer: &mut Buffer,
+        interval: i32,
+    ) -> Result<Index, Error> {
+        assert!(interval > 0);
+        let mut index = Index {
+            checkpoints: Vec::new(),
+            players: VecMap::new(),
+            ticks: None,
+        };
+        let mut next_checkpoint = i32::MIN;
+        let mut checkpoint = Some(reader.cursor(buffer));
+        let mut tick = Tick(0);
+        loop {
+            let (tick_end, cid) = match reader.read(buffer)? {
+                None => break,
+                Some(Item::TickStart(t)) => {
+                    tick = t;
+                    if let Some(c) = checkpoint.take() {
+                        index.checkpoints.push((t, c));
+                        next_checkpoint = t.to_i32().saturating_add(interval);
+                    }
+                    let first = index.ticks.map(|(f, _)| f).unwrap_or(t);
+                    index.ticks = Some((first, t));
+                    (false, None)
+                }
+                Some(Item::TickEnd(_)) => (true, None),
+                Some(item) => (false, item.cid()),
+            };
+            if let Some(cid) = cid.and_then(|c| c.try_usize()) {
+                let ranges = index.players.entry(cid).or_insert_with(Vec::new);
+                match ranges.last_mut() {
+                    Some(&mut (_, ref mut end)) if tick.wrapping_diff(*end) <= interval => {
+                        *end = tick
+                    }
+                    _ => ranges.push((tick, tick)),
+                }
+            }
+            // The next tick is at least one tick later, take the cursor now
+            // if it is due for a checkpoint.
+            if tick_end && tick.to_i32().saturating_add(1) >= next_checkpoint {
+                checkpoint = Some(reader.cursor(buffer));
+            }
+        }
+        Ok(index)
+    }
+    /// The first and the last tick of the file.
+    pub fn ticks(&self) -> Option<(Tick, Tick)> {
+        self.ticks
+    }
+    /// The ranges of ticks (both inclusive) containing items of the client,
+    /// sorted and non-overlapping.
+    pub fn player_ticks(&self, cid: i32) -> &[(Tick, Tick)] {
+        cid.try_usize()
+            .and_then(|cid| self.players.get(cid))
+            .map(|r| &r[..])
+            .unwrap_or(&[])
+    }
+    /// The last checkpoint at or before `tick`, the first one if there is
+    /// none.
+    pub fn checkpoint(&self, tick: Tick) -> Option<&Cursor> {
+        let i = match self
+            .checkpoints
+            .binary_search_by_key(&tick.to_i32(), |&(t, _)| t.to_i32())
+        {
+            Ok(i) => i,
+            Err(0) => 0,
+            Err(i) => i - 1,
+        };
+        self.checkpoints.get(i).map(|(_, c)| c)
+    }
+    /// Calls `f` with the tick and the item for all items in the ticks from
+    /// `start` to `end` (both inclusive).
+    ///
+    /// `file` must contain the data the index was built from.
+    pub fn items<R, F>(&self, file: R, start: Tick, end: Tick, mut f: F) -> Result<(), Error>
+    where
+        R: io::Read + io::Seek,
+        F: FnMut(Tick, &Item),
+    {
+        let cursor = match self.checkpoint(start) {
+            Some(c) => c,
+            None => return Ok(()),
+        };
+        let mut file = file;
+        file.seek(SeekFrom::Start(cursor.offset()))?;
+        let mut buffer = Buffer::new();
+        let mut reader = Reader::resume(file, cursor, &mut buffer);
+        let mut tick = None;
+        while let Some(item) = reader.read(&mut buffer)? {
+            if let Item::TickStart(t) = item {
+                if t.is_after(end) {
+                    break;
+                }
+                tick = Some(t);
+            }
+            if let Some(t) = tick {
+                if !start.is_after(t) {
+                    f(t, &item);
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Calls `f` for the items of a client in the ticks from `start` to
+    /// `end` (both inclusive), only reading the ticks that contain some.
+    pub fn player_items<R, F>(
+        &self,
+        mut file: R,
+        cid: i32,
+        start: Tick,
+        end: Tick,
+        mut f: F,
+    ) -> Result<(), Error>
+    where
+        R: io::Read + io::Seek,
+        F: FnMut(Tick, &Item),
+    {
+        for &(s, e) in self.player_ticks(cid) {
+            if start.is_after(e) || s.is_after(end) {
+                continue;
+            }
+            let s = if s.is_after(start) { s } else { start };
+            let e = if end.is_after(e) { e } else { end };
+            self.items(&mut file, s, e, |t, item| {
+                if item.cid() == Some(cid) {
+                    f(t, item);
+                }
+            })?;
+        }
+        Ok(())
+    }
+    /// All inputs of a client in the ticks from `start` to `end` (both
+    /// inclusive).
+    pub fn inputs<R>(
+        &self,
+        file: R,
+        cid: i32,
+        start: Tick,
+        end: Tick,
+    ) -> Result<Vec<(Tick, [i32; INPUT_LEN])>, Error>
+    where
+        R: io::Read + io::Seek,
+    {
+        let mut result = Vec::new();
+        self.player_items(file, cid, start, end, |t, item| {
+            if let Item::Input(ref i) = *item {
+                result.push((t, i.input));
+            }
+        })?;
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::DateTime;
+    use common::time::Tick;
+    use std::collections::HashMap;
+    use std::io;
+
+    use super::Index;
+    use file::Buffer;
+    use file::Item;
+    use file::Reader;
+    use format::Header;
+    use raw::Input;
+    use writer::Writer;
+
+    #[test]
+    fn inputs() {
+        let header = Header {
+            version: 2,
+            game_uuid: "97b2e4ec-a72e-4f2f-8ea3-0fae2dcb8a16".parse().unwrap(),
+            timestamp: DateTime::parse_from_rfc3339("2020-05-04T13:37:00+02:00").unwrap(),
+            server_port: 8303,
+            map_name: "Tutorial".into(),
+            map_size: 12345,
+            map_sha256: None,
+            map_crc: 0x0123abcd,
+            config: HashMap::new(),
+        };
+        let mut writer = Writer::new(Vec::new(), &header).unwrap();
+        for tick in 0..100 {
+            writer.write(&Item::TickStart(Tick(tick))).unwrap();
+            for cid in 0..4 {
+                if cid == 2 && (40..60).contains(&tick) || tick % (cid + 1) != 0 {
+                    continue;
+                }
+                let input = [tick, cid, 0, 0, 0, 0, 0, 0, 0, 0];
+                writer.write(&Item::Input(Input { cid, input })).unwrap();
+            }
+            writer.write(&Item::TickEnd(Tick(tick))).unwrap();
+        }
+        let data = writer.finish().unwrap();
+
+        let mut buffer = Buffer::new();
+        let (_, mut reader) = Reader::new(&data[..], &mut buffer).unwrap();
+        let index = Index::build(&mut reader, &mut buffer, 10).unwrap();
+        assert_eq!(index.ticks(), Some((Tick(0), Tick(99))));
+        assert_eq!(
+            index.player_ticks(2),
+            &[(Tick(0), Tick(39)), (Tick(60), Tick(99))]
+        );
+
+        let inputs = index
+            .inputs(io::Cursor::new(&data), 2, Tick(25), Tick(75))
+            .unwrap();
+        let expected: Vec<_> = (25..76)
+            .filter(|&t| !(40..60).contains(&t) && t % 3 == 0)
+            .map(|t| (Tick(t), [t, 2, 0, 0, 0, 0, 0, 0, 0, 0]))
+            .collect();
+        assert_eq!(inputs, expected);
+    }
+}
@@ -16,14 +16,23 @@ extern crate warn;
 mod bitmagic;
 mod file;
 pub mod format;
+mod index;
+mod playback;
 mod raw;
+mod writer;
 
 pub use file::Buffer;
+pub use file::Cursor;
 pub use file::Error;
 pub use file::Item;
 pub use file::Reader;
+pub use index::Index;
+pub use playback::Playback;
+pub use playback::PlayerState;
 pub use raw::Header;
 pub use raw::Input;
 pub use raw::Player;
 pub use raw::PlayerChange;
 pub use raw::Pos;
+pub use writer::WriteError;
+pub use writer::Writer;
@@ -0,0 +1,180 @@
+use common::num::Cast;
+use common::time::Tick;
+use std::io;
+use vec_map::VecMap;
+
+use file::Buffer;
+use file::Error;
+use file::Item;
+use file::Reader;
+use format::item::INPUT_LEN;
+use raw::Pos;
+
+/// State of a single client at the current tick.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PlayerState {
+    /// Whether the client has joined and not dropped yet.
+    pub connected: bool,
+    /// Position of the character, `None` if it isn't alive.
+    pub pos: Option<Pos>,
+    /// The last input of the client.
+    pub input: Option<[i32; INPUT_LEN]>,
+    /// The tick the last input was received in.
+    pub input_tick: Option<Tick>,
+}
+
+/// Accumulates the position and input changes of a teehistorian file into
+/// the world state at each tick.
+///
+/// Either feed all items to `process` or let `next_tick` read them from a
+/// `Reader`.
+#[derive(Default)]
+pub struct Playback {
+    tick: Option<Tick>,
+    in_tick: bool,
+    players: VecMap<PlayerState>,
+}
+
+impl Playback {
+    pub fn new() -> Playback {
+        Default::default()
+    }
+    /// Reads the items of the next tick, returns its number or `None` at the
+    /// end of the file.
+    pub fn next_tick<R: io::Read>(
+        &mut self,
+        reader: &mut Reader<R>,
+        buffer: &mut Buffer,
+    ) -> Result<Option<Tick>, Error> {
+        while let Some(item) = reader.read(buffer)? {
+            self.process(&item);
+            if let Item::TickEnd(t) = item {
+                return Ok(Some(t));
+            }
+        }
+        Ok(None)
+    }
+    /// Applies a single item to the state.
+    pub fn process(&mut self, item: &Item) {
+        match *item {
+            Item::TickStart(t) => {
+                self.tick = Some(t);
+                self.in_tick = true;
+            }
+            Item::TickEnd(_) => self.in_tick = false,
+            Item::PlayerNew(ref p) => {
+                if let Some(player) = self.player_mut(p.cid) {
+                    player.pos = Some(p.pos);
+                }
+            }
+            Item::PlayerChange(ref p) => {
+                if let Some(player) = self.player_mut(p.cid) {
+                    player.pos = Some(p.pos);
+                }
+            }
+            Item::PlayerOld(ref p) => {
+                if let Some(player) = self.player_mut(p.cid) {
+                    player.pos = None;
+                }
+            }
+            Item::Input(ref i) => {
+                let tick = self.tick;
+                if let Some(player) = self.player_mut(i.cid) {
+                    player.input = Some(i.input);
+                    player.input_tick = tick;
+                }
+            }
+            Item::Join(ref j) => {
+                if let Some(player) = self.player_mut(j.cid) {
+                    player.connected = true;
+                }
+            }
+            Item::Drop(ref d) => {
+                if let Some(player) = self.player_mut(d.cid) {
+                    player.connected = false;
+                    player.input = None;
+                    player.input_tick = None;
+                }
+            }
+            _ => {}
+        }
+    }
+    /// The last started tick.
+    pub fn tick(&self) -> Option<Tick> {
+        self.tick
+    }
+    /// Whether the last started tick has not ended yet.
+    pub fn in_tick(&self) -> bool {
+        self.in_tick
+    }
+    pub fn player(&self, cid: i32) -> Option<&PlayerState> {
+        cid.try_usize().and_then(|cid| self.players.get(cid))
+    }
+    pub fn player_pos(&self, cid: i32) -> Option<Pos> {
+        self.player(cid).and_then(|p| p.pos)
+    }
+    pub fn input(&self, cid: i32) -> Option<[i32; INPUT_LEN]> {
+        self.player(cid).and_then(|p| p.input)
+    }
+    /// Whether the client sent a new input in the current tick.
+    pub fn input_changed(&self, cid: i32) -> bool {
+        self.tick.is_some() && self.player(cid).and_then(|p| p.input_tick) == self.tick
+    }
+    /// All clients that were seen so far, sorted by client ID.
+    pub fn players(&self) -> impl Iterator<Item = (i32, &PlayerState)> {
+        self.players.iter().map(|(cid, p)| (cid.assert_i32(), p))
+    }
+    fn player_mut(&mut self, cid: i32) -> Option<&mut PlayerState> {
+        let cid = cid.try_usize()?;
+        Some(self.players.entry(cid).or_insert_with(Default::default))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Playback;
+    use common::time::Tick;
+    use format::item;
+    use raw::Input;
+    use raw::Item;
+    use raw::Player;
+    use raw::PlayerChange;
+    use raw::Pos;
+
+    #[test]
+    fn state() {
+        let pos0 = Pos { x: 10, y: 20 };
+        let pos1 = Pos { x: 15, y: 20 };
+        let input = [0, 0, 1, 0, 0, 0, 0, 0, 0, 0];
+        let mut playback = Playback::new();
+        for item in &[
+            Item::TickStart(Tick(0)),
+            Item::Join(item::Join { cid: 1 }),
+            Item::PlayerNew(Player { cid: 1, pos: pos0 }),
+            Item::Input(Input { cid: 1, input }),
+            Item::TickEnd(Tick(0)),
+            Item::TickStart(Tick(1)),
+            Item::PlayerChange(PlayerChange {
+                cid: 1,
+                pos: pos1,
+                old_pos: pos0,
+            }),
+        ] {
+            playback.process(item);
+        }
+        assert_eq!(playback.tick(), Some(Tick(1)));
+        assert!(playback.in_tick());
+        assert_eq!(playback.player_pos(1).map(|p| (p.x, p.y)), Some((15, 20)));
+        assert_eq!(playback.input(1), Some(input));
+        assert!(!playback.input_changed(1));
+        assert!(playback.player(1).unwrap().connected);
+        assert_eq!(
+            playback.players().map(|(cid, _)| cid).collect::<Vec<_>>(),
+            [1]
+        );
+
+        playback.process(&Item::PlayerOld(Player { cid: 1, pos: pos1 }));
+        assert!(playback.player_pos(1).is_none());
+        assert!(playback.player(0).is_none());
+    }
+}
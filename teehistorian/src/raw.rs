@@ -1,4 +1,5 @@
 use common::num::Cast;
+use common::time::Tick;
 use itertools::zip_eq;
 use packer::Unpacker;
 use std::cmp;
@@ -101,7 +102,13 @@ impl<T, CE> ResultExt for Result<T, CE> {
     }
 }
 
+/// Buffer for the data read from the callback.
+///
+/// Consumed data is discarded before reading more, so the buffer only grows
+/// beyond `BUFFER_SIZE` if a single item doesn't fit into it.
 pub struct Buffer {
+    /// Stream position of `buffer[0]`.
+    base: u64,
     offset: usize,
     buffer: Vec<u8>,
 }
@@ -109,14 +116,36 @@ pub struct Buffer {
 impl Buffer {
     pub fn new() -> Buffer {
         Buffer {
+            base: 0,
             offset: 0,
             buffer: Vec::new(),
         }
     }
     pub fn clear(&mut self) {
+        self.base = 0;
         self.offset = 0;
         self.buffer.clear();
     }
+    /// Number of bytes of the stream that have been consumed.
+    pub fn position(&self) -> u64 {
+        self.base + self.offset.u64()
+    }
+}
+
+/// Position in a teehistorian stream from which reading can be resumed.
+///
+/// Contains the byte offset and the state of the decoder at that offset.
+#[derive(Clone)]
+pub struct Cursor {
+    offset: u64,
+    reader: Reader,
+}
+
+impl Cursor {
+    /// Byte offset of the first unread byte in the stream.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
 }
 
 #[derive(Clone, Copy, Serialize)]
@@ -140,6 +169,7 @@ impl fmt::Debug for Pos {
     }
 }
 
+#[derive(Clone)]
 pub struct Reader {
     version: format::Version,
     tick: i32,
@@ -191,6 +221,20 @@ impl Reader {
             }
         }
     }
+    /// Returns the reader state for resuming at the current position.
+    pub fn cursor(&self, buffer: &Buffer) -> Cursor {
+        Cursor {
+            offset: buffer.position(),
+            reader: self.clone(),
+        }
+    }
+    /// Restores the reader state of `cursor`, the callback must continue
+    /// with the data at `cursor.offset()`.
+    pub fn resume(cursor: &Cursor, buffer: &mut Buffer) -> Reader {
+        buffer.clear();
+        buffer.base = cursor.offset;
+        cursor.reader.clone()
+    }
     pub fn from_header(header: &Header) -> Result<Reader, format::Error> {
         Ok(match header.version {
             1 => Reader::empty(format::Version::V1),
@@ -216,7 +260,7 @@ impl Reader {
         if item_kind != item::Kind::TickSkip && item_kind != item::Kind::Finish && !self.in_tick {
             self.next_item_kind = Some(item_kind);
             self.in_tick = true;
-            return Ok(Some(Item::TickStart(self.tick)));
+            return Ok(Some(Item::TickStart(Tick(self.tick))));
         }
 
         if let Some(cid) = item_kind.player_cid() {
@@ -226,12 +270,12 @@ impl Reader {
                 self.prev_player_cid = None;
                 self.next_item_kind = Some(item_kind);
                 self.in_tick = false;
-                return Ok(Some(Item::TickEnd(old_tick)));
+                return Ok(Some(Item::TickEnd(Tick(old_tick))));
             }
         } else if item_kind == item::Kind::Finish && self.in_tick {
             self.next_item_kind = Some(item_kind);
             self.in_tick = false;
-            return Ok(Some(Item::TickEnd(self.tick)));
+            return Ok(Some(Item::TickEnd(Tick(self.tick))));
         }
 
         let item = buffer.read_item(cb, item_kind)?;
@@ -250,18 +294,22 @@ impl Reader {
                     .ok_or(format::Error::TickOverflow)?
                     .checked_add(dt)
                     .ok_or(format::Error::TickOverflow)?;
+                // The tick skip starts a new tick explicitly, player IDs start
+                // over.
+                self.prev_player_cid = None;
                 if self.in_tick {
                     self.in_tick = false;
-                    Item::TickEnd(old_tick)
+                    Item::TickEnd(Tick(old_tick))
                 } else {
                     self.in_tick = true;
-                    Item::TickStart(self.tick)
+                    Item::TickStart(Tick(self.tick))
                 }
             }
             format::Item::Message(i) => Item::Message(i),
             format::Item::Join(i) => Item::Join(i),
             format::Item::Drop(i) => Item::Drop(i),
             format::Item::ConsoleCommand(i) => Item::ConsoleCommand(i),
+            format::Item::Antibot(i) => Item::Antibot(i),
             format::Item::AuthInit(i) => Item::AuthInit(i),
             format::Item::AuthLogin(i) => Item::AuthLogin(i),
             format::Item::AuthLogout(i) => Item::AuthLogout(i),
@@ -269,7 +317,13 @@ impl Reader {
             format::Item::DdnetverOld(i) => Item::DdnetverOld(i),
             format::Item::Joinver6(i) => Item::Joinver6(i),
             format::Item::Joinver7(i) => Item::Joinver7(i),
+            format::Item::PlayerFinish(i) => Item::PlayerFinish(i),
+            format::Item::PlayerName(i) => Item::PlayerName(i),
+            format::Item::PlayerReady(i) => Item::PlayerReady(i),
+            format::Item::PlayerSwap(i) => Item::PlayerSwap(i),
             format::Item::PlayerTeam(i) => Item::PlayerTeam(i),
+            format::Item::Rejoinver6(i) => Item::Rejoinver6(i),
+            format::Item::TeamFinish(i) => Item::TeamFinish(i),
             format::Item::TeamLoadFailure(i) => Item::TeamLoadFailure(i),
             format::Item::TeamLoadSuccess(i) => Item::TeamLoadSuccess(i),
             format::Item::TeamPractice(i) => Item::TeamPractice(i),
@@ -366,6 +420,7 @@ impl Buffer {
         } else {
             if self.offset != 0 {
                 self.buffer.drain(0..self.offset);
+                self.base += self.offset.u64();
                 self.offset = 0;
             } else {
                 let len = self.buffer.len();
@@ -450,8 +505,8 @@ pub struct Input {
 #[serde(tag = "type")]
 #[serde(rename_all = "snake_case")]
 pub enum Item<'a> {
-    TickStart(i32),
-    TickEnd(i32),
+    TickStart(Tick),
+    TickEnd(Tick),
     PlayerNew(Player),
     PlayerChange(PlayerChange),
     PlayerOld(Player),
@@ -460,6 +515,7 @@ pub enum Item<'a> {
     Join(item::Join),
     Drop(item::Drop<'a>),
     ConsoleCommand(item::ConsoleCommand<'a>),
+    Antibot(item::Antibot<'a>),
     AuthInit(item::AuthInit<'a>),
     AuthLogin(item::AuthLogin<'a>),
     AuthLogout(item::AuthLogout),
@@ -467,7 +523,13 @@ pub enum Item<'a> {
     DdnetverOld(item::DdnetverOld),
     Joinver6(item::Joinver6),
     Joinver7(item::Joinver7),
+    PlayerFinish(item::PlayerFinish),
+    PlayerName(item::PlayerName<'a>),
+    PlayerReady(item::PlayerReady),
+    PlayerSwap(item::PlayerSwap),
     PlayerTeam(item::PlayerTeam),
+    Rejoinver6(item::Rejoinver6),
+    TeamFinish(item::TeamFinish),
     TeamLoadFailure(item::TeamLoadFailure),
     TeamLoadSuccess(item::TeamLoadSuccess<'a>),
     TeamPractice(item::TeamPractice),
@@ -476,11 +538,49 @@ pub enum Item<'a> {
     UnknownEx(item::UnknownEx<'a>),
 }
 
+impl<'a> Item<'a> {
+    pub fn cid(&self) -> Option<i32> {
+        Some(match *self {
+            Item::TickStart(_) => return None,
+            Item::TickEnd(_) => return None,
+            Item::PlayerNew(ref i) => i.cid,
+            Item::PlayerChange(ref i) => i.cid,
+            Item::PlayerOld(ref i) => i.cid,
+            Item::Input(ref i) => i.cid,
+            Item::Message(ref i) => i.cid,
+            Item::Join(ref i) => i.cid,
+            Item::Drop(ref i) => i.cid,
+            Item::ConsoleCommand(ref i) => i.cid,
+            Item::Antibot(_) => return None,
+            Item::AuthInit(ref i) => i.cid,
+            Item::AuthLogin(ref i) => i.cid,
+            Item::AuthLogout(ref i) => i.cid,
+            Item::Ddnetver(ref i) => i.cid,
+            Item::DdnetverOld(ref i) => i.cid,
+            Item::Joinver6(ref i) => i.cid,
+            Item::Joinver7(ref i) => i.cid,
+            Item::PlayerFinish(ref i) => i.cid,
+            Item::PlayerName(ref i) => i.cid,
+            Item::PlayerReady(ref i) => i.cid,
+            Item::PlayerSwap(ref i) => i.cid1,
+            Item::PlayerTeam(ref i) => i.cid,
+            Item::Rejoinver6(ref i) => i.cid,
+            Item::TeamFinish(_) => return None,
+            Item::TeamLoadFailure(_) => return None,
+            Item::TeamLoadSuccess(_) => return None,
+            Item::TeamPractice(_) => return None,
+            Item::TeamSaveFailure(_) => return None,
+            Item::TeamSaveSuccess(_) => return None,
+            Item::UnknownEx(_) => return None,
+        })
+    }
+}
+
 impl<'a> fmt::Debug for Item<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Item::TickStart(ref i) => f.debug_tuple("TickStart").field(&i).finish(),
-            Item::TickEnd(ref i) => f.debug_tuple("TickEnd").field(&i).finish(),
+            Item::TickStart(ref i) => f.debug_tuple("TickStart").field(&i.0).finish(),
+            Item::TickEnd(ref i) => f.debug_tuple("TickEnd").field(&i.0).finish(),
             Item::PlayerNew(ref i) => f
                 .debug_struct("PlayerNew")
                 .field("cid", &i.cid)
@@ -502,6 +602,7 @@ impl<'a> fmt::Debug for Item<'a> {
             Item::Join(ref i) => i.fmt(f),
             Item::Drop(ref i) => i.fmt(f),
             Item::ConsoleCommand(ref i) => i.fmt(f),
+            Item::Antibot(ref i) => i.fmt(f),
             Item::AuthInit(ref i) => i.fmt(f),
             Item::AuthLogin(ref i) => i.fmt(f),
             Item::AuthLogout(ref i) => i.fmt(f),
@@ -509,7 +610,13 @@ impl<'a> fmt::Debug for Item<'a> {
             Item::DdnetverOld(ref i) => i.fmt(f),
             Item::Joinver6(ref i) => i.fmt(f),
             Item::Joinver7(ref i) => i.fmt(f),
+            Item::PlayerFinish(ref i) => i.fmt(f),
+            Item::PlayerName(ref i) => i.fmt(f),
+            Item::PlayerReady(ref i) => i.fmt(f),
+            Item::PlayerSwap(ref i) => i.fmt(f),
             Item::PlayerTeam(ref i) => i.fmt(f),
+            Item::Rejoinver6(ref i) => i.fmt(f),
+            Item::TeamFinish(ref i) => i.fmt(f),
             Item::TeamLoadFailure(ref i) => i.fmt(f),
             Item::TeamLoadSuccess(ref i) => i.fmt(f),
             Item::TeamPractice(ref i) => i.fmt(f),
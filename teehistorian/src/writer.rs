@@ -0,0 +1,413 @@
+use common::num::Cast;
+use packer::IoPacker;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use vec_map::VecMap;
+
+use format;
+use format::item;
+use format::item::INPUT_LEN;
+use format::Header;
+use raw::Item;
+use raw::Pos;
+
+#[derive(Debug)]
+pub enum WriteError {
+    Io(io::Error),
+    UnknownVersion,
+    /// Extended items can't be written to version 1 files.
+    ExUnsupported,
+    InvalidClientId,
+    /// An item other than `TickStart` outside of a tick.
+    ItemOutsideTick,
+    UnexpectedTickStart,
+    UnexpectedTickEnd,
+    TickNotIncreasing,
+    /// Player items must be sorted by client ID within a tick.
+    PlayerOrder,
+    PlayerNewDuplicate,
+    PlayerChangeWithoutNew,
+    PlayerOldWithoutNew,
+}
+
+impl From<io::Error> for WriteError {
+    fn from(err: io::Error) -> WriteError {
+        WriteError::Io(err)
+    }
+}
+
+/// Writes teehistorian files from the items returned by `Reader::read`.
+///
+/// Player positions and inputs are written as differences to the previously
+/// written ones, ticks are written implicitly where possible, like DDNet
+/// does. Reading the result back yields the same items, except for ticks
+/// without any items.
+pub struct Writer<W: io::Write> {
+    packer: IoPacker<W>,
+    version: format::Version,
+    /// The tick as seen by a reader of the written data.
+    tick: i32,
+    in_tick: bool,
+    prev_player_cid: Option<i32>,
+    /// The tick started by the last `TickStart` that hasn't ended yet.
+    current_tick: Option<i32>,
+    players: VecMap<Pos>,
+    inputs: VecMap<[i32; INPUT_LEN]>,
+}
+
+impl Writer<File> {
+    pub fn create<P: AsRef<Path>>(path: P, header: &Header) -> Result<Writer<File>, WriteError> {
+        fn inner(path: &Path, header: &Header) -> Result<Writer<File>, WriteError> {
+            Writer::new(File::create(path)?, header)
+        }
+        inner(path.as_ref(), header)
+    }
+}
+
+impl<W: io::Write> Writer<W> {
+    pub fn new(file: W, header: &Header) -> Result<Writer<W>, WriteError> {
+        let version = match header.version {
+            1 => format::Version::V1,
+            2 => format::Version::V2,
+            _ => return Err(WriteError::UnknownVersion),
+        };
+        let mut packer = IoPacker::new(file);
+        format::write_magic(&mut packer)?;
+        format::write_header(&mut packer, header)?;
+        Ok(Writer {
+            packer,
+            version,
+            tick: 0,
+            in_tick: false,
+            prev_player_cid: None,
+            current_tick: None,
+            players: VecMap::new(),
+            inputs: VecMap::new(),
+        })
+    }
+    pub fn write(&mut self, item: &Item) -> Result<(), WriteError> {
+        let tick = match *item {
+            Item::TickStart(t) => {
+                if self.current_tick.is_some() {
+                    return Err(WriteError::UnexpectedTickStart);
+                }
+                self.current_tick = Some(t.to_i32());
+                return Ok(());
+            }
+            Item::TickEnd(t) => {
+                if self.current_tick != Some(t.to_i32()) {
+                    return Err(WriteError::UnexpectedTickEnd);
+                }
+                self.current_tick = None;
+                return Ok(());
+            }
+            _ => self.current_tick.ok_or(WriteError::ItemOutsideTick)?,
+        };
+        let format_item: item::Item = match *item {
+            Item::TickStart(_) | Item::TickEnd(_) => unreachable!(),
+            Item::PlayerNew(ref p) => {
+                self.begin_player(tick, p.cid)?;
+                if self.players.insert(cid_usize(p.cid)?, p.pos).is_some() {
+                    return Err(WriteError::PlayerNewDuplicate);
+                }
+                item::PlayerNew {
+                    cid: p.cid,
+                    x: p.pos.x,
+                    y: p.pos.y,
+                }
+                .into()
+            }
+            Item::PlayerChange(ref p) => {
+                self.begin_player(tick, p.cid)?;
+                let player = self
+                    .players
+                    .get_mut(cid_usize(p.cid)?)
+                    .ok_or(WriteError::PlayerChangeWithoutNew)?;
+                let old_pos = *player;
+                *player = p.pos;
+                item::PlayerDiff {
+                    cid: p.cid,
+                    dx: p.pos.x.wrapping_sub(old_pos.x),
+                    dy: p.pos.y.wrapping_sub(old_pos.y),
+                }
+                .into()
+            }
+            Item::PlayerOld(ref p) => {
+                self.begin_player(tick, p.cid)?;
+                if self.players.remove(cid_usize(p.cid)?).is_none() {
+                    return Err(WriteError::PlayerOldWithoutNew);
+                }
+                item::PlayerOld { cid: p.cid }.into()
+            }
+            Item::Input(ref i) => {
+                self.begin(tick)?;
+                let cid = cid_usize(i.cid)?;
+                let result = if let Some(old) = self.inputs.get(cid) {
+                    let mut diff = [0; INPUT_LEN];
+                    for (d, (&new, &old)) in diff.iter_mut().zip(i.input.iter().zip(old.iter())) {
+                        *d = new.wrapping_sub(old);
+                    }
+                    item::InputDiff { cid: i.cid, diff }.into()
+                } else {
+                    item::InputNew {
+                        cid: i.cid,
+                        new: i.input,
+                    }
+                    .into()
+                };
+                self.inputs.insert(cid, i.input);
+                result
+            }
+            Item::Message(ref i) => i.clone().into(),
+            Item::Join(ref i) => i.clone().into(),
+            Item::Drop(ref i) => i.clone().into(),
+            Item::ConsoleCommand(ref i) => i.clone().into(),
+            Item::Antibot(ref i) => i.clone().into(),
+            Item::AuthInit(ref i) => i.clone().into(),
+            Item::AuthLogin(ref i) => i.clone().into(),
+            Item::AuthLogout(ref i) => i.clone().into(),
+            Item::Ddnetver(ref i) => i.clone().into(),
+            Item::DdnetverOld(ref i) => i.clone().into(),
+            Item::Joinver6(ref i) => i.clone().into(),
+            Item::Joinver7(ref i) => i.clone().into(),
+            Item::PlayerFinish(ref i) => i.clone().into(),
+            Item::PlayerName(ref i) => i.clone().into(),
+            Item::PlayerReady(ref i) => i.clone().into(),
+            Item::PlayerSwap(ref i) => i.clone().into(),
+            Item::PlayerTeam(ref i) => i.clone().into(),
+            Item::Rejoinver6(ref i) => i.clone().into(),
+            Item::TeamFinish(ref i) => i.clone().into(),
+            Item::TeamLoadFailure(ref i) => i.clone().into(),
+            Item::TeamLoadSuccess(ref i) => i.clone().into(),
+            Item::TeamPractice(ref i) => i.clone().into(),
+            Item::TeamSaveFailure(ref i) => i.clone().into(),
+            Item::TeamSaveSuccess(ref i) => i.clone().into(),
+            Item::UnknownEx(ref i) => i.clone().into(),
+        };
+        match format_item {
+            item::Item::PlayerNew(_)
+            | item::Item::PlayerDiff(_)
+            | item::Item::PlayerOld(_)
+            | item::Item::InputNew(_)
+            | item::Item::InputDiff(_) => {}
+            item::Item::Message(_)
+            | item::Item::Join(_)
+            | item::Item::Drop(_)
+            | item::Item::ConsoleCommand(_) => self.begin(tick)?,
+            _ => {
+                if !self.version.has_ex() {
+                    return Err(WriteError::ExUnsupported);
+                }
+                self.begin(tick)?
+            }
+        }
+        format_item.encode(&mut self.packer)?;
+        Ok(())
+    }
+    /// Writes the end marker and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W, WriteError> {
+        item::Item::from(item::Finish).encode(&mut self.packer)?;
+        Ok(self.packer.finish()?)
+    }
+    fn begin_player(&mut self, tick: i32, cid: i32) -> Result<(), WriteError> {
+        if self.in_tick && self.tick == tick {
+            if self.prev_player_cid.map(|p| p >= cid).unwrap_or(false) {
+                return Err(WriteError::PlayerOrder);
+            }
+        } else if self.in_tick
+            && self.tick.checked_add(1) == Some(tick)
+            && self.prev_player_cid.map(|p| p >= cid).unwrap_or(false)
+        {
+            // The reader detects the new tick from the client ID going down.
+            self.tick = tick;
+        } else {
+            self.begin(tick)?;
+        }
+        self.prev_player_cid = Some(cid);
+        Ok(())
+    }
+    fn begin(&mut self, tick: i32) -> Result<(), WriteError> {
+        if self.tick == tick {
+            // Only not in a tick at the start of the file, where the reader
+            // starts at tick 0 without a tick skip.
+            self.in_tick = true;
+            return Ok(());
+        }
+        if tick <= self.tick {
+            return Err(WriteError::TickNotIncreasing);
+        }
+        let dt = (tick - self.tick - 1).assert_u32();
+        item::Item::from(item::TickSkip { dt }).encode(&mut self.packer)?;
+        self.tick = tick;
+        self.in_tick = true;
+        self.prev_player_cid = None;
+        Ok(())
+    }
+}
+
+fn cid_usize(cid: i32) -> Result<usize, WriteError> {
+    cid.try_usize().ok_or(WriteError::InvalidClientId)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::DateTime;
+    use packer::IoPacker;
+    use raw;
+    use raw::Buffer;
+    use raw::Callback;
+    use std::cmp;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    use super::Writer;
+    use format;
+    use format::item;
+    use format::Header;
+
+    struct Slice<'a>(&'a [u8]);
+
+    impl<'a> Callback for Slice<'a> {
+        type Error = ();
+        fn read_at_most(&mut self, buffer: &mut [u8]) -> Result<Option<usize>, ()> {
+            if self.0.is_empty() {
+                return Ok(None);
+            }
+            let len = cmp::min(buffer.len(), self.0.len());
+            buffer[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(Some(len))
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut config = HashMap::new();
+        config.insert("sv_name".into(), "test server".into());
+        let header = Header {
+            version: 2,
+            game_uuid: "97b2e4ec-a72e-4f2f-8ea3-0fae2dcb8a16".parse().unwrap(),
+            timestamp: DateTime::parse_from_rfc3339("2020-05-04T13:37:00+02:00").unwrap(),
+            server_port: 8303,
+            map_name: "Tutorial".into(),
+            map_size: 12345,
+            map_sha256: None,
+            map_crc: 0x0123abcd,
+            config,
+        };
+
+        // Items as written by DDNet, including implicit ticks and tick skips
+        // after ticks without players.
+        let items: Vec<item::Item> = vec![
+            item::TickSkip { dt: 4 }.into(),
+            item::PlayerNew {
+                cid: 0,
+                x: 100,
+                y: 200,
+            }
+            .into(),
+            item::PlayerNew {
+                cid: 3,
+                x: -5,
+                y: 7,
+            }
+            .into(),
+            item::InputNew {
+                cid: 0,
+                new: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+            }
+            .into(),
+            item::Join { cid: 3 }.into(),
+            item::Ddnetver {
+                cid: 3,
+                connection_id: Uuid::nil(),
+                ddnet_version: 15000,
+                ddnet_version_str: b"DDNet 15.0",
+            }
+            .into(),
+            item::Message {
+                cid: 0,
+                msg: b"\x01\x02\x03",
+            }
+            .into(),
+            item::PlayerDiff {
+                cid: 0,
+                dx: 1,
+                dy: -1,
+            }
+            .into(),
+            item::PlayerDiff {
+                cid: 3,
+                dx: 0,
+                dy: 32,
+            }
+            .into(),
+            item::InputDiff {
+                cid: 0,
+                diff: [0, 0, 1, 0, 0, 0, 0, 0, 0, -10],
+            }
+            .into(),
+            item::TickSkip { dt: 0 }.into(),
+            item::ConsoleCommand {
+                cid: -1,
+                flag_mask: 1,
+                cmd: b"say",
+                args: [&b"hello"[..], &b"world"[..]].iter().cloned().collect(),
+            }
+            .into(),
+            item::TickSkip { dt: 2 }.into(),
+            item::PlayerDiff {
+                cid: 0,
+                dx: 5,
+                dy: 0,
+            }
+            .into(),
+            item::PlayerOld { cid: 3 }.into(),
+            item::Drop {
+                cid: 3,
+                reason: b"timeout",
+            }
+            .into(),
+            item::PlayerSwap { cid1: 0, cid2: 1 }.into(),
+            item::PlayerName {
+                cid: 0,
+                name: b"nameless tee",
+            }
+            .into(),
+            item::Antibot { data: b"\x12\x34" }.into(),
+            item::UnknownEx {
+                uuid: Uuid::nil(),
+                data: b"\x00\x01",
+            }
+            .into(),
+            item::Finish.into(),
+        ];
+        let mut packer = IoPacker::new(Vec::new());
+        format::write_magic(&mut packer).unwrap();
+        format::write_header(&mut packer, &header).unwrap();
+        for item in &items {
+            item.encode(&mut packer).unwrap();
+        }
+        let original = packer.finish().unwrap();
+
+        let mut slice = Slice(&original);
+        let mut buffer = Buffer::new();
+        let mut writer = {
+            let (header, _) = raw::Reader::new(&mut slice, &mut buffer).unwrap();
+            assert_eq!(header.map_name, "Tutorial");
+            assert_eq!(header.map_crc, 0x0123abcd);
+            Writer::new(Vec::new(), &header).unwrap()
+        };
+        let mut reader = raw::Reader::from_header(&header).unwrap();
+        let mut ticks = Vec::new();
+        while let Some(item) = reader.read(&mut slice, &mut buffer).unwrap() {
+            if let raw::Item::TickStart(t) = item {
+                ticks.push(t.to_i32());
+            }
+            writer.write(&item).unwrap();
+        }
+        assert_eq!(ticks, [5, 6, 7, 10]);
+        assert_eq!(writer.finish().unwrap(), original);
+    }
+}
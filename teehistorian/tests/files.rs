@@ -0,0 +1,68 @@
+extern crate serde_json;
+extern crate teehistorian;
+
+use std::fs;
+use std::path::Path;
+use teehistorian::Buffer;
+use teehistorian::Item;
+use teehistorian::Reader;
+use teehistorian::Writer;
+
+/// Returns the non-tick items together with the tick they belong to.
+///
+/// The writer doesn't write ticks without items, so only the ticks of the
+/// other items are compared.
+fn items(data: &[u8]) -> Vec<(i32, String)> {
+    let mut buffer = Buffer::new();
+    let (_, mut reader) = Reader::new(data, &mut buffer).unwrap();
+    let mut tick = None;
+    let mut result = Vec::new();
+    while let Some(item) = reader.read(&mut buffer).unwrap() {
+        match item {
+            Item::TickStart(t) => tick = Some(t),
+            Item::TickEnd(_) => tick = None,
+            _ => result.push((
+                tick.unwrap().to_i32(),
+                serde_json::to_string(&item).unwrap(),
+            )),
+        }
+    }
+    result
+}
+
+/// Returns the data following the magic and the header.
+fn body(data: &[u8]) -> &[u8] {
+    let header_end = 16 + data[16..].iter().position(|&b| b == 0).unwrap();
+    &data[header_end + 1..]
+}
+
+fn roundtrip(data: &[u8]) {
+    let mut buffer = Buffer::new();
+    let (header, mut reader) = Reader::new(data, &mut buffer).unwrap();
+    let mut writer = Writer::new(Vec::new(), &header).unwrap();
+    while let Some(item) = reader.read(&mut buffer).unwrap() {
+        writer.write(&item).unwrap();
+    }
+    let written = writer.finish().unwrap();
+
+    assert_eq!(items(&written), items(data));
+    // Player positions, inputs and ticks are encoded like DDNet does.
+    assert_eq!(body(&written), body(data));
+}
+
+/// Round trips the teehistorian files recorded by DDNet in `tests/files`, if
+/// the directory exists.
+#[test]
+fn ddnet_files() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/files");
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries {
+        let path = entry.unwrap().path();
+        if path.extension() == Some("teehistorian".as_ref()) {
+            roundtrip(&fs::read(path).unwrap());
+        }
+    }
+}
@@ -8,30 +8,28 @@ use std::path::Path;
 use std::process;
 
 fn tele_tile_name(index: u8) -> Option<&'static str> {
+    use map::ddnet::*;
     Some(match index {
-        10 => "TELEINEVIL",
-        14 => "TELEINWEAPON",
-        15 => "TELEINHOOK",
-        26 => "TELEIN",
-        27 => "TELEOUT",
-        29 => "TELECHECK",
-        30 => "TELECHECKOUT",
-        31 => "TELECHECKIN",
-        63 => "TELECHECKINEVIL",
+        TILE_TELEINEVIL => "TELEINEVIL",
+        TILE_TELEINWEAPON => "TELEINWEAPON",
+        TILE_TELEINHOOK => "TELEINHOOK",
+        TILE_TELEIN => "TELEIN",
+        TILE_TELEOUT => "TELEOUT",
+        TILE_TELECHECK => "TELECHECK",
+        TILE_TELECHECKOUT => "TELECHECKOUT",
+        TILE_TELECHECKIN => "TELECHECKIN",
+        TILE_TELECHECKINEVIL => "TELECHECKINEVIL",
         _ => return None,
     })
 }
 
 fn process(path: &Path) -> Result<(), Error> {
     let mut map = map::Reader::open(path)?;
-    let game_layers = map.game_layers()?;
-
-    let tele = if let Some(t) = game_layers.teleport() {
+    let tele_tiles = if let Some(t) = map.game_layer_tiles()?.teleport {
         t
     } else {
         return Ok(());
     };
-    let tele_tiles = map.tele_layer_tiles(tele)?;
     for ((y, x), &t) in tele_tiles.indexed_iter() {
         if t.index != 0 && t.number == 0 {
             if let Some(name) = tele_tile_name(t.index) {
@@ -140,7 +140,7 @@ fn process<W: Warn<Warning>>(warn: &mut W, path: &Path) -> Result<(), Error> {
             demo::RawChunk::Tick { tick, .. } => println!("tick={}", tick),
             demo::RawChunk::Snapshot(_) => println!("snapshot"),
             demo::RawChunk::SnapshotDelta(_) => println!("snapshot_delta"),
-            demo::RawChunk::Unknown => println!("Unknown chunk"),
+            demo::RawChunk::Unknown(_) => println!("Unknown chunk"),
         }
     }
     println!();
@@ -1,6 +1,6 @@
 extern crate demo;
 
-use demo::{Reader, Writer};
+use demo::{DemoHeader, Reader, Writer};
 use std::env;
 use std::error::Error;
 use std::fs;
@@ -32,19 +32,29 @@ fn read_write(input: &str, output: &str) -> Result<(), Box<dyn Error>> {
     let input_file = fs::File::open(input)?;
     let output_file = fs::File::create(output)?;
     let mut reader = Reader::new(input_file, &mut warn::Ignore)?;
-    let mut writer = Writer::new(
-        output_file,
-        reader.net_version(),
-        reader.map_name(),
-        reader.map_sha256(),
-        reader.map_crc(),
-        reader.kind(),
-        reader.length(),
-        reader.timestamp(),
-        reader.map_data(),
-    )?;
+    let header = DemoHeader {
+        version: reader.version(),
+        net_version: reader.net_version(),
+        map_name: reader.map_name(),
+        map_sha256: reader.map_sha256(),
+        test_demo: reader.is_test_demo(),
+        map_crc: reader.map_crc(),
+        kind: reader.kind(),
+        length: reader.length(),
+        timestamp: reader.timestamp(),
+    };
+    let mut writer = Writer::with_header(output_file, &header, reader.map_data())?;
+    let markers = reader.timeline_markers().to_vec();
     while let Some(chunk) = reader.read_chunk(&mut warn::Ignore)? {
+        let marker = match chunk {
+            demo::RawChunk::Tick { tick, .. } => markers.contains(&tick.to_i32()),
+            _ => false,
+        };
         writer.write_chunk(chunk)?;
+        if marker {
+            writer.add_timeline_marker();
+        }
     }
+    writer.finalize()?;
     Ok(())
 }
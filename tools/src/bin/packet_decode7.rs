@@ -0,0 +1,27 @@
+extern crate arrayvec;
+extern crate buffer;
+extern crate tools;
+
+use arrayvec::ArrayVec;
+use buffer::ReadBuffer;
+use std::io;
+use tools::packet_decode;
+use tools::packet_decode::Teeworlds07;
+use tools::unhexdump::Unhexdump;
+
+fn main() {
+    let mut un = Unhexdump::new();
+    let mut buf: ArrayVec<[u8; 4096]> = ArrayVec::new();
+    let stdin = io::stdin();
+    let mut stdin = stdin.lock();
+
+    while {
+        buf.clear();
+        stdin.read_buffer(&mut buf).unwrap().len() != 0
+    } {
+        un.feed(&buf).unwrap();
+    }
+
+    let bytes = un.into_inner().unwrap();
+    print!("{}", packet_decode::decode::<Teeworlds07>(&bytes));
+}
@@ -6,6 +6,7 @@ extern crate serverbrowse;
 extern crate tools;
 
 use serverbrowse::protocol as browse_protocol;
+use serverbrowse::protocol::AddrPackedExt;
 use serverbrowse::protocol::List6Response;
 use serverbrowse::protocol::Response;
 
@@ -13,12 +13,15 @@ extern crate warn;
 extern crate world;
 
 use arrayvec::ArrayVec;
+use common::digest;
 use common::num::Cast;
+use common::time::Tick;
 use demo::Writer;
-use gamenet_ddnet::enums::Emote;
-use gamenet_ddnet::enums::Team;
-use gamenet_ddnet::enums::Weapon;
+use gamenet_ddnet::enums::EMOTE_NORMAL;
+use gamenet_ddnet::enums::SPEC_FREEVIEW;
+use gamenet_ddnet::enums::TEAM_RED;
 use gamenet_ddnet::enums::VERSION;
+use gamenet_ddnet::enums::WEAPON_HAMMER;
 use gamenet_ddnet::msg::game as game_ddnet;
 use gamenet_ddnet::msg::Game as GameDdnet;
 use gamenet_ddnet::snap_obj;
@@ -39,6 +42,7 @@ use std::path::Path;
 use std::process;
 use teehistorian::Buffer;
 use teehistorian::Item;
+use teehistorian::Playback;
 use teehistorian::Pos;
 use teehistorian::Reader;
 use vec_map::VecMap;
@@ -47,6 +51,7 @@ use world::vec2;
 
 const TICKS_PER_SECOND: i32 = 50;
 
+#[derive(Default)]
 struct Info {
     name: ArrayVec<[u8; 4 * 4 - 1]>,
     clan: ArrayVec<[u8; 3 * 4 - 1]>,
@@ -99,7 +104,7 @@ impl<'a> From<game7::ClStartInfo<'a>> for Info {
     }
 }
 
-fn process(in_: &Path, out: &Path) -> Result<(), String> {
+fn process(in_: &Path, out: &Path, map: Option<&Path>) -> Result<(), String> {
     let mut buffer = Buffer::new();
     let mut snap_buffer = Vec::new();
     let mut th;
@@ -108,34 +113,34 @@ fn process(in_: &Path, out: &Path) -> Result<(), String> {
         let (header, teehistorian) =
             Reader::open(in_, &mut buffer).map_err(|err| format!("{:?}", err))?;
         th = teehistorian;
-        let file = fs::File::create(out).map_err(|err| err.to_string())?;
-        if let Some(map_sha256) = header.map_sha256 {
-            demo = Writer::new(
-                file,
-                VERSION.as_bytes(),
-                header.map_name.as_bytes(),
-                Some(map_sha256),
-                header.map_crc,
-                demo::DemoKind::Server,
-                0,   // Length
-                b"", // Timestamp
-                &[], // Map data
-            )
-            .map_err(|err| err.to_string())?;
-        } else {
-            demo = Writer::new(
-                file,
-                VERSION.as_bytes(),
-                header.map_name.as_bytes(),
-                None,
-                header.map_crc,
-                demo::DemoKind::Server,
-                0,   // Length
-                b"", // Timestamp
-                &[], // Map data
-            )
-            .map_err(|err| err.to_string())?;
+        let map_data = match map {
+            Some(m) => fs::read(m).map_err(|err| err.to_string())?,
+            None => Vec::new(),
+        };
+        if map.is_some() {
+            if digest::crc32(&map_data) != header.map_crc {
+                return Err("map CRC mismatch".into());
+            }
+            if let Some(map_sha256) = header.map_sha256 {
+                if digest::sha256(&map_data).0 != map_sha256.0 {
+                    return Err("map SHA256 mismatch".into());
+                }
+            }
         }
+        let file = fs::File::create(out).map_err(|err| err.to_string())?;
+        let timestamp = header.timestamp.format("%Y-%m-%d_%H-%M-%S").to_string();
+        demo = Writer::new(
+            file,
+            VERSION.as_bytes(),
+            header.map_name.as_bytes(),
+            header.map_sha256,
+            header.map_crc,
+            demo::DemoKind::Server,
+            0, // Length, filled in by `finalize`.
+            timestamp.as_bytes(),
+            &map_data,
+        )
+        .map_err(|err| err.to_string())?;
     }
     let mut delta = snap::Delta::new();
     let mut last_full_snap_tick = None;
@@ -144,26 +149,22 @@ fn process(in_: &Path, out: &Path) -> Result<(), String> {
     let mut last_tick = 0;
     let mut ver7: VecMap<bool> = VecMap::new();
     let mut supplied_infos: VecMap<Info> = VecMap::new();
-    let mut inputs: VecMap<PlayerInput> = VecMap::new();
+    let mut playback = Playback::new();
     let mut prev_pos: VecMap<Pos> = VecMap::new();
+    let mut spectator_pos = Pos { x: 0, y: 0 };
     let mut encoded: Vec<u8> = Vec::with_capacity(MAX_SNAPSHOT_SIZE);
     while let Some(item) = th.read(&mut buffer).map_err(|err| format!("{:?}", err))? {
         let mut do_ticks = 0..0;
+        playback.process(&item);
         match item {
             Item::TickStart(tick) => {
-                do_ticks = last_tick + 1..tick;
+                do_ticks = last_tick + 1..tick.to_i32();
             }
             Item::TickEnd(tick) => {
+                let tick = tick.to_i32();
                 last_tick = tick;
                 do_ticks = tick..tick + 1;
             }
-            Item::Input(input) => {
-                if let Ok(pi) =
-                    PlayerInput::decode(&mut Ignore, &mut IntUnpacker::new(&input.input))
-                {
-                    inputs.insert(input.cid.assert_usize(), pi);
-                }
-            }
             Item::Joinver6(jv) => {
                 ver7.insert(jv.cid.assert_usize(), false);
             }
@@ -198,87 +199,116 @@ fn process(in_: &Path, out: &Path) -> Result<(), String> {
             _ => {}
         }
         for tick in do_ticks {
-            for cid in th.cids() {
-                let maybe_pos = th.player_pos(cid);
-                if let Some(pos) = maybe_pos {
-                    let ppos = prev_pos.get(cid.assert_usize()).cloned().unwrap_or(pos);
-                    let default = PlayerInput::default();
-                    let input = inputs.get(cid.assert_usize()).unwrap_or(&default);
-                    let info = &supplied_infos[cid.assert_usize()];
-                    let name: &[u8] = if !info.name.is_empty() {
-                        &info.name
-                    } else {
-                        // Theoretically we have to track all the names. We
-                        // don't do that, so just pretend we care and do the
-                        // common case.
-                        b"(1)"
-                    };
-                    let client_info = snap_obj::ClientInfo {
-                        name: string_to_ints4(name),
-                        clan: string_to_ints3(&info.clan),
-                        country: info.country,
-                        skin: string_to_ints6(&info.skin),
-                        use_custom_color: info.use_custom_color as i32,
-                        color_body: info.color_body,
-                        color_feet: info.color_feet,
-                    };
-                    let player_info = snap_obj::PlayerInfo {
-                        local: 0,
-                        client_id: cid,
-                        team: Team::Red,
-                        score: -9999,
-                        latency: 0,
-                    };
-                    let target = vec2::new(input.target_x as f32, input.target_y as f32);
-                    let character = snap_obj::Character {
-                        character_core: snap_obj::CharacterCore {
-                            tick: tick,
-                            x: pos.x,
-                            y: pos.y,
-                            vel_x: pos.x - ppos.x,
-                            vel_y: pos.y - ppos.y,
-                            angle: target.angle().to_net(),
-                            direction: input.direction,
-                            jumped: (input.jump != 0) as i32,
-                            hooked_player: 0,
-                            hook_state: -1,
-                            hook_tick: snap_obj::Tick(0),
-                            hook_x: 0,
-                            hook_y: 0,
-                            hook_dx: 0,
-                            hook_dy: 0,
-                        },
-                        player_flags: snap_obj::PLAYERFLAG_PLAYING,
-                        health: 10,
-                        armor: 10,
-                        ammo_count: 0,
-                        weapon: Weapon::Hammer,
-                        emote: Emote::Normal,
-                        attack_tick: 0,
-                    };
-                    builder
-                        .add_item(
-                            snap_obj::CLIENT_INFO,
-                            cid.assert_u16(),
-                            client_info.encode(),
-                        )
-                        .unwrap();
-                    builder
-                        .add_item(
-                            snap_obj::PLAYER_INFO,
-                            cid.assert_u16(),
-                            player_info.encode(),
-                        )
-                        .unwrap();
-                    builder
-                        .add_item(snap_obj::CHARACTER, cid.assert_u16(), character.encode())
-                        .unwrap();
-
-                    prev_pos.insert(cid.assert_usize(), pos);
-                } else {
-                    prev_pos.remove(cid.assert_usize());
+            // Follow the alive player with the lowest client ID.
+            let mut spectator_id = SPEC_FREEVIEW;
+            for (cid, player) in playback.players() {
+                let pos = match player.pos {
+                    Some(pos) => pos,
+                    None => {
+                        prev_pos.remove(cid.assert_usize());
+                        continue;
+                    }
+                };
+                if spectator_id == SPEC_FREEVIEW {
+                    spectator_id = cid;
+                    spectator_pos = pos;
                 }
+                let ppos = prev_pos.get(cid.assert_usize()).cloned().unwrap_or(pos);
+                let input = player
+                    .input
+                    .and_then(|i| PlayerInput::decode(&mut Ignore, &mut IntUnpacker::new(&i)).ok())
+                    .unwrap_or_default();
+                let default_info = Info::default();
+                let info = supplied_infos
+                    .get(cid.assert_usize())
+                    .unwrap_or(&default_info);
+                let name: &[u8] = if !info.name.is_empty() {
+                    &info.name
+                } else {
+                    // Theoretically we have to track all the names. We
+                    // don't do that, so just pretend we care and do the
+                    // common case.
+                    b"(1)"
+                };
+                let client_info = snap_obj::ClientInfo {
+                    name: string_to_ints4(name),
+                    clan: string_to_ints3(&info.clan),
+                    country: info.country,
+                    skin: string_to_ints6(&info.skin),
+                    use_custom_color: info.use_custom_color as i32,
+                    color_body: info.color_body,
+                    color_feet: info.color_feet,
+                };
+                let player_info = snap_obj::PlayerInfo {
+                    local: 0,
+                    client_id: cid,
+                    team: TEAM_RED,
+                    score: -9999,
+                    latency: 0,
+                };
+                let target = vec2::new(input.target_x as f32, input.target_y as f32);
+                let character = snap_obj::Character {
+                    character_core: snap_obj::CharacterCore {
+                        tick: tick,
+                        x: pos.x,
+                        y: pos.y,
+                        vel_x: pos.x - ppos.x,
+                        vel_y: pos.y - ppos.y,
+                        angle: target.angle().to_net(),
+                        direction: input.direction,
+                        jumped: (input.jump != 0) as i32,
+                        hooked_player: 0,
+                        hook_state: -1,
+                        hook_tick: snap_obj::Tick(0),
+                        hook_x: 0,
+                        hook_y: 0,
+                        hook_dx: 0,
+                        hook_dy: 0,
+                    },
+                    player_flags: snap_obj::PLAYERFLAG_PLAYING,
+                    health: 10,
+                    armor: 10,
+                    ammo_count: 0,
+                    weapon: WEAPON_HAMMER,
+                    emote: EMOTE_NORMAL,
+                    attack_tick: 0,
+                };
+                builder
+                    .add_item(
+                        snap_obj::CLIENT_INFO,
+                        cid.assert_u16(),
+                        client_info.encode().unwrap(),
+                    )
+                    .unwrap();
+                builder
+                    .add_item(
+                        snap_obj::PLAYER_INFO,
+                        cid.assert_u16(),
+                        player_info.encode().unwrap(),
+                    )
+                    .unwrap();
+                builder
+                    .add_item(
+                        snap_obj::CHARACTER,
+                        cid.assert_u16(),
+                        character.encode().unwrap(),
+                    )
+                    .unwrap();
+
+                prev_pos.insert(cid.assert_usize(), pos);
             }
+            let spectator_info = snap_obj::SpectatorInfo {
+                spectator_id,
+                x: spectator_pos.x,
+                y: spectator_pos.y,
+            };
+            builder
+                .add_item(
+                    snap_obj::SPECTATOR_INFO,
+                    0,
+                    spectator_info.encode().unwrap(),
+                )
+                .unwrap();
             let game_info = snap_obj::GameInfo {
                 game_flags: 0,
                 game_state_flags: 0,
@@ -290,14 +320,14 @@ fn process(in_: &Path, out: &Path) -> Result<(), String> {
                 round_current: 1,
             };
             builder
-                .add_item(snap_obj::GAME_INFO, 0, game_info.encode())
+                .add_item(snap_obj::GAME_INFO, 0, game_info.encode().unwrap())
                 .unwrap();
             let snap = builder.finish();
 
             encoded.clear();
             match (&last_snap, last_full_snap_tick) {
                 (&Some(ref l), Some(t)) if tick - t <= 5 * TICKS_PER_SECOND => {
-                    demo.write_tick(false, tick)
+                    demo.write_tick(false, Tick(tick))
                         .map_err(|err| err.to_string())?;
                     delta.create(l, &snap);
                     demo.write_snapshot_delta(with_packer(&mut encoded, |p| {
@@ -306,7 +336,8 @@ fn process(in_: &Path, out: &Path) -> Result<(), String> {
                     .map_err(|err| err.to_string())?;
                 }
                 _ => {
-                    demo.write_tick(true, tick).map_err(|err| err.to_string())?;
+                    demo.write_tick(true, Tick(tick))
+                        .map_err(|err| err.to_string())?;
                     demo.write_snapshot(with_packer(&mut encoded, |p| {
                         snap.write(&mut snap_buffer, p).unwrap()
                     }))
@@ -322,6 +353,7 @@ fn process(in_: &Path, out: &Path) -> Result<(), String> {
             last_snap = Some(snap);
         }
     }
+    demo.finalize().map_err(|err| err.to_string())?;
     Ok(())
 }
 
@@ -339,6 +371,13 @@ fn main() {
                 .required(true),
         )
         .arg(Arg::with_name("DEMO").help("Sets the output demo file"))
+        .arg(
+            Arg::with_name("map")
+                .long("map")
+                .takes_value(true)
+                .value_name("MAP")
+                .help("Embeds the map the teehistorian file was recorded on"),
+        )
         .get_matches();
 
     let mut buffer;
@@ -352,7 +391,9 @@ fn main() {
         }
     };
 
-    match process(in_, out) {
+    let map = matches.value_of_os("map").map(Path::new);
+
+    match process(in_, out, map) {
         Ok(()) => {}
         Err(err) => {
             println!("{}: {:?}", in_.display(), err);
@@ -53,7 +53,7 @@ fn process(path: &Path, json: bool) -> Result<(), Error> {
                     serde_json::to_writer(
                         stdout.lock(),
                         &TickAndItem {
-                            tick: tick.unwrap(),
+                            tick: tick.unwrap().to_i32(),
                             item: item,
                         },
                     )
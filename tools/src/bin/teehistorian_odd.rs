@@ -12,6 +12,7 @@ extern crate warn;
 use arrayvec::ArrayVec;
 use common::num::Cast;
 use common::pretty;
+use common::time::Tick;
 use gamenet::msg::game;
 use gamenet::msg::Game;
 use packer::Unpacker;
@@ -47,7 +48,7 @@ impl<'a> From<game::ClStartInfo<'a>> for Info {
 }
 
 struct PrevInput {
-    tick: i32,
+    tick: Tick,
     input: [i32; INPUT_LEN],
 }
 
@@ -81,7 +82,7 @@ fn process(path: &Path) -> Result<(), Error> {
                     let df = (fire + INPUT_STATE_MASK + 1 - prev_fire) & INPUT_STATE_MASK;
                     if df > ODD {
                         let clicks = df / 2 * TICKS_PER_SECOND * 10;
-                        let dt = tick - prev_input.tick;
+                        let dt = tick.wrapping_diff(prev_input.tick);
                         if dt != 0 {
                             let cps = clicks / dt;
                             println!(
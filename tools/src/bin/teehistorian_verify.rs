@@ -0,0 +1,267 @@
+extern crate clap;
+extern crate common;
+extern crate datafile;
+extern crate gamenet_teeworlds_0_6 as gamenet;
+extern crate logger;
+extern crate map;
+extern crate packer;
+extern crate teehistorian;
+extern crate vec_map;
+extern crate warn;
+extern crate world;
+
+use common::digest;
+use common::num::Cast;
+use gamenet::snap_obj::PlayerInput;
+use map::collision::Collision;
+use packer::IntUnpacker;
+use std::cell::Cell;
+use std::fs;
+use std::path::Path;
+use std::process;
+use teehistorian::Buffer;
+use teehistorian::Item;
+use teehistorian::Playback;
+use teehistorian::Pos;
+use teehistorian::Reader;
+use vec_map::VecMap;
+use warn::Ignore;
+use world::vec2;
+use world::Character;
+use world::CharacterId;
+use world::Tuning;
+
+struct Map(Collision);
+
+impl world::Collision for Map {
+    fn check_point(&mut self, pos: vec2) -> Option<world::CollisionType> {
+        if !self.0.is_solid(pos.x, pos.y) {
+            None
+        } else if self.0.is_hookable(pos.x, pos.y) {
+            Some(world::CollisionType::Normal)
+        } else {
+            Some(world::CollisionType::Unhookable)
+        }
+    }
+    fn dimensions(&self) -> (u32, u32) {
+        (self.0.width(), self.0.height())
+    }
+}
+
+struct OtherCharacters<'a> {
+    characters: &'a VecMap<Cell<Character>>,
+    own_cid: CharacterId,
+}
+
+impl<'a> world::OtherCharacters for OtherCharacters<'a> {
+    type Iter = usize;
+    fn is_self(&self, cid: CharacterId) -> bool {
+        cid == self.own_cid
+    }
+    fn get(&self, cid: CharacterId) -> Character {
+        assert!(!self.is_self(cid));
+        self.characters[cid.0.usize()].get()
+    }
+    fn modify<F: FnOnce(&mut Character)>(&self, cid: CharacterId, f: F) {
+        let mut character = self.get(cid);
+        f(&mut character);
+        self.characters[cid.0.usize()].set(character);
+    }
+    fn iter(&self) -> usize {
+        0
+    }
+    fn next(&self, iter: &mut usize) -> Option<(CharacterId, Character)> {
+        let capacity = self.characters.capacity();
+        while *iter < capacity {
+            let cur = *iter;
+            *iter += 1;
+            if let Some(c) = self.characters.get(cur) {
+                let cid = CharacterId(cur.assert_u32());
+                if !self.is_self(cid) {
+                    return Some((cid, c.get()));
+                }
+            }
+        }
+        None
+    }
+}
+
+struct Divergence {
+    tick: i32,
+    cid: usize,
+    recorded: Pos,
+    simulated: Pos,
+}
+
+/// Simulates one tick of all characters, in the order the reference
+/// implementation does: first all character ticks, then all moves, then
+/// the quantization.
+fn simulate(
+    characters: &VecMap<Cell<Character>>,
+    collision: &mut Map,
+    playback: &Playback,
+    tuning: &Tuning,
+) {
+    let cids: Vec<usize> = characters.keys().collect();
+    let others = |cid: usize| OtherCharacters {
+        characters,
+        own_cid: CharacterId(cid.assert_u32()),
+    };
+    for &cid in &cids {
+        let input = playback
+            .input(cid.assert_i32())
+            .and_then(|i| PlayerInput::decode(&mut Ignore, &mut IntUnpacker::new(&i)).ok())
+            .unwrap_or_default();
+        let mut character = characters[cid].get();
+        character.tick(collision, &mut others(cid), input, tuning);
+        characters[cid].set(character);
+    }
+    for &cid in &cids {
+        let mut character = characters[cid].get();
+        character.move_(collision, &mut others(cid), tuning);
+        characters[cid].set(character);
+    }
+    for &cid in &cids {
+        let mut character = characters[cid].get();
+        character.quantize();
+        characters[cid].set(character);
+    }
+}
+
+/// Compares the simulated characters to the recorded positions and spawns
+/// or removes characters to follow the recording.
+fn compare(
+    tick: i32,
+    characters: &mut VecMap<Cell<Character>>,
+    spawned: &mut Vec<usize>,
+    playback: &Playback,
+) -> Result<(), Divergence> {
+    let mut dead = Vec::new();
+    for (cid, character) in characters.iter() {
+        let recorded = match playback.player_pos(cid.assert_i32()) {
+            Some(pos) => pos,
+            None => {
+                dead.push(cid);
+                continue;
+            }
+        };
+        if spawned.contains(&cid) {
+            continue;
+        }
+        let core = character.get().to_net();
+        if (core.x, core.y) != (recorded.x, recorded.y) {
+            return Err(Divergence {
+                tick,
+                cid,
+                recorded,
+                simulated: Pos {
+                    x: core.x,
+                    y: core.y,
+                },
+            });
+        }
+    }
+    for cid in dead {
+        characters.remove(cid);
+    }
+    for (cid, player) in playback.players() {
+        let cid = cid.assert_usize();
+        if let Some(pos) = player.pos {
+            if spawned.contains(&cid) || !characters.contains_key(cid) {
+                let pos = vec2::new(pos.x as f32, pos.y as f32);
+                characters.insert(cid, Cell::new(Character::spawn(pos)));
+            }
+        }
+    }
+    spawned.clear();
+    Ok(())
+}
+
+fn describe(d: Divergence) -> String {
+    format!(
+        "divergence in tick {}, player {}: recorded ({}, {}), simulated ({}, {})",
+        d.tick, d.cid, d.recorded.x, d.recorded.y, d.simulated.x, d.simulated.y,
+    )
+}
+
+fn process(path: &Path, map_path: &Path) -> Result<(), String> {
+    let mut buffer = Buffer::new();
+    let (header, mut th) = Reader::open(path, &mut buffer).map_err(|err| format!("{:?}", err))?;
+
+    let map_data = fs::read(map_path).map_err(|err| err.to_string())?;
+    if digest::crc32(&map_data) != header.map_crc {
+        return Err("map CRC mismatch".into());
+    }
+    let reader = datafile::Reader::open(map_path).map_err(|err| format!("{:?}", err))?;
+    let mut map = map::Reader::from_datafile(reader);
+    let tiles = map.game_layer_tiles().map_err(|err| format!("{:?}", err))?;
+    let mut collision = Map(Collision::new(tiles));
+    let tuning = Tuning::default();
+
+    let mut characters: VecMap<Cell<Character>> = VecMap::new();
+    let mut spawned = Vec::new();
+    let mut playback = Playback::new();
+    let mut last_tick = None;
+    let mut verified = 0u64;
+    while let Some(item) = th.read(&mut buffer).map_err(|err| format!("{:?}", err))? {
+        playback.process(&item);
+        match item {
+            Item::TickStart(tick) => {
+                // Nothing is recorded for skipped ticks, but the characters
+                // still move.
+                for t in last_tick.map(|t| t + 1..tick.to_i32()).unwrap_or(0..0) {
+                    simulate(&characters, &mut collision, &playback, &tuning);
+                    compare(t, &mut characters, &mut spawned, &playback).map_err(describe)?;
+                }
+            }
+            Item::TickEnd(tick) => {
+                let tick = tick.to_i32();
+                last_tick = Some(tick);
+                simulate(&characters, &mut collision, &playback, &tuning);
+                verified += characters.len().u64();
+                compare(tick, &mut characters, &mut spawned, &playback).map_err(describe)?;
+            }
+            Item::PlayerNew(p) => spawned.push(p.cid.assert_usize()),
+            _ => {}
+        }
+    }
+    println!("no divergence, {} character positions verified", verified);
+    Ok(())
+}
+
+fn main() {
+    use clap::App;
+    use clap::Arg;
+
+    logger::init();
+
+    let matches = App::new("Teehistorian verifier")
+        .about(
+            "Replays the recorded inputs of a teehistorian file with the \
+             world physics and reports the first tick in which a character \
+             position differs from the recorded one. DDNet-specific tiles and \
+             tunings aren't simulated.",
+        )
+        .arg(
+            Arg::with_name("TEEHISTORIAN")
+                .help("Sets the teehistorian file to verify")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("MAP")
+                .help("Sets the map the teehistorian file was recorded on")
+                .required(true),
+        )
+        .get_matches();
+
+    let path = Path::new(matches.value_of_os("TEEHISTORIAN").unwrap());
+    let map = Path::new(matches.value_of_os("MAP").unwrap());
+
+    match process(path, map) {
+        Ok(()) => {}
+        Err(err) => {
+            eprintln!("{}: {}", path.display(), err);
+            process::exit(1);
+        }
+    }
+}
@@ -1,9 +1,16 @@
+extern crate arrayvec;
 extern crate datafile as df;
+extern crate gamenet_teeworlds_0_6 as gamenet6;
+extern crate gamenet_teeworlds_0_7 as gamenet7;
+extern crate hexdump;
 extern crate logger;
 extern crate map;
+extern crate net;
+extern crate packer;
 extern crate warn;
 
 pub mod client;
 pub mod map_stats;
+pub mod packet_decode;
 pub mod unhexdump;
 pub mod warn_stdout;
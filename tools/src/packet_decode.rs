@@ -0,0 +1,273 @@
+use arrayvec::ArrayVec;
+use gamenet6;
+use gamenet7;
+use hexdump::hexdump_iter;
+use net::protocol;
+use net::protocol7;
+use packer::with_packer;
+use packer::Unpacker;
+use std::fmt;
+use std::fmt::Write as _;
+use warn::Warn;
+
+/// Output of the packet decoder, collects both the decoded packet and the
+/// warnings encountered while decoding it.
+#[derive(Default)]
+pub struct Output(String);
+
+impl Output {
+    fn line<T: fmt::Display>(&mut self, line: T) {
+        writeln!(self.0, "{}", line).unwrap();
+    }
+    fn hexdump(&mut self, bytes: &[u8]) {
+        for line in hexdump_iter(bytes) {
+            self.line(line);
+        }
+    }
+}
+
+impl<W: fmt::Debug> Warn<W> for Output {
+    fn warn(&mut self, warning: W) {
+        self.line(format_args!("WARN: {:?}", warning));
+    }
+}
+
+/// Packet, independent of the protocol version.
+pub enum Packet<'a> {
+    Connless(&'a [u8]),
+    Control {
+        ack: u16,
+        token: Option<String>,
+        control: String,
+    },
+    Chunks {
+        ack: u16,
+        token: Option<String>,
+        request_resend: bool,
+        num_chunks: u8,
+        payload: &'a [u8],
+    },
+}
+
+pub struct Chunk<'a> {
+    /// Warnings encountered while reading the chunk header.
+    pub warnings: String,
+    pub data: &'a [u8],
+    // vital: Some((sequence, resend))
+    pub vital: Option<(u16, bool)>,
+}
+
+/// The version-specific parts of the packet decoder.
+pub trait Version {
+    fn read_packet<'a>(
+        out: &mut Output,
+        bytes: &'a [u8],
+        buffer: &'a mut ArrayVec<[u8; 4096]>,
+    ) -> Result<Packet<'a>, String>;
+    /// Returns the chunks and the warnings encountered after the last chunk.
+    fn read_chunks<'a>(payload: &'a [u8], num_chunks: u8) -> (Vec<Chunk<'a>>, String);
+    fn decode_connless(out: &mut Output, data: &[u8]) -> Result<String, String>;
+    /// Returns the decoded message and its re-encoding.
+    fn decode_msg(out: &mut Output, data: &[u8]) -> Result<(String, Vec<u8>), String>;
+}
+
+/// Teeworlds 0.6, including the DDNet token extension.
+pub enum Teeworlds06 {}
+/// Teeworlds 0.7.
+pub enum Teeworlds07 {}
+
+fn token<T: fmt::Display>(token: Option<T>) -> Option<String> {
+    token.map(|t| t.to_string())
+}
+
+// The chunk iterators of both protocol versions have the same interface but
+// no common trait.
+macro_rules! read_chunks {
+    ($iter:expr) => {{
+        let mut iter = $iter;
+        let mut result = Vec::new();
+        loop {
+            let mut warnings = Output::default();
+            match iter.next_warn(&mut warnings) {
+                Some(chunk) => result.push(Chunk {
+                    warnings: warnings.0,
+                    data: chunk.data,
+                    vital: chunk.vital,
+                }),
+                None => return (result, warnings.0),
+            }
+        }
+    }};
+}
+
+macro_rules! decode_msg {
+    ($gamenet:ident, $out:expr, $data:expr) => {{
+        use $gamenet::msg::SystemOrGame;
+        let msg = $gamenet::msg::decode($out, &mut Unpacker::new($data))
+            .map_err(|e| format!("{:?}", e))?;
+        let mut buf = Vec::with_capacity(4096);
+        with_packer(&mut buf, |p| match msg {
+            SystemOrGame::System(ref m) => m.encode(p).map(|_| ()),
+            SystemOrGame::Game(ref m) => m.encode(p).map(|_| ()),
+        })
+        .unwrap();
+        Ok((format!("{:?}", msg), buf))
+    }};
+}
+
+impl Version for Teeworlds06 {
+    fn read_packet<'a>(
+        out: &mut Output,
+        bytes: &'a [u8],
+        buffer: &'a mut ArrayVec<[u8; 4096]>,
+    ) -> Result<Packet<'a>, String> {
+        use net::protocol::ConnectedPacketType::*;
+        let cp = match protocol::Packet::read(out, bytes, None, buffer) {
+            Err(e) => return Err(format!("{:?}", e)),
+            Ok(protocol::Packet::Connless(data)) => return Ok(Packet::Connless(data)),
+            Ok(protocol::Packet::Connected(cp)) => cp,
+        };
+        Ok(match cp.type_ {
+            Control(control) => Packet::Control {
+                ack: cp.ack,
+                token: token(cp.token),
+                control: format!("{:?}", control),
+            },
+            Chunks(request_resend, num_chunks, payload) => Packet::Chunks {
+                ack: cp.ack,
+                token: token(cp.token),
+                request_resend,
+                num_chunks,
+                payload,
+            },
+        })
+    }
+    fn read_chunks<'a>(payload: &'a [u8], num_chunks: u8) -> (Vec<Chunk<'a>>, String) {
+        read_chunks!(protocol::ChunksIter::new(payload, num_chunks))
+    }
+    fn decode_connless(out: &mut Output, data: &[u8]) -> Result<String, String> {
+        gamenet6::msg::Connless::decode(out, &mut Unpacker::new(data))
+            .map(|m| format!("{:?}", m))
+            .map_err(|e| format!("{:?}", e))
+    }
+    fn decode_msg(out: &mut Output, data: &[u8]) -> Result<(String, Vec<u8>), String> {
+        decode_msg!(gamenet6, out, data)
+    }
+}
+
+impl Version for Teeworlds07 {
+    fn read_packet<'a>(
+        out: &mut Output,
+        bytes: &'a [u8],
+        buffer: &'a mut ArrayVec<[u8; 4096]>,
+    ) -> Result<Packet<'a>, String> {
+        use net::protocol7::ConnectedPacketType::*;
+        let cp = match protocol7::Packet::read(out, bytes, buffer) {
+            Err(e) => return Err(format!("{:?}", e)),
+            Ok(protocol7::Packet::Connless(data)) => return Ok(Packet::Connless(data)),
+            Ok(protocol7::Packet::Connected(cp)) => cp,
+        };
+        Ok(match cp.type_ {
+            Control(control) => Packet::Control {
+                ack: cp.ack,
+                token: None,
+                control: format!("{:?}", control),
+            },
+            Chunks(request_resend, num_chunks, payload) => Packet::Chunks {
+                ack: cp.ack,
+                token: None,
+                request_resend,
+                num_chunks,
+                payload,
+            },
+        })
+    }
+    fn read_chunks<'a>(payload: &'a [u8], num_chunks: u8) -> (Vec<Chunk<'a>>, String) {
+        read_chunks!(protocol7::ChunksIter::new(payload, num_chunks))
+    }
+    fn decode_connless(out: &mut Output, data: &[u8]) -> Result<String, String> {
+        gamenet7::msg::Connless::decode(out, &mut Unpacker::new(data))
+            .map(|m| format!("{:?}", m))
+            .map_err(|e| format!("{:?}", e))
+    }
+    fn decode_msg(out: &mut Output, data: &[u8]) -> Result<(String, Vec<u8>), String> {
+        decode_msg!(gamenet7, out, data)
+    }
+}
+
+/// Decodes a packet and describes it in a human-readable form.
+pub fn decode<V: Version>(bytes: &[u8]) -> String {
+    let mut out = Output::default();
+    let mut buffer: ArrayVec<[u8; 4096]> = ArrayVec::new();
+    decode_impl::<V>(&mut out, bytes, &mut buffer);
+    out.0
+}
+
+fn decode_impl<V: Version>(out: &mut Output, bytes: &[u8], buffer: &mut ArrayVec<[u8; 4096]>) {
+    out.line("packet");
+    out.hexdump(bytes);
+    let (ack, token, request_resend, num_chunks, payload) = match V::read_packet(out, bytes, buffer)
+    {
+        Err(e) => return out.line(format_args!("ERROR: {}", e)),
+        Ok(Packet::Connless(data)) => {
+            out.line("connless");
+            match V::decode_connless(out, data) {
+                Err(e) => out.line(format_args!("ERROR: {}", e)),
+                Ok(msg) => out.line(msg),
+            }
+            return;
+        }
+        Ok(Packet::Control {
+            ack,
+            token,
+            control,
+        }) => {
+            if let Some(token) = token {
+                out.line(format_args!("token={}", token));
+            }
+            out.line(format_args!("control ack={}", ack));
+            out.line(control);
+            return;
+        }
+        Ok(Packet::Chunks {
+            ack,
+            token,
+            request_resend,
+            num_chunks,
+            payload,
+        }) => (ack, token, request_resend, num_chunks, payload),
+    };
+    if let Some(token) = token {
+        out.line(format_args!("token={}", token));
+    }
+    out.line(format_args!(
+        "chunks ack={} request_resend={} num_chunks={}",
+        ack, request_resend, num_chunks
+    ));
+    out.hexdump(payload);
+    let (chunks, warnings) = V::read_chunks(payload, num_chunks);
+    for (i, chunk) in chunks.into_iter().enumerate() {
+        out.line(format_args!("chunk {}", i));
+        out.0.push_str(&chunk.warnings);
+        match chunk.vital {
+            Some((sequence, resend)) => out.line(format_args!(
+                "vital=true sequence={} resend={}",
+                sequence, resend
+            )),
+            None => out.line("vital=false"),
+        }
+        out.hexdump(chunk.data);
+
+        match V::decode_msg(out, chunk.data) {
+            Err(e) => out.line(format_args!("ERROR: {}", e)),
+            Ok((msg, reencoded)) => {
+                out.line(msg);
+                if reencoded != chunk.data {
+                    out.line("WARN: message re-encodes differently");
+                    out.hexdump(&reencoded);
+                }
+            }
+        }
+    }
+    out.0.push_str(&warnings);
+}
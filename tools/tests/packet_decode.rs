@@ -0,0 +1,46 @@
+extern crate tools;
+
+use std::fs;
+use std::path::Path;
+use tools::packet_decode;
+use tools::packet_decode::Teeworlds06;
+use tools::packet_decode::Teeworlds07;
+use tools::packet_decode::Version;
+use tools::unhexdump::Unhexdump;
+
+/// Decodes each `<name>.in` hexdump in the fixture directory and compares
+/// the result to `<name>.out`.
+fn check_fixtures<V: Version>(version: &str) {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/packet_decode")
+        .join(version);
+    let mut num_fixtures = 0;
+    for entry in fs::read_dir(&dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().map(|e| e != "in").unwrap_or(true) {
+            continue;
+        }
+        let mut un = Unhexdump::new();
+        un.feed(&fs::read(&path).unwrap()).unwrap();
+        let bytes = un.into_inner().unwrap();
+        let expected = fs::read_to_string(path.with_extension("out")).unwrap();
+        assert_eq!(
+            packet_decode::decode::<V>(&bytes),
+            expected,
+            "{}",
+            path.display()
+        );
+        num_fixtures += 1;
+    }
+    assert!(num_fixtures != 0);
+}
+
+#[test]
+fn teeworlds_0_6() {
+    check_fixtures::<Teeworlds06>("0.6");
+}
+
+#[test]
+fn teeworlds_0_7() {
+    check_fixtures::<Teeworlds07>("0.7");
+}
@@ -379,6 +379,8 @@ unsafe fn dissect_impl(
             let ctrl_raw = data[3];
             let (ctrl_str, ctrl_id) = match ctrl {
                 KeepAlive => ("Keep alive", "ctrl.keep_alive\0"),
+                MtuProbe(_) => ("MTU probe", "ctrl.mtu_probe\0"),
+                MtuReply(_) => ("MTU reply", "ctrl.mtu_reply\0"),
                 Connect => ("Connect", "ctrl.connect\0"),
                 ConnectAccept => ("Accept connection", "ctrl.accept_connection\0"),
                 Accept => (
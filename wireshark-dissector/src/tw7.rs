@@ -97,6 +97,7 @@ unsafe fn dissect_heur_impl(tvb: *mut sys::tvbuff_t) -> Result<(), ()> {
     match packet {
         protocol::Packet::Connected(protocol::ConnectedPacket {
             ack: _,
+            token: _,
             type_: protocol::ConnectedPacketType::Chunks(_, num_chunks, chunks_data),
         }) => {
             let mut iter = protocol::ChunksIter::new(chunks_data, num_chunks);
@@ -397,6 +398,7 @@ unsafe fn dissect_impl(
     match packet {
         protocol::Packet::Connected(protocol::ConnectedPacket {
             ack: _,
+            token: _,
             type_: protocol::ConnectedPacketType::Control(ctrl),
         }) => {
             use self::protocol::ControlPacket::*;
@@ -451,6 +453,7 @@ unsafe fn dissect_impl(
         }
         protocol::Packet::Connected(protocol::ConnectedPacket {
             ack: _,
+            token: _,
             type_: protocol::ConnectedPacketType::Chunks(_, num_chunks, chunks_data),
         }) => {
             let data = &data[7..];
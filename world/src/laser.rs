@@ -0,0 +1,101 @@
+use projectile::intersect_character;
+use vec2;
+use CharacterId;
+use Collision;
+use OtherCharacters;
+use Tuning;
+
+/// The path of a laser shot, bouncing off walls.
+#[derive(Clone, Debug)]
+pub struct LaserPath {
+    /// The start of the laser, followed by the end of each segment.
+    ///
+    /// The `n`-th segment appears `n` times `laser_bounce_delay`
+    /// milliseconds after the shot.
+    pub points: Vec<vec2>,
+    /// The character that was hit at the end of the path, if any.
+    pub character: Option<CharacterId>,
+}
+
+/// Evaluates the path of a laser shot from `pos` in `direction`, which
+/// must be normalized.
+///
+/// `characters` must yield all characters that can be hit, i.e. all but
+/// the owner's. The characters are assumed not to move during the bounces.
+pub fn laser_path<C, OC>(
+    pos: vec2,
+    direction: vec2,
+    collision: &mut C,
+    characters: &mut OC,
+    tuning: &Tuning,
+) -> LaserPath
+where
+    C: Collision,
+    OC: OtherCharacters,
+{
+    // Code copied from CLaser::DoBounce
+    let mut points = vec![pos];
+    let mut pos = pos;
+    let mut direction = direction;
+    let mut energy = tuning.laser_reach.to_float();
+    let mut bounces = 0;
+    while energy >= 0.0 {
+        let mut to = pos + direction * energy;
+        // The laser stops just before the wall.
+        let collide = collision.intersect_line(pos, to);
+        if let Some((_, before, _)) = collide {
+            to = before;
+        }
+        if let Some((cid, at)) = intersect_character(pos, to, 0.0, characters) {
+            points.push(at);
+            return LaserPath {
+                points,
+                character: Some(cid),
+            };
+        }
+        if collide.is_none() {
+            points.push(to);
+            break;
+        }
+        let from = pos;
+        let (new_pos, vel) = collision.move_point(to, direction * 4.0, 1.0);
+        pos = new_pos;
+        direction = vel.normalize();
+        points.push(pos);
+        energy -= vec2::distance(from, pos) + tuning.laser_bounce_cost.to_float();
+        bounces += 1;
+        if bounces as f32 > tuning.laser_bounce_num.to_float() {
+            break;
+        }
+    }
+    LaserPath {
+        points,
+        character: None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::laser_path;
+    use test::Ground;
+    use test::Nobody;
+    use vec2;
+    use Tuning;
+
+    #[test]
+    fn bounce() {
+        let tuning = &Tuning::default();
+        let direction = vec2::new(1.0, 1.0).normalize();
+        let pos = vec2::new(100.0, 200.0);
+        let path = laser_path(pos, direction, &mut Ground, &mut Nobody, tuning);
+        assert!(path.character.is_none());
+        assert_eq!(path.points.len(), 3);
+        // Bounces just above the ground.
+        assert_eq!(path.points[1].y.round(), 319.0);
+        // Reflected upwards, the remaining reach is used up.
+        let last = path.points[2];
+        assert!(last.x > path.points[1].x && last.y < path.points[1].y);
+        let length = vec2::distance(pos, path.points[1]) + vec2::distance(path.points[1], last);
+        assert!((length - 800.0).abs() < 1.0);
+    }
+}
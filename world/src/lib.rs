@@ -1,9 +1,9 @@
 extern crate common;
+extern crate gamenet_ddnet;
 extern crate gamenet_teeworlds_0_6 as gamenet;
 
 use common::num::Cast;
 use common::num::CastFloat;
-use gamenet::msg::game::SvTuneParams;
 use gamenet::snap_obj::CharacterCore;
 use gamenet::snap_obj::PlayerInput;
 use gamenet::snap_obj::Tick;
@@ -11,12 +11,24 @@ use std::f32::consts::PI;
 use std::fmt;
 use std::ops;
 
+pub use laser::laser_path;
+pub use laser::LaserPath;
+pub use projectile::explosion;
+pub use projectile::Impact;
+pub use projectile::Projectile;
+pub use tuning::TuneZones;
+pub use tuning::Tuning;
+
+pub mod laser;
+pub mod projectile;
+pub mod tuning;
+
 pub const CHARACTER_SIZE: f32 = 28.0;
 pub const DISABLE_HOOK_DISTANCE: f32 = 46.0;
 pub const MAX_VELOCITY: f32 = 6000.0;
 pub const MAX_HOOK_GRAB_TIME: u32 = 60; // 1.2 s with 50 Hz ticks.
 
-#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct CharacterId(pub u32);
 
 #[allow(non_camel_case_types)]
@@ -135,6 +147,19 @@ impl Angle {
     pub fn from_net(net: i32) -> Angle {
         Angle::from_radians((net as f32) / 256.0)
     }
+    /// Angle of the aim target, calculated the same way as in the original
+    /// code.
+    pub fn from_target(target_x: i32, target_y: i32) -> Angle {
+        let mut a = if target_x == 0 {
+            (target_y as f32).atan()
+        } else {
+            (target_y as f32 / target_x as f32).atan()
+        };
+        if target_x < 0 {
+            a += PI;
+        }
+        Angle::from_radians(a)
+    }
 }
 
 impl fmt::Debug for Angle {
@@ -335,18 +360,28 @@ pub enum CollisionType {
 
 pub trait Collision {
     fn check_point(&mut self, pos: vec2) -> Option<CollisionType>;
+    /// Width and height of the game layer in tiles.
+    fn dimensions(&self) -> (u32, u32);
     fn check_line(&mut self, from: vec2, to: vec2) -> Option<(vec2, CollisionType)> {
+        self.intersect_line(from, to)
+            .map(|(point, _, col)| (point, col))
+    }
+    /// Like `check_line`, but additionally returns the last checked point
+    /// before the collision.
+    fn intersect_line(&mut self, from: vec2, to: vec2) -> Option<(vec2, vec2, CollisionType)> {
         let dist = vec2::distance(from, to);
         // Overflow?
         let end = (dist + 1.0).trunc_to_i32();
+        let mut last = from;
         // Note: `0..end+1` would work better here, but we have to stay
         // compatible to the original code.
         for i in 0..end {
             // Note: Should rather be `/ end` instead of `/ dist`.
             let point = vec2::mix(from, to, i as f32 / dist);
             if let Some(col) = self.check_point(point) {
-                return Some((point, col));
+                return Some((point, last, col));
             }
+            last = point;
         }
         None
     }
@@ -359,11 +394,32 @@ pub trait Collision {
             || self.check_point(pos + diff2).is_some()
             || self.check_point(pos - diff2).is_some()
     }
+    /// Moves a point by `vel` unless that would collide, in which case the
+    /// velocity is reflected and scaled by `elasticity` instead.
+    fn move_point(&mut self, pos: vec2, mut vel: vec2, elasticity: f32) -> (vec2, vec2) {
+        // Code copied from CCollision::MovePoint
+        if self.check_point(pos + vel).is_none() {
+            return (pos + vel, vel);
+        }
+        let mut affected = false;
+        if self.check_point(vec2::new(pos.x + vel.x, pos.y)).is_some() {
+            vel.x *= -elasticity;
+            affected = true;
+        }
+        if self.check_point(vec2::new(pos.x, pos.y + vel.y)).is_some() {
+            vel.y *= -elasticity;
+            affected = true;
+        }
+        if !affected {
+            vel = vel * -elasticity;
+        }
+        (pos, vel)
+    }
     fn move_box(&mut self, mut pos: vec2, mut vel: vec2, box_: vec2) -> (vec2, vec2) {
         let dist = vel.length();
         // Magic number :(
         if dist > 0.00001 {
-            let end = dist.round_to_i32();
+            let end = dist.trunc_to_i32();
             let fraction = 1.0 / (end + 1) as f32;
             for _ in 0..end + 1 {
                 let mut new_pos = pos + vel * fraction;
@@ -412,7 +468,7 @@ impl Character {
         collision: &mut C,
         other_characters: &mut OC,
         input: PlayerInput,
-        tuning: &SvTuneParams,
+        tuning: &Tuning,
     ) where
         C: Collision,
         OC: OtherCharacters,
@@ -446,7 +502,7 @@ impl Character {
         .to_float();
 
         self.move_direction = MoveDirection::from_int(input.direction);
-        self.angle = target_dir.angle();
+        self.angle = Angle::from_target(input.target_x, input.target_y);
 
         self.vel.y += tuning.gravity.to_float();
 
@@ -491,23 +547,23 @@ impl Character {
             Hook::Retracted => {}
             Hook::Flying(pos, dir) => {
                 let mut new_pos = pos + dir * tuning.hook_fire_speed.to_float();
-                if vec2::distance(new_pos, self.pos) > tuning.hook_length.to_float() {
+                let too_long = vec2::distance(new_pos, self.pos) > tuning.hook_length.to_float();
+                if too_long {
                     new_pos =
                         self.pos + (new_pos - self.pos).normalize() * tuning.hook_length.to_float();
-                    self.hook = Hook::Retracting0(new_pos);
-                }
-                if let Some((p, t)) = collision.check_line(pos, new_pos) {
-                    match t {
-                        CollisionType::Normal => self.hook = Hook::Attached(p),
-                        CollisionType::Unhookable => self.hook = Hook::Retracting0(p),
-                    }
                 }
+                // Make sure that the hook doesn't go through the ground.
+                let hit = collision.check_line(pos, new_pos).map(|(p, t)| {
+                    new_pos = p;
+                    t
+                });
+                let mut grabbed = None;
                 if tuning.player_hooking.to_float() != 0.0 {
                     let mut iter = other_characters.iter();
                     let mut min_distance = None;
                     while let Some((cid, other)) = other_characters.next(&mut iter) {
                         let closest = other.pos.closest_point_on_line(pos, new_pos);
-                        if vec2::distance(other.pos, closest) < SIZE {
+                        if vec2::distance(other.pos, closest) < SIZE + 2.0 {
                             let new_minimum;
                             let distance = vec2::distance(pos, other.pos);
                             if let Some(md) = min_distance {
@@ -517,14 +573,23 @@ impl Character {
                             }
                             if new_minimum {
                                 min_distance = Some(distance);
-                                self.hook = Hook::Grabbed(cid, 0);
+                                grabbed = Some(cid);
                             }
                         }
                     }
                 }
-                if let Hook::Flying(..) = self.hook {
-                    self.hook = Hook::Flying(new_pos, dir);
-                }
+                self.hook = if let Some(cid) = grabbed {
+                    Hook::Grabbed(cid, 0)
+                } else if too_long {
+                    // The original code doesn't move the hook in this case.
+                    Hook::Retracting0(pos)
+                } else {
+                    match hit {
+                        Some(CollisionType::Normal) => Hook::Attached(new_pos),
+                        Some(CollisionType::Unhookable) => Hook::Retracting0(new_pos),
+                        None => Hook::Flying(new_pos, dir),
+                    }
+                };
             }
             Hook::Retracting0(pos) => self.hook = Hook::Retracting1(pos),
             Hook::Retracting1(pos) => self.hook = Hook::Retracting2(pos),
@@ -629,12 +694,8 @@ impl Character {
             self.vel = self.vel.normalize() * MAX_VELOCITY;
         }
     }
-    pub fn move_<C, OC>(
-        &mut self,
-        collision: &mut C,
-        other_characters: &mut OC,
-        tuning: &SvTuneParams,
-    ) where
+    pub fn move_<C, OC>(&mut self, collision: &mut C, other_characters: &mut OC, tuning: &Tuning)
+    where
         C: Collision,
         OC: OtherCharacters,
     {
@@ -670,6 +731,17 @@ impl Character {
         }
         self.pos = new_pos;
     }
+    pub fn pos(&self) -> vec2 {
+        self.pos
+    }
+    pub fn vel(&self) -> vec2 {
+        self.vel
+    }
+    /// Adds the force to the velocity, e.g. from an explosion or a hammer
+    /// hit.
+    pub fn apply_force(&mut self, force: vec2) {
+        self.vel += force;
+    }
     fn net_jumped(&self) -> i32 {
         ((self.used_airjump as i32) << 1) | (self.jumped_already as i32)
     }
@@ -747,7 +819,7 @@ pub fn saturated_add(min: f32, max: f32, value: f32, modifier: f32) -> f32 {
     clamp(value + modifier, min, max)
 }
 
-pub fn velocity_ramp(value: f32, tuning: &SvTuneParams) -> f32 {
+pub fn velocity_ramp(value: f32, tuning: &Tuning) -> f32 {
     let start = tuning.velramp_start.to_float();
     if value < start {
         1.0
@@ -757,3 +829,75 @@ pub fn velocity_ramp(value: f32, tuning: &SvTuneParams) -> f32 {
         1.0 / curvature.powf((value - start) / range)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use vec2;
+    use Character;
+    use CharacterId;
+    use Collision;
+    use CollisionType;
+    use OtherCharacters;
+    use Tuning;
+
+    /// Solid ground from `y = 320` on.
+    pub struct Ground;
+
+    impl Collision for Ground {
+        fn check_point(&mut self, pos: vec2) -> Option<CollisionType> {
+            if pos.y.round() >= 320.0 {
+                Some(CollisionType::Normal)
+            } else {
+                None
+            }
+        }
+        fn dimensions(&self) -> (u32, u32) {
+            (100, 10)
+        }
+    }
+
+    /// No collision at all.
+    pub struct Air;
+
+    impl Collision for Air {
+        fn check_point(&mut self, _: vec2) -> Option<CollisionType> {
+            None
+        }
+        fn dimensions(&self) -> (u32, u32) {
+            (100, 10)
+        }
+    }
+
+    pub struct Nobody;
+
+    impl OtherCharacters for Nobody {
+        type Iter = ();
+        fn is_self(&self, _: CharacterId) -> bool {
+            false
+        }
+        fn get(&self, _: CharacterId) -> Character {
+            unreachable!();
+        }
+        fn modify<F: FnOnce(&mut Character)>(&self, _: CharacterId, _: F) {
+            unreachable!();
+        }
+        fn iter(&self) {}
+        fn next(&self, _: &mut ()) -> Option<(CharacterId, Character)> {
+            None
+        }
+    }
+
+    #[test]
+    fn falling() {
+        let tuning = &Tuning::default();
+        let mut character = Character::spawn(vec2::new(100.0, 100.0));
+        for _ in 0..100 {
+            character.tick(&mut Ground, &mut Nobody, Default::default(), tuning);
+            character.move_(&mut Ground, &mut Nobody, tuning);
+            character.quantize();
+        }
+        // Standing on the ground.
+        assert_eq!(character.pos().y, 320.0 - 15.0);
+        assert_eq!(character.vel().y, 0.0);
+    }
+}
@@ -0,0 +1,318 @@
+use common::num::Cast;
+use common::num::CastFloat;
+use gamenet::enums::Weapon;
+use gamenet::snap_obj;
+use gamenet::snap_obj::Tick;
+
+use vec2;
+use CharacterId;
+use Collision;
+use OtherCharacters;
+use Tuning;
+use CHARACTER_SIZE;
+
+pub const TICK_SPEED: u32 = 50;
+pub const PROJECTILE_RADIUS: f32 = 6.0;
+pub const EXPLOSION_RADIUS: f32 = 135.0;
+pub const EXPLOSION_INNER_RADIUS: f32 = 48.0;
+/// Projectiles are removed this many tiles outside of the game layer.
+pub const MAX_DISTANCE_OUTSIDE: i32 = 200;
+
+#[derive(Clone, Copy)]
+pub struct Projectile {
+    weapon: Weapon,
+    owner: Option<CharacterId>,
+    /// Position at `start_tick`.
+    pos: vec2,
+    direction: vec2,
+    start_tick: u32,
+    lifespan: i32,
+}
+
+/// Where and what a projectile hit in its last tick.
+#[derive(Clone, Copy, Debug)]
+pub struct Impact {
+    pub pos: vec2,
+    /// The character that was hit, if any.
+    pub character: Option<CharacterId>,
+    /// Whether the projectile explodes, see `explosion`.
+    pub explosive: bool,
+}
+
+fn curvature_speed_lifetime(weapon: Weapon, tuning: &Tuning) -> (f32, f32, f32) {
+    let (curvature, speed, lifetime) = match weapon {
+        Weapon::Pistol => (tuning.gun_curvature, tuning.gun_speed, tuning.gun_lifetime),
+        Weapon::Shotgun => (
+            tuning.shotgun_curvature,
+            tuning.shotgun_speed,
+            tuning.shotgun_lifetime,
+        ),
+        Weapon::Grenade => (
+            tuning.grenade_curvature,
+            tuning.grenade_speed,
+            tuning.grenade_lifetime,
+        ),
+        _ => return (0.0, 0.0, 0.0),
+    };
+    (curvature.to_float(), speed.to_float(), lifetime.to_float())
+}
+
+/// Position of a projectile `time` seconds after it was fired.
+pub fn calc_pos(pos: vec2, velocity: vec2, curvature: f32, speed: f32, time: f32) -> vec2 {
+    let time = time * speed;
+    vec2::new(
+        pos.x + velocity.x * time,
+        pos.y + velocity.y * time + curvature / 10000.0 * (time * time),
+    )
+}
+
+/// Force and damage of an explosion at `center` on a character at `pos`.
+///
+/// Returns `None` if the character is too far away to be affected.
+pub fn explosion(center: vec2, pos: vec2) -> Option<(vec2, i32)> {
+    let diff = pos - center;
+    let mut l = diff.length();
+    let force_dir = if l != 0.0 {
+        diff.normalize()
+    } else {
+        vec2::new(0.0, 1.0)
+    };
+    l = 1.0
+        - ::clamp(
+            (l - EXPLOSION_INNER_RADIUS) / (EXPLOSION_RADIUS - EXPLOSION_INNER_RADIUS),
+            0.0,
+            1.0,
+        );
+    let damage = 6.0 * l;
+    if damage.trunc_to_i32() == 0 {
+        return None;
+    }
+    Some((force_dir * damage * 2.0, damage.trunc_to_i32()))
+}
+
+impl Projectile {
+    /// A projectile fired at `start_tick` from `pos`. The length of
+    /// `direction` scales the speed, as used by the shotgun.
+    pub fn new(
+        weapon: Weapon,
+        owner: Option<CharacterId>,
+        pos: vec2,
+        direction: vec2,
+        start_tick: u32,
+        tuning: &Tuning,
+    ) -> Projectile {
+        let (_, _, lifetime) = curvature_speed_lifetime(weapon, tuning);
+        Projectile {
+            weapon,
+            owner,
+            pos,
+            direction,
+            start_tick,
+            lifespan: (TICK_SPEED as f32 * lifetime).trunc_to_i32(),
+        }
+    }
+    pub fn weapon(&self) -> Weapon {
+        self.weapon
+    }
+    pub fn owner(&self) -> Option<CharacterId> {
+        self.owner
+    }
+    /// Position of the projectile at `tick`.
+    pub fn pos(&self, tick: u32, tuning: &Tuning) -> vec2 {
+        let time = (tick as f32 - self.start_tick as f32) / TICK_SPEED as f32;
+        self.pos_at(time, tuning)
+    }
+    fn pos_at(&self, time: f32, tuning: &Tuning) -> vec2 {
+        let (curvature, speed, _) = curvature_speed_lifetime(self.weapon, tuning);
+        calc_pos(self.pos, self.direction, curvature, speed, time)
+    }
+    /// Advances the projectile to `tick`, returns where it hit if it is
+    /// destroyed in this tick.
+    ///
+    /// `characters` must yield all characters that can be hit, i.e. all but
+    /// the owner's.
+    pub fn tick<C, OC>(
+        &mut self,
+        tick: u32,
+        collision: &mut C,
+        characters: &mut OC,
+        tuning: &Tuning,
+    ) -> Option<Impact>
+    where
+        C: Collision,
+        OC: OtherCharacters,
+    {
+        // Code copied from CProjectile::Tick
+        let ticks = tick as f32 - self.start_tick as f32;
+        let prev_pos = self.pos_at((ticks - 1.0) / TICK_SPEED as f32, tuning);
+        let mut cur_pos = self.pos_at(ticks / TICK_SPEED as f32, tuning);
+        let collide = collision.check_line(prev_pos, cur_pos);
+        if let Some((pos, _)) = collide {
+            cur_pos = pos;
+        }
+        let target = intersect_character(prev_pos, cur_pos, PROJECTILE_RADIUS, characters);
+        if let Some((_, pos)) = target {
+            cur_pos = pos;
+        }
+        self.lifespan -= 1;
+        let clipped = game_layer_clipped(collision, cur_pos);
+        if target.is_none() && collide.is_none() && self.lifespan >= 0 && !clipped {
+            return None;
+        }
+        Some(Impact {
+            pos: cur_pos,
+            character: target.map(|(cid, _)| cid),
+            explosive: matches!(self.weapon, Weapon::Grenade),
+        })
+    }
+    /// Predicts in which tick after `tick` and where the projectile will
+    /// hit, assuming the characters don't move.
+    ///
+    /// Projectiles without a known lifespan, e.g. from `from_net`, are
+    /// assumed to have been fired at their start tick.
+    pub fn predict_impact<C, OC>(
+        &self,
+        tick: u32,
+        collision: &mut C,
+        characters: &mut OC,
+        tuning: &Tuning,
+    ) -> (u32, Impact)
+    where
+        C: Collision,
+        OC: OtherCharacters,
+    {
+        let mut projectile = *self;
+        let elapsed = tick.saturating_sub(self.start_tick).assert_i32();
+        let lifespan = Projectile::new(self.weapon, None, self.pos, self.direction, 0, tuning)
+            .lifespan
+            - elapsed;
+        projectile.lifespan = projectile.lifespan.min(lifespan);
+        let mut tick = tick;
+        loop {
+            tick += 1;
+            if let Some(impact) = projectile.tick(tick, collision, characters, tuning) {
+                return (tick, impact);
+            }
+        }
+    }
+    pub fn to_net(&self) -> snap_obj::Projectile {
+        snap_obj::Projectile {
+            x: self.pos.x.trunc_to_i32(),
+            y: self.pos.y.trunc_to_i32(),
+            vel_x: (self.direction.x * 100.0).trunc_to_i32(),
+            vel_y: (self.direction.y * 100.0).trunc_to_i32(),
+            type_: self.weapon.to_i32(),
+            start_tick: Tick(self.start_tick.assert_i32()),
+        }
+    }
+    /// Reconstructs a projectile from the network, e.g. for prediction.
+    ///
+    /// The owner and the remaining lifespan aren't transmitted.
+    pub fn from_net(projectile: &snap_obj::Projectile, tuning: &Tuning) -> Projectile {
+        let mut result = Projectile::new(
+            Weapon::from_i32(projectile.type_),
+            None,
+            vec2::new(projectile.x as f32, projectile.y as f32),
+            vec2::new(projectile.vel_x as f32, projectile.vel_y as f32) / 100.0,
+            projectile.start_tick.0 as u32,
+            tuning,
+        );
+        result.lifespan = i32::MAX;
+        result
+    }
+}
+
+/// Returns the character closest to `pos0` that is within `radius` of the
+/// line from `pos0` to `pos1`, and the closest point on the line to it.
+pub fn intersect_character<OC: OtherCharacters>(
+    pos0: vec2,
+    pos1: vec2,
+    radius: f32,
+    characters: &mut OC,
+) -> Option<(CharacterId, vec2)> {
+    // Code copied from CGameWorld::IntersectCharacter
+    let mut closest_len = vec2::distance(pos0, pos1) * 100.0;
+    let mut closest = None;
+    let mut iter = characters.iter();
+    while let Some((cid, character)) = characters.next(&mut iter) {
+        let intersect_pos = character.pos.closest_point_on_line(pos0, pos1);
+        let len = vec2::distance(character.pos, intersect_pos);
+        if len < CHARACTER_SIZE + radius {
+            let len = vec2::distance(pos0, intersect_pos);
+            if len < closest_len {
+                closest_len = len;
+                closest = Some((cid, intersect_pos));
+            }
+        }
+    }
+    closest
+}
+
+fn game_layer_clipped<C: Collision>(collision: &C, pos: vec2) -> bool {
+    let (width, height) = collision.dimensions();
+    let x = pos.x.round_to_i32() / 32;
+    let y = pos.y.round_to_i32() / 32;
+    x < -MAX_DISTANCE_OUTSIDE
+        || x > width.assert_i32() + MAX_DISTANCE_OUTSIDE
+        || y < -MAX_DISTANCE_OUTSIDE
+        || y > height.assert_i32() + MAX_DISTANCE_OUTSIDE
+}
+
+#[cfg(test)]
+mod test {
+    use gamenet::enums::Weapon;
+
+    use super::explosion;
+    use super::Projectile;
+    use common::num::Cast;
+    use test::Air;
+    use test::Ground;
+    use test::Nobody;
+    use vec2;
+    use Tuning;
+
+    #[test]
+    fn grenade() {
+        let tuning = &Tuning::default();
+        let pos = vec2::new(100.0, 100.0);
+        let mut grenade =
+            Projectile::new(Weapon::Grenade, None, pos, vec2::new(1.0, 0.0), 10, tuning);
+        let mut impact = None;
+        for tick in 11..100 {
+            impact = grenade.tick(tick, &mut Ground, &mut Nobody, tuning);
+            if impact.is_some() {
+                break;
+            }
+        }
+        let impact = impact.unwrap();
+        assert!(impact.explosive);
+        assert!(impact.character.is_none());
+        assert_eq!(impact.pos.y.round(), 320.0);
+        assert!(impact.pos.x > pos.x);
+    }
+
+    #[test]
+    fn predict() {
+        let tuning = &Tuning::default();
+        let pos = vec2::new(100.0, 100.0);
+        let grenade = Projectile::new(Weapon::Grenade, None, pos, vec2::new(1.0, 0.0), 10, tuning);
+        let (tick, impact) = grenade.predict_impact(20, &mut Ground, &mut Nobody, tuning);
+        assert!(tick > 20);
+        assert_eq!(impact.pos.y.round(), 320.0);
+
+        // Without collisions, the grenade explodes at the end of its
+        // lifetime.
+        let net = Projectile::from_net(&grenade.to_net(), tuning);
+        let (tick, _) = net.predict_impact(10, &mut Air, &mut Nobody, tuning);
+        assert_eq!(tick, 10 + 1 + grenade.lifespan.assert_u32());
+    }
+
+    #[test]
+    fn explosion_force() {
+        let center = vec2::new(0.0, 0.0);
+        let (force, damage) = explosion(center, vec2::new(0.0, -10.0)).unwrap();
+        assert_eq!(damage, 6);
+        assert_eq!((force.x, force.y), (0.0, -12.0));
+        assert!(explosion(center, vec2::new(200.0, 0.0)).is_none());
+    }
+}
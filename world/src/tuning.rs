@@ -0,0 +1,327 @@
+use gamenet::msg::game::SvTuneParams;
+use gamenet::msg::game::TuneParam;
+use gamenet_ddnet::msg::game::SvTuneParams as DdnetSvTuneParams;
+
+/// Number of tune zones, zone 0 is the tuning outside of all tune zones.
+pub const NUM_TUNE_ZONES: usize = 256;
+
+macro_rules! tuning {
+    ($($name:ident: $default:expr,)*) => {
+        /// Physics parameters of the game.
+        ///
+        /// The parameters are in the order of the DDNet network message, the
+        /// Teeworlds 0.6 message has a prefix of them. Like in the reference
+        /// implementation, the values are stored with two decimal places.
+        #[derive(Clone, Copy, Debug)]
+        pub struct Tuning {
+            $(pub $name: TuneParam,)*
+        }
+
+        /// Names of the tuning parameters, as used by the `tune` command.
+        pub const TUNING_NAMES: &[&str] = &[$(stringify!($name),)*];
+
+        impl Default for Tuning {
+            fn default() -> Tuning {
+                Tuning {
+                    $($name: TuneParam::from_float($default),)*
+                }
+            }
+        }
+
+        impl Tuning {
+            fn param_mut(&mut self, name: &str) -> Option<&mut TuneParam> {
+                match name {
+                    $(stringify!($name) => Some(&mut self.$name),)*
+                    _ => None,
+                }
+            }
+            /// Returns the parameter with the given name, as used by the
+            /// `tune` command.
+            pub fn get(&self, name: &str) -> Option<f32> {
+                match name {
+                    $(stringify!($name) => Some(self.$name.to_float()),)*
+                    _ => None,
+                }
+            }
+            /// Returns the parameters in the order of the network message.
+            pub fn to_params(&self) -> Vec<TuneParam> {
+                vec![$(self.$name,)*]
+            }
+            /// Creates the tuning from parameters in the order of the network
+            /// message. Missing parameters keep their default value, extra
+            /// ones are ignored.
+            pub fn from_params(params: &[TuneParam]) -> Tuning {
+                let mut result = Tuning::default();
+                let mut params = params.iter();
+                $(
+                    if let Some(&p) = params.next() {
+                        result.$name = p;
+                    }
+                )*
+                result
+            }
+        }
+    };
+}
+
+tuning! {
+    ground_control_speed: 10.0,
+    ground_control_accel: 2.0,
+    ground_friction: 0.5,
+    ground_jump_impulse: 13.2,
+    air_jump_impulse: 12.0,
+    air_control_speed: 5.0,
+    air_control_accel: 1.5,
+    air_friction: 0.95,
+    hook_length: 380.0,
+    hook_fire_speed: 80.0,
+    hook_drag_accel: 3.0,
+    hook_drag_speed: 15.0,
+    gravity: 0.5,
+    velramp_start: 550.0,
+    velramp_range: 2000.0,
+    velramp_curvature: 1.4,
+    gun_curvature: 1.25,
+    gun_speed: 2200.0,
+    gun_lifetime: 2.0,
+    shotgun_curvature: 1.25,
+    shotgun_speed: 2750.0,
+    shotgun_speeddiff: 0.8,
+    shotgun_lifetime: 0.2,
+    grenade_curvature: 7.0,
+    grenade_speed: 1000.0,
+    grenade_lifetime: 2.0,
+    laser_reach: 800.0,
+    laser_bounce_delay: 150.0,
+    laser_bounce_num: 1.0,
+    laser_bounce_cost: 0.0,
+    laser_damage: 5.0,
+    player_collision: 1.0,
+    player_hooking: 1.0,
+    jetpack_strength: 400.0,
+    shotgun_strength: 10.0,
+    explosion_strength: 6.0,
+    hammer_strength: 1.0,
+    hook_duration: 1.25,
+    hammer_fire_delay: 125.0,
+    gun_fire_delay: 125.0,
+    shotgun_fire_delay: 500.0,
+    grenade_fire_delay: 500.0,
+    laser_fire_delay: 800.0,
+    ninja_fire_delay: 800.0,
+    hammer_hit_fire_delay: 320.0,
+    ground_elasticity_x: 0.0,
+    ground_elasticity_y: 0.0,
+}
+
+impl Tuning {
+    /// Sets the parameter with the given name, as used by the `tune`
+    /// command. Returns `false` if there's no such parameter.
+    pub fn set(&mut self, name: &str, value: f32) -> bool {
+        match self.param_mut(name) {
+            Some(p) => {
+                *p = TuneParam::from_float(value);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<'a> From<&'a SvTuneParams> for Tuning {
+    fn from(params: &'a SvTuneParams) -> Tuning {
+        Tuning {
+            ground_control_speed: params.ground_control_speed,
+            ground_control_accel: params.ground_control_accel,
+            ground_friction: params.ground_friction,
+            ground_jump_impulse: params.ground_jump_impulse,
+            air_jump_impulse: params.air_jump_impulse,
+            air_control_speed: params.air_control_speed,
+            air_control_accel: params.air_control_accel,
+            air_friction: params.air_friction,
+            hook_length: params.hook_length,
+            hook_fire_speed: params.hook_fire_speed,
+            hook_drag_accel: params.hook_drag_accel,
+            hook_drag_speed: params.hook_drag_speed,
+            gravity: params.gravity,
+            velramp_start: params.velramp_start,
+            velramp_range: params.velramp_range,
+            velramp_curvature: params.velramp_curvature,
+            gun_curvature: params.gun_curvature,
+            gun_speed: params.gun_speed,
+            gun_lifetime: params.gun_lifetime,
+            shotgun_curvature: params.shotgun_curvature,
+            shotgun_speed: params.shotgun_speed,
+            shotgun_speeddiff: params.shotgun_speeddiff,
+            shotgun_lifetime: params.shotgun_lifetime,
+            grenade_curvature: params.grenade_curvature,
+            grenade_speed: params.grenade_speed,
+            grenade_lifetime: params.grenade_lifetime,
+            laser_reach: params.laser_reach,
+            laser_bounce_delay: params.laser_bounce_delay,
+            laser_bounce_num: params.laser_bounce_num,
+            laser_bounce_cost: params.laser_bounce_cost,
+            laser_damage: params.laser_damage,
+            player_collision: params.player_collision,
+            player_hooking: params.player_hooking,
+            ..Tuning::default()
+        }
+    }
+}
+
+impl<'a> From<&'a DdnetSvTuneParams> for Tuning {
+    fn from(params: &'a DdnetSvTuneParams) -> Tuning {
+        Tuning {
+            ground_control_speed: params.ground_control_speed,
+            ground_control_accel: params.ground_control_accel,
+            ground_friction: params.ground_friction,
+            ground_jump_impulse: params.ground_jump_impulse,
+            air_jump_impulse: params.air_jump_impulse,
+            air_control_speed: params.air_control_speed,
+            air_control_accel: params.air_control_accel,
+            air_friction: params.air_friction,
+            hook_length: params.hook_length,
+            hook_fire_speed: params.hook_fire_speed,
+            hook_drag_accel: params.hook_drag_accel,
+            hook_drag_speed: params.hook_drag_speed,
+            gravity: params.gravity,
+            velramp_start: params.velramp_start,
+            velramp_range: params.velramp_range,
+            velramp_curvature: params.velramp_curvature,
+            gun_curvature: params.gun_curvature,
+            gun_speed: params.gun_speed,
+            gun_lifetime: params.gun_lifetime,
+            shotgun_curvature: params.shotgun_curvature,
+            shotgun_speed: params.shotgun_speed,
+            shotgun_speeddiff: params.shotgun_speeddiff,
+            shotgun_lifetime: params.shotgun_lifetime,
+            grenade_curvature: params.grenade_curvature,
+            grenade_speed: params.grenade_speed,
+            grenade_lifetime: params.grenade_lifetime,
+            laser_reach: params.laser_reach,
+            laser_bounce_delay: params.laser_bounce_delay,
+            laser_bounce_num: params.laser_bounce_num,
+            laser_bounce_cost: params.laser_bounce_cost,
+            laser_damage: params.laser_damage,
+            player_collision: params.player_collision,
+            player_hooking: params.player_hooking,
+            jetpack_strength: params.jetpack_strength,
+            shotgun_strength: params.shotgun_strength,
+            explosion_strength: params.explosion_strength,
+            hammer_strength: params.hammer_strength,
+            hook_duration: params.hook_duration,
+            hammer_fire_delay: params.hammer_fire_delay,
+            gun_fire_delay: params.gun_fire_delay,
+            shotgun_fire_delay: params.shotgun_fire_delay,
+            grenade_fire_delay: params.grenade_fire_delay,
+            laser_fire_delay: params.laser_fire_delay,
+            ninja_fire_delay: params.ninja_fire_delay,
+            hammer_hit_fire_delay: params.hammer_hit_fire_delay,
+            ground_elasticity_x: params.ground_elasticity_x,
+            ground_elasticity_y: params.ground_elasticity_y,
+        }
+    }
+}
+
+impl<'a> From<&'a Tuning> for SvTuneParams {
+    fn from(tuning: &'a Tuning) -> SvTuneParams {
+        SvTuneParams {
+            ground_control_speed: tuning.ground_control_speed,
+            ground_control_accel: tuning.ground_control_accel,
+            ground_friction: tuning.ground_friction,
+            ground_jump_impulse: tuning.ground_jump_impulse,
+            air_jump_impulse: tuning.air_jump_impulse,
+            air_control_speed: tuning.air_control_speed,
+            air_control_accel: tuning.air_control_accel,
+            air_friction: tuning.air_friction,
+            hook_length: tuning.hook_length,
+            hook_fire_speed: tuning.hook_fire_speed,
+            hook_drag_accel: tuning.hook_drag_accel,
+            hook_drag_speed: tuning.hook_drag_speed,
+            gravity: tuning.gravity,
+            velramp_start: tuning.velramp_start,
+            velramp_range: tuning.velramp_range,
+            velramp_curvature: tuning.velramp_curvature,
+            gun_curvature: tuning.gun_curvature,
+            gun_speed: tuning.gun_speed,
+            gun_lifetime: tuning.gun_lifetime,
+            shotgun_curvature: tuning.shotgun_curvature,
+            shotgun_speed: tuning.shotgun_speed,
+            shotgun_speeddiff: tuning.shotgun_speeddiff,
+            shotgun_lifetime: tuning.shotgun_lifetime,
+            grenade_curvature: tuning.grenade_curvature,
+            grenade_speed: tuning.grenade_speed,
+            grenade_lifetime: tuning.grenade_lifetime,
+            laser_reach: tuning.laser_reach,
+            laser_bounce_delay: tuning.laser_bounce_delay,
+            laser_bounce_num: tuning.laser_bounce_num,
+            laser_bounce_cost: tuning.laser_bounce_cost,
+            laser_damage: tuning.laser_damage,
+            player_collision: tuning.player_collision,
+            player_hooking: tuning.player_hooking,
+        }
+    }
+}
+
+/// Tunings of the DDNet tune zones, selected by the number of the tune tile
+/// a character is on.
+#[derive(Clone, Debug)]
+pub struct TuneZones {
+    zones: Vec<Tuning>,
+}
+
+impl Default for TuneZones {
+    fn default() -> TuneZones {
+        TuneZones {
+            zones: vec![Tuning::default(); NUM_TUNE_ZONES],
+        }
+    }
+}
+
+impl TuneZones {
+    /// The tuning of the zone. Zone 0 is the tuning outside of all tune
+    /// zones, changed by the `tune` command.
+    pub fn zone(&self, zone: u8) -> &Tuning {
+        &self.zones[usize::from(zone)]
+    }
+    /// Mutable access to the tuning of the zone, e.g. for the `tune_zone`
+    /// command.
+    pub fn zone_mut(&mut self, zone: u8) -> &mut Tuning {
+        &mut self.zones[usize::from(zone)]
+    }
+    /// Resets all zones to the default tuning, like `tune_reset`.
+    pub fn reset(&mut self) {
+        *self = TuneZones::default();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use gamenet::msg::game::SV_TUNE_PARAMS_DEFAULT;
+
+    use super::TuneZones;
+    use super::Tuning;
+    use super::TUNING_NAMES;
+
+    #[test]
+    fn defaults() {
+        let from_net = Tuning::from(&SV_TUNE_PARAMS_DEFAULT).to_params();
+        let default = Tuning::default().to_params();
+        assert_eq!(from_net.len(), TUNING_NAMES.len());
+        for (i, (a, b)) in from_net.iter().zip(&default).enumerate() {
+            assert_eq!(a.0, b.0, "{}", TUNING_NAMES[i]);
+        }
+    }
+
+    #[test]
+    fn set() {
+        let mut zones = TuneZones::default();
+        assert!(zones.zone_mut(3).set("gravity", 0.25));
+        assert!(!zones.zone_mut(3).set("gravitation", 0.25));
+        assert_eq!(zones.zone(3).get("gravity"), Some(0.25));
+        assert_eq!(zones.zone(0).get("gravity"), Some(0.5));
+        let params = zones.zone(3).to_params();
+        assert_eq!(Tuning::from_params(&params[..13]).gravity.0, 25);
+        assert_eq!(Tuning::from_params(&params[..12]).gravity.0, 50);
+    }
+}